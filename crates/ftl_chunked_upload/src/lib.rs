@@ -0,0 +1,331 @@
+//! Chunked upload protocol for payloads too large to send in a single tool call.
+//!
+//! Clients upload a large input in pieces, then reference the session from the
+//! tool that consumes it:
+//!
+//! 1. `init` creates a session and returns its id.
+//! 2. `append` stores the next chunk of text under that session.
+//! 3. `finalize` seals the session so no more chunks can be added.
+//! 4. The consuming tool (e.g. csv_parser, histogram) takes the session id,
+//!    streams the chunks through [`ChunkReader`] and calls [`discard`].
+//!
+//! Sessions live in a [`KvStore`]. Tools use [`SpinStore`], backed by the
+//! application's default Spin key-value store, so every component sees the
+//! same sessions.
+
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read};
+
+pub use ftl_kv_store::{KvStore, MemoryStore, SpinStore};
+
+/// Largest chunk accepted by `append`, in bytes
+pub const MAX_CHUNK_BYTES: usize = 1024 * 1024;
+/// Largest number of chunks in a single session
+pub const MAX_CHUNKS: usize = 1024;
+
+const KEY_PREFIX: &str = "chunked_upload";
+
+/// State of an upload session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub session_id: String,
+    /// Number of chunks received so far
+    pub chunk_count: usize,
+    /// Total bytes received so far
+    pub total_bytes: usize,
+    /// Number of chunks the client announced at init, if any
+    pub expected_chunks: Option<usize>,
+    /// Whether the session has been sealed and is ready to be consumed
+    pub finalized: bool,
+}
+
+fn meta_key(session_id: &str) -> String {
+    format!("{KEY_PREFIX}:{session_id}:meta")
+}
+
+fn chunk_key(session_id: &str, index: usize) -> String {
+    format!("{KEY_PREFIX}:{session_id}:chunk:{index}")
+}
+
+fn save_session(store: &impl KvStore, info: &SessionInfo) -> Result<(), String> {
+    let bytes = serde_json::to_vec(info).map_err(|e| format!("Failed to encode session: {e}"))?;
+    store.set(&meta_key(&info.session_id), &bytes)
+}
+
+/// Look up a session
+pub fn status(store: &impl KvStore, session_id: &str) -> Result<SessionInfo, String> {
+    if session_id.is_empty() || !session_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid upload session id '{session_id}'"));
+    }
+    let bytes = store
+        .get(&meta_key(session_id))?
+        .ok_or_else(|| format!("Unknown upload session '{session_id}'"))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Corrupt upload session: {e}"))
+}
+
+/// Start a new session
+pub fn init(store: &impl KvStore, expected_chunks: Option<usize>) -> Result<SessionInfo, String> {
+    if let Some(n) = expected_chunks
+        && (n == 0 || n > MAX_CHUNKS)
+    {
+        return Err(format!(
+            "expected_chunks must be between 1 and {MAX_CHUNKS}"
+        ));
+    }
+
+    let info = SessionInfo {
+        session_id: uuid::Uuid::new_v4().simple().to_string(),
+        chunk_count: 0,
+        total_bytes: 0,
+        expected_chunks,
+        finalized: false,
+    };
+    save_session(store, &info)?;
+    Ok(info)
+}
+
+/// Append the next chunk to an open session.
+///
+/// `chunk_index`, when given, must match the number of chunks already received;
+/// this catches lost or duplicated chunks on retries.
+pub fn append(
+    store: &impl KvStore,
+    session_id: &str,
+    chunk_index: Option<usize>,
+    data: &str,
+) -> Result<SessionInfo, String> {
+    let mut info = status(store, session_id)?;
+    if info.finalized {
+        return Err(format!(
+            "Upload session '{session_id}' is already finalized"
+        ));
+    }
+    if let Some(index) = chunk_index
+        && index != info.chunk_count
+    {
+        return Err(format!(
+            "Expected chunk {} but received chunk {index}",
+            info.chunk_count
+        ));
+    }
+    if data.len() > MAX_CHUNK_BYTES {
+        return Err(format!(
+            "Chunk is {} bytes; the maximum is {MAX_CHUNK_BYTES}",
+            data.len()
+        ));
+    }
+    let limit = info.expected_chunks.unwrap_or(MAX_CHUNKS);
+    if info.chunk_count >= limit {
+        return Err(format!(
+            "Upload session '{session_id}' already has {limit} chunks"
+        ));
+    }
+
+    store.set(&chunk_key(session_id, info.chunk_count), data.as_bytes())?;
+    info.chunk_count += 1;
+    info.total_bytes += data.len();
+    save_session(store, &info)?;
+    Ok(info)
+}
+
+/// Seal a session so it can be consumed
+pub fn finalize(store: &impl KvStore, session_id: &str) -> Result<SessionInfo, String> {
+    let mut info = status(store, session_id)?;
+    if let Some(expected) = info.expected_chunks
+        && info.chunk_count != expected
+    {
+        return Err(format!(
+            "Upload session '{session_id}' has {} of {expected} chunks",
+            info.chunk_count
+        ));
+    }
+    if info.chunk_count == 0 {
+        return Err(format!("Upload session '{session_id}' has no chunks"));
+    }
+
+    info.finalized = true;
+    save_session(store, &info)?;
+    Ok(info)
+}
+
+/// Delete a session and all of its chunks
+pub fn discard(store: &impl KvStore, session_id: &str) -> Result<(), String> {
+    let info = status(store, session_id)?;
+    for index in 0..info.chunk_count {
+        store.delete(&chunk_key(session_id, index))?;
+    }
+    store.delete(&meta_key(session_id))
+}
+
+/// Streams the chunks of a finalized session, loading one chunk at a time
+pub struct ChunkReader<'a, S: KvStore> {
+    store: &'a S,
+    session_id: String,
+    chunk_count: usize,
+    next_chunk: usize,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<'a, S: KvStore> ChunkReader<'a, S> {
+    pub fn open(store: &'a S, session_id: &str) -> Result<Self, String> {
+        let info = status(store, session_id)?;
+        if !info.finalized {
+            return Err(format!(
+                "Upload session '{session_id}' must be finalized before it is read"
+            ));
+        }
+        Ok(ChunkReader {
+            store,
+            session_id: info.session_id,
+            chunk_count: info.chunk_count,
+            next_chunk: 0,
+            buffer: Vec::new(),
+            position: 0,
+        })
+    }
+}
+
+impl<S: KvStore> Read for ChunkReader<'_, S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.buffer.len() {
+            if self.next_chunk == self.chunk_count {
+                return Ok(0);
+            }
+            let key = chunk_key(&self.session_id, self.next_chunk);
+            self.buffer = self
+                .store
+                .get(&key)
+                .map_err(io::Error::other)?
+                .ok_or_else(|| io::Error::other(format!("Missing chunk {}", self.next_chunk)))?;
+            self.position = 0;
+            self.next_chunk += 1;
+        }
+
+        let n = buf.len().min(self.buffer.len() - self.position);
+        buf[..n].copy_from_slice(&self.buffer[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Read a whole finalized session into a string
+pub fn read_to_string(store: &impl KvStore, session_id: &str) -> Result<String, String> {
+    let mut content = String::new();
+    ChunkReader::open(store, session_id)?
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read upload session: {e}"))?;
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upload(store: &MemoryStore, chunks: &[&str]) -> String {
+        let session = init(store, Some(chunks.len())).unwrap();
+        for (i, chunk) in chunks.iter().enumerate() {
+            append(store, &session.session_id, Some(i), chunk).unwrap();
+        }
+        finalize(store, &session.session_id).unwrap();
+        session.session_id
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let store = MemoryStore::new();
+        let id = upload(&store, &["a,b\n1,", "2\n3,4", "\n"]);
+
+        let info = status(&store, &id).unwrap();
+        assert_eq!(info.chunk_count, 3);
+        assert_eq!(info.total_bytes, 12);
+        assert!(info.finalized);
+        assert_eq!(read_to_string(&store, &id).unwrap(), "a,b\n1,2\n3,4\n");
+    }
+
+    #[test]
+    fn test_reader_spans_chunk_boundaries() {
+        let store = MemoryStore::new();
+        let id = upload(&store, &["hello ", "", "chunked ", "world"]);
+
+        let mut reader = ChunkReader::open(&store, &id).unwrap();
+        let mut out = Vec::new();
+        let mut buf = [0u8; 4];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(String::from_utf8(out).unwrap(), "hello chunked world");
+    }
+
+    #[test]
+    fn test_out_of_order_chunk_rejected() {
+        let store = MemoryStore::new();
+        let session = init(&store, None).unwrap();
+        append(&store, &session.session_id, Some(0), "x").unwrap();
+
+        let err = append(&store, &session.session_id, Some(0), "x").unwrap_err();
+        assert!(err.contains("Expected chunk 1"));
+        assert_eq!(status(&store, &session.session_id).unwrap().chunk_count, 1);
+    }
+
+    #[test]
+    fn test_finalize_checks_expected_chunks() {
+        let store = MemoryStore::new();
+        let session = init(&store, Some(2)).unwrap();
+        append(&store, &session.session_id, None, "x").unwrap();
+
+        assert!(finalize(&store, &session.session_id).is_err());
+        append(&store, &session.session_id, None, "y").unwrap();
+        assert!(append(&store, &session.session_id, None, "z").is_err());
+        assert!(finalize(&store, &session.session_id).is_ok());
+    }
+
+    #[test]
+    fn test_finalized_session_is_sealed() {
+        let store = MemoryStore::new();
+        let id = upload(&store, &["data"]);
+        assert!(append(&store, &id, None, "more").is_err());
+    }
+
+    #[test]
+    fn test_unfinalized_session_cannot_be_read() {
+        let store = MemoryStore::new();
+        let session = init(&store, None).unwrap();
+        append(&store, &session.session_id, None, "partial").unwrap();
+        assert!(read_to_string(&store, &session.session_id).is_err());
+    }
+
+    #[test]
+    fn test_discard_removes_all_keys() {
+        let store = MemoryStore::new();
+        let id = upload(&store, &["a", "b", "c"]);
+        assert_eq!(store.len(), 4);
+
+        discard(&store, &id).unwrap();
+        assert!(store.is_empty());
+        assert!(status(&store, &id).is_err());
+    }
+
+    #[test]
+    fn test_session_id_must_be_alphanumeric() {
+        let store = MemoryStore::new();
+        let err = status(&store, "abc:meta").unwrap_err();
+        assert!(err.contains("Invalid upload session id"));
+    }
+
+    #[test]
+    fn test_limits() {
+        let store = MemoryStore::new();
+        assert!(init(&store, Some(0)).is_err());
+        assert!(init(&store, Some(MAX_CHUNKS + 1)).is_err());
+
+        let session = init(&store, None).unwrap();
+        let big = "x".repeat(MAX_CHUNK_BYTES + 1);
+        assert!(append(&store, &session.session_id, None, &big).is_err());
+        assert!(finalize(&store, &session.session_id).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Negotiated response encoding for tools that return large arrays.
+//!
+//! Histogram bins, regression residuals and buffer polygons are mostly
+//! numbers, and writing every `f64` as decimal JSON text roughly doubles
+//! their size. Such tools accept an optional `response_encoding` input and
+//! serialize their result through [`encode`]:
+//!
+//! ```ignore
+//! let encoding = input.response_encoding.unwrap_or_default();
+//! // ... compute `response` ...
+//! match ftl_encoding::encode(&response, encoding, precision.as_ref()) {
+//!     Ok(body) => ToolResponse::text(body),
+//!     Err(e) => ToolResponse::text(format!("Error: {e}")),
+//! }
+//! ```
+//!
+//! With [`ResponseEncoding::Msgpack`] the result is MessagePack instead of
+//! JSON. Tool results travel as text, so the MessagePack bytes are returned
+//! base64-encoded; clients decode the base64 and then the MessagePack. The
+//! value is identical to the JSON response, including key order and any
+//! `precision` or `output_precision` rounding.
+
+use base64::{Engine as _, engine::general_purpose};
+use ftl_precision::Precision;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How a tool response body is encoded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseEncoding {
+    /// Canonical JSON text
+    #[default]
+    Json,
+    /// Base64-encoded MessagePack
+    Msgpack,
+}
+
+/// Encode `value` as a tool response body, rounding floating point numbers
+/// to `precision`
+pub fn encode<T: Serialize>(
+    value: &T,
+    encoding: ResponseEncoding,
+    precision: Option<&Precision>,
+) -> Result<String, String> {
+    let json = ftl_response::to_json_with_precision(value, None, precision)?;
+    match encoding {
+        ResponseEncoding::Json => Ok(json),
+        ResponseEncoding::Msgpack => {
+            // Go through the canonical text so rounding and key order match
+            let canonical: Value = serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to serialize result: {e}"))?;
+            let bytes = rmp_serde::to_vec(&canonical)
+                .map_err(|e| format!("Failed to encode result as MessagePack: {e}"))?;
+            Ok(general_purpose::STANDARD.encode(bytes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn decode(body: &str) -> Value {
+        let bytes = general_purpose::STANDARD.decode(body).unwrap();
+        rmp_serde::from_slice(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_json_is_canonical_text() {
+        let value = json!({ "b": [1.5, 2.0], "a": 1 });
+        assert_eq!(
+            encode(&value, ResponseEncoding::Json, None).unwrap(),
+            r#"{"a":1,"b":[1.5,2.0]}"#
+        );
+    }
+
+    #[test]
+    fn test_msgpack_round_trips() {
+        let value = json!({ "bins": [0.25, 0.5, 0.75], "count": 3, "label": "x" });
+        let body = encode(&value, ResponseEncoding::Msgpack, None).unwrap();
+        assert_eq!(decode(&body), value);
+    }
+
+    #[test]
+    fn test_msgpack_applies_output_precision() {
+        let precision = Precision::significant_digits(2);
+        let body = encode(
+            &json!([2.0 / 3.0]),
+            ResponseEncoding::Msgpack,
+            Some(&precision),
+        )
+        .unwrap();
+        assert_eq!(decode(&body), json!([0.67]));
+    }
+
+    #[test]
+    fn test_msgpack_is_smaller_for_float_arrays() {
+        let values: Vec<f64> = (1..500).map(|i| 1.0 / f64::from(i)).collect();
+        let json = encode(&values, ResponseEncoding::Json, None).unwrap();
+        let msgpack = encode(&values, ResponseEncoding::Msgpack, None).unwrap();
+        assert!(msgpack.len() < json.len());
+    }
+}
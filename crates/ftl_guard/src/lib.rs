@@ -0,0 +1,262 @@
+//! Input size limits checked by tool wrappers before any logic runs.
+//!
+//! Oversized inputs (a million-element array, a huge regex pattern) can make a
+//! WASM component hang or run out of memory. Wrappers check the size of their
+//! large inputs first and return a structured `payload_too_large` error
+//! instead:
+//!
+//! ```ignore
+//! if let Err(e) = Guard::from_env().array("x", &input.x).array("y", &input.y).finish() {
+//!     return ToolResponse::text(e.to_json());
+//! }
+//! ```
+//!
+//! Each limit can be changed per component through an environment variable;
+//! see [`Limits::from_env`].
+
+use serde::Serialize;
+use std::fmt;
+
+pub const MAX_ARRAY_LENGTH_ENV: &str = "FTL_MAX_ARRAY_LENGTH";
+pub const MAX_STRING_BYTES_ENV: &str = "FTL_MAX_STRING_BYTES";
+pub const MAX_PATTERN_BYTES_ENV: &str = "FTL_MAX_PATTERN_BYTES";
+pub const MAX_POLYGON_VERTICES_ENV: &str = "FTL_MAX_POLYGON_VERTICES";
+
+/// Size limits for tool inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Most elements allowed in a single array input
+    pub max_array_length: usize,
+    /// Most bytes allowed in a single string input
+    pub max_string_bytes: usize,
+    /// Most bytes allowed in a regular expression pattern
+    pub max_pattern_bytes: usize,
+    /// Most vertices allowed in a polygon
+    pub max_polygon_vertices: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_array_length: 100_000,
+            max_string_bytes: 1024 * 1024,
+            max_pattern_bytes: 1024,
+            max_polygon_vertices: 10_000,
+        }
+    }
+}
+
+impl Limits {
+    /// Default limits, overridden by `FTL_MAX_ARRAY_LENGTH`,
+    /// `FTL_MAX_STRING_BYTES`, `FTL_MAX_PATTERN_BYTES` and
+    /// `FTL_MAX_POLYGON_VERTICES` when they are set to a number
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = Limits::default();
+        let read = |name: &str, default: usize| {
+            lookup(name)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        Limits {
+            max_array_length: read(MAX_ARRAY_LENGTH_ENV, defaults.max_array_length),
+            max_string_bytes: read(MAX_STRING_BYTES_ENV, defaults.max_string_bytes),
+            max_pattern_bytes: read(MAX_PATTERN_BYTES_ENV, defaults.max_pattern_bytes),
+            max_polygon_vertices: read(MAX_POLYGON_VERTICES_ENV, defaults.max_polygon_vertices),
+        }
+    }
+}
+
+/// Structured error for an input that exceeds a size limit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PayloadTooLarge {
+    /// Always "payload_too_large"
+    pub error: &'static str,
+    /// Input field that is too large
+    pub field: String,
+    /// Size of the field (elements, bytes or vertices)
+    pub actual: usize,
+    /// Largest accepted size
+    pub limit: usize,
+    /// Environment variable that configures the limit
+    pub limit_setting: &'static str,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl PayloadTooLarge {
+    fn new(field: &str, unit: &str, actual: usize, limit: usize, setting: &'static str) -> Self {
+        PayloadTooLarge {
+            error: "payload_too_large",
+            field: field.to_string(),
+            actual,
+            limit,
+            limit_setting: setting,
+            message: format!(
+                "Payload too large: '{field}' has {actual} {unit}; the limit is {limit}"
+            ),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        ftl_json::to_string(self).unwrap_or_else(|_| self.message.clone())
+    }
+}
+
+impl fmt::Display for PayloadTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Checks a tool input field by field, keeping the first violation
+#[derive(Debug, Clone)]
+pub struct Guard {
+    limits: Limits,
+    violation: Option<PayloadTooLarge>,
+}
+
+impl Guard {
+    pub fn new(limits: Limits) -> Self {
+        Guard {
+            limits,
+            violation: None,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(Limits::from_env())
+    }
+
+    fn check(
+        mut self,
+        field: &str,
+        unit: &str,
+        actual: usize,
+        limit: usize,
+        setting: &'static str,
+    ) -> Self {
+        if self.violation.is_none() && actual > limit {
+            self.violation = Some(PayloadTooLarge::new(field, unit, actual, limit, setting));
+        }
+        self
+    }
+
+    /// Limit the number of elements in an array field
+    pub fn array<T>(self, field: &str, items: &[T]) -> Self {
+        self.array_len(field, items.len())
+    }
+
+    /// Limit an element count directly, e.g. the total size of nested arrays
+    pub fn array_len(self, field: &str, len: usize) -> Self {
+        let limit = self.limits.max_array_length;
+        self.check(field, "elements", len, limit, MAX_ARRAY_LENGTH_ENV)
+    }
+
+    /// Limit the size of a string field in bytes
+    pub fn string(self, field: &str, value: &str) -> Self {
+        let limit = self.limits.max_string_bytes;
+        self.check(field, "bytes", value.len(), limit, MAX_STRING_BYTES_ENV)
+    }
+
+    /// Limit the size of a free-form JSON field, measured as compact JSON text
+    pub fn json<T: Serialize>(self, field: &str, value: &T) -> Self {
+        let len = serde_json::to_string(value).map_or(0, |json| json.len());
+        let limit = self.limits.max_string_bytes;
+        self.check(field, "bytes", len, limit, MAX_STRING_BYTES_ENV)
+    }
+
+    /// Limit the size of a regular expression pattern in bytes
+    pub fn pattern(self, field: &str, pattern: &str) -> Self {
+        let limit = self.limits.max_pattern_bytes;
+        self.check(field, "bytes", pattern.len(), limit, MAX_PATTERN_BYTES_ENV)
+    }
+
+    /// Limit the number of vertices in a polygon
+    pub fn polygon<T>(self, field: &str, vertices: &[T]) -> Self {
+        let limit = self.limits.max_polygon_vertices;
+        self.check(
+            field,
+            "vertices",
+            vertices.len(),
+            limit,
+            MAX_POLYGON_VERTICES_ENV,
+        )
+    }
+
+    pub fn finish(self) -> Result<(), PayloadTooLarge> {
+        match self.violation {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small() -> Limits {
+        Limits {
+            max_array_length: 3,
+            max_string_bytes: 4,
+            max_pattern_bytes: 2,
+            max_polygon_vertices: 5,
+        }
+    }
+
+    #[test]
+    fn test_within_limits() {
+        let result = Guard::new(small())
+            .array("data", &[1.0, 2.0, 3.0])
+            .string("content", "abcd")
+            .pattern("pattern", "a+")
+            .polygon("polygon", &[0; 5])
+            .finish();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_first_violation_is_reported() {
+        let err = Guard::new(small())
+            .array("x", &[1, 2])
+            .array("y", &[1, 2, 3, 4])
+            .string("content", "too long")
+            .finish()
+            .unwrap_err();
+        assert_eq!(err.field, "y");
+        assert_eq!(err.actual, 4);
+        assert_eq!(err.limit, 3);
+        assert_eq!(err.limit_setting, MAX_ARRAY_LENGTH_ENV);
+        assert!(err.to_json().contains(r#""error":"payload_too_large""#));
+    }
+
+    #[test]
+    fn test_each_limit_applies() {
+        let guard = || Guard::new(small());
+        assert!(guard().string("s", "12345").finish().is_err());
+        assert!(guard().pattern("p", "abc").finish().is_err());
+        assert!(guard().polygon("v", &[0; 6]).finish().is_err());
+        assert!(guard().array_len("m", 4).finish().is_err());
+        assert!(guard().json("j", &[1, 2]).finish().is_err());
+        assert!(guard().json("j", &[1]).finish().is_ok());
+    }
+
+    #[test]
+    fn test_limits_from_lookup() {
+        let limits = Limits::from_lookup(|name| match name {
+            MAX_ARRAY_LENGTH_ENV => Some(" 10 ".to_string()),
+            MAX_PATTERN_BYTES_ENV => Some("lots".to_string()),
+            _ => None,
+        });
+        assert_eq!(limits.max_array_length, 10);
+        assert_eq!(
+            limits.max_pattern_bytes,
+            Limits::default().max_pattern_bytes
+        );
+        assert_eq!(limits.max_string_bytes, Limits::default().max_string_bytes);
+    }
+}
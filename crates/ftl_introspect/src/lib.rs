@@ -0,0 +1,207 @@
+//! Reserved introspection request answered by every tool.
+//!
+//! Sending `{"__introspect": true}` to a tool returns a description of the tool
+//! instead of running it: its name, crate version, input and output JSON
+//! schemas, and the optional input fields it accepts. Clients use `options` to
+//! feature-detect newer fields such as `fields`, `precision` or
+//! `validate_only` before sending them.
+//!
+//! A tool opts in by taking its input wrapped in [`Request`], which
+//! deserializes and advertises exactly the same schema as the wrapped type:
+//!
+//! ```ignore
+//! pub fn add(request: Request<TwoNumberInput>) -> ToolResponse {
+//!     let input = match request {
+//!         Request::Call(input) => input,
+//!         Request::Introspect => {
+//!             return ToolResponse::text(describe!("add", TwoNumberInput, ArithmeticResult));
+//!         }
+//!     };
+//!     // ...
+//! }
+//! ```
+//!
+//! The [`describe!`] macro reports the calling crate's version.
+
+use std::borrow::Cow;
+
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::Schema;
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Input field that marks an introspection request
+pub const INTROSPECT_FIELD: &str = "__introspect";
+
+/// Tool input that may instead be an introspection request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Request<T> {
+    /// `{"__introspect": true}` was sent
+    Introspect,
+    /// Regular tool call
+    Call(T),
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Request<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if value.get(INTROSPECT_FIELD).and_then(Value::as_bool) == Some(true) {
+            return Ok(Request::Introspect);
+        }
+        T::deserialize(value)
+            .map(Request::Call)
+            .map_err(D::Error::custom)
+    }
+}
+
+// The advertised input schema is the wrapped type's, so wrapping a tool input
+// changes nothing for clients that never introspect
+impl<T: JsonSchema> JsonSchema for Request<T> {
+    fn is_referenceable() -> bool {
+        T::is_referenceable()
+    }
+
+    fn schema_name() -> String {
+        T::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        T::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        T::json_schema(generator)
+    }
+}
+
+/// Description returned for an introspection request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Introspection {
+    /// Tool name
+    pub name: String,
+    /// Semver version of the tool crate
+    pub version: String,
+    /// JSON schema of the tool input
+    pub input_schema: Value,
+    /// JSON schema of a successful tool result
+    pub output_schema: Value,
+    /// Optional input fields, sorted by name
+    pub options: Vec<String>,
+}
+
+impl Introspection {
+    pub fn new<I: JsonSchema, O: JsonSchema>(name: &str, version: &str) -> Self {
+        let input_schema = schema_value::<I>();
+        let options = optional_fields(&input_schema);
+        Introspection {
+            name: name.to_string(),
+            version: version.to_string(),
+            input_schema,
+            output_schema: schema_value::<O>(),
+            options,
+        }
+    }
+}
+
+/// JSON description of the calling tool, versioned with the calling crate:
+/// `describe!("add", TwoNumberInput, ArithmeticResult)`
+#[macro_export]
+macro_rules! describe {
+    ($name:expr, $input:ty, $output:ty) => {
+        $crate::describe::<$input, $output>($name, env!("CARGO_PKG_VERSION"))
+    };
+}
+
+/// JSON description of a tool taking `I` and returning `O`
+pub fn describe<I: JsonSchema, O: JsonSchema>(name: &str, version: &str) -> String {
+    ftl_json::to_string(&Introspection::new::<I, O>(name, version))
+        .unwrap_or_else(|e| format!("Error: Failed to serialize introspection: {e}"))
+}
+
+fn schema_value<T: JsonSchema>() -> Value {
+    let schema = SchemaGenerator::default().into_root_schema_for::<T>();
+    serde_json::to_value(schema).unwrap_or(Value::Null)
+}
+
+/// Properties of an object schema that are not listed as required
+fn optional_fields(schema: &Value) -> Vec<String> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut options: Vec<String> = properties
+        .keys()
+        .filter(|name| !required.contains(&name.as_str()))
+        .cloned()
+        .collect();
+    options.sort();
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize, JsonSchema)]
+    struct Input {
+        /// Required value
+        a: f64,
+        /// Optional rounding
+        precision: Option<u32>,
+        /// Optional field selection
+        fields: Option<Vec<String>>,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct Output {
+        result: f64,
+    }
+
+    #[test]
+    fn test_regular_call_deserializes_input() {
+        let request: Request<Input> = serde_json::from_str(r#"{"a": 1.5}"#).unwrap();
+        assert_eq!(
+            request,
+            Request::Call(Input {
+                a: 1.5,
+                precision: None,
+                fields: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_introspect_request_skips_input_validation() {
+        let request: Request<Input> = serde_json::from_str(r#"{"__introspect": true}"#).unwrap();
+        assert_eq!(request, Request::Introspect);
+
+        let err = serde_json::from_str::<Request<Input>>(r#"{"__introspect": false}"#);
+        assert!(err.unwrap_err().to_string().contains("missing field `a`"));
+    }
+
+    #[test]
+    fn test_schema_matches_wrapped_type() {
+        assert_eq!(
+            schemars::schema_for!(Request<Input>),
+            schemars::schema_for!(Input)
+        );
+    }
+
+    #[test]
+    fn test_describe_lists_optional_fields() {
+        let description: Introspection =
+            serde_json::from_str(&describe::<Input, Output>("demo", "1.2.3")).unwrap();
+        assert_eq!(description.name, "demo");
+        assert_eq!(description.version, "1.2.3");
+        assert_eq!(description.options, vec!["fields", "precision"]);
+        assert_eq!(description.input_schema["title"], "Input");
+        assert!(description.output_schema["properties"]["result"].is_object());
+    }
+}
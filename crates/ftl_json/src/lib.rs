@@ -0,0 +1,221 @@
+//! Canonical JSON serialization for tool responses.
+//!
+//! `serde_json` writes object keys in struct declaration order, so two tools
+//! returning the same data can differ byte for byte, and reordering a struct's
+//! fields changes every snapshot and cache entry built from its output. Tools
+//! serialize their responses through this crate instead, which always emits:
+//!
+//! - object keys sorted by their UTF-8 bytes, at every nesting level
+//! - integers as plain integers and `-0.0` as `0.0`
+//! - floating point numbers in one [`FloatFormat`] (shortest round-trip by
+//!   default)
+//! - either compact output or two-space indented output, with no trailing
+//!   whitespace
+//!
+//! [`to_string`] and [`to_string_pretty`] are drop-in replacements for their
+//! `serde_json` counterparts:
+//!
+//! ```ignore
+//! ToolResponse::text(ftl_json::to_string(&response).unwrap())
+//! ```
+//!
+//! This crate only controls how numbers are written. Rounding them, including
+//! a tool's `output_precision` option, is the shared `ftl_precision` policy
+//! applied by `ftl_response`.
+
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+/// How floating point numbers are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// Shortest representation that parses back to the same `f64`
+    #[default]
+    Shortest,
+    /// Always this many digits after the decimal point
+    Fixed(usize),
+}
+
+/// Output options for canonical serialization
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonFormat {
+    pub float_format: FloatFormat,
+    /// Indent nested values by two spaces per level
+    pub pretty: bool,
+}
+
+/// Serialize `value` as compact canonical JSON
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    to_string_with(value, &JsonFormat::default())
+}
+
+/// Serialize `value` as indented canonical JSON
+pub fn to_string_pretty<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    to_string_with(
+        value,
+        &JsonFormat {
+            pretty: true,
+            ..JsonFormat::default()
+        },
+    )
+}
+
+/// Serialize `value` as canonical JSON using `format`
+pub fn to_string_with<T: Serialize + ?Sized>(
+    value: &T,
+    format: &JsonFormat,
+) -> serde_json::Result<String> {
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write_value(&mut out, &value, format, 0)?;
+    Ok(out)
+}
+
+fn write_value(
+    out: &mut String,
+    value: &Value,
+    format: &JsonFormat,
+    depth: usize,
+) -> serde_json::Result<()> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&format_number(n, format.float_format)),
+        Value::String(s) => out.push_str(&serde_json::to_string(s)?),
+        Value::Array(items) => {
+            if items.is_empty() {
+                out.push_str("[]");
+                return Ok(());
+            }
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                newline(out, format, depth + 1);
+                write_value(out, item, format, depth + 1)?;
+            }
+            newline(out, format, depth);
+            out.push(']');
+        }
+        Value::Object(map) => {
+            if map.is_empty() {
+                out.push_str("{}");
+                return Ok(());
+            }
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                newline(out, format, depth + 1);
+                out.push_str(&serde_json::to_string(key)?);
+                out.push_str(if format.pretty { ": " } else { ":" });
+                write_value(out, item, format, depth + 1)?;
+            }
+            newline(out, format, depth);
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn newline(out: &mut String, format: &JsonFormat, depth: usize) {
+    if format.pretty {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    }
+}
+
+fn format_number(n: &Number, float_format: FloatFormat) -> String {
+    if n.is_i64() || n.is_u64() {
+        return n.to_string();
+    }
+    // Non-finite floats never reach here: serde_json turns them into null
+    let x = n.as_f64().unwrap_or_default();
+    let x = if x == 0.0 { 0.0 } else { x };
+    match float_format {
+        FloatFormat::Shortest => shortest(x),
+        FloatFormat::Fixed(places) => {
+            let s = format!("{x:.places$}");
+            // Rounding a tiny negative number can leave "-0.00"
+            match s.strip_prefix('-') {
+                Some(digits) if digits.chars().all(|c| c == '0' || c == '.') => digits.to_string(),
+                _ => s,
+            }
+        }
+    }
+}
+
+fn shortest(x: f64) -> String {
+    Number::from_f64(x).map_or_else(|| x.to_string(), |n| n.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Report {
+        zeta: f64,
+        alpha: i32,
+        nested: Nested,
+    }
+
+    #[derive(Serialize)]
+    struct Nested {
+        b: Vec<f64>,
+        a: String,
+    }
+
+    fn sample() -> Report {
+        Report {
+            zeta: 0.5,
+            alpha: -3,
+            nested: Nested {
+                b: vec![1.0, -0.0, 1e-7],
+                a: "x\"y".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_keys_sorted_at_every_level() {
+        assert_eq!(
+            to_string(&sample()).unwrap(),
+            r#"{"alpha":-3,"nested":{"a":"x\"y","b":[1.0,0.0,1e-7]},"zeta":0.5}"#
+        );
+    }
+
+    #[test]
+    fn test_pretty_output() {
+        let json = to_string_pretty(&serde_json::json!({"b": [1, 2], "a": {}, "c": []})).unwrap();
+        assert_eq!(
+            json,
+            "{\n  \"a\": {},\n  \"b\": [\n    1,\n    2\n  ],\n  \"c\": []\n}"
+        );
+    }
+
+    #[test]
+    fn test_fixed_float_format() {
+        let format = JsonFormat {
+            float_format: FloatFormat::Fixed(2),
+            pretty: false,
+        };
+        assert_eq!(
+            to_string_with(&[1.0, 2.345, -0.001, 7.0], &format).unwrap(),
+            "[1.00,2.35,0.00,7.00]"
+        );
+        // Integers are never given a fractional part
+        assert_eq!(to_string_with(&[7_i64], &format).unwrap(), "[7]");
+    }
+
+    #[test]
+    fn test_output_parses_back_to_same_value() {
+        let original = serde_json::to_value(sample()).unwrap();
+        let json = to_string_pretty(&sample()).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), original);
+    }
+}
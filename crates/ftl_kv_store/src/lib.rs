@@ -0,0 +1,86 @@
+//! Minimal key-value abstraction shared by the tool support crates.
+//!
+//! Tools run against [`SpinStore`], the application's default Spin key-value
+//! store (components need `key_value_stores = ["default"]` in spin.toml).
+//! Unit tests use [`MemoryStore`] so the protocol logic runs natively.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Byte-oriented key-value storage
+pub trait KvStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String>;
+    fn delete(&self, key: &str) -> Result<(), String>;
+}
+
+/// Store backed by the default Spin key-value store
+pub struct SpinStore(spin_sdk::key_value::Store);
+
+impl SpinStore {
+    /// Open the default store; the component needs `key_value_stores = ["default"]`
+    pub fn open_default() -> Result<Self, String> {
+        spin_sdk::key_value::Store::open_default()
+            .map(SpinStore)
+            .map_err(|e| format!("Failed to open key-value store: {e}"))
+    }
+}
+
+impl KvStore for SpinStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        self.0
+            .get(key)
+            .map_err(|e| format!("Key-value get failed: {e}"))
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.0
+            .set(key, value)
+            .map_err(|e| format!("Key-value set failed: {e}"))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        self.0
+            .delete(key)
+            .map_err(|e| format!("Key-value delete failed: {e}"))
+    }
+}
+
+/// In-memory store for tests
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: RefCell<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of keys currently held
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+}
+
+impl KvStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.entries.borrow().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+        self.entries
+            .borrow_mut()
+            .insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        self.entries.borrow_mut().remove(key);
+        Ok(())
+    }
+}
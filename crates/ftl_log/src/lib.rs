@@ -0,0 +1,674 @@
+//! Structured JSON log events for tool calls.
+//!
+//! Every tool wrapper starts a [`ToolLog`] once the request is parsed and
+//! passes its response through [`ToolLog::finish`], which writes one JSON line
+//! to stderr, where Spin collects component logs:
+//!
+//! ```ignore
+//! let log = ToolLog::start("add", &input);
+//! log.finish(add_call(input))
+//! ```
+//!
+//! An event carries the tool name, a hash of the input (so repeated inputs can
+//! be grouped without logging their contents), the duration, the outcome and,
+//! for failures, an error class:
+//!
+//! ```json
+//! {"timestamp_ms":1760000000000,"level":"error","tool":"divide","input_hash":"5f0c6a1e9b2d4c83","duration_ms":0.04,"outcome":"error","error_class":"tool_error"}
+//! ```
+//!
+//! The `FTL_LOG_LEVEL` environment variable selects which events are written:
+//! `off`, `error` (failed calls only), `info` (every call, the default) or
+//! `debug` (every call, with input and output sizes). Introspection requests
+//! and inputs the SDK cannot deserialize never reach the wrapper and are not
+//! logged.
+//!
+//! The same start and finish points time the call for its caller: when the
+//! input sets `include_metrics: true`, [`ToolLog::finish`] appends
+//! `ftl_metrics` execution metrics to the response text. A wrapper opts in by
+//! declaring the field on its input; it never reads it itself.
+
+use ftl_metrics::Metrics;
+use ftl_sdk::ToolResponse;
+use serde::Serialize;
+use serde::ser::{self, Impossible};
+use serde_json::Value;
+use std::fmt;
+use std::io;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub const LOG_LEVEL_ENV: &str = "FTL_LOG_LEVEL";
+/// Input field that asks for execution metrics in the response
+pub const INCLUDE_METRICS_FIELD: &str = "include_metrics";
+
+/// Verbosity of the log, from quietest to most detailed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Level {
+    Off,
+    Error,
+    Info,
+    Debug,
+}
+
+impl Level {
+    pub fn parse(value: &str) -> Option<Level> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" => Some(Level::Off),
+            "error" => Some(Level::Error),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            _ => None,
+        }
+    }
+
+    /// `FTL_LOG_LEVEL` if it names a level, otherwise `info`
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        lookup(LOG_LEVEL_ENV)
+            .and_then(|value| Level::parse(&value))
+            .unwrap_or(Level::Info)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Ok,
+    Error,
+}
+
+/// One log line, written when a tool call completes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogEvent {
+    /// Completion time, in milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    /// `error` for failed calls, `info` otherwise
+    pub level: Level,
+    pub tool: &'static str,
+    /// FNV-1a hash of the input as compact JSON, in hex; not a cryptographic hash
+    pub input_hash: String,
+    pub duration_ms: f64,
+    pub outcome: Outcome,
+    /// Kind of failure: the `error` code of a structured error such as
+    /// `payload_too_large`, `operation_failed` for a category envelope with
+    /// `success: false`, or `tool_error` for an `Error: ...` message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_class: Option<String>,
+    /// Size of the input as compact JSON, in bytes (debug level only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_bytes: Option<usize>,
+    /// Size of the response text, in bytes (debug level only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_bytes: Option<usize>,
+}
+
+/// Log of a single tool call, inert when logging is off
+pub struct ToolLog {
+    tool: &'static str,
+    level: Level,
+    started: Instant,
+    input_hash: u64,
+    input_bytes: usize,
+    metrics: Metrics,
+}
+
+impl ToolLog {
+    /// Start timing a call to `tool` at the level set by `FTL_LOG_LEVEL`;
+    /// `input` is the tool's wrapper input
+    pub fn start<T: Serialize + ?Sized>(tool: &'static str, input: &T) -> Self {
+        Self::with_level(Level::from_env(), tool, input)
+    }
+
+    /// Start timing a call at `level`; the input is serialized only if the
+    /// event or the requested metrics need its hash or size
+    pub fn with_level<T: Serialize + ?Sized>(level: Level, tool: &'static str, input: &T) -> Self {
+        let include_metrics = match lookup(input, &[Step::Field(INCLUDE_METRICS_FIELD)]) {
+            Found::Bool(flag) => Some(flag),
+            _ => None,
+        };
+        let mut digest = Digest::default();
+        if level != Level::Off || include_metrics == Some(true) {
+            digest.hash = FNV_OFFSET_BASIS;
+            // Hash and count the input as it is written instead of buffering it
+            let _ = serde_json::to_writer(&mut digest, input);
+        }
+        ToolLog {
+            tool,
+            level,
+            started: Instant::now(),
+            input_hash: digest.hash,
+            input_bytes: digest.len,
+            metrics: Metrics::start(include_metrics, digest.len),
+        }
+    }
+
+    /// Append metrics to `response` if the input asked for them, write the
+    /// event for it if its level is enabled, and return it
+    pub fn finish(self, response: ToolResponse) -> ToolResponse {
+        if self.level == Level::Off && !self.metrics.enabled() {
+            return response;
+        }
+        let body = response_text(&response);
+        if !self.metrics.enabled() {
+            self.write(&body);
+            return response;
+        }
+        let body = self.metrics.finish(body);
+        self.write(&body);
+        ToolResponse::text(body)
+    }
+
+    /// The event for a response body, or `None` if its level is not enabled
+    pub fn event(&self, body: &str) -> Option<LogEvent> {
+        let error_class = classify(body);
+        let (level, outcome) = match error_class {
+            Some(_) => (Level::Error, Outcome::Error),
+            None => (Level::Info, Outcome::Ok),
+        };
+        if self.level == Level::Off || level > self.level {
+            return None;
+        }
+        let debug = self.level >= Level::Debug;
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        Some(LogEvent {
+            timestamp_ms,
+            level,
+            tool: self.tool,
+            input_hash: format!("{:016x}", self.input_hash),
+            duration_ms: self.started.elapsed().as_secs_f64() * 1000.0,
+            outcome,
+            error_class,
+            input_bytes: debug.then_some(self.input_bytes),
+            output_bytes: debug.then_some(body.len()),
+        })
+    }
+
+    /// Write the event for a response body to stderr, if its level is enabled
+    fn write(&self, body: &str) {
+        let line = self
+            .event(body)
+            .and_then(|e| serde_json::to_string(&e).ok());
+        if let Some(line) = line {
+            eprintln!("{line}");
+        }
+    }
+}
+
+/// Text of the first content item of `response`, or the whole response as
+/// JSON if it has none
+fn response_text(response: &ToolResponse) -> String {
+    let path = [Step::Field("content"), Step::Index(0), Step::Field("text")];
+    match lookup(response, &path) {
+        Found::Str(text) => text,
+        _ => serde_json::to_string(response).unwrap_or_default(),
+    }
+}
+
+/// Error class of a response body, or `None` for a successful call
+pub fn classify(body: &str) -> Option<String> {
+    let body = body.trim_start();
+    if body.starts_with("Error") {
+        return Some("tool_error".to_string());
+    }
+    match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(map)) => {
+            // Responses with metrics wrap a non-JSON body
+            if let Some(Value::String(inner)) = map.get("body") {
+                return classify(inner);
+            }
+            match map.get("error") {
+                Some(Value::String(code))
+                    if !code.is_empty()
+                        && code.chars().all(|c| c.is_ascii_lowercase() || c == '_') =>
+                {
+                    return Some(code.clone());
+                }
+                _ => {}
+            }
+            (map.get("success") == Some(&Value::Bool(false)))
+                .then(|| "operation_failed".to_string())
+        }
+        _ => None,
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// FNV-1a hash and length of the bytes written to it
+#[derive(Default)]
+struct Digest {
+    hash: u64,
+    len: usize,
+}
+
+impl io::Write for Digest {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            self.hash ^= u64::from(byte);
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// One step of a [`Lookup`] path
+#[derive(Clone, Copy)]
+enum Step<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Value at the end of a [`Lookup`] path
+enum Found {
+    Bool(bool),
+    Str(String),
+    Nothing,
+}
+
+/// The bool or string at the end of `path` in `value`
+fn lookup<T: Serialize + ?Sized>(value: &T, path: &[Step<'_>]) -> Found {
+    value.serialize(Lookup(path)).unwrap_or(Found::Nothing)
+}
+
+/// Serializer that follows a path of fields and sequence indices and returns
+/// the bool or string at its end; values off the path are never visited
+struct Lookup<'a>(&'a [Step<'a>]);
+
+/// The path leads through a value that has no fields or elements
+#[derive(Debug)]
+struct Missing;
+
+impl fmt::Display for Missing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("path not found")
+    }
+}
+
+impl std::error::Error for Missing {}
+
+impl ser::Error for Missing {
+    fn custom<T: fmt::Display>(_: T) -> Self {
+        Missing
+    }
+}
+
+macro_rules! found_nothing {
+    ($($method:ident($($arg:ty),*);)*) => {
+        $(fn $method(self, $(_: $arg),*) -> Result<Found, Missing> {
+            Ok(Found::Nothing)
+        })*
+    };
+}
+
+impl<'a> Lookup<'a> {
+    fn fields(self) -> Result<Fields<'a>, Missing> {
+        match self.0.split_first() {
+            Some((Step::Field(name), rest)) => Ok(Fields {
+                name,
+                rest,
+                matched: false,
+                found: Found::Nothing,
+            }),
+            _ => Err(Missing),
+        }
+    }
+}
+
+impl<'a> ser::Serializer for Lookup<'a> {
+    type Ok = Found;
+    type Error = Missing;
+    type SerializeSeq = Elements<'a>;
+    type SerializeTuple = Impossible<Found, Missing>;
+    type SerializeTupleStruct = Impossible<Found, Missing>;
+    type SerializeTupleVariant = Impossible<Found, Missing>;
+    type SerializeMap = Fields<'a>;
+    type SerializeStruct = Fields<'a>;
+    type SerializeStructVariant = Impossible<Found, Missing>;
+
+    fn serialize_bool(self, v: bool) -> Result<Found, Missing> {
+        Ok(match self.0 {
+            [] => Found::Bool(v),
+            _ => Found::Nothing,
+        })
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Found, Missing> {
+        Ok(match self.0 {
+            [] => Found::Str(v.to_string()),
+            _ => Found::Nothing,
+        })
+    }
+
+    found_nothing! {
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Found, Missing> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Found, Missing> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Found, Missing> {
+        Ok(Found::Nothing)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Elements<'a>, Missing> {
+        match self.0.split_first() {
+            Some((Step::Index(index), rest)) => Ok(Elements {
+                index: *index,
+                rest,
+                seen: 0,
+                found: Found::Nothing,
+            }),
+            _ => Err(Missing),
+        }
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Missing> {
+        Err(Missing)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Missing> {
+        Err(Missing)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Missing> {
+        Err(Missing)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Fields<'a>, Missing> {
+        self.fields()
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Fields<'a>, Missing> {
+        self.fields()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Missing> {
+        Err(Missing)
+    }
+}
+
+/// Struct or map being searched for the field `name`
+struct Fields<'a> {
+    name: &'a str,
+    rest: &'a [Step<'a>],
+    /// Whether the last map key was `name`
+    matched: bool,
+    found: Found,
+}
+
+impl ser::SerializeStruct for Fields<'_> {
+    type Ok = Found;
+    type Error = Missing;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Missing> {
+        if key == self.name {
+            self.found = lookup(value, self.rest);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Found, Missing> {
+        Ok(self.found)
+    }
+}
+
+// Wrappers that flatten their parameters serialize as maps
+impl ser::SerializeMap for Fields<'_> {
+    type Ok = Found;
+    type Error = Missing;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Missing> {
+        self.matched = matches!(lookup(key, &[]), Found::Str(key) if key == self.name);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Missing> {
+        if self.matched {
+            self.found = lookup(value, self.rest);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Found, Missing> {
+        Ok(self.found)
+    }
+}
+
+/// Sequence being searched for the element at `index`
+struct Elements<'a> {
+    index: usize,
+    rest: &'a [Step<'a>],
+    seen: usize,
+    found: Found,
+}
+
+impl ser::SerializeSeq for Elements<'_> {
+    type Ok = Found;
+    type Error = Missing;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Missing> {
+        if self.seen == self.index {
+            self.found = lookup(value, self.rest);
+        }
+        self.seen += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Found, Missing> {
+        Ok(self.found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_level_from_env() {
+        let level = |value: Option<&str>| {
+            Level::from_lookup(|name| {
+                assert_eq!(name, LOG_LEVEL_ENV);
+                value.map(str::to_string)
+            })
+        };
+        assert_eq!(level(None), Level::Info);
+        assert_eq!(level(Some(" DEBUG ")), Level::Debug);
+        assert_eq!(level(Some("off")), Level::Off);
+        assert_eq!(level(Some("verbose")), Level::Info);
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(r#"{"result":5.0}"#), None);
+        assert_eq!(
+            classify("Error: Division by zero").as_deref(),
+            Some("tool_error")
+        );
+        let too_large = r#"{"error":"payload_too_large","field":"x","message":"..."}"#;
+        assert_eq!(classify(too_large).as_deref(), Some("payload_too_large"));
+        let envelope = r#"{"operation":"divide","success":false,"error":"Division by zero"}"#;
+        assert_eq!(classify(envelope).as_deref(), Some("operation_failed"));
+        let with_metrics = r#"{"body":"Error: empty","metrics":{"duration_ms":0.1}}"#;
+        assert_eq!(classify(with_metrics).as_deref(), Some("tool_error"));
+        // A plain string result that mentions an error is not a failure
+        assert_eq!(classify(r#"{"message":"Error rate is low"}"#), None);
+    }
+
+    #[test]
+    fn test_event_fields_and_levels() {
+        let input = json!({ "a": 1, "b": 0 });
+        let log = ToolLog::with_level(Level::Info, "divide", &input);
+        let event = log.event("Error: Division by zero").unwrap();
+        assert_eq!(event.tool, "divide");
+        assert_eq!(event.outcome, Outcome::Error);
+        assert_eq!(event.level, Level::Error);
+        assert_eq!(event.error_class.as_deref(), Some("tool_error"));
+        assert_eq!(event.input_hash.len(), 16);
+        assert_eq!(event.input_bytes, None);
+
+        // Identical inputs hash identically
+        let again = ToolLog::with_level(Level::Info, "divide", &input);
+        assert_eq!(again.event("{}").unwrap().input_hash, event.input_hash);
+
+        let quiet = ToolLog::with_level(Level::Error, "divide", &input);
+        assert!(quiet.event(r#"{"result":1.0}"#).is_none());
+        assert!(quiet.event("Error: Division by zero").is_some());
+        assert!(
+            ToolLog::with_level(Level::Off, "divide", &input)
+                .event("Error")
+                .is_none()
+        );
+
+        let verbose = ToolLog::with_level(Level::Debug, "divide", &input);
+        let event = verbose.event(r#"{"result":1.0}"#).unwrap();
+        assert_eq!(
+            event.input_bytes,
+            Some(serde_json::to_vec(&input).unwrap().len())
+        );
+        assert_eq!(event.output_bytes, Some(14));
+    }
+
+    #[test]
+    fn test_event_json() {
+        let log = ToolLog::with_level(Level::Info, "add", &json!({ "a": 1, "b": 2 }));
+        let event = serde_json::to_value(log.event(r#"{"result":3.0}"#).unwrap()).unwrap();
+        assert_eq!(event["level"], "info");
+        assert_eq!(event["outcome"], "ok");
+        assert!(event.get("error_class").is_none());
+        assert!(event.get("input_bytes").is_none());
+    }
+
+    #[test]
+    fn test_finish_returns_response_and_reads_content() {
+        assert_eq!(
+            response_text(&ToolResponse::text("Error: bad")),
+            "Error: bad"
+        );
+        let log = ToolLog::with_level(Level::Off, "add", &json!({}));
+        let response = log.finish(ToolResponse::text(r#"{"result":3.0}"#));
+        assert_eq!(response_text(&response), r#"{"result":3.0}"#);
+    }
+
+    #[derive(Serialize)]
+    struct Wrapper {
+        #[serde(flatten)]
+        params: Params,
+        include_metrics: Option<bool>,
+    }
+
+    #[derive(Serialize)]
+    struct Params {
+        a: f64,
+        include_metrics: Vec<u8>,
+    }
+
+    #[test]
+    fn test_lookup_reads_only_top_level_fields() {
+        let path = [Step::Field(INCLUDE_METRICS_FIELD)];
+        let wrapper = |include_metrics| Wrapper {
+            params: Params {
+                a: 1.0,
+                include_metrics: vec![1],
+            },
+            include_metrics,
+        };
+        assert!(matches!(
+            lookup(&wrapper(Some(true)), &path),
+            Found::Bool(true)
+        ));
+        assert!(matches!(lookup(&wrapper(None), &path), Found::Nothing));
+        assert!(matches!(lookup(&json!([true]), &path), Found::Nothing));
+
+        let log = ToolLog::with_level(Level::Off, "add", &wrapper(None));
+        assert_eq!((log.input_hash, log.input_bytes), (0, 0));
+        let log = ToolLog::with_level(Level::Off, "add", &wrapper(Some(true)));
+        assert_eq!(
+            log.input_bytes,
+            serde_json::to_vec(&wrapper(Some(true))).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_finish_appends_requested_metrics() {
+        let response = || ToolResponse::text(r#"{"result":3.0}"#);
+        let plain = ToolLog::with_level(Level::Off, "add", &json!({ "a": 1, "b": 2 }));
+        assert_eq!(
+            response_text(&plain.finish(response())),
+            r#"{"result":3.0}"#
+        );
+
+        let input = json!({ "a": 1, "b": 2, "include_metrics": true });
+        let log = ToolLog::with_level(Level::Off, "add", &input);
+        let text = response_text(&log.finish(response()));
+        let body: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(body["result"], 3.0);
+        assert_eq!(
+            body["metrics"]["input_bytes"],
+            serde_json::to_vec(&input).unwrap().len()
+        );
+        assert_eq!(body["metrics"]["output_bytes"], 14);
+    }
+}
@@ -0,0 +1,471 @@
+//! 4x4 transform matrices shared by the math3d tools.
+//!
+//! [`Matrix4`] is serialized as four rows, `[[m00, m01, m02, m03], ...]`, and
+//! acts on column vectors: a point `p` maps to `M · [p, 1]`, so the
+//! translation sits in the last column and `a.multiply(&b)` applies `b`
+//! first. This matches the 3x3 matrices returned by rotation_matrix.
+//!
+//! An affine matrix (bottom row `[0, 0, 0, 1]`) without shear splits into
+//! translation, rotation and scale with [`Matrix4::decompose`], and
+//! [`Matrix4::compose`] rebuilds it as `T · R · S`. Rotations can be built from a
+//! quaternion or an axis and angle with [`quaternion_rotation`] and
+//! [`axis_angle_rotation`].
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Pivots smaller than this, relative to the largest entry, make a matrix singular
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+/// Largest deviation accepted in the affine bottom row
+const AFFINE_TOLERANCE: f64 = 1e-9;
+/// Largest deviation from orthonormality accepted between the scaled-out axes
+const ORTHONORMAL_TOLERANCE: f64 = 1e-6;
+
+/// 4x4 matrix as four rows of four numbers
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct Matrix4(pub [[f64; 4]; 4]);
+
+/// Translation, rotation and scale of an affine matrix, as applied in reverse order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decomposition {
+    pub translation: [f64; 3],
+    /// Proper rotation matrix, as rows
+    pub rotation: [[f64; 3]; 3],
+    /// Unit quaternion `[x, y, z, w]` of `rotation`, with w ≥ 0
+    pub quaternion: [f64; 4],
+    /// Scale along each local axis; a reflection shows up as a negative x scale
+    pub scale: [f64; 3],
+}
+
+impl Matrix4 {
+    pub const IDENTITY: Matrix4 = Matrix4([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    pub fn is_finite(&self) -> bool {
+        self.0.iter().flatten().all(|v| v.is_finite())
+    }
+
+    pub fn from_translation(translation: [f64; 3]) -> Matrix4 {
+        let mut m = Matrix4::IDENTITY;
+        for (row, t) in translation.into_iter().enumerate() {
+            m.0[row][3] = t;
+        }
+        m
+    }
+
+    pub fn from_rotation(rotation: [[f64; 3]; 3]) -> Matrix4 {
+        let mut m = Matrix4::IDENTITY;
+        for (row, values) in rotation.into_iter().enumerate() {
+            m.0[row][..3].copy_from_slice(&values);
+        }
+        m
+    }
+
+    pub fn from_scale(scale: [f64; 3]) -> Matrix4 {
+        let mut m = Matrix4::IDENTITY;
+        for (i, s) in scale.into_iter().enumerate() {
+            m.0[i][i] = s;
+        }
+        m
+    }
+
+    /// Maps a point through `self · [p, 1]`, dividing by the resulting w for projective
+    /// matrices
+    pub fn transform_point(&self, point: [f64; 3]) -> Result<[f64; 3], String> {
+        let [x, y, z] = point;
+        let row = |r: &[f64; 4]| r[0] * x + r[1] * y + r[2] * z + r[3];
+        let w = row(&self.0[3]);
+        if w.abs() < SINGULAR_TOLERANCE {
+            return Err("Point maps to infinity (w = 0)".to_string());
+        }
+        Ok([
+            row(&self.0[0]) / w,
+            row(&self.0[1]) / w,
+            row(&self.0[2]) / w,
+        ])
+    }
+
+    /// `T · R · S`: scale first, then rotate, then translate
+    pub fn compose(translation: [f64; 3], rotation: [[f64; 3]; 3], scale: [f64; 3]) -> Matrix4 {
+        Matrix4::from_translation(translation)
+            .multiply(&Matrix4::from_rotation(rotation))
+            .multiply(&Matrix4::from_scale(scale))
+    }
+
+    /// `self · other`, which applies `other` first
+    pub fn multiply(&self, other: &Matrix4) -> Matrix4 {
+        let mut m = [[0.0; 4]; 4];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.0[row][k] * other.0[k][col]).sum();
+            }
+        }
+        Matrix4(m)
+    }
+
+    pub fn transpose(&self) -> Matrix4 {
+        let mut m = [[0.0; 4]; 4];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                *value = self.0[col][row];
+            }
+        }
+        Matrix4(m)
+    }
+
+    /// Determinant by Laplace expansion over 2x2 minors of the top and bottom row pairs
+    pub fn determinant(&self) -> f64 {
+        let m = &self.0;
+        let top = |a: usize, b: usize| m[0][a] * m[1][b] - m[0][b] * m[1][a];
+        let bottom = |a: usize, b: usize| m[2][a] * m[3][b] - m[2][b] * m[3][a];
+        top(0, 1) * bottom(2, 3) - top(0, 2) * bottom(1, 3)
+            + top(0, 3) * bottom(1, 2)
+            + top(1, 2) * bottom(0, 3)
+            - top(1, 3) * bottom(0, 2)
+            + top(2, 3) * bottom(0, 1)
+    }
+
+    /// Inverse by Gauss-Jordan elimination with partial pivoting
+    pub fn inverse(&self) -> Result<Matrix4, String> {
+        let largest = self
+            .0
+            .iter()
+            .flatten()
+            .fold(0.0_f64, |acc, v| acc.max(v.abs()));
+        let mut a = self.0;
+        let mut inv = Matrix4::IDENTITY.0;
+        for col in 0..4 {
+            let pivot = (col..4)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap_or(col);
+            if a[pivot][col].abs() <= SINGULAR_TOLERANCE * largest {
+                return Err("Matrix is singular and has no inverse".to_string());
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let scale = 1.0 / a[col][col];
+            let pivot_row = a[col].map(|v| v * scale);
+            let pivot_inv = inv[col].map(|v| v * scale);
+            for row in 0..4 {
+                if row == col {
+                    a[row] = pivot_row;
+                    inv[row] = pivot_inv;
+                    continue;
+                }
+                let factor = a[row][col];
+                for (value, pivot) in a[row].iter_mut().zip(pivot_row) {
+                    *value -= factor * pivot;
+                }
+                for (value, pivot) in inv[row].iter_mut().zip(pivot_inv) {
+                    *value -= factor * pivot;
+                }
+            }
+        }
+        Ok(Matrix4(inv))
+    }
+
+    /// Split an affine matrix into translation, rotation and scale. Fails for a
+    /// projective bottom row, a zero scale, or shear between the axes.
+    pub fn decompose(&self) -> Result<Decomposition, String> {
+        let m = &self.0;
+        let bottom = [0.0, 0.0, 0.0, 1.0];
+        if m[3]
+            .iter()
+            .zip(bottom)
+            .any(|(v, expected)| (v - expected).abs() > AFFINE_TOLERANCE)
+        {
+            return Err(
+                "Only affine matrices, with bottom row [0, 0, 0, 1], can be decomposed".to_string(),
+            );
+        }
+
+        let translation = [m[0][3], m[1][3], m[2][3]];
+        let mut axes = [[0.0; 3]; 3];
+        let mut scale = [0.0; 3];
+        for col in 0..3 {
+            let axis = [m[0][col], m[1][col], m[2][col]];
+            let length = axis.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if length <= SINGULAR_TOLERANCE {
+                return Err("Matrix has a zero scale and cannot be decomposed".to_string());
+            }
+            scale[col] = length;
+            axes[col] = axis.map(|v| v / length);
+        }
+
+        let [x, y, z] = axes;
+        let cross = [
+            x[1] * y[2] - x[2] * y[1],
+            x[2] * y[0] - x[0] * y[2],
+            x[0] * y[1] - x[1] * y[0],
+        ];
+        if cross.iter().zip(z).map(|(c, v)| c * v).sum::<f64>() < 0.0 {
+            // Fold a reflection into the x scale so the rotation stays proper
+            scale[0] = -scale[0];
+            axes[0] = x.map(|v| -v);
+        }
+
+        for i in 0..3 {
+            for j in 0..3 {
+                let dot: f64 = (0..3).map(|k| axes[i][k] * axes[j][k]).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                if (dot - expected).abs() > ORTHONORMAL_TOLERANCE {
+                    return Err(
+                        "Matrix has shear and cannot be decomposed into translation, \
+                                rotation and scale"
+                            .to_string(),
+                    );
+                }
+            }
+        }
+
+        // The axes are the rotation's columns
+        let mut rotation = [[0.0; 3]; 3];
+        for (row, values) in rotation.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                *value = axes[col][row];
+            }
+        }
+
+        Ok(Decomposition {
+            translation,
+            rotation,
+            quaternion: rotation_quaternion(&rotation),
+            scale,
+        })
+    }
+}
+
+/// Rotation matrix of a quaternion `[x, y, z, w]`, normalized first
+pub fn quaternion_rotation(q: [f64; 4]) -> Result<[[f64; 3]; 3], String> {
+    let norm = q.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if !norm.is_finite() || norm < 1e-10 {
+        return Err("Quaternion must be finite and non-zero".to_string());
+    }
+    let [x, y, z, w] = q.map(|v| v / norm);
+    Ok([
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - z * w),
+            2.0 * (x * z + y * w),
+        ],
+        [
+            2.0 * (x * y + z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - x * w),
+        ],
+        [
+            2.0 * (x * z - y * w),
+            2.0 * (y * z + x * w),
+            1.0 - 2.0 * (x * x + y * y),
+        ],
+    ])
+}
+
+/// Rotation matrix for `angle` radians about `axis`, counterclockwise looking down the
+/// axis (Rodrigues' formula)
+pub fn axis_angle_rotation(axis: [f64; 3], angle: f64) -> Result<[[f64; 3]; 3], String> {
+    let length = axis.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if !length.is_finite() || length < 1e-10 || !angle.is_finite() {
+        return Err("Rotation axis must be finite and non-zero, and angle finite".to_string());
+    }
+    let [x, y, z] = axis.map(|v| v / length);
+    let (s, c) = angle.sin_cos();
+    let t = 1.0 - c;
+    Ok([
+        [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+        [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+        [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+    ])
+}
+
+/// Unit quaternion `[x, y, z, w]` of a proper rotation matrix (Shepperd's method),
+/// with w ≥ 0
+pub fn rotation_quaternion(r: &[[f64; 3]; 3]) -> [f64; 4] {
+    let trace = r[0][0] + r[1][1] + r[2][2];
+    let q = if trace >= r[0][0].max(r[1][1]).max(r[2][2]) {
+        let s = (1.0 + trace).sqrt() * 2.0;
+        [
+            (r[2][1] - r[1][2]) / s,
+            (r[0][2] - r[2][0]) / s,
+            (r[1][0] - r[0][1]) / s,
+            s / 4.0,
+        ]
+    } else if r[0][0] >= r[1][1] && r[0][0] >= r[2][2] {
+        let s = (1.0 + r[0][0] - r[1][1] - r[2][2]).sqrt() * 2.0;
+        [
+            s / 4.0,
+            (r[0][1] + r[1][0]) / s,
+            (r[0][2] + r[2][0]) / s,
+            (r[2][1] - r[1][2]) / s,
+        ]
+    } else if r[1][1] >= r[2][2] {
+        let s = (1.0 + r[1][1] - r[0][0] - r[2][2]).sqrt() * 2.0;
+        [
+            (r[0][1] + r[1][0]) / s,
+            s / 4.0,
+            (r[1][2] + r[2][1]) / s,
+            (r[0][2] - r[2][0]) / s,
+        ]
+    } else {
+        let s = (1.0 + r[2][2] - r[0][0] - r[1][1]).sqrt() * 2.0;
+        [
+            (r[0][2] + r[2][0]) / s,
+            (r[1][2] + r[2][1]) / s,
+            s / 4.0,
+            (r[1][0] - r[0][1]) / s,
+        ]
+    };
+    let norm = q.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let sign = if q[3] < 0.0 { -1.0 } else { 1.0 };
+    q.map(|v| sign * v / norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Matrix4, b: &Matrix4) {
+        for (x, y) in a.0.iter().flatten().zip(b.0.iter().flatten()) {
+            assert!((x - y).abs() < 1e-9, "{a:?} != {b:?}");
+        }
+    }
+
+    /// 90° about z
+    const QUARTER_TURN_Z: [[f64; 3]; 3] = [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+
+    #[test]
+    fn test_serializes_as_rows() {
+        let json = serde_json::to_string(&Matrix4::IDENTITY).unwrap();
+        assert_eq!(
+            json,
+            "[[1.0,0.0,0.0,0.0],[0.0,1.0,0.0,0.0],[0.0,0.0,1.0,0.0],[0.0,0.0,0.0,1.0]]"
+        );
+        let m: Matrix4 = serde_json::from_str(&json).unwrap();
+        assert_eq!(m, Matrix4::IDENTITY);
+        assert!(serde_json::from_str::<Matrix4>("[[1, 2, 3]]").is_err());
+    }
+
+    #[test]
+    fn test_multiply_applies_right_operand_first() {
+        let translate = Matrix4::from_translation([1.0, 0.0, 0.0]);
+        let rotate = Matrix4::from_rotation(QUARTER_TURN_Z);
+        // Rotate then translate: the translation is unaffected by the rotation
+        let m = translate.multiply(&rotate);
+        assert_eq!([m.0[0][3], m.0[1][3], m.0[2][3]], [1.0, 0.0, 0.0]);
+        // Translate then rotate: (1, 0, 0) turns into (0, 1, 0)
+        let m = rotate.multiply(&translate);
+        assert_eq!([m.0[0][3], m.0[1][3], m.0[2][3]], [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_transpose_and_determinant() {
+        let m = Matrix4([
+            [2.0, 0.0, 1.0, 3.0],
+            [1.0, -1.0, 0.0, 2.0],
+            [0.0, 4.0, 1.0, 0.0],
+            [1.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(m.transpose().transpose(), m);
+        assert_eq!(m.transpose().0[0], [2.0, 1.0, 0.0, 1.0]);
+        // Cofactor expansion along the bottom row: -5 + 2, from the minors of the two ones
+        assert!((m.determinant() + 3.0).abs() < 1e-12);
+        assert!((m.transpose().determinant() + 3.0).abs() < 1e-12);
+        assert_eq!(Matrix4::from_scale([2.0, 3.0, 4.0]).determinant(), 24.0);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = Matrix4::compose([1.0, -2.0, 3.0], QUARTER_TURN_Z, [2.0, 0.5, 1.0]);
+        let inv = m.inverse().unwrap();
+        assert_close(&m.multiply(&inv), &Matrix4::IDENTITY);
+        assert_close(&inv.multiply(&m), &Matrix4::IDENTITY);
+
+        let mut singular = Matrix4::IDENTITY;
+        singular.0[2] = singular.0[1];
+        assert!(singular.inverse().unwrap_err().contains("singular"));
+        assert!(Matrix4([[0.0; 4]; 4]).inverse().is_err());
+    }
+
+    #[test]
+    fn test_decompose_round_trip() {
+        let m = Matrix4::compose([4.0, 5.0, 6.0], QUARTER_TURN_Z, [1.0, 2.0, 3.0]);
+        let d = m.decompose().unwrap();
+        assert_close(&Matrix4::compose(d.translation, d.rotation, d.scale), &m);
+        assert_eq!(d.translation, [4.0, 5.0, 6.0]);
+        for (s, expected) in d.scale.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((s - expected).abs() < 1e-12);
+        }
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        for (q, expected) in d.quaternion.iter().zip([0.0, 0.0, half, half]) {
+            assert!((q - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_decompose_reflection() {
+        let m = Matrix4::from_scale([1.0, -1.0, 1.0]);
+        let d = m.decompose().unwrap();
+        assert_eq!(d.scale[0], -1.0);
+        assert_close(&Matrix4::compose(d.translation, d.rotation, d.scale), &m);
+        let r = Matrix4::from_rotation(d.rotation);
+        assert!((r.determinant() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_transform_point() {
+        let m = Matrix4::compose([1.0, 2.0, 3.0], QUARTER_TURN_Z, [2.0, 2.0, 2.0]);
+        // Scaled to (2, 0, 0), turned to (0, 2, 0), then moved
+        assert_eq!(m.transform_point([1.0, 0.0, 0.0]).unwrap(), [1.0, 4.0, 3.0]);
+
+        let mut projective = Matrix4::IDENTITY;
+        projective.0[3] = [0.0, 0.0, 1.0, 0.0];
+        assert_eq!(
+            projective.transform_point([2.0, 4.0, 2.0]).unwrap(),
+            [1.0, 2.0, 1.0]
+        );
+        assert!(projective.transform_point([1.0, 1.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn test_rotation_builders() {
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let from_quaternion = quaternion_rotation([0.0, 0.0, half, half]).unwrap();
+        let from_axis = axis_angle_rotation([0.0, 0.0, 5.0], std::f64::consts::FRAC_PI_2).unwrap();
+        for rotation in [from_quaternion, from_axis] {
+            assert_close(
+                &Matrix4::from_rotation(rotation),
+                &Matrix4::from_rotation(QUARTER_TURN_Z),
+            );
+        }
+        // Round trip through rotation_quaternion, including the normalization
+        let q = rotation_quaternion(&quaternion_rotation([1.0, 2.0, 3.0, 4.0]).unwrap());
+        let norm = 30.0_f64.sqrt();
+        for (value, expected) in q.iter().zip([1.0, 2.0, 3.0, 4.0]) {
+            assert!((value - expected / norm).abs() < 1e-12);
+        }
+        assert!(quaternion_rotation([0.0; 4]).is_err());
+        assert!(axis_angle_rotation([0.0; 3], 1.0).is_err());
+    }
+
+    #[test]
+    fn test_decompose_rejects() {
+        let mut projective = Matrix4::IDENTITY;
+        projective.0[3][2] = -1.0;
+        assert!(projective.decompose().unwrap_err().contains("affine"));
+        assert!(
+            Matrix4::from_scale([1.0, 0.0, 1.0])
+                .decompose()
+                .unwrap_err()
+                .contains("zero scale")
+        );
+        let mut shear = Matrix4::IDENTITY;
+        shear.0[0][1] = 0.5;
+        assert!(shear.decompose().unwrap_err().contains("shear"));
+    }
+}
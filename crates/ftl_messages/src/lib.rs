@@ -0,0 +1,269 @@
+//! Localized human-readable strings in tool results.
+//!
+//! Interpretations ("Data appears to be normally distributed ...") and
+//! validation reasons ("Email must contain @ symbol") are meant to be shown to
+//! end users, so a tool that returns them accepts an optional `locale` input
+//! and looks each string up in this catalog instead of hard-coding English:
+//!
+//! ```ignore
+//! let locale = input.locale.unwrap_or_default();
+//! let reason = ftl_messages::EMAIL_MISSING_AT.text(locale);
+//! let summary = ftl_messages::NORMALITY_NORMAL.format(locale, &[("p_value", p.as_str())]);
+//! ```
+//!
+//! English is the default and the reference text. Identifiers, field names
+//! and error messages for malformed requests stay in English.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Language of the human-readable strings in a tool result
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+    /// Spanish
+    Es,
+    /// German
+    De,
+    /// Japanese
+    Ja,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::En, Locale::Es, Locale::De, Locale::Ja];
+}
+
+/// One catalog entry, translated into every [`Locale`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    pub en: &'static str,
+    pub es: &'static str,
+    pub de: &'static str,
+    pub ja: &'static str,
+}
+
+impl Message {
+    pub fn text(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::En => self.en,
+            Locale::Es => self.es,
+            Locale::De => self.de,
+            Locale::Ja => self.ja,
+        }
+    }
+
+    /// The text with each `{name}` placeholder replaced by its argument
+    pub fn format(&self, locale: Locale, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(self.text(locale).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+}
+
+// email_validator
+
+pub const EMAIL_LENGTH: Message = Message {
+    en: "Email length must be between 3 and 320 characters",
+    es: "La longitud del correo electrónico debe estar entre 3 y 320 caracteres",
+    de: "Die E-Mail-Adresse muss zwischen 3 und 320 Zeichen lang sein",
+    ja: "メールアドレスの長さは3～320文字である必要があります",
+};
+
+pub const EMAIL_MISSING_AT: Message = Message {
+    en: "Email must contain @ symbol",
+    es: "El correo electrónico debe contener el símbolo @",
+    de: "Die E-Mail-Adresse muss das Zeichen @ enthalten",
+    ja: "メールアドレスには@記号が必要です",
+};
+
+pub const EMAIL_MULTIPLE_AT: Message = Message {
+    en: "Email must contain exactly one @ symbol",
+    es: "El correo electrónico debe contener exactamente un símbolo @",
+    de: "Die E-Mail-Adresse muss genau ein @-Zeichen enthalten",
+    ja: "メールアドレスには@記号を1つだけ含める必要があります",
+};
+
+pub const EMAIL_CONSECUTIVE_DOTS: Message = Message {
+    en: "Email cannot contain consecutive dots",
+    es: "El correo electrónico no puede contener puntos consecutivos",
+    de: "Die E-Mail-Adresse darf keine aufeinanderfolgenden Punkte enthalten",
+    ja: "メールアドレスに連続したドットを含めることはできません",
+};
+
+pub const EMAIL_EDGE_DOTS: Message = Message {
+    en: "Email parts cannot start or end with dots",
+    es: "Las partes del correo electrónico no pueden empezar ni terminar con un punto",
+    de: "Teile der E-Mail-Adresse dürfen nicht mit einem Punkt beginnen oder enden",
+    ja: "メールアドレスの各部分をドットで始めたり終えたりすることはできません",
+};
+
+pub const EMAIL_INVALID_LOCAL: Message = Message {
+    en: "Invalid local part (before @)",
+    es: "Parte local no válida (antes de @)",
+    de: "Ungültiger lokaler Teil (vor @)",
+    ja: "ローカル部（@の前）が無効です",
+};
+
+pub const EMAIL_INVALID_DOMAIN: Message = Message {
+    en: "Invalid domain part (after @)",
+    es: "Parte de dominio no válida (después de @)",
+    de: "Ungültiger Domainteil (nach @)",
+    ja: "ドメイン部（@の後）が無効です",
+};
+
+pub const EMAIL_INVALID_CHARACTERS: Message = Message {
+    en: "Email contains invalid characters",
+    es: "El correo electrónico contiene caracteres no válidos",
+    de: "Die E-Mail-Adresse enthält ungültige Zeichen",
+    ja: "メールアドレスに無効な文字が含まれています",
+};
+
+// test_normality: {p_value} and {confidence_level}
+
+pub const NORMALITY_NORMAL: Message = Message {
+    en: "Data appears to be normally distributed (p-value: {p_value} > {confidence_level})",
+    es: "Los datos parecen seguir una distribución normal (valor p: {p_value} > {confidence_level})",
+    de: "Die Daten scheinen normalverteilt zu sein (p-Wert: {p_value} > {confidence_level})",
+    ja: "データは正規分布に従っているようです（p値: {p_value} > {confidence_level}）",
+};
+
+pub const NORMALITY_NOT_NORMAL: Message = Message {
+    en: "Data does not appear to be normally distributed (p-value: {p_value} <= {confidence_level})",
+    es: "Los datos no parecen seguir una distribución normal (valor p: {p_value} <= {confidence_level})",
+    de: "Die Daten scheinen nicht normalverteilt zu sein (p-Wert: {p_value} <= {confidence_level})",
+    ja: "データは正規分布に従っていないようです（p値: {p_value} <= {confidence_level}）",
+};
+
+// analyze_distribution
+
+pub const DISTRIBUTION_NORMAL: Message = Message {
+    en: "Normal Distribution",
+    es: "Distribución normal",
+    de: "Normalverteilung",
+    ja: "正規分布",
+};
+
+pub const DISTRIBUTION_APPROXIMATELY_NORMAL: Message = Message {
+    en: "Approximately Normal Distribution",
+    es: "Distribución aproximadamente normal",
+    de: "Annähernde Normalverteilung",
+    ja: "ほぼ正規分布",
+};
+
+pub const DISTRIBUTION_RIGHT_SKEWED: Message = Message {
+    en: "Right-skewed Distribution (consider Log-normal, Exponential, or Gamma)",
+    es: "Distribución sesgada a la derecha (considere log-normal, exponencial o gamma)",
+    de: "Rechtsschiefe Verteilung (Log-Normal-, Exponential- oder Gammaverteilung erwägen)",
+    ja: "右に歪んだ分布（対数正規分布、指数分布、ガンマ分布を検討してください）",
+};
+
+pub const DISTRIBUTION_LEFT_SKEWED: Message = Message {
+    en: "Left-skewed Distribution (consider Beta or transformed distributions)",
+    es: "Distribución sesgada a la izquierda (considere la distribución beta o distribuciones transformadas)",
+    de: "Linksschiefe Verteilung (Betaverteilung oder transformierte Verteilungen erwägen)",
+    ja: "左に歪んだ分布（ベータ分布または変換した分布を検討してください）",
+};
+
+pub const DISTRIBUTION_HEAVY_TAILED: Message = Message {
+    en: "Heavy-tailed Distribution (consider t-distribution or Laplace)",
+    es: "Distribución de colas pesadas (considere la distribución t o la de Laplace)",
+    de: "Verteilung mit schweren Rändern (t- oder Laplace-Verteilung erwägen)",
+    ja: "裾の重い分布（t分布またはラプラス分布を検討してください）",
+};
+
+pub const DISTRIBUTION_LIGHT_TAILED: Message = Message {
+    en: "Light-tailed Distribution (consider Uniform or truncated distributions)",
+    es: "Distribución de colas ligeras (considere la distribución uniforme o distribuciones truncadas)",
+    de: "Verteilung mit leichten Rändern (Gleichverteilung oder gestutzte Verteilungen erwägen)",
+    ja: "裾の軽い分布（一様分布または切断分布を検討してください）",
+};
+
+pub const DISTRIBUTION_NON_NORMAL: Message = Message {
+    en: "Non-normal Distribution (consider non-parametric approaches)",
+    es: "Distribución no normal (considere métodos no paramétricos)",
+    de: "Nicht normalverteilt (nichtparametrische Verfahren erwägen)",
+    ja: "非正規分布（ノンパラメトリック手法を検討してください）",
+};
+
+/// Every catalog entry, for checks that apply to all of them
+pub const CATALOG: &[Message] = &[
+    EMAIL_LENGTH,
+    EMAIL_MISSING_AT,
+    EMAIL_MULTIPLE_AT,
+    EMAIL_CONSECUTIVE_DOTS,
+    EMAIL_EDGE_DOTS,
+    EMAIL_INVALID_LOCAL,
+    EMAIL_INVALID_DOMAIN,
+    EMAIL_INVALID_CHARACTERS,
+    NORMALITY_NORMAL,
+    NORMALITY_NOT_NORMAL,
+    DISTRIBUTION_NORMAL,
+    DISTRIBUTION_APPROXIMATELY_NORMAL,
+    DISTRIBUTION_RIGHT_SKEWED,
+    DISTRIBUTION_LEFT_SKEWED,
+    DISTRIBUTION_HEAVY_TAILED,
+    DISTRIBUTION_LIGHT_TAILED,
+    DISTRIBUTION_NON_NORMAL,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `{name}` placeholders in order of appearance
+    fn placeholders(text: &str) -> Vec<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_entry_is_translated_with_the_same_placeholders() {
+        for message in CATALOG {
+            let mut expected = placeholders(message.en);
+            expected.sort_unstable();
+            for locale in Locale::ALL {
+                let text = message.text(locale);
+                assert!(
+                    !text.is_empty(),
+                    "{:?} missing for '{}'",
+                    locale,
+                    message.en
+                );
+                let mut found = placeholders(text);
+                found.sort_unstable();
+                assert_eq!(found, expected, "{locale:?} for '{}'", message.en);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_fills_placeholders() {
+        let text = NORMALITY_NORMAL.format(
+            Locale::De,
+            &[("p_value", "0.4213"), ("confidence_level", "0.05")],
+        );
+        assert_eq!(
+            text,
+            "Die Daten scheinen normalverteilt zu sein (p-Wert: 0.4213 > 0.05)"
+        );
+    }
+
+    #[test]
+    fn test_locale_codes() {
+        let locale: Locale = serde_json::from_str("\"ja\"").unwrap();
+        assert_eq!(locale, Locale::Ja);
+        assert_eq!(serde_json::to_string(&Locale::Es).unwrap(), "\"es\"");
+        assert_eq!(Locale::default(), Locale::En);
+        assert!(serde_json::from_str::<Locale>("\"fr\"").is_err());
+        assert_eq!(
+            EMAIL_MISSING_AT.text(Locale::En),
+            "Email must contain @ symbol"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Opt-in execution metrics appended to tool responses.
+//!
+//! Profiling a slow call (a polynomial regression over a large dataset, say)
+//! used to need external tooling. Every tool accepts an optional
+//! `include_metrics` flag. `ftl_log::ToolLog` starts a [`Metrics`] when it
+//! starts logging the call and passes the response body through
+//! [`Metrics::finish`] when the call completes, so wrappers only declare the
+//! flag on their input:
+//!
+//! ```ignore
+//! /// Append execution metrics (duration and input/output size) to the response
+//! pub include_metrics: Option<bool>,
+//! ```
+//!
+//! When the flag is set, a JSON object body gets a `metrics` field holding a
+//! [`ResponseMetrics`]. Any other body (an error message, a base64
+//! MessagePack response) is returned as `{"body": <text>, "metrics": {..}}`
+//! so the metrics are always machine-readable. Without the flag the body is
+//! returned untouched.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Instant;
+
+/// Field added to the response object
+pub const METRICS_FIELD: &str = "metrics";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ResponseMetrics {
+    /// Wall-clock time from parsing the request to building the response, in milliseconds
+    pub duration_ms: f64,
+    /// Size of the tool input, as compact JSON, in bytes
+    pub input_bytes: usize,
+    /// Size of the response body without these metrics, in bytes
+    pub output_bytes: usize,
+}
+
+/// Measurement of a single tool call, inert unless metrics were requested
+pub struct Metrics {
+    started: Option<(Instant, usize)>,
+}
+
+impl Metrics {
+    /// Start the clock if `include_metrics` is set; `input_bytes` is the size
+    /// of the tool's wrapper input as compact JSON
+    pub fn start(include_metrics: Option<bool>, input_bytes: usize) -> Self {
+        let started = include_metrics
+            .unwrap_or(false)
+            .then(|| (Instant::now(), input_bytes));
+        Metrics { started }
+    }
+
+    /// Whether metrics were requested
+    pub fn enabled(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Metrics for a response body, if they were requested
+    pub fn measure(&self, body: &str) -> Option<ResponseMetrics> {
+        self.started.map(|(started, input_bytes)| ResponseMetrics {
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            input_bytes,
+            output_bytes: body.len(),
+        })
+    }
+
+    /// Append the metrics to a response body, if they were requested
+    pub fn finish(&self, body: String) -> String {
+        let Some(metrics) = self.measure(&body) else {
+            return body;
+        };
+        let metrics = serde_json::to_value(&metrics).expect("metrics serialize to JSON");
+
+        let value = match serde_json::from_str::<Value>(&body) {
+            Ok(Value::Object(mut map)) => {
+                map.insert(METRICS_FIELD.to_string(), metrics);
+                Value::Object(map)
+            }
+            _ => serde_json::json!({ "body": body.clone(), METRICS_FIELD: metrics }),
+        };
+        // Keep the body's layout: pretty bodies span several lines
+        let serialized = if body.contains('\n') {
+            ftl_json::to_string_pretty(&value)
+        } else {
+            ftl_json::to_string(&value)
+        };
+        serialized.unwrap_or(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metrics_of(body: &str) -> Value {
+        serde_json::from_str::<Value>(body).unwrap()[METRICS_FIELD].clone()
+    }
+
+    #[test]
+    fn test_disabled_leaves_body_untouched() {
+        for flag in [None, Some(false)] {
+            let metrics = Metrics::start(flag, 17);
+            assert!(!metrics.enabled());
+            assert_eq!(metrics.finish(r#"{"a":1}"#.to_string()), r#"{"a":1}"#);
+        }
+    }
+
+    #[test]
+    fn test_object_body_gets_metrics_field() {
+        let metrics = Metrics::start(Some(true), 15);
+        let body = r#"{"mean":2.0}"#;
+
+        let finished = metrics.finish(body.to_string());
+        let value: Value = serde_json::from_str(&finished).unwrap();
+        assert_eq!(value["mean"], 2.0);
+        let m = metrics_of(&finished);
+        assert_eq!(m["input_bytes"], 15);
+        assert_eq!(m["output_bytes"], body.len());
+        assert!(m["duration_ms"].as_f64().unwrap() >= 0.0);
+        assert!(!finished.contains('\n'));
+    }
+
+    #[test]
+    fn test_pretty_body_stays_pretty() {
+        let metrics = Metrics::start(Some(true), 2);
+        let body = ftl_json::to_string_pretty(&json!({ "a": 1 })).unwrap();
+        let finished = metrics.finish(body);
+        assert!(finished.starts_with("{\n  \"a\": 1,\n  \"metrics\": {"));
+    }
+
+    #[test]
+    fn test_non_object_body_is_wrapped() {
+        let metrics = Metrics::start(Some(true), 2);
+        let finished = metrics.finish("Error: Input data cannot be empty".to_string());
+        let value: Value = serde_json::from_str(&finished).unwrap();
+        assert_eq!(value["body"], "Error: Input data cannot be empty");
+        assert_eq!(metrics_of(&finished)["output_bytes"], 33);
+    }
+}
@@ -0,0 +1,643 @@
+//! Timeouts, retries and a circuit breaker for calls between components.
+//!
+//! Composite tools call sibling components over Spin's internal HTTP. Without
+//! a policy, a hung or crashing sibling stalls or fails the whole call with an
+//! opaque transport error. Composite tools send through an [`Outbound`]
+//! instead:
+//!
+//! ```ignore
+//! let outbound = Outbound::from_env();
+//! let body = outbound.post_json("vector-magnitude", &request_body, Some(id)).await?;
+//! ```
+//!
+//! Each attempt is limited to the policy's timeout. Failed attempts (transport
+//! errors, timeouts and 5xx responses) are retried with exponential backoff
+//! and full jitter. Timeouts and backoff wait on wasi:clocks timers registered
+//! with Spin's executor, so a pending call is woken by its response or its
+//! deadline rather than re-polled in a loop. When a sibling keeps failing, its
+//! circuit opens and calls fail fast with `circuit_open` until the cooldown
+//! has passed. Breaker state is kept in the default key-value store when the
+//! component has one, so it is shared across requests; otherwise it only
+//! lasts for the current request.
+//!
+//! Exhausted calls return an [`UpstreamFailure`], which serializes as a
+//! structured `upstream_failure` error. Wrappers pass logic errors through
+//! [`error_body`] so that error reaches the client unchanged.
+
+use ftl_kv_store::{KvStore, SpinStore};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::{Future, poll_fn};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub const TIMEOUT_MS_ENV: &str = "FTL_OUTBOUND_TIMEOUT_MS";
+pub const MAX_RETRIES_ENV: &str = "FTL_OUTBOUND_MAX_RETRIES";
+pub const BACKOFF_MS_ENV: &str = "FTL_OUTBOUND_BACKOFF_MS";
+pub const FAILURE_THRESHOLD_ENV: &str = "FTL_CIRCUIT_FAILURE_THRESHOLD";
+pub const COOLDOWN_MS_ENV: &str = "FTL_CIRCUIT_COOLDOWN_MS";
+
+/// Longest delay between two attempts
+const MAX_BACKOFF_MS: u64 = 2_000;
+
+const KEY_PREFIX: &str = "circuit";
+
+/// Timeout, retry and circuit breaker settings for outbound calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallPolicy {
+    /// Longest wait for a single attempt
+    pub timeout_ms: u64,
+    /// Attempts after the first one
+    pub max_retries: u32,
+    /// Upper bound of the first backoff; doubles with each retry
+    pub backoff_ms: u64,
+    /// Consecutive failed calls that open the circuit; 0 disables the breaker
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects calls before allowing a trial call
+    pub cooldown_ms: u64,
+}
+
+impl Default for CallPolicy {
+    fn default() -> Self {
+        CallPolicy {
+            timeout_ms: 5_000,
+            max_retries: 2,
+            backoff_ms: 50,
+            failure_threshold: 5,
+            cooldown_ms: 30_000,
+        }
+    }
+}
+
+impl CallPolicy {
+    /// Default policy, overridden by `FTL_OUTBOUND_TIMEOUT_MS`,
+    /// `FTL_OUTBOUND_MAX_RETRIES`, `FTL_OUTBOUND_BACKOFF_MS`,
+    /// `FTL_CIRCUIT_FAILURE_THRESHOLD` and `FTL_CIRCUIT_COOLDOWN_MS` when they
+    /// are set to a number
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = CallPolicy::default();
+        let read = |name: &str, default: u64| {
+            lookup(name)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        // Counts too large for a u32 fall back to the default rather than wrapping
+        let read_u32 = |name: &str, default: u32| {
+            u32::try_from(read(name, u64::from(default))).unwrap_or(default)
+        };
+        CallPolicy {
+            timeout_ms: read(TIMEOUT_MS_ENV, defaults.timeout_ms),
+            max_retries: read_u32(MAX_RETRIES_ENV, defaults.max_retries),
+            backoff_ms: read(BACKOFF_MS_ENV, defaults.backoff_ms),
+            failure_threshold: read_u32(FAILURE_THRESHOLD_ENV, defaults.failure_threshold),
+            cooldown_ms: read(COOLDOWN_MS_ENV, defaults.cooldown_ms),
+        }
+    }
+
+    /// Random delay before retry number `retry` (1-based), up to the capped
+    /// exponential bound
+    fn backoff(&self, retry: u32) -> Duration {
+        let bound = self
+            .backoff_ms
+            .saturating_mul(1 << (retry - 1).min(16))
+            .min(MAX_BACKOFF_MS);
+        Duration::from_millis((rand::random::<f64>() * bound as f64) as u64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// Every attempt failed to connect, errored or returned a 5xx status
+    Unavailable,
+    /// The last attempt ran out of time
+    Timeout,
+    /// The circuit is open, so no attempt was made
+    CircuitOpen,
+}
+
+/// Structured error for a sibling component that could not be reached
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpstreamFailure {
+    /// Always "upstream_failure"
+    pub error: &'static str,
+    /// Component that was called
+    pub upstream: String,
+    pub reason: FailureReason,
+    /// Attempts made, including the first
+    pub attempts: u32,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl UpstreamFailure {
+    fn new(upstream: &str, reason: FailureReason, attempts: u32, detail: &str) -> Self {
+        let message = match reason {
+            FailureReason::CircuitOpen => {
+                format!("{upstream} is failing repeatedly; calls are paused ({detail})")
+            }
+            _ => format!("{upstream} failed after {attempts} attempt(s): {detail}"),
+        };
+        UpstreamFailure {
+            error: "upstream_failure",
+            upstream: upstream.to_string(),
+            reason,
+            attempts,
+            message,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        ftl_json::to_string(self).unwrap_or_else(|_| self.message.clone())
+    }
+}
+
+impl fmt::Display for UpstreamFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<UpstreamFailure> for String {
+    /// Logic functions return `String` errors; this keeps the structured form
+    fn from(failure: UpstreamFailure) -> Self {
+        failure.to_json()
+    }
+}
+
+/// Response body for a logic error: an [`UpstreamFailure`] is returned as is,
+/// any other message as `Error: ...`
+pub fn error_body(error: &str) -> String {
+    let structured = serde_json::from_str::<serde_json::Value>(error)
+        .is_ok_and(|v| v.get("error").and_then(|e| e.as_str()) == Some("upstream_failure"));
+    if structured {
+        error.to_string()
+    } else {
+        format!("Error: {error}")
+    }
+}
+
+/// Circuit breaker record for one upstream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+struct BreakerState {
+    consecutive_failures: u32,
+    /// Unix time in milliseconds until which calls are rejected
+    open_until_ms: u64,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Sends calls to sibling components under a [`CallPolicy`]
+pub struct Outbound {
+    policy: CallPolicy,
+    store: Option<Box<dyn KvStore>>,
+    local: RefCell<HashMap<String, BreakerState>>,
+}
+
+impl Outbound {
+    pub fn new(policy: CallPolicy, store: Option<Box<dyn KvStore>>) -> Self {
+        Outbound {
+            policy,
+            store,
+            local: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Policy from the environment, with breaker state in the default
+    /// key-value store if the component has one
+    pub fn from_env() -> Self {
+        let store = SpinStore::open_default()
+            .ok()
+            .map(|store| Box::new(store) as Box<dyn KvStore>);
+        Self::new(CallPolicy::from_env(), store)
+    }
+
+    pub fn policy(&self) -> &CallPolicy {
+        &self.policy
+    }
+
+    /// POST a JSON body to `http://{upstream}.spin.internal` and return the
+    /// response body
+    pub async fn post_json(
+        &self,
+        upstream: &str,
+        body: &str,
+        correlation_id: Option<&str>,
+    ) -> Result<String, UpstreamFailure> {
+        use spin_sdk::http::{Method, Request, Response};
+
+        let uri = format!("http://{upstream}.spin.internal");
+        self.call(upstream, || {
+            let mut builder = Request::builder();
+            builder
+                .method(Method::Post)
+                .uri(uri.as_str())
+                .header("Content-Type", "application/json");
+            if let Some(id) = correlation_id {
+                builder.header(ftl_request_context::CORRELATION_ID_HEADER, id);
+            }
+            let request = builder.body(body.as_bytes().to_vec()).build();
+            async move {
+                let response: Response = spin_sdk::http::send(request)
+                    .await
+                    .map_err(|e| format!("{e:?}"))?;
+                let status = *response.status();
+                if status >= 500 {
+                    return Err(format!("status {status}"));
+                }
+                String::from_utf8(response.into_body())
+                    .map_err(|e| format!("response is not UTF-8: {e}"))
+            }
+        })
+        .await
+    }
+
+    /// Run `attempt` under the policy: each try is limited to the timeout and
+    /// failures are retried with backoff, unless the circuit is open
+    pub async fn call<T, F, Fut>(
+        &self,
+        upstream: &str,
+        mut attempt: F,
+    ) -> Result<T, UpstreamFailure>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, String>>,
+    {
+        let state = self.load(upstream);
+        let now = now_ms();
+        if state.open_until_ms > now {
+            let detail = format!("retry in {} ms", state.open_until_ms - now);
+            return Err(UpstreamFailure::new(
+                upstream,
+                FailureReason::CircuitOpen,
+                0,
+                &detail,
+            ));
+        }
+
+        let timeout = Duration::from_millis(self.policy.timeout_ms);
+        let mut attempts = 0;
+        let (reason, detail) = loop {
+            attempts += 1;
+            let (reason, detail) = match with_timeout(attempt(), timeout).await {
+                Some(Ok(value)) => {
+                    if state.consecutive_failures > 0 {
+                        self.save(upstream, BreakerState::default());
+                    }
+                    return Ok(value);
+                }
+                Some(Err(e)) => (FailureReason::Unavailable, e),
+                None => (
+                    FailureReason::Timeout,
+                    format!("no response within {} ms", self.policy.timeout_ms),
+                ),
+            };
+            if attempts > self.policy.max_retries {
+                break (reason, detail);
+            }
+            sleep(self.policy.backoff(attempts)).await;
+        };
+
+        self.record_failure(upstream, state);
+        Err(UpstreamFailure::new(upstream, reason, attempts, &detail))
+    }
+
+    fn record_failure(&self, upstream: &str, state: BreakerState) {
+        let consecutive_failures = state.consecutive_failures.saturating_add(1);
+        let threshold = self.policy.failure_threshold;
+        let open_until_ms = if threshold > 0 && consecutive_failures >= threshold {
+            now_ms() + self.policy.cooldown_ms
+        } else {
+            0
+        };
+        self.save(
+            upstream,
+            BreakerState {
+                consecutive_failures,
+                open_until_ms,
+            },
+        );
+    }
+
+    fn load(&self, upstream: &str) -> BreakerState {
+        let stored = self.store.as_ref().and_then(|store| {
+            let bytes = store.get(&breaker_key(upstream)).ok()??;
+            serde_json::from_slice(&bytes).ok()
+        });
+        stored
+            .or_else(|| self.local.borrow().get(upstream).copied())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, upstream: &str, state: BreakerState) {
+        self.local.borrow_mut().insert(upstream.to_string(), state);
+        // Best effort: a store failure leaves the breaker local to this request
+        if let Some(store) = &self.store {
+            let key = breaker_key(upstream);
+            let _ = if state == BreakerState::default() {
+                store.delete(&key)
+            } else {
+                serde_json::to_vec(&state)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| store.set(&key, &bytes))
+            };
+        }
+    }
+}
+
+fn breaker_key(upstream: &str) -> String {
+    format!("{KEY_PREFIX}:{upstream}")
+}
+
+/// Await `future` for at most `timeout`, returning `None` if it runs out
+async fn with_timeout<F: Future>(future: F, timeout: Duration) -> Option<F::Output> {
+    let mut deadline = Deadline::after(timeout);
+    let mut future = pin!(future);
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        if deadline.poll_passed(cx) {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+async fn sleep(duration: Duration) {
+    let mut deadline = Deadline::after(duration);
+    poll_fn(|cx| {
+        if deadline.poll_passed(cx) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// A point in time that wakes the waiting task once it has passed
+struct Deadline {
+    /// `None` when the duration is too long to represent, i.e. never
+    at: Option<Instant>,
+    armed: bool,
+}
+
+impl Deadline {
+    fn after(duration: Duration) -> Self {
+        Deadline {
+            at: Instant::now().checked_add(duration),
+            armed: false,
+        }
+    }
+
+    /// Whether the deadline has passed. If not, a timer is set (once) to wake
+    /// the task when it does, so a pending call is not re-polled in a loop.
+    fn poll_passed(&mut self, cx: &Context<'_>) -> bool {
+        let Some(at) = self.at else {
+            return false;
+        };
+        let remaining = at.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return true;
+        }
+        if !self.armed {
+            self.armed = true;
+            wake_after(remaining, cx.waker().clone());
+        }
+        false
+    }
+}
+
+/// Wake `waker` once `duration` has passed. Inside a component the waker is
+/// registered with Spin's executor on a wasi:clocks timer, which the executor
+/// waits on together with the outbound request.
+#[cfg(target_arch = "wasm32")]
+fn wake_after(duration: Duration, waker: Waker) {
+    let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+    let timer = wasi::clocks::monotonic_clock::subscribe_duration(nanos);
+    spin_executor::push_waker(timer, waker);
+}
+
+/// Wake `waker` once `duration` has passed, from a timer thread outside a
+/// component (native builds and tests)
+#[cfg(not(target_arch = "wasm32"))]
+fn wake_after(duration: Duration, waker: Waker) {
+    std::thread::spawn(move || {
+        std::thread::sleep(duration);
+        waker.wake();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftl_kv_store::MemoryStore;
+    use ftl_yield::{block_on, yield_now};
+    use std::cell::Cell;
+
+    fn policy() -> CallPolicy {
+        CallPolicy {
+            timeout_ms: 50,
+            max_retries: 2,
+            backoff_ms: 1,
+            failure_threshold: 2,
+            cooldown_ms: 60_000,
+        }
+    }
+
+    #[test]
+    fn test_policy_from_env() {
+        let policy = CallPolicy::from_lookup(|name| match name {
+            TIMEOUT_MS_ENV => Some(" 250 ".to_string()),
+            MAX_RETRIES_ENV => Some("0".to_string()),
+            COOLDOWN_MS_ENV => Some("soon".to_string()),
+            _ => None,
+        });
+        assert_eq!(policy.timeout_ms, 250);
+        assert_eq!(policy.max_retries, 0);
+        assert_eq!(policy.cooldown_ms, CallPolicy::default().cooldown_ms);
+
+        let overflow = CallPolicy::from_lookup(|name| match name {
+            MAX_RETRIES_ENV => Some("4294967298".to_string()),
+            FAILURE_THRESHOLD_ENV => Some("4294967296".to_string()),
+            _ => None,
+        });
+        assert_eq!(overflow.max_retries, CallPolicy::default().max_retries);
+        assert_eq!(
+            overflow.failure_threshold,
+            CallPolicy::default().failure_threshold
+        );
+        assert!(policy.backoff(20) <= Duration::from_millis(MAX_BACKOFF_MS));
+    }
+
+    #[test]
+    fn test_retries_until_success() {
+        let outbound = Outbound::new(policy(), None);
+        let calls = Cell::new(0);
+        let result = block_on(outbound.call("flaky", || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move {
+                if n < 3 {
+                    Err("connection refused".to_string())
+                } else {
+                    Ok(n)
+                }
+            }
+        }));
+        assert_eq!(result, Ok(3));
+        assert_eq!(outbound.load("flaky"), BreakerState::default());
+    }
+
+    #[test]
+    fn test_exhausted_retries_are_structured() {
+        let outbound = Outbound::new(policy(), None);
+        let result: Result<(), _> =
+            block_on(outbound.call("down", || async { Err("connection refused".to_string()) }));
+        let failure = result.unwrap_err();
+        assert_eq!(failure.reason, FailureReason::Unavailable);
+        assert_eq!(failure.attempts, 3);
+
+        let body: serde_json::Value = serde_json::from_str(&failure.to_json()).unwrap();
+        assert_eq!(body["error"], "upstream_failure");
+        assert_eq!(body["upstream"], "down");
+        assert_eq!(body["reason"], "unavailable");
+        assert_eq!(error_body(&failure.to_json()), failure.to_json());
+        assert_eq!(
+            error_body("Vector must be 3-dimensional"),
+            "Error: Vector must be 3-dimensional"
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut policy = policy();
+        policy.max_retries = 0;
+        let outbound = Outbound::new(policy, None);
+        let result: Result<(), _> = block_on(outbound.call("slow", || async {
+            // Never completes
+            loop {
+                yield_now().await;
+            }
+        }));
+        assert_eq!(result.unwrap_err().reason, FailureReason::Timeout);
+    }
+
+    #[test]
+    fn test_circuit_opens_and_is_shared_through_store() {
+        let store = std::rc::Rc::new(MemoryStore::new());
+        struct Shared(std::rc::Rc<MemoryStore>);
+        impl KvStore for Shared {
+            fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+                self.0.get(key)
+            }
+            fn set(&self, key: &str, value: &[u8]) -> Result<(), String> {
+                self.0.set(key, value)
+            }
+            fn delete(&self, key: &str) -> Result<(), String> {
+                self.0.delete(key)
+            }
+        }
+
+        let outbound = Outbound::new(policy(), Some(Box::new(Shared(store.clone()))));
+        let calls = Cell::new(0);
+        let failing = || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>("status 500".to_string()) }
+        };
+        for _ in 0..2 {
+            let failure = block_on(outbound.call("histogram", failing)).unwrap_err();
+            assert_eq!(failure.reason, FailureReason::Unavailable);
+        }
+        assert_eq!(calls.get(), 6);
+
+        // A new request sees the open circuit and does not call at all
+        let next = Outbound::new(policy(), Some(Box::new(Shared(store.clone()))));
+        let failure = block_on(next.call("histogram", failing)).unwrap_err();
+        assert_eq!(failure.reason, FailureReason::CircuitOpen);
+        assert_eq!(failure.attempts, 0);
+        assert_eq!(calls.get(), 6);
+
+        // Other upstreams are unaffected
+        assert_eq!(
+            block_on(next.call("test-normality", || async { Ok(1) })),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_trial_call_after_cooldown_closes_circuit() {
+        let mut policy = policy();
+        policy.cooldown_ms = 0;
+        policy.max_retries = 0;
+        let outbound = Outbound::new(policy, None);
+        for _ in 0..2 {
+            let _ = block_on(outbound.call("histogram", || async {
+                Err::<(), _>("refused".to_string())
+            }));
+        }
+        assert_eq!(outbound.load("histogram").consecutive_failures, 2);
+        assert_eq!(
+            block_on(outbound.call("histogram", || async { Ok(()) })),
+            Ok(())
+        );
+        assert_eq!(outbound.load("histogram"), BreakerState::default());
+    }
+
+    /// Run a future with a waker that unparks this thread, returning the
+    /// output and the number of polls. A future that re-wakes itself instead
+    /// of setting a timer is polled in a tight loop.
+    fn block_on_parked<F: Future>(future: F) -> (F::Output, usize) {
+        struct Unpark(std::thread::Thread);
+        impl std::task::Wake for Unpark {
+            fn wake(self: std::sync::Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+        let waker = Waker::from(std::sync::Arc::new(Unpark(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        let mut polls = 0;
+        loop {
+            polls += 1;
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return (output, polls);
+            }
+            std::thread::park();
+        }
+    }
+
+    #[test]
+    fn test_backoff_does_not_spin() {
+        let started = Instant::now();
+        let ((), polls) = block_on_parked(sleep(Duration::from_millis(20)));
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        // Pending once, then ready when the timer fires (allowing one spurious unpark)
+        assert!(polls <= 3, "{polls} polls");
+    }
+
+    #[test]
+    fn test_timeout_wakes_a_call_that_never_responds() {
+        let started = Instant::now();
+        let (output, polls) = block_on_parked(with_timeout(
+            std::future::pending::<()>(),
+            Duration::from_millis(20),
+        ));
+        assert_eq!(output, None);
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert!(polls <= 3, "{polls} polls");
+    }
+}
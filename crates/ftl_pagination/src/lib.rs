@@ -0,0 +1,201 @@
+//! Shared pagination convention for tools that can return very large lists.
+//!
+//! A paginated tool accepts optional `offset`, `limit` and `cursor` input
+//! fields and reports a [`PageInfo`] as its `page_info` result field, so every
+//! such tool reports the same `offset`, `limit`, `total`, `truncated` and
+//! `next_cursor` fields:
+//!
+//! ```ignore
+//! let page = PageRequest::from_input(input.offset, input.limit, input.cursor.as_deref())?;
+//! let (matches, page_info) = page.apply(all_matches);
+//! ```
+//!
+//! When `truncated` is true, the client fetches the next page by calling again
+//! with `cursor` set to `next_cursor` (or with `offset` set to
+//! `offset + limit`). Omitting `limit` returns everything from `offset`
+//! onwards. Cursors are opaque to clients.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Position of the returned page within the full result list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PageInfo {
+    /// Index of the first returned item within the full result list
+    pub offset: usize,
+    /// Maximum number of items requested for this page (absent if unlimited)
+    pub limit: Option<usize>,
+    /// Total number of items in the full result list
+    pub total: usize,
+    /// Whether more items remain after this page
+    pub truncated: bool,
+    /// Cursor for the next page (present when `truncated`)
+    pub next_cursor: Option<String>,
+}
+
+/// Validated `offset` / `limit` pair from a tool input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PageRequest {
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+impl PageRequest {
+    pub fn new(offset: Option<usize>, limit: Option<usize>) -> Result<Self, String> {
+        if limit == Some(0) {
+            return Err("limit must be at least 1".to_string());
+        }
+        Ok(PageRequest {
+            offset: offset.unwrap_or(0),
+            limit,
+        })
+    }
+
+    /// Page from a tool's `offset`, `limit` and `cursor` inputs. A cursor
+    /// carries the offset and limit of the page after the one it came from;
+    /// an explicit `limit` overrides the cursor's.
+    pub fn from_input(
+        offset: Option<usize>,
+        limit: Option<usize>,
+        cursor: Option<&str>,
+    ) -> Result<Self, String> {
+        let Some(cursor) = cursor else {
+            return PageRequest::new(offset, limit);
+        };
+        if offset.is_some() {
+            return Err("Give either offset or cursor, not both".to_string());
+        }
+        let (cursor_offset, cursor_limit) = cursor
+            .split_once(':')
+            .and_then(|(offset, limit)| Some((offset.parse().ok()?, limit.parse().ok()?)))
+            .ok_or_else(|| format!("Invalid cursor '{cursor}'"))?;
+        PageRequest::new(Some(cursor_offset), limit.or(Some(cursor_limit)))
+    }
+
+    /// Index one past the last item of the page
+    fn end(&self) -> usize {
+        match self.limit {
+            Some(limit) => self.offset.saturating_add(limit),
+            None => usize::MAX,
+        }
+    }
+
+    /// Whether the item at `index` of the full list belongs to this page.
+    /// Lets streaming tools keep only the requested page in memory.
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.offset && index < self.end()
+    }
+
+    /// Page metadata for a full list of `total` items
+    pub fn info(&self, total: usize) -> PageInfo {
+        let truncated = self.end() < total;
+        let next_cursor = match self.limit {
+            Some(limit) if truncated => Some(format!("{}:{limit}", self.end())),
+            _ => None,
+        };
+        PageInfo {
+            offset: self.offset,
+            limit: self.limit,
+            total,
+            truncated,
+            next_cursor,
+        }
+    }
+
+    /// Cut this page out of the full list
+    pub fn apply<T>(&self, items: Vec<T>) -> (Vec<T>, PageInfo) {
+        let info = self.info(items.len());
+        let page = items
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        (page, info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_return_everything() {
+        let page = PageRequest::new(None, None).unwrap();
+        let (items, info) = page.apply(vec![1, 2, 3]);
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(
+            info,
+            PageInfo {
+                offset: 0,
+                limit: None,
+                total: 3,
+                truncated: false,
+                next_cursor: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_pages_walk_the_list() {
+        let items: Vec<u32> = (0..5).collect();
+        let first = PageRequest::new(None, Some(2)).unwrap();
+        let (page, info) = first.apply(items.clone());
+        assert_eq!(page, vec![0, 1]);
+        assert!(info.truncated);
+
+        let last = PageRequest::new(Some(4), Some(2)).unwrap();
+        let (page, info) = last.apply(items);
+        assert_eq!(page, vec![4]);
+        assert_eq!(info.total, 5);
+        assert!(!info.truncated);
+    }
+
+    #[test]
+    fn test_offset_past_end_is_empty() {
+        let page = PageRequest::new(Some(10), Some(5)).unwrap();
+        let (items, info) = page.apply(vec!['a', 'b']);
+        assert!(items.is_empty());
+        assert!(!info.truncated);
+    }
+
+    #[test]
+    fn test_contains_matches_apply() {
+        let page = PageRequest::new(Some(1), Some(2)).unwrap();
+        let kept: Vec<usize> = (0..5).filter(|&i| page.contains(i)).collect();
+        assert_eq!(kept, page.apply((0..5).collect()).0);
+    }
+
+    #[test]
+    fn test_zero_limit_rejected() {
+        assert!(PageRequest::new(None, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_cursor_walks_the_list() {
+        let items: Vec<u32> = (0..5).collect();
+        let first = PageRequest::from_input(None, Some(2), None).unwrap();
+        let (_, info) = first.apply(items.clone());
+        let cursor = info.next_cursor.unwrap();
+
+        let second = PageRequest::from_input(None, None, Some(&cursor)).unwrap();
+        assert_eq!(second, PageRequest::new(Some(2), Some(2)).unwrap());
+        let (page, info) = second.apply(items.clone());
+        assert_eq!(page, vec![2, 3]);
+
+        let third = PageRequest::from_input(None, None, info.next_cursor.as_deref()).unwrap();
+        let (page, info) = third.apply(items);
+        assert_eq!(page, vec![4]);
+        assert!(!info.truncated);
+        assert!(info.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_cursor_input_errors() {
+        assert!(PageRequest::from_input(Some(1), None, Some("2:2")).is_err());
+        assert!(PageRequest::from_input(None, None, Some("next")).is_err());
+        assert_eq!(
+            PageRequest::from_input(None, Some(10), Some("4:2")).unwrap(),
+            PageRequest::new(Some(4), Some(10)).unwrap()
+        );
+    }
+}
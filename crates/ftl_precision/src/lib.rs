@@ -0,0 +1,233 @@
+//! Shared numeric precision and rounding policy for tool results.
+//!
+//! Floating point results such as `0.1 + 0.2 = 0.30000000000000004` are exact
+//! as far as `f64` goes but noisy for clients. Every numeric tool (basic math,
+//! math3d, geospatial, statistics, and the visualization and color tools that
+//! report measurements) accepts an optional [`Precision`] in its input and
+//! passes it to `ftl_response::to_json_with_precision`, which rounds every
+//! floating point number in the result the same way. Integers and non-numeric
+//! values are left untouched, and omitting `precision` keeps full `f64`
+//! precision. Tools that echo caller records back, such as `dedupe_records`,
+//! never round them.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+/// Largest number of significant digits that is meaningful for an `f64`
+pub const MAX_SIGNIFICANT_DIGITS: u32 = 17;
+/// Largest number of decimal places accepted
+pub const MAX_DECIMAL_PLACES: u32 = 15;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round to nearest, ties to even (banker's rounding)
+    #[default]
+    HalfEven,
+    /// Round to nearest, ties away from zero
+    HalfUp,
+    /// Round toward zero (truncate)
+    Down,
+    /// Round toward negative infinity
+    Floor,
+    /// Round toward positive infinity
+    Ceiling,
+}
+
+impl RoundingMode {
+    fn round(self, x: f64) -> f64 {
+        match self {
+            RoundingMode::HalfEven => x.round_ties_even(),
+            RoundingMode::HalfUp => x.round(),
+            RoundingMode::Down => x.trunc(),
+            RoundingMode::Floor => x.floor(),
+            RoundingMode::Ceiling => x.ceil(),
+        }
+    }
+}
+
+/// How numeric results are rounded. Set exactly one of `significant_digits`
+/// and `decimal_places`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Precision {
+    /// Number of significant digits to keep (1-17)
+    pub significant_digits: Option<u32>,
+    /// Number of digits to keep after the decimal point (0-15)
+    pub decimal_places: Option<u32>,
+    /// Rounding mode: "half_even" (default), "half_up", "down", "floor" or "ceiling"
+    #[serde(default)]
+    pub rounding: RoundingMode,
+}
+
+impl Precision {
+    pub fn significant_digits(digits: u32) -> Self {
+        Precision {
+            significant_digits: Some(digits),
+            decimal_places: None,
+            rounding: RoundingMode::default(),
+        }
+    }
+
+    pub fn decimal_places(places: u32) -> Self {
+        Precision {
+            significant_digits: None,
+            decimal_places: Some(places),
+            rounding: RoundingMode::default(),
+        }
+    }
+
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.significant_digits, self.decimal_places) {
+            (Some(_), Some(_)) => Err(
+                "precision takes either significant_digits or decimal_places, not both".to_string(),
+            ),
+            (None, None) => {
+                Err("precision requires significant_digits or decimal_places".to_string())
+            }
+            (Some(digits), None) if digits == 0 || digits > MAX_SIGNIFICANT_DIGITS => Err(format!(
+                "significant_digits must be between 1 and {MAX_SIGNIFICANT_DIGITS}"
+            )),
+            (None, Some(places)) if places > MAX_DECIMAL_PLACES => Err(format!(
+                "decimal_places must be between 0 and {MAX_DECIMAL_PLACES}"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Round a single value. Values that cannot be scaled without overflow
+    /// (and NaN or infinities) are returned unchanged.
+    pub fn round(&self, x: f64) -> f64 {
+        if !x.is_finite() || x == 0.0 {
+            return x;
+        }
+        // Round at 10^-exponent, i.e. keep `exponent` digits after the point
+        let exponent = match (self.significant_digits, self.decimal_places) {
+            (Some(digits), _) => digits as i32 - 1 - x.abs().log10().floor() as i32,
+            (None, Some(places)) => places as i32,
+            (None, None) => return x,
+        };
+
+        let scale = 10f64.powi(exponent.abs());
+        let rounded = if exponent >= 0 {
+            let scaled = x * scale;
+            if !scaled.is_finite() {
+                return x;
+            }
+            self.rounding.round(scaled) / scale
+        } else {
+            self.rounding.round(x / scale) * scale
+        };
+        // Avoid reporting -0 for small negative values
+        if rounded == 0.0 { 0.0 } else { rounded }
+    }
+
+    /// Round every floating point number inside a JSON value
+    pub fn apply(&self, value: &mut Value) {
+        match value {
+            Value::Number(n) if n.is_f64() => {
+                if let Some(rounded) = n.as_f64().and_then(|x| Number::from_f64(self.round(x))) {
+                    *n = rounded;
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.apply(item)),
+            Value::Object(map) => map.values_mut().for_each(|item| self.apply(item)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decimal_places_remove_float_noise() {
+        let p = Precision::decimal_places(10);
+        assert_eq!(p.round(0.1 + 0.2), 0.3);
+        assert_eq!(Precision::decimal_places(2).round(1.23456), 1.23);
+        assert_eq!(Precision::decimal_places(0).round(-2.5), -2.0);
+    }
+
+    #[test]
+    fn test_significant_digits() {
+        let p = Precision::significant_digits(3);
+        assert_eq!(p.round(123456.0), 123000.0);
+        assert_eq!(p.round(0.000123456), 0.000123);
+        assert_eq!(p.round(-9.876), -9.88);
+        assert_eq!(p.round(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let base = Precision::decimal_places(1);
+        assert_eq!(base.round(0.25), 0.2);
+        assert_eq!(base.with_rounding(RoundingMode::HalfUp).round(0.25), 0.3);
+        assert_eq!(base.with_rounding(RoundingMode::Down).round(-0.29), -0.2);
+        assert_eq!(base.with_rounding(RoundingMode::Floor).round(-0.21), -0.3);
+        assert_eq!(base.with_rounding(RoundingMode::Ceiling).round(0.21), 0.3);
+        assert_eq!(
+            base.with_rounding(RoundingMode::Down)
+                .round(-0.01)
+                .to_bits(),
+            0.0f64.to_bits()
+        );
+    }
+
+    #[test]
+    fn test_extreme_values_unchanged() {
+        let p = Precision::decimal_places(15);
+        assert_eq!(p.round(f64::MAX), f64::MAX);
+        assert_eq!(
+            Precision::significant_digits(17).round(f64::MIN_POSITIVE),
+            f64::MIN_POSITIVE
+        );
+        assert!(p.round(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_apply_walks_json() {
+        let mut value = json!({
+            "result": 0.30000000000000004,
+            "count": 3,
+            "inputs": [0.1, 0.2],
+            "nested": { "mean": 1.23456, "label": "x" }
+        });
+        Precision::decimal_places(2).apply(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "result": 0.3,
+                "count": 3,
+                "inputs": [0.1, 0.2],
+                "nested": { "mean": 1.23, "label": "x" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Precision::decimal_places(2).validate().is_ok());
+        assert!(Precision::significant_digits(0).validate().is_err());
+        assert!(Precision::significant_digits(18).validate().is_err());
+        assert!(Precision::decimal_places(16).validate().is_err());
+        let both = Precision {
+            significant_digits: Some(3),
+            decimal_places: Some(2),
+            rounding: RoundingMode::HalfEven,
+        };
+        assert!(both.validate().is_err());
+        let neither = Precision {
+            significant_digits: None,
+            decimal_places: None,
+            rounding: RoundingMode::HalfEven,
+        };
+        assert!(neither.validate().is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! Structured input validation for dry-run (`validate_only`) tool calls.
+//!
+//! A tool that supports pre-flight checks exposes a validation function in its
+//! logic module that collects every problem with the input into a
+//! [`ValidationReport`], instead of stopping at the first one. The logic
+//! function runs the same checks via [`ValidationReport::into_result`], so a
+//! dry run and a real call always agree. When the input has
+//! `validate_only: true`, the wrapper returns the report and skips the
+//! computation:
+//!
+//! ```ignore
+//! let report = logic::validate_regression_input(&logic_input);
+//! if validate_only {
+//!     return ToolResponse::text(ftl_json::to_string(&report).unwrap());
+//! }
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A single problem found in a tool input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ValidationIssue {
+    /// Input field the issue refers to, e.g. "coordinates[2].lat"
+    pub field: String,
+    /// Human-readable description of the issue
+    pub message: String,
+}
+
+/// Result of checking a tool input without running the computation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ValidationReport {
+    /// Whether the input would be accepted by the tool
+    pub valid: bool,
+    /// Problems that would make the tool return an error
+    pub errors: Vec<ValidationIssue>,
+    /// Accepted input that may not mean what the caller expects
+    pub warnings: Vec<ValidationIssue>,
+}
+
+impl Default for ValidationReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValidationReport {
+    pub fn new() -> Self {
+        ValidationReport {
+            valid: true,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn error(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.valid = false;
+        self.errors.push(ValidationIssue {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn warning(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.warnings.push(ValidationIssue {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    /// The first error message, for logic functions that stop at the first problem
+    pub fn into_result(self) -> Result<(), String> {
+        match self.errors.into_iter().next() {
+            Some(issue) => Err(issue.message),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_report_is_valid() {
+        let report = ValidationReport::new();
+        assert!(report.valid);
+        assert_eq!(report.into_result(), Ok(()));
+    }
+
+    #[test]
+    fn test_warnings_keep_report_valid() {
+        let mut report = ValidationReport::new();
+        report.warning("coordinates", "Ring is not closed");
+        assert!(report.valid);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_first_error_is_returned() {
+        let mut report = ValidationReport::new();
+        report.error("x", "first");
+        report.error("y", "second");
+        assert!(!report.valid);
+        assert_eq!(report.errors[1].field, "y");
+        assert_eq!(report.into_result(), Err("first".to_string()));
+    }
+}
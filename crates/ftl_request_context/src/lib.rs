@@ -0,0 +1,150 @@
+//! Correlation ids and per-step timing for composite tools.
+//!
+//! A composite tool creates a [`RequestContext`] from the optional
+//! `correlation_id` in its input (a new id is generated when absent), sends the
+//! id to every downstream tool in the [`CORRELATION_ID_HEADER`] header, and wraps
+//! each downstream call in [`RequestContext::time`]. The [`ResponseMetadata`]
+//! returned by [`RequestContext::finish`] goes into the tool response so a
+//! multi-tool call chain can be traced and profiled from a single result.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Instant;
+
+/// Header carrying the correlation id on calls between components
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
+/// Longest accepted caller-supplied correlation id
+const MAX_CORRELATION_ID_LEN: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct StepTiming {
+    /// Name of the downstream tool or step
+    pub step: String,
+    /// Wall-clock time spent in the step, in milliseconds
+    pub duration_ms: f64,
+    /// Whether the step completed without error
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ResponseMetadata {
+    /// Correlation id shared by every call in this chain
+    pub correlation_id: String,
+    /// Total time spent handling the request, in milliseconds
+    pub total_duration_ms: f64,
+    /// Downstream steps in the order they ran
+    pub steps: Vec<StepTiming>,
+}
+
+pub struct RequestContext {
+    correlation_id: String,
+    started: Instant,
+    steps: Vec<StepTiming>,
+}
+
+/// Use the caller's id if it is safe to forward as a header value, otherwise
+/// generate a fresh one
+fn normalize_correlation_id(id: Option<String>) -> String {
+    match id.map(|id| id.trim().to_string()) {
+        Some(id)
+            if !id.is_empty()
+                && id.len() <= MAX_CORRELATION_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')) =>
+        {
+            id
+        }
+        _ => uuid::Uuid::new_v4().to_string(),
+    }
+}
+
+impl RequestContext {
+    pub fn new(correlation_id: Option<String>) -> Self {
+        RequestContext {
+            correlation_id: normalize_correlation_id(correlation_id),
+            started: Instant::now(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn correlation_id(&self) -> &str {
+        &self.correlation_id
+    }
+
+    /// Await a downstream call and record how long it took and whether it succeeded
+    pub async fn time<T, E, F>(&mut self, step: &str, call: F) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        let started = Instant::now();
+        let result = call.await;
+        self.steps.push(StepTiming {
+            step: step.to_string(),
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            success: result.is_ok(),
+        });
+        result
+    }
+
+    /// Steps recorded so far
+    pub fn steps(&self) -> &[StepTiming] {
+        &self.steps
+    }
+
+    pub fn finish(self) -> ResponseMetadata {
+        ResponseMetadata {
+            correlation_id: self.correlation_id,
+            total_duration_ms: self.started.elapsed().as_secs_f64() * 1000.0,
+            steps: self.steps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftl_yield::block_on;
+
+    #[test]
+    fn test_caller_id_is_kept() {
+        let ctx = RequestContext::new(Some(" trace-42.a:b_c ".to_string()));
+        assert_eq!(ctx.correlation_id(), "trace-42.a:b_c");
+    }
+
+    #[test]
+    fn test_missing_or_unsafe_id_is_replaced() {
+        let generated = RequestContext::new(None);
+        assert_eq!(generated.correlation_id().len(), 36);
+
+        let injected = RequestContext::new(Some("abc\r\nX-Evil: 1".to_string()));
+        assert_ne!(injected.correlation_id(), "abc\r\nX-Evil: 1");
+        assert_eq!(injected.correlation_id().len(), 36);
+
+        let long = RequestContext::new(Some("a".repeat(200)));
+        assert_eq!(long.correlation_id().len(), 36);
+    }
+
+    #[test]
+    fn test_steps_are_recorded_in_order() {
+        let mut ctx = RequestContext::new(Some("chain".to_string()));
+        let first: Result<u32, String> = block_on(ctx.time("first", async { Ok(1) }));
+        let second: Result<u32, String> =
+            block_on(ctx.time("second", async { Err("boom".to_string()) }));
+        assert_eq!(first, Ok(1));
+        assert!(second.is_err());
+
+        let metadata = ctx.finish();
+        assert_eq!(metadata.correlation_id, "chain");
+        let steps: Vec<(&str, bool)> = metadata
+            .steps
+            .iter()
+            .map(|s| (s.step.as_str(), s.success))
+            .collect();
+        assert_eq!(steps, vec![("first", true), ("second", false)]);
+        assert!(metadata.steps.iter().all(|s| s.duration_ms >= 0.0));
+        assert!(metadata.total_duration_ms >= metadata.steps[0].duration_ms);
+    }
+}
@@ -0,0 +1,271 @@
+//! Response building with optional field selection.
+//!
+//! Tools return every computed field, but an LLM client often needs only one
+//! or two of them. A tool opts in by accepting `fields: Option<Vec<String>>`
+//! in its input and serializing its result through [`to_json`]:
+//!
+//! ```ignore
+//! let fields = input.fields.clone();
+//! // ... compute `result` ...
+//! match ftl_response::to_json(&result, fields.as_deref()) {
+//!     Ok(json) => ToolResponse::text(json),
+//!     Err(e) => ToolResponse::text(format!("Error: {e}")),
+//! }
+//! ```
+//!
+//! Each entry in `fields` names a top-level field of the result, or a dotted
+//! path into nested objects such as `"stats.mean"`. A path that crosses an array
+//! selects the field from every element, so `"points.distance"` keeps only
+//! `distance` in each item of `points`. Omitting `fields`, or passing an empty
+//! list, returns the full result.
+//!
+//! Numeric tools that also accept a `precision` option use
+//! [`to_json_with_precision`], which rounds floating point numbers in the
+//! selected fields according to the shared `ftl_precision` policy. The
+//! `output_precision` option is shorthand for
+//! `precision: {"significant_digits": n}`; [`rounding`] turns the two inputs
+//! into the single policy to serialize with:
+//!
+//! ```ignore
+//! let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+//!     Ok(precision) => precision,
+//!     Err(e) => return ToolResponse::text(format!("Error: {e}")),
+//! };
+//! // ... compute `result` ...
+//! match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+//!     Ok(json) => ToolResponse::text(json),
+//!     Err(e) => ToolResponse::text(format!("Error: {e}")),
+//! }
+//! ```
+
+use ftl_precision::{MAX_SIGNIFICANT_DIGITS, Precision};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Serialize `result` as JSON, keeping only the requested fields
+pub fn to_json<T: Serialize>(result: &T, fields: Option<&[String]>) -> Result<String, String> {
+    to_json_with_precision(result, fields, None)
+}
+
+/// Serialize `result` as JSON, keeping only the requested fields and rounding
+/// floating point numbers to `precision`
+pub fn to_json_with_precision<T: Serialize>(
+    result: &T,
+    fields: Option<&[String]>,
+    precision: Option<&Precision>,
+) -> Result<String, String> {
+    let value =
+        serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {e}"))?;
+    let mut value = match fields {
+        Some(fields) if !fields.is_empty() => project(&value, fields)?,
+        _ => value,
+    };
+    if let Some(precision) = precision {
+        precision.validate()?;
+        precision.apply(&mut value);
+    }
+    ftl_json::to_string(&value).map_err(|e| format!("Failed to serialize result: {e}"))
+}
+
+/// Rounding for a tool's optional `precision` and `output_precision` inputs.
+/// `output_precision` is the number of significant figures to keep (1-17), so
+/// a call may set one or the other but not both.
+pub fn rounding(
+    precision: Option<&Precision>,
+    output_precision: Option<u32>,
+) -> Result<Option<Precision>, String> {
+    match (precision, output_precision) {
+        (Some(_), Some(_)) => Err("precision and output_precision cannot both be set".to_string()),
+        (Some(precision), None) => {
+            precision.validate()?;
+            Ok(Some(*precision))
+        }
+        (None, Some(figures)) if (1..=MAX_SIGNIFICANT_DIGITS).contains(&figures) => {
+            Ok(Some(Precision::significant_digits(figures)))
+        }
+        (None, Some(figures)) => Err(format!(
+            "output_precision must be between 1 and {MAX_SIGNIFICANT_DIGITS}, got {figures}"
+        )),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Copy of `value` containing only the given field paths
+pub fn project(value: &Value, fields: &[String]) -> Result<Value, String> {
+    if !value.is_object() {
+        return Err("Field selection is only supported for object results".to_string());
+    }
+    let mut out = Value::Object(Map::new());
+    for field in fields {
+        let path: Vec<&str> = field.split('.').collect();
+        if path.iter().any(|segment| segment.is_empty()) {
+            return Err(format!("Invalid field path '{field}'"));
+        }
+        select(value, &path, &mut out, field)?;
+    }
+    Ok(out)
+}
+
+/// Copy the value at `path` from `source` into the same position in `target`
+fn select(source: &Value, path: &[&str], target: &mut Value, field: &str) -> Result<(), String> {
+    let Some((name, rest)) = path.split_first() else {
+        *target = source.clone();
+        return Ok(());
+    };
+
+    match source {
+        Value::Object(map) => {
+            let child = map.get(*name).ok_or_else(|| {
+                let available: Vec<&str> = map.keys().map(String::as_str).collect();
+                format!(
+                    "Unknown field '{field}'. Available fields: {}",
+                    available.join(", ")
+                )
+            })?;
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            let target_map = target.as_object_mut().expect("target is an object");
+            let slot = target_map.entry(name.to_string()).or_insert_with(|| {
+                if rest.is_empty() {
+                    Value::Null
+                } else {
+                    Value::Object(Map::new())
+                }
+            });
+            select(child, rest, slot, field)
+        }
+        Value::Array(items) => {
+            if !target.is_array() {
+                *target = Value::Array(vec![Value::Object(Map::new()); items.len()]);
+            }
+            let target_items = target.as_array_mut().expect("target is an array");
+            for (item, slot) in items.iter().zip(target_items.iter_mut()) {
+                select(item, path, slot, field)?;
+            }
+            Ok(())
+        }
+        _ => Err(format!(
+            "Field '{field}' goes inside a value that is not an object"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_top_level_fields() {
+        let result = json!({ "hypotenuse": 5.0, "leg_a": 3.0, "leg_b": 4.0 });
+        let projected = project(&result, &fields(&["hypotenuse"])).unwrap();
+        assert_eq!(projected, json!({ "hypotenuse": 5.0 }));
+    }
+
+    #[test]
+    fn test_nested_and_array_paths() {
+        let result = json!({
+            "stats": { "mean": 2.0, "median": 2.0 },
+            "points": [
+                { "id": "a", "distance": 1.0 },
+                { "id": "b", "distance": 2.0 }
+            ],
+            "count": 2
+        });
+        let projected = project(
+            &result,
+            &fields(&["stats.mean", "points.id", "points.distance"]),
+        )
+        .unwrap();
+        assert_eq!(
+            projected,
+            json!({
+                "stats": { "mean": 2.0 },
+                "points": [
+                    { "id": "a", "distance": 1.0 },
+                    { "id": "b", "distance": 2.0 }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_whole_field_wins_over_subpath() {
+        let result = json!({ "stats": { "mean": 1.0, "median": 2.0 } });
+        let projected = project(&result, &fields(&["stats", "stats.mean"])).unwrap();
+        assert_eq!(projected, result);
+    }
+
+    #[test]
+    fn test_unknown_field_lists_available() {
+        let result = json!({ "hypotenuse": 5.0, "leg_a": 3.0 });
+        let err = project(&result, &fields(&["hypotenus"])).unwrap_err();
+        assert!(err.contains("hypotenus"));
+        assert!(err.contains("hypotenuse, leg_a"));
+        assert!(project(&result, &fields(&["hypotenuse.x"])).is_err());
+        assert!(project(&result, &fields(&["leg_a."])).is_err());
+    }
+
+    #[test]
+    fn test_to_json_without_fields_is_unchanged() {
+        let result = json!({ "a": 1, "b": [1, 2] });
+        assert_eq!(to_json(&result, None).unwrap(), r#"{"a":1,"b":[1,2]}"#);
+        assert_eq!(
+            to_json(&result, Some(&[][..])).unwrap(),
+            r#"{"a":1,"b":[1,2]}"#
+        );
+        assert_eq!(
+            to_json(&result, Some(fields(&["a"]).as_slice())).unwrap(),
+            r#"{"a":1}"#
+        );
+    }
+
+    #[test]
+    fn test_precision_applies_to_selected_fields() {
+        let result = json!({ "inputs": [0.1, 0.2], "result": 0.30000000000000004 });
+        let precision = Precision::decimal_places(10);
+        assert_eq!(
+            to_json_with_precision(&result, None, Some(&precision)).unwrap(),
+            r#"{"inputs":[0.1,0.2],"result":0.3}"#
+        );
+        assert_eq!(
+            to_json_with_precision(
+                &result,
+                Some(fields(&["result"]).as_slice()),
+                Some(&precision)
+            )
+            .unwrap(),
+            r#"{"result":0.3}"#
+        );
+        let invalid = Precision::significant_digits(0);
+        assert!(to_json_with_precision(&result, None, Some(&invalid)).is_err());
+    }
+
+    #[test]
+    fn test_output_precision_rounds_to_significant_figures() {
+        let result = json!({ "result": 2.0 / 3.0, "count": 3 });
+        let precision = rounding(None, Some(4)).unwrap();
+        assert_eq!(precision, Some(Precision::significant_digits(4)));
+        assert_eq!(
+            to_json_with_precision(&result, None, precision.as_ref()).unwrap(),
+            r#"{"count":3,"result":0.6667}"#
+        );
+    }
+
+    #[test]
+    fn test_rounding_takes_one_of_precision_and_output_precision() {
+        let precision = Precision::decimal_places(2);
+        assert_eq!(rounding(Some(&precision), None).unwrap(), Some(precision));
+        assert_eq!(rounding(None, None).unwrap(), None);
+        assert!(rounding(Some(&precision), Some(3)).is_err());
+        assert!(rounding(None, Some(0)).is_err());
+        assert!(rounding(None, Some(MAX_SIGNIFICANT_DIGITS)).is_ok());
+        assert!(rounding(None, Some(MAX_SIGNIFICANT_DIGITS + 1)).is_err());
+        assert!(rounding(Some(&Precision::significant_digits(0)), None).is_err());
+    }
+}
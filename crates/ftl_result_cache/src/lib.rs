@@ -0,0 +1,335 @@
+//! Result cache for pure tools, backed by the Spin key-value store.
+//!
+//! Agents often repeat identical tool calls. A pure tool (output depends only on
+//! its input) can opt in by wrapping its logic call:
+//!
+//! ```ignore
+//! let result = ftl_result_cache::cached("pearson_correlation", &logic_input, || {
+//!     logic::calculate_pearson_correlation(logic_input.clone())
+//! });
+//! ```
+//!
+//! Entries are keyed on a SHA-256 hash of the tool name and the input as
+//! serialized by `ftl_json` (object keys sorted), so field order in the request
+//! does not matter. Only successful results are cached.
+//!
+//! Caching is off unless a component sets a TTL: put `FTL_CACHE_TTL_SECONDS`
+//! in its environment to keep entries for that many seconds. Unset, `0` or
+//! unparsable values leave it disabled. The cache is best effort: if the
+//! component has no key-value store, or the store fails, the logic simply runs.
+
+use ftl_kv_store::{KvStore, SpinStore};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub use ftl_kv_store::MemoryStore;
+
+/// Environment variable holding the cache TTL in seconds
+pub const TTL_ENV_VAR: &str = "FTL_CACHE_TTL_SECONDS";
+/// TTL used when the variable is unset, leaving caching disabled
+pub const DEFAULT_TTL_SECONDS: u64 = 0;
+
+const KEY_PREFIX: &str = "result_cache";
+
+/// Cache settings for a component
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheConfig {
+    /// How long entries stay valid; 0 disables the cache
+    pub ttl_seconds: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            ttl_seconds: DEFAULT_TTL_SECONDS,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Read the TTL from `FTL_CACHE_TTL_SECONDS`, falling back to the default
+    /// (disabled) when it is unset or not a number
+    pub fn from_env() -> Self {
+        Self::from_ttl_setting(std::env::var(TTL_ENV_VAR).ok().as_deref())
+    }
+
+    fn from_ttl_setting(setting: Option<&str>) -> Self {
+        let ttl_seconds = setting
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(DEFAULT_TTL_SECONDS);
+        CacheConfig { ttl_seconds }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.ttl_seconds > 0
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    expires_at: u64,
+    value: Value,
+}
+
+/// Store key for a (tool name, input) pair
+pub fn cache_key<I: Serialize>(tool: &str, input: &I) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    hasher.update(tool.as_bytes());
+    hasher.update([0u8]);
+    let canonical =
+        ftl_json::to_string(input).map_err(|e| format!("Failed to serialize input: {e}"))?;
+    hasher.update(canonical.as_bytes());
+    let digest = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    Ok(format!("{KEY_PREFIX}:{tool}:{digest}"))
+}
+
+fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Cached value for `key`, dropping the entry if it has expired or no longer
+/// matches the output type
+fn lookup<S: KvStore, O: DeserializeOwned>(store: &S, key: &str, now: u64) -> Option<O> {
+    let bytes = store.get(key).ok()??;
+    if let Ok(entry) = serde_json::from_slice::<CacheEntry>(&bytes)
+        && entry.expires_at > now
+        && let Ok(value) = serde_json::from_value(entry.value)
+    {
+        return Some(value);
+    }
+    let _ = store.delete(key);
+    None
+}
+
+fn remember<S: KvStore, O: Serialize>(store: &S, key: &str, expires_at: u64, result: &O) {
+    if let Ok(value) = serde_json::to_value(result) {
+        let entry = CacheEntry { expires_at, value };
+        if let Ok(bytes) = serde_json::to_vec(&entry) {
+            let _ = store.set(key, &bytes);
+        }
+    }
+}
+
+/// Run `compute` through the cache using the component's default store and TTL
+pub fn cached<I, O, F>(tool: &str, input: &I, compute: F) -> Result<O, String>
+where
+    I: Serialize,
+    O: Serialize + DeserializeOwned,
+    F: FnOnce() -> Result<O, String>,
+{
+    let config = CacheConfig::from_env();
+    if !config.enabled() {
+        return compute();
+    }
+    match SpinStore::open_default() {
+        Ok(store) => cached_in(&store, config, now_seconds(), tool, input, compute),
+        Err(_) => compute(),
+    }
+}
+
+/// Run `compute` through the cache held in `store`, treating `now` as the current
+/// unix time in seconds
+pub fn cached_in<S, I, O, F>(
+    store: &S,
+    config: CacheConfig,
+    now: u64,
+    tool: &str,
+    input: &I,
+    compute: F,
+) -> Result<O, String>
+where
+    S: KvStore,
+    I: Serialize,
+    O: Serialize + DeserializeOwned,
+    F: FnOnce() -> Result<O, String>,
+{
+    if !config.enabled() {
+        return compute();
+    }
+    let Ok(key) = cache_key(tool, input) else {
+        return compute();
+    };
+    if let Some(value) = lookup(store, &key, now) {
+        return Ok(value);
+    }
+
+    let result = compute()?;
+    remember(store, &key, now.saturating_add(config.ttl_seconds), &result);
+    Ok(result)
+}
+
+/// Async version of [`cached`] for tools whose logic is async
+pub async fn cached_async<I, O, F, Fut>(tool: &str, input: &I, compute: F) -> Result<O, String>
+where
+    I: Serialize,
+    O: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<O, String>>,
+{
+    let config = CacheConfig::from_env();
+    if !config.enabled() {
+        return compute().await;
+    }
+    match SpinStore::open_default() {
+        Ok(store) => cached_in_async(&store, config, now_seconds(), tool, input, compute).await,
+        Err(_) => compute().await,
+    }
+}
+
+/// Async version of [`cached_in`]
+pub async fn cached_in_async<S, I, O, F, Fut>(
+    store: &S,
+    config: CacheConfig,
+    now: u64,
+    tool: &str,
+    input: &I,
+    compute: F,
+) -> Result<O, String>
+where
+    S: KvStore,
+    I: Serialize,
+    O: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<O, String>>,
+{
+    if !config.enabled() {
+        return compute().await;
+    }
+    let Ok(key) = cache_key(tool, input) else {
+        return compute().await;
+    };
+    if let Some(value) = lookup(store, &key, now) {
+        return Ok(value);
+    }
+
+    let result = compute().await?;
+    remember(store, &key, now.saturating_add(config.ttl_seconds), &result);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::cell::Cell;
+
+    const CONFIG: CacheConfig = CacheConfig { ttl_seconds: 60 };
+
+    fn sum(store: &MemoryStore, now: u64, input: &Value, calls: &Cell<u32>) -> Result<f64, String> {
+        cached_in(store, CONFIG, now, "sum", input, || {
+            calls.set(calls.get() + 1);
+            input["values"]
+                .as_array()
+                .map(|v| v.iter().filter_map(Value::as_f64).sum())
+                .ok_or_else(|| "values must be an array".to_string())
+        })
+    }
+
+    #[test]
+    fn test_repeated_call_is_served_from_cache() {
+        let store = MemoryStore::new();
+        let calls = Cell::new(0);
+        let input = json!({ "values": [1.0, 2.0, 3.5] });
+
+        assert_eq!(sum(&store, 1000, &input, &calls), Ok(6.5));
+        assert_eq!(sum(&store, 1001, &input, &calls), Ok(6.5));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let store = MemoryStore::new();
+        let calls = Cell::new(0);
+        let input = json!({ "values": [1.0] });
+
+        sum(&store, 1000, &input, &calls).unwrap();
+        sum(&store, 1059, &input, &calls).unwrap();
+        assert_eq!(calls.get(), 1);
+        sum(&store, 1060, &input, &calls).unwrap();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let store = MemoryStore::new();
+        let calls = Cell::new(0);
+        let input = json!({ "values": "oops" });
+
+        assert!(sum(&store, 1000, &input, &calls).is_err());
+        assert!(sum(&store, 1000, &input, &calls).is_err());
+        assert_eq!(calls.get(), 2);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_key_ignores_field_order() {
+        let a = json!({ "a": 1, "b": { "x": [1, 2], "y": null } });
+        let b = json!({ "b": { "y": null, "x": [1, 2] }, "a": 1 });
+        assert_eq!(
+            cache_key("tool", &a).unwrap(),
+            cache_key("tool", &b).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_key_depends_on_tool_and_input() {
+        let input = json!({ "values": [1.0] });
+        let key = cache_key("sum", &input).unwrap();
+        assert!(key.starts_with("result_cache:sum:"));
+        assert_ne!(key, cache_key("mean", &input).unwrap());
+        assert_ne!(key, cache_key("sum", &json!({ "values": [2.0] })).unwrap());
+    }
+
+    #[test]
+    fn test_disabled_cache_always_computes() {
+        let store = MemoryStore::new();
+        let calls = Cell::new(0);
+        let config = CacheConfig { ttl_seconds: 0 };
+        for _ in 0..2 {
+            let result: Result<u32, String> = cached_in(&store, config, 0, "t", &1, || {
+                calls.set(calls.get() + 1);
+                Ok(7)
+            });
+            assert_eq!(result, Ok(7));
+        }
+        assert_eq!(calls.get(), 2);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_async_results_are_cached() {
+        let store = MemoryStore::new();
+        let calls = Cell::new(0);
+        let input = json!({ "n": 3 });
+        for _ in 0..2 {
+            let result: Result<u64, String> =
+                ftl_yield::block_on(cached_in_async(&store, CONFIG, 0, "t", &input, || async {
+                    calls.set(calls.get() + 1);
+                    ftl_yield::yield_now().await;
+                    Ok(9)
+                }));
+            assert_eq!(result, Ok(9));
+        }
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_ttl_setting() {
+        assert!(!CacheConfig::default().enabled());
+        assert!(!CacheConfig::from_ttl_setting(None).enabled());
+        assert_eq!(CacheConfig::from_ttl_setting(Some(" 30 ")).ttl_seconds, 30);
+        assert!(CacheConfig::from_ttl_setting(Some(" 30 ")).enabled());
+        assert!(!CacheConfig::from_ttl_setting(Some("0")).enabled());
+        assert!(!CacheConfig::from_ttl_setting(Some("soon")).enabled());
+    }
+}
@@ -0,0 +1,276 @@
+//! Derive macro that removes the wrapper/logic type duplication in tool crates.
+//!
+//! Every tool keeps its business logic free of `JsonSchema`, and exposes a mirrored
+//! set of wrapper types to FTL-SDK. Instead of writing both structs and the
+//! field-by-field conversions by hand, annotate the logic type once:
+//!
+//! ```ignore
+//! #[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+//! pub struct CrossProductInput {
+//!     /// First 3D vector
+//!     #[tool_types(nested)]
+//!     pub vector1: Vector3D,
+//!     /// Second 3D vector
+//!     #[tool_types(nested)]
+//!     pub vector2: Vector3D,
+//! }
+//! ```
+//!
+//! This generates `CrossProductInputSchema`, which derives `Debug`, `Clone`,
+//! `Serialize`, `Deserialize` and `JsonSchema`, carries the same doc comments
+//! (used as schema descriptions) and `serde`/`schemars` attributes, keeps the
+//! original schema title, and converts to and from the logic type with `From`.
+//!
+//! Attributes:
+//! - `#[tool_types(wrapper = "Name")]` on the struct overrides the wrapper name
+//!   (default: `<Struct>Schema`).
+//! - `#[tool_types(nested)]` on a field marks a field whose type is another
+//!   `ToolTypes` struct, optionally wrapped in `Option`, `Vec` or arrays. Use
+//!   `#[tool_types(nested = "Name")]` if that struct has a custom wrapper name.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Fields, GenericArgument, Ident, LitStr, PathArguments, Type,
+    parse_macro_input, spanned::Spanned,
+};
+
+#[proc_macro_derive(ToolTypes, attributes(tool_types))]
+pub fn derive_tool_types(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "ToolTypes does not support generic structs",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "ToolTypes requires a struct with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "ToolTypes can only be derived for structs",
+            ));
+        }
+    };
+
+    let logic = &input.ident;
+    let wrapper = struct_wrapper_name(&input)?;
+    let vis = &input.vis;
+    let struct_attrs = passthrough_attrs(&input.attrs);
+    let schema_title = LitStr::new(&logic.to_string(), logic.span());
+
+    let mut wrapper_fields = Vec::new();
+    let mut to_logic = Vec::new();
+    let mut to_wrapper = Vec::new();
+
+    for field in fields {
+        let name = field.ident.as_ref().expect("named field");
+        let field_vis = &field.vis;
+        let attrs = passthrough_attrs(&field.attrs);
+        let value = quote!(value.#name);
+
+        match field_nested(field)? {
+            Some(nested) => {
+                let ty = rewrite_type(&field.ty, &nested)?;
+                let convert = convert_expr(&field.ty, value)?;
+                wrapper_fields.push(quote! { #(#attrs)* #field_vis #name: #ty });
+                to_logic.push(quote! { #name: #convert });
+                to_wrapper.push(quote! { #name: #convert });
+            }
+            None => {
+                let ty = &field.ty;
+                wrapper_fields.push(quote! { #(#attrs)* #field_vis #name: #ty });
+                to_logic.push(quote! { #name: #value });
+                to_wrapper.push(quote! { #name: #value });
+            }
+        }
+    }
+
+    Ok(quote! {
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::serde::Serialize,
+            ::serde::Deserialize,
+            ::schemars::JsonSchema
+        )]
+        #[schemars(rename = #schema_title)]
+        #(#struct_attrs)*
+        #vis struct #wrapper {
+            #(#wrapper_fields,)*
+        }
+
+        impl ::core::convert::From<#wrapper> for #logic {
+            fn from(value: #wrapper) -> Self {
+                #logic {
+                    #(#to_logic,)*
+                }
+            }
+        }
+
+        impl ::core::convert::From<#logic> for #wrapper {
+            fn from(value: #logic) -> Self {
+                #wrapper {
+                    #(#to_wrapper,)*
+                }
+            }
+        }
+    })
+}
+
+/// Wrapper name of a field's nested `ToolTypes` struct, if the field is marked nested
+enum Nested {
+    Default,
+    Named(Ident),
+}
+
+fn struct_wrapper_name(input: &DeriveInput) -> syn::Result<Ident> {
+    let mut wrapper = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("tool_types"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("wrapper") {
+                let name: LitStr = meta.value()?.parse()?;
+                wrapper = Some(name.parse::<Ident>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `wrapper = \"Name\"`"))
+            }
+        })?;
+    }
+    Ok(wrapper.unwrap_or_else(|| default_wrapper_name(&input.ident)))
+}
+
+fn default_wrapper_name(ident: &Ident) -> Ident {
+    format_ident!("{}Schema", ident)
+}
+
+fn field_nested(field: &syn::Field) -> syn::Result<Option<Nested>> {
+    let mut nested = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("tool_types"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                nested = Some(if meta.input.peek(syn::Token![=]) {
+                    let name: LitStr = meta.value()?.parse()?;
+                    Nested::Named(name.parse::<Ident>()?)
+                } else {
+                    Nested::Default
+                });
+                Ok(())
+            } else {
+                Err(meta.error("expected `nested` or `nested = \"Name\"`"))
+            }
+        })?;
+    }
+    Ok(nested)
+}
+
+/// Doc comments plus serde/schemars attributes carry over to the wrapper
+fn passthrough_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs
+        .iter()
+        .filter(|a| {
+            a.path().is_ident("doc") || a.path().is_ident("serde") || a.path().is_ident("schemars")
+        })
+        .collect()
+}
+
+/// Returns the inner type if `ty` is `Option<T>` or `Vec<T>`
+fn container_inner<'a>(ty: &'a Type, container: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segment = path.path.segments.last()?;
+    if segment.ident != container {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+fn base_ident(ty: &Type) -> syn::Result<&Ident> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map(|s| &s.ident)
+            .ok_or_else(|| syn::Error::new(ty.span(), "expected a type name")),
+        _ => Err(syn::Error::new(
+            ty.span(),
+            "nested fields must be a struct type, optionally inside Option, Vec or an array",
+        )),
+    }
+}
+
+/// Replaces the nested struct inside `Option`/`Vec`/array layers with its wrapper type
+fn rewrite_type(ty: &Type, nested: &Nested) -> syn::Result<TokenStream2> {
+    if let Type::Array(array) = ty {
+        let inner = rewrite_type(&array.elem, nested)?;
+        let len = &array.len;
+        return Ok(quote!([#inner; #len]));
+    }
+    if let Some(inner) = container_inner(ty, "Option") {
+        let inner = rewrite_type(inner, nested)?;
+        return Ok(quote!(::core::option::Option<#inner>));
+    }
+    if let Some(inner) = container_inner(ty, "Vec") {
+        let inner = rewrite_type(inner, nested)?;
+        return Ok(quote!(::std::vec::Vec<#inner>));
+    }
+    let wrapper = match nested {
+        Nested::Default => default_wrapper_name(base_ident(ty)?),
+        Nested::Named(name) => name.clone(),
+    };
+    Ok(quote!(#wrapper))
+}
+
+/// Converts `value` through `Option`/`Vec`/array layers using the generated `From` impls
+fn convert_expr(ty: &Type, value: TokenStream2) -> syn::Result<TokenStream2> {
+    if let Type::Array(array) = ty {
+        let convert = convert_expr(&array.elem, quote!(v))?;
+        return Ok(quote!(#value.map(|v| #convert)));
+    }
+    if let Some(inner) = container_inner(ty, "Option") {
+        let convert = convert_expr(inner, quote!(v))?;
+        return Ok(quote!(#value.map(|v| #convert)));
+    }
+    if let Some(inner) = container_inner(ty, "Vec") {
+        let convert = convert_expr(inner, quote!(v))?;
+        return Ok(quote!(#value.into_iter().map(|v| #convert).collect()));
+    }
+    base_ident(ty)?;
+    Ok(quote!(::core::convert::Into::into(#value)))
+}
@@ -0,0 +1,97 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToolTypes)]
+pub struct Vector3D {
+    /// X component
+    pub x: f64,
+    /// Y component
+    pub y: f64,
+    /// Z component
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+#[tool_types(wrapper = "PathInputWrapper")]
+pub struct PathInput {
+    /// Path name
+    pub name: String,
+    /// Start point
+    #[tool_types(nested)]
+    pub start: Vector3D,
+    /// Optional end point
+    #[tool_types(nested)]
+    pub end: Option<Vector3D>,
+    /// Intermediate points
+    #[tool_types(nested)]
+    pub waypoints: Vec<Vector3D>,
+    /// Optional groups of points
+    #[tool_types(nested = "Vector3DSchema")]
+    pub groups: Option<Vec<Vec<Vector3D>>>,
+    /// Fixed-size corners
+    #[tool_types(nested)]
+    pub corners: [Vector3D; 2],
+    /// Renamed field
+    #[serde(rename = "tolerance_m")]
+    pub tolerance: Option<f64>,
+}
+
+fn v(x: f64) -> Vector3D {
+    Vector3D { x, y: 0.0, z: 0.0 }
+}
+
+#[test]
+fn test_round_trip_conversion() {
+    let logic = PathInput {
+        name: "route".to_string(),
+        start: v(1.0),
+        end: Some(v(2.0)),
+        waypoints: vec![v(3.0), v(4.0)],
+        groups: Some(vec![vec![v(5.0)], vec![]]),
+        corners: [v(6.0), v(7.0)],
+        tolerance: Some(0.5),
+    };
+
+    let wrapper = PathInputWrapper::from(logic.clone());
+    assert_eq!(wrapper.start.x, 1.0);
+    assert_eq!(wrapper.end.as_ref().unwrap().x, 2.0);
+    assert_eq!(wrapper.waypoints[1].x, 4.0);
+    assert_eq!(wrapper.groups.as_ref().unwrap()[0][0].x, 5.0);
+    assert_eq!(wrapper.corners[1].x, 7.0);
+
+    let back: PathInput = wrapper.into();
+    assert_eq!(back.start, logic.start);
+    assert_eq!(back.end, logic.end);
+    assert_eq!(back.waypoints, logic.waypoints);
+    assert_eq!(back.groups, logic.groups);
+    assert_eq!(back.corners, logic.corners);
+    assert_eq!(back.tolerance, Some(0.5));
+}
+
+#[test]
+fn test_wrapper_serializes_like_logic_type() {
+    let logic = PathInput {
+        name: "route".to_string(),
+        start: v(1.0),
+        end: None,
+        waypoints: vec![],
+        groups: None,
+        corners: [v(0.0), v(1.0)],
+        tolerance: Some(2.0),
+    };
+
+    let from_logic = serde_json::to_value(&logic).unwrap();
+    let from_wrapper = serde_json::to_value(PathInputWrapper::from(logic)).unwrap();
+    assert_eq!(from_logic, from_wrapper);
+    assert_eq!(from_wrapper["tolerance_m"], 2.0);
+}
+
+#[test]
+fn test_schema_keeps_title_and_descriptions() {
+    let schema = serde_json::to_value(schemars::schema_for!(PathInputWrapper)).unwrap();
+
+    assert_eq!(schema["title"], "PathInput");
+    assert_eq!(schema["properties"]["name"]["description"], "Path name");
+    assert!(schema["properties"].get("tolerance_m").is_some());
+    assert!(schema["definitions"].get("Vector3D").is_some());
+}
@@ -0,0 +1,336 @@
+//! Units for physical-quantity tool inputs and outputs.
+//!
+//! Distances, areas and angles used to be returned as bare floats with the
+//! unit only implied by the field name (`distance_km`, `bearing_degrees`). A
+//! tool that reports such a value also returns it as a [`Quantity`], which
+//! carries its unit explicitly, and lets the client choose that unit with an
+//! optional [`LengthUnit`] or [`AngleUnit`] input:
+//!
+//! ```ignore
+//! let unit = input.unit.unwrap_or_default();
+//! // ... compute `meters` ...
+//! let distance = Quantity::length(meters, unit);
+//! ```
+//!
+//! Values are always computed in SI units (meters, square meters, radians,
+//! seconds) and only converted when the quantity is built. Angle inputs in a
+//! chosen unit are converted back with [`AngleUnit::to_radians`] before any
+//! math.
+//!
+//! Tools whose angles sit inside a generated result rather than in a
+//! [`Quantity`] echo the unit by wrapping the result in an
+//! [`AngleUnitResponse`]:
+//!
+//! ```ignore
+//! let angle_unit = input.angle_unit.unwrap_or_default();
+//! // ... convert angles with `angle_unit` ...
+//! let response = angle_unit.response(result);
+//! ```
+//!
+//! Length inputs can be unit-qualified with [`Length`]. Tools that already
+//! took a bare `<name>_meters` field keep it and add a `<name>` field of type
+//! [`Length`], resolved with [`length_in_meters`]:
+//!
+//! ```ignore
+//! let radius = length_in_meters("radius", input.radius_meters, input.radius)?;
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const METERS_PER_KILOMETER: f64 = 1_000.0;
+/// International mile
+const METERS_PER_MILE: f64 = 1_609.344;
+const SECONDS_PER_MINUTE: f64 = 60.0;
+const SECONDS_PER_HOUR: f64 = 3_600.0;
+
+/// A numeric value together with the unit it is expressed in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Quantity {
+    /// Numeric value in `unit`
+    pub value: f64,
+    /// Unit of `value`, e.g. "kilometers", "square_miles" or "degrees"
+    pub unit: String,
+}
+
+impl Quantity {
+    /// Length given in meters, expressed in `unit`
+    pub fn length(meters: f64, unit: LengthUnit) -> Self {
+        Quantity {
+            value: unit.value_from_meters(meters),
+            unit: unit.name().to_string(),
+        }
+    }
+
+    /// Area given in square meters, expressed in the square of `unit`
+    pub fn area(square_meters: f64, unit: LengthUnit) -> Self {
+        let per_unit = unit.meters();
+        Quantity {
+            value: square_meters / (per_unit * per_unit),
+            unit: format!("square_{}", unit.name()),
+        }
+    }
+
+    /// Angle given in radians, expressed in `unit`
+    pub fn angle(radians: f64, unit: AngleUnit) -> Self {
+        Quantity {
+            value: unit.value_from_radians(radians),
+            unit: unit.name().to_string(),
+        }
+    }
+
+    /// Duration given in seconds, expressed in `unit`
+    pub fn duration(seconds: f64, unit: TimeUnit) -> Self {
+        Quantity {
+            value: unit.value_from_seconds(seconds),
+            unit: unit.name().to_string(),
+        }
+    }
+}
+
+/// A unit-qualified length in a tool input, e.g. `{"value": 5, "unit": "kilometers"}`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Length {
+    /// Numeric value in `unit`
+    pub value: f64,
+    /// "meters", "kilometers" or "miles"
+    pub unit: LengthUnit,
+}
+
+impl Length {
+    pub fn meters(self) -> f64 {
+        self.unit.to_meters(self.value)
+    }
+
+    /// The same length expressed as an output quantity in its own unit
+    pub fn quantity(self) -> Quantity {
+        Quantity::length(self.meters(), self.unit)
+    }
+}
+
+/// Meters from a tool's bare `<name>_meters` input and its unit-qualified
+/// `<name>` counterpart. At most one of the two may be given.
+pub fn length_in_meters(
+    name: &str,
+    meters: Option<f64>,
+    length: Option<Length>,
+) -> Result<Option<f64>, String> {
+    match (meters, length) {
+        (Some(_), Some(_)) => Err(format!("Give either {name} or {name}_meters, not both")),
+        (Some(meters), None) => Ok(Some(meters)),
+        (None, length) => Ok(length.map(Length::meters)),
+    }
+}
+
+/// Unit for distances, and (squared) for areas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    #[default]
+    Meters,
+    Kilometers,
+    Miles,
+}
+
+impl LengthUnit {
+    pub fn name(self) -> &'static str {
+        match self {
+            LengthUnit::Meters => "meters",
+            LengthUnit::Kilometers => "kilometers",
+            LengthUnit::Miles => "miles",
+        }
+    }
+
+    /// Length of one of this unit in meters
+    fn meters(self) -> f64 {
+        match self {
+            LengthUnit::Meters => 1.0,
+            LengthUnit::Kilometers => METERS_PER_KILOMETER,
+            LengthUnit::Miles => METERS_PER_MILE,
+        }
+    }
+
+    pub fn value_from_meters(self, meters: f64) -> f64 {
+        meters / self.meters()
+    }
+
+    pub fn to_meters(self, value: f64) -> f64 {
+        value * self.meters()
+    }
+}
+
+/// Unit for angles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AngleUnit {
+    #[default]
+    Radians,
+    Degrees,
+}
+
+impl AngleUnit {
+    pub fn name(self) -> &'static str {
+        match self {
+            AngleUnit::Radians => "radians",
+            AngleUnit::Degrees => "degrees",
+        }
+    }
+
+    pub fn value_from_radians(self, radians: f64) -> f64 {
+        match self {
+            AngleUnit::Radians => radians,
+            AngleUnit::Degrees => radians.to_degrees(),
+        }
+    }
+
+    pub fn to_radians(self, value: f64) -> f64 {
+        match self {
+            AngleUnit::Radians => value,
+            AngleUnit::Degrees => value.to_radians(),
+        }
+    }
+
+    /// `result`, whose angles are already in this unit, with the unit echoed
+    pub fn response<T>(self, result: T) -> AngleUnitResponse<T> {
+        AngleUnitResponse {
+            result,
+            angle_unit: self.name().to_string(),
+        }
+    }
+}
+
+/// A tool result together with the unit its angles are expressed in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AngleUnitResponse<T> {
+    #[serde(flatten)]
+    pub result: T,
+    /// Unit of the angles in this response: "radians" or "degrees"
+    pub angle_unit: String,
+}
+
+/// Unit for durations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeUnit {
+    #[default]
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+impl TimeUnit {
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeUnit::Seconds => "seconds",
+            TimeUnit::Minutes => "minutes",
+            TimeUnit::Hours => "hours",
+        }
+    }
+
+    /// Length of one of this unit in seconds
+    fn seconds(self) -> f64 {
+        match self {
+            TimeUnit::Seconds => 1.0,
+            TimeUnit::Minutes => SECONDS_PER_MINUTE,
+            TimeUnit::Hours => SECONDS_PER_HOUR,
+        }
+    }
+
+    pub fn value_from_seconds(self, seconds: f64) -> f64 {
+        seconds / self.seconds()
+    }
+
+    pub fn to_seconds(self, value: f64) -> f64 {
+        value * self.seconds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_length_conversions() {
+        let q = Quantity::length(1_609.344, LengthUnit::Miles);
+        assert!(close(q.value, 1.0));
+        assert_eq!(q.unit, "miles");
+        assert!(close(
+            Quantity::length(2_500.0, LengthUnit::Kilometers).value,
+            2.5
+        ));
+        assert!(close(LengthUnit::Kilometers.to_meters(2.5), 2_500.0));
+    }
+
+    #[test]
+    fn test_area_uses_squared_unit() {
+        let q = Quantity::area(3_000_000.0, LengthUnit::Kilometers);
+        assert!(close(q.value, 3.0));
+        assert_eq!(q.unit, "square_kilometers");
+        assert_eq!(
+            Quantity::area(1.0, LengthUnit::default()).unit,
+            "square_meters"
+        );
+    }
+
+    #[test]
+    fn test_angle_round_trip() {
+        let q = Quantity::angle(PI / 2.0, AngleUnit::Degrees);
+        assert!(close(q.value, 90.0));
+        assert_eq!(q.unit, "degrees");
+        assert!(close(AngleUnit::Degrees.to_radians(180.0), PI));
+        assert_eq!(AngleUnit::Radians.to_radians(1.5), 1.5);
+        assert_eq!(AngleUnit::default(), AngleUnit::Radians);
+    }
+
+    #[test]
+    fn test_angle_unit_response_flattens_result() {
+        #[derive(Serialize)]
+        struct Rotation {
+            angle: f64,
+        }
+        let response = AngleUnit::Degrees.response(Rotation { angle: 90.0 });
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({"angle": 90.0, "angle_unit": "degrees"})
+        );
+    }
+
+    #[test]
+    fn test_duration_conversions() {
+        let q = Quantity::duration(5_400.0, TimeUnit::Hours);
+        assert!(close(q.value, 1.5));
+        assert_eq!(q.unit, "hours");
+        assert!(close(TimeUnit::Minutes.to_seconds(2.0), 120.0));
+    }
+
+    #[test]
+    fn test_length_in_meters() {
+        let km = Length {
+            value: 2.0,
+            unit: LengthUnit::Kilometers,
+        };
+        assert_eq!(
+            length_in_meters("radius", None, Some(km)),
+            Ok(Some(2_000.0))
+        );
+        assert_eq!(length_in_meters("radius", Some(50.0), None), Ok(Some(50.0)));
+        assert_eq!(length_in_meters("radius", None, None), Ok(None));
+        assert!(length_in_meters("radius", Some(50.0), Some(km)).is_err());
+        assert_eq!(km.quantity().unit, "kilometers");
+    }
+
+    #[test]
+    fn test_units_deserialize_snake_case() {
+        let unit: LengthUnit = serde_json::from_str("\"kilometers\"").unwrap();
+        assert_eq!(unit, LengthUnit::Kilometers);
+        let unit: AngleUnit = serde_json::from_str("\"degrees\"").unwrap();
+        assert_eq!(unit, AngleUnit::Degrees);
+        let length: Length = serde_json::from_str(r#"{"value": 3, "unit": "miles"}"#).unwrap();
+        assert!(close(length.meters(), 3.0 * 1_609.344));
+    }
+}
@@ -0,0 +1,202 @@
+//! Flexible 3D vector input for math3d tools.
+//!
+//! Most math3d tools took vectors as `{"x": .., "y": .., "z": ..}` objects
+//! while vector_analysis took `[x, y, z]` arrays. [`Vector3DInput`] accepts
+//! either form everywhere and rejects arrays of the wrong length with an
+//! error naming the count it got.
+//!
+//! A tool's own vector struct keeps its fields and deserializes through
+//! [`Vector3DInput`], with the conversion written by [`from_vector3d_input!`]:
+//!
+//! ```ignore
+//! /// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+//! #[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+//! #[serde(from = "ftl_vector3d::Vector3DInput")]
+//! pub struct Vector3D {
+//!     pub x: f64,
+//!     pub y: f64,
+//!     pub z: f64,
+//! }
+//!
+//! ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+//! ```
+//!
+//! `ToolTypes` carries the `serde` attribute over to the generated schema
+//! type, so both need the conversion. Serialization is unchanged and always
+//! writes the object form.
+
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::Schema;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A 3D vector given as `[x, y, z]` or as `{"x": .., "y": .., "z": ..}`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Vector3DInput {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector3DInput {
+    pub fn to_array(self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl<'de> Deserialize<'de> for Vector3DInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(Vector3DVisitor)
+    }
+}
+
+struct Vector3DVisitor;
+
+impl<'de> Visitor<'de> for Vector3DVisitor {
+    type Value = Vector3DInput;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(r#"a 3D vector as [x, y, z] or {"x": .., "y": .., "z": ..}"#)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut components = Vec::with_capacity(3);
+        while let Some(component) = seq.next_element::<f64>()? {
+            components.push(component);
+        }
+        match components[..] {
+            [x, y, z] => Ok(Vector3DInput { x, y, z }),
+            _ => Err(de::Error::custom(format!(
+                "expected 3 components [x, y, z], got {}",
+                components.len()
+            ))),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut x, mut y, mut z) = (None, None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            let slot = match key.as_str() {
+                "x" => &mut x,
+                "y" => &mut y,
+                "z" => &mut z,
+                // Unknown keys are ignored, as with a derived struct
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                    continue;
+                }
+            };
+            if slot.is_some() {
+                return Err(de::Error::custom(format!("duplicate field `{key}`")));
+            }
+            *slot = Some(map.next_value::<f64>()?);
+        }
+        Ok(Vector3DInput {
+            x: x.ok_or_else(|| <A::Error as de::Error>::missing_field("x"))?,
+            y: y.ok_or_else(|| <A::Error as de::Error>::missing_field("y"))?,
+            z: z.ok_or_else(|| <A::Error as de::Error>::missing_field("z"))?,
+        })
+    }
+}
+
+/// Schema-only mirror of the two accepted forms
+#[derive(JsonSchema)]
+#[schemars(untagged)]
+#[allow(dead_code)]
+enum Vector3DForm {
+    /// {"x": .., "y": .., "z": ..}
+    Object { x: f64, y: f64, z: f64 },
+    /// [x, y, z]
+    Array([f64; 3]),
+}
+
+impl JsonSchema for Vector3DInput {
+    fn schema_name() -> String {
+        "Vector3DInput".to_string()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        Vector3DForm::json_schema(generator)
+    }
+}
+
+/// Implement `From<Vector3DInput>` for structs with `x`, `y` and `z` fields,
+/// as needed by `#[serde(from = "ftl_vector3d::Vector3DInput")]`
+#[macro_export]
+macro_rules! from_vector3d_input {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl ::core::convert::From<$crate::Vector3DInput> for $ty {
+                fn from(v: $crate::Vector3DInput) -> Self {
+                    Self {
+                        x: v.x,
+                        y: v.y,
+                        z: v.z,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(from = "Vector3DInput")]
+    struct Point {
+        x: f64,
+        y: f64,
+        z: f64,
+    }
+
+    from_vector3d_input!(Point);
+
+    #[test]
+    fn test_accepts_array_and_object() {
+        let from_array: Vector3DInput = serde_json::from_str("[1, 2.5, -3]").unwrap();
+        let from_object: Vector3DInput =
+            serde_json::from_str(r#"{"x": 1, "y": 2.5, "z": -3}"#).unwrap();
+        assert_eq!(from_array, from_object);
+        assert_eq!(from_array.to_array(), [1.0, 2.5, -3.0]);
+    }
+
+    #[test]
+    fn test_rejects_wrong_length() {
+        let err = serde_json::from_str::<Vector3DInput>("[1, 2]").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected 3 components [x, y, z], got 2")
+        );
+        let err = serde_json::from_str::<Vector3DInput>("[1, 2, 3, 4]").unwrap_err();
+        assert!(err.to_string().contains("got 4"));
+    }
+
+    #[test]
+    fn test_object_fields() {
+        let v: Vector3DInput =
+            serde_json::from_str(r#"{"z": 3, "x": 1, "y": 2, "label": "a"}"#).unwrap();
+        assert_eq!(v.to_array(), [1.0, 2.0, 3.0]);
+        let err = serde_json::from_str::<Vector3DInput>(r#"{"x": 1, "y": 2}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `z`"));
+        assert!(
+            serde_json::from_str::<Vector3DInput>(r#"{"x": 1, "x": 1, "y": 2, "z": 3}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_from_attribute_on_tool_struct() {
+        let points: Vec<Point> =
+            serde_json::from_str(r#"[[0, 0, 1], {"x": 1, "y": 0, "z": 0}]"#).unwrap();
+        let components: Vec<_> = points.iter().map(|p| (p.x, p.y, p.z)).collect();
+        assert_eq!(components, vec![(0.0, 0.0, 1.0), (1.0, 0.0, 0.0)]);
+        let v: Vector3DInput = serde_json::from_str("[1, 2, 3]").unwrap();
+        assert_eq!(
+            serde_json::to_string(&v).unwrap(),
+            r#"{"x":1.0,"y":2.0,"z":3.0}"#
+        );
+    }
+}
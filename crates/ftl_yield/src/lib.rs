@@ -0,0 +1,203 @@
+//! Cooperative yielding for CPU-heavy async tools.
+//!
+//! A tool handler that crunches a large input in one synchronous call holds the
+//! Spin executor until it finishes, so nothing else scheduled on that executor
+//! (outbound calls from composite tools, other futures) makes progress. Tools
+//! with long loops are `async` and call [`Yielder::tick`] once per iteration;
+//! every [`DEFAULT_YIELD_INTERVAL`] iterations the future returns `Pending` once
+//! and immediately re-wakes itself, handing control back to the executor.
+//!
+//! [`block_on`] drives such futures to completion from synchronous code, e.g.
+//! unit tests or category tools that call another tool's logic directly.
+
+use std::future::Future;
+use std::pin::{Pin, pin};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+/// Iterations between yields for [`Yielder::default`]
+pub const DEFAULT_YIELD_INTERVAL: usize = 4096;
+
+/// Future that is pending exactly once, waking itself before it returns
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Give control back to the executor once
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// Counts loop iterations and yields every `interval` of them
+#[derive(Debug, Clone)]
+pub struct Yielder {
+    interval: usize,
+    count: usize,
+    yields: usize,
+}
+
+impl Default for Yielder {
+    fn default() -> Self {
+        Self::new(DEFAULT_YIELD_INTERVAL)
+    }
+}
+
+impl Yielder {
+    pub fn new(interval: usize) -> Self {
+        Yielder {
+            interval: interval.max(1),
+            count: 0,
+            yields: 0,
+        }
+    }
+
+    /// Record one unit of work, yielding if the interval has been reached
+    pub async fn tick(&mut self) {
+        self.count += 1;
+        if self.count.is_multiple_of(self.interval) {
+            self.yields += 1;
+            yield_now().await;
+        }
+    }
+
+    /// Number of times this yielder has handed control back
+    pub fn yields(&self) -> usize {
+        self.yields
+    }
+}
+
+/// Waker that unparks the thread blocked in [`block_on`]
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a future to completion on the current thread.
+///
+/// The thread parks while the future is pending and is unparked by its waker,
+/// so a future waiting on a timer or another thread sleeps instead of being
+/// re-polled in a loop. A yield wakes before returning `Pending`, so yielding
+/// logic is polled again straight away.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_yield_now_is_pending_once() {
+        let mut future = pin!(yield_now());
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(future.as_mut().poll(&mut cx).is_pending());
+        assert!(future.as_mut().poll(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn test_yielder_interval() {
+        let yields = block_on(async {
+            let mut yielder = Yielder::new(10);
+            for _ in 0..95 {
+                yielder.tick().await;
+            }
+            yielder.yields()
+        });
+        assert_eq!(yields, 9);
+    }
+
+    #[test]
+    fn test_zero_interval_yields_every_tick() {
+        let yields = block_on(async {
+            let mut yielder = Yielder::new(0);
+            for _ in 0..3 {
+                yielder.tick().await;
+            }
+            yielder.yields()
+        });
+        assert_eq!(yields, 3);
+    }
+
+    #[test]
+    fn test_block_on_counts_polls() {
+        let mut polls = 0;
+        let mut future = pin!(async {
+            let mut yielder = Yielder::new(2);
+            for _ in 0..4 {
+                yielder.tick().await;
+            }
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+        while future.as_mut().poll(&mut cx).is_pending() {
+            polls += 1;
+        }
+        assert_eq!(polls, 2);
+    }
+
+    /// Pending until another thread sets `done` and wakes it, counting polls
+    struct WokenElsewhere {
+        done: Arc<AtomicBool>,
+        polls: usize,
+    }
+
+    impl Future for WokenElsewhere {
+        type Output = usize;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+            self.polls += 1;
+            if self.done.load(Ordering::SeqCst) {
+                return Poll::Ready(self.polls);
+            }
+            if self.polls == 1 {
+                let (done, waker) = (self.done.clone(), cx.waker().clone());
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(20));
+                    done.store(true, Ordering::SeqCst);
+                    waker.wake();
+                });
+            }
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn test_block_on_parks_until_woken() {
+        let polls = block_on(WokenElsewhere {
+            done: Arc::default(),
+            polls: 0,
+        });
+        // Pending once, then ready after the wake (allowing one spurious unpark)
+        assert!(polls <= 3, "{polls} polls");
+    }
+}
@@ -0,0 +1,120 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+use ftl_guard::Guard;
+use ftl_introspect::{Request, describe};
+use ftl_log::ToolLog;
+use ftl_sdk::ToolResponse;
+
+#[cfg(not(test))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    BasicMathCategoryInput as LogicInput, BasicMathCategoryResult as LogicOutput,
+    BasicMathOperation, ChainStep as LogicChainStep,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BasicMathCategoryInput {
+    /// Operation to perform: "add", "subtract", "multiply", "divide", "modulus",
+    /// "remainder", "power", "square", "sqrt", "pythagorean", "distance_2d", the
+    /// list operations "sum", "product", "min", "max" and "mean" (taking
+    /// `{"numbers": [...]}`), "compare_numbers", or "chain" to run `steps`
+    pub operation: String,
+    /// Arguments for the selected operation, using the same fields as the individual tool
+    pub input: Option<serde_json::Value>,
+    /// Operations to run in order when `operation` is "chain". Each step after the
+    /// first takes the previous step's numeric result as `a` (or `value` for
+    /// "square" and "sqrt"), so that field is left out of its input. List
+    /// operations and "distance_2d" can only be the first step.
+    pub steps: Option<Vec<ChainStep>>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChainStep {
+    /// Operation for this step, e.g. "multiply"
+    pub operation: String,
+    /// Arguments for this step, e.g. {"b": 2} (optional for "square" and "sqrt")
+    pub input: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BasicMathCategoryResult {
+    /// Operation that was run
+    pub operation: String,
+    /// Whether the operation succeeded
+    pub success: bool,
+    /// Operation result (present on success). For "chain": `value`, the last
+    /// step's numeric result, and `steps`, every step's full result.
+    pub result: Option<serde_json::Value>,
+    /// Error message (present on failure)
+    pub error: Option<String>,
+}
+
+/// Run any basic math tool, or a chain of them, through a single
+/// operation-based interface with a consistent `{operation, success, result, error}` envelope
+#[cfg_attr(not(test), tool)]
+pub fn basic_math_category(request: Request<BasicMathCategoryInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "basic_math_category",
+                BasicMathCategoryInput,
+                BasicMathCategoryResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("basic_math_category", &input);
+    log.finish(run_basic_math_category(input))
+}
+
+fn run_basic_math_category(input: BasicMathCategoryInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .json("input", &input.input)
+        .array("steps", input.steps.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        operation: input.operation,
+        input: input.input,
+        steps: input.steps.map(|steps| {
+            steps
+                .into_iter()
+                .map(|step| LogicChainStep {
+                    operation: step.operation,
+                    input: step.input,
+                })
+                .collect()
+        }),
+    };
+
+    // Call logic implementation
+    let result = logic::dispatch(logic_input);
+
+    // Convert back to wrapper types
+    let output = BasicMathCategoryResult {
+        operation: result.operation,
+        success: result.success,
+        result: result.result,
+        error: result.error,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
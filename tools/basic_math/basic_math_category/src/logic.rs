@@ -0,0 +1,528 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// Operation name that runs `steps` instead of a single operation
+pub const CHAIN: &str = "chain";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasicMathCategoryInput {
+    /// Operation to perform, or "chain"
+    pub operation: String,
+    /// Arguments for the selected operation
+    pub input: Option<Value>,
+    /// Operations to run in order when `operation` is "chain"
+    pub steps: Option<Vec<ChainStep>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainStep {
+    /// Operation for this step
+    pub operation: String,
+    /// Arguments for this step, without the one taken from the previous step
+    pub input: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasicMathCategoryResult {
+    /// Operation that was run (normalized name)
+    pub operation: String,
+    /// Whether the operation succeeded
+    pub success: bool,
+    /// Operation result (present on success)
+    pub result: Option<Value>,
+    /// Error message (present on failure)
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainResult {
+    /// Numeric result of the last step
+    pub value: f64,
+    /// Full result of every step, in order
+    pub steps: Vec<StepResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepResult {
+    pub operation: String,
+    pub result: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasicMathOperation {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulus,
+    Remainder,
+    Power,
+    Square,
+    Sqrt,
+    Pythagorean,
+    Distance2d,
+    Sum,
+    Product,
+    Min,
+    Max,
+    Mean,
+    CompareNumbers,
+}
+
+impl BasicMathOperation {
+    pub const ALL: &'static [BasicMathOperation] = &[
+        BasicMathOperation::Add,
+        BasicMathOperation::Subtract,
+        BasicMathOperation::Multiply,
+        BasicMathOperation::Divide,
+        BasicMathOperation::Modulus,
+        BasicMathOperation::Remainder,
+        BasicMathOperation::Power,
+        BasicMathOperation::Square,
+        BasicMathOperation::Sqrt,
+        BasicMathOperation::Pythagorean,
+        BasicMathOperation::Distance2d,
+        BasicMathOperation::Sum,
+        BasicMathOperation::Product,
+        BasicMathOperation::Min,
+        BasicMathOperation::Max,
+        BasicMathOperation::Mean,
+        BasicMathOperation::CompareNumbers,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BasicMathOperation::Add => "add",
+            BasicMathOperation::Subtract => "subtract",
+            BasicMathOperation::Multiply => "multiply",
+            BasicMathOperation::Divide => "divide",
+            BasicMathOperation::Modulus => "modulus",
+            BasicMathOperation::Remainder => "remainder",
+            BasicMathOperation::Power => "power",
+            BasicMathOperation::Square => "square",
+            BasicMathOperation::Sqrt => "sqrt",
+            BasicMathOperation::Pythagorean => "pythagorean",
+            BasicMathOperation::Distance2d => "distance_2d",
+            BasicMathOperation::Sum => "sum",
+            BasicMathOperation::Product => "product",
+            BasicMathOperation::Min => "min",
+            BasicMathOperation::Max => "max",
+            BasicMathOperation::Mean => "mean",
+            BasicMathOperation::CompareNumbers => "compare_numbers",
+        }
+    }
+
+    /// Input field that receives the previous step's value in a chain; list
+    /// operations and distance_2d can only start one
+    fn chained_field(self) -> Option<&'static str> {
+        match self {
+            BasicMathOperation::Square | BasicMathOperation::Sqrt => Some("value"),
+            BasicMathOperation::Distance2d
+            | BasicMathOperation::Sum
+            | BasicMathOperation::Product
+            | BasicMathOperation::Min
+            | BasicMathOperation::Max
+            | BasicMathOperation::Mean => None,
+            _ => Some("a"),
+        }
+    }
+
+    /// Result field passed on to the next step in a chain
+    fn value_field(self) -> &'static str {
+        match self {
+            BasicMathOperation::Pythagorean => "hypotenuse",
+            BasicMathOperation::Distance2d => "distance",
+            BasicMathOperation::CompareNumbers => "difference",
+            _ => "result",
+        }
+    }
+}
+
+impl FromStr for BasicMathOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace('-', "_");
+        BasicMathOperation::ALL
+            .iter()
+            .copied()
+            .find(|op| op.name() == normalized)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = BasicMathOperation::ALL
+                    .iter()
+                    .map(|op| op.name())
+                    .chain([CHAIN])
+                    .collect();
+                format!(
+                    "Unknown operation '{s}'. Valid operations: {}",
+                    valid.join(", ")
+                )
+            })
+    }
+}
+
+/// Run the requested operation or chain, always returning a result envelope
+pub fn dispatch(input: BasicMathCategoryInput) -> BasicMathCategoryResult {
+    if input.operation.trim().eq_ignore_ascii_case(CHAIN) {
+        return envelope(CHAIN.to_string(), run_chain(input.steps));
+    }
+
+    let operation = match input.operation.parse::<BasicMathOperation>() {
+        Ok(op) => op,
+        Err(e) => return envelope(input.operation, Err(e)),
+    };
+
+    if input.steps.is_some() {
+        return envelope(
+            operation.name().to_string(),
+            Err(format!("steps are only used with operation '{CHAIN}'")),
+        );
+    }
+
+    let args = input
+        .input
+        .unwrap_or_else(|| Value::Object(Default::default()));
+    envelope(operation.name().to_string(), run(operation, args))
+}
+
+/// Run each step in order, feeding every step's numeric result into the next
+fn run_chain(steps: Option<Vec<ChainStep>>) -> Result<Value, String> {
+    let mut previous: Option<f64> = None;
+    let mut results = Vec::new();
+
+    for (i, step) in steps.unwrap_or_default().into_iter().enumerate() {
+        let n = i + 1;
+        let operation = step
+            .operation
+            .parse::<BasicMathOperation>()
+            .map_err(|e| format!("Step {n}: {e}"))?;
+        let mut args = step
+            .input
+            .unwrap_or_else(|| Value::Object(Default::default()));
+
+        if let Some(value) = previous {
+            let field = operation.chained_field().ok_or_else(|| {
+                format!(
+                    "Step {n}: '{}' cannot take the previous step's result",
+                    operation.name()
+                )
+            })?;
+            let object = args
+                .as_object_mut()
+                .ok_or_else(|| format!("Step {n}: input must be an object"))?;
+            if object.contains_key(field) {
+                return Err(format!(
+                    "Step {n}: '{field}' comes from the previous step's result and must be left out"
+                ));
+            }
+            object.insert(field.to_string(), Value::from(value));
+        }
+
+        let result = run(operation, args).map_err(|e| format!("Step {n}: {e}"))?;
+        previous = Some(step_value(operation, &result).map_err(|e| format!("Step {n}: {e}"))?);
+        results.push(StepResult {
+            operation: operation.name().to_string(),
+            result,
+        });
+    }
+
+    let value = previous.ok_or_else(|| format!("Operation '{CHAIN}' needs at least one step"))?;
+    to_value(ChainResult {
+        value,
+        steps: results,
+    })
+}
+
+fn run(operation: BasicMathOperation, args: Value) -> Result<Value, String> {
+    match operation {
+        BasicMathOperation::Add => parse_args::<add_tool::LogicInput>(operation, args)
+            .and_then(add_tool::add_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Subtract => parse_args::<subtract_tool::LogicInput>(operation, args)
+            .and_then(subtract_tool::subtract_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Multiply => parse_args::<multiply_tool::LogicInput>(operation, args)
+            .and_then(multiply_tool::multiply_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Divide => parse_args::<divide_tool::LogicInput>(operation, args)
+            .and_then(divide_tool::divide_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Modulus => parse_args::<modulus_tool::LogicInput>(operation, args)
+            .and_then(modulus_tool::modulus_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Remainder => parse_args::<remainder_tool::LogicInput>(operation, args)
+            .and_then(remainder_tool::remainder_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Power => parse_args::<power_tool::LogicInput>(operation, args)
+            .and_then(power_tool::power_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Square => parse_args::<square_tool::LogicInput>(operation, args)
+            .and_then(square_tool::square_number)
+            .and_then(to_value),
+        BasicMathOperation::Sqrt => parse_args::<sqrt_tool::LogicInput>(operation, args)
+            .and_then(sqrt_tool::calculate_sqrt)
+            .and_then(to_value),
+        BasicMathOperation::Pythagorean => {
+            parse_args::<pythagorean_tool::LogicInput>(operation, args)
+                .and_then(pythagorean_tool::calculate_pythagorean)
+                .and_then(to_value)
+        }
+        BasicMathOperation::Distance2d => {
+            parse_args::<distance_2d_tool::LogicInput>(operation, args)
+                .and_then(distance_2d_tool::calculate_distance_2d)
+                .and_then(to_value)
+        }
+        BasicMathOperation::Sum => parse_args::<sum_tool::LogicInput>(operation, args)
+            .and_then(sum_tool::sum_numbers)
+            .and_then(to_value),
+        BasicMathOperation::Product => parse_args::<product_tool::LogicInput>(operation, args)
+            .and_then(product_tool::multiply_all)
+            .and_then(to_value),
+        BasicMathOperation::Min => parse_args::<min_tool::LogicInput>(operation, args)
+            .and_then(min_tool::find_minimum)
+            .and_then(to_value),
+        BasicMathOperation::Max => parse_args::<max_tool::LogicInput>(operation, args)
+            .and_then(max_tool::find_maximum)
+            .and_then(to_value),
+        BasicMathOperation::Mean => parse_args::<mean_tool::LogicInput>(operation, args)
+            .and_then(mean_tool::calculate_mean)
+            .and_then(to_value),
+        BasicMathOperation::CompareNumbers => {
+            parse_args::<compare_numbers_tool::LogicInput>(operation, args)
+                .and_then(compare_numbers_tool::compare_values)
+                .and_then(to_value)
+        }
+    }
+}
+
+/// Numeric value of a step's result; sqrt reports a negative input in its
+/// result rather than as an error, which would end the chain with NaN
+fn step_value(operation: BasicMathOperation, result: &Value) -> Result<f64, String> {
+    if result["is_valid"] == Value::Bool(false) {
+        let error = result["error"].as_str().unwrap_or("invalid result");
+        return Err(format!("{}: {error}", operation.name()));
+    }
+    result[operation.value_field()]
+        .as_f64()
+        .ok_or_else(|| format!("'{}' returned no numeric result", operation.name()))
+}
+
+fn envelope(operation: String, outcome: Result<Value, String>) -> BasicMathCategoryResult {
+    match outcome {
+        Ok(result) => BasicMathCategoryResult {
+            operation,
+            success: true,
+            result: Some(result),
+            error: None,
+        },
+        Err(e) => BasicMathCategoryResult {
+            operation,
+            success: false,
+            result: None,
+            error: Some(e),
+        },
+    }
+}
+
+fn parse_args<T: for<'de> Deserialize<'de>>(
+    operation: BasicMathOperation,
+    args: Value,
+) -> Result<T, String> {
+    serde_json::from_value(args)
+        .map_err(|e| format!("Invalid input for '{}': {e}", operation.name()))
+}
+
+fn to_value<T: Serialize>(result: T) -> Result<Value, String> {
+    serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(operation: &str, args: Option<Value>) -> BasicMathCategoryResult {
+        dispatch(BasicMathCategoryInput {
+            operation: operation.to_string(),
+            input: args,
+            steps: None,
+        })
+    }
+
+    fn chain(steps: Value) -> BasicMathCategoryResult {
+        dispatch(BasicMathCategoryInput {
+            operation: "chain".to_string(),
+            input: None,
+            steps: Some(serde_json::from_value(steps).unwrap()),
+        })
+    }
+
+    #[test]
+    fn test_operation_parsing() {
+        assert_eq!(
+            " Distance-2D ".parse::<BasicMathOperation>().unwrap(),
+            BasicMathOperation::Distance2d
+        );
+        let error = "log".parse::<BasicMathOperation>().unwrap_err();
+        assert!(error.contains("Unknown operation 'log'"));
+        assert!(error.ends_with("compare_numbers, chain"));
+    }
+
+    #[test]
+    fn test_single_operation() {
+        let result = call("power", Some(json!({ "a": 2, "b": 10 })));
+
+        assert!(result.success);
+        assert_eq!(result.operation, "power");
+        assert_eq!(result.result.unwrap()["result"], 1024.0);
+
+        let result = call("divide", Some(json!({ "a": 1, "b": 0 })));
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Division by zero"));
+    }
+
+    #[test]
+    fn test_list_operations() {
+        let numbers = json!({ "numbers": [4, -2, 7.5, 0.5] });
+        let expected = [
+            ("sum", 10.0),
+            ("product", -30.0),
+            ("min", -2.0),
+            ("max", 7.5),
+            ("mean", 2.5),
+        ];
+        for (operation, value) in expected {
+            let result = call(operation, Some(numbers.clone()));
+            assert!(result.success, "{operation}: {:?}", result.error);
+            assert_eq!(result.result.unwrap()["result"], value);
+        }
+
+        let result = call("mean", Some(json!({ "numbers": [] })));
+        assert_eq!(result.error.unwrap(), "Input list cannot be empty");
+    }
+
+    #[test]
+    fn test_chain_starts_from_list_operation() {
+        let result = chain(json!([
+            { "operation": "sum", "input": { "numbers": [1, 2, 3, 4] } },
+            { "operation": "divide", "input": { "b": 4 } }
+        ]));
+        assert!(result.success);
+        assert_eq!(result.result.unwrap()["value"], 2.5);
+
+        let result = chain(json!([
+            { "operation": "add", "input": { "a": 1, "b": 2 } },
+            { "operation": "max", "input": { "numbers": [5] } }
+        ]));
+        assert!(
+            result
+                .error
+                .unwrap()
+                .contains("'max' cannot take the previous step's result")
+        );
+    }
+
+    #[test]
+    fn test_chain_feeds_results_forward() {
+        // ((3 + 4) * 2)^2, then its square root
+        let result = chain(json!([
+            { "operation": "add", "input": { "a": 3, "b": 4 } },
+            { "operation": "multiply", "input": { "b": 2 } },
+            { "operation": "square" },
+            { "operation": "sqrt" }
+        ]));
+
+        assert!(result.success);
+        assert_eq!(result.operation, "chain");
+        let value = result.result.unwrap();
+        assert_eq!(value["value"], 14.0);
+        let steps = value["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[1]["operation"], "multiply");
+        assert_eq!(steps[1]["result"]["inputs"], json!([7.0, 2.0]));
+        assert_eq!(steps[2]["result"]["result"], 196.0);
+    }
+
+    #[test]
+    fn test_chain_checks_result_with_compare_numbers() {
+        // Verify that sqrt(2)^2 comes back as 2 up to rounding error
+        let result = chain(json!([
+            { "operation": "sqrt", "input": { "value": 2 } },
+            { "operation": "square" },
+            { "operation": "compare_numbers", "input": { "b": 2, "max_ulps": 4 } }
+        ]));
+
+        assert!(result.success);
+        let value = result.result.unwrap();
+        let comparison = &value["steps"][2]["result"];
+        assert_eq!(comparison["exactly_equal"], false);
+        assert_eq!(comparison["approximately_equal"], true);
+        assert_eq!(comparison["within_ulps"], true);
+        assert_eq!(value["value"], comparison["difference"]);
+    }
+
+    #[test]
+    fn test_chain_starts_from_point_distance() {
+        let result = chain(json!([
+            {
+                "operation": "distance_2d",
+                "input": { "point1": { "x": 0, "y": 0 }, "point2": { "x": 3, "y": 4 } }
+            },
+            { "operation": "subtract", "input": { "b": 1 } }
+        ]));
+
+        assert!(result.success);
+        assert_eq!(result.result.unwrap()["value"], 4.0);
+    }
+
+    #[test]
+    fn test_chain_errors_name_the_step() {
+        let result = chain(json!([
+            { "operation": "subtract", "input": { "a": 1, "b": 5 } },
+            { "operation": "sqrt" }
+        ]));
+        assert!(!result.success);
+        assert_eq!(
+            result.error.unwrap(),
+            "Step 2: sqrt: Cannot compute square root of negative number"
+        );
+
+        let result = chain(json!([
+            { "operation": "add", "input": { "a": 1, "b": 2 } },
+            { "operation": "add", "input": { "a": 1, "b": 2 } }
+        ]));
+        assert!(result.error.unwrap().starts_with("Step 2: 'a' comes from"));
+
+        let result = chain(json!([
+            { "operation": "add", "input": { "a": 1, "b": 2 } },
+            { "operation": "distance_2d" }
+        ]));
+        assert!(
+            result
+                .error
+                .unwrap()
+                .contains("'distance_2d' cannot take the previous step's result")
+        );
+    }
+
+    #[test]
+    fn test_chain_requires_steps() {
+        let result = chain(json!([]));
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("needs at least one step"));
+
+        let result = dispatch(BasicMathCategoryInput {
+            operation: "add".to_string(),
+            input: Some(json!({ "a": 1, "b": 2 })),
+            steps: Some(Vec::new()),
+        });
+        assert!(!result.success);
+        assert!(
+            result
+                .error
+                .unwrap()
+                .contains("only used with operation 'chain'")
+        );
+    }
+}
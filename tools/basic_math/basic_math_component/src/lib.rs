@@ -0,0 +1,220 @@
+//! Typed WIT exports of the basic_math tools.
+//!
+//! Hosts that link wasm components can call `ftl:basic-math/tools` (see
+//! `wit/world.wit`) with plain numbers and records instead of sending JSON to
+//! each tool's HTTP endpoint. Every function calls the same logic as the tool,
+//! linked in library mode, so results and error messages are identical.
+//!
+//! Build with `cargo build --target wasm32-wasip2 --release`.
+
+wit_bindgen::generate!({
+    world: "basic-math",
+    path: "wit",
+});
+
+use exports::ftl::basic_math::tools::{
+    ArithmeticResult, CompareOptions, ComparisonResult, DistanceResult, Guest, Point2d,
+    PythagoreanResult, SquareRootResult,
+};
+
+struct BasicMath;
+
+// Export names contain `:`, which native linkers reject in version scripts
+#[cfg(target_arch = "wasm32")]
+export!(BasicMath);
+
+/// Convert any of the tools' own `ArithmeticResult`s, which share their fields
+macro_rules! arithmetic_result {
+    ($result:expr) => {
+        $result.map(|r| ArithmeticResult {
+            result: r.result,
+            operation: r.operation,
+            inputs: r.inputs,
+        })
+    };
+}
+
+impl Guest for BasicMath {
+    fn add(a: f64, b: f64) -> Result<ArithmeticResult, String> {
+        let input = add_tool::LogicInput { a, b };
+        arithmetic_result!(add_tool::add_numbers(input))
+    }
+
+    fn subtract(a: f64, b: f64) -> Result<ArithmeticResult, String> {
+        let input = subtract_tool::LogicInput { a, b };
+        arithmetic_result!(subtract_tool::subtract_numbers(input))
+    }
+
+    fn multiply(a: f64, b: f64) -> Result<ArithmeticResult, String> {
+        let input = multiply_tool::LogicInput { a, b };
+        arithmetic_result!(multiply_tool::multiply_numbers(input))
+    }
+
+    fn divide(a: f64, b: f64) -> Result<ArithmeticResult, String> {
+        let input = divide_tool::LogicInput { a, b };
+        arithmetic_result!(divide_tool::divide_numbers(input))
+    }
+
+    fn modulus(a: f64, b: f64) -> Result<ArithmeticResult, String> {
+        let input = modulus_tool::LogicInput { a, b };
+        arithmetic_result!(modulus_tool::modulus_numbers(input))
+    }
+
+    fn remainder(a: f64, b: f64) -> Result<ArithmeticResult, String> {
+        let input = remainder_tool::LogicInput { a, b };
+        arithmetic_result!(remainder_tool::remainder_numbers(input))
+    }
+
+    fn power(a: f64, b: f64) -> Result<ArithmeticResult, String> {
+        let input = power_tool::LogicInput { a, b };
+        arithmetic_result!(power_tool::power_numbers(input))
+    }
+
+    fn square(value: f64) -> Result<ArithmeticResult, String> {
+        let input = square_tool::LogicInput { value };
+        arithmetic_result!(square_tool::square_number(input))
+    }
+
+    fn sqrt(value: f64) -> Result<SquareRootResult, String> {
+        sqrt_tool::calculate_sqrt(sqrt_tool::LogicInput { value }).map(|r| SquareRootResult {
+            result: r.result,
+            input: r.input,
+            is_valid: r.is_valid,
+            error: r.error,
+        })
+    }
+
+    fn pythagorean(a: f64, b: f64) -> Result<PythagoreanResult, String> {
+        pythagorean_tool::calculate_pythagorean(pythagorean_tool::LogicInput { a, b }).map(|r| {
+            PythagoreanResult {
+                hypotenuse: r.hypotenuse,
+                leg_a: r.leg_a,
+                leg_b: r.leg_b,
+                calculation_steps: r.calculation_steps,
+                tool_calls: r.tool_calls,
+            }
+        })
+    }
+
+    fn distance_two_d(point1: Point2d, point2: Point2d) -> Result<DistanceResult, String> {
+        let point = |p: Point2d| distance_2d_tool::LogicPoint { x: p.x, y: p.y };
+        let input = distance_2d_tool::LogicInput {
+            point1: point(point1),
+            point2: point(point2),
+        };
+        distance_2d_tool::calculate_distance_2d(input).map(|r| DistanceResult {
+            distance: r.distance,
+            point1: Point2d {
+                x: r.point1.x,
+                y: r.point1.y,
+            },
+            point2: Point2d {
+                x: r.point2.x,
+                y: r.point2.y,
+            },
+            delta_x: r.delta_x,
+            delta_y: r.delta_y,
+            calculation_steps: r.calculation_steps,
+            note: r.note,
+        })
+    }
+
+    fn sum(numbers: Vec<f64>) -> Result<ArithmeticResult, String> {
+        let input = sum_tool::LogicInput { numbers };
+        arithmetic_result!(sum_tool::sum_numbers(input))
+    }
+
+    fn product(numbers: Vec<f64>) -> Result<ArithmeticResult, String> {
+        let input = product_tool::LogicInput { numbers };
+        arithmetic_result!(product_tool::multiply_all(input))
+    }
+
+    fn min(numbers: Vec<f64>) -> Result<ArithmeticResult, String> {
+        let input = min_tool::LogicInput { numbers };
+        arithmetic_result!(min_tool::find_minimum(input))
+    }
+
+    fn max(numbers: Vec<f64>) -> Result<ArithmeticResult, String> {
+        let input = max_tool::LogicInput { numbers };
+        arithmetic_result!(max_tool::find_maximum(input))
+    }
+
+    fn mean(numbers: Vec<f64>) -> Result<ArithmeticResult, String> {
+        let input = mean_tool::LogicInput { numbers };
+        arithmetic_result!(mean_tool::calculate_mean(input))
+    }
+
+    fn compare_numbers(
+        a: f64,
+        b: f64,
+        options: CompareOptions,
+    ) -> Result<ComparisonResult, String> {
+        let input = compare_numbers_tool::LogicInput {
+            a,
+            b,
+            absolute_tolerance: options.absolute_tolerance,
+            relative_tolerance: options.relative_tolerance,
+            max_ulps: options.max_ulps,
+        };
+        compare_numbers_tool::compare_values(input).map(|r| ComparisonResult {
+            a: r.a,
+            b: r.b,
+            difference: r.difference,
+            absolute_difference: r.absolute_difference,
+            relative_difference: r.relative_difference,
+            percent_error: r.percent_error,
+            percent_difference: r.percent_difference,
+            ulp_distance: r.ulp_distance,
+            ordering: r.ordering,
+            exactly_equal: r.exactly_equal,
+            approximately_equal: r.approximately_equal,
+            within_ulps: r.within_ulps,
+            absolute_tolerance: r.absolute_tolerance,
+            relative_tolerance: r.relative_tolerance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic_matches_tools() {
+        let result = BasicMath::add(2.0, 3.0).unwrap();
+        assert_eq!(result.result, 5.0);
+        assert_eq!(result.operation, "addition");
+        assert_eq!(result.inputs, vec![2.0, 3.0]);
+
+        assert_eq!(BasicMath::square(4.0).unwrap().result, 16.0);
+        assert_eq!(BasicMath::mean(vec![1.0, 2.0, 3.0]).unwrap().result, 2.0);
+        assert_eq!(BasicMath::sqrt(9.0).unwrap().result, 3.0);
+    }
+
+    #[test]
+    fn test_errors_are_passed_through() {
+        assert_eq!(
+            BasicMath::divide(1.0, 0.0).unwrap_err(),
+            divide_tool::divide_numbers(divide_tool::LogicInput { a: 1.0, b: 0.0 }).unwrap_err()
+        );
+        assert!(BasicMath::sum(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_records() {
+        let result =
+            BasicMath::distance_two_d(Point2d { x: 0.0, y: 0.0 }, Point2d { x: 3.0, y: 4.0 })
+                .unwrap();
+        assert_eq!(result.distance, 5.0);
+        assert_eq!(result.point2.y, 4.0);
+
+        let options = CompareOptions {
+            absolute_tolerance: None,
+            relative_tolerance: None,
+            max_ulps: Some(1),
+        };
+        let result = BasicMath::compare_numbers(0.1 + 0.2, 0.3, options).unwrap();
+        assert!(result.approximately_equal);
+        assert_eq!(result.within_ulps, Some(true));
+    }
+}
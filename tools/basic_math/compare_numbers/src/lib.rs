@@ -0,0 +1,140 @@
+use ftl_precision::Precision;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(test)))]
+use ftl_sdk::tool;
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    CompareNumbersInput as LogicInput, ComparisonResult as LogicOutput, compare_values,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompareNumbersInput {
+    /// Value to check, e.g. a result returned by another tool
+    pub a: f64,
+    /// Reference (expected) value; relative differences are taken against it
+    pub b: f64,
+    /// Largest absolute difference still counted as approximately equal (default: 0)
+    pub absolute_tolerance: Option<f64>,
+    /// Largest difference relative to the larger magnitude still counted as
+    /// approximately equal (default: 1e-9)
+    pub relative_tolerance: Option<f64>,
+    /// Also report whether the values are at most this many representable doubles apart
+    pub max_ulps: Option<u64>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComparisonResult {
+    /// Value that was checked
+    pub a: f64,
+    /// Reference value
+    pub b: f64,
+    /// Signed difference a - b
+    pub difference: f64,
+    /// |a - b|
+    pub absolute_difference: f64,
+    /// |a - b| / |b| (absent when b is 0)
+    pub relative_difference: Option<f64>,
+    /// Relative difference as a percentage of b (absent when b is 0)
+    pub percent_error: Option<f64>,
+    /// |a - b| as a percentage of the mean of |a| and |b|
+    pub percent_difference: f64,
+    /// Number of representable doubles between a and b
+    pub ulp_distance: u64,
+    /// How a compares to b: "less", "equal" or "greater"
+    pub ordering: String,
+    /// Whether a and b are exactly the same number
+    pub exactly_equal: bool,
+    /// Whether |a - b| is within the relative or absolute tolerance
+    pub approximately_equal: bool,
+    /// Whether ulp_distance is at most max_ulps (present when max_ulps is given)
+    pub within_ulps: Option<bool>,
+    /// Absolute tolerance used
+    pub absolute_tolerance: f64,
+    /// Relative tolerance used
+    pub relative_tolerance: f64,
+}
+
+/// Compare two numbers: absolute, relative and percent difference, ULP
+/// distance, and whether they are approximately equal within a tolerance
+#[cfg(feature = "individual")]
+#[cfg_attr(not(test), tool)]
+pub fn compare_numbers(request: Request<CompareNumbersInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "compare_numbers",
+                CompareNumbersInput,
+                ComparisonResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("compare_numbers", &input);
+    log.finish(run_compare_numbers(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_compare_numbers(input: CompareNumbersInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        a: input.a,
+        b: input.b,
+        absolute_tolerance: input.absolute_tolerance,
+        relative_tolerance: input.relative_tolerance,
+        max_ulps: input.max_ulps,
+    };
+
+    // Call logic implementation
+    match logic::compare_values(logic_input) {
+        Ok(result) => {
+            // Convert back to wrapper types
+            let response = ComparisonResult {
+                a: result.a,
+                b: result.b,
+                difference: result.difference,
+                absolute_difference: result.absolute_difference,
+                relative_difference: result.relative_difference,
+                percent_error: result.percent_error,
+                percent_difference: result.percent_difference,
+                ulp_distance: result.ulp_distance,
+                ordering: result.ordering,
+                exactly_equal: result.exactly_equal,
+                approximately_equal: result.approximately_equal,
+                within_ulps: result.within_ulps,
+                absolute_tolerance: result.absolute_tolerance,
+                relative_tolerance: result.relative_tolerance,
+            };
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(json) => ToolResponse::text(json),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
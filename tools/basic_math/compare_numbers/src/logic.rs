@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+
+/// Default relative tolerance, as in Python's `math.isclose`
+pub const DEFAULT_RELATIVE_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareNumbersInput {
+    pub a: f64,
+    pub b: f64,
+    pub absolute_tolerance: Option<f64>,
+    pub relative_tolerance: Option<f64>,
+    pub max_ulps: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonResult {
+    pub a: f64,
+    pub b: f64,
+    pub difference: f64,
+    pub absolute_difference: f64,
+    pub relative_difference: Option<f64>,
+    pub percent_error: Option<f64>,
+    pub percent_difference: f64,
+    pub ulp_distance: u64,
+    pub ordering: String,
+    pub exactly_equal: bool,
+    pub approximately_equal: bool,
+    pub within_ulps: Option<bool>,
+    pub absolute_tolerance: f64,
+    pub relative_tolerance: f64,
+}
+
+pub fn compare_values(input: CompareNumbersInput) -> Result<ComparisonResult, String> {
+    // Validate input - check for invalid values
+    if !input.a.is_finite() || !input.b.is_finite() {
+        return Err("Input contains invalid values (NaN or Infinite)".to_string());
+    }
+    let absolute_tolerance = tolerance("absolute_tolerance", input.absolute_tolerance, 0.0)?;
+    let relative_tolerance = tolerance(
+        "relative_tolerance",
+        input.relative_tolerance,
+        DEFAULT_RELATIVE_TOLERANCE,
+    )?;
+
+    let (a, b) = (input.a, input.b);
+    let absolute_difference = (a - b).abs();
+
+    // Relative to `b`, the reference value; undefined when it is zero
+    let relative_difference = (b != 0.0).then(|| absolute_difference / b.abs());
+    // Relative to the mean magnitude of both values
+    let mean_magnitude = (a.abs() + b.abs()) / 2.0;
+    let percent_difference = if mean_magnitude == 0.0 {
+        0.0
+    } else {
+        absolute_difference / mean_magnitude * 100.0
+    };
+
+    let ulp_distance = ulp_distance(a, b);
+    let approximately_equal =
+        absolute_difference <= (relative_tolerance * a.abs().max(b.abs())).max(absolute_tolerance);
+
+    Ok(ComparisonResult {
+        a,
+        b,
+        difference: a - b,
+        absolute_difference,
+        relative_difference,
+        percent_error: relative_difference.map(|r| r * 100.0),
+        percent_difference,
+        ulp_distance,
+        ordering: match a.partial_cmp(&b) {
+            Some(std::cmp::Ordering::Less) => "less",
+            Some(std::cmp::Ordering::Greater) => "greater",
+            _ => "equal",
+        }
+        .to_string(),
+        exactly_equal: a == b,
+        approximately_equal,
+        within_ulps: input.max_ulps.map(|max| ulp_distance <= max),
+        absolute_tolerance,
+        relative_tolerance,
+    })
+}
+
+fn tolerance(name: &str, value: Option<f64>, default: f64) -> Result<f64, String> {
+    match value {
+        None => Ok(default),
+        Some(t) if t.is_finite() && t >= 0.0 => Ok(t),
+        Some(t) => Err(format!(
+            "{name} must be a finite, non-negative number, got {t}"
+        )),
+    }
+}
+
+/// Number of representable doubles between `a` and `b`; 0.0 and -0.0 are the same
+fn ulp_distance(a: f64, b: f64) -> u64 {
+    // Map the bit patterns onto a line where adjacent doubles differ by one
+    fn ordered(x: f64) -> i64 {
+        let bits = x.to_bits() as i64;
+        if bits < 0 {
+            i64::MIN.wrapping_sub(bits)
+        } else {
+            bits
+        }
+    }
+    (i128::from(ordered(a)) - i128::from(ordered(b))).unsigned_abs() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(a: f64, b: f64) -> ComparisonResult {
+        compare_values(CompareNumbersInput {
+            a,
+            b,
+            absolute_tolerance: None,
+            relative_tolerance: None,
+            max_ulps: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_differences() {
+        let result = compare(105.0, 100.0);
+        assert_eq!(result.difference, 5.0);
+        assert_eq!(result.absolute_difference, 5.0);
+        assert_eq!(result.relative_difference, Some(0.05));
+        assert_eq!(result.percent_error, Some(5.0));
+        assert!((result.percent_difference - 5.0 / 102.5 * 100.0).abs() < 1e-12);
+        assert_eq!(result.ordering, "greater");
+        assert!(!result.exactly_equal);
+        assert!(!result.approximately_equal);
+    }
+
+    #[test]
+    fn test_floating_point_noise_is_approximately_equal() {
+        let result = compare(0.1 + 0.2, 0.3);
+        assert!(!result.exactly_equal);
+        assert!(result.approximately_equal);
+        assert_eq!(result.ulp_distance, 1);
+        assert_eq!(result.ordering, "greater");
+    }
+
+    #[test]
+    fn test_zero_reference() {
+        let result = compare(0.0, 0.0);
+        assert_eq!(result.relative_difference, None);
+        assert_eq!(result.percent_error, None);
+        assert_eq!(result.percent_difference, 0.0);
+        assert!(result.exactly_equal);
+
+        let result = compare(1e-12, 0.0);
+        assert_eq!(result.relative_difference, None);
+        // A relative tolerance alone never matches zero
+        assert!(!result.approximately_equal);
+    }
+
+    #[test]
+    fn test_ulp_distance() {
+        assert_eq!(ulp_distance(1.0, 1.0), 0);
+        assert_eq!(ulp_distance(0.0, -0.0), 0);
+        assert_eq!(ulp_distance(1.0, f64::from_bits(1.0f64.to_bits() + 3)), 3);
+        // Across zero: the smallest positive and negative subnormals are two apart
+        assert_eq!(ulp_distance(f64::from_bits(1), -f64::from_bits(1)), 2);
+        assert_eq!(ulp_distance(f64::MAX, -f64::MAX), 2 * f64::MAX.to_bits());
+    }
+
+    #[test]
+    fn test_tolerance_options() {
+        let input = CompareNumbersInput {
+            a: 1e-12,
+            b: 0.0,
+            absolute_tolerance: Some(1e-9),
+            relative_tolerance: Some(0.0),
+            max_ulps: Some(4),
+        };
+        let result = compare_values(input).unwrap();
+        assert!(result.approximately_equal);
+        assert_eq!(result.within_ulps, Some(false));
+        assert_eq!(result.absolute_tolerance, 1e-9);
+
+        let input = CompareNumbersInput {
+            a: 1.0,
+            b: 1.1,
+            absolute_tolerance: None,
+            relative_tolerance: Some(0.1),
+            max_ulps: None,
+        };
+        let result = compare_values(input).unwrap();
+        assert!(result.approximately_equal);
+        assert_eq!(result.within_ulps, None);
+    }
+
+    #[test]
+    fn test_invalid_input_errors() {
+        let input = CompareNumbersInput {
+            a: f64::NAN,
+            b: 1.0,
+            absolute_tolerance: None,
+            relative_tolerance: None,
+            max_ulps: None,
+        };
+        assert_eq!(
+            compare_values(input).unwrap_err(),
+            "Input contains invalid values (NaN or Infinite)"
+        );
+
+        let input = CompareNumbersInput {
+            a: 1.0,
+            b: 1.0,
+            absolute_tolerance: Some(-1.0),
+            relative_tolerance: None,
+            max_ulps: None,
+        };
+        assert_eq!(
+            compare_values(input).unwrap_err(),
+            "absolute_tolerance must be a finite, non-negative number, got -1"
+        );
+    }
+}
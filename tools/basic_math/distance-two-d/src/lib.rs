@@ -1,14 +1,25 @@
+use ftl_precision::Precision;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[cfg(all(feature = "individual", not(test)))]
 use ftl_sdk::tool;
 
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
 #[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
 mod logic;
 
+// Re-export types from logic module
+pub use logic::{
+    DistanceResult as LogicOutput, Point2D as LogicPoint, TwoPointInput as LogicInput,
+    calculate_distance_2d,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Point2D {
     /// X coordinate
@@ -27,6 +38,12 @@ pub struct TwoPointInput {
     pub x2: f64,
     /// Y coordinate of second point
     pub y2: f64,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -44,8 +61,29 @@ pub struct DistanceResult {
 }
 
 /// Calculate the distance between two 2D points using the Pythagorean theorem
+#[cfg(feature = "individual")]
 #[cfg_attr(not(test), tool)]
-pub fn distance_2d(input: TwoPointInput) -> ToolResponse {
+pub fn distance_2d(request: Request<TwoPointInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("distance_2d", TwoPointInput, DistanceResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("distance_2d", &input);
+    log.finish(run_distance_2d(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_distance_2d(input: TwoPointInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     // Convert from flat coordinate input to logic types
     let logic_input = logic::TwoPointInput {
         point1: logic::Point2D {
@@ -75,7 +113,10 @@ pub fn distance_2d(input: TwoPointInput) -> ToolResponse {
                 delta_x: result.delta_x,
                 delta_y: result.delta_y,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(json) => ToolResponse::text(json),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NumberListInput {
+    pub numbers: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArithmeticResult {
+    pub result: f64,
+    pub operation: String,
+    pub inputs: Vec<f64>,
+}
+
+pub fn find_maximum(input: NumberListInput) -> Result<ArithmeticResult, String> {
+    if input.numbers.is_empty() {
+        return Err("Input list cannot be empty".to_string());
+    }
+
+    // Validate input - check for invalid values
+    if input.numbers.iter().any(|n| !n.is_finite()) {
+        return Err("Input contains invalid values (NaN or Infinite)".to_string());
+    }
+
+    let result = input
+        .numbers
+        .iter()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    Ok(ArithmeticResult {
+        result,
+        operation: "maximum".to_string(),
+        inputs: input.numbers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(numbers: Vec<f64>) -> Result<ArithmeticResult, String> {
+        find_maximum(NumberListInput { numbers })
+    }
+
+    #[test]
+    fn test_several_numbers() {
+        let result = run(vec![3.0, -7.5, 2.0, 10.0]).unwrap();
+        assert_eq!(result.result, 10.0);
+        assert_eq!(result.operation, "maximum");
+        assert_eq!(result.inputs, vec![3.0, -7.5, 2.0, 10.0]);
+    }
+
+    #[test]
+    fn test_all_negative() {
+        let result = run(vec![-4.0, -2.0]).unwrap();
+        assert_eq!(result.result, -2.0);
+    }
+
+    #[test]
+    fn test_empty_input_error() {
+        assert_eq!(run(vec![]).unwrap_err(), "Input list cannot be empty");
+    }
+
+    #[test]
+    fn test_invalid_values_error() {
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                run(vec![1.0, bad]).unwrap_err(),
+                "Input contains invalid values (NaN or Infinite)"
+            );
+        }
+    }
+}
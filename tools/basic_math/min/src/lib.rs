@@ -0,0 +1,94 @@
+use ftl_precision::Precision;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(test)))]
+use ftl_sdk::tool;
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{ArithmeticResult as LogicOutput, NumberListInput as LogicInput, find_minimum};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NumberListInput {
+    /// Numbers to compare (at least one)
+    pub numbers: Vec<f64>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArithmeticResult {
+    /// The calculated result
+    pub result: f64,
+    /// The operation performed
+    pub operation: String,
+    /// The input values
+    pub inputs: Vec<f64>,
+}
+
+/// Find the smallest of any number of values
+#[cfg(feature = "individual")]
+#[cfg_attr(not(test), tool)]
+pub fn min(request: Request<NumberListInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("min", NumberListInput, ArithmeticResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("min", &input);
+    log.finish(run_min(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_min(input: NumberListInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().array("numbers", &input.numbers).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        numbers: input.numbers,
+    };
+
+    // Call logic implementation
+    match logic::find_minimum(logic_input) {
+        Ok(result) => {
+            // Convert back to wrapper types
+            let response = ArithmeticResult {
+                result: result.result,
+                operation: result.operation,
+                inputs: result.inputs,
+            };
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(json) => ToolResponse::text(json),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -1,6 +1,11 @@
+use ftl_precision::Precision;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
 #[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 #[cfg(all(feature = "individual", not(test)))]
@@ -9,7 +14,7 @@ use ftl_sdk::tool;
 mod logic;
 
 // Re-export types from logic module
-pub use logic::{ArithmeticResult as LogicOutput, TwoNumberInput as LogicInput};
+pub use logic::{ArithmeticResult as LogicOutput, TwoNumberInput as LogicInput, multiply_numbers};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +23,12 @@ pub struct TwoNumberInput {
     pub a: f64,
     /// Second number  
     pub b: f64,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -31,8 +42,29 @@ pub struct ArithmeticResult {
 }
 
 /// Multiply two numbers together
+#[cfg(feature = "individual")]
 #[cfg_attr(not(test), tool)]
-pub fn multiply(input: TwoNumberInput) -> ToolResponse {
+pub fn multiply(request: Request<TwoNumberInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("multiply", TwoNumberInput, ArithmeticResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("multiply", &input);
+    log.finish(run_multiply(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_multiply(input: TwoNumberInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     // Convert to logic types
     let logic_input = LogicInput {
         a: input.a,
@@ -48,7 +80,10 @@ pub fn multiply(input: TwoNumberInput) -> ToolResponse {
                 operation: result.operation,
                 inputs: result.inputs,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(json) => ToolResponse::text(json),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,3 +1,4 @@
+use ftl_precision::Precision;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -6,11 +7,17 @@ mod logic;
 #[cfg(all(feature = "individual", not(test)))]
 use ftl_sdk::tool;
 
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
 #[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
 // Re-export types from logic module
-pub use logic::{PythagoreanInput as LogicInput, PythagoreanResult as LogicOutput};
+pub use logic::{
+    PythagoreanInput as LogicInput, PythagoreanResult as LogicOutput, calculate_pythagorean,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -19,6 +26,14 @@ pub struct PythagoreanInput {
     pub a: f64,
     /// Second leg of right triangle
     pub b: f64,
+    /// Only return these result fields, e.g. ["hypotenuse"] (default: all)
+    pub fields: Option<Vec<String>>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -38,8 +53,33 @@ pub struct PythagoreanResult {
 }
 
 /// Calculate the hypotenuse of a right triangle using the Pythagorean theorem: c = sqrt(a² + b²)
+#[cfg(feature = "individual")]
 #[cfg_attr(not(test), tool)]
-pub fn pythagorean(input: PythagoreanInput) -> ToolResponse {
+pub fn pythagorean(request: Request<PythagoreanInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "pythagorean",
+                PythagoreanInput,
+                PythagoreanResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("pythagorean", &input);
+    log.finish(run_pythagorean(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_pythagorean(input: PythagoreanInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     // Convert to logic types
     let logic_input = LogicInput {
         a: input.a,
@@ -63,7 +103,14 @@ pub fn pythagorean(input: PythagoreanInput) -> ToolResponse {
                 b_squared,
                 sum_of_squares,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(
+                &response,
+                input.fields.as_deref(),
+                precision.as_ref(),
+            ) {
+                Ok(json) => ToolResponse::text(json),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NumberListInput {
+    pub numbers: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArithmeticResult {
+    pub result: f64,
+    pub operation: String,
+    pub inputs: Vec<f64>,
+}
+
+pub fn sum_numbers(input: NumberListInput) -> Result<ArithmeticResult, String> {
+    if input.numbers.is_empty() {
+        return Err("Input list cannot be empty".to_string());
+    }
+
+    // Validate input - check for invalid values
+    if input.numbers.iter().any(|n| !n.is_finite()) {
+        return Err("Input contains invalid values (NaN or Infinite)".to_string());
+    }
+
+    let result = input.numbers.iter().sum();
+
+    Ok(ArithmeticResult {
+        result,
+        operation: "sum".to_string(),
+        inputs: input.numbers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(numbers: Vec<f64>) -> Result<ArithmeticResult, String> {
+        sum_numbers(NumberListInput { numbers })
+    }
+
+    #[test]
+    fn test_several_numbers() {
+        let result = run(vec![1.5, 2.5, 3.0, -1.0]).unwrap();
+        assert_eq!(result.result, 6.0);
+        assert_eq!(result.operation, "sum");
+        assert_eq!(result.inputs, vec![1.5, 2.5, 3.0, -1.0]);
+    }
+
+    #[test]
+    fn test_single_number() {
+        let result = run(vec![42.0]).unwrap();
+        assert_eq!(result.result, 42.0);
+    }
+
+    #[test]
+    fn test_empty_input_error() {
+        assert_eq!(run(vec![]).unwrap_err(), "Input list cannot be empty");
+    }
+
+    #[test]
+    fn test_invalid_values_error() {
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                run(vec![1.0, bad]).unwrap_err(),
+                "Input contains invalid values (NaN or Infinite)"
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    Adjustment as LogicAdjustment, ColorPair as LogicColorPair, ContrastAuditInput as LogicInput,
+    ContrastAuditOutput as LogicOutput, MAX_PAIRS, PairAudit as LogicPairAudit,
+    contrast_audit_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    AdjustmentSchema as Adjustment, ColorPairSchema as ColorPair,
+    ContrastAuditOutputSchema as ContrastAuditOutput, PairAuditSchema as PairAudit,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContrastAuditInput {
+    #[serde(flatten)]
+    pub params: logic::ContrastAuditInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Audit foreground/background pairs against WCAG AA/AAA and APCA, suggesting the
+/// nearest passing colors for pairs that fall short
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn contrast_audit(request: Request<ContrastAuditInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "contrast_audit",
+                ContrastAuditInput,
+                ContrastAuditOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("contrast_audit", &input);
+    log.finish(run_contrast_audit(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_contrast_audit(input: ContrastAuditInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("pairs", input.params.pairs.as_deref().unwrap_or_default())
+        .array(
+            "palette",
+            input.params.palette.as_deref().unwrap_or_default(),
+        )
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::contrast_audit_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &ContrastAuditOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
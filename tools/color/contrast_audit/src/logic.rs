@@ -0,0 +1,488 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most pairs audited in one call
+pub const MAX_PAIRS: usize = 1000;
+
+/// WCAG 2.x contrast ratios for levels AA and AAA, normal and large text
+const AA_NORMAL: f64 = 4.5;
+const AA_LARGE: f64 = 3.0;
+const AAA_NORMAL: f64 = 7.0;
+const AAA_LARGE: f64 = 4.5;
+
+/// Lightness step when searching for a passing adjustment
+const LIGHTNESS_STEP: f64 = 0.25;
+
+/// D65 reference white
+const WHITE: [f64; 3] = [0.95047, 1.0, 1.08883];
+
+const SRGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.4124564, 0.3575761, 0.1804375],
+    [0.2126729, 0.7151522, 0.0721750],
+    [0.0193339, 0.1191920, 0.9503041],
+];
+
+const XYZ_TO_SRGB: [[f64; 3]; 3] = [
+    [3.2404542, -1.5371385, -0.4985314],
+    [-0.9692660, 1.8760108, 0.0415560],
+    [0.0556434, -0.2040259, 1.0572252],
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ColorPair {
+    /// Text or foreground color, as #rgb or #rrggbb
+    pub foreground: String,
+    /// Background color, as #rgb or #rrggbb
+    pub background: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ContrastAuditInput {
+    /// Foreground/background pairs to audit
+    #[tool_types(nested)]
+    pub pairs: Option<Vec<ColorPair>>,
+    /// Palette colors to audit as foregrounds against `background`
+    pub palette: Option<Vec<String>>,
+    /// Background for the palette colors (default: "#ffffff")
+    pub background: Option<String>,
+    /// WCAG level that adjustments must reach: "AA" or "AAA" (default: AA)
+    pub level: Option<String>,
+    /// Whether adjustments only need to pass for large text, 18pt or 14pt bold
+    /// (default: false)
+    pub large_text: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Adjustment {
+    /// Which color was changed: "foreground" or "background"
+    pub adjusted: String,
+    /// Adjusted color as #rrggbb, same hue with lightness moved as little as possible
+    pub color: String,
+    /// WCAG contrast ratio after the change
+    pub contrast_ratio: f64,
+    /// CIE76 difference between the original and adjusted color
+    pub delta_e: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PairAudit {
+    /// Foreground as #rrggbb
+    pub foreground: String,
+    /// Background as #rrggbb
+    pub background: String,
+    /// WCAG 2.x contrast ratio, 1 to 21
+    pub contrast_ratio: f64,
+    /// Passes AA for normal text (4.5:1)
+    pub aa_normal: bool,
+    /// Passes AA for large text (3:1)
+    pub aa_large: bool,
+    /// Passes AAA for normal text (7:1)
+    pub aaa_normal: bool,
+    /// Passes AAA for large text (4.5:1)
+    pub aaa_large: bool,
+    /// APCA lightness contrast Lc (APCA 0.0.98G); negative for light text on a dark
+    /// background
+    pub apca: f64,
+    /// Most demanding use the APCA score supports: "fluent text" (|Lc| 90+), "body text"
+    /// (75+), "content text" (60+), "large text" (45+), "non-text" (30+) or "insufficient"
+    pub apca_use: String,
+    /// Whether the pair meets the requested level and text size
+    pub passes: bool,
+    /// Nearest passing colors when the pair fails, closest first
+    #[tool_types(nested)]
+    pub adjustments: Vec<Adjustment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ContrastAuditOutput {
+    /// Level checked for `passes` and adjustments
+    pub level: String,
+    /// Whether the large text thresholds were used
+    pub large_text: bool,
+    /// Contrast ratio the level and text size require
+    pub required_ratio: f64,
+    /// One audit per pair, pairs first and then palette colors
+    #[tool_types(nested)]
+    pub results: Vec<PairAudit>,
+    /// Pairs meeting the requirement
+    pub passing: usize,
+    /// Pairs falling short
+    pub failing: usize,
+}
+
+type V = [f64; 3];
+
+fn apply(m: &[[f64; 3]; 3], v: V) -> V {
+    [0, 1, 2].map(|r| m[r][0] * v[0] + m[r][1] * v[1] + m[r][2] * v[2])
+}
+
+fn to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn lab_to_linear(lab: V) -> V {
+    const DELTA: f64 = 6.0 / 29.0;
+    let finv = |t: f64| {
+        if t > DELTA {
+            t * t * t
+        } else {
+            3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+        }
+    };
+    let fy = (lab[0] + 16.0) / 116.0;
+    let xyz = [fy + lab[1] / 500.0, fy, fy - lab[2] / 200.0];
+    apply(&XYZ_TO_SRGB, [0, 1, 2].map(|i| WHITE[i] * finv(xyz[i])))
+}
+
+fn linear_to_lab(rgb: V) -> V {
+    const DELTA: f64 = 6.0 / 29.0;
+    let f = |t: f64| {
+        if t > DELTA * DELTA * DELTA {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+    let xyz = apply(&SRGB_TO_XYZ, rgb);
+    let [fx, fy, fz] = [0, 1, 2].map(|i| f(xyz[i] / WHITE[i]));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn lch_to_lab(l: f64, c: f64, h: f64) -> V {
+    let h = h.to_radians();
+    [l, c * h.cos(), c * h.sin()]
+}
+
+fn lab_to_lch(lab: V) -> V {
+    let c = lab[1].hypot(lab[2]);
+    let h = lab[2].atan2(lab[1]).to_degrees().rem_euclid(360.0);
+    [lab[0], c, if c < 1e-9 { 0.0 } else { h }]
+}
+
+fn in_gamut(rgb: V) -> bool {
+    rgb.iter().all(|c| (-1e-9..=1.0 + 1e-9).contains(c))
+}
+
+/// Linear sRGB of the LCh color, lowering chroma until it fits the sRGB gamut
+fn lch_to_linear(l: f64, c: f64, h: f64) -> V {
+    let rgb = lab_to_linear(lch_to_lab(l, c, h));
+    if in_gamut(rgb) {
+        return rgb;
+    }
+    let (mut low, mut high) = (0.0, c);
+    for _ in 0..40 {
+        let mid = (low + high) / 2.0;
+        if in_gamut(lab_to_linear(lch_to_lab(l, mid, h))) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    lab_to_linear(lch_to_lab(l, low, h))
+}
+
+/// Rounds to 8-bit sRGB; returns the hex string and the linear value actually shown
+fn quantize(rgb: V) -> (String, V) {
+    let bytes = rgb.map(|c| (from_linear(c.clamp(0.0, 1.0)) * 255.0).round() as u8);
+    let hex = format!("#{:02x}{:02x}{:02x}", bytes[0], bytes[1], bytes[2]);
+    (hex, bytes.map(|b| to_linear(b as f64 / 255.0)))
+}
+
+fn parse_hex(hex: &str) -> Result<V, String> {
+    let digits = hex.trim().trim_start_matches('#');
+    let expanded: String = match digits.len() {
+        _ if !digits.is_ascii() => {
+            return Err(format!("Invalid hex color '{hex}', use #rgb or #rrggbb"));
+        }
+        3 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 => digits.to_string(),
+        _ => return Err(format!("Invalid hex color '{hex}', use #rgb or #rrggbb")),
+    };
+    let channel = |i: usize| {
+        u8::from_str_radix(&expanded[i..i + 2], 16)
+            .map(|b| to_linear(b as f64 / 255.0))
+            .map_err(|_| format!("Invalid hex color '{hex}', use #rgb or #rrggbb"))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn luminance(rgb: V) -> f64 {
+    apply(&SRGB_TO_XYZ, rgb)[1]
+}
+
+fn contrast(a: V, b: V) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+fn delta_e(a: V, b: V) -> f64 {
+    let (a, b) = (linear_to_lab(a), linear_to_lab(b));
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// APCA 0.0.98G-4g lightness contrast of text on a background
+fn apca(text: V, background: V) -> f64 {
+    let screen_luminance = |rgb: V| {
+        let y = apply(&SRGB_TO_XYZ, rgb.map(|c| from_linear(c).powf(2.4)))[1];
+        // Soft clamp for near-black colors
+        if y < 0.022 {
+            y + (0.022 - y).powf(1.414)
+        } else {
+            y
+        }
+    };
+    let (yt, yb) = (screen_luminance(text), screen_luminance(background));
+    if (yb - yt).abs() < 0.0005 {
+        return 0.0;
+    }
+    let lc = if yb > yt {
+        let sapc = (yb.powf(0.56) - yt.powf(0.57)) * 1.14;
+        if sapc < 0.1 { 0.0 } else { sapc - 0.027 }
+    } else {
+        let sapc = (yb.powf(0.65) - yt.powf(0.62)) * 1.14;
+        if sapc > -0.1 { 0.0 } else { sapc + 0.027 }
+    };
+    lc * 100.0
+}
+
+fn apca_use(lc: f64) -> &'static str {
+    match lc.abs() {
+        lc if lc >= 90.0 => "fluent text",
+        lc if lc >= 75.0 => "body text",
+        lc if lc >= 60.0 => "content text",
+        lc if lc >= 45.0 => "large text",
+        lc if lc >= 30.0 => "non-text",
+        _ => "insufficient",
+    }
+}
+
+/// Moves the lightness of `moving` up and down in CIELCh, keeping its hue, until it
+/// reaches `required` against `fixed`; returns the passing color closest to the original
+fn adjust(moving: V, fixed: V, required: f64) -> Option<(String, f64, f64)> {
+    let [lightness, chroma, hue] = lab_to_lch(linear_to_lab(moving));
+    [1.0, -1.0]
+        .into_iter()
+        .filter_map(|direction| {
+            let mut l = lightness;
+            loop {
+                l = (l + direction * LIGHTNESS_STEP).clamp(0.0, 100.0);
+                let (hex, shown) = quantize(lch_to_linear(l, chroma, hue));
+                let ratio = contrast(shown, fixed);
+                if ratio >= required {
+                    return Some((hex, ratio, delta_e(moving, shown)));
+                }
+                if l == 0.0 || l == 100.0 {
+                    return None;
+                }
+            }
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+}
+
+fn audit_pair(foreground: V, background: V, required: f64) -> PairAudit {
+    let ratio = contrast(foreground, background);
+    let passes = ratio >= required;
+    let mut adjustments = Vec::new();
+    if !passes {
+        for (adjusted, moving, fixed) in [
+            ("foreground", foreground, background),
+            ("background", background, foreground),
+        ] {
+            if let Some((color, contrast_ratio, delta_e)) = adjust(moving, fixed, required) {
+                adjustments.push(Adjustment {
+                    adjusted: adjusted.to_string(),
+                    color,
+                    contrast_ratio,
+                    delta_e,
+                });
+            }
+        }
+        adjustments.sort_by(|a, b| a.delta_e.total_cmp(&b.delta_e));
+    }
+    let lc = apca(foreground, background);
+    PairAudit {
+        foreground: quantize(foreground).0,
+        background: quantize(background).0,
+        contrast_ratio: ratio,
+        aa_normal: ratio >= AA_NORMAL,
+        aa_large: ratio >= AA_LARGE,
+        aaa_normal: ratio >= AAA_NORMAL,
+        aaa_large: ratio >= AAA_LARGE,
+        apca: lc,
+        apca_use: apca_use(lc).to_string(),
+        passes,
+        adjustments,
+    }
+}
+
+pub fn contrast_audit_logic(input: ContrastAuditInput) -> Result<ContrastAuditOutput, String> {
+    let level = input
+        .level
+        .as_deref()
+        .map(|l| l.trim().to_uppercase())
+        .unwrap_or_else(|| "AA".to_string());
+    let large_text = input.large_text.unwrap_or(false);
+    let required_ratio = match (level.as_str(), large_text) {
+        ("AA", false) => AA_NORMAL,
+        ("AA", true) => AA_LARGE,
+        ("AAA", false) => AAA_NORMAL,
+        ("AAA", true) => AAA_LARGE,
+        _ => return Err(format!("Unknown level '{level}', use 'AA' or 'AAA'")),
+    };
+
+    let mut pairs = Vec::new();
+    for pair in input.pairs.unwrap_or_default() {
+        pairs.push((parse_hex(&pair.foreground)?, parse_hex(&pair.background)?));
+    }
+    let palette = input.palette.unwrap_or_default();
+    if !palette.is_empty() {
+        let background = parse_hex(input.background.as_deref().unwrap_or("#ffffff"))?;
+        for color in &palette {
+            pairs.push((parse_hex(color)?, background));
+        }
+    } else if input.background.is_some() {
+        return Err("background applies to palette colors; provide palette".to_string());
+    }
+    if pairs.is_empty() {
+        return Err("Provide pairs or a palette to audit".to_string());
+    }
+    if pairs.len() > MAX_PAIRS {
+        return Err(format!("At most {MAX_PAIRS} pairs can be audited at once"));
+    }
+
+    let results: Vec<PairAudit> = pairs
+        .into_iter()
+        .map(|(foreground, background)| audit_pair(foreground, background, required_ratio))
+        .collect();
+    let passing = results.iter().filter(|r| r.passes).count();
+    Ok(ContrastAuditOutput {
+        level,
+        large_text,
+        required_ratio,
+        failing: results.len() - passing,
+        passing,
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audit(foreground: &str, background: &str) -> PairAudit {
+        let input = ContrastAuditInput {
+            pairs: Some(vec![ColorPair {
+                foreground: foreground.to_string(),
+                background: background.to_string(),
+            }]),
+            palette: None,
+            background: None,
+            level: None,
+            large_text: None,
+        };
+        contrast_audit_logic(input).unwrap().results.remove(0)
+    }
+
+    #[test]
+    fn test_black_and_white() {
+        let result = audit("#000", "#FFFFFF");
+        assert!((result.contrast_ratio - 21.0).abs() < 1e-4);
+        assert!(result.aa_normal && result.aaa_normal && result.passes);
+        assert!(result.adjustments.is_empty());
+        assert!((result.apca - 106.04).abs() < 0.01);
+        assert_eq!(result.apca_use, "fluent text");
+        // APCA is polarity aware, WCAG 2 is not
+        let reversed = audit("#ffffff", "#000000");
+        assert!((reversed.contrast_ratio - 21.0).abs() < 1e-4);
+        assert!((reversed.apca + 107.88).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_mid_gray_thresholds() {
+        let result = audit("#888888", "#ffffff");
+        assert!((result.contrast_ratio - 3.54).abs() < 0.01);
+        assert!(result.aa_large && !result.aa_normal && !result.aaa_large);
+        assert!((result.apca - 63.06).abs() < 0.01);
+        assert_eq!(result.apca_use, "content text");
+        assert!(!result.passes);
+    }
+
+    #[test]
+    fn test_adjustments_reach_target() {
+        let result = audit("#888888", "#ffffff");
+        assert_eq!(result.adjustments.len(), 2);
+        let nearest = &result.adjustments[0];
+        assert_eq!(nearest.adjusted, "foreground");
+        assert_eq!(nearest.color, "#767676");
+        assert!(nearest.contrast_ratio >= 4.5);
+        // White can only darken, which costs more
+        let background = &result.adjustments[1];
+        assert_eq!(background.adjusted, "background");
+        assert!(background.contrast_ratio >= 4.5 && background.delta_e > nearest.delta_e);
+
+        // Hue survives the adjustment
+        let blue = audit("#6699ff", "#ffffff");
+        let color = parse_hex(&blue.adjustments[0].color).unwrap();
+        let before = lab_to_lch(linear_to_lab(parse_hex("#6699ff").unwrap()))[2];
+        assert!((lab_to_lch(linear_to_lab(color))[2] - before).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_palette_against_background() {
+        let output = contrast_audit_logic(ContrastAuditInput {
+            pairs: None,
+            palette: Some(vec!["#ffffff".into(), "#777".into(), "#1a1a1a".into()]),
+            background: Some("#000000".into()),
+            level: Some("aaa".into()),
+            large_text: Some(true),
+        })
+        .unwrap();
+        assert_eq!(output.level, "AAA");
+        assert_eq!(output.required_ratio, 4.5);
+        assert_eq!(output.passing, 2);
+        assert_eq!(output.failing, 1);
+        assert!(output.results.iter().all(|r| r.background == "#000000"));
+        assert!(!output.results[2].passes);
+        assert!(output.results[2].adjustments[0].contrast_ratio >= 4.5);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut input = ContrastAuditInput {
+            pairs: None,
+            palette: None,
+            background: None,
+            level: None,
+            large_text: None,
+        };
+        assert_eq!(
+            contrast_audit_logic(input.clone()).unwrap_err(),
+            "Provide pairs or a palette to audit"
+        );
+        input.palette = Some(vec!["#12".into()]);
+        assert!(
+            contrast_audit_logic(input.clone())
+                .unwrap_err()
+                .contains("Invalid hex")
+        );
+        input.palette = Some(vec!["#123".into()]);
+        input.level = Some("A".into());
+        assert!(
+            contrast_audit_logic(input)
+                .unwrap_err()
+                .contains("Unknown level")
+        );
+    }
+}
@@ -0,0 +1,64 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    DeltaESummary as LogicDeltaESummary, MAX_CATEGORICAL, MAX_ORDERED, PaletteColor as LogicColor,
+    PaletteInput as LogicInput, PaletteOutput as LogicOutput, generate_palette,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    DeltaESummarySchema as DeltaESummary, PaletteColorSchema as PaletteColor,
+    PaletteOutputSchema as PaletteOutput,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PaletteInput {
+    #[serde(flatten)]
+    pub params: logic::PaletteInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Generate categorical, sequential or diverging palettes spaced in CIELAB/HCL, checked
+/// for contrast and for protanopia, deuteranopia and tritanopia
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn palette_generate(request: Request<PaletteInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("palette_generate", PaletteInput, PaletteOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("palette_generate", &input);
+    log.finish(run_palette_generate(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_palette_generate(input: PaletteInput) -> ToolResponse {
+    let result = match logic::generate_palette(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&PaletteOutput::from(result))
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
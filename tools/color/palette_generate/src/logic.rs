@@ -0,0 +1,557 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most colors in a categorical palette
+pub const MAX_CATEGORICAL: usize = 20;
+/// Most colors in a sequential or diverging palette
+pub const MAX_ORDERED: usize = 64;
+/// Default WCAG contrast ratio for graphical elements (WCAG 2.1, 1.4.11)
+const DEFAULT_MIN_CONTRAST: f64 = 3.0;
+/// Default CIE76 difference below which two colors count as hard to tell apart
+const DEFAULT_MIN_DELTA_E: f64 = 10.0;
+
+/// D65 reference white
+const WHITE: [f64; 3] = [0.95047, 1.0, 1.08883];
+
+const SRGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.4124564, 0.3575761, 0.1804375],
+    [0.2126729, 0.7151522, 0.0721750],
+    [0.0193339, 0.1191920, 0.9503041],
+];
+
+const XYZ_TO_SRGB: [[f64; 3]; 3] = [
+    [3.2404542, -1.5371385, -0.4985314],
+    [-0.9692660, 1.8760108, 0.0415560],
+    [0.0556434, -0.2040259, 1.0572252],
+];
+
+/// Color vision deficiency simulations at full severity, acting on linear sRGB
+/// (Machado, Oliveira and Fernandes, 2009)
+const CVD: [(&str, [[f64; 3]; 3]); 3] = [
+    (
+        "protanopia",
+        [
+            [0.152286, 1.052583, -0.204868],
+            [0.114503, 0.786281, 0.099216],
+            [-0.003882, -0.048116, 1.051998],
+        ],
+    ),
+    (
+        "deuteranopia",
+        [
+            [0.367322, 0.860646, -0.227968],
+            [0.280085, 0.672501, 0.047413],
+            [-0.011820, 0.042940, 0.968881],
+        ],
+    ),
+    (
+        "tritanopia",
+        [
+            [1.255528, -0.076749, -0.178779],
+            [-0.078411, 0.930809, 0.147602],
+            [0.004733, 0.691367, 0.303900],
+        ],
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PaletteInput {
+    /// Palette kind: "categorical" (distinct colors for unordered groups), "sequential"
+    /// (light to dark in one hue) or "diverging" (two hues meeting at a light neutral)
+    /// (default: categorical)
+    pub kind: Option<String>,
+    /// Number of colors
+    pub count: usize,
+    /// Hue in degrees (0-360) of the sequential palette, the first diverging arm, or the
+    /// first categorical color (default: 250, blue)
+    pub hue: Option<f64>,
+    /// Hue in degrees of the second diverging arm (default: 30, orange-red)
+    pub hue_end: Option<f64>,
+    /// Background color the palette is drawn on, as hex (default: "#ffffff")
+    pub background: Option<String>,
+    /// Contrast ratio against the background below which a color gets a warning
+    /// (default: 3, the WCAG minimum for graphical objects)
+    pub min_contrast: Option<f64>,
+    /// CIE76 color difference below which two colors get a warning for being hard to
+    /// tell apart (default: 10)
+    pub min_delta_e: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct PaletteColor {
+    /// Color as #rrggbb
+    pub hex: String,
+    /// CIELAB lightness, 0-100
+    pub lightness: f64,
+    /// CIELCh chroma
+    pub chroma: f64,
+    /// CIELCh hue in degrees
+    pub hue: f64,
+    /// WCAG contrast ratio against the background
+    pub contrast: f64,
+    /// Color as seen with protanopia (no red cones)
+    pub protanopia: String,
+    /// Color as seen with deuteranopia (no green cones)
+    pub deuteranopia: String,
+    /// Color as seen with tritanopia (no blue cones)
+    pub tritanopia: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DeltaESummary {
+    pub normal: f64,
+    pub protanopia: f64,
+    pub deuteranopia: f64,
+    pub tritanopia: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PaletteOutput {
+    pub kind: String,
+    /// Palette colors, in order
+    #[tool_types(nested)]
+    pub colors: Vec<PaletteColor>,
+    /// Background used for contrast ratios, as #rrggbb
+    pub background: String,
+    /// Smallest CIE76 difference between any two colors (categorical) or neighboring
+    /// colors (sequential, diverging), for normal vision and each deficiency
+    #[tool_types(nested)]
+    pub min_delta_e: DeltaESummary,
+    /// Whether every compared pair stays at least min_delta_e apart under every
+    /// simulated deficiency
+    pub cvd_safe: bool,
+    /// Colors with low contrast and pairs that are hard to tell apart
+    pub warnings: Vec<String>,
+}
+
+type V = [f64; 3];
+
+fn apply(m: &[[f64; 3]; 3], v: V) -> V {
+    [0, 1, 2].map(|r| m[r][0] * v[0] + m[r][1] * v[1] + m[r][2] * v[2])
+}
+
+fn to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn lab_to_linear(lab: V) -> V {
+    const DELTA: f64 = 6.0 / 29.0;
+    let finv = |t: f64| {
+        if t > DELTA {
+            t * t * t
+        } else {
+            3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+        }
+    };
+    let fy = (lab[0] + 16.0) / 116.0;
+    let xyz = [fy + lab[1] / 500.0, fy, fy - lab[2] / 200.0];
+    apply(&XYZ_TO_SRGB, [0, 1, 2].map(|i| WHITE[i] * finv(xyz[i])))
+}
+
+fn linear_to_lab(rgb: V) -> V {
+    const DELTA: f64 = 6.0 / 29.0;
+    let f = |t: f64| {
+        if t > DELTA * DELTA * DELTA {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+    let xyz = apply(&SRGB_TO_XYZ, rgb);
+    let [fx, fy, fz] = [0, 1, 2].map(|i| f(xyz[i] / WHITE[i]));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn lch_to_lab(l: f64, c: f64, h: f64) -> V {
+    let h = h.to_radians();
+    [l, c * h.cos(), c * h.sin()]
+}
+
+fn lab_to_lch(lab: V) -> V {
+    let c = lab[1].hypot(lab[2]);
+    let h = lab[2].atan2(lab[1]).to_degrees().rem_euclid(360.0);
+    [lab[0], c, if c < 1e-9 { 0.0 } else { h }]
+}
+
+fn in_gamut(rgb: V) -> bool {
+    rgb.iter().all(|c| (-1e-9..=1.0 + 1e-9).contains(c))
+}
+
+/// Linear sRGB of the LCh color, lowering chroma until it fits the sRGB gamut
+fn lch_to_linear(l: f64, c: f64, h: f64) -> V {
+    let rgb = lab_to_linear(lch_to_lab(l, c, h));
+    if in_gamut(rgb) {
+        return rgb;
+    }
+    let (mut low, mut high) = (0.0, c);
+    for _ in 0..40 {
+        let mid = (low + high) / 2.0;
+        if in_gamut(lab_to_linear(lch_to_lab(l, mid, h))) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    lab_to_linear(lch_to_lab(l, low, h))
+}
+
+/// Rounds to 8-bit sRGB; returns the hex string and the linear value actually shown
+fn quantize(rgb: V) -> (String, V) {
+    let bytes = rgb.map(|c| (from_linear(c.clamp(0.0, 1.0)) * 255.0).round() as u8);
+    let hex = format!("#{:02x}{:02x}{:02x}", bytes[0], bytes[1], bytes[2]);
+    (hex, bytes.map(|b| to_linear(b as f64 / 255.0)))
+}
+
+fn parse_hex(hex: &str) -> Result<V, String> {
+    let digits = hex.trim().trim_start_matches('#');
+    let expanded: String = match digits.len() {
+        _ if !digits.is_ascii() => {
+            return Err(format!("Invalid hex color '{hex}', use #rgb or #rrggbb"));
+        }
+        3 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 => digits.to_string(),
+        _ => return Err(format!("Invalid hex color '{hex}', use #rgb or #rrggbb")),
+    };
+    let channel = |i: usize| {
+        u8::from_str_radix(&expanded[i..i + 2], 16)
+            .map(|b| to_linear(b as f64 / 255.0))
+            .map_err(|_| format!("Invalid hex color '{hex}', use #rgb or #rrggbb"))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn luminance(rgb: V) -> f64 {
+    apply(&SRGB_TO_XYZ, rgb)[1]
+}
+
+fn contrast(a: V, b: V) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+fn delta_e(a: V, b: V) -> f64 {
+    let (a, b) = (linear_to_lab(a), linear_to_lab(b));
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// The color as seen under each deficiency, in CVD order
+fn simulate(rgb: V) -> [V; 3] {
+    CVD.map(|(_, m)| apply(&m, rgb).map(|c| c.clamp(0.0, 1.0)))
+}
+
+/// Smallest difference over normal vision and every simulated deficiency
+fn worst_delta_e(a: V, b: V) -> f64 {
+    let (sa, sb) = (simulate(a), simulate(b));
+    (0..3).fold(delta_e(a, b), |worst, i| worst.min(delta_e(sa[i], sb[i])))
+}
+
+/// Greedy farthest-point selection from an in-gamut LCh grid, measuring distance by the
+/// worst case over normal vision and the simulated deficiencies
+fn categorical(count: usize, hue: f64) -> Vec<V> {
+    let mut pool: Vec<(V, V)> = Vec::new();
+    for l in [40.0, 50.0, 60.0, 70.0] {
+        for c in [35.0, 50.0, 65.0] {
+            for step in 0..36 {
+                let h = (hue + step as f64 * 10.0).rem_euclid(360.0);
+                let rgb = lab_to_linear(lch_to_lab(l, c, h));
+                if in_gamut(rgb) {
+                    pool.push(([l, c, h], quantize(rgb).1));
+                }
+            }
+        }
+    }
+    // Start from the most saturated mid-lightness color at the requested hue
+    let first = pool
+        .iter()
+        .position(|(lch, _)| lch[0] == 50.0 && lch[2] == hue.rem_euclid(360.0))
+        .unwrap_or(0);
+    let mut chosen = vec![pool.swap_remove(first)];
+    while chosen.len() < count && !pool.is_empty() {
+        let best = pool
+            .iter()
+            .enumerate()
+            .map(|(i, (_, rgb))| {
+                let spacing = chosen
+                    .iter()
+                    .map(|(_, other)| worst_delta_e(*rgb, *other))
+                    .fold(f64::INFINITY, f64::min);
+                (i, spacing)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        chosen.push(pool.swap_remove(best));
+    }
+    chosen.into_iter().map(|(_, rgb)| rgb).collect()
+}
+
+/// Lightness falls from 92 to 30 while chroma rises, in one hue
+fn sequential(count: usize, hue: f64) -> Vec<V> {
+    (0..count)
+        .map(|i| {
+            let t = i as f64 / (count - 1) as f64;
+            lch_to_linear(92.0 - 62.0 * t, 15.0 + 55.0 * t, hue)
+        })
+        .collect()
+}
+
+/// Two arms darkening away from a light neutral center
+fn diverging(count: usize, hue: f64, hue_end: f64) -> Vec<V> {
+    (0..count)
+        .map(|i| {
+            let s = 2.0 * i as f64 / (count - 1) as f64 - 1.0;
+            let h = if s < 0.0 { hue } else { hue_end };
+            lch_to_linear(92.0 - 57.0 * s.abs(), 70.0 * s.abs(), h)
+        })
+        .collect()
+}
+
+pub fn generate_palette(input: PaletteInput) -> Result<PaletteOutput, String> {
+    let kind = input
+        .kind
+        .as_deref()
+        .map(|k| k.trim().to_lowercase())
+        .unwrap_or_else(|| "categorical".to_string());
+    let limit = match kind.as_str() {
+        "categorical" => MAX_CATEGORICAL,
+        "sequential" | "diverging" => MAX_ORDERED,
+        _ => {
+            return Err(format!(
+                "Unknown kind '{kind}', use 'categorical', 'sequential' or 'diverging'"
+            ));
+        }
+    };
+    let minimum = if kind == "categorical" { 1 } else { 2 };
+    if input.count < minimum || input.count > limit {
+        return Err(format!(
+            "count must be between {minimum} and {limit} for {kind} palettes"
+        ));
+    }
+    let hue = input.hue.unwrap_or(250.0);
+    let hue_end = input.hue_end.unwrap_or(30.0);
+    if !hue.is_finite() || !hue_end.is_finite() {
+        return Err("hue and hue_end must be finite numbers of degrees".to_string());
+    }
+    let background = parse_hex(input.background.as_deref().unwrap_or("#ffffff"))?;
+    let min_contrast = input.min_contrast.unwrap_or(DEFAULT_MIN_CONTRAST);
+    if !min_contrast.is_finite() || !(1.0..=21.0).contains(&min_contrast) {
+        return Err("min_contrast must be between 1 and 21".to_string());
+    }
+    let min_delta_e = input.min_delta_e.unwrap_or(DEFAULT_MIN_DELTA_E);
+    if !min_delta_e.is_finite() || min_delta_e < 0.0 {
+        return Err("min_delta_e must be a non-negative number".to_string());
+    }
+
+    let linear: Vec<V> = match kind.as_str() {
+        "categorical" => categorical(input.count, hue),
+        "sequential" => sequential(input.count, hue),
+        _ => diverging(input.count, hue, hue_end),
+    };
+    let shown: Vec<(String, V)> = linear.into_iter().map(quantize).collect();
+
+    let mut warnings = Vec::new();
+    let colors: Vec<PaletteColor> = shown
+        .iter()
+        .enumerate()
+        .map(|(i, (hex, rgb))| {
+            let [lightness, chroma, hue] = lab_to_lch(linear_to_lab(*rgb));
+            let ratio = contrast(*rgb, background);
+            if ratio < min_contrast {
+                warnings.push(format!(
+                    "Color {i} ({hex}) has contrast {ratio:.2}:1 against the background, below {min_contrast}:1"
+                ));
+            }
+            let [protanopia, deuteranopia, tritanopia] = simulate(*rgb).map(|s| quantize(s).0);
+            PaletteColor {
+                hex: hex.clone(),
+                lightness,
+                chroma,
+                hue,
+                contrast: ratio,
+                protanopia,
+                deuteranopia,
+                tritanopia,
+            }
+        })
+        .collect();
+
+    // Every pair for categorical palettes, neighbors for ordered ones
+    let pairs: Vec<(usize, usize)> = if kind == "categorical" {
+        (0..shown.len())
+            .flat_map(|i| (i + 1..shown.len()).map(move |j| (i, j)))
+            .collect()
+    } else {
+        (1..shown.len()).map(|j| (j - 1, j)).collect()
+    };
+    let mut smallest = [f64::INFINITY; 4];
+    for &(i, j) in &pairs {
+        let (a, b) = (shown[i].1, shown[j].1);
+        let (sa, sb) = (simulate(a), simulate(b));
+        let differences = [
+            delta_e(a, b),
+            delta_e(sa[0], sb[0]),
+            delta_e(sa[1], sb[1]),
+            delta_e(sa[2], sb[2]),
+        ];
+        for (vision, difference) in ["normal vision", CVD[0].0, CVD[1].0, CVD[2].0]
+            .iter()
+            .zip(differences)
+        {
+            if difference < min_delta_e {
+                warnings.push(format!(
+                    "Colors {i} and {j} are hard to tell apart with {vision} (ΔE {difference:.1})"
+                ));
+            }
+        }
+        for (s, d) in smallest.iter_mut().zip(differences) {
+            *s = s.min(d);
+        }
+    }
+    if pairs.is_empty() {
+        smallest = [0.0; 4];
+    }
+
+    Ok(PaletteOutput {
+        kind,
+        colors,
+        background: quantize(background).0,
+        min_delta_e: DeltaESummary {
+            normal: smallest[0],
+            protanopia: smallest[1],
+            deuteranopia: smallest[2],
+            tritanopia: smallest[3],
+        },
+        cvd_safe: pairs.is_empty() || smallest.iter().all(|&d| d >= min_delta_e),
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette(kind: &str, count: usize) -> PaletteOutput {
+        generate_palette(PaletteInput {
+            kind: Some(kind.to_string()),
+            count,
+            hue: None,
+            hue_end: None,
+            background: None,
+            min_contrast: None,
+            min_delta_e: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_color_conversions() {
+        let white = linear_to_lab(parse_hex("#fff").unwrap());
+        assert!((white[0] - 100.0).abs() < 1e-3 && white[1].abs() < 1e-3 && white[2].abs() < 1e-3);
+        let red = linear_to_lab(parse_hex("#FF0000").unwrap());
+        assert!((red[0] - 53.24).abs() < 0.01);
+        assert!((red[1] - 80.09).abs() < 0.01);
+        assert!((red[2] - 67.20).abs() < 0.01);
+        assert_eq!(quantize(lab_to_linear(red)).0, "#ff0000");
+        assert!(
+            (contrast(parse_hex("#000").unwrap(), parse_hex("#fff").unwrap()) - 21.0).abs() < 1e-4
+        );
+        assert!(parse_hex("#12345").is_err());
+    }
+
+    #[test]
+    fn test_cvd_simulation() {
+        // Neutral colors look the same to everyone
+        for sim in simulate(parse_hex("#ffffff").unwrap()) {
+            assert_eq!(quantize(sim).0, "#ffffff");
+        }
+        // Red and green collapse together without green cones, but not without blue ones
+        let (red, green) = (parse_hex("#d62728").unwrap(), parse_hex("#2ca02c").unwrap());
+        let (r, g) = (simulate(red), simulate(green));
+        assert!(delta_e(r[1], g[1]) < delta_e(red, green) / 2.0);
+        assert!(delta_e(r[2], g[2]) > delta_e(red, green) / 2.0);
+    }
+
+    #[test]
+    fn test_categorical_spacing() {
+        let result = palette("categorical", 6);
+        assert_eq!(result.colors.len(), 6);
+        let mut hexes: Vec<_> = result.colors.iter().map(|c| c.hex.clone()).collect();
+        hexes.dedup();
+        assert_eq!(hexes.len(), 6);
+        assert!(result.min_delta_e.normal > 25.0);
+        assert!(result.min_delta_e.deuteranopia > 10.0);
+        // The first color keeps the requested hue
+        assert!((result.colors[0].hue - 250.0).abs() < 2.0);
+        // Same input, same palette
+        assert_eq!(result.colors, palette("categorical", 6).colors);
+    }
+
+    #[test]
+    fn test_sequential_ramp() {
+        let result = palette("sequential", 7);
+        for pair in result.colors.windows(2) {
+            assert!(pair[1].lightness < pair[0].lightness);
+            assert!((pair[1].hue - 250.0).abs() < 3.0);
+        }
+        // The lightest steps are too faint on white
+        assert!(result.colors[0].contrast < 1.5);
+        assert!(result.warnings[0].starts_with("Color 0"));
+        assert!(result.colors[6].contrast > 7.0);
+    }
+
+    #[test]
+    fn test_diverging_center() {
+        let result = palette("diverging", 9);
+        let center = &result.colors[4];
+        assert!(center.chroma < 1.0);
+        for i in 0..4 {
+            let (left, right) = (&result.colors[i], &result.colors[8 - i]);
+            assert!((left.lightness - right.lightness).abs() < 1.0);
+            assert!((left.hue - 250.0).abs() < 5.0, "{}", left.hue);
+            assert!((right.hue - 30.0).abs() < 5.0, "{}", right.hue);
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut input = PaletteInput {
+            kind: Some("rainbow".to_string()),
+            count: 5,
+            hue: None,
+            hue_end: None,
+            background: None,
+            min_contrast: None,
+            min_delta_e: None,
+        };
+        assert!(
+            generate_palette(input.clone())
+                .unwrap_err()
+                .contains("Unknown kind")
+        );
+        input.kind = Some("sequential".to_string());
+        input.count = 1;
+        assert_eq!(
+            generate_palette(input.clone()).unwrap_err(),
+            "count must be between 2 and 64 for sequential palettes"
+        );
+        input.count = 3;
+        input.background = Some("white".to_string());
+        assert!(
+            generate_palette(input)
+                .unwrap_err()
+                .contains("Invalid hex color")
+        );
+    }
+}
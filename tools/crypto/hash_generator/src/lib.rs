@@ -3,13 +3,22 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{HashGeneratorInput as LogicInput, HashGeneratorResult as LogicOutput};
+pub use logic::{
+    HashGeneratorInput as LogicInput, HashGeneratorResult as LogicOutput, generate_hash,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -20,6 +29,8 @@ pub struct HashGeneratorInput {
     pub algorithm: String,
     /// Output format (hex, base64) - defaults to hex
     pub format: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -38,8 +49,32 @@ pub struct HashGeneratorResult {
     pub input_length: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn hash_generator(input: HashGeneratorInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn hash_generator(request: Request<HashGeneratorInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "hash_generator",
+                HashGeneratorInput,
+                HashGeneratorResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("hash_generator", &input);
+    log.finish(run_hash_generator(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_hash_generator(input: HashGeneratorInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("text", &input.text).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         text: input.text,
@@ -64,7 +99,7 @@ pub fn hash_generator(input: HashGeneratorInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
@@ -0,0 +1,216 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    IntegrityManifestInput as LogicInput, IntegrityManifestOutput as LogicOutput,
+    build_integrity_manifest,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Blob {
+    /// Unique name of the blob, such as a file or part name
+    pub name: String,
+    /// Blob contents, base64 encoded
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ManifestEntry {
+    /// Blob name
+    pub name: String,
+    /// Decoded size in bytes
+    pub size: u64,
+    /// Hex hash of the whole blob
+    pub hash: String,
+    /// Hex hash of each chunk, in order
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Manifest {
+    /// Hash algorithm used (sha256 or sha512)
+    pub algorithm: String,
+    /// Chunk size in bytes
+    pub chunk_size: usize,
+    /// One entry per blob, in the order given
+    pub entries: Vec<ManifestEntry>,
+    /// Hex hash over every blob's name and hash, independent of blob order
+    pub payload_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IntegrityManifestInput {
+    /// Named base64 blobs to hash
+    pub blobs: Vec<Blob>,
+    /// Hash algorithm (sha256, sha512) - defaults to sha256; ignored when verifying
+    pub algorithm: Option<String>,
+    /// Chunk size in bytes, 1-16777216 (default: 65536); ignored when verifying
+    pub chunk_size: Option<usize>,
+    /// Manifest from an earlier call to verify the blobs against, using its algorithm
+    /// and chunk size
+    pub manifest: Option<Manifest>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BlobCheck {
+    /// Blob name
+    pub name: String,
+    /// ok, mismatch, missing (in the manifest only) or unexpected (in the blobs only)
+    pub status: String,
+    /// Size recorded in the manifest
+    pub expected_size: Option<u64>,
+    /// Size of the supplied blob
+    pub actual_size: Option<u64>,
+    /// Indexes of chunks whose hash differs or that exist on one side only
+    pub mismatched_chunks: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Verification {
+    /// True when every blob matches and none are missing or unexpected
+    pub valid: bool,
+    /// Whether the payload hash matches the manifest
+    pub payload_hash_matches: bool,
+    /// Result for each manifest entry, then each unexpected blob
+    pub blobs: Vec<BlobCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IntegrityManifestOutput {
+    /// Manifest of the supplied blobs
+    pub manifest: Manifest,
+    /// Comparison with the given manifest, when one was given
+    pub verification: Option<Verification>,
+}
+
+/// Compute per-chunk and whole-payload hashes for named base64 blobs, or verify blobs
+/// against an earlier manifest, so agents can check multi-part transfers between tools
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn integrity_manifest(request: Request<IntegrityManifestInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "integrity_manifest",
+                IntegrityManifestInput,
+                IntegrityManifestOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("integrity_manifest", &input);
+    log.finish(run_integrity_manifest(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_integrity_manifest(input: IntegrityManifestInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    let guard = Guard::from_env().array("blobs", &input.blobs);
+    let guard = input
+        .blobs
+        .iter()
+        .fold(guard, |guard, blob| guard.string("blobs", &blob.data));
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        blobs: input
+            .blobs
+            .into_iter()
+            .map(|blob| logic::Blob {
+                name: blob.name,
+                data: blob.data,
+            })
+            .collect(),
+        algorithm: input.algorithm,
+        chunk_size: input.chunk_size,
+        manifest: input.manifest.map(|manifest| logic::Manifest {
+            algorithm: manifest.algorithm,
+            chunk_size: manifest.chunk_size,
+            entries: manifest
+                .entries
+                .into_iter()
+                .map(|entry| logic::ManifestEntry {
+                    name: entry.name,
+                    size: entry.size,
+                    hash: entry.hash,
+                    chunks: entry.chunks,
+                })
+                .collect(),
+            payload_hash: manifest.payload_hash,
+        }),
+    };
+
+    // Call logic implementation
+    match logic::build_integrity_manifest(logic_input) {
+        Ok(result) => {
+            // Convert back to wrapper types
+            let response = IntegrityManifestOutput {
+                manifest: Manifest {
+                    algorithm: result.manifest.algorithm,
+                    chunk_size: result.manifest.chunk_size,
+                    entries: result
+                        .manifest
+                        .entries
+                        .into_iter()
+                        .map(|entry| ManifestEntry {
+                            name: entry.name,
+                            size: entry.size,
+                            hash: entry.hash,
+                            chunks: entry.chunks,
+                        })
+                        .collect(),
+                    payload_hash: result.manifest.payload_hash,
+                },
+                verification: result.verification.map(|verification| Verification {
+                    valid: verification.valid,
+                    payload_hash_matches: verification.payload_hash_matches,
+                    blobs: verification
+                        .blobs
+                        .into_iter()
+                        .map(|check| BlobCheck {
+                            name: check.name,
+                            status: status_name(check.status).to_string(),
+                            expected_size: check.expected_size,
+                            actual_size: check.actual_size,
+                            mismatched_chunks: check.mismatched_chunks,
+                        })
+                        .collect(),
+                }),
+            };
+            ToolResponse::text(ftl_json::to_string(&response).unwrap())
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
+
+#[cfg(feature = "individual")]
+fn status_name(status: logic::BlobStatus) -> &'static str {
+    match status {
+        logic::BlobStatus::Ok => "ok",
+        logic::BlobStatus::Mismatch => "mismatch",
+        logic::BlobStatus::Missing => "missing",
+        logic::BlobStatus::Unexpected => "unexpected",
+    }
+}
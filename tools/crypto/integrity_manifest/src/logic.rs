@@ -0,0 +1,435 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_ALGORITHM: &str = "sha256";
+/// Default chunk size in bytes (64 KiB)
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+/// Largest chunk size in bytes (16 MiB)
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blob {
+    pub name: String,
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub size: u64,
+    pub hash: String,
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub algorithm: String,
+    pub chunk_size: usize,
+    pub entries: Vec<ManifestEntry>,
+    pub payload_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityManifestInput {
+    pub blobs: Vec<Blob>,
+    pub algorithm: Option<String>,
+    pub chunk_size: Option<usize>,
+    pub manifest: Option<Manifest>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobStatus {
+    /// Size and every chunk match the manifest
+    Ok,
+    /// Present in both but the contents differ
+    Mismatch,
+    /// Listed in the manifest but not supplied
+    Missing,
+    /// Supplied but not listed in the manifest
+    Unexpected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobCheck {
+    pub name: String,
+    pub status: BlobStatus,
+    pub expected_size: Option<u64>,
+    pub actual_size: Option<u64>,
+    pub mismatched_chunks: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verification {
+    pub valid: bool,
+    pub payload_hash_matches: bool,
+    pub blobs: Vec<BlobCheck>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityManifestOutput {
+    pub manifest: Manifest,
+    pub verification: Option<Verification>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha512" => Ok(Algorithm::Sha512),
+            _ => Err(format!(
+                "Unsupported algorithm: {name}. Use 'sha256' or 'sha512'"
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn digest(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::Sha256 => Sha256::digest(bytes).to_vec(),
+            Algorithm::Sha512 => Sha512::digest(bytes).to_vec(),
+        }
+    }
+}
+
+/// Build a manifest of the blobs and, when a manifest is given, verify the blobs
+/// against it using its algorithm and chunk size
+pub fn build_integrity_manifest(
+    input: IntegrityManifestInput,
+) -> Result<IntegrityManifestOutput, String> {
+    let (algorithm, chunk_size) = match &input.manifest {
+        Some(expected) => (Algorithm::parse(&expected.algorithm)?, expected.chunk_size),
+        None => (
+            Algorithm::parse(input.algorithm.as_deref().unwrap_or(DEFAULT_ALGORITHM))?,
+            input.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+        ),
+    };
+    if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+        return Err(format!(
+            "Chunk size must be between 1 and {MAX_CHUNK_SIZE} bytes, got {chunk_size}"
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut digests = Vec::with_capacity(input.blobs.len());
+    let mut entries = Vec::with_capacity(input.blobs.len());
+    for blob in &input.blobs {
+        if blob.name.is_empty() {
+            return Err("Blob names must not be empty".to_string());
+        }
+        if !seen.insert(blob.name.as_str()) {
+            return Err(format!("Duplicate blob name: {}", blob.name));
+        }
+        let bytes = general_purpose::STANDARD
+            .decode(&blob.data)
+            .map_err(|e| format!("Blob '{}' is not valid base64: {e}", blob.name))?;
+        let digest = algorithm.digest(&bytes);
+        entries.push(ManifestEntry {
+            name: blob.name.clone(),
+            size: bytes.len() as u64,
+            hash: hex::encode(&digest),
+            chunks: bytes
+                .chunks(chunk_size)
+                .map(|chunk| hex::encode(algorithm.digest(chunk)))
+                .collect(),
+        });
+        digests.push((blob.name.as_str(), digest));
+    }
+
+    let manifest = Manifest {
+        algorithm: algorithm.name().to_string(),
+        chunk_size,
+        payload_hash: payload_hash(algorithm, digests),
+        entries,
+    };
+    let verification = input
+        .manifest
+        .as_ref()
+        .map(|expected| verify(&manifest, expected));
+
+    Ok(IntegrityManifestOutput {
+        manifest,
+        verification,
+    })
+}
+
+/// Hash of every blob's name and hash, sorted by name so the order blobs are sent
+/// in does not matter
+fn payload_hash(algorithm: Algorithm, mut digests: Vec<(&str, Vec<u8>)>) -> String {
+    digests.sort_by(|a, b| a.0.cmp(b.0));
+    let mut bytes = Vec::new();
+    for (name, digest) in digests {
+        bytes.extend_from_slice(&(name.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(&digest);
+    }
+    hex::encode(algorithm.digest(&bytes))
+}
+
+/// Compare a freshly built manifest with the expected one, blob by blob and chunk by chunk
+fn verify(actual: &Manifest, expected: &Manifest) -> Verification {
+    let actual_entries: HashMap<&str, &ManifestEntry> = actual
+        .entries
+        .iter()
+        .map(|entry| (entry.name.as_str(), entry))
+        .collect();
+    let expected_names: HashSet<&str> = expected
+        .entries
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect();
+
+    let mut blobs: Vec<BlobCheck> = expected
+        .entries
+        .iter()
+        .map(|want| match actual_entries.get(want.name.as_str()) {
+            Some(got) => {
+                let longest = want.chunks.len().max(got.chunks.len());
+                let mismatched_chunks: Vec<usize> = (0..longest)
+                    .filter(|&i| {
+                        !want.chunks.get(i).is_some_and(|hash| {
+                            got.chunks
+                                .get(i)
+                                .is_some_and(|h| h.eq_ignore_ascii_case(hash))
+                        })
+                    })
+                    .collect();
+                let matches = want.size == got.size
+                    && want.hash.eq_ignore_ascii_case(&got.hash)
+                    && mismatched_chunks.is_empty();
+                BlobCheck {
+                    name: want.name.clone(),
+                    status: if matches {
+                        BlobStatus::Ok
+                    } else {
+                        BlobStatus::Mismatch
+                    },
+                    expected_size: Some(want.size),
+                    actual_size: Some(got.size),
+                    mismatched_chunks,
+                }
+            }
+            None => BlobCheck {
+                name: want.name.clone(),
+                status: BlobStatus::Missing,
+                expected_size: Some(want.size),
+                actual_size: None,
+                mismatched_chunks: Vec::new(),
+            },
+        })
+        .collect();
+    blobs.extend(
+        actual
+            .entries
+            .iter()
+            .filter(|entry| !expected_names.contains(entry.name.as_str()))
+            .map(|entry| BlobCheck {
+                name: entry.name.clone(),
+                status: BlobStatus::Unexpected,
+                expected_size: None,
+                actual_size: Some(entry.size),
+                mismatched_chunks: Vec::new(),
+            }),
+    );
+
+    let payload_hash_matches = actual
+        .payload_hash
+        .eq_ignore_ascii_case(&expected.payload_hash);
+    Verification {
+        valid: payload_hash_matches && blobs.iter().all(|b| b.status == BlobStatus::Ok),
+        payload_hash_matches,
+        blobs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(name: &str, bytes: &[u8]) -> Blob {
+        Blob {
+            name: name.to_string(),
+            data: general_purpose::STANDARD.encode(bytes),
+        }
+    }
+
+    fn build(blobs: Vec<Blob>, chunk_size: Option<usize>) -> IntegrityManifestOutput {
+        build_integrity_manifest(IntegrityManifestInput {
+            blobs,
+            algorithm: None,
+            chunk_size,
+            manifest: None,
+        })
+        .unwrap()
+    }
+
+    fn verify_against(blobs: Vec<Blob>, manifest: Manifest) -> Verification {
+        build_integrity_manifest(IntegrityManifestInput {
+            blobs,
+            algorithm: None,
+            chunk_size: None,
+            manifest: Some(manifest),
+        })
+        .unwrap()
+        .verification
+        .unwrap()
+    }
+
+    #[test]
+    fn test_blob_and_chunk_hashes() {
+        let output = build(vec![blob("greeting", b"hello world")], Some(5));
+        let entry = &output.manifest.entries[0];
+        assert_eq!(output.manifest.algorithm, "sha256");
+        assert_eq!(entry.size, 11);
+        assert_eq!(
+            entry.hash,
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+        // "hello", " worl", "d"
+        assert_eq!(entry.chunks.len(), 3);
+        assert_eq!(
+            entry.chunks[0],
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert!(output.verification.is_none());
+    }
+
+    #[test]
+    fn test_empty_blob_has_no_chunks() {
+        let output = build(vec![blob("empty", b"")], None);
+        let entry = &output.manifest.entries[0];
+        assert_eq!(entry.size, 0);
+        assert!(entry.chunks.is_empty());
+        assert_eq!(
+            entry.hash,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_payload_hash_ignores_blob_order() {
+        let forward = build(vec![blob("a", b"one"), blob("b", b"two")], None);
+        let reversed = build(vec![blob("b", b"two"), blob("a", b"one")], None);
+        assert_eq!(
+            forward.manifest.payload_hash,
+            reversed.manifest.payload_hash
+        );
+
+        let renamed = build(vec![blob("a", b"one"), blob("c", b"two")], None);
+        assert_ne!(forward.manifest.payload_hash, renamed.manifest.payload_hash);
+    }
+
+    #[test]
+    fn test_sha512() {
+        let output = build_integrity_manifest(IntegrityManifestInput {
+            blobs: vec![blob("greeting", b"hello world")],
+            algorithm: Some("SHA512".to_string()),
+            chunk_size: None,
+            manifest: None,
+        })
+        .unwrap();
+        assert_eq!(output.manifest.algorithm, "sha512");
+        assert_eq!(output.manifest.entries[0].hash.len(), 128);
+    }
+
+    #[test]
+    fn test_verify_unchanged_blobs() {
+        let blobs = vec![blob("a", b"first part"), blob("b", b"second part")];
+        let manifest = build(blobs.clone(), Some(4)).manifest;
+        let verification = verify_against(blobs, manifest);
+        assert!(verification.valid);
+        assert!(verification.payload_hash_matches);
+        assert!(
+            verification
+                .blobs
+                .iter()
+                .all(|b| b.status == BlobStatus::Ok)
+        );
+    }
+
+    #[test]
+    fn test_verify_uses_manifest_chunk_size() {
+        let manifest = build(vec![blob("a", b"abcdefgh")], Some(2)).manifest;
+        // The default chunk size would give a single chunk
+        let verification = verify_against(vec![blob("a", b"abcdefgh")], manifest);
+        assert!(verification.valid);
+    }
+
+    #[test]
+    fn test_verify_reports_corrupted_chunk() {
+        let manifest = build(vec![blob("a", b"aaaabbbbcccc")], Some(4)).manifest;
+        let verification = verify_against(vec![blob("a", b"aaaaXbbbcccc")], manifest);
+        assert!(!verification.valid);
+        assert!(!verification.payload_hash_matches);
+        let check = &verification.blobs[0];
+        assert_eq!(check.status, BlobStatus::Mismatch);
+        assert_eq!(check.mismatched_chunks, vec![1]);
+    }
+
+    #[test]
+    fn test_verify_reports_truncated_blob() {
+        let manifest = build(vec![blob("a", b"aaaabbbbcccc")], Some(4)).manifest;
+        let verification = verify_against(vec![blob("a", b"aaaabbbb")], manifest);
+        let check = &verification.blobs[0];
+        assert_eq!(check.status, BlobStatus::Mismatch);
+        assert_eq!(check.expected_size, Some(12));
+        assert_eq!(check.actual_size, Some(8));
+        assert_eq!(check.mismatched_chunks, vec![2]);
+    }
+
+    #[test]
+    fn test_verify_reports_missing_and_unexpected() {
+        let manifest = build(vec![blob("a", b"one"), blob("b", b"two")], None).manifest;
+        let verification = verify_against(vec![blob("a", b"one"), blob("c", b"three")], manifest);
+        assert!(!verification.valid);
+        let statuses: Vec<(&str, BlobStatus)> = verification
+            .blobs
+            .iter()
+            .map(|b| (b.name.as_str(), b.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("a", BlobStatus::Ok),
+                ("b", BlobStatus::Missing),
+                ("c", BlobStatus::Unexpected),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        let run = |blobs: Vec<Blob>, algorithm: Option<&str>, chunk_size: Option<usize>| {
+            build_integrity_manifest(IntegrityManifestInput {
+                blobs,
+                algorithm: algorithm.map(str::to_string),
+                chunk_size,
+                manifest: None,
+            })
+            .unwrap_err()
+        };
+        assert!(run(vec![blob("a", b"x"), blob("a", b"y")], None, None).contains("Duplicate"));
+        assert!(run(vec![blob("", b"x")], None, None).contains("must not be empty"));
+        assert!(run(vec![blob("a", b"x")], Some("md5"), None).contains("Unsupported algorithm"));
+        assert!(run(vec![blob("a", b"x")], None, Some(0)).contains("Chunk size"));
+        let bad = Blob {
+            name: "a".to_string(),
+            data: "not base64!".to_string(),
+        };
+        assert!(run(vec![bad], None, None).contains("not valid base64"));
+    }
+}
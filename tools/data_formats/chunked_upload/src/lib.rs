@@ -0,0 +1,119 @@
+#[cfg(feature = "individual")]
+use ftl_chunked_upload::SpinStore;
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    ChunkedUploadInput as LogicInput, ChunkedUploadResult as LogicOutput, handle_upload,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChunkedUploadInput {
+    /// Operation: "init", "append", "finalize", "status" or "discard"
+    pub operation: String,
+    /// Session id returned by init (required for every other operation)
+    pub session_id: Option<String>,
+    /// Text to append to the session (append only, at most 1 MiB)
+    pub chunk: Option<String>,
+    /// Zero-based index of the chunk, used to detect lost or repeated chunks
+    pub chunk_index: Option<usize>,
+    /// Number of chunks that will be sent; finalize fails until all have arrived (init only)
+    pub expected_chunks: Option<usize>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChunkedUploadResult {
+    /// Operation that was performed
+    pub operation: String,
+    /// Upload session id; pass it as upload_session_id to csv_parser or histogram
+    pub session_id: String,
+    /// Number of chunks received so far
+    pub chunk_count: usize,
+    /// Total bytes received so far
+    pub total_bytes: usize,
+    /// Number of chunks announced at init, if any
+    pub expected_chunks: Option<usize>,
+    /// Whether the session is sealed and ready to be consumed
+    pub finalized: bool,
+    /// Whether the session was deleted by this call
+    pub discarded: bool,
+}
+
+/// Upload large inputs in chunks for tools that accept an upload_session_id.
+/// Call init, append each chunk in order, then finalize and pass the session id to the consuming tool.
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn chunked_upload(request: Request<ChunkedUploadInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "chunked_upload",
+                ChunkedUploadInput,
+                ChunkedUploadResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("chunked_upload", &input);
+    log.finish(run_chunked_upload(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_chunked_upload(input: ChunkedUploadInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("chunk", input.chunk.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        operation: input.operation,
+        session_id: input.session_id,
+        chunk: input.chunk,
+        chunk_index: input.chunk_index,
+        expected_chunks: input.expected_chunks,
+    };
+
+    // Call logic implementation against the shared key-value store
+    let result =
+        match SpinStore::open_default().and_then(|store| handle_upload(&store, logic_input)) {
+            Ok(result) => result,
+            Err(e) => return ToolResponse::text(format!("Error: {e}")),
+        };
+
+    // Convert back to wrapper types
+    let response = ChunkedUploadResult {
+        operation: result.operation,
+        session_id: result.session_id,
+        chunk_count: result.chunk_count,
+        total_bytes: result.total_bytes,
+        expected_chunks: result.expected_chunks,
+        finalized: result.finalized,
+        discarded: result.discarded,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
@@ -0,0 +1,173 @@
+use ftl_chunked_upload::{KvStore, SessionInfo};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkedUploadInput {
+    /// "init", "append", "finalize", "status" or "discard"
+    pub operation: String,
+    /// Session id returned by init (required for every other operation)
+    pub session_id: Option<String>,
+    /// Text to append (append only)
+    pub chunk: Option<String>,
+    /// Zero-based index of the chunk, checked against the chunks received so far
+    pub chunk_index: Option<usize>,
+    /// Number of chunks that will be sent (init only)
+    pub expected_chunks: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkedUploadResult {
+    pub operation: String,
+    pub session_id: String,
+    pub chunk_count: usize,
+    pub total_bytes: usize,
+    pub expected_chunks: Option<usize>,
+    pub finalized: bool,
+    /// Whether the session was deleted by this call
+    pub discarded: bool,
+}
+
+impl ChunkedUploadResult {
+    fn from_session(operation: &str, info: SessionInfo, discarded: bool) -> Self {
+        ChunkedUploadResult {
+            operation: operation.to_string(),
+            session_id: info.session_id,
+            chunk_count: info.chunk_count,
+            total_bytes: info.total_bytes,
+            expected_chunks: info.expected_chunks,
+            finalized: info.finalized,
+            discarded,
+        }
+    }
+}
+
+pub fn handle_upload(
+    store: &impl KvStore,
+    input: ChunkedUploadInput,
+) -> Result<ChunkedUploadResult, String> {
+    let operation = input.operation.trim().to_lowercase();
+    if operation == "init" {
+        let info = ftl_chunked_upload::init(store, input.expected_chunks)?;
+        return Ok(ChunkedUploadResult::from_session(&operation, info, false));
+    }
+
+    let session_id = input
+        .session_id
+        .as_deref()
+        .ok_or_else(|| format!("session_id is required for '{operation}'"))?;
+
+    let (info, discarded) = match operation.as_str() {
+        "append" => {
+            let chunk = input
+                .chunk
+                .as_deref()
+                .ok_or("chunk is required for 'append'")?;
+            (
+                ftl_chunked_upload::append(store, session_id, input.chunk_index, chunk)?,
+                false,
+            )
+        }
+        "finalize" => (ftl_chunked_upload::finalize(store, session_id)?, false),
+        "status" => (ftl_chunked_upload::status(store, session_id)?, false),
+        "discard" => {
+            let info = ftl_chunked_upload::status(store, session_id)?;
+            ftl_chunked_upload::discard(store, session_id)?;
+            (info, true)
+        }
+        other => {
+            return Err(format!(
+                "Unknown operation '{other}'. Expected one of: init, append, finalize, status, discard"
+            ));
+        }
+    };
+
+    Ok(ChunkedUploadResult::from_session(
+        &operation, info, discarded,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ftl_chunked_upload::MemoryStore;
+
+    fn request(
+        operation: &str,
+        session_id: Option<&str>,
+        chunk: Option<&str>,
+    ) -> ChunkedUploadInput {
+        ChunkedUploadInput {
+            operation: operation.to_string(),
+            session_id: session_id.map(str::to_string),
+            chunk: chunk.map(str::to_string),
+            chunk_index: None,
+            expected_chunks: None,
+        }
+    }
+
+    #[test]
+    fn test_full_upload_flow() {
+        let store = MemoryStore::new();
+        let init = handle_upload(&store, request("init", None, None)).unwrap();
+        let id = init.session_id.as_str();
+        assert_eq!(init.chunk_count, 0);
+
+        handle_upload(&store, request("append", Some(id), Some("1,2,"))).unwrap();
+        let appended = handle_upload(&store, request("append", Some(id), Some("3"))).unwrap();
+        assert_eq!(appended.chunk_count, 2);
+        assert_eq!(appended.total_bytes, 5);
+
+        let finalized = handle_upload(&store, request("finalize", Some(id), None)).unwrap();
+        assert!(finalized.finalized);
+        assert_eq!(
+            ftl_chunked_upload::read_to_string(&store, id).unwrap(),
+            "1,2,3"
+        );
+    }
+
+    #[test]
+    fn test_chunk_index_is_checked() {
+        let store = MemoryStore::new();
+        let init = handle_upload(&store, request("init", None, None)).unwrap();
+
+        let mut append = request("append", Some(&init.session_id), Some("data"));
+        append.chunk_index = Some(1);
+        let err = handle_upload(&store, append).unwrap_err();
+        assert!(err.contains("Expected chunk 0"));
+    }
+
+    #[test]
+    fn test_status_and_discard() {
+        let store = MemoryStore::new();
+        let init = handle_upload(&store, request("init", None, None)).unwrap();
+        let id = init.session_id.as_str();
+        handle_upload(&store, request("append", Some(id), Some("abc"))).unwrap();
+
+        let status = handle_upload(&store, request("STATUS", Some(id), None)).unwrap();
+        assert_eq!(status.operation, "status");
+        assert!(!status.finalized);
+
+        let discarded = handle_upload(&store, request("discard", Some(id), None)).unwrap();
+        assert!(discarded.discarded);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_missing_arguments() {
+        let store = MemoryStore::new();
+        let err = handle_upload(&store, request("append", None, Some("x"))).unwrap_err();
+        assert!(err.contains("session_id is required"));
+
+        let init = handle_upload(&store, request("init", None, None)).unwrap();
+        let err =
+            handle_upload(&store, request("append", Some(&init.session_id), None)).unwrap_err();
+        assert!(err.contains("chunk is required"));
+    }
+
+    #[test]
+    fn test_unknown_operation() {
+        let store = MemoryStore::new();
+        let err = handle_upload(&store, request("upload", Some("abc"), None)).unwrap_err();
+        assert!(err.contains("Unknown operation 'upload'"));
+    }
+}
@@ -1,5 +1,15 @@
+#[cfg(feature = "individual")]
+use ftl_chunked_upload::{ChunkReader, SpinStore};
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_pagination::PageInfo;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,13 +19,17 @@ mod logic;
 // Re-export types from logic module
 pub use logic::{
     CsvParserInput as LogicInput, CsvParserResult as LogicOutput, ParsingStats as LogicStats,
+    parse_csv, parse_csv_reader,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CsvParserInput {
-    /// CSV content to parse
+    /// CSV content to parse (omit when using upload_session_id)
+    #[serde(default)]
     pub content: String,
+    /// Finalized chunked upload session to read the CSV content from
+    pub upload_session_id: Option<String>,
     /// Whether first row contains headers
     pub has_headers: Option<bool>,
     /// Custom delimiter (default: comma)
@@ -24,15 +38,23 @@ pub struct CsvParserInput {
     pub skip_empty_lines: Option<bool>,
     /// Whether to trim whitespace from fields
     pub trim_fields: Option<bool>,
+    /// Number of data rows to skip before the returned page (default: 0)
+    pub offset: Option<usize>,
+    /// Maximum number of data rows to return (default: all)
+    pub limit: Option<usize>,
+    /// Cursor from a previous response's `page_info.next_cursor`, in place of `offset`
+    pub cursor: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CsvParserResult {
     /// Parsed headers (if any)
     pub headers: Option<Vec<String>>,
-    /// Parsed data rows
+    /// Parsed data rows in the requested page
     pub rows: Vec<Vec<String>>,
-    /// Number of rows parsed
+    /// Number of rows parsed (across all pages)
     pub row_count: usize,
     /// Number of columns detected
     pub column_count: usize,
@@ -40,6 +62,8 @@ pub struct CsvParserResult {
     pub stats: ParsingStats,
     /// Error if parsing failed
     pub error: Option<String>,
+    /// Position of the returned rows within all parsed rows
+    pub page_info: PageInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -54,8 +78,37 @@ pub struct ParsingStats {
     pub delimiter_used: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn csv_parser(input: CsvParserInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+async fn parse_uploaded_csv(session_id: &str, options: &LogicInput) -> Result<LogicOutput, String> {
+    let store = SpinStore::open_default()?;
+    let reader = ChunkReader::open(&store, session_id)?;
+    let result = parse_csv_reader(reader, options).await?;
+    ftl_chunked_upload::discard(&store, session_id)?;
+    Ok(result)
+}
+
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn csv_parser(request: Request<CsvParserInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("csv_parser", CsvParserInput, CsvParserResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("csv_parser", &input);
+    log.finish(run_csv_parser(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_csv_parser(input: CsvParserInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("content", &input.content).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         content: input.content,
@@ -63,10 +116,17 @@ pub fn csv_parser(input: CsvParserInput) -> ToolResponse {
         delimiter: input.delimiter,
         skip_empty_lines: input.skip_empty_lines,
         trim_fields: input.trim_fields,
+        offset: input.offset,
+        limit: input.limit,
+        cursor: input.cursor,
     };
 
-    // Call logic implementation
-    let result = match logic::parse_csv(logic_input) {
+    // Call logic implementation, streaming the content from a chunked upload if given
+    let result = match input.upload_session_id {
+        Some(session_id) => parse_uploaded_csv(&session_id, &logic_input).await,
+        None => logic::parse_csv(logic_input).await,
+    };
+    let result = match result {
         Ok(result) => result,
         Err(e) => return ToolResponse::text(format!("Error parsing CSV: {e}")),
     };
@@ -84,9 +144,10 @@ pub fn csv_parser(input: CsvParserInput) -> ToolResponse {
             delimiter_used: result.stats.delimiter_used,
         },
         error: result.error,
+        page_info: result.page_info,
     };
 
     ToolResponse::text(
-        serde_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
     )
 }
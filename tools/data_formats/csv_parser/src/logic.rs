@@ -1,6 +1,8 @@
 use csv::ReaderBuilder;
+use ftl_pagination::{PageInfo, PageRequest};
+use ftl_yield::Yielder;
 use serde::{Deserialize, Serialize};
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CsvParserInput {
@@ -14,15 +16,21 @@ pub struct CsvParserInput {
     pub skip_empty_lines: Option<bool>,
     /// Whether to trim whitespace from fields
     pub trim_fields: Option<bool>,
+    /// Number of data rows to skip before the returned page (default: 0)
+    pub offset: Option<usize>,
+    /// Maximum number of data rows to return (default: all)
+    pub limit: Option<usize>,
+    /// Cursor from a previous response's `page_info.next_cursor`, in place of `offset`
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CsvParserResult {
     /// Parsed headers (if any)
     pub headers: Option<Vec<String>>,
-    /// Parsed data rows
+    /// Parsed data rows in the requested page
     pub rows: Vec<Vec<String>>,
-    /// Number of rows parsed
+    /// Number of rows parsed (across all pages)
     pub row_count: usize,
     /// Number of columns detected
     pub column_count: usize,
@@ -30,6 +38,8 @@ pub struct CsvParserResult {
     pub stats: ParsingStats,
     /// Error if parsing failed
     pub error: Option<String>,
+    /// Position of the returned rows within all parsed rows
+    pub page_info: PageInfo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,7 +54,19 @@ pub struct ParsingStats {
     pub delimiter_used: String,
 }
 
-pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
+pub async fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
+    parse_csv_reader(Cursor::new(input.content.as_bytes()), &input).await
+}
+
+/// Parse CSV data streamed from `source`, using the options from `input`
+/// (its `content` field is ignored). Used for chunked uploads.
+///
+/// Only the rows in the requested page are kept in memory.
+pub async fn parse_csv_reader<R: Read>(
+    source: R,
+    input: &CsvParserInput,
+) -> Result<CsvParserResult, String> {
+    let page = PageRequest::from_input(input.offset, input.limit, input.cursor.as_deref())?;
     let has_headers = input.has_headers.unwrap_or(true);
     let skip_empty = input.skip_empty_lines.unwrap_or(true);
     let trim_fields = input.trim_fields.unwrap_or(true);
@@ -72,7 +94,7 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
         .has_headers(has_headers)
         .trim(csv::Trim::All)
         .flexible(true) // Allow variable number of fields per record
-        .from_reader(Cursor::new(input.content.as_bytes()));
+        .from_reader(source);
 
     // Parse headers if present
     let headers = if has_headers {
@@ -101,6 +123,7 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
                         delimiter_used: delimiter_str,
                     },
                     error: Some(format!("Failed to parse headers: {e}")),
+                    page_info: page.info(0),
                 });
             }
         }
@@ -114,7 +137,10 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
     let mut lines_skipped = 0;
     let mut column_counts = Vec::new();
 
+    let mut yielder = Yielder::default();
+
     for result in reader.records() {
+        yielder.tick().await;
         lines_processed += 1;
 
         match result {
@@ -125,6 +151,12 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
                     continue;
                 }
 
+                let row_index = column_counts.len();
+                column_counts.push(record.len());
+                if !page.contains(row_index) {
+                    continue;
+                }
+
                 let row: Vec<String> = record
                     .iter()
                     .map(|field| {
@@ -135,8 +167,6 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
                         }
                     })
                     .collect();
-
-                column_counts.push(row.len());
                 rows.push(row);
             }
             Err(_e) => {
@@ -150,8 +180,8 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
     // Calculate statistics
     let column_count = if let Some(ref h) = headers {
         h.len()
-    } else if !rows.is_empty() {
-        rows[0].len()
+    } else if let Some(&first) = column_counts.first() {
+        first
     } else {
         0
     };
@@ -164,7 +194,7 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
 
     Ok(CsvParserResult {
         headers,
-        row_count: rows.len(),
+        row_count: column_counts.len(),
         rows,
         column_count,
         stats: ParsingStats {
@@ -174,6 +204,7 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
             delimiter_used: delimiter_str,
         },
         error: None,
+        page_info: page.info(column_counts.len()),
     })
 }
 
@@ -181,6 +212,18 @@ pub fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
 mod tests {
     use super::*;
 
+    // Drive the async logic to completion from synchronous tests
+    fn parse_csv(input: CsvParserInput) -> Result<CsvParserResult, String> {
+        ftl_yield::block_on(super::parse_csv(input))
+    }
+
+    fn parse_csv_reader<R: Read>(
+        source: R,
+        input: &CsvParserInput,
+    ) -> Result<CsvParserResult, String> {
+        ftl_yield::block_on(super::parse_csv_reader(source, input))
+    }
+
     #[test]
     fn test_basic_csv() {
         let input = CsvParserInput {
@@ -189,6 +232,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -214,6 +260,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -230,6 +279,9 @@ mod tests {
             delimiter: Some("\\t".to_string()),
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -253,6 +305,9 @@ mod tests {
             delimiter: Some("|".to_string()),
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -276,6 +331,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: Some(true),
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -298,6 +356,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: Some(true),
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -317,6 +378,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -336,6 +400,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -354,6 +421,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -370,6 +440,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -393,6 +466,9 @@ mod tests {
             delimiter: Some(",,".to_string()),
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input);
 
@@ -408,6 +484,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: None,
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -423,6 +502,9 @@ mod tests {
             delimiter: None,
             skip_empty_lines: Some(true),
             trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
         };
         let result = parse_csv(input).unwrap();
 
@@ -430,4 +512,72 @@ mod tests {
         assert!(!result.stats.uniform_columns); // Different column counts
         // With flexible parsing, empty lines are handled internally by the CSV parser
     }
+
+    #[test]
+    fn test_parse_chunked_upload() {
+        use ftl_chunked_upload::{ChunkReader, MemoryStore};
+
+        let store = MemoryStore::new();
+        let session = ftl_chunked_upload::init(&store, None).unwrap();
+        for chunk in ["Name,Note\nJo", "hn,\"split ", "field\"\nJane,ok\n"] {
+            ftl_chunked_upload::append(&store, &session.session_id, None, chunk).unwrap();
+        }
+        ftl_chunked_upload::finalize(&store, &session.session_id).unwrap();
+
+        let options = CsvParserInput {
+            content: String::new(),
+            has_headers: Some(true),
+            delimiter: None,
+            skip_empty_lines: None,
+            trim_fields: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+        };
+        let reader = ChunkReader::open(&store, &session.session_id).unwrap();
+        let result = parse_csv_reader(reader, &options).unwrap();
+
+        assert_eq!(result.row_count, 2);
+        assert_eq!(result.rows[0], vec!["John", "split field"]);
+        assert_eq!(result.rows[1], vec!["Jane", "ok"]);
+    }
+
+    #[test]
+    fn test_pagination() {
+        let input = CsvParserInput {
+            content: "n\n1\n2\n3\n4\n5".to_string(),
+            has_headers: Some(true),
+            delimiter: None,
+            skip_empty_lines: None,
+            trim_fields: None,
+            offset: Some(1),
+            limit: Some(2),
+            cursor: None,
+        };
+        let result = parse_csv(input).unwrap();
+        assert_eq!(result.row_count, 5);
+        assert_eq!(result.rows, vec![vec!["2"], vec!["3"]]);
+        assert_eq!(result.page_info.total, 5);
+        assert!(result.page_info.truncated);
+        assert!(result.stats.uniform_columns);
+    }
+
+    #[test]
+    fn test_column_count_without_headers_uses_first_row() {
+        let input = CsvParserInput {
+            content: "a,b,c\nd,e".to_string(),
+            has_headers: Some(false),
+            delimiter: None,
+            skip_empty_lines: None,
+            trim_fields: None,
+            offset: Some(1),
+            limit: None,
+            cursor: None,
+        };
+        let result = parse_csv(input).unwrap();
+        assert_eq!(result.column_count, 3);
+        assert_eq!(result.rows, vec![vec!["d", "e"]]);
+        assert!(!result.page_info.truncated);
+        assert!(!result.stats.uniform_columns);
+    }
 }
@@ -0,0 +1,69 @@
+use ftl_guard::Guard;
+use ftl_introspect::{Request, describe};
+use ftl_log::ToolLog;
+use ftl_sdk::ToolResponse;
+#[cfg(not(test))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::DataFormatsCategoryInput as LogicInput;
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DataFormatsCategoryInput {
+    /// Operation to perform: "csv_parser", "data_mask", "dedupe_records",
+    /// "generate_from_schema", "json_flatten", "json_formatter", "json_validator",
+    /// "mock_response", "openapi_inspect", "record_link", "yaml_formatter"
+    pub operation: String,
+    /// Arguments for the selected operation, using the same fields as the individual tool
+    pub input: serde_json::Value,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Run any data format tool (CSV parsing, JSON field masking, record deduplication, sample
+/// generation from JSON Schema, JSON flattening/formatting/validation, mock API responses,
+/// OpenAPI inspection, record linkage, YAML formatting) through a single operation-based
+/// interface. Tools are linked in as libraries, so no internal HTTP calls are made.
+#[cfg_attr(not(test), tool)]
+pub fn data_formats_category(request: Request<DataFormatsCategoryInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "data_formats_category",
+                DataFormatsCategoryInput,
+                serde_json::Value
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("data_formats_category", &input);
+    log.finish(run_data_formats_category(input))
+}
+
+fn run_data_formats_category(input: DataFormatsCategoryInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().json("input", &input.input).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        operation: input.operation,
+        input: input.input,
+    };
+
+    // Call logic implementation
+    match logic::dispatch(logic_input) {
+        Ok(result) => ToolResponse::text(
+            ftl_json::to_string(&result).unwrap_or_else(|e| format!("Serialization error: {e}")),
+        ),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataFormatsCategoryInput {
+    /// Operation to perform
+    pub operation: String,
+    /// Arguments for the selected operation
+    pub input: Value,
+}
+
+/// Operations available through this category, in the order they are reported
+pub const OPERATIONS: &[&str] = &[
+    "csv_parser",
+    "data_mask",
+    "dedupe_records",
+    "generate_from_schema",
+    "json_flatten",
+    "json_formatter",
+    "json_validator",
+    "mock_response",
+    "openapi_inspect",
+    "record_link",
+    "yaml_formatter",
+];
+
+pub fn dispatch(input: DataFormatsCategoryInput) -> Result<Value, String> {
+    let operation = input.operation.trim().to_lowercase();
+
+    match operation.as_str() {
+        "csv_parser" => {
+            let args: csv_parser_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(ftl_yield::block_on(csv_parser_tool::parse_csv(args))?)
+        }
+        "data_mask" => {
+            let args: data_mask_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(data_mask_tool::mask_document(args)?)
+        }
+        "dedupe_records" => {
+            let args: dedupe_records_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(dedupe_records_tool::remove_duplicates(args)?)
+        }
+        "generate_from_schema" => {
+            let args: generate_from_schema_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(generate_from_schema_tool::generate_documents(args)?)
+        }
+        "json_flatten" => {
+            let args: json_flatten_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(json_flatten_tool::flatten_json(args)?)
+        }
+        "json_formatter" => {
+            let args: json_formatter_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(json_formatter_tool::format_json(args)?)
+        }
+        "json_validator" => {
+            let args: json_validator_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(json_validator_tool::validate_json(args)?)
+        }
+        "mock_response" => {
+            let args: mock_response_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(mock_response_tool::build_mock_response(args)?)
+        }
+        "openapi_inspect" => {
+            let args: openapi_inspect_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(openapi_inspect_tool::inspect_openapi(args)?)
+        }
+        "record_link" => {
+            let args: record_link_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(record_link_tool::link_records(args)?)
+        }
+        "yaml_formatter" => {
+            let args: yaml_formatter_tool::LogicInput = parse_args(&operation, input.input)?;
+            to_value(yaml_formatter_tool::format_yaml(args)?)
+        }
+        _ => Err(format!(
+            "Unknown operation '{}'. Valid operations: {}",
+            input.operation,
+            OPERATIONS.join(", ")
+        )),
+    }
+}
+
+fn parse_args<T: for<'de> Deserialize<'de>>(operation: &str, args: Value) -> Result<T, String> {
+    serde_json::from_value(args).map_err(|e| format!("Invalid input for '{operation}': {e}"))
+}
+
+fn to_value<T: Serialize>(result: T) -> Result<Value, String> {
+    serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(operation: &str, args: Value) -> Result<Value, String> {
+        dispatch(DataFormatsCategoryInput {
+            operation: operation.to_string(),
+            input: args,
+        })
+    }
+
+    #[test]
+    fn test_csv_parser_operation() {
+        let result = call(
+            "csv_parser",
+            json!({ "content": "name,age\nAlice,30\nBob,25", "has_headers": true }),
+        )
+        .unwrap();
+
+        assert_eq!(result["row_count"], 2);
+        assert_eq!(result["headers"], json!(["name", "age"]));
+        assert_eq!(result["rows"][1], json!(["Bob", "25"]));
+    }
+
+    #[test]
+    fn test_data_mask_operation() {
+        let result = call(
+            "data_mask",
+            json!({
+                "document": "{\"card\": \"4111-1111-1111-1234\", \"name\": \"Ann\"}",
+                "rules": [{"path": "card", "strategy": "partial"}]
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result["masked"],
+            json!({ "card": "****-****-****-1234", "name": "Ann" })
+        );
+        assert_eq!(result["masked_count"], 1);
+    }
+
+    #[test]
+    fn test_dedupe_records_operation() {
+        let result = call(
+            "dedupe_records",
+            json!({
+                "records": [{"email": "ann@x.com"}, {"email": "ANN@x.com "}, {"email": "bob@x.com"}],
+                "keys": [{"field": "email"}]
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(result["kept_indices"], json!([0, 2]));
+        assert_eq!(result["groups"][0]["removed"][0]["index"], 1);
+    }
+
+    #[test]
+    fn test_generate_from_schema_operation() {
+        let result = call(
+            "generate_from_schema",
+            json!({ "schema": "{\"type\": \"integer\", \"const\": 7}", "count": 2, "seed": 1 }),
+        )
+        .unwrap();
+
+        assert_eq!(result["documents"], json!([7, 7]));
+        assert_eq!(result["seed"], 1);
+    }
+
+    #[test]
+    fn test_json_flatten_operation() {
+        let result = call(
+            "json_flatten",
+            json!({ "json_string": "{\"a\": {\"b\": [1, 2]}}" }),
+        )
+        .unwrap();
+
+        assert_eq!(result["entries"][0], json!({ "key": "a.b[0]", "value": 1 }));
+        assert_eq!(result["entries"][1], json!({ "key": "a.b[1]", "value": 2 }));
+    }
+
+    #[test]
+    fn test_json_formatter_operation() {
+        let result = call(
+            "json_formatter",
+            json!({ "json_string": "{\"a\": 1}", "indent": 0 }),
+        )
+        .unwrap();
+
+        assert_eq!(result["is_valid"], true);
+        assert_eq!(result["formatted"], "{\"a\":1}");
+    }
+
+    #[test]
+    fn test_json_validator_operation() {
+        let result = call("json_validator", json!({ "json_string": "[1, 2, 3]" })).unwrap();
+
+        assert_eq!(result["is_valid"], true);
+        assert_eq!(result["details"]["root_type"], "array");
+        assert_eq!(result["details"]["element_count"], 3);
+    }
+
+    #[test]
+    fn test_json_validator_reports_invalid_json() {
+        let result = call("json_validator", json!({ "json_string": "{invalid" })).unwrap();
+
+        assert_eq!(result["is_valid"], false);
+        assert!(result["error"].is_string());
+    }
+
+    #[test]
+    fn test_mock_response_operation() {
+        let operation = json!({
+            "responses": {
+                "200": {"content": {"application/json": {"example": {"id": 1}}}}
+            }
+        });
+        let result = call("mock_response", json!({ "source": operation.to_string() })).unwrap();
+
+        assert_eq!(result["status"], "200");
+        assert_eq!(result["body"], json!({ "id": 1 }));
+        assert_eq!(result["body_source"], "example");
+    }
+
+    #[test]
+    fn test_openapi_inspect_operation() {
+        let document = json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Minimal", "version": "1"},
+            "paths": {"/ping": {"get": {"responses": {"200": {"description": "pong"}}}}}
+        });
+        let result = call(
+            "openapi_inspect",
+            json!({ "document": document.to_string() }),
+        )
+        .unwrap();
+
+        assert_eq!(result["operation_count"], 1);
+        assert_eq!(result["is_valid"], true);
+        assert_eq!(
+            result["issues"][0]["message"],
+            "Operation has no operationId"
+        );
+    }
+
+    #[test]
+    fn test_record_link_operation() {
+        let result = call(
+            "record_link",
+            json!({
+                "left": [{"name": "Jonathan Smith"}, {"name": "Wei Chen"}],
+                "right": [{"name": "Jonathon Smith"}],
+                "fields": [{"field": "name"}]
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(result["matches"][0]["left_index"], 0);
+        assert_eq!(result["matches"][0]["right_index"], 0);
+        assert_eq!(result["unmatched_left"], json!([1]));
+    }
+
+    #[test]
+    fn test_yaml_formatter_operation() {
+        let result = call(
+            "yaml_formatter",
+            json!({ "content": "name: test\nvalue: 42", "validate_only": true }),
+        )
+        .unwrap();
+
+        assert_eq!(result["is_valid"], true);
+        assert_eq!(result["stats"]["document_count"], 1);
+    }
+
+    #[test]
+    fn test_operation_is_case_insensitive() {
+        let result = call("  JSON_Validator ", json!({ "json_string": "{}" })).unwrap();
+        assert_eq!(result["is_valid"], true);
+    }
+
+    #[test]
+    fn test_unknown_operation() {
+        let err = call("xml_parser", json!({})).unwrap_err();
+        assert!(err.contains("Unknown operation 'xml_parser'"));
+        assert!(err.contains("csv_parser"));
+    }
+
+    #[test]
+    fn test_invalid_operation_input() {
+        let err = call("csv_parser", json!({ "has_headers": true })).unwrap_err();
+        assert!(err.contains("Invalid input for 'csv_parser'"));
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    DataMaskInput as LogicInput, DataMaskOutput as LogicOutput, MAX_RULES, MaskRule as LogicRule,
+    mask_document,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    DataMaskOutputSchema as DataMaskOutput, MaskRuleSchema as MaskRule,
+    RuleReportSchema as RuleReport,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DataMaskInput {
+    #[serde(flatten)]
+    pub params: logic::DataMaskInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Mask sensitive fields of a JSON document by a policy of paths and strategies (fixed,
+/// partial, email, hash or remove), keeping formats like last-4 digits and email domains
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn data_mask(request: Request<DataMaskInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("data_mask", DataMaskInput, DataMaskOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("data_mask", &input);
+    log.finish(run_data_mask(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_data_mask(input: DataMaskInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("document", &input.params.document)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::mask_document(input.params.into()) {
+        Ok(result) => DataMaskOutput::from(result),
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&result).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
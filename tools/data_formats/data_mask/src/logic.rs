@@ -0,0 +1,645 @@
+use ftl_tool_types_derive::ToolTypes;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Most rules in one policy
+pub const MAX_RULES: usize = 100;
+/// Text fixed rules write by default
+const DEFAULT_REPLACEMENT: &str = "[REDACTED]";
+/// Characters partial rules leave visible at the end by default
+const DEFAULT_KEEP_LAST: usize = 4;
+/// Hex characters hash rules keep by default
+const DEFAULT_HASH_LENGTH: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct MaskRule {
+    /// Fields to mask: keys separated by dots, [n] for an array element, [*] for every
+    /// element, * for every key and ** for any depth, e.g. "users[*].email" or "**.ssn"
+    pub path: String,
+    /// How to mask: "fixed" (replace the value), "partial" (mask letters and digits,
+    /// keeping the last few and all punctuation), "email" (mask the local part, keep the
+    /// domain), "hash" (deterministic token) or "remove" (delete the field)
+    pub strategy: String,
+    /// Value written by fixed (default: "[REDACTED]")
+    pub replacement: Option<Value>,
+    /// Letters and digits partial leaves visible at the start (default: 0)
+    pub keep_first: Option<usize>,
+    /// Letters and digits partial leaves visible at the end (default: 4)
+    pub keep_last: Option<usize>,
+    /// Character that replaces masked characters (default: "*")
+    pub mask_char: Option<String>,
+    /// Hex characters in hash tokens, 8-64 (default: 16)
+    pub hash_length: Option<usize>,
+    /// Text put in front of hash tokens, e.g. "tok_" (default: none)
+    pub hash_prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DataMaskInput {
+    /// JSON document to mask
+    pub document: String,
+    /// Masking policy; a field matched by several rules is masked by the first
+    #[tool_types(nested)]
+    pub rules: Vec<MaskRule>,
+    /// Secret key; when given, hash tokens use HMAC-SHA256 instead of SHA-256 so they
+    /// cannot be recomputed by guessing values
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RuleReport {
+    pub path: String,
+    pub strategy: String,
+    /// Fields this rule masked
+    pub matches: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DataMaskOutput {
+    /// Document with the matched fields masked
+    pub masked: Value,
+    /// Number of fields masked
+    pub masked_count: usize,
+    /// Concrete paths of the masked fields, like "users[0].email"
+    pub masked_paths: Vec<String>,
+    /// Matches per rule, in policy order; zero flags a path that may be misspelled
+    #[tool_types(nested)]
+    pub rules: Vec<RuleReport>,
+    /// Digest behind hash tokens: "HMAC-SHA256" or "SHA-256"
+    pub hash_algorithm: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    AnyIndex,
+    AnyKey,
+    AnyDepth,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    Fixed,
+    Partial,
+    Email,
+    Hash,
+    Remove,
+}
+
+impl Strategy {
+    fn parse(name: &str) -> Result<Strategy, String> {
+        match name.to_lowercase().as_str() {
+            "fixed" => Ok(Strategy::Fixed),
+            "partial" => Ok(Strategy::Partial),
+            "email" => Ok(Strategy::Email),
+            "hash" => Ok(Strategy::Hash),
+            "remove" => Ok(Strategy::Remove),
+            _ => Err(format!(
+                "Unsupported strategy: {name}. Use 'fixed', 'partial', 'email', 'hash' or 'remove'"
+            )),
+        }
+    }
+}
+
+/// A validated rule
+struct Rule<'a> {
+    segments: Vec<Segment>,
+    strategy: Strategy,
+    source: &'a MaskRule,
+    mask_char: char,
+    hash_length: usize,
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+    let invalid = |why: &str| format!("Invalid path '{path}': {why}");
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (name, mut rest) = match part.find('[') {
+            Some(i) => (&part[..i], &part[i..]),
+            None => (part, ""),
+        };
+        match name {
+            "" if rest.is_empty() => return Err(invalid("empty key")),
+            "" => {}
+            "*" => segments.push(Segment::AnyKey),
+            "**" => segments.push(Segment::AnyDepth),
+            _ => segments.push(Segment::Key(name.to_string())),
+        }
+        while !rest.is_empty() {
+            let close = rest
+                .find(']')
+                .filter(|_| rest.starts_with('['))
+                .ok_or_else(|| invalid("unbalanced brackets"))?;
+            let index = &rest[1..close];
+            segments.push(if index == "*" {
+                Segment::AnyIndex
+            } else {
+                Segment::Index(
+                    index
+                        .parse()
+                        .map_err(|_| invalid(&format!("'{index}' is not an array index")))?,
+                )
+            });
+            rest = &rest[close + 1..];
+        }
+    }
+    if segments.is_empty() {
+        return Err(invalid("empty path"));
+    }
+    Ok(segments)
+}
+
+fn render_path(steps: &[Step]) -> String {
+    let mut path = String::new();
+    for step in steps {
+        match step {
+            Step::Key(key) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+            }
+            Step::Index(i) => path.push_str(&format!("[{i}]")),
+        }
+    }
+    path
+}
+
+/// Collects the concrete paths the segments reach from this value
+fn collect(
+    value: &Value,
+    segments: &[Segment],
+    here: &mut Vec<Step>,
+    out: &mut BTreeSet<Vec<Step>>,
+) {
+    let Some((first, rest)) = segments.split_first() else {
+        out.insert(here.clone());
+        return;
+    };
+    match (first, value) {
+        (Segment::Key(key), Value::Object(map)) => {
+            if let Some(child) = map.get(key) {
+                descend(Step::Key(key.clone()), child, rest, here, out);
+            }
+        }
+        (Segment::Index(i), Value::Array(items)) => {
+            if let Some(child) = items.get(*i) {
+                descend(Step::Index(*i), child, rest, here, out);
+            }
+        }
+        (Segment::AnyKey, Value::Object(map)) => {
+            for (key, child) in map {
+                descend(Step::Key(key.clone()), child, rest, here, out);
+            }
+        }
+        (Segment::AnyIndex, Value::Array(items)) => {
+            for (i, child) in items.iter().enumerate() {
+                descend(Step::Index(i), child, rest, here, out);
+            }
+        }
+        (Segment::AnyDepth, _) => {
+            // ** matches here, or any number of levels further down
+            collect(value, rest, here, out);
+            match value {
+                Value::Object(map) => {
+                    for (key, child) in map {
+                        descend(Step::Key(key.clone()), child, segments, here, out);
+                    }
+                }
+                Value::Array(items) => {
+                    for (i, child) in items.iter().enumerate() {
+                        descend(Step::Index(i), child, segments, here, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+fn descend(
+    step: Step,
+    child: &Value,
+    segments: &[Segment],
+    here: &mut Vec<Step>,
+    out: &mut BTreeSet<Vec<Step>>,
+) {
+    here.push(step);
+    collect(child, segments, here, out);
+    here.pop();
+}
+
+fn value_at_mut<'v>(value: &'v mut Value, steps: &[Step]) -> Option<&'v mut Value> {
+    steps
+        .iter()
+        .try_fold(value, |current, step| match (step, current) {
+            (Step::Key(key), Value::Object(map)) => map.get_mut(key),
+            (Step::Index(i), Value::Array(items)) => items.get_mut(*i),
+            _ => None,
+        })
+}
+
+fn digest(message: &[u8], key: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    match key {
+        Some(key) => {
+            let mut mac =
+                HmacSha256::new_from_slice(key).map_err(|e| format!("Invalid HMAC key: {e}"))?;
+            mac.update(message);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+        None => Ok(Sha256::digest(message).to_vec()),
+    }
+}
+
+/// Masks letters and digits outside the first `keep_first` and last `keep_last`,
+/// leaving punctuation and spaces so "4111-1111-1111-1234" keeps its shape
+fn mask_partial(text: &str, keep_first: usize, keep_last: usize, mask: char) -> String {
+    let total = text.chars().filter(|c| c.is_alphanumeric()).count();
+    // Values too short to hide anything are masked in full
+    let (keep_first, keep_last) = if keep_first + keep_last >= total {
+        (0, 0)
+    } else {
+        (keep_first, keep_last)
+    };
+    let mut seen = 0;
+    text.chars()
+        .map(|c| {
+            if !c.is_alphanumeric() {
+                return c;
+            }
+            seen += 1;
+            if seen <= keep_first || seen > total - keep_last {
+                c
+            } else {
+                mask
+            }
+        })
+        .collect()
+}
+
+/// Keeps the first character of the local part and the whole domain, so
+/// "jane.doe@example.com" becomes "j*******@example.com"
+fn mask_email(text: &str, mask: char) -> Option<String> {
+    let (local, domain) = text.rsplit_once('@')?;
+    let mut chars = local.chars();
+    let first = chars.next()?;
+    if domain.is_empty() {
+        return None;
+    }
+    let hidden: String = chars.map(|_| mask).collect();
+    Some(format!("{first}{hidden}@{domain}"))
+}
+
+/// Masks every string and number under the value with the scalar strategy
+fn mask_leaves(value: &mut Value, rule: &Rule, key: Option<&[u8]>) -> Result<(), String> {
+    match value {
+        Value::Object(map) => {
+            for child in map.values_mut() {
+                mask_leaves(child, rule, key)?;
+            }
+        }
+        Value::Array(items) => {
+            for child in items {
+                mask_leaves(child, rule, key)?;
+            }
+        }
+        Value::String(_) | Value::Number(_) => {
+            let text = match &*value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let keep_first = rule.source.keep_first.unwrap_or(0);
+            let keep_last = rule.source.keep_last.unwrap_or(DEFAULT_KEEP_LAST);
+            let masked = match rule.strategy {
+                Strategy::Email => mask_email(&text, rule.mask_char)
+                    .unwrap_or_else(|| mask_partial(&text, 0, 0, rule.mask_char)),
+                Strategy::Hash => {
+                    let digest = digest(text.as_bytes(), key)?;
+                    let hex: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+                    format!(
+                        "{}{}",
+                        rule.source.hash_prefix.as_deref().unwrap_or(""),
+                        &hex[..rule.hash_length]
+                    )
+                }
+                _ => mask_partial(&text, keep_first, keep_last, rule.mask_char),
+            };
+            *value = Value::String(masked);
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+    Ok(())
+}
+
+fn remove_at(document: &mut Value, steps: &[Step]) {
+    let Some((last, parent)) = steps.split_last() else {
+        *document = Value::Null;
+        return;
+    };
+    match (value_at_mut(document, parent), last) {
+        (Some(Value::Object(map)), Step::Key(key)) => {
+            map.remove(key);
+        }
+        // Removing array elements would shift the indices other rules match, so the
+        // element is nulled instead
+        (Some(Value::Array(items)), Step::Index(i)) => {
+            if let Some(item) = items.get_mut(*i) {
+                *item = Value::Null;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_rule(rule: &MaskRule) -> Result<Rule<'_>, String> {
+    let segments = parse_path(&rule.path)?;
+    let strategy = Strategy::parse(&rule.strategy)?;
+    let mask_char = match rule.mask_char.as_deref() {
+        None => '*',
+        Some(text) => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => {
+                    return Err(format!(
+                        "mask_char must be a single character, got '{text}'"
+                    ));
+                }
+            }
+        }
+    };
+    let hash_length = rule.hash_length.unwrap_or(DEFAULT_HASH_LENGTH);
+    if !(8..=64).contains(&hash_length) {
+        return Err(format!(
+            "hash_length must be between 8 and 64, got {hash_length}"
+        ));
+    }
+    Ok(Rule {
+        segments,
+        strategy,
+        source: rule,
+        mask_char,
+        hash_length,
+    })
+}
+
+pub fn mask_document(input: DataMaskInput) -> Result<DataMaskOutput, String> {
+    if input.rules.is_empty() {
+        return Err("rules cannot be empty".to_string());
+    }
+    if input.rules.len() > MAX_RULES {
+        return Err(format!("At most {MAX_RULES} rules are allowed"));
+    }
+    let rules = input
+        .rules
+        .iter()
+        .map(validate_rule)
+        .collect::<Result<Vec<_>, _>>()?;
+    let key = input.key.as_deref().map(str::as_bytes);
+    if key.is_some_and(|k| k.is_empty()) {
+        return Err("key must not be empty".to_string());
+    }
+
+    // A byte order mark is common in files saved by Windows editors
+    let text = input.document.trim_start_matches('\u{feff}');
+    let mut document: Value =
+        serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {e}"))?;
+
+    // Fields masked so far; their descendants are left to the rule that masked them
+    let mut masked: Vec<Vec<Step>> = Vec::new();
+    let mut reports = Vec::with_capacity(rules.len());
+    for rule in &rules {
+        let mut found = BTreeSet::new();
+        collect(&document, &rule.segments, &mut Vec::new(), &mut found);
+        let fresh: Vec<Vec<Step>> = found
+            .into_iter()
+            .filter(|path| !masked.iter().any(|done| path.starts_with(done)))
+            .collect();
+        // Deepest first, so removing a field never moves one still to be masked
+        for path in fresh.iter().rev() {
+            match rule.strategy {
+                Strategy::Remove => remove_at(&mut document, path),
+                Strategy::Fixed => {
+                    if let Some(value) = value_at_mut(&mut document, path) {
+                        *value = rule
+                            .source
+                            .replacement
+                            .clone()
+                            .unwrap_or_else(|| Value::String(DEFAULT_REPLACEMENT.to_string()));
+                    }
+                }
+                _ => {
+                    if let Some(value) = value_at_mut(&mut document, path) {
+                        mask_leaves(value, rule, key)?;
+                    }
+                }
+            }
+        }
+        reports.push(RuleReport {
+            path: rule.source.path.clone(),
+            strategy: rule.source.strategy.to_lowercase(),
+            matches: fresh.len(),
+        });
+        masked.extend(fresh);
+    }
+
+    let uses_hash = rules.iter().any(|r| r.strategy == Strategy::Hash);
+    Ok(DataMaskOutput {
+        masked: document,
+        masked_count: masked.len(),
+        masked_paths: masked.iter().map(|p| render_path(p)).collect(),
+        rules: reports,
+        hash_algorithm: uses_hash.then(|| {
+            if key.is_some() {
+                "HMAC-SHA256"
+            } else {
+                "SHA-256"
+            }
+            .to_string()
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(path: &str, strategy: &str) -> MaskRule {
+        MaskRule {
+            path: path.to_string(),
+            strategy: strategy.to_string(),
+            replacement: None,
+            keep_first: None,
+            keep_last: None,
+            mask_char: None,
+            hash_length: None,
+            hash_prefix: None,
+        }
+    }
+
+    fn mask(document: Value, rules: Vec<MaskRule>, key: Option<&str>) -> DataMaskOutput {
+        mask_document(DataMaskInput {
+            document: document.to_string(),
+            rules,
+            key: key.map(str::to_string),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_format_preserving_strategies() {
+        let document = json!({
+            "card": "4111-1111-1111-1234",
+            "phone": 5551234567u64,
+            "email": "jane.doe@example.com",
+            "name": "Jane"
+        });
+        let output = mask(
+            document,
+            vec![
+                rule("card", "partial"),
+                rule("phone", "partial"),
+                rule("email", "email"),
+                rule("name", "fixed"),
+            ],
+            None,
+        );
+        assert_eq!(
+            output.masked,
+            json!({
+                "card": "****-****-****-1234",
+                "phone": "******4567",
+                "email": "j*******@example.com",
+                "name": "[REDACTED]"
+            })
+        );
+        assert_eq!(output.masked_count, 4);
+        assert_eq!(output.hash_algorithm, None);
+    }
+
+    #[test]
+    fn test_wildcards_and_reports() {
+        let document = json!({
+            "users": [
+                {"email": "a@x.io", "profile": {"ssn": "123-45-6789"}},
+                {"email": "bob@y.org", "ssn": "987-65-4321"}
+            ],
+            "ssn": "000-00-0000"
+        });
+        let mut ssn = rule("**.ssn", "partial");
+        ssn.mask_char = Some("#".to_string());
+        let output = mask(
+            document,
+            vec![
+                rule("users[*].email", "email"),
+                ssn,
+                rule("users[5].name", "fixed"),
+            ],
+            None,
+        );
+        assert_eq!(output.masked["users"][1]["email"], "b**@y.org");
+        assert_eq!(output.masked["users"][0]["profile"]["ssn"], "###-##-6789");
+        assert_eq!(output.masked["ssn"], "###-##-0000");
+        let matches: Vec<usize> = output.rules.iter().map(|r| r.matches).collect();
+        assert_eq!(matches, vec![2, 3, 0]);
+        assert!(
+            output
+                .masked_paths
+                .contains(&"users[0].profile.ssn".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hash_tokens_are_deterministic() {
+        let document = json!({"ids": ["alice", "bob", "alice"]});
+        let mut hash = rule("ids[*]", "hash");
+        hash.hash_prefix = Some("tok_".to_string());
+        let output = mask(document.clone(), vec![hash.clone()], None);
+        let ids = output.masked["ids"].as_array().unwrap();
+        assert_eq!(ids[0], ids[2]);
+        assert_ne!(ids[0], ids[1]);
+        // First 16 hex characters of SHA-256("alice")
+        let expected: String = Sha256::digest(b"alice")
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert_eq!(ids[0], format!("tok_{}", &expected[..16]));
+        assert_eq!(output.hash_algorithm.as_deref(), Some("SHA-256"));
+
+        let keyed = mask(document, vec![hash], Some("secret"));
+        assert_ne!(keyed.masked["ids"][0], ids[0]);
+        assert_eq!(keyed.hash_algorithm.as_deref(), Some("HMAC-SHA256"));
+    }
+
+    #[test]
+    fn test_remove_and_first_rule_wins() {
+        let document = json!({
+            "account": {"password": "hunter2", "token": "abc", "pin": 1234},
+            "tags": ["a", "b"]
+        });
+        let mut fixed = rule("account.*", "fixed");
+        fixed.replacement = Some(Value::Null);
+        let output = mask(
+            document,
+            vec![
+                rule("account.password", "remove"),
+                fixed,
+                rule("tags[0]", "remove"),
+            ],
+            None,
+        );
+        assert_eq!(
+            output.masked,
+            json!({"account": {"token": null, "pin": null}, "tags": [null, "b"]})
+        );
+        let matches: Vec<usize> = output.rules.iter().map(|r| r.matches).collect();
+        assert_eq!(matches, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_containers_mask_their_leaves() {
+        let document = json!({"address": {"street": "12 High St", "zip": 90210, "verified": true}});
+        let output = mask(document, vec![rule("address", "partial")], None);
+        assert_eq!(
+            output.masked,
+            json!({"address": {"street": "** **gh St", "zip": "*0210", "verified": true}})
+        );
+        // Too short to keep the last four
+        assert_eq!(mask_partial("abc", 0, 4, '*'), "***");
+        assert_eq!(mask_partial("ABCDEFGH", 2, 2, '*'), "AB****GH");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |rules: Vec<MaskRule>, document: &str| {
+            mask_document(DataMaskInput {
+                document: document.to_string(),
+                rules,
+                key: None,
+            })
+            .unwrap_err()
+        };
+        assert!(err(vec![rule("a", "scramble")], "{}").starts_with("Unsupported strategy"));
+        assert!(err(vec![rule("a[x]", "fixed")], "{}").contains("not an array index"));
+        assert!(err(vec![rule("a..b", "fixed")], "{}").contains("empty key"));
+        assert!(err(vec![rule("a", "fixed")], "{oops").starts_with("Invalid JSON"));
+        assert!(err(vec![], "{}").contains("rules cannot be empty"));
+        let mut long_hash = rule("a", "hash");
+        long_hash.hash_length = Some(65);
+        assert!(err(vec![long_hash], "{}").contains("hash_length"));
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    DedupeRecordsInput as LogicInput, DedupeRecordsOutput as LogicOutput, MAX_COMPARISONS,
+    MAX_RECORDS, remove_duplicates,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    DedupeKeySchema as DedupeKey, DedupeRecordsOutputSchema as DedupeRecordsOutput,
+    DuplicateGroupSchema as DuplicateGroup, KeySimilaritySchema as KeySimilarity,
+    RemovedRecordSchema as RemovedRecord,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DedupeRecordsInput {
+    #[serde(flatten)]
+    pub params: logic::DedupeRecordsInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Remove exact and fuzzy duplicates from an array of JSON objects, matching on
+/// normalized key fields with similarity thresholds, and report which records were
+/// dropped or merged and why
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn dedupe_records(request: Request<DedupeRecordsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "dedupe_records",
+                DedupeRecordsInput,
+                DedupeRecordsOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("dedupe_records", &input);
+    log.finish(run_dedupe_records(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_dedupe_records(input: DedupeRecordsInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("records", &input.params.records)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::remove_duplicates(input.params.into()) {
+        Ok(result) => DedupeRecordsOutput::from(result),
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&result).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
@@ -0,0 +1,688 @@
+use ftl_tool_types_derive::ToolTypes;
+use record_link_tool::{jaro_winkler, levenshtein_similarity, token_jaccard};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Most records in one call
+pub const MAX_RECORDS: usize = 10_000;
+/// Most record pairs compared with fuzzy keys
+pub const MAX_COMPARISONS: usize = 1_000_000;
+/// Most key fields
+const MAX_KEYS: usize = 20;
+/// Similarity fuzzy comparators need by default
+const DEFAULT_THRESHOLD: f64 = 0.9;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DedupeKey {
+    /// Field to compare; dots reach into nested objects, e.g. "contact.email"
+    pub field: String,
+    /// "exact", "levenshtein", "jaro_winkler" or "jaccard" (word overlap) (default: exact)
+    pub comparator: Option<String>,
+    /// Similarity, 0-1, the values need to count as the same (default: 0.9; exact always
+    /// needs 1)
+    pub threshold: Option<f64>,
+    /// Normalization applied before comparing, in order: "case", "whitespace" (trim and
+    /// collapse runs), "punctuation" (drop it), "digits_only" or "token_order" (sort
+    /// words) (default: ["case", "whitespace"])
+    pub normalize: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DedupeRecordsInput {
+    /// Records to deduplicate, each a JSON object
+    pub records: Vec<Value>,
+    /// Fields that identify a record; two records are duplicates when every key matches,
+    /// and records missing a key are never duplicates on it (default: the whole record
+    /// must match exactly)
+    #[tool_types(nested)]
+    pub keys: Option<Vec<DedupeKey>>,
+    /// Only records sharing this field's value are compared with fuzzy keys, ignoring
+    /// case, spaces and punctuation (default: every pair)
+    pub blocking_key: Option<String>,
+    /// Record kept from each group: "first", "last" or "most_complete" (most non-null
+    /// fields, then first) (default: first)
+    pub keep: Option<String>,
+    /// Fill fields missing or null in the kept record from its duplicates (default: false)
+    pub merge: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct KeySimilarity {
+    pub field: String,
+    /// Similarity of the normalized values, 0-1
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RemovedRecord {
+    /// Index of the removed record in records
+    pub index: usize,
+    /// Index of the record it was found to duplicate
+    pub matched_index: usize,
+    /// "exact" when every normalized key was equal, otherwise "fuzzy"
+    pub kind: String,
+    /// Key similarities behind the match, empty for whole-record matches
+    #[tool_types(nested)]
+    pub keys: Vec<KeySimilarity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DuplicateGroup {
+    /// Index of the record kept for the group
+    pub kept_index: usize,
+    /// Records removed as its duplicates, in index order
+    #[tool_types(nested)]
+    pub removed: Vec<RemovedRecord>,
+    /// Fields the kept record took from its duplicates (merge only)
+    pub filled_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DedupeRecordsOutput {
+    /// Records left after deduplication, in their original order
+    pub records: Vec<Value>,
+    /// Index of each remaining record in the input
+    pub kept_indices: Vec<usize>,
+    /// Groups of two or more records, ordered by kept index
+    #[tool_types(nested)]
+    pub groups: Vec<DuplicateGroup>,
+    pub input_count: usize,
+    pub output_count: usize,
+    pub removed_count: usize,
+    pub exact_duplicates: usize,
+    pub fuzzy_duplicates: usize,
+    /// Record pairs compared with fuzzy keys
+    pub compared_pairs: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparator {
+    Exact,
+    Levenshtein,
+    JaroWinkler,
+    Jaccard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Normalization {
+    Case,
+    Whitespace,
+    Punctuation,
+    DigitsOnly,
+    TokenOrder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Keep {
+    First,
+    Last,
+    MostComplete,
+}
+
+/// A validated key
+struct Key {
+    name: String,
+    path: Vec<String>,
+    comparator: Comparator,
+    threshold: f64,
+    normalize: Vec<Normalization>,
+}
+
+impl Key {
+    fn parse(key: &DedupeKey) -> Result<Key, String> {
+        let name = key.field.trim();
+        if name.is_empty() {
+            return Err("Key fields cannot be empty".to_string());
+        }
+        let comparator = match key
+            .comparator
+            .as_deref()
+            .map(|c| c.trim().to_lowercase())
+            .as_deref()
+        {
+            None | Some("exact") => Comparator::Exact,
+            Some("levenshtein") => Comparator::Levenshtein,
+            Some("jaro_winkler") => Comparator::JaroWinkler,
+            Some("jaccard") => Comparator::Jaccard,
+            Some(other) => {
+                return Err(format!(
+                    "Unsupported comparator: {other}. Use 'exact', 'levenshtein', 'jaro_winkler' or 'jaccard'"
+                ));
+            }
+        };
+        let threshold = match comparator {
+            Comparator::Exact => 1.0,
+            _ => key.threshold.unwrap_or(DEFAULT_THRESHOLD),
+        };
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(format!(
+                "threshold for '{name}' must be above 0 and at most 1"
+            ));
+        }
+        let normalize = match &key.normalize {
+            None => vec![Normalization::Case, Normalization::Whitespace],
+            Some(steps) => steps
+                .iter()
+                .map(|step| match step.trim().to_lowercase().as_str() {
+                    "case" => Ok(Normalization::Case),
+                    "whitespace" => Ok(Normalization::Whitespace),
+                    "punctuation" => Ok(Normalization::Punctuation),
+                    "digits_only" => Ok(Normalization::DigitsOnly),
+                    "token_order" => Ok(Normalization::TokenOrder),
+                    other => Err(format!(
+                        "Unsupported normalization: {other}. Use 'case', 'whitespace', 'punctuation', 'digits_only' or 'token_order'"
+                    )),
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        Ok(Key {
+            name: name.to_string(),
+            path: split_path(name),
+            comparator,
+            threshold,
+            normalize,
+        })
+    }
+
+    /// Normalized value of the key, or None when it is missing, null or blank
+    fn value(&self, record: &Value) -> Option<String> {
+        let mut text = match lookup(record, &self.path)? {
+            Value::String(s) => s.clone(),
+            Value::Null => return None,
+            other => other.to_string(),
+        };
+        for step in &self.normalize {
+            text = match step {
+                Normalization::Case => text.to_lowercase(),
+                Normalization::Whitespace => text.split_whitespace().collect::<Vec<_>>().join(" "),
+                Normalization::Punctuation => text
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+                    .collect(),
+                Normalization::DigitsOnly => text.chars().filter(char::is_ascii_digit).collect(),
+                Normalization::TokenOrder => {
+                    let mut words: Vec<&str> = text.split_whitespace().collect();
+                    words.sort_unstable();
+                    words.join(" ")
+                }
+            };
+        }
+        (!text.trim().is_empty()).then_some(text)
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        match self.comparator {
+            Comparator::Exact => f64::from(u8::from(a == b)),
+            Comparator::Levenshtein => levenshtein_similarity(a, b),
+            Comparator::JaroWinkler => jaro_winkler(a, b),
+            Comparator::Jaccard => token_jaccard(a, b),
+        }
+    }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('.').map(str::to_string).collect()
+}
+
+fn lookup<'v>(record: &'v Value, path: &[String]) -> Option<&'v Value> {
+    path.iter()
+        .try_fold(record, |value, key| value.as_object()?.get(key))
+}
+
+/// Merges groups with union-find, keeping the lower index as the root
+struct Groups {
+    parent: Vec<usize>,
+}
+
+impl Groups {
+    fn new(size: usize) -> Self {
+        Groups {
+            parent: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut node = i;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    /// Joins the groups of a and b; false when they were already one group
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        self.parent[ra.max(rb)] = ra.min(rb);
+        true
+    }
+}
+
+/// A match that joined two groups
+struct Link {
+    left: usize,
+    right: usize,
+    exact: bool,
+    keys: Vec<KeySimilarity>,
+}
+
+fn non_null_fields(record: &Value) -> usize {
+    record
+        .as_object()
+        .map_or(0, |map| map.values().filter(|v| !v.is_null()).count())
+}
+
+pub fn remove_duplicates(input: DedupeRecordsInput) -> Result<DedupeRecordsOutput, String> {
+    let records = &input.records;
+    if records.len() > MAX_RECORDS {
+        return Err(format!("At most {MAX_RECORDS} records are allowed"));
+    }
+    if let Some(i) = records.iter().position(|r| !r.is_object()) {
+        return Err(format!("records[{i}] must be a JSON object"));
+    }
+    let keys = input
+        .keys
+        .iter()
+        .flatten()
+        .map(Key::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    if keys.len() > MAX_KEYS {
+        return Err(format!("At most {MAX_KEYS} keys are allowed"));
+    }
+    let keep = match input
+        .keep
+        .as_deref()
+        .map(|k| k.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("first") => Keep::First,
+        Some("last") => Keep::Last,
+        Some("most_complete") => Keep::MostComplete,
+        Some(other) => {
+            return Err(format!(
+                "Unsupported keep: {other}. Use 'first', 'last' or 'most_complete'"
+            ));
+        }
+    };
+
+    let mut groups = Groups::new(records.len());
+    let mut links: Vec<Link> = Vec::new();
+    let mut compared_pairs = 0;
+    if keys.iter().all(|k| k.comparator == Comparator::Exact) {
+        // Exact keys group by value without comparing pairs
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        for (i, record) in records.iter().enumerate() {
+            let signature = if keys.is_empty() {
+                // Keys of a serde_json map are sorted, so equal records print alike
+                Some(record.to_string())
+            } else {
+                keys.iter()
+                    .map(|k| k.value(record))
+                    .collect::<Option<Vec<_>>>()
+                    .map(|values| serde_json::to_string(&values).unwrap_or_default())
+            };
+            let Some(signature) = signature else {
+                continue;
+            };
+            match first_seen.get(&signature) {
+                Some(&j) => {
+                    groups.union(j, i);
+                    links.push(Link {
+                        left: j,
+                        right: i,
+                        exact: true,
+                        keys: keys
+                            .iter()
+                            .map(|k| KeySimilarity {
+                                field: k.name.clone(),
+                                similarity: 1.0,
+                            })
+                            .collect(),
+                    });
+                }
+                None => {
+                    first_seen.insert(signature, i);
+                }
+            }
+        }
+    } else {
+        let values: Vec<Option<Vec<String>>> = records
+            .iter()
+            .map(|r| keys.iter().map(|k| k.value(r)).collect())
+            .collect();
+        let blocks: Vec<Vec<usize>> = match &input.blocking_key {
+            None => vec![(0..records.len()).collect()],
+            Some(field) => {
+                let path = split_path(field.trim());
+                let mut blocks: HashMap<String, Vec<usize>> = HashMap::new();
+                for (i, record) in records.iter().enumerate() {
+                    let block: Option<String> = match lookup(record, &path) {
+                        Some(Value::String(s)) => Some(s.clone()),
+                        Some(Value::Null) | None => None,
+                        Some(other) => Some(other.to_string()),
+                    }
+                    .map(|s| {
+                        s.chars()
+                            .filter(|c| c.is_alphanumeric())
+                            .collect::<String>()
+                            .to_lowercase()
+                    })
+                    .filter(|s| !s.is_empty());
+                    if let Some(block) = block {
+                        blocks.entry(block).or_default().push(i);
+                    }
+                }
+                blocks.into_values().collect()
+            }
+        };
+        let total: usize = blocks
+            .iter()
+            .map(|b| b.len() * b.len().saturating_sub(1) / 2)
+            .sum();
+        if total > MAX_COMPARISONS {
+            return Err(format!(
+                "More than {MAX_COMPARISONS} record pairs to compare; set or narrow blocking_key"
+            ));
+        }
+        for block in &blocks {
+            for (a, &j) in block.iter().enumerate() {
+                for &i in &block[a + 1..] {
+                    compared_pairs += 1;
+                    let (Some(left), Some(right)) = (&values[j], &values[i]) else {
+                        continue;
+                    };
+                    let similarities: Vec<f64> = keys
+                        .iter()
+                        .zip(left.iter().zip(right))
+                        .map(|(k, (a, b))| k.similarity(a, b))
+                        .collect();
+                    let matches = keys
+                        .iter()
+                        .zip(&similarities)
+                        .all(|(k, s)| *s >= k.threshold);
+                    // Only matches that join groups are kept, so the links form a tree
+                    if matches && groups.union(j, i) {
+                        links.push(Link {
+                            left: j,
+                            right: i,
+                            exact: similarities.iter().all(|s| *s == 1.0),
+                            keys: keys
+                                .iter()
+                                .zip(&similarities)
+                                .map(|(k, s)| KeySimilarity {
+                                    field: k.name.clone(),
+                                    similarity: *s,
+                                })
+                                .collect(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    let mut neighbours: HashMap<usize, Vec<(usize, &Link)>> = HashMap::new();
+    for link in &links {
+        neighbours
+            .entry(link.left)
+            .or_default()
+            .push((link.right, link));
+        neighbours
+            .entry(link.right)
+            .or_default()
+            .push((link.left, link));
+    }
+
+    // Collect members of each group in index order
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); records.len()];
+    for i in 0..records.len() {
+        let root = groups.find(i);
+        members[root].push(i);
+    }
+    let merge = input.merge.unwrap_or(false);
+    let mut kept: Vec<(usize, Value)> = Vec::new();
+    let mut duplicate_groups = Vec::new();
+    let (mut exact_duplicates, mut fuzzy_duplicates) = (0, 0);
+    for group in members.into_iter().filter(|m| !m.is_empty()) {
+        let kept_index = match keep {
+            Keep::First => group[0],
+            Keep::Last => group[group.len() - 1],
+            Keep::MostComplete => *group
+                .iter()
+                .max_by(|a, b| {
+                    non_null_fields(&records[**a])
+                        .cmp(&non_null_fields(&records[**b]))
+                        .then(b.cmp(a))
+                })
+                .expect("groups are never empty"),
+        };
+        let mut record = records[kept_index].clone();
+        if group.len() == 1 {
+            kept.push((kept_index, record));
+            continue;
+        }
+        let mut filled_fields = Vec::new();
+        if merge {
+            let target = record.as_object_mut().expect("records are objects");
+            for &other in group.iter().filter(|&&i| i != kept_index) {
+                for (field, value) in records[other].as_object().expect("records are objects") {
+                    let missing = target.get(field).is_none_or(Value::is_null);
+                    if missing && !value.is_null() {
+                        target.insert(field.clone(), value.clone());
+                        filled_fields.push(field.clone());
+                    }
+                }
+            }
+        }
+        // Walk the links out from the kept record so each removed record points at the
+        // record that brought it into the group
+        let mut removed = Vec::new();
+        let mut queue = std::collections::VecDeque::from([kept_index]);
+        let mut seen = vec![kept_index];
+        while let Some(current) = queue.pop_front() {
+            for &(next, link) in neighbours.get(&current).into_iter().flatten() {
+                if seen.contains(&next) {
+                    continue;
+                }
+                seen.push(next);
+                queue.push_back(next);
+                removed.push(RemovedRecord {
+                    index: next,
+                    matched_index: current,
+                    kind: if link.exact { "exact" } else { "fuzzy" }.to_string(),
+                    keys: link.keys.clone(),
+                });
+            }
+        }
+        removed.sort_by_key(|r| r.index);
+        for r in &removed {
+            if r.kind == "exact" {
+                exact_duplicates += 1;
+            } else {
+                fuzzy_duplicates += 1;
+            }
+        }
+        duplicate_groups.push(DuplicateGroup {
+            kept_index,
+            removed,
+            filled_fields,
+        });
+        kept.push((kept_index, record));
+    }
+    kept.sort_by_key(|(i, _)| *i);
+    duplicate_groups.sort_by_key(|g| g.kept_index);
+
+    let kept_count = kept.len();
+    let removed_count = records.len() - kept_count;
+    Ok(DedupeRecordsOutput {
+        kept_indices: kept.iter().map(|(i, _)| *i).collect(),
+        records: kept.into_iter().map(|(_, r)| r).collect(),
+        groups: duplicate_groups,
+        input_count: records.len(),
+        output_count: kept_count,
+        removed_count,
+        exact_duplicates,
+        fuzzy_duplicates,
+        compared_pairs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn key(field: &str, comparator: &str) -> DedupeKey {
+        DedupeKey {
+            field: field.to_string(),
+            comparator: Some(comparator.to_string()),
+            threshold: None,
+            normalize: None,
+        }
+    }
+
+    fn input(records: Value, keys: Option<Vec<DedupeKey>>) -> DedupeRecordsInput {
+        DedupeRecordsInput {
+            records: records.as_array().unwrap().clone(),
+            keys,
+            blocking_key: None,
+            keep: None,
+            merge: None,
+        }
+    }
+
+    #[test]
+    fn test_whole_record_duplicates() {
+        let result = remove_duplicates(input(
+            json!([{"a": 1, "b": 2}, {"b": 2, "a": 1}, {"a": 1, "b": 3}]),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(result.kept_indices, vec![0, 2]);
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.exact_duplicates, 1);
+        assert_eq!(result.groups[0].removed[0].matched_index, 0);
+        assert_eq!(result.compared_pairs, 0);
+    }
+
+    #[test]
+    fn test_normalized_exact_keys() {
+        let mut email = key("contact.email", "exact");
+        email.normalize = Some(vec!["case".to_string(), "whitespace".to_string()]);
+        let mut phone = key("phone", "exact");
+        phone.normalize = Some(vec!["digits_only".to_string()]);
+        let result = remove_duplicates(input(
+            json!([
+                {"contact": {"email": "Ann@Example.com"}, "phone": "(555) 010-2000"},
+                {"contact": {"email": " ann@example.com "}, "phone": "555.010.2000"},
+                {"contact": {"email": "ann@example.com"}},
+                {"contact": {"email": "bob@example.com"}, "phone": "555-010-2000"}
+            ]),
+            Some(vec![email, phone]),
+        ))
+        .unwrap();
+        // Record 2 has no phone, so it is never a duplicate on it
+        assert_eq!(result.kept_indices, vec![0, 2, 3]);
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].removed[0].index, 1);
+        assert_eq!(result.groups[0].removed[0].kind, "exact");
+    }
+
+    #[test]
+    fn test_fuzzy_keys_group_transitively() {
+        let mut name = key("name", "jaro_winkler");
+        name.threshold = Some(0.9);
+        let result = remove_duplicates(input(
+            json!([
+                {"name": "Jonathan Smith", "zip": "02118"},
+                {"name": "Jonathon Smith", "zip": "02118"},
+                {"name": "Maria Garcia", "zip": "94107"},
+                {"name": "Jonathon Smyth", "zip": "02118"}
+            ]),
+            Some(vec![name, key("zip", "exact")]),
+        ))
+        .unwrap();
+        assert_eq!(result.kept_indices, vec![0, 2]);
+        assert_eq!(result.fuzzy_duplicates, 2);
+        let removed = &result.groups[0].removed;
+        assert_eq!(
+            removed.iter().map(|r| r.index).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(removed[0].matched_index, 0);
+        assert!(removed[0].keys[0].similarity < 1.0);
+        assert_eq!(removed[0].keys[1].similarity, 1.0);
+        assert_eq!(result.compared_pairs, 6);
+    }
+
+    #[test]
+    fn test_blocking_key_limits_comparisons() {
+        let mut request = input(
+            json!([
+                {"name": "Acme Corp", "country": "US"},
+                {"name": "ACME Corp.", "country": "us"},
+                {"name": "Acme Corp", "country": "DE"}
+            ]),
+            Some(vec![DedupeKey {
+                field: "name".to_string(),
+                comparator: Some("levenshtein".to_string()),
+                threshold: Some(0.95),
+                normalize: Some(vec!["case".to_string(), "punctuation".to_string()]),
+            }]),
+        );
+        request.blocking_key = Some("country".to_string());
+        let result = remove_duplicates(request).unwrap();
+        assert_eq!(result.kept_indices, vec![0, 2]);
+        assert_eq!(result.compared_pairs, 1);
+        // Identical after normalization, so reported as exact
+        assert_eq!(result.groups[0].removed[0].kind, "exact");
+    }
+
+    #[test]
+    fn test_keep_most_complete_and_merge() {
+        let mut request = input(
+            json!([
+                {"email": "a@x.com", "name": null},
+                {"email": "A@x.com", "name": "Ann", "city": "Oslo"},
+                {"email": "a@x.com", "phone": "123"}
+            ]),
+            Some(vec![key("email", "exact")]),
+        );
+        request.keep = Some("most_complete".to_string());
+        request.merge = Some(true);
+        let result = remove_duplicates(request).unwrap();
+        assert_eq!(result.kept_indices, vec![1]);
+        let group = &result.groups[0];
+        assert_eq!(group.kept_index, 1);
+        assert_eq!(
+            group.removed.iter().map(|r| r.index).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(group.removed[0].matched_index, 1);
+        assert_eq!(group.filled_fields, vec!["phone".to_string()]);
+        assert_eq!(
+            result.records[0],
+            json!({"email": "A@x.com", "name": "Ann", "city": "Oslo", "phone": "123"})
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(remove_duplicates(input(json!([1]), None)).is_err());
+        assert!(remove_duplicates(input(json!([{}]), Some(vec![key("a", "soundex")]))).is_err());
+        let mut request = input(json!([{}]), None);
+        request.keep = Some("newest".to_string());
+        assert!(remove_duplicates(request).is_err());
+        let mut bad = key("a", "levenshtein");
+        bad.threshold = Some(1.5);
+        assert!(remove_duplicates(input(json!([{}]), Some(vec![bad]))).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    DEFAULT_OPTIONAL_PROBABILITY, GenerateFromSchemaInput as LogicInput,
+    GenerateFromSchemaOutput as LogicOutput, Generator, generate_documents,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GenerateFromSchemaInput {
+    /// JSON Schema to generate documents for (as JSON string). Local $ref, allOf, anyOf,
+    /// oneOf, enum, const, numeric bounds, string lengths and common formats, array and
+    /// object constraints are honored
+    pub schema: String,
+    /// Number of documents to generate (default: 1, at most 1000)
+    pub count: Option<usize>,
+    /// Seed for reproducible documents (default: random, returned in the response)
+    pub seed: Option<u64>,
+    /// Favor boundary values: minimum and maximum numbers, shortest and longest strings
+    /// and arrays (default: false)
+    pub boundary_values: Option<bool>,
+    /// Chance from 0 to 1 that each optional property is included (default: 0.5)
+    pub optional_probability: Option<f64>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GenerateFromSchemaOutput {
+    /// Generated documents, each valid against the schema apart from any warnings
+    pub documents: Vec<Value>,
+    /// Number of documents generated
+    pub count: usize,
+    /// Seed used; pass it again to reproduce the same documents
+    pub seed: u64,
+    /// Unsupported keywords that were ignored, such as pattern, with their location
+    pub warnings: Vec<String>,
+}
+
+/// Generate sample documents that satisfy a JSON Schema, with seeded randomness and an
+/// option to favor boundary values, for contract testing alongside json_validator
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn generate_from_schema(request: Request<GenerateFromSchemaInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "generate_from_schema",
+                GenerateFromSchemaInput,
+                GenerateFromSchemaOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("generate_from_schema", &input);
+    log.finish(run_generate_from_schema(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_generate_from_schema(input: GenerateFromSchemaInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("schema", &input.schema).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        schema: input.schema,
+        count: input.count,
+        seed: input.seed,
+        boundary_values: input.boundary_values,
+        optional_probability: input.optional_probability,
+    };
+
+    // Call logic implementation
+    let result = match logic::generate_documents(logic_input) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let response = GenerateFromSchemaOutput {
+        documents: result.documents,
+        count: result.count,
+        seed: result.seed,
+        warnings: result.warnings,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
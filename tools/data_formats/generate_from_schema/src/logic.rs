@@ -0,0 +1,1387 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+pub const DEFAULT_COUNT: usize = 1;
+pub const MAX_COUNT: usize = 1_000;
+pub const DEFAULT_OPTIONAL_PROBABILITY: f64 = 0.5;
+
+/// Nesting depth after which optional properties and array items beyond the minimum
+/// are left out, so recursive schemas terminate
+const SOFT_DEPTH: usize = 8;
+/// Nesting depth at which generation fails, for schemas whose required properties
+/// recurse forever
+const MAX_DEPTH: usize = 64;
+/// Span of numbers generated for a side the schema leaves unbounded
+const DEFAULT_NUMBER_SPAN: f64 = 1000.0;
+/// Extra length allowed over minLength and minItems when no maximum is given
+const DEFAULT_EXTRA_LENGTH: usize = 12;
+const DEFAULT_EXTRA_ITEMS: usize = 4;
+/// Fresh items tried for each slot of a uniqueItems array before giving up
+const UNIQUE_ATTEMPTS: usize = 20;
+/// Integers beyond this are not exactly representable as f64
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Keywords that constrain values but are not generated for; documents may violate them
+const UNSUPPORTED_KEYWORDS: [&str; 13] = [
+    "pattern",
+    "patternProperties",
+    "not",
+    "if",
+    "contains",
+    "minContains",
+    "maxContains",
+    "dependencies",
+    "dependentRequired",
+    "dependentSchemas",
+    "propertyNames",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+const SCALAR_TYPES: [&str; 5] = ["string", "integer", "number", "boolean", "null"];
+const WORDS: [&str; 16] = [
+    "alpha", "bravo", "cedar", "delta", "ember", "falcon", "garnet", "harbor", "indigo", "juniper",
+    "kestrel", "lumen", "maple", "nova", "orbit", "pebble",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateFromSchemaInput {
+    /// JSON Schema to generate documents for (as JSON string)
+    pub schema: String,
+    /// Number of documents to generate (default: 1, at most 1000)
+    pub count: Option<usize>,
+    /// Seed for reproducible documents (default: random)
+    pub seed: Option<u64>,
+    /// Favor boundary values: minimum and maximum numbers, shortest and longest strings
+    /// and arrays (default: false)
+    pub boundary_values: Option<bool>,
+    /// Chance from 0 to 1 that each optional property is included (default: 0.5)
+    pub optional_probability: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateFromSchemaOutput {
+    pub documents: Vec<Value>,
+    pub count: usize,
+    /// Seed used, to reproduce the same documents
+    pub seed: u64,
+    /// Keywords that were ignored, with their schema location
+    pub warnings: Vec<String>,
+}
+
+/// SplitMix64, so a seed gives the same documents on every platform
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, bound)
+    fn below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        self.unit() < probability
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// Random documents for a JSON Schema. Other schema-driven tools link this crate and
+/// call [`Generator::generate`] directly.
+pub struct Generator<'a> {
+    root: &'a Value,
+    rng: SplitMix64,
+    boundary_values: bool,
+    optional_probability: f64,
+    null_probability: Option<f64>,
+    ignored: BTreeSet<String>,
+}
+
+impl<'a> Generator<'a> {
+    pub fn new(
+        root: &'a Value,
+        seed: u64,
+        boundary_values: bool,
+        optional_probability: f64,
+    ) -> Generator<'a> {
+        Generator {
+            root,
+            rng: SplitMix64(seed),
+            boundary_values,
+            optional_probability,
+            null_probability: None,
+            ignored: BTreeSet::new(),
+        }
+    }
+
+    /// Chance from 0 to 1 of choosing null wherever a type list or an anyOf/oneOf branch
+    /// allows it, instead of picking uniformly among the alternatives
+    pub fn with_null_probability(mut self, null_probability: f64) -> Generator<'a> {
+        self.null_probability = Some(null_probability);
+        self
+    }
+
+    /// One document for the root schema
+    pub fn generate(&mut self) -> Result<Value, String> {
+        self.value(self.root, "#", 0)
+    }
+
+    /// One document for the subschema at a local reference such as `#/$defs/item`
+    pub fn generate_at(&mut self, reference: &str) -> Result<Value, String> {
+        let schema = self.resolve(reference, "#")?;
+        self.value(schema, reference, 0)
+    }
+
+    /// Unsupported keywords met so far, as warnings
+    pub fn warnings(&self) -> Vec<String> {
+        self.ignored
+            .iter()
+            .map(|location| {
+                format!("Ignored unsupported keyword {location}; documents may not satisfy it")
+            })
+            .collect()
+    }
+
+    fn value(&mut self, schema: &Value, path: &str, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err(format!(
+                "Schema at {path} nests deeper than {MAX_DEPTH} levels; its required \
+                 properties or minItems recurse without end"
+            ));
+        }
+        let obj = match schema {
+            Value::Bool(true) => return Ok(self.any_scalar()),
+            Value::Bool(false) => {
+                return Err(format!("Schema at {path} is false and accepts no value"));
+            }
+            Value::Object(obj) => obj,
+            _ => return Err(format!("Schema at {path} must be an object or a boolean")),
+        };
+
+        for keyword in UNSUPPORTED_KEYWORDS {
+            if obj.contains_key(keyword) {
+                self.ignored.insert(format!("'{keyword}' at {path}"));
+            }
+        }
+
+        if let Some(reference) = obj.get("$ref") {
+            let reference = reference
+                .as_str()
+                .ok_or_else(|| format!("$ref at {path} must be a string"))?;
+            let target = self.resolve(reference, path)?;
+            return self.value(target, reference, depth + 1);
+        }
+        if let Some(value) = obj.get("const") {
+            return Ok(value.clone());
+        }
+        if let Some(options) = obj.get("enum") {
+            return match options.as_array() {
+                Some(options) if !options.is_empty() => Ok(self.rng.pick(options).clone()),
+                _ => Err(format!("enum at {path} must be a non-empty array")),
+            };
+        }
+        if obj.contains_key("allOf") {
+            let merged = self.merge_all_of(obj, path)?;
+            return self.value(&Value::Object(merged), path, depth);
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(branches) = obj.get(keyword) {
+                let branches = match branches.as_array() {
+                    Some(branches) if !branches.is_empty() => branches,
+                    _ => return Err(format!("{keyword} at {path} must be a non-empty array")),
+                };
+                let null_branch = branches
+                    .iter()
+                    .position(|branch| branch.get("type").and_then(Value::as_str) == Some("null"));
+                let index = self.choose(branches.len(), null_branch);
+                // The branch is combined with the keywords next to anyOf/oneOf
+                let mut merged = obj.clone();
+                merged.remove(keyword);
+                let branch = self.dereference(&branches[index], path)?;
+                merge_schema(&mut merged, branch, path)?;
+                let branch_path = format!("{path}/{keyword}/{index}");
+                return self.value(&Value::Object(merged), &branch_path, depth);
+            }
+        }
+
+        let type_name = match obj.get("type") {
+            Some(Value::String(name)) => name.clone(),
+            Some(Value::Array(names)) if !names.is_empty() => {
+                let null_index = names.iter().position(|name| name.as_str() == Some("null"));
+                names[self.choose(names.len(), null_index)]
+                    .as_str()
+                    .ok_or_else(|| format!("type at {path} must list type names"))?
+                    .to_string()
+            }
+            Some(_) => {
+                return Err(format!(
+                    "type at {path} must be a type name or a non-empty array of them"
+                ));
+            }
+            None => match infer_type(obj) {
+                Some(name) => name.to_string(),
+                None => self.rng.pick(&SCALAR_TYPES).to_string(),
+            },
+        };
+
+        match type_name.as_str() {
+            "null" => Ok(Value::Null),
+            "boolean" => Ok(Value::Bool(self.rng.chance(0.5))),
+            "integer" => self.integer(obj, path),
+            "number" => self.number(obj, path),
+            "string" => self.string(obj, path),
+            "array" => self.array(obj, path, depth),
+            "object" => self.object(obj, path, depth),
+            other => Err(format!("Unknown type '{other}' at {path}")),
+        }
+    }
+
+    /// Index among `len` alternatives, weighting the null one by `null_probability` when set
+    fn choose(&mut self, len: usize, null_index: Option<usize>) -> usize {
+        match (self.null_probability, null_index) {
+            (Some(p), Some(null_index)) if len > 1 => {
+                if self.rng.chance(p) {
+                    null_index
+                } else {
+                    // Uniform among the others, skipping over the null alternative
+                    let index = self.rng.below(len as u64 - 1) as usize;
+                    if index >= null_index {
+                        index + 1
+                    } else {
+                        index
+                    }
+                }
+            }
+            _ => self.rng.below(len as u64) as usize,
+        }
+    }
+
+    fn resolve(&self, reference: &str, path: &str) -> Result<&'a Value, String> {
+        let pointer = reference.strip_prefix('#').ok_or_else(|| {
+            format!(
+                "Only local references such as #/$defs/name are supported, got \
+                 '{reference}' at {path}"
+            )
+        })?;
+        self.root
+            .pointer(pointer)
+            .ok_or_else(|| format!("Reference '{reference}' at {path} does not resolve"))
+    }
+
+    /// The schema itself, or its target when it is only a $ref
+    fn dereference<'s>(&self, schema: &'s Value, path: &str) -> Result<&'s Value, String>
+    where
+        'a: 's,
+    {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => self.resolve(reference, path),
+            None => Ok(schema),
+        }
+    }
+
+    /// Fold allOf, including allOf nested in its members, into a single schema
+    fn merge_all_of(
+        &self,
+        obj: &Map<String, Value>,
+        path: &str,
+    ) -> Result<Map<String, Value>, String> {
+        let mut merged = obj.clone();
+        while let Some(members) = merged.remove("allOf") {
+            let members = members
+                .as_array()
+                .ok_or_else(|| format!("allOf at {path} must be an array"))?
+                .clone();
+            for member in &members {
+                let member = self.dereference(member, path)?;
+                merge_schema(&mut merged, member, path)?;
+            }
+        }
+        Ok(merged)
+    }
+
+    fn any_scalar(&mut self) -> Value {
+        match *self.rng.pick(&["string", "integer", "boolean", "null"]) {
+            "string" => Value::String(self.rng.pick(&WORDS).to_string()),
+            "integer" => Value::from(self.rng.below(1000) as i64),
+            "boolean" => Value::Bool(self.rng.chance(0.5)),
+            _ => Value::Null,
+        }
+    }
+
+    /// A count in [min, max], at either end half the time with boundary_values
+    fn count_between(&mut self, min: usize, max: usize) -> usize {
+        if self.boundary_values && self.rng.chance(0.5) {
+            return if self.rng.chance(0.5) { min } else { max };
+        }
+        min + self.rng.below((max - min) as u64 + 1) as usize
+    }
+
+    fn integer(&mut self, obj: &Map<String, Value>, path: &str) -> Result<Value, String> {
+        let (lower, upper) = numeric_bounds(obj, path)?;
+        let low = match lower {
+            Some((value, true)) => value.floor() + 1.0,
+            Some((value, false)) => value.ceil(),
+            None => f64::NAN,
+        };
+        let high = match upper {
+            Some((value, true)) => value.ceil() - 1.0,
+            Some((value, false)) => value.floor(),
+            None => f64::NAN,
+        };
+        let (low, high) = default_span(low, high);
+        if low.abs() > MAX_SAFE_INTEGER || high.abs() > MAX_SAFE_INTEGER {
+            return Err(format!(
+                "Integer bounds at {path} are beyond ±{MAX_SAFE_INTEGER} and cannot be \
+                 generated exactly"
+            ));
+        }
+
+        let step = match multiple_of(obj, path)? {
+            Some(step) if step.fract() != 0.0 => {
+                return Err(format!(
+                    "multipleOf for integers at {path} must be a whole number"
+                ));
+            }
+            Some(step) => step,
+            None => 1.0,
+        };
+        let first = (low / step).ceil();
+        let last = (high / step).floor();
+        if first > last {
+            return Err(format!(
+                "No integer at {path} satisfies its bounds and multipleOf"
+            ));
+        }
+        let span = (last - first).min(u32::MAX as f64) as usize;
+        let k = first + self.count_between(0, span) as f64;
+        Ok(Value::from((k * step) as i64))
+    }
+
+    fn number(&mut self, obj: &Map<String, Value>, path: &str) -> Result<Value, String> {
+        let (lower, upper) = numeric_bounds(obj, path)?;
+        let low = match lower {
+            Some((value, true)) => next_toward(value, f64::INFINITY),
+            Some((value, false)) => value,
+            None => f64::NAN,
+        };
+        let high = match upper {
+            Some((value, true)) => next_toward(value, f64::NEG_INFINITY),
+            Some((value, false)) => value,
+            None => f64::NAN,
+        };
+        let (low, high) = default_span(low, high);
+        if low > high {
+            return Err(format!("No number at {path} satisfies its bounds"));
+        }
+
+        let value = if let Some(step) = multiple_of(obj, path)? {
+            let first = (low / step).ceil();
+            let last = (high / step).floor();
+            if first > last {
+                return Err(format!(
+                    "No number at {path} satisfies its bounds and multipleOf"
+                ));
+            }
+            let span = (last - first).min(u32::MAX as f64) as usize;
+            (first + self.count_between(0, span) as f64) * step
+        } else if self.boundary_values && self.rng.chance(0.5) {
+            if self.rng.chance(0.5) { low } else { high }
+        } else {
+            let value = low + self.rng.unit() * (high - low);
+            // Two decimals read better, as long as rounding stays in range
+            let rounded = (value * 100.0).round() / 100.0;
+            if (low..=high).contains(&rounded) {
+                rounded
+            } else {
+                value
+            }
+        };
+        Ok(Value::from(value))
+    }
+
+    fn string(&mut self, obj: &Map<String, Value>, path: &str) -> Result<Value, String> {
+        let min = usize_keyword(obj, "minLength", path)?.unwrap_or(0);
+        let max_length = usize_keyword(obj, "maxLength", path)?;
+        let max = max_length.unwrap_or(min + DEFAULT_EXTRA_LENGTH);
+        if min > max {
+            return Err(format!("minLength at {path} is greater than maxLength"));
+        }
+        // A formatted value that breaks the length limits falls back to plain text
+        if let Some(format) = obj.get("format").and_then(Value::as_str)
+            && let Some(value) = self.formatted(format)
+            && value.chars().count() >= min
+            && max_length.is_none_or(|max| value.chars().count() <= max)
+        {
+            return Ok(Value::String(value));
+        }
+
+        let length = self.count_between(min, max);
+        let text = (0..length)
+            .map(|_| (b'a' + self.rng.below(26) as u8) as char)
+            .collect();
+        Ok(Value::String(text))
+    }
+
+    /// Values for the common string formats; `None` for formats generated as plain text
+    fn formatted(&mut self, format: &str) -> Option<String> {
+        let word = *self.rng.pick(&WORDS);
+        let value = match format {
+            "date-time" => format!("{}T{}", self.date(), self.time()),
+            "date" => self.date(),
+            "time" => self.time(),
+            "email" | "idn-email" => format!("{word}@example.com"),
+            "hostname" | "idn-hostname" => format!("{word}.example.com"),
+            "uri" | "iri" | "url" => format!("https://example.com/{word}"),
+            "uri-reference" | "iri-reference" => format!("/{word}"),
+            "uuid" => {
+                let hex = format!("{:016x}{:016x}", self.rng.next_u64(), self.rng.next_u64());
+                // Version 4, RFC 4122 variant
+                let variant = ['8', '9', 'a', 'b'][self.rng.below(4) as usize];
+                format!(
+                    "{}-{}-4{}-{}{}-{}",
+                    &hex[0..8],
+                    &hex[8..12],
+                    &hex[13..16],
+                    variant,
+                    &hex[17..20],
+                    &hex[20..32]
+                )
+            }
+            "ipv4" => {
+                let octets: Vec<String> = (0..4).map(|_| self.rng.below(256).to_string()).collect();
+                octets.join(".")
+            }
+            "ipv6" => {
+                let groups: Vec<String> = (0..8)
+                    .map(|_| format!("{:x}", self.rng.below(0x10000)))
+                    .collect();
+                groups.join(":")
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Date between 2000-01-01 and 2030-12-31
+    fn date(&mut self) -> String {
+        let year = 2000 + self.rng.below(31);
+        let month = 1 + self.rng.below(12);
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        let day = 1 + self.rng.below(days);
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+
+    fn time(&mut self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}Z",
+            self.rng.below(24),
+            self.rng.below(60),
+            self.rng.below(60)
+        )
+    }
+
+    fn array(
+        &mut self,
+        obj: &Map<String, Value>,
+        path: &str,
+        depth: usize,
+    ) -> Result<Value, String> {
+        // prefixItems (2020-12) or an items array (earlier drafts) fixes the leading items
+        let (prefix, rest, rest_keyword) = match (obj.get("prefixItems"), obj.get("items")) {
+            (Some(Value::Array(prefix)), rest) => (prefix.as_slice(), rest, "items"),
+            (_, Some(Value::Array(prefix))) => (
+                prefix.as_slice(),
+                obj.get("additionalItems"),
+                "additionalItems",
+            ),
+            (_, rest) => (&[][..], rest, "items"),
+        };
+        let rest_allowed = !matches!(rest, Some(Value::Bool(false)));
+
+        let min = usize_keyword(obj, "minItems", path)?.unwrap_or(0);
+        let mut max = usize_keyword(obj, "maxItems", path)?
+            .unwrap_or(min.max(prefix.len()) + DEFAULT_EXTRA_ITEMS);
+        if !rest_allowed {
+            max = max.min(prefix.len());
+        }
+        if min > max {
+            return Err(format!(
+                "No array at {path} satisfies minItems, maxItems and its item schemas"
+            ));
+        }
+        let length = if depth >= SOFT_DEPTH {
+            min
+        } else {
+            self.count_between(min, max)
+        };
+        let unique = obj.get("uniqueItems").and_then(Value::as_bool) == Some(true);
+
+        let any = Value::Bool(true);
+        let mut items: Vec<Value> = Vec::with_capacity(length);
+        for index in 0..length {
+            let (schema, item_path) = match prefix.get(index) {
+                Some(schema) => (schema, format!("{path}/prefixItems/{index}")),
+                None => (rest.unwrap_or(&any), format!("{path}/{rest_keyword}")),
+            };
+            let mut item = self.value(schema, &item_path, depth + 1)?;
+            if unique {
+                let mut attempts = 1;
+                while items.contains(&item) && attempts < UNIQUE_ATTEMPTS {
+                    item = self.value(schema, &item_path, depth + 1)?;
+                    attempts += 1;
+                }
+                if items.contains(&item) {
+                    if items.len() >= min {
+                        break;
+                    }
+                    return Err(format!(
+                        "Could not generate {min} unique items at {path}; the item schema \
+                         allows too few distinct values"
+                    ));
+                }
+            }
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn object(
+        &mut self,
+        obj: &Map<String, Value>,
+        path: &str,
+        depth: usize,
+    ) -> Result<Value, String> {
+        let empty = Map::new();
+        let properties = match obj.get("properties") {
+            Some(Value::Object(properties)) => properties,
+            Some(_) => return Err(format!("properties at {path} must be an object")),
+            None => &empty,
+        };
+        let required: Vec<&str> = match obj.get("required") {
+            Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+            Some(_) => return Err(format!("required at {path} must be an array of names")),
+            None => Vec::new(),
+        };
+        let additional = obj.get("additionalProperties");
+        let additional_allowed = !matches!(additional, Some(Value::Bool(false)));
+        let min = usize_keyword(obj, "minProperties", path)?.unwrap_or(0);
+        let max = usize_keyword(obj, "maxProperties", path)?.unwrap_or(usize::MAX);
+        if required.len() > max {
+            return Err(format!(
+                "Object at {path} requires {} properties but maxProperties is {max}",
+                required.len()
+            ));
+        }
+
+        let any = Value::Bool(true);
+        let property_schema = |name: &str| -> Result<(&Value, String), String> {
+            if let Some(schema) = properties.get(name) {
+                return Ok((schema, format!("{path}/properties/{name}")));
+            }
+            if !additional_allowed {
+                return Err(format!(
+                    "Property '{name}' at {path} is required but additionalProperties is false"
+                ));
+            }
+            Ok((
+                additional.unwrap_or(&any),
+                format!("{path}/additionalProperties"),
+            ))
+        };
+
+        let mut result = Map::new();
+        for name in &required {
+            let (schema, property_path) = property_schema(name)?;
+            let value = self.value(schema, &property_path, depth + 1)?;
+            result.insert(name.to_string(), value);
+        }
+        let optional: Vec<&String> = properties
+            .keys()
+            .filter(|name| !result.contains_key(name.as_str()))
+            .collect();
+        let mut skipped = Vec::new();
+        for name in optional {
+            let include = depth < SOFT_DEPTH && self.rng.chance(self.optional_probability);
+            if include && result.len() < max {
+                let (schema, property_path) = property_schema(name)?;
+                let value = self.value(schema, &property_path, depth + 1)?;
+                result.insert(name.clone(), value);
+            } else {
+                skipped.push(name);
+            }
+        }
+
+        // Top up to minProperties with skipped properties, then with extra ones
+        for name in skipped {
+            if result.len() >= min {
+                break;
+            }
+            let (schema, property_path) = property_schema(name)?;
+            let value = self.value(schema, &property_path, depth + 1)?;
+            result.insert(name.clone(), value);
+        }
+        let mut extra = 1;
+        while result.len() < min {
+            if !additional_allowed {
+                return Err(format!(
+                    "Object at {path} needs {min} properties but only {} are defined and \
+                     additionalProperties is false",
+                    result.len()
+                ));
+            }
+            let name = format!("property_{extra}");
+            extra += 1;
+            if result.contains_key(&name) {
+                continue;
+            }
+            let (schema, property_path) = property_schema(&name)?;
+            let value = self.value(schema, &property_path, depth + 1)?;
+            result.insert(name, value);
+        }
+        Ok(Value::Object(result))
+    }
+}
+
+pub fn generate_documents(
+    input: GenerateFromSchemaInput,
+) -> Result<GenerateFromSchemaOutput, String> {
+    let schema: Value =
+        serde_json::from_str(&input.schema).map_err(|e| format!("Invalid schema JSON: {e}"))?;
+    let count = input.count.unwrap_or(DEFAULT_COUNT);
+    if !(1..=MAX_COUNT).contains(&count) {
+        return Err(format!("count must be between 1 and {MAX_COUNT}"));
+    }
+    let optional_probability = input
+        .optional_probability
+        .unwrap_or(DEFAULT_OPTIONAL_PROBABILITY);
+    if !(0.0..=1.0).contains(&optional_probability) {
+        return Err("optional_probability must be between 0 and 1".to_string());
+    }
+    let seed = input.seed.unwrap_or_else(rand::random);
+
+    let mut generator = Generator::new(
+        &schema,
+        seed,
+        input.boundary_values.unwrap_or(false),
+        optional_probability,
+    );
+    let documents = (0..count)
+        .map(|_| generator.generate())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(GenerateFromSchemaOutput {
+        documents,
+        count,
+        seed,
+        warnings: generator.warnings(),
+    })
+}
+
+/// Type implied by the keywords present, when the schema has no type
+fn infer_type(obj: &Map<String, Value>) -> Option<&'static str> {
+    let has = |keywords: &[&str]| keywords.iter().any(|k| obj.contains_key(*k));
+    if has(&[
+        "properties",
+        "required",
+        "additionalProperties",
+        "minProperties",
+        "maxProperties",
+    ]) {
+        Some("object")
+    } else if has(&[
+        "items",
+        "prefixItems",
+        "minItems",
+        "maxItems",
+        "uniqueItems",
+    ]) {
+        Some("array")
+    } else if has(&["minLength", "maxLength", "format"]) {
+        Some("string")
+    } else if has(&[
+        "minimum",
+        "maximum",
+        "exclusiveMinimum",
+        "exclusiveMaximum",
+        "multipleOf",
+    ]) {
+        Some("number")
+    } else {
+        None
+    }
+}
+
+/// A bound and whether it is exclusive
+type Bound = Option<(f64, bool)>;
+
+/// Lower and upper bounds, reading both the numeric exclusive keywords of draft 6+ and
+/// the boolean flags of draft 4
+fn numeric_bounds(obj: &Map<String, Value>, path: &str) -> Result<(Bound, Bound), String> {
+    let number = |keyword: &str| -> Result<Option<f64>, String> {
+        match obj.get(keyword) {
+            None | Some(Value::Bool(_)) => Ok(None),
+            Some(value) => value
+                .as_f64()
+                .map(Some)
+                .ok_or_else(|| format!("{keyword} at {path} must be a number")),
+        }
+    };
+    let flag = |keyword: &str| obj.get(keyword).and_then(Value::as_bool) == Some(true);
+
+    let tighter = |a: Bound, b: Bound, lower: bool| match (a, b) {
+        (Some(x), Some(y)) => {
+            let x_wins = if lower { x.0 > y.0 } else { x.0 < y.0 };
+            // At equal values the exclusive bound is the tighter one
+            Some(if x.0 == y.0 {
+                (x.0, x.1 || y.1)
+            } else if x_wins {
+                x
+            } else {
+                y
+            })
+        }
+        (x, y) => x.or(y),
+    };
+    let lower = tighter(
+        number("minimum")?.map(|v| (v, flag("exclusiveMinimum"))),
+        number("exclusiveMinimum")?.map(|v| (v, true)),
+        true,
+    );
+    let upper = tighter(
+        number("maximum")?.map(|v| (v, flag("exclusiveMaximum"))),
+        number("exclusiveMaximum")?.map(|v| (v, true)),
+        false,
+    );
+    Ok((lower, upper))
+}
+
+/// Fill a missing side of a range, NaN meaning unbounded, DEFAULT_NUMBER_SPAN away from
+/// the other side, or use [0, DEFAULT_NUMBER_SPAN] when both are missing
+fn default_span(low: f64, high: f64) -> (f64, f64) {
+    match (low.is_nan(), high.is_nan()) {
+        (true, true) => (0.0, DEFAULT_NUMBER_SPAN),
+        (true, false) => (high - DEFAULT_NUMBER_SPAN, high),
+        (false, true) => (low, low + DEFAULT_NUMBER_SPAN),
+        (false, false) => (low, high),
+    }
+}
+
+fn multiple_of(obj: &Map<String, Value>, path: &str) -> Result<Option<f64>, String> {
+    match obj.get("multipleOf") {
+        None => Ok(None),
+        Some(value) => match value.as_f64() {
+            Some(step) if step > 0.0 => Ok(Some(step)),
+            _ => Err(format!("multipleOf at {path} must be a positive number")),
+        },
+    }
+}
+
+fn usize_keyword(
+    obj: &Map<String, Value>,
+    keyword: &str,
+    path: &str,
+) -> Result<Option<usize>, String> {
+    match obj.get(keyword) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(|n| Some(n as usize))
+            .ok_or_else(|| format!("{keyword} at {path} must be a non-negative integer")),
+    }
+}
+
+/// Closest f64 to `value` in the direction of `toward`
+fn next_toward(value: f64, toward: f64) -> f64 {
+    if value == 0.0 {
+        let tiny = f64::from_bits(1);
+        return if toward > 0.0 { tiny } else { -tiny };
+    }
+    let bits = value.to_bits();
+    let away_from_zero = (toward > value) == (value > 0.0);
+    f64::from_bits(if away_from_zero { bits + 1 } else { bits - 1 })
+}
+
+/// Combine `from` into `into` as both must hold: properties and required are joined,
+/// bounds take the tighter value, types intersect, and other keywords keep the first
+fn merge_schema(into: &mut Map<String, Value>, from: &Value, path: &str) -> Result<(), String> {
+    let from = match from {
+        Value::Object(from) => from,
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => {
+            return Err(format!(
+                "Schema at {path} combines with false and accepts no value"
+            ));
+        }
+        _ => return Err(format!("Schema at {path} must be an object or a boolean")),
+    };
+
+    for (keyword, value) in from {
+        let Some(existing) = into.get(keyword).cloned() else {
+            into.insert(keyword.clone(), value.clone());
+            continue;
+        };
+        let combined = match keyword.as_str() {
+            "properties" => match (existing, value) {
+                (Value::Object(mut mine), Value::Object(theirs)) => {
+                    for (name, schema) in theirs {
+                        let joined = match mine.remove(name) {
+                            Some(own) => Value::Object(
+                                [("allOf".to_string(), Value::Array(vec![own, schema.clone()]))]
+                                    .into_iter()
+                                    .collect(),
+                            ),
+                            None => schema.clone(),
+                        };
+                        mine.insert(name.clone(), joined);
+                    }
+                    Value::Object(mine)
+                }
+                (existing, _) => existing,
+            },
+            "required" => match (existing, value) {
+                (Value::Array(mut mine), Value::Array(theirs)) => {
+                    for name in theirs {
+                        if !mine.contains(name) {
+                            mine.push(name.clone());
+                        }
+                    }
+                    Value::Array(mine)
+                }
+                (existing, _) => existing,
+            },
+            "minimum" | "exclusiveMinimum" | "minLength" | "minItems" | "minProperties" => {
+                match (existing.as_f64(), value.as_f64()) {
+                    (Some(a), Some(b)) if b > a => value.clone(),
+                    _ => existing,
+                }
+            }
+            "maximum" | "exclusiveMaximum" | "maxLength" | "maxItems" | "maxProperties" => {
+                match (existing.as_f64(), value.as_f64()) {
+                    (Some(a), Some(b)) if b < a => value.clone(),
+                    _ => existing,
+                }
+            }
+            "type" => {
+                let names = |types: &Value| -> Vec<Value> {
+                    match types {
+                        Value::Array(names) => names.clone(),
+                        other => vec![other.clone()],
+                    }
+                };
+                let theirs = names(value);
+                let common: Vec<Value> = names(&existing)
+                    .into_iter()
+                    .filter(|name| theirs.contains(name))
+                    .collect();
+                if common.is_empty() {
+                    return Err(format!("Schemas combined at {path} have no type in common"));
+                }
+                Value::Array(common)
+            }
+            "allOf" => match (existing, value) {
+                (Value::Array(mut mine), Value::Array(theirs)) => {
+                    mine.extend(theirs.iter().cloned());
+                    Value::Array(mine)
+                }
+                (existing, _) => existing,
+            },
+            _ => existing,
+        };
+        into.insert(keyword.clone(), combined);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn generate(schema: Value, count: usize, seed: u64) -> GenerateFromSchemaOutput {
+        generate_documents(GenerateFromSchemaInput {
+            schema: schema.to_string(),
+            count: Some(count),
+            seed: Some(seed),
+            boundary_values: None,
+            optional_probability: None,
+        })
+        .unwrap()
+    }
+
+    fn error(schema: Value) -> String {
+        generate_documents(GenerateFromSchemaInput {
+            schema: schema.to_string(),
+            count: Some(5),
+            seed: Some(1),
+            boundary_values: None,
+            optional_probability: None,
+        })
+        .unwrap_err()
+    }
+
+    /// Checks the supported keywords, to confirm generated documents are valid
+    fn check(root: &Value, schema: &Value, value: &Value) -> Result<(), String> {
+        let obj = match schema {
+            Value::Bool(accepts) => {
+                return if *accepts {
+                    Ok(())
+                } else {
+                    Err("false schema".into())
+                };
+            }
+            Value::Object(obj) => obj,
+            _ => return Err("bad schema".into()),
+        };
+        if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+            return check(root, root.pointer(&reference[1..]).unwrap(), value);
+        }
+        let fail = |what: &str| Err(format!("{value} fails {what}"));
+        if let Some(expected) = obj.get("const")
+            && expected != value
+        {
+            return fail("const");
+        }
+        if let Some(options) = obj.get("enum").and_then(Value::as_array)
+            && !options.contains(value)
+        {
+            return fail("enum");
+        }
+        for member in obj
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            check(root, member, value)?;
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(branches) = obj.get(keyword).and_then(Value::as_array)
+                && !branches.iter().any(|b| check(root, b, value).is_ok())
+            {
+                return fail(keyword);
+            }
+        }
+        if let Some(types) = obj.get("type") {
+            let names: Vec<&str> = match types {
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                other => vec![other.as_str().unwrap()],
+            };
+            let matches = names.iter().any(|name| match *name {
+                "null" => value.is_null(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "string" => value.is_string(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                _ => false,
+            });
+            if !matches {
+                return fail("type");
+            }
+        }
+        if let Some(n) = value.as_f64() {
+            let bound = |k: &str| obj.get(k).and_then(Value::as_f64);
+            if bound("minimum").is_some_and(|m| n < m)
+                || bound("maximum").is_some_and(|m| n > m)
+                || bound("exclusiveMinimum").is_some_and(|m| n <= m)
+                || bound("exclusiveMaximum").is_some_and(|m| n >= m)
+            {
+                return fail("bounds");
+            }
+            if let Some(step) = bound("multipleOf") {
+                let k = n / step;
+                if (k - k.round()).abs() > 1e-9 {
+                    return fail("multipleOf");
+                }
+            }
+        }
+        let size = |k: &str| obj.get(k).and_then(Value::as_u64).map(|n| n as usize);
+        if let Some(s) = value.as_str() {
+            let length = s.chars().count();
+            if size("minLength").is_some_and(|m| length < m)
+                || size("maxLength").is_some_and(|m| length > m)
+            {
+                return fail("length");
+            }
+        }
+        if let Some(items) = value.as_array() {
+            if size("minItems").is_some_and(|m| items.len() < m)
+                || size("maxItems").is_some_and(|m| items.len() > m)
+            {
+                return fail("item count");
+            }
+            if obj.get("uniqueItems") == Some(&Value::Bool(true)) {
+                for (i, item) in items.iter().enumerate() {
+                    if items[..i].contains(item) {
+                        return fail("uniqueItems");
+                    }
+                }
+            }
+            let prefix = obj.get("prefixItems").and_then(Value::as_array);
+            for (i, item) in items.iter().enumerate() {
+                if let Some(schema) = prefix.and_then(|p| p.get(i)).or(obj.get("items")) {
+                    check(root, schema, item)?;
+                }
+            }
+        }
+        if let Some(map) = value.as_object() {
+            for name in obj
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if !map.contains_key(name.as_str().unwrap()) {
+                    return fail("required");
+                }
+            }
+            if size("minProperties").is_some_and(|m| map.len() < m)
+                || size("maxProperties").is_some_and(|m| map.len() > m)
+            {
+                return fail("property count");
+            }
+            let properties = obj.get("properties").and_then(Value::as_object);
+            for (name, property) in map {
+                match properties.and_then(|p| p.get(name)) {
+                    Some(schema) => check(root, schema, property)?,
+                    None => {
+                        if let Some(additional) = obj.get("additionalProperties") {
+                            check(root, additional, property)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn assert_all_valid(schema: Value, boundary_values: bool) {
+        for seed in 0..20 {
+            let output = generate_documents(GenerateFromSchemaInput {
+                schema: schema.to_string(),
+                count: Some(10),
+                seed: Some(seed),
+                boundary_values: Some(boundary_values),
+                optional_probability: None,
+            })
+            .unwrap();
+            for document in &output.documents {
+                check(&schema, &schema, document).unwrap();
+            }
+        }
+    }
+
+    fn contract_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["id", "name", "tags", "price"],
+            "additionalProperties": false,
+            "properties": {
+                "id": {"type": "integer", "minimum": 1, "maximum": 9999},
+                "name": {"type": "string", "minLength": 3, "maxLength": 8},
+                "price": {"type": "number", "exclusiveMinimum": 0, "maximum": 50, "multipleOf": 0.25},
+                "status": {"enum": ["active", "retired"]},
+                "kind": {"const": "product"},
+                "email": {"type": "string", "format": "email"},
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string", "maxLength": 4},
+                    "minItems": 1,
+                    "maxItems": 3,
+                    "uniqueItems": true
+                },
+                "owner": {"$ref": "#/$defs/person"},
+                "discount": {"type": ["number", "null"], "minimum": -1, "maximum": 0}
+            },
+            "$defs": {
+                "person": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": {"type": "string"},
+                        "manager": {"$ref": "#/$defs/person"}
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_documents_match_schema() {
+        assert_all_valid(contract_schema(), false);
+        assert_all_valid(contract_schema(), true);
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let first = generate(contract_schema(), 5, 42);
+        let second = generate(contract_schema(), 5, 42);
+        assert_eq!(first.documents, second.documents);
+        assert_eq!(first.seed, 42);
+        assert_eq!(first.count, 5);
+        let other = generate(contract_schema(), 5, 43);
+        assert_ne!(first.documents, other.documents);
+    }
+
+    #[test]
+    fn test_boundary_values_reach_both_ends() {
+        let schema = json!({"type": "integer", "minimum": -5, "maximum": 5});
+        let output = generate_documents(GenerateFromSchemaInput {
+            schema: schema.to_string(),
+            count: Some(100),
+            seed: Some(7),
+            boundary_values: Some(true),
+            optional_probability: None,
+        })
+        .unwrap();
+        let values: Vec<i64> = output.documents.iter().filter_map(Value::as_i64).collect();
+        assert!(values.contains(&-5) && values.contains(&5));
+        assert!(values.iter().all(|v| (-5..=5).contains(v)));
+        // Boundaries make up roughly half the values, far more than uniform sampling
+        let ends = values.iter().filter(|v| v.abs() == 5).count();
+        assert!(ends > 30, "{ends}");
+    }
+
+    #[test]
+    fn test_exclusive_number_bounds() {
+        let schema = json!({"type": "number", "exclusiveMinimum": 1, "exclusiveMaximum": 2});
+        assert_all_valid(schema, true);
+        let draft4 =
+            json!({"type": "integer", "minimum": 1, "exclusiveMinimum": true, "maximum": 2});
+        let output = generate(draft4, 20, 3);
+        assert!(output.documents.iter().all(|v| v.as_i64() == Some(2)));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let schema = json!({
+            "allOf": [
+                {"type": "object", "required": ["a"], "properties": {"a": {"type": "integer", "minimum": 0}}},
+                {"required": ["b"], "properties": {"a": {"maximum": 3}, "b": {"type": "boolean"}}}
+            ],
+            "properties": {
+                "c": {"oneOf": [{"type": "string", "format": "uuid"}, {"type": "null"}]}
+            }
+        });
+        assert_all_valid(schema, false);
+        let output = generate(
+            json!({"anyOf": [{"type": "string", "format": "date"}, {"type": "integer"}]}),
+            50,
+            9,
+        );
+        assert!(output.documents.iter().any(Value::is_string));
+        assert!(output.documents.iter().any(Value::is_i64));
+        for date in output.documents.iter().filter_map(Value::as_str) {
+            assert_eq!(date.len(), 10);
+            assert_eq!(&date[4..5], "-");
+        }
+    }
+
+    #[test]
+    fn test_string_formats() {
+        let formats = ["email", "uuid", "date-time", "ipv6", "uri"];
+        for format in formats {
+            let output = generate(json!({"type": "string", "format": format}), 5, 4);
+            for value in output.documents.iter().map(|v| v.as_str().unwrap()) {
+                let ok = match format {
+                    "email" => value.ends_with("@example.com"),
+                    "uuid" => value.len() == 36 && &value[14..15] == "4",
+                    "date-time" => value.len() == 20 && &value[10..11] == "T",
+                    "ipv6" => value.split(':').count() == 8,
+                    _ => value.starts_with("https://"),
+                };
+                assert!(ok, "{format}: {value}");
+            }
+        }
+        // Too long for maxLength, so plain text is generated instead
+        let output = generate(
+            json!({"type": "string", "format": "email", "maxLength": 4}),
+            5,
+            4,
+        );
+        assert!(
+            output
+                .documents
+                .iter()
+                .all(|v| v.as_str().unwrap().len() <= 4)
+        );
+    }
+
+    #[test]
+    fn test_tuples_and_min_properties() {
+        let schema = json!({
+            "type": "array",
+            "prefixItems": [{"type": "string", "format": "ipv4"}, {"type": "integer", "multipleOf": 5}],
+            "items": false,
+            "minItems": 2
+        });
+        assert_all_valid(schema, true);
+        let schema = json!({
+            "type": "object",
+            "minProperties": 3,
+            "properties": {"x": {"type": "null"}},
+            "additionalProperties": {"type": "integer"}
+        });
+        let output = generate(schema.clone(), 10, 5);
+        for document in &output.documents {
+            assert!(document.as_object().unwrap().len() >= 3);
+            check(&schema, &schema, document).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_optional_probability() {
+        let schema = json!({"type": "object", "properties": {"a": {"type": "integer"}, "b": {"type": "string"}}});
+        let run = |probability: f64| {
+            generate_documents(GenerateFromSchemaInput {
+                schema: schema.to_string(),
+                count: Some(10),
+                seed: Some(11),
+                boundary_values: None,
+                optional_probability: Some(probability),
+            })
+            .unwrap()
+            .documents
+        };
+        assert!(run(0.0).iter().all(|d| d.as_object().unwrap().is_empty()));
+        assert!(run(1.0).iter().all(|d| d.as_object().unwrap().len() == 2));
+    }
+
+    #[test]
+    fn test_null_probability_and_generate_at() {
+        let root = json!({
+            "$defs": {
+                "maybe": {"type": ["integer", "string", "null"]},
+                "either": {"oneOf": [{"type": "null"}, {"type": "boolean"}]}
+            }
+        });
+        let sample = |probability: f64, reference: &str| {
+            let mut generator =
+                Generator::new(&root, 5, false, 0.5).with_null_probability(probability);
+            (0..20)
+                .map(|_| generator.generate_at(reference).unwrap())
+                .collect::<Vec<_>>()
+        };
+        for reference in ["#/$defs/maybe", "#/$defs/either"] {
+            assert!(sample(1.0, reference).iter().all(Value::is_null));
+            assert!(sample(0.0, reference).iter().all(|v| !v.is_null()));
+        }
+        let mut generator = Generator::new(&root, 5, false, 0.5);
+        let err = generator.generate_at("#/$defs/missing").unwrap_err();
+        assert!(err.contains("does not resolve"));
+    }
+
+    #[test]
+    fn test_recursive_schema_terminates() {
+        let schema = json!({
+            "$defs": {"node": {"type": "object", "properties": {"children": {"type": "array", "items": {"$ref": "#/$defs/node"}}}}},
+            "$ref": "#/$defs/node"
+        });
+        let output = generate_documents(GenerateFromSchemaInput {
+            schema: schema.to_string(),
+            count: Some(10),
+            seed: Some(2),
+            boundary_values: None,
+            optional_probability: Some(1.0),
+        })
+        .unwrap();
+        assert_eq!(output.documents.len(), 10);
+
+        let endless = json!({"$defs": {"loop": {"type": "object", "required": ["next"], "properties": {"next": {"$ref": "#/$defs/loop"}}}}, "$ref": "#/$defs/loop"});
+        assert!(error(endless).contains("recurse without end"));
+    }
+
+    #[test]
+    fn test_unsupported_keywords_warn() {
+        let output = generate(
+            json!({"type": "object", "required": ["code"], "properties": {"code": {"type": "string", "pattern": "^[A-Z]{3}$"}}}),
+            1,
+            1,
+        );
+        assert_eq!(
+            output.warnings,
+            vec![
+                "Ignored unsupported keyword 'pattern' at #/properties/code; documents may not satisfy it"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsatisfiable_schemas() {
+        assert!(error(json!(false)).contains("accepts no value"));
+        assert!(
+            error(json!({"type": "integer", "minimum": 5, "maximum": 4})).contains("No integer")
+        );
+        assert!(
+            error(json!({"type": "string", "minLength": 5, "maxLength": 2})).contains("minLength")
+        );
+        assert!(error(json!({"type": "array", "items": {"enum": [1, 2]}, "minItems": 3, "uniqueItems": true})).contains("unique"));
+        assert!(
+            error(json!({"type": "object", "required": ["a"], "additionalProperties": false}))
+                .contains("additionalProperties")
+        );
+        assert!(
+            error(json!({"allOf": [{"type": "string"}, {"type": "integer"}]}))
+                .contains("no type in common")
+        );
+        assert!(
+            error(json!({"$ref": "https://example.com/schema.json"})).contains("local references")
+        );
+        assert!(error(json!({"$ref": "#/$defs/missing"})).contains("does not resolve"));
+        assert!(error(json!({"type": "date"})).contains("Unknown type 'date'"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_options() {
+        let run = |count: Option<usize>, probability: Option<f64>, schema: &str| {
+            generate_documents(GenerateFromSchemaInput {
+                schema: schema.to_string(),
+                count,
+                seed: None,
+                boundary_values: None,
+                optional_probability: probability,
+            })
+        };
+        assert!(run(Some(0), None, "{}").unwrap_err().contains("count"));
+        assert!(
+            run(Some(MAX_COUNT + 1), None, "{}")
+                .unwrap_err()
+                .contains("count")
+        );
+        assert!(
+            run(None, Some(1.5), "{}")
+                .unwrap_err()
+                .contains("optional_probability")
+        );
+        assert!(
+            run(None, None, "{")
+                .unwrap_err()
+                .starts_with("Invalid schema JSON")
+        );
+        assert_eq!(run(None, None, "true").unwrap().documents.len(), 1);
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{JsonFlattenInput as LogicInput, JsonFlattenOutput as LogicOutput, flatten_json};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JsonFlattenInput {
+    /// "flatten" (default) or "unflatten"
+    pub operation: Option<String>,
+    /// Document to flatten, or the flat entries to rebuild: an object of key/value
+    /// pairs or an array of {"key", "value"} entries
+    pub json_string: String,
+    /// Single character between object keys (default: ".")
+    pub separator: Option<String>,
+    /// How arrays are keyed: "brackets" (a[0].b, default), "dots" (a.0.b) or
+    /// "keep" (arrays stay whole as values)
+    pub array_policy: Option<String>,
+    /// Escape separators, brackets and backslashes inside keys with a backslash so every
+    /// path can be rebuilt exactly (default: true)
+    pub escape_keys: Option<bool>,
+    /// Containers nested deeper than this stay whole as values (default: unlimited)
+    pub max_depth: Option<usize>,
+    /// What to do when two values flatten to the same key: "error" (default),
+    /// "keep_first" or "keep_last"
+    pub on_collision: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FlatEntry {
+    /// Path of the value, such as user.tags[0]
+    pub key: String,
+    /// Scalar, empty container, or container kept whole by array_policy or max_depth
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Collision {
+    /// Key produced by more than one value
+    pub key: String,
+    /// Number of values that produced it
+    pub occurrences: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JsonFlattenOutput {
+    /// Operation performed
+    pub operation: String,
+    /// Flat entries in document order (flatten)
+    pub entries: Option<Vec<FlatEntry>>,
+    /// Rebuilt document (unflatten)
+    pub document: Option<Value>,
+    /// Keys produced by more than one value, resolved by on_collision (flatten)
+    pub collisions: Vec<Collision>,
+}
+
+/// Flatten nested JSON into path/value pairs such as user.tags[0], or rebuild a document
+/// from them, so tabular tools can consume arbitrary JSON documents
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn json_flatten(request: Request<JsonFlattenInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "json_flatten",
+                JsonFlattenInput,
+                JsonFlattenOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("json_flatten", &input);
+    log.finish(run_json_flatten(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_json_flatten(input: JsonFlattenInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("json_string", &input.json_string)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        operation: input.operation,
+        json_string: input.json_string,
+        separator: input.separator,
+        array_policy: input.array_policy,
+        escape_keys: input.escape_keys,
+        max_depth: input.max_depth,
+        on_collision: input.on_collision,
+    };
+
+    // Call logic implementation
+    let result = match logic::flatten_json(logic_input) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let response = JsonFlattenOutput {
+        operation: result.operation,
+        entries: result.entries.map(|entries| {
+            entries
+                .into_iter()
+                .map(|entry| FlatEntry {
+                    key: entry.key,
+                    value: entry.value,
+                })
+                .collect()
+        }),
+        document: result.document,
+        collisions: result
+            .collisions
+            .into_iter()
+            .map(|collision| Collision {
+                key: collision.key,
+                occurrences: collision.occurrences,
+            })
+            .collect(),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
@@ -0,0 +1,677 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+pub const DEFAULT_SEPARATOR: char = '.';
+/// Largest array index accepted when unflattening, so a single key cannot allocate a
+/// huge sparse array
+pub const MAX_ARRAY_INDEX: usize = 100_000;
+
+/// Marks the next character of a key as literal
+const ESCAPE: char = '\\';
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonFlattenInput {
+    /// "flatten" (default) or "unflatten"
+    pub operation: Option<String>,
+    /// Document to flatten, or the flat entries to rebuild: an object of key/value
+    /// pairs or an array of {"key", "value"} entries
+    pub json_string: String,
+    /// Single character between object keys (default: ".")
+    pub separator: Option<String>,
+    /// How arrays are keyed: "brackets" (a[0].b, default), "dots" (a.0.b) or
+    /// "keep" (arrays stay whole as values)
+    pub array_policy: Option<String>,
+    /// Escape separators, brackets and backslashes inside keys so every path can be
+    /// rebuilt exactly (default: true)
+    pub escape_keys: Option<bool>,
+    /// Containers nested deeper than this stay whole as values (default: unlimited)
+    pub max_depth: Option<usize>,
+    /// What to do when two values flatten to the same key: "error" (default),
+    /// "keep_first" or "keep_last"
+    pub on_collision: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlatEntry {
+    pub key: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Collision {
+    pub key: String,
+    pub occurrences: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonFlattenOutput {
+    pub operation: String,
+    /// Flat entries in document order (flatten)
+    pub entries: Option<Vec<FlatEntry>>,
+    /// Rebuilt document (unflatten)
+    pub document: Option<Value>,
+    /// Keys produced by more than one value (flatten)
+    pub collisions: Vec<Collision>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArrayPolicy {
+    Brackets,
+    Dots,
+    Keep,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CollisionPolicy {
+    Error,
+    KeepFirst,
+    KeepLast,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Options {
+    separator: char,
+    arrays: ArrayPolicy,
+    escape: bool,
+    max_depth: Option<usize>,
+}
+
+pub fn flatten_json(input: JsonFlattenInput) -> Result<JsonFlattenOutput, String> {
+    let separator = match input.separator.as_deref() {
+        None => DEFAULT_SEPARATOR,
+        Some(text) => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if !matches!(c, '[' | ']' | ESCAPE) => c,
+                _ => {
+                    return Err(format!(
+                        "Separator must be a single character other than '[', ']' or '\\', got '{text}'"
+                    ));
+                }
+            }
+        }
+    };
+    let arrays = match input
+        .array_policy
+        .as_deref()
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        None | Some("brackets") => ArrayPolicy::Brackets,
+        Some("dots") => ArrayPolicy::Dots,
+        Some("keep") => ArrayPolicy::Keep,
+        Some(other) => {
+            return Err(format!(
+                "Unsupported array policy: {other}. Use 'brackets', 'dots' or 'keep'"
+            ));
+        }
+    };
+    let options = Options {
+        separator,
+        arrays,
+        escape: input.escape_keys.unwrap_or(true),
+        max_depth: input.max_depth,
+    };
+
+    // A byte order mark is common in files saved by Windows editors
+    let text = input.json_string.trim_start_matches('\u{feff}');
+    let parsed: Value = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {e}"))?;
+
+    let operation = input
+        .operation
+        .as_deref()
+        .unwrap_or("flatten")
+        .to_lowercase();
+    match operation.as_str() {
+        "flatten" => {
+            let on_collision = match input
+                .on_collision
+                .as_deref()
+                .map(str::to_lowercase)
+                .as_deref()
+            {
+                None | Some("error") => CollisionPolicy::Error,
+                Some("keep_first") => CollisionPolicy::KeepFirst,
+                Some("keep_last") => CollisionPolicy::KeepLast,
+                Some(other) => {
+                    return Err(format!(
+                        "Unsupported collision policy: {other}. Use 'error', 'keep_first' or 'keep_last'"
+                    ));
+                }
+            };
+            let (entries, collisions) = flatten(&parsed, options, on_collision)?;
+            Ok(JsonFlattenOutput {
+                operation,
+                entries: Some(entries),
+                document: None,
+                collisions,
+            })
+        }
+        "unflatten" => Ok(JsonFlattenOutput {
+            operation,
+            entries: None,
+            document: Some(unflatten(parsed, options)?),
+            collisions: Vec::new(),
+        }),
+        _ => Err(format!(
+            "Unsupported operation: {operation}. Use 'flatten' or 'unflatten'"
+        )),
+    }
+}
+
+fn flatten(
+    document: &Value,
+    options: Options,
+    on_collision: CollisionPolicy,
+) -> Result<(Vec<FlatEntry>, Vec<Collision>), String> {
+    let is_container = match document {
+        Value::Object(_) => true,
+        Value::Array(_) => options.arrays != ArrayPolicy::Keep,
+        _ => false,
+    };
+    if !is_container {
+        return Err(if options.arrays == ArrayPolicy::Keep {
+            "Document to flatten must be a JSON object".to_string()
+        } else {
+            "Document to flatten must be a JSON object or array".to_string()
+        });
+    }
+
+    let mut flat = Vec::new();
+    walk(document, String::new(), 0, options, &mut flat);
+
+    // Group values by key, keeping the position of each key's first appearance
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut counts: Vec<usize> = Vec::new();
+    let mut entries: Vec<FlatEntry> = Vec::new();
+    for (key, value) in flat {
+        match positions.get(&key) {
+            Some(&i) => {
+                counts[i] += 1;
+                if on_collision == CollisionPolicy::KeepLast {
+                    entries[i].value = value;
+                }
+            }
+            None => {
+                positions.insert(key.clone(), entries.len());
+                counts.push(1);
+                entries.push(FlatEntry { key, value });
+            }
+        }
+    }
+    let collisions: Vec<Collision> = entries
+        .iter()
+        .zip(&counts)
+        .filter(|(_, count)| **count > 1)
+        .map(|(entry, &occurrences)| Collision {
+            key: entry.key.clone(),
+            occurrences,
+        })
+        .collect();
+
+    if on_collision == CollisionPolicy::Error && !collisions.is_empty() {
+        let keys: Vec<&str> = collisions.iter().map(|c| c.key.as_str()).collect();
+        return Err(format!(
+            "Several values flatten to the same key: {}. Enable escape_keys or choose \
+             another on_collision policy",
+            keys.join(", ")
+        ));
+    }
+    Ok((entries, collisions))
+}
+
+fn walk(
+    value: &Value,
+    path: String,
+    depth: usize,
+    options: Options,
+    out: &mut Vec<(String, Value)>,
+) {
+    let descend = options.max_depth.is_none_or(|max| depth < max);
+    match value {
+        Value::Object(map) if descend && !map.is_empty() => {
+            for (key, child) in map {
+                let segment = escape_key(key, options);
+                let child_path = if depth == 0 {
+                    segment
+                } else {
+                    format!("{path}{}{segment}", options.separator)
+                };
+                walk(child, child_path, depth + 1, options, out);
+            }
+        }
+        Value::Array(items)
+            if descend && !items.is_empty() && options.arrays != ArrayPolicy::Keep =>
+        {
+            for (index, child) in items.iter().enumerate() {
+                let child_path = match options.arrays {
+                    ArrayPolicy::Dots if depth > 0 => format!("{path}{}{index}", options.separator),
+                    ArrayPolicy::Dots => index.to_string(),
+                    _ => format!("{path}[{index}]"),
+                };
+                walk(child, child_path, depth + 1, options, out);
+            }
+        }
+        // Scalars, empty containers and containers past max_depth are leaves
+        _ => out.push((path, value.clone())),
+    }
+}
+
+fn escape_key(key: &str, options: Options) -> String {
+    if !options.escape {
+        return key.to_string();
+    }
+    let mut escaped = String::with_capacity(key.len());
+    // Under the dots policy an all-digit key would read back as an array index
+    if options.arrays == ArrayPolicy::Dots
+        && !key.is_empty()
+        && key.bytes().all(|b| b.is_ascii_digit())
+    {
+        escaped.push(ESCAPE);
+    }
+    for c in key.chars() {
+        if c == options.separator || matches!(c, '[' | ']' | ESCAPE) {
+            escaped.push(ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Split a flat key back into object keys and array indexes
+fn parse_key(key: &str, options: Options) -> Result<Vec<Segment>, String> {
+    let bad = |reason: &str| format!("Invalid key '{key}': {reason}");
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    // Whether the current segment holds anything, including an escaped character,
+    // and whether any of it was escaped
+    let mut started = false;
+    let mut escaped = false;
+    // Just closed a bracket, so only a separator, another bracket or the end may follow
+    let mut after_index = false;
+    let mut after_separator = false;
+    let mut chars = key.chars();
+
+    let finish =
+        |segments: &mut Vec<Segment>, current: &mut String, escaped: bool| -> Result<(), String> {
+            let text = std::mem::take(current);
+            let is_index = options.arrays == ArrayPolicy::Dots
+                && !escaped
+                && !text.is_empty()
+                && text.bytes().all(|b| b.is_ascii_digit());
+            let segment = if is_index {
+                Segment::Index(text.parse().map_err(|_| bad("array index is too large"))?)
+            } else {
+                Segment::Key(text)
+            };
+            segments.push(segment);
+            Ok(())
+        };
+
+    while let Some(c) = chars.next() {
+        if after_index && c != options.separator && c != '[' {
+            return Err(bad("expected a separator or '[' after ']'"));
+        }
+        if options.escape && c == ESCAPE {
+            let next = chars
+                .next()
+                .ok_or_else(|| bad("ends with an escape character"))?;
+            current.push(next);
+            started = true;
+            escaped = true;
+        } else if c == options.separator {
+            if !after_index {
+                finish(&mut segments, &mut current, escaped)?;
+            }
+            started = false;
+            escaped = false;
+            after_index = false;
+            after_separator = true;
+        } else if c == '[' && options.arrays != ArrayPolicy::Dots {
+            // "a[0]" and "a.[0]" (an empty key holding an array) both close a key first
+            if started || after_separator {
+                finish(&mut segments, &mut current, escaped)?;
+            }
+            let digits: String = chars.by_ref().take_while(|&c| c != ']').collect();
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(bad("expected an array index inside brackets"));
+            }
+            let index = digits
+                .parse::<usize>()
+                .map_err(|_| bad("array index is too large"))?;
+            segments.push(Segment::Index(index));
+            started = false;
+            escaped = false;
+            after_index = true;
+            after_separator = false;
+        } else {
+            current.push(c);
+            started = true;
+        }
+    }
+    if !after_index {
+        finish(&mut segments, &mut current, escaped)?;
+    }
+    Ok(segments)
+}
+
+/// Partially rebuilt document; `Empty` marks a slot no key has set yet
+#[derive(Debug)]
+enum Node {
+    Empty,
+    Leaf(Value),
+    Object(Vec<(String, Node)>),
+    Array(Vec<Node>),
+}
+
+impl Node {
+    fn insert(&mut self, path: &[Segment], value: Value, key: &str) -> Result<(), String> {
+        let conflict = || format!("Key '{key}' conflicts with another key");
+        let Some((first, rest)) = path.split_first() else {
+            return match self {
+                Node::Empty => {
+                    *self = Node::Leaf(value);
+                    Ok(())
+                }
+                _ => Err(conflict()),
+            };
+        };
+        match first {
+            Segment::Key(name) => {
+                if let Node::Empty = self {
+                    *self = Node::Object(Vec::new());
+                }
+                let Node::Object(fields) = self else {
+                    return Err(conflict());
+                };
+                let position = match fields.iter().position(|(field, _)| field == name) {
+                    Some(position) => position,
+                    None => {
+                        fields.push((name.clone(), Node::Empty));
+                        fields.len() - 1
+                    }
+                };
+                fields[position].1.insert(rest, value, key)
+            }
+            Segment::Index(index) => {
+                if *index > MAX_ARRAY_INDEX {
+                    return Err(format!(
+                        "Key '{key}' has array index {index}, above the limit of {MAX_ARRAY_INDEX}"
+                    ));
+                }
+                if let Node::Empty = self {
+                    *self = Node::Array(Vec::new());
+                }
+                let Node::Array(items) = self else {
+                    return Err(conflict());
+                };
+                if items.len() <= *index {
+                    items.resize_with(index + 1, || Node::Empty);
+                }
+                items[*index].insert(rest, value, key)
+            }
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            // Array slots that no key filled
+            Node::Empty => Value::Null,
+            Node::Leaf(value) => value,
+            Node::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, node)| (name, node.into_value()))
+                    .collect::<Map<String, Value>>(),
+            ),
+            Node::Array(items) => Value::Array(items.into_iter().map(Node::into_value).collect()),
+        }
+    }
+}
+
+fn unflatten(flat: Value, options: Options) -> Result<Value, String> {
+    let entries: Vec<(String, Value)> = match flat {
+        Value::Object(map) => map.into_iter().collect(),
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| {
+                serde_json::from_value::<FlatEntry>(item)
+                    .map(|entry| (entry.key, entry.value))
+                    .map_err(|e| format!("Invalid flat entry: {e}"))
+            })
+            .collect::<Result<_, _>>()?,
+        _ => {
+            return Err(
+                "Flat input must be an object of key/value pairs or an array of entries"
+                    .to_string(),
+            );
+        }
+    };
+
+    let mut root = Node::Empty;
+    for (key, value) in entries {
+        let path = parse_key(&key, options)?;
+        root.insert(&path, value, &key)?;
+    }
+    Ok(match root {
+        Node::Empty => Value::Object(Map::new()),
+        node => node.into_value(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(operation: &str, document: Value) -> JsonFlattenInput {
+        JsonFlattenInput {
+            operation: Some(operation.to_string()),
+            json_string: document.to_string(),
+            separator: None,
+            array_policy: None,
+            escape_keys: None,
+            max_depth: None,
+            on_collision: None,
+        }
+    }
+
+    fn pairs(output: &JsonFlattenOutput) -> Vec<(String, Value)> {
+        output
+            .entries
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|e| (e.key.clone(), e.value.clone()))
+            .collect()
+    }
+
+    fn round_trip(flat: &JsonFlattenOutput, mut template: JsonFlattenInput) -> Value {
+        template.operation = Some("unflatten".to_string());
+        template.json_string = serde_json::to_string(flat.entries.as_ref().unwrap()).unwrap();
+        flatten_json(template).unwrap().document.unwrap()
+    }
+
+    #[test]
+    fn test_flatten_nested_document() {
+        let doc = json!({"user": {"name": "Ada", "tags": ["x", "y"]}, "active": true});
+        let output = flatten_json(input("flatten", doc)).unwrap();
+        assert_eq!(
+            pairs(&output),
+            vec![
+                ("active".to_string(), json!(true)),
+                ("user.name".to_string(), json!("Ada")),
+                ("user.tags[0]".to_string(), json!("x")),
+                ("user.tags[1]".to_string(), json!("y")),
+            ]
+        );
+        assert!(output.collisions.is_empty());
+    }
+
+    #[test]
+    fn test_round_trip_with_every_array_policy() {
+        let doc = json!({
+            "a": [{"b": 1}, {"c": [2, 3]}],
+            "d": {"": null, "e": {}, "h": {"": [1]}},
+            "f": [],
+            "g": "text"
+        });
+        for policy in ["brackets", "dots", "keep"] {
+            let mut template = input("flatten", doc.clone());
+            template.array_policy = Some(policy.to_string());
+            let flat = flatten_json(template.clone()).unwrap();
+            assert_eq!(round_trip(&flat, template), doc, "policy {policy}");
+        }
+    }
+
+    #[test]
+    fn test_dots_and_keep_policies() {
+        let doc = json!({"a": [1, {"b": 2}]});
+        let mut dots = input("flatten", doc.clone());
+        dots.array_policy = Some("dots".to_string());
+        let keys: Vec<String> = pairs(&flatten_json(dots).unwrap())
+            .into_iter()
+            .map(|p| p.0)
+            .collect();
+        assert_eq!(keys, vec!["a.0", "a.1.b"]);
+
+        let mut keep = input("flatten", doc);
+        keep.array_policy = Some("keep".to_string());
+        assert_eq!(
+            pairs(&flatten_json(keep).unwrap()),
+            vec![("a".to_string(), json!([1, {"b": 2}]))]
+        );
+    }
+
+    #[test]
+    fn test_root_array() {
+        let doc = json!([{"id": 1}, {"id": 2}]);
+        let flat = flatten_json(input("flatten", doc.clone())).unwrap();
+        assert_eq!(flat.entries.as_ref().unwrap()[1].key, "[1].id");
+        assert_eq!(round_trip(&flat, input("flatten", json!(null))), doc);
+    }
+
+    #[test]
+    fn test_special_characters_in_keys_are_escaped() {
+        let doc = json!({"a.b": {"c[0]": 1, "back\\slash": 2}, "a": {"b": 3}, "é ü": 4});
+        let flat = flatten_json(input("flatten", doc.clone())).unwrap();
+        let keys: Vec<String> = pairs(&flat).into_iter().map(|p| p.0).collect();
+        assert!(keys.contains(&"a\\.b.c\\[0\\]".to_string()));
+        assert!(keys.contains(&"a\\.b.back\\\\slash".to_string()));
+        assert!(keys.contains(&"a.b".to_string()));
+        assert!(keys.contains(&"é ü".to_string()));
+        assert_eq!(round_trip(&flat, input("flatten", json!(null))), doc);
+    }
+
+    #[test]
+    fn test_digit_keys_under_dots_policy() {
+        let doc = json!({"a": {"0": "key"}, "b": ["index"]});
+        let mut template = input("flatten", doc.clone());
+        template.array_policy = Some("dots".to_string());
+        let flat = flatten_json(template.clone()).unwrap();
+        let keys: Vec<String> = pairs(&flat).into_iter().map(|p| p.0).collect();
+        assert_eq!(keys, vec!["a.\\0", "b.0"]);
+        assert_eq!(round_trip(&flat, template), doc);
+    }
+
+    #[test]
+    fn test_collisions_without_escaping() {
+        // "a" comes first both in key order and in document order
+        let doc = json!({"a": {"b": 1}, "a.b": 2});
+        let mut template = input("flatten", doc);
+        template.escape_keys = Some(false);
+        let error = flatten_json(template.clone()).unwrap_err();
+        assert!(error.contains("a.b"));
+
+        template.on_collision = Some("keep_last".to_string());
+        let output = flatten_json(template.clone()).unwrap();
+        assert_eq!(pairs(&output), vec![("a.b".to_string(), json!(2))]);
+        assert_eq!(
+            output.collisions,
+            vec![Collision {
+                key: "a.b".to_string(),
+                occurrences: 2
+            }]
+        );
+
+        template.on_collision = Some("keep_first".to_string());
+        let output = flatten_json(template).unwrap();
+        assert_eq!(pairs(&output), vec![("a.b".to_string(), json!(1))]);
+    }
+
+    #[test]
+    fn test_max_depth_and_separator() {
+        let doc = json!({"a": {"b": {"c": 1}}});
+        let mut template = input("flatten", doc);
+        template.max_depth = Some(2);
+        template.separator = Some("/".to_string());
+        assert_eq!(
+            pairs(&flatten_json(template).unwrap()),
+            vec![("a/b".to_string(), json!({"c": 1}))]
+        );
+    }
+
+    #[test]
+    fn test_unflatten_object_with_sparse_array() {
+        let flat = json!({"rows[2].id": 7, "rows[0].id": 5, "name": "t"});
+        let document = flatten_json(input("unflatten", flat))
+            .unwrap()
+            .document
+            .unwrap();
+        assert_eq!(
+            document,
+            json!({"rows": [{"id": 5}, null, {"id": 7}], "name": "t"})
+        );
+    }
+
+    #[test]
+    fn test_unflatten_conflicts() {
+        let error = flatten_json(input("unflatten", json!({"a": 1, "a.b": 2}))).unwrap_err();
+        assert!(error.contains("conflicts"));
+        let error = flatten_json(input("unflatten", json!({"a[0]": 1, "a.b": 2}))).unwrap_err();
+        assert!(error.contains("conflicts"));
+        let error = flatten_json(input("unflatten", json!({"a[x]": 1}))).unwrap_err();
+        assert!(error.contains("array index"));
+        let error = flatten_json(input("unflatten", json!({"a[0]b": 1}))).unwrap_err();
+        assert!(error.contains("after ']'"));
+        let error = flatten_json(input("unflatten", json!({"a[999999999]": 1}))).unwrap_err();
+        assert!(error.contains("limit"));
+    }
+
+    #[test]
+    fn test_byte_order_mark_is_ignored() {
+        let mut template = input("flatten", json!(null));
+        template.json_string = "\u{feff}{\"a\": 1}".to_string();
+        assert_eq!(
+            pairs(&flatten_json(template).unwrap()),
+            vec![("a".to_string(), json!(1))]
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(
+            flatten_json(input("flatten", json!(5)))
+                .unwrap_err()
+                .contains("must be a JSON object")
+        );
+        assert!(
+            flatten_json(input("zip", json!({})))
+                .unwrap_err()
+                .contains("Unsupported operation")
+        );
+        let mut template = input("flatten", json!({}));
+        template.separator = Some("::".to_string());
+        assert!(flatten_json(template).unwrap_err().contains("Separator"));
+        let mut template = input("flatten", json!({}));
+        template.json_string = "{".to_string();
+        assert!(flatten_json(template).unwrap_err().contains("Invalid JSON"));
+    }
+}
@@ -1,5 +1,12 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -7,7 +14,9 @@ use serde::{Deserialize, Serialize};
 mod logic;
 
 // Re-export types from logic module
-pub use logic::{JsonFormatterInput as LogicInput, JsonFormatterResult as LogicOutput};
+pub use logic::{
+    JsonFormatterInput as LogicInput, JsonFormatterResult as LogicOutput, format_json,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -16,6 +25,8 @@ pub struct JsonFormatterInput {
     pub json_string: String,
     /// Number of spaces for indentation (0 for compact, default is 2)
     pub indent: Option<usize>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -32,8 +43,35 @@ pub struct JsonFormatterResult {
     pub output_length: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn json_formatter(input: JsonFormatterInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn json_formatter(request: Request<JsonFormatterInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "json_formatter",
+                JsonFormatterInput,
+                JsonFormatterResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("json_formatter", &input);
+    log.finish(run_json_formatter(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_json_formatter(input: JsonFormatterInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("json_string", &input.json_string)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         json_string: input.json_string,
@@ -56,6 +94,6 @@ pub fn json_formatter(input: JsonFormatterInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
     )
 }
@@ -1,5 +1,12 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,7 +16,7 @@ mod logic;
 // Re-export types from logic module
 pub use logic::{
     JsonValidatorInput as LogicInput, JsonValidatorResult as LogicOutput,
-    ValidationDetails as LogicDetails,
+    ValidationDetails as LogicDetails, validate_json,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -19,6 +26,8 @@ pub struct JsonValidatorInput {
     pub json_string: String,
     /// Optional JSON schema to validate against (as JSON string)
     pub schema: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -51,8 +60,35 @@ pub struct ValidationDetails {
     pub error_column: Option<usize>,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn json_validator(input: JsonValidatorInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn json_validator(request: Request<JsonValidatorInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "json_validator",
+                JsonValidatorInput,
+                JsonValidatorResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("json_validator", &input);
+    log.finish(run_json_validator(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_json_validator(input: JsonValidatorInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("json_string", &input.json_string)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         json_string: input.json_string,
@@ -82,6 +118,6 @@ pub fn json_validator(input: JsonValidatorInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
     )
 }
@@ -0,0 +1,130 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    MockResponseInput as LogicInput, MockResponseOutput as LogicOutput, build_mock_response,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MockResponseInput {
+    /// OpenAPI document, OpenAPI operation object or JSON Schema (as JSON string). $ref
+    /// must be local; an operation fragment can carry the components it refers to at its
+    /// top level
+    pub source: String,
+    /// Path of the operation when source is a full OpenAPI document, e.g. "/pets/{id}"
+    pub path: Option<String>,
+    /// HTTP method of the operation when source is a full OpenAPI document
+    pub method: Option<String>,
+    /// Response status to mock (default: the lowest 2xx status, else "default")
+    pub status: Option<String>,
+    /// Media type to mock (default: application/json, else the first one listed)
+    pub media_type: Option<String>,
+    /// Seed for a reproducible body (default: random, returned in the response)
+    pub seed: Option<u64>,
+    /// Chance from 0 to 1 that a nullable value is null (default: 0)
+    pub null_probability: Option<f64>,
+    /// Favor edge cases: boundary numbers and shortest or longest strings and arrays
+    /// (default: false)
+    pub edge_cases: Option<bool>,
+    /// Chance from 0 to 1 that each optional property is included (default: 0.5)
+    pub optional_probability: Option<f64>,
+    /// Return the example given in the document when there is one (default: true)
+    pub use_examples: Option<bool>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MockResponseOutput {
+    /// Mocked response body; null when the response has no content
+    pub body: Value,
+    /// Response status mocked, absent for a plain JSON Schema
+    pub status: Option<String>,
+    /// Media type mocked, absent for a plain JSON Schema or a response without content
+    pub media_type: Option<String>,
+    /// Where the body came from: "example", "generated" or "empty"
+    pub body_source: String,
+    /// Seed used; pass it again to reproduce the same body
+    pub seed: u64,
+    /// Unsupported keywords that were ignored, such as pattern, with their location
+    pub warnings: Vec<String>,
+}
+
+/// Build a mock response body for an OpenAPI operation or a JSON Schema, using the
+/// document's example when present and schema-driven generation otherwise, with
+/// configurable nulls and edge cases for stubbing integrations
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn mock_response(request: Request<MockResponseInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "mock_response",
+                MockResponseInput,
+                MockResponseOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("mock_response", &input);
+    log.finish(run_mock_response(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_mock_response(input: MockResponseInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("source", &input.source).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        source: input.source,
+        path: input.path,
+        method: input.method,
+        status: input.status,
+        media_type: input.media_type,
+        seed: input.seed,
+        null_probability: input.null_probability,
+        edge_cases: input.edge_cases,
+        optional_probability: input.optional_probability,
+        use_examples: input.use_examples,
+    };
+
+    // Call logic implementation
+    let result = match logic::build_mock_response(logic_input) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let response = MockResponseOutput {
+        body: result.body,
+        status: result.status,
+        media_type: result.media_type,
+        body_source: result.body_source,
+        seed: result.seed,
+        warnings: result.warnings,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
@@ -0,0 +1,568 @@
+use generate_from_schema_tool::{DEFAULT_OPTIONAL_PROBABILITY, Generator};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const DEFAULT_MEDIA_TYPE: &str = "application/json";
+/// Keywords whose values are data rather than schemas, left untouched by normalize
+const LITERAL_KEYWORDS: [&str; 5] = ["const", "default", "enum", "example", "examples"];
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockResponseInput {
+    /// OpenAPI document, OpenAPI operation object or JSON Schema (as JSON string)
+    pub source: String,
+    /// Path of the operation when source is a full OpenAPI document, e.g. "/pets/{id}"
+    pub path: Option<String>,
+    /// HTTP method of the operation when source is a full OpenAPI document
+    pub method: Option<String>,
+    /// Response status to mock (default: the lowest 2xx status, else "default")
+    pub status: Option<String>,
+    /// Media type to mock (default: application/json, else the first one listed)
+    pub media_type: Option<String>,
+    /// Seed for a reproducible body (default: random)
+    pub seed: Option<u64>,
+    /// Chance from 0 to 1 that a nullable value is null (default: 0)
+    pub null_probability: Option<f64>,
+    /// Favor edge cases: boundary numbers and shortest or longest strings and arrays
+    /// (default: false)
+    pub edge_cases: Option<bool>,
+    /// Chance from 0 to 1 that each optional property is included (default: 0.5)
+    pub optional_probability: Option<f64>,
+    /// Return the example given in the document when there is one (default: true)
+    pub use_examples: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockResponseOutput {
+    /// Mocked response body; null when the response has no content
+    pub body: Value,
+    /// Response status mocked, absent for a plain JSON Schema
+    pub status: Option<String>,
+    /// Media type mocked, absent for a plain JSON Schema or a response without content
+    pub media_type: Option<String>,
+    /// Where the body came from: "example", "generated" or "empty"
+    pub body_source: String,
+    /// Seed used, to reproduce the same body
+    pub seed: u64,
+    /// Keywords that were ignored, with their schema location
+    pub warnings: Vec<String>,
+}
+
+/// Where the body is taken from once the response is located
+enum Body {
+    Example(Value),
+    /// Local reference to the schema within the normalized document
+    Schema(String),
+    Empty,
+}
+
+/// Escape a key for use in a JSON pointer
+fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Rewrite OpenAPI 3.0 schema extensions into JSON Schema the generator understands:
+/// `nullable: true` adds "null" to the type, and writeOnly properties are dropped since
+/// they never appear in responses
+fn normalize(value: &mut Value) {
+    match value {
+        Value::Object(obj) => {
+            if obj.get("nullable") == Some(&Value::Bool(true)) {
+                obj.remove("nullable");
+                match obj.get_mut("type") {
+                    Some(Value::String(name)) => {
+                        let name = Value::String(name.clone());
+                        obj.insert("type".to_string(), Value::Array(vec![name, "null".into()]));
+                    }
+                    Some(Value::Array(names)) if !names.contains(&Value::from("null")) => {
+                        names.push("null".into());
+                    }
+                    Some(_) => {}
+                    None => {
+                        let schema = Value::Object(std::mem::take(obj));
+                        obj.insert(
+                            "anyOf".to_string(),
+                            Value::Array(vec![schema, serde_json::json!({"type": "null"})]),
+                        );
+                    }
+                }
+            }
+            if let Some(Value::Object(properties)) = obj.get_mut("properties") {
+                let write_only: Vec<String> = properties
+                    .iter()
+                    .filter(|(_, schema)| schema.get("writeOnly") == Some(&Value::Bool(true)))
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                for name in &write_only {
+                    properties.remove(name);
+                }
+                if let Some(Value::Array(required)) = obj.get_mut("required") {
+                    required.retain(|name| {
+                        !write_only
+                            .iter()
+                            .any(|removed| name.as_str() == Some(removed))
+                    });
+                }
+            }
+            obj.iter_mut()
+                .filter(|(key, _)| !LITERAL_KEYWORDS.contains(&key.as_str()))
+                .for_each(|(_, value)| normalize(value));
+        }
+        Value::Array(items) => items.iter_mut().for_each(normalize),
+        _ => {}
+    }
+}
+
+/// Follow a response-level `$ref` such as `#/components/responses/NotFound`
+fn follow<'a>(
+    root: &'a Value,
+    value: &'a Value,
+    pointer: String,
+) -> Result<(&'a Value, String), String> {
+    match value.get("$ref").and_then(Value::as_str) {
+        Some(reference) => {
+            let target = reference
+                .strip_prefix('#')
+                .and_then(|pointer| root.pointer(pointer))
+                .ok_or_else(|| format!("Reference '{reference}' does not resolve"))?;
+            Ok((target, reference.to_string()))
+        }
+        None => Ok((value, pointer)),
+    }
+}
+
+/// Locate the operation within the source, returning it with its JSON pointer
+fn find_operation<'a>(
+    root: &'a Value,
+    input: &MockResponseInput,
+) -> Result<Option<(&'a Value, String)>, String> {
+    if let Some(paths) = root.get("paths") {
+        let (Some(path), Some(method)) = (&input.path, &input.method) else {
+            return Err(
+                "path and method are required when source is a full OpenAPI document".to_string(),
+            );
+        };
+        let path_item = paths.get(path).ok_or_else(|| {
+            let known: Vec<&str> = paths
+                .as_object()
+                .map(|paths| paths.keys().map(String::as_str).collect())
+                .unwrap_or_default();
+            format!(
+                "Path '{path}' not found. Available paths: {}",
+                known.join(", ")
+            )
+        })?;
+        let method = method.to_lowercase();
+        let operation = path_item.get(&method).ok_or_else(|| {
+            let known: Vec<&str> = METHODS
+                .into_iter()
+                .filter(|&m| path_item.get(m).is_some())
+                .collect();
+            format!(
+                "Method '{method}' not found for '{path}'. Available methods: {}",
+                known.join(", ")
+            )
+        })?;
+        let pointer = format!("#/paths/{}/{method}", pointer_token(path));
+        return Ok(Some((operation, pointer)));
+    }
+    if root.get("responses").is_some() {
+        return Ok(Some((root, "#".to_string())));
+    }
+    Ok(None)
+}
+
+/// Pick the response status and media type, and where the body comes from
+fn select_body(
+    root: &Value,
+    operation: &Value,
+    pointer: &str,
+    input: &MockResponseInput,
+) -> Result<(String, Option<String>, Body), String> {
+    let responses = operation
+        .get("responses")
+        .and_then(Value::as_object)
+        .filter(|responses| !responses.is_empty())
+        .ok_or_else(|| format!("Operation at {pointer} has no responses"))?;
+
+    let status = match &input.status {
+        Some(status) => status.clone(),
+        None => {
+            let mut success: Vec<&String> = responses
+                .keys()
+                .filter(|status| status.starts_with('2'))
+                .collect();
+            success.sort();
+            match success.first() {
+                Some(status) => status.to_string(),
+                None if responses.contains_key("default") => "default".to_string(),
+                None => {
+                    return Err(format!(
+                        "Operation at {pointer} has no 2xx or default response; choose one \
+                         of: {}",
+                        responses.keys().cloned().collect::<Vec<_>>().join(", ")
+                    ));
+                }
+            }
+        }
+    };
+    let response = responses.get(&status).ok_or_else(|| {
+        format!(
+            "Status '{status}' not found. Available statuses: {}",
+            responses.keys().cloned().collect::<Vec<_>>().join(", ")
+        )
+    })?;
+    let response_pointer = format!("{pointer}/responses/{}", pointer_token(&status));
+    let (response, response_pointer) = follow(root, response, response_pointer)?;
+
+    // Swagger 2.0 puts the schema and examples straight on the response
+    if let Some(schema) = response.get("schema") {
+        let media_type = input
+            .media_type
+            .clone()
+            .unwrap_or_else(|| DEFAULT_MEDIA_TYPE.to_string());
+        let example = response.get("examples").and_then(|e| e.get(&media_type));
+        let body = match example {
+            Some(example) if input.use_examples.unwrap_or(true) => Body::Example(example.clone()),
+            _ => Body::Schema(schema_reference(
+                schema,
+                &format!("{response_pointer}/schema"),
+            )),
+        };
+        return Ok((status, Some(media_type), body));
+    }
+
+    let content = match response.get("content").and_then(Value::as_object) {
+        Some(content) if !content.is_empty() => content,
+        _ => return Ok((status, None, Body::Empty)),
+    };
+    let media_type = match &input.media_type {
+        Some(media_type) if content.contains_key(media_type) => media_type.clone(),
+        Some(media_type) => {
+            return Err(format!(
+                "Media type '{media_type}' not found for status '{status}'. Available media \
+                 types: {}",
+                content.keys().cloned().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        None if content.contains_key(DEFAULT_MEDIA_TYPE) => DEFAULT_MEDIA_TYPE.to_string(),
+        None => content.keys().next().cloned().unwrap_or_default(),
+    };
+    let media = &content[&media_type];
+    let media_pointer = format!("{response_pointer}/content/{}", pointer_token(&media_type));
+
+    if input.use_examples.unwrap_or(true)
+        && let Some(example) = media_example(root, media)?
+    {
+        return Ok((status, Some(media_type), Body::Example(example)));
+    }
+    let body = match media.get("schema") {
+        Some(schema) => Body::Schema(schema_reference(schema, &format!("{media_pointer}/schema"))),
+        None => Body::Empty,
+    };
+    Ok((status, Some(media_type), body))
+}
+
+/// The `example`, or the first of `examples`, of a media type object or its schema
+fn media_example(root: &Value, media: &Value) -> Result<Option<Value>, String> {
+    if let Some(example) = media.get("example") {
+        return Ok(Some(example.clone()));
+    }
+    if let Some(first) = media
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|examples| examples.values().next())
+    {
+        let (example, _) = follow(root, first, String::new())?;
+        if let Some(value) = example.get("value") {
+            return Ok(Some(value.clone()));
+        }
+    }
+    Ok(media
+        .get("schema")
+        .and_then(|schema| schema.get("example"))
+        .cloned())
+}
+
+/// Reference to generate from: the schema's own `$ref` when it is only that, so
+/// warnings name the component, otherwise its location in the document
+fn schema_reference(schema: &Value, pointer: &str) -> String {
+    match schema.as_object() {
+        Some(obj) if obj.len() == 1 => match obj.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference.to_string(),
+            None => pointer.to_string(),
+        },
+        _ => pointer.to_string(),
+    }
+}
+
+fn probability(value: Option<f64>, default: f64, name: &str) -> Result<f64, String> {
+    let value = value.unwrap_or(default);
+    if !(0.0..=1.0).contains(&value) {
+        return Err(format!("{name} must be between 0 and 1"));
+    }
+    Ok(value)
+}
+
+pub fn build_mock_response(input: MockResponseInput) -> Result<MockResponseOutput, String> {
+    let mut root: Value =
+        serde_json::from_str(&input.source).map_err(|e| format!("Invalid source JSON: {e}"))?;
+    let null_probability = probability(input.null_probability, 0.0, "null_probability")?;
+    let optional_probability = probability(
+        input.optional_probability,
+        DEFAULT_OPTIONAL_PROBABILITY,
+        "optional_probability",
+    )?;
+    let seed = input.seed.unwrap_or_else(rand::random);
+    normalize(&mut root);
+
+    let (status, media_type, body) = match find_operation(&root, &input)? {
+        Some((operation, pointer)) => {
+            let (status, media_type, body) = select_body(&root, operation, &pointer, &input)?;
+            (Some(status), media_type, body)
+        }
+        None => (None, None, Body::Schema("#".to_string())),
+    };
+
+    let mut generator = Generator::new(
+        &root,
+        seed,
+        input.edge_cases.unwrap_or(false),
+        optional_probability,
+    )
+    .with_null_probability(null_probability);
+    let (body, body_source) = match body {
+        Body::Example(example) => (example, "example"),
+        Body::Schema(reference) => (generator.generate_at(&reference)?, "generated"),
+        Body::Empty => (Value::Null, "empty"),
+    };
+
+    Ok(MockResponseOutput {
+        body,
+        status,
+        media_type,
+        body_source: body_source.to_string(),
+        seed,
+        warnings: generator.warnings(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn petstore() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "paths": {
+                "/pets/{id}": {
+                    "get": {
+                        "responses": {
+                            "404": {"$ref": "#/components/responses/NotFound"},
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/Pet"}
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "delete": {"responses": {"204": {"description": "Deleted"}}}
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "required": ["id", "name", "tag", "password"],
+                        "properties": {
+                            "id": {"type": "integer", "minimum": 1},
+                            "name": {"type": "string", "minLength": 1},
+                            "tag": {"type": "string", "nullable": true},
+                            "password": {"type": "string", "writeOnly": true}
+                        }
+                    }
+                },
+                "responses": {
+                    "NotFound": {
+                        "description": "Not found",
+                        "content": {
+                            "application/json": {
+                                "example": {"code": 404, "message": "Pet not found"}
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    fn input(source: Value, path: Option<&str>, method: Option<&str>) -> MockResponseInput {
+        MockResponseInput {
+            source: source.to_string(),
+            path: path.map(str::to_string),
+            method: method.map(str::to_string),
+            status: None,
+            media_type: None,
+            seed: Some(42),
+            null_probability: None,
+            edge_cases: None,
+            optional_probability: None,
+            use_examples: None,
+        }
+    }
+
+    #[test]
+    fn test_generates_default_success_response() {
+        let output =
+            build_mock_response(input(petstore(), Some("/pets/{id}"), Some("GET"))).unwrap();
+        assert_eq!(output.status.as_deref(), Some("200"));
+        assert_eq!(output.media_type.as_deref(), Some("application/json"));
+        assert_eq!(output.body_source, "generated");
+        let body = &output.body;
+        assert!(body["id"].as_i64().unwrap() >= 1);
+        assert!(!body["name"].as_str().unwrap().is_empty());
+        // writeOnly properties never appear in responses, even when required
+        assert!(body.get("password").is_none());
+        assert!(output.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_null_probability() {
+        let run = |null_probability: f64| {
+            let mut request = input(petstore(), Some("/pets/{id}"), Some("get"));
+            request.null_probability = Some(null_probability);
+            build_mock_response(request).unwrap().body["tag"].clone()
+        };
+        assert!(run(1.0).is_null());
+        assert!(run(0.0).is_string());
+    }
+
+    #[test]
+    fn test_examples_and_response_refs() {
+        let mut request = input(petstore(), Some("/pets/{id}"), Some("get"));
+        request.status = Some("404".to_string());
+        let output = build_mock_response(request.clone()).unwrap();
+        assert_eq!(output.body_source, "example");
+        assert_eq!(
+            output.body,
+            json!({"code": 404, "message": "Pet not found"})
+        );
+
+        // Without the example and without a schema there is nothing to mock
+        request.use_examples = Some(false);
+        let output = build_mock_response(request).unwrap();
+        assert_eq!(output.body_source, "empty");
+        assert!(output.body.is_null());
+    }
+
+    #[test]
+    fn test_empty_response() {
+        let output =
+            build_mock_response(input(petstore(), Some("/pets/{id}"), Some("delete"))).unwrap();
+        assert_eq!(output.status.as_deref(), Some("204"));
+        assert_eq!(output.body_source, "empty");
+        assert_eq!(output.media_type, None);
+    }
+
+    #[test]
+    fn test_operation_fragment_and_plain_schema() {
+        let operation = json!({
+            "responses": {
+                "default": {
+                    "content": {
+                        "text/plain": {"schema": {"type": "string", "enum": ["ok"]}}
+                    }
+                }
+            }
+        });
+        let output = build_mock_response(input(operation, None, None)).unwrap();
+        assert_eq!(output.status.as_deref(), Some("default"));
+        assert_eq!(output.media_type.as_deref(), Some("text/plain"));
+        assert_eq!(output.body, json!("ok"));
+
+        let schema =
+            json!({"type": "array", "items": {"type": "boolean"}, "minItems": 2, "maxItems": 2});
+        let output = build_mock_response(input(schema, None, None)).unwrap();
+        assert_eq!(output.status, None);
+        assert_eq!(output.body.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_edge_cases_and_seed() {
+        let schema = json!({"type": "integer", "minimum": 3, "maximum": 1000});
+        let values: Vec<i64> = (0..20)
+            .map(|seed| {
+                let mut request = input(schema.clone(), None, None);
+                request.edge_cases = Some(true);
+                request.seed = Some(seed);
+                build_mock_response(request).unwrap().body.as_i64().unwrap()
+            })
+            .collect();
+        assert!(values.contains(&3) && values.contains(&1000), "{values:?}");
+
+        let first = build_mock_response(input(schema.clone(), None, None)).unwrap();
+        assert_eq!(first.seed, 42);
+        assert_eq!(
+            build_mock_response(input(schema, None, None)).unwrap().body,
+            first.body
+        );
+    }
+
+    #[test]
+    fn test_swagger_2_response() {
+        let source = json!({
+            "swagger": "2.0",
+            "paths": {
+                "/health": {
+                    "get": {
+                        "responses": {
+                            "200": {"schema": {"type": "object", "required": ["up"], "properties": {"up": {"const": true}}}}
+                        }
+                    }
+                }
+            }
+        });
+        let output = build_mock_response(input(source, Some("/health"), Some("get"))).unwrap();
+        assert_eq!(output.body, json!({"up": true}));
+    }
+
+    #[test]
+    fn test_lookup_errors() {
+        let err = build_mock_response(input(petstore(), None, None)).unwrap_err();
+        assert!(err.contains("path and method are required"));
+        let err = build_mock_response(input(petstore(), Some("/owners"), Some("get"))).unwrap_err();
+        assert_eq!(err, "Path '/owners' not found. Available paths: /pets/{id}");
+        let err =
+            build_mock_response(input(petstore(), Some("/pets/{id}"), Some("post"))).unwrap_err();
+        assert!(err.ends_with("Available methods: get, delete"), "{err}");
+
+        let mut request = input(petstore(), Some("/pets/{id}"), Some("get"));
+        request.status = Some("500".to_string());
+        assert!(
+            build_mock_response(request)
+                .unwrap_err()
+                .starts_with("Status '500' not found")
+        );
+
+        let mut request = input(petstore(), Some("/pets/{id}"), Some("get"));
+        request.media_type = Some("application/xml".to_string());
+        assert!(
+            build_mock_response(request)
+                .unwrap_err()
+                .contains("Available media types: application/json")
+        );
+
+        let mut request = input(json!({"type": "string"}), None, None);
+        request.null_probability = Some(2.0);
+        assert_eq!(
+            build_mock_response(request).unwrap_err(),
+            "null_probability must be between 0 and 1"
+        );
+        assert!(build_mock_response(input(json!(1), None, None)).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    OpenApiInspectInput as LogicInput, OpenApiInspectResult as LogicOutput, inspect_openapi,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    IssueSchema as Issue, OpenApiInspectResultSchema as OpenApiInspectResult,
+    OperationSummarySchema as OperationSummary, ParameterSummarySchema as ParameterSummary,
+    SecuritySchemeSummarySchema as SecuritySchemeSummary,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OpenApiInspectInput {
+    #[serde(flatten)]
+    pub params: logic::OpenApiInspectInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Summarize an OpenAPI 3.x document (operations, parameters, schemas and auth schemes)
+/// and flag common issues such as missing operationIds and unresolved references
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn openapi_inspect(request: Request<OpenApiInspectInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "openapi_inspect",
+                OpenApiInspectInput,
+                OpenApiInspectResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("openapi_inspect", &input);
+    log.finish(run_openapi_inspect(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_openapi_inspect(input: OpenApiInspectInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("document", &input.params.document)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::inspect_openapi(input.params.into()) {
+        Ok(result) => OpenApiInspectResult::from(result),
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&result).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
@@ -0,0 +1,808 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Path item keys that hold operations, in the order operations are listed
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct OpenApiInspectInput {
+    /// OpenAPI 3.x document, as JSON or YAML
+    pub document: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct ParameterSummary {
+    pub name: String,
+    /// Where the parameter goes: path, query, header or cookie
+    pub location: String,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct OperationSummary {
+    /// HTTP method, upper case
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    pub summary: Option<String>,
+    pub tags: Vec<String>,
+    /// Path-level and operation-level parameters, with $ref resolved
+    #[tool_types(nested)]
+    pub parameters: Vec<ParameterSummary>,
+    pub has_request_body: bool,
+    /// Response status codes, such as "200" and "default"
+    pub responses: Vec<String>,
+    /// Security schemes that apply, from the operation or else the document; empty when
+    /// the operation is public
+    pub security: Vec<String>,
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct SecuritySchemeSummary {
+    pub name: String,
+    /// Scheme type: apiKey, http, mutualTLS, oauth2 or openIdConnect
+    pub scheme_type: String,
+    /// Where the credential goes, e.g. "bearer", "header X-API-Key" or the oauth2 flows
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Issue {
+    /// "error" for documents that break the specification or cannot resolve, "warning"
+    /// for common omissions
+    pub severity: String,
+    /// JSON pointer to the offending part of the document
+    pub location: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct OpenApiInspectResult {
+    /// Value of the openapi field, e.g. "3.1.0"
+    pub openapi_version: String,
+    pub title: Option<String>,
+    /// Version of the API itself, from info.version
+    pub api_version: Option<String>,
+    pub servers: Vec<String>,
+    pub operation_count: usize,
+    #[tool_types(nested)]
+    pub operations: Vec<OperationSummary>,
+    /// Names of the component schemas
+    pub schemas: Vec<String>,
+    #[tool_types(nested)]
+    pub security_schemes: Vec<SecuritySchemeSummary>,
+    /// Problems found, errors first
+    #[tool_types(nested)]
+    pub issues: Vec<Issue>,
+    /// Whether no errors were found; warnings do not count
+    pub is_valid: bool,
+}
+
+/// Escape a key for use in a JSON pointer
+fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn string_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn parse_document(document: &str) -> Result<Value, String> {
+    match serde_json::from_str::<Value>(document) {
+        Ok(value) => Ok(value),
+        Err(json_error) => serde_yml::from_str::<Value>(document).map_err(|yaml_error| {
+            format!("Document is neither valid JSON ({json_error}) nor valid YAML ({yaml_error})")
+        }),
+    }
+}
+
+/// Names inside `{}` in a path template
+fn template_parameters(path: &str) -> Vec<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+struct Inspector<'a> {
+    root: &'a Value,
+    issues: Vec<Issue>,
+    /// Local references seen, to find unresolved ones and unused schemas
+    references: BTreeMap<String, String>,
+}
+
+impl<'a> Inspector<'a> {
+    fn error(&mut self, location: &str, message: String) {
+        self.issue("error", location, message);
+    }
+
+    fn warning(&mut self, location: &str, message: String) {
+        self.issue("warning", location, message);
+    }
+
+    fn issue(&mut self, severity: &str, location: &str, message: String) {
+        self.issues.push(Issue {
+            severity: severity.to_string(),
+            location: location.to_string(),
+            message,
+        });
+    }
+
+    /// Record every $ref in the document with where it appears
+    fn collect_references(&mut self, value: &Value, location: &str) {
+        match value {
+            Value::Object(obj) => {
+                for (key, child) in obj {
+                    let child_location = format!("{location}/{}", pointer_token(key));
+                    if key == "$ref"
+                        && let Some(reference) = child.as_str()
+                    {
+                        self.references
+                            .entry(reference.to_string())
+                            .or_insert(child_location);
+                    } else {
+                        self.collect_references(child, &child_location);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.collect_references(item, &format!("{location}/{index}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_references(&mut self) {
+        let references = std::mem::take(&mut self.references);
+        let mut external = 0;
+        for (reference, location) in &references {
+            match reference.strip_prefix('#') {
+                Some(pointer) if self.root.pointer(pointer).is_none() => {
+                    self.error(
+                        location,
+                        format!("Reference '{reference}' does not resolve"),
+                    );
+                }
+                Some(_) => {}
+                None => external += 1,
+            }
+        }
+        if external > 0 {
+            self.warning(
+                "#",
+                format!("{external} external reference(s) were not checked"),
+            );
+        }
+        self.references = references;
+    }
+
+    /// Follow a local $ref, if any, to the object it points at
+    fn resolve(&self, value: &'a Value) -> &'a Value {
+        let mut current = value;
+        // Bounded so reference cycles cannot loop forever
+        for _ in 0..32 {
+            match current
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+                .and_then(|pointer| self.root.pointer(pointer))
+            {
+                Some(target) => current = target,
+                None => break,
+            }
+        }
+        current
+    }
+
+    fn parameters(&mut self, list: Option<&'a Value>, location: &str) -> Vec<ParameterSummary> {
+        let Some(items) = list.and_then(Value::as_array) else {
+            return Vec::new();
+        };
+        let mut parameters = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            let parameter = self.resolve(item);
+            let item_location = format!("{location}/{index}");
+            let (Some(name), Some(place)) = (
+                string_field(parameter, "name"),
+                string_field(parameter, "in"),
+            ) else {
+                // Unresolved references are reported on their own
+                if item.get("$ref").is_none() {
+                    self.error(
+                        &item_location,
+                        "Parameter needs both name and in".to_string(),
+                    );
+                }
+                continue;
+            };
+            let required = parameter.get("required").and_then(Value::as_bool) == Some(true);
+            if place == "path" && !required {
+                self.error(
+                    &item_location,
+                    format!("Path parameter '{name}' must be marked required: true"),
+                );
+            }
+            if parameters
+                .iter()
+                .any(|p: &ParameterSummary| p.name == name && p.location == place)
+            {
+                self.error(
+                    &item_location,
+                    format!("Parameter '{name}' in {place} is declared twice"),
+                );
+                continue;
+            }
+            parameters.push(ParameterSummary {
+                name,
+                location: place,
+                required,
+            });
+        }
+        parameters
+    }
+
+    fn security_names(requirements: &Value) -> Vec<String> {
+        let mut names = BTreeSet::new();
+        for requirement in requirements.as_array().into_iter().flatten() {
+            if let Some(requirement) = requirement.as_object() {
+                names.extend(requirement.keys().cloned());
+            }
+        }
+        names.into_iter().collect()
+    }
+
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        operation: &'a Value,
+        path_parameters: &[ParameterSummary],
+        global_security: Option<&'a Value>,
+        operation_ids: &mut BTreeMap<String, String>,
+    ) -> OperationSummary {
+        let location = format!("#/paths/{}/{method}", pointer_token(path));
+
+        let operation_id = string_field(operation, "operationId");
+        match &operation_id {
+            None => self.warning(&location, "Operation has no operationId".to_string()),
+            Some(id) => {
+                if let Some(first) = operation_ids.get(id) {
+                    let message = format!("operationId '{id}' is already used at {first}");
+                    self.error(&location, message);
+                } else {
+                    operation_ids.insert(id.clone(), location.clone());
+                }
+            }
+        }
+
+        // Operation parameters override path-level ones with the same name and location
+        let own = self.parameters(
+            operation.get("parameters"),
+            &format!("{location}/parameters"),
+        );
+        let mut parameters: Vec<ParameterSummary> = path_parameters
+            .iter()
+            .filter(|p| {
+                !own.iter()
+                    .any(|o| o.name == p.name && o.location == p.location)
+            })
+            .cloned()
+            .collect();
+        parameters.extend(own);
+
+        let template = template_parameters(path);
+        for name in &template {
+            if !parameters
+                .iter()
+                .any(|p| p.location == "path" && &p.name == name)
+            {
+                self.error(
+                    &location,
+                    format!("Path parameter '{name}' is in the path but not declared"),
+                );
+            }
+        }
+        for parameter in parameters.iter().filter(|p| p.location == "path") {
+            if !template.contains(&parameter.name) {
+                self.error(
+                    &location,
+                    format!(
+                        "Path parameter '{}' is declared but not in the path",
+                        parameter.name
+                    ),
+                );
+            }
+        }
+
+        let has_request_body = operation.get("requestBody").is_some();
+        if has_request_body && matches!(method, "get" | "head" | "delete") {
+            self.warning(
+                &location,
+                format!(
+                    "{} request body has no defined meaning and is often dropped",
+                    method.to_uppercase()
+                ),
+            );
+        }
+
+        let responses: Vec<String> = match operation.get("responses").and_then(Value::as_object) {
+            Some(responses) if !responses.is_empty() => responses.keys().cloned().collect(),
+            _ => {
+                self.error(&location, "Operation has no responses".to_string());
+                Vec::new()
+            }
+        };
+        if !responses.is_empty()
+            && !responses
+                .iter()
+                .any(|status| status.starts_with('2') || status == "default")
+        {
+            self.warning(
+                &location,
+                "Operation has no 2xx or default response".to_string(),
+            );
+        }
+
+        let security = match operation.get("security").or(global_security) {
+            Some(requirements) => Self::security_names(requirements),
+            None => Vec::new(),
+        };
+
+        OperationSummary {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            operation_id,
+            summary: string_field(operation, "summary"),
+            tags: operation
+                .get("tags")
+                .and_then(Value::as_array)
+                .map(|tags| {
+                    tags.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            parameters,
+            has_request_body,
+            responses,
+            security,
+            deprecated: operation.get("deprecated").and_then(Value::as_bool) == Some(true),
+        }
+    }
+
+    fn security_schemes(
+        &mut self,
+        schemes: Option<&'a Map<String, Value>>,
+    ) -> Vec<SecuritySchemeSummary> {
+        let mut summaries = Vec::new();
+        for (name, scheme) in schemes.into_iter().flatten() {
+            let scheme = self.resolve(scheme);
+            let location = format!("#/components/securitySchemes/{}", pointer_token(name));
+            let Some(scheme_type) = string_field(scheme, "type") else {
+                self.error(&location, "Security scheme has no type".to_string());
+                continue;
+            };
+            let detail = match scheme_type.as_str() {
+                "apiKey" => match (string_field(scheme, "in"), string_field(scheme, "name")) {
+                    (Some(place), Some(key)) => Some(format!("{place} {key}")),
+                    _ => {
+                        self.error(&location, "apiKey scheme needs in and name".to_string());
+                        None
+                    }
+                },
+                "http" => {
+                    let detail = string_field(scheme, "scheme");
+                    if detail.is_none() {
+                        self.error(&location, "http scheme needs scheme".to_string());
+                    }
+                    detail
+                }
+                "oauth2" => scheme.get("flows").and_then(Value::as_object).map(|flows| {
+                    format!(
+                        "flows: {}",
+                        flows.keys().cloned().collect::<Vec<_>>().join(", ")
+                    )
+                }),
+                "openIdConnect" => string_field(scheme, "openIdConnectUrl"),
+                "mutualTLS" => None,
+                other => {
+                    self.error(&location, format!("Unknown security scheme type '{other}'"));
+                    None
+                }
+            };
+            summaries.push(SecuritySchemeSummary {
+                name: name.clone(),
+                scheme_type,
+                detail,
+            });
+        }
+        summaries
+    }
+
+    fn check_security_requirements(
+        &mut self,
+        operations: &[OperationSummary],
+        schemes: &[SecuritySchemeSummary],
+    ) {
+        let mut reported = BTreeSet::new();
+        for operation in operations {
+            for name in &operation.security {
+                if !schemes.iter().any(|s| &s.name == name) && reported.insert(name.clone()) {
+                    let location = format!(
+                        "#/paths/{}/{}",
+                        pointer_token(&operation.path),
+                        operation.method.to_lowercase()
+                    );
+                    self.error(
+                        &location,
+                        format!("Security scheme '{name}' is not defined in components"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+pub fn inspect_openapi(input: OpenApiInspectInput) -> Result<OpenApiInspectResult, String> {
+    let root = parse_document(&input.document)?;
+    if !root.is_object() {
+        return Err("Document must be an object".to_string());
+    }
+    if let Some(version) = root.get("swagger") {
+        return Err(format!(
+            "Only OpenAPI 3.x documents are supported, this is Swagger {}",
+            version.as_str().unwrap_or("2.0")
+        ));
+    }
+    let openapi_version = match root.get("openapi").and_then(Value::as_str) {
+        Some(version) if version.starts_with("3.") => version.to_string(),
+        Some(version) => {
+            return Err(format!(
+                "Unsupported openapi version '{version}', expected 3.x"
+            ));
+        }
+        None => {
+            return Err(
+                "Document has no openapi field; is it an OpenAPI 3.x document?".to_string(),
+            );
+        }
+    };
+
+    let mut inspector = Inspector {
+        root: &root,
+        issues: Vec::new(),
+        references: BTreeMap::new(),
+    };
+    inspector.collect_references(&root, "#");
+    inspector.check_references();
+
+    let info = root.get("info");
+    let title = info.and_then(|info| string_field(info, "title"));
+    let api_version = info.and_then(|info| string_field(info, "version"));
+    if title.is_none() || api_version.is_none() {
+        inspector.error("#/info", "info needs both title and version".to_string());
+    }
+    let servers = root
+        .get("servers")
+        .and_then(Value::as_array)
+        .map(|servers| {
+            servers
+                .iter()
+                .filter_map(|s| string_field(s, "url"))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let paths = root.get("paths").and_then(Value::as_object);
+    // 3.1 allows documents with only webhooks or components
+    if paths.is_none() && openapi_version.starts_with("3.0") {
+        inspector.error(
+            "#",
+            "Document has no paths, which OpenAPI 3.0 requires".to_string(),
+        );
+    }
+
+    let global_security = root.get("security");
+    let mut operation_ids = BTreeMap::new();
+    let mut operations = Vec::new();
+    for (path, item) in paths.into_iter().flatten() {
+        let location = format!("#/paths/{}", pointer_token(path));
+        if !path.starts_with('/') {
+            inspector.error(&location, format!("Path '{path}' must start with '/'"));
+        }
+        let item = inspector.resolve(item);
+        let path_parameters =
+            inspector.parameters(item.get("parameters"), &format!("{location}/parameters"));
+        let methods: Vec<&str> = METHODS
+            .into_iter()
+            .filter(|&method| item.get(method).is_some())
+            .collect();
+        if methods.is_empty() {
+            inspector.warning(&location, "Path has no operations".to_string());
+        }
+        for method in methods {
+            let operation = inspector.operation(
+                path,
+                method,
+                &item[method],
+                &path_parameters,
+                global_security,
+                &mut operation_ids,
+            );
+            operations.push(operation);
+        }
+    }
+
+    let components = root.get("components");
+    let schemas: Vec<String> = components
+        .and_then(|c| c.get("schemas"))
+        .and_then(Value::as_object)
+        .map(|schemas| schemas.keys().cloned().collect())
+        .unwrap_or_default();
+    for name in &schemas {
+        let reference = format!("#/components/schemas/{}", pointer_token(name));
+        if !inspector.references.contains_key(&reference) {
+            inspector.warning(&reference, format!("Schema '{name}' is never referenced"));
+        }
+    }
+
+    let security_schemes = inspector.security_schemes(
+        components
+            .and_then(|c| c.get("securitySchemes"))
+            .and_then(Value::as_object),
+    );
+    inspector.check_security_requirements(&operations, &security_schemes);
+
+    let mut issues = inspector.issues;
+    // Stable sort keeps document order within each severity
+    issues.sort_by_key(|issue| issue.severity != "error");
+    let is_valid = !issues.iter().any(|issue| issue.severity == "error");
+
+    Ok(OpenApiInspectResult {
+        openapi_version,
+        title,
+        api_version,
+        servers,
+        operation_count: operations.len(),
+        operations,
+        schemas,
+        security_schemes,
+        issues,
+        is_valid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn inspect(document: Value) -> OpenApiInspectResult {
+        inspect_openapi(OpenApiInspectInput {
+            document: document.to_string(),
+        })
+        .unwrap()
+    }
+
+    fn messages(result: &OpenApiInspectResult, severity: &str) -> Vec<String> {
+        result
+            .issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .map(|issue| issue.message.clone())
+            .collect()
+    }
+
+    fn petstore() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Petstore", "version": "1.2.0"},
+            "servers": [{"url": "https://api.example.com/v1"}],
+            "security": [{"bearer": []}],
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "summary": "List pets",
+                        "tags": ["pets"],
+                        "parameters": [{"$ref": "#/components/parameters/Limit"}],
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"type": "array", "items": {"$ref": "#/components/schemas/Pet"}}
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "post": {
+                        "operationId": "createPet",
+                        "security": [{"api_key": []}],
+                        "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}},
+                        "responses": {"201": {"description": "Created"}}
+                    }
+                },
+                "/pets/{id}": {
+                    "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}}],
+                    "get": {
+                        "operationId": "getPet",
+                        "security": [],
+                        "deprecated": true,
+                        "responses": {"200": {"description": "A pet"}, "404": {"description": "Missing"}}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {"Pet": {"type": "object"}},
+                "parameters": {"Limit": {"name": "limit", "in": "query", "schema": {"type": "integer"}}},
+                "securitySchemes": {
+                    "bearer": {"type": "http", "scheme": "bearer"},
+                    "api_key": {"type": "apiKey", "in": "header", "name": "X-API-Key"}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_summarizes_document() {
+        let result = inspect(petstore());
+        assert!(result.is_valid, "{:?}", result.issues);
+        assert!(result.issues.is_empty(), "{:?}", result.issues);
+        assert_eq!(result.openapi_version, "3.0.3");
+        assert_eq!(result.title.as_deref(), Some("Petstore"));
+        assert_eq!(result.api_version.as_deref(), Some("1.2.0"));
+        assert_eq!(result.servers, vec!["https://api.example.com/v1"]);
+        assert_eq!(result.schemas, vec!["Pet"]);
+        assert_eq!(result.operation_count, 3);
+
+        let list = &result.operations[0];
+        assert_eq!((list.method.as_str(), list.path.as_str()), ("GET", "/pets"));
+        assert_eq!(list.operation_id.as_deref(), Some("listPets"));
+        assert_eq!(list.tags, vec!["pets"]);
+        assert_eq!(
+            list.parameters,
+            vec![ParameterSummary {
+                name: "limit".to_string(),
+                location: "query".to_string(),
+                required: false
+            }]
+        );
+        assert_eq!(list.security, vec!["bearer"]);
+        assert_eq!(list.responses, vec!["200"]);
+
+        let create = &result.operations[1];
+        assert_eq!(create.method, "POST");
+        assert!(create.has_request_body);
+        assert_eq!(create.security, vec!["api_key"]);
+
+        let get = &result.operations[2];
+        assert_eq!(get.parameters[0].name, "id");
+        assert!(get.security.is_empty() && get.deprecated);
+    }
+
+    #[test]
+    fn test_security_schemes() {
+        let result = inspect(petstore());
+        assert_eq!(
+            result.security_schemes,
+            vec![
+                SecuritySchemeSummary {
+                    name: "api_key".to_string(),
+                    scheme_type: "apiKey".to_string(),
+                    detail: Some("header X-API-Key".to_string())
+                },
+                SecuritySchemeSummary {
+                    name: "bearer".to_string(),
+                    scheme_type: "http".to_string(),
+                    detail: Some("bearer".to_string())
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flags_common_issues() {
+        let mut document = petstore();
+        let pets = &mut document["paths"]["/pets"];
+        pets["get"].as_object_mut().unwrap().remove("operationId");
+        pets["post"]["operationId"] = json!("getPet");
+        pets["post"]["security"] = json!([{"oauth": []}]);
+        document["paths"]["/pets/{id}"]["get"]["responses"] =
+            json!({"404": {"description": "Missing"}});
+        document["paths"]["/owners/{ownerId}"] =
+            json!({"get": {"responses": {"200": {"description": "ok"}}}});
+        document["components"]["schemas"]["Owner"] =
+            json!({"$ref": "#/components/schemas/Missing"});
+
+        let result = inspect(document);
+        assert!(!result.is_valid);
+        assert_eq!(
+            messages(&result, "error"),
+            vec![
+                "Reference '#/components/schemas/Missing' does not resolve",
+                "Path parameter 'ownerId' is in the path but not declared",
+                "operationId 'getPet' is already used at #/paths/~1pets/post",
+                "Security scheme 'oauth' is not defined in components",
+            ]
+        );
+        let warnings = messages(&result, "warning");
+        assert!(warnings.contains(&"Operation has no operationId".to_string()));
+        assert!(warnings.contains(&"Operation has no 2xx or default response".to_string()));
+        assert!(warnings.contains(&"Schema 'Owner' is never referenced".to_string()));
+        // Errors are listed before warnings
+        assert_eq!(result.issues[0].severity, "error");
+        assert_eq!(result.issues[0].location, "#/components/schemas/Owner/$ref");
+    }
+
+    #[test]
+    fn test_parameter_checks() {
+        let document = json!({
+            "openapi": "3.1.0",
+            "info": {"title": "t", "version": "1"},
+            "paths": {
+                "/items/{id}": {
+                    "get": {
+                        "operationId": "getItem",
+                        "requestBody": {"content": {}},
+                        "parameters": [
+                            {"name": "id", "in": "path"},
+                            {"name": "q", "in": "query"},
+                            {"name": "q", "in": "query"},
+                            {"name": "extra", "in": "path", "required": true}
+                        ],
+                        "responses": {"default": {"description": "ok"}}
+                    }
+                }
+            }
+        });
+        let result = inspect(document);
+        assert_eq!(
+            messages(&result, "error"),
+            vec![
+                "Path parameter 'id' must be marked required: true",
+                "Parameter 'q' in query is declared twice",
+                "Path parameter 'extra' is declared but not in the path",
+            ]
+        );
+        assert_eq!(
+            messages(&result, "warning"),
+            vec!["GET request body has no defined meaning and is often dropped"]
+        );
+        assert_eq!(result.operations[0].parameters.len(), 3);
+    }
+
+    #[test]
+    fn test_minimal_and_rejected_documents() {
+        let result = inspect(json!({"openapi": "3.1.0", "info": {"title": "t", "version": "1"}}));
+        assert!(result.is_valid && result.operations.is_empty());
+
+        let result = inspect(json!({"openapi": "3.0.0", "info": {"title": "t"}}));
+        assert_eq!(
+            messages(&result, "error"),
+            vec![
+                "info needs both title and version",
+                "Document has no paths, which OpenAPI 3.0 requires"
+            ]
+        );
+
+        let error = |document: &str| {
+            inspect_openapi(OpenApiInspectInput {
+                document: document.to_string(),
+            })
+            .unwrap_err()
+        };
+        assert!(error(r#"{"swagger": "2.0"}"#).contains("Swagger 2.0"));
+        assert!(error(r#"{"openapi": "2.5"}"#).contains("expected 3.x"));
+        assert!(error(r#"{"info": {}}"#).contains("no openapi field"));
+        assert_eq!(error("[1, 2]"), "Document must be an object");
+    }
+}
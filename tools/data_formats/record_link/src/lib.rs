@@ -0,0 +1,74 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_COMPARISONS, MAX_RECORDS, RecordLinkInput as LogicInput, RecordLinkOutput as LogicOutput,
+    jaro_winkler, levenshtein_similarity, link_records, token_jaccard,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    FieldComparisonSchema as FieldComparison, FieldScoreSchema as FieldScore,
+    LinkedPairSchema as LinkedPair, RecordLinkOutputSchema as RecordLinkOutput,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RecordLinkInput {
+    #[serde(flatten)]
+    pub params: logic::RecordLinkInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Link records across two lists that describe the same entity, scoring field
+/// similarities Fellegi-Sunter style with optional blocking keys, and return matched
+/// pairs, pairs to review and the unmatched records
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn record_link(request: Request<RecordLinkInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("record_link", RecordLinkInput, RecordLinkOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("record_link", &input);
+    log.finish(run_record_link(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_record_link(input: RecordLinkInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("left", &input.params.left)
+        .array("right", &input.params.right)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::link_records(input.params.into()) {
+        Ok(result) => RecordLinkOutput::from(result),
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&result).unwrap_or_else(|e| format!("Serialization error: {e}")),
+    )
+}
@@ -0,0 +1,644 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Most records on each side
+pub const MAX_RECORDS: usize = 10_000;
+/// Most record pairs scored in one call
+pub const MAX_COMPARISONS: usize = 1_000_000;
+/// Most compared fields
+const MAX_FIELDS: usize = 50;
+/// Probability that a field agrees on a true match, by default
+const DEFAULT_M: f64 = 0.9;
+/// Probability that a field agrees on a non-match, by default
+const DEFAULT_U: f64 = 0.1;
+/// Similarity at which fuzzy comparators count a field as agreeing, by default
+const DEFAULT_AGREEMENT: f64 = 0.85;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FieldComparison {
+    /// Field to compare; dots reach into nested objects, e.g. "address.city"
+    pub field: String,
+    /// Field holding the value in right records (default: the same as field)
+    pub right_field: Option<String>,
+    /// "exact", "levenshtein", "jaro_winkler" or "jaccard" (word overlap)
+    /// (default: jaro_winkler)
+    pub comparator: Option<String>,
+    /// Similarity, 0-1, at or above which the field agrees (default: 0.85; exact always
+    /// needs 1)
+    pub agreement_threshold: Option<f64>,
+    /// Chance the field agrees when the records are the same entity (default: 0.9)
+    pub m: Option<f64>,
+    /// Chance the field agrees when they are not, lower than m (default: 0.1)
+    pub u: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RecordLinkInput {
+    /// First list of records, each a JSON object
+    pub left: Vec<Value>,
+    /// Second list of records, each a JSON object
+    pub right: Vec<Value>,
+    /// Fields to score pairs on
+    #[tool_types(nested)]
+    pub fields: Vec<FieldComparison>,
+    /// Only pairs sharing the value of at least one of these fields are compared,
+    /// ignoring case, spaces and punctuation (default: every pair)
+    pub blocking_keys: Option<Vec<String>>,
+    /// Score at or above which a pair is a match (default: half the highest possible
+    /// score)
+    pub match_threshold: Option<f64>,
+    /// Score at or above which a pair below match_threshold is reported for review
+    /// (default: 0)
+    pub possible_threshold: Option<f64>,
+    /// Field identifying records, copied into each pair (default: none)
+    pub id_field: Option<String>,
+    /// Link each record to at most one other, taking the best scores first (default: true)
+    pub one_to_one: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FieldScore {
+    pub field: String,
+    /// Similarity of the two values, 0-1; absent when either record lacks the field
+    pub similarity: Option<f64>,
+    /// Whether the similarity reached the agreement threshold
+    pub agrees: Option<bool>,
+    /// log2(m/u) when agreeing, log2((1-m)/(1-u)) when not, 0 when missing
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct LinkedPair {
+    /// Index into left
+    pub left_index: usize,
+    /// Index into right
+    pub right_index: usize,
+    pub left_id: Option<Value>,
+    pub right_id: Option<Value>,
+    /// Sum of the field weights
+    pub score: f64,
+    #[tool_types(nested)]
+    pub fields: Vec<FieldScore>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RecordLinkOutput {
+    /// Pairs scoring at or above match_threshold, best first
+    #[tool_types(nested)]
+    pub matches: Vec<LinkedPair>,
+    /// Pairs between possible_threshold and match_threshold, best first, for review
+    #[tool_types(nested)]
+    pub possible_matches: Vec<LinkedPair>,
+    /// Indices of left records in no match
+    pub unmatched_left: Vec<usize>,
+    /// Indices of right records in no match
+    pub unmatched_right: Vec<usize>,
+    /// Pairs scored after blocking
+    pub compared_pairs: usize,
+    /// Pairs without blocking
+    pub total_pairs: usize,
+    pub match_threshold: f64,
+    pub possible_threshold: f64,
+    /// Score of a pair agreeing on every field
+    pub max_score: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparator {
+    Exact,
+    Levenshtein,
+    JaroWinkler,
+    Jaccard,
+}
+
+impl Comparator {
+    fn parse(name: Option<&str>) -> Result<Comparator, String> {
+        match name.map(|n| n.trim().to_lowercase()).as_deref() {
+            Some("exact") => Ok(Comparator::Exact),
+            Some("levenshtein") => Ok(Comparator::Levenshtein),
+            None | Some("jaro_winkler") => Ok(Comparator::JaroWinkler),
+            Some("jaccard") => Ok(Comparator::Jaccard),
+            Some(other) => Err(format!(
+                "Unsupported comparator: {other}. Use 'exact', 'levenshtein', 'jaro_winkler' or 'jaccard'"
+            )),
+        }
+    }
+
+    fn similarity(self, a: &str, b: &str) -> f64 {
+        match self {
+            Comparator::Exact => f64::from(u8::from(a == b)),
+            Comparator::Levenshtein => levenshtein_similarity(a, b),
+            Comparator::JaroWinkler => jaro_winkler(a, b),
+            Comparator::Jaccard => token_jaccard(a, b),
+        }
+    }
+}
+
+/// A validated field comparison
+struct Field {
+    name: String,
+    left_path: Vec<String>,
+    right_path: Vec<String>,
+    comparator: Comparator,
+    threshold: f64,
+    agree_weight: f64,
+    disagree_weight: f64,
+}
+
+/// 1 minus the edit distance over the longer length
+pub fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Jaro similarity boosted by up to four shared leading characters
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_taken = vec![false; b.len()];
+    let mut a_matched = Vec::new();
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_taken[j] && b[j] == *ca) {
+            b_taken[j] = true;
+            a_matched.push(*ca);
+        }
+    }
+    if a_matched.is_empty() {
+        return 0.0;
+    }
+    let b_matched = b.iter().zip(&b_taken).filter(|(_, taken)| **taken);
+    let transpositions = a_matched
+        .iter()
+        .zip(b_matched)
+        .filter(|(x, (y, _))| x != y)
+        .count() as f64
+        / 2.0;
+    let m = a_matched.len() as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions) / m) / 3.0;
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// Shared words over all distinct words of the two values
+pub fn token_jaccard(a: &str, b: &str) -> f64 {
+    let words = |s: &str| -> HashSet<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+fn lookup<'v>(record: &'v Value, path: &[String]) -> Option<&'v Value> {
+    path.iter()
+        .try_fold(record, |value, key| value.as_object()?.get(key))
+}
+
+/// Comparable text of a field: lowercased with runs of whitespace collapsed, or None
+/// when the field is missing, null or blank
+fn field_text(record: &Value, path: &[String]) -> Option<String> {
+    let text = match lookup(record, path)? {
+        Value::String(s) => s.clone(),
+        Value::Null => return None,
+        other => other.to_string(),
+    };
+    let text = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Blocking value of a field: letters and digits only, lowercased
+fn block_value(record: &Value, path: &[String]) -> Option<String> {
+    let text: String = field_text(record, path)?
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+    (!text.is_empty()).then_some(text)
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('.').map(str::to_string).collect()
+}
+
+fn check_records(side: &str, records: &[Value]) -> Result<(), String> {
+    if records.len() > MAX_RECORDS {
+        return Err(format!("At most {MAX_RECORDS} {side} records are allowed"));
+    }
+    match records.iter().position(|r| !r.is_object()) {
+        Some(i) => Err(format!("{side}[{i}] must be a JSON object")),
+        None => Ok(()),
+    }
+}
+
+fn build_field(comparison: &FieldComparison) -> Result<Field, String> {
+    let name = comparison.field.trim();
+    if name.is_empty() {
+        return Err("Field names cannot be empty".to_string());
+    }
+    let comparator = Comparator::parse(comparison.comparator.as_deref())?;
+    let threshold = match comparator {
+        Comparator::Exact => 1.0,
+        _ => comparison.agreement_threshold.unwrap_or(DEFAULT_AGREEMENT),
+    };
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(format!(
+            "agreement_threshold for '{name}' must be between 0 and 1"
+        ));
+    }
+    let m = comparison.m.unwrap_or(DEFAULT_M);
+    let u = comparison.u.unwrap_or(DEFAULT_U);
+    if !(0.0 < u && u < m && m < 1.0) {
+        return Err(format!("m and u for '{name}' must satisfy 0 < u < m < 1"));
+    }
+    Ok(Field {
+        name: name.to_string(),
+        left_path: split_path(name),
+        right_path: split_path(comparison.right_field.as_deref().unwrap_or(name).trim()),
+        comparator,
+        threshold,
+        agree_weight: (m / u).log2(),
+        disagree_weight: ((1.0 - m) / (1.0 - u)).log2(),
+    })
+}
+
+/// Candidate pairs sharing a blocking value, or every pair without blocking keys
+fn candidate_pairs(
+    left: &[Value],
+    right: &[Value],
+    blocking_keys: &[Vec<String>],
+) -> Result<Vec<(usize, usize)>, String> {
+    let too_many = || {
+        format!("More than {MAX_COMPARISONS} record pairs to compare; add or narrow blocking_keys")
+    };
+    if blocking_keys.is_empty() {
+        if left.len().saturating_mul(right.len()) > MAX_COMPARISONS {
+            return Err(too_many());
+        }
+        return Ok((0..left.len())
+            .flat_map(|i| (0..right.len()).map(move |j| (i, j)))
+            .collect());
+    }
+    let mut pairs = HashSet::new();
+    for key in blocking_keys {
+        let mut blocks: HashMap<String, Vec<usize>> = HashMap::new();
+        for (j, record) in right.iter().enumerate() {
+            if let Some(value) = block_value(record, key) {
+                blocks.entry(value).or_default().push(j);
+            }
+        }
+        for (i, record) in left.iter().enumerate() {
+            let Some(block) = block_value(record, key).and_then(|v| blocks.get(&v)) else {
+                continue;
+            };
+            pairs.extend(block.iter().map(|&j| (i, j)));
+            if pairs.len() > MAX_COMPARISONS {
+                return Err(too_many());
+            }
+        }
+    }
+    let mut pairs: Vec<(usize, usize)> = pairs.into_iter().collect();
+    pairs.sort_unstable();
+    Ok(pairs)
+}
+
+fn score_pair(left: &Value, right: &Value, fields: &[Field]) -> (f64, Vec<FieldScore>) {
+    let scores: Vec<FieldScore> = fields
+        .iter()
+        .map(|field| {
+            let values =
+                field_text(left, &field.left_path).zip(field_text(right, &field.right_path));
+            let Some((a, b)) = values else {
+                // A missing value is no evidence either way
+                return FieldScore {
+                    field: field.name.clone(),
+                    similarity: None,
+                    agrees: None,
+                    weight: 0.0,
+                };
+            };
+            let similarity = field.comparator.similarity(&a, &b);
+            let agrees = similarity >= field.threshold;
+            FieldScore {
+                field: field.name.clone(),
+                similarity: Some(similarity),
+                agrees: Some(agrees),
+                weight: if agrees {
+                    field.agree_weight
+                } else {
+                    field.disagree_weight
+                },
+            }
+        })
+        .collect();
+    (scores.iter().map(|s| s.weight).sum(), scores)
+}
+
+pub fn link_records(input: RecordLinkInput) -> Result<RecordLinkOutput, String> {
+    check_records("left", &input.left)?;
+    check_records("right", &input.right)?;
+    if input.fields.is_empty() {
+        return Err("Give at least one field to compare".to_string());
+    }
+    if input.fields.len() > MAX_FIELDS {
+        return Err(format!("At most {MAX_FIELDS} fields can be compared"));
+    }
+    let fields = input
+        .fields
+        .iter()
+        .map(build_field)
+        .collect::<Result<Vec<_>, _>>()?;
+    let max_score: f64 = fields.iter().map(|f| f.agree_weight).sum();
+    let match_threshold = input.match_threshold.unwrap_or(max_score / 2.0);
+    let possible_threshold = input.possible_threshold.unwrap_or(0.0);
+    if !match_threshold.is_finite() || !possible_threshold.is_finite() {
+        return Err("Thresholds must be finite".to_string());
+    }
+    if possible_threshold > match_threshold {
+        return Err("possible_threshold cannot be above match_threshold".to_string());
+    }
+    let blocking_keys: Vec<Vec<String>> = input
+        .blocking_keys
+        .iter()
+        .flatten()
+        .map(|key| split_path(key.trim()))
+        .collect();
+    let id_path = input.id_field.as_deref().map(split_path);
+
+    let candidates = candidate_pairs(&input.left, &input.right, &blocking_keys)?;
+    let mut scored: Vec<LinkedPair> = candidates
+        .iter()
+        .filter_map(|&(i, j)| {
+            let (score, field_scores) = score_pair(&input.left[i], &input.right[j], &fields);
+            (score >= possible_threshold).then(|| LinkedPair {
+                left_index: i,
+                right_index: j,
+                left_id: id_path
+                    .as_ref()
+                    .and_then(|p| lookup(&input.left[i], p).cloned()),
+                right_id: id_path
+                    .as_ref()
+                    .and_then(|p| lookup(&input.right[j], p).cloned()),
+                score,
+                fields: field_scores,
+            })
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.left_index.cmp(&b.left_index))
+            .then(a.right_index.cmp(&b.right_index))
+    });
+
+    let one_to_one = input.one_to_one.unwrap_or(true);
+    let mut left_linked = vec![false; input.left.len()];
+    let mut right_linked = vec![false; input.right.len()];
+    let mut matches = Vec::new();
+    let mut possible_matches = Vec::new();
+    for pair in scored {
+        if pair.score >= match_threshold {
+            // Best scores claim their records first
+            if one_to_one && (left_linked[pair.left_index] || right_linked[pair.right_index]) {
+                continue;
+            }
+            left_linked[pair.left_index] = true;
+            right_linked[pair.right_index] = true;
+            matches.push(pair);
+        } else if !one_to_one || !(left_linked[pair.left_index] || right_linked[pair.right_index]) {
+            possible_matches.push(pair);
+        }
+    }
+    let unmatched = |linked: &[bool]| -> Vec<usize> {
+        linked
+            .iter()
+            .enumerate()
+            .filter(|(_, linked)| !**linked)
+            .map(|(i, _)| i)
+            .collect()
+    };
+
+    Ok(RecordLinkOutput {
+        unmatched_left: unmatched(&left_linked),
+        unmatched_right: unmatched(&right_linked),
+        matches,
+        possible_matches,
+        compared_pairs: candidates.len(),
+        total_pairs: input.left.len() * input.right.len(),
+        match_threshold,
+        possible_threshold,
+        max_score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(name: &str, comparator: &str) -> FieldComparison {
+        FieldComparison {
+            field: name.to_string(),
+            right_field: None,
+            comparator: Some(comparator.to_string()),
+            agreement_threshold: None,
+            m: None,
+            u: None,
+        }
+    }
+
+    fn input(left: Value, right: Value, fields: Vec<FieldComparison>) -> RecordLinkInput {
+        RecordLinkInput {
+            left: left.as_array().unwrap().clone(),
+            right: right.as_array().unwrap().clone(),
+            fields,
+            blocking_keys: None,
+            match_threshold: None,
+            possible_threshold: None,
+            id_field: None,
+            one_to_one: None,
+        }
+    }
+
+    fn people() -> RecordLinkInput {
+        let left = json!([
+            {"id": "L1", "name": "Jonathan Smith", "city": "Boston", "zip": "02118"},
+            {"id": "L2", "name": "Maria Garcia", "city": "Austin", "zip": "78701"},
+            {"id": "L3", "name": "Wei Chen", "city": "Seattle", "zip": "98101"}
+        ]);
+        let right = json!([
+            {"id": "R1", "name": "Maria Garcia", "city": "austin", "zip": "78701"},
+            {"id": "R2", "name": "Jonathon Smith", "city": "Boston", "zip": "02118"},
+            {"id": "R3", "name": "Ana Lopez", "city": "Denver", "zip": "80202"}
+        ]);
+        let mut input = input(
+            left,
+            right,
+            vec![
+                field("name", "jaro_winkler"),
+                field("city", "exact"),
+                field("zip", "exact"),
+            ],
+        );
+        input.id_field = Some("id".to_string());
+        input
+    }
+
+    #[test]
+    fn test_similarity_metrics() {
+        assert!((jaro_winkler("martha", "marhta") - 0.9611).abs() < 1e-4);
+        assert!((jaro_winkler("dwayne", "duane") - 0.84).abs() < 1e-4);
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+        assert!((levenshtein_similarity("kitten", "sitting") - (1.0 - 3.0 / 7.0)).abs() < 1e-12);
+        assert_eq!(levenshtein_similarity("", ""), 1.0);
+        assert!((token_jaccard("acme corp inc", "acme corp") - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_links_records_despite_typos_and_case() {
+        let result = link_records(people()).unwrap();
+        let pairs: Vec<(usize, usize)> = result
+            .matches
+            .iter()
+            .map(|p| (p.left_index, p.right_index))
+            .collect();
+        assert_eq!(pairs, vec![(0, 1), (1, 0)]);
+        assert_eq!(result.matches[0].left_id, Some(json!("L1")));
+        assert_eq!(result.matches[0].right_id, Some(json!("R2")));
+        // Both agree on all three fields at the default m and u
+        assert!((result.matches[1].score - 3.0 * 9f64.log2()).abs() < 1e-9);
+        assert_eq!(result.max_score, result.matches[0].score);
+        assert_eq!(result.unmatched_left, vec![2]);
+        assert_eq!(result.unmatched_right, vec![2]);
+        assert_eq!((result.compared_pairs, result.total_pairs), (9, 9));
+    }
+
+    #[test]
+    fn test_blocking_limits_comparisons() {
+        let mut input = people();
+        input.blocking_keys = Some(vec!["zip".to_string()]);
+        let result = link_records(input).unwrap();
+        assert_eq!(result.compared_pairs, 2);
+        assert_eq!(result.total_pairs, 9);
+        assert_eq!(result.matches.len(), 2);
+        assert!(result.possible_matches.is_empty());
+    }
+
+    #[test]
+    fn test_one_to_one_and_possible_matches() {
+        let left = json!([{"name": "Acme Corporation", "state": "NY"}]);
+        let right = json!([
+            {"name": "Acme Corporation", "state": "NY"},
+            {"name": "Acme Corporation", "state": "NJ"}
+        ]);
+        let fields = vec![field("name", "levenshtein"), field("state", "exact")];
+        let mut linked = input(left, right, fields);
+        linked.match_threshold = Some(2.0);
+        linked.possible_threshold = Some(-10.0);
+        linked.one_to_one = Some(false);
+        let result = link_records(linked.clone()).unwrap();
+        assert_eq!(result.matches.len(), 1);
+        // Agreeing on name, disagreeing on state scores zero at the default m and u
+        assert_eq!(result.possible_matches.len(), 1);
+        assert!(result.possible_matches[0].score.abs() < 1e-9);
+
+        // A record already linked is not offered again for review
+        linked.one_to_one = Some(true);
+        let result = link_records(linked).unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.possible_matches.is_empty());
+        assert_eq!(result.unmatched_right, vec![1]);
+    }
+
+    #[test]
+    fn test_missing_values_and_nested_fields() {
+        let left = json!([{"name": "Ann Lee", "address": {"city": "Reno"}, "phone": null}]);
+        let right = json!([{"full_name": "ann  lee", "address": {"city": "RENO"}}]);
+        let mut name = field("name", "exact");
+        name.right_field = Some("full_name".to_string());
+        let fields = vec![
+            name,
+            field("address.city", "exact"),
+            field("phone", "exact"),
+        ];
+        let result = link_records(input(left, right, fields)).unwrap();
+        let pair = &result.matches[0];
+        assert_eq!(pair.fields[0].agrees, Some(true));
+        assert_eq!(pair.fields[1].agrees, Some(true));
+        assert_eq!(pair.fields[2].similarity, None);
+        assert_eq!(pair.fields[2].weight, 0.0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |input: RecordLinkInput| link_records(input).unwrap_err();
+        let records = json!([{"a": 1}]);
+
+        assert!(
+            err(input(records.clone(), records.clone(), vec![])).contains("at least one field")
+        );
+        assert!(
+            err(input(
+                json!([1]),
+                records.clone(),
+                vec![field("a", "exact")]
+            ))
+            .contains("left[0]")
+        );
+        assert!(
+            err(input(
+                records.clone(),
+                records.clone(),
+                vec![field("a", "soundex")]
+            ))
+            .contains("Unsupported comparator")
+        );
+
+        let mut bad_weights = field("a", "exact");
+        bad_weights.m = Some(0.1);
+        bad_weights.u = Some(0.5);
+        assert!(
+            err(input(records.clone(), records.clone(), vec![bad_weights]))
+                .contains("0 < u < m < 1")
+        );
+
+        let mut thresholds = input(records.clone(), records, vec![field("a", "exact")]);
+        thresholds.match_threshold = Some(1.0);
+        thresholds.possible_threshold = Some(2.0);
+        assert!(err(thresholds).contains("possible_threshold"));
+    }
+}
@@ -1,5 +1,12 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,6 +16,7 @@ mod logic;
 // Re-export types from logic module
 pub use logic::{
     YamlFormatterInput as LogicInput, YamlFormatterResult as LogicOutput, YamlStats as LogicStats,
+    format_yaml,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -24,6 +32,8 @@ pub struct YamlFormatterInput {
     pub quote_all_strings: Option<bool>,
     /// Whether to sort keys alphabetically
     pub sort_keys: Option<bool>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -50,8 +60,32 @@ pub struct YamlStats {
     pub value_types: Vec<String>,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn yaml_formatter(input: YamlFormatterInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn yaml_formatter(request: Request<YamlFormatterInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "yaml_formatter",
+                YamlFormatterInput,
+                YamlFormatterResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("yaml_formatter", &input);
+    log.finish(run_yaml_formatter(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_yaml_formatter(input: YamlFormatterInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("content", &input.content).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         content: input.content,
@@ -81,6 +115,6 @@ pub fn yaml_formatter(input: YamlFormatterInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
+        ftl_json::to_string(&response).unwrap_or_else(|e| format!("Serialization error: {e}")),
     )
 }
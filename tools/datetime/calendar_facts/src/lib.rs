@@ -0,0 +1,231 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    CalendarFactsInput as LogicInput, CalendarFactsOutput as LogicOutput,
+    DateFacts as LogicDateFacts, Holiday as LogicHoliday, MonthFacts as LogicMonthFacts,
+    WeekdayCount as LogicWeekdayCount, calendar_facts_logic, days_in_month, is_leap_year,
+    orthodox_easter, western_easter,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CalendarFactsInput {
+    /// Year to describe, 1583-9999 (optional when `date` is given)
+    pub year: Option<i32>,
+    /// Month to describe in detail, 1-12 (optional)
+    pub month: Option<u32>,
+    /// Date to describe (YYYY-MM-DD); sets the year when `year` is left out
+    pub date: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CalendarFactsOutput {
+    pub year: i32,
+    pub is_leap_year: bool,
+    pub days_in_year: u32,
+    /// ISO 8601 weeks in the year, 52 or 53
+    pub iso_weeks_in_year: u32,
+    /// Day of the week of January 1, like "Monday"
+    pub first_weekday: String,
+    /// How often each weekday occurs in the year, Monday first
+    pub weekday_counts: Vec<WeekdayCount>,
+    /// Every month of the year
+    pub months: Vec<MonthFacts>,
+    /// The requested month, when `month` is given
+    pub month: Option<MonthFacts>,
+    /// Western (Gregorian) Easter Sunday (YYYY-MM-DD)
+    pub easter: String,
+    /// Orthodox (Julian computus) Easter Sunday, as a Gregorian date (YYYY-MM-DD)
+    pub orthodox_easter: String,
+    /// Western movable feasts that depend on Easter, from Ash Wednesday to Corpus
+    /// Christi, in date order
+    pub movable_holidays: Vec<Holiday>,
+    /// Facts about `date`, when given
+    pub date: Option<DateFacts>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WeekdayCount {
+    pub weekday: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MonthFacts {
+    /// Month number, 1-12
+    pub month: u32,
+    pub name: String,
+    pub days: u32,
+    /// Day of the week of the first day
+    pub first_weekday: String,
+    /// Day of the week of the last day
+    pub last_weekday: String,
+    /// Monday to Friday days in the month
+    pub weekdays: u32,
+    /// Saturdays and Sundays in the month
+    pub weekend_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Holiday {
+    pub name: String,
+    /// Date (YYYY-MM-DD)
+    pub date: String,
+    /// Day of the week
+    pub weekday: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DateFacts {
+    /// The date (YYYY-MM-DD)
+    pub date: String,
+    pub weekday: String,
+    /// Day of the year, starting at 1
+    pub day_of_year: u32,
+    /// Days left in the year after this one
+    pub days_remaining: u32,
+    /// ISO 8601 week number
+    pub iso_week: u32,
+    /// ISO 8601 week-numbering year, which differs from the calendar year near New Year
+    pub iso_year: i32,
+    /// Calendar quarter, 1-4
+    pub quarter: u32,
+    /// Which occurrence of its weekday in the month the date is, like 3 for the third
+    /// Thursday
+    pub weekday_of_month: u32,
+    /// Whether it is the last occurrence of its weekday in the month
+    pub is_last_weekday_of_month: bool,
+    /// Whether the date is a Saturday or Sunday
+    pub is_weekend: bool,
+}
+
+impl From<LogicMonthFacts> for MonthFacts {
+    fn from(m: LogicMonthFacts) -> Self {
+        MonthFacts {
+            month: m.month,
+            name: m.name,
+            days: m.days,
+            first_weekday: m.first_weekday,
+            last_weekday: m.last_weekday,
+            weekdays: m.weekdays,
+            weekend_days: m.weekend_days,
+        }
+    }
+}
+
+impl From<LogicDateFacts> for DateFacts {
+    fn from(d: LogicDateFacts) -> Self {
+        DateFacts {
+            date: d.date,
+            weekday: d.weekday,
+            day_of_year: d.day_of_year,
+            days_remaining: d.days_remaining,
+            iso_week: d.iso_week,
+            iso_year: d.iso_year,
+            quarter: d.quarter,
+            weekday_of_month: d.weekday_of_month,
+            is_last_weekday_of_month: d.is_last_weekday_of_month,
+            is_weekend: d.is_weekend,
+        }
+    }
+}
+
+/// Look up calendar facts for a year: leap-year status, days per month and year, ISO
+/// week count, weekday distribution, Easter and the holidays that move with it, plus
+/// details of a single month or date
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn calendar_facts(request: Request<CalendarFactsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "calendar_facts",
+                CalendarFactsInput,
+                CalendarFactsOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("calendar_facts", &input);
+    log.finish(run_calendar_facts(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_calendar_facts(input: CalendarFactsInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("date", input.date.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        year: input.year,
+        month: input.month,
+        date: input.date,
+    };
+
+    // Call logic implementation
+    let result = match logic::calendar_facts_logic(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = CalendarFactsOutput {
+        year: result.year,
+        is_leap_year: result.is_leap_year,
+        days_in_year: result.days_in_year,
+        iso_weeks_in_year: result.iso_weeks_in_year,
+        first_weekday: result.first_weekday,
+        weekday_counts: result
+            .weekday_counts
+            .into_iter()
+            .map(|c| WeekdayCount {
+                weekday: c.weekday,
+                count: c.count,
+            })
+            .collect(),
+        months: result.months.into_iter().map(MonthFacts::from).collect(),
+        month: result.month.map(MonthFacts::from),
+        easter: result.easter,
+        orthodox_easter: result.orthodox_easter,
+        movable_holidays: result
+            .movable_holidays
+            .into_iter()
+            .map(|h| Holiday {
+                name: h.name,
+                date: h.date,
+                weekday: h.weekday,
+            })
+            .collect(),
+        date: result.date.map(DateFacts::from),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
@@ -0,0 +1,452 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Years covered: the Gregorian calendar and its Easter computus start in 1583
+const MIN_YEAR: i32 = 1583;
+const MAX_YEAR: i32 = 9999;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// Western movable feasts, as days from Easter Sunday
+const EASTER_HOLIDAYS: &[(&str, i64)] = &[
+    ("Ash Wednesday", -46),
+    ("Palm Sunday", -7),
+    ("Maundy Thursday", -3),
+    ("Good Friday", -2),
+    ("Holy Saturday", -1),
+    ("Easter Sunday", 0),
+    ("Easter Monday", 1),
+    ("Ascension Day", 39),
+    ("Pentecost", 49),
+    ("Whit Monday", 50),
+    ("Trinity Sunday", 56),
+    ("Corpus Christi", 60),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarFactsInput {
+    /// Year to describe, 1583-9999 (optional when `date` is given)
+    pub year: Option<i32>,
+    /// Month to describe in detail, 1-12 (optional)
+    pub month: Option<u32>,
+    /// Date to describe (YYYY-MM-DD); sets the year when `year` is left out
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarFactsOutput {
+    pub year: i32,
+    pub is_leap_year: bool,
+    pub days_in_year: u32,
+    /// ISO 8601 weeks in the year, 52 or 53
+    pub iso_weeks_in_year: u32,
+    /// Day of the week of January 1, like "Monday"
+    pub first_weekday: String,
+    /// How often each weekday occurs in the year, Monday first
+    pub weekday_counts: Vec<WeekdayCount>,
+    /// Every month of the year
+    pub months: Vec<MonthFacts>,
+    /// The requested month, when `month` is given
+    pub month: Option<MonthFacts>,
+    /// Western (Gregorian) Easter Sunday (YYYY-MM-DD)
+    pub easter: String,
+    /// Orthodox (Julian computus) Easter Sunday, as a Gregorian date (YYYY-MM-DD)
+    pub orthodox_easter: String,
+    /// Western movable feasts that depend on Easter, in date order
+    pub movable_holidays: Vec<Holiday>,
+    /// Facts about `date`, when given
+    pub date: Option<DateFacts>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeekdayCount {
+    pub weekday: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonthFacts {
+    /// Month number, 1-12
+    pub month: u32,
+    pub name: String,
+    pub days: u32,
+    /// Day of the week of the first day
+    pub first_weekday: String,
+    /// Day of the week of the last day
+    pub last_weekday: String,
+    /// Monday to Friday days in the month
+    pub weekdays: u32,
+    /// Saturdays and Sundays in the month
+    pub weekend_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Holiday {
+    pub name: String,
+    /// Date (YYYY-MM-DD)
+    pub date: String,
+    /// Day of the week
+    pub weekday: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DateFacts {
+    /// The date (YYYY-MM-DD)
+    pub date: String,
+    pub weekday: String,
+    /// Day of the year, starting at 1
+    pub day_of_year: u32,
+    /// Days left in the year after this one
+    pub days_remaining: u32,
+    /// ISO 8601 week number
+    pub iso_week: u32,
+    /// ISO 8601 week-numbering year, which differs from the calendar year near New Year
+    pub iso_year: i32,
+    /// Calendar quarter, 1-4
+    pub quarter: u32,
+    /// Which occurrence of its weekday in the month the date is, like 3 for the third
+    /// Thursday
+    pub weekday_of_month: u32,
+    /// Whether it is the last occurrence of its weekday in the month
+    pub is_last_weekday_of_month: bool,
+    /// Whether the date is a Saturday or Sunday
+    pub is_weekend: bool,
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+fn is_weekend(weekday: Weekday) -> bool {
+    matches!(weekday, Weekday::Sat | Weekday::Sun)
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Gregorian Easter Sunday, by the anonymous (Meeus/Jones/Butcher) algorithm
+pub fn western_easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Orthodox Easter Sunday: the Julian computus (Meeus), moved to the Gregorian calendar
+pub fn orthodox_easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 4;
+    let b = year % 7;
+    let c = year % 19;
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34) % 7;
+    let month = (d + e + 114) / 31;
+    let day = (d + e + 114) % 31 + 1;
+    // Days the Julian calendar lags the Gregorian one in this century
+    let lag = year / 100 - year / 400 - 2;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)?
+        .checked_add_signed(Duration::days(lag as i64))
+}
+
+fn month_facts(year: i32, month: u32) -> Result<MonthFacts, String> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)
+        .ok_or_else(|| format!("Invalid month {month}. Expected 1-12"))?;
+    let days = days_in_month(year, month);
+    let last = first + Duration::days(days as i64 - 1);
+    let weekend_days = (0..days as i64)
+        .filter(|&offset| is_weekend((first + Duration::days(offset)).weekday()))
+        .count() as u32;
+    Ok(MonthFacts {
+        month,
+        name: MONTH_NAMES[month as usize - 1].to_string(),
+        days,
+        first_weekday: weekday_name(first.weekday()).to_string(),
+        last_weekday: weekday_name(last.weekday()).to_string(),
+        weekdays: days - weekend_days,
+        weekend_days,
+    })
+}
+
+fn date_facts(date: NaiveDate) -> DateFacts {
+    let year = date.year();
+    let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+    let days_left_in_month = days_in_month(year, date.month()) - date.day();
+    DateFacts {
+        date: format_date(date),
+        weekday: weekday_name(date.weekday()).to_string(),
+        day_of_year: date.ordinal(),
+        days_remaining: days_in_year - date.ordinal(),
+        iso_week: date.iso_week().week(),
+        iso_year: date.iso_week().year(),
+        quarter: (date.month() - 1) / 3 + 1,
+        weekday_of_month: (date.day() - 1) / 7 + 1,
+        is_last_weekday_of_month: days_left_in_month < 7,
+        is_weekend: is_weekend(date.weekday()),
+    }
+}
+
+pub fn calendar_facts_logic(input: CalendarFactsInput) -> Result<CalendarFactsOutput, String> {
+    let date = input
+        .date
+        .as_deref()
+        .map(|value| {
+            NaiveDate::parse_from_str(value.trim(), DATE_FORMAT)
+                .map_err(|_| format!("Invalid date '{value}'. Expected format YYYY-MM-DD"))
+        })
+        .transpose()?;
+    let year = match (input.year, date) {
+        (Some(year), _) => year,
+        (None, Some(date)) => date.year(),
+        (None, None) => return Err("Give a year or a date".to_string()),
+    };
+    if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+        return Err(format!(
+            "Year {year} is out of range. Supported years are {MIN_YEAR}-{MAX_YEAR}"
+        ));
+    }
+    if let Some(date) = date.filter(|d| d.year() != year) {
+        return Err(format!("Date {} is not in year {year}", format_date(date)));
+    }
+    let month = input
+        .month
+        .map(|month| month_facts(year, month))
+        .transpose()?;
+
+    let january_first = NaiveDate::from_ymd_opt(year, 1, 1)
+        .ok_or_else(|| format!("Year {year} is out of range"))?;
+    let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+    // The year has 53 ISO weeks when December 28 falls in week 53
+    let iso_weeks_in_year = NaiveDate::from_ymd_opt(year, 12, 28)
+        .map(|d| d.iso_week().week())
+        .unwrap_or(52);
+
+    // The weekdays of January 1 (and January 2 in a leap year) occur 53 times
+    let extra_days = days_in_year - 364;
+    let first_index = january_first.weekday().num_days_from_monday();
+    let weekday_counts = WEEKDAYS
+        .iter()
+        .map(|&weekday| {
+            let offset = (weekday.num_days_from_monday() + 7 - first_index) % 7;
+            WeekdayCount {
+                weekday: weekday_name(weekday).to_string(),
+                count: if offset < extra_days { 53 } else { 52 },
+            }
+        })
+        .collect();
+
+    let easter = western_easter(year).ok_or("Could not compute Easter")?;
+    let orthodox = orthodox_easter(year).ok_or("Could not compute Orthodox Easter")?;
+    let movable_holidays = EASTER_HOLIDAYS
+        .iter()
+        .map(|&(name, offset)| {
+            let day = easter + Duration::days(offset);
+            Holiday {
+                name: name.to_string(),
+                date: format_date(day),
+                weekday: weekday_name(day.weekday()).to_string(),
+            }
+        })
+        .collect();
+
+    Ok(CalendarFactsOutput {
+        year,
+        is_leap_year: is_leap_year(year),
+        days_in_year,
+        iso_weeks_in_year,
+        first_weekday: weekday_name(january_first.weekday()).to_string(),
+        weekday_counts,
+        months: (1..=12)
+            .map(|m| month_facts(year, m))
+            .collect::<Result<_, _>>()?,
+        month,
+        easter: format_date(easter),
+        orthodox_easter: format_date(orthodox),
+        movable_holidays,
+        date: date.map(date_facts),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts(year: Option<i32>, month: Option<u32>, date: Option<&str>) -> CalendarFactsOutput {
+        calendar_facts_logic(CalendarFactsInput {
+            year,
+            month,
+            date: date.map(str::to_string),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+
+        let result = facts(Some(2024), Some(2), None);
+        assert!(result.is_leap_year);
+        assert_eq!(result.days_in_year, 366);
+        let february = result.month.unwrap();
+        assert_eq!(february.name, "February");
+        assert_eq!(february.days, 29);
+        assert_eq!(february.first_weekday, "Thursday");
+        assert_eq!(february.weekend_days, 8);
+        assert_eq!(february.weekdays, 21);
+        assert_eq!(result.months.len(), 12);
+        assert_eq!(result.months[1], february);
+        assert_eq!(result.months.iter().map(|m| m.days).sum::<u32>(), 366);
+    }
+
+    #[test]
+    fn test_weeks_and_weekday_counts() {
+        // 2024 starts on a Monday and is a leap year
+        let result = facts(Some(2024), None, None);
+        assert_eq!(result.first_weekday, "Monday");
+        assert_eq!(result.iso_weeks_in_year, 52);
+        let counts: Vec<u32> = result.weekday_counts.iter().map(|c| c.count).collect();
+        assert_eq!(counts, [53, 53, 52, 52, 52, 52, 52]);
+
+        // 2026 starts on a Thursday, giving 53 ISO weeks
+        let result = facts(Some(2026), None, None);
+        assert_eq!(result.iso_weeks_in_year, 53);
+        assert_eq!(result.weekday_counts[3].weekday, "Thursday");
+        assert_eq!(result.weekday_counts[3].count, 53);
+        assert_eq!(
+            result.weekday_counts.iter().map(|c| c.count).sum::<u32>(),
+            365
+        );
+    }
+
+    #[test]
+    fn test_easter() {
+        let cases = [
+            (2019, "2019-04-21", "2019-04-28"),
+            (2024, "2024-03-31", "2024-05-05"),
+            (2025, "2025-04-20", "2025-04-20"),
+            (2038, "2038-04-25", "2038-04-25"),
+            (1818, "1818-03-22", "1818-04-26"),
+        ];
+        for (year, western, orthodox) in cases {
+            let result = facts(Some(year), None, None);
+            assert_eq!(result.easter, western, "{year}");
+            assert_eq!(result.orthodox_easter, orthodox, "{year}");
+        }
+
+        let result = facts(Some(2024), None, None);
+        let holiday = |name: &str| {
+            result
+                .movable_holidays
+                .iter()
+                .find(|h| h.name == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(holiday("Ash Wednesday").date, "2024-02-14");
+        assert_eq!(holiday("Good Friday").date, "2024-03-29");
+        assert_eq!(holiday("Ascension Day").date, "2024-05-09");
+        assert_eq!(holiday("Ascension Day").weekday, "Thursday");
+        assert_eq!(holiday("Whit Monday").date, "2024-05-20");
+    }
+
+    #[test]
+    fn test_date_facts() {
+        let result = facts(None, None, Some("2024-11-28"));
+        assert_eq!(result.year, 2024);
+        let date = result.date.unwrap();
+        assert_eq!(date.weekday, "Thursday");
+        assert_eq!(date.day_of_year, 333);
+        assert_eq!(date.days_remaining, 33);
+        assert_eq!(date.quarter, 4);
+        // US Thanksgiving: the fourth and last Thursday of November
+        assert_eq!(date.weekday_of_month, 4);
+        assert!(date.is_last_weekday_of_month);
+        assert!(!date.is_weekend);
+
+        // Early January can belong to the previous ISO year
+        let date = facts(None, None, Some("2021-01-03")).date.unwrap();
+        assert_eq!(date.iso_week, 53);
+        assert_eq!(date.iso_year, 2020);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |year: Option<i32>, month: Option<u32>, date: Option<&str>| {
+            calendar_facts_logic(CalendarFactsInput {
+                year,
+                month,
+                date: date.map(str::to_string),
+            })
+            .unwrap_err()
+        };
+        assert_eq!(err(None, None, None), "Give a year or a date");
+        assert!(err(Some(1200), None, None).contains("out of range"));
+        assert_eq!(
+            err(Some(2024), Some(13), None),
+            "Invalid month 13. Expected 1-12"
+        );
+        assert!(err(None, None, Some("2024-02-30")).contains("Invalid date"));
+        assert_eq!(
+            err(Some(2023), None, Some("2024-01-01")),
+            "Date 2024-01-01 is not in year 2023"
+        );
+    }
+}
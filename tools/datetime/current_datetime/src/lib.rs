@@ -3,15 +3,20 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
     CurrentDatetimeInput as LogicInput, CurrentDatetimeOutput as LogicOutput,
-    DateTimeComponents as LogicComponents,
+    DateTimeComponents as LogicComponents, get_current_datetime,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -23,6 +28,8 @@ pub struct CurrentDatetimeInput {
     /// Format for the output (optional)
     /// Options: "iso", "rfc2822", "rfc3339", "unix", "components"
     pub format: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -57,8 +64,27 @@ pub struct DateTimeComponents {
     pub week_of_year: u32,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn current_datetime(input: CurrentDatetimeInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn current_datetime(request: Request<CurrentDatetimeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "current_datetime",
+                CurrentDatetimeInput,
+                CurrentDatetimeOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("current_datetime", &input);
+    log.finish(run_current_datetime(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_current_datetime(input: CurrentDatetimeInput) -> ToolResponse {
     // Convert to logic types
     let logic_input = LogicInput {
         timezone: input.timezone,
@@ -94,7 +120,7 @@ pub fn current_datetime(input: CurrentDatetimeInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
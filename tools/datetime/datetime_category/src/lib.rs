@@ -0,0 +1,92 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+use ftl_guard::Guard;
+use ftl_introspect::{Request, describe};
+use ftl_log::ToolLog;
+use ftl_sdk::ToolResponse;
+
+#[cfg(not(test))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    DatetimeCategoryInput as LogicInput, DatetimeCategoryResult as LogicOutput, DatetimeOperation,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DatetimeCategoryInput {
+    /// Operation to perform: "current_datetime", "project_schedule", "humanize_duration",
+    /// "fiscal_period", "meeting_planner", "iso_interval_parse", "duration_aggregate",
+    /// "calendar_facts"
+    pub operation: String,
+    /// Arguments for the selected operation, using the same fields as the individual tool
+    /// (optional for operations without required fields)
+    pub input: Option<serde_json::Value>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DatetimeCategoryResult {
+    /// Operation that was run
+    pub operation: String,
+    /// Whether the operation succeeded
+    pub success: bool,
+    /// Operation result (present on success)
+    pub result: Option<serde_json::Value>,
+    /// Error message (present on failure)
+    pub error: Option<String>,
+}
+
+/// Run any date/time tool through a single operation-based interface with a
+/// consistent `{operation, success, result, error}` envelope
+#[cfg_attr(not(test), tool)]
+pub fn datetime_category(request: Request<DatetimeCategoryInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "datetime_category",
+                DatetimeCategoryInput,
+                DatetimeCategoryResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("datetime_category", &input);
+    log.finish(run_datetime_category(input))
+}
+
+fn run_datetime_category(input: DatetimeCategoryInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().json("input", &input.input).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        operation: input.operation,
+        input: input.input,
+    };
+
+    // Call logic implementation
+    let result = logic::dispatch(logic_input);
+
+    // Convert back to wrapper types
+    let output = DatetimeCategoryResult {
+        operation: result.operation,
+        success: result.success,
+        result: result.result,
+        error: result.error,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
@@ -0,0 +1,360 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatetimeCategoryInput {
+    /// Operation to perform
+    pub operation: String,
+    /// Arguments for the selected operation
+    pub input: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatetimeCategoryResult {
+    /// Operation that was run (normalized name)
+    pub operation: String,
+    /// Whether the operation succeeded
+    pub success: bool,
+    /// Operation result (present on success)
+    pub result: Option<Value>,
+    /// Error message (present on failure)
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatetimeOperation {
+    CurrentDatetime,
+    ProjectSchedule,
+    HumanizeDuration,
+    FiscalPeriod,
+    MeetingPlanner,
+    IsoIntervalParse,
+    DurationAggregate,
+    CalendarFacts,
+}
+
+impl DatetimeOperation {
+    pub const ALL: &'static [DatetimeOperation] = &[
+        DatetimeOperation::CurrentDatetime,
+        DatetimeOperation::ProjectSchedule,
+        DatetimeOperation::HumanizeDuration,
+        DatetimeOperation::FiscalPeriod,
+        DatetimeOperation::MeetingPlanner,
+        DatetimeOperation::IsoIntervalParse,
+        DatetimeOperation::DurationAggregate,
+        DatetimeOperation::CalendarFacts,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DatetimeOperation::CurrentDatetime => "current_datetime",
+            DatetimeOperation::ProjectSchedule => "project_schedule",
+            DatetimeOperation::HumanizeDuration => "humanize_duration",
+            DatetimeOperation::FiscalPeriod => "fiscal_period",
+            DatetimeOperation::MeetingPlanner => "meeting_planner",
+            DatetimeOperation::IsoIntervalParse => "iso_interval_parse",
+            DatetimeOperation::DurationAggregate => "duration_aggregate",
+            DatetimeOperation::CalendarFacts => "calendar_facts",
+        }
+    }
+}
+
+impl FromStr for DatetimeOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace('-', "_");
+        DatetimeOperation::ALL
+            .iter()
+            .copied()
+            .find(|op| op.name() == normalized)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = DatetimeOperation::ALL.iter().map(|op| op.name()).collect();
+                format!(
+                    "Unknown operation '{s}'. Valid operations: {}",
+                    valid.join(", ")
+                )
+            })
+    }
+}
+
+/// Run the requested operation, always returning a result envelope
+pub fn dispatch(input: DatetimeCategoryInput) -> DatetimeCategoryResult {
+    let operation = match input.operation.parse::<DatetimeOperation>() {
+        Ok(op) => op,
+        Err(e) => {
+            return DatetimeCategoryResult {
+                operation: input.operation,
+                success: false,
+                result: None,
+                error: Some(e),
+            };
+        }
+    };
+
+    // Operations without required fields accept a missing input
+    let args = input
+        .input
+        .unwrap_or_else(|| Value::Object(Default::default()));
+
+    let outcome = match operation {
+        DatetimeOperation::CurrentDatetime => {
+            parse_args::<current_datetime_tool::LogicInput>(operation, args)
+                .and_then(current_datetime_tool::get_current_datetime)
+                .and_then(to_value)
+        }
+        DatetimeOperation::ProjectSchedule => {
+            parse_args::<project_schedule_tool::LogicInput>(operation, args)
+                .and_then(project_schedule_tool::schedule_project)
+                .and_then(to_value)
+        }
+        DatetimeOperation::HumanizeDuration => {
+            parse_args::<humanize_duration_tool::LogicInput>(operation, args)
+                .and_then(humanize_duration_tool::convert_duration)
+                .and_then(to_value)
+        }
+        DatetimeOperation::FiscalPeriod => {
+            parse_args::<fiscal_period_tool::LogicInput>(operation, args)
+                .and_then(fiscal_period_tool::fiscal_period_logic)
+                .and_then(to_value)
+        }
+        DatetimeOperation::MeetingPlanner => {
+            parse_args::<meeting_planner_tool::LogicInput>(operation, args)
+                .and_then(meeting_planner_tool::plan_meeting)
+                .and_then(to_value)
+        }
+        DatetimeOperation::IsoIntervalParse => {
+            parse_args::<iso_interval_parse_tool::LogicInput>(operation, args)
+                .and_then(iso_interval_parse_tool::parse_interval)
+                .and_then(to_value)
+        }
+        DatetimeOperation::DurationAggregate => {
+            parse_args::<duration_aggregate_tool::LogicInput>(operation, args)
+                .and_then(duration_aggregate_tool::aggregate_durations)
+                .and_then(to_value)
+        }
+        DatetimeOperation::CalendarFacts => {
+            parse_args::<calendar_facts_tool::LogicInput>(operation, args)
+                .and_then(calendar_facts_tool::calendar_facts_logic)
+                .and_then(to_value)
+        }
+    };
+
+    match outcome {
+        Ok(result) => DatetimeCategoryResult {
+            operation: operation.name().to_string(),
+            success: true,
+            result: Some(result),
+            error: None,
+        },
+        Err(e) => DatetimeCategoryResult {
+            operation: operation.name().to_string(),
+            success: false,
+            result: None,
+            error: Some(e),
+        },
+    }
+}
+
+fn parse_args<T: for<'de> Deserialize<'de>>(
+    operation: DatetimeOperation,
+    args: Value,
+) -> Result<T, String> {
+    serde_json::from_value(args)
+        .map_err(|e| format!("Invalid input for '{}': {e}", operation.name()))
+}
+
+fn to_value<T: Serialize>(result: T) -> Result<Value, String> {
+    serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(operation: &str, args: Option<Value>) -> DatetimeCategoryResult {
+        dispatch(DatetimeCategoryInput {
+            operation: operation.to_string(),
+            input: args,
+        })
+    }
+
+    #[test]
+    fn test_operation_parsing() {
+        assert_eq!(
+            "current_datetime".parse::<DatetimeOperation>().unwrap(),
+            DatetimeOperation::CurrentDatetime
+        );
+        assert_eq!(
+            " Project-Schedule ".parse::<DatetimeOperation>().unwrap(),
+            DatetimeOperation::ProjectSchedule
+        );
+        assert!("tomorrow".parse::<DatetimeOperation>().is_err());
+    }
+
+    #[test]
+    fn test_current_datetime_without_input() {
+        let result = call("current_datetime", None);
+
+        assert!(result.success);
+        assert_eq!(result.operation, "current_datetime");
+        assert!(result.error.is_none());
+        let value = result.result.unwrap();
+        assert_eq!(value["timezone"], "UTC");
+        assert!(value["unix_timestamp"].as_i64().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_current_datetime_with_offset() {
+        let result = call("current_datetime", Some(json!({ "timezone": "+05:30" })));
+
+        assert!(result.success);
+        assert!(
+            result.result.unwrap()["iso"]
+                .as_str()
+                .unwrap()
+                .ends_with("+05:30")
+        );
+    }
+
+    #[test]
+    fn test_project_schedule() {
+        let result = call(
+            "project_schedule",
+            Some(json!({
+                "start_date": "2024-01-01",
+                "tasks": [
+                    { "id": "a", "duration_days": 2 },
+                    { "id": "b", "duration_days": 1, "depends_on": ["a"] }
+                ]
+            })),
+        );
+
+        assert!(result.success);
+        let value = result.result.unwrap();
+        assert_eq!(value["project_end"], "2024-01-03");
+        assert_eq!(value["critical_path"], json!(["a", "b"]));
+    }
+
+    #[test]
+    fn test_humanize_duration() {
+        let result = call("humanize_duration", Some(json!({ "seconds": 183600 })));
+        assert!(result.success);
+        let value = result.result.unwrap();
+        assert_eq!(value["text"], "2 days, 3 hours");
+        assert_eq!(value["iso"], "P2DT3H");
+
+        let result = call(
+            "humanize-duration",
+            Some(json!({ "operation": "parse", "text": "2 Tage und 3 Stunden", "locale": "de" })),
+        );
+        assert!(result.success);
+        assert_eq!(result.result.unwrap()["seconds"], 183600.0);
+    }
+
+    #[test]
+    fn test_fiscal_period() {
+        let result = call(
+            "fiscal_period",
+            Some(json!({ "date": "2024-02-15", "fiscal_year_start_month": 7 })),
+        );
+        assert!(result.success);
+        let value = result.result.unwrap();
+        assert_eq!(value["fiscal_year"]["label"], "FY2024");
+        assert_eq!(value["fiscal_quarter"]["label"], "FY2024-Q3");
+        assert_eq!(value["comparisons"]["previous_year"], "2023-02-15");
+    }
+
+    #[test]
+    fn test_meeting_planner() {
+        let result = call(
+            "meeting_planner",
+            Some(json!({
+                "participants": [
+                    { "name": "ana", "timezone": "America/New_York" },
+                    { "name": "ben", "timezone": "Europe/Berlin" }
+                ],
+                "start_date": "2024-01-10",
+                "duration_minutes": 60
+            })),
+        );
+        assert!(result.success);
+        let value = result.result.unwrap();
+        assert_eq!(value["total_slots"], 3);
+        assert_eq!(value["slots"][0]["start_utc"], "2024-01-10T14:00:00Z");
+    }
+
+    #[test]
+    fn test_iso_interval_parse() {
+        let result = call(
+            "iso-interval-parse",
+            Some(json!({ "interval": "R3/2024-01-01T09:00:00Z/P1W" })),
+        );
+        assert!(result.success);
+        let value = result.result.unwrap();
+        assert_eq!(value["repetitions"], 3);
+        assert_eq!(value["occurrences"][2]["start"], "2024-01-15T09:00:00Z");
+    }
+
+    #[test]
+    fn test_duration_aggregate() {
+        let result = call(
+            "duration_aggregate",
+            Some(json!({
+                "entries": [
+                    { "duration": "PT1H30M", "tag": "api" },
+                    { "start": "2024-03-04T09:00", "end": "2024-03-04T10:15", "tag": "docs" },
+                    { "duration": "PT45M", "tag": "api" }
+                ],
+                "group_by": "tag"
+            })),
+        );
+        assert!(result.success);
+        let value = result.result.unwrap();
+        assert_eq!(value["total"]["formatted"], "3:30:00");
+        assert_eq!(value["groups"][0]["key"], "api");
+        assert_eq!(value["groups"][0]["totals"]["iso"], "PT2H15M");
+    }
+
+    #[test]
+    fn test_calendar_facts() {
+        let result = call("calendar_facts", Some(json!({ "year": 2024, "month": 2 })));
+        assert!(result.success);
+        let value = result.result.unwrap();
+        assert_eq!(value["is_leap_year"], true);
+        assert_eq!(value["month"]["days"], 29);
+        assert_eq!(value["easter"], "2024-03-31");
+    }
+
+    #[test]
+    fn test_errors_use_envelope() {
+        let result = call(
+            "current_datetime",
+            Some(json!({ "timezone": "Mars/Olympus" })),
+        );
+        assert!(!result.success);
+        assert!(result.result.is_none());
+        assert!(result.error.unwrap().contains("Invalid timezone"));
+
+        let result = call("project_schedule", None);
+        assert!(!result.success);
+        assert!(
+            result
+                .error
+                .unwrap()
+                .contains("Invalid input for 'project_schedule'")
+        );
+    }
+
+    #[test]
+    fn test_unknown_operation() {
+        let result = call("sunrise", None);
+        assert!(!result.success);
+        assert_eq!(result.operation, "sunrise");
+        let error = result.error.unwrap();
+        assert!(error.contains("Unknown operation 'sunrise'"));
+        assert!(error.contains("current_datetime, project_schedule"));
+    }
+}
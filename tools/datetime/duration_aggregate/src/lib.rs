@@ -0,0 +1,179 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    DurationAggregateInput as LogicInput, DurationAggregateOutput as LogicOutput,
+    Group as LogicGroup, TimeEntry as LogicEntry, Totals as LogicTotals, aggregate_durations,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DurationAggregateInput {
+    /// Time entries, each with a duration or a start and end
+    pub entries: Vec<TimeEntry>,
+    /// How to group entries (optional, default: "none")
+    /// Options: "none", "day", "week" (ISO week), "month", "tag"
+    pub group_by: Option<String>,
+    /// Language of the `text` totals (optional, default: "en")
+    /// Options: "en", "de", "fr", "es"
+    pub locale: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TimeEntry {
+    /// ISO 8601 duration, like "PT1H30M"
+    pub duration: Option<String>,
+    /// Start time, RFC 3339 ("2024-03-01T09:00:00+01:00") or without an offset
+    /// ("2024-03-01T09:00")
+    pub start: Option<String>,
+    /// End time, in the same forms as `start`
+    pub end: Option<String>,
+    /// Day the entry belongs to (YYYY-MM-DD); needed to group duration-only entries by
+    /// day, week or month. Entries with a start count on the day they start
+    pub date: Option<String>,
+    /// Label to group by, like a project or task name
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DurationAggregateOutput {
+    /// Totals over all entries
+    pub total: Totals,
+    /// Grouping applied
+    pub group_by: String,
+    /// Totals per group, ordered by key; empty when not grouping
+    pub groups: Vec<Group>,
+    /// Length of each entry in seconds, in input order, ready for statistics tools
+    pub seconds: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Group {
+    /// Group key: "2024-03-01", "2024-W09", "2024-03" or the tag ("untagged" for
+    /// entries without one)
+    pub key: String,
+    pub totals: Totals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Totals {
+    /// Number of entries
+    pub count: usize,
+    pub total_seconds: f64,
+    pub mean_seconds: f64,
+    pub min_seconds: f64,
+    pub max_seconds: f64,
+    /// Total in hours, as used on timesheets
+    pub total_hours: f64,
+    /// Total as hours, minutes and seconds, like "37:30:00"
+    pub formatted: String,
+    /// Total as an ISO 8601 duration with hours as the largest unit, like "PT37H30M"
+    pub iso: String,
+    /// Total as a phrase rounded to two units, like "1 day, 14 hours"
+    pub text: String,
+}
+
+impl From<LogicTotals> for Totals {
+    fn from(t: LogicTotals) -> Self {
+        Totals {
+            count: t.count,
+            total_seconds: t.total_seconds,
+            mean_seconds: t.mean_seconds,
+            min_seconds: t.min_seconds,
+            max_seconds: t.max_seconds,
+            total_hours: t.total_hours,
+            formatted: t.formatted,
+            iso: t.iso,
+            text: t.text,
+        }
+    }
+}
+
+/// Sum, average and group time-tracking entries, given as ISO 8601 durations or
+/// start/end pairs, by day, ISO week, month or tag
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn duration_aggregate(request: Request<DurationAggregateInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "duration_aggregate",
+                DurationAggregateInput,
+                DurationAggregateOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("duration_aggregate", &input);
+    log.finish(run_duration_aggregate(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_duration_aggregate(input: DurationAggregateInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().array("entries", &input.entries).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        entries: input
+            .entries
+            .into_iter()
+            .map(|e| LogicEntry {
+                duration: e.duration,
+                start: e.start,
+                end: e.end,
+                date: e.date,
+                tag: e.tag,
+            })
+            .collect(),
+        group_by: input.group_by,
+        locale: input.locale,
+    };
+
+    // Call logic implementation
+    let result = match logic::aggregate_durations(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = DurationAggregateOutput {
+        total: result.total.into(),
+        group_by: result.group_by,
+        groups: result
+            .groups
+            .into_iter()
+            .map(|g| Group {
+                key: g.key,
+                totals: g.totals.into(),
+            })
+            .collect(),
+        seconds: result.seconds,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
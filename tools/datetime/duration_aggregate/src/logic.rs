@@ -0,0 +1,440 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime};
+use humanize_duration_tool::{LogicInput as HumanizeInput, convert_duration, parse_iso_duration};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Date-time layouts accepted besides RFC 3339, read without an offset
+const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+/// Most entries accepted in one request
+pub const MAX_ENTRIES: usize = 100_000;
+/// Group key for entries without a tag
+const UNTAGGED: &str = "untagged";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationAggregateInput {
+    /// Time entries, each with a duration or a start and end
+    pub entries: Vec<TimeEntry>,
+    /// How to group entries (optional, default: "none")
+    /// Options: "none", "day", "week" (ISO week), "month", "tag"
+    pub group_by: Option<String>,
+    /// Language of the `text` totals (optional, default: "en")
+    /// Options: "en", "de", "fr", "es"
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeEntry {
+    /// ISO 8601 duration, like "PT1H30M"
+    pub duration: Option<String>,
+    /// Start time, RFC 3339 ("2024-03-01T09:00:00+01:00") or without an offset
+    /// ("2024-03-01T09:00")
+    pub start: Option<String>,
+    /// End time, in the same forms as `start`
+    pub end: Option<String>,
+    /// Day the entry belongs to (YYYY-MM-DD); needed to group duration-only entries by
+    /// day, week or month
+    pub date: Option<String>,
+    /// Label to group by, like a project or task name
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationAggregateOutput {
+    /// Totals over all entries
+    pub total: Totals,
+    /// Grouping applied
+    pub group_by: String,
+    /// Totals per group, ordered by key; empty when not grouping
+    pub groups: Vec<Group>,
+    /// Length of each entry in seconds, in input order
+    pub seconds: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Group {
+    /// Group key: "2024-03-01", "2024-W09", "2024-03" or the tag
+    pub key: String,
+    pub totals: Totals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Totals {
+    /// Number of entries
+    pub count: usize,
+    pub total_seconds: f64,
+    pub mean_seconds: f64,
+    pub min_seconds: f64,
+    pub max_seconds: f64,
+    /// Total in hours, as used on timesheets
+    pub total_hours: f64,
+    /// Total as hours, minutes and seconds, like "37:30:00"
+    pub formatted: String,
+    /// Total as an ISO 8601 duration with hours as the largest unit, like "PT37H30M"
+    pub iso: String,
+    /// Total as a phrase, like "1 day, 14 hours"
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GroupBy {
+    None,
+    Day,
+    Week,
+    Month,
+    Tag,
+}
+
+impl GroupBy {
+    fn parse(value: Option<&str>) -> Result<GroupBy, String> {
+        match value.map(|v| v.trim().to_lowercase()).as_deref() {
+            None | Some("none") => Ok(GroupBy::None),
+            Some("day") => Ok(GroupBy::Day),
+            Some("week") => Ok(GroupBy::Week),
+            Some("month") => Ok(GroupBy::Month),
+            Some("tag") => Ok(GroupBy::Tag),
+            Some(other) => Err(format!(
+                "Invalid group_by '{other}'. Use 'none', 'day', 'week', 'month' or 'tag'"
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            GroupBy::None => "none",
+            GroupBy::Day => "day",
+            GroupBy::Week => "week",
+            GroupBy::Month => "month",
+            GroupBy::Tag => "tag",
+        }
+    }
+}
+
+/// A parsed time: local date-time plus the UTC timestamp when an offset was given
+struct Stamp {
+    local: NaiveDateTime,
+    timestamp: Option<i64>,
+}
+
+fn parse_datetime(value: &str) -> Result<Stamp, String> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Stamp {
+            local: datetime.naive_local(),
+            timestamp: Some(datetime.timestamp()),
+        });
+    }
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|local| Stamp {
+            local,
+            timestamp: None,
+        })
+        .ok_or_else(|| {
+            format!("Invalid date-time '{value}'. Use RFC 3339 or YYYY-MM-DDTHH:MM[:SS]")
+        })
+}
+
+/// Length of an entry in seconds and the day it is counted on
+fn measure(entry: &TimeEntry) -> Result<(f64, Option<NaiveDate>), String> {
+    let date = entry
+        .date
+        .as_deref()
+        .map(|value| {
+            NaiveDate::parse_from_str(value.trim(), DATE_FORMAT)
+                .map_err(|_| format!("Invalid date '{value}'. Use YYYY-MM-DD"))
+        })
+        .transpose()?;
+
+    match (&entry.duration, &entry.start, &entry.end) {
+        (Some(duration), None, None) => Ok((parse_iso_duration(duration)?, date)),
+        (None, Some(start), Some(end)) => {
+            let (start, end) = (parse_datetime(start)?, parse_datetime(end)?);
+            let seconds = match (start.timestamp, end.timestamp) {
+                (Some(from), Some(to)) => to - from,
+                _ => end.local.signed_duration_since(start.local).num_seconds(),
+            };
+            if seconds < 0 {
+                return Err("end is before start".to_string());
+            }
+            // Entries running past midnight count on the day they start
+            Ok((seconds as f64, date.or(Some(start.local.date()))))
+        }
+        (None, Some(_), None) | (None, None, Some(_)) => {
+            Err("needs both start and end".to_string())
+        }
+        (None, None, None) => Err("needs a duration or a start and end".to_string()),
+        (Some(_), _, _) => Err("give either a duration or a start and end, not both".to_string()),
+    }
+}
+
+/// Whole seconds as "H:MM:SS", with hours growing past 24
+fn format_clock(seconds: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Whole seconds as an ISO 8601 duration with hours as the largest unit
+fn format_iso(seconds: u64) -> String {
+    let (hours, minutes, secs) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    let mut iso = String::from("PT");
+    for (value, designator) in [(hours, 'H'), (minutes, 'M'), (secs, 'S')] {
+        if value > 0 {
+            iso.push_str(&format!("{value}{designator}"));
+        }
+    }
+    if iso == "PT" {
+        iso.push_str("0S");
+    }
+    iso
+}
+
+fn totals(durations: &[f64], locale: Option<&str>) -> Result<Totals, String> {
+    let total: f64 = durations.iter().sum();
+    let whole = total.round() as u64;
+    let text = convert_duration(HumanizeInput {
+        operation: None,
+        seconds: Some(total),
+        duration: None,
+        text: None,
+        locale: locale.map(str::to_string),
+        precision: None,
+        style: None,
+    })?
+    .text;
+    Ok(Totals {
+        count: durations.len(),
+        total_seconds: total,
+        mean_seconds: if durations.is_empty() {
+            0.0
+        } else {
+            total / durations.len() as f64
+        },
+        min_seconds: durations.iter().copied().reduce(f64::min).unwrap_or(0.0),
+        max_seconds: durations.iter().copied().reduce(f64::max).unwrap_or(0.0),
+        total_hours: total / 3600.0,
+        formatted: format_clock(whole),
+        iso: format_iso(whole),
+        text,
+    })
+}
+
+pub fn aggregate_durations(
+    input: DurationAggregateInput,
+) -> Result<DurationAggregateOutput, String> {
+    if input.entries.len() > MAX_ENTRIES {
+        return Err(format!("At most {MAX_ENTRIES} entries are supported"));
+    }
+    let group_by = GroupBy::parse(input.group_by.as_deref())?;
+    let locale = input.locale.as_deref();
+
+    let mut seconds = Vec::with_capacity(input.entries.len());
+    let mut groups: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for (index, entry) in input.entries.iter().enumerate() {
+        let (length, date) = measure(entry).map_err(|e| format!("Entry {index}: {e}"))?;
+        seconds.push(length);
+
+        let dated = |date: Option<NaiveDate>| {
+            date.ok_or_else(|| {
+                format!(
+                    "Entry {index}: needs a start or date to group by {}",
+                    group_by.name()
+                )
+            })
+        };
+        let key = match group_by {
+            GroupBy::None => continue,
+            GroupBy::Day => dated(date)?.format(DATE_FORMAT).to_string(),
+            GroupBy::Week => {
+                let week = dated(date)?.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            GroupBy::Month => {
+                let date = dated(date)?;
+                format!("{:04}-{:02}", date.year(), date.month())
+            }
+            GroupBy::Tag => match entry.tag.as_deref().map(str::trim) {
+                Some(tag) if !tag.is_empty() => tag.to_string(),
+                _ => UNTAGGED.to_string(),
+            },
+        };
+        groups.entry(key).or_default().push(length);
+    }
+
+    Ok(DurationAggregateOutput {
+        total: totals(&seconds, locale)?,
+        group_by: group_by.name().to_string(),
+        groups: groups
+            .into_iter()
+            .map(|(key, durations)| {
+                Ok(Group {
+                    key,
+                    totals: totals(&durations, locale)?,
+                })
+            })
+            .collect::<Result<_, String>>()?,
+        seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(duration: Option<&str>, span: Option<(&str, &str)>, tag: Option<&str>) -> TimeEntry {
+        TimeEntry {
+            duration: duration.map(str::to_string),
+            start: span.map(|(start, _)| start.to_string()),
+            end: span.map(|(_, end)| end.to_string()),
+            date: None,
+            tag: tag.map(str::to_string),
+        }
+    }
+
+    fn aggregate(
+        entries: Vec<TimeEntry>,
+        group_by: &str,
+    ) -> Result<DurationAggregateOutput, String> {
+        aggregate_durations(DurationAggregateInput {
+            entries,
+            group_by: Some(group_by.to_string()),
+            locale: None,
+        })
+    }
+
+    fn sample() -> Vec<TimeEntry> {
+        vec![
+            entry(
+                None,
+                Some(("2024-03-04T09:00", "2024-03-04T12:30")),
+                Some("api"),
+            ),
+            entry(
+                None,
+                Some(("2024-03-04T13:00:00Z", "2024-03-04T15:00:00Z")),
+                Some("docs"),
+            ),
+            entry(
+                None,
+                Some(("2024-03-11T23:00:00+01:00", "2024-03-12T01:15:00+01:00")),
+                Some("api"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_totals() {
+        let result = aggregate(sample(), "none").unwrap();
+        assert_eq!(result.seconds, vec![12_600.0, 7_200.0, 8_100.0]);
+        assert!(result.groups.is_empty());
+        let total = result.total;
+        assert_eq!(total.count, 3);
+        assert_eq!(total.total_seconds, 27_900.0);
+        assert_eq!(total.mean_seconds, 9_300.0);
+        assert_eq!(total.min_seconds, 7_200.0);
+        assert_eq!(total.max_seconds, 12_600.0);
+        assert_eq!(total.total_hours, 7.75);
+        assert_eq!(total.formatted, "7:45:00");
+        assert_eq!(total.iso, "PT7H45M");
+        assert_eq!(total.text, "7 hours, 45 minutes");
+    }
+
+    #[test]
+    fn test_group_by_day_and_week() {
+        let result = aggregate(sample(), "day").unwrap();
+        let keys: Vec<&str> = result.groups.iter().map(|g| g.key.as_str()).collect();
+        // The late entry counts on the day it starts, in its own offset
+        assert_eq!(keys, ["2024-03-04", "2024-03-11"]);
+        assert_eq!(result.groups[0].totals.total_seconds, 19_800.0);
+        assert_eq!(result.groups[0].totals.formatted, "5:30:00");
+
+        let result = aggregate(sample(), "week").unwrap();
+        let keys: Vec<&str> = result.groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, ["2024-W10", "2024-W11"]);
+        assert_eq!(result.groups[1].totals.count, 1);
+    }
+
+    #[test]
+    fn test_group_by_tag_with_durations() {
+        let mut entries = sample();
+        entries.push(entry(Some("PT45M"), None, None));
+        entries.push(entry(Some("P1DT2H"), None, Some("api")));
+        let result = aggregate(entries, "tag").unwrap();
+        let keys: Vec<&str> = result.groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, ["api", "docs", "untagged"]);
+        let api = &result.groups[0].totals;
+        assert_eq!(api.count, 3);
+        assert_eq!(api.total_seconds, 12_600.0 + 8_100.0 + 93_600.0);
+        // Hours keep growing past a day on timesheets
+        assert_eq!(api.formatted, "31:45:00");
+        assert_eq!(api.iso, "PT31H45M");
+        // The phrase is rounded to its two largest units
+        assert_eq!(api.text, "1 day, 8 hours");
+        assert_eq!(result.total.count, 5);
+    }
+
+    #[test]
+    fn test_duration_entries_need_a_date_for_calendar_groups() {
+        let err = aggregate(vec![entry(Some("PT1H"), None, None)], "week").unwrap_err();
+        assert_eq!(err, "Entry 0: needs a start or date to group by week");
+
+        let mut dated = entry(Some("PT1H"), None, None);
+        dated.date = Some("2024-02-29".to_string());
+        let result = aggregate(vec![dated], "month").unwrap();
+        assert_eq!(result.groups[0].key, "2024-02");
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        let err = |e: TimeEntry| aggregate(vec![e], "none").unwrap_err();
+        assert_eq!(
+            err(entry(None, None, None)),
+            "Entry 0: needs a duration or a start and end"
+        );
+        assert!(
+            err(entry(
+                Some("PT1H"),
+                Some(("2024-03-04T09:00", "2024-03-04T10:00")),
+                None
+            ))
+            .contains("not both")
+        );
+        assert_eq!(
+            err(entry(
+                None,
+                Some(("2024-03-04T10:00", "2024-03-04T09:00")),
+                None
+            )),
+            "Entry 0: end is before start"
+        );
+        assert!(
+            err(entry(None, Some(("yesterday", "2024-03-04T09:00")), None))
+                .contains("Invalid date-time 'yesterday'")
+        );
+        assert!(err(entry(Some("1 hour"), None, None)).contains("Invalid ISO 8601 duration"));
+        assert!(
+            aggregate(vec![], "hour")
+                .unwrap_err()
+                .contains("Invalid group_by")
+        );
+    }
+
+    #[test]
+    fn test_empty_entries() {
+        let result = aggregate(vec![], "tag").unwrap();
+        assert_eq!(result.total.count, 0);
+        assert_eq!(result.total.mean_seconds, 0.0);
+        assert_eq!(result.total.iso, "PT0S");
+        assert!(result.groups.is_empty());
+    }
+}
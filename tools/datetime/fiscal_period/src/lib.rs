@@ -0,0 +1,206 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    Comparisons as LogicComparisons, DateRange as LogicDateRange, FiscalPeriodInput as LogicInput,
+    FiscalPeriodOutput as LogicOutput, Period as LogicPeriod, fiscal_period_logic,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FiscalPeriodInput {
+    /// Date to classify (YYYY-MM-DD)
+    pub date: String,
+    /// Month the fiscal year starts in, 1-12 (optional, default: 1, the calendar year)
+    pub fiscal_year_start_month: Option<u32>,
+    /// How fiscal years are numbered (optional, default: "end")
+    /// Options: "end" (July 2024 - June 2025 is FY2025), "start" (it is FY2024)
+    pub fiscal_year_naming: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FiscalPeriodOutput {
+    /// The input date (YYYY-MM-DD)
+    pub date: String,
+    /// Day of the week, like "Monday"
+    pub weekday: String,
+    /// Day of the calendar year, starting at 1
+    pub day_of_year: u32,
+    /// ISO 8601 week (Monday to Sunday; week 1 holds the year's first Thursday)
+    pub iso_week: Period,
+    /// Calendar month
+    pub month: Period,
+    /// Calendar quarter
+    pub quarter: Period,
+    /// Fiscal year
+    pub fiscal_year: Period,
+    /// Fiscal quarter, counted from the fiscal year start
+    pub fiscal_quarter: Period,
+    /// Fiscal month, counted from the fiscal year start
+    pub fiscal_month: Period,
+    /// Matching dates and ranges for period-over-period comparisons
+    pub comparisons: Comparisons,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Period {
+    /// Label like "2024-W05", "2024-02", "2024-Q1", "FY2025", "FY2025-Q3" or "FY2025-P08"
+    pub label: String,
+    /// Year the period belongs to (ISO week-numbering year for weeks, fiscal year for
+    /// fiscal periods)
+    pub year: i32,
+    /// Week, month or quarter number within the year; 1 for a fiscal year
+    pub number: u32,
+    /// First day of the period (YYYY-MM-DD)
+    pub start: String,
+    /// Last day of the period (YYYY-MM-DD)
+    pub end: String,
+    /// Days in the period
+    pub days: i64,
+    /// Position of the date within the period, starting at 1
+    pub day_of_period: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DateRange {
+    pub start: String,
+    pub end: String,
+    /// Days in the range, inclusive
+    pub days: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Comparisons {
+    /// Same weekday one week earlier
+    pub previous_week: String,
+    /// Same day one month earlier, clamped to the month end
+    pub previous_month: String,
+    /// Same day three months earlier, clamped to the month end
+    pub previous_quarter: String,
+    /// Same date one year earlier (29 February maps to 28 February)
+    pub previous_year: String,
+    /// Same ISO weekday and week one ISO year earlier, for week-based reporting
+    pub previous_year_same_week: String,
+    /// Fiscal quarter start to the date
+    pub fiscal_quarter_to_date: DateRange,
+    /// The same span of the previous fiscal quarter
+    pub previous_fiscal_quarter_to_date: DateRange,
+    /// Fiscal year start to the date
+    pub fiscal_year_to_date: DateRange,
+    /// The same span of the previous fiscal year
+    pub previous_fiscal_year_to_date: DateRange,
+}
+
+impl From<LogicPeriod> for Period {
+    fn from(p: LogicPeriod) -> Self {
+        Period {
+            label: p.label,
+            year: p.year,
+            number: p.number,
+            start: p.start,
+            end: p.end,
+            days: p.days,
+            day_of_period: p.day_of_period,
+        }
+    }
+}
+
+impl From<LogicDateRange> for DateRange {
+    fn from(r: LogicDateRange) -> Self {
+        DateRange {
+            start: r.start,
+            end: r.end,
+            days: r.days,
+        }
+    }
+}
+
+/// Map a date to its ISO week, month, quarter and fiscal year, quarter and month with
+/// their boundaries, plus the matching dates and to-date ranges of earlier periods for
+/// period-over-period comparisons
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn fiscal_period(request: Request<FiscalPeriodInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "fiscal_period",
+                FiscalPeriodInput,
+                FiscalPeriodOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("fiscal_period", &input);
+    log.finish(run_fiscal_period(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_fiscal_period(input: FiscalPeriodInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("date", &input.date).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        date: input.date,
+        fiscal_year_start_month: input.fiscal_year_start_month,
+        fiscal_year_naming: input.fiscal_year_naming,
+    };
+
+    // Call logic implementation
+    let result = match logic::fiscal_period_logic(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let c = result.comparisons;
+    let output = FiscalPeriodOutput {
+        date: result.date,
+        weekday: result.weekday,
+        day_of_year: result.day_of_year,
+        iso_week: result.iso_week.into(),
+        month: result.month.into(),
+        quarter: result.quarter.into(),
+        fiscal_year: result.fiscal_year.into(),
+        fiscal_quarter: result.fiscal_quarter.into(),
+        fiscal_month: result.fiscal_month.into(),
+        comparisons: Comparisons {
+            previous_week: c.previous_week,
+            previous_month: c.previous_month,
+            previous_quarter: c.previous_quarter,
+            previous_year: c.previous_year,
+            previous_year_same_week: c.previous_year_same_week,
+            fiscal_quarter_to_date: c.fiscal_quarter_to_date.into(),
+            previous_fiscal_quarter_to_date: c.previous_fiscal_quarter_to_date.into(),
+            fiscal_year_to_date: c.fiscal_year_to_date.into(),
+            previous_fiscal_year_to_date: c.previous_fiscal_year_to_date.into(),
+        },
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
@@ -0,0 +1,421 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiscalPeriodInput {
+    /// Date to classify (YYYY-MM-DD)
+    pub date: String,
+    /// Month the fiscal year starts in, 1-12 (default: 1, the calendar year)
+    pub fiscal_year_start_month: Option<u32>,
+    /// How fiscal years are numbered (default: "end")
+    /// Options: "end" (by the calendar year the fiscal year ends in),
+    /// "start" (by the calendar year it starts in)
+    pub fiscal_year_naming: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FiscalPeriodOutput {
+    /// The input date (YYYY-MM-DD)
+    pub date: String,
+    /// Day of the week, like "Monday"
+    pub weekday: String,
+    /// Day of the calendar year, starting at 1
+    pub day_of_year: u32,
+    /// ISO 8601 week (Monday to Sunday; week 1 holds the year's first Thursday)
+    pub iso_week: Period,
+    /// Calendar month
+    pub month: Period,
+    /// Calendar quarter
+    pub quarter: Period,
+    /// Fiscal year
+    pub fiscal_year: Period,
+    /// Fiscal quarter, counted from the fiscal year start
+    pub fiscal_quarter: Period,
+    /// Fiscal month, counted from the fiscal year start
+    pub fiscal_month: Period,
+    /// Matching dates and ranges for period-over-period comparisons
+    pub comparisons: Comparisons,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Period {
+    /// Label like "2024-W05", "2024-02", "2024-Q1", "FY2025", "FY2025-Q3" or "FY2025-P08"
+    pub label: String,
+    /// Year the period belongs to (ISO week-numbering year for weeks, fiscal year for
+    /// fiscal periods)
+    pub year: i32,
+    /// Week, month or quarter number within the year; 1 for a fiscal year
+    pub number: u32,
+    /// First day of the period (YYYY-MM-DD)
+    pub start: String,
+    /// Last day of the period (YYYY-MM-DD)
+    pub end: String,
+    /// Days in the period
+    pub days: i64,
+    /// Position of the date within the period, starting at 1
+    pub day_of_period: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DateRange {
+    pub start: String,
+    pub end: String,
+    /// Days in the range, inclusive
+    pub days: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Comparisons {
+    /// Same weekday one week earlier
+    pub previous_week: String,
+    /// Same day one month earlier, clamped to the month end
+    pub previous_month: String,
+    /// Same day three months earlier, clamped to the month end
+    pub previous_quarter: String,
+    /// Same date one year earlier (29 February maps to 28 February)
+    pub previous_year: String,
+    /// Same ISO weekday and week one ISO year earlier, for week-based reporting
+    /// (week 53 maps to week 52 when the earlier year has no week 53)
+    pub previous_year_same_week: String,
+    /// Fiscal quarter start to the date
+    pub fiscal_quarter_to_date: DateRange,
+    /// The same span of the previous fiscal quarter
+    pub previous_fiscal_quarter_to_date: DateRange,
+    /// Fiscal year start to the date
+    pub fiscal_year_to_date: DateRange,
+    /// The same span of the previous fiscal year
+    pub previous_fiscal_year_to_date: DateRange,
+}
+
+fn parse_date(value: &str, field: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), DATE_FORMAT)
+        .map_err(|_| format!("Invalid {field} '{value}'. Expected format YYYY-MM-DD"))
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// First day of the month `months` months after the start of `year`-`month`
+fn month_start(year: i32, month: u32, months: u32) -> Result<NaiveDate, String> {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|d| d.checked_add_months(Months::new(months)))
+        .ok_or_else(|| "Date is out of range".to_string())
+}
+
+fn shift_back(date: NaiveDate, months: u32) -> Result<NaiveDate, String> {
+    date.checked_sub_months(Months::new(months))
+        .ok_or_else(|| "Date is out of range".to_string())
+}
+
+fn period(
+    label: String,
+    year: i32,
+    number: u32,
+    start: NaiveDate,
+    next_start: NaiveDate,
+    date: NaiveDate,
+) -> Period {
+    Period {
+        label,
+        year,
+        number,
+        start: format_date(start),
+        end: format_date(next_start - Duration::days(1)),
+        days: (next_start - start).num_days(),
+        day_of_period: (date - start).num_days() + 1,
+    }
+}
+
+fn range(start: NaiveDate, end: NaiveDate) -> DateRange {
+    DateRange {
+        start: format_date(start),
+        end: format_date(end),
+        days: (end - start).num_days() + 1,
+    }
+}
+
+pub fn fiscal_period_logic(input: FiscalPeriodInput) -> Result<FiscalPeriodOutput, String> {
+    let date = parse_date(&input.date, "date")?;
+    let start_month = input.fiscal_year_start_month.unwrap_or(1);
+    if !(1..=12).contains(&start_month) {
+        return Err(format!(
+            "fiscal_year_start_month must be between 1 and 12, got {start_month}"
+        ));
+    }
+    let name_by_end = match input
+        .fiscal_year_naming
+        .as_deref()
+        .map(|s| s.trim().to_lowercase())
+    {
+        None => true,
+        Some(s) if s == "end" => true,
+        Some(s) if s == "start" => false,
+        Some(s) => {
+            return Err(format!(
+                "Invalid fiscal_year_naming '{s}'. Use 'end' or 'start'"
+            ));
+        }
+    };
+
+    let (year, month) = (date.year(), date.month());
+
+    // ISO week
+    let iso = date.iso_week();
+    let week_start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    let iso_week = period(
+        format!("{}-W{:02}", iso.year(), iso.week()),
+        iso.year(),
+        iso.week(),
+        week_start,
+        week_start + Duration::days(7),
+        date,
+    );
+
+    // Calendar month and quarter
+    let month_first = month_start(year, month, 0)?;
+    let calendar_month = period(
+        format!("{year}-{month:02}"),
+        year,
+        month,
+        month_first,
+        month_start(year, month, 1)?,
+        date,
+    );
+    let quarter_number = (month - 1) / 3 + 1;
+    let quarter_first = month_start(year, (quarter_number - 1) * 3 + 1, 0)?;
+    let quarter = period(
+        format!("{year}-Q{quarter_number}"),
+        year,
+        quarter_number,
+        quarter_first,
+        month_start(year, (quarter_number - 1) * 3 + 1, 3)?,
+        date,
+    );
+
+    // Fiscal year, quarter and month, counted from the fiscal start month
+    let start_year = if month >= start_month { year } else { year - 1 };
+    let fiscal_year_number = if name_by_end && start_month > 1 {
+        start_year + 1
+    } else {
+        start_year
+    };
+    let fiscal_month_index = (month + 12 - start_month) % 12;
+    let fiscal_quarter_index = fiscal_month_index / 3;
+
+    let fiscal_first = month_start(start_year, start_month, 0)?;
+    let fiscal_year = period(
+        format!("FY{fiscal_year_number}"),
+        fiscal_year_number,
+        1,
+        fiscal_first,
+        month_start(start_year, start_month, 12)?,
+        date,
+    );
+    let fiscal_quarter_first = month_start(start_year, start_month, fiscal_quarter_index * 3)?;
+    let fiscal_quarter = period(
+        format!("FY{fiscal_year_number}-Q{}", fiscal_quarter_index + 1),
+        fiscal_year_number,
+        fiscal_quarter_index + 1,
+        fiscal_quarter_first,
+        month_start(start_year, start_month, fiscal_quarter_index * 3 + 3)?,
+        date,
+    );
+    let fiscal_month = period(
+        format!("FY{fiscal_year_number}-P{:02}", fiscal_month_index + 1),
+        fiscal_year_number,
+        fiscal_month_index + 1,
+        month_first,
+        month_start(year, month, 1)?,
+        date,
+    );
+
+    // Period-over-period alignment
+    let previous_year = shift_back(date, 12)?;
+    let previous_iso_year = iso.year() - 1;
+    let weeks_in_previous = NaiveDate::from_ymd_opt(previous_iso_year, 12, 28)
+        .map(|d| d.iso_week().week())
+        .ok_or_else(|| "Date is out of range".to_string())?;
+    let previous_year_same_week = NaiveDate::from_isoywd_opt(
+        previous_iso_year,
+        iso.week().min(weeks_in_previous),
+        date.weekday(),
+    )
+    .ok_or_else(|| "Date is out of range".to_string())?;
+    let previous_quarter = shift_back(date, 3)?;
+
+    let comparisons = Comparisons {
+        previous_week: format_date(date - Duration::days(7)),
+        previous_month: format_date(shift_back(date, 1)?),
+        previous_quarter: format_date(previous_quarter),
+        previous_year: format_date(previous_year),
+        previous_year_same_week: format_date(previous_year_same_week),
+        fiscal_quarter_to_date: range(fiscal_quarter_first, date),
+        previous_fiscal_quarter_to_date: range(
+            shift_back(fiscal_quarter_first, 3)?,
+            previous_quarter,
+        ),
+        fiscal_year_to_date: range(fiscal_first, date),
+        previous_fiscal_year_to_date: range(shift_back(fiscal_first, 12)?, previous_year),
+    };
+
+    Ok(FiscalPeriodOutput {
+        date: format_date(date),
+        weekday: weekday_name(date.weekday()).to_string(),
+        day_of_year: date.ordinal(),
+        iso_week,
+        month: calendar_month,
+        quarter,
+        fiscal_year,
+        fiscal_quarter,
+        fiscal_month,
+        comparisons,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(date: &str, start_month: Option<u32>, naming: Option<&str>) -> FiscalPeriodOutput {
+        fiscal_period_logic(FiscalPeriodInput {
+            date: date.to_string(),
+            fiscal_year_start_month: start_month,
+            fiscal_year_naming: naming.map(str::to_string),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_calendar_periods() {
+        let result = run("2024-02-29", None, None);
+        assert_eq!(result.weekday, "Thursday");
+        assert_eq!(result.day_of_year, 60);
+
+        assert_eq!(result.iso_week.label, "2024-W09");
+        assert_eq!(result.iso_week.start, "2024-02-26");
+        assert_eq!(result.iso_week.end, "2024-03-03");
+        assert_eq!(result.iso_week.day_of_period, 4);
+
+        assert_eq!(result.month.label, "2024-02");
+        assert_eq!(result.month.end, "2024-02-29");
+        assert_eq!(result.month.days, 29);
+
+        assert_eq!(result.quarter.label, "2024-Q1");
+        assert_eq!(result.quarter.start, "2024-01-01");
+        assert_eq!(result.quarter.end, "2024-03-31");
+        assert_eq!(result.quarter.days, 91);
+        assert_eq!(result.quarter.day_of_period, 60);
+
+        // With the default fiscal year, fiscal periods follow the calendar
+        assert_eq!(result.fiscal_year.label, "FY2024");
+        assert_eq!(result.fiscal_year.days, 366);
+        assert_eq!(result.fiscal_quarter.label, "FY2024-Q1");
+        assert_eq!(result.fiscal_month.label, "FY2024-P02");
+    }
+
+    #[test]
+    fn test_iso_week_across_year_boundary() {
+        // 2024-12-30 is a Monday in week 1 of ISO year 2025
+        let result = run("2024-12-30", None, None);
+        assert_eq!(result.iso_week.label, "2025-W01");
+        assert_eq!(result.iso_week.year, 2025);
+        assert_eq!(result.iso_week.end, "2025-01-05");
+
+        // 2021-01-03 is a Sunday in week 53 of ISO year 2020
+        let result = run("2021-01-03", None, None);
+        assert_eq!(result.iso_week.label, "2020-W53");
+        assert_eq!(result.iso_week.start, "2020-12-28");
+    }
+
+    #[test]
+    fn test_custom_fiscal_year() {
+        // Fiscal year starting in July, named by the year it ends in
+        let result = run("2024-02-15", Some(7), None);
+        assert_eq!(result.fiscal_year.label, "FY2024");
+        assert_eq!(result.fiscal_year.start, "2023-07-01");
+        assert_eq!(result.fiscal_year.end, "2024-06-30");
+        assert_eq!(result.fiscal_year.days, 366);
+        assert_eq!(result.fiscal_quarter.label, "FY2024-Q3");
+        assert_eq!(result.fiscal_quarter.start, "2024-01-01");
+        assert_eq!(result.fiscal_quarter.end, "2024-03-31");
+        assert_eq!(result.fiscal_month.label, "FY2024-P08");
+        assert_eq!(result.fiscal_month.number, 8);
+
+        // Named by the start year instead
+        let result = run("2024-08-01", Some(7), Some("start"));
+        assert_eq!(result.fiscal_year.label, "FY2024");
+        assert_eq!(result.fiscal_year.start, "2024-07-01");
+        assert_eq!(result.fiscal_quarter.label, "FY2024-Q1");
+        assert_eq!(result.fiscal_quarter.day_of_period, 32);
+
+        let result = run("2024-08-01", Some(7), Some("end"));
+        assert_eq!(result.fiscal_year.label, "FY2025");
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let result = run("2024-05-31", Some(4), None);
+        let c = &result.comparisons;
+        assert_eq!(c.previous_week, "2024-05-24");
+        assert_eq!(c.previous_month, "2024-04-30");
+        assert_eq!(c.previous_quarter, "2024-02-29");
+        assert_eq!(c.previous_year, "2023-05-31");
+        // Friday of week 22 in 2023
+        assert_eq!(c.previous_year_same_week, "2023-06-02");
+
+        // Fiscal quarter April-June
+        assert_eq!(
+            c.fiscal_quarter_to_date,
+            DateRange {
+                start: "2024-04-01".to_string(),
+                end: "2024-05-31".to_string(),
+                days: 61
+            }
+        );
+        assert_eq!(c.previous_fiscal_quarter_to_date.start, "2024-01-01");
+        assert_eq!(c.previous_fiscal_quarter_to_date.end, "2024-02-29");
+        assert_eq!(c.fiscal_year_to_date.start, "2024-04-01");
+        assert_eq!(c.previous_fiscal_year_to_date.start, "2023-04-01");
+        assert_eq!(c.previous_fiscal_year_to_date.end, "2023-05-31");
+        assert_eq!(c.previous_fiscal_year_to_date.days, 61);
+
+        // Leap day and week 53 fall back to the nearest equivalent
+        let result = run("2024-02-29", None, None);
+        assert_eq!(result.comparisons.previous_year, "2023-02-28");
+        let result = run("2020-12-31", None, None);
+        assert_eq!(result.iso_week.label, "2020-W53");
+        assert_eq!(result.comparisons.previous_year_same_week, "2019-12-26");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |date: &str, start: Option<u32>, naming: Option<&str>| {
+            fiscal_period_logic(FiscalPeriodInput {
+                date: date.to_string(),
+                fiscal_year_start_month: start,
+                fiscal_year_naming: naming.map(str::to_string),
+            })
+            .unwrap_err()
+        };
+        assert!(err("2024-13-01", None, None).contains("Invalid date"));
+        assert!(err("next friday", None, None).contains("YYYY-MM-DD"));
+        assert!(err("2024-01-01", Some(0), None).contains("between 1 and 12"));
+        assert!(err("2024-01-01", Some(13), None).contains("between 1 and 12"));
+        assert!(err("2024-01-01", None, Some("middle")).contains("fiscal_year_naming"));
+    }
+}
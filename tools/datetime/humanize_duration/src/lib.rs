@@ -0,0 +1,139 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    DurationPart as LogicPart, HumanizeDurationInput as LogicInput,
+    HumanizeDurationOutput as LogicOutput, convert_duration, parse_iso_duration, parse_phrase,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HumanizeDurationInput {
+    /// Operation to perform (optional, default: "humanize")
+    /// Options: "humanize" (seconds or ISO 8601 duration to a phrase),
+    /// "parse" (phrase back to seconds)
+    pub operation: Option<String>,
+    /// Duration in seconds to humanize
+    pub seconds: Option<f64>,
+    /// ISO 8601 duration to humanize, like "P2DT3H" or "PT90M"
+    pub duration: Option<String>,
+    /// Phrase to parse, like "2 days, 3 hours", "1.5 hours" or "1h30m"
+    pub text: Option<String>,
+    /// Language of the phrase (optional, default: "en")
+    /// Options: "en", "de", "fr", "es"; English units are also understood when parsing
+    pub locale: Option<String>,
+    /// Most units to show, counted from the largest one (optional, default: 2)
+    pub precision: Option<u32>,
+    /// Phrase style (optional, default: "long")
+    /// Options: "long" ("2 days, 3 hours"), "short" ("2d 3h")
+    pub style: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HumanizeDurationOutput {
+    /// Exact duration in seconds (years count as 365 days, months as 30)
+    pub seconds: f64,
+    /// Duration as a phrase, rounded to the smallest unit shown
+    pub text: String,
+    /// Exact duration in ISO 8601 form, using days as the largest unit
+    pub iso: String,
+    /// Units in the phrase, largest first
+    pub parts: Vec<DurationPart>,
+    /// Locale used for the phrase
+    pub locale: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DurationPart {
+    /// Unit name: "years", "months", "days", "hours", "minutes" or "seconds"
+    pub unit: String,
+    pub value: u64,
+}
+
+/// Turn seconds or an ISO 8601 duration into a phrase like "2 days, 3 hours" in
+/// several languages, or parse such a phrase back into seconds
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn humanize_duration(request: Request<HumanizeDurationInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "humanize_duration",
+                HumanizeDurationInput,
+                HumanizeDurationOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("humanize_duration", &input);
+    log.finish(run_humanize_duration(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_humanize_duration(input: HumanizeDurationInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("duration", input.duration.as_deref().unwrap_or_default())
+        .string("text", input.text.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        operation: input.operation,
+        seconds: input.seconds,
+        duration: input.duration,
+        text: input.text,
+        locale: input.locale,
+        precision: input.precision,
+        style: input.style,
+    };
+
+    // Call logic implementation
+    let result = match logic::convert_duration(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = HumanizeDurationOutput {
+        seconds: result.seconds,
+        text: result.text,
+        iso: result.iso,
+        parts: result
+            .parts
+            .into_iter()
+            .map(|p| DurationPart {
+                unit: p.unit,
+                value: p.value,
+            })
+            .collect(),
+        locale: result.locale,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
@@ -0,0 +1,762 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest duration accepted, in seconds (about 31.7 million years)
+pub const MAX_SECONDS: f64 = 1e15;
+
+const DEFAULT_PRECISION: usize = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumanizeDurationInput {
+    /// Operation to perform (optional, default: "humanize")
+    /// Options: "humanize", "parse"
+    pub operation: Option<String>,
+    /// Duration in seconds to humanize
+    pub seconds: Option<f64>,
+    /// ISO 8601 duration to humanize, like "P2DT3H" or "PT90M"
+    pub duration: Option<String>,
+    /// Phrase to parse back into seconds, like "2 days, 3 hours" or "1h 30m"
+    pub text: Option<String>,
+    /// Language of the phrase (optional, default: "en")
+    /// Options: "en", "de", "fr", "es"
+    pub locale: Option<String>,
+    /// Most units to show, counted from the largest one (optional, default: 2)
+    pub precision: Option<u32>,
+    /// Phrase style (optional, default: "long")
+    /// Options: "long" ("2 days, 3 hours"), "short" ("2d 3h")
+    pub style: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumanizeDurationOutput {
+    /// Exact duration in seconds
+    pub seconds: f64,
+    /// Duration as a phrase, rounded to the smallest unit shown
+    pub text: String,
+    /// Exact duration in ISO 8601 form, using days as the largest unit
+    pub iso: String,
+    /// Units in the phrase, largest first
+    pub parts: Vec<DurationPart>,
+    /// Locale used for the phrase
+    pub locale: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DurationPart {
+    /// Unit name: "years", "months", "weeks", "days", "hours", "minutes" or "seconds"
+    pub unit: String,
+    pub value: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl Unit {
+    /// Units used when humanizing. Weeks are left out so "10 days" stays as it is.
+    const HUMANIZE: [Unit; 6] = [
+        Unit::Year,
+        Unit::Month,
+        Unit::Day,
+        Unit::Hour,
+        Unit::Minute,
+        Unit::Second,
+    ];
+
+    const ALL: [Unit; 7] = [
+        Unit::Year,
+        Unit::Month,
+        Unit::Week,
+        Unit::Day,
+        Unit::Hour,
+        Unit::Minute,
+        Unit::Second,
+    ];
+
+    /// Length in seconds; years are 365 days and months 30 days
+    fn seconds(self) -> u64 {
+        match self {
+            Unit::Year => 365 * 86_400,
+            Unit::Month => 30 * 86_400,
+            Unit::Week => 7 * 86_400,
+            Unit::Day => 86_400,
+            Unit::Hour => 3_600,
+            Unit::Minute => 60,
+            Unit::Second => 1,
+        }
+    }
+
+    fn index(self) -> usize {
+        Unit::ALL.iter().position(|&u| u == self).unwrap_or(0)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Unit::Year => "years",
+            Unit::Month => "months",
+            Unit::Week => "weeks",
+            Unit::Day => "days",
+            Unit::Hour => "hours",
+            Unit::Minute => "minutes",
+            Unit::Second => "seconds",
+        }
+    }
+}
+
+/// Unit words for one language, indexed like `Unit::ALL`
+struct Locale {
+    code: &'static str,
+    singular: [&'static str; 7],
+    plural: [&'static str; 7],
+    short: [&'static str; 7],
+    /// Extra spellings accepted when parsing
+    aliases: &'static [(&'static str, Unit)],
+    /// Words skipped when parsing, like "and"
+    fillers: &'static [&'static str],
+    /// Put a space between the number and the short unit
+    spaced_short: bool,
+    /// Use the singular for zero as well as one
+    singular_zero: bool,
+}
+
+static LOCALES: [Locale; 4] = [
+    Locale {
+        code: "en",
+        singular: ["year", "month", "week", "day", "hour", "minute", "second"],
+        plural: [
+            "years", "months", "weeks", "days", "hours", "minutes", "seconds",
+        ],
+        short: ["y", "mo", "w", "d", "h", "m", "s"],
+        aliases: &[
+            ("yr", Unit::Year),
+            ("yrs", Unit::Year),
+            ("mos", Unit::Month),
+            ("wk", Unit::Week),
+            ("wks", Unit::Week),
+            ("hr", Unit::Hour),
+            ("hrs", Unit::Hour),
+            ("min", Unit::Minute),
+            ("mins", Unit::Minute),
+            ("sec", Unit::Second),
+            ("secs", Unit::Second),
+        ],
+        fillers: &["and"],
+        spaced_short: false,
+        singular_zero: false,
+    },
+    Locale {
+        code: "de",
+        singular: [
+            "Jahr", "Monat", "Woche", "Tag", "Stunde", "Minute", "Sekunde",
+        ],
+        plural: [
+            "Jahre", "Monate", "Wochen", "Tage", "Stunden", "Minuten", "Sekunden",
+        ],
+        short: ["J.", "Mon.", "Wo.", "T.", "Std.", "Min.", "Sek."],
+        aliases: &[
+            ("jahren", Unit::Year),
+            ("monaten", Unit::Month),
+            ("tagen", Unit::Day),
+        ],
+        fillers: &["und"],
+        spaced_short: true,
+        singular_zero: false,
+    },
+    Locale {
+        code: "fr",
+        singular: [
+            "an", "mois", "semaine", "jour", "heure", "minute", "seconde",
+        ],
+        plural: [
+            "ans", "mois", "semaines", "jours", "heures", "minutes", "secondes",
+        ],
+        short: ["a", "mois", "sem.", "j", "h", "min", "s"],
+        aliases: &[("année", Unit::Year), ("années", Unit::Year)],
+        fillers: &["et"],
+        spaced_short: true,
+        singular_zero: true,
+    },
+    Locale {
+        code: "es",
+        singular: ["año", "mes", "semana", "día", "hora", "minuto", "segundo"],
+        plural: [
+            "años", "meses", "semanas", "días", "horas", "minutos", "segundos",
+        ],
+        short: ["a", "mes", "sem.", "d", "h", "min", "s"],
+        aliases: &[("dia", Unit::Day), ("dias", Unit::Day)],
+        fillers: &["y"],
+        spaced_short: true,
+        singular_zero: false,
+    },
+];
+
+impl Locale {
+    fn find(code: &str) -> Result<&'static Locale, String> {
+        let normalized = code.trim().to_lowercase();
+        // Accept region tags like "en-GB" or "fr_CA"
+        let language = normalized.split(['-', '_']).next().unwrap_or("");
+        LOCALES.iter().find(|l| l.code == language).ok_or_else(|| {
+            let valid: Vec<&str> = LOCALES.iter().map(|l| l.code).collect();
+            format!(
+                "Unsupported locale '{code}'. Supported locales: {}",
+                valid.join(", ")
+            )
+        })
+    }
+
+    fn format(&self, unit: Unit, value: u64, short: bool) -> String {
+        let i = unit.index();
+        if short {
+            if self.spaced_short {
+                format!("{value} {}", self.short[i])
+            } else {
+                format!("{value}{}", self.short[i])
+            }
+        } else if value == 1 || (value == 0 && self.singular_zero) {
+            format!("{value} {}", self.singular[i])
+        } else {
+            format!("{value} {}", self.plural[i])
+        }
+    }
+
+    fn unit(&self, word: &str) -> Option<Unit> {
+        let word = word.to_lowercase();
+        let trimmed = word.trim_end_matches('.');
+        Unit::ALL
+            .iter()
+            .copied()
+            .find(|u| {
+                let i = u.index();
+                [self.singular[i], self.plural[i], self.short[i]]
+                    .iter()
+                    .any(|w| w.to_lowercase().trim_end_matches('.') == trimmed)
+            })
+            .or_else(|| {
+                self.aliases
+                    .iter()
+                    .find(|(alias, _)| *alias == trimmed)
+                    .map(|&(_, unit)| unit)
+            })
+    }
+
+    fn is_filler(&self, word: &str) -> bool {
+        self.fillers.iter().any(|f| f.eq_ignore_ascii_case(word))
+    }
+}
+
+fn validate_seconds(seconds: f64) -> Result<f64, String> {
+    if !seconds.is_finite() {
+        return Err("Duration must be a finite number of seconds".to_string());
+    }
+    if seconds < 0.0 {
+        return Err("Duration must not be negative".to_string());
+    }
+    if seconds > MAX_SECONDS {
+        return Err(format!("Duration must be at most {MAX_SECONDS:e} seconds"));
+    }
+    Ok(seconds)
+}
+
+/// Parse an ISO 8601 duration such as "P1Y2M3DT4H5M6.5S" or "P2W" into seconds
+pub fn parse_iso_duration(duration: &str) -> Result<f64, String> {
+    let invalid = || format!("Invalid ISO 8601 duration '{duration}'");
+    let rest = duration
+        .trim()
+        .strip_prefix(['P', 'p'])
+        .ok_or_else(invalid)?;
+
+    let mut total = 0.0;
+    let mut in_time = false;
+    let mut saw_component = false;
+    let mut last_unit: Option<Unit> = None;
+    let mut number = String::new();
+
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if !in_time && number.is_empty() => {
+                in_time = true;
+                last_unit = None;
+            }
+            '0'..='9' => number.push(c),
+            // ISO 8601 allows a comma as the decimal sign
+            '.' | ',' => number.push('.'),
+            designator => {
+                let unit = match (in_time, designator) {
+                    (false, 'Y') => Unit::Year,
+                    (false, 'M') => Unit::Month,
+                    (false, 'W') => Unit::Week,
+                    (false, 'D') => Unit::Day,
+                    (true, 'H') => Unit::Hour,
+                    (true, 'M') => Unit::Minute,
+                    (true, 'S') => Unit::Second,
+                    _ => return Err(invalid()),
+                };
+                // Components must appear largest first, each at most once
+                if last_unit.is_some_and(|last| last.index() >= unit.index()) {
+                    return Err(invalid());
+                }
+                let value: f64 = number.parse().map_err(|_| invalid())?;
+                total += value * unit.seconds() as f64;
+                number.clear();
+                last_unit = Some(unit);
+                saw_component = true;
+            }
+        }
+    }
+
+    // A trailing number without a designator, or a "T" with nothing after it
+    if !number.is_empty() || !saw_component || (in_time && last_unit.is_none()) {
+        return Err(invalid());
+    }
+    validate_seconds(total)
+}
+
+/// Parse a phrase like "2 days, 3 hours", "1.5 hours" or "1h30m" into seconds
+pub fn parse_phrase(text: &str, locale: &str) -> Result<f64, String> {
+    let locale = Locale::find(locale)?;
+    let english = &LOCALES[0];
+
+    // Split into numbers and words; punctuation other than '.' separates tokens
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_is_number = false;
+    for c in text.chars() {
+        let is_number = c.is_ascii_digit() || (c == '.' && current_is_number);
+        let is_word = c.is_alphabetic() || (c == '.' && !current.is_empty());
+        if (is_number || is_word) && !current.is_empty() && is_number != current_is_number {
+            tokens.push(std::mem::take(&mut current));
+        }
+        if is_number || is_word {
+            current_is_number = is_number;
+            current.push(c);
+        } else if !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    let mut total = 0.0;
+    let mut pending: Option<f64> = None;
+    let mut saw_component = false;
+    for token in tokens {
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            if let Some(previous) = pending {
+                return Err(format!("Expected a unit after '{previous}'"));
+            }
+            let value: f64 = token
+                .trim_end_matches('.')
+                .parse()
+                .map_err(|_| format!("Invalid number '{token}'"))?;
+            pending = Some(value);
+            continue;
+        }
+        if pending.is_none() && (locale.is_filler(&token) || english.is_filler(&token)) {
+            continue;
+        }
+        let unit = locale
+            .unit(&token)
+            .or_else(|| english.unit(&token))
+            .ok_or_else(|| format!("Unknown unit '{token}' for locale '{}'", locale.code))?;
+        let value = pending
+            .take()
+            .ok_or_else(|| format!("Missing number before '{token}'"))?;
+        total += value * unit.seconds() as f64;
+        saw_component = true;
+    }
+
+    if pending.is_some() {
+        return Err("Phrase ends with a number but no unit".to_string());
+    }
+    if !saw_component {
+        return Err(format!("No duration found in '{text}'"));
+    }
+    validate_seconds(total)
+}
+
+/// Split a duration into at most `precision` units, counted from the largest unit
+/// present, with the smallest of them rounded. Zero units are left out.
+fn split(seconds: f64, precision: usize) -> Vec<(Unit, u64)> {
+    let units = Unit::HUMANIZE;
+    let largest = units
+        .iter()
+        .position(|u| seconds >= u.seconds() as f64)
+        .unwrap_or(units.len() - 1);
+    let smallest = (largest + precision - 1).min(units.len() - 1);
+
+    let mut values = [0u64; Unit::HUMANIZE.len()];
+    let mut remaining = seconds;
+    for (value, unit) in values.iter_mut().zip(units).take(smallest).skip(largest) {
+        *value = (remaining / unit.seconds() as f64).floor() as u64;
+        remaining -= (*value * unit.seconds()) as f64;
+    }
+    values[smallest] = (remaining / units[smallest].seconds() as f64).round() as u64;
+
+    // Carry rounding overflow upwards, so 60 minutes become 1 hour
+    for i in (1..=smallest).rev() {
+        let per_larger = units[i - 1].seconds() / units[i].seconds();
+        if values[i] >= per_larger {
+            values[i] -= per_larger;
+            values[i - 1] += 1;
+        }
+    }
+
+    let parts: Vec<(Unit, u64)> = units
+        .iter()
+        .zip(values)
+        .filter(|&(_, value)| value > 0)
+        .map(|(&unit, value)| (unit, value))
+        .collect();
+    if parts.is_empty() {
+        vec![(units[smallest], 0)]
+    } else {
+        parts
+    }
+}
+
+/// Exact ISO 8601 form, with days as the largest unit and milliseconds kept
+fn to_iso(seconds: f64) -> String {
+    let millis = (seconds * 1000.0).round() as u64;
+    let days = millis / 86_400_000;
+    let hours = millis / 3_600_000 % 24;
+    let minutes = millis / 60_000 % 60;
+    let secs = millis % 60_000;
+
+    let mut iso = String::from("P");
+    if days > 0 {
+        iso.push_str(&format!("{days}D"));
+    }
+    if hours > 0 || minutes > 0 || secs > 0 || days == 0 {
+        iso.push('T');
+        if hours > 0 {
+            iso.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            iso.push_str(&format!("{minutes}M"));
+        }
+        if secs > 0 || (hours == 0 && minutes == 0) {
+            if secs.is_multiple_of(1000) {
+                iso.push_str(&format!("{}S", secs / 1000));
+            } else {
+                let fraction = format!("{:03}", secs % 1000);
+                iso.push_str(&format!(
+                    "{}.{}S",
+                    secs / 1000,
+                    fraction.trim_end_matches('0')
+                ));
+            }
+        }
+    }
+    iso
+}
+
+pub fn convert_duration(input: HumanizeDurationInput) -> Result<HumanizeDurationOutput, String> {
+    let operation = input
+        .operation
+        .as_deref()
+        .unwrap_or("humanize")
+        .trim()
+        .to_lowercase();
+    let locale = Locale::find(input.locale.as_deref().unwrap_or("en"))?;
+    let precision = match input.precision {
+        None => DEFAULT_PRECISION,
+        Some(0) => return Err("precision must be at least 1".to_string()),
+        Some(p) => p as usize,
+    };
+    let short = match input.style.as_deref().map(|s| s.trim().to_lowercase()) {
+        None => false,
+        Some(s) if s == "long" => false,
+        Some(s) if s == "short" => true,
+        Some(s) => return Err(format!("Invalid style '{s}'. Use 'long' or 'short'")),
+    };
+
+    let seconds = match operation.as_str() {
+        "humanize" => match (input.seconds, &input.duration) {
+            (Some(seconds), None) => validate_seconds(seconds)?,
+            (None, Some(duration)) => parse_iso_duration(duration)?,
+            (Some(_), Some(_)) => {
+                return Err("Give either seconds or duration, not both".to_string());
+            }
+            (None, None) => return Err("humanize needs seconds or duration".to_string()),
+        },
+        "parse" => {
+            let text = input
+                .text
+                .as_deref()
+                .ok_or_else(|| "parse needs text".to_string())?;
+            parse_phrase(text, locale.code)?
+        }
+        other => {
+            return Err(format!(
+                "Invalid operation '{other}'. Use 'humanize' or 'parse'"
+            ));
+        }
+    };
+
+    let parts = split(seconds, precision);
+    let separator = if short { " " } else { ", " };
+    let text = parts
+        .iter()
+        .map(|&(unit, value)| locale.format(unit, value, short))
+        .collect::<Vec<_>>()
+        .join(separator);
+
+    Ok(HumanizeDurationOutput {
+        seconds,
+        text,
+        iso: to_iso(seconds),
+        parts: parts
+            .into_iter()
+            .map(|(unit, value)| DurationPart {
+                unit: unit.name().to_string(),
+                value,
+            })
+            .collect(),
+        locale: locale.code.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> HumanizeDurationInput {
+        HumanizeDurationInput {
+            operation: None,
+            seconds: None,
+            duration: None,
+            text: None,
+            locale: None,
+            precision: None,
+            style: None,
+        }
+    }
+
+    fn humanize(seconds: f64) -> HumanizeDurationOutput {
+        convert_duration(HumanizeDurationInput {
+            seconds: Some(seconds),
+            ..input()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_humanize_seconds() {
+        let result = humanize(2.0 * 86_400.0 + 3.0 * 3_600.0);
+        assert_eq!(result.text, "2 days, 3 hours");
+        assert_eq!(result.iso, "P2DT3H");
+        assert_eq!(
+            result.parts,
+            vec![
+                DurationPart {
+                    unit: "days".to_string(),
+                    value: 2
+                },
+                DurationPart {
+                    unit: "hours".to_string(),
+                    value: 3
+                }
+            ]
+        );
+
+        assert_eq!(humanize(1.0).text, "1 second");
+        assert_eq!(humanize(0.0).text, "0 seconds");
+        assert_eq!(humanize(0.0).iso, "PT0S");
+        assert_eq!(humanize(90.5).iso, "PT1M30.5S");
+        assert_eq!(humanize(400.0 * 86_400.0).text, "1 year, 1 month");
+    }
+
+    #[test]
+    fn test_precision_rounds_smallest_unit() {
+        // 2 days, 3 hours, 40 minutes rounds up to the hour
+        let result = humanize(2.0 * 86_400.0 + 3.0 * 3_600.0 + 40.0 * 60.0);
+        assert_eq!(result.text, "2 days, 4 hours");
+        assert_eq!(result.iso, "P2DT3H40M");
+
+        // Rounding carries into the larger unit
+        assert_eq!(
+            humanize(86_400.0 + 23.0 * 3_600.0 + 59.0 * 60.0).text,
+            "2 days"
+        );
+
+        let full = convert_duration(HumanizeDurationInput {
+            seconds: Some(93_784.0),
+            precision: Some(4),
+            ..input()
+        })
+        .unwrap();
+        assert_eq!(full.text, "1 day, 2 hours, 3 minutes, 4 seconds");
+
+        let one = convert_duration(HumanizeDurationInput {
+            seconds: Some(93_784.0),
+            precision: Some(1),
+            ..input()
+        })
+        .unwrap();
+        assert_eq!(one.text, "1 day");
+    }
+
+    #[test]
+    fn test_iso_duration_input() {
+        let result = convert_duration(HumanizeDurationInput {
+            duration: Some("P2DT3H".to_string()),
+            ..input()
+        })
+        .unwrap();
+        assert_eq!(result.seconds, 183_600.0);
+        assert_eq!(result.text, "2 days, 3 hours");
+
+        assert_eq!(parse_iso_duration("PT90M").unwrap(), 5_400.0);
+        assert_eq!(parse_iso_duration("P2W").unwrap(), 1_209_600.0);
+        assert_eq!(parse_iso_duration("PT0,5S").unwrap(), 0.5);
+        assert_eq!(parse_iso_duration("P1Y").unwrap(), 31_536_000.0);
+        for bad in ["", "P", "PT", "2D", "P1H", "PT1D", "P1D2Y", "P5", "-P1D"] {
+            assert!(parse_iso_duration(bad).is_err(), "{bad} should fail");
+        }
+    }
+
+    #[test]
+    fn test_locales_and_styles() {
+        let seconds = 2.0 * 86_400.0 + 3_600.0;
+        let phrase = |locale: &str, style: &str| {
+            convert_duration(HumanizeDurationInput {
+                seconds: Some(seconds),
+                locale: Some(locale.to_string()),
+                style: Some(style.to_string()),
+                ..input()
+            })
+            .unwrap()
+            .text
+        };
+        assert_eq!(phrase("en", "short"), "2d 1h");
+        assert_eq!(phrase("de", "long"), "2 Tage, 1 Stunde");
+        assert_eq!(phrase("de-AT", "short"), "2 T. 1 Std.");
+        assert_eq!(phrase("fr", "long"), "2 jours, 1 heure");
+        assert_eq!(phrase("es", "long"), "2 días, 1 hora");
+
+        let zero = convert_duration(HumanizeDurationInput {
+            seconds: Some(0.0),
+            locale: Some("fr".to_string()),
+            ..input()
+        })
+        .unwrap();
+        assert_eq!(zero.text, "0 seconde");
+    }
+
+    #[test]
+    fn test_parse_phrases() {
+        assert_eq!(parse_phrase("2 days, 3 hours", "en").unwrap(), 183_600.0);
+        assert_eq!(parse_phrase("1h30m", "en").unwrap(), 5_400.0);
+        assert_eq!(
+            parse_phrase("1.5 hours and 10 secs", "en").unwrap(),
+            5_410.0
+        );
+        assert_eq!(parse_phrase("1 week", "en").unwrap(), 604_800.0);
+        assert_eq!(
+            parse_phrase("2 Tage und 3 Stunden", "de").unwrap(),
+            183_600.0
+        );
+        assert_eq!(parse_phrase("2 T. 1 Std.", "de").unwrap(), 176_400.0);
+        assert_eq!(parse_phrase("1 jour et 2 heures", "fr").unwrap(), 93_600.0);
+        assert_eq!(parse_phrase("3 días y 5 minutos", "es").unwrap(), 259_500.0);
+        // English units are understood whatever the locale
+        assert_eq!(parse_phrase("2 Tage 4h", "de").unwrap(), 187_200.0);
+
+        let result = convert_duration(HumanizeDurationInput {
+            operation: Some("parse".to_string()),
+            text: Some("90 minutes".to_string()),
+            ..input()
+        })
+        .unwrap();
+        assert_eq!(result.seconds, 5_400.0);
+        assert_eq!(result.text, "1 hour, 30 minutes");
+        assert_eq!(result.iso, "PT1H30M");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_phrase("", "en").unwrap_err().contains("No duration"));
+        assert!(parse_phrase("3", "en").unwrap_err().contains("no unit"));
+        assert!(
+            parse_phrase("hours", "en")
+                .unwrap_err()
+                .contains("Missing number")
+        );
+        assert!(
+            parse_phrase("2 fortnights", "en")
+                .unwrap_err()
+                .contains("Unknown unit 'fortnights'")
+        );
+        assert!(parse_phrase("2 3 hours", "en").is_err());
+        assert!(
+            parse_phrase("1 hour", "tlh")
+                .unwrap_err()
+                .contains("Unsupported locale")
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |request: HumanizeDurationInput| convert_duration(request).unwrap_err();
+        assert!(err(input()).contains("needs seconds or duration"));
+        assert!(
+            err(HumanizeDurationInput {
+                seconds: Some(1.0),
+                duration: Some("PT1S".to_string()),
+                ..input()
+            })
+            .contains("not both")
+        );
+        assert!(
+            err(HumanizeDurationInput {
+                seconds: Some(-5.0),
+                ..input()
+            })
+            .contains("negative")
+        );
+        assert!(
+            err(HumanizeDurationInput {
+                seconds: Some(f64::NAN),
+                ..input()
+            })
+            .contains("finite")
+        );
+        assert!(
+            err(HumanizeDurationInput {
+                seconds: Some(1.0),
+                precision: Some(0),
+                ..input()
+            })
+            .contains("precision")
+        );
+        assert!(
+            err(HumanizeDurationInput {
+                seconds: Some(1.0),
+                style: Some("tiny".to_string()),
+                ..input()
+            })
+            .contains("Invalid style")
+        );
+        assert!(
+            err(HumanizeDurationInput {
+                operation: Some("parse".to_string()),
+                ..input()
+            })
+            .contains("needs text")
+        );
+        assert!(
+            err(HumanizeDurationInput {
+                operation: Some("format".to_string()),
+                ..input()
+            })
+            .contains("Invalid operation")
+        );
+    }
+}
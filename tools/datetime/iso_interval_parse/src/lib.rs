@@ -0,0 +1,138 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    IsoIntervalInput as LogicInput, IsoIntervalOutput as LogicOutput,
+    Occurrence as LogicOccurrence, parse_interval,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IsoIntervalInput {
+    /// ISO 8601 interval: "start/end" ("2024-03-01T09:00Z/2024-03-01T17:00Z", or concise
+    /// ends like "2024-03-01T09:00/17:00"), "start/duration" ("2024-03-01/P1M") or
+    /// "duration/end" ("PT2H/2024-03-01T12:00Z"). Prefix with "Rn/" for n occurrences or
+    /// "R/" for an unbounded repeating interval, like "R5/2024-01-01T09:00Z/P1W"
+    pub interval: String,
+    /// Most occurrences to list (optional, default: 100, max: 10000)
+    pub max_occurrences: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IsoIntervalOutput {
+    /// Form of the interval: "start/end", "start/duration" or "duration/end"
+    pub form: String,
+    /// Start of the (first) interval, in the offset it was given with
+    pub start: String,
+    /// End of the (first) interval, in the same offset as the start
+    pub end: String,
+    /// Duration of the interval in ISO 8601 form; exact days and time for "start/end"
+    pub duration: String,
+    /// Length of the (first) interval in seconds
+    pub duration_seconds: i64,
+    /// Whether the interval repeats
+    pub repeating: bool,
+    /// Number of occurrences the interval describes: 1 unless repeating, absent when
+    /// unbounded
+    pub repetitions: Option<u32>,
+    /// Occurrences in order; a "duration/end" series counts back from the end
+    pub occurrences: Vec<Occurrence>,
+    /// Whether occurrences were left out because of `max_occurrences`
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Occurrence {
+    /// Position in the series, starting at 0
+    pub index: u32,
+    pub start: String,
+    pub end: String,
+}
+
+/// Parse an ISO 8601 time interval ("start/end", "start/duration", "duration/end") or
+/// repeating interval ("Rn/...") into normalized start and end instants and the list of
+/// occurrences it describes
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn iso_interval_parse(request: Request<IsoIntervalInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "iso_interval_parse",
+                IsoIntervalInput,
+                IsoIntervalOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("iso_interval_parse", &input);
+    log.finish(run_iso_interval_parse(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_iso_interval_parse(input: IsoIntervalInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("interval", &input.interval)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        interval: input.interval,
+        max_occurrences: input.max_occurrences,
+    };
+
+    // Call logic implementation
+    let result = match logic::parse_interval(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = IsoIntervalOutput {
+        form: result.form,
+        start: result.start,
+        end: result.end,
+        duration: result.duration,
+        duration_seconds: result.duration_seconds,
+        repeating: result.repeating,
+        repetitions: result.repetitions,
+        occurrences: result
+            .occurrences
+            .into_iter()
+            .map(|o| Occurrence {
+                index: o.index,
+                start: o.start,
+                end: o.end,
+            })
+            .collect(),
+        truncated: result.truncated,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
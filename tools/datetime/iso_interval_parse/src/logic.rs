@@ -0,0 +1,679 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Occurrences listed when the input does not ask for a number
+const DEFAULT_MAX_OCCURRENCES: u32 = 100;
+/// Upper limit for `max_occurrences`
+const MAX_OCCURRENCES: u32 = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IsoIntervalInput {
+    /// ISO 8601 interval: "start/end", "start/duration", "duration/end", optionally
+    /// prefixed with "Rn/" (n occurrences) or "R/" (unbounded) for a repeating interval
+    pub interval: String,
+    /// Most occurrences to list (optional, default: 100, max: 10000)
+    pub max_occurrences: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IsoIntervalOutput {
+    /// Form of the interval: "start/end", "start/duration" or "duration/end"
+    pub form: String,
+    /// Start of the (first) interval
+    pub start: String,
+    /// End of the (first) interval
+    pub end: String,
+    /// Duration of the interval in ISO 8601 form; exact days and time for "start/end"
+    pub duration: String,
+    /// Length of the (first) interval in seconds
+    pub duration_seconds: i64,
+    /// Whether the interval repeats
+    pub repeating: bool,
+    /// Number of occurrences the interval describes: 1 unless repeating, absent when
+    /// unbounded
+    pub repetitions: Option<u32>,
+    /// Occurrences in order; a "duration/end" series counts back from the end
+    pub occurrences: Vec<Occurrence>,
+    /// Whether occurrences were left out because of `max_occurrences`
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Occurrence {
+    /// Position in the series, starting at 0
+    pub index: u32,
+    pub start: String,
+    pub end: String,
+}
+
+/// A parsed date or date-time with its UTC offset in seconds, if one was given
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Stamp {
+    local: NaiveDateTime,
+    offset: Option<i32>,
+    date_only: bool,
+}
+
+/// Calendar-aware ISO 8601 duration
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct IsoDuration {
+    months: u32,
+    days: i64,
+    seconds: i64,
+}
+
+impl IsoDuration {
+    fn is_zero(&self) -> bool {
+        self.months == 0 && self.days == 0 && self.seconds == 0
+    }
+
+    /// `times` copies of the duration, as one offset from a fixed start so month-end
+    /// clamping does not drift across a series
+    fn times(&self, times: u32) -> Option<IsoDuration> {
+        Some(IsoDuration {
+            months: self.months.checked_mul(times)?,
+            days: self.days.checked_mul(times as i64)?,
+            seconds: self.seconds.checked_mul(times as i64)?,
+        })
+    }
+
+    fn add_to(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        local
+            .checked_add_months(Months::new(self.months))?
+            .checked_add_signed(Duration::days(self.days))?
+            .checked_add_signed(Duration::seconds(self.seconds))
+    }
+
+    fn subtract_from(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        local
+            .checked_sub_signed(Duration::seconds(self.seconds))?
+            .checked_sub_signed(Duration::days(self.days))?
+            .checked_sub_months(Months::new(self.months))
+    }
+
+    fn to_iso(self) -> String {
+        let (years, months) = (self.months / 12, self.months % 12);
+        let mut text = String::from("P");
+        for (value, designator) in [(years as i64, 'Y'), (months as i64, 'M'), (self.days, 'D')] {
+            if value != 0 {
+                text.push_str(&format!("{value}{designator}"));
+            }
+        }
+        let (hours, minutes, seconds) = (
+            self.seconds / 3600,
+            self.seconds % 3600 / 60,
+            self.seconds % 60,
+        );
+        if self.seconds != 0 {
+            text.push('T');
+            for (value, designator) in [(hours, 'H'), (minutes, 'M'), (seconds, 'S')] {
+                if value != 0 {
+                    text.push_str(&format!("{value}{designator}"));
+                }
+            }
+        }
+        if text == "P" {
+            text.push_str("T0S");
+        }
+        text
+    }
+}
+
+/// Parse an ISO 8601 duration like "P1Y2M10DT2H30M" or "P2W" into calendar months,
+/// days and seconds; fractions are allowed on weeks, days and time components
+fn parse_duration(text: &str) -> Result<IsoDuration, String> {
+    let invalid = || format!("Invalid ISO 8601 duration '{text}'");
+    let rest = text.strip_prefix(['P', 'p']).ok_or_else(invalid)?;
+
+    let mut duration = IsoDuration {
+        months: 0,
+        days: 0,
+        seconds: 0,
+    };
+    // Seconds from fractional components, summed before rounding
+    let mut exact_seconds = 0.0;
+    let mut in_time = false;
+    let mut saw_component = false;
+    let mut last_rank = 0;
+    let mut number = String::new();
+
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if !in_time && number.is_empty() => in_time = true,
+            '0'..='9' => number.push(c),
+            // ISO 8601 allows a comma as the decimal sign
+            '.' | ',' => number.push('.'),
+            designator => {
+                let (rank, seconds_per_unit) = match (in_time, designator) {
+                    (false, 'Y') => (1, None),
+                    (false, 'M') => (2, None),
+                    (false, 'W') => (3, Some(7 * 86_400)),
+                    (false, 'D') => (4, Some(86_400)),
+                    (true, 'H') => (5, Some(3_600)),
+                    (true, 'M') => (6, Some(60)),
+                    (true, 'S') => (7, Some(1)),
+                    _ => return Err(invalid()),
+                };
+                // Components must appear largest first, each at most once
+                if rank <= last_rank || number.is_empty() {
+                    return Err(invalid());
+                }
+                match seconds_per_unit {
+                    None => {
+                        // Years and months vary in length, so only whole ones are allowed
+                        let value: u32 = number.parse().map_err(|_| {
+                            format!("Years and months must be whole numbers in '{text}'")
+                        })?;
+                        let months = if rank == 1 {
+                            value.checked_mul(12)
+                        } else {
+                            Some(value)
+                        };
+                        duration.months = months
+                            .and_then(|m| m.checked_add(duration.months))
+                            .ok_or_else(|| format!("Duration '{text}' is too long"))?;
+                    }
+                    Some(unit) => {
+                        let value: f64 = number.parse().map_err(|_| invalid())?;
+                        if value > 1e9 {
+                            return Err(format!("Duration '{text}' is too long"));
+                        }
+                        // Whole weeks and days stay calendar days
+                        if unit % 86_400 == 0 && value.fract() == 0.0 {
+                            duration.days += value as i64 * (unit / 86_400);
+                        } else {
+                            exact_seconds += value * unit as f64;
+                        }
+                    }
+                }
+                number.clear();
+                last_rank = rank;
+                saw_component = true;
+            }
+        }
+    }
+
+    // A trailing number without a designator, or a "T" with nothing after it
+    if !number.is_empty() || !saw_component || (in_time && last_rank < 5) {
+        return Err(invalid());
+    }
+    if exact_seconds.fract() != 0.0 {
+        return Err(format!(
+            "Duration '{text}' is not a whole number of seconds"
+        ));
+    }
+    if exact_seconds > 1e12 || duration.days > 100_000_000 {
+        return Err(format!("Duration '{text}' is too long"));
+    }
+    duration.seconds = exact_seconds as i64;
+    Ok(duration)
+}
+
+fn parse_number(digits: &str, text: &str) -> Result<u32, String> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("Invalid ISO 8601 date-time '{text}'"));
+    }
+    digits
+        .parse()
+        .map_err(|_| format!("Invalid ISO 8601 date-time '{text}'"))
+}
+
+/// Split a UTC offset ("Z", "+05:30", "+0530" or "+05") off the end of a date-time
+fn split_offset(text: &str) -> Result<(&str, Option<i32>), String> {
+    if let Some(rest) = text.strip_suffix(['Z', 'z']) {
+        return Ok((rest, Some(0)));
+    }
+    // Offsets only follow a time, so look for the sign after the time designator or
+    // inside a time-only value
+    let time_start = text.find(['T', 't']).map_or(0, |i| i + 1);
+    if time_start == 0 && !text.contains(':') {
+        return Ok((text, None));
+    }
+    let Some(sign_at) = text[time_start..].rfind(['+', '-']).map(|i| i + time_start) else {
+        return Ok((text, None));
+    };
+    let invalid = || format!("Invalid UTC offset in '{text}'");
+    let digits = text[sign_at + 1..].replace(':', "");
+    let (hours, minutes) = match digits.len() {
+        2 => (parse_number(&digits, text)?, 0),
+        4 => (
+            parse_number(&digits[..2], text)?,
+            parse_number(&digits[2..], text)?,
+        ),
+        _ => return Err(invalid()),
+    };
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    let seconds = (hours * 3600 + minutes * 60) as i32;
+    let offset = if text.as_bytes()[sign_at] == b'-' {
+        -seconds
+    } else {
+        seconds
+    };
+    Ok((&text[..sign_at], Some(offset)))
+}
+
+/// Parse a date or date-time in extended ("2024-03-01T09:30:00") or basic
+/// ("20240301T093000") form. With a `context`, the concise end forms of ISO 8601 are
+/// accepted too, taking the missing leading parts from it: "15:30", "14" or "03-14T10:00"
+fn parse_stamp(text: &str, context: Option<&Stamp>) -> Result<Stamp, String> {
+    let invalid = || format!("Invalid ISO 8601 date-time '{text}'");
+    let (body, offset) = split_offset(text)?;
+
+    let (date_part, time_part) = match body.find(['T', 't']) {
+        Some(i) => (&body[..i], Some(&body[i + 1..])),
+        // A bare time like "15:30"
+        None if body.contains(':') => ("", Some(body)),
+        None => (body, None),
+    };
+
+    let date_fields: Vec<&str> = match date_part.len() {
+        // Basic form "YYYYMMDD"
+        8 if !date_part.contains('-') => vec![&date_part[..4], &date_part[4..6], &date_part[6..]],
+        0 => Vec::new(),
+        _ => date_part.split('-').collect(),
+    };
+    let context_date = || {
+        context.map(|c| c.local.date()).ok_or_else(|| {
+            format!("'{text}' leaves out the date, which only an interval end may do")
+        })
+    };
+    let date = match date_fields.as_slice() {
+        [year, month, day] if year.len() == 4 && month.len() == 2 && day.len() == 2 => {
+            NaiveDate::from_ymd_opt(
+                parse_number(year, text)? as i32,
+                parse_number(month, text)?,
+                parse_number(day, text)?,
+            )
+        }
+        [month, day] if month.len() == 2 && day.len() == 2 => NaiveDate::from_ymd_opt(
+            context_date()?.year(),
+            parse_number(month, text)?,
+            parse_number(day, text)?,
+        ),
+        [day] if day.len() == 2 => {
+            let base = context_date()?;
+            NaiveDate::from_ymd_opt(base.year(), base.month(), parse_number(day, text)?)
+        }
+        [] => Some(context_date()?),
+        _ => return Err(invalid()),
+    }
+    .ok_or_else(|| format!("Date '{text}' does not exist"))?;
+
+    let Some(time_part) = time_part else {
+        let local = date.and_hms_opt(0, 0, 0).ok_or_else(invalid)?;
+        return Ok(Stamp {
+            local,
+            offset: offset.or(context.and_then(|c| c.offset)),
+            date_only: true,
+        });
+    };
+
+    if time_part.contains(['.', ',']) {
+        return Err(format!("Fractional seconds are not supported in '{text}'"));
+    }
+    let time_fields: Vec<&str> = if time_part.contains(':') {
+        time_part.split(':').collect()
+    } else {
+        // Basic form "HHMMSS", "HHMM" or "HH"
+        (0..time_part.len())
+            .step_by(2)
+            .map(|i| &time_part[i..(i + 2).min(time_part.len())])
+            .collect()
+    };
+    if time_fields.is_empty() || time_fields.len() > 3 || time_fields.iter().any(|f| f.len() != 2) {
+        return Err(invalid());
+    }
+    let mut hms = [0; 3];
+    for (slot, field) in hms.iter_mut().zip(&time_fields) {
+        *slot = parse_number(field, text)?;
+    }
+    let local = match hms {
+        // "24:00" is the end of the day
+        [24, 0, 0] => date
+            .and_hms_opt(0, 0, 0)
+            .and_then(|midnight| midnight.checked_add_signed(Duration::days(1))),
+        [hour, minute, second] => date.and_hms_opt(hour, minute, second),
+    }
+    .ok_or_else(|| format!("Time in '{text}' is out of range"))?;
+
+    Ok(Stamp {
+        local,
+        offset: offset.or(context.and_then(|c| c.offset)),
+        date_only: false,
+    })
+}
+
+/// Move `stamp` to `offset`, keeping the instant it names
+fn with_offset(stamp: Stamp, offset: Option<i32>) -> Stamp {
+    match (stamp.offset, offset) {
+        (Some(from), Some(to)) if from != to => Stamp {
+            local: stamp.local + Duration::seconds((to - from) as i64),
+            offset: Some(to),
+            date_only: false,
+        },
+        _ => Stamp { offset, ..stamp },
+    }
+}
+
+fn format_stamp(local: NaiveDateTime, offset: Option<i32>, date_only: bool) -> String {
+    if date_only {
+        return local.format("%Y-%m-%d").to_string();
+    }
+    let mut text = local.format("%Y-%m-%dT%H:%M:%S").to_string();
+    match offset {
+        Some(0) => text.push('Z'),
+        Some(seconds) => {
+            let sign = if seconds < 0 { '-' } else { '+' };
+            let minutes = seconds.abs() / 60;
+            text.push_str(&format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60));
+        }
+        None => {}
+    }
+    text
+}
+
+/// Exact length of a span as days and seconds, for "start/end" intervals
+fn exact_duration(seconds: i64) -> IsoDuration {
+    IsoDuration {
+        months: 0,
+        days: seconds / 86_400,
+        seconds: seconds % 86_400,
+    }
+}
+
+/// Split off a leading "Rn" or "R", returning the occurrence count (None if unbounded)
+fn parse_repetitions(text: &str) -> Result<Option<u32>, String> {
+    let count = &text[1..];
+    if count.is_empty() || count == "-1" {
+        return Ok(None);
+    }
+    count
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid repetition count '{text}'"))
+}
+
+pub fn parse_interval(input: IsoIntervalInput) -> Result<IsoIntervalOutput, String> {
+    let max_occurrences = input.max_occurrences.unwrap_or(DEFAULT_MAX_OCCURRENCES);
+    if max_occurrences == 0 || max_occurrences > MAX_OCCURRENCES {
+        return Err(format!(
+            "max_occurrences must be between 1 and {MAX_OCCURRENCES}"
+        ));
+    }
+
+    let text = input.interval.trim();
+    let mut parts: Vec<&str> = text.split('/').collect();
+    let repeating = text.starts_with(['R', 'r']);
+    let repetitions = if repeating {
+        parse_repetitions(parts.remove(0))?
+    } else {
+        Some(1)
+    };
+    let [first, second] = parts.as_slice() else {
+        return Err(format!(
+            "Interval '{text}' must have two parts separated by '/', like \"start/end\""
+        ));
+    };
+    let is_duration = |part: &str| part.starts_with(['P', 'p']);
+
+    let (form, start, end, duration) = match (is_duration(first), is_duration(second)) {
+        (false, false) => {
+            let start = parse_stamp(first, None)?;
+            let end = with_offset(parse_stamp(second, Some(&start))?, start.offset);
+            let start = Stamp {
+                offset: start.offset.or(end.offset),
+                ..start
+            };
+            let seconds = end.local.signed_duration_since(start.local).num_seconds();
+            if seconds < 0 {
+                return Err(format!("Interval '{text}' ends before it starts"));
+            }
+            ("start/end", start, end, exact_duration(seconds))
+        }
+        (false, true) => {
+            let start = parse_stamp(first, None)?;
+            let duration = parse_duration(second)?;
+            let end = duration
+                .add_to(start.local)
+                .ok_or_else(|| format!("Interval '{text}' is out of range"))?;
+            let end = Stamp {
+                local: end,
+                ..start
+            };
+            ("start/duration", start, end, duration)
+        }
+        (true, false) => {
+            let end = parse_stamp(second, None)?;
+            let duration = parse_duration(first)?;
+            let start = duration
+                .subtract_from(end.local)
+                .ok_or_else(|| format!("Interval '{text}' is out of range"))?;
+            let start = Stamp {
+                local: start,
+                ..end
+            };
+            ("duration/end", start, end, duration)
+        }
+        (true, true) => {
+            return Err(format!(
+                "Interval '{text}' needs a start or an end, not two durations"
+            ));
+        }
+    };
+
+    if repeating && duration.is_zero() {
+        return Err(format!("Repeating interval '{text}' has a zero duration"));
+    }
+    // Date-only inputs keep date-only output while every occurrence stays on midnight
+    let date_only = start.date_only && end.date_only && duration.seconds == 0;
+    let offset = start.offset;
+
+    let count = repetitions.unwrap_or(u32::MAX);
+    let listed = count.min(max_occurrences);
+    let out_of_range = || format!("Occurrences of '{text}' run out of the supported range");
+    let mut occurrences = Vec::with_capacity(listed as usize);
+    for index in 0..listed {
+        let (occurrence_start, occurrence_end) = if form == "duration/end" {
+            // Series anchored at the end repeat backwards in time
+            let back = |k: u32| {
+                duration
+                    .times(k)
+                    .and_then(|d| d.subtract_from(end.local))
+                    .ok_or_else(out_of_range)
+            };
+            (back(index + 1)?, back(index)?)
+        } else if form == "start/end" {
+            let step = |k: u32| {
+                (end.local.signed_duration_since(start.local).num_seconds())
+                    .checked_mul(k as i64)
+                    .and_then(|s| start.local.checked_add_signed(Duration::seconds(s)))
+                    .ok_or_else(out_of_range)
+            };
+            (step(index)?, step(index + 1)?)
+        } else {
+            let forward = |k: u32| {
+                duration
+                    .times(k)
+                    .and_then(|d| d.add_to(start.local))
+                    .ok_or_else(out_of_range)
+            };
+            (forward(index)?, forward(index + 1)?)
+        };
+        occurrences.push(Occurrence {
+            index,
+            start: format_stamp(occurrence_start, offset, date_only),
+            end: format_stamp(occurrence_end, offset, date_only),
+        });
+    }
+
+    Ok(IsoIntervalOutput {
+        form: form.to_string(),
+        start: format_stamp(start.local, offset, date_only),
+        end: format_stamp(end.local, offset, date_only),
+        duration: duration.to_iso(),
+        duration_seconds: end.local.signed_duration_since(start.local).num_seconds(),
+        repeating,
+        repetitions,
+        truncated: listed < count,
+        occurrences,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(interval: &str) -> Result<IsoIntervalOutput, String> {
+        parse_interval(IsoIntervalInput {
+            interval: interval.to_string(),
+            max_occurrences: None,
+        })
+    }
+
+    #[test]
+    fn test_start_end() {
+        let result = parse("2024-03-01T09:00:00Z/2024-03-02T11:30:00Z").unwrap();
+        assert_eq!(result.form, "start/end");
+        assert_eq!(result.start, "2024-03-01T09:00:00Z");
+        assert_eq!(result.end, "2024-03-02T11:30:00Z");
+        assert_eq!(result.duration, "P1DT2H30M");
+        assert_eq!(result.duration_seconds, 95_400);
+        assert!(!result.repeating);
+        assert_eq!(result.repetitions, Some(1));
+        assert_eq!(result.occurrences.len(), 1);
+        assert!(!result.truncated);
+
+        // The end is normalized to the start's offset; basic format is accepted
+        let result = parse("20240301T090000+01:00/2024-03-01T10:00:00Z").unwrap();
+        assert_eq!(result.end, "2024-03-01T11:00:00+01:00");
+        assert_eq!(result.duration, "PT2H");
+    }
+
+    #[test]
+    fn test_concise_end_forms() {
+        let result = parse("2007-12-14T13:30/15:30").unwrap();
+        assert_eq!(result.end, "2007-12-14T15:30:00");
+        assert_eq!(result.duration_seconds, 7_200);
+
+        let result = parse("2008-02-15/03-14").unwrap();
+        assert_eq!(result.end, "2008-03-14");
+        assert_eq!(result.duration, "P28D");
+
+        let result = parse("2007-11-13T09:00+02:00/15T17:00").unwrap();
+        assert_eq!(result.end, "2007-11-15T17:00:00+02:00");
+
+        assert!(
+            parse("15:30/2007-12-14T13:30")
+                .unwrap_err()
+                .contains("leaves out the date")
+        );
+    }
+
+    #[test]
+    fn test_start_duration_and_duration_end() {
+        let result = parse("2024-01-31/P1M").unwrap();
+        assert_eq!(result.form, "start/duration");
+        assert_eq!(result.end, "2024-02-29");
+        assert_eq!(result.duration, "P1M");
+
+        let result = parse("2024-03-01T12:00:00Z/P1.5D").unwrap();
+        assert_eq!(result.end, "2024-03-03T00:00:00Z");
+        assert_eq!(result.duration, "PT36H");
+
+        let result = parse("PT90M/2024-03-01T12:00:00-05:00").unwrap();
+        assert_eq!(result.form, "duration/end");
+        assert_eq!(result.start, "2024-03-01T10:30:00-05:00");
+        assert_eq!(result.duration, "PT1H30M");
+    }
+
+    #[test]
+    fn test_repeating_intervals() {
+        let result = parse("R3/2024-01-31T09:00:00Z/P1M").unwrap();
+        assert!(result.repeating);
+        assert_eq!(result.repetitions, Some(3));
+        let starts: Vec<&str> = result
+            .occurrences
+            .iter()
+            .map(|o| o.start.as_str())
+            .collect();
+        // Each occurrence is offset from the first, so day 31 returns after February
+        assert_eq!(
+            starts,
+            [
+                "2024-01-31T09:00:00Z",
+                "2024-02-29T09:00:00Z",
+                "2024-03-31T09:00:00Z"
+            ]
+        );
+        assert_eq!(result.occurrences[2].end, "2024-04-30T09:00:00Z");
+
+        let result = parse("R2/2024-05-01/2024-05-08").unwrap();
+        assert_eq!(result.occurrences[1].start, "2024-05-08");
+        assert_eq!(result.occurrences[1].end, "2024-05-15");
+
+        let result = parse("R2/P1D/2024-05-10").unwrap();
+        assert_eq!(result.occurrences[0].start, "2024-05-09");
+        assert_eq!(result.occurrences[1].start, "2024-05-08");
+        assert_eq!(result.occurrences[1].end, "2024-05-09");
+    }
+
+    #[test]
+    fn test_unbounded_repeat_is_truncated() {
+        let result = parse_interval(IsoIntervalInput {
+            interval: "R/2024-01-01T00:00:00Z/PT6H".to_string(),
+            max_occurrences: Some(5),
+        })
+        .unwrap();
+        assert_eq!(result.repetitions, None);
+        assert_eq!(result.occurrences.len(), 5);
+        assert!(result.truncated);
+        assert_eq!(result.occurrences[4].start, "2024-01-02T00:00:00Z");
+
+        let result = parse("R0/2024-01-01/P1D").unwrap();
+        assert!(result.occurrences.is_empty());
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_invalid_intervals() {
+        assert!(parse("2024-03-01").unwrap_err().contains("two parts"));
+        assert!(parse("P1D/P2D").unwrap_err().contains("two durations"));
+        assert!(
+            parse("2024-03-02/2024-03-01")
+                .unwrap_err()
+                .contains("ends before")
+        );
+        assert!(
+            parse("2024-02-30/P1D")
+                .unwrap_err()
+                .contains("does not exist")
+        );
+        assert!(
+            parse("2024-03-01/P1.5M")
+                .unwrap_err()
+                .contains("whole numbers")
+        );
+        assert!(
+            parse("2024-03-01/PT")
+                .unwrap_err()
+                .contains("Invalid ISO 8601 duration")
+        );
+        assert!(
+            parse("R/2024-03-01/2024-03-01")
+                .unwrap_err()
+                .contains("zero duration")
+        );
+        assert!(
+            parse("Rx/2024-03-01/P1D")
+                .unwrap_err()
+                .contains("repetition count")
+        );
+        assert!(
+            parse("2024-03-01T10:00:00.5Z/P1D")
+                .unwrap_err()
+                .contains("Fractional")
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    LocalTime as LogicLocalTime, MeetingPlannerInput as LogicInput,
+    MeetingPlannerOutput as LogicOutput, MeetingSlot as LogicSlot, Participant as LogicParticipant,
+    TimeWindow as LogicWindow, plan_meeting,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeetingPlannerInput {
+    /// People to schedule, each with a timezone and working hours
+    pub participants: Vec<Participant>,
+    /// First day to search (YYYY-MM-DD, in the reporting timezone)
+    pub start_date: String,
+    /// Last day to search, inclusive (YYYY-MM-DD, optional, default: start_date).
+    /// At most 92 days after start_date
+    pub end_date: Option<String>,
+    /// Meeting length in minutes
+    pub duration_minutes: u32,
+    /// Spacing between candidate start times in minutes (optional, default: 30)
+    pub step_minutes: Option<u32>,
+    /// IANA timezone for the date range and slot times (optional, default: "UTC")
+    pub timezone: Option<String>,
+    /// Most slots to return (optional, default: 50)
+    pub max_slots: Option<usize>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Participant {
+    /// Display name (optional, default: "participant N")
+    pub name: Option<String>,
+    /// IANA timezone, like "America/New_York" or "Asia/Kolkata"
+    pub timezone: String,
+    /// Local start of working hours, HH:MM (optional, default: "09:00")
+    pub work_start: Option<String>,
+    /// Local end of working hours, HH:MM (optional, default: "17:00").
+    /// Earlier than work_start for hours that run past midnight
+    pub work_end: Option<String>,
+    /// Local working weekdays (optional, default: monday to friday)
+    /// Accepts full or short names like "monday" or "mon"
+    pub working_days: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeetingPlannerOutput {
+    /// Timezone used for the date range and slot times
+    pub timezone: String,
+    pub duration_minutes: u32,
+    /// Meeting slots inside everyone's working hours, earliest first
+    pub slots: Vec<MeetingSlot>,
+    /// Number of slots found, including any beyond max_slots
+    pub total_slots: usize,
+    /// Whether slots were left out because of max_slots
+    pub truncated: bool,
+    /// Stretches of time when everyone is working
+    pub common_windows: Vec<TimeWindow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeetingSlot {
+    /// Start in the reporting timezone (RFC 3339)
+    pub start: String,
+    /// End in the reporting timezone (RFC 3339)
+    pub end: String,
+    /// Start in UTC (RFC 3339)
+    pub start_utc: String,
+    /// The slot in each participant's local time, in input order
+    pub local_times: Vec<LocalTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LocalTime {
+    pub name: String,
+    pub timezone: String,
+    /// Local start (RFC 3339)
+    pub start: String,
+    /// Local end (RFC 3339)
+    pub end: String,
+    /// Local weekday of the start, like "Monday"
+    pub weekday: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TimeWindow {
+    /// Start in the reporting timezone (RFC 3339)
+    pub start: String,
+    /// End in the reporting timezone (RFC 3339)
+    pub end: String,
+    pub minutes: i64,
+}
+
+/// Find meeting slots of a given length that fall inside every participant's local
+/// working hours over a date range, using the IANA timezone database so daylight saving
+/// changes are handled, with each slot shown in every participant's local time
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn meeting_planner(request: Request<MeetingPlannerInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "meeting_planner",
+                MeetingPlannerInput,
+                MeetingPlannerOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("meeting_planner", &input);
+    log.finish(run_meeting_planner(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_meeting_planner(input: MeetingPlannerInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("participants", &input.participants)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        participants: input
+            .participants
+            .into_iter()
+            .map(|p| LogicParticipant {
+                name: p.name,
+                timezone: p.timezone,
+                work_start: p.work_start,
+                work_end: p.work_end,
+                working_days: p.working_days,
+            })
+            .collect(),
+        start_date: input.start_date,
+        end_date: input.end_date,
+        duration_minutes: input.duration_minutes,
+        step_minutes: input.step_minutes,
+        timezone: input.timezone,
+        max_slots: input.max_slots,
+    };
+
+    // Call logic implementation
+    let result = match logic::plan_meeting(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = MeetingPlannerOutput {
+        timezone: result.timezone,
+        duration_minutes: result.duration_minutes,
+        slots: result
+            .slots
+            .into_iter()
+            .map(|s| MeetingSlot {
+                start: s.start,
+                end: s.end,
+                start_utc: s.start_utc,
+                local_times: s
+                    .local_times
+                    .into_iter()
+                    .map(|l| LocalTime {
+                        name: l.name,
+                        timezone: l.timezone,
+                        start: l.start,
+                        end: l.end,
+                        weekday: l.weekday,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        total_slots: result.total_slots,
+        truncated: result.truncated,
+        common_windows: result
+            .common_windows
+            .into_iter()
+            .map(|w| TimeWindow {
+                start: w.start,
+                end: w.end,
+                minutes: w.minutes,
+            })
+            .collect(),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
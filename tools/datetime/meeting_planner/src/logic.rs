@@ -0,0 +1,583 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_WORK_START: &str = "09:00";
+const DEFAULT_WORK_END: &str = "17:00";
+const DEFAULT_STEP_MINUTES: u32 = 30;
+const DEFAULT_MAX_SLOTS: usize = 50;
+/// Longest date range searched in one call
+pub const MAX_RANGE_DAYS: i64 = 92;
+/// Most participants in one call
+pub const MAX_PARTICIPANTS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingPlannerInput {
+    /// People to schedule, each with a timezone and working hours
+    pub participants: Vec<Participant>,
+    /// First day to search (YYYY-MM-DD, in the reporting timezone)
+    pub start_date: String,
+    /// Last day to search, inclusive (YYYY-MM-DD, default: start_date)
+    pub end_date: Option<String>,
+    /// Meeting length in minutes
+    pub duration_minutes: u32,
+    /// Spacing between candidate start times in minutes (default: 30)
+    pub step_minutes: Option<u32>,
+    /// IANA timezone for the date range and slot times (default: "UTC")
+    pub timezone: Option<String>,
+    /// Most slots to return (default: 50)
+    pub max_slots: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Participant {
+    /// Display name (default: "participant N")
+    pub name: Option<String>,
+    /// IANA timezone, like "America/New_York" or "Asia/Kolkata"
+    pub timezone: String,
+    /// Local start of working hours, HH:MM (default: "09:00")
+    pub work_start: Option<String>,
+    /// Local end of working hours, HH:MM (default: "17:00"); earlier than work_start
+    /// for hours that run past midnight
+    pub work_end: Option<String>,
+    /// Local working weekdays (default: monday to friday)
+    pub working_days: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingPlannerOutput {
+    /// Timezone used for the date range and slot times
+    pub timezone: String,
+    pub duration_minutes: u32,
+    /// Meeting slots inside everyone's working hours, earliest first
+    pub slots: Vec<MeetingSlot>,
+    /// Number of slots found, including any beyond max_slots
+    pub total_slots: usize,
+    /// Whether slots were left out because of max_slots
+    pub truncated: bool,
+    /// Stretches of time when everyone is working
+    pub common_windows: Vec<TimeWindow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MeetingSlot {
+    /// Start in the reporting timezone (RFC 3339)
+    pub start: String,
+    /// End in the reporting timezone (RFC 3339)
+    pub end: String,
+    /// Start in UTC (RFC 3339)
+    pub start_utc: String,
+    /// The slot in each participant's local time, in input order
+    pub local_times: Vec<LocalTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LocalTime {
+    pub name: String,
+    pub timezone: String,
+    /// Local start (RFC 3339)
+    pub start: String,
+    /// Local end (RFC 3339)
+    pub end: String,
+    /// Local weekday of the start, like "Monday"
+    pub weekday: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimeWindow {
+    /// Start in the reporting timezone (RFC 3339)
+    pub start: String,
+    /// End in the reporting timezone (RFC 3339)
+    pub end: String,
+    pub minutes: i64,
+}
+
+/// A participant's settings in parsed form
+struct Schedule {
+    name: String,
+    tz: Tz,
+    work_start: i64,
+    work_end: i64,
+    working_days: Vec<Weekday>,
+}
+
+fn parse_date(value: &str, field: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), DATE_FORMAT)
+        .map_err(|_| format!("Invalid {field} '{value}'. Expected format YYYY-MM-DD"))
+}
+
+fn parse_timezone(value: &str) -> Result<Tz, String> {
+    value
+        .trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("Unknown timezone '{value}'. Use an IANA name like 'Europe/Berlin'"))
+}
+
+/// Minutes after midnight for "HH:MM", allowing "24:00" for the end of the day
+fn parse_clock(value: &str, field: &str) -> Result<i64, String> {
+    let invalid = || format!("Invalid {field} '{value}'. Expected format HH:MM");
+    let (hours, minutes) = value.trim().split_once(':').ok_or_else(invalid)?;
+    let hours: i64 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+    if !(0..60).contains(&minutes) || !(0..=24).contains(&hours) || (hours == 24 && minutes > 0) {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+fn parse_working_days(days: Option<&[String]>) -> Result<Vec<Weekday>, String> {
+    let Some(days) = days else {
+        return Ok(vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]);
+    };
+
+    let mut parsed = Vec::new();
+    for day in days {
+        let weekday = day.trim().parse::<Weekday>().map_err(|_| {
+            format!("Invalid working day '{day}'. Use names like 'monday' or 'mon'")
+        })?;
+        if !parsed.contains(&weekday) {
+            parsed.push(weekday);
+        }
+    }
+
+    if parsed.is_empty() {
+        return Err("At least one working day is required".to_string());
+    }
+    Ok(parsed)
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Minutes since the Unix epoch of a local wall-clock time. Times skipped by a DST
+/// change move forward by the size of the gap, and repeated times take the first one.
+fn local_to_utc(tz: &Tz, local: NaiveDateTime) -> Result<i64, String> {
+    (0..=3)
+        .find_map(|half_hours| {
+            tz.from_local_datetime(&(local + Duration::minutes(half_hours * 30)))
+                .earliest()
+        })
+        .map(|dt| dt.timestamp().div_euclid(60))
+        .ok_or_else(|| format!("Cannot resolve local time {local} in {tz}"))
+}
+
+/// RFC 3339 time for minutes since the Unix epoch, in the given timezone
+fn format_time(tz: &Tz, minutes: i64) -> Result<(String, Weekday), String> {
+    let dt = tz
+        .timestamp_opt(minutes * 60, 0)
+        .single()
+        .ok_or_else(|| "Time is out of range".to_string())?;
+    let local = dt.naive_local();
+    let offset = dt.offset().fix().local_minus_utc() / 60;
+    let sign = if offset < 0 { '-' } else { '+' };
+    Ok((
+        format!(
+            "{}{sign}{:02}:{:02}",
+            local.format("%Y-%m-%dT%H:%M:%S"),
+            offset.abs() / 60,
+            offset.abs() % 60
+        ),
+        local.date().weekday(),
+    ))
+}
+
+fn format_utc(minutes: i64) -> Result<String, String> {
+    let (text, _) = format_time(&Tz::UTC, minutes)?;
+    Ok(text.replace("+00:00", "Z"))
+}
+
+/// Working intervals of one participant, in epoch minutes, over local dates first..=last
+fn working_intervals(
+    schedule: &Schedule,
+    first: NaiveDate,
+    last: NaiveDate,
+) -> Result<Vec<(i64, i64)>, String> {
+    let mut intervals: Vec<(i64, i64)> = Vec::new();
+    let mut date = first;
+    while date <= last {
+        if schedule.working_days.contains(&date.weekday()) {
+            let midnight = date
+                .and_hms_opt(0, 0, 0)
+                .ok_or_else(|| "Date is out of range".to_string())?;
+            let start = local_to_utc(
+                &schedule.tz,
+                midnight + Duration::minutes(schedule.work_start),
+            )?;
+            let end = local_to_utc(
+                &schedule.tz,
+                midnight + Duration::minutes(schedule.work_end),
+            )?;
+            if end > start {
+                // Merge hours that continue from the previous day's overnight shift
+                match intervals.last_mut() {
+                    Some(previous) if previous.1 >= start => previous.1 = previous.1.max(end),
+                    _ => intervals.push((start, end)),
+                }
+            }
+        }
+        date += Duration::days(1);
+    }
+    Ok(intervals)
+}
+
+/// Overlap of two sorted lists of disjoint intervals
+fn intersect(a: &[(i64, i64)], b: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+    while i < a.len() && j < b.len() {
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start < end {
+            result.push((start, end));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+pub fn plan_meeting(input: MeetingPlannerInput) -> Result<MeetingPlannerOutput, String> {
+    if input.participants.is_empty() {
+        return Err("At least one participant is required".to_string());
+    }
+    if input.participants.len() > MAX_PARTICIPANTS {
+        return Err(format!(
+            "At most {MAX_PARTICIPANTS} participants are supported"
+        ));
+    }
+    if input.duration_minutes == 0 {
+        return Err("duration_minutes must be greater than 0".to_string());
+    }
+    let step = input.step_minutes.unwrap_or(DEFAULT_STEP_MINUTES);
+    if step == 0 {
+        return Err("step_minutes must be greater than 0".to_string());
+    }
+    let max_slots = input.max_slots.unwrap_or(DEFAULT_MAX_SLOTS);
+
+    let timezone = input.timezone.as_deref().unwrap_or("UTC");
+    let reference = parse_timezone(timezone)?;
+    let start_date = parse_date(&input.start_date, "start_date")?;
+    let end_date = match &input.end_date {
+        Some(end) => parse_date(end, "end_date")?,
+        None => start_date,
+    };
+    if end_date < start_date {
+        return Err("end_date must not be before start_date".to_string());
+    }
+    if (end_date - start_date).num_days() >= MAX_RANGE_DAYS {
+        return Err(format!(
+            "The date range can span at most {MAX_RANGE_DAYS} days"
+        ));
+    }
+
+    let schedules = input
+        .participants
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let name = p
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("participant {}", i + 1));
+            let work_start = parse_clock(
+                p.work_start.as_deref().unwrap_or(DEFAULT_WORK_START),
+                "work_start",
+            )
+            .map_err(|e| format!("{name}: {e}"))?;
+            let mut work_end = parse_clock(
+                p.work_end.as_deref().unwrap_or(DEFAULT_WORK_END),
+                "work_end",
+            )
+            .map_err(|e| format!("{name}: {e}"))?;
+            if work_end == work_start {
+                return Err(format!("{name}: work_start and work_end must differ"));
+            }
+            // Hours past midnight end on the next local day
+            if work_end < work_start {
+                work_end += 24 * 60;
+            }
+            Ok(Schedule {
+                tz: parse_timezone(&p.timezone).map_err(|e| format!("{name}: {e}"))?,
+                working_days: parse_working_days(p.working_days.as_deref())
+                    .map_err(|e| format!("{name}: {e}"))?,
+                name,
+                work_start,
+                work_end,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    // Search range in epoch minutes, from the first midnight to after the last day
+    let range_start = local_to_utc(
+        &reference,
+        start_date
+            .and_hms_opt(0, 0, 0)
+            .ok_or("Date is out of range")?,
+    )?;
+    let range_end = local_to_utc(
+        &reference,
+        (end_date + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .ok_or("Date is out of range")?,
+    )?;
+
+    // Local dates can differ from the reporting dates by up to a day either way
+    let mut common = vec![(range_start, range_end)];
+    for schedule in &schedules {
+        let intervals = working_intervals(
+            schedule,
+            start_date - Duration::days(2),
+            end_date + Duration::days(1),
+        )?;
+        common = intersect(&common, &intervals);
+    }
+
+    let duration = i64::from(input.duration_minutes);
+    let step = i64::from(step);
+    let mut slots = Vec::new();
+    let mut total_slots = 0;
+    for &(window_start, window_end) in &common {
+        // Candidate starts sit on a grid of step minutes from the start of the range
+        let offset = (window_start - range_start).rem_euclid(step);
+        let mut start = if offset == 0 {
+            window_start
+        } else {
+            window_start + step - offset
+        };
+        while start + duration <= window_end {
+            total_slots += 1;
+            if slots.len() < max_slots {
+                let end = start + duration;
+                let local_times = schedules
+                    .iter()
+                    .map(|s| {
+                        let (local_start, weekday) = format_time(&s.tz, start)?;
+                        Ok(LocalTime {
+                            name: s.name.clone(),
+                            timezone: s.tz.name().to_string(),
+                            start: local_start,
+                            end: format_time(&s.tz, end)?.0,
+                            weekday: weekday_name(weekday).to_string(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                slots.push(MeetingSlot {
+                    start: format_time(&reference, start)?.0,
+                    end: format_time(&reference, end)?.0,
+                    start_utc: format_utc(start)?,
+                    local_times,
+                });
+            }
+            start += step;
+        }
+    }
+
+    let common_windows = common
+        .iter()
+        .map(|&(start, end)| {
+            Ok(TimeWindow {
+                start: format_time(&reference, start)?.0,
+                end: format_time(&reference, end)?.0,
+                minutes: end - start,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(MeetingPlannerOutput {
+        timezone: reference.name().to_string(),
+        duration_minutes: input.duration_minutes,
+        truncated: total_slots > slots.len(),
+        slots,
+        total_slots,
+        common_windows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(name: &str, timezone: &str) -> Participant {
+        Participant {
+            name: Some(name.to_string()),
+            timezone: timezone.to_string(),
+            work_start: None,
+            work_end: None,
+            working_days: None,
+        }
+    }
+
+    fn input(participants: Vec<Participant>, start_date: &str) -> MeetingPlannerInput {
+        MeetingPlannerInput {
+            participants,
+            start_date: start_date.to_string(),
+            end_date: None,
+            duration_minutes: 60,
+            step_minutes: None,
+            timezone: None,
+            max_slots: None,
+        }
+    }
+
+    #[test]
+    fn test_new_york_and_berlin() {
+        // Wednesday 2024-01-10: New York is UTC-5, Berlin UTC+1, so 9-17 local overlap
+        // from 14:00 to 16:00 UTC
+        let result = plan_meeting(input(
+            vec![
+                participant("ana", "America/New_York"),
+                participant("ben", "Europe/Berlin"),
+            ],
+            "2024-01-10",
+        ))
+        .unwrap();
+
+        assert_eq!(result.common_windows.len(), 1);
+        assert_eq!(result.common_windows[0].start, "2024-01-10T14:00:00+00:00");
+        assert_eq!(result.common_windows[0].minutes, 120);
+
+        assert_eq!(result.total_slots, 3);
+        assert!(!result.truncated);
+        let first = &result.slots[0];
+        assert_eq!(first.start_utc, "2024-01-10T14:00:00Z");
+        assert_eq!(first.local_times[0].start, "2024-01-10T09:00:00-05:00");
+        assert_eq!(first.local_times[1].start, "2024-01-10T15:00:00+01:00");
+        assert_eq!(first.local_times[1].end, "2024-01-10T16:00:00+01:00");
+        assert_eq!(result.slots[2].start, "2024-01-10T15:00:00+00:00");
+    }
+
+    #[test]
+    fn test_daylight_saving_shift() {
+        // US clocks change on 2024-03-10, Europe's on 2024-03-31: in between the
+        // overlap grows to three hours
+        let mut request = input(
+            vec![
+                participant("ana", "America/New_York"),
+                participant("ben", "Europe/Berlin"),
+            ],
+            "2024-03-11",
+        );
+        request.step_minutes = Some(60);
+        let result = plan_meeting(request).unwrap();
+        assert_eq!(result.common_windows[0].start, "2024-03-11T13:00:00+00:00");
+        assert_eq!(result.common_windows[0].minutes, 180);
+        assert_eq!(result.total_slots, 3);
+        assert_eq!(
+            result.slots[0].local_times[0].start,
+            "2024-03-11T09:00:00-04:00"
+        );
+    }
+
+    #[test]
+    fn test_reporting_timezone_and_weekends() {
+        // Friday to Monday in Tokyo time; the weekend has no working hours
+        let mut tokyo = participant("kei", "Asia/Tokyo");
+        tokyo.work_start = Some("08:00".to_string());
+        tokyo.work_end = Some("20:00".to_string());
+        let mut request = input(
+            vec![tokyo, participant("raj", "Asia/Kolkata")],
+            "2024-01-12",
+        );
+        request.end_date = Some("2024-01-15".to_string());
+        request.timezone = Some("Asia/Tokyo".to_string());
+        request.duration_minutes = 30;
+        let result = plan_meeting(request).unwrap();
+
+        // Kolkata 09:00-17:00 is 12:30-20:30 in Tokyo, cut at 20:00
+        assert_eq!(result.timezone, "Asia/Tokyo");
+        assert_eq!(result.common_windows.len(), 2);
+        assert_eq!(result.common_windows[0].start, "2024-01-12T12:30:00+09:00");
+        assert_eq!(result.common_windows[0].end, "2024-01-12T20:00:00+09:00");
+        assert_eq!(result.common_windows[1].start, "2024-01-15T12:30:00+09:00");
+        assert_eq!(result.total_slots, 30);
+        assert_eq!(
+            result.slots[0].local_times[1].start,
+            "2024-01-12T09:00:00+05:30"
+        );
+        assert_eq!(result.slots[0].local_times[1].weekday, "Friday");
+    }
+
+    #[test]
+    fn test_overnight_hours_and_no_overlap() {
+        // A night shift 22:00-06:00 UTC overlaps Tokyo's 09:00-17:00 (00:00-08:00 UTC)
+        let mut night = participant("nia", "UTC");
+        night.work_start = Some("22:00".to_string());
+        night.work_end = Some("06:00".to_string());
+        night.working_days = Some(
+            ["mon", "tue", "wed", "thu", "fri", "sat", "sun"]
+                .map(String::from)
+                .to_vec(),
+        );
+        let mut request = input(vec![night, participant("kei", "Asia/Tokyo")], "2024-01-10");
+        request.max_slots = Some(2);
+        let result = plan_meeting(request).unwrap();
+        assert_eq!(result.common_windows[0].start, "2024-01-10T00:00:00+00:00");
+        assert_eq!(result.common_windows[0].minutes, 360);
+        assert_eq!(result.total_slots, 11);
+        assert!(result.truncated);
+        assert_eq!(result.slots.len(), 2);
+
+        // New York and Tokyo office hours never meet
+        let result = plan_meeting(input(
+            vec![
+                participant("ana", "America/New_York"),
+                participant("kei", "Asia/Tokyo"),
+            ],
+            "2024-01-10",
+        ))
+        .unwrap();
+        assert!(result.slots.is_empty());
+        assert!(result.common_windows.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |request: MeetingPlannerInput| plan_meeting(request).unwrap_err();
+
+        assert!(err(input(vec![], "2024-01-10")).contains("At least one participant"));
+        assert!(
+            err(input(vec![participant("x", "Mars/Olympus")], "2024-01-10"))
+                .contains("x: Unknown timezone 'Mars/Olympus'")
+        );
+
+        let mut bad_hours = participant("x", "UTC");
+        bad_hours.work_start = Some("9am".to_string());
+        assert!(err(input(vec![bad_hours], "2024-01-10")).contains("Invalid work_start"));
+
+        let mut same = participant("x", "UTC");
+        same.work_start = Some("09:00".to_string());
+        same.work_end = Some("09:00".to_string());
+        assert!(err(input(vec![same], "2024-01-10")).contains("must differ"));
+
+        let mut backwards = input(vec![participant("x", "UTC")], "2024-01-10");
+        backwards.end_date = Some("2024-01-09".to_string());
+        assert!(err(backwards).contains("before start_date"));
+
+        let mut long = input(vec![participant("x", "UTC")], "2024-01-01");
+        long.end_date = Some("2024-12-31".to_string());
+        assert!(err(long).contains("at most"));
+
+        let mut zero = input(vec![participant("x", "UTC")], "2024-01-10");
+        zero.duration_minutes = 0;
+        assert!(err(zero).contains("duration_minutes"));
+    }
+}
@@ -0,0 +1,178 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    ProjectScheduleInput as LogicInput, ProjectScheduleOutput as LogicOutput,
+    TaskInput as LogicTask, schedule_project,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectScheduleInput {
+    /// Project start date (YYYY-MM-DD). Moved forward to the next working day if needed
+    pub start_date: String,
+    /// Tasks to schedule, with durations and finish-to-start dependencies
+    pub tasks: Vec<TaskInput>,
+    /// Non-working dates to skip (YYYY-MM-DD)
+    pub holidays: Option<Vec<String>>,
+    /// Working weekdays (optional, default: monday to friday)
+    /// Accepts full or short names like "monday" or "mon"
+    pub working_days: Option<Vec<String>>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TaskInput {
+    /// Unique task identifier
+    pub id: String,
+    /// Optional display name
+    pub name: Option<String>,
+    /// Duration in working days (0 for a milestone)
+    pub duration_days: u32,
+    /// IDs of tasks that must finish before this one starts
+    pub depends_on: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectScheduleOutput {
+    /// Scheduled tasks in input order
+    pub tasks: Vec<ScheduledTask>,
+    /// First working day of the project
+    pub project_start: String,
+    /// Last working day of the project
+    pub project_end: String,
+    /// Total project duration in working days
+    pub total_working_days: u32,
+    /// Total project duration in calendar days (inclusive)
+    pub total_calendar_days: i64,
+    /// Task IDs along the critical path, in execution order
+    pub critical_path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduledTask {
+    pub id: String,
+    pub name: Option<String>,
+    pub duration_days: u32,
+    pub depends_on: Vec<String>,
+    /// First working day of the task (YYYY-MM-DD)
+    pub start_date: String,
+    /// Last working day of the task (YYYY-MM-DD)
+    pub end_date: String,
+    /// Earliest start, in working days from project start
+    pub earliest_start: u32,
+    /// Earliest finish, in working days from project start
+    pub earliest_finish: u32,
+    /// Latest start that does not delay the project
+    pub latest_start: u32,
+    /// Latest finish that does not delay the project
+    pub latest_finish: u32,
+    /// Working days the task can slip without delaying the project
+    pub slack_days: u32,
+    /// Whether the task is on the critical path (zero slack)
+    pub is_critical: bool,
+}
+
+/// Expand tasks with durations and finish-to-start dependencies into a dated
+/// schedule over business days, with per-task slack and the critical path
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn project_schedule(request: Request<ProjectScheduleInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "project_schedule",
+                ProjectScheduleInput,
+                ProjectScheduleOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("project_schedule", &input);
+    log.finish(run_project_schedule(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_project_schedule(input: ProjectScheduleInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("tasks", &input.tasks)
+        .array("holidays", input.holidays.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        start_date: input.start_date,
+        tasks: input
+            .tasks
+            .into_iter()
+            .map(|t| LogicTask {
+                id: t.id,
+                name: t.name,
+                duration_days: t.duration_days,
+                depends_on: t.depends_on,
+            })
+            .collect(),
+        holidays: input.holidays,
+        working_days: input.working_days,
+    };
+
+    // Call logic implementation
+    let result = match logic::schedule_project(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = ProjectScheduleOutput {
+        tasks: result
+            .tasks
+            .into_iter()
+            .map(|t| ScheduledTask {
+                id: t.id,
+                name: t.name,
+                duration_days: t.duration_days,
+                depends_on: t.depends_on,
+                start_date: t.start_date,
+                end_date: t.end_date,
+                earliest_start: t.earliest_start,
+                earliest_finish: t.earliest_finish,
+                latest_start: t.latest_start,
+                latest_finish: t.latest_finish,
+                slack_days: t.slack_days,
+                is_critical: t.is_critical,
+            })
+            .collect(),
+        project_start: result.project_start,
+        project_end: result.project_end,
+        total_working_days: result.total_working_days,
+        total_calendar_days: result.total_calendar_days,
+        critical_path: result.critical_path,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
@@ -0,0 +1,491 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Upper bound on the number of calendar days scanned while laying out working days
+const MAX_CALENDAR_DAYS: i64 = 366 * 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectScheduleInput {
+    /// Project start date (YYYY-MM-DD)
+    pub start_date: String,
+    /// Tasks to schedule
+    pub tasks: Vec<TaskInput>,
+    /// Non-working dates (YYYY-MM-DD)
+    pub holidays: Option<Vec<String>>,
+    /// Working weekdays (default: Monday to Friday)
+    pub working_days: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskInput {
+    /// Unique task identifier
+    pub id: String,
+    /// Optional display name
+    pub name: Option<String>,
+    /// Duration in working days (0 for a milestone)
+    pub duration_days: u32,
+    /// IDs of tasks that must finish before this one starts (finish-to-start)
+    pub depends_on: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectScheduleOutput {
+    /// Scheduled tasks in input order
+    pub tasks: Vec<ScheduledTask>,
+    /// First working day of the project
+    pub project_start: String,
+    /// Last working day of the project
+    pub project_end: String,
+    /// Total project duration in working days
+    pub total_working_days: u32,
+    /// Total project duration in calendar days (inclusive)
+    pub total_calendar_days: i64,
+    /// Task IDs along the critical path, in execution order
+    pub critical_path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    pub id: String,
+    pub name: Option<String>,
+    pub duration_days: u32,
+    pub depends_on: Vec<String>,
+    /// First working day of the task (YYYY-MM-DD)
+    pub start_date: String,
+    /// Last working day of the task (YYYY-MM-DD)
+    pub end_date: String,
+    /// Earliest start, in working days from project start
+    pub earliest_start: u32,
+    /// Earliest finish, in working days from project start
+    pub earliest_finish: u32,
+    /// Latest start that does not delay the project
+    pub latest_start: u32,
+    /// Latest finish that does not delay the project
+    pub latest_finish: u32,
+    /// Working days the task can slip without delaying the project
+    pub slack_days: u32,
+    /// Whether the task is on the critical path (zero slack)
+    pub is_critical: bool,
+}
+
+pub fn schedule_project(input: ProjectScheduleInput) -> Result<ProjectScheduleOutput, String> {
+    if input.tasks.is_empty() {
+        return Err("At least one task is required".to_string());
+    }
+
+    let start = parse_date(&input.start_date, "start_date")?;
+    let working_days = parse_working_days(input.working_days.as_deref())?;
+    let holidays = input
+        .holidays
+        .unwrap_or_default()
+        .iter()
+        .map(|h| parse_date(h, "holiday"))
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    let tasks = &input.tasks;
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, task) in tasks.iter().enumerate() {
+        if task.id.trim().is_empty() {
+            return Err(format!("Task at position {i} has an empty id"));
+        }
+        if index.insert(task.id.as_str(), i).is_some() {
+            return Err(format!("Duplicate task id '{}'", task.id));
+        }
+    }
+
+    // Resolve dependencies into index lists
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+    for (i, task) in tasks.iter().enumerate() {
+        for dep in task.depends_on.iter().flatten() {
+            let &j = index
+                .get(dep.as_str())
+                .ok_or_else(|| format!("Task '{}' depends on unknown task '{dep}'", task.id))?;
+            if j == i {
+                return Err(format!("Task '{}' cannot depend on itself", task.id));
+            }
+            if !predecessors[i].contains(&j) {
+                predecessors[i].push(j);
+                successors[j].push(i);
+            }
+        }
+    }
+
+    let order = topological_order(tasks, &predecessors, &successors)?;
+
+    // Forward pass: earliest start/finish
+    let mut earliest_start = vec![0u32; tasks.len()];
+    let mut earliest_finish = vec![0u32; tasks.len()];
+    for &i in &order {
+        earliest_start[i] = predecessors[i]
+            .iter()
+            .map(|&p| earliest_finish[p])
+            .max()
+            .unwrap_or(0);
+        earliest_finish[i] = earliest_start[i] + tasks[i].duration_days;
+    }
+    let project_length = earliest_finish.iter().copied().max().unwrap_or(0);
+
+    // Backward pass: latest start/finish
+    let mut latest_start = vec![0u32; tasks.len()];
+    let mut latest_finish = vec![0u32; tasks.len()];
+    for &i in order.iter().rev() {
+        latest_finish[i] = successors[i]
+            .iter()
+            .map(|&s| latest_start[s])
+            .min()
+            .unwrap_or(project_length);
+        latest_start[i] = latest_finish[i] - tasks[i].duration_days;
+    }
+
+    let calendar = WorkingCalendar::new(start, &working_days, &holidays, project_length)?;
+
+    let scheduled: Vec<ScheduledTask> = tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            let start_date = calendar.day(earliest_start[i]);
+            let end_date = if task.duration_days == 0 {
+                start_date
+            } else {
+                calendar.day(earliest_finish[i] - 1)
+            };
+            let slack_days = latest_start[i] - earliest_start[i];
+            ScheduledTask {
+                id: task.id.clone(),
+                name: task.name.clone(),
+                duration_days: task.duration_days,
+                depends_on: predecessors[i]
+                    .iter()
+                    .map(|&p| tasks[p].id.clone())
+                    .collect(),
+                start_date: start_date.format(DATE_FORMAT).to_string(),
+                end_date: end_date.format(DATE_FORMAT).to_string(),
+                earliest_start: earliest_start[i],
+                earliest_finish: earliest_finish[i],
+                latest_start: latest_start[i],
+                latest_finish: latest_finish[i],
+                slack_days,
+                is_critical: slack_days == 0,
+            }
+        })
+        .collect();
+
+    let critical_path = trace_critical_path(
+        &order,
+        &successors,
+        &earliest_start,
+        &earliest_finish,
+        &scheduled,
+    );
+
+    let project_start = calendar.day(0);
+    let project_end = if project_length == 0 {
+        project_start
+    } else {
+        calendar.day(project_length - 1)
+    };
+
+    Ok(ProjectScheduleOutput {
+        tasks: scheduled,
+        project_start: project_start.format(DATE_FORMAT).to_string(),
+        project_end: project_end.format(DATE_FORMAT).to_string(),
+        total_working_days: project_length,
+        total_calendar_days: (project_end - project_start).num_days() + 1,
+        critical_path,
+    })
+}
+
+/// Maps working-day offsets (0 = first working day) to calendar dates
+struct WorkingCalendar {
+    days: Vec<NaiveDate>,
+}
+
+impl WorkingCalendar {
+    fn new(
+        start: NaiveDate,
+        working_days: &[Weekday],
+        holidays: &HashSet<NaiveDate>,
+        length: u32,
+    ) -> Result<Self, String> {
+        // One extra day so zero-length tasks at the end still map to a date
+        let needed = length as usize + 1;
+        let mut days = Vec::with_capacity(needed);
+        let mut date = start;
+        let mut scanned = 0;
+
+        while days.len() < needed {
+            if scanned > MAX_CALENDAR_DAYS {
+                return Err("Schedule extends too far; check working_days and holidays".to_string());
+            }
+            if working_days.contains(&date.weekday()) && !holidays.contains(&date) {
+                days.push(date);
+            }
+            date += Duration::days(1);
+            scanned += 1;
+        }
+
+        Ok(Self { days })
+    }
+
+    fn day(&self, offset: u32) -> NaiveDate {
+        self.days[offset as usize]
+    }
+}
+
+fn parse_date(value: &str, field: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), DATE_FORMAT)
+        .map_err(|_| format!("Invalid {field} '{value}'. Expected format YYYY-MM-DD"))
+}
+
+fn parse_working_days(days: Option<&[String]>) -> Result<Vec<Weekday>, String> {
+    let Some(days) = days else {
+        return Ok(vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]);
+    };
+
+    let mut parsed = Vec::new();
+    for day in days {
+        let weekday = day.trim().parse::<Weekday>().map_err(|_| {
+            format!("Invalid working day '{day}'. Use names like 'monday' or 'mon'")
+        })?;
+        if !parsed.contains(&weekday) {
+            parsed.push(weekday);
+        }
+    }
+
+    if parsed.is_empty() {
+        return Err("At least one working day is required".to_string());
+    }
+    Ok(parsed)
+}
+
+/// Kahn's algorithm, preferring input order among ready tasks
+fn topological_order(
+    tasks: &[TaskInput],
+    predecessors: &[Vec<usize>],
+    successors: &[Vec<usize>],
+) -> Result<Vec<usize>, String> {
+    let mut remaining: Vec<usize> = predecessors.iter().map(Vec::len).collect();
+    let mut ready: VecDeque<usize> = (0..tasks.len()).filter(|&i| remaining[i] == 0).collect();
+    let mut order = Vec::with_capacity(tasks.len());
+
+    while let Some(i) = ready.pop_front() {
+        order.push(i);
+        for &s in &successors[i] {
+            remaining[s] -= 1;
+            if remaining[s] == 0 {
+                ready.push_back(s);
+            }
+        }
+    }
+
+    if order.len() != tasks.len() {
+        let cyclic: Vec<&str> = (0..tasks.len())
+            .filter(|&i| remaining[i] > 0)
+            .map(|i| tasks[i].id.as_str())
+            .collect();
+        return Err(format!(
+            "Dependency cycle detected involving tasks: {}",
+            cyclic.join(", ")
+        ));
+    }
+    Ok(order)
+}
+
+/// Follows zero-slack tasks from project start to project end
+fn trace_critical_path(
+    order: &[usize],
+    successors: &[Vec<usize>],
+    earliest_start: &[u32],
+    earliest_finish: &[u32],
+    scheduled: &[ScheduledTask],
+) -> Vec<String> {
+    let Some(mut current) = order
+        .iter()
+        .copied()
+        .find(|&i| scheduled[i].is_critical && earliest_start[i] == 0)
+    else {
+        return Vec::new();
+    };
+
+    let mut path = vec![scheduled[current].id.clone()];
+    while let Some(next) = successors[current]
+        .iter()
+        .copied()
+        .find(|&s| scheduled[s].is_critical && earliest_start[s] == earliest_finish[current])
+    {
+        path.push(scheduled[next].id.clone());
+        current = next;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, duration_days: u32, depends_on: &[&str]) -> TaskInput {
+        TaskInput {
+            id: id.to_string(),
+            name: None,
+            duration_days,
+            depends_on: if depends_on.is_empty() {
+                None
+            } else {
+                Some(depends_on.iter().map(|d| d.to_string()).collect())
+            },
+        }
+    }
+
+    fn input(start_date: &str, tasks: Vec<TaskInput>) -> ProjectScheduleInput {
+        ProjectScheduleInput {
+            start_date: start_date.to_string(),
+            tasks,
+            holidays: None,
+            working_days: None,
+        }
+    }
+
+    fn find<'a>(output: &'a ProjectScheduleOutput, id: &str) -> &'a ScheduledTask {
+        output.tasks.iter().find(|t| t.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_single_task_skips_weekend() {
+        // 2024-01-05 is a Friday
+        let result = schedule_project(input("2024-01-05", vec![task("a", 3, &[])])).unwrap();
+
+        let a = find(&result, "a");
+        assert_eq!(a.start_date, "2024-01-05");
+        assert_eq!(a.end_date, "2024-01-09");
+        assert_eq!(result.total_working_days, 3);
+        assert_eq!(result.total_calendar_days, 5);
+    }
+
+    #[test]
+    fn test_start_on_weekend_moves_to_monday() {
+        // 2024-01-06 is a Saturday
+        let result = schedule_project(input("2024-01-06", vec![task("a", 1, &[])])).unwrap();
+
+        assert_eq!(result.project_start, "2024-01-08");
+        assert_eq!(find(&result, "a").end_date, "2024-01-08");
+    }
+
+    #[test]
+    fn test_finish_to_start_dependencies() {
+        let result = schedule_project(input(
+            "2024-01-01",
+            vec![task("design", 2, &[]), task("build", 3, &["design"])],
+        ))
+        .unwrap();
+
+        let build = find(&result, "build");
+        assert_eq!(build.earliest_start, 2);
+        assert_eq!(build.start_date, "2024-01-03");
+        assert_eq!(build.end_date, "2024-01-05");
+        assert_eq!(result.project_end, "2024-01-05");
+    }
+
+    #[test]
+    fn test_critical_path_and_slack() {
+        let result = schedule_project(input(
+            "2024-01-01",
+            vec![
+                task("start", 1, &[]),
+                task("long", 5, &["start"]),
+                task("short", 2, &["start"]),
+                task("finish", 1, &["long", "short"]),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(result.critical_path, vec!["start", "long", "finish"]);
+        assert_eq!(find(&result, "short").slack_days, 3);
+        assert!(!find(&result, "short").is_critical);
+        assert_eq!(find(&result, "long").slack_days, 0);
+        assert_eq!(result.total_working_days, 7);
+    }
+
+    #[test]
+    fn test_holidays_are_skipped() {
+        let mut request = input("2024-12-24", vec![task("a", 2, &[])]);
+        request.holidays = Some(vec!["2024-12-25".to_string()]);
+
+        let result = schedule_project(request).unwrap();
+        assert_eq!(find(&result, "a").end_date, "2024-12-26");
+    }
+
+    #[test]
+    fn test_custom_working_days() {
+        let mut request = input("2024-01-05", vec![task("a", 2, &[])]);
+        request.working_days = Some(vec![
+            "mon".to_string(),
+            "tue".to_string(),
+            "wed".to_string(),
+            "thu".to_string(),
+            "fri".to_string(),
+            "saturday".to_string(),
+        ]);
+
+        let result = schedule_project(request).unwrap();
+        assert_eq!(find(&result, "a").end_date, "2024-01-06");
+    }
+
+    #[test]
+    fn test_milestone_has_zero_duration() {
+        let result = schedule_project(input(
+            "2024-01-01",
+            vec![task("work", 2, &[]), task("review", 0, &["work"])],
+        ))
+        .unwrap();
+
+        let review = find(&result, "review");
+        assert_eq!(review.start_date, "2024-01-03");
+        assert_eq!(review.end_date, "2024-01-03");
+        assert!(review.is_critical);
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let result = schedule_project(input(
+            "2024-01-01",
+            vec![task("a", 1, &["b"]), task("b", 1, &["a"])],
+        ));
+        assert!(result.unwrap_err().contains("cycle"));
+    }
+
+    #[test]
+    fn test_unknown_dependency() {
+        let result = schedule_project(input("2024-01-01", vec![task("a", 1, &["missing"])]));
+        assert!(result.unwrap_err().contains("unknown task 'missing'"));
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let result = schedule_project(input(
+            "2024-01-01",
+            vec![task("a", 1, &[]), task("a", 2, &[])],
+        ));
+        assert!(result.unwrap_err().contains("Duplicate task id"));
+    }
+
+    #[test]
+    fn test_invalid_start_date() {
+        let result = schedule_project(input("01/02/2024", vec![task("a", 1, &[])]));
+        assert!(result.unwrap_err().contains("Invalid start_date"));
+    }
+
+    #[test]
+    fn test_empty_tasks() {
+        let result = schedule_project(input("2024-01-01", vec![]));
+        assert!(result.is_err());
+    }
+}
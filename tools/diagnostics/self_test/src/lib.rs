@@ -0,0 +1,127 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+use ftl_guard::Guard;
+use ftl_introspect::{Request, describe};
+use ftl_log::ToolLog;
+use ftl_sdk::ToolResponse;
+#[cfg(not(test))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{SelfTestInput as LogicInput, SelfTestOutput as LogicOutput};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SelfTestInput {
+    /// Only run the golden cases for these tools, e.g. ["add", "csv_parser"] (default: all)
+    pub tools: Option<Vec<String>>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CaseFailure {
+    /// Name of the failing case
+    pub case: String,
+    /// What differed from the golden output
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolReport {
+    /// Tool whose logic was exercised
+    pub tool: String,
+    /// Whether every case for this tool passed
+    pub passed: bool,
+    /// Number of cases run
+    pub cases_run: usize,
+    /// Number of cases that passed
+    pub cases_passed: usize,
+    /// Details of each failing case
+    pub failures: Vec<CaseFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SelfTestOutput {
+    /// Whether every tool passed
+    pub passed: bool,
+    /// Number of tools tested
+    pub tools_tested: usize,
+    /// Number of tools with at least one failing case
+    pub tools_failed: usize,
+    /// Total number of cases run
+    pub cases_run: usize,
+    /// Total number of failing cases
+    pub cases_failed: usize,
+    /// Per-tool results
+    pub tools: Vec<ToolReport>,
+}
+
+/// Run golden input/output cases against the logic of every library-mode tool
+/// bundled in this deployment and report pass/fail per tool
+#[cfg_attr(not(test), tool)]
+pub fn self_test(request: Request<SelfTestInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("self_test", SelfTestInput, SelfTestOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("self_test", &input);
+    log.finish(run_self_test(input))
+}
+
+fn run_self_test(input: SelfTestInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("tools", input.tools.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput { tools: input.tools };
+
+    // Call logic implementation
+    let result = match logic::run_self_test(logic_input) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let response = SelfTestOutput {
+        passed: result.passed,
+        tools_tested: result.tools_tested,
+        tools_failed: result.tools_failed,
+        cases_run: result.cases_run,
+        cases_failed: result.cases_failed,
+        tools: result
+            .tools
+            .into_iter()
+            .map(|t| ToolReport {
+                tool: t.tool,
+                passed: t.passed,
+                cases_run: t.cases_run,
+                cases_passed: t.cases_passed,
+                failures: t
+                    .failures
+                    .into_iter()
+                    .map(|f| CaseFailure {
+                        case: f.case,
+                        message: f.message,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&response).unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
@@ -0,0 +1,2712 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestInput {
+    /// Only run the cases for these tools (default: all)
+    pub tools: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseFailure {
+    /// Name of the failing case
+    pub case: String,
+    /// What differed from the golden output
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolReport {
+    /// Tool whose logic was exercised
+    pub tool: String,
+    /// Whether every case for this tool passed
+    pub passed: bool,
+    /// Number of cases run
+    pub cases_run: usize,
+    /// Number of cases that passed
+    pub cases_passed: usize,
+    /// Details of each failing case
+    pub failures: Vec<CaseFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestOutput {
+    /// Whether every tool passed
+    pub passed: bool,
+    /// Number of tools tested
+    pub tools_tested: usize,
+    /// Number of tools with at least one failing case
+    pub tools_failed: usize,
+    /// Total number of cases run
+    pub cases_run: usize,
+    /// Total number of failing cases
+    pub cases_failed: usize,
+    /// Per-tool results
+    pub tools: Vec<ToolReport>,
+}
+
+/// Relative tolerance when comparing numbers against golden values
+const TOLERANCE: f64 = 1e-9;
+
+type Runner = fn(Value) -> Result<Value, String>;
+
+enum Expected {
+    /// The call succeeds and its output contains these fields and values
+    Output(Value),
+    /// The call fails with an error containing this text
+    Error(&'static str),
+}
+
+struct GoldenCase {
+    tool: &'static str,
+    name: &'static str,
+    run: Runner,
+    input: Value,
+    expected: Expected,
+}
+
+fn case(
+    tool: &'static str,
+    name: &'static str,
+    run: Runner,
+    input: Value,
+    output: Value,
+) -> GoldenCase {
+    GoldenCase {
+        tool,
+        name,
+        run,
+        input,
+        expected: Expected::Output(output),
+    }
+}
+
+fn error_case(
+    tool: &'static str,
+    name: &'static str,
+    run: Runner,
+    input: Value,
+    error: &'static str,
+) -> GoldenCase {
+    GoldenCase {
+        tool,
+        name,
+        run,
+        input,
+        expected: Expected::Error(error),
+    }
+}
+
+/// Run a library-mode logic function on a JSON input, the same way its tool
+/// wrapper would
+fn invoke<I: DeserializeOwned, O: Serialize>(
+    logic: fn(I) -> Result<O, String>,
+    input: Value,
+) -> Result<Value, String> {
+    let input = serde_json::from_value(input).map_err(|e| format!("Invalid golden input: {e}"))?;
+    let output = logic(input)?;
+    serde_json::to_value(output).map_err(|e| format!("Failed to serialize output: {e}"))
+}
+
+/// Minimal OpenAPI document for the API tooling cases
+const PING_API: &str = r#"{
+    "openapi": "3.0.3",
+    "info": {"title": "Ping", "version": "1.0.0"},
+    "paths": {
+        "/ping": {
+            "get": {
+                "responses": {
+                    "200": {
+                        "description": "Pong",
+                        "content": {"application/json": {"example": {"ok": true}}}
+                    }
+                }
+            }
+        }
+    }
+}"#;
+
+fn golden_cases() -> Vec<GoldenCase> {
+    [
+        basic_math_cases(),
+        color_cases(),
+        crypto_cases(),
+        data_formats_cases(),
+        datetime_cases(),
+        encoding_cases(),
+        geospatial_cases(),
+        identifiers_cases(),
+        math3d_cases(),
+        statistics_cases(),
+        string_cases(),
+        validation_cases(),
+        visualization_cases(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn basic_math_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "add",
+            "positive_numbers",
+            |input| invoke(add_tool::add_numbers, input),
+            json!({"a": 5.0, "b": 3.0}),
+            json!({"result": 8.0, "operation": "addition", "inputs": [5.0, 3.0]}),
+        ),
+        case(
+            "subtract",
+            "mixed_signs",
+            |input| invoke(subtract_tool::subtract_numbers, input),
+            json!({"a": 10.0, "b": -3.0}),
+            json!({"result": 13.0, "operation": "subtraction"}),
+        ),
+        case(
+            "multiply",
+            "negative_numbers",
+            |input| invoke(multiply_tool::multiply_numbers, input),
+            json!({"a": -4.0, "b": -5.0}),
+            json!({"result": 20.0, "operation": "multiplication"}),
+        ),
+        case(
+            "divide",
+            "positive_numbers",
+            |input| invoke(divide_tool::divide_numbers, input),
+            json!({"a": 10.0, "b": 2.0}),
+            json!({"result": 5.0, "operation": "division"}),
+        ),
+        error_case(
+            "divide",
+            "division_by_zero",
+            |input| invoke(divide_tool::divide_numbers, input),
+            json!({"a": 1.0, "b": 0.0}),
+            "Division by zero",
+        ),
+        case(
+            "modulus",
+            "negative_dividend",
+            |input| invoke(modulus_tool::modulus_numbers, input),
+            json!({"a": -10.0, "b": 3.0}),
+            json!({"result": 2.0, "operation": "modulus"}),
+        ),
+        case(
+            "remainder",
+            "negative_dividend",
+            |input| invoke(remainder_tool::remainder_numbers, input),
+            json!({"a": -10.0, "b": 3.0}),
+            json!({"result": -1.0, "operation": "remainder"}),
+        ),
+        case(
+            "power",
+            "positive_integers",
+            |input| invoke(power_tool::power_numbers, input),
+            json!({"a": 2.0, "b": 3.0}),
+            json!({"result": 8.0, "operation": "exponentiation"}),
+        ),
+        case(
+            "square",
+            "negative_integer",
+            |input| invoke(square_tool::square_number, input),
+            json!({"value": -4.0}),
+            json!({"result": 16.0, "operation": "square"}),
+        ),
+        case(
+            "sqrt",
+            "perfect_square",
+            |input| invoke(sqrt_tool::calculate_sqrt, input),
+            json!({"value": 9.0}),
+            json!({"result": 3.0, "input": 9.0, "is_valid": true}),
+        ),
+        case(
+            "pythagorean",
+            "classic_3_4_5",
+            |input| invoke(pythagorean_tool::calculate_pythagorean, input),
+            json!({"a": 3.0, "b": 4.0}),
+            json!({"hypotenuse": 5.0, "leg_a": 3.0, "leg_b": 4.0}),
+        ),
+        case(
+            "distance_2d",
+            "classic_3_4_5",
+            |input| invoke(distance_2d_tool::calculate_distance_2d, input),
+            json!({"point1": {"x": 0.0, "y": 0.0}, "point2": {"x": 3.0, "y": 4.0}}),
+            json!({"distance": 5.0, "delta_x": 3.0, "delta_y": 4.0}),
+        ),
+        case(
+            "sum",
+            "mixed_signs",
+            |input| invoke(sum_tool::sum_numbers, input),
+            json!({"numbers": [1.5, 2.5, 3.0, -1.0]}),
+            json!({"result": 6.0, "operation": "sum"}),
+        ),
+        case(
+            "product",
+            "several_numbers",
+            |input| invoke(product_tool::multiply_all, input),
+            json!({"numbers": [2.0, 3.0, 4.0]}),
+            json!({"result": 24.0, "operation": "product"}),
+        ),
+        case(
+            "min",
+            "several_numbers",
+            |input| invoke(min_tool::find_minimum, input),
+            json!({"numbers": [3.0, -7.5, 2.0, 10.0]}),
+            json!({"result": -7.5, "operation": "minimum"}),
+        ),
+        case(
+            "max",
+            "several_numbers",
+            |input| invoke(max_tool::find_maximum, input),
+            json!({"numbers": [3.0, -7.5, 2.0, 10.0]}),
+            json!({"result": 10.0, "operation": "maximum"}),
+        ),
+        case(
+            "mean",
+            "several_numbers",
+            |input| invoke(mean_tool::calculate_mean, input),
+            json!({"numbers": [1.0, 2.0, 3.0, 4.0]}),
+            json!({"result": 2.5, "operation": "mean"}),
+        ),
+        case(
+            "compare_numbers",
+            "five_percent_apart",
+            |input| invoke(compare_numbers_tool::compare_values, input),
+            json!({"a": 105.0, "b": 100.0}),
+            json!({
+                "difference": 5.0,
+                "absolute_difference": 5.0,
+                "relative_difference": 0.05,
+                "percent_error": 5.0,
+                "ordering": "greater",
+                "exactly_equal": false,
+                "approximately_equal": false
+            }),
+        ),
+    ]
+}
+
+fn color_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "palette_generate",
+            "categorical_count",
+            |input| invoke(palette_generate_tool::generate_palette, input),
+            json!({"kind": "categorical", "count": 4}),
+            json!({"kind": "categorical", "background": "#ffffff"}),
+        ),
+        case(
+            "contrast_audit",
+            "black_on_white",
+            |input| invoke(contrast_audit_tool::contrast_audit_logic, input),
+            json!({"pairs": [{"foreground": "#000", "background": "#FFFFFF"}]}),
+            json!({
+                "results": [{
+                    "contrast_ratio": 21.000002,
+                    "aa_normal": true,
+                    "aaa_normal": true,
+                    "apca_use": "fluent text",
+                    "passes": true,
+                    "adjustments": []
+                }]
+            }),
+        ),
+    ]
+}
+
+fn crypto_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "hash_generator",
+            "md5_hex",
+            |input| invoke(hash_generator_tool::generate_hash, input),
+            json!({"text": "hello world", "algorithm": "md5", "format": "hex"}),
+            json!({
+                "hash": "5eb63bbbe01eeed093cb22bb8f5acdc3",
+                "algorithm": "md5",
+                "format": "hex",
+                "byte_length": 16,
+                "string_length": 32
+            }),
+        ),
+        case(
+            "integrity_manifest",
+            "chunked_blob",
+            |input| invoke(integrity_manifest_tool::build_integrity_manifest, input),
+            json!({"blobs": [{"name": "greeting", "data": "aGVsbG8gd29ybGQ="}], "chunk_size": 5}),
+            json!({
+                "manifest": {
+                    "algorithm": "sha256",
+                    "entries": [{
+                        "name": "greeting",
+                        "size": 11,
+                        "hash": "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+                    }]
+                }
+            }),
+        ),
+    ]
+}
+
+fn data_formats_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "csv_parser",
+            "headers_and_rows",
+            |input| {
+                invoke(
+                    |input: csv_parser_tool::LogicInput| {
+                        ftl_yield::block_on(csv_parser_tool::parse_csv(input))
+                    },
+                    input,
+                )
+            },
+            json!({"content": "Name,Age,City\nJohn,30,New York\nJane,25,Boston", "has_headers": true}),
+            json!({
+                "headers": ["Name", "Age", "City"],
+                "rows": [["John", "30", "New York"], ["Jane", "25", "Boston"]],
+                "row_count": 2,
+                "column_count": 3
+            }),
+        ),
+        case(
+            "json_formatter",
+            "compact",
+            |input| invoke(json_formatter_tool::format_json, input),
+            json!({"json_string": "{ \"a\" : [1, 2] }", "indent": 0}),
+            json!({"formatted": "{\"a\":[1,2]}", "is_valid": true}),
+        ),
+        case(
+            "json_validator",
+            "simple_object",
+            |input| invoke(json_validator_tool::validate_json, input),
+            json!({"json_string": "{\"name\": \"John\", \"age\": 30}"}),
+            json!({
+                "is_valid": true,
+                "details": {"root_type": "object", "key_count": 2, "max_depth": 2}
+            }),
+        ),
+        case(
+            "yaml_formatter",
+            "flat_mapping",
+            |input| invoke(yaml_formatter_tool::format_yaml, input),
+            json!({"content": "name: John\nage: 30\ncity: New York"}),
+            json!({"is_valid": true, "stats": {"key_count": 3, "max_depth": 1}}),
+        ),
+        case(
+            "json_flatten",
+            "nested_document",
+            |input| invoke(json_flatten_tool::flatten_json, input),
+            json!({
+                "operation": "flatten",
+                "json_string": "{\"user\": {\"name\": \"Ada\", \"tags\": [\"x\", \"y\"]}, \"active\": true}"
+            }),
+            json!({
+                "entries": [
+                    {"key": "active", "value": true},
+                    {"key": "user.name", "value": "Ada"},
+                    {"key": "user.tags[0]", "value": "x"},
+                    {"key": "user.tags[1]", "value": "y"}
+                ],
+                "collisions": []
+            }),
+        ),
+        case(
+            "generate_from_schema",
+            "fixed_integer",
+            |input| invoke(generate_from_schema_tool::generate_documents, input),
+            json!({
+                "schema": "{\"type\": \"object\", \"properties\": {\"id\": {\"type\": \"integer\", \"minimum\": 7, \"maximum\": 7}}, \"required\": [\"id\"]}",
+                "count": 2,
+                "seed": 42
+            }),
+            json!({"documents": [{"id": 7}, {"id": 7}], "count": 2, "seed": 42}),
+        ),
+        case(
+            "mock_response",
+            "response_example",
+            |input| invoke(mock_response_tool::build_mock_response, input),
+            json!({"source": PING_API, "path": "/ping", "method": "GET"}),
+            json!({
+                "body": {"ok": true},
+                "status": "200",
+                "media_type": "application/json",
+                "body_source": "example"
+            }),
+        ),
+        case(
+            "openapi_inspect",
+            "single_operation",
+            |input| invoke(openapi_inspect_tool::inspect_openapi, input),
+            json!({"document": PING_API}),
+            json!({
+                "is_valid": true,
+                "openapi_version": "3.0.3",
+                "title": "Ping",
+                "operation_count": 1
+            }),
+        ),
+        case(
+            "data_mask",
+            "partial_and_fixed",
+            |input| invoke(data_mask_tool::mask_document, input),
+            json!({
+                "document": "{\"card\": \"4111-1111-1111-1234\", \"name\": \"Jane\"}",
+                "rules": [
+                    {"path": "card", "strategy": "partial"},
+                    {"path": "name", "strategy": "fixed"}
+                ]
+            }),
+            json!({
+                "masked": {"card": "****-****-****-1234", "name": "[REDACTED]"},
+                "masked_count": 2
+            }),
+        ),
+        case(
+            "record_link",
+            "identical_names",
+            |input| invoke(record_link_tool::link_records, input),
+            json!({
+                "left": [{"id": 1, "name": "Acme Corp"}],
+                "right": [{"id": "a", "name": "Acme Corp"}],
+                "fields": [{"field": "name"}],
+                "id_field": "id"
+            }),
+            json!({
+                "matches": [{"left_index": 0, "right_index": 0, "left_id": 1, "right_id": "a"}],
+                "unmatched_left": [],
+                "unmatched_right": []
+            }),
+        ),
+        case(
+            "dedupe_records",
+            "whole_record_duplicates",
+            |input| invoke(dedupe_records_tool::remove_duplicates, input),
+            json!({"records": [{"a": 1, "b": 2}, {"b": 2, "a": 1}, {"a": 1, "b": 3}]}),
+            json!({"kept_indices": [0, 2], "removed_count": 1, "exact_duplicates": 1}),
+        ),
+        case(
+            "chunked_upload",
+            "init_session",
+            |input| {
+                invoke(
+                    |input: chunked_upload_tool::LogicInput| {
+                        chunked_upload_tool::handle_upload(&ftl_kv_store::MemoryStore::new(), input)
+                    },
+                    input,
+                )
+            },
+            json!({"operation": "init"}),
+            json!({"operation": "init", "chunk_count": 0, "total_bytes": 0, "finalized": false}),
+        ),
+    ]
+}
+
+fn datetime_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "current_datetime",
+            "utc_default",
+            |input| invoke(current_datetime_tool::get_current_datetime, input),
+            json!({}),
+            json!({"timezone": "UTC"}),
+        ),
+        case(
+            "project_schedule",
+            "single_task_skips_weekend",
+            |input| invoke(project_schedule_tool::schedule_project, input),
+            json!({"start_date": "2024-01-05", "tasks": [{"id": "a", "duration_days": 3}]}),
+            json!({
+                "tasks": [{"id": "a", "start_date": "2024-01-05", "end_date": "2024-01-09"}],
+                "total_working_days": 3,
+                "total_calendar_days": 5
+            }),
+        ),
+        case(
+            "humanize_duration",
+            "days_and_hours",
+            |input| invoke(humanize_duration_tool::convert_duration, input),
+            json!({"seconds": 183600.0}),
+            json!({
+                "text": "2 days, 3 hours",
+                "iso": "P2DT3H",
+                "parts": [{"unit": "days", "value": 2}, {"unit": "hours", "value": 3}]
+            }),
+        ),
+        case(
+            "fiscal_period",
+            "leap_day",
+            |input| invoke(fiscal_period_tool::fiscal_period_logic, input),
+            json!({"date": "2024-02-29"}),
+            json!({
+                "weekday": "Thursday",
+                "day_of_year": 60,
+                "iso_week": {"label": "2024-W09", "start": "2024-02-26", "end": "2024-03-03"},
+                "quarter": {"label": "2024-Q1", "days": 91},
+                "fiscal_year": {"label": "FY2024", "days": 366}
+            }),
+        ),
+        case(
+            "meeting_planner",
+            "new_york_and_berlin",
+            |input| invoke(meeting_planner_tool::plan_meeting, input),
+            json!({
+                "participants": [
+                    {"name": "ana", "timezone": "America/New_York"},
+                    {"name": "ben", "timezone": "Europe/Berlin"}
+                ],
+                "start_date": "2024-01-10",
+                "duration_minutes": 60
+            }),
+            json!({
+                "common_windows": [{"start": "2024-01-10T14:00:00+00:00", "minutes": 120}],
+                "total_slots": 3,
+                "truncated": false
+            }),
+        ),
+        case(
+            "iso_interval_parse",
+            "start_end",
+            |input| invoke(iso_interval_parse_tool::parse_interval, input),
+            json!({"interval": "2024-03-01T09:00:00Z/2024-03-02T11:30:00Z"}),
+            json!({
+                "form": "start/end",
+                "duration": "P1DT2H30M",
+                "duration_seconds": 95400,
+                "repeating": false
+            }),
+        ),
+        case(
+            "duration_aggregate",
+            "start_end_entries",
+            |input| invoke(duration_aggregate_tool::aggregate_durations, input),
+            json!({
+                "entries": [
+                    {"start": "2024-03-04T09:00:00Z", "end": "2024-03-04T12:30:00Z"},
+                    {"duration": "PT2H"}
+                ]
+            }),
+            json!({
+                "seconds": [12600.0, 7200.0],
+                "total": {"count": 2, "total_seconds": 19800.0, "iso": "PT5H30M"}
+            }),
+        ),
+        case(
+            "calendar_facts",
+            "leap_february",
+            |input| invoke(calendar_facts_tool::calendar_facts_logic, input),
+            json!({"year": 2024, "month": 2}),
+            json!({
+                "is_leap_year": true,
+                "days_in_year": 366,
+                "month": {"name": "February", "days": 29, "first_weekday": "Thursday"}
+            }),
+        ),
+    ]
+}
+
+fn encoding_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "base64_decoder",
+            "hello_world",
+            |input| invoke(base64_decoder_tool::decode_base64, input),
+            json!({"encoded": "SGVsbG8sIFdvcmxkIQ=="}),
+            json!({"decoded": "Hello, World!", "decoded_length": 13, "variant": "standard"}),
+        ),
+        case(
+            "base64_encoder",
+            "hello_world",
+            |input| invoke(base64_encoder_tool::encode_base64, input),
+            json!({"data": "Hello, World!"}),
+            json!({"encoded": "SGVsbG8sIFdvcmxkIQ==", "encoded_length": 20, "variant": "standard"}),
+        ),
+        case(
+            "hex_decoder",
+            "hello",
+            |input| invoke(hex_decoder_tool::decode_hex, input),
+            json!({"encoded": "48656c6c6f"}),
+            json!({"decoded": "Hello", "decoded_length": 5, "is_valid_utf8": true}),
+        ),
+        case(
+            "hex_encoder",
+            "hello",
+            |input| invoke(hex_encoder_tool::encode_hex, input),
+            json!({"data": "Hello"}),
+            json!({"encoded": "48656c6c6f", "encoded_length": 10, "case": "lowercase"}),
+        ),
+        case(
+            "url_decoder",
+            "encoded_space",
+            |input| invoke(url_decoder_tool::decode_url, input),
+            json!({"encoded": "hello%20world"}),
+            json!({"decoded": "hello world", "sequences_decoded": 1}),
+        ),
+        case(
+            "url_encoder",
+            "space",
+            |input| invoke(url_encoder_tool::encode_url, input),
+            json!({"data": "hello world"}),
+            json!({"encoded": "hello%20world", "mode": "component", "chars_encoded": 1}),
+        ),
+    ]
+}
+
+fn geospatial_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "bearing",
+            "due_north",
+            |input| invoke(bearing_tool::calculate_bearing_between_points, input),
+            json!({"lat1": 0.0, "lon1": 0.0, "lat2": 1.0, "lon2": 0.0}),
+            json!({"bearing_degrees": 0.0, "compass_direction": "N"}),
+        ),
+        case(
+            "buffer_polygon",
+            "sixteen_vertices",
+            |input| {
+                invoke(
+                    |input: buffer_polygon_tool::LogicInput| {
+                        buffer_polygon_tool::create_circular_buffer(
+                            input.center,
+                            input.radius_meters,
+                            input.num_points,
+                        )
+                    },
+                    input,
+                )
+            },
+            json!({"center": {"lat": 40.7128, "lon": -74.006}, "radius_meters": 1000.0, "num_points": 16}),
+            json!({"algorithm_used": "circular_geodesic"}),
+        ),
+        case(
+            "coordinate_conversion",
+            "new_york",
+            |input| {
+                invoke(
+                    |input: geospatial_coordinate_conversion_tool::LogicInput| {
+                        geospatial_coordinate_conversion_tool::convert_to_dms(
+                            input.latitude,
+                            input.longitude,
+                        )
+                    },
+                    input,
+                )
+            },
+            json!({"latitude": 40.5, "longitude": -74.25}),
+            json!({
+                "latitude": {"degrees": 40, "minutes": 30, "seconds": 0.0, "direction": "N"},
+                "longitude": {"degrees": 74, "minutes": 15, "seconds": 0.0, "direction": "W"}
+            }),
+        ),
+        case(
+            "distance",
+            "same_point",
+            |input| invoke(distance_tool::calculate_distance_between_points, input),
+            json!({"lat1": 40.7128, "lon1": -74.006, "lat2": 40.7128, "lon2": -74.006}),
+            json!({"distance_km": 0.0, "distance_miles": 0.0, "distance_nautical_miles": 0.0}),
+        ),
+        case(
+            "point_in_polygon",
+            "inside_square",
+            |input| {
+                invoke(
+                    |input: point_in_polygon_tool::LogicInput| {
+                        point_in_polygon_tool::point_in_polygon_check(input.point, input.polygon)
+                    },
+                    input,
+                )
+            },
+            json!({
+                "point": {"lat": 0.5, "lon": 0.5},
+                "polygon": [
+                    {"lat": 0.0, "lon": 0.0},
+                    {"lat": 0.0, "lon": 1.0},
+                    {"lat": 1.0, "lon": 1.0},
+                    {"lat": 1.0, "lon": 0.0}
+                ]
+            }),
+            json!({"is_inside": true, "on_boundary": false, "algorithm_used": "ray_casting"}),
+        ),
+        error_case(
+            "polygon_area",
+            "too_few_points",
+            |input| {
+                invoke(
+                    |input: polygon_area_tool::LogicInput| {
+                        polygon_area_tool::get_polygon_area(input.coordinates)
+                    },
+                    input,
+                )
+            },
+            json!({"coordinates": [{"lat": 0.0, "lon": 0.0}, {"lat": 0.0, "lon": 1.0}]}),
+            "at least 3",
+        ),
+        case(
+            "polygon_simplification",
+            "straight_line",
+            |input| {
+                invoke(
+                    polygon_simplification_tool::polygon_simplification_logic,
+                    input,
+                )
+            },
+            json!({
+                "polygon": [
+                    {"lat": 0.0, "lon": 0.0},
+                    {"lat": 0.0, "lon": 0.001},
+                    {"lat": 0.0, "lon": 0.002},
+                    {"lat": 0.0, "lon": 0.003}
+                ],
+                "tolerance_meters": 10.0
+            }),
+            json!({
+                "original_vertex_count": 4,
+                "simplified_vertex_count": 2,
+                "reduction_percentage": 50.0,
+                "algorithm_used": "douglas_peucker"
+            }),
+        ),
+        case(
+            "proximity_search",
+            "nearest_first",
+            |input| {
+                invoke(
+                    |input: proximity_search_tool::LogicInput| {
+                        let page = ftl_pagination::PageRequest::new(input.offset, input.limit)?;
+                        proximity_search_tool::find_nearest_points_page(
+                            input.query_point,
+                            input.candidate_points,
+                            input.max_results,
+                            input.max_distance_meters,
+                            page,
+                        )
+                    },
+                    input,
+                )
+            },
+            json!({
+                "query_point": {"lat": 0.0, "lon": 0.0},
+                "candidate_points": [
+                    {"lat": 0.0, "lon": 2.0, "id": "far"},
+                    {"lat": 0.0, "lon": 1.0, "id": "near"}
+                ]
+            }),
+            json!({
+                "total_candidates": 2,
+                "results_returned": 2,
+                "nearest_points": [{"point": {"id": "near"}}, {"point": {"id": "far"}}]
+            }),
+        ),
+        case(
+            "proximity_zone",
+            "one_inside_one_outside",
+            |input| {
+                invoke(
+                    |input: proximity_zone_tool::LogicInput| {
+                        proximity_zone_tool::proximity_zone_analysis(
+                            input.center,
+                            input.radius_meters,
+                            input.candidate_points,
+                        )
+                    },
+                    input,
+                )
+            },
+            json!({
+                "center": {"lat": 0.0, "lon": 0.0},
+                "radius_meters": 50000.0,
+                "candidate_points": [
+                    {"lat": 0.0, "lon": 0.1, "id": "inside"},
+                    {"lat": 0.0, "lon": 1.0, "id": "outside"}
+                ]
+            }),
+            json!({"summary": {"total_points": 2, "points_inside": 1, "points_outside": 1}}),
+        ),
+        case(
+            "geo_bin",
+            "same_square_cell",
+            |input| invoke(geo_bin_tool::bin_points, input),
+            json!({
+                "points": [{"lat": 0.0001, "lon": 0.0001}, {"lat": 0.0002, "lon": 0.0002}],
+                "grid_type": "square",
+                "cell_size_meters": 1000.0
+            }),
+            json!({
+                "grid_type": "square",
+                "total_points": 2,
+                "cell_count": 1,
+                "max_count": 2,
+                "cells": [{"count": 2, "weight_sum": 2.0}]
+            }),
+        ),
+        case(
+            "line_of_sight",
+            "flat_terrain",
+            |input| invoke(line_of_sight_tool::calculate_line_of_sight, input),
+            json!({
+                "observer": {"lat": 0.0, "lon": 0.0, "elevation_m": 100.0, "antenna_height_m": 10.0},
+                "target": {"lat": 0.0, "lon": 0.1, "elevation_m": 100.0, "antenna_height_m": 10.0},
+                "terrain_profile": [
+                    {"distance_m": 2000.0, "elevation_m": 100.0},
+                    {"distance_m": 8000.0, "elevation_m": 100.0}
+                ],
+                "earth_curvature": false
+            }),
+            json!({"visible": true, "obstruction": null, "obstructed_samples": 0}),
+        ),
+        case(
+            "raster_sample",
+            "nearest_cell",
+            |input| invoke(raster_sample_tool::raster_sample_logic, input),
+            json!({
+                "raster": {
+                    "origin": {"lat": 10.0, "lon": 20.0},
+                    "cell_size": 1.0,
+                    "values": [[1.0, 2.0], [3.0, 4.0]]
+                },
+                "points": [{"lat": 9.5, "lon": 20.5}],
+                "method": "nearest"
+            }),
+            json!({
+                "samples": [{"value": 1.0, "row": 0, "column": 0}],
+                "rows": 2,
+                "columns": 2,
+                "sampled_count": 1,
+                "missing_count": 0
+            }),
+        ),
+        case(
+            "delaunay_triangulate",
+            "square_with_center",
+            |input| invoke(delaunay_triangulate_tool::delaunay_triangulate_logic, input),
+            json!({
+                "points": [
+                    {"x": 0.0, "y": 0.0},
+                    {"x": 2.0, "y": 0.0},
+                    {"x": 2.0, "y": 2.0},
+                    {"x": 0.0, "y": 2.0},
+                    {"x": 1.0, "y": 1.0}
+                ]
+            }),
+            json!({"total_area": 4.0, "hull": [0, 1, 2, 3], "duplicate_points": []}),
+        ),
+        case(
+            "voronoi_cells",
+            "square_quadrants",
+            |input| invoke(voronoi_cells_tool::voronoi_cells_logic, input),
+            json!({
+                "points": [
+                    {"x": 1.0, "y": 1.0},
+                    {"x": 3.0, "y": 1.0},
+                    {"x": 3.0, "y": 3.0},
+                    {"x": 1.0, "y": 3.0}
+                ],
+                "bounds": {"min_x": 0.0, "min_y": 0.0, "max_x": 4.0, "max_y": 4.0}
+            }),
+            json!({
+                "total_area": 16.0,
+                "cells": [
+                    {"site": 0, "area": 4.0, "neighbors": [1, 3]},
+                    {"site": 1, "area": 4.0},
+                    {"site": 2, "area": 4.0},
+                    {"site": 3, "area": 4.0}
+                ]
+            }),
+        ),
+        case(
+            "balance_territories",
+            "two_equal_points",
+            |input| invoke(balance_territories_tool::balance_territories_logic, input),
+            json!({
+                "points": [{"lat": 0.0, "lon": 0.0}, {"lat": 0.0, "lon": 1.0}],
+                "territories": 2,
+                "seed": 1
+            }),
+            json!({"statistics": {"max_deviation": 0.0}, "contiguous": true}),
+        ),
+    ]
+}
+
+fn identifiers_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "random_integer",
+            "fixed_range",
+            |input| invoke(random_integer_tool::generate_random_integers, input),
+            json!({"min": 7, "max": 7, "count": 3}),
+            json!({"values": [7, 7, 7], "range": {"min": 7, "max": 7}}),
+        ),
+        case(
+            "random_string",
+            "single_character_charset",
+            |input| invoke(random_string_tool::generate_random_strings, input),
+            json!({"length": 4, "charset": "numeric", "count": 1}),
+            json!({"config": {"length": 4, "charset": "numeric", "charset_size": 10}}),
+        ),
+        case(
+            "stable_id",
+            "sha256_hex",
+            |input| invoke(stable_id_tool::derive_ids, input),
+            json!({"input": "abc", "encoding": "hex", "length": 64}),
+            json!({
+                "ids": [{
+                    "input": "abc",
+                    "id": "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+                }],
+                "algorithm": "sha256",
+                "encoding": "hex"
+            }),
+        ),
+        case(
+            "id_format",
+            "invoice_numbers",
+            |input| invoke(id_format_tool::format_ids, input),
+            json!({
+                "template": "INV-{YYYY}-{seq:06}-{luhn}",
+                "sequence": 42,
+                "count": 3,
+                "date": "2024-03-15"
+            }),
+            json!({"ids": ["INV-2024-000042-0", "INV-2024-000043-8", "INV-2024-000044-6"]}),
+        ),
+        case(
+            "uuid_generator",
+            "simple_format",
+            |input| invoke(uuid_generator_tool::generate_uuids, input),
+            json!({"count": 2, "format": "simple"}),
+            json!({"version": "4", "format": "simple"}),
+        ),
+        error_case(
+            "uuid_generator",
+            "unknown_format",
+            |input| invoke(uuid_generator_tool::generate_uuids, input),
+            json!({"format": "octal"}),
+            "Invalid format 'octal'",
+        ),
+    ]
+}
+
+fn math3d_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "aabb_volume",
+            "two_corners",
+            |input| invoke(aabb_volume_tool::compute_aabb_volume, input),
+            json!({"points": [{"x": 0.0, "y": 0.0, "z": 0.0}, {"x": 2.0, "y": 3.0, "z": 4.0}]}),
+            json!({
+                "volume": 24.0,
+                "min_point": {"x": 0.0, "y": 0.0, "z": 0.0},
+                "max_point": {"x": 2.0, "y": 3.0, "z": 4.0},
+                "dimensions": {"x": 2.0, "y": 3.0, "z": 4.0}
+            }),
+        ),
+        case(
+            "arbitrary_rotation",
+            "zero_angle_is_identity",
+            |input| invoke(arbitrary_rotation_tool::arbitrary_rotation_logic, input),
+            json!({"axis": {"x": 0.0, "y": 0.0, "z": 1.0}, "angle": 0.0}),
+            json!({
+                "matrix": {
+                    "m00": 1.0, "m01": 0.0, "m02": 0.0,
+                    "m10": 0.0, "m11": 1.0, "m12": 0.0,
+                    "m20": 0.0, "m21": 0.0, "m22": 1.0
+                }
+            }),
+        ),
+        case(
+            "capsule_ray_intersection",
+            "hits_cylindrical_side",
+            |input| {
+                invoke(
+                    capsule_ray_intersection_tool::capsule_ray_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "capsule": {
+                    "start": {"x": 0.0, "y": 0.0, "z": 0.0},
+                    "end": {"x": 0.0, "y": 0.0, "z": 4.0},
+                    "radius": 1.0
+                },
+                "ray": {"origin": {"x": -5.0, "y": 0.0, "z": 2.0}, "direction": {"x": 2.0, "y": 0.0, "z": 0.0}}
+            }),
+            json!({
+                "intersects": true,
+                "intersection_points": [
+                    {"point": {"x": -1.0, "y": 0.0, "z": 2.0}, "distance": 4.0},
+                    {"point": {"x": 1.0, "y": 0.0, "z": 2.0}, "distance": 6.0}
+                ],
+                "closest_distance": 4.0
+            }),
+        ),
+        case(
+            "cone_ray_intersection",
+            "hits_side_twice",
+            |input| {
+                invoke(
+                    cone_ray_intersection_tool::cone_ray_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "cone": {
+                    "apex": {"x": 0.0, "y": 0.0, "z": 4.0},
+                    "axis": {"x": 0.0, "y": 0.0, "z": -1.0},
+                    "height": 4.0,
+                    "half_angle_degrees": 45.0
+                },
+                "ray": {"origin": {"x": -10.0, "y": 0.0, "z": 1.0}, "direction": {"x": 1.0, "y": 0.0, "z": 0.0}}
+            }),
+            json!({
+                "intersects": true,
+                "intersection_points": [{"distance": 7.0}, {"distance": 13.0}],
+                "closest_distance": 7.0
+            }),
+        ),
+        case(
+            "ellipsoid_ray_intersection",
+            "along_long_axis",
+            |input| {
+                invoke(
+                    ellipsoid_ray_intersection_tool::ellipsoid_ray_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "ellipsoid": {
+                    "center": {"x": 0.0, "y": 0.0, "z": 1.0},
+                    "radii": {"x": 3.0, "y": 2.0, "z": 1.0}
+                },
+                "ray": {"origin": {"x": -5.0, "y": 0.0, "z": 1.0}, "direction": {"x": 2.0, "y": 0.0, "z": 0.0}}
+            }),
+            json!({
+                "intersects": true,
+                "intersection_points": [
+                    {"point": {"x": -3.0, "y": 0.0, "z": 1.0}, "distance": 2.0, "normal": {"x": -1.0, "y": 0.0, "z": 0.0}},
+                    {"point": {"x": 3.0, "y": 0.0, "z": 1.0}, "distance": 8.0, "normal": {"x": 1.0, "y": 0.0, "z": 0.0}}
+                ],
+                "closest_distance": 2.0
+            }),
+        ),
+        case(
+            "cartesian_to_spherical",
+            "unit_z",
+            |input| {
+                invoke(
+                    cartesian_to_spherical_tool::cartesian_to_spherical_logic,
+                    input,
+                )
+            },
+            json!({"coordinates": {"x": 0.0, "y": 0.0, "z": 2.0}}),
+            json!({"spherical_coordinates": {"radius": 2.0, "phi": 0.0}}),
+        ),
+        case(
+            "cross_product",
+            "unit_axes",
+            |input| invoke(cross_product_tool::cross_product_logic, input),
+            json!({"vector1": {"x": 1.0, "y": 0.0, "z": 0.0}, "vector2": {"x": 0.0, "y": 1.0, "z": 0.0}}),
+            json!({
+                "cross_product": {"x": 0.0, "y": 0.0, "z": 1.0},
+                "magnitude": 1.0,
+                "area_parallelogram": 1.0,
+                "are_parallel": false
+            }),
+        ),
+        case(
+            "cylinder_ray_intersection",
+            "through_axis",
+            |input| {
+                invoke(
+                    cylinder_ray_intersection_tool::cylinder_ray_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "cylinder": {
+                    "center": {"x": 0.0, "y": 0.0, "z": 0.0},
+                    "axis": {"x": 0.0, "y": 0.0, "z": 1.0},
+                    "radius": 1.0,
+                    "height": 2.0
+                },
+                "ray": {"origin": {"x": -2.0, "y": 0.0, "z": 0.5}, "direction": {"x": 1.0, "y": 0.0, "z": 0.0}}
+            }),
+            json!({"intersects": true, "closest_distance": 1.0}),
+        ),
+        case(
+            "cylinder_volume",
+            "unit_cylinder",
+            |input| invoke(cylinder_volume_tool::compute_cylinder_volume, input),
+            json!({
+                "base_center": {"x": 0.0, "y": 0.0, "z": 0.0},
+                "axis": {"x": 0.0, "y": 0.0, "z": 1.0},
+                "radius": 1.0,
+                "height": 1.0
+            }),
+            json!({"volume": std::f64::consts::PI, "radius": 1.0, "height": 1.0}),
+        ),
+        case(
+            "cone_volume",
+            "three_four_five",
+            |input| invoke(cone_volume_tool::compute_cone_volume, input),
+            json!({
+                "apex": {"x": 0.0, "y": 0.0, "z": 4.0},
+                "axis": {"x": 0.0, "y": 0.0, "z": -2.0},
+                "height": 4.0,
+                "base_radius": 3.0
+            }),
+            json!({
+                "volume": 12.0 * std::f64::consts::PI,
+                "slant_height": 5.0,
+                "calculation_method": "Cone formula: πr²h/3"
+            }),
+        ),
+        case(
+            "ellipsoid_volume",
+            "sphere",
+            |input| invoke(ellipsoid_volume_tool::compute_ellipsoid_volume, input),
+            json!({
+                "center": {"x": 0.0, "y": 0.0, "z": 0.0},
+                "radii": {"x": 2.0, "y": 2.0, "z": 2.0}
+            }),
+            json!({
+                "volume": 32.0 / 3.0 * std::f64::consts::PI,
+                "surface_area": 16.0 * std::f64::consts::PI,
+                "shape": "sphere"
+            }),
+        ),
+        case(
+            "dot_product",
+            "sequential_components",
+            |input| invoke(dot_product_tool::dot_product_logic, input),
+            json!({"vector1": {"x": 1.0, "y": 2.0, "z": 3.0}, "vector2": {"x": 4.0, "y": 5.0, "z": 6.0}}),
+            json!({"dot_product": 32.0, "are_perpendicular": false, "are_parallel": false}),
+        ),
+        case(
+            "line_intersection",
+            "crossing_at_origin",
+            |input| invoke(line_intersection_tool::line_intersection_logic, input),
+            json!({
+                "line1": {"point": {"x": 0.0, "y": 0.0, "z": 0.0}, "direction": {"x": 1.0, "y": 0.0, "z": 0.0}},
+                "line2": {"point": {"x": 0.0, "y": 1.0, "z": 0.0}, "direction": {"x": 0.0, "y": -1.0, "z": 0.0}}
+            }),
+            json!({
+                "intersection_type": "intersecting",
+                "intersects": true,
+                "intersection_point": {"x": 0.0, "y": 0.0, "z": 0.0},
+                "minimum_distance": 0.0,
+                "are_parallel": false,
+                "are_skew": false
+            }),
+        ),
+        case(
+            "line_plane_intersection",
+            "through_origin",
+            |input| {
+                invoke(
+                    line_plane_intersection_tool::line_plane_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "line": {"point": {"x": 0.0, "y": 0.0, "z": -1.0}, "direction": {"x": 0.0, "y": 0.0, "z": 1.0}},
+                "plane": {"point": {"x": 0.0, "y": 0.0, "z": 0.0}, "normal": {"x": 0.0, "y": 0.0, "z": 1.0}}
+            }),
+            json!({
+                "intersection_type": "point",
+                "intersects": true,
+                "intersection_point": {"x": 0.0, "y": 0.0, "z": 0.0},
+                "parameter": 1.0,
+                "line_is_parallel": false
+            }),
+        ),
+        case(
+            "line_segment_intersection",
+            "crossing_segments",
+            |input| {
+                invoke(
+                    line_segment_intersection_tool::line_segment_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "segment1_start": {"x": 0.0, "y": 0.0, "z": 0.0},
+                "segment1_end": {"x": 2.0, "y": 0.0, "z": 0.0},
+                "segment2_start": {"x": 1.0, "y": -1.0, "z": 0.0},
+                "segment2_end": {"x": 1.0, "y": 1.0, "z": 0.0}
+            }),
+            json!({
+                "intersects": true,
+                "intersection_on_both_segments": true,
+                "intersection_point": {"x": 1.0, "y": 0.0, "z": 0.0},
+                "minimum_distance": 0.0
+            }),
+        ),
+        case(
+            "matrix_vector_multiply",
+            "scale_matrix",
+            |input| {
+                invoke(
+                    matrix_vector_multiply_tool::matrix_vector_multiply_logic,
+                    input,
+                )
+            },
+            json!({
+                "matrix": {
+                    "m00": 2.0, "m01": 0.0, "m02": 0.0,
+                    "m10": 0.0, "m11": 3.0, "m12": 0.0,
+                    "m20": 0.0, "m21": 0.0, "m22": 4.0
+                },
+                "vector": {"x": 1.0, "y": 2.0, "z": 3.0}
+            }),
+            json!({"result": {"x": 2.0, "y": 6.0, "z": 12.0}}),
+        ),
+        case(
+            "multiple_line_intersection",
+            "two_lines_meet_at_origin",
+            |input| {
+                invoke(
+                    multiple_line_intersection_tool::multiple_line_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "lines": [
+                    {"point": {"x": 0.0, "y": 0.0, "z": 0.0}, "direction": {"x": 1.0, "y": 0.0, "z": 0.0}},
+                    {"point": {"x": 0.0, "y": 1.0, "z": 0.0}, "direction": {"x": 0.0, "y": -1.0, "z": 0.0}}
+                ]
+            }),
+            json!({
+                "lines_processed": 2,
+                "total_squared_distance": 0.0,
+                "best_intersection_point": {"x": 0.0, "y": 0.0, "z": 0.0}
+            }),
+        ),
+        case(
+            "plane_plane_intersection",
+            "parallel_planes",
+            |input| {
+                invoke(
+                    plane_plane_intersection_tool::plane_plane_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "plane1": {"point": {"x": 0.0, "y": 0.0, "z": 0.0}, "normal": {"x": 0.0, "y": 0.0, "z": 1.0}},
+                "plane2": {"point": {"x": 0.0, "y": 0.0, "z": 1.0}, "normal": {"x": 0.0, "y": 0.0, "z": 1.0}}
+            }),
+            json!({
+                "intersection_type": "parallel",
+                "intersects": false,
+                "intersection_line": null,
+                "are_parallel": true,
+                "are_coincident": false
+            }),
+        ),
+        case(
+            "point_line_distance",
+            "point_on_line",
+            |input| invoke(point_line_distance_tool::point_line_distance_logic, input),
+            json!({
+                "point": {"x": 2.0, "y": 3.0, "z": 4.0},
+                "line": {"point": {"x": 0.0, "y": 1.0, "z": 2.0}, "direction": {"x": 1.0, "y": 1.0, "z": 1.0}}
+            }),
+            json!({
+                "distance": 0.0,
+                "closest_point_on_line": {"x": 2.0, "y": 3.0, "z": 4.0},
+                "parameter_on_line": 2.0,
+                "point_is_on_line": true
+            }),
+        ),
+        case(
+            "closest_point",
+            "segment_interior",
+            |input| invoke(closest_point_tool::closest_point_logic, input),
+            json!({
+                "point": {"x": 1.0, "y": 2.0, "z": 0.0},
+                "segment": {"start": {"x": 0.0, "y": 0.0, "z": 0.0}, "end": {"x": 4.0, "y": 0.0, "z": 0.0}}
+            }),
+            json!({
+                "primitive": "segment",
+                "closest_point": {"x": 1.0, "y": 0.0, "z": 0.0},
+                "distance": 2.0,
+                "parameter": 0.25
+            }),
+        ),
+        case(
+            "point_plane_distance",
+            "above_plane",
+            |input| invoke(point_plane_distance_tool::point_plane_distance_logic, input),
+            json!({
+                "point": {"x": 1.0, "y": 1.0, "z": 3.0},
+                "plane": {"point": {"x": 0.0, "y": 0.0, "z": 0.0}, "normal": {"x": 0.0, "y": 0.0, "z": 1.0}}
+            }),
+            json!({
+                "distance": 3.0,
+                "signed_distance": 3.0,
+                "closest_point_on_plane": {"x": 1.0, "y": 1.0, "z": 0.0},
+                "is_on_plane": false
+            }),
+        ),
+        case(
+            "pyramid_volume",
+            "triangular_base",
+            |input| invoke(pyramid_volume_tool::compute_pyramid_volume, input),
+            json!({
+                "base_points": [
+                    {"x": 0.0, "y": 0.0, "z": 0.0},
+                    {"x": 2.0, "y": 0.0, "z": 0.0},
+                    {"x": 1.0, "y": 2.0, "z": 0.0}
+                ],
+                "apex": {"x": 1.0, "y": 1.0, "z": 3.0}
+            }),
+            json!({"volume": 2.0, "base_area": 2.0, "height": 3.0}),
+        ),
+        case(
+            "quaternion_from_axis_angle",
+            "zero_angle",
+            |input| {
+                invoke(
+                    quaternion_from_axis_angle_tool::compute_quaternion_from_axis_angle,
+                    input,
+                )
+            },
+            json!({"axis": {"x": 1.0, "y": 0.0, "z": 0.0}, "angle": 0.0}),
+            json!({"quaternion": {"x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0}}),
+        ),
+        case(
+            "quaternion_multiply",
+            "identity_left",
+            |input| invoke(quaternion_multiply_tool::compute_quaternion_multiply, input),
+            json!({
+                "q1": {"x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0},
+                "q2": {"x": 1.0, "y": 2.0, "z": 3.0, "w": 4.0}
+            }),
+            json!({"result": {"x": 1.0, "y": 2.0, "z": 3.0, "w": 4.0}}),
+        ),
+        case(
+            "quaternion_slerp",
+            "identity_halfway",
+            |input| invoke(quaternion_slerp_tool::quaternion_slerp_logic, input),
+            json!({
+                "q1": {"x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0},
+                "q2": {"x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0},
+                "t": 0.5
+            }),
+            json!({"result": {"x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0}}),
+        ),
+        case(
+            "quaternion_ops",
+            "conjugate",
+            |input| invoke(quaternion_ops_tool::compute_quaternion_ops, input),
+            json!({"operation": "conjugate", "q1": {"x": 1.0, "y": 2.0, "z": 3.0, "w": 4.0}}),
+            json!({
+                "operation": "conjugate",
+                "quaternion": {"x": -1.0, "y": -2.0, "z": -3.0, "w": 4.0}
+            }),
+        ),
+        case(
+            "rotation_conversion",
+            "identity_euler",
+            |input| invoke(rotation_conversion_tool::convert_rotation, input),
+            json!({"euler": {"first": 0.0, "second": 0.0, "third": 0.0}, "order": "zyx"}),
+            json!({
+                "order": "zyx",
+                "quaternion": {"x": 0.0, "y": 0.0, "z": 0.0, "w": 1.0},
+                "matrix": {
+                    "m00": 1.0, "m01": 0.0, "m02": 0.0,
+                    "m10": 0.0, "m11": 1.0, "m12": 0.0,
+                    "m20": 0.0, "m21": 0.0, "m22": 1.0
+                },
+                "gimbal_lock": false,
+                "warning": null
+            }),
+        ),
+        error_case(
+            "matrix3_ops",
+            "multiply_without_b",
+            |input| invoke(matrix3_ops_tool::compute_matrix3_ops, input),
+            json!({
+                "operation": "multiply",
+                "a": {
+                    "m00": 1.0, "m01": 0.0, "m02": 0.0,
+                    "m10": 0.0, "m11": 1.0, "m12": 0.0,
+                    "m20": 0.0, "m21": 0.0, "m22": 1.0
+                }
+            }),
+            "Operation 'multiply' requires b",
+        ),
+        case(
+            "matrix4_ops",
+            "compose_translations",
+            |input| invoke(matrix4_ops_tool::compute_matrix4_ops, input),
+            json!({
+                "operation": "multiply",
+                "a": [[1.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 2.0], [0.0, 0.0, 1.0, 3.0], [0.0, 0.0, 0.0, 1.0]],
+                "b": [[1.0, 0.0, 0.0, -1.0], [0.0, 1.0, 0.0, 0.5], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]]
+            }),
+            json!({
+                "operation": "multiply",
+                "matrix": [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 2.5], [0.0, 0.0, 1.0, 3.0], [0.0, 0.0, 0.0, 1.0]]
+            }),
+        ),
+        case(
+            "transform_points",
+            "translate",
+            |input| invoke(transform_points_tool::apply_transform, input),
+            json!({
+                "points": [{"x": 1.0, "y": 0.0, "z": 0.0}],
+                "translation": {"x": 10.0, "y": 0.0, "z": 0.0}
+            }),
+            json!({"points": [{"x": 11.0, "y": 0.0, "z": 0.0}], "count": 1}),
+        ),
+        case(
+            "rotate_points",
+            "quarter_turn_about_z",
+            |input| invoke(rotate_points_tool::rotate_points_logic, input),
+            json!({
+                "points": [{"x": 1.0, "y": 0.0, "z": 0.0}],
+                "axis_direction": {"x": 0.0, "y": 0.0, "z": 2.0},
+                "angle": std::f64::consts::FRAC_PI_2
+            }),
+            json!({
+                "points": [{"x": 0.0, "y": 1.0, "z": 0.0}],
+                "axis": {"x": 0.0, "y": 0.0, "z": 1.0},
+                "count": 1
+            }),
+        ),
+        case(
+            "look_at",
+            "down_negative_z",
+            |input| invoke(look_at_tool::look_at_logic, input),
+            json!({"eye": {"x": 0.0, "y": 0.0, "z": 5.0}, "target": {"x": 0.0, "y": 0.0, "z": 0.0}}),
+            json!({
+                "view_matrix": [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, -5.0], [0.0, 0.0, 0.0, 1.0]],
+                "forward": {"x": 0.0, "y": 0.0, "z": -1.0}
+            }),
+        ),
+        case(
+            "project_points",
+            "center_of_view",
+            |input| invoke(project_points_tool::project_points_logic, input),
+            json!({
+                "points": [{"x": 0.0, "y": 0.0, "z": -10.0}],
+                "projection": "perspective",
+                "fov_y": std::f64::consts::FRAC_PI_2,
+                "aspect": 2.0,
+                "near": 1.0,
+                "far": 100.0,
+                "viewport": {"width": 200.0, "height": 100.0}
+            }),
+            json!({
+                "points": [{"pixel": {"x": 100.0, "y": 50.0}, "behind_camera": false, "visible": true}],
+                "visible_count": 1,
+                "behind_count": 0
+            }),
+        ),
+        case(
+            "ray_aabb_intersection",
+            "front_face",
+            |input| {
+                invoke(
+                    ray_aabb_intersection_tool::ray_aabb_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "aabb": {"min": {"x": -1.0, "y": -1.0, "z": -1.0}, "max": {"x": 1.0, "y": 1.0, "z": 1.0}},
+                "ray": {"origin": {"x": 0.0, "y": 0.0, "z": -5.0}, "direction": {"x": 0.0, "y": 0.0, "z": 1.0}}
+            }),
+            json!({"intersects": true, "closest_distance": 4.0}),
+        ),
+        case(
+            "aabb_aabb_intersection",
+            "overlapping_boxes",
+            |input| invoke(aabb_aabb_intersection_tool::aabb_pair_logic, input),
+            json!({
+                "aabb1": {"min": {"x": 0.0, "y": 0.0, "z": 0.0}, "max": {"x": 2.0, "y": 2.0, "z": 2.0}},
+                "aabb2": {"min": {"x": 1.0, "y": 1.0, "z": 1.0}, "max": {"x": 3.0, "y": 3.0, "z": 3.0}}
+            }),
+            json!({
+                "intersects": true,
+                "touching": false,
+                "intersection_volume": 1.0,
+                "union_volume": 27.0,
+                "iou": 1.0 / 15.0,
+                "distance": 0.0
+            }),
+        ),
+        case(
+            "triangle_triangle_intersection",
+            "separated_planes",
+            |input| {
+                invoke(
+                    triangle_triangle_intersection_tool::triangle_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "triangle1": [
+                    {"x": 0.0, "y": 0.0, "z": 0.0},
+                    {"x": 1.0, "y": 0.0, "z": 0.0},
+                    {"x": 0.0, "y": 1.0, "z": 0.0}
+                ],
+                "triangle2": [
+                    {"x": 0.0, "y": 0.0, "z": 1.0},
+                    {"x": 1.0, "y": 0.0, "z": 1.0},
+                    {"x": 0.0, "y": 1.0, "z": 1.0}
+                ]
+            }),
+            json!({"intersects": false, "segment": null, "overlap_polygon": null}),
+        ),
+        case(
+            "mesh_raycast",
+            "single_triangle",
+            |input| invoke(mesh_raycast_tool::mesh_raycast_logic, input),
+            json!({
+                "vertices": [
+                    {"x": 0.0, "y": 0.0, "z": 0.0},
+                    {"x": 2.0, "y": 0.0, "z": 0.0},
+                    {"x": 0.0, "y": 2.0, "z": 0.0}
+                ],
+                "indices": [0, 1, 2],
+                "ray": {"origin": {"x": 0.5, "y": 0.5, "z": 3.0}, "direction": {"x": 0.0, "y": 0.0, "z": -1.0}}
+            }),
+            json!({
+                "hit": true,
+                "triangle_count": 1,
+                "nearest": {
+                    "triangle": 0,
+                    "distance": 3.0,
+                    "point": {"x": 0.5, "y": 0.5, "z": 0.0},
+                    "normal": {"x": 0.0, "y": 0.0, "z": 1.0},
+                    "front_facing": true
+                }
+            }),
+        ),
+        case(
+            "frustum_test",
+            "single_plane",
+            |input| invoke(frustum_test_tool::frustum_test_logic, input),
+            json!({
+                "planes": [{"normal": {"x": 0.0, "y": 0.0, "z": 1.0}, "d": 0.0}],
+                "spheres": [
+                    {"center": {"x": 0.0, "y": 0.0, "z": 5.0}, "radius": 1.0},
+                    {"center": {"x": 0.0, "y": 0.0, "z": 0.0}, "radius": 1.0},
+                    {"center": {"x": 0.0, "y": 0.0, "z": -5.0}, "radius": 1.0}
+                ]
+            }),
+            json!({
+                "spheres": [
+                    {"index": 0, "result": "inside"},
+                    {"index": 1, "result": "intersecting"},
+                    {"index": 2, "result": "outside", "culled_by": 0}
+                ],
+                "summary": {"inside": 1, "intersecting": 1, "outside": 1}
+            }),
+        ),
+        case(
+            "point_in_polyhedron",
+            "tetrahedron",
+            |input| invoke(point_in_polyhedron_tool::point_in_polyhedron_logic, input),
+            json!({
+                "vertices": [
+                    {"x": 0.0, "y": 0.0, "z": 0.0},
+                    {"x": 1.0, "y": 0.0, "z": 0.0},
+                    {"x": 0.0, "y": 1.0, "z": 0.0},
+                    {"x": 0.0, "y": 0.0, "z": 1.0}
+                ],
+                "indices": [0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3],
+                "points": [{"x": 0.1, "y": 0.1, "z": 0.1}, {"x": 2.0, "y": 2.0, "z": 2.0}]
+            }),
+            json!({
+                "results": [{"inside": true}, {"inside": false}],
+                "inside_count": 1,
+                "mesh": {"watertight": true, "triangle_count": 4, "vertex_count": 4}
+            }),
+        ),
+        case(
+            "point_in_ellipsoid",
+            "along_axes",
+            |input| invoke(point_in_ellipsoid_tool::point_in_ellipsoid_logic, input),
+            json!({
+                "ellipsoid": {
+                    "center": {"x": 0.0, "y": 0.0, "z": 0.0},
+                    "radii": {"x": 3.0, "y": 2.0, "z": 1.0}
+                },
+                "points": [{"x": 4.0, "y": 0.0, "z": 0.0}, {"x": 0.0, "y": 2.0, "z": 0.0}]
+            }),
+            json!({
+                "results": [
+                    {
+                        "inside": false,
+                        "distance": 1.0,
+                        "closest_point": {"x": 3.0, "y": 0.0, "z": 0.0},
+                        "normal": {"x": 1.0, "y": 0.0, "z": 0.0}
+                    },
+                    {"on_surface": true}
+                ],
+                "inside_count": 1
+            }),
+        ),
+        case(
+            "point_cloud_ops",
+            "centroid",
+            |input| invoke(point_cloud_ops_tool::compute_point_cloud_ops, input),
+            json!({
+                "operation": "centroid",
+                "points": [
+                    {"x": 0.0, "y": 0.0, "z": 0.0},
+                    {"x": 2.0, "y": 0.0, "z": 0.0},
+                    {"x": 2.0, "y": 4.0, "z": 0.0},
+                    {"x": 0.0, "y": 4.0, "z": 6.0}
+                ]
+            }),
+            json!({"point_count": 4, "centroid": {"x": 1.0, "y": 2.0, "z": 1.5}}),
+        ),
+        case(
+            "field_interpolation",
+            "linear_along_x",
+            |input| invoke(field_interpolation_tool::interpolate_field, input),
+            json!({
+                "grid": {
+                    "origin": {"x": 0.0, "y": 0.0, "z": 0.0},
+                    "spacing": {"x": 1.0, "y": 1.0, "z": 1.0},
+                    "counts": [2, 1, 1]
+                },
+                "scalars": [0.0, 10.0],
+                "points": [{"x": 0.25, "y": 0.0, "z": 0.0}]
+            }),
+            json!({
+                "field": "scalar",
+                "samples": [{"inside": true, "value": 2.5}],
+                "inside_count": 1,
+                "outside_count": 0
+            }),
+        ),
+        case(
+            "spline_interpolation",
+            "straight_line",
+            |input| invoke(spline_interpolation_tool::spline_logic, input),
+            json!({
+                "control_points": [
+                    {"x": 0.0, "y": 0.0, "z": 0.0},
+                    {"x": 1.0, "y": 0.0, "z": 0.0},
+                    {"x": 2.0, "y": 0.0, "z": 0.0}
+                ],
+                "samples_per_segment": 2
+            }),
+            json!({"spline_type": "catmull_rom", "closed": false, "segment_count": 2, "arc_length": 2.0}),
+        ),
+        case(
+            "rotation_matrix",
+            "x_zero",
+            |input| invoke(rotation_matrix_tool::compute_rotation_matrix, input),
+            json!({"axis": "x", "angle": 0.0}),
+            json!({
+                "matrix": {
+                    "m00": 1.0, "m01": 0.0, "m02": 0.0,
+                    "m10": 0.0, "m11": 1.0, "m12": 0.0,
+                    "m20": 0.0, "m21": 0.0, "m22": 1.0
+                }
+            }),
+        ),
+        case(
+            "sphere_ray_intersection",
+            "through_center",
+            |input| {
+                invoke(
+                    sphere_ray_intersection_tool::sphere_ray_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "sphere": {"center": {"x": 0.0, "y": 0.0, "z": 5.0}, "radius": 1.0},
+                "ray": {"origin": {"x": 0.0, "y": 0.0, "z": 0.0}, "direction": {"x": 0.0, "y": 0.0, "z": 1.0}}
+            }),
+            json!({
+                "intersects": true,
+                "closest_distance": 4.0,
+                "intersection_points": [{"distance": 4.0}, {"distance": 6.0}]
+            }),
+        ),
+        case(
+            "sphere_sphere_intersection",
+            "separate",
+            |input| {
+                invoke(
+                    sphere_sphere_intersection_tool::sphere_sphere_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "sphere1": {"center": {"x": 0.0, "y": 0.0, "z": 0.0}, "radius": 1.0},
+                "sphere2": {"center": {"x": 5.0, "y": 0.0, "z": 0.0}, "radius": 1.0}
+            }),
+            json!({
+                "intersects": false,
+                "intersection_type": "separate",
+                "distance_between_centers": 5.0,
+                "intersection_circle": null
+            }),
+        ),
+        case(
+            "torus_ray_intersection",
+            "through_ring",
+            |input| {
+                invoke(
+                    torus_ray_intersection_tool::torus_ray_intersection_logic,
+                    input,
+                )
+            },
+            json!({
+                "torus": {
+                    "center": {"x": 0.0, "y": 0.0, "z": 0.0},
+                    "axis": {"x": 0.0, "y": 0.0, "z": 1.0},
+                    "major_radius": 3.0,
+                    "minor_radius": 1.0
+                },
+                "ray": {"origin": {"x": -10.0, "y": 0.0, "z": 0.0}, "direction": {"x": 1.0, "y": 0.0, "z": 0.0}}
+            }),
+            json!({
+                "intersects": true,
+                "closest_distance": 6.0,
+                "intersection_points": [
+                    {"distance": 6.0, "point": {"x": -4.0, "y": 0.0, "z": 0.0}},
+                    {"distance": 8.0},
+                    {"distance": 12.0},
+                    {"distance": 14.0}
+                ]
+            }),
+        ),
+        case(
+            "sphere_fit",
+            "unit_sphere_axes",
+            |input| invoke(sphere_fit_tool::sphere_fit_logic, input),
+            json!({
+                "points": [
+                    {"x": 1.0, "y": 0.0, "z": 0.0},
+                    {"x": -1.0, "y": 0.0, "z": 0.0},
+                    {"x": 0.0, "y": 1.0, "z": 0.0},
+                    {"x": 0.0, "y": -1.0, "z": 0.0},
+                    {"x": 0.0, "y": 0.0, "z": 1.0},
+                    {"x": 0.0, "y": 0.0, "z": -1.0}
+                ],
+                "method": "algebraic"
+            }),
+            json!({"center": {"x": 0.0, "y": 0.0, "z": 0.0}, "radius": 1.0, "rms_residual": 0.0}),
+        ),
+        case(
+            "fit_plane",
+            "flat_square",
+            |input| invoke(fit_plane_tool::fit_plane_logic, input),
+            json!({
+                "points": [
+                    {"x": 0.0, "y": 0.0, "z": 2.0},
+                    {"x": 1.0, "y": 0.0, "z": 2.0},
+                    {"x": 1.0, "y": 1.0, "z": 2.0},
+                    {"x": 0.0, "y": 1.0, "z": 2.0}
+                ],
+                "method": "pca"
+            }),
+            json!({
+                "plane": {"point": {"x": 0.5, "y": 0.5, "z": 2.0}},
+                "rms_error": 0.0,
+                "max_abs_error": 0.0,
+                "method": "pca"
+            }),
+        ),
+        case(
+            "sphere_volume",
+            "unit_radius",
+            |input| invoke(sphere_volume_tool::compute_sphere_volume, input),
+            json!({"center": {"x": 0.0, "y": 0.0, "z": 0.0}, "radius": 1.0}),
+            json!({"volume": 4.0 / 3.0 * std::f64::consts::PI, "radius": 1.0}),
+        ),
+        case(
+            "spherical_to_cartesian",
+            "positive_z_pole",
+            |input| {
+                invoke(
+                    spherical_to_cartesian_tool::spherical_to_cartesian_logic,
+                    input,
+                )
+            },
+            json!({"coordinates": {"radius": 2.0, "theta": 0.0, "phi": 0.0}}),
+            json!({"cartesian_coordinates": {"x": 0.0, "y": 0.0, "z": 2.0}}),
+        ),
+        case(
+            "cartesian_to_cylindrical",
+            "on_x_axis",
+            |input| {
+                invoke(
+                    cartesian_to_cylindrical_tool::cartesian_to_cylindrical_logic,
+                    input,
+                )
+            },
+            json!({"x": 1.0, "y": 0.0, "z": 2.0}),
+            json!({"cylindrical_coordinates": {"radius": 1.0, "theta": 0.0, "z": 2.0}}),
+        ),
+        case(
+            "cylindrical_to_cartesian",
+            "on_x_axis",
+            |input| {
+                invoke(
+                    cylindrical_to_cartesian_tool::cylindrical_to_cartesian_logic,
+                    input,
+                )
+            },
+            json!({"radius": 1.0, "theta": 0.0, "z": 2.0}),
+            json!({"cartesian_coordinates": {"x": 1.0, "y": 0.0, "z": 2.0}}),
+        ),
+        case(
+            "tetrahedron_volume",
+            "unit_tetrahedron",
+            |input| invoke(tetrahedron_volume_tool::compute_tetrahedron_volume, input),
+            json!({
+                "point_a": {"x": 0.0, "y": 0.0, "z": 0.0},
+                "point_b": {"x": 1.0, "y": 0.0, "z": 0.0},
+                "point_c": {"x": 0.0, "y": 1.0, "z": 0.0},
+                "point_d": {"x": 0.0, "y": 0.0, "z": 1.0}
+            }),
+            json!({"volume": 1.0 / 6.0, "calculation_method": "Scalar triple product"}),
+        ),
+        case(
+            "vector_angle",
+            "perpendicular",
+            |input| invoke(vector_angle_tool::vector_angle_logic, input),
+            json!({"vector1": {"x": 1.0, "y": 0.0, "z": 0.0}, "vector2": {"x": 0.0, "y": 1.0, "z": 0.0}}),
+            json!({
+                "angle_radians": std::f64::consts::FRAC_PI_2,
+                "angle_degrees": 90.0,
+                "cos_angle": 0.0,
+                "is_perpendicular": true,
+                "is_parallel": false
+            }),
+        ),
+        error_case(
+            "vector_analysis",
+            "two_dimensional",
+            |input| {
+                invoke(
+                    |input: vector_analysis::LogicInput| {
+                        ftl_yield::block_on(vector_analysis::analyze_vectors(input))
+                    },
+                    input,
+                )
+            },
+            json!({"vector_a": [1.0, 2.0], "vector_b": [3.0, 4.0, 5.0]}),
+            "Both vectors must be 3-dimensional",
+        ),
+        case(
+            "vector_magnitude",
+            "three_four_five",
+            |input| invoke(vector_magnitude::compute_vector_magnitude, input),
+            json!({"vector": {"x": 3.0, "y": 4.0, "z": 0.0}}),
+            json!({
+                "magnitude": 5.0,
+                "is_zero_vector": false,
+                "unit_vector": {"x": 0.6, "y": 0.8, "z": 0.0}
+            }),
+        ),
+        case(
+            "coordinate_conversion",
+            "cartesian_to_cylindrical",
+            |input| {
+                invoke(
+                    math3d_coordinate_conversion_tool::coordinate_conversion_logic,
+                    input,
+                )
+            },
+            json!({
+                "from_type": "cartesian",
+                "to_type": "cylindrical",
+                "coordinates": {"x": 0.0, "y": 2.0, "z": 3.0}
+            }),
+            json!({
+                "converted": {"x": 2.0, "y": std::f64::consts::FRAC_PI_2, "z": 3.0},
+                "from_type": "cartesian",
+                "to_type": "cylindrical"
+            }),
+        ),
+    ]
+}
+
+fn statistics_cases() -> Vec<GoldenCase> {
+    vec![
+        error_case(
+            "analyze_distribution",
+            "too_few_points",
+            |input| {
+                invoke(
+                    |input: analyze_distribution::LogicInput| {
+                        ftl_yield::block_on(analyze_distribution::calculate_analyze_distribution(
+                            input,
+                        ))
+                    },
+                    input,
+                )
+            },
+            json!({"data": [1.0, 2.0]}),
+            "Need at least 3 data points",
+        ),
+        case(
+            "correlation_matrix",
+            "single_variable",
+            |input| {
+                invoke(
+                    |input: correlation_matrix::LogicMultiSeriesInput| {
+                        ftl_yield::block_on(correlation_matrix::calculate_correlation_matrix(input))
+                    },
+                    input,
+                )
+            },
+            json!({"data": [[1.0, 2.0, 3.0, 4.0, 5.0]], "variable_names": ["X"]}),
+            json!({"correlation_matrix": [[1.0]], "variables": ["X"], "sample_size": 5}),
+        ),
+        case(
+            "descriptive_statistics",
+            "one_to_five",
+            |input| {
+                invoke(
+                    descriptive_statistics_tool::descriptive_statistics_logic,
+                    input,
+                )
+            },
+            json!({"data": [1.0, 2.0, 3.0, 4.0, 5.0]}),
+            json!({
+                "count": 5,
+                "mean": 3.0,
+                "median": 3.0,
+                "min": 1.0,
+                "max": 5.0,
+                "range": 4.0,
+                "sum": 15.0
+            }),
+        ),
+        case(
+            "histogram",
+            "five_bins",
+            |input| {
+                invoke(
+                    |input: histogram::LogicInput| {
+                        ftl_yield::block_on(histogram::generate_histogram(input))
+                    },
+                    input,
+                )
+            },
+            json!({"data": [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0], "num_bins": 5}),
+            json!({"total_count": 10, "bin_width": 1.8, "range": [1.0, 10.0]}),
+        ),
+        case(
+            "linear_regression",
+            "y_equals_two_x",
+            |input| {
+                invoke(
+                    |input: linear_regression::LogicInput| {
+                        ftl_yield::block_on(linear_regression::calculate_linear_regression(input))
+                    },
+                    input,
+                )
+            },
+            json!({"x": [1.0, 2.0, 3.0, 4.0, 5.0], "y": [2.0, 4.0, 6.0, 8.0, 10.0]}),
+            json!({"slope": 2.0, "intercept": 0.0, "r_squared": 1.0, "sample_size": 5}),
+        ),
+        case(
+            "pearson_correlation",
+            "y_equals_two_x",
+            |input| invoke(pearson_correlation::calculate_correlation, input),
+            json!({"x": [1.0, 2.0, 3.0, 4.0, 5.0], "y": [2.0, 4.0, 6.0, 8.0, 10.0]}),
+            json!({
+                "correlation_coefficient": 1.0,
+                "sample_size": 5,
+                "interpretation": "very strong positive correlation"
+            }),
+        ),
+        case(
+            "polynomial_regression",
+            "degree_one",
+            |input| {
+                invoke(
+                    |input: polynomial_regression::LogicInput| {
+                        ftl_yield::block_on(polynomial_regression::calculate_polynomial_regression(
+                            input,
+                        ))
+                    },
+                    input,
+                )
+            },
+            json!({"x": [1.0, 2.0, 3.0, 4.0, 5.0], "y": [2.0, 4.0, 6.0, 8.0, 10.0], "degree": 1}),
+            json!({"degree": 1, "coefficients": [0.0, 2.0], "r_squared": 1.0}),
+        ),
+        case(
+            "predict_values",
+            "slope_two_intercept_one",
+            |input| invoke(predict_values::predict_values_logic, input),
+            json!({"slope": 2.0, "intercept": 1.0, "x_values": [1.0, 2.0, 3.0]}),
+            json!({
+                "predictions": [{"y_predicted": 3.0}, {"y_predicted": 5.0}, {"y_predicted": 7.0}]
+            }),
+        ),
+        case(
+            "spearman_correlation",
+            "monotonic_quadratic",
+            |input| invoke(spearman_correlation::calculate_spearman_correlation, input),
+            json!({"x": [1.0, 2.0, 3.0, 4.0, 5.0], "y": [1.0, 4.0, 9.0, 16.0, 25.0]}),
+            json!({"correlation_coefficient": 1.0, "sample_size": 5}),
+        ),
+        case(
+            "summary_statistics",
+            "one_to_five",
+            |input| invoke(summary_statistics::summary_statistics_logic, input),
+            json!({"data": [1.0, 2.0, 3.0, 4.0, 5.0]}),
+            json!({
+                "count": 5,
+                "mean": 3.0,
+                "min": 1.0,
+                "max": 5.0,
+                "median": 3.0,
+                "std_dev": std::f64::consts::SQRT_2
+            }),
+        ),
+        case(
+            "test_normality",
+            "symmetric_sample",
+            |input| invoke(test_normality::calculate_test_normality, input),
+            json!({"data": [1.0, 2.0, 3.0, 4.0, 5.0, 4.0, 3.0, 2.0, 1.0, 3.0]}),
+            json!({"confidence_level": 0.05}),
+        ),
+        case(
+            "tdigest",
+            "exact_extremes",
+            |input| invoke(tdigest_tool::process_tdigest, input),
+            json!({"data": [1.0, 2.0, 3.0, 4.0, 5.0], "quantiles": [0.0, 1.0]}),
+            json!({
+                "count": 5.0,
+                "mean": 3.0,
+                "min": 1.0,
+                "max": 5.0,
+                "quantiles": [{"value": 1.0}, {"value": 5.0}]
+            }),
+        ),
+        case(
+            "hll",
+            "duplicates_count_once",
+            |input| invoke(hll_tool::estimate_cardinality, input),
+            json!({"items": ["a", "b", "c", "a", "b", "a"]}),
+            json!({"estimate": 3.0010991695491978, "items_added": 6}),
+        ),
+        case(
+            "bloom",
+            "sizing",
+            |input| invoke(bloom_tool::process_bloom_filter, input),
+            json!({"keys": [], "expected_items": 1000}),
+            json!({"bits": 9586, "hash_functions": 7, "bits_set": 0, "false_positive_rate": 0.0}),
+        ),
+        case(
+            "reservoir_sample",
+            "fills_before_sampling",
+            |input| invoke(reservoir_sample_tool::update_reservoir, input),
+            json!({"values": [0.0, 1.0, 2.0, 3.0, 4.0], "sample_size": 10}),
+            json!({
+                "sample": [0.0, 1.0, 2.0, 3.0, 4.0],
+                "items_seen": 5,
+                "capacity": 10,
+                "total_weight": null
+            }),
+        ),
+        case(
+            "power_analysis",
+            "t_test_sample_size",
+            |input| invoke(power_analysis_tool::power_analysis_logic, input),
+            json!({"test": "t_test", "effect_size": 0.5}),
+            json!({
+                "solved_for": "sample_size",
+                "sample_size": 64,
+                "total_sample_size": 128,
+                "effect_magnitude": "medium"
+            }),
+        ),
+        case(
+            "effect_size",
+            "cohens_d_from_summaries",
+            |input| invoke(effect_size_tool::effect_size_logic, input),
+            json!({
+                "measure": "cohens_d",
+                "summaries": [
+                    {"mean": 105.0, "sd": 10.0, "n": 30},
+                    {"mean": 100.0, "sd": 10.0, "n": 30}
+                ]
+            }),
+            json!({"estimate": 0.5, "magnitude": "medium"}),
+        ),
+        case(
+            "impute_missing",
+            "mean_fill",
+            |input| invoke(impute_missing_tool::impute_missing_logic, input),
+            json!({"series": [[1.0, null, 2.0, 2.0, 10.0, null]], "strategy": "mean"}),
+            json!({
+                "series": [[1.0, 3.75, 2.0, 2.0, 10.0, 3.75]],
+                "imputed_count": 2,
+                "remaining_missing": 0
+            }),
+        ),
+        case(
+            "scale_data",
+            "min_max",
+            |input| invoke(scale_data_tool::scale_data_logic, input),
+            json!({"series": [[2.0, 4.0, 6.0, 10.0]], "method": "min_max"}),
+            json!({"series": [[0.0, 0.25, 0.5, 1.0]]}),
+        ),
+        case(
+            "eval_metrics",
+            "regression",
+            |input| invoke(eval_metrics_tool::eval_metrics_logic, input),
+            json!({"actual": [3.0, -0.5, 2.0, 7.0], "predicted": [2.5, 0.0, 2.0, 8.0]}),
+            json!({"regression": {"mae": 0.5, "mse": 0.375}, "classification": null}),
+        ),
+        case(
+            "naive_bayes_train",
+            "gaussian_parameters",
+            |input| invoke(naive_bayes_train_tool::naive_bayes_train_logic, input),
+            json!({
+                "features": [[1.0, 10.0], [3.0, 12.0], [8.0, 1.0], [10.0, 3.0]],
+                "labels": ["b", "b", "a", "a"],
+                "variant": "gaussian"
+            }),
+            json!({
+                "variant": "gaussian",
+                "feature_count": 2,
+                "classes": [{"label": "a", "means": [9.0, 2.0], "prior": 0.5}, {"label": "b", "prior": 0.5}],
+                "training_accuracy": 1.0
+            }),
+        ),
+        case(
+            "naive_bayes_predict",
+            "trained_gaussian",
+            |input| {
+                // The tool takes an encoded model, so train one on the same input first
+                let queries = input["queries"].clone();
+                let model =
+                    invoke(naive_bayes_train_tool::naive_bayes_train_logic, input)?["model"]
+                        .clone();
+                invoke(
+                    naive_bayes_predict_tool::naive_bayes_predict_logic,
+                    json!({"model": model, "features": queries}),
+                )
+            },
+            json!({
+                "features": [[1.0], [2.0], [3.0], [7.0], [8.0], [9.0]],
+                "labels": ["small", "small", "small", "large", "large", "large"],
+                "variant": "gaussian",
+                "queries": [[1.5], [8.5]]
+            }),
+            json!({
+                "classes": ["large", "small"],
+                "predictions": [{"label": "small"}, {"label": "large"}]
+            }),
+        ),
+        case(
+            "knn_predict",
+            "majority_vote",
+            |input| invoke(knn_predict_tool::knn_predict_logic, input),
+            json!({
+                "features": [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [5.0, 5.0], [5.0, 6.0]],
+                "labels": ["a", "a", "b", "c", "c"],
+                "queries": [[0.2, 0.2], [5.0, 5.4]],
+                "k": 3
+            }),
+            json!({
+                "task": "classification",
+                "predictions": [{"label": "a", "confidence": 2.0 / 3.0}, {"label": "c"}]
+            }),
+        ),
+        case(
+            "decision_tree_train",
+            "single_split",
+            |input| invoke(decision_tree_train_tool::decision_tree_train_logic, input),
+            json!({
+                "features": [[1.0, 7.0], [2.0, 3.0], [3.0, 9.0], [6.0, 2.0], [7.0, 8.0], [8.0, 4.0]],
+                "labels": ["small", "small", "small", "large", "large", "large"],
+                "feature_names": ["size", "noise"]
+            }),
+            json!({
+                "tree": {"rule": "size <= 4.5", "impurity": 0.5},
+                "rules": ["IF size <= 4.5 THEN small", "IF size > 4.5 THEN large"],
+                "depth": 1,
+                "leaf_count": 2,
+                "training_accuracy": 1.0
+            }),
+        ),
+        case(
+            "decision_tree_predict",
+            "single_split",
+            |input| {
+                invoke(
+                    decision_tree_predict_tool::decision_tree_predict_logic,
+                    input,
+                )
+            },
+            json!({
+                "tree": {
+                    "feature": 0,
+                    "feature_name": "size",
+                    "threshold": 4.5,
+                    "rule": "size <= 4.5",
+                    "samples": 6,
+                    "impurity": 0.5,
+                    "label": "large",
+                    "confidence": 0.5,
+                    "children": [
+                        {"samples": 3, "impurity": 0.0, "label": "small", "confidence": 1.0, "children": []},
+                        {"samples": 3, "impurity": 0.0, "label": "large", "confidence": 1.0, "children": []}
+                    ]
+                },
+                "features": [[2.0], [7.0]]
+            }),
+            json!({
+                "task": "classification",
+                "predictions": [
+                    {"label": "small", "path": ["size <= 4.5"], "leaf_samples": 3},
+                    {"label": "large", "path": ["size > 4.5"], "leaf_samples": 3}
+                ]
+            }),
+        ),
+        case(
+            "text_vectorize",
+            "counts_and_vocabulary",
+            |input| invoke(text_vectorize_tool::text_vectorize_logic, input),
+            json!({"documents": ["The cat sat. The cat!", "A dog sat"], "weighting": "count"}),
+            json!({
+                "vocabulary": ["cat", "dog", "sat", "the"],
+                "document_frequencies": [1, 1, 2, 1],
+                "rows": [{"indices": [0, 2, 3], "values": [2.0, 1.0, 2.0]}, {"indices": [1, 2], "values": [1.0, 1.0]}]
+            }),
+        ),
+        case(
+            "topic_cluster",
+            "two_topics",
+            |input| invoke(topic_cluster_tool::topic_cluster_logic, input),
+            json!({
+                "documents": [
+                    "the cat chased the mouse",
+                    "a cat and a kitten sleep",
+                    "the kitten is a young cat",
+                    "stocks fell as the market dropped",
+                    "the market rallied and stocks rose",
+                    "investors sold stocks in the market"
+                ],
+                "clusters": 2
+            }),
+            json!({"converged": true, "clusters": [{"size": 3}, {"size": 3}]}),
+        ),
+    ]
+}
+
+fn string_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "string_case_converter",
+            "to_lowercase",
+            |input| invoke(string_case_converter_tool::convert_case, input),
+            json!({"text": "HELLO World", "target_case": "lower"}),
+            json!({"converted": "hello world", "target_case": "lower", "changed": true}),
+        ),
+        case(
+            "string_trimmer",
+            "trim_whitespace",
+            |input| invoke(string_trimmer_tool::process_string, input),
+            json!({"text": "  hello world  ", "operation": "trim"}),
+            json!({"processed": "hello world", "length_before": 15, "length_after": 11}),
+        ),
+        case(
+            "string_splitter",
+            "comma_delimited",
+            |input| invoke(string_splitter_tool::split_string, input),
+            json!({"text": "apple,banana,cherry", "delimiter": ","}),
+            json!({"parts": ["apple", "banana", "cherry"], "count": 3}),
+        ),
+    ]
+}
+
+fn validation_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "email_validator",
+            "valid_address",
+            |input| invoke(email_validator_tool::validate_email, input),
+            json!({"email": "test@example.com"}),
+            json!({"is_valid": true}),
+        ),
+        case(
+            "email_validator",
+            "missing_at_symbol",
+            |input| invoke(email_validator_tool::validate_email, input),
+            json!({"email": "test"}),
+            json!({"is_valid": false}),
+        ),
+        case(
+            "url_validator",
+            "valid_https",
+            |input| invoke(url_validator_tool::validate_url, input),
+            json!({"url": "https://www.example.com"}),
+            json!({"is_valid": true}),
+        ),
+        case(
+            "url_validator",
+            "invalid_syntax",
+            |input| invoke(url_validator_tool::validate_url, input),
+            json!({"url": "not a url"}),
+            json!({"is_valid": false}),
+        ),
+        case(
+            "regex_matcher",
+            "first_match",
+            |input| invoke(regex_matcher_tool::match_regex, input),
+            json!({"text": "The quick brown fox", "pattern": "quick"}),
+            json!({
+                "has_match": true,
+                "match_count": 1,
+                "matches": [{"text": "quick", "start": 4, "end": 9}]
+            }),
+        ),
+        case(
+            "regex_matcher",
+            "find_all",
+            |input| invoke(regex_matcher_tool::match_regex, input),
+            json!({"text": "cat bat rat", "pattern": "\\b\\w{3}\\b", "find_all": true}),
+            json!({
+                "match_count": 3,
+                "matches": [{"text": "cat"}, {"text": "bat"}, {"text": "rat"}]
+            }),
+        ),
+        case(
+            "check_digit",
+            "luhn_generate",
+            |input| invoke(check_digit_tool::check_digit_logic, input),
+            json!({"scheme": "luhn", "mode": "generate", "value": "7992739871"}),
+            json!({
+                "scheme": "luhn",
+                "mode": "generate",
+                "results": [{"check_digit": "3", "with_check_digit": "79927398713"}]
+            }),
+        ),
+        case(
+            "address_parse",
+            "us_address",
+            |input| invoke(address_parse_tool::parse_address, input),
+            json!({"address": "123 North Main Street Apt 4b, Springfield, IL 62704-1234"}),
+            json!({
+                "components": {
+                    "house_number": "123",
+                    "street": "North Main Street",
+                    "unit": "Apt 4B",
+                    "city": "Springfield",
+                    "region": "IL",
+                    "postal_code": "62704-1234",
+                    "country": "US"
+                },
+                "normalized": "123 N Main St Apt 4B, Springfield, IL 62704-1234, United States",
+                "missing": []
+            }),
+        ),
+        case(
+            "postal_code",
+            "us_zip_plus_four",
+            |input| invoke(postal_code_tool::postal_code_logic, input),
+            json!({"postal_codes": ["60601 1234", "1234"], "country": "us"}),
+            json!({
+                "results": [
+                    {"valid": true, "normalized": "60601-1234", "centroid_area": "Chicago"},
+                    {"valid": false}
+                ],
+                "valid_count": 1,
+                "invalid_count": 1
+            }),
+        ),
+    ]
+}
+
+fn visualization_cases() -> Vec<GoldenCase> {
+    vec![
+        case(
+            "svg_path",
+            "square",
+            |input| invoke(svg_path_tool::svg_path_logic, input),
+            json!({"path": "M0 0 H10 V10 H0 Z"}),
+            json!({
+                "path": {
+                    "normalized": "M 0 0 L 10 0 L 10 10 L 0 10 Z",
+                    "subpath_count": 1,
+                    "segment_count": 4,
+                    "length": 40.0
+                }
+            }),
+        ),
+        case(
+            "plot_spec",
+            "histogram_bars",
+            |input| invoke(plot_spec_tool::plot_spec_logic, input),
+            json!({
+                "chart": "histogram",
+                "result": {
+                    "bins": [
+                        {"lower_bound": 0.0, "upper_bound": 1.0, "count": 2, "frequency": 0.5, "density": 0.5},
+                        {"lower_bound": 1.0, "upper_bound": 2.0, "count": 2, "frequency": 0.5, "density": 0.5}
+                    ],
+                    "total_count": 4,
+                    "bin_width": 1.0,
+                    "range": [0.0, 2.0]
+                }
+            }),
+            json!({
+                "chart": "histogram",
+                "spec": {"mark": {"type": "bar"}},
+                "data_points": 2
+            }),
+        ),
+        case(
+            "heatmap_bin",
+            "matrix_cells",
+            |input| invoke(heatmap_bin_tool::heatmap_bin_logic, input),
+            json!({"matrix": [[0.0, 1.0], [2.0, 4.0]]}),
+            json!({
+                "x_edges": [0.0, 1.0, 2.0],
+                "grid": [[0.0, 0.25], [0.5, 1.0]],
+                "aggregate": "mean"
+            }),
+        ),
+    ]
+}
+
+/// Names of the tools covered by the golden cases, in run order
+pub fn available_tools() -> Vec<&'static str> {
+    let mut tools: Vec<&'static str> = Vec::new();
+    for case in golden_cases() {
+        if !tools.contains(&case.tool) {
+            tools.push(case.tool);
+        }
+    }
+    tools
+}
+
+pub fn run_self_test(input: SelfTestInput) -> Result<SelfTestOutput, String> {
+    let available = available_tools();
+    let selected: Vec<&'static str> = match &input.tools {
+        None => available.clone(),
+        Some(requested) => {
+            if requested.is_empty() {
+                return Err("tools must list at least one tool".to_string());
+            }
+            if let Some(unknown) = requested.iter().find(|t| !available.contains(&t.as_str())) {
+                return Err(format!(
+                    "Unknown tool '{unknown}'. Available tools: {}",
+                    available.join(", ")
+                ));
+            }
+            available
+                .iter()
+                .copied()
+                .filter(|tool| requested.iter().any(|t| t == tool))
+                .collect()
+        }
+    };
+
+    let cases = golden_cases();
+    let tools: Vec<ToolReport> = selected
+        .into_iter()
+        .map(|tool| {
+            let tool_cases: Vec<&GoldenCase> = cases.iter().filter(|c| c.tool == tool).collect();
+            let failures: Vec<CaseFailure> = tool_cases
+                .iter()
+                .filter_map(|c| {
+                    run_case(c).err().map(|message| CaseFailure {
+                        case: c.name.to_string(),
+                        message,
+                    })
+                })
+                .collect();
+            ToolReport {
+                tool: tool.to_string(),
+                passed: failures.is_empty(),
+                cases_run: tool_cases.len(),
+                cases_passed: tool_cases.len() - failures.len(),
+                failures,
+            }
+        })
+        .collect();
+
+    let cases_run = tools.iter().map(|t| t.cases_run).sum();
+    let cases_failed = tools.iter().map(|t| t.failures.len()).sum();
+    let tools_failed = tools.iter().filter(|t| !t.passed).count();
+
+    Ok(SelfTestOutput {
+        passed: tools_failed == 0,
+        tools_tested: tools.len(),
+        tools_failed,
+        cases_run,
+        cases_failed,
+        tools,
+    })
+}
+
+fn run_case(case: &GoldenCase) -> Result<(), String> {
+    match (&case.expected, (case.run)(case.input.clone())) {
+        (Expected::Output(expected), Ok(actual)) => compare(expected, &actual, "output"),
+        (Expected::Output(_), Err(e)) => Err(format!("Unexpected error: {e}")),
+        (Expected::Error(expected), Err(e)) if e.contains(expected) => Ok(()),
+        (Expected::Error(expected), Err(e)) => {
+            Err(format!("Expected error containing '{expected}', got '{e}'"))
+        }
+        (Expected::Error(expected), Ok(_)) => Err(format!(
+            "Expected error containing '{expected}', but the call succeeded"
+        )),
+    }
+}
+
+/// Check that every field of `expected` appears in `actual` with the same
+/// value. Objects may carry extra fields; arrays must match element by element.
+fn compare(expected: &Value, actual: &Value, path: &str) -> Result<(), String> {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected) in expected {
+                let field = format!("{path}.{key}");
+                let actual = actual
+                    .get(key)
+                    .ok_or_else(|| format!("{field}: missing from output"))?;
+                compare(expected, actual, &field)?;
+            }
+            Ok(())
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            if expected.len() != actual.len() {
+                return Err(format!(
+                    "{path}: expected {} items, got {}",
+                    expected.len(),
+                    actual.len()
+                ));
+            }
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                compare(expected, actual, &format!("{path}[{i}]"))?;
+            }
+            Ok(())
+        }
+        (Value::Number(e), Value::Number(a)) => {
+            let (e, a) = (
+                e.as_f64().unwrap_or(f64::NAN),
+                a.as_f64().unwrap_or(f64::NAN),
+            );
+            if (e - a).abs() <= TOLERANCE * e.abs().max(1.0) {
+                Ok(())
+            } else {
+                Err(format!("{path}: expected {e}, got {a}"))
+            }
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(format!("{path}: expected {expected}, got {actual}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_golden_cases_pass() {
+        let result = run_self_test(SelfTestInput { tools: None }).unwrap();
+        let failures: Vec<String> = result
+            .tools
+            .iter()
+            .flat_map(|t| {
+                t.failures
+                    .iter()
+                    .map(move |f| format!("{}/{}: {}", t.tool, f.case, f.message))
+            })
+            .collect();
+        assert!(result.passed, "golden case failures: {failures:?}");
+        assert_eq!(result.tools_tested, available_tools().len());
+        assert_eq!(result.cases_failed, 0);
+    }
+
+    #[test]
+    fn test_every_tool_has_a_golden_case() {
+        // Every workspace member with a library mode is a tool self_test can reach;
+        // category tools only fan out over HTTP and have none
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../..");
+        let read = |path: &std::path::Path| {
+            std::fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+        };
+        let workspace = read(&root.join("Cargo.toml"));
+        let members = workspace
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("\"tools/"))
+            .filter_map(|line| line.strip_suffix("\","));
+
+        let names: Vec<String> = members
+            .filter(|member| {
+                let manifest = read(&root.join("tools").join(member).join("Cargo.toml"));
+                manifest.lines().any(|line| line == "library = []")
+            })
+            .map(|member| {
+                let lib = read(&root.join("tools").join(member).join("src/lib.rs"));
+                let start = lib
+                    .find("ToolLog::start(\"")
+                    .unwrap_or_else(|| panic!("{member}: no ToolLog::start call"))
+                    + "ToolLog::start(\"".len();
+                let end = start + lib[start..].find('"').unwrap();
+                lib[start..end].to_string()
+            })
+            .collect();
+
+        // Tools sharing a name need a case each
+        let cases = golden_cases();
+        let count = |name: &String| names.iter().filter(|n| *n == name).count();
+        let mut missing: Vec<&String> = names
+            .iter()
+            .filter(|name| cases.iter().filter(|c| c.tool == name.as_str()).count() < count(name))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        assert!(!names.is_empty());
+        assert!(
+            missing.is_empty(),
+            "tools without golden cases: {missing:?}"
+        );
+    }
+
+    #[test]
+    fn test_filter_by_tool() {
+        let result = run_self_test(SelfTestInput {
+            tools: Some(vec!["regex_matcher".to_string(), "divide".to_string()]),
+        })
+        .unwrap();
+        let tools: Vec<&str> = result.tools.iter().map(|t| t.tool.as_str()).collect();
+        assert_eq!(tools, vec!["divide", "regex_matcher"]);
+        assert_eq!(result.cases_run, 4);
+    }
+
+    #[test]
+    fn test_unknown_tool_rejected() {
+        let err = run_self_test(SelfTestInput {
+            tools: Some(vec!["teleport".to_string()]),
+        })
+        .unwrap_err();
+        assert!(err.contains("Unknown tool 'teleport'"));
+    }
+
+    #[test]
+    fn test_compare_reports_differences() {
+        let actual = json!({"result": 8.0, "inputs": [5.0, 3.0], "extra": true});
+        assert!(compare(&json!({"result": 8.0 + 1e-12}), &actual, "output").is_ok());
+
+        let err = compare(&json!({"result": 9.0}), &actual, "output").unwrap_err();
+        assert_eq!(err, "output.result: expected 9, got 8");
+
+        let err = compare(&json!({"inputs": [5.0]}), &actual, "output").unwrap_err();
+        assert!(err.contains("expected 1 items, got 2"));
+
+        let err = compare(&json!({"missing": 1}), &actual, "output").unwrap_err();
+        assert!(err.contains("missing from output"));
+    }
+}
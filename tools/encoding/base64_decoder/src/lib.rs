@@ -3,12 +3,21 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{Base64DecoderInput as LogicInput, Base64DecoderOutput as LogicOutput};
+pub use logic::{
+    Base64DecoderInput as LogicInput, Base64DecoderOutput as LogicOutput, decode_base64,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +27,8 @@ pub struct Base64DecoderInput {
     /// Decoding variant (optional, default: "standard")
     /// Options: "standard", "standard_no_pad", "url_safe", "url_safe_no_pad"
     pub variant: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -36,8 +47,32 @@ pub struct Base64DecoderOutput {
     pub is_valid_utf8: bool,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn base64_decoder(input: Base64DecoderInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn base64_decoder(request: Request<Base64DecoderInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "base64_decoder",
+                Base64DecoderInput,
+                Base64DecoderOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("base64_decoder", &input);
+    log.finish(run_base64_decoder(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_base64_decoder(input: Base64DecoderInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("encoded", &input.encoded).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         encoded: input.encoded,
@@ -56,7 +91,7 @@ pub fn base64_decoder(input: Base64DecoderInput) -> ToolResponse {
                 variant: result.variant,
                 is_valid_utf8: result.is_valid_utf8,
             };
-            ToolResponse::text(serde_json::to_string(&output).unwrap())
+            ToolResponse::text(ftl_json::to_string(&output).unwrap())
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -3,12 +3,21 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{Base64EncoderInput as LogicInput, Base64EncoderOutput as LogicOutput};
+pub use logic::{
+    Base64EncoderInput as LogicInput, Base64EncoderOutput as LogicOutput, encode_base64,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +27,8 @@ pub struct Base64EncoderInput {
     /// Encoding variant (optional, default: "standard")
     /// Options: "standard", "standard_no_pad", "url_safe", "url_safe_no_pad"
     pub variant: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -32,8 +43,32 @@ pub struct Base64EncoderOutput {
     pub variant: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn base64_encoder(input: Base64EncoderInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn base64_encoder(request: Request<Base64EncoderInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "base64_encoder",
+                Base64EncoderInput,
+                Base64EncoderOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("base64_encoder", &input);
+    log.finish(run_base64_encoder(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_base64_encoder(input: Base64EncoderInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("data", &input.data).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         data: input.data,
@@ -50,7 +85,7 @@ pub fn base64_encoder(input: Base64EncoderInput) -> ToolResponse {
                 encoded_length: result.encoded_length,
                 variant: result.variant,
             };
-            ToolResponse::text(serde_json::to_string(&output).unwrap())
+            ToolResponse::text(ftl_json::to_string(&output).unwrap())
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
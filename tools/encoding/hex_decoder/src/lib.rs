@@ -3,12 +3,19 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{HexDecoderInput as LogicInput, HexDecoderOutput as LogicOutput};
+pub use logic::{HexDecoderInput as LogicInput, HexDecoderOutput as LogicOutput, decode_hex};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -17,6 +24,8 @@ pub struct HexDecoderInput {
     pub encoded: String,
     /// Whether to ignore whitespace in the input (optional, default: true)
     pub ignore_whitespace: Option<bool>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -35,8 +44,28 @@ pub struct HexDecoderOutput {
     pub pairs_decoded: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn hex_decoder(input: HexDecoderInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn hex_decoder(request: Request<HexDecoderInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("hex_decoder", HexDecoderInput, HexDecoderOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("hex_decoder", &input);
+    log.finish(run_hex_decoder(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_hex_decoder(input: HexDecoderInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("encoded", &input.encoded).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         encoded: input.encoded,
@@ -55,7 +84,7 @@ pub fn hex_decoder(input: HexDecoderInput) -> ToolResponse {
                 is_valid_utf8: result.is_valid_utf8,
                 pairs_decoded: result.pairs_decoded,
             };
-            ToolResponse::text(serde_json::to_string(&output).unwrap())
+            ToolResponse::text(ftl_json::to_string(&output).unwrap())
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
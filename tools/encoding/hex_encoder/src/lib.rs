@@ -3,12 +3,19 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{HexEncoderInput as LogicInput, HexEncoderOutput as LogicOutput};
+pub use logic::{HexEncoderInput as LogicInput, HexEncoderOutput as LogicOutput, encode_hex};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +25,8 @@ pub struct HexEncoderInput {
     /// Output case (optional, default: "lowercase")
     /// Options: "lowercase", "uppercase"
     pub case: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -32,8 +41,28 @@ pub struct HexEncoderOutput {
     pub case: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn hex_encoder(input: HexEncoderInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn hex_encoder(request: Request<HexEncoderInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("hex_encoder", HexEncoderInput, HexEncoderOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("hex_encoder", &input);
+    log.finish(run_hex_encoder(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_hex_encoder(input: HexEncoderInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("data", &input.data).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         data: input.data,
@@ -50,7 +79,7 @@ pub fn hex_encoder(input: HexEncoderInput) -> ToolResponse {
                 encoded_length: result.encoded_length,
                 case: result.case,
             };
-            ToolResponse::text(serde_json::to_string(&output).unwrap())
+            ToolResponse::text(ftl_json::to_string(&output).unwrap())
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -3,12 +3,19 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{UrlDecoderInput as LogicInput, UrlDecoderOutput as LogicOutput};
+pub use logic::{UrlDecoderInput as LogicInput, UrlDecoderOutput as LogicOutput, decode_url};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +25,8 @@ pub struct UrlDecoderInput {
     /// Whether to decode plus signs as spaces (optional, default: false)
     /// This is common in query strings where spaces are encoded as +
     pub decode_plus: Option<bool>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -36,8 +45,28 @@ pub struct UrlDecoderOutput {
     pub error: Option<String>,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn url_decoder(input: UrlDecoderInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn url_decoder(request: Request<UrlDecoderInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("url_decoder", UrlDecoderInput, UrlDecoderOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("url_decoder", &input);
+    log.finish(run_url_decoder(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_url_decoder(input: UrlDecoderInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("encoded", &input.encoded).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         encoded: input.encoded,
@@ -56,7 +85,7 @@ pub fn url_decoder(input: UrlDecoderInput) -> ToolResponse {
                 is_valid_utf8: result.is_valid_utf8,
                 error: result.error,
             };
-            ToolResponse::text(serde_json::to_string(&output).unwrap())
+            ToolResponse::text(ftl_json::to_string(&output).unwrap())
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
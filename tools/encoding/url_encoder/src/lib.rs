@@ -3,12 +3,19 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{UrlEncoderInput as LogicInput, UrlEncoderOutput as LogicOutput};
+pub use logic::{UrlEncoderInput as LogicInput, UrlEncoderOutput as LogicOutput, encode_url};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -18,6 +25,8 @@ pub struct UrlEncoderInput {
     /// Encoding mode (optional, default: "component")
     /// Options: "component", "path", "query", "full"
     pub mode: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -34,8 +43,28 @@ pub struct UrlEncoderOutput {
     pub chars_encoded: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn url_encoder(input: UrlEncoderInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn url_encoder(request: Request<UrlEncoderInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("url_encoder", UrlEncoderInput, UrlEncoderOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("url_encoder", &input);
+    log.finish(run_url_encoder(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_url_encoder(input: UrlEncoderInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("data", &input.data).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         data: input.data,
@@ -53,7 +82,7 @@ pub fn url_encoder(input: UrlEncoderInput) -> ToolResponse {
                 mode: result.mode,
                 chars_encoded: result.chars_encoded,
             };
-            ToolResponse::text(serde_json::to_string(&output).unwrap())
+            ToolResponse::text(ftl_json::to_string(&output).unwrap())
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -0,0 +1,90 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    BalanceTerritoriesInput as LogicInput, BalanceTerritoriesOutput as LogicOutput, MAX_POINTS,
+    MAX_TERRITORIES, balance_territories_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    BalanceStatisticsSchema as BalanceStatistics,
+    BalanceTerritoriesOutputSchema as BalanceTerritoriesOutput, CoordinateSchema as Coordinate,
+    TerritoryPointSchema as TerritoryPoint, TerritorySchema as Territory,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceTerritoriesInput {
+    #[serde(flatten)]
+    pub params: logic::BalanceTerritoriesInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Divide weighted locations, such as accounts or stores, into contiguous territories of
+/// near-equal total weight, with centroids and balance statistics
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn balance_territories(request: Request<BalanceTerritoriesInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "balance_territories",
+                BalanceTerritoriesInput,
+                BalanceTerritoriesOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("balance_territories", &input);
+    log.finish(run_balance_territories(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_balance_territories(input: BalanceTerritoriesInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::balance_territories_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = BalanceTerritoriesOutput::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,684 @@
+use delaunay_triangulate_tool::{Frame, triangulate};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Most points accepted in one call
+pub const MAX_POINTS: usize = delaunay_triangulate_tool::MAX_POINTS;
+/// Most territories in one call
+pub const MAX_TERRITORIES: usize = 500;
+/// Most k-means iterations a caller may request
+const MAX_ITERATIONS: usize = 1000;
+/// Most balancing moves a caller may request
+const MAX_MOVES: usize = 100_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TerritoryPoint {
+    /// Latitude in decimal degrees
+    pub lat: f64,
+    /// Longitude in decimal degrees
+    pub lon: f64,
+    /// Workload, revenue or other quantity to balance, non-negative (default: 1.0)
+    pub weight: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct BalanceTerritoriesInput {
+    /// Accounts, stores or other locations to divide
+    #[tool_types(nested)]
+    pub points: Vec<TerritoryPoint>,
+    /// Number of territories, at most the number of distinct locations
+    pub territories: usize,
+    /// Most k-means iterations for the starting partition (default: 100)
+    pub max_iterations: Option<usize>,
+    /// Most single-point moves while balancing (default: 10000)
+    pub max_moves: Option<usize>,
+    /// Stop balancing once every territory's weight is within this fraction of the
+    /// target (default: 0, balance as far as contiguous moves allow)
+    pub tolerance: Option<f64>,
+    /// Seed for the k-means starting centers (default: random, returned in the result)
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Coordinate {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Territory {
+    pub territory: usize,
+    pub point_count: usize,
+    pub weight: f64,
+    /// Fraction of the total weight
+    pub share: f64,
+    /// Weight relative to the target, minus 1: 0.1 is 10% over
+    pub deviation: f64,
+    /// Weighted center of the territory's points
+    #[tool_types(nested)]
+    pub centroid: Coordinate,
+    /// Distance from the centroid to the farthest point, in kilometers
+    pub max_distance_km: f64,
+    /// Territories that border this one
+    pub neighbors: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct BalanceStatistics {
+    /// Total weight divided by the number of territories
+    pub target_weight: f64,
+    pub min_weight: f64,
+    pub max_weight: f64,
+    /// Largest absolute deviation from the target, as a fraction of it
+    pub max_deviation: f64,
+    /// Largest absolute deviation before balancing moves
+    pub initial_max_deviation: f64,
+    /// Standard deviation of territory weights over their mean
+    pub coefficient_of_variation: f64,
+    /// Points moved between territories while balancing
+    pub moves: usize,
+    /// Weighted mean distance from points to their territory centroid, in kilometers
+    pub mean_distance_km: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct BalanceTerritoriesOutput {
+    /// Territory of each point, in input order
+    pub assignments: Vec<usize>,
+    /// Territories from heaviest to lightest
+    #[tool_types(nested)]
+    pub territories: Vec<Territory>,
+    #[tool_types(nested)]
+    pub statistics: BalanceStatistics,
+    /// Whether every territory is one connected region of neighboring points
+    pub contiguous: bool,
+    /// Seed used for the starting centers
+    pub seed: u64,
+}
+
+/// SplitMix64, so a seed gives the same territories on every platform
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Neighboring points: edges of the Delaunay triangulation, a chain when the points lie
+/// on one line, and each repeated point linked to its first occurrence
+fn adjacency(projected: &[(f64, f64)]) -> Vec<Vec<usize>> {
+    let mesh = triangulate(projected);
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); projected.len()];
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    if mesh.triangles.is_empty() {
+        let mut order: Vec<usize> = (0..projected.len())
+            .filter(|i| mesh.duplicate_of[*i].is_none())
+            .collect();
+        order.sort_by(|a, b| {
+            let (a, b) = (projected[*a], projected[*b]);
+            a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1))
+        });
+        edges.extend(order.windows(2).map(|pair| (pair[0], pair[1])));
+    } else {
+        for [a, b, c] in &mesh.triangles {
+            edges.extend([(*a, *b), (*b, *c), (*c, *a)]);
+        }
+    }
+    for (i, first) in mesh.duplicate_of.iter().enumerate() {
+        if let Some(first) = first {
+            edges.push((i, *first));
+        }
+    }
+    for (a, b) in edges {
+        adjacent[a].push(b);
+        adjacent[b].push(a);
+    }
+    for list in &mut adjacent {
+        list.sort_unstable();
+        list.dedup();
+    }
+    adjacent
+}
+
+/// Weighted center of each territory, the plain mean where the weights sum to zero
+fn centroids(
+    projected: &[(f64, f64)],
+    weights: &[f64],
+    assignments: &[usize],
+    k: usize,
+) -> Vec<(f64, f64)> {
+    let mut sums = vec![(0.0, 0.0, 0.0, 0.0, 0.0, 0); k];
+    for (i, t) in assignments.iter().enumerate() {
+        let (x, y) = projected[i];
+        let s = &mut sums[*t];
+        s.0 += weights[i] * x;
+        s.1 += weights[i] * y;
+        s.2 += weights[i];
+        s.3 += x;
+        s.4 += y;
+        s.5 += 1;
+    }
+    sums.iter()
+        .map(|(wx, wy, w, x, y, n)| {
+            if *w > 0.0 {
+                (wx / w, wy / w)
+            } else {
+                (x / (*n).max(1) as f64, y / (*n).max(1) as f64)
+            }
+        })
+        .collect()
+}
+
+fn nearest(centers: &[(f64, f64)], p: (f64, f64)) -> usize {
+    (0..centers.len())
+        .min_by(|a, b| distance(centers[*a], p).total_cmp(&distance(centers[*b], p)))
+        .unwrap_or(0)
+}
+
+/// Weighted k-means from k-means++ starting centers
+fn kmeans(
+    projected: &[(f64, f64)],
+    weights: &[f64],
+    k: usize,
+    max_iterations: usize,
+    rng: &mut SplitMix64,
+) -> Vec<usize> {
+    let pick = |scores: &[f64], rng: &mut SplitMix64| {
+        let mut target = rng.unit() * scores.iter().sum::<f64>();
+        scores
+            .iter()
+            .position(|s| {
+                target -= s;
+                target < 0.0 && *s > 0.0
+            })
+            .unwrap_or_else(|| scores.iter().rposition(|s| *s > 0.0).unwrap_or(0))
+    };
+    let mut centers = vec![projected[pick(weights, rng)]];
+    while centers.len() < k {
+        let distances: Vec<f64> = projected
+            .iter()
+            .map(|p| distance(centers[nearest(&centers, *p)], *p).powi(2))
+            .collect();
+        let scores: Vec<f64> = distances.iter().zip(weights).map(|(d, w)| d * w).collect();
+        // Fall back to unweighted spread when every weighted point is already a center
+        let index = if scores.iter().sum::<f64>() > 0.0 {
+            pick(&scores, rng)
+        } else {
+            pick(&distances, rng)
+        };
+        centers.push(projected[index]);
+    }
+
+    let mut assignments: Vec<usize> = projected.iter().map(|p| nearest(&centers, *p)).collect();
+    for _ in 0..max_iterations {
+        centers = centroids(projected, weights, &assignments, k);
+        let mut sizes = vec![0; k];
+        assignments.iter().for_each(|t| sizes[*t] += 1);
+        // Restart an empty territory at the point farthest from its own center
+        for t in 0..k {
+            if sizes[t] == 0 {
+                let far = (0..projected.len())
+                    .filter(|i| sizes[assignments[*i]] > 1)
+                    .max_by(|a, b| {
+                        let da = distance(centers[assignments[*a]], projected[*a]);
+                        let db = distance(centers[assignments[*b]], projected[*b]);
+                        da.total_cmp(&db)
+                    });
+                if let Some(far) = far {
+                    sizes[assignments[far]] -= 1;
+                    sizes[t] = 1;
+                    centers[t] = projected[far];
+                }
+            }
+        }
+        let next: Vec<usize> = projected.iter().map(|p| nearest(&centers, *p)).collect();
+        if next == assignments {
+            break;
+        }
+        assignments = next;
+    }
+    assignments
+}
+
+/// Connected pieces of territory t, as point lists
+fn components(assignments: &[usize], adjacent: &[Vec<usize>], t: usize) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; assignments.len()];
+    let mut pieces = Vec::new();
+    for start in 0..assignments.len() {
+        if assignments[start] != t || seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut piece = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(p) = queue.pop_front() {
+            for q in &adjacent[p] {
+                if assignments[*q] == t && !seen[*q] {
+                    seen[*q] = true;
+                    piece.push(*q);
+                    queue.push_back(*q);
+                }
+            }
+        }
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Hands each detached piece of a territory to the neighbor it shares most edges with,
+/// until every territory is one piece
+fn make_contiguous(assignments: &mut [usize], adjacent: &[Vec<usize>], weights: &[f64], k: usize) {
+    for _ in 0..assignments.len() {
+        let mut changed = false;
+        for t in 0..k {
+            let mut pieces = components(assignments, adjacent, t);
+            if pieces.len() < 2 {
+                continue;
+            }
+            let piece_weight = |piece: &Vec<usize>| piece.iter().map(|p| weights[*p]).sum::<f64>();
+            pieces.sort_by(|a, b| {
+                piece_weight(b)
+                    .total_cmp(&piece_weight(a))
+                    .then(b.len().cmp(&a.len()))
+            });
+            for piece in &pieces[1..] {
+                let mut shared = vec![0usize; k];
+                for p in piece {
+                    for q in &adjacent[*p] {
+                        if assignments[*q] != t {
+                            shared[assignments[*q]] += 1;
+                        }
+                    }
+                }
+                let Some(to) = (0..k).filter(|u| shared[*u] > 0).max_by_key(|u| shared[*u]) else {
+                    continue;
+                };
+                piece.iter().for_each(|p| assignments[*p] = to);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Whether territory `t` stays connected without point `p`: the neighbors of p inside t
+/// must still reach each other
+fn removable(assignments: &[usize], adjacent: &[Vec<usize>], p: usize) -> bool {
+    let t = assignments[p];
+    let targets: Vec<usize> = adjacent[p]
+        .iter()
+        .copied()
+        .filter(|q| assignments[*q] == t)
+        .collect();
+    let Some(start) = targets.first() else {
+        return false;
+    };
+    let mut seen = vec![false; assignments.len()];
+    seen[p] = true;
+    seen[*start] = true;
+    let mut found = 1;
+    let mut queue = VecDeque::from([*start]);
+    while let Some(a) = queue.pop_front() {
+        for b in &adjacent[a] {
+            if assignments[*b] == t && !seen[*b] {
+                seen[*b] = true;
+                if targets.contains(b) {
+                    found += 1;
+                    if found == targets.len() {
+                        return true;
+                    }
+                }
+                queue.push_back(*b);
+            }
+        }
+    }
+    found == targets.len()
+}
+
+fn max_deviation(territory_weights: &[f64], target: f64) -> f64 {
+    territory_weights
+        .iter()
+        .map(|w| (w / target - 1.0).abs())
+        .fold(0.0, f64::max)
+}
+
+pub fn balance_territories_logic(
+    input: BalanceTerritoriesInput,
+) -> Result<BalanceTerritoriesOutput, String> {
+    if input.points.is_empty() {
+        return Err("At least one point must be provided".to_string());
+    }
+    if input.points.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} points are allowed"));
+    }
+    for (i, p) in input.points.iter().enumerate() {
+        if !p.lat.is_finite() || !p.lon.is_finite() {
+            return Err(format!("Point {i} has a non-finite coordinate"));
+        }
+        if p.lat < -90.0 || p.lat > 90.0 {
+            return Err(format!(
+                "Point {i} latitude must be between -90 and 90 degrees"
+            ));
+        }
+        if p.lon < -180.0 || p.lon > 180.0 {
+            return Err(format!(
+                "Point {i} longitude must be between -180 and 180 degrees"
+            ));
+        }
+    }
+    // The triangulation's frame reads x as longitude and y as latitude
+    let locations: Vec<(f64, f64)> = input.points.iter().map(|p| (p.lon, p.lat)).collect();
+    let weights: Vec<f64> = input
+        .points
+        .iter()
+        .map(|p| p.weight.unwrap_or(1.0))
+        .collect();
+    if let Some(i) = weights.iter().position(|w| !(w.is_finite() && *w >= 0.0)) {
+        return Err(format!(
+            "Point {i} weight must be a non-negative finite number"
+        ));
+    }
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return Err("Total weight must be positive".to_string());
+    }
+    let k = input.territories;
+    if k == 0 || k > MAX_TERRITORIES {
+        return Err(format!(
+            "territories must be between 1 and {MAX_TERRITORIES}"
+        ));
+    }
+    let max_iterations = input.max_iterations.unwrap_or(100);
+    if max_iterations == 0 || max_iterations > MAX_ITERATIONS {
+        return Err(format!(
+            "max_iterations must be between 1 and {MAX_ITERATIONS}"
+        ));
+    }
+    let max_moves = input.max_moves.unwrap_or(10_000);
+    if max_moves > MAX_MOVES {
+        return Err(format!("max_moves must be at most {MAX_MOVES}"));
+    }
+    let tolerance = input.tolerance.unwrap_or(0.0);
+    if !(tolerance.is_finite() && tolerance >= 0.0) {
+        return Err("tolerance must be a non-negative finite number".to_string());
+    }
+
+    let frame = Frame::new(&locations, true);
+    let projected: Vec<(f64, f64)> = locations.iter().map(|p| frame.forward(*p)).collect();
+    let mut distinct = projected.clone();
+    distinct.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    distinct.dedup();
+    if k > distinct.len() {
+        return Err(format!(
+            "territories ({k}) cannot exceed the number of distinct locations ({})",
+            distinct.len()
+        ));
+    }
+
+    let seed = input.seed.unwrap_or_else(rand::random);
+    let mut rng = SplitMix64(seed);
+    let adjacent = adjacency(&projected);
+    let mut assignments = kmeans(&projected, &weights, k, max_iterations, &mut rng);
+    make_contiguous(&mut assignments, &adjacent, &weights, k);
+
+    let target = total / k as f64;
+    let mut territory_weights = vec![0.0; k];
+    let mut sizes = vec![0usize; k];
+    for (i, t) in assignments.iter().enumerate() {
+        territory_weights[*t] += weights[i];
+        sizes[*t] += 1;
+    }
+    let initial_max_deviation = max_deviation(&territory_weights, target);
+
+    // Move boundary points from heavier to lighter neighbors. Moving weight w from a to b
+    // changes the sum of squared deviations by 2w(w - (weight_a - weight_b)), so a move
+    // helps exactly when the gap exceeds w; among helpful moves take the one that least
+    // stretches the territories
+    let mut moves = 0;
+    while moves < max_moves && max_deviation(&territory_weights, target) > tolerance {
+        let centers = centroids(&projected, &weights, &assignments, k);
+        let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+        for p in 0..projected.len() {
+            let from = assignments[p];
+            if weights[p] == 0.0 || sizes[from] < 2 {
+                continue;
+            }
+            let mut targets: Vec<usize> = adjacent[p]
+                .iter()
+                .map(|q| assignments[*q])
+                .filter(|to| *to != from)
+                .collect();
+            targets.sort_unstable();
+            targets.dedup();
+            for to in targets {
+                if territory_weights[from] - territory_weights[to] > weights[p] {
+                    let cost =
+                        distance(centers[to], projected[p]) - distance(centers[from], projected[p]);
+                    candidates.push((cost, p, to));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let Some((_, p, to)) = candidates
+            .into_iter()
+            .find(|(_, p, _)| removable(&assignments, &adjacent, *p))
+        else {
+            break;
+        };
+        let from = assignments[p];
+        territory_weights[from] -= weights[p];
+        territory_weights[to] += weights[p];
+        sizes[from] -= 1;
+        sizes[to] += 1;
+        assignments[p] = to;
+        moves += 1;
+    }
+
+    let centers = centroids(&projected, &weights, &assignments, k);
+    let mut max_distance = vec![0.0f64; k];
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); k];
+    let mut weighted_distance = 0.0;
+    for (p, t) in assignments.iter().enumerate() {
+        let d = distance(centers[*t], projected[p]);
+        max_distance[*t] = max_distance[*t].max(d);
+        weighted_distance += weights[p] * d;
+        for q in &adjacent[p] {
+            if assignments[*q] != *t {
+                neighbors[*t].push(assignments[*q]);
+            }
+        }
+    }
+    let mut territories: Vec<Territory> = (0..k)
+        .map(|t| {
+            let mut bordering = std::mem::take(&mut neighbors[t]);
+            bordering.sort_unstable();
+            bordering.dedup();
+            let (lon, lat) = frame.inverse(centers[t]);
+            Territory {
+                territory: t,
+                point_count: sizes[t],
+                weight: territory_weights[t],
+                share: territory_weights[t] / total,
+                deviation: territory_weights[t] / target - 1.0,
+                centroid: Coordinate { lat, lon },
+                max_distance_km: max_distance[t] / 1000.0,
+                neighbors: bordering,
+            }
+        })
+        .collect();
+    territories.sort_by(|a, b| {
+        b.weight
+            .total_cmp(&a.weight)
+            .then(a.territory.cmp(&b.territory))
+    });
+
+    let variance = territory_weights
+        .iter()
+        .map(|w| (w - target).powi(2))
+        .sum::<f64>()
+        / k as f64;
+    let statistics = BalanceStatistics {
+        target_weight: target,
+        min_weight: territory_weights
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min),
+        max_weight: territory_weights
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max),
+        max_deviation: max_deviation(&territory_weights, target),
+        initial_max_deviation,
+        coefficient_of_variation: variance.sqrt() / target,
+        moves,
+        mean_distance_km: weighted_distance / total / 1000.0,
+    };
+
+    Ok(BalanceTerritoriesOutput {
+        contiguous: (0..k).all(|t| components(&assignments, &adjacent, t).len() == 1),
+        assignments,
+        territories,
+        statistics,
+        seed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(points: Vec<TerritoryPoint>, territories: usize) -> BalanceTerritoriesInput {
+        BalanceTerritoriesInput {
+            points,
+            territories,
+            max_iterations: None,
+            max_moves: None,
+            tolerance: None,
+            seed: Some(7),
+        }
+    }
+
+    /// A rows x columns grid of points 0.01 degrees apart, weights from `weight(row, col)`
+    fn grid(
+        rows: usize,
+        columns: usize,
+        weight: impl Fn(usize, usize) -> f64,
+    ) -> Vec<TerritoryPoint> {
+        (0..rows * columns)
+            .map(|i| TerritoryPoint {
+                lat: 40.0 + (i / columns) as f64 * 0.01,
+                lon: -74.0 + (i % columns) as f64 * 0.01,
+                weight: Some(weight(i / columns, i % columns)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_two_clusters_split_cleanly() {
+        let mut points = grid(3, 3, |_, _| 1.0);
+        points.extend(grid(3, 3, |_, _| 1.0).into_iter().map(|p| TerritoryPoint {
+            lon: p.lon + 1.0,
+            ..p
+        }));
+        let result = balance_territories_logic(input(points, 2)).unwrap();
+        assert!(
+            result.assignments[..9]
+                .iter()
+                .all(|t| *t == result.assignments[0])
+        );
+        assert!(
+            result.assignments[9..]
+                .iter()
+                .all(|t| *t == result.assignments[9])
+        );
+        assert_ne!(result.assignments[0], result.assignments[9]);
+        assert_eq!(result.statistics.max_deviation, 0.0);
+        assert_eq!(result.statistics.moves, 0);
+        assert!(result.contiguous);
+    }
+
+    #[test]
+    fn test_moves_balance_skewed_weights() {
+        // The heavy western columns pull weight out of balance for a geometric split
+        let points = grid(6, 6, |_, column| if column < 2 { 5.0 } else { 1.0 });
+        let result = balance_territories_logic(input(points, 3)).unwrap();
+        let statistics = &result.statistics;
+        assert!(statistics.max_deviation <= statistics.initial_max_deviation);
+        assert!(statistics.max_deviation < 0.1, "{statistics:?}");
+        assert!(result.contiguous);
+        let total: f64 = result.territories.iter().map(|t| t.weight).sum();
+        assert!((total - 84.0).abs() < 1e-9);
+        assert!((statistics.target_weight - 28.0).abs() < 1e-9);
+        // Heaviest first
+        assert!(result.territories[0].weight >= result.territories[2].weight);
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let points = grid(5, 5, |row, column| (row * 5 + column) as f64 % 4.0 + 1.0);
+        let first = balance_territories_logic(input(points.clone(), 4)).unwrap();
+        let second = balance_territories_logic(input(points, 4)).unwrap();
+        assert_eq!(first.assignments, second.assignments);
+        assert_eq!(first.seed, 7);
+        for territory in &first.territories {
+            assert!(!territory.neighbors.is_empty());
+            assert!(territory.max_distance_km < 10.0);
+        }
+    }
+
+    #[test]
+    fn test_collinear_and_duplicate_points() {
+        let mut points: Vec<TerritoryPoint> = (0..6)
+            .map(|i| TerritoryPoint {
+                lat: 10.0,
+                lon: i as f64 * 0.1,
+                weight: None,
+            })
+            .collect();
+        points.push(points[0].clone());
+        let result = balance_territories_logic(input(points, 2)).unwrap();
+        assert!(result.contiguous);
+        assert_eq!(result.assignments[6], result.assignments[0]);
+        let mut sizes: Vec<usize> = result.territories.iter().map(|t| t.point_count).collect();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let points = grid(2, 2, |_, _| 1.0);
+        assert_eq!(
+            balance_territories_logic(input(points.clone(), 5)).unwrap_err(),
+            "territories (5) cannot exceed the number of distinct locations (4)"
+        );
+        let mut negative = points.clone();
+        negative[1].weight = Some(-1.0);
+        assert_eq!(
+            balance_territories_logic(input(negative, 2)).unwrap_err(),
+            "Point 1 weight must be a non-negative finite number"
+        );
+        let mut outside = points;
+        outside[0].lat = 91.0;
+        assert_eq!(
+            balance_territories_logic(input(outside, 2)).unwrap_err(),
+            "Point 0 latitude must be between -90 and 90 degrees"
+        );
+    }
+}
@@ -3,12 +3,21 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::{AngleUnit, Quantity};
 
 // Re-export types from logic module
-pub use logic::{BearingInput as LogicInput, BearingResult as LogicOutput};
+pub use logic::{
+    BearingInput as LogicInput, BearingResult as LogicOutput, calculate_bearing_between_points,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -21,17 +30,50 @@ pub struct BearingInput {
     pub lat2: f64,
     /// Longitude of the destination point
     pub lon2: f64,
+    /// Unit for `bearing`: "degrees" or "radians" (default: degrees)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BearingResult {
+    /// Bearing in the requested unit
+    pub bearing: Quantity,
     pub bearing_degrees: f64,
     pub bearing_radians: f64,
     pub compass_direction: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn bearing(input: BearingInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn bearing(request: Request<BearingInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("bearing", BearingInput, BearingResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("bearing", &input);
+    log.finish(run_bearing(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_bearing(input: BearingInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or(AngleUnit::Degrees);
+
     // Convert to logic types
     let logic_input = LogicInput {
         lat1: input.lat1,
@@ -44,11 +86,15 @@ pub fn bearing(input: BearingInput) -> ToolResponse {
     match logic::calculate_bearing_between_points(logic_input) {
         Ok(result) => {
             let response = BearingResult {
+                bearing: Quantity::angle(result.bearing_radians, angle_unit),
                 bearing_degrees: result.bearing_degrees,
                 bearing_radians: result.bearing_radians,
                 compass_direction: result.compass_direction,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,11 +1,23 @@
+use ftl_encoding::ResponseEncoding;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+#[cfg(feature = "individual")]
+use ftl_units::LengthUnit;
+#[cfg(feature = "individual")]
+use ftl_units::Quantity;
+use ftl_units::{Length, length_in_meters};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{CircularBufferInput as LogicInput, Point as LogicPoint, create_circular_buffer};
+pub use logic::{CircularBufferInput as LogicInput, Point as LogicPoint, create_circular_buffer};
 
 #[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
 struct Point {
@@ -24,30 +36,47 @@ impl From<Point> for LogicPoint {
     }
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub struct CircularBufferInput {
     /// Center point for the buffer
     center: Point,
-    /// Buffer radius in meters
-    radius_meters: f64,
+    /// Buffer radius in meters (or give `radius`)
+    radius_meters: Option<f64>,
+    /// Buffer radius with a unit, e.g. {"value": 5, "unit": "kilometers"} (instead of `radius_meters`)
+    radius: Option<Length>,
     /// Number of points to approximate circle (8-360, default 32)
     num_points: Option<usize>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Response encoding: "json" (default) or "msgpack" (base64-encoded MessagePack, smaller for large arrays)
+    response_encoding: Option<ResponseEncoding>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
-impl From<CircularBufferInput> for LogicInput {
-    fn from(input: CircularBufferInput) -> Self {
-        LogicInput {
+impl TryFrom<CircularBufferInput> for LogicInput {
+    type Error = String;
+
+    fn try_from(input: CircularBufferInput) -> Result<Self, String> {
+        let radius_meters = length_in_meters("radius", input.radius_meters, input.radius)?
+            .ok_or("Either radius or radius_meters is required")?;
+        Ok(LogicInput {
             center: input.center.into(),
-            radius_meters: input.radius_meters,
+            radius_meters,
             num_points: input.num_points,
-        }
+        })
     }
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct BufferPolygonResult {
     /// The resulting buffer polygon as a sequence of points
     buffer_polygon: Vec<Point>,
+    /// Buffer radius, in the unit it was given in
+    radius: Quantity,
     /// Area of the buffer polygon in square meters
     area_square_meters: f64,
     /// Perimeter of the buffer polygon in meters
@@ -57,9 +86,42 @@ struct BufferPolygonResult {
 }
 
 /// Create circular buffer around a point using geodesic calculations
-#[cfg_attr(not(test), tool)]
-pub fn buffer_polygon(input: CircularBufferInput) -> ToolResponse {
-    let logic_input = LogicInput::from(input);
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn buffer_polygon(request: Request<CircularBufferInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "buffer_polygon",
+                CircularBufferInput,
+                BufferPolygonResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("buffer_polygon", &input);
+    log.finish(run_buffer_polygon(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_buffer_polygon(input: CircularBufferInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let encoding = input.response_encoding.unwrap_or_default();
+
+    let radius_unit = input
+        .radius
+        .map_or(LengthUnit::Meters, |radius| radius.unit);
+    let logic_input = match LogicInput::try_from(input) {
+        Ok(logic_input) => logic_input,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let radius = Quantity::length(logic_input.radius_meters, radius_unit);
 
     match create_circular_buffer(
         logic_input.center,
@@ -76,11 +138,15 @@ pub fn buffer_polygon(input: CircularBufferInput) -> ToolResponse {
                         lon: p.lon,
                     })
                     .collect(),
+                radius,
                 area_square_meters: result.area_square_meters,
                 perimeter_meters: result.perimeter_meters,
                 algorithm_used: result.algorithm_used,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_encoding::encode(&response, encoding, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,18 +1,30 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{DecimalDegreesInput as LogicInput, convert_to_dms};
+pub use logic::{DecimalDegreesInput as LogicInput, convert_to_dms};
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub struct DecimalDegreesInput {
     /// Latitude in decimal degrees
     latitude: f64,
     /// Longitude in decimal degrees
     longitude: f64,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
 impl From<DecimalDegreesInput> for LogicInput {
@@ -24,6 +36,7 @@ impl From<DecimalDegreesInput> for LogicInput {
     }
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema, Debug)]
 struct DMSCoordinate {
     degrees: i32,
@@ -32,6 +45,7 @@ struct DMSCoordinate {
     direction: String,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct CoordinateConversionResult {
     /// Latitude in degrees, minutes, seconds format
@@ -41,8 +55,33 @@ struct CoordinateConversionResult {
 }
 
 /// Convert decimal degrees to degrees, minutes, seconds (DMS) format
-#[cfg_attr(not(test), tool)]
-pub fn coordinate_conversion(input: DecimalDegreesInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn coordinate_conversion(request: Request<DecimalDegreesInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "coordinate_conversion",
+                DecimalDegreesInput,
+                CoordinateConversionResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("coordinate_conversion", &input);
+    log.finish(run_coordinate_conversion(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_coordinate_conversion(input: DecimalDegreesInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     let logic_input = LogicInput::from(input);
 
     match convert_to_dms(logic_input.latitude, logic_input.longitude) {
@@ -61,7 +100,10 @@ pub fn coordinate_conversion(input: DecimalDegreesInput) -> ToolResponse {
                     direction: result.longitude.direction,
                 },
             };
-            ftl_sdk::ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ftl_sdk::ToolResponse::text(body),
+                Err(e) => ftl_sdk::ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ftl_sdk::ToolResponse::text(format!("Error: {e}")),
     }
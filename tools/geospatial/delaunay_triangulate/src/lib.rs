@@ -0,0 +1,89 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    DelaunayInput as LogicInput, DelaunayOutput as LogicOutput, Frame, MAX_POINTS, Mesh,
+    check_points, delaunay_triangulate_logic, triangulate,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    DelaunayOutputSchema as DelaunayOutput, DelaunayTriangleSchema as DelaunayTriangle,
+    Point2DSchema as Point2D,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DelaunayInput {
+    #[serde(flatten)]
+    pub params: logic::DelaunayInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Triangulate planar or longitude/latitude points so no point falls inside any
+/// triangle's circumcircle, with triangle areas, neighboring triangles and point adjacency
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn delaunay_triangulate(request: Request<DelaunayInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "delaunay_triangulate",
+                DelaunayInput,
+                DelaunayOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("delaunay_triangulate", &input);
+    log.finish(run_delaunay_triangulate(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_delaunay_triangulate(input: DelaunayInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::delaunay_triangulate_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = DelaunayOutput::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
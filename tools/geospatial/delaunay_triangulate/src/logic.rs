@@ -0,0 +1,422 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Most points accepted in one call
+pub const MAX_POINTS: usize = 5_000;
+
+const EARTH_RADIUS_M: f64 = 6378137.0;
+
+/// Planar point, or with geographic input a longitude (x) and latitude (y) in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+pub struct Point2D {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DelaunayInput {
+    /// Points to triangulate, at least three not on one line
+    #[tool_types(nested)]
+    pub points: Vec<Point2D>,
+    /// Read x as longitude and y as latitude in degrees and report areas in square meters,
+    /// from a local projection suited to regions up to a few hundred kilometers across
+    /// (default: false)
+    pub geographic: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DelaunayTriangle {
+    /// Indices of the corner points, counter-clockwise
+    pub vertices: [usize; 3],
+    pub area: f64,
+    /// Center of the circle through the corners, which no other point lies inside
+    #[tool_types(nested)]
+    pub circumcenter: Point2D,
+    /// Indices of the triangles sharing an edge with this one
+    pub neighbors: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DelaunayOutput {
+    #[tool_types(nested)]
+    pub triangles: Vec<DelaunayTriangle>,
+    /// Triangle edges as point index pairs, lower index first, sorted
+    pub edges: Vec<[usize; 2]>,
+    /// Points joined to each point by an edge, ascending, in input order
+    pub point_neighbors: Vec<Vec<usize>>,
+    /// Convex hull as point indices, counter-clockwise from the lowest index
+    pub hull: Vec<usize>,
+    /// Points left out because they repeat an earlier point
+    pub duplicate_points: Vec<usize>,
+    /// Sum of the triangle areas, the area of the hull
+    pub total_area: f64,
+    pub geographic: bool,
+}
+
+/// Maps input points to the plane the geometry is computed in: unchanged, or for
+/// geographic input an equirectangular projection in meters about the mean latitude
+pub struct Frame {
+    geographic: bool,
+    cos_ref: f64,
+}
+
+impl Frame {
+    pub fn new(points: &[(f64, f64)], geographic: bool) -> Frame {
+        let mean_lat = points.iter().map(|p| p.1).sum::<f64>() / points.len().max(1) as f64;
+        Frame {
+            geographic,
+            cos_ref: mean_lat.to_radians().cos(),
+        }
+    }
+
+    pub fn forward(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        if self.geographic {
+            (
+                EARTH_RADIUS_M * x.to_radians() * self.cos_ref,
+                EARTH_RADIUS_M * y.to_radians(),
+            )
+        } else {
+            (x, y)
+        }
+    }
+
+    pub fn inverse(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        if self.geographic {
+            (
+                (x / (EARTH_RADIUS_M * self.cos_ref)).to_degrees(),
+                (y / EARTH_RADIUS_M).to_degrees(),
+            )
+        } else {
+            (x, y)
+        }
+    }
+}
+
+/// Checks point count limits and coordinates, with latitude and longitude ranges for
+/// geographic input
+pub fn check_points(points: &[(f64, f64)], geographic: bool) -> Result<(), String> {
+    if points.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} points are allowed"));
+    }
+    for (i, (x, y)) in points.iter().enumerate() {
+        if !x.is_finite() || !y.is_finite() {
+            return Err(format!("Point {i} has a non-finite coordinate"));
+        }
+        if geographic && !(-90.0..=90.0).contains(y) {
+            return Err(format!(
+                "Point {i} latitude (y) must be between -90 and 90 degrees"
+            ));
+        }
+        if geographic && !(-180.0..=180.0).contains(x) {
+            return Err(format!(
+                "Point {i} longitude (x) must be between -180 and 180 degrees"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Delaunay triangulation of planar points
+pub struct Mesh {
+    /// Counter-clockwise corner indices into the points
+    pub triangles: Vec<[usize; 3]>,
+    /// For each point, the earlier point it repeats
+    pub duplicate_of: Vec<Option<usize>>,
+}
+
+fn orientation(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Positive when d lies inside the circle through the counter-clockwise a, b, c
+fn in_circle(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> f64 {
+    let (adx, ady) = (a.0 - d.0, a.1 - d.1);
+    let (bdx, bdy) = (b.0 - d.0, b.1 - d.1);
+    let (cdx, cdy) = (c.0 - d.0, c.1 - d.1);
+    let (ad, bd, cd) = (
+        adx * adx + ady * ady,
+        bdx * bdx + bdy * bdy,
+        cdx * cdx + cdy * cdy,
+    );
+    adx * (bdy * cd - bd * cdy) - ady * (bdx * cd - bd * cdx) + ad * (bdx * cdy - bdy * cdx)
+}
+
+/// Bowyer-Watson: add points one at a time inside a large enclosing triangle, each time
+/// replacing the triangles whose circumcircle holds the new point with a fan around it.
+/// Repeated points are skipped; points all on one line give no triangles.
+pub fn triangulate(points: &[(f64, f64)]) -> Mesh {
+    let mut first_at: HashMap<(u64, u64), usize> = HashMap::new();
+    let duplicate_of: Vec<Option<usize>> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let key = ((p.0 + 0.0).to_bits(), (p.1 + 0.0).to_bits());
+            let first = *first_at.entry(key).or_insert(i);
+            (first != i).then_some(first)
+        })
+        .collect();
+    let unique: Vec<usize> = (0..points.len())
+        .filter(|i| duplicate_of[*i].is_none())
+        .collect();
+    if unique.len() < 3 {
+        return Mesh {
+            triangles: Vec::new(),
+            duplicate_of,
+        };
+    }
+
+    // Scale into the unit square so the enclosing triangle and the predicates behave the
+    // same whatever the units
+    let min_x = unique
+        .iter()
+        .map(|i| points[*i].0)
+        .fold(f64::INFINITY, f64::min);
+    let min_y = unique
+        .iter()
+        .map(|i| points[*i].1)
+        .fold(f64::INFINITY, f64::min);
+    let span = unique
+        .iter()
+        .map(|i| (points[*i].0 - min_x).max(points[*i].1 - min_y))
+        .fold(0.0, f64::max);
+    let mut vertices: Vec<(f64, f64)> = unique
+        .iter()
+        .map(|i| ((points[*i].0 - min_x) / span, (points[*i].1 - min_y) / span))
+        .collect();
+    let n = vertices.len();
+    let far = 1e4;
+    vertices.extend([(-far, -far), (far, -far), (0.5, far)]);
+    let mut triangles: Vec<[usize; 3]> = vec![[n, n + 1, n + 2]];
+
+    for i in 0..n {
+        let p = vertices[i];
+        let (bad, kept): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles
+            .into_iter()
+            .partition(|[a, b, c]| in_circle(vertices[*a], vertices[*b], vertices[*c], p) > 0.0);
+        // The cavity's outline is the set of bad-triangle edges not shared with another
+        let directed: BTreeSet<(usize, usize)> = bad
+            .iter()
+            .flat_map(|[a, b, c]| [(*a, *b), (*b, *c), (*c, *a)])
+            .collect();
+        triangles = kept;
+        triangles.extend(
+            directed
+                .iter()
+                .filter(|(a, b)| !directed.contains(&(*b, *a)))
+                .map(|(a, b)| [*a, *b, i]),
+        );
+    }
+
+    let triangles = triangles
+        .into_iter()
+        .filter(|t| t.iter().all(|v| *v < n))
+        .filter(|[a, b, c]| orientation(vertices[*a], vertices[*b], vertices[*c]) > 1e-12)
+        .map(|t| t.map(|v| unique[v]))
+        .collect();
+    Mesh {
+        triangles,
+        duplicate_of,
+    }
+}
+
+fn triangle_area(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    orientation(a, b, c) / 2.0
+}
+
+fn circumcenter(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> (f64, f64) {
+    let (bx, by) = (b.0 - a.0, b.1 - a.1);
+    let (cx, cy) = (c.0 - a.0, c.1 - a.1);
+    let d = 2.0 * (bx * cy - by * cx);
+    let (b2, c2) = (bx * bx + by * by, cx * cx + cy * cy);
+    (a.0 + (cy * b2 - by * c2) / d, a.1 + (bx * c2 - cx * b2) / d)
+}
+
+pub fn delaunay_triangulate_logic(input: DelaunayInput) -> Result<DelaunayOutput, String> {
+    let geographic = input.geographic.unwrap_or(false);
+    let points: Vec<(f64, f64)> = input.points.iter().map(|p| (p.x, p.y)).collect();
+    check_points(&points, geographic)?;
+    let frame = Frame::new(&points, geographic);
+    let projected: Vec<(f64, f64)> = points.iter().map(|p| frame.forward(*p)).collect();
+    let mesh = triangulate(&projected);
+    if mesh.triangles.is_empty() {
+        return Err(
+            "Triangulation needs at least 3 distinct points not all on one line".to_string(),
+        );
+    }
+
+    // Each edge with the triangles on either side
+    let mut sides: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (t, [a, b, c]) in mesh.triangles.iter().enumerate() {
+        for (u, v) in [(*a, *b), (*b, *c), (*c, *a)] {
+            sides.entry((u.min(v), u.max(v))).or_default().push(t);
+        }
+    }
+    let triangles: Vec<DelaunayTriangle> = mesh
+        .triangles
+        .iter()
+        .enumerate()
+        .map(|(t, [a, b, c])| {
+            let (pa, pb, pc) = (projected[*a], projected[*b], projected[*c]);
+            let mut neighbors: Vec<usize> = [(*a, *b), (*b, *c), (*c, *a)]
+                .iter()
+                .flat_map(|(u, v)| &sides[&(*u.min(v), *u.max(v))])
+                .copied()
+                .filter(|other| *other != t)
+                .collect();
+            neighbors.sort_unstable();
+            let (cx, cy) = frame.inverse(circumcenter(pa, pb, pc));
+            DelaunayTriangle {
+                vertices: [*a, *b, *c],
+                area: triangle_area(pa, pb, pc),
+                circumcenter: Point2D { x: cx, y: cy },
+                neighbors,
+            }
+        })
+        .collect();
+
+    let mut edges: Vec<[usize; 2]> = sides.keys().map(|(u, v)| [*u, *v]).collect();
+    edges.sort_unstable();
+    let mut point_neighbors: Vec<Vec<usize>> = vec![Vec::new(); input.points.len()];
+    for [u, v] in &edges {
+        point_neighbors[*u].push(*v);
+        point_neighbors[*v].push(*u);
+    }
+    point_neighbors.iter_mut().for_each(|n| n.sort_unstable());
+
+    // Hull edges border one triangle; follow them counter-clockwise
+    let mut next: HashMap<usize, usize> = HashMap::new();
+    for [a, b, c] in &mesh.triangles {
+        for (u, v) in [(*a, *b), (*b, *c), (*c, *a)] {
+            if sides[&(u.min(v), u.max(v))].len() == 1 {
+                next.insert(u, v);
+            }
+        }
+    }
+    let start = next.keys().copied().min().unwrap_or(0);
+    let mut hull = vec![start];
+    while let Some(v) = next.get(hull.last().unwrap()).filter(|v| **v != start) {
+        if hull.len() > next.len() {
+            break;
+        }
+        hull.push(*v);
+    }
+
+    Ok(DelaunayOutput {
+        total_area: triangles.iter().map(|t| t.area).sum(),
+        triangles,
+        edges,
+        point_neighbors,
+        hull,
+        duplicate_points: (0..input.points.len())
+            .filter(|i| mesh.duplicate_of[*i].is_some())
+            .collect(),
+        geographic,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point2D> {
+        coordinates
+            .iter()
+            .map(|(x, y)| Point2D { x: *x, y: *y })
+            .collect()
+    }
+
+    fn triangulate_points(coordinates: &[(f64, f64)]) -> DelaunayOutput {
+        delaunay_triangulate_logic(DelaunayInput {
+            points: points(coordinates),
+            geographic: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_square_with_center() {
+        let result =
+            triangulate_points(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (1.0, 1.0)]);
+        assert_eq!(result.triangles.len(), 4);
+        assert!((result.total_area - 4.0).abs() < 1e-12);
+        assert!(
+            result
+                .triangles
+                .iter()
+                .all(|t| (t.area - 1.0).abs() < 1e-12)
+        );
+        assert_eq!(result.point_neighbors[4], vec![0, 1, 2, 3]);
+        assert_eq!(result.hull, vec![0, 1, 2, 3]);
+        assert_eq!(result.edges.len(), 8);
+        assert!(result.triangles.iter().all(|t| t.neighbors.len() == 2));
+    }
+
+    #[test]
+    fn test_empty_circumcircles() {
+        // Scattered points from a fixed sequence, checked against the Delaunay property
+        let mut state = 12345u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 100.0
+        };
+        let coordinates: Vec<(f64, f64)> = (0..200).map(|_| (next(), next())).collect();
+        let result = triangulate_points(&coordinates);
+        for t in &result.triangles {
+            let [a, b, c] = t.vertices.map(|v| coordinates[v]);
+            assert!(orientation(a, b, c) > 0.0);
+            for (i, p) in coordinates.iter().enumerate() {
+                if !t.vertices.contains(&i) {
+                    assert!(in_circle(a, b, c, *p) <= 1e-9, "point {i} inside {t:?}");
+                }
+            }
+        }
+        // Euler: 2n - 2 - h triangles for n points with h on the hull
+        assert_eq!(result.triangles.len(), 2 * 200 - 2 - result.hull.len());
+    }
+
+    #[test]
+    fn test_grid_and_duplicates() {
+        let mut coordinates: Vec<(f64, f64)> =
+            (0..16).map(|i| ((i % 4) as f64, (i / 4) as f64)).collect();
+        coordinates.push((1.0, 1.0));
+        let result = triangulate_points(&coordinates);
+        assert_eq!(result.triangles.len(), 18);
+        assert!((result.total_area - 9.0).abs() < 1e-9);
+        assert_eq!(result.duplicate_points, vec![16]);
+        assert!(result.point_neighbors[16].is_empty());
+    }
+
+    #[test]
+    fn test_geographic_areas() {
+        // A 0.01 degree square at the equator is about 1.11 km on a side
+        let result = delaunay_triangulate_logic(DelaunayInput {
+            points: points(&[(0.0, 0.0), (0.01, 0.0), (0.01, 0.01), (0.0, 0.01)]),
+            geographic: Some(true),
+        })
+        .unwrap();
+        let side = EARTH_RADIUS_M * 0.01f64.to_radians();
+        assert!((result.total_area - side * side).abs() / (side * side) < 1e-3);
+        let center = result.triangles[0].circumcenter;
+        assert!((center.x - 0.005).abs() < 1e-9 && (center.y - 0.005).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let collinear = delaunay_triangulate_logic(DelaunayInput {
+            points: points(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]),
+            geographic: None,
+        });
+        assert!(collinear.unwrap_err().starts_with("Triangulation needs"));
+        let geographic = delaunay_triangulate_logic(DelaunayInput {
+            points: points(&[(0.0, 95.0), (1.0, 1.0), (2.0, 0.0)]),
+            geographic: Some(true),
+        });
+        assert_eq!(
+            geographic.unwrap_err(),
+            "Point 0 latitude (y) must be between -90 and 90 degrees"
+        );
+    }
+}
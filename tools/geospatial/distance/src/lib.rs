@@ -1,14 +1,23 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
+use ftl_units::{LengthUnit, Quantity};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{DistanceInput as LogicInput, DistanceResult as LogicOutput};
+pub use logic::{
+    DistanceInput as LogicInput, DistanceResult as LogicOutput, calculate_distance_between_points,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -21,17 +30,50 @@ pub struct DistanceInput {
     pub lat2: f64,
     /// Longitude of the second point
     pub lon2: f64,
+    /// Unit for `distance`: "meters", "kilometers" or "miles" (default: kilometers)
+    pub unit: Option<LengthUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DistanceResult {
+    /// Distance in the requested unit
+    pub distance: Quantity,
     pub distance_km: f64,
     pub distance_miles: f64,
     pub distance_nautical_miles: f64,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn distance(input: DistanceInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn distance(request: Request<DistanceInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("distance", DistanceInput, DistanceResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("distance", &input);
+    log.finish(run_distance(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_distance(input: DistanceInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let unit = input.unit.unwrap_or(LengthUnit::Kilometers);
+
     // Convert to logic types
     let logic_input = LogicInput {
         lat1: input.lat1,
@@ -48,12 +90,14 @@ pub fn distance(input: DistanceInput) -> ToolResponse {
 
     // Convert back to wrapper types
     let output = DistanceResult {
+        distance: Quantity::length(LengthUnit::Kilometers.to_meters(result.distance_km), unit),
         distance_km: result.distance_km,
         distance_miles: result.distance_miles,
         distance_nautical_miles: result.distance_nautical_miles,
     };
 
     ToolResponse::text(
-        serde_json::to_string(&output).unwrap_or_else(|_| "Error serializing result".to_string()),
+        ftl_response::to_json_with_precision(&output, None, precision.as_ref())
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
     )
 }
@@ -0,0 +1,203 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+pub use logic::{
+    Coordinate as LogicCoordinate, GeoBinInput as LogicInput, WeightedPoint as LogicPoint,
+    bin_points,
+};
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+struct WeightedPoint {
+    /// Latitude in decimal degrees
+    lat: f64,
+    /// Longitude in decimal degrees
+    lon: f64,
+    /// Optional weight for the point (default: 1.0)
+    weight: Option<f64>,
+}
+
+impl From<WeightedPoint> for LogicPoint {
+    fn from(p: WeightedPoint) -> Self {
+        LogicPoint {
+            lat: p.lat,
+            lon: p.lon,
+            weight: p.weight,
+        }
+    }
+}
+
+#[cfg(feature = "individual")]
+#[derive(Serialize, JsonSchema)]
+struct Coordinate {
+    /// Latitude in decimal degrees
+    lat: f64,
+    /// Longitude in decimal degrees
+    lon: f64,
+}
+
+#[cfg(feature = "individual")]
+impl From<LogicCoordinate> for Coordinate {
+    fn from(c: LogicCoordinate) -> Self {
+        Coordinate {
+            lat: c.lat,
+            lon: c.lon,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct GeoBinInput {
+    /// Points to aggregate
+    points: Vec<WeightedPoint>,
+    /// Grid shape: "hex" (default) or "square"
+    grid_type: Option<String>,
+    /// H3-style resolution from 0 (coarsest) to 15 (finest), default 7 (~1.2 km edges)
+    resolution: Option<u32>,
+    /// Explicit cell size in meters (hex edge length or square side); overrides resolution
+    cell_size_meters: Option<f64>,
+    /// Latitude for the local projection; fix this to get stable cell IDs across calls
+    /// (default: mean latitude of the points)
+    reference_latitude: Option<f64>,
+    /// Whether to include cell boundary polygons (default: false)
+    include_boundaries: Option<bool>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
+}
+
+impl From<GeoBinInput> for LogicInput {
+    fn from(input: GeoBinInput) -> Self {
+        LogicInput {
+            points: input.points.into_iter().map(|p| p.into()).collect(),
+            grid_type: input.grid_type,
+            resolution: input.resolution,
+            cell_size_meters: input.cell_size_meters,
+            reference_latitude: input.reference_latitude,
+            include_boundaries: input.include_boundaries,
+        }
+    }
+}
+
+#[cfg(feature = "individual")]
+#[derive(Serialize, JsonSchema)]
+struct GridCell {
+    /// Cell identifier ("hex_q_r" or "sq_x_y")
+    id: String,
+    /// Cell center
+    center: Coordinate,
+    /// Number of points in the cell
+    count: usize,
+    /// Sum of point weights in the cell
+    weight_sum: f64,
+    /// Points per square kilometer
+    density_per_km2: f64,
+    /// Weight per square kilometer
+    weighted_density_per_km2: f64,
+    /// Cell boundary polygon (if requested)
+    boundary: Option<Vec<Coordinate>>,
+}
+
+#[cfg(feature = "individual")]
+#[derive(Serialize, JsonSchema)]
+struct GeoBinResult {
+    /// Grid shape used
+    grid_type: String,
+    /// Resolution used (absent when cell_size_meters was given)
+    resolution: Option<u32>,
+    /// Cell size in meters
+    cell_size_meters: f64,
+    /// Area of a single cell in square kilometers
+    cell_area_km2: f64,
+    /// Latitude used for the local projection
+    reference_latitude: f64,
+    /// Number of input points
+    total_points: usize,
+    /// Number of non-empty cells
+    cell_count: usize,
+    /// Highest point count in any cell
+    max_count: usize,
+    /// Non-empty cells ordered by count (descending)
+    cells: Vec<GridCell>,
+}
+
+/// Aggregate points into hexagonal or square grid cells for heatmap rendering
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn geo_bin(request: Request<GeoBinInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("geo_bin", GeoBinInput, GeoBinResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("geo_bin", &input);
+    log.finish(run_geo_bin(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_geo_bin(input: GeoBinInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().array("points", &input.points).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let logic_input = LogicInput::from(input);
+
+    match bin_points(logic_input) {
+        Ok(result) => {
+            let response = GeoBinResult {
+                grid_type: result.grid_type,
+                resolution: result.resolution,
+                cell_size_meters: result.cell_size_meters,
+                cell_area_km2: result.cell_area_km2,
+                reference_latitude: result.reference_latitude,
+                total_points: result.total_points,
+                cell_count: result.cell_count,
+                max_count: result.max_count,
+                cells: result
+                    .cells
+                    .into_iter()
+                    .map(|c| GridCell {
+                        id: c.id,
+                        center: c.center.into(),
+                        count: c.count,
+                        weight_sum: c.weight_sum,
+                        density_per_km2: c.density_per_km2,
+                        weighted_density_per_km2: c.weighted_density_per_km2,
+                        boundary: c
+                            .boundary
+                            .map(|b| b.into_iter().map(|p| p.into()).collect()),
+                    })
+                    .collect(),
+            };
+            ToolResponse::text(
+                ftl_response::to_json_with_precision(&response, None, precision.as_ref())
+                    .unwrap_or_else(|_| "Error serializing result".to_string()),
+            )
+        }
+        Err(error) => ToolResponse::text(error),
+    }
+}
@@ -0,0 +1,443 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+const EARTH_RADIUS_M: f64 = 6378137.0;
+/// Average hexagon edge length at resolution 0, matching the H3 grid
+const RES0_EDGE_LENGTH_M: f64 = 1_107_712.591;
+const MAX_RESOLUTION: u32 = 15;
+const DEFAULT_RESOLUTION: u32 = 7;
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct WeightedPoint {
+    /// Latitude in decimal degrees
+    pub lat: f64,
+    /// Longitude in decimal degrees
+    pub lon: f64,
+    /// Optional weight (default: 1.0)
+    pub weight: Option<f64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Coordinate {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GeoBinInput {
+    pub points: Vec<WeightedPoint>,
+    /// "hex" (default) or "square"
+    pub grid_type: Option<String>,
+    /// H3-style resolution 0-15 (default: 7, ~1.2 km edges)
+    pub resolution: Option<u32>,
+    /// Explicit cell size in meters; overrides resolution
+    pub cell_size_meters: Option<f64>,
+    /// Latitude used for the local projection (default: mean latitude of the points)
+    pub reference_latitude: Option<f64>,
+    /// Whether to include cell boundary polygons
+    pub include_boundaries: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GridCell {
+    pub id: String,
+    pub center: Coordinate,
+    pub count: usize,
+    pub weight_sum: f64,
+    /// Points per square kilometer
+    pub density_per_km2: f64,
+    /// Weight per square kilometer
+    pub weighted_density_per_km2: f64,
+    pub boundary: Option<Vec<Coordinate>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GeoBinResult {
+    pub grid_type: String,
+    pub resolution: Option<u32>,
+    pub cell_size_meters: f64,
+    pub cell_area_km2: f64,
+    pub reference_latitude: f64,
+    pub total_points: usize,
+    pub cell_count: usize,
+    pub max_count: usize,
+    /// Cells ordered by count (descending), then id
+    pub cells: Vec<GridCell>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GridType {
+    Hex,
+    Square,
+}
+
+/// Local equirectangular projection centered on a reference latitude
+struct Projection {
+    cos_ref: f64,
+}
+
+impl Projection {
+    fn new(reference_latitude: f64) -> Self {
+        Projection {
+            cos_ref: (reference_latitude * PI / 180.0).cos(),
+        }
+    }
+
+    fn forward(&self, lat: f64, lon: f64) -> (f64, f64) {
+        let x = EARTH_RADIUS_M * lon * PI / 180.0 * self.cos_ref;
+        let y = EARTH_RADIUS_M * lat * PI / 180.0;
+        (x, y)
+    }
+
+    fn inverse(&self, x: f64, y: f64) -> Coordinate {
+        Coordinate {
+            lat: y / EARTH_RADIUS_M * 180.0 / PI,
+            lon: x / (EARTH_RADIUS_M * self.cos_ref) * 180.0 / PI,
+        }
+    }
+}
+
+pub fn edge_length_for_resolution(resolution: u32) -> f64 {
+    RES0_EDGE_LENGTH_M / 7f64.sqrt().powi(resolution as i32)
+}
+
+pub fn bin_points(input: GeoBinInput) -> Result<GeoBinResult, String> {
+    if input.points.is_empty() {
+        return Err("At least one point must be provided".to_string());
+    }
+
+    for (i, p) in input.points.iter().enumerate() {
+        if !p.lat.is_finite() || !p.lon.is_finite() {
+            return Err(format!("Point {i} has a non-finite coordinate"));
+        }
+        if p.lat < -90.0 || p.lat > 90.0 {
+            return Err(format!(
+                "Point {i} latitude must be between -90 and 90 degrees"
+            ));
+        }
+        if p.lon < -180.0 || p.lon > 180.0 {
+            return Err(format!(
+                "Point {i} longitude must be between -180 and 180 degrees"
+            ));
+        }
+        if let Some(w) = p.weight
+            && !w.is_finite()
+        {
+            return Err(format!("Point {i} has a non-finite weight"));
+        }
+    }
+
+    let grid_type = match input
+        .grid_type
+        .as_deref()
+        .unwrap_or("hex")
+        .to_lowercase()
+        .as_str()
+    {
+        "hex" | "hexagon" | "hexagonal" => GridType::Hex,
+        "square" | "grid" => GridType::Square,
+        other => {
+            return Err(format!(
+                "Invalid grid_type '{other}'. Use 'hex' or 'square'"
+            ));
+        }
+    };
+
+    let (cell_size, resolution) = match input.cell_size_meters {
+        Some(size) => {
+            if !size.is_finite() || size <= 0.0 {
+                return Err("cell_size_meters must be a positive number".to_string());
+            }
+            (size, None)
+        }
+        None => {
+            let resolution = input.resolution.unwrap_or(DEFAULT_RESOLUTION);
+            if resolution > MAX_RESOLUTION {
+                return Err(format!("resolution must be between 0 and {MAX_RESOLUTION}"));
+            }
+            (edge_length_for_resolution(resolution), Some(resolution))
+        }
+    };
+
+    let reference_latitude = match input.reference_latitude {
+        Some(lat) => {
+            if !lat.is_finite() || lat.abs() >= 90.0 {
+                return Err(
+                    "reference_latitude must be between -90 and 90 degrees (exclusive)".to_string(),
+                );
+            }
+            lat
+        }
+        None => {
+            let mean = input.points.iter().map(|p| p.lat).sum::<f64>() / input.points.len() as f64;
+            // Keep the projection usable for data clustered at the poles
+            mean.clamp(-89.0, 89.0)
+        }
+    };
+
+    let projection = Projection::new(reference_latitude);
+    let cell_area_m2 = match grid_type {
+        GridType::Hex => 1.5 * SQRT_3 * cell_size * cell_size,
+        GridType::Square => cell_size * cell_size,
+    };
+    let cell_area_km2 = cell_area_m2 / 1_000_000.0;
+
+    // Accumulate (count, weight) per cell key
+    let mut bins: HashMap<(i64, i64), (usize, f64)> = HashMap::new();
+    for p in &input.points {
+        let (x, y) = projection.forward(p.lat, p.lon);
+        let key = match grid_type {
+            GridType::Hex => hex_cell(x, y, cell_size),
+            GridType::Square => (
+                (x / cell_size).floor() as i64,
+                (y / cell_size).floor() as i64,
+            ),
+        };
+        let entry = bins.entry(key).or_insert((0, 0.0));
+        entry.0 += 1;
+        entry.1 += p.weight.unwrap_or(1.0);
+    }
+
+    let include_boundaries = input.include_boundaries.unwrap_or(false);
+    let mut cells: Vec<GridCell> = bins
+        .into_iter()
+        .map(|((a, b), (count, weight_sum))| {
+            let (cx, cy, id) = match grid_type {
+                GridType::Hex => {
+                    let (cx, cy) = hex_center(a, b, cell_size);
+                    (cx, cy, format!("hex_{a}_{b}"))
+                }
+                GridType::Square => (
+                    (a as f64 + 0.5) * cell_size,
+                    (b as f64 + 0.5) * cell_size,
+                    format!("sq_{a}_{b}"),
+                ),
+            };
+            let boundary = include_boundaries.then(|| {
+                cell_corners(grid_type, cx, cy, cell_size)
+                    .into_iter()
+                    .map(|(x, y)| projection.inverse(x, y))
+                    .collect()
+            });
+            GridCell {
+                id,
+                center: projection.inverse(cx, cy),
+                count,
+                weight_sum,
+                density_per_km2: count as f64 / cell_area_km2,
+                weighted_density_per_km2: weight_sum / cell_area_km2,
+                boundary,
+            }
+        })
+        .collect();
+
+    cells.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.id.cmp(&b.id)));
+    let max_count = cells.first().map(|c| c.count).unwrap_or(0);
+
+    Ok(GeoBinResult {
+        grid_type: match grid_type {
+            GridType::Hex => "hex".to_string(),
+            GridType::Square => "square".to_string(),
+        },
+        resolution,
+        cell_size_meters: cell_size,
+        cell_area_km2,
+        reference_latitude,
+        total_points: input.points.len(),
+        cell_count: cells.len(),
+        max_count,
+        cells,
+    })
+}
+
+/// Axial coordinates of the pointy-top hexagon containing (x, y)
+fn hex_cell(x: f64, y: f64, size: f64) -> (i64, i64) {
+    let q = (SQRT_3 / 3.0 * x - y / 3.0) / size;
+    let r = (2.0 / 3.0 * y) / size;
+
+    // Cube rounding
+    let s = -q - r;
+    let mut rq = q.round();
+    let mut rr = r.round();
+    let rs = s.round();
+    let dq = (rq - q).abs();
+    let dr = (rr - r).abs();
+    let ds = (rs - s).abs();
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    (rq as i64, rr as i64)
+}
+
+fn hex_center(q: i64, r: i64, size: f64) -> (f64, f64) {
+    let x = size * (SQRT_3 * q as f64 + SQRT_3 / 2.0 * r as f64);
+    let y = size * 1.5 * r as f64;
+    (x, y)
+}
+
+fn cell_corners(grid_type: GridType, cx: f64, cy: f64, size: f64) -> Vec<(f64, f64)> {
+    match grid_type {
+        GridType::Hex => (0..6)
+            .map(|i| {
+                let angle = PI / 180.0 * (60.0 * i as f64 - 30.0);
+                (cx + size * angle.cos(), cy + size * angle.sin())
+            })
+            .collect(),
+        GridType::Square => {
+            let h = size / 2.0;
+            vec![
+                (cx - h, cy - h),
+                (cx + h, cy - h),
+                (cx + h, cy + h),
+                (cx - h, cy + h),
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: f64, lon: f64) -> WeightedPoint {
+        WeightedPoint {
+            lat,
+            lon,
+            weight: None,
+        }
+    }
+
+    fn input(points: Vec<WeightedPoint>) -> GeoBinInput {
+        GeoBinInput {
+            points,
+            grid_type: None,
+            resolution: None,
+            cell_size_meters: None,
+            reference_latitude: None,
+            include_boundaries: None,
+        }
+    }
+
+    #[test]
+    fn test_resolution_edge_lengths() {
+        assert!((edge_length_for_resolution(0) - 1_107_712.591).abs() < 1e-6);
+        // H3 resolution 7 has ~1.22 km average edges
+        assert!((edge_length_for_resolution(7) - 1220.6).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_nearby_points_share_hex_cell() {
+        let points = vec![
+            point(40.7128, -74.0060),
+            point(40.7129, -74.0061),
+            point(40.7127, -74.0059),
+        ];
+        let result = bin_points(input(points)).unwrap();
+
+        assert_eq!(result.grid_type, "hex");
+        assert_eq!(result.resolution, Some(7));
+        assert_eq!(result.cell_count, 1);
+        assert_eq!(result.cells[0].count, 3);
+        assert_eq!(result.max_count, 3);
+    }
+
+    #[test]
+    fn test_distant_points_use_separate_cells() {
+        let points = vec![
+            point(40.7128, -74.0060),
+            point(40.7128, -74.0060),
+            point(34.0522, -118.2437),
+        ];
+        let result = bin_points(input(points)).unwrap();
+
+        assert_eq!(result.cell_count, 2);
+        assert_eq!(result.cells[0].count, 2);
+        assert_eq!(result.cells[1].count, 1);
+        assert_eq!(result.total_points, 3);
+    }
+
+    #[test]
+    fn test_square_grid_cells() {
+        let mut request = input(vec![point(0.0, 0.0005), point(0.0, 0.0015)]);
+        request.grid_type = Some("square".to_string());
+        request.cell_size_meters = Some(100.0);
+        request.reference_latitude = Some(0.0);
+
+        let result = bin_points(request).unwrap();
+        // 0.0005 and 0.0015 degrees of longitude at the equator are ~56 m and ~167 m apart
+        assert_eq!(result.cell_count, 2);
+        assert!(result.cells.iter().any(|c| c.id == "sq_0_0"));
+        assert!(result.cells.iter().any(|c| c.id == "sq_1_0"));
+        assert!((result.cell_area_km2 - 0.01).abs() < 1e-12);
+        assert!((result.cells[0].density_per_km2 - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cell_center_contains_points() {
+        let mut request = input(vec![point(51.5074, -0.1278)]);
+        request.resolution = Some(9);
+
+        let result = bin_points(request).unwrap();
+        let center = &result.cells[0].center;
+        // Resolution 9 edges are ~175 m, so the center is within a few hundred meters
+        assert!((center.lat - 51.5074).abs() < 0.003);
+        assert!((center.lon - (-0.1278)).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_weights_and_boundaries() {
+        let mut request = input(vec![
+            WeightedPoint {
+                lat: 10.0,
+                lon: 10.0,
+                weight: Some(2.5),
+            },
+            WeightedPoint {
+                lat: 10.0,
+                lon: 10.0,
+                weight: Some(1.5),
+            },
+        ]);
+        request.include_boundaries = Some(true);
+
+        let result = bin_points(request).unwrap();
+        let cell = &result.cells[0];
+        assert_eq!(cell.count, 2);
+        assert!((cell.weight_sum - 4.0).abs() < 1e-12);
+        assert_eq!(cell.boundary.as_ref().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_hex_area() {
+        let mut request = input(vec![point(0.0, 0.0)]);
+        request.cell_size_meters = Some(1000.0);
+
+        let result = bin_points(request).unwrap();
+        // Regular hexagon area = 3 * sqrt(3) / 2 * s^2
+        assert!((result.cell_area_km2 - 2.598_076).abs() < 1e-5);
+        assert_eq!(result.resolution, None);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(bin_points(input(vec![])).is_err());
+        assert!(bin_points(input(vec![point(91.0, 0.0)])).is_err());
+        assert!(bin_points(input(vec![point(0.0, 181.0)])).is_err());
+
+        let mut request = input(vec![point(0.0, 0.0)]);
+        request.resolution = Some(16);
+        assert!(bin_points(request).is_err());
+
+        let mut request = input(vec![point(0.0, 0.0)]);
+        request.grid_type = Some("triangle".to_string());
+        assert!(bin_points(request).is_err());
+
+        let mut request = input(vec![point(0.0, 0.0)]);
+        request.cell_size_meters = Some(-5.0);
+        assert!(bin_points(request).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+pub use logic::{
+    Endpoint as LogicEndpoint, LineOfSightInput as LogicInput, TerrainSample as LogicSample,
+    calculate_line_of_sight,
+};
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+struct Endpoint {
+    /// Latitude in decimal degrees
+    lat: f64,
+    /// Longitude in decimal degrees
+    lon: f64,
+    /// Ground elevation above sea level in meters
+    elevation_m: f64,
+    /// Antenna or eye height above ground in meters (default: 0)
+    antenna_height_m: Option<f64>,
+}
+
+impl From<Endpoint> for LogicEndpoint {
+    fn from(e: Endpoint) -> Self {
+        LogicEndpoint {
+            lat: e.lat,
+            lon: e.lon,
+            elevation_m: e.elevation_m,
+            antenna_height_m: e.antenna_height_m,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+struct TerrainSample {
+    /// Distance from the observer along the path in meters
+    distance_m: f64,
+    /// Terrain (or obstacle top) elevation above sea level in meters
+    elevation_m: f64,
+}
+
+impl From<TerrainSample> for LogicSample {
+    fn from(s: TerrainSample) -> Self {
+        LogicSample {
+            distance_m: s.distance_m,
+            elevation_m: s.elevation_m,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct LineOfSightInput {
+    /// Observer position and height
+    observer: Endpoint,
+    /// Target position and height
+    target: Endpoint,
+    /// Terrain elevations sampled along the path between observer and target
+    terrain_profile: Vec<TerrainSample>,
+    /// Refraction coefficient applied to the earth radius (default: 4/3, standard atmosphere)
+    k_factor: Option<f64>,
+    /// Whether to correct the profile for earth curvature (default: true)
+    earth_curvature: Option<bool>,
+    /// Radio frequency in MHz; enables first Fresnel zone clearance checks
+    frequency_mhz: Option<f64>,
+    /// Fraction of the first Fresnel zone that must be clear (default: 0.6)
+    fresnel_clearance: Option<f64>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
+}
+
+impl From<LineOfSightInput> for LogicInput {
+    fn from(input: LineOfSightInput) -> Self {
+        LogicInput {
+            observer: input.observer.into(),
+            target: input.target.into(),
+            terrain_profile: input
+                .terrain_profile
+                .into_iter()
+                .map(|s| s.into())
+                .collect(),
+            k_factor: input.k_factor,
+            earth_curvature: input.earth_curvature,
+            frequency_mhz: input.frequency_mhz,
+            fresnel_clearance: input.fresnel_clearance,
+        }
+    }
+}
+
+#[cfg(feature = "individual")]
+#[derive(Serialize, JsonSchema)]
+struct Obstruction {
+    /// Distance from the observer in meters
+    distance_m: f64,
+    /// Latitude of the obstruction
+    lat: f64,
+    /// Longitude of the obstruction
+    lon: f64,
+    /// Sampled terrain elevation in meters
+    terrain_elevation_m: f64,
+    /// Terrain elevation after earth curvature correction
+    effective_elevation_m: f64,
+    /// Height of the sight line at this point
+    sight_line_height_m: f64,
+    /// How far the terrain rises above the sight line
+    obstruction_height_m: f64,
+}
+
+#[cfg(feature = "individual")]
+#[derive(Serialize, JsonSchema)]
+struct LineOfSightResult {
+    /// Whether the target is visible from the observer
+    visible: bool,
+    /// Great-circle distance between the endpoints in meters
+    distance_m: f64,
+    /// Observer height above sea level (ground + antenna)
+    observer_height_m: f64,
+    /// Target height above sea level (ground + antenna)
+    target_height_m: f64,
+    /// Earth radius used after the refraction correction
+    effective_earth_radius_m: f64,
+    /// Curvature drop at the path midpoint
+    midpoint_earth_bulge_m: f64,
+    /// Smallest sight-line clearance over the profile (negative when blocked)
+    min_clearance_m: f64,
+    /// Distance from the observer of the smallest clearance
+    min_clearance_distance_m: f64,
+    /// Worst obstruction along the path, if any
+    obstruction: Option<Obstruction>,
+    /// Number of profile samples above the sight line
+    obstructed_samples: usize,
+    /// First Fresnel zone radius at the path midpoint (if frequency given)
+    fresnel_radius_midpoint_m: Option<f64>,
+    /// Whether the required Fresnel zone fraction is clear (if frequency given)
+    fresnel_clear: Option<bool>,
+    /// Observer antenna height needed for a clear path with the target antenna unchanged
+    required_observer_antenna_m: f64,
+    /// Target antenna height needed for a clear path with the observer antenna unchanged
+    required_target_antenna_m: f64,
+}
+
+/// Determine whether two points can see each other over a sampled terrain profile,
+/// with earth curvature and atmospheric refraction corrections
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn line_of_sight(request: Request<LineOfSightInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "line_of_sight",
+                LineOfSightInput,
+                LineOfSightResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("line_of_sight", &input);
+    log.finish(run_line_of_sight(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_line_of_sight(input: LineOfSightInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("terrain_profile", &input.terrain_profile)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let logic_input = LogicInput::from(input);
+
+    match calculate_line_of_sight(logic_input) {
+        Ok(result) => {
+            let response = LineOfSightResult {
+                visible: result.visible,
+                distance_m: result.distance_m,
+                observer_height_m: result.observer_height_m,
+                target_height_m: result.target_height_m,
+                effective_earth_radius_m: result.effective_earth_radius_m,
+                midpoint_earth_bulge_m: result.midpoint_earth_bulge_m,
+                min_clearance_m: result.min_clearance_m,
+                min_clearance_distance_m: result.min_clearance_distance_m,
+                obstruction: result.obstruction.map(|o| Obstruction {
+                    distance_m: o.distance_m,
+                    lat: o.lat,
+                    lon: o.lon,
+                    terrain_elevation_m: o.terrain_elevation_m,
+                    effective_elevation_m: o.effective_elevation_m,
+                    sight_line_height_m: o.sight_line_height_m,
+                    obstruction_height_m: o.obstruction_height_m,
+                }),
+                obstructed_samples: result.obstructed_samples,
+                fresnel_radius_midpoint_m: result.fresnel_radius_midpoint_m,
+                fresnel_clear: result.fresnel_clear,
+                required_observer_antenna_m: result.required_observer_antenna_m,
+                required_target_antenna_m: result.required_target_antenna_m,
+            };
+            ToolResponse::text(
+                ftl_response::to_json_with_precision(&response, None, precision.as_ref())
+                    .unwrap_or_else(|_| "Error serializing result".to_string()),
+            )
+        }
+        Err(error) => ToolResponse::text(error),
+    }
+}
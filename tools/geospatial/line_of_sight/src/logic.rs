@@ -0,0 +1,443 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+const EARTH_RADIUS_M: f64 = 6371008.8;
+/// Standard atmosphere refraction coefficient (effective earth radius = 4/3 R)
+const DEFAULT_K_FACTOR: f64 = 4.0 / 3.0;
+const DEFAULT_FRESNEL_CLEARANCE: f64 = 0.6;
+const SPEED_OF_LIGHT_M_S: f64 = 299_792_458.0;
+const EPSILON: f64 = 1e-9;
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Endpoint {
+    /// Latitude in decimal degrees
+    pub lat: f64,
+    /// Longitude in decimal degrees
+    pub lon: f64,
+    /// Ground elevation above sea level in meters
+    pub elevation_m: f64,
+    /// Antenna or eye height above ground in meters (default: 0)
+    pub antenna_height_m: Option<f64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct TerrainSample {
+    /// Distance from the observer along the path in meters
+    pub distance_m: f64,
+    /// Terrain (or obstacle top) elevation above sea level in meters
+    pub elevation_m: f64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LineOfSightInput {
+    pub observer: Endpoint,
+    pub target: Endpoint,
+    /// Terrain samples between the endpoints, ordered or unordered
+    pub terrain_profile: Vec<TerrainSample>,
+    /// Refraction coefficient applied to the earth radius (default: 4/3)
+    pub k_factor: Option<f64>,
+    /// Whether to apply earth curvature to the profile (default: true)
+    pub earth_curvature: Option<bool>,
+    /// Radio frequency in MHz; enables Fresnel zone clearance checks
+    pub frequency_mhz: Option<f64>,
+    /// Fraction of the first Fresnel zone that must be clear (default: 0.6)
+    pub fresnel_clearance: Option<f64>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Obstruction {
+    pub distance_m: f64,
+    pub lat: f64,
+    pub lon: f64,
+    pub terrain_elevation_m: f64,
+    /// Terrain elevation after earth curvature correction
+    pub effective_elevation_m: f64,
+    /// Height of the sight line at this point
+    pub sight_line_height_m: f64,
+    /// How far the terrain rises above the sight line
+    pub obstruction_height_m: f64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LineOfSightResult {
+    pub visible: bool,
+    pub distance_m: f64,
+    pub observer_height_m: f64,
+    pub target_height_m: f64,
+    pub effective_earth_radius_m: f64,
+    /// Curvature drop at the path midpoint
+    pub midpoint_earth_bulge_m: f64,
+    /// Smallest sight-line clearance over the profile (negative when blocked)
+    pub min_clearance_m: f64,
+    pub min_clearance_distance_m: f64,
+    /// Worst obstruction along the path, if any
+    pub obstruction: Option<Obstruction>,
+    pub obstructed_samples: usize,
+    /// First Fresnel zone radius at the path midpoint (if frequency given)
+    pub fresnel_radius_midpoint_m: Option<f64>,
+    /// Whether the required Fresnel zone fraction is clear (if frequency given)
+    pub fresnel_clear: Option<bool>,
+    /// Observer antenna height needed for a clear path with the target antenna unchanged
+    pub required_observer_antenna_m: f64,
+    /// Target antenna height needed for a clear path with the observer antenna unchanged
+    pub required_target_antenna_m: f64,
+}
+
+fn validate_endpoint(name: &str, endpoint: &Endpoint) -> Result<(), String> {
+    if !endpoint.lat.is_finite() || !endpoint.lon.is_finite() || !endpoint.elevation_m.is_finite() {
+        return Err(format!("{name} contains invalid values (NaN or Infinite)"));
+    }
+    if endpoint.lat < -90.0 || endpoint.lat > 90.0 {
+        return Err(format!(
+            "{name} latitude must be between -90 and 90 degrees"
+        ));
+    }
+    if endpoint.lon < -180.0 || endpoint.lon > 180.0 {
+        return Err(format!(
+            "{name} longitude must be between -180 and 180 degrees"
+        ));
+    }
+    if let Some(h) = endpoint.antenna_height_m
+        && (!h.is_finite() || h < 0.0)
+    {
+        return Err(format!(
+            "{name} antenna height must be a non-negative number"
+        ));
+    }
+    Ok(())
+}
+
+fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let lat1_rad = lat1 * PI / 180.0;
+    let lat2_rad = lat2 * PI / 180.0;
+    let delta_lat = (lat2 - lat1) * PI / 180.0;
+    let delta_lon = (lon2 - lon1) * PI / 180.0;
+
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat1_rad.cos() * lat2_rad.cos() * (delta_lon / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_M * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+/// Point at `fraction` of the way along the great circle between two endpoints
+fn intermediate_point(from: &Endpoint, to: &Endpoint, fraction: f64) -> (f64, f64) {
+    let lat1 = from.lat * PI / 180.0;
+    let lon1 = from.lon * PI / 180.0;
+    let lat2 = to.lat * PI / 180.0;
+    let lon2 = to.lon * PI / 180.0;
+
+    let delta = haversine_distance(from.lat, from.lon, to.lat, to.lon) / EARTH_RADIUS_M;
+    if delta < EPSILON {
+        return (from.lat, from.lon);
+    }
+
+    let a = ((1.0 - fraction) * delta).sin() / delta.sin();
+    let b = (fraction * delta).sin() / delta.sin();
+    let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+    let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+    let z = a * lat1.sin() + b * lat2.sin();
+
+    let lat = z.atan2((x * x + y * y).sqrt());
+    let lon = y.atan2(x);
+    (lat * 180.0 / PI, lon * 180.0 / PI)
+}
+
+pub fn calculate_line_of_sight(input: LineOfSightInput) -> Result<LineOfSightResult, String> {
+    validate_endpoint("Observer", &input.observer)?;
+    validate_endpoint("Target", &input.target)?;
+
+    if input.terrain_profile.is_empty() {
+        return Err("Terrain profile must contain at least one sample".to_string());
+    }
+
+    let k_factor = input.k_factor.unwrap_or(DEFAULT_K_FACTOR);
+    if !k_factor.is_finite() || k_factor <= 0.0 {
+        return Err("k_factor must be a positive number".to_string());
+    }
+
+    let fresnel_clearance = input.fresnel_clearance.unwrap_or(DEFAULT_FRESNEL_CLEARANCE);
+    if !(0.0..=1.0).contains(&fresnel_clearance) {
+        return Err("fresnel_clearance must be between 0 and 1".to_string());
+    }
+
+    let wavelength = match input.frequency_mhz {
+        Some(f) if !f.is_finite() || f <= 0.0 => {
+            return Err("frequency_mhz must be a positive number".to_string());
+        }
+        Some(f) => Some(SPEED_OF_LIGHT_M_S / (f * 1e6)),
+        None => None,
+    };
+
+    let distance = haversine_distance(
+        input.observer.lat,
+        input.observer.lon,
+        input.target.lat,
+        input.target.lon,
+    );
+    if distance < EPSILON {
+        return Err("Observer and target must be at different locations".to_string());
+    }
+
+    for (i, sample) in input.terrain_profile.iter().enumerate() {
+        if !sample.distance_m.is_finite() || !sample.elevation_m.is_finite() {
+            return Err(format!("Terrain sample {i} contains invalid values"));
+        }
+        if sample.distance_m < 0.0 || sample.distance_m > distance + 1.0 {
+            return Err(format!(
+                "Terrain sample {i} distance must be between 0 and the path length ({distance:.1} m)"
+            ));
+        }
+    }
+
+    let apply_curvature = input.earth_curvature.unwrap_or(true);
+    let effective_radius = k_factor * EARTH_RADIUS_M;
+    let bulge = |d: f64| {
+        if apply_curvature {
+            d * (distance - d) / (2.0 * effective_radius)
+        } else {
+            0.0
+        }
+    };
+    let fresnel_radius = |d: f64| {
+        wavelength.map_or(0.0, |lambda| {
+            (lambda * d * (distance - d) / distance).sqrt()
+        })
+    };
+
+    let observer_ground = input.observer.elevation_m;
+    let target_ground = input.target.elevation_m;
+    let observer_height = observer_ground + input.observer.antenna_height_m.unwrap_or(0.0);
+    let target_height = target_ground + input.target.antenna_height_m.unwrap_or(0.0);
+
+    let mut min_clearance = f64::INFINITY;
+    let mut min_clearance_distance = 0.0;
+    let mut min_fresnel_margin = f64::INFINITY;
+    let mut obstructed_samples = 0;
+    let mut worst: Option<(&TerrainSample, f64, f64, f64)> = None;
+    let mut needed_observer = f64::NEG_INFINITY;
+    let mut needed_target = f64::NEG_INFINITY;
+
+    for sample in &input.terrain_profile {
+        let d = sample.distance_m.min(distance);
+        let t = d / distance;
+        let effective = sample.elevation_m + bulge(d);
+        let sight_line = observer_height + (target_height - observer_height) * t;
+        let clearance = sight_line - effective;
+
+        if clearance < min_clearance {
+            min_clearance = clearance;
+            min_clearance_distance = d;
+        }
+        if clearance < 0.0 {
+            obstructed_samples += 1;
+            if worst.is_none_or(|(_, _, _, c)| clearance < c) {
+                worst = Some((sample, effective, sight_line, clearance));
+            }
+        }
+
+        let required = effective + fresnel_clearance * fresnel_radius(d);
+        min_fresnel_margin = min_fresnel_margin.min(sight_line - required);
+
+        // Samples at the endpoints cannot be cleared by raising the far antenna
+        if t < 1.0 - EPSILON {
+            needed_observer = needed_observer.max((required - target_height * t) / (1.0 - t));
+        }
+        if t > EPSILON {
+            needed_target = needed_target.max((required - observer_height * (1.0 - t)) / t);
+        }
+    }
+
+    let obstruction = worst.map(|(sample, effective, sight_line, clearance)| {
+        let d = sample.distance_m.min(distance);
+        let (lat, lon) = intermediate_point(&input.observer, &input.target, d / distance);
+        Obstruction {
+            distance_m: d,
+            lat,
+            lon,
+            terrain_elevation_m: sample.elevation_m,
+            effective_elevation_m: effective,
+            sight_line_height_m: sight_line,
+            obstruction_height_m: -clearance,
+        }
+    });
+
+    Ok(LineOfSightResult {
+        visible: obstructed_samples == 0,
+        distance_m: distance,
+        observer_height_m: observer_height,
+        target_height_m: target_height,
+        effective_earth_radius_m: effective_radius,
+        midpoint_earth_bulge_m: bulge(distance / 2.0),
+        min_clearance_m: min_clearance,
+        min_clearance_distance_m: min_clearance_distance,
+        obstruction,
+        obstructed_samples,
+        fresnel_radius_midpoint_m: wavelength.map(|_| fresnel_radius(distance / 2.0)),
+        fresnel_clear: wavelength.map(|_| min_fresnel_margin >= 0.0),
+        required_observer_antenna_m: (needed_observer - observer_ground).max(0.0),
+        required_target_antenna_m: (needed_target - target_ground).max(0.0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(lat: f64, lon: f64, elevation_m: f64, antenna: f64) -> Endpoint {
+        Endpoint {
+            lat,
+            lon,
+            elevation_m,
+            antenna_height_m: Some(antenna),
+        }
+    }
+
+    fn input(profile: Vec<(f64, f64)>, observer_antenna: f64) -> LineOfSightInput {
+        LineOfSightInput {
+            observer: endpoint(0.0, 0.0, 100.0, observer_antenna),
+            target: endpoint(0.0, 0.1, 100.0, 10.0),
+            terrain_profile: profile
+                .into_iter()
+                .map(|(distance_m, elevation_m)| TerrainSample {
+                    distance_m,
+                    elevation_m,
+                })
+                .collect(),
+            k_factor: None,
+            earth_curvature: Some(false),
+            frequency_mhz: None,
+            fresnel_clearance: None,
+        }
+    }
+
+    #[test]
+    fn test_flat_terrain_is_visible() {
+        let result =
+            calculate_line_of_sight(input(vec![(2000.0, 100.0), (8000.0, 100.0)], 10.0)).unwrap();
+
+        assert!(result.visible);
+        assert!(result.obstruction.is_none());
+        assert_eq!(result.obstructed_samples, 0);
+        assert!((result.min_clearance_m - 10.0).abs() < 1e-9);
+        assert!((result.distance_m - 11119.5).abs() < 1.0);
+        assert_eq!(result.required_observer_antenna_m, 0.0);
+        assert_eq!(result.required_target_antenna_m, 0.0);
+    }
+
+    #[test]
+    fn test_ridge_blocks_view() {
+        let distance = haversine_distance(0.0, 0.0, 0.0, 0.1);
+        let result = calculate_line_of_sight(input(vec![(distance / 2.0, 130.0)], 10.0)).unwrap();
+
+        assert!(!result.visible);
+        let obstruction = result.obstruction.unwrap();
+        assert!((obstruction.obstruction_height_m - 20.0).abs() < 1e-9);
+        assert!((obstruction.lon - 0.05).abs() < 1e-6);
+        assert!(obstruction.lat.abs() < 1e-9);
+        // Raising one end must lift the sight line 20 m at the midpoint: 2 * 130 - 110 - 100
+        assert!((result.required_observer_antenna_m - 50.0).abs() < 1e-6);
+        assert!((result.required_target_antenna_m - 50.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_required_height_clears_path() {
+        let profile = vec![(3000.0, 125.0), (6000.0, 140.0), (9000.0, 118.0)];
+        let blocked = calculate_line_of_sight(input(profile.clone(), 5.0)).unwrap();
+        assert!(!blocked.visible);
+
+        let raised =
+            calculate_line_of_sight(input(profile, blocked.required_observer_antenna_m + 0.01))
+                .unwrap();
+        assert!(raised.visible);
+        assert!(raised.min_clearance_m >= 0.0);
+    }
+
+    #[test]
+    fn test_worst_obstruction_is_reported() {
+        let result = calculate_line_of_sight(input(
+            vec![(2000.0, 115.0), (5000.0, 150.0), (8000.0, 120.0)],
+            10.0,
+        ))
+        .unwrap();
+
+        assert_eq!(result.obstructed_samples, 3);
+        let obstruction = result.obstruction.unwrap();
+        assert_eq!(obstruction.distance_m, 5000.0);
+        assert_eq!(result.min_clearance_distance_m, 5000.0);
+    }
+
+    #[test]
+    fn test_earth_curvature_and_refraction() {
+        // 50 km path over the sea with low antennas
+        let mut sea = LineOfSightInput {
+            observer: endpoint(0.0, 0.0, 0.0, 30.0),
+            target: endpoint(0.0, 0.449_66, 0.0, 30.0),
+            terrain_profile: vec![TerrainSample {
+                distance_m: 25_000.0,
+                elevation_m: 0.0,
+            }],
+            k_factor: Some(1.0),
+            earth_curvature: None,
+            frequency_mhz: None,
+            fresnel_clearance: None,
+        };
+        let geometric = calculate_line_of_sight(sea).unwrap();
+        // Bulge at midpoint: d^2 / (8R) ~= 49 m for 50 km with no refraction
+        assert!((geometric.midpoint_earth_bulge_m - 49.05).abs() < 0.1);
+        assert!(!geometric.visible);
+
+        sea = LineOfSightInput {
+            observer: endpoint(0.0, 0.0, 0.0, 40.0),
+            target: endpoint(0.0, 0.449_66, 0.0, 40.0),
+            terrain_profile: vec![TerrainSample {
+                distance_m: 25_000.0,
+                elevation_m: 0.0,
+            }],
+            k_factor: None,
+            earth_curvature: None,
+            frequency_mhz: None,
+            fresnel_clearance: None,
+        };
+        let refracted = calculate_line_of_sight(sea).unwrap();
+        // Standard refraction shrinks the bulge to ~36.8 m
+        assert!((refracted.midpoint_earth_bulge_m - 36.79).abs() < 0.1);
+        assert!(refracted.visible);
+    }
+
+    #[test]
+    fn test_fresnel_zone_clearance() {
+        let mut request = input(vec![(5559.0, 105.0)], 10.0);
+        request.frequency_mhz = Some(2400.0);
+
+        let result = calculate_line_of_sight(request).unwrap();
+        assert!(result.visible);
+        let radius = result.fresnel_radius_midpoint_m.unwrap();
+        // r = sqrt(lambda * d1 * d2 / D) for 2.4 GHz over ~11 km
+        assert!((radius - 18.6).abs() < 0.1);
+        assert_eq!(result.fresnel_clear, Some(false));
+        assert!(result.required_observer_antenna_m > 10.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(calculate_line_of_sight(input(vec![], 10.0)).is_err());
+
+        let mut bad_latitude = input(vec![(100.0, 0.0)], 10.0);
+        bad_latitude.observer.lat = 95.0;
+        assert!(calculate_line_of_sight(bad_latitude).is_err());
+
+        let bad_antenna = input(vec![(100.0, 0.0)], -1.0);
+        assert!(calculate_line_of_sight(bad_antenna).is_err());
+
+        let beyond_target = input(vec![(50_000.0, 0.0)], 10.0);
+        assert!(calculate_line_of_sight(beyond_target).is_err());
+
+        let mut bad_k = input(vec![(100.0, 0.0)], 10.0);
+        bad_k.k_factor = Some(0.0);
+        assert!(calculate_line_of_sight(bad_k).is_err());
+
+        let mut same_point = input(vec![(0.0, 0.0)], 10.0);
+        same_point.target.lon = 0.0;
+        assert!(calculate_line_of_sight(same_point).is_err());
+    }
+}
@@ -1,11 +1,19 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{Point as LogicPoint, PointInPolygonInput as LogicInput, point_in_polygon_check};
+pub use logic::{Point as LogicPoint, PointInPolygonInput as LogicInput, point_in_polygon_check};
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 struct Point {
     /// Latitude in decimal degrees
     lat: f64,
@@ -22,12 +30,18 @@ impl From<Point> for LogicPoint {
     }
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 struct PointInPolygonInput {
     /// Point to test
     point: Point,
     /// Polygon vertices
     polygon: Vec<Point>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
 #[derive(Serialize, JsonSchema)]
@@ -51,14 +65,50 @@ impl From<PointInPolygonInput> for LogicInput {
 }
 
 /// Check if a point is inside a polygon using ray casting algorithm
-#[cfg_attr(not(test), ftl_sdk::tool)]
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), ftl_sdk::tool)]
 #[allow(dead_code)]
-fn point_in_polygon(input: PointInPolygonInput) -> ToolResponse {
+fn point_in_polygon(request: Request<PointInPolygonInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "point_in_polygon",
+                PointInPolygonInput,
+                PointInPolygonResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("point_in_polygon", &input);
+    log.finish(run_point_in_polygon(input))
+}
+
+#[cfg(feature = "individual")]
+#[allow(dead_code)]
+fn run_point_in_polygon(input: PointInPolygonInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .polygon("polygon", &input.polygon)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     let logic_input = LogicInput::from(input);
 
     let result = match point_in_polygon_check(logic_input.point, logic_input.polygon) {
         Ok(result) => result,
-        Err(e) => return ToolResponse::text(format!("Error checking point in polygon: {e}")),
+        Err(e) => {
+            return ToolResponse::text(format!("Error checking point in polygon: {e}"));
+        }
     };
 
     let output = PointInPolygonResult {
@@ -68,6 +118,7 @@ fn point_in_polygon(input: PointInPolygonInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string(&output).unwrap_or_else(|_| "Error serializing result".to_string()),
+        ftl_response::to_json_with_precision(&output, None, precision.as_ref())
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
     )
 }
@@ -1,11 +1,22 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
+use ftl_units::LengthUnit;
+#[cfg(feature = "individual")]
+use ftl_units::Quantity;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{Coordinate as LogicCoordinate, PolygonInput as LogicInput, get_polygon_area};
+pub use logic::{Coordinate as LogicCoordinate, PolygonInput as LogicInput, get_polygon_area};
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 struct Coordinate {
     /// Latitude in decimal degrees
     lat: f64,
@@ -22,14 +33,27 @@ impl From<Coordinate> for LogicCoordinate {
     }
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub struct PolygonInput {
     /// Array of coordinates defining the polygon
     coordinates: Vec<Coordinate>,
+    /// Only check the polygon and return a validation report, without computing the area
+    validate_only: Option<bool>,
+    /// Length unit squared for `area`: "meters", "kilometers" or "miles" (default: meters)
+    unit: Option<LengthUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct PolygonAreaResult {
+    /// Area in the square of the requested unit
+    area: Quantity,
     /// Area in square meters
     area_square_meters: f64,
     /// Area in square kilometers
@@ -51,16 +75,57 @@ impl From<PolygonInput> for LogicInput {
 }
 
 /// Calculate area of a GPS polygon
-#[cfg_attr(not(test), ftl_sdk::tool)]
-pub fn polygon_area(input: PolygonInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), ftl_sdk::tool)]
+pub fn polygon_area(request: Request<PolygonInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("polygon_area", PolygonInput, PolygonAreaResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("polygon_area", &input);
+    log.finish(run_polygon_area(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_polygon_area(input: PolygonInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .polygon("coordinates", &input.coordinates)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let validate_only = input.validate_only.unwrap_or(false);
+    let unit = input.unit.unwrap_or_default();
     let logic_input = LogicInput::from(input);
 
+    if validate_only {
+        let report = logic::validate_polygon(&logic_input.coordinates);
+        return ToolResponse::text(
+            ftl_json::to_string(&report).unwrap_or_else(|_| "Error serializing result".to_string()),
+        );
+    }
+
     let result = match get_polygon_area(logic_input.coordinates) {
         Ok(result) => result,
-        Err(e) => return ToolResponse::text(format!("Error calculating polygon area: {e}")),
+        Err(e) => {
+            return ToolResponse::text(format!("Error calculating polygon area: {e}"));
+        }
     };
 
     let output = PolygonAreaResult {
+        area: Quantity::area(result.area_square_meters, unit),
         area_square_meters: result.area_square_meters,
         area_square_kilometers: result.area_square_kilometers,
         area_square_miles: result.area_square_miles,
@@ -69,6 +134,7 @@ pub fn polygon_area(input: PolygonInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string(&output).unwrap_or_else(|_| "Error serializing result".to_string()),
+        ftl_response::to_json_with_precision(&output, None, precision.as_ref())
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
     )
 }
@@ -1,3 +1,4 @@
+use ftl_preflight::ValidationReport;
 use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
 
@@ -49,29 +50,65 @@ pub fn calculate_polygon_area(coordinates: &[Coordinate]) -> Result<f64, String>
     Ok(area)
 }
 
-pub fn get_polygon_area(coordinates: Vec<Coordinate>) -> Result<PolygonAreaResult, String> {
-    // Validate coordinates
-    for coord in &coordinates {
-        if coord.lat.is_nan() || coord.lat.is_infinite() {
-            return Err("Coordinate latitude cannot be NaN or infinite".to_string());
+/// Check a polygon ring without computing its area
+pub fn validate_polygon(coordinates: &[Coordinate]) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    for (i, coord) in coordinates.iter().enumerate() {
+        let lat_finite = coord.lat.is_finite();
+        let lon_finite = coord.lon.is_finite();
+        if !lat_finite {
+            report.error(
+                format!("coordinates[{i}].lat"),
+                "Coordinate latitude cannot be NaN or infinite",
+            );
         }
-        if coord.lon.is_nan() || coord.lon.is_infinite() {
-            return Err("Coordinate longitude cannot be NaN or infinite".to_string());
+        if !lon_finite {
+            report.error(
+                format!("coordinates[{i}].lon"),
+                "Coordinate longitude cannot be NaN or infinite",
+            );
         }
-        if coord.lat < -90.0 || coord.lat > 90.0 {
-            return Err(format!(
-                "Invalid latitude: {}. Must be between -90 and 90",
-                coord.lat
-            ));
+        if lat_finite && (coord.lat < -90.0 || coord.lat > 90.0) {
+            report.error(
+                format!("coordinates[{i}].lat"),
+                format!(
+                    "Invalid latitude: {}. Must be between -90 and 90",
+                    coord.lat
+                ),
+            );
         }
-        if coord.lon < -180.0 || coord.lon > 180.0 {
-            return Err(format!(
-                "Invalid longitude: {}. Must be between -180 and 180",
-                coord.lon
-            ));
+        if lon_finite && (coord.lon < -180.0 || coord.lon > 180.0) {
+            report.error(
+                format!("coordinates[{i}].lon"),
+                format!(
+                    "Invalid longitude: {}. Must be between -180 and 180",
+                    coord.lon
+                ),
+            );
         }
     }
 
+    if coordinates.len() < 3 {
+        report.error("coordinates", "Polygon must have at least 3 coordinates");
+    } else if coordinates.first() != coordinates.last() {
+        report.warning(
+            "coordinates",
+            "Ring is not closed; the last coordinate is joined back to the first",
+        );
+    } else if coordinates.len() < 4 {
+        report.warning(
+            "coordinates",
+            "Closed ring has fewer than 3 distinct coordinates; its area is zero",
+        );
+    }
+
+    report
+}
+
+pub fn get_polygon_area(coordinates: Vec<Coordinate>) -> Result<PolygonAreaResult, String> {
+    validate_polygon(&coordinates).into_result()?;
+
     let area_m2 = calculate_polygon_area(&coordinates)?;
 
     Ok(PolygonAreaResult {
@@ -483,4 +520,26 @@ mod tests {
         assert!(result.area_square_meters > 1_000_000.0); // > 1 km²
         assert!(result.area_square_meters < 10_000_000_000.0); // < 10,000 km²
     }
+
+    #[test]
+    fn test_validate_polygon() {
+        let mut polygon = create_unit_square();
+        polygon[1].lat = 95.0;
+        polygon[2].lon = f64::NAN;
+        let report = validate_polygon(&polygon);
+        assert!(!report.valid);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["coordinates[1].lat", "coordinates[2].lon"]);
+
+        // Open rings are accepted with a warning; closed rings are not flagged
+        let report = validate_polygon(&create_unit_square());
+        assert!(report.valid);
+        assert_eq!(report.warnings.len(), 1);
+
+        let mut closed = create_unit_square();
+        closed.push(closed[0]);
+        let report = validate_polygon(&closed);
+        assert!(report.valid);
+        assert!(report.warnings.is_empty());
+    }
 }
@@ -1,12 +1,21 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 use schemars::JsonSchema;
 
 mod logic;
-use logic::{
-    Point as LogicPoint, PolygonSimplificationInput as LogicInput, polygon_simplification_logic,
+pub use logic::{
+    Point as LogicPoint, PolygonSimplificationInput as LogicInput,
+    PolygonSimplificationResultSchema as PolygonSimplificationResult, polygon_simplification_logic,
 };
 
-#[derive(serde::Deserialize, JsonSchema)]
+#[derive(serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct Point {
     pub lat: f64,
     pub lon: f64,
@@ -21,11 +30,17 @@ impl From<Point> for LogicPoint {
     }
 }
 
-#[derive(serde::Deserialize, JsonSchema)]
+#[derive(serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct PolygonSimplificationInput {
     pub polygon: Vec<Point>,
     pub tolerance_meters: f64,
     pub algorithm: Option<String>, // "douglas_peucker" or "visvalingam" (default: douglas_peucker)
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 impl From<PolygonSimplificationInput> for LogicInput {
@@ -38,11 +53,44 @@ impl From<PolygonSimplificationInput> for LogicInput {
     }
 }
 
-#[cfg_attr(not(test), ftl_sdk::tool)]
-pub fn polygon_simplification(input: PolygonSimplificationInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), ftl_sdk::tool)]
+pub fn polygon_simplification(request: Request<PolygonSimplificationInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "polygon_simplification",
+                PolygonSimplificationInput,
+                PolygonSimplificationResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("polygon_simplification", &input);
+    log.finish(run_polygon_simplification(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_polygon_simplification(input: PolygonSimplificationInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .polygon("polygon", &input.polygon)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     match polygon_simplification_logic(input.into()) {
         Ok(result) => ToolResponse::text(
-            serde_json::to_string(&result)
+            ftl_response::to_json_with_precision(&result, None, precision.as_ref())
                 .unwrap_or_else(|_| "Error serializing result".to_string()),
         ),
         Err(error) => ToolResponse::text(error),
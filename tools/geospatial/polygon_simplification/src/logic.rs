@@ -1,6 +1,7 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, ToolTypes)]
 pub struct Point {
     pub lat: f64,
     pub lon: f64,
@@ -13,9 +14,11 @@ pub struct PolygonSimplificationInput {
     pub algorithm: Option<String>, // "douglas_peucker" or "visvalingam" (default: douglas_peucker)
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToolTypes)]
 pub struct PolygonSimplificationResult {
+    #[tool_types(nested)]
     pub original_polygon: Vec<Point>,
+    #[tool_types(nested)]
     pub simplified_polygon: Vec<Point>,
     pub original_vertex_count: usize,
     pub simplified_vertex_count: usize,
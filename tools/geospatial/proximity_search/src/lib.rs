@@ -1,11 +1,26 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_pagination::PageInfo;
+#[cfg(feature = "individual")]
+use ftl_pagination::PageRequest;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+#[cfg(feature = "individual")]
+use ftl_units::Quantity;
+use ftl_units::{Length, LengthUnit, length_in_meters};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{NearestPointsInput as LogicInput, Point as LogicPoint, find_nearest_points};
+pub use logic::{NearestPointsInput as LogicInput, Point as LogicPoint, find_nearest_points_page};
 
 #[derive(Deserialize, Serialize, JsonSchema)]
 struct Point {
@@ -27,7 +42,7 @@ impl From<Point> for LogicPoint {
     }
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub struct NearestPointsInput {
     /// Point to search from
     query_point: Point,
@@ -37,18 +52,38 @@ pub struct NearestPointsInput {
     max_results: Option<usize>,
     /// Only return points within this distance (meters)
     max_distance_meters: Option<f64>,
+    /// Only return points within this distance, with a unit, e.g. {"value": 5, "unit": "miles"}
+    max_distance: Option<Length>,
+    /// Number of results to skip before the returned page (default: 0)
+    offset: Option<usize>,
+    /// Maximum number of results in the returned page (default: all)
+    limit: Option<usize>,
+    /// Cursor from a previous response's `page_info.next_cursor`, in place of `offset`
+    cursor: Option<String>,
+    /// Unit for each result's `distance`: "meters", "kilometers" or "miles" (default: meters)
+    unit: Option<LengthUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct NearestPointResult {
     /// The found point
     point: Point,
+    /// Distance in the requested unit
+    distance: Quantity,
     /// Distance in meters
     distance_meters: f64,
     /// Bearing in degrees
     bearing_degrees: f64,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct NearestPointsResult {
     /// Original query point
@@ -59,11 +94,20 @@ struct NearestPointsResult {
     total_candidates: usize,
     /// Number of results returned
     results_returned: usize,
+    /// Position of the returned points within all matching points
+    page_info: PageInfo,
 }
 
-impl From<NearestPointsInput> for LogicInput {
-    fn from(input: NearestPointsInput) -> Self {
-        LogicInput {
+impl TryFrom<NearestPointsInput> for LogicInput {
+    type Error = String;
+
+    fn try_from(input: NearestPointsInput) -> Result<Self, String> {
+        let max_distance_meters = length_in_meters(
+            "max_distance",
+            input.max_distance_meters,
+            input.max_distance,
+        )?;
+        Ok(LogicInput {
             query_point: input.query_point.into(),
             candidate_points: input
                 .candidate_points
@@ -71,21 +115,70 @@ impl From<NearestPointsInput> for LogicInput {
                 .map(|p| p.into())
                 .collect(),
             max_results: input.max_results,
-            max_distance_meters: input.max_distance_meters,
-        }
+            max_distance_meters,
+            offset: input.offset,
+            limit: input.limit,
+            cursor: input.cursor,
+        })
     }
 }
 
 /// Find nearest points to a query location with distance and bearing
-#[cfg_attr(not(test), tool)]
-pub fn proximity_search(input: NearestPointsInput) -> ToolResponse {
-    let logic_input = LogicInput::from(input);
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn proximity_search(request: Request<NearestPointsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "proximity_search",
+                NearestPointsInput,
+                NearestPointsResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("proximity_search", &input);
+    log.finish(run_proximity_search(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_proximity_search(input: NearestPointsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("candidate_points", &input.candidate_points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let unit = input.unit.unwrap_or_default();
+    let logic_input = match LogicInput::try_from(input) {
+        Ok(logic_input) => logic_input,
+        Err(error) => return ToolResponse::text(error),
+    };
+    let page = match PageRequest::from_input(
+        logic_input.offset,
+        logic_input.limit,
+        logic_input.cursor.as_deref(),
+    ) {
+        Ok(page) => page,
+        Err(error) => return ToolResponse::text(error),
+    };
 
-    match find_nearest_points(
+    match find_nearest_points_page(
         logic_input.query_point,
         logic_input.candidate_points,
         logic_input.max_results,
         logic_input.max_distance_meters,
+        page,
     ) {
         Ok(result) => {
             let response = NearestPointsResult {
@@ -103,15 +196,17 @@ pub fn proximity_search(input: NearestPointsInput) -> ToolResponse {
                             lon: np.point.lon,
                             id: np.point.id,
                         },
+                        distance: Quantity::length(np.distance_meters, unit),
                         distance_meters: np.distance_meters,
                         bearing_degrees: np.bearing_degrees,
                     })
                     .collect(),
                 total_candidates: result.total_candidates,
                 results_returned: result.results_returned,
+                page_info: result.page_info,
             };
             ToolResponse::text(
-                serde_json::to_string(&response)
+                ftl_response::to_json_with_precision(&response, None, precision.as_ref())
                     .unwrap_or_else(|_| "Error serializing result".to_string()),
             )
         }
@@ -1,3 +1,4 @@
+use ftl_pagination::{PageInfo, PageRequest};
 use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
 
@@ -21,6 +22,12 @@ pub struct NearestPointsInput {
     pub max_results: Option<usize>,
     /// Only return points within this distance (meters)
     pub max_distance_meters: Option<f64>,
+    /// Number of results to skip before the returned page (default: 0)
+    pub offset: Option<usize>,
+    /// Maximum number of results in the returned page (default: all)
+    pub limit: Option<usize>,
+    /// Cursor from a previous response's `page_info.next_cursor`, in place of `offset`
+    pub cursor: Option<String>,
 }
 
 #[derive(Serialize, Debug)]
@@ -36,6 +43,7 @@ pub struct NearestPointsResult {
     pub nearest_points: Vec<NearestPointResult>,
     pub total_candidates: usize,
     pub results_returned: usize,
+    pub page_info: PageInfo,
 }
 
 const EARTH_RADIUS_M: f64 = 6378137.0;
@@ -66,11 +74,31 @@ pub fn calculate_bearing(from: &Point, to: &Point) -> f64 {
     (bearing_rad * 180.0 / PI + 360.0) % 360.0
 }
 
+/// All ranked results in one page
+#[cfg(test)]
 pub fn find_nearest_points(
     query_point: Point,
     candidate_points: Vec<Point>,
     max_results: Option<usize>,
     max_distance_meters: Option<f64>,
+) -> Result<NearestPointsResult, String> {
+    find_nearest_points_page(
+        query_point,
+        candidate_points,
+        max_results,
+        max_distance_meters,
+        PageRequest::default(),
+    )
+}
+
+/// Rank candidates by distance from the query point and return one page of the
+/// results. `max_results` caps the full result list that `page` is taken from.
+pub fn find_nearest_points_page(
+    query_point: Point,
+    candidate_points: Vec<Point>,
+    max_results: Option<usize>,
+    max_distance_meters: Option<f64>,
+    page: PageRequest,
 ) -> Result<NearestPointsResult, String> {
     if candidate_points.is_empty() {
         return Err("At least one candidate point must be provided".to_string());
@@ -97,10 +125,10 @@ pub fn find_nearest_points(
     }
 
     // Validate max_distance_meters
-    if let Some(max_dist) = max_distance_meters {
-        if max_dist < 0.0 || max_dist.is_nan() || max_dist.is_infinite() {
-            return Err("Max distance must be positive and finite".to_string());
-        }
+    if let Some(max_dist) = max_distance_meters
+        && (max_dist < 0.0 || max_dist.is_nan() || max_dist.is_infinite())
+    {
+        return Err("Max distance must be positive and finite".to_string());
     }
 
     let mut distances: Vec<(usize, f64)> = Vec::new();
@@ -141,11 +169,13 @@ pub fn find_nearest_points(
     // Sort by distance
     distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
 
-    // Apply result limit
+    // Apply result limit, then cut out the requested page
     let max_results = max_results.unwrap_or(distances.len()).min(distances.len());
+    distances.truncate(max_results);
+    let (distances, page_info) = page.apply(distances);
 
     let mut nearest_points = Vec::new();
-    for &(idx, distance) in distances.iter().take(max_results) {
+    for &(idx, distance) in &distances {
         let candidate = &candidate_points[idx];
         let bearing = calculate_bearing(&query_point, candidate);
 
@@ -158,9 +188,10 @@ pub fn find_nearest_points(
 
     Ok(NearestPointsResult {
         query_point,
+        results_returned: nearest_points.len(),
         nearest_points,
         total_candidates: candidate_points.len(),
-        results_returned: max_results,
+        page_info,
     })
 }
 
@@ -693,4 +724,26 @@ mod tests {
             assert!(nearest.bearing_degrees < 360.0);
         }
     }
+
+    #[test]
+    fn test_paged_results() {
+        let query_point = Point {
+            lat: 39.0,
+            lon: -98.0,
+            id: None,
+        };
+        let all =
+            find_nearest_points(query_point.clone(), create_test_points(), None, None).unwrap();
+        let page = PageRequest::new(Some(1), Some(2)).unwrap();
+        let result =
+            find_nearest_points_page(query_point, create_test_points(), Some(4), None, page)
+                .unwrap();
+
+        assert_eq!(result.results_returned, 2);
+        assert_eq!(result.total_candidates, 5);
+        assert_eq!(result.page_info.total, 4);
+        assert!(result.page_info.truncated);
+        assert_eq!(result.nearest_points[0].point, all.nearest_points[1].point);
+        assert_eq!(result.nearest_points[1].point, all.nearest_points[2].point);
+    }
 }
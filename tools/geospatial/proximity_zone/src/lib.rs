@@ -1,11 +1,24 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+#[cfg(feature = "individual")]
+use ftl_units::LengthUnit;
+#[cfg(feature = "individual")]
+use ftl_units::Quantity;
+use ftl_units::{Length, length_in_meters};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{Point as LogicPoint, ProximityZoneInput as LogicInput, proximity_zone_analysis};
+pub use logic::{Point as LogicPoint, ProximityZoneInput as LogicInput, proximity_zone_analysis};
 
 #[derive(Deserialize, Serialize, JsonSchema)]
 struct Point {
@@ -27,16 +40,25 @@ impl From<Point> for LogicPoint {
     }
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub struct ProximityZoneInput {
     /// Center of the proximity zone
     center: Point,
-    /// Radius of the zone in meters
-    radius_meters: f64,
+    /// Radius of the zone in meters (or give `radius`)
+    radius_meters: Option<f64>,
+    /// Radius of the zone with a unit, e.g. {"value": 2, "unit": "miles"} (instead of `radius_meters`)
+    radius: Option<Length>,
     /// Points to analyze
     candidate_points: Vec<Point>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct NearestPointResult {
     /// The point
@@ -47,6 +69,7 @@ struct NearestPointResult {
     bearing_degrees: f64,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct ProximityZoneSummary {
     /// Total number of points
@@ -63,10 +86,13 @@ struct ProximityZoneSummary {
     farthest_point_distance: f64,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, JsonSchema)]
 struct ProximityZoneResult {
     /// Center of the zone
     center: Point,
+    /// Radius, in the unit it was given in
+    radius: Quantity,
     /// Radius in meters
     radius_meters: f64,
     /// Points within the zone
@@ -77,24 +103,67 @@ struct ProximityZoneResult {
     summary: ProximityZoneSummary,
 }
 
-impl From<ProximityZoneInput> for LogicInput {
-    fn from(input: ProximityZoneInput) -> Self {
-        LogicInput {
+impl TryFrom<ProximityZoneInput> for LogicInput {
+    type Error = String;
+
+    fn try_from(input: ProximityZoneInput) -> Result<Self, String> {
+        let radius_meters = length_in_meters("radius", input.radius_meters, input.radius)?
+            .ok_or("Either radius or radius_meters is required")?;
+        Ok(LogicInput {
             center: input.center.into(),
-            radius_meters: input.radius_meters,
+            radius_meters,
             candidate_points: input
                 .candidate_points
                 .into_iter()
                 .map(|p| p.into())
                 .collect(),
-        }
+        })
     }
 }
 
 /// Analyze points within a proximity zone and provide detailed statistics
-#[cfg_attr(not(test), tool)]
-pub fn proximity_zone(input: ProximityZoneInput) -> ToolResponse {
-    let logic_input = LogicInput::from(input);
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn proximity_zone(request: Request<ProximityZoneInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "proximity_zone",
+                ProximityZoneInput,
+                ProximityZoneResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("proximity_zone", &input);
+    log.finish(run_proximity_zone(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_proximity_zone(input: ProximityZoneInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("candidate_points", &input.candidate_points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let radius_unit = input
+        .radius
+        .map_or(LengthUnit::Meters, |radius| radius.unit);
+    let logic_input = match LogicInput::try_from(input) {
+        Ok(logic_input) => logic_input,
+        Err(error) => return ToolResponse::text(error),
+    };
 
     match proximity_zone_analysis(
         logic_input.center,
@@ -108,6 +177,7 @@ pub fn proximity_zone(input: ProximityZoneInput) -> ToolResponse {
                     lon: result.center.lon,
                     id: result.center.id,
                 },
+                radius: Quantity::length(result.radius_meters, radius_unit),
                 radius_meters: result.radius_meters,
                 points_in_zone: result
                     .points_in_zone
@@ -145,7 +215,7 @@ pub fn proximity_zone(input: ProximityZoneInput) -> ToolResponse {
                 },
             };
             ToolResponse::text(
-                serde_json::to_string(&response)
+                ftl_response::to_json_with_precision(&response, None, precision.as_ref())
                     .unwrap_or_else(|_| "Error serializing result".to_string()),
             )
         }
@@ -0,0 +1,95 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_CELLS, MAX_POINTS, RasterSampleInput as LogicInput, RasterSampleOutput as LogicOutput,
+    raster_sample_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    CoordinateSchema as Coordinate, RasterBoundsSchema as RasterBounds,
+    RasterSampleOutputSchema as RasterSampleOutput, RasterSampleSchema as RasterSample,
+    RasterSchema as Raster,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RasterSampleInput {
+    #[serde(flatten)]
+    pub params: logic::RasterSampleInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Sample a small gridded dataset, such as elevation or population density, at
+/// latitude/longitude points by nearest cell or bilinear interpolation
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn raster_sample(request: Request<RasterSampleInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "raster_sample",
+                RasterSampleInput,
+                RasterSampleOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("raster_sample", &input);
+    log.finish(run_raster_sample(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_raster_sample(input: RasterSampleInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("raster.values", &input.params.raster.values)
+        .array_len(
+            "raster.values",
+            input.params.raster.values.iter().map(Vec::len).sum(),
+        )
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::raster_sample_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = RasterSampleOutput::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,374 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most raster cells accepted in one call
+pub const MAX_CELLS: usize = 1_000_000;
+/// Most points sampled in one call
+pub const MAX_POINTS: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+pub struct Coordinate {
+    /// Latitude in decimal degrees
+    pub lat: f64,
+    /// Longitude in decimal degrees
+    pub lon: f64,
+}
+
+/// Regular latitude/longitude grid of cell values, each value covering its whole cell
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Raster {
+    /// Outer corner of the first cell of the first row: its north-west corner, or its
+    /// south-west corner with row_order "south_to_north"
+    #[tool_types(nested)]
+    pub origin: Coordinate,
+    /// Cell height in degrees of latitude, and its width unless cell_size_lon is given
+    pub cell_size: f64,
+    /// Cell width in degrees of longitude (default: cell_size)
+    pub cell_size_lon: Option<f64>,
+    /// Cell values by row, then column running east; null marks a cell without data
+    pub values: Vec<Vec<Option<f64>>>,
+    /// Value that also marks a cell without data, such as -9999
+    pub nodata: Option<f64>,
+    /// "north_to_south" (default) or "south_to_north"
+    pub row_order: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RasterSampleInput {
+    #[tool_types(nested)]
+    pub raster: Raster,
+    /// Points to sample
+    #[tool_types(nested)]
+    pub points: Vec<Coordinate>,
+    /// "nearest" (the value of the cell containing the point) or "bilinear" (blended
+    /// from the four nearest cell centres) (default: bilinear)
+    pub method: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RasterSample {
+    pub lat: f64,
+    pub lon: f64,
+    /// Sampled value; null outside the raster or on a cell without data
+    pub value: Option<f64>,
+    /// Row of the cell containing the point
+    pub row: Option<usize>,
+    /// Column of the cell containing the point
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RasterBounds {
+    pub north: f64,
+    pub south: f64,
+    pub west: f64,
+    pub east: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RasterSampleOutput {
+    /// One sample per point, in input order
+    #[tool_types(nested)]
+    pub samples: Vec<RasterSample>,
+    pub method: String,
+    pub rows: usize,
+    pub columns: usize,
+    /// Area the raster covers
+    #[tool_types(nested)]
+    pub bounds: RasterBounds,
+    /// Points that got a value
+    pub sampled_count: usize,
+    /// Points outside the raster or on cells without data
+    pub missing_count: usize,
+}
+
+/// Position along one grid axis, in cells from the first cell's outer edge
+fn axis_position(offset: f64, cells: usize) -> Option<f64> {
+    (0.0..=cells as f64).contains(&offset).then_some(offset)
+}
+
+/// The two cell centres to blend along one axis and the weight of the second
+fn axis_neighbours(position: f64, cells: usize) -> (usize, usize, f64) {
+    if cells == 1 {
+        return (0, 0, 0.0);
+    }
+    // Between the outer edge and the first or last centre the edge cell's value holds
+    let centre = (position - 0.5).clamp(0.0, (cells - 1) as f64);
+    let low = (centre.floor() as usize).min(cells - 2);
+    (low, low + 1, centre - low as f64)
+}
+
+pub fn raster_sample_logic(input: RasterSampleInput) -> Result<RasterSampleOutput, String> {
+    let raster = input.raster;
+    let rows = raster.values.len();
+    let columns = raster.values.first().map_or(0, Vec::len);
+    if rows == 0 || columns == 0 {
+        return Err("Raster values must contain at least one row and one column".to_string());
+    }
+    if let Some(row) = raster.values.iter().position(|r| r.len() != columns) {
+        return Err(format!(
+            "Raster row {row} has {} values, expected {columns}",
+            raster.values[row].len()
+        ));
+    }
+    if rows.saturating_mul(columns) > MAX_CELLS {
+        return Err(format!("At most {MAX_CELLS} raster cells are allowed"));
+    }
+    if raster
+        .values
+        .iter()
+        .flatten()
+        .flatten()
+        .any(|v| !v.is_finite())
+    {
+        return Err("Raster values must be finite numbers or null".to_string());
+    }
+    if input.points.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} points are allowed"));
+    }
+    let cell_height = raster.cell_size;
+    let cell_width = raster.cell_size_lon.unwrap_or(raster.cell_size);
+    if !(cell_height.is_finite() && cell_height > 0.0 && cell_width.is_finite() && cell_width > 0.0)
+    {
+        return Err("Cell sizes must be positive and finite".to_string());
+    }
+    let north_up = match raster
+        .row_order
+        .as_deref()
+        .map(|o| o.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("north_to_south") => true,
+        Some("south_to_north") => false,
+        Some(other) => {
+            return Err(format!(
+                "Unknown row_order '{other}', use 'north_to_south' or 'south_to_north'"
+            ));
+        }
+    };
+    let method = input
+        .method
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .unwrap_or_else(|| "bilinear".to_string());
+    if !matches!(method.as_str(), "nearest" | "bilinear") {
+        return Err(format!(
+            "Unknown method '{method}', use 'nearest' or 'bilinear'"
+        ));
+    }
+
+    let origin = raster.origin;
+    if !origin.lat.is_finite() || !origin.lon.is_finite() {
+        return Err("Raster origin has a non-finite coordinate".to_string());
+    }
+    let height = cell_height * rows as f64;
+    let (north, south) = if north_up {
+        (origin.lat, origin.lat - height)
+    } else {
+        (origin.lat + height, origin.lat)
+    };
+    let (west, east) = (origin.lon, origin.lon + cell_width * columns as f64);
+    if north > 90.0 || south < -90.0 || west < -180.0 || east > 180.0 {
+        return Err(
+            "Raster must lie within latitudes -90 to 90 and longitudes -180 to 180".to_string(),
+        );
+    }
+
+    for (i, p) in input.points.iter().enumerate() {
+        if !p.lat.is_finite() || !p.lon.is_finite() {
+            return Err(format!("Point {i} has a non-finite coordinate"));
+        }
+        if p.lat < -90.0 || p.lat > 90.0 {
+            return Err(format!(
+                "Point {i} latitude must be between -90 and 90 degrees"
+            ));
+        }
+        if p.lon < -180.0 || p.lon > 180.0 {
+            return Err(format!(
+                "Point {i} longitude must be between -180 and 180 degrees"
+            ));
+        }
+    }
+
+    let cell = |row: usize, column: usize| {
+        raster.values[row][column].filter(|v| raster.nodata.is_none_or(|nodata| *v != nodata))
+    };
+    let samples: Vec<RasterSample> = input
+        .points
+        .iter()
+        .map(|p| {
+            let row_offset = if north_up {
+                north - p.lat
+            } else {
+                p.lat - south
+            };
+            let position = axis_position(row_offset / cell_height, rows)
+                .zip(axis_position((p.lon - west) / cell_width, columns));
+            let Some((y, x)) = position else {
+                return RasterSample {
+                    lat: p.lat,
+                    lon: p.lon,
+                    value: None,
+                    row: None,
+                    column: None,
+                };
+            };
+            // A point on the far edge belongs to the last cell
+            let row = (y.floor() as usize).min(rows - 1);
+            let column = (x.floor() as usize).min(columns - 1);
+            let value = cell(row, column).map(|own| {
+                if method == "nearest" {
+                    return own;
+                }
+                let (r0, r1, fy) = axis_neighbours(y, rows);
+                let (c0, c1, fx) = axis_neighbours(x, columns);
+                // Cells without data drop out and the remaining weights are rescaled
+                let (sum, weight) = [
+                    (r0, c0, (1.0 - fy) * (1.0 - fx)),
+                    (r0, c1, (1.0 - fy) * fx),
+                    (r1, c0, fy * (1.0 - fx)),
+                    (r1, c1, fy * fx),
+                ]
+                .into_iter()
+                .filter_map(|(r, c, w)| cell(r, c).map(|v| (v * w, w)))
+                .fold((0.0, 0.0), |acc, (v, w)| (acc.0 + v, acc.1 + w));
+                if weight > 0.0 { sum / weight } else { own }
+            });
+            RasterSample {
+                lat: p.lat,
+                lon: p.lon,
+                value,
+                row: Some(row),
+                column: Some(column),
+            }
+        })
+        .collect();
+
+    let sampled_count = samples.iter().filter(|s| s.value.is_some()).count();
+    Ok(RasterSampleOutput {
+        missing_count: samples.len() - sampled_count,
+        sampled_count,
+        samples,
+        method,
+        rows,
+        columns,
+        bounds: RasterBounds {
+            north,
+            south,
+            west,
+            east,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn point(lat: f64, lon: f64) -> Coordinate {
+        Coordinate { lat, lon }
+    }
+
+    /// 2x3 grid of 1-degree cells with its north-west corner at (10, 20)
+    fn input(points: Vec<Coordinate>, method: &str) -> RasterSampleInput {
+        RasterSampleInput {
+            raster: Raster {
+                origin: point(10.0, 20.0),
+                cell_size: 1.0,
+                cell_size_lon: None,
+                values: vec![
+                    vec![Some(0.0), Some(10.0), Some(20.0)],
+                    vec![Some(100.0), Some(110.0), Some(120.0)],
+                ],
+                nodata: None,
+                row_order: None,
+            },
+            points,
+            method: Some(method.to_string()),
+        }
+    }
+
+    fn values(result: &RasterSampleOutput) -> Vec<Option<f64>> {
+        result.samples.iter().map(|s| s.value).collect()
+    }
+
+    #[test]
+    fn test_nearest() {
+        let result = raster_sample_logic(input(
+            vec![point(9.5, 20.5), point(8.2, 22.9), point(8.0, 23.0)],
+            "nearest",
+        ))
+        .unwrap();
+        assert_eq!(values(&result), vec![Some(0.0), Some(120.0), Some(120.0)]);
+        assert_eq!(result.samples[1].row, Some(1));
+        assert_eq!(result.samples[1].column, Some(2));
+        assert_eq!(result.bounds.south, 8.0);
+        assert_eq!(result.bounds.east, 23.0);
+    }
+
+    #[test]
+    fn test_bilinear() {
+        let result = raster_sample_logic(input(
+            vec![point(9.0, 21.0), point(9.25, 20.75), point(9.8, 20.2)],
+            "bilinear",
+        ))
+        .unwrap();
+        let v = values(&result);
+        // Midway between the four upper-left cell centres
+        assert!((v[0].unwrap() - 55.0).abs() < EPSILON);
+        // A quarter of the way across in each direction
+        assert!((v[1].unwrap() - 27.5).abs() < EPSILON);
+        // Inside the half cell beyond the corner centre, the corner value holds
+        assert!((v[2].unwrap() - 0.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_outside_and_nodata() {
+        let mut request = input(
+            vec![point(11.0, 20.5), point(9.5, 21.5), point(9.0, 21.0)],
+            "bilinear",
+        );
+        request.raster.values[0][1] = Some(-9999.0);
+        request.raster.nodata = Some(-9999.0);
+        let result = raster_sample_logic(request).unwrap();
+        let v = values(&result);
+        assert_eq!(v[0], None);
+        assert_eq!(result.samples[0].row, None);
+        assert_eq!(v[1], None);
+        // The no-data neighbour drops out of the blend
+        assert!((v[2].unwrap() - 70.0).abs() < EPSILON);
+        assert_eq!((result.sampled_count, result.missing_count), (1, 2));
+    }
+
+    #[test]
+    fn test_south_to_north_rows() {
+        let mut request = input(vec![point(8.5, 20.5), point(9.5, 20.5)], "nearest");
+        request.raster.origin = point(8.0, 20.0);
+        request.raster.row_order = Some("south_to_north".to_string());
+        let result = raster_sample_logic(request).unwrap();
+        assert_eq!(values(&result), vec![Some(0.0), Some(100.0)]);
+        assert_eq!(result.bounds.north, 10.0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut request = input(vec![point(9.5, 20.5)], "cubic");
+        assert_eq!(
+            raster_sample_logic(request.clone()).unwrap_err(),
+            "Unknown method 'cubic', use 'nearest' or 'bilinear'"
+        );
+        request.method = None;
+        request.raster.values[1].pop();
+        assert_eq!(
+            raster_sample_logic(request.clone()).unwrap_err(),
+            "Raster row 1 has 2 values, expected 3"
+        );
+        request.raster.values[1].push(Some(0.0));
+        request.raster.cell_size = 0.0;
+        assert!(raster_sample_logic(request.clone()).is_err());
+        request.raster.cell_size = 60.0;
+        assert!(raster_sample_logic(request).is_err());
+    }
+}
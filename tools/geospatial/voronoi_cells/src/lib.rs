@@ -0,0 +1,82 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{VoronoiInput as LogicInput, VoronoiOutput as LogicOutput, voronoi_cells_logic};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    BoundsSchema as Bounds, Point2DSchema as Point2D, VoronoiCellSchema as VoronoiCell,
+    VoronoiOutputSchema as VoronoiOutput,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VoronoiInput {
+    #[serde(flatten)]
+    pub params: logic::VoronoiInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Split a box around planar or longitude/latitude points into the region nearest each
+/// point, with cell polygons, areas and which cells border each other
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn voronoi_cells(request: Request<VoronoiInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("voronoi_cells", VoronoiInput, VoronoiOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("voronoi_cells", &input);
+    log.finish(run_voronoi_cells(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_voronoi_cells(input: VoronoiInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::voronoi_cells_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = VoronoiOutput::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,389 @@
+use delaunay_triangulate_tool::{Frame, check_points, triangulate};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Planar point, or with geographic input a longitude (x) and latitude (y) in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+pub struct Point2D {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Axis-aligned box in input units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToolTypes)]
+pub struct Bounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct VoronoiInput {
+    /// Sites whose cells are computed
+    #[tool_types(nested)]
+    pub points: Vec<Point2D>,
+    /// Read x as longitude and y as latitude in degrees and report areas in square meters,
+    /// from a local projection suited to regions up to a few hundred kilometers across
+    /// (default: false)
+    pub geographic: Option<bool>,
+    /// Box the cells are clipped to (default: the points' bounding box grown by 10% of
+    /// its larger side on every edge)
+    #[tool_types(nested)]
+    pub bounds: Option<Bounds>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct VoronoiCell {
+    /// Index of the site in the input points
+    pub site: usize,
+    /// Corners of the cell, counter-clockwise; empty for repeated sites and sites outside
+    /// the bounds
+    #[tool_types(nested)]
+    pub polygon: Vec<Point2D>,
+    pub area: f64,
+    /// Sites whose cells share an edge with this one, ascending
+    pub neighbors: Vec<usize>,
+    /// Whether the bounds cut the cell, so its true extent is larger or unbounded
+    pub clipped: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct VoronoiOutput {
+    /// One cell per input point, in input order
+    #[tool_types(nested)]
+    pub cells: Vec<VoronoiCell>,
+    /// Box the cells were clipped to
+    #[tool_types(nested)]
+    pub bounds: Bounds,
+    /// Sum of the cell areas, the area of the bounds when every site lies inside them
+    pub total_area: f64,
+    /// Points given an empty cell because they repeat an earlier point
+    pub duplicate_points: Vec<usize>,
+    pub geographic: bool,
+}
+
+/// Convex polygon corners, each with the site whose bisector forms the edge leaving it
+/// (None for an edge of the bounds)
+type LabeledPolygon = Vec<((f64, f64), Option<usize>)>;
+
+/// Keeps the part of the polygon closer to `site` than to `other`
+fn clip(
+    polygon: LabeledPolygon,
+    site: (f64, f64),
+    other: (f64, f64),
+    label: usize,
+) -> LabeledPolygon {
+    let (nx, ny) = (other.0 - site.0, other.1 - site.1);
+    let (mx, my) = ((site.0 + other.0) / 2.0, (site.1 + other.1) / 2.0);
+    let side = |p: (f64, f64)| (p.0 - mx) * nx + (p.1 - my) * ny;
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, (p, edge)) in polygon.iter().enumerate() {
+        let q = polygon[(i + 1) % polygon.len()].0;
+        let (sp, sq) = (side(*p), side(q));
+        let crossing = || {
+            let t = sp / (sp - sq);
+            (p.0 + (q.0 - p.0) * t, p.1 + (q.1 - p.1) * t)
+        };
+        match (sp <= 0.0, sq <= 0.0) {
+            (true, true) => clipped.push((*p, *edge)),
+            // Leaving the cell: the edge from the crossing follows the bisector
+            (true, false) => {
+                clipped.push((*p, *edge));
+                clipped.push((crossing(), Some(label)));
+            }
+            (false, true) => clipped.push((crossing(), *edge)),
+            (false, false) => {}
+        }
+    }
+    clipped
+}
+
+fn polygon_area(polygon: &LabeledPolygon) -> f64 {
+    (0..polygon.len())
+        .map(|i| {
+            let (a, b) = (polygon[i].0, polygon[(i + 1) % polygon.len()].0);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+pub fn voronoi_cells_logic(input: VoronoiInput) -> Result<VoronoiOutput, String> {
+    let geographic = input.geographic.unwrap_or(false);
+    if input.points.is_empty() {
+        return Err("At least one point is required".to_string());
+    }
+    let points: Vec<(f64, f64)> = input.points.iter().map(|p| (p.x, p.y)).collect();
+    check_points(&points, geographic)?;
+
+    let bounds = match input.bounds {
+        Some(bounds) => {
+            let values = [bounds.min_x, bounds.min_y, bounds.max_x, bounds.max_y];
+            if values.iter().any(|v| !v.is_finite()) {
+                return Err("Bounds must be finite".to_string());
+            }
+            if bounds.min_x >= bounds.max_x || bounds.min_y >= bounds.max_y {
+                return Err("Bounds minimums must be below their maximums".to_string());
+            }
+            bounds
+        }
+        None => {
+            let min_x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+            let min_y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+            let max_x = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+            let max_y = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+            let span = (max_x - min_x).max(max_y - min_y);
+            let margin = if span > 0.0 { span * 0.1 } else { 1.0 };
+            let bounds = Bounds {
+                min_x: min_x - margin,
+                min_y: min_y - margin,
+                max_x: max_x + margin,
+                max_y: max_y + margin,
+            };
+            if geographic {
+                // Keep the padded box on the globe
+                Bounds {
+                    min_x: bounds.min_x.max(-180.0),
+                    min_y: bounds.min_y.max(-90.0),
+                    max_x: bounds.max_x.min(180.0),
+                    max_y: bounds.max_y.min(90.0),
+                }
+            } else {
+                bounds
+            }
+        }
+    };
+
+    let frame = Frame::new(&points, geographic);
+    let projected: Vec<(f64, f64)> = points.iter().map(|p| frame.forward(*p)).collect();
+    let mesh = triangulate(&projected);
+    let unique: Vec<usize> = (0..points.len())
+        .filter(|i| mesh.duplicate_of[*i].is_none())
+        .collect();
+
+    // A Voronoi cell is bounded by the bisectors with its Delaunay neighbors; without a
+    // triangulation (fewer than three sites, or all on one line) clip against every site
+    let mut candidates: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    if mesh.triangles.is_empty() {
+        for i in &unique {
+            candidates[*i] = unique.iter().copied().filter(|j| j != i).collect();
+        }
+    } else {
+        for [a, b, c] in &mesh.triangles {
+            for (u, v) in [(*a, *b), (*b, *c), (*c, *a)] {
+                candidates[u].push(v);
+                candidates[v].push(u);
+            }
+        }
+        candidates.iter_mut().for_each(|c| {
+            c.sort_unstable();
+            c.dedup();
+        });
+    }
+
+    let (low_x, low_y) = frame.forward((bounds.min_x, bounds.min_y));
+    let (high_x, high_y) = frame.forward((bounds.max_x, bounds.max_y));
+    let box_polygon: LabeledPolygon = vec![
+        ((low_x, low_y), None),
+        ((high_x, low_y), None),
+        ((high_x, high_y), None),
+        ((low_x, high_y), None),
+    ];
+    let scale = (high_x - low_x).max(high_y - low_y);
+
+    let cells: Vec<VoronoiCell> = (0..points.len())
+        .map(|i| {
+            let site = projected[i];
+            let inside = (low_x..=high_x).contains(&site.0) && (low_y..=high_y).contains(&site.1);
+            if mesh.duplicate_of[i].is_some() || !inside {
+                return VoronoiCell {
+                    site: i,
+                    polygon: Vec::new(),
+                    area: 0.0,
+                    neighbors: Vec::new(),
+                    clipped: false,
+                };
+            }
+            let polygon = candidates[i]
+                .iter()
+                .fold(box_polygon.clone(), |polygon, j| {
+                    clip(polygon, site, projected[*j], *j)
+                });
+            // Edges shorter than this come from rounding where several bisectors meet
+            let min_edge = scale * 1e-9;
+            let polygon: LabeledPolygon = (0..polygon.len())
+                .filter(|k| {
+                    let (a, b) = (polygon[*k].0, polygon[(k + 1) % polygon.len()].0);
+                    (a.0 - b.0).hypot(a.1 - b.1) > min_edge
+                })
+                .map(|k| polygon[k])
+                .collect();
+            let mut neighbors: Vec<usize> = polygon.iter().filter_map(|(_, edge)| *edge).collect();
+            let clipped = polygon.iter().any(|(_, edge)| edge.is_none());
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            VoronoiCell {
+                site: i,
+                area: polygon_area(&polygon),
+                polygon: polygon
+                    .iter()
+                    .map(|(p, _)| {
+                        let (x, y) = frame.inverse(*p);
+                        Point2D { x, y }
+                    })
+                    .collect(),
+                neighbors,
+                clipped,
+            }
+        })
+        .collect();
+
+    Ok(VoronoiOutput {
+        total_area: cells.iter().map(|c| c.area).sum(),
+        cells,
+        bounds,
+        duplicate_points: (0..points.len())
+            .filter(|i| mesh.duplicate_of[*i].is_some())
+            .collect(),
+        geographic,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coordinates: &[(f64, f64)]) -> Vec<Point2D> {
+        coordinates
+            .iter()
+            .map(|(x, y)| Point2D { x: *x, y: *y })
+            .collect()
+    }
+
+    fn unit_bounds(size: f64) -> Option<Bounds> {
+        Some(Bounds {
+            min_x: 0.0,
+            min_y: 0.0,
+            max_x: size,
+            max_y: size,
+        })
+    }
+
+    #[test]
+    fn test_square_quadrants() {
+        let result = voronoi_cells_logic(VoronoiInput {
+            points: points(&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]),
+            geographic: None,
+            bounds: unit_bounds(4.0),
+        })
+        .unwrap();
+        for cell in &result.cells {
+            assert!((cell.area - 4.0).abs() < 1e-9);
+            assert!(cell.clipped);
+            assert_eq!(cell.polygon.len(), 4);
+        }
+        assert_eq!(result.cells[0].neighbors, vec![1, 3]);
+        assert!((result.total_area - 16.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_interior_cell_is_unclipped() {
+        // The center site of a 3x3 grid owns the unit square around it
+        let coordinates: Vec<(f64, f64)> = (0..9)
+            .map(|i| ((i % 3) as f64 + 1.0, (i / 3) as f64 + 1.0))
+            .collect();
+        let result = voronoi_cells_logic(VoronoiInput {
+            points: points(&coordinates),
+            geographic: None,
+            bounds: unit_bounds(4.0),
+        })
+        .unwrap();
+        let center = &result.cells[4];
+        assert!(!center.clipped);
+        assert!((center.area - 1.0).abs() < 1e-9);
+        assert_eq!(center.neighbors, vec![1, 3, 5, 7]);
+        assert!((result.total_area - 16.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_collinear_and_single_sites() {
+        let result = voronoi_cells_logic(VoronoiInput {
+            points: points(&[(1.0, 2.0), (2.0, 2.0), (3.0, 2.0)]),
+            geographic: None,
+            bounds: unit_bounds(4.0),
+        })
+        .unwrap();
+        let areas: Vec<f64> = result.cells.iter().map(|c| c.area).collect();
+        assert!((areas[0] - 6.0).abs() < 1e-9 && (areas[1] - 4.0).abs() < 1e-9);
+        assert_eq!(result.cells[1].neighbors, vec![0, 2]);
+
+        // One site fills the default bounds, padded by 1 around it
+        let result = voronoi_cells_logic(VoronoiInput {
+            points: points(&[(5.0, 5.0)]),
+            geographic: None,
+            bounds: None,
+        })
+        .unwrap();
+        assert!((result.cells[0].area - 4.0).abs() < 1e-9);
+        assert_eq!(result.bounds.min_x, 4.0);
+    }
+
+    #[test]
+    fn test_duplicates_and_outside_sites() {
+        let result = voronoi_cells_logic(VoronoiInput {
+            points: points(&[(1.0, 1.0), (3.0, 3.0), (1.0, 1.0), (5.0, 1.0)]),
+            geographic: None,
+            bounds: unit_bounds(4.0),
+        })
+        .unwrap();
+        assert_eq!(result.duplicate_points, vec![2]);
+        assert!(result.cells[2].polygon.is_empty());
+        assert!(result.cells[3].polygon.is_empty());
+        // The outside site still takes its share of the box from its neighbors
+        assert!(result.total_area < 16.0);
+    }
+
+    #[test]
+    fn test_geographic_cells() {
+        let result = voronoi_cells_logic(VoronoiInput {
+            points: points(&[(0.0025, 0.005), (0.0075, 0.005)]),
+            geographic: Some(true),
+            bounds: Some(Bounds {
+                min_x: 0.0,
+                min_y: 0.0,
+                max_x: 0.01,
+                max_y: 0.01,
+            }),
+        })
+        .unwrap();
+        let side = 6378137.0 * 0.01f64.to_radians();
+        assert!((result.total_area - side * side).abs() / (side * side) < 1e-3);
+        let xs: Vec<f64> = result.cells[0].polygon.iter().map(|p| p.x).collect();
+        assert!(xs.iter().any(|x| (x - 0.005).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let empty = voronoi_cells_logic(VoronoiInput {
+            points: Vec::new(),
+            geographic: None,
+            bounds: None,
+        });
+        assert_eq!(empty.unwrap_err(), "At least one point is required");
+        let inverted = voronoi_cells_logic(VoronoiInput {
+            points: points(&[(1.0, 1.0)]),
+            geographic: None,
+            bounds: Some(Bounds {
+                min_x: 2.0,
+                min_y: 0.0,
+                max_x: 1.0,
+                max_y: 1.0,
+            }),
+        });
+        assert_eq!(
+            inverted.unwrap_err(),
+            "Bounds minimums must be below their maximums"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    IdFormatInput as LogicInput, IdFormatOutput as LogicOutput, ParsedId as LogicParsedId,
+    format_ids,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IdFormatInput {
+    /// Identifier template, like "INV-{YYYY}-{seq:06}-{luhn}". Placeholders: {seq} or
+    /// {seq:N} (zero-padded to N digits), {YYYY}, {YY}, {MM}, {DD}, check digits {luhn},
+    /// {verhoeff}, {damm} or {mod97} computed over the digits before them (letters too
+    /// for mod97), and custom {name} fields; write {{ and }} for literal braces
+    pub template: String,
+    /// "format" to render identifiers or "parse" to split them into components
+    /// (default: format)
+    pub mode: Option<String>,
+    /// First sequence number to render (default: 1)
+    pub sequence: Option<u64>,
+    /// Consecutive identifiers to render (default: 1, max: 10000)
+    pub count: Option<usize>,
+    /// Date for {YYYY}, {YY}, {MM} and {DD}, as YYYY-MM-DD
+    pub date: Option<String>,
+    /// Values for custom {name} placeholders, letters and digits only
+    pub fields: Option<BTreeMap<String, String>>,
+    /// Identifiers to parse (parse mode, max: 10000)
+    pub ids: Option<Vec<String>>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IdFormatOutput {
+    /// Mode used
+    pub mode: String,
+    /// Template used
+    pub template: String,
+    /// Rendered identifiers (format mode)
+    pub ids: Vec<String>,
+    /// Components of each identifier (parse mode)
+    pub parsed: Vec<ParsedId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ParsedId {
+    /// Identifier as given
+    pub id: String,
+    /// Whether the identifier matches the template, with a real date and correct check
+    /// digits
+    pub valid: bool,
+    /// Sequence number from {seq}
+    pub sequence: Option<u64>,
+    /// Year from {YYYY}, or 2000 plus {YY}
+    pub year: Option<i32>,
+    /// Month from {MM}
+    pub month: Option<u32>,
+    /// Day from {DD}
+    pub day: Option<u32>,
+    /// Values of custom placeholders
+    pub fields: BTreeMap<String, String>,
+    /// Check digits found, by scheme
+    pub check_digits: BTreeMap<String, String>,
+    /// Why the identifier is invalid
+    pub error: Option<String>,
+}
+
+impl From<LogicParsedId> for ParsedId {
+    fn from(parsed: LogicParsedId) -> Self {
+        ParsedId {
+            id: parsed.id,
+            valid: parsed.valid,
+            sequence: parsed.sequence,
+            year: parsed.year,
+            month: parsed.month,
+            day: parsed.day,
+            fields: parsed.fields,
+            check_digits: parsed.check_digits,
+            error: parsed.error,
+        }
+    }
+}
+
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn id_format(request: Request<IdFormatInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("id_format", IdFormatInput, IdFormatOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("id_format", &input);
+    log.finish(run_id_format(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_id_format(input: IdFormatInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("template", &input.template)
+        .array("ids", input.ids.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        template: input.template,
+        mode: input.mode,
+        sequence: input.sequence,
+        count: input.count,
+        date: input.date,
+        fields: input.fields,
+        ids: input.ids,
+    };
+
+    // Call logic implementation
+    let result = match logic::format_ids(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = IdFormatOutput {
+        mode: result.mode,
+        template: result.template,
+        ids: result.ids,
+        parsed: result.parsed.into_iter().map(ParsedId::from).collect(),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
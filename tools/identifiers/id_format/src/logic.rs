@@ -0,0 +1,521 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Most identifiers rendered or parsed in one request
+pub const MAX_IDS: usize = 10_000;
+/// Widest zero padding accepted for {seq}
+const MAX_WIDTH: usize = 20;
+
+/// Check digit schemes usable as template tokens
+const CHECK_SCHEMES: &[&str] = &["luhn", "verhoeff", "damm", "mod97"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdFormatInput {
+    /// Identifier template, like "INV-{YYYY}-{seq:06}-{luhn}"
+    pub template: String,
+    /// "format" to render identifiers or "parse" to split them into components
+    /// (default: format)
+    pub mode: Option<String>,
+    /// First sequence number to render (default: 1)
+    pub sequence: Option<u64>,
+    /// Consecutive identifiers to render (default: 1)
+    pub count: Option<usize>,
+    /// Date for {YYYY}, {YY}, {MM} and {DD}, as YYYY-MM-DD
+    pub date: Option<String>,
+    /// Values for custom {name} placeholders, letters and digits only
+    pub fields: Option<BTreeMap<String, String>>,
+    /// Identifiers to parse
+    pub ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdFormatOutput {
+    pub mode: String,
+    pub template: String,
+    /// Rendered identifiers (format mode)
+    pub ids: Vec<String>,
+    /// Components of each identifier (parse mode)
+    pub parsed: Vec<ParsedId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParsedId {
+    pub id: String,
+    /// Whether the identifier matches the template, with a real date and correct check
+    /// digits
+    pub valid: bool,
+    pub sequence: Option<u64>,
+    /// Year from {YYYY}, or 2000 plus {YY}
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    /// Values of custom placeholders
+    pub fields: BTreeMap<String, String>,
+    /// Check digits found, by scheme
+    pub check_digits: BTreeMap<String, String>,
+    /// Why the identifier is invalid
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    Year4,
+    Year2,
+    Month,
+    Day,
+    /// Sequence number, zero-padded to the width when given
+    Seq(usize),
+    /// Check digit over the letters and digits rendered before it
+    Check(&'static str),
+    Field(String),
+}
+
+fn parse_template(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("Unmatched '}' in template; write '}}' for a literal brace".into()),
+            '{' => {
+                let mut body = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => body.push(c),
+                        None => return Err("Unclosed '{' in template".to_string()),
+                    }
+                }
+                if !literal.is_empty() {
+                    tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+                tokens.push(placeholder(&body)?);
+            }
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+    if tokens.iter().filter(|t| matches!(t, Token::Seq(_))).count() > 1 {
+        return Err("Template can hold {seq} only once".to_string());
+    }
+    Ok(tokens)
+}
+
+fn placeholder(body: &str) -> Result<Token, String> {
+    let (name, spec) = match body.split_once(':') {
+        Some((name, spec)) => (name.trim(), Some(spec.trim())),
+        None => (body.trim(), None),
+    };
+    if let Some(spec) = spec {
+        if name != "seq" {
+            return Err(format!("Only {{seq}} takes a width, found {{{body}}}"));
+        }
+        let width = spec
+            .parse::<usize>()
+            .ok()
+            .filter(|w| (1..=MAX_WIDTH).contains(w))
+            .ok_or_else(|| format!("Width in {{{body}}} must be between 1 and {MAX_WIDTH}"))?;
+        return Ok(Token::Seq(width));
+    }
+    Ok(match name {
+        "YYYY" => Token::Year4,
+        "YY" => Token::Year2,
+        "MM" => Token::Month,
+        "DD" => Token::Day,
+        "seq" => Token::Seq(0),
+        _ => match CHECK_SCHEMES.iter().find(|&&s| s == name) {
+            Some(scheme) => Token::Check(scheme),
+            None if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+                Token::Field(name.to_string())
+            }
+            None => return Err(format!("Invalid placeholder {{{body}}}")),
+        },
+    })
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn parse_date(date: &str) -> Result<(i32, u32, u32), String> {
+    let invalid = || format!("Invalid date '{date}', use YYYY-MM-DD");
+    let parts: Vec<&str> = date.trim().split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return Err(invalid());
+    }
+    let year: i32 = year.parse().map_err(|_| invalid())?;
+    let month: u32 = month.parse().map_err(|_| invalid())?;
+    let day: u32 = day.parse().map_err(|_| invalid())?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return Err(format!("Date '{date}' does not exist"));
+    }
+    Ok((year, month, day))
+}
+
+/// Check digit for the letters and digits before it; only mod97 counts letters
+fn check_digit(scheme: &str, rendered: &str) -> Result<String, String> {
+    let payload: String = rendered
+        .chars()
+        .filter(|c| c.is_ascii_digit() || (scheme == "mod97" && c.is_ascii_alphabetic()))
+        .collect();
+    if payload.is_empty() {
+        return Err(format!(
+            "{{{scheme}}} needs digits before it in the template"
+        ));
+    }
+    let output = check_digit_tool::check_digit_logic(check_digit_tool::LogicInput {
+        scheme: scheme.to_string(),
+        mode: Some("generate".to_string()),
+        value: Some(payload),
+        values: None,
+    })?;
+    let result = output
+        .results
+        .into_iter()
+        .next()
+        .ok_or("No check digit was computed")?;
+    result
+        .check_digit
+        .ok_or_else(|| result.error.unwrap_or_default())
+}
+
+fn render(
+    tokens: &[Token],
+    sequence: u64,
+    date: Option<(i32, u32, u32)>,
+    fields: &BTreeMap<String, String>,
+) -> Result<String, String> {
+    let mut out = String::new();
+    let need_date = || date.ok_or("Template uses date placeholders; give date as YYYY-MM-DD");
+    for token in tokens {
+        match token {
+            Token::Literal(text) => out.push_str(text),
+            Token::Year4 => out.push_str(&format!("{:04}", need_date()?.0)),
+            Token::Year2 => out.push_str(&format!("{:02}", need_date()?.0.rem_euclid(100))),
+            Token::Month => out.push_str(&format!("{:02}", need_date()?.1)),
+            Token::Day => out.push_str(&format!("{:02}", need_date()?.2)),
+            Token::Seq(width) => out.push_str(&format!("{sequence:0width$}")),
+            Token::Check(scheme) => {
+                let digit = check_digit(scheme, &out)?;
+                out.push_str(&digit);
+            }
+            Token::Field(name) => {
+                let value = fields
+                    .get(name)
+                    .ok_or_else(|| format!("No value given for {{{name}}}"))?;
+                out.push_str(value);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Candidate lengths for a token at the start of rest, longest first
+fn candidates(token: &Token, rest: &[char]) -> Vec<usize> {
+    let run = |accept: fn(&char) -> bool| rest.iter().take_while(|c| accept(c)).count();
+    let exact = |n: usize, accept: fn(&char) -> bool| {
+        if run(accept) >= n { vec![n] } else { vec![] }
+    };
+    match token {
+        Token::Literal(text) => {
+            let text: Vec<char> = text.chars().collect();
+            if rest.starts_with(&text) {
+                vec![text.len()]
+            } else {
+                vec![]
+            }
+        }
+        Token::Year4 => exact(4, char::is_ascii_digit),
+        Token::Year2 | Token::Month | Token::Day => exact(2, char::is_ascii_digit),
+        Token::Seq(width) => (((*width).max(1))..=run(char::is_ascii_digit))
+            .rev()
+            .collect(),
+        Token::Check(scheme) => exact(if *scheme == "mod97" { 2 } else { 1 }, char::is_ascii_digit),
+        Token::Field(_) => (1..=run(|c| c.is_alphanumeric())).rev().collect(),
+    }
+}
+
+/// Splits the identifier into one piece per token, backtracking over ambiguous lengths
+fn split(tokens: &[Token], rest: &[char], pieces: &mut Vec<String>) -> bool {
+    let Some(token) = tokens.first() else {
+        return rest.is_empty();
+    };
+    for len in candidates(token, rest) {
+        pieces.push(rest[..len].iter().collect());
+        if split(&tokens[1..], &rest[len..], pieces) {
+            return true;
+        }
+        pieces.pop();
+    }
+    false
+}
+
+fn parse_id(tokens: &[Token], id: &str) -> ParsedId {
+    let mut parsed = ParsedId {
+        id: id.to_string(),
+        valid: false,
+        sequence: None,
+        year: None,
+        month: None,
+        day: None,
+        fields: BTreeMap::new(),
+        check_digits: BTreeMap::new(),
+        error: None,
+    };
+    let chars: Vec<char> = id.trim().chars().collect();
+    let mut pieces = Vec::with_capacity(tokens.len());
+    if !split(tokens, &chars, &mut pieces) {
+        parsed.error = Some("Identifier does not match the template".to_string());
+        return parsed;
+    }
+
+    let mut errors = Vec::new();
+    let mut rendered = String::new();
+    for (token, piece) in tokens.iter().zip(&pieces) {
+        match token {
+            Token::Year4 => parsed.year = piece.parse().ok(),
+            Token::Year2 => parsed.year = piece.parse::<i32>().ok().map(|y| 2000 + y),
+            Token::Month => parsed.month = piece.parse().ok(),
+            Token::Day => parsed.day = piece.parse().ok(),
+            Token::Seq(_) => match piece.parse() {
+                Ok(sequence) => parsed.sequence = Some(sequence),
+                Err(_) => errors.push("Sequence number is too large".to_string()),
+            },
+            Token::Check(scheme) => {
+                match check_digit(scheme, &rendered) {
+                    Ok(expected) if &expected == piece => {}
+                    Ok(expected) => errors.push(format!(
+                        "{scheme} check digit is {piece}, expected {expected}"
+                    )),
+                    Err(e) => errors.push(e),
+                }
+                parsed
+                    .check_digits
+                    .insert(scheme.to_string(), piece.clone());
+            }
+            Token::Field(name) => {
+                parsed.fields.insert(name.clone(), piece.clone());
+            }
+            Token::Literal(_) => {}
+        }
+        rendered.push_str(piece);
+    }
+
+    if let Some(month) = parsed.month {
+        let year = parsed.year.unwrap_or(2000);
+        let day_ok = parsed
+            .day
+            .is_none_or(|day| day >= 1 && day <= days_in_month(year, month.clamp(1, 12)));
+        if !(1..=12).contains(&month) || !day_ok {
+            errors.push("Date in identifier does not exist".to_string());
+        }
+    } else if parsed.day.is_some_and(|day| !(1..=31).contains(&day)) {
+        errors.push("Date in identifier does not exist".to_string());
+    }
+
+    parsed.valid = errors.is_empty();
+    parsed.error = (!errors.is_empty()).then(|| errors.join("; "));
+    parsed
+}
+
+pub fn format_ids(input: IdFormatInput) -> Result<IdFormatOutput, String> {
+    let tokens = parse_template(&input.template)?;
+    let parse = match input
+        .mode
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("format") => false,
+        Some("parse") => true,
+        Some(other) => {
+            return Err(format!("Unknown mode '{other}', use 'format' or 'parse'"));
+        }
+    };
+
+    let mut output = IdFormatOutput {
+        mode: if parse { "parse" } else { "format" }.to_string(),
+        template: input.template,
+        ids: Vec::new(),
+        parsed: Vec::new(),
+    };
+
+    if parse {
+        let ids = input.ids.ok_or("Give ids to parse")?;
+        if ids.len() > MAX_IDS {
+            return Err(format!(
+                "At most {MAX_IDS} identifiers can be parsed at once"
+            ));
+        }
+        output.parsed = ids.iter().map(|id| parse_id(&tokens, id)).collect();
+        return Ok(output);
+    }
+
+    let count = input.count.unwrap_or(1);
+    if count == 0 || count > MAX_IDS {
+        return Err(format!("count must be between 1 and {MAX_IDS}"));
+    }
+    let start = input.sequence.unwrap_or(1);
+    if start.checked_add(count as u64 - 1).is_none() {
+        return Err("Sequence numbers would overflow".to_string());
+    }
+    let date = input.date.as_deref().map(parse_date).transpose()?;
+    let fields = input.fields.unwrap_or_default();
+    if let Some((name, _)) = fields
+        .iter()
+        .find(|(_, v)| v.is_empty() || !v.chars().all(char::is_alphanumeric))
+    {
+        return Err(format!(
+            "Value for {{{name}}} must be non-empty letters and digits"
+        ));
+    }
+    output.ids = (0..count as u64)
+        .map(|offset| render(&tokens, start + offset, date, &fields))
+        .collect::<Result<_, _>>()?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(template: &str) -> IdFormatInput {
+        IdFormatInput {
+            template: template.to_string(),
+            mode: None,
+            sequence: None,
+            count: None,
+            date: None,
+            fields: None,
+            ids: None,
+        }
+    }
+
+    fn parse(template: &str, ids: &[&str]) -> Vec<ParsedId> {
+        let mut input = request(template);
+        input.mode = Some("parse".to_string());
+        input.ids = Some(ids.iter().map(|id| id.to_string()).collect());
+        format_ids(input).unwrap().parsed
+    }
+
+    #[test]
+    fn test_format_invoice_numbers() {
+        let mut input = request("INV-{YYYY}-{seq:06}-{luhn}");
+        input.sequence = Some(42);
+        input.count = Some(3);
+        input.date = Some("2024-03-15".to_string());
+        let output = format_ids(input).unwrap();
+        assert_eq!(
+            output.ids,
+            [
+                "INV-2024-000042-0",
+                "INV-2024-000043-8",
+                "INV-2024-000044-6"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let parsed = parse(
+            "INV-{YYYY}-{seq:06}-{luhn}",
+            &["INV-2024-000043-8", "INV-2024-000043-9", "INV-2024-43"],
+        );
+        assert!(parsed[0].valid);
+        assert_eq!(parsed[0].sequence, Some(43));
+        assert_eq!(parsed[0].year, Some(2024));
+        assert_eq!(parsed[0].check_digits["luhn"], "8");
+
+        assert!(!parsed[1].valid);
+        assert_eq!(
+            parsed[1].error.as_deref(),
+            Some("luhn check digit is 9, expected 8")
+        );
+        assert_eq!(
+            parsed[2].error.as_deref(),
+            Some("Identifier does not match the template")
+        );
+    }
+
+    #[test]
+    fn test_fields_escapes_and_mod97() {
+        let template = "{{{branch}}}/{YY}{MM}{DD}/{seq}{mod97}";
+        let mut input = request(template);
+        input.sequence = Some(7);
+        input.date = Some("2024-03-15".to_string());
+        input.fields = Some(BTreeMap::from([("branch".to_string(), "NYC".to_string())]));
+        let output = format_ids(input).unwrap();
+        assert_eq!(output.ids, ["{NYC}/240315/705"]);
+
+        let parsed = parse(template, &["{NYC}/240315/705", "{LA}/241315/12345"]);
+        assert!(parsed[0].valid);
+        assert_eq!(parsed[0].fields["branch"], "NYC");
+        assert_eq!(
+            (parsed[0].year, parsed[0].month, parsed[0].day),
+            (Some(2024), Some(3), Some(15))
+        );
+        assert_eq!(parsed[0].sequence, Some(7));
+        assert!(parsed[1].error.as_ref().unwrap().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_parse_backtracks_over_digits() {
+        // An unpadded sequence runs straight into its check digit
+        let parsed = parse("{seq}{damm}", &["5724", "5725"]);
+        assert!(parsed[0].valid);
+        assert_eq!(parsed[0].sequence, Some(572));
+        assert_eq!(parsed[0].check_digits["damm"], "4");
+        assert!(!parsed[1].valid);
+
+        // Sequences outgrow their padding
+        let mut input = request("A{seq:2}");
+        input.sequence = Some(99);
+        input.count = Some(2);
+        assert_eq!(format_ids(input).unwrap().ids, ["A99", "A100"]);
+        assert_eq!(parse("A{seq:2}", &["A100"])[0].sequence, Some(100));
+    }
+
+    #[test]
+    fn test_invalid_templates_and_input() {
+        let err = |input: IdFormatInput| format_ids(input).unwrap_err();
+        assert_eq!(err(request("INV-{seq")), "Unclosed '{' in template");
+        assert!(err(request("A}")).contains("Unmatched '}'"));
+        assert!(err(request("{seq}-{seq}")).contains("only once"));
+        assert!(err(request("{YYYY:4}")).contains("Only {seq} takes a width"));
+        assert!(err(request("{seq:0}")).contains("between 1 and 20"));
+        assert!(err(request("{luhn}")).contains("needs digits before it"));
+        assert!(err(request("{YYYY}-{seq}")).contains("give date"));
+        assert!(err(request("{region}-{seq}")).contains("No value given for {region}"));
+
+        let mut input = request("{YYYY}{seq}");
+        input.date = Some("2023-02-29".to_string());
+        assert_eq!(err(input), "Date '2023-02-29' does not exist");
+
+        let mut input = request("{seq}");
+        input.mode = Some("parse".to_string());
+        assert_eq!(err(input), "Give ids to parse");
+    }
+}
@@ -3,13 +3,19 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
-    RandomIntegerInput as LogicInput, RandomIntegerOutput as LogicOutput, RandomRange as LogicRange,
+    RandomIntegerInput as LogicInput, RandomIntegerOutput as LogicOutput,
+    RandomRange as LogicRange, generate_random_integers,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -21,6 +27,8 @@ pub struct RandomIntegerInput {
     pub max: Option<i64>,
     /// Number of random integers to generate (default: 1, max: 100)
     pub count: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -37,8 +45,27 @@ pub struct RandomRange {
     pub max: i64,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn random_integer(input: RandomIntegerInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn random_integer(request: Request<RandomIntegerInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "random_integer",
+                RandomIntegerInput,
+                RandomIntegerOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("random_integer", &input);
+    log.finish(run_random_integer(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_random_integer(input: RandomIntegerInput) -> ToolResponse {
     // Convert to logic types
     let logic_input = LogicInput {
         min: input.min,
@@ -62,7 +89,7 @@ pub fn random_integer(input: RandomIntegerInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
@@ -3,14 +3,22 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
-    RandomStringInput as LogicInput, RandomStringOutput as LogicOutput, StringConfig as LogicConfig,
+    RandomStringInput as LogicInput, RandomStringOutput as LogicOutput,
+    StringConfig as LogicConfig, generate_random_strings,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -23,6 +31,8 @@ pub struct RandomStringInput {
     pub charset: Option<String>,
     /// Number of random strings to generate (default: 1, max: 100)
     pub count: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -40,8 +50,35 @@ pub struct StringConfig {
     pub charset_size: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn random_string(input: RandomStringInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn random_string(request: Request<RandomStringInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "random_string",
+                RandomStringInput,
+                RandomStringOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("random_string", &input);
+    log.finish(run_random_string(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_random_string(input: RandomStringInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("charset", input.charset.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         length: input.length,
@@ -66,7 +103,7 @@ pub fn random_string(input: RandomStringInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
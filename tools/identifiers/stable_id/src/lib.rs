@@ -0,0 +1,147 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    StableId as LogicStableId, StableIdInput as LogicInput, StableIdOutput as LogicOutput,
+    derive_ids,
+};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StableIdInput {
+    /// Value to derive an identifier from
+    pub input: Option<String>,
+    /// Values to derive identifiers from, in one batch (max: 10000)
+    pub inputs: Option<Vec<String>>,
+    /// Namespace mixed into the hash, so the same value gets different identifiers in
+    /// different namespaces (optional)
+    pub namespace: Option<String>,
+    /// Secret key; when given, identifiers use HMAC-SHA256 instead of SHA-256 so they
+    /// cannot be recomputed without the key (optional)
+    pub key: Option<String>,
+    /// Identifier encoding (default: "base32")
+    /// Options: "hex", "base32" (lowercase RFC 4648, no padding), "base58" (Bitcoin
+    /// alphabet), "base64url" (no padding)
+    pub encoding: Option<String>,
+    /// Identifier length in characters, before the prefix (default: enough characters
+    /// for 128 bits; max: 64 hex, 51 base32, 43 base58 or base64url)
+    pub length: Option<usize>,
+    /// Text put in front of every identifier, like "usr_" (optional, max 32 bytes)
+    pub prefix: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StableIdOutput {
+    /// Identifiers in input order
+    pub ids: Vec<StableId>,
+    /// Hash used: "sha256" or "hmac-sha256"
+    pub algorithm: String,
+    /// Encoding used
+    pub encoding: String,
+    /// Identifier length in characters, before the prefix
+    pub length: usize,
+    /// Bits of the hash kept in each identifier
+    pub entropy_bits: f64,
+    /// Chance that any two of this many distinct inputs share an identifier
+    pub collision_probability: f64,
+    /// Identifiers shared by different inputs in this batch
+    pub collisions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StableId {
+    /// Input value
+    pub input: String,
+    /// Identifier derived from it, including the prefix
+    pub id: String,
+}
+
+impl From<LogicStableId> for StableId {
+    fn from(id: LogicStableId) -> Self {
+        StableId {
+            input: id.input,
+            id: id.id,
+        }
+    }
+}
+
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn stable_id(request: Request<StableIdInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("stable_id", StableIdInput, StableIdOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("stable_id", &input);
+    log.finish(run_stable_id(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_stable_id(input: StableIdInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    let guard = Guard::from_env()
+        .string("input", input.input.as_deref().unwrap_or_default())
+        .array("inputs", input.inputs.as_deref().unwrap_or_default());
+    let guard = input
+        .inputs
+        .iter()
+        .flatten()
+        .fold(guard, |guard, text| guard.string("inputs", text));
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        input: input.input,
+        inputs: input.inputs,
+        namespace: input.namespace,
+        key: input.key,
+        encoding: input.encoding,
+        length: input.length,
+        prefix: input.prefix,
+    };
+
+    // Call logic implementation
+    let result = match logic::derive_ids(logic_input) {
+        Ok(r) => r,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let output = StableIdOutput {
+        ids: result.ids.into_iter().map(StableId::from).collect(),
+        algorithm: result.algorithm,
+        encoding: result.encoding,
+        length: result.length,
+        entropy_bits: result.entropy_bits,
+        collision_probability: result.collision_probability,
+        collisions: result.collisions,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string_pretty(&output)
+            .unwrap_or_else(|_| "Error serializing output".to_string()),
+    )
+}
@@ -0,0 +1,393 @@
+use base64::{Engine as _, engine::general_purpose};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Most inputs accepted in one request
+pub const MAX_INPUTS: usize = 10_000;
+/// Longest accepted prefix, in bytes
+const MAX_PREFIX_LEN: usize = 32;
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StableIdInput {
+    /// Value to derive an identifier from
+    pub input: Option<String>,
+    /// Values to derive identifiers from, in one batch
+    pub inputs: Option<Vec<String>>,
+    /// Namespace mixed into the hash, so the same value gets different identifiers in
+    /// different namespaces (optional)
+    pub namespace: Option<String>,
+    /// Secret key; when given, identifiers use HMAC-SHA256 instead of SHA-256 so they
+    /// cannot be recomputed without the key (optional)
+    pub key: Option<String>,
+    /// Identifier encoding (optional, default: "base32")
+    /// Options: "hex", "base32" (lowercase RFC 4648, no padding), "base58" (Bitcoin
+    /// alphabet), "base64url" (no padding)
+    pub encoding: Option<String>,
+    /// Identifier length in characters, before the prefix (optional, default: enough
+    /// characters for 128 bits)
+    pub length: Option<usize>,
+    /// Text put in front of every identifier, like "usr_" (optional)
+    pub prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StableIdOutput {
+    /// Identifiers in input order
+    pub ids: Vec<StableId>,
+    /// Hash used: "sha256" or "hmac-sha256"
+    pub algorithm: String,
+    pub encoding: String,
+    /// Identifier length in characters, before the prefix
+    pub length: usize,
+    /// Bits of the hash kept in each identifier
+    pub entropy_bits: f64,
+    /// Chance that any two of this many distinct inputs share an identifier
+    pub collision_probability: f64,
+    /// Identifiers shared by different inputs in this batch
+    pub collisions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StableId {
+    pub input: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Encoding {
+    Hex,
+    Base32,
+    Base58,
+    Base64Url,
+}
+
+impl Encoding {
+    fn parse(value: Option<&str>) -> Result<Encoding, String> {
+        match value.map(|v| v.trim().to_lowercase()).as_deref() {
+            None | Some("base32") => Ok(Encoding::Base32),
+            Some("hex") => Ok(Encoding::Hex),
+            Some("base58") => Ok(Encoding::Base58),
+            Some("base64url") => Ok(Encoding::Base64Url),
+            Some(other) => Err(format!(
+                "Unsupported encoding: {other}. Use 'hex', 'base32', 'base58' or 'base64url'"
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Encoding::Hex => "hex",
+            Encoding::Base32 => "base32",
+            Encoding::Base58 => "base58",
+            Encoding::Base64Url => "base64url",
+        }
+    }
+
+    fn bits_per_char(self) -> f64 {
+        match self {
+            Encoding::Hex => 4.0,
+            Encoding::Base32 => 5.0,
+            Encoding::Base58 => 58f64.log2(),
+            Encoding::Base64Url => 6.0,
+        }
+    }
+
+    /// Longest identifier whose characters all come from the 256-bit digest
+    fn max_length(self) -> usize {
+        (256.0 / self.bits_per_char()).floor() as usize
+    }
+
+    /// Characters needed for 128 bits
+    fn default_length(self) -> usize {
+        (128.0 / self.bits_per_char()).ceil() as usize
+    }
+
+    fn encode(self, digest: &[u8], length: usize) -> String {
+        match self {
+            Encoding::Hex => hex::encode(digest)[..length].to_string(),
+            Encoding::Base32 => base32(digest)[..length].to_string(),
+            Encoding::Base64Url => {
+                general_purpose::URL_SAFE_NO_PAD.encode(digest)[..length].to_string()
+            }
+            // Base58 treats the digest as one number whose leading digit is skewed, so
+            // keep the trailing, evenly spread digits
+            Encoding::Base58 => {
+                let encoded = base58(digest);
+                encoded[encoded.len() - length..].to_string()
+            }
+        }
+    }
+}
+
+/// Lowercase RFC 4648 base32 without padding
+fn base32(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+/// Base58 (Bitcoin alphabet) of the bytes as a big-endian number, padded with the zero
+/// digit to the width of the largest value of that many bytes
+fn base58(bytes: &[u8]) -> String {
+    let width = (bytes.len() as f64 * 8.0 / 58f64.log2()).ceil() as usize;
+    // Little-endian base-58 digits
+    let mut digits: Vec<u8> = Vec::with_capacity(width);
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    digits.resize(width.max(digits.len()), 0);
+    digits
+        .iter()
+        .rev()
+        .map(|&d| BASE58_ALPHABET[d as usize] as char)
+        .collect()
+}
+
+fn digest(message: &[u8], key: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    match key {
+        Some(key) => {
+            let mut mac =
+                HmacSha256::new_from_slice(key).map_err(|e| format!("Invalid HMAC key: {e}"))?;
+            mac.update(message);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+        None => Ok(Sha256::digest(message).to_vec()),
+    }
+}
+
+pub fn derive_ids(input: StableIdInput) -> Result<StableIdOutput, String> {
+    let values = match (input.input, input.inputs) {
+        (Some(value), None) => vec![value],
+        (None, Some(values)) => values,
+        (Some(_), Some(_)) => return Err("Give either input or inputs, not both".to_string()),
+        (None, None) => return Err("Give an input or a list of inputs".to_string()),
+    };
+    if values.len() > MAX_INPUTS {
+        return Err(format!("At most {MAX_INPUTS} inputs are supported"));
+    }
+    let encoding = Encoding::parse(input.encoding.as_deref())?;
+    let length = input.length.unwrap_or(encoding.default_length());
+    if length == 0 || length > encoding.max_length() {
+        return Err(format!(
+            "length must be between 1 and {} for {} identifiers",
+            encoding.max_length(),
+            encoding.name()
+        ));
+    }
+    let prefix = input.prefix.unwrap_or_default();
+    if prefix.len() > MAX_PREFIX_LEN {
+        return Err(format!("prefix must be at most {MAX_PREFIX_LEN} bytes"));
+    }
+    let key = input.key.as_deref().map(str::as_bytes);
+    if key.is_some_and(|k| k.is_empty()) {
+        return Err("key must not be empty".to_string());
+    }
+
+    let mut ids = Vec::with_capacity(values.len());
+    // Distinct inputs behind each identifier, to report truncation collisions
+    let mut seen: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for value in &values {
+        // A zero byte separates the namespace so ("ab", "c") and ("a", "bc") differ
+        let mut message = Vec::new();
+        if let Some(namespace) = &input.namespace {
+            message.extend_from_slice(namespace.as_bytes());
+            message.push(0);
+        }
+        message.extend_from_slice(value.as_bytes());
+
+        let id = format!(
+            "{prefix}{}",
+            encoding.encode(&digest(&message, key)?, length)
+        );
+        let sources = seen.entry(id.clone()).or_default();
+        if !sources.contains(&value.as_str()) {
+            sources.push(value);
+        }
+        ids.push(StableId {
+            input: value.clone(),
+            id,
+        });
+    }
+
+    let entropy_bits = length as f64 * encoding.bits_per_char();
+    let distinct = seen.values().map(Vec::len).sum::<usize>() as f64;
+    // Birthday bound: 1 - exp(-n(n-1) / 2^(bits+1))
+    let collision_probability =
+        -(-(distinct * (distinct - 1.0)) / 2f64.powf(entropy_bits + 1.0)).exp_m1();
+
+    Ok(StableIdOutput {
+        ids,
+        algorithm: if key.is_some() {
+            "hmac-sha256"
+        } else {
+            "sha256"
+        }
+        .to_string(),
+        encoding: encoding.name().to_string(),
+        length,
+        entropy_bits,
+        collision_probability,
+        collisions: seen
+            .into_iter()
+            .filter(|(_, sources)| sources.len() > 1)
+            .map(|(id, _)| id)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(value: &str) -> StableIdInput {
+        StableIdInput {
+            input: Some(value.to_string()),
+            inputs: None,
+            namespace: None,
+            key: None,
+            encoding: None,
+            length: None,
+            prefix: None,
+        }
+    }
+
+    fn id(request: StableIdInput) -> String {
+        derive_ids(request).unwrap().ids.remove(0).id
+    }
+
+    #[test]
+    fn test_sha256_encodings() {
+        // SHA-256("abc") = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+        let mut hex = request("abc");
+        hex.encoding = Some("hex".to_string());
+        hex.length = Some(64);
+        assert_eq!(
+            id(hex),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        let result = derive_ids(request("abc")).unwrap();
+        assert_eq!(result.algorithm, "sha256");
+        assert_eq!(result.encoding, "base32");
+        assert_eq!(result.length, 26);
+        assert_eq!(result.entropy_bits, 130.0);
+        assert_eq!(result.ids[0].id, "xj4bnp4pahh6uqkbidpf3lrceo");
+
+        let mut url = request("abc");
+        url.encoding = Some("base64url".to_string());
+        assert_eq!(id(url), "ungWv48Bz-pBQUDeXa4iI7");
+    }
+
+    #[test]
+    fn test_encoders() {
+        assert_eq!(base32(b"foobar"), "mzxw6ytboi");
+        assert_eq!(base32(b"f"), "my");
+        assert_eq!(base58(b"hello world"), "1StV1DL6CwTryKyV");
+        assert_eq!(base58(&[0, 0, 1]), "11112");
+
+        let mut b58 = request("abc");
+        b58.encoding = Some("base58".to_string());
+        let result = derive_ids(b58).unwrap();
+        assert_eq!(result.length, 22);
+        let id = &result.ids[0].id;
+        assert_eq!(id.len(), 22);
+        assert!(base58(&Sha256::digest(b"abc")).ends_with(id.as_str()));
+    }
+
+    #[test]
+    fn test_hmac_and_namespace() {
+        // RFC 4231 test case 2
+        let mut keyed = request("what do ya want for nothing?");
+        keyed.key = Some("Jefe".to_string());
+        keyed.encoding = Some("hex".to_string());
+        keyed.length = Some(64);
+        let result = derive_ids(keyed).unwrap();
+        assert_eq!(result.algorithm, "hmac-sha256");
+        assert_eq!(
+            result.ids[0].id,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let plain = id(request("user-42"));
+        let mut spaced = request("user-42");
+        spaced.namespace = Some("orders".to_string());
+        let namespaced = id(spaced.clone());
+        assert_ne!(plain, namespaced);
+        // Same inputs give the same identifier every time
+        assert_eq!(namespaced, id(spaced));
+    }
+
+    #[test]
+    fn test_batch_prefix_and_collisions() {
+        let values: Vec<String> = (0..200).map(|i| format!("item-{i}")).collect();
+        let mut batch = request("");
+        batch.input = None;
+        batch.inputs = Some(
+            values
+                .iter()
+                .cloned()
+                .chain(["item-0".to_string()])
+                .collect(),
+        );
+        batch.encoding = Some("hex".to_string());
+        batch.length = Some(2);
+        batch.prefix = Some("itm_".to_string());
+        let result = derive_ids(batch).unwrap();
+        assert_eq!(result.ids.len(), 201);
+        assert!(
+            result
+                .ids
+                .iter()
+                .all(|i| i.id.starts_with("itm_") && i.id.len() == 6)
+        );
+        // The repeated input matches its first identifier without counting as a collision
+        assert_eq!(result.ids[0].id, result.ids[200].id);
+        // 200 values in 256 buckets must collide
+        assert!(!result.collisions.is_empty());
+        assert!(result.collision_probability > 0.99);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |mut r: StableIdInput, f: fn(&mut StableIdInput)| {
+            f(&mut r);
+            derive_ids(r).unwrap_err()
+        };
+        assert!(err(request("a"), |r| r.input = None).contains("Give an input"));
+        assert!(err(request("a"), |r| r.inputs = Some(vec![])).contains("not both"));
+        assert!(err(request("a"), |r| r.encoding = Some("base36".into())).contains("base36"));
+        assert_eq!(
+            err(request("a"), |r| r.length = Some(53)),
+            "length must be between 1 and 51 for base32 identifiers"
+        );
+        assert!(err(request("a"), |r| r.key = Some(String::new())).contains("key"));
+        assert!(err(request("a"), |r| r.prefix = Some("x".repeat(40))).contains("prefix"));
+    }
+}
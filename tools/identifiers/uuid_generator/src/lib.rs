@@ -3,13 +3,20 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{UuidGeneratorInput as LogicInput, UuidGeneratorOutput as LogicOutput};
+pub use logic::{
+    UuidGeneratorInput as LogicInput, UuidGeneratorOutput as LogicOutput, generate_uuids,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -19,6 +26,8 @@ pub struct UuidGeneratorInput {
     /// Format for the UUIDs (default: "hyphenated")
     /// Options: "hyphenated", "simple", "urn", "braced"
     pub format: Option<String>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -31,8 +40,27 @@ pub struct UuidGeneratorOutput {
     pub format: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn uuid_generator(input: UuidGeneratorInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn uuid_generator(request: Request<UuidGeneratorInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "uuid_generator",
+                UuidGeneratorInput,
+                UuidGeneratorOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("uuid_generator", &input);
+    log.finish(run_uuid_generator(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_uuid_generator(input: UuidGeneratorInput) -> ToolResponse {
     // Convert to logic types
     let logic_input = LogicInput {
         count: input.count,
@@ -53,7 +81,7 @@ pub fn uuid_generator(input: UuidGeneratorInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
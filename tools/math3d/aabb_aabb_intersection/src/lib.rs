@@ -0,0 +1,75 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{AabbPairInput as LogicInput, AabbPairResult as LogicOutput, aabb_pair_logic};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    AabbPairResultSchema as AabbPairResult, AabbSchema as Aabb, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AabbPairInput {
+    #[serde(flatten)]
+    pub params: logic::AabbPairInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Compare two axis-aligned bounding boxes: whether they overlap, touch or contain one
+/// another, with their intersection box, enclosing box and the gap between them
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn aabb_aabb_intersection(request: Request<AabbPairInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "aabb_aabb_intersection",
+                AabbPairInput,
+                AabbPairResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("aabb_aabb_intersection", &input);
+    log.finish(run_aabb_aabb_intersection(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_aabb_aabb_intersection(input: AabbPairInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match logic::aabb_pair_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = AabbPairResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
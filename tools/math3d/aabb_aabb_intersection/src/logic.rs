@@ -0,0 +1,311 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Aabb {
+    /// Corner with the smallest coordinates
+    #[tool_types(nested)]
+    pub min: Vector3D,
+    /// Corner with the largest coordinates
+    #[tool_types(nested)]
+    pub max: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct AabbPairInput {
+    /// First axis-aligned bounding box
+    #[tool_types(nested)]
+    pub aabb1: Aabb,
+    /// Second axis-aligned bounding box
+    #[tool_types(nested)]
+    pub aabb2: Aabb,
+    /// Gap below which faces count as touching (default: 0, exact comparison)
+    pub tolerance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct AabbPairResult {
+    /// Whether the boxes share any point, including touching faces, edges or corners
+    pub intersects: bool,
+    /// Whether the boxes only touch, sharing no volume
+    pub touching: bool,
+    /// Whether the first box contains the second one
+    pub aabb1_contains_aabb2: bool,
+    /// Whether the second box contains the first one
+    pub aabb2_contains_aabb1: bool,
+    /// Whether the boxes are the same
+    pub equal: bool,
+    /// Box shared by both (absent when they do not intersect)
+    #[tool_types(nested)]
+    pub intersection: Option<Aabb>,
+    /// Volume of the shared box, 0 when they do not intersect
+    pub intersection_volume: f64,
+    /// Smallest box enclosing both
+    #[tool_types(nested)]
+    pub union: Aabb,
+    /// Volume of the enclosing box
+    pub union_volume: f64,
+    /// Intersection volume over the volume covered by either box (intersection over
+    /// union); 0 when both boxes are flat
+    pub iou: f64,
+    /// Gap between the boxes along each axis, 0 where their extents overlap
+    #[tool_types(nested)]
+    pub separation: Vector3D,
+    /// Shortest distance between the boxes, 0 when they intersect
+    pub distance: f64,
+    pub volume1: f64,
+    pub volume2: f64,
+}
+
+type V = [f64; 3];
+
+fn to_array(v: &Vector3D) -> V {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(v: V) -> Vector3D {
+    Vector3D {
+        x: v[0],
+        y: v[1],
+        z: v[2],
+    }
+}
+
+fn volume(min: V, max: V) -> f64 {
+    (0..3)
+        .map(|axis| (max[axis] - min[axis]).max(0.0))
+        .product()
+}
+
+fn corners(aabb: &Aabb, name: &str) -> Result<(V, V), String> {
+    let (min, max) = (to_array(&aabb.min), to_array(&aabb.max));
+    if !min.iter().chain(&max).all(|c| c.is_finite()) {
+        return Err(format!("{name} coordinates must be finite"));
+    }
+    if (0..3).any(|axis| min[axis] > max[axis]) {
+        return Err(format!(
+            "{name} min coordinates must not exceed max coordinates"
+        ));
+    }
+    Ok((min, max))
+}
+
+/// Box between the given corners; where boxes within the tolerance do not quite meet,
+/// the axis collapses to the middle of the gap
+fn intersection_box(min: V, max: V) -> Aabb {
+    let (mut min, mut max) = (min, max);
+    for axis in 0..3 {
+        if min[axis] > max[axis] {
+            let middle = (min[axis] + max[axis]) / 2.0;
+            (min[axis], max[axis]) = (middle, middle);
+        }
+    }
+    Aabb {
+        min: to_vector(min),
+        max: to_vector(max),
+    }
+}
+
+pub fn aabb_pair_logic(input: AabbPairInput) -> Result<AabbPairResult, String> {
+    let (min1, max1) = corners(&input.aabb1, "AABB 1")?;
+    let (min2, max2) = corners(&input.aabb2, "AABB 2")?;
+    let tolerance = match input.tolerance {
+        Some(t) if !t.is_finite() || t < 0.0 => {
+            return Err("tolerance must be a non-negative number".to_string());
+        }
+        Some(t) => t,
+        None => 0.0,
+    };
+
+    let intersection_min: V = [0, 1, 2].map(|axis| min1[axis].max(min2[axis]));
+    let intersection_max: V = [0, 1, 2].map(|axis| max1[axis].min(max2[axis]));
+    // Negative where the extents overlap, positive where there is a gap
+    let gap: V = [0, 1, 2].map(|axis| intersection_min[axis] - intersection_max[axis]);
+
+    let intersects = gap.iter().all(|&g| g <= tolerance);
+    // Touching boxes meet at a face, edge or corner: along some axis one box ends where
+    // the other begins. A flat box lying inside another one does not touch it.
+    let touching = intersects
+        && (0..3).any(|axis| {
+            (max1[axis] - min2[axis]).abs() <= tolerance
+                || (max2[axis] - min1[axis]).abs() <= tolerance
+        });
+
+    let contains = |outer: (V, V), inner: (V, V)| {
+        (0..3).all(|axis| {
+            outer.0[axis] <= inner.0[axis] + tolerance && inner.1[axis] <= outer.1[axis] + tolerance
+        })
+    };
+    let aabb1_contains_aabb2 = contains((min1, max1), (min2, max2));
+    let aabb2_contains_aabb1 = contains((min2, max2), (min1, max1));
+
+    let (volume1, volume2) = (volume(min1, max1), volume(min2, max2));
+    let intersection_volume = if intersects {
+        volume(intersection_min, intersection_max)
+    } else {
+        0.0
+    };
+    let union_min: V = [0, 1, 2].map(|axis| min1[axis].min(min2[axis]));
+    let union_max: V = [0, 1, 2].map(|axis| max1[axis].max(max2[axis]));
+    let covered = volume1 + volume2 - intersection_volume;
+    let separation: V = gap.map(|g| g.max(0.0));
+
+    Ok(AabbPairResult {
+        intersects,
+        touching,
+        aabb1_contains_aabb2,
+        aabb2_contains_aabb1,
+        equal: aabb1_contains_aabb2 && aabb2_contains_aabb1,
+        intersection: intersects.then(|| intersection_box(intersection_min, intersection_max)),
+        intersection_volume,
+        union: Aabb {
+            min: to_vector(union_min),
+            max: to_vector(union_max),
+        },
+        union_volume: volume(union_min, union_max),
+        iou: if covered > 0.0 {
+            intersection_volume / covered
+        } else {
+            0.0
+        },
+        separation: to_vector(separation),
+        distance: separation.iter().map(|g| g * g).sum::<f64>().sqrt(),
+        volume1,
+        volume2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aabb(min: [f64; 3], max: [f64; 3]) -> Aabb {
+        Aabb {
+            min: to_vector(min),
+            max: to_vector(max),
+        }
+    }
+
+    fn pair(aabb1: Aabb, aabb2: Aabb) -> AabbPairResult {
+        aabb_pair_logic(AabbPairInput {
+            aabb1,
+            aabb2,
+            tolerance: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_overlapping_boxes() {
+        let result = pair(
+            aabb([0.0, 0.0, 0.0], [2.0, 2.0, 2.0]),
+            aabb([1.0, 1.0, 1.0], [3.0, 3.0, 3.0]),
+        );
+        assert!(result.intersects);
+        assert!(!result.touching);
+        assert!(!result.aabb1_contains_aabb2 && !result.aabb2_contains_aabb1);
+        assert_eq!(result.intersection, Some(aabb([1.0; 3], [2.0; 3])));
+        assert_eq!(result.intersection_volume, 1.0);
+        assert_eq!(result.union, aabb([0.0; 3], [3.0; 3]));
+        assert_eq!(result.union_volume, 27.0);
+        assert!((result.iou - 1.0 / 15.0).abs() < 1e-12);
+        assert_eq!(result.distance, 0.0);
+    }
+
+    #[test]
+    fn test_separated_boxes() {
+        let result = pair(
+            aabb([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]),
+            aabb([4.0, 5.0, 0.5], [6.0, 6.0, 2.0]),
+        );
+        assert!(!result.intersects);
+        assert!(!result.touching);
+        assert!(result.intersection.is_none());
+        assert_eq!(result.intersection_volume, 0.0);
+        assert_eq!(result.iou, 0.0);
+        assert_eq!(result.separation, to_vector([3.0, 4.0, 0.0]));
+        assert_eq!(result.distance, 5.0);
+        assert_eq!(result.union, aabb([0.0, 0.0, 0.0], [6.0, 6.0, 2.0]));
+    }
+
+    #[test]
+    fn test_touching_and_tolerance() {
+        let a = aabb([0.0; 3], [1.0; 3]);
+        let b = aabb([1.0, 0.0, 0.0], [2.0, 1.0, 1.0]);
+        let result = pair(a, b);
+        assert!(result.intersects && result.touching);
+        assert_eq!(
+            result.intersection,
+            Some(aabb([1.0, 0.0, 0.0], [1.0, 1.0, 1.0]))
+        );
+        assert_eq!(result.intersection_volume, 0.0);
+
+        // A small gap only closes within the tolerance
+        let c = aabb([1.001, 0.0, 0.0], [2.0, 1.0, 1.0]);
+        assert!(!pair(a, c).intersects);
+        let result = aabb_pair_logic(AabbPairInput {
+            aabb1: a,
+            aabb2: c,
+            tolerance: Some(0.01),
+        })
+        .unwrap();
+        assert!(result.intersects && result.touching);
+        assert_eq!(result.intersection.unwrap().min.x, 1.0005);
+    }
+
+    #[test]
+    fn test_containment() {
+        let outer = aabb([0.0; 3], [4.0; 3]);
+        let inner = aabb([1.0, 1.0, 2.0], [2.0, 3.0, 2.0]);
+        let result = pair(outer, inner);
+        assert!(result.aabb1_contains_aabb2);
+        assert!(!result.aabb2_contains_aabb1);
+        assert!(!result.equal);
+        // A flat box inside another is contained, not touching
+        assert!(!result.touching);
+        assert_eq!(result.intersection, Some(inner));
+        assert_eq!(result.volume2, 0.0);
+
+        let result = pair(outer, outer);
+        assert!(result.equal);
+        assert_eq!(result.iou, 1.0);
+    }
+
+    #[test]
+    fn test_invalid_boxes() {
+        let good = aabb([0.0; 3], [1.0; 3]);
+        let err = aabb_pair_logic(AabbPairInput {
+            aabb1: aabb([0.0, 2.0, 0.0], [1.0, 1.0, 1.0]),
+            aabb2: good,
+            tolerance: None,
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "AABB 1 min coordinates must not exceed max coordinates"
+        );
+
+        let err = aabb_pair_logic(AabbPairInput {
+            aabb1: good,
+            aabb2: aabb([0.0, f64::NAN, 0.0], [1.0; 3]),
+            tolerance: None,
+        })
+        .unwrap_err();
+        assert_eq!(err, "AABB 2 coordinates must be finite");
+
+        let err = aabb_pair_logic(AabbPairInput {
+            aabb1: good,
+            aabb2: good,
+            tolerance: Some(-1.0),
+        })
+        .unwrap_err();
+        assert!(err.contains("tolerance"));
+    }
+}
@@ -1,70 +1,83 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    BoundingBoxInput as LogicInput, BoundingBoxResponse as LogicOutput, Vector3D as LogicVector3D,
+    compute_aabb_volume,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{BoundingBoxResponseSchema as BoundingBoxResponse, Vector3DSchema as Vector3D};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BoundingBoxInput {
-    pub points: Vec<Vector3D>,
+    #[serde(flatten)]
+    pub params: logic::BoundingBoxInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct BoundingBoxResponse {
-    pub volume: f64,
-    pub box_type: String,
-    pub min_point: Vector3D,
-    pub max_point: Vector3D,
-    pub dimensions: Vector3D,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn aabb_volume(request: Request<BoundingBoxInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "aabb_volume",
+                BoundingBoxInput,
+                BoundingBoxResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("aabb_volume", &input);
+    log.finish(run_aabb_volume(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn aabb_volume(input: BoundingBoxInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::BoundingBoxInput {
-        points: input
-            .points
-            .into_iter()
-            .map(|p| logic::Vector3D {
-                x: p.x,
-                y: p.y,
-                z: p.z,
-            })
-            .collect(),
+#[cfg(feature = "individual")]
+fn run_aabb_volume(input: BoundingBoxInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_aabb_volume(logic_input) {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::compute_aabb_volume(input.params.into()) {
         Ok(logic_result) => {
-            let result = BoundingBoxResponse {
-                volume: logic_result.volume,
-                box_type: logic_result.box_type,
-                min_point: Vector3D {
-                    x: logic_result.min_point.x,
-                    y: logic_result.min_point.y,
-                    z: logic_result.min_point.z,
-                },
-                max_point: Vector3D {
-                    x: logic_result.max_point.x,
-                    y: logic_result.max_point.y,
-                    z: logic_result.max_point.z,
-                },
-                dimensions: Vector3D {
-                    x: logic_result.dimensions.x,
-                    y: logic_result.dimensions.y,
-                    z: logic_result.dimensions.z,
-                },
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = BoundingBoxResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
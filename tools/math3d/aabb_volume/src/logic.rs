@@ -1,23 +1,32 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct BoundingBoxInput {
+    #[tool_types(nested)]
     pub points: Vec<Vector3D>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct BoundingBoxResponse {
     pub volume: f64,
     pub box_type: String,
+    #[tool_types(nested)]
     pub min_point: Vector3D,
+    #[tool_types(nested)]
     pub max_point: Vector3D,
+    #[tool_types(nested)]
     pub dimensions: Vector3D,
 }
 
@@ -1,36 +1,84 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
+#[cfg(feature = "individual")]
+use ftl_units::AngleUnitResponse;
 use schemars::JsonSchema;
 
 mod logic;
-use logic::{ArbitraryRotationInput, arbitrary_rotation_logic};
+pub use logic::{ArbitraryRotationInput, arbitrary_rotation_logic};
 
-#[derive(serde::Deserialize, JsonSchema)]
+#[derive(serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct ToolInput {
     axis: logic::Vector3D,
     angle: f64,
+    /// Unit of `angle`: "radians" or "degrees" (default: radians)
+    angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(serde::Serialize, JsonSchema)]
 struct ToolOutput {
     /// The 3x3 rotation matrix representing the rotation
     matrix: logic::Matrix3x3,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn arbitrary_rotation(input: ToolInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn arbitrary_rotation(request: Request<ToolInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "arbitrary_rotation",
+                ToolInput,
+                AngleUnitResponse<ToolOutput>
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("arbitrary_rotation", &input);
+    log.finish(run_arbitrary_rotation(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_arbitrary_rotation(input: ToolInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
     let logic_input = ArbitraryRotationInput {
         axis: input.axis,
-        angle: input.angle,
+        angle: angle_unit.to_radians(input.angle),
     };
 
     match arbitrary_rotation_logic(logic_input) {
         Ok(output) => {
-            let result = ToolOutput {
+            let response = angle_unit.response(ToolOutput {
                 matrix: output.matrix,
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            });
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,13 +1,17 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D);
+
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Matrix3x3 {
     pub m00: f64,
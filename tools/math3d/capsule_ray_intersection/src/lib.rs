@@ -0,0 +1,79 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    CapsuleRayInput as LogicInput, CapsuleRayResult as LogicOutput, Vector3D as LogicVector3D,
+    capsule_ray_intersection_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    CapsuleRayResultSchema as CapsuleRayResult, CapsuleSchema as Capsule,
+    IntersectionPointSchema as IntersectionPoint, RaySchema as Ray, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CapsuleRayInput {
+    #[serde(flatten)]
+    pub params: logic::CapsuleRayInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Intersect a ray with a capsule (a segment swept by a radius, the usual character
+/// collision shape), returning hit points, distances and surface normals
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn capsule_ray_intersection(request: Request<CapsuleRayInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "capsule_ray_intersection",
+                CapsuleRayInput,
+                CapsuleRayResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("capsule_ray_intersection", &input);
+    log.finish(run_capsule_ray_intersection(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_capsule_ray_intersection(input: CapsuleRayInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match logic::capsule_ray_intersection_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = CapsuleRayResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
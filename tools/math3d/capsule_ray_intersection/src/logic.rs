@@ -0,0 +1,344 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Ray {
+    #[tool_types(nested)]
+    pub origin: Vector3D,
+    /// Direction of travel, normalized before use
+    #[tool_types(nested)]
+    pub direction: Vector3D,
+}
+
+/// Every point within radius of the segment from start to end: a cylinder with
+/// hemispherical caps
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Capsule {
+    /// Center of the first cap
+    #[tool_types(nested)]
+    pub start: Vector3D,
+    /// Center of the second cap; equal to start for a sphere
+    #[tool_types(nested)]
+    pub end: Vector3D,
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct CapsuleRayInput {
+    #[tool_types(nested)]
+    pub capsule: Capsule,
+    #[tool_types(nested)]
+    pub ray: Ray,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct IntersectionPoint {
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Distance along the normalized ray direction
+    pub distance: f64,
+    /// Outward unit surface normal at the point
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct CapsuleRayResult {
+    pub intersects: bool,
+    /// Entry then exit point, skipping any behind the origin; a ray starting inside the
+    /// capsule has only its exit
+    #[tool_types(nested)]
+    pub intersection_points: Vec<IntersectionPoint>,
+    pub closest_distance: Option<f64>,
+}
+
+impl Vector3D {
+    fn add(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    fn subtract(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+
+    fn scale(&self, scalar: f64) -> Vector3D {
+        Vector3D {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
+
+    fn dot(&self, other: &Vector3D) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+/// Ray parameters where the ray meets a sphere, nearest first
+fn sphere_hits(
+    origin: &Vector3D,
+    direction: &Vector3D,
+    center: &Vector3D,
+    radius: f64,
+) -> Vec<f64> {
+    let offset = origin.subtract(center);
+    let b = offset.dot(direction);
+    let c = offset.dot(&offset) - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return Vec::new();
+    }
+    let root = discriminant.sqrt();
+    vec![-b - root, -b + root]
+}
+
+pub fn capsule_ray_intersection_logic(input: CapsuleRayInput) -> Result<CapsuleRayResult, String> {
+    let capsule = input.capsule;
+    let ray = input.ray;
+
+    if !capsule.start.is_finite() || !capsule.end.is_finite() {
+        return Err("Capsule endpoint coordinates must be finite".to_string());
+    }
+    if !(capsule.radius.is_finite() && capsule.radius > 0.0) {
+        return Err("Capsule radius must be positive and finite".to_string());
+    }
+    if !ray.origin.is_finite() {
+        return Err("Ray origin coordinates must be finite".to_string());
+    }
+    if !ray.direction.is_finite() {
+        return Err("Ray direction coordinates must be finite".to_string());
+    }
+    let speed = ray.direction.magnitude();
+    if speed == 0.0 {
+        return Err("Ray direction cannot be zero vector".to_string());
+    }
+
+    let direction = ray.direction.scale(1.0 / speed);
+    let segment = capsule.end.subtract(&capsule.start);
+    let length = segment.magnitude();
+    let radius = capsule.radius;
+    // Position of a point along the segment, from 0 at start to length at end
+    let along = |p: &Vector3D| {
+        if length > 0.0 {
+            p.subtract(&capsule.start).dot(&segment) / length
+        } else {
+            0.0
+        }
+    };
+    let at = |t: f64| ray.origin.add(&direction.scale(t));
+
+    // The surface is the side of the cylinder around the segment plus the two caps, each
+    // counted only over the part of it that bounds the capsule
+    let mut hits: Vec<f64> = Vec::new();
+    if length > 0.0 {
+        let axis = segment.scale(1.0 / length);
+        let offset = ray.origin.subtract(&capsule.start);
+        let (d_axis, o_axis) = (direction.dot(&axis), offset.dot(&axis));
+        let a = 1.0 - d_axis * d_axis;
+        let b = offset.dot(&direction) - o_axis * d_axis;
+        let c = offset.dot(&offset) - o_axis * o_axis - radius * radius;
+        let discriminant = b * b - a * c;
+        // A ray parallel to the axis only meets the caps
+        if a > 1e-12 && discriminant >= 0.0 {
+            let root = discriminant.sqrt();
+            hits.extend(
+                [(-b - root) / a, (-b + root) / a]
+                    .into_iter()
+                    .filter(|t| (0.0..=length).contains(&along(&at(*t)))),
+            );
+        }
+    }
+    hits.extend(
+        sphere_hits(&ray.origin, &direction, &capsule.start, radius)
+            .into_iter()
+            .filter(|t| along(&at(*t)) <= 0.0),
+    );
+    hits.extend(
+        sphere_hits(&ray.origin, &direction, &capsule.end, radius)
+            .into_iter()
+            .filter(|t| along(&at(*t)) >= length),
+    );
+
+    if hits.is_empty() {
+        return Ok(CapsuleRayResult {
+            intersects: false,
+            intersection_points: vec![],
+            closest_distance: None,
+        });
+    }
+    // The capsule is convex, so the ray enters at the first crossing and leaves at the last
+    let entry = hits.iter().copied().fold(f64::INFINITY, f64::min);
+    let exit = hits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let intersection_points: Vec<IntersectionPoint> = [entry, exit]
+        .into_iter()
+        .filter(|t| *t >= 0.0)
+        .map(|t| {
+            let point = at(t);
+            // Outward from the nearest point of the segment
+            let s = along(&point).clamp(0.0, length);
+            let core = if length > 0.0 {
+                capsule.start.add(&segment.scale(s / length))
+            } else {
+                capsule.start
+            };
+            let outward = point.subtract(&core);
+            IntersectionPoint {
+                point,
+                distance: t,
+                normal: outward.scale(1.0 / outward.magnitude()),
+            }
+        })
+        .collect();
+
+    Ok(CapsuleRayResult {
+        intersects: !intersection_points.is_empty(),
+        closest_distance: intersection_points.first().map(|p| p.distance),
+        intersection_points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-10;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    /// Capsule along the z axis from z = 0 to z = 4 with radius 1
+    fn input(origin: Vector3D, direction: Vector3D) -> CapsuleRayInput {
+        CapsuleRayInput {
+            capsule: Capsule {
+                start: v(0.0, 0.0, 0.0),
+                end: v(0.0, 0.0, 4.0),
+                radius: 1.0,
+            },
+            ray: Ray { origin, direction },
+        }
+    }
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        assert!(a.subtract(&b).magnitude() < EPSILON, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_hits_cylindrical_side() {
+        let result =
+            capsule_ray_intersection_logic(input(v(-5.0, 0.0, 2.0), v(2.0, 0.0, 0.0))).unwrap();
+        assert!(result.intersects);
+        assert_eq!(result.intersection_points.len(), 2);
+        let entry = &result.intersection_points[0];
+        assert!((entry.distance - 4.0).abs() < EPSILON);
+        assert_close(entry.point, v(-1.0, 0.0, 2.0));
+        assert_close(entry.normal, v(-1.0, 0.0, 0.0));
+        assert!((result.intersection_points[1].distance - 6.0).abs() < EPSILON);
+        assert_eq!(result.closest_distance, Some(entry.distance));
+    }
+
+    #[test]
+    fn test_hits_caps_along_axis() {
+        let result =
+            capsule_ray_intersection_logic(input(v(0.0, 0.0, 10.0), v(0.0, 0.0, -1.0))).unwrap();
+        let points = &result.intersection_points;
+        // Top of the upper cap at z = 5, bottom of the lower cap at z = -1
+        assert!((points[0].distance - 5.0).abs() < EPSILON);
+        assert_close(points[0].normal, v(0.0, 0.0, 1.0));
+        assert!((points[1].distance - 11.0).abs() < EPSILON);
+        assert_close(points[1].normal, v(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_hits_cap_off_axis() {
+        // Passes below the lower end, where only the hemisphere is in the way
+        let result =
+            capsule_ray_intersection_logic(input(v(-5.0, 0.0, -0.6), v(1.0, 0.0, 0.0))).unwrap();
+        let entry = &result.intersection_points[0];
+        assert!((entry.point.x + 0.8).abs() < EPSILON);
+        assert_close(entry.normal, v(-0.8, 0.0, -0.6));
+
+        let miss =
+            capsule_ray_intersection_logic(input(v(-5.0, 0.0, -1.5), v(1.0, 0.0, 0.0))).unwrap();
+        assert!(!miss.intersects && miss.closest_distance.is_none());
+    }
+
+    #[test]
+    fn test_origin_inside_and_behind() {
+        let inside =
+            capsule_ray_intersection_logic(input(v(0.0, 0.0, 2.0), v(0.0, 1.0, 0.0))).unwrap();
+        assert_eq!(inside.intersection_points.len(), 1);
+        assert!((inside.closest_distance.unwrap() - 1.0).abs() < EPSILON);
+
+        let behind =
+            capsule_ray_intersection_logic(input(v(0.0, 5.0, 2.0), v(0.0, 1.0, 0.0))).unwrap();
+        assert!(!behind.intersects);
+    }
+
+    #[test]
+    fn test_degenerate_capsule_is_sphere() {
+        let mut request = input(v(0.0, -3.0, 0.0), v(0.0, 1.0, 0.0));
+        request.capsule.end = request.capsule.start;
+        let result = capsule_ray_intersection_logic(request).unwrap();
+        let distances: Vec<f64> = result
+            .intersection_points
+            .iter()
+            .map(|p| p.distance)
+            .collect();
+        assert_eq!(distances, vec![2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut request = input(v(0.0, 0.0, 0.0), v(0.0, 0.0, 0.0));
+        assert_eq!(
+            capsule_ray_intersection_logic(request.clone()).unwrap_err(),
+            "Ray direction cannot be zero vector"
+        );
+        request.ray.direction = v(1.0, 0.0, 0.0);
+        request.capsule.radius = 0.0;
+        assert!(capsule_ray_intersection_logic(request.clone()).is_err());
+        request.capsule.radius = 1.0;
+        request.capsule.end.y = f64::NAN;
+        assert!(capsule_ray_intersection_logic(request).is_err());
+    }
+
+    #[test]
+    fn test_accepts_array_vectors() {
+        let json = r#"{
+            "capsule": {"start": [0, 0, 0], "end": [0, 0, 4], "radius": 1},
+            "ray": {"origin": [-5, 0, 2], "direction": {"x": 1, "y": 0, "z": 0}}
+        }"#;
+        let request: CapsuleRayInputSchema = serde_json::from_str(json).unwrap();
+        let result = capsule_ray_intersection_logic(request.into()).unwrap();
+        assert_eq!(result.closest_distance, Some(4.0));
+    }
+}
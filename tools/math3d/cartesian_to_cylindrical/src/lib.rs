@@ -1,16 +1,24 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{CartesianCoordinates as LogicInput, cartesian_to_cylindrical_logic};
+#[cfg(feature = "individual")]
+use logic::CartesianCoordinates as LogicInput;
 
 // Re-export for testing
 pub use logic::{
     CartesianCoordinates as LogicCartesian, CartesianToCylindricalResult as LogicResult,
-    CylindricalCoordinates as LogicCylindrical,
+    CylindricalCoordinates as LogicCylindrical, cartesian_to_cylindrical_logic,
 };
 
 #[derive(Deserialize, Serialize, JsonSchema)]
@@ -23,11 +31,26 @@ pub struct CartesianCoordinates {
     pub z: f64,
 }
 
+// The coordinates to convert plus options that only affect how the result is serialized
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct ConversionInput {
+    #[serde(flatten)]
+    pub coordinates: CartesianCoordinates,
+    /// Unit for the returned theta: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
 #[derive(Serialize, JsonSchema)]
 pub struct CylindricalCoordinates {
     /// Distance from z-axis (ρ)
     pub radius: f64,
-    /// Azimuthal angle in radians (θ)
+    /// Azimuthal angle in `angle_unit` (θ)
     pub theta: f64,
     /// Height along z-axis
     pub z: f64,
@@ -41,20 +64,49 @@ pub struct CartesianToCylindricalResult {
     pub cylindrical_coordinates: CylindricalCoordinates,
     /// Conversion notes
     pub conversion_notes: String,
+    /// Unit of theta
+    pub angle_unit: String,
 }
 
 /// Convert Cartesian coordinates (x, y, z) to cylindrical coordinates (ρ, θ, z)
 ///
 /// Cylindrical coordinates represent a point using:
 /// - ρ (radius): distance from the z-axis
-/// - θ (theta): azimuthal angle in radians around the z-axis
+/// - θ (theta): azimuthal angle around the z-axis, in radians unless `angle_unit` says otherwise
 /// - z: height along the z-axis (unchanged from Cartesian)
-#[cfg_attr(not(test), tool)]
-pub fn cartesian_to_cylindrical(input: CartesianCoordinates) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn cartesian_to_cylindrical(request: Request<ConversionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "cartesian_to_cylindrical",
+                ConversionInput,
+                CartesianToCylindricalResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("cartesian_to_cylindrical", &input);
+    log.finish(run_cartesian_to_cylindrical(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_cartesian_to_cylindrical(input: ConversionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
     let logic_input = LogicInput {
-        x: input.x,
-        y: input.y,
-        z: input.z,
+        x: input.coordinates.x,
+        y: input.coordinates.y,
+        z: input.coordinates.z,
     };
 
     match cartesian_to_cylindrical_logic(logic_input) {
@@ -67,12 +119,17 @@ pub fn cartesian_to_cylindrical(input: CartesianCoordinates) -> ToolResponse {
                 },
                 cylindrical_coordinates: CylindricalCoordinates {
                     radius: logic_result.cylindrical_coordinates.radius,
-                    theta: logic_result.cylindrical_coordinates.theta,
+                    theta: angle_unit
+                        .value_from_radians(logic_result.cylindrical_coordinates.theta),
                     z: logic_result.cylindrical_coordinates.z,
                 },
                 conversion_notes: logic_result.conversion_notes,
+                angle_unit: angle_unit.name().to_string(),
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
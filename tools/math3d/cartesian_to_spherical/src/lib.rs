@@ -1,11 +1,18 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{CartesianToSphericalInput, cartesian_to_spherical_logic};
+pub use logic::{CartesianToSphericalInput, cartesian_to_spherical_logic};
 
 #[derive(Deserialize, Serialize, JsonSchema)]
 pub struct CartesianCoordinates {
@@ -17,13 +24,28 @@ pub struct CartesianCoordinates {
     pub z: f64,
 }
 
+// The coordinates to convert plus options that only affect how the result is serialized
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct ConversionInput {
+    #[serde(flatten)]
+    pub coordinates: CartesianCoordinates,
+    /// Unit for the returned theta and phi: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
 #[derive(Serialize, JsonSchema)]
 pub struct SphericalCoordinates {
     /// Radial distance (radius)
     pub radius: f64,
-    /// Polar angle (theta) in radians
+    /// Polar angle (theta) in `angle_unit`
     pub theta: f64,
-    /// Azimuthal angle (phi) in radians
+    /// Azimuthal angle (phi) in `angle_unit`
     pub phi: f64,
 }
 
@@ -35,16 +57,45 @@ pub struct CartesianToSphericalResult {
     pub spherical_coordinates: SphericalCoordinates,
     /// Conversion notes
     pub conversion_notes: String,
+    /// Unit of theta and phi
+    pub angle_unit: String,
 }
 
 /// Convert Cartesian coordinates (x, y, z) to spherical coordinates (r, theta, phi)
-#[cfg_attr(not(test), tool)]
-pub fn cartesian_to_spherical(input: CartesianCoordinates) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn cartesian_to_spherical(request: Request<ConversionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "cartesian_to_spherical",
+                ConversionInput,
+                CartesianToSphericalResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("cartesian_to_spherical", &input);
+    log.finish(run_cartesian_to_spherical(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_cartesian_to_spherical(input: ConversionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
     let logic_input = CartesianToSphericalInput {
         coordinates: logic::Vector3D {
-            x: input.x,
-            y: input.y,
-            z: input.z,
+            x: input.coordinates.x,
+            y: input.coordinates.y,
+            z: input.coordinates.z,
         },
     };
 
@@ -58,12 +109,16 @@ pub fn cartesian_to_spherical(input: CartesianCoordinates) -> ToolResponse {
                 },
                 spherical_coordinates: SphericalCoordinates {
                     radius: output.spherical_coordinates.radius,
-                    theta: output.spherical_coordinates.theta,
-                    phi: output.spherical_coordinates.phi,
+                    theta: angle_unit.value_from_radians(output.spherical_coordinates.theta),
+                    phi: angle_unit.value_from_radians(output.spherical_coordinates.phi),
                 },
                 conversion_notes: output.conversion_notes,
+                angle_unit: angle_unit.name().to_string(),
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -0,0 +1,445 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Twice-areas below this fraction of the squared longest edge count as collinear
+const DEGENERATE_TOLERANCE: f64 = 1e-12;
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Triangle {
+    #[tool_types(nested)]
+    pub a: Vector3D,
+    #[tool_types(nested)]
+    pub b: Vector3D,
+    #[tool_types(nested)]
+    pub c: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Segment {
+    #[tool_types(nested)]
+    pub start: Vector3D,
+    #[tool_types(nested)]
+    pub end: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ClosestPointInput {
+    /// Query point
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Triangle to search; give either triangle or segment
+    #[tool_types(nested)]
+    pub triangle: Option<Triangle>,
+    /// Line segment to search; give either triangle or segment
+    #[tool_types(nested)]
+    pub segment: Option<Segment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ClosestPointResult {
+    /// Shape searched: "triangle" or "segment"
+    pub primitive: String,
+    #[tool_types(nested)]
+    pub closest_point: Vector3D,
+    pub distance: f64,
+    /// Where the closest point lies: "vertex", "edge" or "face"
+    pub region: String,
+    /// The vertex, edge or face holding the closest point: "a", "b", "c", "ab", "bc",
+    /// "ca" or "abc" on a triangle; "start", "end" or "interior" on a segment
+    pub feature: String,
+    /// Weights of a, b and c giving the closest point (triangle only)
+    pub barycentric: Option<[f64; 3]>,
+    /// Fraction of the way from start to end, 0-1 (segment only)
+    pub parameter: Option<f64>,
+    /// Unit normal along (b − a) × (c − a) (triangle only)
+    #[tool_types(nested)]
+    pub normal: Option<Vector3D>,
+    /// Distance of the query point above the triangle's plane along the normal,
+    /// negative below it (triangle only)
+    pub signed_plane_distance: Option<f64>,
+}
+
+impl Vector3D {
+    fn add(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    fn subtract(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+
+    fn scale(&self, scalar: f64) -> Vector3D {
+        Vector3D {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
+
+    fn dot(&self, other: &Vector3D) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    fn cross(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+fn check_vector(name: &str, v: &Vector3D) -> Result<(), String> {
+    if !v.is_finite() {
+        return Err(format!("{name} coordinates must be finite"));
+    }
+    Ok(())
+}
+
+/// Closest point on the segment as (parameter, feature)
+fn closest_on_segment(point: &Vector3D, segment: &Segment) -> (f64, &'static str) {
+    let direction = segment.end.subtract(&segment.start);
+    let length_squared = direction.dot(&direction);
+    if length_squared == 0.0 {
+        return (0.0, "start");
+    }
+    let t = point.subtract(&segment.start).dot(&direction) / length_squared;
+    if t <= 0.0 {
+        (0.0, "start")
+    } else if t >= 1.0 {
+        (1.0, "end")
+    } else {
+        (t, "interior")
+    }
+}
+
+/// Barycentric weights of the closest point on the triangle and the feature holding it,
+/// by the Voronoi regions of its vertices and edges (Ericson, Real-Time Collision
+/// Detection, 5.1.5)
+fn closest_on_triangle(p: &Vector3D, t: &Triangle) -> ([f64; 3], &'static str) {
+    let ab = t.b.subtract(&t.a);
+    let ac = t.c.subtract(&t.a);
+    let ap = p.subtract(&t.a);
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return ([1.0, 0.0, 0.0], "a");
+    }
+
+    let bp = p.subtract(&t.b);
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return ([0.0, 1.0, 0.0], "b");
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return ([1.0 - v, v, 0.0], "ab");
+    }
+
+    let cp = p.subtract(&t.c);
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return ([0.0, 0.0, 1.0], "c");
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return ([1.0 - w, 0.0, w], "ca");
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return ([0.0, 1.0 - w, w], "bc");
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    ([1.0 - v - w, v, w], "abc")
+}
+
+pub fn closest_point_logic(input: ClosestPointInput) -> Result<ClosestPointResult, String> {
+    check_vector("Point", &input.point)?;
+    let point = input.point;
+
+    match (input.triangle, input.segment) {
+        (Some(_), Some(_)) => Err("Give either triangle or segment, not both".to_string()),
+        (None, None) => Err("Give a triangle or a segment to search".to_string()),
+        (None, Some(segment)) => {
+            check_vector("Segment start", &segment.start)?;
+            check_vector("Segment end", &segment.end)?;
+            let (t, feature) = closest_on_segment(&point, &segment);
+            let region = if feature == "interior" {
+                "edge"
+            } else {
+                "vertex"
+            };
+            let closest_point = segment
+                .start
+                .add(&segment.end.subtract(&segment.start).scale(t));
+            Ok(ClosestPointResult {
+                primitive: "segment".to_string(),
+                distance: point.subtract(&closest_point).magnitude(),
+                closest_point,
+                region: region.to_string(),
+                feature: feature.to_string(),
+                barycentric: None,
+                parameter: Some(t),
+                normal: None,
+                signed_plane_distance: None,
+            })
+        }
+        (Some(triangle), None) => {
+            check_vector("Triangle vertex a", &triangle.a)?;
+            check_vector("Triangle vertex b", &triangle.b)?;
+            check_vector("Triangle vertex c", &triangle.c)?;
+            let ab = triangle.b.subtract(&triangle.a);
+            let ac = triangle.c.subtract(&triangle.a);
+            let bc = triangle.c.subtract(&triangle.b);
+            let cross = ab.cross(&ac);
+            let longest_squared = ab.dot(&ab).max(ac.dot(&ac)).max(bc.dot(&bc));
+            if cross.magnitude() <= DEGENERATE_TOLERANCE * longest_squared || longest_squared == 0.0
+            {
+                return Err(
+                    "Triangle vertices are collinear; search it as a segment instead".to_string(),
+                );
+            }
+            let normal = cross.scale(1.0 / cross.magnitude());
+
+            let (barycentric, feature) = closest_on_triangle(&point, &triangle);
+            let [u, v, w] = barycentric;
+            let closest_point = triangle
+                .a
+                .scale(u)
+                .add(&triangle.b.scale(v))
+                .add(&triangle.c.scale(w));
+            let region = match feature.len() {
+                1 => "vertex",
+                2 => "edge",
+                _ => "face",
+            };
+            Ok(ClosestPointResult {
+                primitive: "triangle".to_string(),
+                distance: point.subtract(&closest_point).magnitude(),
+                closest_point,
+                region: region.to_string(),
+                feature: feature.to_string(),
+                barycentric: Some(barycentric),
+                parameter: None,
+                signed_plane_distance: Some(point.subtract(&triangle.a).dot(&normal)),
+                normal: Some(normal),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-12;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn triangle() -> Triangle {
+        Triangle {
+            a: v(0.0, 0.0, 0.0),
+            b: v(4.0, 0.0, 0.0),
+            c: v(0.0, 4.0, 0.0),
+        }
+    }
+
+    fn on_triangle(point: Vector3D) -> ClosestPointResult {
+        closest_point_logic(ClosestPointInput {
+            point,
+            triangle: Some(triangle()),
+            segment: None,
+        })
+        .unwrap()
+    }
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        assert!(
+            a.subtract(&b).magnitude() < EPSILON,
+            "expected {b:?}, got {a:?}"
+        );
+    }
+
+    #[test]
+    fn test_face_region() {
+        let result = on_triangle(v(1.0, 1.0, 3.0));
+        assert_eq!(result.region, "face");
+        assert_eq!(result.feature, "abc");
+        assert_close(result.closest_point, v(1.0, 1.0, 0.0));
+        assert!((result.distance - 3.0).abs() < EPSILON);
+        assert!((result.signed_plane_distance.unwrap() - 3.0).abs() < EPSILON);
+        let [u, v, w] = result.barycentric.unwrap();
+        assert!(
+            (u - 0.5).abs() < EPSILON && (v - 0.25).abs() < EPSILON && (w - 0.25).abs() < EPSILON
+        );
+    }
+
+    #[test]
+    fn test_vertex_regions() {
+        let result = on_triangle(v(-1.0, -2.0, -1.0));
+        assert_eq!(
+            (result.region.as_str(), result.feature.as_str()),
+            ("vertex", "a")
+        );
+        assert!((result.signed_plane_distance.unwrap() + 1.0).abs() < EPSILON);
+        let result = on_triangle(v(6.0, -1.0, 0.0));
+        assert_eq!(result.feature, "b");
+        assert_close(result.closest_point, v(4.0, 0.0, 0.0));
+        let result = on_triangle(v(0.0, 7.0, 2.0));
+        assert_eq!(result.feature, "c");
+        assert_eq!(result.barycentric, Some([0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_edge_regions() {
+        let result = on_triangle(v(2.0, -3.0, 0.0));
+        assert_eq!(
+            (result.region.as_str(), result.feature.as_str()),
+            ("edge", "ab")
+        );
+        assert_close(result.closest_point, v(2.0, 0.0, 0.0));
+        assert!((result.distance - 3.0).abs() < EPSILON);
+
+        let result = on_triangle(v(3.0, 3.0, 0.0));
+        assert_eq!(result.feature, "bc");
+        assert_close(result.closest_point, v(2.0, 2.0, 0.0));
+
+        let result = on_triangle(v(-2.0, 1.0, 1.0));
+        assert_eq!(result.feature, "ca");
+        assert_close(result.closest_point, v(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_matches_dense_sampling() {
+        // No sampled point of the triangle may be closer than the reported one
+        let t = Triangle {
+            a: v(1.0, -2.0, 0.5),
+            b: v(3.0, 1.0, -1.0),
+            c: v(-1.0, 2.0, 2.0),
+        };
+        for query in [
+            v(0.0, 0.0, 3.0),
+            v(5.0, 5.0, 5.0),
+            v(-3.0, -3.0, 0.0),
+            v(2.0, 0.0, -4.0),
+        ] {
+            let result = closest_point_logic(ClosestPointInput {
+                point: query,
+                triangle: Some(t.clone()),
+                segment: None,
+            })
+            .unwrap();
+            let steps = 200;
+            for i in 0..=steps {
+                for j in 0..=(steps - i) {
+                    let (v, w) = (i as f64 / steps as f64, j as f64 / steps as f64);
+                    let sample = t.a.scale(1.0 - v - w).add(&t.b.scale(v)).add(&t.c.scale(w));
+                    assert!(sample.subtract(&query).magnitude() >= result.distance - 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_segment() {
+        let segment = Segment {
+            start: v(0.0, 0.0, 0.0),
+            end: v(10.0, 0.0, 0.0),
+        };
+        let run = |point| {
+            closest_point_logic(ClosestPointInput {
+                point,
+                triangle: None,
+                segment: Some(segment.clone()),
+            })
+            .unwrap()
+        };
+        let result = run(v(2.5, 3.0, 4.0));
+        assert_eq!(
+            (result.region.as_str(), result.feature.as_str()),
+            ("edge", "interior")
+        );
+        assert!((result.parameter.unwrap() - 0.25).abs() < EPSILON);
+        assert!((result.distance - 5.0).abs() < EPSILON);
+        let result = run(v(-4.0, 3.0, 0.0));
+        assert_eq!(
+            (result.region.as_str(), result.feature.as_str()),
+            ("vertex", "start")
+        );
+        assert!((result.distance - 5.0).abs() < EPSILON);
+        let result = run(v(12.0, 0.0, 0.0));
+        assert_eq!(result.feature, "end");
+        assert_eq!(result.parameter, Some(1.0));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let collinear = Triangle {
+            a: v(0.0, 0.0, 0.0),
+            b: v(1.0, 1.0, 1.0),
+            c: v(2.0, 2.0, 2.0),
+        };
+        let error = closest_point_logic(ClosestPointInput {
+            point: v(0.0, 0.0, 0.0),
+            triangle: Some(collinear),
+            segment: None,
+        })
+        .unwrap_err();
+        assert!(error.contains("collinear"));
+        let neither = ClosestPointInput {
+            point: v(0.0, 0.0, 0.0),
+            triangle: None,
+            segment: None,
+        };
+        assert!(closest_point_logic(neither).is_err());
+        let nan = ClosestPointInput {
+            point: v(f64::NAN, 0.0, 0.0),
+            triangle: Some(triangle()),
+            segment: None,
+        };
+        assert_eq!(
+            closest_point_logic(nan).unwrap_err(),
+            "Point coordinates must be finite"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    ConeRayInput as LogicInput, ConeRayResult as LogicOutput, Vector3D as LogicVector3D,
+    cone_ray_intersection_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    ConeRayResultSchema as ConeRayResult, ConeSchema as Cone,
+    IntersectionPointSchema as IntersectionPoint, RaySchema as Ray, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConeRayInput {
+    #[serde(flatten)]
+    pub params: logic::ConeRayInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Intersect a ray with a solid finite cone given by apex, axis, height and base radius or
+/// half angle, returning hit points, distances and surface normals
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn cone_ray_intersection(request: Request<ConeRayInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "cone_ray_intersection",
+                ConeRayInput,
+                ConeRayResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("cone_ray_intersection", &input);
+    log.finish(run_cone_ray_intersection(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_cone_ray_intersection(input: ConeRayInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match logic::cone_ray_intersection_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = ConeRayResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
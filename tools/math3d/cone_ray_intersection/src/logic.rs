@@ -0,0 +1,337 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Ray {
+    #[tool_types(nested)]
+    pub origin: Vector3D,
+    /// Direction of travel, normalized before use
+    #[tool_types(nested)]
+    pub direction: Vector3D,
+}
+
+/// Solid finite cone, sized by base_radius or by half_angle_degrees
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Cone {
+    #[tool_types(nested)]
+    pub apex: Vector3D,
+    /// Direction from the apex toward the base; normalized before use
+    #[tool_types(nested)]
+    pub axis: Vector3D,
+    /// Distance from the apex to the base along the axis
+    pub height: f64,
+    /// Radius of the base
+    pub base_radius: Option<f64>,
+    /// Angle between the axis and the side, in degrees, between 0 and 90
+    pub half_angle_degrees: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ConeRayInput {
+    #[tool_types(nested)]
+    pub cone: Cone,
+    #[tool_types(nested)]
+    pub ray: Ray,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct IntersectionPoint {
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Distance along the normalized ray direction
+    pub distance: f64,
+    /// Outward unit surface normal at the point
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ConeRayResult {
+    pub intersects: bool,
+    /// Entry then exit point, skipping any behind the origin; a ray starting inside the
+    /// cone has only its exit
+    #[tool_types(nested)]
+    pub intersection_points: Vec<IntersectionPoint>,
+    pub closest_distance: Option<f64>,
+}
+
+impl Vector3D {
+    fn add(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    fn subtract(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+
+    fn scale(&self, scalar: f64) -> Vector3D {
+        Vector3D {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
+
+    fn dot(&self, other: &Vector3D) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+pub fn cone_ray_intersection_logic(input: ConeRayInput) -> Result<ConeRayResult, String> {
+    let cone = input.cone;
+    let ray = input.ray;
+
+    if !cone.apex.is_finite() {
+        return Err("Cone apex coordinates must be finite".to_string());
+    }
+    if !cone.axis.is_finite() || cone.axis.magnitude() == 0.0 {
+        return Err("Cone axis must be a finite, non-zero vector".to_string());
+    }
+    let height = cone.height;
+    if !(height.is_finite() && height > 0.0) {
+        return Err("Cone height must be positive and finite".to_string());
+    }
+    let radius = match (cone.base_radius, cone.half_angle_degrees) {
+        (Some(_), Some(_)) => {
+            return Err("Give either base_radius or half_angle_degrees, not both".to_string());
+        }
+        (None, None) => {
+            return Err("Cone needs base_radius or half_angle_degrees".to_string());
+        }
+        (Some(radius), None) if radius.is_finite() && radius > 0.0 => radius,
+        (Some(_), None) => return Err("Cone base_radius must be positive and finite".to_string()),
+        (None, Some(angle)) if angle > 0.0 && angle < 90.0 => height * angle.to_radians().tan(),
+        (None, Some(_)) => {
+            return Err("Cone half_angle_degrees must be between 0 and 90".to_string());
+        }
+    };
+    if !ray.origin.is_finite() {
+        return Err("Ray origin coordinates must be finite".to_string());
+    }
+    if !ray.direction.is_finite() {
+        return Err("Ray direction coordinates must be finite".to_string());
+    }
+    let speed = ray.direction.magnitude();
+    if speed == 0.0 {
+        return Err("Ray direction cannot be zero vector".to_string());
+    }
+
+    let axis = cone.axis.scale(1.0 / cone.axis.magnitude());
+    let direction = ray.direction.scale(1.0 / speed);
+    let slant = radius.hypot(height);
+    let (cos, sin) = (height / slant, radius / slant);
+    let offset = ray.origin.subtract(&cone.apex);
+    let at = |t: f64| offset.add(&direction.scale(t));
+    // Height of a point above the apex along the axis, allowing for rounding at the ends
+    let within = |p: &Vector3D| {
+        let h = p.dot(&axis);
+        h >= -1e-12 * height && h <= height * (1.0 + 1e-12)
+    };
+
+    // The side lies on the double cone (p.axis)^2 = cos^2 |p|^2, cut to the nappe
+    // between the apex and the base
+    let mut hits: Vec<f64> = Vec::new();
+    let (d_axis, o_axis) = (direction.dot(&axis), offset.dot(&axis));
+    let a = d_axis * d_axis - cos * cos;
+    let b = d_axis * o_axis - cos * cos * direction.dot(&offset);
+    let c = o_axis * o_axis - cos * cos * offset.dot(&offset);
+    if a.abs() > 1e-12 {
+        let discriminant = b * b - a * c;
+        // A ray through the apex touches both nappes there, a double root that rounding
+        // can push slightly negative
+        if discriminant >= -1e-12 * (b * b).max((a * c).abs()) {
+            let root = discriminant.max(0.0).sqrt();
+            hits.extend([(-b - root) / a, (-b + root) / a]);
+        }
+    } else if b != 0.0 {
+        // Ray parallel to a line of the side meets the double cone once
+        hits.push(-c / (2.0 * b));
+    }
+    hits.retain(|t| within(&at(*t)));
+    // The base disk
+    if d_axis != 0.0 {
+        let t = (height - o_axis) / d_axis;
+        let p = at(t);
+        let rim = p.subtract(&axis.scale(height));
+        if rim.dot(&rim) <= radius * radius * (1.0 + 1e-12) {
+            hits.push(t);
+        }
+    }
+
+    if hits.is_empty() {
+        return Ok(ConeRayResult {
+            intersects: false,
+            intersection_points: vec![],
+            closest_distance: None,
+        });
+    }
+    // The cone is convex, so the ray enters at the first crossing and leaves at the last
+    let entry = hits.iter().copied().fold(f64::INFINITY, f64::min);
+    let exit = hits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let intersection_points: Vec<IntersectionPoint> = [entry, exit]
+        .into_iter()
+        .filter(|t| *t >= 0.0)
+        .map(|t| {
+            let local = at(t);
+            let h = local.dot(&axis);
+            let radial = local.subtract(&axis.scale(h));
+            let radial_length = radial.magnitude();
+            let normal = if (h - height).abs() <= 1e-9 * height {
+                // On the base
+                axis
+            } else if radial_length == 0.0 {
+                // At the apex the side has no single normal; point back along the axis
+                axis.scale(-1.0)
+            } else {
+                radial.scale(cos / radial_length).subtract(&axis.scale(sin))
+            };
+            IntersectionPoint {
+                point: cone.apex.add(&local),
+                distance: t,
+                normal,
+            }
+        })
+        .collect();
+
+    Ok(ConeRayResult {
+        intersects: !intersection_points.is_empty(),
+        closest_distance: intersection_points.first().map(|p| p.distance),
+        intersection_points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    /// Cone with its apex at (0, 0, 4) opening down to a base of radius 4 on z = 0
+    fn input(origin: Vector3D, direction: Vector3D) -> ConeRayInput {
+        ConeRayInput {
+            cone: Cone {
+                apex: v(0.0, 0.0, 4.0),
+                axis: v(0.0, 0.0, -1.0),
+                height: 4.0,
+                base_radius: None,
+                half_angle_degrees: Some(45.0),
+            },
+            ray: Ray { origin, direction },
+        }
+    }
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        assert!(a.subtract(&b).magnitude() < EPSILON, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_hits_side_twice() {
+        let result =
+            cone_ray_intersection_logic(input(v(-10.0, 0.0, 1.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert!(result.intersects);
+        let points = &result.intersection_points;
+        assert_eq!(points.len(), 2);
+        // At z = 1 the cone's radius is 3
+        assert!((points[0].distance - 7.0).abs() < EPSILON);
+        assert_close(points[0].point, v(-3.0, 0.0, 1.0));
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        assert_close(points[0].normal, v(-s, 0.0, s));
+        assert!((points[1].distance - 13.0).abs() < EPSILON);
+        assert_eq!(result.closest_distance, Some(points[0].distance));
+    }
+
+    #[test]
+    fn test_enters_through_base() {
+        let result =
+            cone_ray_intersection_logic(input(v(1.0, 0.0, -5.0), v(0.0, 0.0, 2.0))).unwrap();
+        let points = &result.intersection_points;
+        assert!((points[0].distance - 5.0).abs() < EPSILON);
+        assert_close(points[0].normal, v(0.0, 0.0, -1.0));
+        // Leaves through the side at z = 3, where the radius is 1
+        assert!((points[1].distance - 8.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_misses_and_behind() {
+        // Passes above the apex, through the other nappe of the double cone
+        let above =
+            cone_ray_intersection_logic(input(v(-10.0, 0.0, 6.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert!(!above.intersects && above.closest_distance.is_none());
+        let behind =
+            cone_ray_intersection_logic(input(v(-10.0, 0.0, 1.0), v(-1.0, 0.0, 0.0))).unwrap();
+        assert!(!behind.intersects);
+    }
+
+    #[test]
+    fn test_origin_inside_and_radius_form() {
+        let mut request = input(v(0.0, 0.0, 1.0), v(0.0, 1.0, 0.0));
+        request.cone.half_angle_degrees = None;
+        request.cone.base_radius = Some(4.0);
+        let result = cone_ray_intersection_logic(request).unwrap();
+        assert_eq!(result.intersection_points.len(), 1);
+        assert!((result.closest_distance.unwrap() - 3.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_down_the_axis() {
+        let result =
+            cone_ray_intersection_logic(input(v(0.0, 0.0, 10.0), v(0.0, 0.0, -1.0))).unwrap();
+        let points = &result.intersection_points;
+        assert!((points[0].distance - 6.0).abs() < EPSILON);
+        assert_close(points[0].normal, v(0.0, 0.0, 1.0));
+        assert!((points[1].distance - 10.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut request = input(v(0.0, 0.0, 0.0), v(0.0, 0.0, 0.0));
+        assert_eq!(
+            cone_ray_intersection_logic(request.clone()).unwrap_err(),
+            "Ray direction cannot be zero vector"
+        );
+        request.ray.direction = v(1.0, 0.0, 0.0);
+        request.cone.base_radius = Some(1.0);
+        assert_eq!(
+            cone_ray_intersection_logic(request.clone()).unwrap_err(),
+            "Give either base_radius or half_angle_degrees, not both"
+        );
+        request.cone.base_radius = None;
+        request.cone.half_angle_degrees = Some(90.0);
+        assert!(cone_ray_intersection_logic(request.clone()).is_err());
+        request.cone.half_angle_degrees = Some(30.0);
+        request.cone.height = 0.0;
+        assert!(cone_ray_intersection_logic(request).is_err());
+    }
+}
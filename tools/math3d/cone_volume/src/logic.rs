@@ -0,0 +1,235 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+/// Finite right circular cone, sized by base_radius or by half_angle_degrees
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ConeVolumeInput {
+    #[tool_types(nested)]
+    pub apex: Vector3D,
+    /// Direction from the apex toward the base
+    #[tool_types(nested)]
+    pub axis: Vector3D,
+    /// Distance from the apex to the base along the axis
+    pub height: f64,
+    /// Radius of the base
+    pub base_radius: Option<f64>,
+    /// Angle between the axis and the side, in degrees, below 90
+    pub half_angle_degrees: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ConeVolumeResponse {
+    pub volume: f64,
+    pub calculation_method: String,
+    #[tool_types(nested)]
+    pub apex: Vector3D,
+    #[tool_types(nested)]
+    pub axis: Vector3D,
+    /// Center of the base, height along the normalized axis from the apex
+    #[tool_types(nested)]
+    pub base_center: Vector3D,
+    pub base_radius: f64,
+    pub height: f64,
+    pub half_angle_degrees: f64,
+    /// Distance from the apex to the rim of the base
+    pub slant_height: f64,
+    /// Area of the side, excluding the base
+    pub lateral_surface_area: f64,
+    /// Side plus base area
+    pub total_surface_area: f64,
+}
+
+fn check_vector(name: &str, v: &Vector3D) -> Result<(), String> {
+    if v.x.is_nan() || v.y.is_nan() || v.z.is_nan() {
+        return Err(format!("{name} coordinates cannot contain NaN values"));
+    }
+    if v.x.is_infinite() || v.y.is_infinite() || v.z.is_infinite() {
+        return Err(format!("{name} coordinates cannot contain infinite values"));
+    }
+    Ok(())
+}
+
+pub fn compute_cone_volume(input: ConeVolumeInput) -> Result<ConeVolumeResponse, String> {
+    // Validate height
+    if input.height < 0.0 {
+        return Err("Height cannot be negative".to_string());
+    }
+    if input.height.is_nan() {
+        return Err("Height cannot be NaN".to_string());
+    }
+    if input.height.is_infinite() {
+        return Err("Height cannot be infinite".to_string());
+    }
+
+    check_vector("Apex", &input.apex)?;
+    check_vector("Axis", &input.axis)?;
+    let axis_length =
+        (input.axis.x * input.axis.x + input.axis.y * input.axis.y + input.axis.z * input.axis.z)
+            .sqrt();
+    if axis_length == 0.0 {
+        return Err("Axis cannot be zero vector".to_string());
+    }
+
+    // Exactly one of the radius and the half angle sizes the base
+    let (base_radius, half_angle) = match (input.base_radius, input.half_angle_degrees) {
+        (Some(_), Some(_)) => {
+            return Err("Give either base_radius or half_angle_degrees, not both".to_string());
+        }
+        (None, None) => {
+            return Err("Cone needs base_radius or half_angle_degrees".to_string());
+        }
+        (Some(radius), None) => {
+            if radius < 0.0 {
+                return Err("Radius cannot be negative".to_string());
+            }
+            if !radius.is_finite() {
+                return Err("Radius must be finite".to_string());
+            }
+            let half_angle = if input.height > 0.0 {
+                radius.atan2(input.height).to_degrees()
+            } else if radius > 0.0 {
+                90.0
+            } else {
+                0.0
+            };
+            (radius, half_angle)
+        }
+        (None, Some(angle)) => {
+            if !(0.0..90.0).contains(&angle) {
+                return Err("Half angle must be at least 0 and below 90 degrees".to_string());
+            }
+            (input.height * angle.to_radians().tan(), angle)
+        }
+    };
+
+    // Volume = π * r² * h / 3
+    let volume = std::f64::consts::PI * base_radius.powi(2) * input.height / 3.0;
+    let slant_height = base_radius.hypot(input.height);
+    let lateral_surface_area = std::f64::consts::PI * base_radius * slant_height;
+    let scale = input.height / axis_length;
+
+    Ok(ConeVolumeResponse {
+        volume,
+        calculation_method: "Cone formula: πr²h/3".to_string(),
+        base_center: Vector3D {
+            x: input.apex.x + input.axis.x * scale,
+            y: input.apex.y + input.axis.y * scale,
+            z: input.apex.z + input.axis.z * scale,
+        },
+        apex: input.apex,
+        axis: input.axis,
+        base_radius,
+        height: input.height,
+        half_angle_degrees: half_angle,
+        slant_height,
+        lateral_surface_area,
+        total_surface_area: lateral_surface_area + std::f64::consts::PI * base_radius.powi(2),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn input(base_radius: Option<f64>, half_angle_degrees: Option<f64>) -> ConeVolumeInput {
+        ConeVolumeInput {
+            apex: v(0.0, 0.0, 4.0),
+            axis: v(0.0, 0.0, -2.0),
+            height: 4.0,
+            base_radius,
+            half_angle_degrees,
+        }
+    }
+
+    #[test]
+    fn test_volume_from_radius() {
+        let result = compute_cone_volume(input(Some(3.0), None)).unwrap();
+        let expected = std::f64::consts::PI * 9.0 * 4.0 / 3.0; // π * 3² * 4 / 3
+        assert!((result.volume - expected).abs() < 1e-12);
+        assert_eq!(result.slant_height, 5.0);
+        assert!((result.lateral_surface_area - std::f64::consts::PI * 15.0).abs() < 1e-12);
+        assert!((result.total_surface_area - std::f64::consts::PI * 24.0).abs() < 1e-12);
+        assert!((result.half_angle_degrees - 36.869_897_645_844_02).abs() < 1e-9);
+        assert_eq!(result.calculation_method, "Cone formula: πr²h/3");
+    }
+
+    #[test]
+    fn test_volume_from_half_angle() {
+        let result = compute_cone_volume(input(None, Some(45.0))).unwrap();
+        assert!((result.base_radius - 4.0).abs() < 1e-12);
+        let expected = std::f64::consts::PI * 16.0 * 4.0 / 3.0;
+        assert!((result.volume - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_base_center_follows_axis() {
+        let result = compute_cone_volume(input(Some(1.0), None)).unwrap();
+        assert_eq!(
+            (
+                result.base_center.x,
+                result.base_center.y,
+                result.base_center.z
+            ),
+            (0.0, 0.0, 0.0)
+        );
+        // The axis is returned as given
+        assert_eq!(result.axis.z, -2.0);
+    }
+
+    #[test]
+    fn test_degenerate_cones() {
+        let mut flat = input(Some(2.0), None);
+        flat.height = 0.0;
+        let result = compute_cone_volume(flat).unwrap();
+        assert_eq!(result.volume, 0.0);
+        assert_eq!(result.half_angle_degrees, 90.0);
+
+        let result = compute_cone_volume(input(Some(0.0), None)).unwrap();
+        assert_eq!(result.volume, 0.0);
+        assert_eq!(result.half_angle_degrees, 0.0);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            compute_cone_volume(input(Some(1.0), Some(30.0))).unwrap_err(),
+            "Give either base_radius or half_angle_degrees, not both"
+        );
+        assert_eq!(
+            compute_cone_volume(input(None, None)).unwrap_err(),
+            "Cone needs base_radius or half_angle_degrees"
+        );
+        assert_eq!(
+            compute_cone_volume(input(Some(-1.0), None)).unwrap_err(),
+            "Radius cannot be negative"
+        );
+        assert!(compute_cone_volume(input(None, Some(90.0))).is_err());
+        let mut request = input(Some(1.0), None);
+        request.axis = v(0.0, 0.0, 0.0);
+        assert_eq!(
+            compute_cone_volume(request).unwrap_err(),
+            "Axis cannot be zero vector"
+        );
+        let mut request = input(Some(1.0), None);
+        request.height = f64::NAN;
+        assert_eq!(
+            compute_cone_volume(request).unwrap_err(),
+            "Height cannot be NaN"
+        );
+    }
+}
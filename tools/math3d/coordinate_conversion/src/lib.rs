@@ -1,22 +1,58 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_outbound::Outbound;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_request_context::RequestContext;
+use ftl_request_context::ResponseMetadata;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, JsonSchema)]
+pub use logic::{
+    COORDINATE_SYSTEMS, CoordinateConversionInput as LogicInput,
+    CoordinateConversionOutput as LogicOutput, MAX_POINTS, Vector3D as LogicVector3D,
+    convert_direct, convert_point, convert_points, converts_directly, coordinate_conversion_logic,
+};
+
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub struct CoordinateConversionInput {
     /// Source coordinate system: "cartesian", "spherical", "cylindrical"
     pub from_type: String,
     /// Target coordinate system: "cartesian", "spherical", "cylindrical"  
     pub to_type: String,
-    /// Input coordinates as Vector3D
-    pub coordinates: Vector3D,
+    /// Input coordinates as Vector3D; give either coordinates or points
+    pub coordinates: Option<Vector3D>,
+    /// Several points to convert in one call, such as a trajectory; converted within this
+    /// component, with an error reported per point (give either coordinates or points)
+    pub points: Option<Vec<Vector3D>>,
+    /// Unit of the spherical and cylindrical angles, in and out: "radians" or "degrees"
+    /// (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Correlation id to propagate to the conversion tools; generated when omitted
+    pub correlation_id: Option<String>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     /// X coordinate (or radius for spherical/cylindrical)
     pub x: f64,
@@ -26,19 +62,43 @@ pub struct Vector3D {
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D);
+
 #[derive(Serialize, JsonSchema)]
-pub struct CoordinateConversionResult {
-    /// Original coordinates
+pub struct PointConversion {
+    /// Position of the point in the input points
+    pub index: usize,
     pub original: Vector3D,
-    /// Converted coordinates
-    pub converted: Vector3D,
+    /// Converted coordinates; absent when the point could not be converted
+    pub converted: Option<Vector3D>,
+    /// Why the point could not be converted
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct CoordinateConversionResult {
+    /// Original coordinates (single conversions)
+    pub original: Option<Vector3D>,
+    /// Converted coordinates (single conversions)
+    pub converted: Option<Vector3D>,
+    /// One result per input point, in order (batch conversions)
+    pub points: Option<Vec<PointConversion>>,
+    /// Points converted (batch conversions)
+    pub converted_count: Option<usize>,
+    /// Points that failed (batch conversions)
+    pub failed_count: Option<usize>,
     /// Source coordinate system
     pub from_type: String,
     /// Target coordinate system
     pub to_type: String,
+    /// Unit of the spherical and cylindrical angles in this response
+    pub angle_unit: String,
+    /// Correlation id and per-tool timings for this call chain
+    pub metadata: ResponseMetadata,
 }
 
 // Helper structs for calling individual tools
+#[cfg(feature = "individual")]
 #[derive(Serialize, Deserialize)]
 struct CartesianCoordinates {
     x: f64,
@@ -46,6 +106,7 @@ struct CartesianCoordinates {
     z: f64,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, Deserialize)]
 struct SphericalCoordinates {
     radius: f64,
@@ -53,16 +114,19 @@ struct SphericalCoordinates {
     phi: f64,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Deserialize)]
 struct CartesianToSphericalResult {
     spherical_coordinates: SphericalCoordinates,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Deserialize)]
 struct SphericalToCartesianResult {
     cartesian_coordinates: CartesianCoordinates,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Serialize, Deserialize)]
 struct CylindricalCoordinates {
     radius: f64,
@@ -70,21 +134,25 @@ struct CylindricalCoordinates {
     z: f64,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Deserialize)]
 struct CartesianToCylindricalResult {
     cylindrical_coordinates: CylindricalCoordinates,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Deserialize)]
 struct CylindricalToCartesianResult {
     cartesian_coordinates: CartesianCoordinates,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Deserialize)]
 struct ToolResponseWrapper {
     content: Vec<ContentItem>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(Deserialize)]
 struct ContentItem {
     #[serde(rename = "type")]
@@ -93,22 +161,97 @@ struct ContentItem {
 }
 
 /// Convert between different 3D coordinate systems (cartesian, spherical, cylindrical)
-/// For cartesian↔spherical conversions, delegates to individual tools via HTTP
-#[cfg_attr(not(test), tool)]
-pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResponse {
-    use spin_sdk::http::{Method, Request};
+/// Conversions to or from cartesian delegate to individual tools via HTTP;
+/// cylindrical↔spherical, same-system and batch requests are computed directly
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn coordinate_conversion(request: Request<CoordinateConversionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "coordinate_conversion",
+                CoordinateConversionInput,
+                CoordinateConversionResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("coordinate_conversion", &input);
+    log.finish(run_coordinate_conversion(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_coordinate_conversion(input: CoordinateConversionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", input.points.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
 
     // Normalize coordinate system names
     let from_type = input.from_type.to_lowercase();
     let to_type = input.to_type.to_lowercase();
+    let angle_unit = input.angle_unit.unwrap_or_default();
+    let mut ctx = RequestContext::new(input.correlation_id);
+
+    let original = match (input.coordinates, input.points) {
+        (Some(coordinates), None) => coordinates,
+        (None, Some(points)) => {
+            // Batches skip the HTTP hops a point-by-point conversion would need
+            let results = match convert_batch(points, &from_type, &to_type, angle_unit) {
+                Ok(results) => results,
+                Err(e) => return ToolResponse::text(format!("Error: {e}")),
+            };
+            let failed_count = results.iter().filter(|r| r.error.is_some()).count();
+
+            let result = CoordinateConversionResult {
+                original: None,
+                converted: None,
+                converted_count: Some(results.len() - failed_count),
+                failed_count: Some(failed_count),
+                points: Some(results),
+                from_type: input.from_type,
+                to_type: input.to_type,
+                angle_unit: angle_unit.name().to_string(),
+                metadata: ctx.finish(),
+            };
+            return match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            };
+        }
+        (Some(_), Some(_)) => {
+            return ToolResponse::text(
+                "Error: Give either coordinates or points, not both".to_string(),
+            );
+        }
+        (None, None) => {
+            return ToolResponse::text("Error: Give coordinates or points to convert".to_string());
+        }
+    };
+
+    // The sibling tools and the direct conversions all work in radians
+    let coordinates = map_angles(&from_type, &original, |angle| angle_unit.to_radians(angle));
+    let correlation_id = ctx.correlation_id().to_string();
+    let outbound = Outbound::from_env();
 
     let converted = match (from_type.as_str(), to_type.as_str()) {
         ("cartesian", "spherical") => {
             // Call cartesian-to-spherical tool via HTTP
             let cartesian_input = CartesianCoordinates {
-                x: input.coordinates.x,
-                y: input.coordinates.y,
-                z: input.coordinates.z,
+                x: coordinates.x,
+                y: coordinates.y,
+                z: coordinates.z,
             };
             let request_body = match serde_json::to_string(&cartesian_input) {
                 Ok(body) => body,
@@ -119,30 +262,14 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
                 }
             };
 
-            let request = Request::builder()
-                .method(Method::Post)
-                .uri("http://cartesian-to-spherical.spin.internal")
-                .header("Content-Type", "application/json")
-                .body(request_body.into_bytes())
-                .build();
-
-            let response: spin_sdk::http::Response = match spin_sdk::http::send(request).await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Error calling cartesian-to-spherical tool: {e:?}"
-                    ));
-                }
-            };
-
-            let body_bytes = response.into_body();
-            let body = match String::from_utf8(body_bytes) {
+            let call = outbound.post_json(
+                "cartesian-to-spherical",
+                &request_body,
+                Some(&correlation_id),
+            );
+            let body = match ctx.time("cartesian_to_spherical", call).await {
                 Ok(body) => body,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Failed to parse response body: {e}"
-                    ));
-                }
+                Err(failure) => return ToolResponse::text(failure.to_json()),
             };
 
             let wrapper: ToolResponseWrapper = match serde_json::from_str(&body) {
@@ -173,9 +300,9 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
         ("spherical", "cartesian") => {
             // Call spherical-to-cartesian tool via HTTP
             let spherical_input = SphericalCoordinates {
-                radius: input.coordinates.x,
-                theta: input.coordinates.y,
-                phi: input.coordinates.z,
+                radius: coordinates.x,
+                theta: coordinates.y,
+                phi: coordinates.z,
             };
             let request_body = match serde_json::to_string(&spherical_input) {
                 Ok(body) => body,
@@ -186,30 +313,14 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
                 }
             };
 
-            let request = Request::builder()
-                .method(Method::Post)
-                .uri("http://spherical-to-cartesian.spin.internal")
-                .header("Content-Type", "application/json")
-                .body(request_body.into_bytes())
-                .build();
-
-            let response: spin_sdk::http::Response = match spin_sdk::http::send(request).await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Error calling spherical-to-cartesian tool: {e:?}"
-                    ));
-                }
-            };
-
-            let body_bytes = response.into_body();
-            let body = match String::from_utf8(body_bytes) {
+            let call = outbound.post_json(
+                "spherical-to-cartesian",
+                &request_body,
+                Some(&correlation_id),
+            );
+            let body = match ctx.time("spherical_to_cartesian", call).await {
                 Ok(body) => body,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Failed to parse response body: {e}"
-                    ));
-                }
+                Err(failure) => return ToolResponse::text(failure.to_json()),
             };
 
             let wrapper: ToolResponseWrapper = match serde_json::from_str(&body) {
@@ -240,9 +351,9 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
         ("cartesian", "cylindrical") => {
             // Call cartesian-to-cylindrical tool via HTTP
             let cartesian_input = CartesianCoordinates {
-                x: input.coordinates.x,
-                y: input.coordinates.y,
-                z: input.coordinates.z,
+                x: coordinates.x,
+                y: coordinates.y,
+                z: coordinates.z,
             };
             let request_body = match serde_json::to_string(&cartesian_input) {
                 Ok(body) => body,
@@ -253,30 +364,14 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
                 }
             };
 
-            let request = Request::builder()
-                .method(Method::Post)
-                .uri("http://cartesian-to-cylindrical.spin.internal")
-                .header("Content-Type", "application/json")
-                .body(request_body.into_bytes())
-                .build();
-
-            let response: spin_sdk::http::Response = match spin_sdk::http::send(request).await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Error calling cartesian-to-cylindrical tool: {e:?}"
-                    ));
-                }
-            };
-
-            let body_bytes = response.into_body();
-            let body = match String::from_utf8(body_bytes) {
+            let call = outbound.post_json(
+                "cartesian-to-cylindrical",
+                &request_body,
+                Some(&correlation_id),
+            );
+            let body = match ctx.time("cartesian_to_cylindrical", call).await {
                 Ok(body) => body,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Failed to parse response body: {e}"
-                    ));
-                }
+                Err(failure) => return ToolResponse::text(failure.to_json()),
             };
 
             let wrapper: ToolResponseWrapper = match serde_json::from_str(&body) {
@@ -307,9 +402,9 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
         ("cylindrical", "cartesian") => {
             // Call cylindrical-to-cartesian tool via HTTP
             let cylindrical_input = CylindricalCoordinates {
-                radius: input.coordinates.x,
-                theta: input.coordinates.y,
-                z: input.coordinates.z,
+                radius: coordinates.x,
+                theta: coordinates.y,
+                z: coordinates.z,
             };
             let request_body = match serde_json::to_string(&cylindrical_input) {
                 Ok(body) => body,
@@ -320,30 +415,14 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
                 }
             };
 
-            let request = Request::builder()
-                .method(Method::Post)
-                .uri("http://cylindrical-to-cartesian.spin.internal")
-                .header("Content-Type", "application/json")
-                .body(request_body.into_bytes())
-                .build();
-
-            let response: spin_sdk::http::Response = match spin_sdk::http::send(request).await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Error calling cylindrical-to-cartesian tool: {e:?}"
-                    ));
-                }
-            };
-
-            let body_bytes = response.into_body();
-            let body = match String::from_utf8(body_bytes) {
+            let call = outbound.post_json(
+                "cylindrical-to-cartesian",
+                &request_body,
+                Some(&correlation_id),
+            );
+            let body = match ctx.time("cylindrical_to_cartesian", call).await {
                 Ok(body) => body,
-                Err(e) => {
-                    return ToolResponse::text(format!(
-                        "Error: Failed to parse response body: {e}"
-                    ));
-                }
+                Err(failure) => return ToolResponse::text(failure.to_json()),
             };
 
             let wrapper: ToolResponseWrapper = match serde_json::from_str(&body) {
@@ -371,18 +450,110 @@ pub async fn coordinate_conversion(input: CoordinateConversionInput) -> ToolResp
                 z: result.cartesian_coordinates.z,
             }
         }
+        (from, to) if logic::converts_directly(from, to) => {
+            // No sibling tool covers these pairs, so they need no HTTP hop
+            let coordinates = logic::Vector3D {
+                x: coordinates.x,
+                y: coordinates.y,
+                z: coordinates.z,
+            };
+            match logic::convert_direct(from, to, &coordinates) {
+                Ok(converted) => Vector3D {
+                    x: converted.x,
+                    y: converted.y,
+                    z: converted.z,
+                },
+                Err(e) => return ToolResponse::text(format!("Error: {e}")),
+            }
+        }
         _ => {
-            return ToolResponse::text(
-                "Error: Invalid coordinate conversion. Supported: cartesian↔spherical, cartesian↔cylindrical".to_string()
-            );
+            return ToolResponse::text(format!("Error: {}", logic::UNSUPPORTED_CONVERSION));
         }
     };
 
     let result = CoordinateConversionResult {
-        original: input.coordinates,
-        converted,
+        original: Some(original),
+        converted: Some(map_angles(&to_type, &converted, |angle| {
+            angle_unit.value_from_radians(angle)
+        })),
+        points: None,
+        converted_count: None,
+        failed_count: None,
         from_type: input.from_type,
         to_type: input.to_type,
+        angle_unit: angle_unit.name().to_string(),
+        metadata: ctx.finish(),
     };
-    ToolResponse::text(serde_json::to_string(&result).unwrap())
+    match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+        Ok(body) => ToolResponse::text(body),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
+
+/// Applies `convert` to the angle components of coordinates in `system`: theta and phi
+/// for spherical, theta for cylindrical
+#[cfg(feature = "individual")]
+fn map_angles(system: &str, coordinates: &Vector3D, convert: impl Fn(f64) -> f64) -> Vector3D {
+    let Vector3D { x, y, z } = *coordinates;
+    match system {
+        "spherical" => Vector3D {
+            x,
+            y: convert(y),
+            z: convert(z),
+        },
+        "cylindrical" => Vector3D {
+            x,
+            y: convert(y),
+            z,
+        },
+        _ => Vector3D { x, y, z },
+    }
+}
+
+/// Converts every point within this component, keeping per-point errors
+#[cfg(feature = "individual")]
+fn convert_batch(
+    points: Vec<Vector3D>,
+    from_type: &str,
+    to_type: &str,
+    angle_unit: AngleUnit,
+) -> Result<Vec<PointConversion>, String> {
+    let logic_points: Vec<logic::Vector3D> = points
+        .iter()
+        .map(|p| {
+            let p = map_angles(from_type, p, |angle| angle_unit.to_radians(angle));
+            logic::Vector3D {
+                x: p.x,
+                y: p.y,
+                z: p.z,
+            }
+        })
+        .collect();
+    let converted = logic::convert_points(from_type, to_type, &logic_points)?;
+
+    Ok(points
+        .into_iter()
+        .zip(converted)
+        .enumerate()
+        .map(|(index, (original, converted))| {
+            let (converted, error) = match converted {
+                Ok(c) => {
+                    let c = Vector3D {
+                        x: c.x,
+                        y: c.y,
+                        z: c.z,
+                    };
+                    let c = map_angles(to_type, &c, |angle| angle_unit.value_from_radians(angle));
+                    (Some(c), None)
+                }
+                Err(e) => (None, Some(e)),
+            };
+            PointConversion {
+                index,
+                original,
+                converted,
+                error,
+            }
+        })
+        .collect())
 }
@@ -1,6 +1,12 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// Coordinate systems the conversion understands
+pub const COORDINATE_SYSTEMS: [&str; 3] = ["cartesian", "spherical", "cylindrical"];
+/// Most points converted in one batch
+pub const MAX_POINTS: usize = 100_000;
+pub const UNSUPPORTED_CONVERSION: &str = "Invalid coordinate conversion. Supported: cartesian↔spherical, cartesian↔cylindrical, cylindrical↔spherical, or a system to itself";
+
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Vector3D {
     pub x: f64,
@@ -38,12 +44,10 @@ pub struct CoordinateConversionOutput {
 }
 
 impl Vector3D {
-    #[cfg(test)]
     pub fn is_valid(&self) -> bool {
         self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
     }
 
-    #[cfg(test)]
     pub fn to_spherical(&self) -> SphericalCoord {
         let radius = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
         let theta = self.y.atan2(self.x);
@@ -56,7 +60,6 @@ impl Vector3D {
         SphericalCoord { radius, theta, phi }
     }
 
-    #[cfg(test)]
     pub fn to_cylindrical(&self) -> CylindricalCoord {
         let radius = (self.x * self.x + self.y * self.y).sqrt();
         let theta = self.y.atan2(self.x);
@@ -70,7 +73,6 @@ impl Vector3D {
 }
 
 impl SphericalCoord {
-    #[cfg(test)]
     pub fn is_valid(&self) -> bool {
         self.radius.is_finite()
             && self.theta.is_finite()
@@ -78,7 +80,6 @@ impl SphericalCoord {
             && self.radius >= 0.0
     }
 
-    #[cfg(test)]
     pub fn to_cartesian(&self) -> Vector3D {
         let sin_phi = self.phi.sin();
         let cos_phi = self.phi.cos();
@@ -91,10 +92,17 @@ impl SphericalCoord {
             z: self.radius * cos_phi,
         }
     }
+
+    pub fn to_cylindrical(&self) -> CylindricalCoord {
+        CylindricalCoord {
+            radius: self.radius * self.phi.sin(),
+            theta: self.theta,
+            z: self.radius * self.phi.cos(),
+        }
+    }
 }
 
 impl CylindricalCoord {
-    #[cfg(test)]
     pub fn is_valid(&self) -> bool {
         self.radius.is_finite()
             && self.theta.is_finite()
@@ -102,7 +110,6 @@ impl CylindricalCoord {
             && self.radius >= 0.0
     }
 
-    #[cfg(test)]
     pub fn to_cartesian(&self) -> Vector3D {
         let cos_theta = self.theta.cos();
         let sin_theta = self.theta.sin();
@@ -113,24 +120,108 @@ impl CylindricalCoord {
             z: self.z,
         }
     }
+
+    pub fn to_spherical(&self) -> SphericalCoord {
+        let radius = self.radius.hypot(self.z);
+        // Same polar angle as acos(z / r), without the rounding near the poles
+        let phi = if radius > 0.0 {
+            self.radius.atan2(self.z)
+        } else {
+            0.0
+        };
+
+        SphericalCoord {
+            radius,
+            theta: self.theta,
+            phi,
+        }
+    }
 }
 
-#[cfg(test)]
-pub fn coordinate_conversion_logic(
-    input: CoordinateConversionInput,
-) -> Result<CoordinateConversionOutput, String> {
-    // Input validation
-    if !input.coordinates.is_valid() {
+/// Whether a pair converts without calling a sibling tool: cylindrical↔spherical, or a
+/// supported system to itself
+pub fn converts_directly(from_type: &str, to_type: &str) -> bool {
+    matches!(
+        (from_type, to_type),
+        ("cylindrical", "spherical") | ("spherical", "cylindrical")
+    ) || (from_type == to_type && COORDINATE_SYSTEMS.contains(&from_type))
+}
+
+/// Converts the pairs accepted by `converts_directly`; same-system requests return the
+/// coordinates unchanged once validated
+pub fn convert_direct(
+    from_type: &str,
+    to_type: &str,
+    coordinates: &Vector3D,
+) -> Result<Vector3D, String> {
+    if !coordinates.is_valid() {
         return Err("Invalid coordinates: contains NaN or infinite values".to_string());
     }
+    let spherical = || {
+        let spherical = SphericalCoord {
+            radius: coordinates.x,
+            theta: coordinates.y,
+            phi: coordinates.z,
+        };
+        if spherical.is_valid() {
+            Ok(spherical)
+        } else {
+            Err("Invalid spherical coordinates: radius must be non-negative".to_string())
+        }
+    };
+    let cylindrical = || {
+        let cylindrical = CylindricalCoord {
+            radius: coordinates.x,
+            theta: coordinates.y,
+            z: coordinates.z,
+        };
+        if cylindrical.is_valid() {
+            Ok(cylindrical)
+        } else {
+            Err("Invalid cylindrical coordinates: radius must be non-negative".to_string())
+        }
+    };
 
-    // Normalize coordinate system names
-    let from_type = input.from_type.to_lowercase();
-    let to_type = input.to_type.to_lowercase();
+    match (from_type, to_type) {
+        ("cylindrical", "spherical") => {
+            let spherical = cylindrical()?.to_spherical();
+            Ok(Vector3D {
+                x: spherical.radius,
+                y: spherical.theta,
+                z: spherical.phi,
+            })
+        }
+        ("spherical", "cylindrical") => {
+            let cylindrical = spherical()?.to_cylindrical();
+            Ok(Vector3D {
+                x: cylindrical.radius,
+                y: cylindrical.theta,
+                z: cylindrical.z,
+            })
+        }
+        ("spherical", "spherical") => spherical().map(|_| coordinates.clone()),
+        ("cylindrical", "cylindrical") => cylindrical().map(|_| coordinates.clone()),
+        ("cartesian", "cartesian") => Ok(coordinates.clone()),
+        _ => Err(format!(
+            "Conversion from {from_type} to {to_type} needs the cartesian conversion tools"
+        )),
+    }
+}
+
+/// Converts one point between any two supported systems within this component
+pub fn convert_point(
+    from_type: &str,
+    to_type: &str,
+    coordinates: &Vector3D,
+) -> Result<Vector3D, String> {
+    // Input validation
+    if !coordinates.is_valid() {
+        return Err("Invalid coordinates: contains NaN or infinite values".to_string());
+    }
 
-    let converted = match (from_type.as_str(), to_type.as_str()) {
+    let converted = match (from_type, to_type) {
         ("cartesian", "spherical") => {
-            let spherical = input.coordinates.to_spherical();
+            let spherical = coordinates.to_spherical();
             if !spherical.is_valid() {
                 return Err(
                     "Conversion to spherical coordinates resulted in invalid values".to_string(),
@@ -144,9 +235,9 @@ pub fn coordinate_conversion_logic(
         }
         ("spherical", "cartesian") => {
             let spherical = SphericalCoord {
-                radius: input.coordinates.x,
-                theta: input.coordinates.y,
-                phi: input.coordinates.z,
+                radius: coordinates.x,
+                theta: coordinates.y,
+                phi: coordinates.z,
             };
             if !spherical.is_valid() {
                 return Err(
@@ -162,7 +253,7 @@ pub fn coordinate_conversion_logic(
             cartesian
         }
         ("cartesian", "cylindrical") => {
-            let cylindrical = input.coordinates.to_cylindrical();
+            let cylindrical = coordinates.to_cylindrical();
             if !cylindrical.is_valid() {
                 return Err(
                     "Conversion to cylindrical coordinates resulted in invalid values".to_string(),
@@ -176,9 +267,9 @@ pub fn coordinate_conversion_logic(
         }
         ("cylindrical", "cartesian") => {
             let cylindrical = CylindricalCoord {
-                radius: input.coordinates.x,
-                theta: input.coordinates.y,
-                z: input.coordinates.z,
+                radius: coordinates.x,
+                theta: coordinates.y,
+                z: coordinates.z,
             };
             if !cylindrical.is_valid() {
                 return Err(
@@ -194,8 +285,9 @@ pub fn coordinate_conversion_logic(
             }
             cartesian
         }
+        (from, to) if converts_directly(from, to) => convert_direct(from, to, coordinates)?,
         _ => {
-            return Err("Invalid coordinate conversion. Supported: cartesian↔spherical, cartesian↔cylindrical".to_string());
+            return Err(UNSUPPORTED_CONVERSION.to_string());
         }
     };
 
@@ -204,6 +296,39 @@ pub fn coordinate_conversion_logic(
         return Err("Coordinate conversion resulted in invalid values".to_string());
     }
 
+    Ok(converted)
+}
+
+/// Converts every point, keeping each point's error instead of failing the batch; only an
+/// unsupported pair of systems or too many points fail the whole call
+pub fn convert_points(
+    from_type: &str,
+    to_type: &str,
+    points: &[Vector3D],
+) -> Result<Vec<Result<Vector3D, String>>, String> {
+    if !(COORDINATE_SYSTEMS.contains(&from_type) && COORDINATE_SYSTEMS.contains(&to_type)) {
+        return Err(UNSUPPORTED_CONVERSION.to_string());
+    }
+    if points.len() > MAX_POINTS {
+        return Err(format!(
+            "At most {MAX_POINTS} points can be converted at once"
+        ));
+    }
+    Ok(points
+        .iter()
+        .map(|point| convert_point(from_type, to_type, point))
+        .collect())
+}
+
+/// Converts a single point, echoing the request's system names
+pub fn coordinate_conversion_logic(
+    input: CoordinateConversionInput,
+) -> Result<CoordinateConversionOutput, String> {
+    // Normalize coordinate system names
+    let from_type = input.from_type.to_lowercase();
+    let to_type = input.to_type.to_lowercase();
+    let converted = convert_point(&from_type, &to_type, &input.coordinates)?;
+
     Ok(CoordinateConversionOutput {
         original: input.coordinates,
         converted,
@@ -366,7 +491,167 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            "Invalid coordinate conversion. Supported: cartesian↔spherical, cartesian↔cylindrical"
+            "Invalid coordinate conversion. Supported: cartesian↔spherical, cartesian↔cylindrical, cylindrical↔spherical, or a system to itself"
+        );
+    }
+
+    #[test]
+    fn test_cylindrical_spherical_conversions() {
+        // Cylindrical (3, θ, 4) is spherical (5, θ, atan2(3, 4)) for any θ
+        let cylindrical = Vector3D {
+            x: 3.0,
+            y: 1.2,
+            z: 4.0,
+        };
+        let to_spherical = CoordinateConversionInput {
+            from_type: "cylindrical".to_string(),
+            to_type: "spherical".to_string(),
+            coordinates: cylindrical.clone(),
+        };
+        let spherical = coordinate_conversion_logic(to_spherical).unwrap().converted;
+        assert!((spherical.x - 5.0).abs() < 1e-14);
+        assert!((spherical.y - 1.2).abs() < 1e-15);
+        assert!((spherical.z - 3.0_f64.atan2(4.0)).abs() < 1e-15);
+
+        // Matches going through cartesian
+        let via_cartesian = CylindricalCoord {
+            radius: 3.0,
+            theta: 1.2,
+            z: 4.0,
+        }
+        .to_cartesian()
+        .to_spherical();
+        assert!((via_cartesian.radius - spherical.x).abs() < 1e-14);
+        assert!((via_cartesian.phi - spherical.z).abs() < 1e-14);
+
+        let back = CoordinateConversionInput {
+            from_type: "spherical".to_string(),
+            to_type: "cylindrical".to_string(),
+            coordinates: spherical,
+        };
+        let round_trip = coordinate_conversion_logic(back).unwrap().converted;
+        assert!((round_trip.x - cylindrical.x).abs() < 1e-14);
+        assert!((round_trip.y - cylindrical.y).abs() < 1e-15);
+        assert!((round_trip.z - cylindrical.z).abs() < 1e-14);
+
+        // Below the xy-plane the polar angle passes π/2; on the negative z-axis it is π
+        let down = convert_direct(
+            "cylindrical",
+            "spherical",
+            &Vector3D {
+                x: 0.0,
+                y: 0.0,
+                z: -2.0,
+            },
+        )
+        .unwrap();
+        assert!((down.x - 2.0).abs() < 1e-15);
+        assert!((down.z - std::f64::consts::PI).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_same_system_passthrough() {
+        let coordinates = Vector3D {
+            x: 2.0,
+            y: -0.5,
+            z: 1.0,
+        };
+        for system in ["cartesian", "Spherical", "CYLINDRICAL"] {
+            let input = CoordinateConversionInput {
+                from_type: system.to_string(),
+                to_type: system.to_string(),
+                coordinates: coordinates.clone(),
+            };
+            let result = coordinate_conversion_logic(input).unwrap();
+            assert_eq!(result.converted, coordinates);
+        }
+
+        // Passthrough still checks the coordinates are valid in that system
+        let negative_radius = Vector3D {
+            x: -1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        assert_eq!(
+            convert_direct("spherical", "spherical", &negative_radius).unwrap_err(),
+            "Invalid spherical coordinates: radius must be non-negative"
+        );
+        assert!(convert_direct("cartesian", "cartesian", &negative_radius).is_ok());
+        assert!(!converts_directly("cartesian", "spherical"));
+        assert!(!converts_directly("polar", "polar"));
+    }
+
+    #[test]
+    fn test_batch_conversion() {
+        let points = vec![
+            Vector3D {
+                x: 3.0,
+                y: 4.0,
+                z: 5.0,
+            },
+            Vector3D {
+                x: f64::NAN,
+                y: 0.0,
+                z: 0.0,
+            },
+            Vector3D {
+                x: 0.0,
+                y: 0.0,
+                z: -2.0,
+            },
+        ];
+        let results = convert_points("cartesian", "spherical", &points).unwrap();
+        assert_eq!(results.len(), 3);
+
+        // Each point converts as it would alone
+        for (point, result) in points.iter().zip(&results) {
+            let single = CoordinateConversionInput {
+                from_type: "cartesian".to_string(),
+                to_type: "spherical".to_string(),
+                coordinates: point.clone(),
+            };
+            match coordinate_conversion_logic(single) {
+                Ok(output) => assert_eq!(result.as_ref().unwrap(), &output.converted),
+                Err(e) => assert_eq!(result.as_ref().unwrap_err(), &e),
+            }
+        }
+        // A bad point fails alone
+        assert_eq!(
+            results[1].as_ref().unwrap_err(),
+            "Invalid coordinates: contains NaN or infinite values"
+        );
+        assert!((results[2].as_ref().unwrap().z - std::f64::consts::PI).abs() < 1e-15);
+
+        // Per-point validation follows the source system
+        let negative_radius = [Vector3D {
+            x: -1.0,
+            y: 0.0,
+            z: 0.0,
+        }];
+        let results = convert_points("cylindrical", "cartesian", &negative_radius).unwrap();
+        assert!(
+            results[0]
+                .as_ref()
+                .unwrap_err()
+                .contains("radius must be non-negative")
+        );
+    }
+
+    #[test]
+    fn test_batch_rejects_unsupported_systems() {
+        let points = [Vector3D {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        }];
+        assert_eq!(
+            convert_points("cartesian", "polar", &points).unwrap_err(),
+            UNSUPPORTED_CONVERSION
+        );
+        assert!(
+            convert_points("cartesian", "cylindrical", &[])
+                .unwrap()
+                .is_empty()
         );
     }
 
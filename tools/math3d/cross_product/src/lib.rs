@@ -1,77 +1,74 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{CrossProductInput as LogicInput, Vector3D as LogicVector3D, cross_product_logic};
 
-#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
-pub struct Vector3D {
-    /// X component of the vector
-    x: f64,
-    /// Y component of the vector
-    y: f64,
-    /// Z component of the vector
-    z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    CrossProductInput as LogicInput, CrossProductResult as LogicOutput, Vector3D as LogicVector3D,
+    cross_product_logic,
+};
 
-#[derive(Deserialize, JsonSchema)]
-pub struct CrossProductInput {
-    /// First 3D vector
-    vector1: Vector3D,
-    /// Second 3D vector
-    vector2: Vector3D,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{CrossProductResultSchema as CrossProductResult, Vector3DSchema as Vector3D};
 
-#[derive(Serialize, JsonSchema)]
-pub struct CrossProductResult {
-    /// The resulting cross product vector
-    pub cross_product: Vector3D,
-    /// Magnitude of the cross product vector
-    pub magnitude: f64,
-    /// Area of the parallelogram formed by the two vectors
-    pub area_parallelogram: f64,
-    /// Whether the vectors are parallel (cross product ≈ zero vector)
-    pub are_parallel: bool,
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrossProductInput {
+    #[serde(flatten)]
+    pub params: logic::CrossProductInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-impl From<Vector3D> for LogicVector3D {
-    fn from(v: Vector3D) -> Self {
-        LogicVector3D {
-            x: v.x,
-            y: v.y,
-            z: v.z,
+/// Calculate cross product of two 3D vectors
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn cross_product(request: Request<CrossProductInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "cross_product",
+                CrossProductInput,
+                CrossProductResult
+            ));
         }
-    }
-}
+    };
 
-impl From<CrossProductInput> for LogicInput {
-    fn from(input: CrossProductInput) -> Self {
-        LogicInput {
-            vector1: input.vector1.into(),
-            vector2: input.vector2.into(),
-        }
-    }
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("cross_product", &input);
+    log.finish(run_cross_product(input))
 }
 
-/// Calculate cross product of two 3D vectors
-#[cfg_attr(not(test), tool)]
-pub fn cross_product(input: CrossProductInput) -> ToolResponse {
-    match cross_product_logic(input.into()) {
+#[cfg(feature = "individual")]
+fn run_cross_product(input: CrossProductInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match logic::cross_product_logic(input.params.into()) {
         Ok(logic_result) => {
-            let result = CrossProductResult {
-                cross_product: Vector3D {
-                    x: logic_result.cross_product.x,
-                    y: logic_result.cross_product.y,
-                    z: logic_result.cross_product.z,
-                },
-                magnitude: logic_result.magnitude,
-                area_parallelogram: logic_result.area_parallelogram,
-                are_parallel: logic_result.are_parallel,
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = CrossProductResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
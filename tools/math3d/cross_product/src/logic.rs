@@ -1,6 +1,9 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     /// X component of the vector
     pub x: f64,
@@ -10,19 +13,28 @@ pub struct Vector3D {
     pub z: f64,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Deserialize, Clone, Debug, ToolTypes)]
 pub struct CrossProductInput {
     /// First 3D vector
+    #[tool_types(nested)]
     pub vector1: Vector3D,
     /// Second 3D vector
+    #[tool_types(nested)]
     pub vector2: Vector3D,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, ToolTypes)]
 pub struct CrossProductResult {
+    /// The resulting cross product vector
+    #[tool_types(nested)]
     pub cross_product: Vector3D,
+    /// Magnitude of the cross product vector
     pub magnitude: f64,
+    /// Area of the parallelogram formed by the two vectors
     pub area_parallelogram: f64,
+    /// Whether the vectors are parallel (cross product ≈ zero vector)
     pub are_parallel: bool,
 }
 
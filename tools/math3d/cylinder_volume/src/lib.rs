@@ -1,75 +1,75 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    CylinderVolumeInput as LogicInput, CylinderVolumeResponse as LogicOutput,
+    Vector3D as LogicVector3D, compute_cylinder_volume,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    CylinderVolumeResponseSchema as CylinderVolumeResponse, Vector3DSchema as Vector3D,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CylinderVolumeInput {
-    pub base_center: Vector3D,
-    pub axis: Vector3D,
-    pub radius: f64,
-    pub height: f64,
+    #[serde(flatten)]
+    pub params: logic::CylinderVolumeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct CylinderVolumeResponse {
-    pub volume: f64,
-    pub calculation_method: String,
-    pub base_center: Vector3D,
-    pub axis: Vector3D,
-    pub radius: f64,
-    pub height: f64,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn cylinder_volume(request: Request<CylinderVolumeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "cylinder_volume",
+                CylinderVolumeInput,
+                CylinderVolumeResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("cylinder_volume", &input);
+    log.finish(run_cylinder_volume(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn cylinder_volume(input: CylinderVolumeInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::CylinderVolumeInput {
-        base_center: logic::Vector3D {
-            x: input.base_center.x,
-            y: input.base_center.y,
-            z: input.base_center.z,
-        },
-        axis: logic::Vector3D {
-            x: input.axis.x,
-            y: input.axis.y,
-            z: input.axis.z,
-        },
-        radius: input.radius,
-        height: input.height,
+#[cfg(feature = "individual")]
+fn run_cylinder_volume(input: CylinderVolumeInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_cylinder_volume(logic_input) {
+    match logic::compute_cylinder_volume(input.params.into()) {
         Ok(logic_result) => {
-            // Convert logic types back to API types
-            let result = CylinderVolumeResponse {
-                volume: logic_result.volume,
-                calculation_method: logic_result.calculation_method,
-                base_center: Vector3D {
-                    x: logic_result.base_center.x,
-                    y: logic_result.base_center.y,
-                    z: logic_result.base_center.z,
-                },
-                axis: Vector3D {
-                    x: logic_result.axis.x,
-                    y: logic_result.axis.y,
-                    z: logic_result.axis.z,
-                },
-                radius: logic_result.radius,
-                height: logic_result.height,
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = CylinderVolumeResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,25 +1,34 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct CylinderVolumeInput {
+    #[tool_types(nested)]
     pub base_center: Vector3D,
+    #[tool_types(nested)]
     pub axis: Vector3D,
     pub radius: f64,
     pub height: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct CylinderVolumeResponse {
     pub volume: f64,
     pub calculation_method: String,
+    #[tool_types(nested)]
     pub base_center: Vector3D,
+    #[tool_types(nested)]
     pub axis: Vector3D,
     pub radius: f64,
     pub height: f64,
@@ -1,28 +1,51 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{CylindricalCoordinates as LogicInput, cylindrical_to_cartesian_logic};
+#[cfg(feature = "individual")]
+use logic::CylindricalCoordinates as LogicInput;
 
 // Re-export for testing
 pub use logic::{
     CartesianCoordinates as LogicCartesian, CylindricalCoordinates as LogicCylindrical,
-    CylindricalToCartesianResult as LogicResult,
+    CylindricalToCartesianResult as LogicResult, cylindrical_to_cartesian_logic,
 };
 
 #[derive(Deserialize, Serialize, JsonSchema)]
 pub struct CylindricalCoordinates {
     /// Distance from z-axis (ρ)
     pub radius: f64,
-    /// Azimuthal angle in radians (θ)
+    /// Azimuthal angle in `angle_unit` (θ)
     pub theta: f64,
     /// Height along z-axis
     pub z: f64,
 }
 
+// The coordinates to convert plus options that only affect how the result is serialized
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct ConversionInput {
+    #[serde(flatten)]
+    pub coordinates: CylindricalCoordinates,
+    /// Unit of the given theta: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
 #[derive(Serialize, JsonSchema)]
 pub struct CartesianCoordinates {
     /// X coordinate
@@ -41,25 +64,54 @@ pub struct CylindricalToCartesianResult {
     pub cartesian_coordinates: CartesianCoordinates,
     /// Conversion notes
     pub conversion_notes: String,
+    /// Unit of theta
+    pub angle_unit: String,
 }
 
 /// Convert cylindrical coordinates (ρ, θ, z) to Cartesian coordinates (x, y, z)
 ///
 /// Cylindrical coordinates use:
 /// - ρ (radius): distance from the z-axis
-/// - θ (theta): azimuthal angle in radians around the z-axis
+/// - θ (theta): azimuthal angle around the z-axis, in radians unless `angle_unit` says otherwise
 /// - z: height along the z-axis
 ///
 /// Conversion formulas:
 /// - x = ρ * cos(θ)
 /// - y = ρ * sin(θ)
 /// - z = z (unchanged)
-#[cfg_attr(not(test), tool)]
-pub fn cylindrical_to_cartesian(input: CylindricalCoordinates) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn cylindrical_to_cartesian(request: Request<ConversionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "cylindrical_to_cartesian",
+                ConversionInput,
+                CylindricalToCartesianResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("cylindrical_to_cartesian", &input);
+    log.finish(run_cylindrical_to_cartesian(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_cylindrical_to_cartesian(input: ConversionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
     let logic_input = LogicInput {
-        radius: input.radius,
-        theta: input.theta,
-        z: input.z,
+        radius: input.coordinates.radius,
+        theta: angle_unit.to_radians(input.coordinates.theta),
+        z: input.coordinates.z,
     };
 
     match cylindrical_to_cartesian_logic(logic_input) {
@@ -67,7 +119,7 @@ pub fn cylindrical_to_cartesian(input: CylindricalCoordinates) -> ToolResponse {
             let result = CylindricalToCartesianResult {
                 original_cylindrical: CylindricalCoordinates {
                     radius: logic_result.original_cylindrical.radius,
-                    theta: logic_result.original_cylindrical.theta,
+                    theta: angle_unit.value_from_radians(logic_result.original_cylindrical.theta),
                     z: logic_result.original_cylindrical.z,
                 },
                 cartesian_coordinates: CartesianCoordinates {
@@ -76,8 +128,12 @@ pub fn cylindrical_to_cartesian(input: CylindricalCoordinates) -> ToolResponse {
                     z: logic_result.cartesian_coordinates.z,
                 },
                 conversion_notes: logic_result.conversion_notes,
+                angle_unit: angle_unit.name().to_string(),
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
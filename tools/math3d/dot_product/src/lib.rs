@@ -1,76 +1,91 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::{AngleUnit, Quantity};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{DotProductInput as LogicInput, Vector3D as LogicVector3D, dot_product_logic};
 
-#[derive(Deserialize, JsonSchema, Clone, Debug, PartialEq)]
-struct Vector3D {
-    /// X component of the vector
-    x: f64,
-    /// Y component of the vector
-    y: f64,
-    /// Z component of the vector
-    z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    DotProductInput as LogicInput, DotProductResult as LogicOutput, Vector3D as LogicVector3D,
+    dot_product_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{DotProductResultSchema as DotProductResult, Vector3DSchema as Vector3D};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DotProductInput {
-    /// First 3D vector
-    vector1: Vector3D,
-    /// Second 3D vector
-    vector2: Vector3D,
+    #[serde(flatten)]
+    pub params: logic::DotProductInputSchema,
+    /// Unit for `angle`: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-struct DotProductResult {
-    /// The calculated dot product value
-    pub dot_product: f64,
-    /// Angle between vectors in radians
-    pub angle_radians: f64,
-    /// Angle between vectors in degrees
-    pub angle_degrees: f64,
-    /// Whether the vectors are perpendicular (dot product ≈ 0)
-    pub are_perpendicular: bool,
-    /// Whether the vectors are parallel (angle ≈ 0° or 180°)
-    pub are_parallel: bool,
+// The generated result plus the angle in the requested unit
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DotProductResponse {
+    /// Angle between the vectors in the requested unit
+    pub angle: Quantity,
+    #[serde(flatten)]
+    pub result: DotProductResult,
 }
 
-impl From<Vector3D> for LogicVector3D {
-    fn from(v: Vector3D) -> Self {
-        LogicVector3D {
-            x: v.x,
-            y: v.y,
-            z: v.z,
+/// Calculate dot product of two 3D vectors
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn dot_product(request: Request<DotProductInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "dot_product",
+                DotProductInput,
+                DotProductResponse
+            ));
         }
-    }
-}
+    };
 
-impl From<DotProductInput> for LogicInput {
-    fn from(input: DotProductInput) -> Self {
-        LogicInput {
-            vector1: input.vector1.into(),
-            vector2: input.vector2.into(),
-        }
-    }
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("dot_product", &input);
+    log.finish(run_dot_product(input))
 }
 
-/// Calculate dot product of two 3D vectors
-#[cfg_attr(not(test), tool)]
-pub fn dot_product(input: DotProductInput) -> ToolResponse {
-    match dot_product_logic(input.into()) {
+#[cfg(feature = "individual")]
+fn run_dot_product(input: DotProductInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    match logic::dot_product_logic(input.params.into()) {
         Ok(logic_result) => {
-            let result = DotProductResult {
-                dot_product: logic_result.dot_product,
-                angle_radians: logic_result.angle_radians,
-                angle_degrees: logic_result.angle_degrees,
-                are_perpendicular: logic_result.are_perpendicular,
-                are_parallel: logic_result.are_parallel,
+            let response = DotProductResponse {
+                angle: Quantity::angle(logic_result.angle_radians, angle_unit),
+                result: logic_result.into(),
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,6 +1,9 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Clone, Debug, PartialEq)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Deserialize, Clone, Debug, PartialEq, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     /// X component of the vector
     pub x: f64,
@@ -10,20 +13,29 @@ pub struct Vector3D {
     pub z: f64,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Deserialize, Clone, Debug, ToolTypes)]
 pub struct DotProductInput {
     /// First 3D vector
+    #[tool_types(nested)]
     pub vector1: Vector3D,
     /// Second 3D vector
+    #[tool_types(nested)]
     pub vector2: Vector3D,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, ToolTypes)]
 pub struct DotProductResult {
+    /// The calculated dot product value
     pub dot_product: f64,
+    /// Angle between vectors in radians
     pub angle_radians: f64,
+    /// Angle between vectors in degrees
     pub angle_degrees: f64,
+    /// Whether the vectors are perpendicular (dot product ≈ 0)
     pub are_perpendicular: bool,
+    /// Whether the vectors are parallel (angle ≈ 0° or 180°)
     pub are_parallel: bool,
 }
 
@@ -326,4 +338,19 @@ mod tests {
         let angle = v1.angle_with(&v2).unwrap();
         assert!(angle.abs() < 1e-10); // Should be 0 for identical vectors
     }
+
+    #[test]
+    fn test_vectors_as_arrays_or_objects() {
+        let schema: DotProductInputSchema =
+            serde_json::from_str(r#"{"vector1": [1, 2, 3], "vector2": {"x": 4, "y": 5, "z": 6}}"#)
+                .unwrap();
+        let result = dot_product_logic(schema.into()).unwrap();
+        assert_eq!(result.dot_product, 32.0);
+
+        let err = serde_json::from_str::<DotProductInputSchema>(
+            r#"{"vector1": [1, 2], "vector2": [4, 5, 6]}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("expected 3 components"));
+    }
 }
@@ -0,0 +1,315 @@
+use ftl_matrix4::{axis_angle_rotation, quaternion_rotation};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+/// Rotation as a quaternion, or as an axis and angle
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Rotation {
+    /// Rotation as a quaternion, normalized before use
+    #[tool_types(nested)]
+    pub quaternion: Option<Quaternion>,
+    /// Rotation axis, normalized before use; give it with angle instead of quaternion
+    #[tool_types(nested)]
+    pub axis: Option<Vector3D>,
+    /// Rotation angle in radians about axis, counterclockwise looking down the axis
+    pub angle: Option<f64>,
+}
+
+/// Solid ellipsoid, axis-aligned or rotated
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Ellipsoid {
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    /// Semi-axis lengths along the ellipsoid's local x, y and z axes, all positive
+    #[tool_types(nested)]
+    pub radii: Vector3D,
+    /// Rotation taking the local axes to world space (default: axis-aligned)
+    #[tool_types(nested)]
+    pub rotation: Option<Rotation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Ray {
+    #[tool_types(nested)]
+    pub origin: Vector3D,
+    /// Direction of travel, normalized before use
+    #[tool_types(nested)]
+    pub direction: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EllipsoidRayInput {
+    #[tool_types(nested)]
+    pub ellipsoid: Ellipsoid,
+    #[tool_types(nested)]
+    pub ray: Ray,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct IntersectionPoint {
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Distance along the normalized ray direction
+    pub distance: f64,
+    /// Outward unit surface normal at the point
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EllipsoidRayResult {
+    pub intersects: bool,
+    /// Entry then exit point, skipping any behind the origin; a ray starting inside the
+    /// ellipsoid has only its exit, and a tangent ray a single point
+    #[tool_types(nested)]
+    pub intersection_points: Vec<IntersectionPoint>,
+    pub closest_distance: Option<f64>,
+}
+
+fn check_vector(name: &str, v: &Vector3D) -> Result<(), String> {
+    if v.x.is_nan() || v.y.is_nan() || v.z.is_nan() {
+        return Err(format!("{name} coordinates cannot contain NaN values"));
+    }
+    if v.x.is_infinite() || v.y.is_infinite() || v.z.is_infinite() {
+        return Err(format!("{name} coordinates cannot contain infinite values"));
+    }
+    Ok(())
+}
+
+fn rotation_matrix(rotation: &Option<Rotation>) -> Result<[[f64; 3]; 3], String> {
+    let Some(rotation) = rotation else {
+        return Ok([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+    };
+    match (&rotation.quaternion, &rotation.axis, rotation.angle) {
+        (Some(q), None, None) => quaternion_rotation([q.x, q.y, q.z, q.w]),
+        (None, Some(axis), Some(angle)) => axis_angle_rotation([axis.x, axis.y, axis.z], angle),
+        _ => Err("rotation needs either quaternion, or axis and angle".to_string()),
+    }
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn ellipsoid_ray_intersection_logic(
+    input: EllipsoidRayInput,
+) -> Result<EllipsoidRayResult, String> {
+    let ellipsoid = &input.ellipsoid;
+    check_vector("Center", &ellipsoid.center)?;
+    check_vector("Radii", &ellipsoid.radii)?;
+    let radii = [ellipsoid.radii.x, ellipsoid.radii.y, ellipsoid.radii.z];
+    if radii.iter().any(|r| *r <= 0.0) {
+        return Err("Radii must be positive".to_string());
+    }
+    let rotation = rotation_matrix(&ellipsoid.rotation)?;
+    check_vector("Ray origin", &input.ray.origin)?;
+    check_vector("Ray direction", &input.ray.direction)?;
+    let direction = [
+        input.ray.direction.x,
+        input.ray.direction.y,
+        input.ray.direction.z,
+    ];
+    let speed = dot(direction, direction).sqrt();
+    if speed == 0.0 {
+        return Err("Ray direction cannot be zero vector".to_string());
+    }
+
+    // Into local coordinates, then scaled so the ellipsoid becomes the unit sphere; the
+    // ray parameter is unchanged, so it stays the distance along the world ray
+    let center = [ellipsoid.center.x, ellipsoid.center.y, ellipsoid.center.z];
+    let offset = [
+        input.ray.origin.x - center[0],
+        input.ray.origin.y - center[1],
+        input.ray.origin.z - center[2],
+    ];
+    let to_local = |v: [f64; 3]| -> [f64; 3] {
+        [0, 1, 2].map(|i| (0..3).map(|j| rotation[j][i] * v[j]).sum())
+    };
+    let local_origin = to_local(offset);
+    let local_direction = to_local(direction.map(|v| v / speed));
+    let o = [0, 1, 2].map(|i| local_origin[i] / radii[i]);
+    let d = [0, 1, 2].map(|i| local_direction[i] / radii[i]);
+
+    // |o + t d|² = 1
+    let a = dot(d, d);
+    let b = dot(o, d);
+    let c = dot(o, o) - 1.0;
+    let discriminant = b * b - a * c;
+    // A grazing ray touches the surface once, a double root that rounding can push
+    // slightly negative
+    let tolerance = 1e-12 * (b * b).max((a * c).abs()).max(a);
+    let hits: Vec<f64> = if discriminant.abs() <= tolerance {
+        vec![-b / a]
+    } else if discriminant > 0.0 {
+        let root = discriminant.sqrt();
+        vec![(-b - root) / a, (-b + root) / a]
+    } else {
+        vec![]
+    };
+
+    let intersection_points: Vec<IntersectionPoint> = hits
+        .into_iter()
+        .filter(|t| *t >= 0.0)
+        .map(|t| {
+            let local = [0, 1, 2].map(|i| local_origin[i] + local_direction[i] * t);
+            let gradient = [0, 1, 2].map(|i| local[i] / (radii[i] * radii[i]));
+            let length = dot(gradient, gradient).sqrt();
+            let normal: [f64; 3] =
+                [0, 1, 2].map(|i| (0..3).map(|j| rotation[i][j] * gradient[j] / length).sum());
+            IntersectionPoint {
+                point: Vector3D {
+                    x: input.ray.origin.x + direction[0] / speed * t,
+                    y: input.ray.origin.y + direction[1] / speed * t,
+                    z: input.ray.origin.z + direction[2] / speed * t,
+                },
+                distance: t,
+                normal: Vector3D {
+                    x: normal[0],
+                    y: normal[1],
+                    z: normal[2],
+                },
+            }
+        })
+        .collect();
+
+    Ok(EllipsoidRayResult {
+        intersects: !intersection_points.is_empty(),
+        closest_distance: intersection_points.first().map(|p| p.distance),
+        intersection_points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    /// Ellipsoid centered at (0, 0, 1) with semi-axes 3, 2, 1
+    fn input(origin: Vector3D, direction: Vector3D) -> EllipsoidRayInput {
+        EllipsoidRayInput {
+            ellipsoid: Ellipsoid {
+                center: v(0.0, 0.0, 1.0),
+                radii: v(3.0, 2.0, 1.0),
+                rotation: None,
+            },
+            ray: Ray { origin, direction },
+        }
+    }
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        let d = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt();
+        assert!(d < EPSILON, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_hits_along_long_axis() {
+        let result =
+            ellipsoid_ray_intersection_logic(input(v(-5.0, 0.0, 1.0), v(2.0, 0.0, 0.0))).unwrap();
+        assert!(result.intersects);
+        let points = &result.intersection_points;
+        assert_eq!(points.len(), 2);
+        assert!((points[0].distance - 2.0).abs() < EPSILON);
+        assert!((points[1].distance - 8.0).abs() < EPSILON);
+        assert_close(points[0].point, v(-3.0, 0.0, 1.0));
+        assert_close(points[0].normal, v(-1.0, 0.0, 0.0));
+        assert_close(points[1].normal, v(1.0, 0.0, 0.0));
+        assert_eq!(result.closest_distance, Some(2.0));
+    }
+
+    #[test]
+    fn test_oblique_normal() {
+        // Straight down through x = 1.5: the surface point has z - 1 = ±√(1 - 1/4)
+        let result =
+            ellipsoid_ray_intersection_logic(input(v(1.5, 0.0, 5.0), v(0.0, 0.0, -1.0))).unwrap();
+        let top = &result.intersection_points[0];
+        let height = 0.75f64.sqrt();
+        assert!((top.point.z - (1.0 + height)).abs() < EPSILON);
+        // Gradient (x/a², 0, z/c²) = (1/6, 0, √0.75), normalized
+        let length = (1.0f64 / 36.0 + 0.75).sqrt();
+        assert_close(top.normal, v(1.0 / 6.0 / length, 0.0, height / length));
+    }
+
+    #[test]
+    fn test_misses_tangent_and_behind() {
+        let miss =
+            ellipsoid_ray_intersection_logic(input(v(0.0, 3.0, 1.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert!(!miss.intersects);
+        assert_eq!(miss.closest_distance, None);
+
+        let tangent =
+            ellipsoid_ray_intersection_logic(input(v(-5.0, 2.0, 1.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert_eq!(tangent.intersection_points.len(), 1);
+        assert_close(tangent.intersection_points[0].point, v(0.0, 2.0, 1.0));
+
+        let behind =
+            ellipsoid_ray_intersection_logic(input(v(5.0, 0.0, 1.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert!(!behind.intersects);
+    }
+
+    #[test]
+    fn test_origin_inside() {
+        let result =
+            ellipsoid_ray_intersection_logic(input(v(0.0, 0.0, 1.0), v(0.0, 1.0, 0.0))).unwrap();
+        assert_eq!(result.intersection_points.len(), 1);
+        assert!((result.intersection_points[0].distance - 2.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_rotated_ellipsoid() {
+        // A quarter turn about z points the long axis along world y
+        let mut request = input(v(0.0, -5.0, 1.0), v(0.0, 1.0, 0.0));
+        request.ellipsoid.rotation = Some(Rotation {
+            quaternion: Some(Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: std::f64::consts::FRAC_PI_4.sin(),
+                w: std::f64::consts::FRAC_PI_4.cos(),
+            }),
+            axis: None,
+            angle: None,
+        });
+        let result = ellipsoid_ray_intersection_logic(request).unwrap();
+        assert!((result.intersection_points[0].distance - 2.0).abs() < EPSILON);
+        assert_close(result.intersection_points[0].normal, v(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut request = input(v(0.0, 0.0, 0.0), v(1.0, 0.0, 0.0));
+        request.ellipsoid.radii = v(1.0, 1.0, 0.0);
+        assert_eq!(
+            ellipsoid_ray_intersection_logic(request).unwrap_err(),
+            "Radii must be positive"
+        );
+        assert_eq!(
+            ellipsoid_ray_intersection_logic(input(v(0.0, 0.0, 0.0), v(0.0, 0.0, 0.0)))
+                .unwrap_err(),
+            "Ray direction cannot be zero vector"
+        );
+    }
+}
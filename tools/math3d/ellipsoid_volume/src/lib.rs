@@ -0,0 +1,79 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    EllipsoidVolumeInput as LogicInput, EllipsoidVolumeResponse as LogicOutput,
+    Vector3D as LogicVector3D, compute_ellipsoid_volume,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    EllipsoidVolumeResponseSchema as EllipsoidVolumeResponse, QuaternionSchema as Quaternion,
+    RotationSchema as Rotation, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EllipsoidVolumeInput {
+    #[serde(flatten)]
+    pub params: logic::EllipsoidVolumeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Volume, surface area and world-space semi-axes of an ellipsoid given by center, radii
+/// and an optional rotation
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn ellipsoid_volume(request: Request<EllipsoidVolumeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "ellipsoid_volume",
+                EllipsoidVolumeInput,
+                EllipsoidVolumeResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("ellipsoid_volume", &input);
+    log.finish(run_ellipsoid_volume(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_ellipsoid_volume(input: EllipsoidVolumeInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match logic::compute_ellipsoid_volume(input.params.into()) {
+        Ok(logic_result) => {
+            let result = EllipsoidVolumeResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
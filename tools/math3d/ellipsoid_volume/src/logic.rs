@@ -0,0 +1,291 @@
+use ftl_matrix4::{axis_angle_rotation, quaternion_rotation};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+/// Rotation as a quaternion, or as an axis and angle
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Rotation {
+    /// Rotation as a quaternion, normalized before use
+    #[tool_types(nested)]
+    pub quaternion: Option<Quaternion>,
+    /// Rotation axis, normalized before use; give it with angle instead of quaternion
+    #[tool_types(nested)]
+    pub axis: Option<Vector3D>,
+    /// Rotation angle in radians about axis, counterclockwise looking down the axis
+    pub angle: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EllipsoidVolumeInput {
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    /// Semi-axis lengths along the ellipsoid's local x, y and z axes
+    #[tool_types(nested)]
+    pub radii: Vector3D,
+    /// Rotation taking the local axes to world space (default: axis-aligned)
+    #[tool_types(nested)]
+    pub rotation: Option<Rotation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EllipsoidVolumeResponse {
+    pub volume: f64,
+    pub calculation_method: String,
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    #[tool_types(nested)]
+    pub radii: Vector3D,
+    /// Exact surface area, from Carlson's elliptic integrals
+    pub surface_area: f64,
+    /// "sphere", "prolate spheroid", "oblate spheroid" or "triaxial ellipsoid"
+    pub shape: String,
+    /// World-space semi-axes: each local axis rotated and scaled by its radius
+    #[tool_types(nested)]
+    pub semi_axes: Vec<Vector3D>,
+}
+
+fn check_vector(name: &str, v: &Vector3D) -> Result<(), String> {
+    if v.x.is_nan() || v.y.is_nan() || v.z.is_nan() {
+        return Err(format!("{name} coordinates cannot contain NaN values"));
+    }
+    if v.x.is_infinite() || v.y.is_infinite() || v.z.is_infinite() {
+        return Err(format!("{name} coordinates cannot contain infinite values"));
+    }
+    Ok(())
+}
+
+fn rotation_matrix(rotation: &Option<Rotation>) -> Result<[[f64; 3]; 3], String> {
+    let Some(rotation) = rotation else {
+        return Ok([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+    };
+    match (&rotation.quaternion, &rotation.axis, rotation.angle) {
+        (Some(q), None, None) => quaternion_rotation([q.x, q.y, q.z, q.w]),
+        (None, Some(axis), Some(angle)) => axis_angle_rotation([axis.x, axis.y, axis.z], angle),
+        _ => Err("rotation needs either quaternion, or axis and angle".to_string()),
+    }
+}
+
+/// Carlson's symmetric integral R_F(x, y, z), by duplication
+fn carlson_rf(mut x: f64, mut y: f64, mut z: f64) -> f64 {
+    loop {
+        let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        x = 0.25 * (x + lambda);
+        y = 0.25 * (y + lambda);
+        z = 0.25 * (z + lambda);
+        let mean = (x + y + z) / 3.0;
+        let (dx, dy, dz) = ((mean - x) / mean, (mean - y) / mean, (mean - z) / mean);
+        if dx.abs().max(dy.abs()).max(dz.abs()) < 0.0025 {
+            let e2 = dx * dy - dz * dz;
+            let e3 = dx * dy * dz;
+            return (1.0 + (e2 / 24.0 - 0.1 - 3.0 * e3 / 44.0) * e2 + e3 / 14.0) / mean.sqrt();
+        }
+    }
+}
+
+/// Carlson's symmetric integral R_D(x, y, z), by duplication
+fn carlson_rd(mut x: f64, mut y: f64, mut z: f64) -> f64 {
+    let (mut sum, mut factor) = (0.0, 1.0);
+    loop {
+        let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        sum += factor / (sz * (z + lambda));
+        factor *= 0.25;
+        x = 0.25 * (x + lambda);
+        y = 0.25 * (y + lambda);
+        z = 0.25 * (z + lambda);
+        let mean = 0.2 * (x + y + 3.0 * z);
+        let (dx, dy, dz) = ((mean - x) / mean, (mean - y) / mean, (mean - z) / mean);
+        if dx.abs().max(dy.abs()).max(dz.abs()) < 0.0015 {
+            let ea = dx * dy;
+            let eb = dz * dz;
+            let ec = ea - eb;
+            let ed = ea - 6.0 * eb;
+            let ee = ed + ec + ec;
+            let series = 1.0
+                + ed * (-3.0 / 14.0 + 9.0 / 88.0 * ed - 9.0 / 52.0 * dz * ee)
+                + dz * (ee / 6.0 + dz * (-9.0 / 22.0 * ec + dz * 3.0 / 26.0 * ea));
+            return 3.0 * sum + factor * series / (mean * mean.sqrt());
+        }
+    }
+}
+
+/// Surface area of an ellipsoid with semi-axes a ≥ b ≥ c ≥ 0, through the incomplete
+/// elliptic integrals F(φ, k) and E(φ, k) with cos φ = c/a
+fn surface_area(a: f64, b: f64, c: f64) -> f64 {
+    if a == 0.0 || b == 0.0 {
+        return 0.0;
+    }
+    if c == 0.0 {
+        // Both faces of a flat ellipse
+        return 2.0 * PI * a * b;
+    }
+    if a == c {
+        return 4.0 * PI * a * a;
+    }
+    let cos_phi = c / a;
+    let sin_phi = (1.0 - cos_phi * cos_phi).sqrt();
+    let k2 = (a * a * (b * b - c * c)) / (b * b * (a * a - c * c));
+    let (x, y) = (cos_phi * cos_phi, 1.0 - k2 * sin_phi * sin_phi);
+    let rf = carlson_rf(x, y, 1.0);
+    let f = sin_phi * rf;
+    let e = f - k2 * sin_phi.powi(3) / 3.0 * carlson_rd(x, y, 1.0);
+    2.0 * PI * c * c + 2.0 * PI * a * b / sin_phi * (e * sin_phi * sin_phi + f * cos_phi * cos_phi)
+}
+
+pub fn compute_ellipsoid_volume(
+    input: EllipsoidVolumeInput,
+) -> Result<EllipsoidVolumeResponse, String> {
+    check_vector("Center", &input.center)?;
+    check_vector("Radii", &input.radii)?;
+    let radii = [input.radii.x, input.radii.y, input.radii.z];
+    if radii.iter().any(|r| *r < 0.0) {
+        return Err("Radii cannot be negative".to_string());
+    }
+    let rotation = rotation_matrix(&input.rotation)?;
+
+    let mut sorted = radii;
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let [a, b, c] = sorted;
+    let shape = if a == c {
+        "sphere"
+    } else if a == b {
+        "oblate spheroid"
+    } else if b == c {
+        "prolate spheroid"
+    } else {
+        "triaxial ellipsoid"
+    };
+
+    // Volume = (4/3) * π * a * b * c
+    let volume = (4.0 / 3.0) * PI * a * b * c;
+
+    // Column i of the rotation is local axis i in world space
+    let semi_axes = (0..3)
+        .map(|i| Vector3D {
+            x: rotation[0][i] * radii[i],
+            y: rotation[1][i] * radii[i],
+            z: rotation[2][i] * radii[i],
+        })
+        .collect();
+
+    Ok(EllipsoidVolumeResponse {
+        volume,
+        calculation_method: "Ellipsoid formula: (4/3)πabc".to_string(),
+        center: input.center,
+        radii: input.radii,
+        surface_area: surface_area(a, b, c),
+        shape: shape.to_string(),
+        semi_axes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn input(radii: Vector3D) -> EllipsoidVolumeInput {
+        EllipsoidVolumeInput {
+            center: v(1.0, 2.0, 3.0),
+            radii,
+            rotation: None,
+        }
+    }
+
+    #[test]
+    fn test_sphere() {
+        let result = compute_ellipsoid_volume(input(v(2.0, 2.0, 2.0))).unwrap();
+        assert!((result.volume - 32.0 / 3.0 * PI).abs() < 1e-12);
+        assert!((result.surface_area - 16.0 * PI).abs() < 1e-12);
+        assert_eq!(result.shape, "sphere");
+        assert_eq!(result.calculation_method, "Ellipsoid formula: (4/3)πabc");
+    }
+
+    #[test]
+    fn test_spheroid_areas() {
+        // Oblate: 2πa²(1 + (1 - e²)/e · atanh e), e² = 1 - c²/a²
+        let result = compute_ellipsoid_volume(input(v(2.0, 1.0, 2.0))).unwrap();
+        let e = (1.0f64 - 0.25).sqrt();
+        let expected = 2.0 * PI * 4.0 * (1.0 + (1.0 - e * e) / e * e.atanh());
+        assert!((result.surface_area - expected).abs() < 1e-10);
+        assert_eq!(result.shape, "oblate spheroid");
+
+        // Prolate: 2πb²(1 + a/(b e) · asin e), e² = 1 - b²/a²
+        let result = compute_ellipsoid_volume(input(v(1.0, 1.0, 3.0))).unwrap();
+        let e = (1.0f64 - 1.0 / 9.0).sqrt();
+        let expected = 2.0 * PI * (1.0 + 3.0 / e * e.asin());
+        assert!((result.surface_area - expected).abs() < 1e-10);
+        assert!((result.volume - 4.0 * PI).abs() < 1e-12);
+        assert_eq!(result.shape, "prolate spheroid");
+    }
+
+    #[test]
+    fn test_triaxial_area() {
+        // Reference value for semi-axes 3, 2, 1
+        let result = compute_ellipsoid_volume(input(v(1.0, 3.0, 2.0))).unwrap();
+        assert!((result.surface_area - 48.882_146_302_582_1).abs() < 1e-9);
+        assert!((result.volume - 8.0 * PI).abs() < 1e-12);
+        assert_eq!(result.shape, "triaxial ellipsoid");
+    }
+
+    #[test]
+    fn test_rotated_semi_axes() {
+        let mut request = input(v(3.0, 1.0, 1.0));
+        request.rotation = Some(Rotation {
+            quaternion: None,
+            axis: Some(v(0.0, 0.0, 1.0)),
+            angle: Some(std::f64::consts::FRAC_PI_2),
+        });
+        let result = compute_ellipsoid_volume(request).unwrap();
+        let long = result.semi_axes[0];
+        assert!(long.x.abs() < 1e-12 && (long.y - 3.0).abs() < 1e-12);
+        assert!((result.semi_axes[1].x + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_degenerate_and_invalid() {
+        let flat = compute_ellipsoid_volume(input(v(2.0, 1.0, 0.0))).unwrap();
+        assert_eq!(flat.volume, 0.0);
+        assert!((flat.surface_area - 4.0 * PI).abs() < 1e-12);
+
+        assert_eq!(
+            compute_ellipsoid_volume(input(v(1.0, -1.0, 1.0))).unwrap_err(),
+            "Radii cannot be negative"
+        );
+        let mut request = input(v(1.0, 1.0, 1.0));
+        request.rotation = Some(Rotation {
+            quaternion: None,
+            axis: Some(v(0.0, 0.0, 1.0)),
+            angle: None,
+        });
+        assert_eq!(
+            compute_ellipsoid_volume(request).unwrap_err(),
+            "rotation needs either quaternion, or axis and angle"
+        );
+    }
+}
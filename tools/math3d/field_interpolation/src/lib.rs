@@ -0,0 +1,97 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    FieldInterpolationInput as LogicInput, FieldInterpolationOutput as LogicOutput, MAX_POINTS,
+    MAX_SAMPLES, Vector3D as LogicVector3D, interpolate_field,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    FieldInterpolationOutputSchema as FieldInterpolationOutput, FieldSampleSchema as FieldSample,
+    GridSchema as Grid, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FieldInterpolationInput {
+    #[serde(flatten)]
+    pub params: logic::FieldInterpolationInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Trilinear interpolation of a scalar or vector field sampled on a regular 3D grid,
+/// with the gradient, or the jacobian, divergence and curl, at each query point
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn field_interpolation(request: Request<FieldInterpolationInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "field_interpolation",
+                FieldInterpolationInput,
+                FieldInterpolationOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("field_interpolation", &input);
+    log.finish(run_field_interpolation(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_field_interpolation(input: FieldInterpolationInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array(
+            "scalars",
+            input.params.scalars.as_deref().unwrap_or_default(),
+        )
+        .array(
+            "vectors",
+            input.params.vectors.as_deref().unwrap_or_default(),
+        )
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::interpolate_field(input.params.into()) {
+        Ok(logic_result) => {
+            let result = FieldInterpolationOutput::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,531 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most grid samples in one field
+pub const MAX_SAMPLES: usize = 1_000_000;
+/// Most query points in one call
+pub const MAX_POINTS: usize = 100_000;
+/// Query points this far outside the grid, in cells, still count as on its boundary
+const BOUNDARY_TOLERANCE: f64 = 1e-9;
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+/// Regular grid whose sample (i, j, k) sits at origin + (i·spacing.x, j·spacing.y, k·spacing.z)
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Grid {
+    /// Position of sample (0, 0, 0)
+    #[tool_types(nested)]
+    pub origin: Vector3D,
+    /// Distance between neighboring samples along each axis, positive
+    #[tool_types(nested)]
+    pub spacing: Vector3D,
+    /// Samples along x, y and z, each at least 1
+    pub counts: [usize; 3],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FieldInterpolationInput {
+    #[tool_types(nested)]
+    pub grid: Grid,
+    /// Scalar samples with x varying fastest, then y, then z: sample (i, j, k) is at
+    /// index i + nx·(j + ny·k); give either scalars or vectors
+    pub scalars: Option<Vec<f64>>,
+    /// Vector samples in the same order as scalars; give either scalars or vectors
+    #[tool_types(nested)]
+    pub vectors: Option<Vec<Vector3D>>,
+    /// Points to interpolate at
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Points outside the grid: "null" (no value) or "clamp" (value at the nearest
+    /// boundary point) (default: null)
+    pub out_of_bounds: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FieldSample {
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Whether the point lies within the grid
+    pub inside: bool,
+    /// Interpolated value (scalar fields only)
+    pub value: Option<f64>,
+    /// Gradient of the interpolated value (scalar fields only)
+    #[tool_types(nested)]
+    pub gradient: Option<Vector3D>,
+    /// Interpolated vector (vector fields only)
+    #[tool_types(nested)]
+    pub vector: Option<Vector3D>,
+    /// Rows are the x, y and z components, columns their derivatives along x, y and z
+    /// (vector fields only)
+    pub jacobian: Option<[[f64; 3]; 3]>,
+    /// Trace of the jacobian (vector fields only)
+    pub divergence: Option<f64>,
+    /// (∂vz/∂y − ∂vy/∂z, ∂vx/∂z − ∂vz/∂x, ∂vy/∂x − ∂vx/∂y) (vector fields only)
+    #[tool_types(nested)]
+    pub curl: Option<Vector3D>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FieldInterpolationOutput {
+    /// "scalar" or "vector"
+    pub field: String,
+    /// One sample per query point, in input order
+    #[tool_types(nested)]
+    pub samples: Vec<FieldSample>,
+    /// Corner of the grid with the smallest coordinates
+    #[tool_types(nested)]
+    pub bounds_min: Vector3D,
+    /// Corner of the grid with the largest coordinates
+    #[tool_types(nested)]
+    pub bounds_max: Vector3D,
+    pub inside_count: usize,
+    pub outside_count: usize,
+}
+
+impl Vector3D {
+    fn component(&self, axis: usize) -> f64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    fn from_array(a: [f64; 3]) -> Vector3D {
+        Vector3D {
+            x: a[0],
+            y: a[1],
+            z: a[2],
+        }
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+/// Where a query falls along one axis: the two samples to blend, the weight of the
+/// second, and whether the axis has a second sample to differentiate against
+#[derive(Debug, Clone, Copy)]
+struct AxisCell {
+    low: usize,
+    high: usize,
+    fraction: f64,
+    varies: bool,
+}
+
+/// Locates a coordinate along one axis; None when it is outside and not clamped
+fn locate(
+    coordinate: f64,
+    origin: f64,
+    spacing: f64,
+    count: usize,
+    clamp: bool,
+) -> Option<AxisCell> {
+    let position = (coordinate - origin) / spacing;
+    let last = (count - 1) as f64;
+    let outside = position < -BOUNDARY_TOLERANCE || position > last + BOUNDARY_TOLERANCE;
+    if outside && !clamp {
+        return None;
+    }
+    if count == 1 {
+        return Some(AxisCell {
+            low: 0,
+            high: 0,
+            fraction: 0.0,
+            varies: false,
+        });
+    }
+    let position = position.clamp(0.0, last);
+    // Points on the far face use the last cell rather than one past the end
+    let low = (position.floor() as usize).min(count - 2);
+    Some(AxisCell {
+        low,
+        high: low + 1,
+        fraction: position - low as f64,
+        varies: true,
+    })
+}
+
+/// Trilinear value of one component and its derivatives along x, y and z
+fn trilinear(
+    cells: &[AxisCell; 3],
+    spacing: [f64; 3],
+    sample: impl Fn(usize, usize, usize) -> f64,
+) -> (f64, [f64; 3]) {
+    let mut value = 0.0;
+    let mut derivatives = [0.0; 3];
+    for corner in 0..8 {
+        let upper = [corner & 1 != 0, corner & 2 != 0, corner & 4 != 0];
+        let index: [usize; 3] = std::array::from_fn(|a| {
+            if upper[a] {
+                cells[a].high
+            } else {
+                cells[a].low
+            }
+        });
+        let weight: [f64; 3] = std::array::from_fn(|a| {
+            if upper[a] {
+                cells[a].fraction
+            } else {
+                1.0 - cells[a].fraction
+            }
+        });
+        let v = sample(index[0], index[1], index[2]);
+        value += weight[0] * weight[1] * weight[2] * v;
+        for axis in 0..3 {
+            if !cells[axis].varies {
+                continue;
+            }
+            // The weight along this axis changes by ±1 per cell
+            let slope = if upper[axis] { 1.0 } else { -1.0 };
+            let others: f64 = (0..3).filter(|&a| a != axis).map(|a| weight[a]).product();
+            derivatives[axis] += slope * others * v / spacing[axis];
+        }
+    }
+    (value, derivatives)
+}
+
+pub fn interpolate_field(
+    input: FieldInterpolationInput,
+) -> Result<FieldInterpolationOutput, String> {
+    let grid = &input.grid;
+    let counts = grid.counts;
+    if counts.contains(&0) {
+        return Err("Grid counts must each be at least 1".to_string());
+    }
+    let total = counts
+        .iter()
+        .try_fold(1usize, |acc, &n| acc.checked_mul(n))
+        .filter(|&n| n <= MAX_SAMPLES)
+        .ok_or_else(|| format!("At most {MAX_SAMPLES} grid samples are allowed"))?;
+    if !grid.origin.is_finite() {
+        return Err("Grid origin coordinates must be finite".to_string());
+    }
+    let spacing = [grid.spacing.x, grid.spacing.y, grid.spacing.z];
+    if spacing.iter().any(|s| !(s.is_finite() && *s > 0.0)) {
+        return Err("Grid spacing must be positive and finite along every axis".to_string());
+    }
+    let clamp = match input
+        .out_of_bounds
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("null") => false,
+        Some("clamp") => true,
+        Some(other) => {
+            return Err(format!(
+                "Unsupported out_of_bounds: {other}. Use 'null' or 'clamp'"
+            ));
+        }
+    };
+    let length = match (&input.scalars, &input.vectors) {
+        (Some(_), Some(_)) => return Err("Give either scalars or vectors, not both".to_string()),
+        (None, None) => return Err("Give scalars or vectors to interpolate".to_string()),
+        (Some(scalars), None) => {
+            if scalars.iter().any(|v| !v.is_finite()) {
+                return Err("Scalar samples must be finite".to_string());
+            }
+            scalars.len()
+        }
+        (None, Some(vectors)) => {
+            if vectors.iter().any(|v| !v.is_finite()) {
+                return Err("Vector samples must be finite".to_string());
+            }
+            vectors.len()
+        }
+    };
+    if length != total {
+        return Err(format!(
+            "Grid of {}×{}×{} needs {total} samples, got {length}",
+            counts[0], counts[1], counts[2]
+        ));
+    }
+    if input.points.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} points are allowed"));
+    }
+    if input.points.iter().any(|p| !p.is_finite()) {
+        return Err("Point coordinates must be finite".to_string());
+    }
+
+    let at = |i: usize, j: usize, k: usize| i + counts[0] * (j + counts[1] * k);
+    let mut inside_count = 0;
+    let mut samples = Vec::with_capacity(input.points.len());
+    for &point in &input.points {
+        let locate_axis = |axis: usize, clamp: bool| {
+            locate(
+                point.component(axis),
+                grid.origin.component(axis),
+                spacing[axis],
+                counts[axis],
+                clamp,
+            )
+        };
+        let inside = (0..3).all(|axis| locate_axis(axis, false).is_some());
+        let mut sample = FieldSample {
+            point,
+            inside,
+            value: None,
+            gradient: None,
+            vector: None,
+            jacobian: None,
+            divergence: None,
+            curl: None,
+        };
+        if inside {
+            inside_count += 1;
+        } else if !clamp {
+            samples.push(sample);
+            continue;
+        }
+        let cells: [AxisCell; 3] = std::array::from_fn(|axis| {
+            locate_axis(axis, true).expect("clamped lookups always find a cell")
+        });
+        if let Some(scalars) = &input.scalars {
+            let (value, gradient) = trilinear(&cells, spacing, |i, j, k| scalars[at(i, j, k)]);
+            sample.value = Some(value);
+            sample.gradient = Some(Vector3D::from_array(gradient));
+        } else if let Some(vectors) = &input.vectors {
+            let mut vector = [0.0; 3];
+            let mut jacobian = [[0.0; 3]; 3];
+            for component in 0..3 {
+                let (value, row) = trilinear(&cells, spacing, |i, j, k| {
+                    vectors[at(i, j, k)].component(component)
+                });
+                vector[component] = value;
+                jacobian[component] = row;
+            }
+            sample.vector = Some(Vector3D::from_array(vector));
+            sample.divergence = Some(jacobian[0][0] + jacobian[1][1] + jacobian[2][2]);
+            sample.curl = Some(Vector3D {
+                x: jacobian[2][1] - jacobian[1][2],
+                y: jacobian[0][2] - jacobian[2][0],
+                z: jacobian[1][0] - jacobian[0][1],
+            });
+            sample.jacobian = Some(jacobian);
+        }
+        samples.push(sample);
+    }
+
+    let extent: [f64; 3] = std::array::from_fn(|a| (counts[a] - 1) as f64 * spacing[a]);
+    Ok(FieldInterpolationOutput {
+        field: if input.scalars.is_some() {
+            "scalar"
+        } else {
+            "vector"
+        }
+        .to_string(),
+        outside_count: samples.len() - inside_count,
+        inside_count,
+        samples,
+        bounds_min: grid.origin,
+        bounds_max: Vector3D {
+            x: grid.origin.x + extent[0],
+            y: grid.origin.y + extent[1],
+            z: grid.origin.z + extent[2],
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn grid(counts: [usize; 3]) -> Grid {
+        Grid {
+            origin: v(-1.0, 0.0, 2.0),
+            spacing: v(0.5, 1.0, 2.0),
+            counts,
+        }
+    }
+
+    /// Positions of a grid's samples in storage order
+    fn sample_positions(grid: &Grid) -> Vec<Vector3D> {
+        let [nx, ny, nz] = grid.counts;
+        let mut positions = Vec::new();
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    positions.push(v(
+                        grid.origin.x + i as f64 * grid.spacing.x,
+                        grid.origin.y + j as f64 * grid.spacing.y,
+                        grid.origin.z + k as f64 * grid.spacing.z,
+                    ));
+                }
+            }
+        }
+        positions
+    }
+
+    fn scalar_input(
+        grid: Grid,
+        f: impl Fn(&Vector3D) -> f64,
+        points: Vec<Vector3D>,
+    ) -> FieldInterpolationInput {
+        FieldInterpolationInput {
+            scalars: Some(sample_positions(&grid).iter().map(f).collect()),
+            vectors: None,
+            grid,
+            points,
+            out_of_bounds: None,
+        }
+    }
+
+    fn assert_vector(a: Vector3D, b: Vector3D) {
+        assert!(
+            (a.x - b.x).abs() < EPSILON
+                && (a.y - b.y).abs() < EPSILON
+                && (a.z - b.z).abs() < EPSILON,
+            "expected {b:?}, got {a:?}"
+        );
+    }
+
+    #[test]
+    fn test_linear_field_is_reproduced() {
+        // Trilinear interpolation is exact for linear fields, gradient included
+        let f = |p: &Vector3D| 2.0 * p.x + 3.0 * p.y - p.z + 1.0;
+        let points = vec![
+            v(-0.8, 0.3, 2.5),
+            v(0.2, 1.7, 5.9),
+            v(0.5, 2.0, 6.0),
+            v(-1.0, 0.0, 2.0),
+        ];
+        let result = interpolate_field(scalar_input(grid([4, 3, 3]), f, points.clone())).unwrap();
+        assert_eq!(result.field, "scalar");
+        assert_eq!(result.inside_count, 4);
+        for (sample, point) in result.samples.iter().zip(&points) {
+            assert!((sample.value.unwrap() - f(point)).abs() < EPSILON);
+            assert_vector(sample.gradient.unwrap(), v(2.0, 3.0, -1.0));
+            assert!(sample.vector.is_none());
+        }
+        assert_vector(result.bounds_max, v(0.5, 2.0, 6.0));
+    }
+
+    #[test]
+    fn test_cell_centre_and_bilinear_terms() {
+        // f = xyz on the unit cube: 1 at (1, 1, 1), 0 at every other corner
+        let unit = Grid {
+            origin: v(0.0, 0.0, 0.0),
+            spacing: v(1.0, 1.0, 1.0),
+            counts: [2, 2, 2],
+        };
+        let input = scalar_input(
+            unit,
+            |p| p.x * p.y * p.z,
+            vec![v(0.5, 0.5, 0.5), v(0.25, 0.5, 1.0)],
+        );
+        let result = interpolate_field(input).unwrap();
+        let centre = &result.samples[0];
+        assert!((centre.value.unwrap() - 0.125).abs() < EPSILON);
+        assert_vector(centre.gradient.unwrap(), v(0.25, 0.25, 0.25));
+        let off = &result.samples[1];
+        assert!((off.value.unwrap() - 0.125).abs() < EPSILON);
+        assert_vector(off.gradient.unwrap(), v(0.5, 0.25, 0.125));
+    }
+
+    #[test]
+    fn test_vector_field_derivatives() {
+        // v = (-y, x, 2z): divergence 2 and curl (0, 0, 2) everywhere
+        let g = grid([3, 3, 3]);
+        let vectors = sample_positions(&g)
+            .iter()
+            .map(|p| v(-p.y, p.x, 2.0 * p.z))
+            .collect();
+        let result = interpolate_field(FieldInterpolationInput {
+            grid: g,
+            scalars: None,
+            vectors: Some(vectors),
+            points: vec![v(-0.3, 1.2, 4.4)],
+            out_of_bounds: None,
+        })
+        .unwrap();
+        assert_eq!(result.field, "vector");
+        let sample = &result.samples[0];
+        assert_vector(sample.vector.unwrap(), v(-1.2, -0.3, 8.8));
+        assert_eq!(
+            sample.jacobian.unwrap().map(|row| row.map(|d| d.round())),
+            [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]]
+        );
+        assert!((sample.divergence.unwrap() - 2.0).abs() < EPSILON);
+        assert_vector(sample.curl.unwrap(), v(0.0, 0.0, 2.0));
+        assert!(sample.value.is_none() && sample.gradient.is_none());
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let f = |p: &Vector3D| p.x + p.y + p.z;
+        let points = vec![v(-2.0, 1.0, 3.0), v(0.0, 1.0, 3.0)];
+        let result = interpolate_field(scalar_input(grid([4, 3, 3]), f, points.clone())).unwrap();
+        assert!(!result.samples[0].inside);
+        assert!(result.samples[0].value.is_none());
+        assert_eq!((result.inside_count, result.outside_count), (1, 1));
+
+        let mut input = scalar_input(grid([4, 3, 3]), f, points);
+        input.out_of_bounds = Some("clamp".to_string());
+        let result = interpolate_field(input).unwrap();
+        // Clamped to x = -1 on the grid's near face
+        assert!(!result.samples[0].inside);
+        assert!((result.samples[0].value.unwrap() - 3.0).abs() < EPSILON);
+        assert_eq!(result.outside_count, 1);
+    }
+
+    #[test]
+    fn test_single_sample_axis() {
+        // A one-layer grid interpolates bilinearly and has no z derivative
+        let f = |p: &Vector3D| p.x * p.y;
+        let points = vec![v(-0.25, 0.5, 2.0), v(-0.25, 0.5, 2.5)];
+        let result = interpolate_field(scalar_input(grid([3, 2, 1]), f, points)).unwrap();
+        let sample = &result.samples[0];
+        assert!((sample.value.unwrap() - -0.125).abs() < EPSILON);
+        assert_vector(sample.gradient.unwrap(), v(0.5, -0.25, 0.0));
+        // Off the layer is outside the grid
+        assert!(!result.samples[1].inside);
+        assert_eq!(result.bounds_min.z, result.bounds_max.z);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let f = |p: &Vector3D| p.x;
+        let err = |input: FieldInterpolationInput| interpolate_field(input).unwrap_err();
+
+        let mut input = scalar_input(grid([2, 2, 2]), f, vec![]);
+        input.scalars.as_mut().unwrap().pop();
+        assert!(err(input).contains("needs 8 samples, got 7"));
+
+        let mut input = scalar_input(grid([2, 2, 2]), f, vec![]);
+        input.vectors = Some(vec![v(0.0, 0.0, 0.0); 8]);
+        assert!(err(input).contains("not both"));
+
+        let mut input = scalar_input(grid([2, 2, 2]), f, vec![]);
+        input.grid.spacing.y = 0.0;
+        assert!(err(input).contains("spacing"));
+
+        let mut input = scalar_input(grid([2, 2, 2]), f, vec![]);
+        input.grid.counts[2] = 0;
+        assert!(err(input).contains("at least 1"));
+
+        let mut input = scalar_input(grid([2, 2, 2]), f, vec![]);
+        input.out_of_bounds = Some("wrap".to_string());
+        assert!(err(input).contains("Unsupported out_of_bounds"));
+
+        let mut input = scalar_input(grid([2, 2, 2]), f, vec![]);
+        input.scalars.as_mut().unwrap()[3] = f64::NAN;
+        assert!(err(input).contains("finite"));
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    DEFAULT_ITERATIONS, FitPlaneInput as LogicInput, FitPlaneResult as LogicOutput, MAX_ITERATIONS,
+    MAX_POINTS, fit_plane_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    FitPlaneResultSchema as FitPlaneResult, Plane3DSchema as Plane3D, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FitPlaneInput {
+    #[serde(flatten)]
+    pub params: logic::FitPlaneInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Fit a plane to 3D points by least squares (PCA) or robustly with RANSAC, returning the
+/// plane as point and unit normal, the fit error, each point's signed distance and, given
+/// a distance threshold, which points are inliers
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn fit_plane(request: Request<FitPlaneInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("fit_plane", FitPlaneInput, FitPlaneResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("fit_plane", &input);
+    log.finish(run_fit_plane(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_fit_plane(input: FitPlaneInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::fit_plane_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = FitPlaneResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,455 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most points fitted in one call
+pub const MAX_POINTS: usize = 100_000;
+/// Default RANSAC iterations
+pub const DEFAULT_ITERATIONS: usize = 500;
+/// Most RANSAC iterations in one call
+pub const MAX_ITERATIONS: usize = 100_000;
+/// Jacobi sweeps before giving up; 3x3 matrices converge in well under ten
+const MAX_SWEEPS: usize = 50;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Plane3D {
+    /// Centroid of the points the plane was fitted to
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Unit normal, with its largest component positive
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FitPlaneInput {
+    /// Points measured on or near the plane; at least 3, not all collinear
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// "pca" minimizes the sum of squared perpendicular distances over every point;
+    /// "ransac" finds the plane with the most points within threshold, then refits it to
+    /// those inliers, ignoring outliers (default: pca)
+    pub method: Option<String>,
+    /// Largest distance from the plane at which a point counts as an inlier; required
+    /// for ransac, optional for pca
+    pub threshold: Option<f64>,
+    /// RANSAC: random three-point candidate planes to try, at most 100000 (default: 500)
+    pub iterations: Option<usize>,
+    /// RANSAC: seed for reproducible sampling (default: random)
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FitPlaneResult {
+    #[tool_types(nested)]
+    pub plane: Plane3D,
+    /// [a, b, c, d] with ax + by + cz + d = 0 and (a, b, c) the unit normal
+    pub coefficients: [f64; 4],
+    /// Root mean square distance of the fitted points from the plane
+    pub rms_error: f64,
+    /// Largest distance of a fitted point from the plane
+    pub max_abs_error: f64,
+    /// Signed distance of every point from the plane, positive on the normal's side, in
+    /// input order
+    pub distances: Vec<f64>,
+    /// Indices of points within threshold of the plane; absent without a threshold
+    pub inliers: Option<Vec<usize>>,
+    /// Indices of points farther than threshold; absent without a threshold
+    pub outliers: Option<Vec<usize>>,
+    pub method: String,
+    /// RANSAC: candidate planes tried
+    pub iterations: Option<usize>,
+    /// RANSAC: seed used, to reproduce the same fit
+    pub seed: Option<u64>,
+}
+
+/// SplitMix64, so a seed gives the same samples on every platform
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, bound)
+    fn below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next_u64()) * bound as u128) >> 64) as usize
+    }
+}
+
+fn components(v: &Vector3D) -> [f64; 3] {
+    [v.x, v.y, v.z]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn centroid(points: &[[f64; 3]]) -> [f64; 3] {
+    let n = points.len() as f64;
+    std::array::from_fn(|k| points.iter().map(|p| p[k]).sum::<f64>() / n)
+}
+
+/// Eigenvalues of a symmetric 3x3 matrix by cyclic Jacobi rotations, smallest first,
+/// with the matching unit eigenvectors
+fn symmetric_eigen(mut a: [[f64; 3]; 3]) -> Option<([f64; 3], [[f64; 3]; 3])> {
+    let scale = a.iter().flatten().fold(0.0f64, |m, v| m.max(v.abs()));
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let mut sweeps = 0;
+    while a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2) > (f64::EPSILON * scale).powi(2) {
+        if sweeps == MAX_SWEEPS {
+            return None;
+        }
+        sweeps += 1;
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0.0 {
+                continue;
+            }
+            // Rotation in the p-q plane that zeroes a[p][q]
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            for row in a.iter_mut().chain(v.iter_mut()) {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+            let (row_p, row_q) = (a[p], a[q]);
+            for k in 0..3 {
+                a[p][k] = c * row_p[k] - s * row_q[k];
+                a[q][k] = s * row_p[k] + c * row_q[k];
+            }
+            a[p][q] = 0.0;
+            a[q][p] = 0.0;
+        }
+    }
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+    Some((
+        order.map(|i| a[i][i]),
+        order.map(|i| [v[0][i], v[1][i], v[2][i]]),
+    ))
+}
+
+/// Total least squares plane through the points: their centroid and the direction of
+/// least spread, or None when the points are (nearly) collinear
+fn pca_plane(points: &[[f64; 3]]) -> Option<([f64; 3], [f64; 3])> {
+    let center = centroid(points);
+    let mut scatter = [[0.0; 3]; 3];
+    for p in points {
+        let d = sub(*p, center);
+        for i in 0..3 {
+            for j in 0..3 {
+                scatter[i][j] += d[i] * d[j];
+            }
+        }
+    }
+    let (values, vectors) = symmetric_eigen(scatter)?;
+    // A line spreads along one direction only, leaving the plane's tilt undetermined
+    if values[1] <= 1e-12 * values[2] {
+        return None;
+    }
+    Some((center, vectors[0]))
+}
+
+/// Unit normal with its largest component positive, so equal planes compare equal
+fn canonical(normal: [f64; 3]) -> [f64; 3] {
+    let length = dot(normal, normal).sqrt();
+    let largest = normal
+        .iter()
+        .copied()
+        .fold(0.0_f64, |max, c| if c.abs() > max.abs() { c } else { max });
+    let sign = if largest < 0.0 { -1.0 } else { 1.0 };
+    normal.map(|c| sign * c / length)
+}
+
+fn inliers(points: &[[f64; 3]], center: [f64; 3], normal: [f64; 3], threshold: f64) -> Vec<usize> {
+    (0..points.len())
+        .filter(|&i| dot(sub(points[i], center), normal).abs() <= threshold)
+        .collect()
+}
+
+pub fn fit_plane_logic(input: FitPlaneInput) -> Result<FitPlaneResult, String> {
+    if input.points.len() < 3 {
+        return Err("At least 3 points are required to fit a plane".to_string());
+    }
+    if input.points.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} points can be fitted at once"));
+    }
+    if let Some(index) = input
+        .points
+        .iter()
+        .position(|p| !components(p).iter().all(|v| v.is_finite()))
+    {
+        return Err(format!("Point {index} contains NaN or infinite values"));
+    }
+    let method = input
+        .method
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .unwrap_or_else(|| "pca".to_string());
+    if method != "pca" && method != "ransac" {
+        return Err(format!("Unknown method '{method}', use 'pca' or 'ransac'"));
+    }
+    if input
+        .threshold
+        .is_some_and(|t| !(t.is_finite() && t >= 0.0))
+    {
+        return Err("threshold must be a non-negative number".to_string());
+    }
+    if method == "pca" && (input.iterations.is_some() || input.seed.is_some()) {
+        return Err("iterations and seed only apply to ransac".to_string());
+    }
+
+    let points: Vec<[f64; 3]> = input.points.iter().map(components).collect();
+    let collinear = "Points are collinear or coincide, so they do not determine a plane";
+    let (mut center, mut normal) = pca_plane(&points).ok_or(collinear)?;
+
+    let (fitted, iterations, seed) = if method == "ransac" {
+        let threshold = input
+            .threshold
+            .ok_or("ransac requires a threshold distance")?;
+        let iterations = input.iterations.unwrap_or(DEFAULT_ITERATIONS);
+        if iterations == 0 || iterations > MAX_ITERATIONS {
+            return Err(format!("iterations must be between 1 and {MAX_ITERATIONS}"));
+        }
+        let seed = input.seed.unwrap_or_else(rand::random);
+        let mut rng = SplitMix64(seed);
+        let n = points.len();
+        // Squared extent of the cloud; shorter candidate normals come from (nearly)
+        // collinear samples
+        let extent = points
+            .iter()
+            .map(|p| dot(sub(*p, center), sub(*p, center)))
+            .fold(0.0, f64::max);
+        let mut best: (Vec<usize>, [f64; 3], [f64; 3]) = (Vec::new(), center, normal);
+        for _ in 0..iterations {
+            let [a, b, c] = [rng.below(n), rng.below(n), rng.below(n)];
+            if a == b || b == c || a == c {
+                continue;
+            }
+            let candidate = cross(sub(points[b], points[a]), sub(points[c], points[a]));
+            let length = dot(candidate, candidate).sqrt();
+            if length <= 1e-12 * extent {
+                continue;
+            }
+            let candidate = candidate.map(|v| v / length);
+            let found = inliers(&points, points[a], candidate, threshold);
+            if found.len() > best.0.len() {
+                best = (found, points[a], candidate);
+            }
+        }
+        if best.0.len() < 3 {
+            return Err(format!(
+                "No sampled plane had 3 or more points within the threshold after \
+                 {iterations} iterations"
+            ));
+        }
+        // Refit to the consensus set by least squares, keeping the sampled plane if the
+        // consensus is (nearly) a line
+        let consensus: Vec<[f64; 3]> = best.0.iter().map(|&i| points[i]).collect();
+        (center, normal) = pca_plane(&consensus).unwrap_or((best.1, best.2));
+        (
+            inliers(&points, center, normal, threshold),
+            Some(iterations),
+            Some(seed),
+        )
+    } else {
+        ((0..points.len()).collect(), None, None)
+    };
+
+    let normal = canonical(normal);
+    let distances: Vec<f64> = points
+        .iter()
+        .map(|p| dot(sub(*p, center), normal))
+        .collect();
+    let count = fitted.len().max(1) as f64;
+    let rms_error = (fitted.iter().map(|&i| distances[i].powi(2)).sum::<f64>() / count).sqrt();
+    let max_abs_error = fitted
+        .iter()
+        .fold(0.0f64, |m, &i| m.max(distances[i].abs()));
+    let (inliers, outliers) = match input.threshold {
+        Some(threshold) => {
+            let (inside, outside): (Vec<usize>, Vec<usize>) =
+                (0..points.len()).partition(|&i| distances[i].abs() <= threshold);
+            (Some(inside), Some(outside))
+        }
+        None => (None, None),
+    };
+
+    Ok(FitPlaneResult {
+        plane: Plane3D {
+            point: Vector3D {
+                x: center[0],
+                y: center[1],
+                z: center[2],
+            },
+            normal: Vector3D {
+                x: normal[0],
+                y: normal[1],
+                z: normal[2],
+            },
+        },
+        coefficients: [normal[0], normal[1], normal[2], -dot(normal, center)],
+        rms_error,
+        max_abs_error,
+        distances,
+        inliers,
+        outliers,
+        method,
+        iterations,
+        seed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    /// A grid of points on z = 0.5x - 0.25y + 2
+    fn tilted_grid() -> Vec<Vector3D> {
+        let mut points = Vec::new();
+        for i in 0..5 {
+            for j in 0..5 {
+                let (x, y) = (i as f64, j as f64);
+                points.push(point(x, y, 0.5 * x - 0.25 * y + 2.0));
+            }
+        }
+        points
+    }
+
+    fn request(points: Vec<Vector3D>, method: &str) -> FitPlaneInput {
+        FitPlaneInput {
+            points,
+            method: Some(method.to_string()),
+            threshold: None,
+            iterations: None,
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn test_exact_plane() {
+        let result = fit_plane_logic(request(tilted_grid(), "pca")).unwrap();
+        // The normal of z = 0.5x - 0.25y + 2 is (-0.5, 0.25, 1) normalized
+        let length = (0.25f64 + 0.0625 + 1.0).sqrt();
+        let normal = result.plane.normal;
+        assert!((normal.x + 0.5 / length).abs() < 1e-12);
+        assert!((normal.y - 0.25 / length).abs() < 1e-12);
+        assert!((normal.z - 1.0 / length).abs() < 1e-12);
+        assert_eq!(result.plane.point, point(2.0, 2.0, 2.5));
+        assert!((result.coefficients[3] + 2.0 / length).abs() < 1e-12);
+        assert!(result.rms_error < 1e-12 && result.max_abs_error < 1e-12);
+        assert!(result.inliers.is_none() && result.iterations.is_none());
+    }
+
+    #[test]
+    fn test_threshold_classifies_points() {
+        let mut points = vec![
+            point(0.0, 0.0, 0.0),
+            point(1.0, 0.0, 0.0),
+            point(0.0, 1.0, 0.0),
+            point(1.0, 1.0, 0.0),
+        ];
+        points.push(point(0.5, 0.5, 1.0));
+        let mut input = request(points, "pca");
+        input.threshold = Some(0.5);
+        let result = fit_plane_logic(input).unwrap();
+        // The outlier lifts the plane to z = 0.2, leaving it 0.8 above
+        assert!((result.plane.point.z - 0.2).abs() < 1e-12);
+        assert!((result.distances[4] - 0.8).abs() < 1e-12);
+        assert_eq!(result.inliers, Some(vec![0, 1, 2, 3]));
+        assert_eq!(result.outliers, Some(vec![4]));
+    }
+
+    #[test]
+    fn test_ransac_ignores_outliers() {
+        let mut points = tilted_grid();
+        points.push(point(0.0, 0.0, 50.0));
+        points.push(point(4.0, 4.0, -30.0));
+        points.push(point(2.0, 1.0, 9.0));
+        let mut input = request(points.clone(), "ransac");
+        input.threshold = Some(0.01);
+        input.seed = Some(7);
+        let result = fit_plane_logic(input.clone()).unwrap();
+        assert_eq!(result.inliers.clone().unwrap(), (0..25).collect::<Vec<_>>());
+        assert_eq!(result.outliers, Some(vec![25, 26, 27]));
+        assert!(result.rms_error < 1e-12);
+        assert_eq!((result.seed, result.iterations), (Some(7), Some(500)));
+        // The same seed gives the same fit
+        let again = fit_plane_logic(input).unwrap();
+        assert_eq!(again.distances, result.distances);
+
+        let pca = fit_plane_logic(request(points, "pca")).unwrap();
+        assert!(pca.rms_error > 1.0);
+    }
+
+    #[test]
+    fn test_normal_orientation_is_canonical() {
+        let points = vec![
+            point(0.0, 0.0, 1.0),
+            point(0.0, 1.0, 1.0),
+            point(1.0, 0.0, 1.0),
+        ];
+        let result = fit_plane_logic(request(points, "pca")).unwrap();
+        assert_eq!(result.plane.normal, point(0.0, 0.0, 1.0));
+        assert_eq!(result.coefficients, [0.0, 0.0, 1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let too_few = vec![point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0)];
+        assert!(fit_plane_logic(request(too_few, "pca")).is_err());
+        let collinear = vec![
+            point(0.0, 0.0, 0.0),
+            point(1.0, 1.0, 1.0),
+            point(2.0, 2.0, 2.0),
+        ];
+        assert!(
+            fit_plane_logic(request(collinear, "pca"))
+                .unwrap_err()
+                .contains("collinear")
+        );
+        assert_eq!(
+            fit_plane_logic(request(tilted_grid(), "ransac")).unwrap_err(),
+            "ransac requires a threshold distance"
+        );
+        let mut seeded = request(tilted_grid(), "pca");
+        seeded.seed = Some(1);
+        assert!(fit_plane_logic(seeded).is_err());
+        assert!(fit_plane_logic(request(tilted_grid(), "svd")).is_err());
+        let mut bad = tilted_grid();
+        bad[2].z = f64::INFINITY;
+        assert_eq!(
+            fit_plane_logic(request(bad, "pca")).unwrap_err(),
+            "Point 2 contains NaN or infinite values"
+        );
+    }
+}
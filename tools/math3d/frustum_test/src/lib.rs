@@ -0,0 +1,97 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    FrustumTestInput as LogicInput, FrustumTestResult as LogicOutput, frustum_test_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    AabbSchema as Aabb, ClassificationSchema as Classification,
+    FrustumSummarySchema as FrustumSummary, FrustumTestResultSchema as FrustumTestResult,
+    PlaneSchema as Plane, SphereSchema as Sphere, Vector3DSchema as Vector3D,
+};
+
+// Shared matrix type, which derives JsonSchema itself
+pub use ftl_matrix4::Matrix4;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FrustumTestInput {
+    #[serde(flatten)]
+    pub params: logic::FrustumTestInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Classify spheres and axis-aligned boxes as inside, outside or intersecting a view
+/// frustum given as planes or as a projection · view matrix
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn frustum_test(request: Request<FrustumTestInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "frustum_test",
+                FrustumTestInput,
+                FrustumTestResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("frustum_test", &input);
+    log.finish(run_frustum_test(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_frustum_test(input: FrustumTestInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("planes", input.params.planes.as_deref().unwrap_or_default())
+        .array(
+            "spheres",
+            input.params.spheres.as_deref().unwrap_or_default(),
+        )
+        .array("aabbs", input.params.aabbs.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::frustum_test_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = FrustumTestResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
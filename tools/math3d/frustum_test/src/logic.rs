@@ -0,0 +1,486 @@
+use ftl_matrix4::Matrix4;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most spheres and boxes, together, classified in one call
+pub const MAX_VOLUMES: usize = 100_000;
+/// Most planes accepted when they are given directly
+const MAX_PLANES: usize = 16;
+/// Plane normals shorter than this are treated as degenerate
+const DEGENERATE_NORMAL: f64 = 1e-12;
+/// Names of the planes taken from a matrix, in output order
+const MATRIX_PLANES: [&str; 6] = ["left", "right", "bottom", "top", "near", "far"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Plane of points p with normal · p + d = 0; points with normal · p + d ≥ 0 are on the
+/// inner side
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Plane {
+    /// Normal pointing into the frustum, normalized before use
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+    pub d: f64,
+    /// Label reported for the plane (optional)
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Sphere {
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Aabb {
+    /// Corner with the smallest coordinates
+    #[tool_types(nested)]
+    pub min: Vector3D,
+    /// Corner with the largest coordinates
+    #[tool_types(nested)]
+    pub max: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FrustumTestInput {
+    /// Planes bounding the frustum, normals pointing inward, usually six; give either
+    /// these or matrix
+    #[tool_types(nested)]
+    pub planes: Option<Vec<Plane>>,
+    /// Combined projection · view matrix acting on column vectors, mapping world points
+    /// to clip space; its six planes are extracted as left, right, bottom, top, near, far
+    pub matrix: Option<Matrix4>,
+    /// Clip-space depth range of matrix: "negative_one_to_one" (OpenGL) or
+    /// "zero_to_one" (Direct3D, Vulkan, WebGPU) (default: negative_one_to_one)
+    pub depth_range: Option<String>,
+    /// Spheres to classify
+    #[tool_types(nested)]
+    pub spheres: Option<Vec<Sphere>>,
+    /// Axis-aligned bounding boxes to classify
+    #[tool_types(nested)]
+    pub aabbs: Option<Vec<Aabb>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Classification {
+    /// Position in the input list
+    pub index: usize,
+    /// "inside", "outside" or "intersecting"
+    pub result: String,
+    /// Index of the first plane with the volume entirely on its outer side (absent
+    /// unless outside)
+    pub culled_by: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FrustumSummary {
+    pub inside: usize,
+    pub intersecting: usize,
+    pub outside: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FrustumTestResult {
+    /// Planes used, with unit normals pointing inward
+    #[tool_types(nested)]
+    pub planes: Vec<Plane>,
+    /// Classification of each sphere, in input order
+    #[tool_types(nested)]
+    pub spheres: Vec<Classification>,
+    /// Classification of each box, in input order
+    #[tool_types(nested)]
+    pub aabbs: Vec<Classification>,
+    /// Counts over spheres and boxes together
+    #[tool_types(nested)]
+    pub summary: FrustumSummary,
+}
+
+type V = [f64; 3];
+
+fn to_array(v: &Vector3D) -> V {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(v: V) -> Vector3D {
+    Vector3D {
+        x: v[0],
+        y: v[1],
+        z: v[2],
+    }
+}
+
+fn dot(a: V, b: V) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Unit normal and offset, scaled together so distances come out in world units
+fn normalize(normal: V, d: f64, name: &str) -> Result<(V, f64), String> {
+    if !normal.iter().all(|c| c.is_finite()) || !d.is_finite() {
+        return Err(format!("Plane {name} must have finite coefficients"));
+    }
+    let length = dot(normal, normal).sqrt();
+    if length < DEGENERATE_NORMAL {
+        return Err(format!("Plane {name} has a zero-length normal"));
+    }
+    Ok((normal.map(|c| c / length), d / length))
+}
+
+/// Gribb–Hartmann extraction: a clip-space point is inside when -w ≤ x, y ≤ w and
+/// -w ≤ z ≤ w (or 0 ≤ z ≤ w), and each bound is a linear function of the world point
+fn matrix_planes(matrix: &Matrix4, zero_to_one: bool) -> Result<Vec<Plane>, String> {
+    if !matrix.is_finite() {
+        return Err("matrix must contain only finite numbers".to_string());
+    }
+    let row = |i: usize| matrix.0[i];
+    let add = |a: [f64; 4], b: [f64; 4], sign: f64| [0, 1, 2, 3].map(|i| a[i] + sign * b[i]);
+    let near = if zero_to_one {
+        row(2)
+    } else {
+        add(row(3), row(2), 1.0)
+    };
+    let rows = [
+        add(row(3), row(0), 1.0),
+        add(row(3), row(0), -1.0),
+        add(row(3), row(1), 1.0),
+        add(row(3), row(1), -1.0),
+        near,
+        add(row(3), row(2), -1.0),
+    ];
+    rows.iter()
+        .zip(MATRIX_PLANES)
+        .map(|(r, name)| {
+            let (normal, d) = normalize([r[0], r[1], r[2]], r[3], name)
+                .map_err(|_| format!("matrix does not define a {name} plane"))?;
+            Ok(Plane {
+                normal: to_vector(normal),
+                d,
+                name: Some(name.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn given_planes(planes: Vec<Plane>) -> Result<Vec<Plane>, String> {
+    if planes.is_empty() || planes.len() > MAX_PLANES {
+        return Err(format!(
+            "planes must hold between 1 and {MAX_PLANES} planes"
+        ));
+    }
+    planes
+        .into_iter()
+        .enumerate()
+        .map(|(i, plane)| {
+            let label = plane.name.clone().unwrap_or_else(|| i.to_string());
+            let (normal, d) = normalize(to_array(&plane.normal), plane.d, &label)?;
+            Ok(Plane {
+                normal: to_vector(normal),
+                d,
+                name: plane.name,
+            })
+        })
+        .collect()
+}
+
+/// Classifies a volume from the lowest and highest signed distance of its points to
+/// each plane
+fn classify(
+    index: usize,
+    planes: &[(V, f64)],
+    extent: impl Fn(V, f64) -> (f64, f64),
+) -> Classification {
+    let mut straddles = false;
+    for (i, &(normal, d)) in planes.iter().enumerate() {
+        let (lowest, highest) = extent(normal, d);
+        if highest < 0.0 {
+            return Classification {
+                index,
+                result: "outside".to_string(),
+                culled_by: Some(i),
+            };
+        }
+        straddles |= lowest < 0.0;
+    }
+    Classification {
+        index,
+        result: if straddles { "intersecting" } else { "inside" }.to_string(),
+        culled_by: None,
+    }
+}
+
+pub fn frustum_test_logic(input: FrustumTestInput) -> Result<FrustumTestResult, String> {
+    let zero_to_one = match input.depth_range.as_deref() {
+        None | Some("negative_one_to_one") => false,
+        Some("zero_to_one") => true,
+        Some(other) => {
+            return Err(format!(
+                "Unknown depth_range '{other}', use 'negative_one_to_one' or 'zero_to_one'"
+            ));
+        }
+    };
+    let planes = match (input.planes, &input.matrix) {
+        (Some(planes), None) => given_planes(planes)?,
+        (None, Some(matrix)) => matrix_planes(matrix, zero_to_one)?,
+        _ => return Err("Give either planes or matrix".to_string()),
+    };
+
+    let spheres = input.spheres.unwrap_or_default();
+    let aabbs = input.aabbs.unwrap_or_default();
+    if spheres.len() + aabbs.len() > MAX_VOLUMES {
+        return Err(format!(
+            "At most {MAX_VOLUMES} spheres and boxes can be tested at once"
+        ));
+    }
+
+    let coefficients: Vec<(V, f64)> = planes.iter().map(|p| (to_array(&p.normal), p.d)).collect();
+
+    let mut sphere_results = Vec::with_capacity(spheres.len());
+    for (index, sphere) in spheres.iter().enumerate() {
+        let center = to_array(&sphere.center);
+        if !center.iter().all(|c| c.is_finite()) || !sphere.radius.is_finite() {
+            return Err(format!("Sphere {index} must have finite values"));
+        }
+        if sphere.radius < 0.0 {
+            return Err(format!("Sphere {index} has a negative radius"));
+        }
+        sphere_results.push(classify(index, &coefficients, |normal, d| {
+            let distance = dot(normal, center) + d;
+            (distance - sphere.radius, distance + sphere.radius)
+        }));
+    }
+
+    // Box corners lowest and highest along each normal; boxes near a frustum edge can
+    // straddle two planes without entering it and are then reported as intersecting
+    let mut aabb_results = Vec::with_capacity(aabbs.len());
+    for (index, aabb) in aabbs.iter().enumerate() {
+        let (min, max) = (to_array(&aabb.min), to_array(&aabb.max));
+        if !min.iter().chain(&max).all(|c| c.is_finite()) {
+            return Err(format!("AABB {index} coordinates must be finite"));
+        }
+        if (0..3).any(|axis| min[axis] > max[axis]) {
+            return Err(format!(
+                "AABB {index} min coordinates must not exceed max coordinates"
+            ));
+        }
+        aabb_results.push(classify(index, &coefficients, |normal, d| {
+            let corner = |towards: bool| {
+                [0, 1, 2].map(|axis| {
+                    if (normal[axis] >= 0.0) == towards {
+                        max[axis]
+                    } else {
+                        min[axis]
+                    }
+                })
+            };
+            (
+                dot(normal, corner(false)) + d,
+                dot(normal, corner(true)) + d,
+            )
+        }));
+    }
+
+    let count = |result: &str| {
+        sphere_results
+            .iter()
+            .chain(&aabb_results)
+            .filter(|c| c.result == result)
+            .count()
+    };
+    let summary = FrustumSummary {
+        inside: count("inside"),
+        intersecting: count("intersecting"),
+        outside: count("outside"),
+    };
+
+    Ok(FrustumTestResult {
+        planes,
+        spheres: sphere_results,
+        aabbs: aabb_results,
+        summary,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// OpenGL perspective camera at the origin looking down -z, 90° field of view,
+    /// near 1 and far 100
+    fn perspective() -> Matrix4 {
+        let (n, f) = (1.0, 100.0);
+        Matrix4([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, -(f + n) / (f - n), -2.0 * f * n / (f - n)],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
+    fn sphere(center: V, radius: f64) -> Sphere {
+        Sphere {
+            center: to_vector(center),
+            radius,
+        }
+    }
+
+    fn aabb(min: V, max: V) -> Aabb {
+        Aabb {
+            min: to_vector(min),
+            max: to_vector(max),
+        }
+    }
+
+    fn input(matrix: Option<Matrix4>, planes: Option<Vec<Plane>>) -> FrustumTestInput {
+        FrustumTestInput {
+            planes,
+            matrix,
+            depth_range: None,
+            spheres: None,
+            aabbs: None,
+        }
+    }
+
+    fn results(classifications: &[Classification]) -> Vec<&str> {
+        classifications.iter().map(|c| c.result.as_str()).collect()
+    }
+
+    #[test]
+    fn test_matrix_planes() {
+        let result = frustum_test_logic(input(Some(perspective()), None)).unwrap();
+        let names: Vec<_> = result
+            .planes
+            .iter()
+            .map(|p| p.name.as_deref().unwrap())
+            .collect();
+        assert_eq!(names, MATRIX_PLANES);
+        let near = &result.planes[4];
+        assert_eq!(to_array(&near.normal), [0.0, 0.0, -1.0]);
+        assert!((near.d + 1.0).abs() < 1e-12);
+        let far = &result.planes[5];
+        assert!((to_array(&far.normal)[2] - 1.0).abs() < 1e-12);
+        assert!((far.d - 100.0).abs() < 1e-9);
+        let left = to_array(&result.planes[0].normal);
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((left[0] - h).abs() < 1e-12 && (left[2] + h).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_spheres() {
+        let mut request = input(Some(perspective()), None);
+        request.spheres = Some(vec![
+            sphere([0.0, 0.0, -10.0], 1.0),
+            sphere([0.0, 0.0, 10.0], 1.0),
+            sphere([10.0, 0.0, -10.0], 0.5),
+            sphere([0.0, 0.0, -0.5], 0.2),
+            sphere([0.0, 0.0, -150.0], 60.0),
+        ]);
+        let result = frustum_test_logic(request).unwrap();
+        assert_eq!(
+            results(&result.spheres),
+            [
+                "inside",
+                "outside",
+                "intersecting",
+                "outside",
+                "intersecting"
+            ]
+        );
+        assert_eq!(result.spheres[3].culled_by, Some(4));
+        assert_eq!(result.summary.inside, 1);
+        assert_eq!(result.summary.outside, 2);
+        assert_eq!(result.summary.intersecting, 2);
+    }
+
+    #[test]
+    fn test_aabbs_and_depth_range() {
+        // The identity matrix bounds the cube [-1, 1]³, or [-1, 1]² × [0, 1]
+        let mut request = input(Some(Matrix4::IDENTITY), None);
+        request.aabbs = Some(vec![
+            aabb([-0.5; 3], [0.5; 3]),
+            aabb([0.5, 0.5, 0.5], [2.0, 2.0, 2.0]),
+            aabb([1.5, -0.5, -0.5], [2.5, 0.5, 0.5]),
+            aabb([-0.5, -0.5, -0.8], [0.5, 0.5, -0.6]),
+        ]);
+        let result = frustum_test_logic(request.clone()).unwrap();
+        assert_eq!(
+            results(&result.aabbs),
+            ["inside", "intersecting", "outside", "inside"]
+        );
+        assert_eq!(result.aabbs[2].culled_by, Some(1));
+
+        request.depth_range = Some("zero_to_one".to_string());
+        let result = frustum_test_logic(request).unwrap();
+        assert_eq!(
+            results(&result.aabbs),
+            ["intersecting", "intersecting", "outside", "outside"]
+        );
+        assert_eq!(result.aabbs[3].culled_by, Some(4));
+    }
+
+    #[test]
+    fn test_explicit_planes() {
+        // Slab 0 ≤ x ≤ 10 from two planes, normals scaled to check normalization
+        let plane = |normal: V, d: f64| Plane {
+            normal: to_vector(normal),
+            d,
+            name: None,
+        };
+        let mut request = input(
+            None,
+            Some(vec![
+                plane([2.0, 0.0, 0.0], 0.0),
+                plane([-3.0, 0.0, 0.0], 30.0),
+            ]),
+        );
+        request.spheres = Some(vec![
+            sphere([5.0, 100.0, 0.0], 5.0),
+            sphere([11.0, 0.0, 0.0], 2.0),
+        ]);
+        request.aabbs = Some(vec![aabb([-3.0, 0.0, 0.0], [-1.0, 1.0, 1.0])]);
+        let result = frustum_test_logic(request).unwrap();
+        assert_eq!(result.planes[1].normal, to_vector([-1.0, 0.0, 0.0]));
+        assert_eq!(result.planes[1].d, 10.0);
+        // Touching a plane from inside still counts as inside
+        assert_eq!(results(&result.spheres), ["inside", "intersecting"]);
+        assert_eq!(results(&result.aabbs), ["outside"]);
+        assert_eq!(result.aabbs[0].culled_by, Some(0));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = frustum_test_logic(input(None, None)).unwrap_err();
+        assert_eq!(err, "Give either planes or matrix");
+
+        let err = frustum_test_logic(input(Some(Matrix4([[0.0; 4]; 4])), None)).unwrap_err();
+        assert_eq!(err, "matrix does not define a left plane");
+
+        let degenerate = Plane {
+            normal: to_vector([0.0; 3]),
+            d: 1.0,
+            name: Some("top".to_string()),
+        };
+        let err = frustum_test_logic(input(None, Some(vec![degenerate]))).unwrap_err();
+        assert_eq!(err, "Plane top has a zero-length normal");
+
+        let mut request = input(Some(perspective()), None);
+        request.spheres = Some(vec![sphere([0.0; 3], -1.0)]);
+        assert!(
+            frustum_test_logic(request)
+                .unwrap_err()
+                .contains("negative radius")
+        );
+
+        let mut request = input(Some(perspective()), None);
+        request.depth_range = Some("reversed".to_string());
+        assert!(
+            frustum_test_logic(request)
+                .unwrap_err()
+                .contains("depth_range")
+        );
+    }
+}
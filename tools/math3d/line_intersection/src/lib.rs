@@ -1,75 +1,76 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-use logic::{
-    Line3D as LogicLine3D, LineIntersectionInput as LogicInput, Vector3D as LogicVector3D,
+// Re-export types from logic module
+pub use logic::{
+    LineIntersectionInput as LogicInput, LineIntersectionResult as LogicOutput,
     line_intersection_logic,
 };
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Vector3D {
-    /// X component of the vector
-    pub x: f64,
-    /// Y component of the vector
-    pub y: f64,
-    /// Z component of the vector
-    pub z: f64,
-}
-
-#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
-pub struct Line3D {
-    /// A point on the line
-    pub point: Vector3D,
-    /// Direction vector of the line
-    pub direction: Vector3D,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    Line3DSchema as Line3D, LineIntersectionResultSchema as LineIntersectionResult,
+    Vector3DSchema as Vector3D,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LineIntersectionInput {
-    /// First 3D line
-    pub line1: Line3D,
-    /// Second 3D line
-    pub line2: Line3D,
+    #[serde(flatten)]
+    pub params: logic::LineIntersectionInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-impl From<Vector3D> for LogicVector3D {
-    fn from(v: Vector3D) -> Self {
-        LogicVector3D {
-            x: v.x,
-            y: v.y,
-            z: v.z,
+/// Find intersection of two 3D lines
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn line_intersection(request: Request<LineIntersectionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "line_intersection",
+                LineIntersectionInput,
+                LineIntersectionResult
+            ));
         }
-    }
-}
+    };
 
-impl From<Line3D> for LogicLine3D {
-    fn from(line: Line3D) -> Self {
-        LogicLine3D {
-            point: line.point.into(),
-            direction: line.direction.into(),
-        }
-    }
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("line_intersection", &input);
+    log.finish(run_line_intersection(input))
 }
 
-impl From<LineIntersectionInput> for LogicInput {
-    fn from(input: LineIntersectionInput) -> Self {
-        LogicInput {
-            line1: input.line1.into(),
-            line2: input.line2.into(),
-        }
-    }
-}
+#[cfg(feature = "individual")]
+fn run_line_intersection(input: LineIntersectionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
 
-/// Find intersection of two 3D lines
-#[cfg_attr(not(test), tool)]
-pub fn line_intersection(input: LineIntersectionInput) -> ToolResponse {
-    match line_intersection_logic(input.into()) {
-        Ok(result) => ToolResponse::text(serde_json::to_string(&result).unwrap()),
+    match line_intersection_logic(input.params.into()) {
+        Ok(result) => match ftl_response::to_json_with_precision(&result, None, precision.as_ref())
+        {
+            Ok(body) => ToolResponse::text(body),
+            Err(e) => ToolResponse::text(format!("Error: {e}")),
+        },
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
 }
@@ -1,6 +1,9 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     /// X component of the vector
     pub x: f64,
@@ -10,28 +13,37 @@ pub struct Vector3D {
     pub z: f64,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Deserialize, Serialize, Clone, Debug, ToolTypes)]
 pub struct Line3D {
     /// A point on the line
+    #[tool_types(nested)]
     pub point: Vector3D,
     /// Direction vector of the line
+    #[tool_types(nested)]
     pub direction: Vector3D,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToolTypes)]
 pub struct LineIntersectionInput {
     /// First 3D line
+    #[tool_types(nested)]
     pub line1: Line3D,
     /// Second 3D line
+    #[tool_types(nested)]
     pub line2: Line3D,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToolTypes)]
 pub struct LineIntersectionResult {
     pub intersection_type: String,
     pub intersects: bool,
+    #[tool_types(nested)]
     pub intersection_point: Option<Vector3D>,
+    #[tool_types(nested)]
     pub closest_point_line1: Vector3D,
+    #[tool_types(nested)]
     pub closest_point_line2: Vector3D,
     pub minimum_distance: f64,
     pub parameter_line1: f64,
@@ -1,19 +1,34 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::*;
 
+// Re-export types from logic module
+pub use logic::{
+    LinePlaneInput as LogicInput, LinePlaneIntersectionResult as LogicOutput,
+    line_plane_intersection_logic,
+};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D);
+
 #[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
 pub struct Line3D {
     /// A point on the line
@@ -30,12 +45,18 @@ pub struct Plane3D {
     pub normal: Vector3D,
 }
 
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 pub struct LinePlaneInput {
     /// The line to test for intersection
     pub line: Line3D,
     /// The plane to test against
     pub plane: Plane3D,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Serialize, JsonSchema)]
@@ -58,8 +79,33 @@ pub struct LinePlaneIntersectionResult {
 
 /// Calculate the intersection between a 3D line and a plane
 /// Returns detailed information about the intersection including type, point, and geometric relationships
-#[cfg_attr(not(test), tool)]
-pub fn line_plane_intersection(input: LinePlaneInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn line_plane_intersection(request: Request<LinePlaneInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "line_plane_intersection",
+                LinePlaneInput,
+                LinePlaneIntersectionResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("line_plane_intersection", &input);
+    log.finish(run_line_plane_intersection(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_line_plane_intersection(input: LinePlaneInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     // Convert JsonSchema types to logic types
     let logic_input = logic::LinePlaneInput {
         line: logic::Line3D {
@@ -105,7 +151,10 @@ pub fn line_plane_intersection(input: LinePlaneInput) -> ToolResponse {
                 line_is_in_plane: logic_result.line_is_in_plane,
                 distance_to_plane: logic_result.distance_to_plane,
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
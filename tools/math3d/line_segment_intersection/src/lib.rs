@@ -1,54 +1,75 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{
-    LineSegmentInput as LogicInput, Vector3D as LogicVector3D, line_segment_intersection_logic,
+
+// Re-export types from logic module
+pub use logic::{
+    LineSegmentInput as LogicInput, LineSegmentIntersectionResult as LogicOutput,
+    line_segment_intersection_logic,
 };
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    LineSegmentIntersectionResultSchema as LineSegmentIntersectionResult,
+    Vector3DSchema as Vector3D,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LineSegmentInput {
-    pub segment1_start: Vector3D,
-    pub segment1_end: Vector3D,
-    pub segment2_start: Vector3D,
-    pub segment2_end: Vector3D,
+    #[serde(flatten)]
+    pub params: logic::LineSegmentInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-impl From<Vector3D> for LogicVector3D {
-    fn from(v: Vector3D) -> Self {
-        LogicVector3D {
-            x: v.x,
-            y: v.y,
-            z: v.z,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn line_segment_intersection(request: Request<LineSegmentInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "line_segment_intersection",
+                LineSegmentInput,
+                LineSegmentIntersectionResult
+            ));
         }
-    }
-}
+    };
 
-impl From<LineSegmentInput> for LogicInput {
-    fn from(input: LineSegmentInput) -> Self {
-        LogicInput {
-            segment1_start: input.segment1_start.into(),
-            segment1_end: input.segment1_end.into(),
-            segment2_start: input.segment2_start.into(),
-            segment2_end: input.segment2_end.into(),
-        }
-    }
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("line_segment_intersection", &input);
+    log.finish(run_line_segment_intersection(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn line_segment_intersection(input: LineSegmentInput) -> ToolResponse {
-    match line_segment_intersection_logic(input.into()) {
-        Ok(result) => ToolResponse::text(serde_json::to_string(&result).unwrap()),
+#[cfg(feature = "individual")]
+fn run_line_segment_intersection(input: LineSegmentInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match line_segment_intersection_logic(input.params.into()) {
+        Ok(result) => match ftl_response::to_json_with_precision(&result, None, precision.as_ref())
+        {
+            Ok(body) => ToolResponse::text(body),
+            Err(e) => ToolResponse::text(format!("Error: {e}")),
+        },
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
 }
@@ -1,31 +1,43 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Line3D {
     pub point: Vector3D,
     pub direction: Vector3D,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToolTypes)]
 pub struct LineSegmentInput {
+    #[tool_types(nested)]
     pub segment1_start: Vector3D,
+    #[tool_types(nested)]
     pub segment1_end: Vector3D,
+    #[tool_types(nested)]
     pub segment2_start: Vector3D,
+    #[tool_types(nested)]
     pub segment2_end: Vector3D,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToolTypes)]
 pub struct LineSegmentIntersectionResult {
     pub intersects: bool,
+    #[tool_types(nested)]
     pub intersection_point: Option<Vector3D>,
+    #[tool_types(nested)]
     pub closest_point_seg1: Vector3D,
+    #[tool_types(nested)]
     pub closest_point_seg2: Vector3D,
     pub minimum_distance: f64,
     pub intersection_on_both_segments: bool,
@@ -0,0 +1,72 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{LookAtInput as LogicInput, LookAtResult as LogicOutput, look_at_logic};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{LookAtResultSchema as LookAtResult, Vector3DSchema as Vector3D};
+
+// Shared matrix type, which derives JsonSchema itself
+pub use ftl_matrix4::Matrix4;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LookAtInput {
+    #[serde(flatten)]
+    pub params: logic::LookAtInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Build a look-at view matrix from eye, target and up vectors, or decompose an existing
+/// view matrix back into the camera position and its orthonormal basis
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn look_at(request: Request<LookAtInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("look_at", LookAtInput, LookAtResult));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("look_at", &input);
+    log.finish(run_look_at(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_look_at(input: LookAtInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match logic::look_at_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = LookAtResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,370 @@
+use ftl_matrix4::Matrix4;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Vectors shorter than this are treated as zero
+const DEGENERATE_LENGTH: f64 = 1e-12;
+/// Largest deviation from a rigid transform accepted in a view matrix
+const RIGID_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct LookAtInput {
+    /// Camera position; give with target to build a view matrix
+    #[tool_types(nested)]
+    pub eye: Option<Vector3D>,
+    /// Point the camera looks at
+    #[tool_types(nested)]
+    pub target: Option<Vector3D>,
+    /// Approximate up direction, made orthogonal to the view direction
+    /// (default: {"x": 0, "y": 1, "z": 0})
+    #[tool_types(nested)]
+    pub up: Option<Vector3D>,
+    /// Existing world-to-camera matrix, acting on column vectors, to decompose instead
+    pub view_matrix: Option<Matrix4>,
+    /// "right_handed" (OpenGL: the camera looks down -z) or "left_handed" (Direct3D: the
+    /// camera looks down +z) (default: right_handed)
+    pub handedness: Option<String>,
+    /// Distance from the eye to the target reported when decomposing, since a view
+    /// matrix only fixes the direction (default: 1)
+    pub target_distance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct LookAtResult {
+    /// World-to-camera matrix, acting on column vectors
+    pub view_matrix: Matrix4,
+    /// Camera-to-world matrix, the inverse of view_matrix
+    pub camera_matrix: Matrix4,
+    #[tool_types(nested)]
+    pub eye: Vector3D,
+    #[tool_types(nested)]
+    pub target: Vector3D,
+    /// Unit vector along the view direction
+    #[tool_types(nested)]
+    pub forward: Vector3D,
+    /// Unit vector to the camera's right
+    #[tool_types(nested)]
+    pub right: Vector3D,
+    /// Unit up vector, orthogonal to forward and right
+    #[tool_types(nested)]
+    pub up: Vector3D,
+    pub handedness: String,
+}
+
+type V = [f64; 3];
+
+fn components(v: &Vector3D) -> V {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(v: V) -> Vector3D {
+    Vector3D {
+        x: v[0],
+        y: v[1],
+        z: v[2],
+    }
+}
+
+fn sub(a: V, b: V) -> V {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: V, b: V) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: V, b: V) -> V {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: V) -> Option<V> {
+    let length = dot(v, v).sqrt();
+    (length > DEGENERATE_LENGTH).then(|| v.map(|c| c / length))
+}
+
+/// View and camera matrices from an orthonormal camera basis. Camera space has x to the
+/// right and y up; z points backward for right-handed and forward for left-handed
+fn matrices(eye: V, right: V, up: V, forward: V, right_handed: bool) -> (Matrix4, Matrix4) {
+    let z = if right_handed {
+        forward.map(|c| -c)
+    } else {
+        forward
+    };
+    let mut view = Matrix4::IDENTITY;
+    let mut camera = Matrix4::IDENTITY;
+    for (row, axis) in [right, up, z].into_iter().enumerate() {
+        view.0[row] = [axis[0], axis[1], axis[2], -dot(axis, eye)];
+        for (column, value) in axis.into_iter().enumerate() {
+            camera.0[column][row] = value;
+        }
+        camera.0[row][3] = eye[row];
+    }
+    (view, camera)
+}
+
+fn build(input: &LookAtInput, right_handed: bool) -> Result<LookAtResult, String> {
+    let (Some(eye), Some(target)) = (&input.eye, &input.target) else {
+        return Err(
+            "Give eye and target to build a view matrix, or view_matrix to decompose".to_string(),
+        );
+    };
+    let (eye, target) = (components(eye), components(target));
+    let up = input.up.as_ref().map_or([0.0, 1.0, 0.0], components);
+    if !eye.iter().chain(&target).chain(&up).all(|v| v.is_finite()) {
+        return Err("eye, target and up must be finite".to_string());
+    }
+    let forward = normalize(sub(target, eye)).ok_or("eye and target must differ")?;
+    // Right-handed cameras take right = forward × up; left-handed ones mirror the cross
+    // products so the view rotation stays proper in a left-handed world
+    let (a, b) = if right_handed {
+        (forward, up)
+    } else {
+        (up, forward)
+    };
+    let right =
+        normalize(cross(a, b)).ok_or("up must not be zero or parallel to the view direction")?;
+    let true_up = if right_handed {
+        cross(right, forward)
+    } else {
+        cross(forward, right)
+    };
+    let (view_matrix, camera_matrix) = matrices(eye, right, true_up, forward, right_handed);
+    Ok(LookAtResult {
+        view_matrix,
+        camera_matrix,
+        eye: to_vector(eye),
+        target: to_vector(target),
+        forward: to_vector(forward),
+        right: to_vector(right),
+        up: to_vector(true_up),
+        handedness: String::new(),
+    })
+}
+
+fn decompose(view: &Matrix4, right_handed: bool, distance: f64) -> Result<LookAtResult, String> {
+    if !view.is_finite() {
+        return Err("view_matrix contains NaN or infinite values".to_string());
+    }
+    let m = &view.0;
+    if (m[3][0].abs() + m[3][1].abs() + m[3][2].abs() + (m[3][3] - 1.0).abs()) > RIGID_TOLERANCE {
+        return Err("view_matrix must be affine, with a bottom row of [0, 0, 0, 1]".to_string());
+    }
+    let rows: [V; 3] = std::array::from_fn(|r| [m[r][0], m[r][1], m[r][2]]);
+    for i in 0..3 {
+        for j in 0..3 {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            if (dot(rows[i], rows[j]) - expected).abs() > RIGID_TOLERANCE {
+                return Err(
+                    "view_matrix must be a rotation and translation, without scale or shear"
+                        .to_string(),
+                );
+            }
+        }
+    }
+    if dot(cross(rows[0], rows[1]), rows[2]) < 0.0 {
+        return Err("view_matrix contains a reflection".to_string());
+    }
+
+    let [right, up, z] = rows;
+    let forward = if right_handed { z.map(|c| -c) } else { z };
+    // eye = -Rᵀ · t
+    let translation = [m[0][3], m[1][3], m[2][3]];
+    let eye: V = std::array::from_fn(|c| -(0..3).map(|r| rows[r][c] * translation[r]).sum::<f64>());
+    let target: V = std::array::from_fn(|c| eye[c] + forward[c] * distance);
+    let (view_matrix, camera_matrix) = matrices(eye, right, up, forward, right_handed);
+    Ok(LookAtResult {
+        view_matrix,
+        camera_matrix,
+        eye: to_vector(eye),
+        target: to_vector(target),
+        forward: to_vector(forward),
+        right: to_vector(right),
+        up: to_vector(up),
+        handedness: String::new(),
+    })
+}
+
+pub fn look_at_logic(input: LookAtInput) -> Result<LookAtResult, String> {
+    let handedness = input
+        .handedness
+        .as_deref()
+        .map(|h| h.trim().to_lowercase())
+        .unwrap_or_else(|| "right_handed".to_string());
+    let right_handed = match handedness.as_str() {
+        "right_handed" => true,
+        "left_handed" => false,
+        other => {
+            return Err(format!(
+                "Unknown handedness '{other}', use 'right_handed' or 'left_handed'"
+            ));
+        }
+    };
+
+    let mut result = match &input.view_matrix {
+        Some(view) => {
+            if input.eye.is_some() || input.target.is_some() || input.up.is_some() {
+                return Err("Give either view_matrix or eye, target and up, not both".to_string());
+            }
+            let distance = input.target_distance.unwrap_or(1.0);
+            if !(distance.is_finite() && distance > 0.0) {
+                return Err("target_distance must be a positive number".to_string());
+            }
+            decompose(view, right_handed, distance)?
+        }
+        None => {
+            if input.target_distance.is_some() {
+                return Err(
+                    "target_distance only applies when decomposing a view_matrix".to_string(),
+                );
+            }
+            build(&input, right_handed)?
+        }
+    };
+    result.handedness = handedness;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn request(eye: Vector3D, target: Vector3D) -> LookAtInput {
+        LookAtInput {
+            eye: Some(eye),
+            target: Some(target),
+            up: None,
+            view_matrix: None,
+            handedness: None,
+            target_distance: None,
+        }
+    }
+
+    fn assert_vector(actual: Vector3D, expected: [f64; 3]) {
+        let actual = components(&actual);
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_right_handed_view_matrix() {
+        let result = look_at_logic(request(point(0.0, 0.0, 5.0), point(0.0, 0.0, 0.0))).unwrap();
+        // Looking down -z from z = 5 is the identity rotation shifted back by 5
+        assert_eq!(
+            result.view_matrix,
+            Matrix4([
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, -5.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ])
+        );
+        assert_vector(result.forward, [0.0, 0.0, -1.0]);
+        // The target lands on the camera's -z axis
+        let target = result.view_matrix.transform_point([0.0, 0.0, 0.0]).unwrap();
+        assert_eq!(target, [0.0, 0.0, -5.0]);
+    }
+
+    #[test]
+    fn test_left_handed_and_custom_up() {
+        let mut input = request(point(0.0, 0.0, 0.0), point(3.0, 0.0, 0.0));
+        input.up = Some(point(0.0, 0.0, 2.0));
+        input.handedness = Some("left_handed".to_string());
+        let result = look_at_logic(input).unwrap();
+        assert_vector(result.forward, [1.0, 0.0, 0.0]);
+        assert_vector(result.up, [0.0, 0.0, 1.0]);
+        // Left-handed cameras look down +z in camera space
+        let target = result.view_matrix.transform_point([3.0, 0.0, 0.0]).unwrap();
+        assert!((target[2] - 3.0).abs() < 1e-12 && target[0].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_camera_matrix_inverts_view() {
+        let mut input = request(point(1.0, 2.0, 3.0), point(-4.0, 0.5, 7.0));
+        input.up = Some(point(0.2, 1.0, -0.1));
+        let result = look_at_logic(input).unwrap();
+        let product = result.view_matrix.multiply(&result.camera_matrix);
+        for (r, row) in product.0.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                let expected = if r == c { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_decompose_round_trip() {
+        for handedness in ["right_handed", "left_handed"] {
+            let eye = point(1.0, 2.0, 3.0);
+            let mut input = request(eye, point(-4.0, 0.5, 7.0));
+            input.handedness = Some(handedness.to_string());
+            let built = look_at_logic(input).unwrap();
+            let decomposed = look_at_logic(LookAtInput {
+                eye: None,
+                target: None,
+                up: None,
+                view_matrix: Some(built.view_matrix),
+                handedness: Some(handedness.to_string()),
+                target_distance: Some(2.0),
+            })
+            .unwrap();
+            assert_vector(decomposed.eye, [1.0, 2.0, 3.0]);
+            assert_vector(decomposed.forward, components(&built.forward));
+            assert_vector(decomposed.up, components(&built.up));
+            let f = components(&built.forward);
+            assert_vector(
+                decomposed.target,
+                [1.0 + 2.0 * f[0], 2.0 + 2.0 * f[1], 3.0 + 2.0 * f[2]],
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let same = request(point(1.0, 1.0, 1.0), point(1.0, 1.0, 1.0));
+        assert_eq!(
+            look_at_logic(same).unwrap_err(),
+            "eye and target must differ"
+        );
+        let parallel = request(point(0.0, 0.0, 0.0), point(0.0, 5.0, 0.0));
+        assert!(look_at_logic(parallel).unwrap_err().contains("parallel"));
+        let scaled = LookAtInput {
+            eye: None,
+            target: None,
+            up: None,
+            view_matrix: Some(Matrix4::from_scale([2.0, 1.0, 1.0])),
+            handedness: None,
+            target_distance: None,
+        };
+        assert!(look_at_logic(scaled).unwrap_err().contains("without scale"));
+        let mut input = request(point(0.0, 0.0, 1.0), point(0.0, 0.0, 0.0));
+        input.handedness = Some("ambidextrous".to_string());
+        assert!(look_at_logic(input).is_err());
+        assert!(
+            look_at_logic(LookAtInput {
+                eye: None,
+                target: None,
+                up: None,
+                view_matrix: None,
+                handedness: None,
+                target_distance: None,
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Typed WIT exports of the math3d vector and volume tools.
+//!
+//! Hosts that link wasm components can call `ftl:math3d/tools` (see
+//! `wit/world.wit`) with plain records instead of sending JSON to each tool's
+//! HTTP endpoint. Every function calls the same logic as the tool, linked in
+//! library mode, so results and error messages are identical.
+//!
+//! Build with `cargo build --target wasm32-wasip2 --release`.
+
+wit_bindgen::generate!({
+    world: "math3d",
+    path: "wit",
+});
+
+use exports::ftl::math3d::tools::{
+    BoundingBoxResult, ConeVolumeResult, CrossProductResult, CylinderVolumeResult,
+    DotProductResult, EllipsoidVolumeResult, Guest, PyramidVolumeResult, SphereVolumeResult,
+    TetrahedronVolumeResult, Vector3d, VectorAngleResult, VectorMagnitudeResult,
+};
+
+struct Math3d;
+
+// Export names contain `:`, which native linkers reject in version scripts
+#[cfg(target_arch = "wasm32")]
+export!(Math3d);
+
+/// Convert a WIT vector into a tool's own `LogicVector3D`
+macro_rules! to_logic {
+    ($tool:ident, $v:expr) => {{
+        let v: Vector3d = $v;
+        $tool::LogicVector3D {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }};
+}
+
+/// Convert any tool's vector back into a WIT vector
+macro_rules! to_wit {
+    ($v:expr) => {{
+        let v = $v;
+        Vector3d {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }};
+}
+
+impl Guest for Math3d {
+    fn dot_product(vector1: Vector3d, vector2: Vector3d) -> Result<DotProductResult, String> {
+        let input = dot_product_tool::LogicInput {
+            vector1: to_logic!(dot_product_tool, vector1),
+            vector2: to_logic!(dot_product_tool, vector2),
+        };
+        dot_product_tool::dot_product_logic(input).map(|r| DotProductResult {
+            dot_product: r.dot_product,
+            angle_radians: r.angle_radians,
+            angle_degrees: r.angle_degrees,
+            are_perpendicular: r.are_perpendicular,
+            are_parallel: r.are_parallel,
+        })
+    }
+
+    fn cross_product(vector1: Vector3d, vector2: Vector3d) -> Result<CrossProductResult, String> {
+        let input = cross_product_tool::LogicInput {
+            vector1: to_logic!(cross_product_tool, vector1),
+            vector2: to_logic!(cross_product_tool, vector2),
+        };
+        cross_product_tool::cross_product_logic(input).map(|r| CrossProductResult {
+            cross_product: to_wit!(r.cross_product),
+            magnitude: r.magnitude,
+            area_parallelogram: r.area_parallelogram,
+            are_parallel: r.are_parallel,
+        })
+    }
+
+    fn vector_magnitude(vector: Vector3d) -> Result<VectorMagnitudeResult, String> {
+        let input = vector_magnitude::LogicInput {
+            vector: to_logic!(vector_magnitude, vector),
+        };
+        vector_magnitude::compute_vector_magnitude(input).map(|r| VectorMagnitudeResult {
+            magnitude: r.magnitude,
+            unit_vector: to_wit!(r.unit_vector),
+            is_zero_vector: r.is_zero_vector,
+        })
+    }
+
+    fn vector_angle(vector1: Vector3d, vector2: Vector3d) -> Result<VectorAngleResult, String> {
+        let input = vector_angle_tool::LogicInput {
+            vector1: to_logic!(vector_angle_tool, vector1),
+            vector2: to_logic!(vector_angle_tool, vector2),
+        };
+        vector_angle_tool::vector_angle_logic(input).map(|r| VectorAngleResult {
+            angle_radians: r.angle_radians,
+            angle_degrees: r.angle_degrees,
+            cos_angle: r.cos_angle,
+            vector1_magnitude: r.vector1_magnitude,
+            vector2_magnitude: r.vector2_magnitude,
+            is_perpendicular: r.is_perpendicular,
+            is_parallel: r.is_parallel,
+        })
+    }
+
+    fn sphere_volume(center: Vector3d, radius: f64) -> Result<SphereVolumeResult, String> {
+        let input = sphere_volume_tool::LogicInput {
+            center: to_logic!(sphere_volume_tool, center),
+            radius,
+        };
+        sphere_volume_tool::compute_sphere_volume(input).map(|r| SphereVolumeResult {
+            volume: r.volume,
+            calculation_method: r.calculation_method,
+            center: to_wit!(r.center),
+            radius: r.radius,
+        })
+    }
+
+    fn cylinder_volume(
+        base_center: Vector3d,
+        axis: Vector3d,
+        radius: f64,
+        height: f64,
+    ) -> Result<CylinderVolumeResult, String> {
+        let input = cylinder_volume_tool::LogicInput {
+            base_center: to_logic!(cylinder_volume_tool, base_center),
+            axis: to_logic!(cylinder_volume_tool, axis),
+            radius,
+            height,
+        };
+        cylinder_volume_tool::compute_cylinder_volume(input).map(|r| CylinderVolumeResult {
+            volume: r.volume,
+            calculation_method: r.calculation_method,
+            base_center: to_wit!(r.base_center),
+            axis: to_wit!(r.axis),
+            radius: r.radius,
+            height: r.height,
+        })
+    }
+
+    fn cone_volume(
+        apex: Vector3d,
+        axis: Vector3d,
+        height: f64,
+        base_radius: Option<f64>,
+        half_angle_degrees: Option<f64>,
+    ) -> Result<ConeVolumeResult, String> {
+        let input = cone_volume_tool::LogicInput {
+            apex: to_logic!(cone_volume_tool, apex),
+            axis: to_logic!(cone_volume_tool, axis),
+            height,
+            base_radius,
+            half_angle_degrees,
+        };
+        cone_volume_tool::compute_cone_volume(input).map(|r| ConeVolumeResult {
+            volume: r.volume,
+            calculation_method: r.calculation_method,
+            apex: to_wit!(r.apex),
+            axis: to_wit!(r.axis),
+            base_center: to_wit!(r.base_center),
+            base_radius: r.base_radius,
+            height: r.height,
+            half_angle_degrees: r.half_angle_degrees,
+            slant_height: r.slant_height,
+            lateral_surface_area: r.lateral_surface_area,
+            total_surface_area: r.total_surface_area,
+        })
+    }
+
+    fn ellipsoid_volume(
+        center: Vector3d,
+        radii: Vector3d,
+    ) -> Result<EllipsoidVolumeResult, String> {
+        let input = ellipsoid_volume_tool::LogicInput {
+            center: to_logic!(ellipsoid_volume_tool, center),
+            radii: to_logic!(ellipsoid_volume_tool, radii),
+            rotation: None,
+        };
+        ellipsoid_volume_tool::compute_ellipsoid_volume(input).map(|r| EllipsoidVolumeResult {
+            volume: r.volume,
+            calculation_method: r.calculation_method,
+            center: to_wit!(r.center),
+            radii: to_wit!(r.radii),
+            surface_area: r.surface_area,
+            shape: r.shape,
+        })
+    }
+
+    fn aabb_volume(points: Vec<Vector3d>) -> Result<BoundingBoxResult, String> {
+        let input = aabb_volume_tool::LogicInput {
+            points: points
+                .into_iter()
+                .map(|p| to_logic!(aabb_volume_tool, p))
+                .collect(),
+        };
+        aabb_volume_tool::compute_aabb_volume(input).map(|r| BoundingBoxResult {
+            volume: r.volume,
+            box_type: r.box_type,
+            min_point: to_wit!(r.min_point),
+            max_point: to_wit!(r.max_point),
+            dimensions: to_wit!(r.dimensions),
+        })
+    }
+
+    fn pyramid_volume(
+        base_points: Vec<Vector3d>,
+        apex: Vector3d,
+    ) -> Result<PyramidVolumeResult, String> {
+        let input = pyramid_volume_tool::LogicInput {
+            base_points: base_points
+                .into_iter()
+                .map(|p| to_logic!(pyramid_volume_tool, p))
+                .collect(),
+            apex: to_logic!(pyramid_volume_tool, apex),
+        };
+        pyramid_volume_tool::compute_pyramid_volume(input).map(|r| PyramidVolumeResult {
+            volume: r.volume,
+            calculation_method: r.calculation_method,
+            base_area: r.base_area,
+            height: r.height,
+            base_points: r.base_points.into_iter().map(|p| to_wit!(p)).collect(),
+            apex: to_wit!(r.apex),
+        })
+    }
+
+    fn tetrahedron_volume(
+        point_a: Vector3d,
+        point_b: Vector3d,
+        point_c: Vector3d,
+        point_d: Vector3d,
+    ) -> Result<TetrahedronVolumeResult, String> {
+        let input = tetrahedron_volume_tool::LogicInput {
+            point_a: to_logic!(tetrahedron_volume_tool, point_a),
+            point_b: to_logic!(tetrahedron_volume_tool, point_b),
+            point_c: to_logic!(tetrahedron_volume_tool, point_c),
+            point_d: to_logic!(tetrahedron_volume_tool, point_d),
+        };
+        tetrahedron_volume_tool::compute_tetrahedron_volume(input).map(|r| {
+            TetrahedronVolumeResult {
+                volume: r.volume,
+                calculation_method: r.calculation_method,
+                points: r.points.into_iter().map(|p| to_wit!(p)).collect(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3d {
+        Vector3d { x, y, z }
+    }
+
+    #[test]
+    fn test_vector_operations() {
+        let result = Math3d::dot_product(v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0)).unwrap();
+        assert_eq!(result.dot_product, 0.0);
+        assert!(result.are_perpendicular);
+
+        let result = Math3d::cross_product(v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0)).unwrap();
+        assert_eq!(result.cross_product.z, 1.0);
+
+        let result = Math3d::vector_magnitude(v(3.0, 4.0, 0.0)).unwrap();
+        assert_eq!(result.magnitude, 5.0);
+        assert_eq!(result.unit_vector.x, 0.6);
+        assert_eq!(result.unit_vector.y, 0.8);
+    }
+
+    #[test]
+    fn test_volumes() {
+        let result = Math3d::tetrahedron_volume(
+            v(0.0, 0.0, 0.0),
+            v(1.0, 0.0, 0.0),
+            v(0.0, 1.0, 0.0),
+            v(0.0, 0.0, 1.0),
+        )
+        .unwrap();
+        assert!((result.volume - 1.0 / 6.0).abs() < 1e-12);
+        assert_eq!(result.points.len(), 4);
+
+        let result = Math3d::aabb_volume(vec![v(0.0, 0.0, 0.0), v(2.0, 3.0, 4.0)]).unwrap();
+        assert_eq!(result.volume, 24.0);
+        assert_eq!(result.dimensions.z, 4.0);
+
+        let result =
+            Math3d::cone_volume(v(0.0, 0.0, 4.0), v(0.0, 0.0, -1.0), 4.0, Some(3.0), None).unwrap();
+        assert!((result.volume - 12.0 * std::f64::consts::PI).abs() < 1e-12);
+        assert_eq!(result.base_center.z, 0.0);
+
+        let result = Math3d::ellipsoid_volume(v(0.0, 0.0, 0.0), v(3.0, 2.0, 1.0)).unwrap();
+        assert!((result.volume - 8.0 * std::f64::consts::PI).abs() < 1e-12);
+        assert_eq!(result.shape, "triaxial ellipsoid");
+    }
+
+    #[test]
+    fn test_errors_are_passed_through() {
+        let error = Math3d::sphere_volume(v(0.0, 0.0, 0.0), -1.0).unwrap_err();
+        let input = sphere_volume_tool::LogicInput {
+            center: sphere_volume_tool::LogicVector3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            radius: -1.0,
+        };
+        assert_eq!(
+            error,
+            sphere_volume_tool::compute_sphere_volume(input).unwrap_err()
+        );
+    }
+}
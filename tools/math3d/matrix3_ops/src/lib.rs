@@ -0,0 +1,96 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use ftl_units::AngleUnit;
+#[cfg(feature = "individual")]
+use ftl_units::AngleUnitResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    Matrix3OpsInput as LogicInput, Matrix3OpsResponse as LogicOutput, compute_matrix3_ops,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    Matrix3OpsResponseSchema as Matrix3OpsResponse, Matrix3x3Schema as Matrix3x3,
+    Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Matrix3OpsInput {
+    #[serde(flatten)]
+    pub params: logic::Matrix3OpsInputSchema,
+    /// Only check the input and return a validation report, without computing
+    pub validate_only: Option<bool>,
+    /// Unit of `angle`: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Multiply, invert or take the determinant of 3x3 matrices, estimate the eigenvalues
+/// and eigenvectors of a symmetric matrix, or build a rotation matrix from an axis and angle
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn matrix3_ops(request: Request<Matrix3OpsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "matrix3_ops",
+                Matrix3OpsInput,
+                AngleUnitResponse<Matrix3OpsResponse>
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("matrix3_ops", &input);
+    log.finish(run_matrix3_ops(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_matrix3_ops(input: Matrix3OpsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    let mut params: logic::Matrix3OpsInput = input.params.into();
+    params.angle = params.angle.map(|angle| angle_unit.to_radians(angle));
+
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_matrix3_input(&params);
+        return ToolResponse::text(
+            ftl_json::to_string(&report).unwrap_or_else(|_| "Error serializing result".to_string()),
+        );
+    }
+
+    match logic::compute_matrix3_ops(params) {
+        Ok(logic_result) => {
+            let response = angle_unit.response(Matrix3OpsResponse::from(logic_result));
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
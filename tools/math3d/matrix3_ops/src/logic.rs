@@ -0,0 +1,621 @@
+use ftl_preflight::ValidationReport;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Determinants this small relative to the entries (cubed) are treated as singular
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+/// Largest asymmetry, relative to the largest entry, accepted by eigenvalues
+const SYMMETRY_TOLERANCE: f64 = 1e-9;
+/// Jacobi sweeps before giving up; 3x3 matrices converge in well under ten
+const MAX_SWEEPS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// 3x3 matrix, mRC is row R and column C
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Matrix3x3 {
+    pub m00: f64,
+    pub m01: f64,
+    pub m02: f64,
+    pub m10: f64,
+    pub m11: f64,
+    pub m12: f64,
+    pub m20: f64,
+    pub m21: f64,
+    pub m22: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Matrix3OpsInput {
+    /// Operation: multiply, inverse, determinant, eigenvalues or from_axis_angle
+    pub operation: String,
+    /// First matrix, required by every operation except from_axis_angle; eigenvalues
+    /// requires it to be symmetric
+    #[tool_types(nested)]
+    pub a: Option<Matrix3x3>,
+    /// Second matrix, required by multiply, which returns a · b (b applied first)
+    #[tool_types(nested)]
+    pub b: Option<Matrix3x3>,
+    /// Rotation axis for from_axis_angle, normalized before use
+    #[tool_types(nested)]
+    pub axis: Option<Vector3D>,
+    /// Rotation angle in radians for from_axis_angle, counterclockwise looking down the axis
+    pub angle: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Matrix3OpsResponse {
+    /// Operation performed
+    pub operation: String,
+    /// Resulting matrix (multiply, inverse and from_axis_angle)
+    #[tool_types(nested)]
+    pub matrix: Option<Matrix3x3>,
+    /// Determinant of a (determinant and inverse)
+    pub determinant: Option<f64>,
+    /// Eigenvalues of a, largest first (eigenvalues only)
+    pub eigenvalues: Option<Vec<f64>>,
+    /// Unit eigenvectors in the same order as the eigenvalues, mutually orthogonal and
+    /// with their largest component positive (eigenvalues only)
+    #[tool_types(nested)]
+    pub eigenvectors: Option<Vec<Vector3D>>,
+    /// Jacobi sweeps needed to reach the eigenvalues (eigenvalues only)
+    pub iterations: Option<usize>,
+}
+
+type Rows = [[f64; 3]; 3];
+
+impl Matrix3x3 {
+    fn from_rows(m: Rows) -> Self {
+        Matrix3x3 {
+            m00: m[0][0],
+            m01: m[0][1],
+            m02: m[0][2],
+            m10: m[1][0],
+            m11: m[1][1],
+            m12: m[1][2],
+            m20: m[2][0],
+            m21: m[2][1],
+            m22: m[2][2],
+        }
+    }
+
+    fn rows(&self) -> Rows {
+        [
+            [self.m00, self.m01, self.m02],
+            [self.m10, self.m11, self.m12],
+            [self.m20, self.m21, self.m22],
+        ]
+    }
+
+    fn is_finite(&self) -> bool {
+        self.rows().iter().flatten().all(|v| v.is_finite())
+    }
+
+    fn max_abs(&self) -> f64 {
+        self.rows()
+            .iter()
+            .flatten()
+            .fold(0.0, |max, v| max.max(v.abs()))
+    }
+
+    pub fn multiply(&self, other: &Matrix3x3) -> Matrix3x3 {
+        let (a, b) = (self.rows(), other.rows());
+        let mut m = [[0.0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+            }
+        }
+        Matrix3x3::from_rows(m)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.m00 * (self.m11 * self.m22 - self.m12 * self.m21)
+            - self.m01 * (self.m10 * self.m22 - self.m12 * self.m20)
+            + self.m02 * (self.m10 * self.m21 - self.m11 * self.m20)
+    }
+
+    /// Inverse through the adjugate
+    pub fn inverse(&self) -> Result<Matrix3x3, String> {
+        let det = self.determinant();
+        if det.abs() <= SINGULAR_TOLERANCE * self.max_abs().powi(3) || det == 0.0 {
+            return Err("Matrix is singular and has no inverse".to_string());
+        }
+        let m = self.rows();
+        let mut inverse = [[0.0; 3]; 3];
+        for (i, row) in inverse.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                // Cofactor of m[j][i], which gives the transpose directly
+                let (r0, r1) = ((j + 1) % 3, (j + 2) % 3);
+                let (c0, c1) = ((i + 1) % 3, (i + 2) % 3);
+                *value = (m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]) / det;
+            }
+        }
+        Ok(Matrix3x3::from_rows(inverse))
+    }
+
+    /// Rotation about a unit axis by `angle` radians (Rodrigues' formula)
+    pub fn from_axis_angle(axis: &Vector3D, angle: f64) -> Result<Matrix3x3, String> {
+        let length = (axis.x * axis.x + axis.y * axis.y + axis.z * axis.z).sqrt();
+        if length < 1e-10 {
+            return Err("Rotation axis cannot be a zero vector".to_string());
+        }
+        let (x, y, z) = (axis.x / length, axis.y / length, axis.z / length);
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+        Ok(Matrix3x3::from_rows([
+            [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+            [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+            [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+        ]))
+    }
+}
+
+fn check_symmetric(matrix: &Matrix3x3) -> Result<(), String> {
+    let scale = matrix.max_abs();
+    let m = matrix.rows();
+    for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+        if (m[p][q] - m[q][p]).abs() > SYMMETRY_TOLERANCE * scale.max(1.0) {
+            return Err(format!(
+                "Operation 'eigenvalues' requires a symmetric matrix, but m{p}{q} = {} and \
+                 m{q}{p} = {}",
+                m[p][q], m[q][p]
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Eigenvalues and eigenvectors of a symmetric matrix by cyclic Jacobi rotations,
+/// largest eigenvalue first, plus the number of sweeps taken
+fn symmetric_eigen(matrix: &Matrix3x3) -> Result<(Vec<f64>, Vec<Vector3D>, usize), String> {
+    check_symmetric(matrix)?;
+    let scale = matrix.max_abs();
+    let m = matrix.rows();
+
+    // Work on the exactly symmetric average so rounding in the input cannot stall
+    let mut a = m;
+    for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+        let mean = (m[p][q] + m[q][p]) / 2.0;
+        a[p][q] = mean;
+        a[q][p] = mean;
+    }
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    let mut sweeps = 0;
+    loop {
+        let off_diagonal = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+        if off_diagonal <= (f64::EPSILON * scale).powi(2) {
+            break;
+        }
+        if sweeps == MAX_SWEEPS {
+            return Err(format!(
+                "Eigenvalues did not converge after {MAX_SWEEPS} Jacobi sweeps"
+            ));
+        }
+        sweeps += 1;
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q] == 0.0 {
+                continue;
+            }
+            // Rotation in the p-q plane that zeroes a[p][q]
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            for row in a.iter_mut().chain(v.iter_mut()) {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+            let (row_p, row_q) = (a[p], a[q]);
+            for k in 0..3 {
+                a[p][k] = c * row_p[k] - s * row_q[k];
+                a[q][k] = s * row_p[k] + c * row_q[k];
+            }
+            // Exactly zero rather than whatever rounding left behind
+            a[p][q] = 0.0;
+            a[q][p] = 0.0;
+        }
+    }
+
+    let mut pairs: Vec<(f64, Vector3D)> = (0..3)
+        .map(|i| {
+            let mut vector = [v[0][i], v[1][i], v[2][i]];
+            // Eigenvectors are only defined up to sign; make the largest component positive
+            let largest = vector
+                .iter()
+                .copied()
+                .fold(0.0_f64, |max, c| if c.abs() > max.abs() { c } else { max });
+            if largest < 0.0 {
+                vector.iter_mut().for_each(|c| *c = -*c);
+            }
+            let [x, y, z] = vector;
+            (a[i][i], Vector3D { x, y, z })
+        })
+        .collect();
+    pairs.sort_by(|l, r| r.0.total_cmp(&l.0));
+    let (values, vectors) = pairs.into_iter().unzip();
+    Ok((values, vectors, sweeps))
+}
+
+fn required_matrix(
+    matrix: Option<Matrix3x3>,
+    name: &str,
+    operation: &str,
+) -> Result<Matrix3x3, String> {
+    matrix.ok_or_else(|| format!("Operation '{operation}' requires {name}"))
+}
+
+/// Check the inputs of an operation without computing it
+pub fn validate_matrix3_input(input: &Matrix3OpsInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    for (name, matrix) in [("a", &input.a), ("b", &input.b)] {
+        if let Some(matrix) = matrix
+            && !matrix.is_finite()
+        {
+            report.error(
+                name,
+                format!("Matrix {name} contains NaN or infinite values"),
+            );
+        }
+    }
+
+    let operation = input.operation.to_lowercase();
+    let used: &[&str] = match operation.as_str() {
+        "multiply" => &["a", "b"],
+        "determinant" | "inverse" | "eigenvalues" => &["a"],
+        "from_axis_angle" => &["axis", "angle"],
+        _ => {
+            report.error(
+                "operation",
+                format!(
+                    "Unsupported operation: {}. Use 'multiply', 'inverse', 'determinant', \
+                     'eigenvalues' or 'from_axis_angle'",
+                    input.operation
+                ),
+            );
+            return report;
+        }
+    };
+
+    for (name, matrix) in [("a", &input.a), ("b", &input.b)] {
+        if matrix.is_none() && used.contains(&name) {
+            report.error(name, format!("Operation '{operation}' requires {name}"));
+        }
+    }
+    for (name, present) in [
+        ("a", input.a.is_some()),
+        ("b", input.b.is_some()),
+        ("axis", input.axis.is_some()),
+        ("angle", input.angle.is_some()),
+    ] {
+        if present && !used.contains(&name) {
+            report.warning(name, format!("{name} is ignored by '{operation}'"));
+        }
+    }
+
+    match (operation.as_str(), &input.a) {
+        ("inverse", Some(a)) if a.is_finite() => {
+            if let Err(e) = a.inverse() {
+                report.error("a", e);
+            }
+        }
+        ("eigenvalues", Some(a)) if a.is_finite() => {
+            if let Err(e) = check_symmetric(a) {
+                report.error("a", e);
+            }
+        }
+        ("from_axis_angle", _) => match (&input.axis, input.angle) {
+            (Some(axis), Some(angle)) => {
+                if ![axis.x, axis.y, axis.z, angle]
+                    .iter()
+                    .all(|v| v.is_finite())
+                {
+                    report.error("axis", "Axis and angle must be finite");
+                } else if let Err(e) = Matrix3x3::from_axis_angle(axis, angle) {
+                    report.error("axis", e);
+                }
+            }
+            (axis, _) => {
+                let field = if axis.is_none() { "axis" } else { "angle" };
+                report.error(field, "Operation 'from_axis_angle' requires axis and angle");
+            }
+        },
+        _ => {}
+    }
+
+    report
+}
+
+pub fn compute_matrix3_ops(input: Matrix3OpsInput) -> Result<Matrix3OpsResponse, String> {
+    validate_matrix3_input(&input).into_result()?;
+
+    let operation = input.operation.to_lowercase();
+    let mut response = Matrix3OpsResponse {
+        operation: operation.clone(),
+        matrix: None,
+        determinant: None,
+        eigenvalues: None,
+        eigenvectors: None,
+        iterations: None,
+    };
+    match operation.as_str() {
+        "multiply" => {
+            let a = required_matrix(input.a, "a", &operation)?;
+            let b = required_matrix(input.b, "b", &operation)?;
+            response.matrix = Some(a.multiply(&b));
+        }
+        "determinant" => {
+            let a = required_matrix(input.a, "a", &operation)?;
+            response.determinant = Some(a.determinant());
+        }
+        "inverse" => {
+            let a = required_matrix(input.a, "a", &operation)?;
+            response.matrix = Some(a.inverse()?);
+            response.determinant = Some(a.determinant());
+        }
+        "eigenvalues" => {
+            let a = required_matrix(input.a, "a", &operation)?;
+            let (values, vectors, sweeps) = symmetric_eigen(&a)?;
+            response.eigenvalues = Some(values);
+            response.eigenvectors = Some(vectors);
+            response.iterations = Some(sweeps);
+        }
+        "from_axis_angle" => {
+            let (Some(axis), Some(angle)) = (input.axis, input.angle) else {
+                return Err("Operation 'from_axis_angle' requires axis and angle".to_string());
+            };
+            if ![axis.x, axis.y, axis.z, angle]
+                .iter()
+                .all(|v| v.is_finite())
+            {
+                return Err("Axis and angle must be finite".to_string());
+            }
+            response.matrix = Some(Matrix3x3::from_axis_angle(&axis, angle)?);
+        }
+        _ => {
+            return Err(format!(
+                "Unsupported operation: {}. Use 'multiply', 'inverse', 'determinant', \
+                 'eigenvalues' or 'from_axis_angle'",
+                input.operation
+            ));
+        }
+    }
+
+    if let Some(matrix) = &response.matrix
+        && !matrix.is_finite()
+    {
+        return Err("Result contains NaN or infinite values".to_string());
+    }
+    if let Some(determinant) = response.determinant
+        && !determinant.is_finite()
+    {
+        return Err("Result contains NaN or infinite values".to_string());
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    const EPSILON: f64 = 1e-12;
+
+    fn run(
+        operation: &str,
+        a: Option<Rows>,
+        b: Option<Rows>,
+    ) -> Result<Matrix3OpsResponse, String> {
+        compute_matrix3_ops(Matrix3OpsInput {
+            operation: operation.to_string(),
+            a: a.map(Matrix3x3::from_rows),
+            b: b.map(Matrix3x3::from_rows),
+            axis: None,
+            angle: None,
+        })
+    }
+
+    fn assert_close(actual: &Matrix3x3, expected: Rows) {
+        for (row, expected_row) in actual.rows().iter().zip(expected) {
+            for (value, expected_value) in row.iter().zip(expected_row) {
+                assert!(
+                    (value - expected_value).abs() < EPSILON,
+                    "{actual:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiply() {
+        let a = [[1.0, 2.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 2.0]];
+        let b = [[1.0, 0.0, 0.0], [3.0, 1.0, 0.0], [0.0, 1.0, 1.0]];
+        let result = run("multiply", Some(a), Some(b)).unwrap();
+        assert_eq!(result.operation, "multiply");
+        assert_close(
+            &result.matrix.unwrap(),
+            [[7.0, 2.0, 0.0], [3.0, 1.0, 0.0], [0.0, 2.0, 2.0]],
+        );
+        assert!(result.determinant.is_none() && result.eigenvalues.is_none());
+
+        let err = run("multiply", Some(a), None).unwrap_err();
+        assert_eq!(err, "Operation 'multiply' requires b");
+    }
+
+    #[test]
+    fn test_determinant() {
+        let a = [[2.0, 0.0, 1.0], [1.0, 3.0, 2.0], [1.0, 1.0, 4.0]];
+        let result = run("Determinant", Some(a), None).unwrap();
+        assert_eq!(result.operation, "determinant");
+        // 2·(12 - 2) - 0·(4 - 2) + 1·(1 - 3)
+        assert_eq!(result.determinant, Some(18.0));
+        assert!(result.matrix.is_none());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let a = [[2.0, 0.0, 0.0], [0.0, 1.0, 1.0], [0.0, 0.0, 4.0]];
+        let result = run("inverse", Some(a), None).unwrap();
+        assert_eq!(result.determinant, Some(8.0));
+        let inverse = result.matrix.unwrap();
+        assert_close(
+            &inverse,
+            [[0.5, 0.0, 0.0], [0.0, 1.0, -0.25], [0.0, 0.0, 0.25]],
+        );
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        assert_close(&Matrix3x3::from_rows(a).multiply(&inverse), identity);
+
+        let singular = [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]];
+        let err = run("inverse", Some(singular), None).unwrap_err();
+        assert_eq!(err, "Matrix is singular and has no inverse");
+    }
+
+    #[test]
+    fn test_eigenvalues() {
+        let a = [[2.0, 1.0, 0.0], [1.0, 2.0, 0.0], [0.0, 0.0, 5.0]];
+        let result = run("eigenvalues", Some(a), None).unwrap();
+        let values = result.eigenvalues.unwrap();
+        let vectors = result.eigenvectors.unwrap();
+        for (value, expected) in values.iter().zip([5.0, 3.0, 1.0]) {
+            assert!((value - expected).abs() < EPSILON, "{values:?}");
+        }
+        assert!(result.iterations.unwrap() <= 5);
+
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((vectors[0].z - 1.0).abs() < EPSILON);
+        assert!((vectors[1].x - half).abs() < EPSILON && (vectors[1].y - half).abs() < EPSILON);
+        // Each pair satisfies a · v = λ · v
+        for (value, vector) in values.iter().zip(&vectors) {
+            let v = [vector.x, vector.y, vector.z];
+            for (row, component) in a.iter().zip(v) {
+                let product: f64 = row.iter().zip(v).map(|(m, c)| m * c).sum();
+                assert!((product - value * component).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eigenvalues_dense_symmetric() {
+        let a = [[4.0, -2.0, 1.0], [-2.0, 3.0, 0.5], [1.0, 0.5, -1.0]];
+        let result = run("eigenvalues", Some(a), None).unwrap();
+        let values = result.eigenvalues.unwrap();
+        let vectors = result.eigenvectors.unwrap();
+        // Trace and determinant are preserved by the eigenvalues
+        let trace: f64 = values.iter().sum();
+        let product: f64 = values.iter().product();
+        assert!((trace - 6.0).abs() < 1e-10);
+        assert!((product - Matrix3x3::from_rows(a).determinant()).abs() < 1e-10);
+        assert!(values.windows(2).all(|w| w[0] >= w[1]));
+        let dot = |u: &Vector3D, v: &Vector3D| u.x * v.x + u.y * v.y + u.z * v.z;
+        assert!(dot(&vectors[0], &vectors[1]).abs() < 1e-10);
+        assert!((dot(&vectors[2], &vectors[2]) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_eigenvalues_requires_symmetric() {
+        let a = [[1.0, 2.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let err = run("eigenvalues", Some(a), None).unwrap_err();
+        assert!(err.contains("requires a symmetric matrix"), "{err}");
+        assert!(err.contains("m01 = 2"));
+    }
+
+    #[test]
+    fn test_from_axis_angle() {
+        let result = compute_matrix3_ops(Matrix3OpsInput {
+            operation: "from_axis_angle".to_string(),
+            a: None,
+            b: None,
+            axis: Some(Vector3D {
+                x: 0.0,
+                y: 0.0,
+                z: 2.0,
+            }),
+            angle: Some(FRAC_PI_2),
+        })
+        .unwrap();
+        let rotation = result.matrix.unwrap();
+        assert_close(
+            &rotation,
+            [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+        );
+        assert!((rotation.determinant() - 1.0).abs() < EPSILON);
+
+        let err = compute_matrix3_ops(Matrix3OpsInput {
+            operation: "from_axis_angle".to_string(),
+            a: None,
+            b: None,
+            axis: Some(Vector3D {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            }),
+            angle: Some(1.0),
+        })
+        .unwrap_err();
+        assert_eq!(err, "Rotation axis cannot be a zero vector");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let mut nan = identity;
+        nan[1][2] = f64::NAN;
+        assert_eq!(
+            run("determinant", Some(nan), None).unwrap_err(),
+            "Matrix a contains NaN or infinite values"
+        );
+        assert_eq!(
+            run("multiply", Some(identity), Some(nan)).unwrap_err(),
+            "Matrix b contains NaN or infinite values"
+        );
+        assert_eq!(
+            run("inverse", None, None).unwrap_err(),
+            "Operation 'inverse' requires a"
+        );
+        assert_eq!(
+            run("from_axis_angle", None, None).unwrap_err(),
+            "Operation 'from_axis_angle' requires axis and angle"
+        );
+        let err = run("transpose", Some(identity), None).unwrap_err();
+        assert!(err.starts_with("Unsupported operation: transpose"));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let mut nan = Matrix3x3::from_rows([[1.0, 2.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        nan.m22 = f64::INFINITY;
+        let report = validate_matrix3_input(&Matrix3OpsInput {
+            operation: "multiply".to_string(),
+            a: Some(nan),
+            b: None,
+            axis: None,
+            angle: Some(1.0),
+        });
+        assert!(!report.valid);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["a", "b"]);
+        assert_eq!(report.warnings[0].message, "angle is ignored by 'multiply'");
+
+        let singular = [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]];
+        let report = validate_matrix3_input(&Matrix3OpsInput {
+            operation: "inverse".to_string(),
+            a: Some(Matrix3x3::from_rows(singular)),
+            b: None,
+            axis: None,
+            angle: None,
+        });
+        assert_eq!(
+            report.errors[0].message,
+            "Matrix is singular and has no inverse"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    Matrix4OpsInput as LogicInput, Matrix4OpsResponse as LogicOutput, compute_matrix4_ops,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    DecompositionSchema as Decomposition, Matrix3x3Schema as Matrix3x3,
+    Matrix4OpsResponseSchema as Matrix4OpsResponse, QuaternionSchema as Quaternion,
+    Vector3DSchema as Vector3D,
+};
+
+// Shared matrix type, which derives JsonSchema itself
+pub use ftl_matrix4::Matrix4;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Matrix4OpsInput {
+    #[serde(flatten)]
+    pub params: logic::Matrix4OpsInputSchema,
+    /// Only check the input and return a validation report, without computing
+    pub validate_only: Option<bool>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Multiply, transpose, invert or take the determinant of 4x4 transform matrices,
+/// or decompose one into translation, rotation and scale
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn matrix4_ops(request: Request<Matrix4OpsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "matrix4_ops",
+                Matrix4OpsInput,
+                Matrix4OpsResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("matrix4_ops", &input);
+    log.finish(run_matrix4_ops(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_matrix4_ops(input: Matrix4OpsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let params: logic::Matrix4OpsInput = input.params.into();
+
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_matrix4_input(&params);
+        return ToolResponse::text(
+            ftl_json::to_string(&report).unwrap_or_else(|_| "Error serializing result".to_string()),
+        );
+    }
+
+    match logic::compute_matrix4_ops(params) {
+        Ok(logic_result) => {
+            let result = Matrix4OpsResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
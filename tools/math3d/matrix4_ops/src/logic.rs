@@ -0,0 +1,327 @@
+use ftl_matrix4::Matrix4;
+use ftl_preflight::ValidationReport;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+/// 3x3 rotation matrix, mRC is row R and column C
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Matrix3x3 {
+    pub m00: f64,
+    pub m01: f64,
+    pub m02: f64,
+    pub m10: f64,
+    pub m11: f64,
+    pub m12: f64,
+    pub m20: f64,
+    pub m21: f64,
+    pub m22: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Matrix4OpsInput {
+    /// Operation: multiply, transpose, determinant, inverse or decompose
+    pub operation: String,
+    /// First matrix, as four rows of four numbers; the only one used except by multiply
+    pub a: Matrix4,
+    /// Second matrix, required by multiply, which returns a · b (b applied first)
+    pub b: Option<Matrix4>,
+}
+
+/// Affine matrix split as translation · rotation · scale
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Decomposition {
+    /// Translation, the last column
+    #[tool_types(nested)]
+    pub translation: Vector3D,
+    /// Rotation matrix, with determinant +1
+    #[tool_types(nested)]
+    pub rotation: Matrix3x3,
+    /// Rotation as a unit quaternion, with w ≥ 0
+    #[tool_types(nested)]
+    pub quaternion: Quaternion,
+    /// Scale along each local axis, applied before the rotation; a reflection gives a
+    /// negative x scale
+    #[tool_types(nested)]
+    pub scale: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Matrix4OpsResponse {
+    /// Operation performed
+    pub operation: String,
+    /// Resulting matrix (multiply, transpose and inverse)
+    pub matrix: Option<Matrix4>,
+    /// Determinant of a (determinant and inverse)
+    pub determinant: Option<f64>,
+    /// Translation, rotation and scale of a (decompose only)
+    #[tool_types(nested)]
+    pub decomposition: Option<Decomposition>,
+}
+
+impl From<ftl_matrix4::Decomposition> for Decomposition {
+    fn from(d: ftl_matrix4::Decomposition) -> Self {
+        let vector = |[x, y, z]: [f64; 3]| Vector3D { x, y, z };
+        let [[m00, m01, m02], [m10, m11, m12], [m20, m21, m22]] = d.rotation;
+        let [x, y, z, w] = d.quaternion;
+        Decomposition {
+            translation: vector(d.translation),
+            rotation: Matrix3x3 {
+                m00,
+                m01,
+                m02,
+                m10,
+                m11,
+                m12,
+                m20,
+                m21,
+                m22,
+            },
+            quaternion: Quaternion { x, y, z, w },
+            scale: vector(d.scale),
+        }
+    }
+}
+
+/// Check the inputs of an operation without computing it
+pub fn validate_matrix4_input(input: &Matrix4OpsInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    if !input.a.is_finite() {
+        report.error("a", "Matrix a contains NaN or infinite values");
+    }
+    if let Some(b) = &input.b
+        && !b.is_finite()
+    {
+        report.error("b", "Matrix b contains NaN or infinite values");
+    }
+
+    let operation = input.operation.to_lowercase();
+    match operation.as_str() {
+        "multiply" => {
+            if input.b.is_none() {
+                report.error("b", "Operation 'multiply' requires b");
+            }
+        }
+        "transpose" | "determinant" | "inverse" | "decompose" => {
+            if input.b.is_some() {
+                report.warning("b", format!("b is ignored by '{operation}'"));
+            }
+        }
+        _ => report.error(
+            "operation",
+            format!(
+                "Unsupported operation: {}. Use 'multiply', 'transpose', 'determinant', \
+                 'inverse' or 'decompose'",
+                input.operation
+            ),
+        ),
+    }
+
+    // Singular and sheared matrices are only found by attempting the operation
+    if report.valid {
+        let attempt = match operation.as_str() {
+            "inverse" => input.a.inverse().map(|_| ()),
+            "decompose" => input.a.decompose().map(|_| ()),
+            _ => Ok(()),
+        };
+        if let Err(e) = attempt {
+            report.error("a", e);
+        }
+    }
+
+    report
+}
+
+pub fn compute_matrix4_ops(input: Matrix4OpsInput) -> Result<Matrix4OpsResponse, String> {
+    validate_matrix4_input(&input).into_result()?;
+
+    let operation = input.operation.to_lowercase();
+    let mut response = Matrix4OpsResponse {
+        operation: operation.clone(),
+        matrix: None,
+        determinant: None,
+        decomposition: None,
+    };
+    match operation.as_str() {
+        "multiply" => {
+            let b = input
+                .b
+                .ok_or_else(|| "Operation 'multiply' requires b".to_string())?;
+            response.matrix = Some(input.a.multiply(&b));
+        }
+        "transpose" => response.matrix = Some(input.a.transpose()),
+        "determinant" => response.determinant = Some(input.a.determinant()),
+        "inverse" => {
+            response.matrix = Some(input.a.inverse()?);
+            response.determinant = Some(input.a.determinant());
+        }
+        "decompose" => response.decomposition = Some(input.a.decompose()?.into()),
+        _ => {
+            return Err(format!(
+                "Unsupported operation: {}. Use 'multiply', 'transpose', 'determinant', \
+                 'inverse' or 'decompose'",
+                input.operation
+            ));
+        }
+    }
+
+    if let Some(matrix) = &response.matrix
+        && !matrix.is_finite()
+    {
+        return Err("Result contains NaN or infinite values".to_string());
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(operation: &str, a: Matrix4, b: Option<Matrix4>) -> Result<Matrix4OpsResponse, String> {
+        compute_matrix4_ops(Matrix4OpsInput {
+            operation: operation.to_string(),
+            a,
+            b,
+        })
+    }
+
+    fn translation(x: f64, y: f64, z: f64) -> Matrix4 {
+        Matrix4::from_translation([x, y, z])
+    }
+
+    #[test]
+    fn test_multiply() {
+        let result = run(
+            "multiply",
+            translation(1.0, 2.0, 3.0),
+            Some(translation(-1.0, 0.5, 0.0)),
+        )
+        .unwrap();
+        assert_eq!(result.operation, "multiply");
+        assert_eq!(result.matrix, Some(translation(0.0, 2.5, 3.0)));
+        assert!(result.determinant.is_none() && result.decomposition.is_none());
+    }
+
+    #[test]
+    fn test_multiply_requires_b() {
+        let err = run("multiply", Matrix4::IDENTITY, None).unwrap_err();
+        assert_eq!(err, "Operation 'multiply' requires b");
+    }
+
+    #[test]
+    fn test_transpose() {
+        let result = run("Transpose", translation(1.0, 2.0, 3.0), None).unwrap();
+        assert_eq!(result.operation, "transpose");
+        let m = result.matrix.unwrap();
+        assert_eq!(m.0[3], [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(m.0[0], [1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_determinant() {
+        let scale = Matrix4::from_scale([2.0, -3.0, 0.5]);
+        let result = run("determinant", scale, None).unwrap();
+        assert_eq!(result.determinant, Some(-3.0));
+        assert!(result.matrix.is_none());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let result = run("inverse", translation(1.0, 2.0, 3.0), None).unwrap();
+        assert_eq!(result.matrix, Some(translation(-1.0, -2.0, -3.0)));
+        assert_eq!(result.determinant, Some(1.0));
+
+        let err = run("inverse", Matrix4::from_scale([1.0, 0.0, 1.0]), None).unwrap_err();
+        assert_eq!(err, "Matrix is singular and has no inverse");
+    }
+
+    #[test]
+    fn test_decompose() {
+        let quarter_turn_z = [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        let a = Matrix4::compose([4.0, 5.0, 6.0], quarter_turn_z, [2.0, 2.0, 2.0]);
+        let d = run("decompose", a, None).unwrap().decomposition.unwrap();
+        assert_eq!(
+            d.translation,
+            Vector3D {
+                x: 4.0,
+                y: 5.0,
+                z: 6.0
+            }
+        );
+        assert_eq!(
+            d.scale,
+            Vector3D {
+                x: 2.0,
+                y: 2.0,
+                z: 2.0
+            }
+        );
+        assert_eq!((d.rotation.m01, d.rotation.m10), (-1.0, 1.0));
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((d.quaternion.z - half).abs() < 1e-12 && (d.quaternion.w - half).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_decompose_rejects_shear() {
+        let mut shear = Matrix4::IDENTITY;
+        shear.0[0][1] = 1.0;
+        let err = run("decompose", shear, None).unwrap_err();
+        assert!(err.contains("shear"));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut nan = Matrix4::IDENTITY;
+        nan.0[1][2] = f64::NAN;
+        assert_eq!(
+            run("transpose", nan, None).unwrap_err(),
+            "Matrix a contains NaN or infinite values"
+        );
+        assert_eq!(
+            run("multiply", Matrix4::IDENTITY, Some(nan)).unwrap_err(),
+            "Matrix b contains NaN or infinite values"
+        );
+        let err = run("adjugate", Matrix4::IDENTITY, None).unwrap_err();
+        assert!(err.starts_with("Unsupported operation: adjugate"));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let mut nan = Matrix4::IDENTITY;
+        nan.0[0][0] = f64::NAN;
+        let report = validate_matrix4_input(&Matrix4OpsInput {
+            operation: "multiply".to_string(),
+            a: nan,
+            b: None,
+        });
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["a", "b"]);
+
+        let mut shear = Matrix4::IDENTITY;
+        shear.0[0][1] = 1.0;
+        let report = validate_matrix4_input(&Matrix4OpsInput {
+            operation: "decompose".to_string(),
+            a: shear,
+            b: Some(Matrix4::IDENTITY),
+        });
+        assert!(!report.valid);
+        assert!(report.errors[0].message.contains("shear"));
+        assert_eq!(report.warnings[0].message, "b is ignored by 'decompose'");
+    }
+}
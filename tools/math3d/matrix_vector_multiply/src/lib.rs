@@ -1,36 +1,82 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 
 mod logic;
-use logic::{MatrixVectorInput, matrix_vector_multiply_logic};
+pub use logic::{MatrixVectorInput, matrix_vector_multiply_logic};
 
-#[derive(serde::Deserialize, JsonSchema)]
+#[derive(serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct ToolInput {
     matrix: logic::Matrix3x3,
     vector: logic::Vector3D,
+    /// Only check the input and return a validation report, without computing
+    validate_only: Option<bool>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(serde::Serialize, JsonSchema)]
 struct ToolOutput {
     /// The resulting vector from matrix-vector multiplication
     result: logic::Vector3D,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn matrix_vector_multiply(input: ToolInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn matrix_vector_multiply(request: Request<ToolInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("matrix_vector_multiply", ToolInput, ToolOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("matrix_vector_multiply", &input);
+    log.finish(run_matrix_vector_multiply(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_matrix_vector_multiply(input: ToolInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     let logic_input = MatrixVectorInput {
         matrix: input.matrix,
         vector: input.vector,
     };
 
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_matrix_vector_input(&logic_input);
+        return ToolResponse::text(
+            ftl_json::to_string(&report).unwrap_or_else(|_| "Error serializing result".to_string()),
+        );
+    }
+
     match matrix_vector_multiply_logic(logic_input) {
         Ok(output) => {
             let result = ToolOutput {
                 result: output.result,
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,3 +1,4 @@
+use ftl_preflight::ValidationReport;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -14,13 +15,17 @@ pub struct Matrix3x3 {
     pub m22: f64,
 }
 
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatrixVectorInput {
     pub matrix: Matrix3x3,
@@ -56,26 +61,37 @@ impl Vector3D {
     }
 }
 
-pub fn matrix_vector_multiply_logic(
-    input: MatrixVectorInput,
-) -> Result<MatrixVectorOutput, String> {
-    // Input validation
+/// Check the matrix and vector without returning the product
+pub fn validate_matrix_vector_input(input: &MatrixVectorInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
     if !input.matrix.is_valid() {
-        return Err("Invalid matrix: contains NaN or infinite values".to_string());
+        report.error("matrix", "Invalid matrix: contains NaN or infinite values");
     }
 
     if !input.vector.is_valid() {
-        return Err("Invalid vector: contains NaN or infinite values".to_string());
+        report.error("vector", "Invalid vector: contains NaN or infinite values");
+    }
+
+    // Finite inputs can still overflow
+    if report.valid && !input.matrix.multiply_vector(&input.vector).is_valid() {
+        report.error(
+            "matrix",
+            "Matrix-vector multiplication resulted in invalid values",
+        );
     }
 
+    report
+}
+
+pub fn matrix_vector_multiply_logic(
+    input: MatrixVectorInput,
+) -> Result<MatrixVectorOutput, String> {
+    validate_matrix_vector_input(&input).into_result()?;
+
     // Perform matrix-vector multiplication
     let result = input.matrix.multiply_vector(&input.vector);
 
-    // Validate result
-    if !result.is_valid() {
-        return Err("Matrix-vector multiplication resulted in invalid values".to_string());
-    }
-
     Ok(MatrixVectorOutput { result })
 }
 
@@ -419,4 +435,47 @@ mod tests {
         };
         assert!(!invalid_vector.is_valid());
     }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let input = MatrixVectorInput {
+            matrix: Matrix3x3 {
+                m00: f64::INFINITY,
+                m01: 0.0,
+                m02: 0.0,
+                m10: 0.0,
+                m11: 1.0,
+                m12: 0.0,
+                m20: 0.0,
+                m21: 0.0,
+                m22: 1.0,
+            },
+            vector: Vector3D {
+                x: f64::NAN,
+                y: 0.0,
+                z: 0.0,
+            },
+        };
+        let report = validate_matrix_vector_input(&input);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["matrix", "vector"]);
+
+        let overflow = MatrixVectorInput {
+            matrix: Matrix3x3 {
+                m00: f64::MAX,
+                m01: f64::MAX,
+                ..input.matrix
+            },
+            vector: Vector3D {
+                x: 2.0,
+                y: 2.0,
+                z: 0.0,
+            },
+        };
+        let report = validate_matrix_vector_input(&overflow);
+        assert_eq!(
+            report.errors[0].message,
+            "Matrix-vector multiplication resulted in invalid values"
+        );
+    }
 }
@@ -0,0 +1,100 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    MeshRaycastInput as LogicInput, MeshRaycastResult as LogicOutput, mesh_raycast_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    MeshRaycastResultSchema as MeshRaycastResult, RayHitSchema as RayHit, RaySchema as Ray,
+    Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeshRaycastInput {
+    #[serde(flatten)]
+    pub params: logic::MeshRaycastInputSchema,
+    /// Only check the mesh and ray and return a validation report, without casting
+    pub validate_only: Option<bool>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Cast a ray against a triangle mesh given as vertex and index arrays, using a bounding
+/// volume hierarchy to return the nearest hit and every hit along the ray
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn mesh_raycast(request: Request<MeshRaycastInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "mesh_raycast",
+                MeshRaycastInput,
+                MeshRaycastResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("mesh_raycast", &input);
+    log.finish(run_mesh_raycast(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_mesh_raycast(input: MeshRaycastInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("vertices", &input.params.vertices)
+        .array("indices", &input.params.indices)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let params: logic::MeshRaycastInput = input.params.into();
+
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_mesh_raycast_input(&params);
+        return ToolResponse::text(
+            ftl_json::to_string(&report).unwrap_or_else(|_| "Error serializing result".to_string()),
+        );
+    }
+
+    match logic::mesh_raycast_logic(params) {
+        Ok(logic_result) => {
+            let result = MeshRaycastResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
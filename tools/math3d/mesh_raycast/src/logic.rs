@@ -0,0 +1,614 @@
+use ftl_preflight::ValidationReport;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most triangles accepted in one mesh
+pub const MAX_TRIANGLES: usize = 500_000;
+/// Triangles per BVH leaf
+const LEAF_SIZE: usize = 4;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Ray {
+    #[tool_types(nested)]
+    pub origin: Vector3D,
+    /// Ray direction, normalized before use so distances are in world units
+    #[tool_types(nested)]
+    pub direction: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct MeshRaycastInput {
+    /// Mesh vertex positions
+    #[tool_types(nested)]
+    pub vertices: Vec<Vector3D>,
+    /// Vertex indices, three per triangle; counterclockwise winding faces the viewer
+    pub indices: Vec<u32>,
+    #[tool_types(nested)]
+    pub ray: Ray,
+    /// Ignore hits farther than this distance (default: unlimited)
+    pub max_distance: Option<f64>,
+    /// Ignore triangles facing away from the ray (default: false)
+    pub cull_backfaces: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct RayHit {
+    /// Index of the triangle hit (position in indices divided by 3)
+    pub triangle: usize,
+    /// Distance from the ray origin
+    pub distance: f64,
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Unit normal of the triangle, following its winding
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+    /// Barycentric weight of the triangle's second vertex
+    pub u: f64,
+    /// Barycentric weight of the triangle's third vertex; the first has 1 - u - v
+    pub v: f64,
+    /// Whether the ray hits the side the normal points to
+    pub front_facing: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct MeshRaycastResult {
+    /// Whether the ray hits the mesh
+    pub hit: bool,
+    /// Closest hit
+    #[tool_types(nested)]
+    pub nearest: Option<RayHit>,
+    /// Every hit, nearest first
+    #[tool_types(nested)]
+    pub hits: Vec<RayHit>,
+    /// Triangles in the mesh, including degenerate ones that cannot be hit
+    pub triangle_count: usize,
+    /// Nodes in the bounding volume hierarchy built for the mesh
+    pub bvh_nodes: usize,
+    /// Triangles tested against the ray after BVH culling
+    pub triangles_tested: usize,
+}
+
+type V = [f64; 3];
+
+fn sub(a: V, b: V) -> V {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: V, b: V) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: V, b: V) -> V {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn to_array(v: &Vector3D) -> V {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(v: V) -> Vector3D {
+    Vector3D {
+        x: v[0],
+        y: v[1],
+        z: v[2],
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    min: V,
+    max: V,
+}
+
+impl Bounds {
+    const EMPTY: Bounds = Bounds {
+        min: [f64::INFINITY; 3],
+        max: [f64::NEG_INFINITY; 3],
+    };
+
+    fn grow(&mut self, p: V) {
+        for (axis, &c) in p.iter().enumerate() {
+            self.min[axis] = self.min[axis].min(c);
+            self.max[axis] = self.max[axis].max(c);
+        }
+    }
+
+    fn longest_axis(&self) -> usize {
+        let extent = sub(self.max, self.min);
+        if extent[0] >= extent[1] && extent[0] >= extent[2] {
+            0
+        } else if extent[1] >= extent[2] {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Whether the ray enters the box within [0, t_max] (slab test)
+    fn hit_by(&self, origin: V, direction: V, t_max: f64) -> bool {
+        let (mut near, mut far) = (0.0_f64, t_max);
+        for axis in 0..3 {
+            if direction[axis] == 0.0 {
+                if origin[axis] < self.min[axis] || origin[axis] > self.max[axis] {
+                    return false;
+                }
+                continue;
+            }
+            let inv = 1.0 / direction[axis];
+            let t0 = (self.min[axis] - origin[axis]) * inv;
+            let t1 = (self.max[axis] - origin[axis]) * inv;
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+            if near > far {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// BVH node; leaves hold `count` triangles starting at `first` in the ordered list,
+/// inner nodes have `count == 0` and children at `first` and `first + 1`
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    bounds: Bounds,
+    first: usize,
+    count: usize,
+}
+
+struct Bvh {
+    nodes: Vec<Node>,
+    /// Triangle indices in leaf order
+    order: Vec<usize>,
+}
+
+impl Bvh {
+    /// Top-down build splitting at the median centroid along the longest axis
+    fn build(triangles: &[[V; 3]]) -> Bvh {
+        let centroids: Vec<V> = triangles
+            .iter()
+            .map(|t| [0, 1, 2].map(|axis| (t[0][axis] + t[1][axis] + t[2][axis]) / 3.0))
+            .collect();
+        let mut bvh = Bvh {
+            nodes: Vec::with_capacity(2 * triangles.len() / LEAF_SIZE + 1),
+            order: (0..triangles.len()).collect(),
+        };
+        bvh.nodes.push(Node {
+            bounds: Bounds::EMPTY,
+            first: 0,
+            count: triangles.len(),
+        });
+
+        // Nodes still to split, as (node, start, end) ranges of `order`
+        let mut pending = vec![(0, 0, triangles.len())];
+        while let Some((node, start, end)) = pending.pop() {
+            let mut bounds = Bounds::EMPTY;
+            let mut centroid_bounds = Bounds::EMPTY;
+            for &t in &bvh.order[start..end] {
+                triangles[t].iter().for_each(|&p| bounds.grow(p));
+                centroid_bounds.grow(centroids[t]);
+            }
+            bvh.nodes[node] = Node {
+                bounds,
+                first: start,
+                count: end - start,
+            };
+            if end - start <= LEAF_SIZE {
+                continue;
+            }
+
+            let axis = centroid_bounds.longest_axis();
+            let mid = start + (end - start) / 2;
+            bvh.order[start..end].select_nth_unstable_by(mid - start, |&a, &b| {
+                centroids[a][axis].total_cmp(&centroids[b][axis])
+            });
+
+            let left = bvh.nodes.len();
+            let placeholder = Node {
+                bounds: Bounds::EMPTY,
+                first: 0,
+                count: 0,
+            };
+            bvh.nodes.push(placeholder);
+            bvh.nodes.push(placeholder);
+            bvh.nodes[node].first = left;
+            bvh.nodes[node].count = 0;
+            pending.push((left, start, mid));
+            pending.push((left + 1, mid, end));
+        }
+        bvh
+    }
+}
+
+/// Möller-Trumbore intersection, returning (distance, u, v, front_facing)
+fn intersect_triangle(
+    origin: V,
+    direction: V,
+    t: &[V; 3],
+    cull_backfaces: bool,
+) -> Option<(f64, f64, f64, bool)> {
+    let edge1 = sub(t[1], t[0]);
+    let edge2 = sub(t[2], t[0]);
+    let p = cross(direction, edge2);
+    let det = dot(edge1, p);
+    let scale = dot(edge1, edge1).max(dot(edge2, edge2));
+    if det.abs() <= 1e-12 * scale {
+        // Parallel to the triangle, or a degenerate triangle
+        return None;
+    }
+    let front_facing = det > 0.0;
+    if cull_backfaces && !front_facing {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let s = sub(origin, t[0]);
+    let u = dot(s, p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = cross(s, edge1);
+    let v = dot(direction, q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = dot(edge2, q) * inv_det;
+    (distance >= 0.0).then_some((distance, u, v, front_facing))
+}
+
+/// Check the mesh and ray without casting
+pub fn validate_mesh_raycast_input(input: &MeshRaycastInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    if !input.indices.len().is_multiple_of(3) {
+        report.error(
+            "indices",
+            format!(
+                "indices must hold three entries per triangle, got {}",
+                input.indices.len()
+            ),
+        );
+    }
+    let triangle_count = input.indices.len() / 3;
+    if triangle_count == 0 {
+        report.error("indices", "The mesh needs at least one triangle");
+    } else if triangle_count > MAX_TRIANGLES {
+        report.error(
+            "indices",
+            format!("At most {MAX_TRIANGLES} triangles are supported"),
+        );
+    }
+    if let Some(i) = input
+        .vertices
+        .iter()
+        .position(|v| !to_array(v).iter().all(|c| c.is_finite()))
+    {
+        report.error(
+            format!("vertices[{i}]"),
+            format!("Vertex {i} contains NaN or infinite values"),
+        );
+    }
+    if let Some((position, &index)) = input
+        .indices
+        .iter()
+        .enumerate()
+        .find(|&(_, &i)| i as usize >= input.vertices.len())
+    {
+        report.error(
+            format!("indices[{position}]"),
+            format!(
+                "Index {index} is out of range for {} vertices",
+                input.vertices.len()
+            ),
+        );
+    }
+
+    let origin = to_array(&input.ray.origin);
+    let direction = to_array(&input.ray.direction);
+    if !origin.iter().chain(&direction).all(|c| c.is_finite()) {
+        report.error("ray", "Ray origin and direction must be finite");
+    } else if dot(direction, direction) == 0.0 {
+        report.error("ray.direction", "Ray direction cannot be zero vector");
+    }
+    if let Some(d) = input.max_distance
+        && (d.is_nan() || d < 0.0)
+    {
+        report.error("max_distance", "max_distance must be a non-negative number");
+    }
+
+    report
+}
+
+pub fn mesh_raycast_logic(input: MeshRaycastInput) -> Result<MeshRaycastResult, String> {
+    validate_mesh_raycast_input(&input).into_result()?;
+
+    let origin = to_array(&input.ray.origin);
+    let direction = to_array(&input.ray.direction);
+    let length = dot(direction, direction).sqrt();
+    let direction = direction.map(|c| c / length);
+    let max_distance = input.max_distance.unwrap_or(f64::INFINITY);
+    let triangle_count = input.indices.len() / 3;
+    let cull_backfaces = input.cull_backfaces.unwrap_or(false);
+
+    let triangles: Vec<[V; 3]> = input
+        .indices
+        .chunks_exact(3)
+        .map(|c| [0, 1, 2].map(|k| to_array(&input.vertices[c[k] as usize])))
+        .collect();
+    let bvh = Bvh::build(&triangles);
+
+    let mut hits = Vec::new();
+    let mut triangles_tested = 0;
+    let mut stack = vec![0];
+    while let Some(index) = stack.pop() {
+        let node = bvh.nodes[index];
+        if !node.bounds.hit_by(origin, direction, max_distance) {
+            continue;
+        }
+        if node.count == 0 {
+            stack.push(node.first);
+            stack.push(node.first + 1);
+            continue;
+        }
+        for &triangle in &bvh.order[node.first..node.first + node.count] {
+            triangles_tested += 1;
+            let t = &triangles[triangle];
+            let Some((distance, u, v, front_facing)) =
+                intersect_triangle(origin, direction, t, cull_backfaces)
+            else {
+                continue;
+            };
+            if distance > max_distance {
+                continue;
+            }
+            let n = cross(sub(t[1], t[0]), sub(t[2], t[0]));
+            let n_length = dot(n, n).sqrt();
+            hits.push(RayHit {
+                triangle,
+                distance,
+                point: to_vector([0, 1, 2].map(|axis| origin[axis] + direction[axis] * distance)),
+                normal: to_vector(n.map(|c| c / n_length)),
+                u,
+                v,
+                front_facing,
+            });
+        }
+    }
+
+    hits.sort_by(|a, b| {
+        a.distance
+            .total_cmp(&b.distance)
+            .then(a.triangle.cmp(&b.triangle))
+    });
+
+    Ok(MeshRaycastResult {
+        hit: !hits.is_empty(),
+        nearest: hits.first().cloned(),
+        hits,
+        triangle_count,
+        bvh_nodes: bvh.nodes.len(),
+        triangles_tested,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn ray(origin: Vector3D, direction: Vector3D) -> Ray {
+        Ray { origin, direction }
+    }
+
+    /// Axis-aligned unit cube from 0 to 1 with outward-facing triangles
+    fn cube() -> (Vec<Vector3D>, Vec<u32>) {
+        let vertices = vec![
+            v(0.0, 0.0, 0.0),
+            v(1.0, 0.0, 0.0),
+            v(1.0, 1.0, 0.0),
+            v(0.0, 1.0, 0.0),
+            v(0.0, 0.0, 1.0),
+            v(1.0, 0.0, 1.0),
+            v(1.0, 1.0, 1.0),
+            v(0.0, 1.0, 1.0),
+        ];
+        let indices = vec![
+            0, 2, 1, 0, 3, 2, // bottom (z = 0)
+            4, 5, 6, 4, 6, 7, // top (z = 1)
+            0, 1, 5, 0, 5, 4, // front (y = 0)
+            3, 7, 6, 3, 6, 2, // back (y = 1)
+            0, 4, 7, 0, 7, 3, // left (x = 0)
+            1, 2, 6, 1, 6, 5, // right (x = 1)
+        ];
+        (vertices, indices)
+    }
+
+    fn input(vertices: Vec<Vector3D>, indices: Vec<u32>, ray: Ray) -> MeshRaycastInput {
+        MeshRaycastInput {
+            vertices,
+            indices,
+            ray,
+            max_distance: None,
+            cull_backfaces: None,
+        }
+    }
+
+    /// A grid of `n` x `n` quads in the plane z = 0, spanning 0..n
+    fn grid(n: u32) -> (Vec<Vector3D>, Vec<u32>) {
+        let mut vertices = Vec::new();
+        for y in 0..=n {
+            for x in 0..=n {
+                vertices.push(v(x as f64, y as f64, 0.0));
+            }
+        }
+        let mut indices = Vec::new();
+        for y in 0..n {
+            for x in 0..n {
+                let i = y * (n + 1) + x;
+                indices.extend([i, i + 1, i + n + 2, i, i + n + 2, i + n + 1]);
+            }
+        }
+        (vertices, indices)
+    }
+
+    #[test]
+    fn test_ray_through_cube() {
+        let (vertices, indices) = cube();
+        let result = mesh_raycast_logic(input(
+            vertices,
+            indices,
+            ray(v(0.25, 0.5, 5.0), v(0.0, 0.0, -2.0)),
+        ))
+        .unwrap();
+
+        assert!(result.hit);
+        assert_eq!(result.triangle_count, 12);
+        assert_eq!(result.hits.len(), 2);
+        let nearest = result.nearest.unwrap();
+        assert_eq!(nearest.distance, 4.0);
+        assert_eq!(nearest.point, v(0.25, 0.5, 1.0));
+        assert_eq!(nearest.normal, v(0.0, 0.0, 1.0));
+        assert!(nearest.front_facing);
+        assert!(nearest.triangle == 2 || nearest.triangle == 3);
+
+        // The exit through the bottom face sees its back
+        let exit = &result.hits[1];
+        assert_eq!(exit.distance, 5.0);
+        assert_eq!(exit.normal, v(0.0, 0.0, -1.0));
+        assert!(!exit.front_facing);
+    }
+
+    #[test]
+    fn test_cull_backfaces_and_max_distance() {
+        let (vertices, indices) = cube();
+        let mut request = input(vertices, indices, ray(v(0.5, -3.0, 0.25), v(0.0, 1.0, 0.0)));
+        request.cull_backfaces = Some(true);
+        let result = mesh_raycast_logic(request.clone()).unwrap();
+        assert_eq!(result.hits.len(), 1);
+        assert_eq!(result.hits[0].distance, 3.0);
+
+        request.cull_backfaces = None;
+        request.max_distance = Some(2.5);
+        let result = mesh_raycast_logic(request).unwrap();
+        assert!(!result.hit);
+        assert!(result.nearest.is_none());
+    }
+
+    #[test]
+    fn test_barycentric_coordinates() {
+        let vertices = vec![v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0)];
+        let result = mesh_raycast_logic(input(
+            vertices,
+            vec![0, 1, 2],
+            ray(v(0.5, 1.0, 1.0), v(0.0, 0.0, -1.0)),
+        ))
+        .unwrap();
+        let hit = result.nearest.unwrap();
+        assert!((hit.u - 0.25).abs() < 1e-12);
+        assert!((hit.v - 0.5).abs() < 1e-12);
+
+        // A ray beside the triangle misses, as does one pointing away
+        let vertices = vec![v(0.0, 0.0, 0.0), v(2.0, 0.0, 0.0), v(0.0, 2.0, 0.0)];
+        let miss = mesh_raycast_logic(input(
+            vertices.clone(),
+            vec![0, 1, 2],
+            ray(v(1.5, 1.5, 1.0), v(0.0, 0.0, -1.0)),
+        ))
+        .unwrap();
+        assert!(!miss.hit);
+        let away = mesh_raycast_logic(input(
+            vertices,
+            vec![0, 1, 2],
+            ray(v(0.5, 0.5, 1.0), v(0.0, 0.0, 1.0)),
+        ))
+        .unwrap();
+        assert!(!away.hit);
+    }
+
+    #[test]
+    fn test_bvh_culls_large_mesh() {
+        let (vertices, indices) = grid(100);
+        let result = mesh_raycast_logic(input(
+            vertices,
+            indices,
+            ray(v(37.25, 61.5, 10.0), v(0.0, 0.0, -1.0)),
+        ))
+        .unwrap();
+        assert_eq!(result.triangle_count, 20_000);
+        assert_eq!(result.hits.len(), 1);
+        assert_eq!(result.nearest.unwrap().point, v(37.25, 61.5, 0.0));
+        // Only a few leaves near the ray are tested
+        assert!(result.triangles_tested <= 16, "{}", result.triangles_tested);
+        assert!(result.bvh_nodes > 1);
+
+        // Grazing rays along the plane hit nothing but stay correct
+        let (vertices, indices) = grid(10);
+        let result = mesh_raycast_logic(input(
+            vertices,
+            indices,
+            ray(v(-1.0, 5.5, 1.0), v(1.0, 0.0, 0.0)),
+        ))
+        .unwrap();
+        assert!(!result.hit);
+        assert_eq!(result.triangles_tested, 0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |vertices: Vec<Vector3D>, indices: Vec<u32>, direction: Vector3D| {
+            mesh_raycast_logic(input(vertices, indices, ray(v(0.0, 0.0, 1.0), direction)))
+                .unwrap_err()
+        };
+        let triangle = vec![v(0.0, 0.0, 0.0), v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0)];
+        let down = v(0.0, 0.0, -1.0);
+
+        assert!(err(triangle.clone(), vec![0, 1], down).contains("three entries"));
+        assert!(err(triangle.clone(), vec![], down).contains("at least one triangle"));
+        assert_eq!(
+            err(triangle.clone(), vec![0, 1, 3], down),
+            "Index 3 is out of range for 3 vertices"
+        );
+        assert!(err(triangle.clone(), vec![0, 1, 2], v(0.0, 0.0, 0.0)).contains("zero vector"));
+        let mut nan = triangle;
+        nan[1].y = f64::NAN;
+        assert_eq!(
+            err(nan, vec![0, 1, 2], down),
+            "Vertex 1 contains NaN or infinite values"
+        );
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let triangle = vec![v(0.0, 0.0, 0.0), v(f64::NAN, 0.0, 0.0), v(0.0, 1.0, 0.0)];
+        let mut bad = input(
+            triangle,
+            vec![0, 1, 5],
+            ray(v(0.0, 0.0, 1.0), v(0.0, 0.0, 0.0)),
+        );
+        bad.max_distance = Some(-1.0);
+        let report = validate_mesh_raycast_input(&bad);
+        assert!(!report.valid);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            ["vertices[1]", "indices[2]", "ray.direction", "max_distance"]
+        );
+
+        let (vertices, indices) = cube();
+        let good = input(vertices, indices, ray(v(0.0, 0.0, 5.0), v(0.0, 0.0, -1.0)));
+        assert!(validate_mesh_raycast_input(&good).valid);
+    }
+}
@@ -1,64 +1,85 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{
-    Line3D as LogicLine3D, MultipleLinesInput as LogicInput, Vector3D as LogicVector3D,
+
+// Re-export types from logic module
+pub use logic::{
+    MultipleLineIntersectionResult as LogicOutput, MultipleLinesInput as LogicInput,
     multiple_line_intersection_logic,
 };
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
-
-#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
-pub struct Line3D {
-    pub point: Vector3D,     // A point on the line
-    pub direction: Vector3D, // Direction vector of the line
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    Line3DSchema as Line3D, MultipleLineIntersectionResultSchema as MultipleLineIntersectionResult,
+    Vector3DSchema as Vector3D,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MultipleLinesInput {
-    pub lines: Vec<Line3D>,
+    #[serde(flatten)]
+    pub params: logic::MultipleLinesInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-impl From<Vector3D> for LogicVector3D {
-    fn from(v: Vector3D) -> Self {
-        LogicVector3D {
-            x: v.x,
-            y: v.y,
-            z: v.z,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn multiple_line_intersection(request: Request<MultipleLinesInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "multiple_line_intersection",
+                MultipleLinesInput,
+                MultipleLineIntersectionResult
+            ));
         }
-    }
-}
+    };
 
-impl From<Line3D> for LogicLine3D {
-    fn from(line: Line3D) -> Self {
-        LogicLine3D {
-            point: line.point.into(),
-            direction: line.direction.into(),
-        }
-    }
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("multiple_line_intersection", &input);
+    log.finish(run_multiple_line_intersection(input))
 }
 
-impl From<MultipleLinesInput> for LogicInput {
-    fn from(input: MultipleLinesInput) -> Self {
-        LogicInput {
-            lines: input.lines.into_iter().map(|line| line.into()).collect(),
-        }
+#[cfg(feature = "individual")]
+fn run_multiple_line_intersection(input: MultipleLinesInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("lines", &input.params.lines)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
     }
-}
 
-#[cfg_attr(not(test), tool)]
-pub fn multiple_line_intersection(input: MultipleLinesInput) -> ToolResponse {
-    match multiple_line_intersection_logic(input.into()) {
-        Ok(result) => ToolResponse::text(serde_json::to_string(&result).unwrap()),
+    match multiple_line_intersection_logic(input.params.into()) {
+        Ok(result) => match ftl_response::to_json_with_precision(&result, None, precision.as_ref())
+        {
+            Ok(body) => ToolResponse::text(body),
+            Err(e) => ToolResponse::text(format!("Error: {e}")),
+        },
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
 }
@@ -1,25 +1,34 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Deserialize, Serialize, Clone, Debug, ToolTypes)]
 pub struct Line3D {
-    pub point: Vector3D,     // A point on the line
+    #[tool_types(nested)]
+    pub point: Vector3D, // A point on the line
+    #[tool_types(nested)]
     pub direction: Vector3D, // Direction vector of the line
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToolTypes)]
 pub struct MultipleLinesInput {
+    #[tool_types(nested)]
     pub lines: Vec<Line3D>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToolTypes)]
 pub struct MultipleLineIntersectionResult {
+    #[tool_types(nested)]
     pub best_intersection_point: Vector3D,
     pub total_squared_distance: f64,
     pub individual_distances: Vec<f64>,
@@ -1,19 +1,32 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 
 mod logic;
-use logic::{PlanePlaneIntersectionInput, plane_plane_intersection_logic};
+pub use logic::{PlanePlaneIntersectionInput, plane_plane_intersection_logic};
 
-#[derive(serde::Deserialize, JsonSchema)]
+#[derive(serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct ToolInput {
     /// First plane
     plane1: logic::Plane3D,
     /// Second plane
     plane2: logic::Plane3D,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(serde::Serialize, JsonSchema)]
 struct ToolOutput {
     /// Type of intersection: "intersecting", "parallel", or "coincident"
@@ -34,8 +47,33 @@ struct ToolOutput {
 
 /// Calculate the intersection between two 3D planes
 /// Returns detailed information about the intersection including the line of intersection if it exists
-#[cfg_attr(not(test), tool)]
-pub fn plane_plane_intersection(input: ToolInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn plane_plane_intersection(request: Request<ToolInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "plane_plane_intersection",
+                ToolInput,
+                ToolOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("plane_plane_intersection", &input);
+    log.finish(run_plane_plane_intersection(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_plane_plane_intersection(input: ToolInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     let logic_input = PlanePlaneIntersectionInput {
         plane1: input.plane1,
         plane2: input.plane2,
@@ -52,7 +90,10 @@ pub fn plane_plane_intersection(input: ToolInput) -> ToolResponse {
                 angle_radians: output.angle_radians,
                 angle_degrees: output.angle_degrees,
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -3,13 +3,17 @@ use serde::{Deserialize, Serialize};
 
 const EPSILON: f64 = 1e-10;
 
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D);
+
 #[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
 pub struct Line3D {
     /// A point on the line
@@ -0,0 +1,94 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    KdTree, MAX_NEIGHBORS, MAX_POINTS, PointCloudOpsInput as LogicInput,
+    PointCloudOpsResponse as LogicOutput, Vector3D as LogicVector3D, compute_point_cloud_ops,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    BoundingBoxSchema as BoundingBox, DensityEstimateSchema as DensityEstimate,
+    NeighborSchema as Neighbor, PointCloudOpsResponseSchema as PointCloudOpsResponse,
+    QueryNeighborsSchema as QueryNeighbors, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PointCloudOpsInput {
+    #[serde(flatten)]
+    pub params: logic::PointCloudOpsInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Centroid, bounding box, density estimate and k-nearest-neighbor or radius queries
+/// over a 3D point cloud, answered from a k-d tree
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn point_cloud_ops(request: Request<PointCloudOpsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "point_cloud_ops",
+                PointCloudOpsInput,
+                PointCloudOpsResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("point_cloud_ops", &input);
+    log.finish(run_point_cloud_ops(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_point_cloud_ops(input: PointCloudOpsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .array(
+            "queries",
+            input.params.queries.as_deref().unwrap_or_default(),
+        )
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::compute_point_cloud_ops(input.params.into()) {
+        Ok(logic_result) => {
+            let result = PointCloudOpsResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
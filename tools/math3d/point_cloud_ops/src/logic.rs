@@ -0,0 +1,669 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::f64::consts::PI;
+
+/// Most points in one cloud, and most query points
+pub const MAX_POINTS: usize = 100_000;
+/// Most neighbors returned across all queries
+pub const MAX_NEIGHBORS: usize = 1_000_000;
+/// Neighbors per query for knn by default
+const DEFAULT_K: usize = 1;
+/// Neighbors the density estimate measures out to by default
+const DEFAULT_DENSITY_K: usize = 8;
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointCloudOpsInput {
+    /// Operation: centroid, aabb, density, knn or radius
+    pub operation: String,
+    /// Points of the cloud
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Points to find neighbors of for knn and radius (default: every cloud point, each
+    /// excluding itself)
+    #[tool_types(nested)]
+    pub queries: Option<Vec<Vector3D>>,
+    /// Neighbors per query for knn (default: 1), or neighbors the density estimate
+    /// measures out to (default: 8)
+    pub k: Option<usize>,
+    /// Search radius for radius, positive
+    pub radius: Option<f64>,
+}
+
+/// Axis-aligned bounding box
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct BoundingBox {
+    #[tool_types(nested)]
+    pub min: Vector3D,
+    #[tool_types(nested)]
+    pub max: Vector3D,
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    /// Extent along each axis
+    #[tool_types(nested)]
+    pub size: Vector3D,
+    pub diagonal: f64,
+    pub volume: f64,
+}
+
+/// Local density from the distance to each point's k-th nearest neighbor
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DensityEstimate {
+    pub k: usize,
+    /// k divided by the volume of the sphere reaching each point's k-th nearest
+    /// neighbor; absent where k other points coincide with the point
+    pub per_point: Vec<Option<f64>>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    /// Mean distance to the k-th nearest neighbor
+    pub mean_kth_distance: f64,
+    /// Point count over bounding box volume; absent for a flat cloud
+    pub bounding_box_density: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Neighbor {
+    /// Index into points
+    pub index: usize,
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    pub distance: f64,
+}
+
+/// Neighbors of one query point, nearest first
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct QueryNeighbors {
+    #[tool_types(nested)]
+    pub query: Vector3D,
+    /// Index of the query in points, when the cloud's own points are the queries
+    pub point_index: Option<usize>,
+    #[tool_types(nested)]
+    pub neighbors: Vec<Neighbor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointCloudOpsResponse {
+    /// Operation performed
+    pub operation: String,
+    pub point_count: usize,
+    /// Mean of the points (centroid only)
+    #[tool_types(nested)]
+    pub centroid: Option<Vector3D>,
+    /// Bounding box of the points (aabb only)
+    #[tool_types(nested)]
+    pub aabb: Option<BoundingBox>,
+    /// Density estimate (density only)
+    #[tool_types(nested)]
+    pub density: Option<DensityEstimate>,
+    /// Neighbors of each query, in query order (knn and radius only)
+    #[tool_types(nested)]
+    pub neighbors: Option<Vec<QueryNeighbors>>,
+}
+
+struct KdNode {
+    point: usize,
+    axis: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Candidate neighbor, ordered by squared distance and then index so ties resolve to
+/// the lower index
+#[derive(PartialEq)]
+struct Candidate {
+    distance_squared: f64,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_squared
+            .total_cmp(&other.distance_squared)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// k-d tree over point indices, split at the median of the widest axis
+pub struct KdTree<'a> {
+    points: &'a [[f64; 3]],
+    nodes: Vec<KdNode>,
+    root: Option<usize>,
+}
+
+impl<'a> KdTree<'a> {
+    pub fn new(points: &'a [[f64; 3]]) -> Self {
+        let mut tree = KdTree {
+            points,
+            nodes: Vec::with_capacity(points.len()),
+            root: None,
+        };
+        let mut indices: Vec<usize> = (0..points.len()).collect();
+        tree.root = tree.build(&mut indices);
+        tree
+    }
+
+    fn build(&mut self, indices: &mut [usize]) -> Option<usize> {
+        if indices.is_empty() {
+            return None;
+        }
+        let points = self.points;
+        let spread = |axis: usize| {
+            let (lo, hi) = indices.iter().fold((f64::MAX, f64::MIN), |(lo, hi), &i| {
+                (lo.min(points[i][axis]), hi.max(points[i][axis]))
+            });
+            hi - lo
+        };
+        let axis = (0..3)
+            .max_by(|&a, &b| spread(a).total_cmp(&spread(b)))
+            .unwrap();
+        let mid = indices.len() / 2;
+        indices.select_nth_unstable_by(mid, |&i, &j| points[i][axis].total_cmp(&points[j][axis]));
+        let point = indices[mid];
+        let (below, above) = indices.split_at_mut(mid);
+        let left = self.build(below);
+        let right = self.build(&mut above[1..]);
+        self.nodes.push(KdNode {
+            point,
+            axis,
+            left,
+            right,
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    fn distance_squared(&self, index: usize, query: &[f64; 3]) -> f64 {
+        let p = &self.points[index];
+        (0..3).map(|a| (p[a] - query[a]).powi(2)).sum()
+    }
+
+    /// The k nearest points to the query as (index, distance), nearest first, skipping
+    /// `exclude`
+    pub fn nearest(&self, query: &[f64; 3], k: usize, exclude: Option<usize>) -> Vec<(usize, f64)> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(self.root, query, k, exclude, &mut heap);
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|c| (c.index, c.distance_squared.sqrt()))
+            .collect()
+    }
+
+    fn search_nearest(
+        &self,
+        node: Option<usize>,
+        query: &[f64; 3],
+        k: usize,
+        exclude: Option<usize>,
+        heap: &mut BinaryHeap<Candidate>,
+    ) {
+        let Some(node) = node else {
+            return;
+        };
+        let node = &self.nodes[node];
+        if exclude != Some(node.point) {
+            let candidate = Candidate {
+                distance_squared: self.distance_squared(node.point, query),
+                index: node.point,
+            };
+            if heap.len() < k {
+                heap.push(candidate);
+            } else if heap.peek().is_some_and(|worst| candidate < *worst) {
+                heap.pop();
+                heap.push(candidate);
+            }
+        }
+        let diff = query[node.axis] - self.points[node.point][node.axis];
+        let (near, far) = if diff <= 0.0 {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+        self.search_nearest(near, query, k, exclude, heap);
+        // The far side can only hold closer points when the splitting plane is closer
+        // than the current k-th neighbor
+        if heap.len() < k
+            || heap
+                .peek()
+                .is_some_and(|worst| diff * diff <= worst.distance_squared)
+        {
+            self.search_nearest(far, query, k, exclude, heap);
+        }
+    }
+
+    /// Points within the radius of the query as (index, distance), nearest first,
+    /// skipping `exclude`
+    pub fn within(
+        &self,
+        query: &[f64; 3],
+        radius: f64,
+        exclude: Option<usize>,
+    ) -> Vec<(usize, f64)> {
+        let mut found = Vec::new();
+        self.search_within(self.root, query, radius * radius, exclude, &mut found);
+        found.sort();
+        found
+            .into_iter()
+            .map(|c| (c.index, c.distance_squared.sqrt()))
+            .collect()
+    }
+
+    fn search_within(
+        &self,
+        node: Option<usize>,
+        query: &[f64; 3],
+        radius_squared: f64,
+        exclude: Option<usize>,
+        found: &mut Vec<Candidate>,
+    ) {
+        let Some(node) = node else {
+            return;
+        };
+        let node = &self.nodes[node];
+        let distance_squared = self.distance_squared(node.point, query);
+        if distance_squared <= radius_squared && exclude != Some(node.point) {
+            found.push(Candidate {
+                distance_squared,
+                index: node.point,
+            });
+        }
+        let diff = query[node.axis] - self.points[node.point][node.axis];
+        if diff <= 0.0 || diff * diff <= radius_squared {
+            self.search_within(node.left, query, radius_squared, exclude, found);
+        }
+        if diff >= 0.0 || diff * diff <= radius_squared {
+            self.search_within(node.right, query, radius_squared, exclude, found);
+        }
+    }
+}
+
+fn check_vector(name: &str, v: &Vector3D) -> Result<(), String> {
+    if v.x.is_nan() || v.y.is_nan() || v.z.is_nan() {
+        return Err(format!("{name} coordinates cannot contain NaN values"));
+    }
+    if v.x.is_infinite() || v.y.is_infinite() || v.z.is_infinite() {
+        return Err(format!("{name} coordinates cannot contain infinite values"));
+    }
+    Ok(())
+}
+
+fn to_array(v: &Vector3D) -> [f64; 3] {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(a: [f64; 3]) -> Vector3D {
+    Vector3D {
+        x: a[0],
+        y: a[1],
+        z: a[2],
+    }
+}
+
+fn centroid(points: &[[f64; 3]]) -> [f64; 3] {
+    let n = points.len() as f64;
+    let mut sum = [0.0; 3];
+    for p in points {
+        for a in 0..3 {
+            sum[a] += p[a];
+        }
+    }
+    sum.map(|s| s / n)
+}
+
+fn bounding_box(points: &[[f64; 3]]) -> BoundingBox {
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for p in points {
+        for a in 0..3 {
+            min[a] = min[a].min(p[a]);
+            max[a] = max[a].max(p[a]);
+        }
+    }
+    let size = [max[0] - min[0], max[1] - min[1], max[2] - min[2]];
+    BoundingBox {
+        min: to_vector(min),
+        max: to_vector(max),
+        center: to_vector([0, 1, 2].map(|a| (min[a] + max[a]) / 2.0)),
+        size: to_vector(size),
+        diagonal: (size[0] * size[0] + size[1] * size[1] + size[2] * size[2]).sqrt(),
+        volume: size[0] * size[1] * size[2],
+    }
+}
+
+fn density(tree: &KdTree, points: &[[f64; 3]], k: usize) -> DensityEstimate {
+    let kth_distances: Vec<f64> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| tree.nearest(p, k, Some(i)).last().map_or(0.0, |&(_, d)| d))
+        .collect();
+    let per_point: Vec<Option<f64>> = kth_distances
+        .iter()
+        .map(|&r| (r > 0.0).then(|| k as f64 / (4.0 / 3.0 * PI * r.powi(3))))
+        .collect();
+    let finite: Vec<f64> = per_point.iter().flatten().copied().collect();
+    let volume = bounding_box(points).volume;
+    DensityEstimate {
+        k,
+        min: finite.iter().copied().min_by(f64::total_cmp),
+        max: finite.iter().copied().max_by(f64::total_cmp),
+        mean: (!finite.is_empty()).then(|| finite.iter().sum::<f64>() / finite.len() as f64),
+        per_point,
+        mean_kth_distance: kth_distances.iter().sum::<f64>() / kth_distances.len() as f64,
+        bounding_box_density: (volume > 0.0).then(|| points.len() as f64 / volume),
+    }
+}
+
+pub fn compute_point_cloud_ops(input: PointCloudOpsInput) -> Result<PointCloudOpsResponse, String> {
+    if input.points.is_empty() {
+        return Err("Point cloud must contain at least one point".to_string());
+    }
+    if input.points.len() > MAX_POINTS {
+        return Err(format!("Point cloud can hold at most {MAX_POINTS} points"));
+    }
+    for (i, point) in input.points.iter().enumerate() {
+        check_vector(&format!("Point {i}"), point)?;
+    }
+    let points: Vec<[f64; 3]> = input.points.iter().map(to_array).collect();
+    let operation = input.operation.to_lowercase();
+    let mut response = PointCloudOpsResponse {
+        operation: operation.clone(),
+        point_count: points.len(),
+        centroid: None,
+        aabb: None,
+        density: None,
+        neighbors: None,
+    };
+
+    match operation.as_str() {
+        "centroid" => response.centroid = Some(to_vector(centroid(&points))),
+        "aabb" => response.aabb = Some(bounding_box(&points)),
+        "density" => {
+            if points.len() < 2 {
+                return Err("Density needs at least 2 points".to_string());
+            }
+            let k = match input.k {
+                Some(k) if k == 0 || k >= points.len() => {
+                    return Err(format!(
+                        "k must be between 1 and {} (the other points in the cloud)",
+                        points.len() - 1
+                    ));
+                }
+                Some(k) => k,
+                None => DEFAULT_DENSITY_K.min(points.len() - 1),
+            };
+            let tree = KdTree::new(&points);
+            response.density = Some(density(&tree, &points, k));
+        }
+        "knn" | "radius" => {
+            // Without explicit queries each cloud point is a query that skips itself
+            let queries: Vec<(Vector3D, Option<usize>)> = match &input.queries {
+                Some(queries) => {
+                    if queries.len() > MAX_POINTS {
+                        return Err(format!("At most {MAX_POINTS} query points are allowed"));
+                    }
+                    for (i, query) in queries.iter().enumerate() {
+                        check_vector(&format!("Query {i}"), query)?;
+                    }
+                    queries.iter().map(|q| (*q, None)).collect()
+                }
+                None => input
+                    .points
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (*p, Some(i)))
+                    .collect(),
+            };
+            let tree = KdTree::new(&points);
+            let mut total = 0;
+            let mut results = Vec::with_capacity(queries.len());
+            for (query, point_index) in queries {
+                let found = if operation == "knn" {
+                    let available = points.len() - usize::from(point_index.is_some());
+                    let k = input.k.unwrap_or(DEFAULT_K);
+                    if k == 0 || k > available {
+                        return Err(format!(
+                            "k must be between 1 and {available} (the points each query can \
+                             reach)"
+                        ));
+                    }
+                    tree.nearest(&to_array(&query), k, point_index)
+                } else {
+                    let radius = input
+                        .radius
+                        .ok_or_else(|| "Operation 'radius' requires radius".to_string())?;
+                    if !radius.is_finite() || radius <= 0.0 {
+                        return Err("Radius must be a positive finite number".to_string());
+                    }
+                    tree.within(&to_array(&query), radius, point_index)
+                };
+                total += found.len();
+                if total > MAX_NEIGHBORS {
+                    return Err(format!(
+                        "Queries would return more than {MAX_NEIGHBORS} neighbors; use fewer \
+                         queries, a smaller k or a smaller radius"
+                    ));
+                }
+                let neighbors = found
+                    .into_iter()
+                    .map(|(index, distance)| Neighbor {
+                        index,
+                        point: input.points[index],
+                        distance,
+                    })
+                    .collect();
+                results.push(QueryNeighbors {
+                    query,
+                    point_index,
+                    neighbors,
+                });
+            }
+            response.neighbors = Some(results);
+        }
+        _ => {
+            return Err(format!(
+                "Unsupported operation: {}. Use 'centroid', 'aabb', 'density', 'knn' or \
+                 'radius'",
+                input.operation
+            ));
+        }
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn input(operation: &str, points: Vec<Vector3D>) -> PointCloudOpsInput {
+        PointCloudOpsInput {
+            operation: operation.to_string(),
+            points,
+            queries: None,
+            k: None,
+            radius: None,
+        }
+    }
+
+    /// Deterministic scattered points in a 10-unit cube
+    fn scattered(n: usize) -> Vec<Vector3D> {
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 10.0
+        };
+        (0..n).map(|_| v(next(), next(), next())).collect()
+    }
+
+    fn brute_force(
+        points: &[Vector3D],
+        query: &Vector3D,
+        skip: Option<usize>,
+    ) -> Vec<(usize, f64)> {
+        let mut all: Vec<(usize, f64)> = points
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != skip)
+            .map(|(i, p)| {
+                let d =
+                    ((p.x - query.x).powi(2) + (p.y - query.y).powi(2) + (p.z - query.z).powi(2))
+                        .sqrt();
+                (i, d)
+            })
+            .collect();
+        all.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        all
+    }
+
+    #[test]
+    fn test_centroid_and_aabb() {
+        let points = vec![
+            v(0.0, 0.0, 0.0),
+            v(2.0, 0.0, 0.0),
+            v(2.0, 4.0, 0.0),
+            v(0.0, 4.0, 6.0),
+        ];
+        let centroid = compute_point_cloud_ops(input("centroid", points.clone()))
+            .unwrap()
+            .centroid
+            .unwrap();
+        assert_eq!((centroid.x, centroid.y, centroid.z), (1.0, 2.0, 1.5));
+
+        let aabb = compute_point_cloud_ops(input("AABB", points))
+            .unwrap()
+            .aabb
+            .unwrap();
+        assert_eq!((aabb.max.x, aabb.max.y, aabb.max.z), (2.0, 4.0, 6.0));
+        assert_eq!(
+            (aabb.center.x, aabb.center.y, aabb.center.z),
+            (1.0, 2.0, 3.0)
+        );
+        assert_eq!(aabb.volume, 48.0);
+        assert!((aabb.diagonal - 56f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_knn_matches_brute_force() {
+        let points = scattered(500);
+        let queries = vec![v(5.0, 5.0, 5.0), v(-3.0, 12.0, 0.5), v(9.9, 0.1, 4.2)];
+        let result = compute_point_cloud_ops(PointCloudOpsInput {
+            queries: Some(queries.clone()),
+            k: Some(7),
+            ..input("knn", points.clone())
+        })
+        .unwrap();
+        for (query, found) in queries.iter().zip(result.neighbors.unwrap()) {
+            let expected = brute_force(&points, query, None);
+            let got: Vec<usize> = found.neighbors.iter().map(|n| n.index).collect();
+            let want: Vec<usize> = expected[..7].iter().map(|e| e.0).collect();
+            assert_eq!(got, want);
+            assert_eq!(found.point_index, None);
+        }
+    }
+
+    #[test]
+    fn test_knn_over_own_points_skips_self() {
+        let points = vec![
+            v(0.0, 0.0, 0.0),
+            v(1.0, 0.0, 0.0),
+            v(3.0, 0.0, 0.0),
+            v(3.0, 0.0, 0.0),
+        ];
+        let result = compute_point_cloud_ops(input("knn", points)).unwrap();
+        let neighbors = result.neighbors.unwrap();
+        assert_eq!(neighbors.len(), 4);
+        assert_eq!(neighbors[0].point_index, Some(0));
+        assert_eq!(neighbors[0].neighbors[0].index, 1);
+        // A duplicate's nearest neighbor is its twin at distance zero
+        assert_eq!(neighbors[2].neighbors[0].index, 3);
+        assert_eq!(neighbors[3].neighbors[0].distance, 0.0);
+    }
+
+    #[test]
+    fn test_radius_matches_brute_force() {
+        let points = scattered(400);
+        let result = compute_point_cloud_ops(PointCloudOpsInput {
+            radius: Some(1.5),
+            ..input("radius", points.clone())
+        })
+        .unwrap();
+        for (i, found) in result.neighbors.unwrap().iter().enumerate() {
+            let expected: Vec<usize> = brute_force(&points, &points[i], Some(i))
+                .into_iter()
+                .take_while(|e| e.1 <= 1.5)
+                .map(|e| e.0)
+                .collect();
+            let got: Vec<usize> = found.neighbors.iter().map(|n| n.index).collect();
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn test_density_of_a_grid() {
+        // Unit grid: every interior point's 6 nearest neighbors are at distance 1
+        let mut points = Vec::new();
+        for x in 0..5 {
+            for y in 0..5 {
+                for z in 0..5 {
+                    points.push(v(x as f64, y as f64, z as f64));
+                }
+            }
+        }
+        let density = compute_point_cloud_ops(PointCloudOpsInput {
+            k: Some(6),
+            ..input("density", points)
+        })
+        .unwrap()
+        .density
+        .unwrap();
+        let center = 2 * 25 + 2 * 5 + 2;
+        let expected = 6.0 / (4.0 / 3.0 * PI);
+        assert!((density.per_point[center].unwrap() - expected).abs() < 1e-12);
+        assert_eq!(density.max, Some(expected));
+        assert!((density.bounding_box_density.unwrap() - 125.0 / 64.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_errors() {
+        let points = vec![v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0)];
+        let err = |input| compute_point_cloud_ops(input).unwrap_err();
+        assert!(err(input("centroid", vec![])).contains("at least one point"));
+        assert!(err(input("hull", points.clone())).starts_with("Unsupported operation"));
+        assert!(err(input("radius", points.clone())).contains("requires radius"));
+        assert!(
+            err(PointCloudOpsInput {
+                k: Some(2),
+                ..input("knn", points.clone())
+            })
+            .starts_with("k must be between 1 and 1")
+        );
+        assert!(err(input("aabb", vec![v(0.0, f64::NAN, 0.0)])).contains("Point 0 coordinates"));
+    }
+}
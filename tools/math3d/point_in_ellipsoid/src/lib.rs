@@ -0,0 +1,90 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_POINTS, PointInEllipsoidInput as LogicInput, PointInEllipsoidResult as LogicOutput,
+    Vector3D as LogicVector3D, point_in_ellipsoid_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    EllipsoidSchema as Ellipsoid, PointContainmentSchema as PointContainment,
+    PointInEllipsoidResultSchema as PointInEllipsoidResult, QuaternionSchema as Quaternion,
+    RotationSchema as Rotation, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PointInEllipsoidInput {
+    #[serde(flatten)]
+    pub params: logic::PointInEllipsoidInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Test whether points lie inside an axis-aligned or rotated ellipsoid, with the
+/// distance to and nearest point of its surface
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn point_in_ellipsoid(request: Request<PointInEllipsoidInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "point_in_ellipsoid",
+                PointInEllipsoidInput,
+                PointInEllipsoidResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("point_in_ellipsoid", &input);
+    log.finish(run_point_in_ellipsoid(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_point_in_ellipsoid(input: PointInEllipsoidInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::point_in_ellipsoid_logic(input.params.into()) {
+        Ok(logic_result) => {
+            let result = PointInEllipsoidResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
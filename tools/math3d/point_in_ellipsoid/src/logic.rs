@@ -0,0 +1,441 @@
+use ftl_matrix4::{axis_angle_rotation, quaternion_rotation};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most points tested in one call
+pub const MAX_POINTS: usize = 100_000;
+/// Default distance within which a point counts as on the surface
+const DEFAULT_TOLERANCE: f64 = 1e-9;
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+/// Rotation as a quaternion, or as an axis and angle
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Rotation {
+    /// Rotation as a quaternion, normalized before use
+    #[tool_types(nested)]
+    pub quaternion: Option<Quaternion>,
+    /// Rotation axis, normalized before use; give it with angle instead of quaternion
+    #[tool_types(nested)]
+    pub axis: Option<Vector3D>,
+    /// Rotation angle in radians about axis, counterclockwise looking down the axis
+    pub angle: Option<f64>,
+}
+
+/// Solid ellipsoid, axis-aligned or rotated
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Ellipsoid {
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    /// Semi-axis lengths along the ellipsoid's local x, y and z axes, all positive
+    #[tool_types(nested)]
+    pub radii: Vector3D,
+    /// Rotation taking the local axes to world space (default: axis-aligned)
+    #[tool_types(nested)]
+    pub rotation: Option<Rotation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointInEllipsoidInput {
+    #[tool_types(nested)]
+    pub ellipsoid: Ellipsoid,
+    /// Points to test
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Distance within which a point counts as on the surface, and inside (default: 1e-9)
+    pub tolerance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointContainment {
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Whether the point is inside the ellipsoid or on its surface
+    pub inside: bool,
+    /// Whether the point lies within the tolerance of the surface
+    pub on_surface: bool,
+    /// (x/a)² + (y/b)² + (z/c)² in local coordinates: below 1 inside, 1 on the surface
+    pub level: f64,
+    /// Distance to the nearest point of the surface
+    pub distance: f64,
+    /// Distance to the surface, negative inside
+    pub signed_distance: f64,
+    /// Nearest point of the surface
+    #[tool_types(nested)]
+    pub closest_point: Vector3D,
+    /// Outward unit surface normal at the closest point
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointInEllipsoidResult {
+    #[tool_types(nested)]
+    pub results: Vec<PointContainment>,
+    pub inside_count: usize,
+}
+
+fn check_vector(name: &str, v: &Vector3D) -> Result<(), String> {
+    if v.x.is_nan() || v.y.is_nan() || v.z.is_nan() {
+        return Err(format!("{name} coordinates cannot contain NaN values"));
+    }
+    if v.x.is_infinite() || v.y.is_infinite() || v.z.is_infinite() {
+        return Err(format!("{name} coordinates cannot contain infinite values"));
+    }
+    Ok(())
+}
+
+fn rotation_matrix(rotation: &Option<Rotation>) -> Result<[[f64; 3]; 3], String> {
+    let Some(rotation) = rotation else {
+        return Ok([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+    };
+    match (&rotation.quaternion, &rotation.axis, rotation.angle) {
+        (Some(q), None, None) => quaternion_rotation([q.x, q.y, q.z, q.w]),
+        (None, Some(axis), Some(angle)) => axis_angle_rotation([axis.x, axis.y, axis.z], angle),
+        _ => Err("rotation needs either quaternion, or axis and angle".to_string()),
+    }
+}
+
+/// Root of Σ (n_i / (s + r_i))² - 1 for s above -1 by bisection, where the last ratio
+/// r is 1 and g is the function's value at s = 0
+fn bisect_root(n: &[f64], r: &[f64], g: f64) -> f64 {
+    let last = n.len() - 1;
+    let mut low = n[last] - 1.0;
+    let mut high = if g < 0.0 {
+        0.0
+    } else {
+        n.iter().map(|v| v * v).sum::<f64>().sqrt() - 1.0
+    };
+    for _ in 0..2000 {
+        let s = 0.5 * (low + high);
+        if s == low || s == high {
+            break;
+        }
+        let value = n
+            .iter()
+            .zip(r)
+            .map(|(n, r)| (n / (s + r)).powi(2))
+            .sum::<f64>()
+            - 1.0;
+        if value > 0.0 {
+            low = s;
+        } else if value < 0.0 {
+            high = s;
+        } else {
+            return s;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Nearest point of the ellipse with semi-axes e0 ≥ e1 > 0 to (y0, y1), both
+/// non-negative (Eberly, "Distance from a Point to an Ellipse, an Ellipsoid, or a
+/// Hyperellipsoid")
+fn closest_on_ellipse(e: [f64; 2], y: [f64; 2]) -> [f64; 2] {
+    if y[1] > 0.0 {
+        if y[0] > 0.0 {
+            let z = [y[0] / e[0], y[1] / e[1]];
+            let g = z[0] * z[0] + z[1] * z[1] - 1.0;
+            if g == 0.0 {
+                return y;
+            }
+            let r0 = (e[0] / e[1]).powi(2);
+            let s = bisect_root(&[r0 * z[0], z[1]], &[r0, 1.0], g);
+            [r0 * y[0] / (s + r0), y[1] / (s + 1.0)]
+        } else {
+            [0.0, e[1]]
+        }
+    } else {
+        let numerator = e[0] * y[0];
+        let denominator = e[0] * e[0] - e[1] * e[1];
+        if numerator < denominator {
+            let ratio = numerator / denominator;
+            [e[0] * ratio, e[1] * (1.0 - ratio * ratio).sqrt()]
+        } else {
+            [e[0], 0.0]
+        }
+    }
+}
+
+/// Nearest point of the ellipsoid with semi-axes e0 ≥ e1 ≥ e2 > 0 to y, all
+/// coordinates non-negative
+fn closest_on_ellipsoid(e: [f64; 3], y: [f64; 3]) -> [f64; 3] {
+    if y[2] > 0.0 {
+        if y[1] > 0.0 {
+            if y[0] > 0.0 {
+                let z = [y[0] / e[0], y[1] / e[1], y[2] / e[2]];
+                let g = z.iter().map(|v| v * v).sum::<f64>() - 1.0;
+                if g == 0.0 {
+                    return y;
+                }
+                let r = [(e[0] / e[2]).powi(2), (e[1] / e[2]).powi(2), 1.0];
+                let s = bisect_root(&[r[0] * z[0], r[1] * z[1], z[2]], &r, g);
+                return [0, 1, 2].map(|i| r[i] * y[i] / (s + r[i]));
+            }
+            let [x1, x2] = closest_on_ellipse([e[1], e[2]], [y[1], y[2]]);
+            return [0.0, x1, x2];
+        }
+        if y[0] > 0.0 {
+            let [x0, x2] = closest_on_ellipse([e[0], e[2]], [y[0], y[2]]);
+            return [x0, 0.0, x2];
+        }
+        return [0.0, 0.0, e[2]];
+    }
+    // In the plane of the two longest axes the nearest point may still lie off it
+    let denominators = [e[0] * e[0] - e[2] * e[2], e[1] * e[1] - e[2] * e[2]];
+    let numerators = [e[0] * y[0], e[1] * y[1]];
+    if numerators[0] < denominators[0] && numerators[1] < denominators[1] {
+        let ratios = [
+            numerators[0] / denominators[0],
+            numerators[1] / denominators[1],
+        ];
+        let discriminant = 1.0 - ratios[0] * ratios[0] - ratios[1] * ratios[1];
+        if discriminant > 0.0 {
+            return [
+                e[0] * ratios[0],
+                e[1] * ratios[1],
+                e[2] * discriminant.sqrt(),
+            ];
+        }
+    }
+    let [x0, x1] = closest_on_ellipse([e[0], e[1]], [y[0], y[1]]);
+    [x0, x1, 0.0]
+}
+
+pub fn point_in_ellipsoid_logic(
+    input: PointInEllipsoidInput,
+) -> Result<PointInEllipsoidResult, String> {
+    let ellipsoid = &input.ellipsoid;
+    check_vector("Center", &ellipsoid.center)?;
+    check_vector("Radii", &ellipsoid.radii)?;
+    let radii = [ellipsoid.radii.x, ellipsoid.radii.y, ellipsoid.radii.z];
+    if radii.iter().any(|r| *r <= 0.0) {
+        return Err("Radii must be positive".to_string());
+    }
+    let rotation = rotation_matrix(&ellipsoid.rotation)?;
+    let tolerance = input.tolerance.unwrap_or(DEFAULT_TOLERANCE);
+    if !(tolerance >= 0.0 && tolerance.is_finite()) {
+        return Err("Tolerance must be a non-negative finite number".to_string());
+    }
+    if input.points.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} points can be tested at once"));
+    }
+    for (i, point) in input.points.iter().enumerate() {
+        check_vector(&format!("Point {i}"), point)?;
+    }
+
+    // Solve with the axes sorted longest first, as the nearest-point method expects
+    let mut order = [0, 1, 2];
+    order.sort_by(|a, b| radii[*b].total_cmp(&radii[*a]));
+    let sorted = order.map(|i| radii[i]);
+    let center = [ellipsoid.center.x, ellipsoid.center.y, ellipsoid.center.z];
+
+    let results: Vec<PointContainment> = input
+        .points
+        .iter()
+        .map(|point| {
+            let offset = [
+                point.x - center[0],
+                point.y - center[1],
+                point.z - center[2],
+            ];
+            // Local coordinates: the transposed rotation undoes it
+            let local: [f64; 3] =
+                [0, 1, 2].map(|i| (0..3).map(|j| rotation[j][i] * offset[j]).sum());
+            let level: f64 = (0..3).map(|i| (local[i] / radii[i]).powi(2)).sum();
+
+            let nearest_sorted = closest_on_ellipsoid(sorted, order.map(|i| local[i].abs()));
+            let mut nearest = [0.0; 3];
+            for (k, i) in order.iter().enumerate() {
+                nearest[*i] = nearest_sorted[k].copysign(local[*i]);
+            }
+            let distance = (0..3)
+                .map(|i| (local[i] - nearest[i]).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            let inside = level <= 1.0 || distance <= tolerance;
+
+            // The gradient of the level at the surface point faces outward
+            let gradient = [0, 1, 2].map(|i| nearest[i] / (radii[i] * radii[i]));
+            let length = gradient.iter().map(|v| v * v).sum::<f64>().sqrt();
+            let world = |v: [f64; 3]| -> [f64; 3] {
+                [0, 1, 2].map(|i| (0..3).map(|j| rotation[i][j] * v[j]).sum())
+            };
+            let closest = world(nearest);
+            let normal = world(gradient.map(|v| v / length));
+
+            PointContainment {
+                point: *point,
+                inside,
+                on_surface: distance <= tolerance,
+                level,
+                distance,
+                signed_distance: if level < 1.0 { -distance } else { distance },
+                closest_point: Vector3D {
+                    x: closest[0] + center[0],
+                    y: closest[1] + center[1],
+                    z: closest[2] + center[2],
+                },
+                normal: Vector3D {
+                    x: normal[0],
+                    y: normal[1],
+                    z: normal[2],
+                },
+            }
+        })
+        .collect();
+
+    Ok(PointInEllipsoidResult {
+        inside_count: results.iter().filter(|r| r.inside).count(),
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn test_points(ellipsoid: Ellipsoid, points: Vec<Vector3D>) -> PointInEllipsoidResult {
+        point_in_ellipsoid_logic(PointInEllipsoidInput {
+            ellipsoid,
+            points,
+            tolerance: None,
+        })
+        .unwrap()
+    }
+
+    fn axis_aligned(radii: Vector3D) -> Ellipsoid {
+        Ellipsoid {
+            center: v(0.0, 0.0, 0.0),
+            radii,
+            rotation: None,
+        }
+    }
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        let d = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt();
+        assert!(d < 1e-9, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_containment_on_axes() {
+        let result = test_points(
+            axis_aligned(v(3.0, 2.0, 1.0)),
+            vec![v(0.0, 0.0, 0.0), v(4.0, 0.0, 0.0), v(0.0, 2.0, 0.0)],
+        );
+        assert_eq!(result.inside_count, 2);
+        let outside = &result.results[1];
+        assert!(!outside.inside);
+        assert!((outside.distance - 1.0).abs() < 1e-12);
+        assert_close(outside.closest_point, v(3.0, 0.0, 0.0));
+        assert_close(outside.normal, v(1.0, 0.0, 0.0));
+        assert!(result.results[2].on_surface);
+        // The center is nearest the ends of the shortest axis
+        let center = &result.results[0];
+        assert!((center.signed_distance + 1.0).abs() < 1e-12);
+        assert!((center.closest_point.z.abs() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nearest_point_is_perpendicular() {
+        // The offset to the nearest point runs along the surface normal, inside and out
+        let result = test_points(
+            axis_aligned(v(3.0, 2.0, 1.0)),
+            vec![v(2.5, 1.5, 1.2), v(0.4, 0.3, 0.1), v(-5.0, 3.0, -2.0)],
+        );
+        for r in &result.results {
+            let d = [
+                r.point.x - r.closest_point.x,
+                r.point.y - r.closest_point.y,
+                r.point.z - r.closest_point.z,
+            ];
+            let along = d[0] * r.normal.x + d[1] * r.normal.y + d[2] * r.normal.z;
+            assert!((along - r.signed_distance).abs() < 1e-9);
+            let p = r.closest_point;
+            assert!(((p.x / 3.0).powi(2) + (p.y / 2.0).powi(2) + p.z.powi(2) - 1.0).abs() < 1e-9);
+        }
+        assert!(result.results[1].signed_distance < 0.0);
+    }
+
+    #[test]
+    fn test_inside_off_the_long_plane() {
+        // On the plane of the two longest axes, inside points near the middle are nearest
+        // a point off that plane
+        let result = test_points(axis_aligned(v(3.0, 2.0, 1.0)), vec![v(0.5, 0.2, 0.0)]);
+        let r = &result.results[0];
+        assert!(r.closest_point.z > 0.5);
+        assert!(r.distance < 1.0);
+    }
+
+    #[test]
+    fn test_rotated_and_translated() {
+        let ellipsoid = Ellipsoid {
+            center: v(1.0, 1.0, 1.0),
+            radii: v(3.0, 1.0, 1.0),
+            rotation: Some(Rotation {
+                quaternion: None,
+                axis: Some(v(0.0, 0.0, 1.0)),
+                angle: Some(std::f64::consts::FRAC_PI_2),
+            }),
+        };
+        // The long axis now runs along world y
+        let result = test_points(ellipsoid, vec![v(1.0, 3.5, 1.0), v(3.5, 1.0, 1.0)]);
+        assert!(result.results[0].inside);
+        assert!(!result.results[1].inside);
+        assert!((result.results[1].distance - 1.5).abs() < 1e-9);
+        assert_close(result.results[1].normal, v(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_sphere_and_tolerance() {
+        let result = point_in_ellipsoid_logic(PointInEllipsoidInput {
+            ellipsoid: axis_aligned(v(2.0, 2.0, 2.0)),
+            points: vec![v(0.0, 2.001, 0.0), v(3.0, 4.0, 0.0)],
+            tolerance: Some(0.01),
+        })
+        .unwrap();
+        assert!(result.results[0].inside && result.results[0].on_surface);
+        assert!((result.results[1].distance - 3.0).abs() < 1e-12);
+        assert_close(result.results[1].closest_point, v(1.2, 1.6, 0.0));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let error = point_in_ellipsoid_logic(PointInEllipsoidInput {
+            ellipsoid: axis_aligned(v(1.0, 0.0, 1.0)),
+            points: vec![],
+            tolerance: None,
+        })
+        .unwrap_err();
+        assert_eq!(error, "Radii must be positive");
+        let error = point_in_ellipsoid_logic(PointInEllipsoidInput {
+            ellipsoid: axis_aligned(v(1.0, 1.0, 1.0)),
+            points: vec![v(f64::NAN, 0.0, 0.0)],
+            tolerance: None,
+        })
+        .unwrap_err();
+        assert_eq!(error, "Point 0 coordinates cannot contain NaN values");
+    }
+}
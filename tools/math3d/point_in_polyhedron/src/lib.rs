@@ -0,0 +1,104 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    PointInPolyhedronInput as LogicInput, PointInPolyhedronResult as LogicOutput,
+    point_in_polyhedron_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    MeshCheckSchema as MeshCheck, PointContainmentSchema as PointContainment,
+    PointInPolyhedronResultSchema as PointInPolyhedronResult, Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PointInPolyhedronInput {
+    #[serde(flatten)]
+    pub params: logic::PointInPolyhedronInputSchema,
+    /// Only check the mesh and points and return a validation report, without testing
+    /// containment
+    pub validate_only: Option<bool>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Test whether points lie inside a closed triangle mesh, by winding number or ray
+/// parity, returning containment, the distance to the nearest surface point and a
+/// watertightness check of the mesh
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn point_in_polyhedron(request: Request<PointInPolyhedronInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "point_in_polyhedron",
+                PointInPolyhedronInput,
+                PointInPolyhedronResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("point_in_polyhedron", &input);
+    log.finish(run_point_in_polyhedron(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_point_in_polyhedron(input: PointInPolyhedronInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("vertices", &input.params.vertices)
+        .array("indices", &input.params.indices)
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let params: logic::PointInPolyhedronInput = input.params.into();
+
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_point_in_polyhedron_input(&params);
+        return ToolResponse::text(
+            ftl_json::to_string(&report).unwrap_or_else(|_| "Error serializing result".to_string()),
+        );
+    }
+
+    match logic::point_in_polyhedron_logic(params) {
+        Ok(logic_result) => {
+            let result = PointInPolyhedronResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,645 @@
+use ftl_preflight::ValidationReport;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Most triangles accepted in one mesh
+pub const MAX_TRIANGLES: usize = 200_000;
+/// Most points tested in one call
+pub const MAX_POINTS: usize = 10_000;
+/// Most point-triangle pairs evaluated in one call
+const MAX_WORK: usize = 50_000_000;
+/// Default distance within which a point counts as on the surface
+const DEFAULT_TOLERANCE: f64 = 1e-9;
+/// Barycentric margin within which a ray hit counts as landing on an edge or vertex
+const EDGE_MARGIN: f64 = 1e-9;
+/// Ray directions for the parity test, tried in turn until a ray misses every
+/// edge and vertex; chosen away from the axes and diagonals that meshes tend to align with
+const PARITY_DIRECTIONS: [V; 3] = [
+    [
+        0.285_346_319_560_924,
+        0.530_463_849_768_542,
+        0.798_233_401_624_319,
+    ],
+    [
+        -0.664_412_301_257_829,
+        0.268_093_715_021_657,
+        0.697_615_238_145_981,
+    ],
+    [
+        0.474_812_091_654_116,
+        -0.812_530_488_114_743,
+        0.338_100_674_982_316,
+    ],
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointInPolyhedronInput {
+    /// Mesh vertex positions
+    #[tool_types(nested)]
+    pub vertices: Vec<Vector3D>,
+    /// Vertex indices, three per triangle; counterclockwise winding faces outward.
+    /// Triangles share an edge when they use the same two vertex indices
+    pub indices: Vec<u32>,
+    /// Points to test
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Containment test: "winding" (generalized winding number, robust to small gaps and
+    /// either orientation) or "ray_parity" (count of ray crossings, falling back to the
+    /// winding number when every test ray grazes an edge) (default: winding)
+    pub method: Option<String>,
+    /// Distance within which a point counts as on the surface, and inside (default: 1e-9)
+    pub tolerance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointContainment {
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Whether the point is inside the mesh or on its surface
+    pub inside: bool,
+    /// Whether the point lies within the tolerance of the surface
+    pub on_surface: bool,
+    /// Generalized winding number: 1 inside an outward-facing closed mesh, -1 inside an
+    /// inward-facing one, 0 outside (absent when ray parity settled the point)
+    pub winding_number: Option<f64>,
+    /// Times the test ray crossed the surface (ray_parity method only)
+    pub crossings: Option<usize>,
+    /// Distance to the nearest point of the surface
+    pub distance: f64,
+    /// Distance to the surface, negative inside
+    pub signed_distance: f64,
+    /// Nearest point of the surface
+    #[tool_types(nested)]
+    pub closest_point: Vector3D,
+    /// Index of the triangle holding the nearest point (position in indices divided by 3)
+    pub closest_triangle: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct MeshCheck {
+    /// Whether every edge is shared by exactly two triangles, so the mesh encloses a
+    /// volume; results for open meshes are approximate
+    pub watertight: bool,
+    /// Edges used by only one triangle
+    pub boundary_edges: usize,
+    /// Edges used by more than two triangles
+    pub non_manifold_edges: usize,
+    /// Whether every edge shared by two triangles is traversed in opposite directions
+    pub consistently_oriented: bool,
+    /// Triangles with zero area
+    pub degenerate_triangles: usize,
+    pub triangle_count: usize,
+    pub vertex_count: usize,
+    /// Enclosed volume, negative when the triangles face inward
+    pub signed_volume: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PointInPolyhedronResult {
+    /// Method used: "winding" or "ray_parity"
+    pub method: String,
+    /// One result per point, in input order
+    #[tool_types(nested)]
+    pub results: Vec<PointContainment>,
+    /// Points inside the mesh or on its surface
+    pub inside_count: usize,
+    /// Topology of the mesh
+    #[tool_types(nested)]
+    pub mesh: MeshCheck,
+}
+
+type V = [f64; 3];
+
+fn sub(a: V, b: V) -> V {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add_scaled(a: V, b: V, s: f64) -> V {
+    [a[0] + b[0] * s, a[1] + b[1] * s, a[2] + b[2] * s]
+}
+
+fn dot(a: V, b: V) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: V, b: V) -> V {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: V) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn to_array(v: &Vector3D) -> V {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(v: V) -> Vector3D {
+    Vector3D {
+        x: v[0],
+        y: v[1],
+        z: v[2],
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Method {
+    Winding,
+    RayParity,
+}
+
+impl Method {
+    fn parse(method: Option<&str>) -> Result<Method, String> {
+        match method {
+            None | Some("winding") => Ok(Method::Winding),
+            Some("ray_parity") => Ok(Method::RayParity),
+            Some(other) => Err(format!(
+                "Unknown method '{other}', use 'winding' or 'ray_parity'"
+            )),
+        }
+    }
+}
+
+/// Edge counts and orientation of the mesh, matching edges by vertex index
+fn check_mesh(indices: &[u32], triangles: &[[V; 3]], vertex_count: usize) -> MeshCheck {
+    // Per undirected edge: triangles using it, and the sum of +1 for each traversal from
+    // the lower to the higher index and -1 for the reverse
+    let mut edges: HashMap<(u32, u32), (usize, i64)> = HashMap::new();
+    for tri in indices.chunks_exact(3) {
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            let entry = edges.entry((a.min(b), a.max(b))).or_default();
+            entry.0 += 1;
+            entry.1 += if a < b { 1 } else { -1 };
+        }
+    }
+    let boundary_edges = edges.values().filter(|(count, _)| *count == 1).count();
+    let non_manifold_edges = edges.values().filter(|(count, _)| *count > 2).count();
+    let consistently_oriented = edges
+        .values()
+        .all(|&(count, direction)| count != 2 || direction == 0);
+
+    let degenerate_triangles = triangles.iter().filter(|t| triangle_area(t) == 0.0).count();
+    // Divergence theorem over tetrahedra from the origin to each triangle
+    let signed_volume = triangles
+        .iter()
+        .map(|t| dot(t[0], cross(t[1], t[2])))
+        .sum::<f64>()
+        / 6.0;
+
+    MeshCheck {
+        watertight: boundary_edges == 0 && non_manifold_edges == 0,
+        boundary_edges,
+        non_manifold_edges,
+        consistently_oriented,
+        degenerate_triangles,
+        triangle_count: triangles.len(),
+        vertex_count,
+        signed_volume,
+    }
+}
+
+fn triangle_area(t: &[V; 3]) -> f64 {
+    norm(cross(sub(t[1], t[0]), sub(t[2], t[0]))) / 2.0
+}
+
+/// Closest point of a triangle to p (Ericson, Real-Time Collision Detection, 5.1.5)
+fn closest_point_on_triangle(p: V, t: &[V; 3]) -> V {
+    let [a, b, c] = *t;
+    let (ab, ac, ap) = (sub(b, a), sub(c, a), sub(p, a));
+    let (d1, d2) = (dot(ab, ap), dot(ac, ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let bp = sub(p, b);
+    let (d3, d4) = (dot(ab, bp), dot(ac, bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return add_scaled(a, ab, d1 / (d1 - d3));
+    }
+    let cp = sub(p, c);
+    let (d5, d6) = (dot(ab, cp), dot(ac, cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return add_scaled(a, ac, d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return add_scaled(b, sub(c, b), (d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    if va + vb + vc == 0.0 {
+        // Degenerate triangle whose point lies between its vertices
+        return a;
+    }
+    let denom = 1.0 / (va + vb + vc);
+    add_scaled(add_scaled(a, ab, vb * denom), ac, vc * denom)
+}
+
+/// Solid angle of the triangle seen from p, over 4π (Van Oosterom and Strackee)
+fn winding_contribution(p: V, t: &[V; 3]) -> f64 {
+    let [a, b, c] = t.map(|v| sub(v, p));
+    let (la, lb, lc) = (norm(a), norm(b), norm(c));
+    let numerator = dot(a, cross(b, c));
+    let denominator = la * lb * lc + dot(a, b) * lc + dot(b, c) * la + dot(c, a) * lb;
+    2.0 * numerator.atan2(denominator) / (4.0 * PI)
+}
+
+/// Crossings of the ray from p along direction, or None when the ray grazes an edge or
+/// vertex and the count cannot be trusted
+fn ray_crossings(p: V, direction: V, triangles: &[[V; 3]]) -> Option<usize> {
+    let mut crossings = 0;
+    for t in triangles {
+        let edge1 = sub(t[1], t[0]);
+        let edge2 = sub(t[2], t[0]);
+        let q = cross(direction, edge2);
+        let det = dot(edge1, q);
+        let scale = dot(edge1, edge1).max(dot(edge2, edge2));
+        if det.abs() <= 1e-12 * scale {
+            // Ray parallel to the triangle's plane; only a problem if it runs through it
+            let normal = cross(edge1, edge2);
+            if norm(normal) > 0.0 && dot(normal, sub(p, t[0])).abs() <= EDGE_MARGIN * scale {
+                return None;
+            }
+            continue;
+        }
+        let inv_det = 1.0 / det;
+        let s = sub(p, t[0]);
+        let u = dot(s, q) * inv_det;
+        let r = cross(s, edge1);
+        let v = dot(direction, r) * inv_det;
+        let distance = dot(edge2, r) * inv_det;
+        if u < -EDGE_MARGIN || v < -EDGE_MARGIN || u + v > 1.0 + EDGE_MARGIN || distance <= 0.0 {
+            continue;
+        }
+        if u < EDGE_MARGIN || v < EDGE_MARGIN || u + v > 1.0 - EDGE_MARGIN {
+            return None;
+        }
+        crossings += 1;
+    }
+    Some(crossings)
+}
+
+fn mesh_triangles(input: &PointInPolyhedronInput) -> Vec<[V; 3]> {
+    input
+        .indices
+        .chunks_exact(3)
+        .map(|c| [0, 1, 2].map(|k| to_array(&input.vertices[c[k] as usize])))
+        .collect()
+}
+
+/// Check the mesh and points without testing containment. A valid input also
+/// gets the mesh topology checked, with open or inconsistent meshes reported as
+/// warnings since their results are approximate.
+pub fn validate_point_in_polyhedron_input(input: &PointInPolyhedronInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    if !input.indices.len().is_multiple_of(3) {
+        report.error(
+            "indices",
+            format!(
+                "indices must hold three entries per triangle, got {}",
+                input.indices.len()
+            ),
+        );
+    }
+    let triangle_count = input.indices.len() / 3;
+    if triangle_count == 0 {
+        report.error("indices", "The mesh needs at least one triangle");
+    } else if triangle_count > MAX_TRIANGLES {
+        report.error(
+            "indices",
+            format!("At most {MAX_TRIANGLES} triangles are supported"),
+        );
+    }
+    if input.points.is_empty() {
+        report.error("points", "Give at least one point to test");
+    } else if input.points.len() > MAX_POINTS {
+        report.error(
+            "points",
+            format!("At most {MAX_POINTS} points are supported"),
+        );
+    }
+    if input.points.len() * triangle_count > MAX_WORK {
+        report.error(
+            "points",
+            format!(
+                "points × triangles must be at most {MAX_WORK}, got {}",
+                input.points.len() * triangle_count
+            ),
+        );
+    }
+    if let Some(i) = input
+        .vertices
+        .iter()
+        .position(|v| !to_array(v).iter().all(|c| c.is_finite()))
+    {
+        report.error(
+            format!("vertices[{i}]"),
+            format!("Vertex {i} contains NaN or infinite values"),
+        );
+    }
+    if let Some(i) = input
+        .points
+        .iter()
+        .position(|v| !to_array(v).iter().all(|c| c.is_finite()))
+    {
+        report.error(
+            format!("points[{i}]"),
+            format!("Point {i} contains NaN or infinite values"),
+        );
+    }
+    if let Some((position, &index)) = input
+        .indices
+        .iter()
+        .enumerate()
+        .find(|&(_, &i)| i as usize >= input.vertices.len())
+    {
+        report.error(
+            format!("indices[{position}]"),
+            format!(
+                "Index {index} is out of range for {} vertices",
+                input.vertices.len()
+            ),
+        );
+    }
+    if let Err(e) = Method::parse(input.method.as_deref()) {
+        report.error("method", e);
+    }
+    if let Some(t) = input.tolerance
+        && (!t.is_finite() || t < 0.0)
+    {
+        report.error("tolerance", "tolerance must be a non-negative number");
+    }
+
+    if report.valid {
+        let mesh = check_mesh(&input.indices, &mesh_triangles(input), input.vertices.len());
+        if !mesh.watertight {
+            report.warning(
+                "indices",
+                format!(
+                    "The mesh is not watertight ({} boundary and {} non-manifold edges), \
+                     so containment is approximate",
+                    mesh.boundary_edges, mesh.non_manifold_edges
+                ),
+            );
+        }
+        if !mesh.consistently_oriented {
+            report.warning("indices", "The triangles are not consistently oriented");
+        }
+        if mesh.degenerate_triangles > 0 {
+            report.warning(
+                "indices",
+                format!("{} triangles have zero area", mesh.degenerate_triangles),
+            );
+        }
+    }
+
+    report
+}
+
+pub fn point_in_polyhedron_logic(
+    input: PointInPolyhedronInput,
+) -> Result<PointInPolyhedronResult, String> {
+    validate_point_in_polyhedron_input(&input).into_result()?;
+
+    let method = Method::parse(input.method.as_deref())?;
+    let tolerance = input.tolerance.unwrap_or(DEFAULT_TOLERANCE);
+    let triangles = mesh_triangles(&input);
+    let mesh = check_mesh(&input.indices, &triangles, input.vertices.len());
+
+    let results: Vec<PointContainment> = input
+        .points
+        .iter()
+        .map(|point| {
+            let p = to_array(point);
+            let (closest_triangle, closest_point, distance) = triangles
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    let q = closest_point_on_triangle(p, t);
+                    (i, q, norm(sub(p, q)))
+                })
+                .min_by(|a, b| a.2.total_cmp(&b.2))
+                .unwrap_or((0, p, f64::INFINITY));
+            let on_surface = distance <= tolerance;
+
+            let crossings = match method {
+                Method::Winding => None,
+                Method::RayParity => PARITY_DIRECTIONS
+                    .iter()
+                    .find_map(|&d| ray_crossings(p, d, &triangles)),
+            };
+            // The winding number also settles points whose every test ray grazed an edge
+            let winding_number = crossings.is_none().then(|| {
+                triangles
+                    .iter()
+                    .map(|t| winding_contribution(p, t))
+                    .sum::<f64>()
+            });
+            let enclosed = match (crossings, winding_number) {
+                (Some(count), _) => count % 2 == 1,
+                (None, w) => w.is_some_and(|w| w.abs() >= 0.5),
+            };
+            let inside = on_surface || enclosed;
+
+            PointContainment {
+                point: *point,
+                inside,
+                on_surface,
+                winding_number,
+                crossings,
+                distance,
+                signed_distance: if inside { -distance } else { distance },
+                closest_point: to_vector(closest_point),
+                closest_triangle,
+            }
+        })
+        .collect();
+
+    Ok(PointInPolyhedronResult {
+        method: match method {
+            Method::Winding => "winding",
+            Method::RayParity => "ray_parity",
+        }
+        .to_string(),
+        inside_count: results.iter().filter(|r| r.inside).count(),
+        results,
+        mesh,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unit cube [0, 1]³ with outward-facing triangles; vertex i sits at
+    /// (i & 1, (i >> 1) & 1, (i >> 2) & 1)
+    fn cube() -> (Vec<Vector3D>, Vec<u32>) {
+        let vertices = (0..8)
+            .map(|i| to_vector([(i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64]))
+            .collect();
+        let indices = vec![
+            0, 2, 1, 1, 2, 3, 4, 5, 6, 5, 7, 6, 0, 1, 4, 1, 5, 4, 2, 6, 3, 3, 6, 7, 0, 4, 2, 2, 4,
+            6, 1, 3, 5, 3, 7, 5,
+        ];
+        (vertices, indices)
+    }
+
+    fn test(indices: Vec<u32>, points: &[V], method: &str) -> PointInPolyhedronResult {
+        point_in_polyhedron_logic(PointInPolyhedronInput {
+            vertices: cube().0,
+            indices,
+            points: points.iter().map(|&p| to_vector(p)).collect(),
+            method: Some(method.to_string()),
+            tolerance: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cube_winding() {
+        let result = test(cube().1, &[[0.5, 0.5, 0.5], [2.0, 0.5, 0.5]], "winding");
+        assert!(result.mesh.watertight && result.mesh.consistently_oriented);
+        assert!((result.mesh.signed_volume - 1.0).abs() < 1e-12);
+
+        let center = &result.results[0];
+        assert!(center.inside && !center.on_surface);
+        assert!((center.winding_number.unwrap() - 1.0).abs() < 1e-9);
+        assert!((center.signed_distance + 0.5).abs() < 1e-12);
+
+        let outside = &result.results[1];
+        assert!(!outside.inside);
+        assert!(outside.winding_number.unwrap().abs() < 1e-9);
+        assert!((outside.distance - 1.0).abs() < 1e-12);
+        assert_eq!(outside.closest_point, to_vector([1.0, 0.5, 0.5]));
+        assert_eq!(result.inside_count, 1);
+    }
+
+    #[test]
+    fn test_methods_agree() {
+        let points: Vec<V> = (0..125)
+            .map(|i| {
+                let c = |k: usize| -0.25 + 0.375 * ((i / k) % 5) as f64;
+                [c(1), c(5), c(25)]
+            })
+            .collect();
+        let winding = test(cube().1, &points, "winding");
+        let parity = test(cube().1, &points, "ray_parity");
+        for (w, p) in winding.results.iter().zip(&parity.results) {
+            assert_eq!(w.inside, p.inside, "{:?}", w.point);
+            assert_eq!(w.distance, p.distance);
+        }
+        // Interior grid points with all coordinates at 0.125, 0.5 or 0.875
+        assert_eq!(winding.inside_count, 27);
+    }
+
+    #[test]
+    fn test_surface_points() {
+        for method in ["winding", "ray_parity"] {
+            let result = test(cube().1, &[[1.0, 0.5, 0.5], [1.0, 1.0, 1.0]], method);
+            for r in &result.results {
+                assert!(r.on_surface && r.inside, "{method} {:?}", r.point);
+                assert_eq!(r.distance, 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mesh_checks() {
+        // Without the top face the mesh is open along the square's four edges
+        let mut open = cube().1;
+        open.drain(6..12);
+        let result = test(open, &[[0.5, 0.5, 0.5]], "winding");
+        assert!(!result.mesh.watertight);
+        assert_eq!(result.mesh.boundary_edges, 4);
+        // The missing face covers a sixth of the solid angle around the center
+        assert!((result.results[0].winding_number.unwrap() - 5.0 / 6.0).abs() < 1e-9);
+
+        // Every triangle flipped: inward facing but still enclosing the point
+        let flipped: Vec<u32> = cube()
+            .1
+            .chunks_exact(3)
+            .flat_map(|t| [t[0], t[2], t[1]])
+            .collect();
+        let result = test(flipped, &[[0.5, 0.5, 0.5]], "winding");
+        assert!(result.mesh.consistently_oriented);
+        assert!((result.mesh.signed_volume + 1.0).abs() < 1e-12);
+        assert!((result.results[0].winding_number.unwrap() + 1.0).abs() < 1e-9);
+        assert!(result.results[0].inside);
+
+        // One triangle flipped
+        let mut mixed = cube().1;
+        mixed.swap(1, 2);
+        let result = test(mixed, &[[0.5, 0.5, 0.5]], "winding");
+        assert!(result.mesh.watertight);
+        assert!(!result.mesh.consistently_oriented);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let (vertices, indices) = cube();
+        let input = |indices: Vec<u32>, points: Vec<Vector3D>, method: Option<&str>| {
+            PointInPolyhedronInput {
+                vertices: vertices.clone(),
+                indices,
+                points,
+                method: method.map(str::to_string),
+                tolerance: None,
+            }
+        };
+        let point = vec![to_vector([0.5; 3])];
+
+        let err = point_in_polyhedron_logic(input(vec![0, 1], point.clone(), None)).unwrap_err();
+        assert!(err.contains("three entries per triangle"));
+        let err = point_in_polyhedron_logic(input(vec![0, 1, 8], point.clone(), None)).unwrap_err();
+        assert_eq!(err, "Index 8 is out of range for 8 vertices");
+        let err = point_in_polyhedron_logic(input(indices.clone(), vec![], None)).unwrap_err();
+        assert_eq!(err, "Give at least one point to test");
+        let err = point_in_polyhedron_logic(input(indices, point, Some("octree"))).unwrap_err();
+        assert!(err.contains("Unknown method 'octree'"));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let (vertices, indices) = cube();
+        let report = validate_point_in_polyhedron_input(&PointInPolyhedronInput {
+            vertices: vertices.clone(),
+            indices: vec![0, 1, 9],
+            points: vec![to_vector([f64::NAN, 0.0, 0.0])],
+            method: Some("octree".to_string()),
+            tolerance: Some(-1.0),
+        });
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["points[0]", "indices[2]", "method", "tolerance"]);
+
+        // An open mesh is accepted with a warning
+        let report = validate_point_in_polyhedron_input(&PointInPolyhedronInput {
+            vertices,
+            indices: indices[..indices.len() - 6].to_vec(),
+            points: vec![to_vector([0.5; 3])],
+            method: None,
+            tolerance: None,
+        });
+        assert!(report.valid);
+        assert!(report.warnings[0].message.contains("not watertight"));
+    }
+}
@@ -1,19 +1,33 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::*;
 
+// Re-export types from logic module
+pub use logic::{
+    PointLineDistanceResult as LogicOutput, PointLineInput as LogicInput, point_line_distance_logic,
+};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D);
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Line3D {
     pub point: Vector3D,
@@ -24,6 +38,12 @@ pub struct Line3D {
 pub struct PointLineInput {
     pub point: Vector3D,
     pub line: Line3D,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -35,8 +55,33 @@ pub struct PointLineDistanceResult {
     pub point_is_on_line: bool,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn point_line_distance(input: PointLineInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn point_line_distance(request: Request<PointLineInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "point_line_distance",
+                PointLineInput,
+                PointLineDistanceResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("point_line_distance", &input);
+    log.finish(run_point_line_distance(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_point_line_distance(input: PointLineInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     // Convert JsonSchema types to logic types
     let logic_input = logic::PointLineInput {
         point: logic::Vector3D {
@@ -77,7 +122,10 @@ pub fn point_line_distance(input: PointLineInput) -> ToolResponse {
                 },
                 point_is_on_line: logic_result.point_is_on_line,
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
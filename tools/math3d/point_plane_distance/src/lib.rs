@@ -1,98 +1,77 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{
-    Plane3D as LogicPlane3D, PointPlaneInput as LogicInput, Vector3D as LogicVector3D,
-    point_plane_distance_logic,
-};
 
-#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug)]
-struct Vector3D {
-    x: f64,
-    y: f64,
-    z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    PointPlaneInput as LogicInput, PointPlaneResult as LogicOutput, point_plane_distance_logic,
+};
 
-#[derive(Deserialize, JsonSchema, Clone, Debug)]
-struct Plane3D {
-    /// A point on the plane
-    point: Vector3D,
-    /// Normal vector to the plane
-    normal: Vector3D,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    Plane3DSchema as Plane3D, PointPlaneResultSchema as PointPlaneResult,
+    Vector3DSchema as Vector3D,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PointPlaneInput {
-    /// The point to measure distance from
-    point: Vector3D,
-    /// The plane to measure distance to
-    plane: Plane3D,
-}
-
-impl From<Vector3D> for LogicVector3D {
-    fn from(v: Vector3D) -> Self {
-        LogicVector3D {
-            x: v.x,
-            y: v.y,
-            z: v.z,
-        }
-    }
+    #[serde(flatten)]
+    pub params: logic::PointPlaneInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-impl From<Plane3D> for LogicPlane3D {
-    fn from(p: Plane3D) -> Self {
-        LogicPlane3D {
-            point: p.point.into(),
-            normal: p.normal.into(),
+/// Calculate the distance from a point to a plane in 3D space
+/// Returns both signed and unsigned distance, the closest point on the plane, and which side of the plane the point is on
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn point_plane_distance(request: Request<PointPlaneInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "point_plane_distance",
+                PointPlaneInput,
+                PointPlaneResult
+            ));
         }
-    }
-}
+    };
 
-impl From<PointPlaneInput> for LogicInput {
-    fn from(input: PointPlaneInput) -> Self {
-        LogicInput {
-            point: input.point.into(),
-            plane: input.plane.into(),
-        }
-    }
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("point_plane_distance", &input);
+    log.finish(run_point_plane_distance(input))
 }
 
-#[derive(Serialize, JsonSchema)]
-struct PointPlaneResult {
-    /// Absolute distance from point to plane
-    distance: f64,
-    /// Signed distance (positive if point is on the side of normal, negative otherwise)
-    signed_distance: f64,
-    /// Closest point on the plane to the given point
-    closest_point_on_plane: Vector3D,
-    /// Whether the point lies exactly on the plane
-    is_on_plane: bool,
-    /// Which side of the plane the point is on
-    side_of_plane: String,
-}
+#[cfg(feature = "individual")]
+fn run_point_plane_distance(input: PointPlaneInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
 
-/// Calculate the distance from a point to a plane in 3D space
-/// Returns both signed and unsigned distance, the closest point on the plane, and which side of the plane the point is on
-#[cfg_attr(not(test), tool)]
-pub fn point_plane_distance(input: PointPlaneInput) -> ToolResponse {
-    match point_plane_distance_logic(input.into()) {
+    match logic::point_plane_distance_logic(input.params.into()) {
         Ok(logic_result) => {
-            let result = PointPlaneResult {
-                distance: logic_result.distance,
-                signed_distance: logic_result.signed_distance,
-                closest_point_on_plane: Vector3D {
-                    x: logic_result.closest_point_on_plane.x,
-                    y: logic_result.closest_point_on_plane.y,
-                    z: logic_result.closest_point_on_plane.z,
-                },
-                is_on_plane: logic_result.is_on_plane,
-                side_of_plane: logic_result.side_of_plane,
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = PointPlaneResult::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
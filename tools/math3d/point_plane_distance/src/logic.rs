@@ -1,35 +1,45 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Deserialize, Serialize, Clone, Debug, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Deserialize, Serialize, Clone, Debug, ToolTypes)]
 pub struct Plane3D {
     /// A point on the plane
+    #[tool_types(nested)]
     pub point: Vector3D,
     /// Normal vector to the plane
+    #[tool_types(nested)]
     pub normal: Vector3D,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug, ToolTypes)]
 pub struct PointPlaneInput {
     /// The point to measure distance from
+    #[tool_types(nested)]
     pub point: Vector3D,
     /// The plane to measure distance to
+    #[tool_types(nested)]
     pub plane: Plane3D,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, ToolTypes)]
 pub struct PointPlaneResult {
     /// Absolute distance from point to plane
     pub distance: f64,
     /// Signed distance (positive if point is on the side of normal, negative otherwise)
     pub signed_distance: f64,
     /// Closest point on the plane to the given point
+    #[tool_types(nested)]
     pub closest_point_on_plane: Vector3D,
     /// Whether the point lies exactly on the plane
     pub is_on_plane: bool,
@@ -0,0 +1,104 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use ftl_units::AngleUnit;
+#[cfg(feature = "individual")]
+use ftl_units::AngleUnitResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_POINTS, ProjectPointsInput as LogicInput, ProjectPointsResponse as LogicOutput,
+    project_points_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    PixelSchema as Pixel, ProjectPointsResponseSchema as ProjectPointsResponse,
+    ProjectedPointSchema as ProjectedPoint, Vector3DSchema as Vector3D, Vector4DSchema as Vector4D,
+    ViewportSchema as Viewport,
+};
+
+// Shared matrix type, which derives JsonSchema itself
+pub use ftl_matrix4::Matrix4;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectPointsInput {
+    #[serde(flatten)]
+    pub params: logic::ProjectPointsInputSchema,
+    /// Unit of fov_y: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Project 3D points through a perspective or orthographic camera, built from field of
+/// view, aspect and clip planes or given as a matrix, returning clip, normalized device
+/// and pixel coordinates with behind-camera and visibility flags
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn project_points(request: Request<ProjectPointsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "project_points",
+                ProjectPointsInput,
+                AngleUnitResponse<ProjectPointsResponse>
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("project_points", &input);
+    log.finish(run_project_points(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_project_points(input: ProjectPointsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    let mut params: logic::ProjectPointsInput = input.params.into();
+    params.fov_y = params.fov_y.map(|fov| angle_unit.to_radians(fov));
+
+    match logic::project_points_logic(params) {
+        Ok(logic_result) => {
+            let response = angle_unit.response(ProjectPointsResponse::from(logic_result));
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
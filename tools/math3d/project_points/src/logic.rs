@@ -0,0 +1,503 @@
+use ftl_matrix4::Matrix4;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most points projected in one call
+pub const MAX_POINTS: usize = 100_000;
+/// Clip-space w at or below this puts a point on or behind the camera plane
+const BEHIND_W: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector4D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Pixel {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Screen rectangle that normalized device coordinates map onto
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Viewport {
+    /// Width in pixels
+    pub width: f64,
+    /// Height in pixels
+    pub height: f64,
+    /// Left edge in pixels (default: 0)
+    pub x: Option<f64>,
+    /// Top edge in pixels, or bottom edge when y_down is false (default: 0)
+    pub y: Option<f64>,
+    /// Pixel rows count down from the top, as in images and most window systems; false
+    /// counts up from the bottom, as in OpenGL (default: true)
+    pub y_down: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ProjectPointsInput {
+    /// World-space points to project
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// "perspective" or "orthographic"; omit when giving matrix
+    pub projection: Option<String>,
+    /// Vertical field of view in radians, for perspective
+    pub fov_y: Option<f64>,
+    /// Width over height; for perspective and orthographic given by height (default: the
+    /// viewport's aspect ratio)
+    pub aspect: Option<f64>,
+    /// Distance to the near clip plane
+    pub near: Option<f64>,
+    /// Distance to the far clip plane; perspective projections may omit it for an
+    /// infinite far plane
+    pub far: Option<f64>,
+    /// Orthographic view volume as [left, right, bottom, top] in camera space; or give
+    /// ortho_height with aspect for a centered volume
+    pub ortho_bounds: Option<[f64; 4]>,
+    /// Height of a centered orthographic view volume
+    pub ortho_height: Option<f64>,
+    /// Explicit projection matrix, acting on column vectors, instead of the parameters
+    pub matrix: Option<Matrix4>,
+    /// World-to-camera matrix applied before the projection (default: identity, so
+    /// points are already in camera space, looking down -z)
+    pub view_matrix: Option<Matrix4>,
+    /// Clip-space depth range: "negative_one_to_one" (OpenGL) or "zero_to_one"
+    /// (Direct3D, Vulkan, WebGPU) (default: negative_one_to_one)
+    pub depth_range: Option<String>,
+    /// Screen rectangle for pixel coordinates; pixels are omitted without it
+    #[tool_types(nested)]
+    pub viewport: Option<Viewport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ProjectedPoint {
+    /// Homogeneous clip coordinates
+    #[tool_types(nested)]
+    pub clip: Vector4D,
+    /// Normalized device coordinates; absent behind the camera
+    #[tool_types(nested)]
+    pub ndc: Option<Vector3D>,
+    /// Viewport coordinates; absent behind the camera or without a viewport
+    #[tool_types(nested)]
+    pub pixel: Option<Pixel>,
+    /// Window depth from 0 at the near plane to 1 at the far plane; absent behind the
+    /// camera
+    pub depth: Option<f64>,
+    /// The point is on or behind the camera plane (clip w ≤ 0)
+    pub behind_camera: bool,
+    /// The point lies inside the view volume
+    pub visible: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ProjectPointsResponse {
+    /// Projected points, in input order
+    #[tool_types(nested)]
+    pub points: Vec<ProjectedPoint>,
+    /// Projection matrix used
+    pub projection_matrix: Matrix4,
+    /// Combined projection · view matrix applied to the points
+    pub view_projection_matrix: Matrix4,
+    pub visible_count: usize,
+    pub behind_count: usize,
+}
+
+fn positive(value: Option<f64>, name: &str) -> Result<f64, String> {
+    match value {
+        Some(v) if v.is_finite() && v > 0.0 => Ok(v),
+        Some(_) => Err(format!("{name} must be a positive number")),
+        None => Err(format!("{name} is required")),
+    }
+}
+
+/// Right-handed perspective projection, with an infinite far plane when `far` is None
+fn perspective(fov_y: f64, aspect: f64, near: f64, far: Option<f64>, zero_to_one: bool) -> Matrix4 {
+    let f = 1.0 / (fov_y / 2.0).tan();
+    let (c, d) = match (far, zero_to_one) {
+        (Some(far), false) => ((far + near) / (near - far), 2.0 * far * near / (near - far)),
+        (Some(far), true) => (far / (near - far), far * near / (near - far)),
+        (None, false) => (-1.0, -2.0 * near),
+        (None, true) => (-1.0, -near),
+    };
+    Matrix4([
+        [f / aspect, 0.0, 0.0, 0.0],
+        [0.0, f, 0.0, 0.0],
+        [0.0, 0.0, c, d],
+        [0.0, 0.0, -1.0, 0.0],
+    ])
+}
+
+/// Right-handed orthographic projection of the box [left, right] × [bottom, top] ×
+/// [-far, -near]
+fn orthographic(
+    [left, right, bottom, top]: [f64; 4],
+    near: f64,
+    far: f64,
+    zero_to_one: bool,
+) -> Matrix4 {
+    let (c, d) = if zero_to_one {
+        (-1.0 / (far - near), -near / (far - near))
+    } else {
+        (-2.0 / (far - near), -(far + near) / (far - near))
+    };
+    Matrix4([
+        [
+            2.0 / (right - left),
+            0.0,
+            0.0,
+            -(right + left) / (right - left),
+        ],
+        [
+            0.0,
+            2.0 / (top - bottom),
+            0.0,
+            -(top + bottom) / (top - bottom),
+        ],
+        [0.0, 0.0, c, d],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+fn projection_matrix(input: &ProjectPointsInput, zero_to_one: bool) -> Result<Matrix4, String> {
+    let has_parameters = input.projection.is_some()
+        || input.fov_y.is_some()
+        || input.near.is_some()
+        || input.far.is_some()
+        || input.aspect.is_some()
+        || input.ortho_bounds.is_some()
+        || input.ortho_height.is_some();
+    if let Some(matrix) = input.matrix {
+        if has_parameters {
+            return Err("Give either matrix or projection parameters, not both".to_string());
+        }
+        if !matrix.is_finite() {
+            return Err("matrix contains NaN or infinite values".to_string());
+        }
+        return Ok(matrix);
+    }
+
+    let aspect = match (input.aspect, &input.viewport) {
+        (Some(aspect), _) => positive(Some(aspect), "aspect")?,
+        (None, Some(viewport)) => viewport.width / viewport.height,
+        (None, None) => 1.0,
+    };
+    let near = positive(input.near, "near")?;
+    let far = input
+        .far
+        .map(|far| positive(Some(far), "far"))
+        .transpose()?;
+    if far.is_some_and(|far| far <= near) {
+        return Err("far must be greater than near".to_string());
+    }
+    let projection = input
+        .projection
+        .as_deref()
+        .map(|p| p.trim().to_lowercase())
+        .ok_or("Give projection ('perspective' or 'orthographic') or matrix")?;
+    match projection.as_str() {
+        "perspective" => {
+            if input.ortho_bounds.is_some() || input.ortho_height.is_some() {
+                return Err("ortho_bounds and ortho_height only apply to orthographic".to_string());
+            }
+            let fov_y = positive(input.fov_y, "fov_y")?;
+            if fov_y >= std::f64::consts::PI {
+                return Err("fov_y must be less than π radians (180°)".to_string());
+            }
+            Ok(perspective(fov_y, aspect, near, far, zero_to_one))
+        }
+        "orthographic" => {
+            if input.fov_y.is_some() {
+                return Err("fov_y only applies to perspective".to_string());
+            }
+            let far = far.ok_or("far is required for orthographic projections")?;
+            let bounds = match (input.ortho_bounds, input.ortho_height) {
+                (Some(bounds), None) => {
+                    if !bounds.iter().all(|v| v.is_finite())
+                        || bounds[0] >= bounds[1]
+                        || bounds[2] >= bounds[3]
+                    {
+                        return Err(
+                            "ortho_bounds must be finite with left < right and bottom < top"
+                                .to_string(),
+                        );
+                    }
+                    bounds
+                }
+                (None, Some(height)) => {
+                    let half_height = positive(Some(height), "ortho_height")? / 2.0;
+                    let half_width = half_height * aspect;
+                    [-half_width, half_width, -half_height, half_height]
+                }
+                _ => return Err("Give one of ortho_bounds or ortho_height".to_string()),
+            };
+            Ok(orthographic(bounds, near, far, zero_to_one))
+        }
+        other => Err(format!(
+            "Unknown projection '{other}', use 'perspective' or 'orthographic'"
+        )),
+    }
+}
+
+pub fn project_points_logic(input: ProjectPointsInput) -> Result<ProjectPointsResponse, String> {
+    if input.points.len() > MAX_POINTS {
+        return Err(format!(
+            "At most {MAX_POINTS} points can be projected at once"
+        ));
+    }
+    if let Some(index) = input
+        .points
+        .iter()
+        .position(|p| !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite()))
+    {
+        return Err(format!("Point {index} contains NaN or infinite values"));
+    }
+    let zero_to_one = match input.depth_range.as_deref().map(str::trim) {
+        None | Some("negative_one_to_one") => false,
+        Some("zero_to_one") => true,
+        Some(other) => {
+            return Err(format!(
+                "Unknown depth_range '{other}', use 'negative_one_to_one' or 'zero_to_one'"
+            ));
+        }
+    };
+    if let Some(viewport) = &input.viewport {
+        let origin = [viewport.x.unwrap_or(0.0), viewport.y.unwrap_or(0.0)];
+        let size = [viewport.width, viewport.height];
+        if !size.iter().all(|v| v.is_finite() && *v > 0.0) || !origin.iter().all(|v| v.is_finite())
+        {
+            return Err(
+                "viewport needs a positive width and height and a finite origin".to_string(),
+            );
+        }
+    }
+
+    let projection_matrix = projection_matrix(&input, zero_to_one)?;
+    let view = match input.view_matrix {
+        Some(view) if !view.is_finite() => {
+            return Err("view_matrix contains NaN or infinite values".to_string());
+        }
+        Some(view) => view,
+        None => Matrix4::IDENTITY,
+    };
+    let view_projection_matrix = projection_matrix.multiply(&view);
+    let m = &view_projection_matrix.0;
+    let min_depth = if zero_to_one { 0.0 } else { -1.0 };
+
+    let points: Vec<ProjectedPoint> = input
+        .points
+        .iter()
+        .map(|p| {
+            let [x, y, z, w] =
+                std::array::from_fn(|r| m[r][0] * p.x + m[r][1] * p.y + m[r][2] * p.z + m[r][3]);
+            let clip = Vector4D { x, y, z, w };
+            if w <= BEHIND_W {
+                return ProjectedPoint {
+                    clip,
+                    ndc: None,
+                    pixel: None,
+                    depth: None,
+                    behind_camera: true,
+                    visible: false,
+                };
+            }
+            let ndc = Vector3D {
+                x: x / w,
+                y: y / w,
+                z: z / w,
+            };
+            let depth = if zero_to_one {
+                ndc.z
+            } else {
+                (ndc.z + 1.0) / 2.0
+            };
+            let pixel = input.viewport.as_ref().map(|viewport| {
+                let up = (ndc.y + 1.0) / 2.0 * viewport.height;
+                Pixel {
+                    x: viewport.x.unwrap_or(0.0) + (ndc.x + 1.0) / 2.0 * viewport.width,
+                    y: viewport.y.unwrap_or(0.0)
+                        + if viewport.y_down.unwrap_or(true) {
+                            viewport.height - up
+                        } else {
+                            up
+                        },
+                }
+            });
+            let visible =
+                ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0 && (min_depth..=1.0).contains(&ndc.z);
+            ProjectedPoint {
+                clip,
+                ndc: Some(ndc),
+                pixel,
+                depth: Some(depth),
+                behind_camera: false,
+                visible,
+            }
+        })
+        .collect();
+
+    Ok(ProjectPointsResponse {
+        visible_count: points.iter().filter(|p| p.visible).count(),
+        behind_count: points.iter().filter(|p| p.behind_camera).count(),
+        points,
+        projection_matrix,
+        view_projection_matrix,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn point(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn perspective_request(points: Vec<Vector3D>) -> ProjectPointsInput {
+        ProjectPointsInput {
+            points,
+            projection: Some("perspective".to_string()),
+            fov_y: Some(FRAC_PI_2),
+            aspect: None,
+            near: Some(1.0),
+            far: Some(100.0),
+            ortho_bounds: None,
+            ortho_height: None,
+            matrix: None,
+            view_matrix: None,
+            depth_range: None,
+            viewport: Some(Viewport {
+                width: 200.0,
+                height: 100.0,
+                x: None,
+                y: None,
+                y_down: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_perspective_ndc_and_pixels() {
+        // 90° vertical field of view on a 2:1 viewport
+        let result = project_points_logic(perspective_request(vec![
+            point(0.0, 0.0, -10.0),
+            point(20.0, 10.0, -10.0),
+            point(0.0, 5.0, -10.0),
+        ]))
+        .unwrap();
+        let center = &result.points[0];
+        assert_eq!(center.pixel, Some(Pixel { x: 100.0, y: 50.0 }));
+        // The top-right corner of the view maps to pixel (200, 0) with y down
+        let corner = result.points[1].pixel.unwrap();
+        assert!((corner.x - 200.0).abs() < 1e-9 && corner.y.abs() < 1e-9);
+        let half_up = result.points[2].ndc.unwrap();
+        assert!((half_up.y - 0.5).abs() < 1e-12);
+        assert!(result.points[0].visible && result.points[2].visible);
+    }
+
+    #[test]
+    fn test_depth_ranges() {
+        let mut input = perspective_request(vec![point(0.0, 0.0, -1.0), point(0.0, 0.0, -100.0)]);
+        let gl = project_points_logic(input.clone()).unwrap();
+        assert!((gl.points[0].ndc.unwrap().z + 1.0).abs() < 1e-12);
+        assert!((gl.points[1].ndc.unwrap().z - 1.0).abs() < 1e-12);
+        assert!((gl.points[0].depth.unwrap()).abs() < 1e-12);
+
+        input.depth_range = Some("zero_to_one".to_string());
+        let zero = project_points_logic(input.clone()).unwrap();
+        assert!(zero.points[0].ndc.unwrap().z.abs() < 1e-12);
+        assert!((zero.points[1].depth.unwrap() - 1.0).abs() < 1e-12);
+
+        // With no far plane every point past near stays inside the depth range
+        input.far = None;
+        input.points = vec![point(0.0, 0.0, -1e9)];
+        let infinite = project_points_logic(input).unwrap();
+        assert!(infinite.points[0].visible);
+    }
+
+    #[test]
+    fn test_behind_camera_and_view_matrix() {
+        let mut input = perspective_request(vec![point(0.0, 0.0, 5.0), point(0.0, 0.0, -0.5)]);
+        let result = project_points_logic(input.clone()).unwrap();
+        assert!(result.points[0].behind_camera && result.points[0].ndc.is_none());
+        // In front of the camera but closer than the near plane
+        assert!(!result.points[1].behind_camera && !result.points[1].visible);
+        assert_eq!(result.behind_count, 1);
+
+        // A camera at z = 10 looking down -z sees the point at the origin
+        input.view_matrix = Some(Matrix4::from_translation([0.0, 0.0, -10.0]));
+        input.points = vec![point(0.0, 0.0, 0.0)];
+        let moved = project_points_logic(input).unwrap();
+        assert!(moved.points[0].visible);
+    }
+
+    #[test]
+    fn test_orthographic_and_explicit_matrix() {
+        let mut input = perspective_request(vec![point(-4.0, 1.0, -5.0)]);
+        input.projection = Some("orthographic".to_string());
+        input.fov_y = None;
+        input.ortho_height = Some(4.0);
+        input.far = Some(10.0);
+        let result = project_points_logic(input.clone()).unwrap();
+        // Height 4 on a 2:1 viewport spans x in [-4, 4] and y in [-2, 2]
+        let ndc = result.points[0].ndc.unwrap();
+        assert!((ndc.x + 1.0).abs() < 1e-12 && (ndc.y - 0.5).abs() < 1e-12);
+        assert_eq!(result.points[0].clip.w, 1.0);
+
+        let explicit = project_points_logic(ProjectPointsInput {
+            points: input.points.clone(),
+            projection: None,
+            fov_y: None,
+            aspect: None,
+            near: None,
+            far: None,
+            ortho_bounds: None,
+            ortho_height: None,
+            matrix: Some(result.projection_matrix),
+            view_matrix: None,
+            depth_range: None,
+            viewport: input.viewport.clone(),
+        })
+        .unwrap();
+        assert_eq!(explicit.points[0].pixel, result.points[0].pixel);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut input = perspective_request(vec![point(0.0, 0.0, -1.0)]);
+        input.far = Some(0.5);
+        assert_eq!(
+            project_points_logic(input).unwrap_err(),
+            "far must be greater than near"
+        );
+        let mut input = perspective_request(vec![]);
+        input.fov_y = None;
+        assert_eq!(
+            project_points_logic(input).unwrap_err(),
+            "fov_y is required"
+        );
+        let mut input = perspective_request(vec![]);
+        input.matrix = Some(Matrix4::IDENTITY);
+        assert!(
+            project_points_logic(input)
+                .unwrap_err()
+                .contains("not both")
+        );
+        let mut input = perspective_request(vec![]);
+        input.projection = Some("fisheye".to_string());
+        assert!(project_points_logic(input).is_err());
+        let mut input = perspective_request(vec![]);
+        input.depth_range = Some("zero_to_ten".to_string());
+        assert!(project_points_logic(input).is_err());
+    }
+}
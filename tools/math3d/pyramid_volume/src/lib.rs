@@ -1,79 +1,79 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    PyramidInput as LogicInput, PyramidResponse as LogicOutput, Vector3D as LogicVector3D,
+    compute_pyramid_volume,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{PyramidResponseSchema as PyramidResponse, Vector3DSchema as Vector3D};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PyramidInput {
-    pub base_points: Vec<Vector3D>,
-    pub apex: Vector3D,
+    #[serde(flatten)]
+    pub params: logic::PyramidInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct PyramidResponse {
-    pub volume: f64,
-    pub calculation_method: String,
-    pub base_area: f64,
-    pub height: f64,
-    pub base_points: Vec<Vector3D>,
-    pub apex: Vector3D,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn pyramid_volume(request: Request<PyramidInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("pyramid_volume", PyramidInput, PyramidResponse));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("pyramid_volume", &input);
+    log.finish(run_pyramid_volume(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn pyramid_volume(input: PyramidInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::PyramidInput {
-        base_points: input
-            .base_points
-            .into_iter()
-            .map(|p| logic::Vector3D {
-                x: p.x,
-                y: p.y,
-                z: p.z,
-            })
-            .collect(),
-        apex: logic::Vector3D {
-            x: input.apex.x,
-            y: input.apex.y,
-            z: input.apex.z,
-        },
+#[cfg(feature = "individual")]
+fn run_pyramid_volume(input: PyramidInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_pyramid_volume(logic_input) {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("base_points", &input.params.base_points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    match logic::compute_pyramid_volume(input.params.into()) {
         Ok(logic_result) => {
-            // Convert logic types back to API types
-            let result = PyramidResponse {
-                volume: logic_result.volume,
-                calculation_method: logic_result.calculation_method,
-                base_area: logic_result.base_area,
-                height: logic_result.height,
-                base_points: logic_result
-                    .base_points
-                    .into_iter()
-                    .map(|p| Vector3D {
-                        x: p.x,
-                        y: p.y,
-                        z: p.z,
-                    })
-                    .collect(),
-                apex: Vector3D {
-                    x: logic_result.apex.x,
-                    y: logic_result.apex.y,
-                    z: logic_result.apex.z,
-                },
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = PyramidResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
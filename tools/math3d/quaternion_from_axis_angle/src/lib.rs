@@ -1,62 +1,86 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
+#[cfg(feature = "individual")]
+use ftl_units::AngleUnitResponse;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    QuaternionFromAxisAngleInput as LogicInput, QuaternionFromAxisAngleResponse as LogicOutput,
+    compute_quaternion_from_axis_angle,
+};
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Quaternion {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-    pub w: f64,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    QuaternionFromAxisAngleResponseSchema as QuaternionFromAxisAngleResponse,
+    QuaternionSchema as Quaternion, Vector3DSchema as Vector3D,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct QuaternionFromAxisAngleInput {
-    pub axis: Vector3D,
-    pub angle: f64,
+    #[serde(flatten)]
+    pub params: logic::QuaternionFromAxisAngleInputSchema,
+    /// Unit of `angle`: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct QuaternionFromAxisAngleResponse {
-    pub quaternion: Quaternion,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn quaternion_from_axis_angle(request: Request<QuaternionFromAxisAngleInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "quaternion_from_axis_angle",
+                QuaternionFromAxisAngleInput,
+                AngleUnitResponse<QuaternionFromAxisAngleResponse>
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("quaternion_from_axis_angle", &input);
+    log.finish(run_quaternion_from_axis_angle(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn quaternion_from_axis_angle(input: QuaternionFromAxisAngleInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::QuaternionFromAxisAngleInput {
-        axis: logic::Vector3D {
-            x: input.axis.x,
-            y: input.axis.y,
-            z: input.axis.z,
-        },
-        angle: input.angle,
+#[cfg(feature = "individual")]
+fn run_quaternion_from_axis_angle(input: QuaternionFromAxisAngleInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_quaternion_from_axis_angle(logic_input) {
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    let mut params: logic::QuaternionFromAxisAngleInput = input.params.into();
+    params.angle = angle_unit.to_radians(params.angle);
+
+    match logic::compute_quaternion_from_axis_angle(params) {
         Ok(logic_result) => {
-            // Convert logic types back to API types
-            let result = QuaternionFromAxisAngleResponse {
-                quaternion: Quaternion {
-                    x: logic_result.quaternion.x,
-                    y: logic_result.quaternion.y,
-                    z: logic_result.quaternion.z,
-                    w: logic_result.quaternion.w,
-                },
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let response = angle_unit.response(QuaternionFromAxisAngleResponse::from(logic_result));
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
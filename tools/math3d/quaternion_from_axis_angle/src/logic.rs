@@ -1,13 +1,18 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
 pub struct Quaternion {
     pub x: f64,
     pub y: f64,
@@ -15,14 +20,16 @@ pub struct Quaternion {
     pub w: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct QuaternionFromAxisAngleInput {
+    #[tool_types(nested)]
     pub axis: Vector3D,
     pub angle: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct QuaternionFromAxisAngleResponse {
+    #[tool_types(nested)]
     pub quaternion: Quaternion,
 }
 
@@ -1,61 +1,75 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Quaternion {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-    pub w: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    QuaternionMultiplyInput as LogicInput, QuaternionMultiplyResponse as LogicOutput,
+    compute_quaternion_multiply,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    QuaternionMultiplyResponseSchema as QuaternionMultiplyResponse, QuaternionSchema as Quaternion,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct QuaternionMultiplyInput {
-    pub q1: Quaternion,
-    pub q2: Quaternion,
+    #[serde(flatten)]
+    pub params: logic::QuaternionMultiplyInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct QuaternionMultiplyResponse {
-    pub result: Quaternion,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn quaternion_multiply(request: Request<QuaternionMultiplyInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "quaternion_multiply",
+                QuaternionMultiplyInput,
+                QuaternionMultiplyResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("quaternion_multiply", &input);
+    log.finish(run_quaternion_multiply(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn quaternion_multiply(input: QuaternionMultiplyInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::QuaternionMultiplyInput {
-        q1: logic::Quaternion {
-            x: input.q1.x,
-            y: input.q1.y,
-            z: input.q1.z,
-            w: input.q1.w,
-        },
-        q2: logic::Quaternion {
-            x: input.q2.x,
-            y: input.q2.y,
-            z: input.q2.z,
-            w: input.q2.w,
-        },
+#[cfg(feature = "individual")]
+fn run_quaternion_multiply(input: QuaternionMultiplyInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_quaternion_multiply(logic_input) {
+    match logic::compute_quaternion_multiply(input.params.into()) {
         Ok(logic_result) => {
-            // Convert logic types back to API types
-            let result = QuaternionMultiplyResponse {
-                result: Quaternion {
-                    x: logic_result.result.x,
-                    y: logic_result.result.y,
-                    z: logic_result.result.z,
-                    w: logic_result.result.w,
-                },
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = QuaternionMultiplyResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
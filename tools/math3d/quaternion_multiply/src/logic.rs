@@ -1,6 +1,7 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
 pub struct Quaternion {
     pub x: f64,
     pub y: f64,
@@ -8,14 +9,17 @@ pub struct Quaternion {
     pub w: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct QuaternionMultiplyInput {
+    #[tool_types(nested)]
     pub q1: Quaternion,
+    #[tool_types(nested)]
     pub q2: Quaternion,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct QuaternionMultiplyResponse {
+    #[tool_types(nested)]
     pub result: Quaternion,
 }
 
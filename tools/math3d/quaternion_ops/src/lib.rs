@@ -0,0 +1,89 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use ftl_units::{AngleUnit, AngleUnitResponse};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    QuaternionOpsInput as LogicInput, QuaternionOpsResponse as LogicOutput, compute_quaternion_ops,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    AxisAngleSchema as AxisAngle, QuaternionOpsResponseSchema, QuaternionSchema as Quaternion,
+    Vector3DSchema as Vector3D,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct QuaternionOpsInput {
+    #[serde(flatten)]
+    pub params: logic::QuaternionOpsInputSchema,
+    /// Unit of the axis-angle `angle`: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+// The generated result plus the unit of the axis-angle `angle`
+pub type QuaternionOpsResponse = AngleUnitResponse<QuaternionOpsResponseSchema>;
+
+/// Multiply, conjugate, invert, normalize, dot or slerp quaternions, returning the
+/// result both as a quaternion and as the axis-angle rotation it represents
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn quaternion_ops(request: Request<QuaternionOpsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "quaternion_ops",
+                QuaternionOpsInput,
+                QuaternionOpsResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("quaternion_ops", &input);
+    log.finish(run_quaternion_ops(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_quaternion_ops(input: QuaternionOpsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    match logic::compute_quaternion_ops(input.params.into()) {
+        Ok(logic_result) => {
+            let mut result = QuaternionOpsResponseSchema::from(logic_result);
+            if let Some(axis_angle) = result.axis_angle.as_mut() {
+                axis_angle.angle = angle_unit.value_from_radians(axis_angle.angle);
+            }
+            let response = angle_unit.response(result);
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,412 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Quaternions shorter than this are treated as zero
+const EPSILON: f64 = 1e-10;
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct QuaternionOpsInput {
+    /// Operation: multiply, conjugate, inverse, normalize, dot or slerp
+    pub operation: String,
+    /// First quaternion, the only one used by conjugate, inverse and normalize
+    #[tool_types(nested)]
+    pub q1: Quaternion,
+    /// Second quaternion, required by multiply, dot and slerp
+    #[tool_types(nested)]
+    pub q2: Option<Quaternion>,
+    /// Interpolation parameter between 0 (q1) and 1 (q2), required by slerp
+    pub t: Option<f64>,
+}
+
+/// Rotation about a unit axis
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct AxisAngle {
+    /// Unit rotation axis; the x axis for the identity rotation
+    #[tool_types(nested)]
+    pub axis: Vector3D,
+    /// Rotation angle in `angle_unit`, from 0 up to a full turn
+    pub angle: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct QuaternionOpsResponse {
+    /// Operation performed
+    pub operation: String,
+    /// Resulting quaternion (all operations except dot)
+    #[tool_types(nested)]
+    pub quaternion: Option<Quaternion>,
+    /// Rotation represented by the resulting quaternion, once normalized; absent for dot
+    /// and for a zero quaternion
+    #[tool_types(nested)]
+    pub axis_angle: Option<AxisAngle>,
+    /// Dot product of q1 and q2 (dot only)
+    pub dot: Option<f64>,
+}
+
+impl Quaternion {
+    pub fn is_valid(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite() && self.w.is_finite()
+    }
+
+    pub fn dot(&self, other: &Quaternion) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn scale(&self, factor: f64) -> Quaternion {
+        Quaternion {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+            w: self.w * factor,
+        }
+    }
+
+    pub fn multiply(&self, other: &Quaternion) -> Quaternion {
+        Quaternion {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        }
+    }
+
+    pub fn conjugate(&self) -> Quaternion {
+        Quaternion {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w,
+        }
+    }
+
+    pub fn normalize(&self) -> Result<Quaternion, String> {
+        let magnitude = self.magnitude();
+        if magnitude < EPSILON {
+            return Err("Cannot normalize a zero quaternion".to_string());
+        }
+        Ok(self.scale(1.0 / magnitude))
+    }
+
+    pub fn inverse(&self) -> Result<Quaternion, String> {
+        let norm_squared = self.dot(self);
+        if norm_squared < EPSILON * EPSILON {
+            return Err("A zero quaternion has no inverse".to_string());
+        }
+        Ok(self.conjugate().scale(1.0 / norm_squared))
+    }
+
+    /// Spherical linear interpolation between the normalized quaternions, along the
+    /// shorter arc
+    pub fn slerp(&self, other: &Quaternion, t: f64) -> Result<Quaternion, String> {
+        if !(0.0..=1.0).contains(&t) {
+            return Err("Interpolation parameter t must be between 0 and 1".to_string());
+        }
+        let q1 = self.normalize()?;
+        let mut q2 = other.normalize()?;
+        let mut dot = q1.dot(&q2);
+        if dot < 0.0 {
+            q2 = q2.scale(-1.0);
+            dot = -dot;
+        }
+
+        // Nearly parallel: linear interpolation avoids dividing by sin(θ) ≈ 0
+        if dot > 0.9995 {
+            let lerp = Quaternion {
+                x: q1.x + t * (q2.x - q1.x),
+                y: q1.y + t * (q2.y - q1.y),
+                z: q1.z + t * (q2.z - q1.z),
+                w: q1.w + t * (q2.w - q1.w),
+            };
+            return lerp.normalize();
+        }
+
+        let theta_0 = dot.acos();
+        let sin_theta_0 = theta_0.sin();
+        let s0 = ((1.0 - t) * theta_0).sin() / sin_theta_0;
+        let s1 = (t * theta_0).sin() / sin_theta_0;
+        Ok(Quaternion {
+            x: s0 * q1.x + s1 * q2.x,
+            y: s0 * q1.y + s1 * q2.y,
+            z: s0 * q1.z + s1 * q2.z,
+            w: s0 * q1.w + s1 * q2.w,
+        })
+    }
+
+    /// Axis and angle of the rotation this quaternion represents, or `None` for zero
+    pub fn to_axis_angle(&self) -> Option<AxisAngle> {
+        let unit = self.normalize().ok()?;
+        let w = unit.w.clamp(-1.0, 1.0);
+        let angle = 2.0 * w.acos();
+        let sin_half = (1.0 - w * w).sqrt();
+        let axis = if sin_half < EPSILON {
+            Vector3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            }
+        } else {
+            Vector3D {
+                x: unit.x / sin_half,
+                y: unit.y / sin_half,
+                z: unit.z / sin_half,
+            }
+        };
+        Some(AxisAngle {
+            axis,
+            angle: angle.clamp(0.0, 2.0 * PI),
+        })
+    }
+}
+
+pub fn compute_quaternion_ops(input: QuaternionOpsInput) -> Result<QuaternionOpsResponse, String> {
+    if !input.q1.is_valid() {
+        return Err("Quaternion q1 contains NaN or infinite values".to_string());
+    }
+    if let Some(q2) = &input.q2
+        && !q2.is_valid()
+    {
+        return Err("Quaternion q2 contains NaN or infinite values".to_string());
+    }
+
+    let operation = input.operation.to_lowercase();
+    let q2 = || {
+        input
+            .q2
+            .as_ref()
+            .ok_or_else(|| format!("Operation '{operation}' requires q2"))
+    };
+
+    let quaternion = match operation.as_str() {
+        "multiply" => input.q1.multiply(q2()?),
+        "conjugate" => input.q1.conjugate(),
+        "inverse" => input.q1.inverse()?,
+        "normalize" => input.q1.normalize()?,
+        "dot" => {
+            return Ok(QuaternionOpsResponse {
+                dot: Some(input.q1.dot(q2()?)),
+                operation,
+                quaternion: None,
+                axis_angle: None,
+            });
+        }
+        "slerp" => {
+            let t = input
+                .t
+                .ok_or_else(|| "Operation 'slerp' requires t".to_string())?;
+            if !t.is_finite() {
+                return Err("Interpolation parameter t must be finite".to_string());
+            }
+            input.q1.slerp(q2()?, t)?
+        }
+        _ => {
+            return Err(format!(
+                "Unsupported operation: {}. Use 'multiply', 'conjugate', 'inverse', \
+                 'normalize', 'dot' or 'slerp'",
+                input.operation
+            ));
+        }
+    };
+
+    if !quaternion.is_valid() {
+        return Err("Result contains NaN or infinite values".to_string());
+    }
+
+    Ok(QuaternionOpsResponse {
+        axis_angle: quaternion.to_axis_angle(),
+        quaternion: Some(quaternion),
+        dot: None,
+        operation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn q(x: f64, y: f64, z: f64, w: f64) -> Quaternion {
+        Quaternion { x, y, z, w }
+    }
+
+    fn run(
+        operation: &str,
+        q1: Quaternion,
+        q2: Option<Quaternion>,
+        t: Option<f64>,
+    ) -> QuaternionOpsResponse {
+        compute_quaternion_ops(QuaternionOpsInput {
+            operation: operation.to_string(),
+            q1,
+            q2,
+            t,
+        })
+        .unwrap()
+    }
+
+    fn assert_quaternion_eq(actual: &Quaternion, expected: &Quaternion, tolerance: f64) {
+        assert!(
+            (actual.x - expected.x).abs() < tolerance
+                && (actual.y - expected.y).abs() < tolerance
+                && (actual.z - expected.z).abs() < tolerance
+                && (actual.w - expected.w).abs() < tolerance,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_multiply_composes_rotations() {
+        // Two 90° turns about z make a 180° turn about z
+        let quarter_z = q(0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2);
+        let result = run("multiply", quarter_z.clone(), Some(quarter_z), None);
+        assert_quaternion_eq(&result.quaternion.unwrap(), &q(0.0, 0.0, 1.0, 0.0), 1e-12);
+        let axis_angle = result.axis_angle.unwrap();
+        assert!((axis_angle.angle - PI).abs() < 1e-12);
+        assert!((axis_angle.axis.z - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_multiply_basis_quaternions() {
+        // i * j = k
+        let result = run(
+            "multiply",
+            q(1.0, 0.0, 0.0, 0.0),
+            Some(q(0.0, 1.0, 0.0, 0.0)),
+            None,
+        );
+        assert_quaternion_eq(&result.quaternion.unwrap(), &q(0.0, 0.0, 1.0, 0.0), 1e-15);
+    }
+
+    #[test]
+    fn test_conjugate() {
+        let result = run("conjugate", q(1.0, -2.0, 3.0, 4.0), None, None);
+        assert_quaternion_eq(&result.quaternion.unwrap(), &q(-1.0, 2.0, -3.0, 4.0), 1e-15);
+    }
+
+    #[test]
+    fn test_inverse_times_original_is_identity() {
+        let original = q(1.0, 2.0, 3.0, 4.0);
+        let inverse = run("inverse", original.clone(), None, None)
+            .quaternion
+            .unwrap();
+        assert_quaternion_eq(&original.multiply(&inverse), &q(0.0, 0.0, 0.0, 1.0), 1e-12);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let result = run("normalize", q(0.0, 0.0, 3.0, 4.0), None, None);
+        let quaternion = result.quaternion.unwrap();
+        assert_quaternion_eq(&quaternion, &q(0.0, 0.0, 0.6, 0.8), 1e-15);
+        assert!((quaternion.magnitude() - 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_dot() {
+        let result = run(
+            "DOT",
+            q(1.0, 2.0, 3.0, 4.0),
+            Some(q(5.0, 6.0, 7.0, 8.0)),
+            None,
+        );
+        assert_eq!(result.operation, "dot");
+        assert_eq!(result.dot, Some(70.0));
+        assert!(result.quaternion.is_none());
+        assert!(result.axis_angle.is_none());
+    }
+
+    #[test]
+    fn test_slerp_halfway() {
+        // Halfway from identity to 90° about x is 45° about x
+        let identity = q(0.0, 0.0, 0.0, 1.0);
+        let quarter_x = q(FRAC_1_SQRT_2, 0.0, 0.0, FRAC_1_SQRT_2);
+        let result = run("slerp", identity, Some(quarter_x), Some(0.5));
+        let expected = q((PI / 8.0).sin(), 0.0, 0.0, (PI / 8.0).cos());
+        assert_quaternion_eq(&result.quaternion.unwrap(), &expected, 1e-12);
+        assert!((result.axis_angle.unwrap().angle - PI / 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_slerp_endpoints_and_shorter_arc() {
+        let q1 = q(0.0, 0.0, 0.0, 1.0);
+        let q2 = q(0.0, FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2);
+        let start = run("slerp", q1.clone(), Some(q2.clone()), Some(0.0));
+        assert_quaternion_eq(&start.quaternion.unwrap(), &q1, 1e-12);
+        let end = run("slerp", q1.clone(), Some(q2.clone()), Some(1.0));
+        assert_quaternion_eq(&end.quaternion.unwrap(), &q2, 1e-12);
+
+        // -q2 is the same rotation, so the result stays on the short arc
+        let flipped = run("slerp", q1, Some(q2.scale(-1.0)), Some(1.0));
+        assert_quaternion_eq(&flipped.quaternion.unwrap(), &q2, 1e-12);
+    }
+
+    #[test]
+    fn test_axis_angle_of_identity() {
+        let axis_angle = run("normalize", q(0.0, 0.0, 0.0, 2.0), None, None)
+            .axis_angle
+            .unwrap();
+        assert_eq!(axis_angle.angle, 0.0);
+        assert_eq!(
+            axis_angle.axis,
+            Vector3D {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_conjugate_of_zero_has_no_axis_angle() {
+        let result = run("conjugate", q(0.0, 0.0, 0.0, 0.0), None, None);
+        assert!(result.quaternion.is_some());
+        assert!(result.axis_angle.is_none());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |operation: &str, q1: Quaternion, q2: Option<Quaternion>, t: Option<f64>| {
+            compute_quaternion_ops(QuaternionOpsInput {
+                operation: operation.to_string(),
+                q1,
+                q2,
+                t,
+            })
+            .unwrap_err()
+        };
+        let unit = q(0.0, 0.0, 0.0, 1.0);
+        let zero = q(0.0, 0.0, 0.0, 0.0);
+        assert!(error("multiply", unit.clone(), None, None).contains("requires q2"));
+        assert!(error("slerp", unit.clone(), Some(unit.clone()), None).contains("requires t"));
+        assert!(
+            error("slerp", unit.clone(), Some(unit.clone()), Some(1.5)).contains("between 0 and 1")
+        );
+        assert!(error("inverse", zero.clone(), None, None).contains("no inverse"));
+        assert!(error("normalize", zero, None, None).contains("zero quaternion"));
+        assert!(error("rotate", unit.clone(), None, None).contains("Unsupported operation"));
+        assert!(error("dot", unit, Some(q(f64::NAN, 0.0, 0.0, 1.0)), None).contains("q2"));
+    }
+}
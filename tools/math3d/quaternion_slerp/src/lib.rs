@@ -1,26 +1,60 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 
 mod logic;
-use logic::{QuaternionSlerpInput, quaternion_slerp_logic};
+pub use logic::{QuaternionSlerpInput, quaternion_slerp_logic};
 
-#[derive(serde::Deserialize, JsonSchema)]
+#[derive(serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct ToolInput {
     q1: logic::Quaternion,
     q2: logic::Quaternion,
     t: f64,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    include_metrics: Option<bool>,
 }
 
+#[cfg(feature = "individual")]
 #[derive(serde::Serialize, JsonSchema)]
 struct ToolOutput {
     /// The interpolated quaternion result from spherical linear interpolation
     result: logic::Quaternion,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn quaternion_slerp(input: ToolInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn quaternion_slerp(request: Request<ToolInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("quaternion_slerp", ToolInput, ToolOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("quaternion_slerp", &input);
+    log.finish(run_quaternion_slerp(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_quaternion_slerp(input: ToolInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     let logic_input = QuaternionSlerpInput {
         q1: input.q1,
         q2: input.q2,
@@ -32,7 +66,10 @@ pub fn quaternion_slerp(input: ToolInput) -> ToolResponse {
             let result = ToolOutput {
                 result: output.result,
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
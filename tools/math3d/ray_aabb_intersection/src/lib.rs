@@ -1,19 +1,33 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::*;
 
+// Re-export types from logic module
+pub use logic::{
+    AABBIntersectionResult as LogicOutput, AABBRayInput as LogicInput, ray_aabb_intersection_logic,
+};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3 {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3);
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Ray {
     pub origin: Vector3,
@@ -30,6 +44,12 @@ pub struct AABB {
 pub struct AABBRayInput {
     pub aabb: AABB,
     pub ray: Ray,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -46,8 +66,33 @@ pub struct AABBIntersectionResult {
     pub intersection_points: Vec<IntersectionPoint>,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn ray_aabb_intersection(input: AABBRayInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn ray_aabb_intersection(request: Request<AABBRayInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "ray_aabb_intersection",
+                AABBRayInput,
+                AABBIntersectionResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("ray_aabb_intersection", &input);
+    log.finish(run_ray_aabb_intersection(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_ray_aabb_intersection(input: AABBRayInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     // Convert JsonSchema types to logic types
     let logic_input = logic::AABBRayInput {
         aabb: logic::AABB {
@@ -103,7 +148,10 @@ pub fn ray_aabb_intersection(input: AABBRayInput) -> ToolResponse {
                 closest_distance: logic_result.closest_distance,
                 intersection_points,
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
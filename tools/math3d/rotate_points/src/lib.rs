@@ -0,0 +1,103 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use ftl_units::AngleUnit;
+#[cfg(feature = "individual")]
+use ftl_units::AngleUnitResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_POINTS, RotatePointsInput as LogicInput, RotatePointsResponse as LogicOutput,
+    rotate_points_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    QuaternionSchema as Quaternion, RotatePointsResponseSchema as RotatePointsResponse,
+    Vector3DSchema as Vector3D,
+};
+
+// Shared matrix type, which derives JsonSchema itself
+pub use ftl_matrix4::Matrix4;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RotatePointsInput {
+    #[serde(flatten)]
+    pub params: logic::RotatePointsInputSchema,
+    /// Unit of the angle: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Rotate a batch of points about an arbitrary axis, given by a point and a direction,
+/// with Rodrigues' formula, returning the rotated points and the equivalent rotation
+/// matrix, quaternion and 4x4 transform
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn rotate_points(request: Request<RotatePointsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "rotate_points",
+                RotatePointsInput,
+                AngleUnitResponse<RotatePointsResponse>
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("rotate_points", &input);
+    log.finish(run_rotate_points(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_rotate_points(input: RotatePointsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    let mut params: logic::RotatePointsInput = input.params.into();
+    params.angle = angle_unit.to_radians(params.angle);
+
+    match logic::rotate_points_logic(params) {
+        Ok(logic_result) => {
+            let response = angle_unit.response(RotatePointsResponse::from(logic_result));
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
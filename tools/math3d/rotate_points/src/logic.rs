@@ -0,0 +1,246 @@
+use ftl_matrix4::{Matrix4, axis_angle_rotation, rotation_quaternion};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most points rotated in one call
+pub const MAX_POINTS: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RotatePointsInput {
+    /// Points to rotate
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Direction of the rotation axis, normalized before use
+    #[tool_types(nested)]
+    pub axis_direction: Vector3D,
+    /// A point the axis passes through (default: the origin)
+    #[tool_types(nested)]
+    pub axis_point: Option<Vector3D>,
+    /// Rotation angle in radians, counterclockwise looking down the axis toward
+    /// axis_point (right-hand rule)
+    pub angle: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RotatePointsResponse {
+    /// Rotated points, in input order
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Unit axis direction used
+    #[tool_types(nested)]
+    pub axis: Vector3D,
+    /// 3x3 rotation about the axis direction, as rows; applies about axis_point
+    pub rotation_matrix: [[f64; 3]; 3],
+    /// Unit quaternion of the rotation, with w ≥ 0
+    #[tool_types(nested)]
+    pub quaternion: Quaternion,
+    /// Full transform including the offset of axis_point, acting on column vectors
+    pub matrix: Matrix4,
+    /// Number of points rotated
+    pub count: usize,
+}
+
+fn components(v: &Vector3D) -> [f64; 3] {
+    [v.x, v.y, v.z]
+}
+
+pub fn rotate_points_logic(input: RotatePointsInput) -> Result<RotatePointsResponse, String> {
+    if input.points.len() > MAX_POINTS {
+        return Err(format!(
+            "At most {MAX_POINTS} points can be rotated at once"
+        ));
+    }
+    if let Some(index) = input
+        .points
+        .iter()
+        .position(|p| !components(p).iter().all(|v| v.is_finite()))
+    {
+        return Err(format!("Point {index} contains NaN or infinite values"));
+    }
+    let pivot = input.axis_point.as_ref().map_or([0.0; 3], components);
+    if !pivot.iter().all(|v| v.is_finite()) {
+        return Err("axis_point contains NaN or infinite values".to_string());
+    }
+
+    // Rodrigues' formula about the axis through the origin
+    let rotation = axis_angle_rotation(components(&input.axis_direction), input.angle)?;
+    let [qx, qy, qz, qw] = rotation_quaternion(&rotation);
+    let length = components(&input.axis_direction)
+        .iter()
+        .map(|v| v * v)
+        .sum::<f64>()
+        .sqrt();
+
+    // Move the pivot to the origin, rotate, and move it back
+    let matrix = Matrix4::from_translation(pivot)
+        .multiply(&Matrix4::from_rotation(rotation))
+        .multiply(&Matrix4::from_translation(pivot.map(|v| -v)));
+
+    let points: Vec<Vector3D> = input
+        .points
+        .iter()
+        .map(|p| {
+            let offset = [p.x - pivot[0], p.y - pivot[1], p.z - pivot[2]];
+            let rotated: [f64; 3] =
+                rotation.map(|row| row.iter().zip(&offset).map(|(r, o)| r * o).sum());
+            Vector3D {
+                x: rotated[0] + pivot[0],
+                y: rotated[1] + pivot[1],
+                z: rotated[2] + pivot[2],
+            }
+        })
+        .collect();
+
+    Ok(RotatePointsResponse {
+        count: points.len(),
+        points,
+        axis: Vector3D {
+            x: input.axis_direction.x / length,
+            y: input.axis_direction.y / length,
+            z: input.axis_direction.z / length,
+        },
+        rotation_matrix: rotation,
+        quaternion: Quaternion {
+            x: qx,
+            y: qy,
+            z: qz,
+            w: qw,
+        },
+        matrix,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn point(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn input(points: Vec<Vector3D>, axis: Vector3D, angle: f64) -> RotatePointsInput {
+        RotatePointsInput {
+            points,
+            axis_direction: axis,
+            axis_point: None,
+            angle,
+        }
+    }
+
+    fn assert_points(actual: &[Vector3D], expected: &[[f64; 3]]) {
+        assert_eq!(actual.len(), expected.len());
+        for (p, e) in actual.iter().zip(expected) {
+            for (value, expected) in components(p).iter().zip(e) {
+                assert!(
+                    (value - expected).abs() < 1e-12,
+                    "{actual:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_quarter_turn_about_z() {
+        let request = input(
+            vec![point(1.0, 0.0, 0.0), point(0.0, 1.0, 5.0)],
+            point(0.0, 0.0, 2.0),
+            FRAC_PI_2,
+        );
+        let result = rotate_points_logic(request).unwrap();
+        assert_eq!(result.count, 2);
+        assert_points(&result.points, &[[0.0, 1.0, 0.0], [-1.0, 0.0, 5.0]]);
+        assert_eq!(result.axis, point(0.0, 0.0, 1.0));
+        let half = FRAC_PI_2 / 2.0;
+        let q = result.quaternion;
+        assert!((q.z - half.sin()).abs() < 1e-12 && (q.w - half.cos()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_axis_through_pivot() {
+        let mut request = input(
+            vec![point(2.0, 1.0, 0.0), point(1.0, 1.0, 3.0)],
+            point(0.0, 0.0, 1.0),
+            PI,
+        );
+        request.axis_point = Some(point(1.0, 1.0, 0.0));
+        let result = rotate_points_logic(request).unwrap();
+        // Points on the axis stay put; others swing around it
+        assert_points(&result.points, &[[0.0, 1.0, 0.0], [1.0, 1.0, 3.0]]);
+        // The 4x4 matrix maps the points the same way
+        let mapped = result.matrix.transform_point([2.0, 1.0, 0.0]).unwrap();
+        assert!((mapped[0] - 0.0).abs() < 1e-12 && (mapped[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_diagonal_axis_cycles_coordinates() {
+        // A third of a turn about (1, 1, 1) maps x → y → z → x
+        let request = input(
+            vec![point(1.0, 0.0, 0.0), point(0.0, 0.0, 1.0)],
+            point(1.0, 1.0, 1.0),
+            2.0 * PI / 3.0,
+        );
+        let result = rotate_points_logic(request).unwrap();
+        assert_points(&result.points, &[[0.0, 1.0, 0.0], [1.0, 0.0, 0.0]]);
+        let r = result.rotation_matrix;
+        assert!((r[1][0] - 1.0).abs() < 1e-12 && r[0][0].abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_preserves_distances_to_axis() {
+        let points = vec![point(3.0, -2.0, 7.0), point(-1.5, 4.0, 0.25)];
+        let mut request = input(points.clone(), point(0.3, -0.5, 0.8), 1.234);
+        request.axis_point = Some(point(1.0, 2.0, 3.0));
+        let result = rotate_points_logic(request).unwrap();
+        let pivot = [1.0, 2.0, 3.0];
+        for (before, after) in points.iter().zip(&result.points) {
+            let distance = |p: &Vector3D| {
+                components(p)
+                    .iter()
+                    .zip(&pivot)
+                    .map(|(a, b)| (a - b).powi(2))
+                    .sum::<f64>()
+            };
+            assert!((distance(before) - distance(after)).abs() < 1e-9);
+        }
+        let q = result.quaternion;
+        assert!((q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w - 1.0).abs() < 1e-12);
+        assert!(q.w >= 0.0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let zero_axis = input(vec![point(1.0, 0.0, 0.0)], point(0.0, 0.0, 0.0), 1.0);
+        assert!(rotate_points_logic(zero_axis).is_err());
+        let bad_angle = input(vec![], point(0.0, 0.0, 1.0), f64::NAN);
+        assert!(rotate_points_logic(bad_angle).is_err());
+        let bad_point = input(
+            vec![point(f64::INFINITY, 0.0, 0.0)],
+            point(0.0, 0.0, 1.0),
+            1.0,
+        );
+        assert_eq!(
+            rotate_points_logic(bad_point).unwrap_err(),
+            "Point 0 contains NaN or infinite values"
+        );
+        let mut bad_pivot = input(vec![], point(0.0, 0.0, 1.0), 1.0);
+        bad_pivot.axis_point = Some(point(f64::NAN, 0.0, 0.0));
+        assert!(rotate_points_logic(bad_pivot).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use ftl_units::{AngleUnit, AngleUnitResponse};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    RotationConversionInput as LogicInput, RotationConversionResponse as LogicOutput,
+    convert_rotation,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    EulerAnglesSchema as EulerAngles, Matrix3x3Schema as Matrix3x3, QuaternionSchema as Quaternion,
+    RotationConversionResponseSchema,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RotationConversionInput {
+    #[serde(flatten)]
+    pub params: logic::RotationConversionInputSchema,
+    /// Unit of the Euler angles, in and out: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+// The generated result plus the unit of the Euler angles
+pub type RotationConversionResponse = AngleUnitResponse<RotationConversionResponseSchema>;
+
+/// Convert a rotation among Euler angles in any axis order, a unit quaternion and a
+/// rotation matrix, flagging gimbal lock for the chosen order
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn rotation_conversion(request: Request<RotationConversionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "rotation_conversion",
+                RotationConversionInput,
+                RotationConversionResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("rotation_conversion", &input);
+    log.finish(run_rotation_conversion(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_rotation_conversion(input: RotationConversionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    let mut params: logic::RotationConversionInput = input.params.into();
+    if let Some(euler) = params.euler.as_mut() {
+        euler.first = angle_unit.to_radians(euler.first);
+        euler.second = angle_unit.to_radians(euler.second);
+        euler.third = angle_unit.to_radians(euler.third);
+    }
+
+    match logic::convert_rotation(params) {
+        Ok(logic_result) => {
+            let mut result = RotationConversionResponseSchema::from(logic_result);
+            let euler = &mut result.euler;
+            euler.first = angle_unit.value_from_radians(euler.first);
+            euler.second = angle_unit.value_from_radians(euler.second);
+            euler.third = angle_unit.value_from_radians(euler.third);
+            let response = angle_unit.response(result);
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,644 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+pub const DEFAULT_ORDER: &str = "xyz";
+/// Middle angle distance, in radians, from its singular value at which the first and
+/// third axes are treated as aligned
+pub const GIMBAL_TOLERANCE: f64 = 1e-6;
+/// Largest deviation from orthonormality accepted for an input matrix
+const MATRIX_TOLERANCE: f64 = 1e-6;
+
+/// Euler angles, applied about the axes of `order` in sequence
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct EulerAngles {
+    /// Angle about the first axis of `order`
+    pub first: f64,
+    /// Angle about the second axis of `order`
+    pub second: f64,
+    /// Angle about the third axis of `order`
+    pub third: f64,
+}
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+/// 3x3 rotation matrix, mRC is row R and column C
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Matrix3x3 {
+    pub m00: f64,
+    pub m01: f64,
+    pub m02: f64,
+    pub m10: f64,
+    pub m11: f64,
+    pub m12: f64,
+    pub m20: f64,
+    pub m21: f64,
+    pub m22: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RotationConversionInput {
+    /// Rotation as Euler angles; give exactly one of euler, quaternion and matrix
+    #[tool_types(nested)]
+    pub euler: Option<EulerAngles>,
+    /// Rotation as a quaternion, normalized before use
+    #[tool_types(nested)]
+    pub quaternion: Option<Quaternion>,
+    /// Rotation as a 3x3 rotation matrix
+    #[tool_types(nested)]
+    pub matrix: Option<Matrix3x3>,
+    /// Euler axis order: a Tait-Bryan order (xyz, xzy, yxz, yzx, zxy, zyx) or a proper
+    /// Euler order (xyx, xzx, yxy, yzy, zxz, zyz) (default: xyz)
+    pub order: Option<String>,
+    /// Rotate about the fixed world axes instead of the rotating body axes
+    /// (default: false, intrinsic rotations)
+    pub extrinsic: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RotationConversionResponse {
+    /// Euler axis order used
+    pub order: String,
+    /// Whether the Euler angles are extrinsic
+    pub extrinsic: bool,
+    /// Euler angles in `angle_unit`: the given ones, or each in [-π, π] when converted
+    #[tool_types(nested)]
+    pub euler: EulerAngles,
+    /// Unit quaternion, with w ≥ 0
+    #[tool_types(nested)]
+    pub quaternion: Quaternion,
+    /// Rotation matrix
+    #[tool_types(nested)]
+    pub matrix: Matrix3x3,
+    /// Whether the rotation is at gimbal lock for this order: the first and third axes
+    /// line up, so only the sum or difference of their angles is determined
+    pub gimbal_lock: bool,
+    /// Explanation when gimbal_lock is true
+    pub warning: Option<String>,
+}
+
+/// Axis sequence such as xyz, as basis indexes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Order {
+    axes: [usize; 3],
+    extrinsic: bool,
+}
+
+impl Order {
+    fn parse(text: &str, extrinsic: bool) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid Euler order: {text}. Use three of x, y and z with no axis repeated \
+                 back to back, such as xyz, zyx or zxz"
+            )
+        };
+        let axes: Vec<usize> = text
+            .to_lowercase()
+            .chars()
+            .map(|c| match c {
+                'x' => Ok(0),
+                'y' => Ok(1),
+                'z' => Ok(2),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_, _>>()?;
+        match axes[..] {
+            [i, j, k] if i != j && j != k => Ok(Order {
+                axes: [i, j, k],
+                extrinsic,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.axes
+            .iter()
+            .map(|&axis| ['x', 'y', 'z'][axis])
+            .collect()
+    }
+}
+
+impl Quaternion {
+    /// Rotation by `angle` about basis axis 0 (x), 1 (y) or 2 (z)
+    fn about_axis(axis: usize, angle: f64) -> Self {
+        let mut vector = [0.0; 3];
+        vector[axis] = (angle / 2.0).sin();
+        Quaternion {
+            x: vector[0],
+            y: vector[1],
+            z: vector[2],
+            w: (angle / 2.0).cos(),
+        }
+    }
+
+    fn vector(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn multiply(&self, other: &Quaternion) -> Quaternion {
+        Quaternion {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        }
+    }
+
+    /// Unit quaternion with w ≥ 0, the canonical one of the pair ±q for a rotation
+    fn canonical(&self) -> Result<Quaternion, String> {
+        let values = [self.x, self.y, self.z, self.w];
+        if values.iter().any(|v| !v.is_finite()) {
+            return Err("Quaternion contains NaN or infinite values".to_string());
+        }
+        let magnitude = values.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if magnitude < 1e-10 {
+            return Err("Quaternion cannot be zero".to_string());
+        }
+        let scale = if self.w < 0.0 { -magnitude } else { magnitude };
+        Ok(Quaternion {
+            x: self.x / scale,
+            y: self.y / scale,
+            z: self.z / scale,
+            w: self.w / scale,
+        })
+    }
+
+    fn from_euler(angles: &EulerAngles, order: Order) -> Quaternion {
+        let [i, j, k] = order.axes;
+        let first = Quaternion::about_axis(i, angles.first);
+        let second = Quaternion::about_axis(j, angles.second);
+        let third = Quaternion::about_axis(k, angles.third);
+        if order.extrinsic {
+            // Each rotation is about a fixed axis, so later ones apply on the left
+            third.multiply(&second).multiply(&first)
+        } else {
+            first.multiply(&second).multiply(&third)
+        }
+    }
+
+    /// Euler angles for `order` and whether the rotation is at gimbal lock, following
+    /// Bernardes and Viollet (2022), "Quaternion to Euler angles conversion: A direct,
+    /// general and computationally efficient method". At gimbal lock the third angle
+    /// is set to 0.
+    fn to_euler(&self, order: Order) -> (EulerAngles, bool) {
+        // The method is stated for extrinsic sequences; an intrinsic sequence is the
+        // reversed extrinsic one with its angles reversed
+        let [mut i, j, mut k] = order.axes;
+        if !order.extrinsic {
+            std::mem::swap(&mut i, &mut k);
+        }
+        let proper = i == k;
+        if proper {
+            k = 3 - i - j;
+        }
+        // +1 for an even permutation of (x, y, z), -1 for an odd one
+        let sign =
+            ((i as i64 - j as i64) * (j as i64 - k as i64) * (k as i64 - i as i64) / 2) as f64;
+
+        let q = self.vector();
+        let (a, b, c, d) = if proper {
+            (self.w, q[i], q[j], q[k] * sign)
+        } else {
+            (
+                self.w - q[j],
+                q[i] + q[k] * sign,
+                q[j] + self.w,
+                q[k] * sign - q[i],
+            )
+        };
+
+        let mut angles = [0.0; 3];
+        angles[1] = 2.0 * c.hypot(d).atan2(a.hypot(b));
+        let half_sum = b.atan2(a);
+        let half_diff = d.atan2(c);
+        let gimbal_lock =
+            angles[1].abs() <= GIMBAL_TOLERANCE || (angles[1] - PI).abs() <= GIMBAL_TOLERANCE;
+        if !gimbal_lock {
+            angles[0] = half_sum - half_diff;
+            angles[2] = half_sum + half_diff;
+        } else {
+            // Only the sum or difference of the outer angles is determined; all of it
+            // goes into the angle that ends up first and the third stays 0
+            let locked = if angles[1].abs() <= GIMBAL_TOLERANCE {
+                2.0 * half_sum
+            } else if order.extrinsic {
+                -2.0 * half_diff
+            } else {
+                2.0 * half_diff
+            };
+            if order.extrinsic {
+                angles[0] = locked;
+            } else {
+                angles[2] = locked;
+            }
+        }
+        if !proper {
+            angles[2] *= sign;
+            angles[1] -= FRAC_PI_2;
+        }
+        if !order.extrinsic {
+            angles.swap(0, 2);
+        }
+
+        let wrap = |angle: f64| {
+            if angle < -PI {
+                angle + 2.0 * PI
+            } else if angle > PI {
+                angle - 2.0 * PI
+            } else {
+                angle
+            }
+        };
+        (
+            EulerAngles {
+                first: wrap(angles[0]),
+                second: wrap(angles[1]),
+                third: wrap(angles[2]),
+            },
+            gimbal_lock,
+        )
+    }
+
+    fn to_matrix(&self) -> Matrix3x3 {
+        let Quaternion { x, y, z, w } = *self;
+        Matrix3x3 {
+            m00: 1.0 - 2.0 * (y * y + z * z),
+            m01: 2.0 * (x * y - z * w),
+            m02: 2.0 * (x * z + y * w),
+            m10: 2.0 * (x * y + z * w),
+            m11: 1.0 - 2.0 * (x * x + z * z),
+            m12: 2.0 * (y * z - x * w),
+            m20: 2.0 * (x * z - y * w),
+            m21: 2.0 * (y * z + x * w),
+            m22: 1.0 - 2.0 * (x * x + y * y),
+        }
+    }
+}
+
+impl Matrix3x3 {
+    fn rows(&self) -> [[f64; 3]; 3] {
+        [
+            [self.m00, self.m01, self.m02],
+            [self.m10, self.m11, self.m12],
+            [self.m20, self.m21, self.m22],
+        ]
+    }
+
+    /// Check that the matrix is orthonormal with determinant +1
+    fn validate(&self) -> Result<(), String> {
+        let m = self.rows();
+        if m.iter().flatten().any(|v| !v.is_finite()) {
+            return Err("Matrix contains NaN or infinite values".to_string());
+        }
+        for r in 0..3 {
+            for s in 0..3 {
+                let dot: f64 = (0..3).map(|c| m[r][c] * m[s][c]).sum();
+                let expected = if r == s { 1.0 } else { 0.0 };
+                if (dot - expected).abs() > MATRIX_TOLERANCE {
+                    return Err(
+                        "Matrix is not a rotation: its rows must be orthonormal".to_string()
+                    );
+                }
+            }
+        }
+        let determinant = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        if determinant < 0.0 {
+            return Err(
+                "Matrix is a reflection, not a rotation: its determinant is -1".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Quaternion of a rotation matrix, using the largest of the four candidate
+    /// denominators for numerical stability (Shepperd's method)
+    fn to_quaternion(&self) -> Quaternion {
+        let m = self.rows();
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        if trace >= m[0][0] && trace >= m[1][1] && trace >= m[2][2] {
+            let s = 2.0 * (1.0 + trace).sqrt();
+            Quaternion {
+                x: (m[2][1] - m[1][2]) / s,
+                y: (m[0][2] - m[2][0]) / s,
+                z: (m[1][0] - m[0][1]) / s,
+                w: s / 4.0,
+            }
+        } else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+            let s = 2.0 * (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt();
+            Quaternion {
+                x: s / 4.0,
+                y: (m[0][1] + m[1][0]) / s,
+                z: (m[0][2] + m[2][0]) / s,
+                w: (m[2][1] - m[1][2]) / s,
+            }
+        } else if m[1][1] >= m[2][2] {
+            let s = 2.0 * (1.0 - m[0][0] + m[1][1] - m[2][2]).sqrt();
+            Quaternion {
+                x: (m[0][1] + m[1][0]) / s,
+                y: s / 4.0,
+                z: (m[1][2] + m[2][1]) / s,
+                w: (m[0][2] - m[2][0]) / s,
+            }
+        } else {
+            let s = 2.0 * (1.0 - m[0][0] - m[1][1] + m[2][2]).sqrt();
+            Quaternion {
+                x: (m[0][2] + m[2][0]) / s,
+                y: (m[1][2] + m[2][1]) / s,
+                z: s / 4.0,
+                w: (m[1][0] - m[0][1]) / s,
+            }
+        }
+    }
+}
+
+pub fn convert_rotation(
+    input: RotationConversionInput,
+) -> Result<RotationConversionResponse, String> {
+    let order = Order::parse(
+        input.order.as_deref().unwrap_or(DEFAULT_ORDER),
+        input.extrinsic.unwrap_or(false),
+    )?;
+
+    let mut given_euler = None;
+    let quaternion = match (input.euler, input.quaternion, input.matrix) {
+        (Some(euler), None, None) => {
+            if ![euler.first, euler.second, euler.third]
+                .iter()
+                .all(|angle| angle.is_finite())
+            {
+                return Err("Euler angles must be finite".to_string());
+            }
+            let quaternion = Quaternion::from_euler(&euler, order);
+            given_euler = Some(euler);
+            quaternion
+        }
+        (None, Some(quaternion), None) => quaternion,
+        (None, None, Some(matrix)) => {
+            matrix.validate()?;
+            matrix.to_quaternion()
+        }
+        _ => {
+            return Err("Give exactly one of euler, quaternion and matrix".to_string());
+        }
+    }
+    .canonical()?;
+
+    let (euler, gimbal_lock) = quaternion.to_euler(order);
+    let warning = gimbal_lock.then(|| {
+        let axes: Vec<char> = order.name().chars().collect();
+        format!(
+            "Gimbal lock: the first ({}) and third ({}) rotations are about the same axis, \
+             so only their combined angle is determined; converted angles put all of it \
+             in the first angle and set the third to 0",
+            axes[0], axes[2]
+        )
+    });
+
+    Ok(RotationConversionResponse {
+        order: order.name(),
+        extrinsic: order.extrinsic,
+        euler: given_euler.unwrap_or(euler),
+        matrix: quaternion.to_matrix(),
+        quaternion,
+        gimbal_lock,
+        warning,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDERS: [&str; 12] = [
+        "xyz", "xzy", "yxz", "yzx", "zxy", "zyx", "xyx", "xzx", "yxy", "yzy", "zxz", "zyz",
+    ];
+
+    fn euler(first: f64, second: f64, third: f64) -> EulerAngles {
+        EulerAngles {
+            first,
+            second,
+            third,
+        }
+    }
+
+    fn from_euler(angles: EulerAngles, order: &str, extrinsic: bool) -> RotationConversionResponse {
+        convert_rotation(RotationConversionInput {
+            euler: Some(angles),
+            quaternion: None,
+            matrix: None,
+            order: Some(order.to_string()),
+            extrinsic: Some(extrinsic),
+        })
+        .unwrap()
+    }
+
+    fn from_quaternion(
+        quaternion: Quaternion,
+        order: &str,
+        extrinsic: bool,
+    ) -> RotationConversionResponse {
+        convert_rotation(RotationConversionInput {
+            euler: None,
+            quaternion: Some(quaternion),
+            matrix: None,
+            order: Some(order.to_string()),
+            extrinsic: Some(extrinsic),
+        })
+        .unwrap()
+    }
+
+    fn from_matrix(matrix: Matrix3x3) -> Result<RotationConversionResponse, String> {
+        convert_rotation(RotationConversionInput {
+            euler: None,
+            quaternion: None,
+            matrix: Some(matrix),
+            order: None,
+            extrinsic: None,
+        })
+    }
+
+    /// Same rotation: q and -q are equal up to sign
+    fn assert_same_rotation(a: &Quaternion, b: &Quaternion) {
+        let dot = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+        assert!((dot.abs() - 1.0).abs() < 1e-9, "{a:?} and {b:?} differ");
+    }
+
+    #[test]
+    fn test_single_axis_rotation() {
+        // 90° about z with any order starting with z
+        let result = from_euler(euler(FRAC_PI_2, 0.0, 0.0), "zyx", false);
+        let half = FRAC_PI_2 / 2.0;
+        assert_same_rotation(
+            &result.quaternion,
+            &Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: half.sin(),
+                w: half.cos(),
+            },
+        );
+        let m = result.matrix;
+        assert!((m.m01 + 1.0).abs() < 1e-12 && (m.m10 - 1.0).abs() < 1e-12);
+        assert!(!result.gimbal_lock);
+        assert!(result.warning.is_none());
+    }
+
+    #[test]
+    fn test_intrinsic_and_extrinsic_orders_are_reverses() {
+        let angles = euler(0.3, -0.7, 1.1);
+        let intrinsic = from_euler(angles.clone(), "xyz", false);
+        let extrinsic = from_euler(euler(1.1, -0.7, 0.3), "zyx", true);
+        assert_same_rotation(&intrinsic.quaternion, &extrinsic.quaternion);
+    }
+
+    #[test]
+    fn test_round_trip_every_order() {
+        let samples = [
+            euler(0.3, -0.7, 1.1),
+            euler(-2.5, 0.4, 2.9),
+            euler(1.0, 2.0, -3.0),
+        ];
+        for order in ORDERS {
+            for extrinsic in [false, true] {
+                for angles in &samples {
+                    let forward = from_euler(angles.clone(), order, extrinsic);
+                    let back = from_quaternion(forward.quaternion.clone(), order, extrinsic);
+                    let again = from_euler(back.euler.clone(), order, extrinsic);
+                    assert_same_rotation(&forward.quaternion, &again.quaternion);
+                    assert!(!back.gimbal_lock, "{order} {extrinsic} {angles:?}");
+                    for angle in [back.euler.first, back.euler.second, back.euler.third] {
+                        assert!((-PI..=PI).contains(&angle));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gimbal_lock_every_order() {
+        for order in ORDERS {
+            let proper = order.as_bytes()[0] == order.as_bytes()[2];
+            let middles: &[f64] = if proper {
+                &[0.0, PI]
+            } else {
+                &[FRAC_PI_2, -FRAC_PI_2]
+            };
+            for extrinsic in [false, true] {
+                for &middle in middles {
+                    let forward = from_euler(euler(0.4, middle, 0.9), order, extrinsic);
+                    assert!(forward.gimbal_lock, "{order} {extrinsic} {middle}");
+                    assert!(forward.warning.is_some());
+
+                    let back = from_quaternion(forward.quaternion.clone(), order, extrinsic);
+                    assert!(back.gimbal_lock);
+                    assert_eq!(back.euler.third, 0.0, "{order} {extrinsic} {middle}");
+                    let again = from_euler(back.euler, order, extrinsic);
+                    assert_same_rotation(&forward.quaternion, &again.quaternion);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let forward = from_euler(euler(0.2, 0.5, -1.3), "zyx", false);
+        // Rotations near 180° use the diagonal branches of Shepperd's method
+        let flipped = from_euler(euler(PI - 1e-3, 0.1, 0.0), "xyz", false);
+        for expected in [forward, flipped] {
+            let result = from_matrix(expected.matrix.clone()).unwrap();
+            assert_same_rotation(&result.quaternion, &expected.quaternion);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_is_normalized_and_canonical() {
+        let result = from_quaternion(
+            Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: -2.0,
+                w: -2.0,
+            },
+            "xyz",
+            false,
+        );
+        let q = result.quaternion;
+        assert!(q.w > 0.0);
+        assert!((q.z - q.w).abs() < 1e-12);
+        assert!((q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        let base = RotationConversionInput {
+            euler: None,
+            quaternion: None,
+            matrix: None,
+            order: None,
+            extrinsic: None,
+        };
+        let error = |input: RotationConversionInput| convert_rotation(input).unwrap_err();
+        assert!(error(base.clone()).contains("exactly one"));
+
+        let mut two = base.clone();
+        two.euler = Some(euler(0.0, 0.0, 0.0));
+        two.quaternion = Some(Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 1.0,
+        });
+        assert!(error(two).contains("exactly one"));
+
+        for order in ["xxy", "xy", "abc", "xyzx"] {
+            let mut bad = base.clone();
+            bad.euler = Some(euler(0.0, 0.0, 0.0));
+            bad.order = Some(order.to_string());
+            assert!(error(bad).contains("Invalid Euler order"), "{order}");
+        }
+
+        let mut zero = base.clone();
+        zero.quaternion = Some(Quaternion {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: 0.0,
+        });
+        assert!(error(zero).contains("cannot be zero"));
+
+        let scaled = Matrix3x3 {
+            m00: 2.0,
+            m01: 0.0,
+            m02: 0.0,
+            m10: 0.0,
+            m11: 1.0,
+            m12: 0.0,
+            m20: 0.0,
+            m21: 0.0,
+            m22: 1.0,
+        };
+        assert!(from_matrix(scaled).unwrap_err().contains("orthonormal"));
+        let mirror = Matrix3x3 {
+            m00: -1.0,
+            m01: 0.0,
+            m02: 0.0,
+            m10: 0.0,
+            m11: 1.0,
+            m12: 0.0,
+            m20: 0.0,
+            m21: 0.0,
+            m22: 1.0,
+        };
+        assert!(from_matrix(mirror).unwrap_err().contains("reflection"));
+    }
+}
@@ -1,61 +1,85 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
+#[cfg(feature = "individual")]
+use ftl_units::AngleUnitResponse;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Matrix3x3 {
-    pub m00: f64,
-    pub m01: f64,
-    pub m02: f64,
-    pub m10: f64,
-    pub m11: f64,
-    pub m12: f64,
-    pub m20: f64,
-    pub m21: f64,
-    pub m22: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    RotationMatrixInput as LogicInput, RotationMatrixResponse as LogicOutput,
+    compute_rotation_matrix,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    Matrix3x3Schema as Matrix3x3, RotationMatrixResponseSchema as RotationMatrixResponse,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RotationMatrixInput {
-    pub axis: String,
-    pub angle: f64,
+    #[serde(flatten)]
+    pub params: logic::RotationMatrixInputSchema,
+    /// Unit of `angle`: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct RotationMatrixResponse {
-    pub matrix: Matrix3x3,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn rotation_matrix(request: Request<RotationMatrixInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "rotation_matrix",
+                RotationMatrixInput,
+                AngleUnitResponse<RotationMatrixResponse>
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("rotation_matrix", &input);
+    log.finish(run_rotation_matrix(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn rotation_matrix(input: RotationMatrixInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::RotationMatrixInput {
-        axis: input.axis,
-        angle: input.angle,
+#[cfg(feature = "individual")]
+fn run_rotation_matrix(input: RotationMatrixInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_rotation_matrix(logic_input) {
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    let mut params: logic::RotationMatrixInput = input.params.into();
+    params.angle = angle_unit.to_radians(params.angle);
+
+    match logic::compute_rotation_matrix(params) {
         Ok(logic_result) => {
-            // Convert logic types back to API types
-            let result = RotationMatrixResponse {
-                matrix: Matrix3x3 {
-                    m00: logic_result.matrix.m00,
-                    m01: logic_result.matrix.m01,
-                    m02: logic_result.matrix.m02,
-                    m10: logic_result.matrix.m10,
-                    m11: logic_result.matrix.m11,
-                    m12: logic_result.matrix.m12,
-                    m20: logic_result.matrix.m20,
-                    m21: logic_result.matrix.m21,
-                    m22: logic_result.matrix.m22,
-                },
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let response = angle_unit.response(RotationMatrixResponse::from(logic_result));
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
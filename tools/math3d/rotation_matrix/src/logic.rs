@@ -1,6 +1,7 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
 pub struct Matrix3x3 {
     pub m00: f64,
     pub m01: f64,
@@ -13,14 +14,15 @@ pub struct Matrix3x3 {
     pub m22: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct RotationMatrixInput {
     pub axis: String,
     pub angle: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct RotationMatrixResponse {
+    #[tool_types(nested)]
     pub matrix: Matrix3x3,
 }
 
@@ -0,0 +1,377 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most points fitted in one call
+pub const MAX_POINTS: usize = 100_000;
+/// Levenberg-Marquardt iterations before giving up on convergence
+const MAX_ITERATIONS: usize = 200;
+/// Relative step size, against the point cloud's extent, that counts as converged
+const STEP_TOLERANCE: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct SphereFitInput {
+    /// Points measured on or near the sphere's surface; at least 4, not all coplanar
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// "geometric" minimizes the sum of squared distances from the surface, starting from
+    /// the algebraic fit; "algebraic" solves the linearized problem directly, which is
+    /// faster but biased for noisy or partial-arc data (default: geometric)
+    pub method: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct SphereFitResult {
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    pub radius: f64,
+    /// Root mean square of the residuals
+    pub rms_residual: f64,
+    /// Largest residual magnitude, the form error in metrology terms
+    pub max_abs_residual: f64,
+    /// Signed distance of each point from the fitted surface, positive outside, in input
+    /// order
+    pub residuals: Vec<f64>,
+    pub method: String,
+    /// Levenberg-Marquardt iterations taken; 0 for algebraic
+    pub iterations: usize,
+    /// Whether the geometric refinement converged; always true for algebraic
+    pub converged: bool,
+}
+
+fn components(v: &Vector3D) -> [f64; 3] {
+    [v.x, v.y, v.z]
+}
+
+/// Solves a 4x4 linear system by Gaussian elimination with partial pivoting, returning
+/// None when a pivot is negligible relative to the matrix's scale
+fn solve4(mut a: [[f64; 4]; 4], mut b: [f64; 4]) -> Option<[f64; 4]> {
+    let scale = a.iter().flatten().fold(0.0f64, |m, v| m.max(v.abs()));
+    if scale == 0.0 {
+        return None;
+    }
+    for col in 0..4 {
+        let pivot = (col..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() <= 1e-12 * scale {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col];
+        for row in col + 1..4 {
+            let factor = a[row][col] / pivot_row[col];
+            for (value, p) in a[row].iter_mut().zip(&pivot_row).skip(col) {
+                *value -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = [0.0; 4];
+    for row in (0..4).rev() {
+        let tail: f64 = (row + 1..4).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Linear least squares on |p|² = 2c·p + (r² - |c|²), in coordinates centered on the
+/// centroid for conditioning
+fn algebraic_fit(points: &[[f64; 3]]) -> Option<([f64; 3], f64)> {
+    let mut normal = [[0.0; 4]; 4];
+    let mut rhs = [0.0; 4];
+    for p in points {
+        let row = [2.0 * p[0], 2.0 * p[1], 2.0 * p[2], 1.0];
+        let target = p.iter().map(|v| v * v).sum::<f64>();
+        for i in 0..4 {
+            for j in 0..4 {
+                normal[i][j] += row[i] * row[j];
+            }
+            rhs[i] += row[i] * target;
+        }
+    }
+    let [cx, cy, cz, d] = solve4(normal, rhs)?;
+    let radius_squared = d + cx * cx + cy * cy + cz * cz;
+    (radius_squared > 0.0).then(|| ([cx, cy, cz], radius_squared.sqrt()))
+}
+
+fn residuals(points: &[[f64; 3]], center: [f64; 3], radius: f64) -> Vec<f64> {
+    points
+        .iter()
+        .map(|p| {
+            let d: f64 = (0..3)
+                .map(|k| (p[k] - center[k]).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            d - radius
+        })
+        .collect()
+}
+
+fn cost(points: &[[f64; 3]], center: [f64; 3], radius: f64) -> f64 {
+    residuals(points, center, radius)
+        .iter()
+        .map(|r| r * r)
+        .sum()
+}
+
+/// Levenberg-Marquardt on the geometric residuals |p - c| - r
+fn geometric_fit(
+    points: &[[f64; 3]],
+    mut center: [f64; 3],
+    mut radius: f64,
+    extent: f64,
+) -> ([f64; 3], f64, usize, bool) {
+    let mut lambda = 1e-3;
+    let mut current = cost(points, center, radius);
+    for iteration in 1..=MAX_ITERATIONS {
+        let mut jtj = [[0.0; 4]; 4];
+        let mut jtr = [0.0; 4];
+        for p in points {
+            let offset = [p[0] - center[0], p[1] - center[1], p[2] - center[2]];
+            let d = offset.iter().map(|v| v * v).sum::<f64>().sqrt();
+            // A point at the center has no defined direction; it only constrains the radius
+            let row = if d > 0.0 {
+                [-offset[0] / d, -offset[1] / d, -offset[2] / d, -1.0]
+            } else {
+                [0.0, 0.0, 0.0, -1.0]
+            };
+            let residual = d - radius;
+            for i in 0..4 {
+                for j in 0..4 {
+                    jtj[i][j] += row[i] * row[j];
+                }
+                jtr[i] += row[i] * residual;
+            }
+        }
+
+        loop {
+            let mut damped = jtj;
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += lambda * jtj[i][i].max(1e-12);
+            }
+            let Some(step) = solve4(damped, jtr.map(|v| -v)) else {
+                return (center, radius, iteration, false);
+            };
+            let candidate = [
+                center[0] + step[0],
+                center[1] + step[1],
+                center[2] + step[2],
+            ];
+            let candidate_radius = radius + step[3];
+            let candidate_cost = cost(points, candidate, candidate_radius);
+            let step_size = step.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if candidate_cost <= current {
+                center = candidate;
+                radius = candidate_radius;
+                let improvement = current - candidate_cost;
+                current = candidate_cost;
+                lambda = (lambda / 10.0).max(1e-12);
+                if step_size <= STEP_TOLERANCE * extent
+                    || improvement <= f64::EPSILON * current.max(f64::MIN_POSITIVE)
+                {
+                    return (center, radius, iteration, true);
+                }
+                break;
+            }
+            if step_size <= STEP_TOLERANCE * extent {
+                // No smaller step improves the fit: at a minimum to working precision
+                return (center, radius, iteration, true);
+            }
+            lambda *= 10.0;
+            if lambda > 1e12 {
+                return (center, radius, iteration, false);
+            }
+        }
+    }
+    (center, radius, MAX_ITERATIONS, false)
+}
+
+pub fn sphere_fit_logic(input: SphereFitInput) -> Result<SphereFitResult, String> {
+    if input.points.len() < 4 {
+        return Err("At least 4 points are required to fit a sphere".to_string());
+    }
+    if input.points.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} points can be fitted at once"));
+    }
+    if let Some(index) = input
+        .points
+        .iter()
+        .position(|p| !components(p).iter().all(|v| v.is_finite()))
+    {
+        return Err(format!("Point {index} contains NaN or infinite values"));
+    }
+    let method = input
+        .method
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .unwrap_or_else(|| "geometric".to_string());
+    if method != "geometric" && method != "algebraic" {
+        return Err(format!(
+            "Unknown method '{method}', use 'geometric' or 'algebraic'"
+        ));
+    }
+
+    let n = input.points.len() as f64;
+    let centroid: [f64; 3] =
+        std::array::from_fn(|k| input.points.iter().map(|p| components(p)[k]).sum::<f64>() / n);
+    let centered: Vec<[f64; 3]> = input
+        .points
+        .iter()
+        .map(|p| {
+            let c = components(p);
+            [c[0] - centroid[0], c[1] - centroid[1], c[2] - centroid[2]]
+        })
+        .collect();
+    let extent = centered
+        .iter()
+        .map(|p| p.iter().map(|v| v * v).sum::<f64>().sqrt())
+        .fold(0.0, f64::max);
+    if extent == 0.0 {
+        return Err("Points must not all coincide".to_string());
+    }
+
+    let (mut center, mut radius) = algebraic_fit(&centered)
+        .ok_or("Points are coplanar or collinear, so they do not determine a unique sphere")?;
+    let (iterations, converged) = if method == "geometric" {
+        let fit = geometric_fit(&centered, center, radius, extent);
+        (center, radius) = (fit.0, fit.1);
+        (fit.2, fit.3)
+    } else {
+        (0, true)
+    };
+    let residuals = residuals(&centered, center, radius);
+    let rms_residual = (residuals.iter().map(|r| r * r).sum::<f64>() / n).sqrt();
+
+    Ok(SphereFitResult {
+        center: Vector3D {
+            x: center[0] + centroid[0],
+            y: center[1] + centroid[1],
+            z: center[2] + centroid[2],
+        },
+        radius,
+        rms_residual,
+        max_abs_residual: residuals.iter().fold(0.0, |m, r| m.max(r.abs())),
+        residuals,
+        method,
+        iterations,
+        converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    /// Points on a sphere, over the band of polar angles [0, max_polar]
+    fn sphere_points(center: [f64; 3], radius: f64, max_polar: f64) -> Vec<Vector3D> {
+        let mut points = Vec::new();
+        for i in 0..6 {
+            let polar = max_polar * (i as f64 + 0.5) / 6.0;
+            for j in 0..8 {
+                let azimuth = std::f64::consts::TAU * j as f64 / 8.0;
+                points.push(point(
+                    center[0] + radius * polar.sin() * azimuth.cos(),
+                    center[1] + radius * polar.sin() * azimuth.sin(),
+                    center[2] + radius * polar.cos(),
+                ));
+            }
+        }
+        points
+    }
+
+    fn request(points: Vec<Vector3D>, method: &str) -> SphereFitInput {
+        SphereFitInput {
+            points,
+            method: Some(method.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_exact_sphere() {
+        let points = sphere_points([10.0, -5.0, 2.5], 3.0, std::f64::consts::PI);
+        for method in ["algebraic", "geometric"] {
+            let result = sphere_fit_logic(request(points.clone(), method)).unwrap();
+            assert!((result.center.x - 10.0).abs() < 1e-9);
+            assert!((result.center.y + 5.0).abs() < 1e-9);
+            assert!((result.center.z - 2.5).abs() < 1e-9);
+            assert!((result.radius - 3.0).abs() < 1e-9);
+            assert!(result.rms_residual < 1e-9 && result.converged);
+        }
+    }
+
+    #[test]
+    fn test_four_points_determine_sphere() {
+        // The unit sphere through four of its points
+        let points = vec![
+            point(1.0, 0.0, 0.0),
+            point(0.0, 1.0, 0.0),
+            point(0.0, 0.0, 1.0),
+            point(-1.0, 0.0, 0.0),
+        ];
+        let result = sphere_fit_logic(request(points, "geometric")).unwrap();
+        assert!(result.center.x.abs() < 1e-9 && result.center.z.abs() < 1e-9);
+        assert!((result.radius - 1.0).abs() < 1e-9);
+        assert_eq!(result.residuals.len(), 4);
+    }
+
+    #[test]
+    fn test_residual_signs() {
+        let mut points = sphere_points([0.0, 0.0, 0.0], 2.0, std::f64::consts::PI);
+        // Raising a point near the top pushes it out; raising one near the bottom pulls it in
+        points[0].z += 0.01;
+        points[47].z += 0.01;
+        let result = sphere_fit_logic(request(points, "geometric")).unwrap();
+        assert!(result.residuals[0] > 0.0 && result.residuals[47] < 0.0);
+        assert!(result.max_abs_residual >= result.rms_residual);
+        assert!(result.iterations > 0);
+    }
+
+    #[test]
+    fn test_geometric_improves_partial_cap() {
+        // A noisy cap covering a quarter of the sphere, where the algebraic fit is biased
+        let mut points = sphere_points([1.0, 2.0, 3.0], 5.0, 0.8);
+        for (i, p) in points.iter_mut().enumerate() {
+            p.z += 0.02 * ((i * 7) % 5) as f64 - 0.04;
+        }
+        let algebraic = sphere_fit_logic(request(points.clone(), "algebraic")).unwrap();
+        let geometric = sphere_fit_logic(request(points, "geometric")).unwrap();
+        assert!(geometric.converged);
+        assert!(geometric.rms_residual <= algebraic.rms_residual);
+        assert_eq!(algebraic.iterations, 0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let too_few = vec![point(1.0, 0.0, 0.0), point(0.0, 1.0, 0.0)];
+        assert!(sphere_fit_logic(request(too_few, "geometric")).is_err());
+        let coplanar = vec![
+            point(1.0, 0.0, 0.0),
+            point(0.0, 1.0, 0.0),
+            point(-1.0, 0.0, 0.0),
+            point(0.0, -1.0, 0.0),
+        ];
+        assert!(
+            sphere_fit_logic(request(coplanar, "geometric"))
+                .unwrap_err()
+                .contains("coplanar")
+        );
+        let points = sphere_points([0.0, 0.0, 0.0], 1.0, 1.0);
+        assert!(sphere_fit_logic(request(points, "ransac")).is_err());
+        let mut bad = sphere_points([0.0, 0.0, 0.0], 1.0, 1.0);
+        bad[3].y = f64::NAN;
+        assert_eq!(
+            sphere_fit_logic(request(bad, "algebraic")).unwrap_err(),
+            "Point 3 contains NaN or infinite values"
+        );
+    }
+}
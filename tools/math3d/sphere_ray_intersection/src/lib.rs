@@ -1,19 +1,33 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::*;
 
+// Re-export types from logic module
+pub use logic::{
+    SphereRayInput as LogicInput, SphereRayResult as LogicOutput, sphere_ray_intersection_logic,
+};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
+ftl_vector3d::from_vector3d_input!(Vector3D);
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Ray {
     pub origin: Vector3D,
@@ -30,6 +44,12 @@ pub struct Sphere {
 pub struct SphereRayInput {
     pub sphere: Sphere,
     pub ray: Ray,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -46,8 +66,33 @@ pub struct SphereRayResult {
     pub closest_distance: Option<f64>,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn sphere_ray_intersection(input: SphereRayInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn sphere_ray_intersection(request: Request<SphereRayInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "sphere_ray_intersection",
+                SphereRayInput,
+                SphereRayResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("sphere_ray_intersection", &input);
+    log.finish(run_sphere_ray_intersection(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_sphere_ray_intersection(input: SphereRayInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
     // Convert JsonSchema types to logic types
     let logic_input = logic::SphereRayInput {
         sphere: logic::Sphere {
@@ -99,7 +144,10 @@ pub fn sphere_ray_intersection(input: SphereRayInput) -> ToolResponse {
                 intersection_points,
                 closest_distance: logic_result.closest_distance,
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
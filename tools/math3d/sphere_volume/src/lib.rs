@@ -1,59 +1,73 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    SphereVolumeInput as LogicInput, SphereVolumeResponse as LogicOutput,
+    Vector3D as LogicVector3D, compute_sphere_volume,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{SphereVolumeResponseSchema as SphereVolumeResponse, Vector3DSchema as Vector3D};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SphereVolumeInput {
-    pub center: Vector3D,
-    pub radius: f64,
+    #[serde(flatten)]
+    pub params: logic::SphereVolumeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct SphereVolumeResponse {
-    pub volume: f64,
-    pub calculation_method: String,
-    pub center: Vector3D,
-    pub radius: f64,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn sphere_volume(request: Request<SphereVolumeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "sphere_volume",
+                SphereVolumeInput,
+                SphereVolumeResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("sphere_volume", &input);
+    log.finish(run_sphere_volume(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn sphere_volume(input: SphereVolumeInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::SphereVolumeInput {
-        center: logic::Vector3D {
-            x: input.center.x,
-            y: input.center.y,
-            z: input.center.z,
-        },
-        radius: input.radius,
+#[cfg(feature = "individual")]
+fn run_sphere_volume(input: SphereVolumeInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_sphere_volume(logic_input) {
+    match logic::compute_sphere_volume(input.params.into()) {
         Ok(logic_result) => {
-            // Convert logic types back to API types
-            let result = SphereVolumeResponse {
-                volume: logic_result.volume,
-                calculation_method: logic_result.calculation_method,
-                center: Vector3D {
-                    x: logic_result.center.x,
-                    y: logic_result.center.y,
-                    z: logic_result.center.z,
-                },
-                radius: logic_result.radius,
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = SphereVolumeResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,22 +1,44 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::AngleUnit;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{SphericalToCartesianInput, spherical_to_cartesian_logic};
+pub use logic::{SphericalToCartesianInput, spherical_to_cartesian_logic};
 
 #[derive(Deserialize, Serialize, JsonSchema)]
 pub struct SphericalCoordinates {
     /// Radial distance (radius)
     pub radius: f64,
-    /// Polar angle (theta) in radians
+    /// Polar angle (theta) in `angle_unit`
     pub theta: f64,
-    /// Azimuthal angle (phi) in radians
+    /// Azimuthal angle (phi) in `angle_unit`
     pub phi: f64,
 }
 
+// The coordinates to convert plus options that only affect how the result is serialized
+#[derive(Deserialize, Serialize, JsonSchema)]
+pub struct ConversionInput {
+    #[serde(flatten)]
+    pub coordinates: SphericalCoordinates,
+    /// Unit of the given theta and phi: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
 #[derive(Serialize, JsonSchema)]
 pub struct CartesianCoordinates {
     /// X coordinate
@@ -35,16 +57,45 @@ pub struct SphericalToCartesianResult {
     pub cartesian_coordinates: CartesianCoordinates,
     /// Conversion notes
     pub conversion_notes: String,
+    /// Unit of theta and phi
+    pub angle_unit: String,
 }
 
 /// Convert spherical coordinates (r, theta, phi) to Cartesian coordinates (x, y, z)
-#[cfg_attr(not(test), tool)]
-pub fn spherical_to_cartesian(input: SphericalCoordinates) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn spherical_to_cartesian(request: Request<ConversionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "spherical_to_cartesian",
+                ConversionInput,
+                SphericalToCartesianResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("spherical_to_cartesian", &input);
+    log.finish(run_spherical_to_cartesian(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_spherical_to_cartesian(input: ConversionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
     let logic_input = SphericalToCartesianInput {
         coordinates: logic::SphericalCoord {
-            radius: input.radius,
-            theta: input.theta,
-            phi: input.phi,
+            radius: input.coordinates.radius,
+            theta: angle_unit.to_radians(input.coordinates.theta),
+            phi: angle_unit.to_radians(input.coordinates.phi),
         },
     };
 
@@ -53,8 +104,8 @@ pub fn spherical_to_cartesian(input: SphericalCoordinates) -> ToolResponse {
             let result = SphericalToCartesianResult {
                 original_spherical: SphericalCoordinates {
                     radius: output.original_spherical.radius,
-                    theta: output.original_spherical.theta,
-                    phi: output.original_spherical.phi,
+                    theta: angle_unit.value_from_radians(output.original_spherical.theta),
+                    phi: angle_unit.value_from_radians(output.original_spherical.phi),
                 },
                 cartesian_coordinates: CartesianCoordinates {
                     x: output.cartesian_coordinates.x,
@@ -62,8 +113,12 @@ pub fn spherical_to_cartesian(input: SphericalCoordinates) -> ToolResponse {
                     z: output.cartesian_coordinates.z,
                 },
                 conversion_notes: output.conversion_notes,
+                angle_unit: angle_unit.name().to_string(),
             };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
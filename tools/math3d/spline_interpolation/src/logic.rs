@@ -0,0 +1,460 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most control points accepted in one call
+pub const MAX_CONTROL_POINTS: usize = 10_000;
+/// Most sampled points returned in one call
+pub const MAX_SAMPLES: usize = 100_000;
+/// Default number of samples per curve segment
+const DEFAULT_SAMPLES_PER_SEGMENT: usize = 16;
+/// Gauss-Legendre nodes and weights on [-1, 1] for arc length integration
+const GAUSS: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+/// Subintervals per segment for the quadrature
+const QUADRATURE_INTERVALS: usize = 8;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct SplineInput {
+    /// Control points in order
+    #[tool_types(nested)]
+    pub control_points: Vec<Vector3D>,
+    /// "catmull_rom" (passes through every control point) or "b_spline" (uniform cubic
+    /// B-spline, smoother but only approximating the interior points) (default: catmull_rom)
+    pub spline_type: Option<String>,
+    /// Samples per curve segment, at evenly spaced parameter values (default: 16)
+    pub samples_per_segment: Option<usize>,
+    /// Join the last control point back to the first (default: false)
+    pub closed: Option<bool>,
+    /// Catmull-Rom knot parameterization: 0 uniform, 0.5 centripetal (no cusps or
+    /// self-intersections within a segment), 1 chordal (default: 0.5)
+    pub alpha: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct SplineSample {
+    #[tool_types(nested)]
+    pub position: Vector3D,
+    /// Unit tangent in the direction of travel; zero where the curve is stationary
+    #[tool_types(nested)]
+    pub tangent: Vector3D,
+    /// Segment the sample lies on
+    pub segment: usize,
+    /// Curve parameter: segment index plus the position within the segment, 0-1
+    pub parameter: f64,
+    /// Arc length from the start of the curve
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct SplineResult {
+    pub spline_type: String,
+    pub closed: bool,
+    pub segment_count: usize,
+    /// Sampled points, from the start of the curve to its end
+    #[tool_types(nested)]
+    pub samples: Vec<SplineSample>,
+    /// Length of each segment
+    pub segment_lengths: Vec<f64>,
+    /// Total length of the curve
+    pub arc_length: f64,
+}
+
+type V = [f64; 3];
+
+fn add(a: V, b: V) -> V {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: V, b: V) -> V {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: V, s: f64) -> V {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn length(a: V) -> f64 {
+    (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt()
+}
+
+fn to_vector(v: V) -> Vector3D {
+    Vector3D {
+        x: v[0],
+        y: v[1],
+        z: v[2],
+    }
+}
+
+/// A cubic segment as polynomial coefficients: p(u) = c0 + c1·u + c2·u² + c3·u³
+struct Cubic([V; 4]);
+
+impl Cubic {
+    fn position(&self, u: f64) -> V {
+        let [c0, c1, c2, c3] = self.0;
+        add(c0, scale(add(c1, scale(add(c2, scale(c3, u)), u)), u))
+    }
+
+    fn derivative(&self, u: f64) -> V {
+        let [_, c1, c2, c3] = self.0;
+        add(c1, scale(add(scale(c2, 2.0), scale(c3, 3.0 * u)), u))
+    }
+
+    /// Arc length from 0 to `u` by composite Gauss-Legendre quadrature
+    fn length_to(&self, u: f64) -> f64 {
+        let step = u / QUADRATURE_INTERVALS as f64;
+        (0..QUADRATURE_INTERVALS)
+            .map(|i| {
+                let mid = step * (i as f64 + 0.5);
+                GAUSS
+                    .iter()
+                    .map(|(x, w)| w * length(self.derivative(mid + x * step / 2.0)))
+                    .sum::<f64>()
+                    * step
+                    / 2.0
+            })
+            .sum()
+    }
+
+    /// Cubic Hermite segment from p0 to p1 with end derivatives m0 and m1
+    fn hermite(p0: V, p1: V, m0: V, m1: V) -> Cubic {
+        let c2 = sub(sub(scale(sub(p1, p0), 3.0), scale(m0, 2.0)), m1);
+        let c3 = add(add(scale(sub(p0, p1), 2.0), m0), m1);
+        Cubic([p0, m0, c2, c3])
+    }
+
+    /// Uniform cubic B-spline segment controlled by four consecutive points
+    fn b_spline([p0, p1, p2, p3]: [V; 4]) -> Cubic {
+        let sixth = 1.0 / 6.0;
+        let c0 = scale(add(add(p0, scale(p1, 4.0)), p2), sixth);
+        let c1 = scale(sub(p2, p0), 0.5);
+        let c2 = scale(add(sub(p0, scale(p1, 2.0)), p2), 0.5);
+        let c3 = scale(add(sub(scale(sub(p1, p2), 3.0), p0), p3), sixth);
+        Cubic([c0, c1, c2, c3])
+    }
+}
+
+/// Catmull-Rom segment from p1 to p2 with knot spacing |Δp|^alpha, written in Hermite
+/// form (Yuksel, Schaefer & Keyser 2011)
+fn catmull_rom([p0, p1, p2, p3]: [V; 4], alpha: f64) -> Cubic {
+    let t01 = length(sub(p1, p0)).powf(alpha);
+    let t12 = length(sub(p2, p1)).powf(alpha);
+    let t23 = length(sub(p3, p2)).powf(alpha);
+    let m1 = scale(
+        add(
+            sub(
+                scale(sub(p1, p0), 1.0 / t01),
+                scale(sub(p2, p0), 1.0 / (t01 + t12)),
+            ),
+            scale(sub(p2, p1), 1.0 / t12),
+        ),
+        t12,
+    );
+    let m2 = scale(
+        add(
+            sub(
+                scale(sub(p2, p1), 1.0 / t12),
+                scale(sub(p3, p1), 1.0 / (t12 + t23)),
+            ),
+            scale(sub(p3, p2), 1.0 / t23),
+        ),
+        t12,
+    );
+    Cubic::hermite(p1, p2, m1, m2)
+}
+
+fn segments(points: &[V], catmull: bool, closed: bool, alpha: f64) -> Vec<Cubic> {
+    let n = points.len();
+    let window = |i: isize| -> [V; 4] {
+        std::array::from_fn(|k| {
+            let j = i + k as isize;
+            if closed {
+                points[j.rem_euclid(n as isize) as usize]
+            } else if catmull {
+                // Reflect the neighbouring point to get a phantom point past each end
+                match j {
+                    j if j < 0 => sub(scale(points[0], 2.0), points[1]),
+                    j if j as usize >= n => sub(scale(points[n - 1], 2.0), points[n - 2]),
+                    j => points[j as usize],
+                }
+            } else {
+                // Repeat each end point so the curve starts and ends on it
+                points[(j - 2).clamp(0, n as isize - 1) as usize]
+            }
+        })
+    };
+    let (first, count) = match (catmull, closed) {
+        (true, false) => (-1, n - 1),
+        (true, true) => (-1, n),
+        (false, false) => (0, n + 1),
+        (false, true) => (0, n),
+    };
+    (0..count)
+        .map(|i| {
+            let window = window(first + i as isize);
+            if catmull {
+                catmull_rom(window, alpha)
+            } else {
+                Cubic::b_spline(window)
+            }
+        })
+        .collect()
+}
+
+pub fn spline_logic(input: SplineInput) -> Result<SplineResult, String> {
+    let spline_type = input
+        .spline_type
+        .as_deref()
+        .map(|t| t.trim().to_lowercase())
+        .unwrap_or_else(|| "catmull_rom".to_string());
+    let catmull = match spline_type.as_str() {
+        "catmull_rom" => true,
+        "b_spline" => false,
+        other => {
+            return Err(format!(
+                "Unknown spline_type '{other}', use 'catmull_rom' or 'b_spline'"
+            ));
+        }
+    };
+    let closed = input.closed.unwrap_or(false);
+    let alpha = input.alpha.unwrap_or(0.5);
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err("alpha must be between 0 and 1".to_string());
+    }
+    if input.alpha.is_some() && !catmull {
+        return Err("alpha only applies to catmull_rom splines".to_string());
+    }
+    let samples_per_segment = input
+        .samples_per_segment
+        .unwrap_or(DEFAULT_SAMPLES_PER_SEGMENT);
+    if samples_per_segment == 0 {
+        return Err("samples_per_segment must be at least 1".to_string());
+    }
+
+    let points: Vec<V> = input
+        .control_points
+        .iter()
+        .map(|p| [p.x, p.y, p.z])
+        .collect();
+    let minimum = if closed { 3 } else { 2 };
+    if points.len() < minimum {
+        return Err(format!(
+            "A {} spline needs at least {minimum} control points",
+            if closed { "closed" } else { "open" }
+        ));
+    }
+    if points.len() > MAX_CONTROL_POINTS {
+        return Err(format!(
+            "At most {MAX_CONTROL_POINTS} control points are allowed"
+        ));
+    }
+    if points.iter().flatten().any(|c| !c.is_finite()) {
+        return Err("Control point coordinates must be finite".to_string());
+    }
+    if catmull && alpha > 0.0 {
+        let wrap = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        if (0..wrap).any(|i| points[i] == points[(i + 1) % points.len()]) {
+            return Err(
+                "Consecutive control points must differ for centripetal or chordal Catmull-Rom"
+                    .to_string(),
+            );
+        }
+    }
+
+    let segments = segments(&points, catmull, closed, alpha);
+    if segments.len() * samples_per_segment + 1 > MAX_SAMPLES {
+        return Err(format!(
+            "{} segments at {samples_per_segment} samples each exceeds {MAX_SAMPLES} samples",
+            segments.len()
+        ));
+    }
+
+    let mut samples = Vec::with_capacity(segments.len() * samples_per_segment + 1);
+    let mut segment_lengths = Vec::with_capacity(segments.len());
+    let mut start = 0.0;
+    for (index, segment) in segments.iter().enumerate() {
+        let last = index + 1 == segments.len();
+        let steps = if last {
+            samples_per_segment + 1
+        } else {
+            samples_per_segment
+        };
+        for k in 0..steps {
+            let u = k as f64 / samples_per_segment as f64;
+            let derivative = segment.derivative(u);
+            let speed = length(derivative);
+            samples.push(SplineSample {
+                position: to_vector(segment.position(u)),
+                tangent: to_vector(if speed > 1e-12 {
+                    scale(derivative, 1.0 / speed)
+                } else {
+                    [0.0; 3]
+                }),
+                segment: index,
+                parameter: index as f64 + u,
+                distance: start + segment.length_to(u),
+            });
+        }
+        let segment_length = segment.length_to(1.0);
+        segment_lengths.push(segment_length);
+        start += segment_length;
+    }
+
+    Ok(SplineResult {
+        spline_type,
+        closed,
+        segment_count: segments.len(),
+        samples,
+        segment_lengths,
+        arc_length: start,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn request(points: Vec<Vector3D>) -> SplineInput {
+        SplineInput {
+            control_points: points,
+            spline_type: None,
+            samples_per_segment: None,
+            closed: None,
+            alpha: None,
+        }
+    }
+
+    fn close(a: Vector3D, b: Vector3D) -> bool {
+        (a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9 && (a.z - b.z).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_catmull_rom_interpolates_control_points() {
+        let points = vec![
+            point(0.0, 0.0, 0.0),
+            point(1.0, 2.0, 0.0),
+            point(3.0, 1.0, 1.0),
+            point(4.0, 3.0, -1.0),
+        ];
+        let mut input = request(points.clone());
+        input.samples_per_segment = Some(4);
+        let result = spline_logic(input).unwrap();
+        assert_eq!(result.segment_count, 3);
+        assert_eq!(result.samples.len(), 13);
+        for (i, p) in points.iter().enumerate() {
+            assert!(close(result.samples[i * 4].position, *p));
+        }
+        let distances: Vec<f64> = result.samples.iter().map(|s| s.distance).collect();
+        assert!(distances.windows(2).all(|w| w[1] > w[0]));
+        assert!((distances[12] - result.arc_length).abs() < 1e-12);
+        // The curve is longer than the polyline through its control points
+        assert!(result.arc_length > 5f64.sqrt() + 6f64.sqrt() + 3.0);
+    }
+
+    #[test]
+    fn test_uniform_catmull_rom_midpoint() {
+        let points = vec![
+            point(0.0, 0.0, 0.0),
+            point(1.0, 1.0, 0.0),
+            point(2.0, 0.0, 2.0),
+            point(3.0, 4.0, 1.0),
+        ];
+        let mut input = request(points);
+        input.alpha = Some(0.0);
+        input.samples_per_segment = Some(2);
+        let result = spline_logic(input).unwrap();
+        // Uniform Catmull-Rom at u = 0.5: (-p0 + 9 p1 + 9 p2 - p3) / 16
+        let expected = point(1.5, 0.3125, 1.0625);
+        assert!(close(result.samples[3].position, expected));
+    }
+
+    #[test]
+    fn test_straight_line_length_and_tangents() {
+        let points: Vec<Vector3D> = (0..5).map(|i| point(i as f64, 0.0, 0.0)).collect();
+        for spline_type in ["catmull_rom", "b_spline"] {
+            let mut input = request(points.clone());
+            input.spline_type = Some(spline_type.to_string());
+            let result = spline_logic(input).unwrap();
+            assert!((result.arc_length - 4.0).abs() < 1e-9, "{spline_type}");
+            assert!(close(result.samples[0].position, point(0.0, 0.0, 0.0)));
+            assert!(close(
+                result.samples.last().unwrap().position,
+                point(4.0, 0.0, 0.0)
+            ));
+            let inner = &result.samples[1..result.samples.len() - 1];
+            assert!(inner.iter().all(|s| close(s.tangent, point(1.0, 0.0, 0.0))));
+        }
+    }
+
+    #[test]
+    fn test_closed_b_spline_square() {
+        let mut input = request(vec![
+            point(1.0, 1.0, 0.0),
+            point(-1.0, 1.0, 0.0),
+            point(-1.0, -1.0, 0.0),
+            point(1.0, -1.0, 0.0),
+        ]);
+        input.spline_type = Some("b_spline".to_string());
+        input.closed = Some(true);
+        input.samples_per_segment = Some(8);
+        let result = spline_logic(input).unwrap();
+        assert_eq!(result.segment_count, 4);
+        // The loop closes on itself and every segment has the same length
+        let first = result.samples[0].position;
+        assert!(close(result.samples.last().unwrap().position, first));
+        assert!(
+            result
+                .segment_lengths
+                .iter()
+                .all(|l| (l - result.segment_lengths[0]).abs() < 1e-9)
+        );
+        // Each segment starts at (p0 + 4 p1 + p2) / 6, inside the control polygon
+        assert!(close(first, point(-2.0 / 3.0, 2.0 / 3.0, 0.0)));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(spline_logic(request(vec![point(0.0, 0.0, 0.0)])).is_err());
+        let mut input = request(vec![point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0)]);
+        input.spline_type = Some("bezier".to_string());
+        assert!(
+            spline_logic(input)
+                .unwrap_err()
+                .contains("Unknown spline_type")
+        );
+        let mut input = request(vec![point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0)]);
+        input.closed = Some(true);
+        assert_eq!(
+            spline_logic(input).unwrap_err(),
+            "A closed spline needs at least 3 control points"
+        );
+        let input = request(vec![
+            point(0.0, 0.0, 0.0),
+            point(0.0, 0.0, 0.0),
+            point(1.0, 0.0, 0.0),
+        ]);
+        assert!(spline_logic(input).unwrap_err().contains("Consecutive"));
+        let mut input = request(vec![point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0)]);
+        input.samples_per_segment = Some(MAX_SAMPLES);
+        assert!(spline_logic(input).is_err());
+    }
+}
@@ -1,90 +1,75 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    TetrahedronVolumeInput as LogicInput, TetrahedronVolumeResponse as LogicOutput,
+    Vector3D as LogicVector3D, compute_tetrahedron_volume,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    TetrahedronVolumeResponseSchema as TetrahedronVolumeResponse, Vector3DSchema as Vector3D,
+};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options that only affect how the result is serialized
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TetrahedronVolumeInput {
-    pub point_a: Vector3D,
-    pub point_b: Vector3D,
-    pub point_c: Vector3D,
-    pub point_d: Vector3D,
+    #[serde(flatten)]
+    pub params: logic::TetrahedronVolumeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Serialize, JsonSchema)]
-pub struct TetrahedronVolumeResponse {
-    pub volume: f64,
-    pub calculation_method: String,
-    pub points: [Vector3D; 4],
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn tetrahedron_volume(request: Request<TetrahedronVolumeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "tetrahedron_volume",
+                TetrahedronVolumeInput,
+                TetrahedronVolumeResponse
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("tetrahedron_volume", &input);
+    log.finish(run_tetrahedron_volume(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn tetrahedron_volume(input: TetrahedronVolumeInput) -> ToolResponse {
-    // Convert API types to logic types
-    let logic_input = logic::TetrahedronVolumeInput {
-        point_a: logic::Vector3D {
-            x: input.point_a.x,
-            y: input.point_a.y,
-            z: input.point_a.z,
-        },
-        point_b: logic::Vector3D {
-            x: input.point_b.x,
-            y: input.point_b.y,
-            z: input.point_b.z,
-        },
-        point_c: logic::Vector3D {
-            x: input.point_c.x,
-            y: input.point_c.y,
-            z: input.point_c.z,
-        },
-        point_d: logic::Vector3D {
-            x: input.point_d.x,
-            y: input.point_d.y,
-            z: input.point_d.z,
-        },
+#[cfg(feature = "individual")]
+fn run_tetrahedron_volume(input: TetrahedronVolumeInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
-    // Call business logic
-    match logic::compute_tetrahedron_volume(logic_input) {
+    match logic::compute_tetrahedron_volume(input.params.into()) {
         Ok(logic_result) => {
-            // Convert logic types back to API types
-            let result = TetrahedronVolumeResponse {
-                volume: logic_result.volume,
-                calculation_method: logic_result.calculation_method,
-                points: [
-                    Vector3D {
-                        x: logic_result.points[0].x,
-                        y: logic_result.points[0].y,
-                        z: logic_result.points[0].z,
-                    },
-                    Vector3D {
-                        x: logic_result.points[1].x,
-                        y: logic_result.points[1].y,
-                        z: logic_result.points[1].z,
-                    },
-                    Vector3D {
-                        x: logic_result.points[2].x,
-                        y: logic_result.points[2].y,
-                        z: logic_result.points[2].z,
-                    },
-                    Vector3D {
-                        x: logic_result.points[3].x,
-                        y: logic_result.points[3].y,
-                        z: logic_result.points[3].z,
-                    },
-                ],
-            };
-            ToolResponse::text(serde_json::to_string(&result).unwrap())
+            let result = TetrahedronVolumeResponse::from(logic_result);
+            match ftl_response::to_json_with_precision(&result, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
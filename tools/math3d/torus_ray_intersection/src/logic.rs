@@ -0,0 +1,375 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Ray {
+    #[tool_types(nested)]
+    pub origin: Vector3D,
+    /// Direction of travel, normalized before use
+    #[tool_types(nested)]
+    pub direction: Vector3D,
+}
+
+/// Ring torus: a circle of minor_radius swept around the axis at major_radius from the
+/// center
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Torus {
+    #[tool_types(nested)]
+    pub center: Vector3D,
+    /// Axis of symmetry, through the hole; normalized before use
+    #[tool_types(nested)]
+    pub axis: Vector3D,
+    /// Distance from the center to the middle of the tube
+    pub major_radius: f64,
+    /// Radius of the tube, less than major_radius
+    pub minor_radius: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TorusRayInput {
+    #[tool_types(nested)]
+    pub torus: Torus,
+    #[tool_types(nested)]
+    pub ray: Ray,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct IntersectionPoint {
+    #[tool_types(nested)]
+    pub point: Vector3D,
+    /// Distance along the normalized ray direction
+    pub distance: f64,
+    /// Outward unit surface normal at the point
+    #[tool_types(nested)]
+    pub normal: Vector3D,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TorusRayResult {
+    pub intersects: bool,
+    /// Up to four surface crossings in front of the origin, nearest first; a tangent
+    /// contact is listed once
+    #[tool_types(nested)]
+    pub intersection_points: Vec<IntersectionPoint>,
+    pub closest_distance: Option<f64>,
+}
+
+impl Vector3D {
+    fn add(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+
+    fn subtract(&self, other: &Vector3D) -> Vector3D {
+        Vector3D {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+
+    fn scale(&self, scalar: f64) -> Vector3D {
+        Vector3D {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+        }
+    }
+
+    fn dot(&self, other: &Vector3D) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+/// Polynomial value, coefficients lowest power first
+fn evaluate(coefficients: &[f64], t: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * t + c)
+}
+
+/// Real roots in [lo, hi], ascending. The derivative's roots split the interval into
+/// monotonic pieces, each holding at most one root, found by bisection; a touching
+/// (double) root shows up as a turning point where the value vanishes.
+fn real_roots(coefficients: &[f64], lo: f64, hi: f64) -> Vec<f64> {
+    let degree = coefficients.len() - 1;
+    if degree == 1 {
+        let root = -coefficients[0] / coefficients[1];
+        return if (lo..=hi).contains(&root) {
+            vec![root]
+        } else {
+            Vec::new()
+        };
+    }
+    let derivative: Vec<f64> = coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(power, c)| c * power as f64)
+        .collect();
+    let span = lo.abs().max(hi.abs()).max(1.0);
+    // Below this the value is rounding noise in the largest term
+    let tolerance = 1e-12
+        * coefficients
+            .iter()
+            .enumerate()
+            .map(|(power, c)| c.abs() * span.powi(power as i32))
+            .sum::<f64>();
+
+    let mut breaks = vec![lo];
+    breaks.extend(real_roots(&derivative, lo, hi));
+    breaks.push(hi);
+    let mut roots = Vec::new();
+    for pair in breaks.windows(2) {
+        let (mut a, mut b) = (pair[0], pair[1]);
+        let (fa, fb) = (evaluate(coefficients, a), evaluate(coefficients, b));
+        if fa.abs() <= tolerance {
+            roots.push(a);
+        } else if fb.abs() > tolerance && fa.signum() != fb.signum() {
+            for _ in 0..200 {
+                let middle = 0.5 * (a + b);
+                if middle <= a || middle >= b {
+                    break;
+                }
+                if evaluate(coefficients, middle).signum() == fa.signum() {
+                    a = middle;
+                } else {
+                    b = middle;
+                }
+            }
+            roots.push(0.5 * (a + b));
+        }
+    }
+    if evaluate(coefficients, hi).abs() <= tolerance {
+        roots.push(hi);
+    }
+    roots.dedup_by(|b, a| (*b - *a).abs() <= 1e-9 * span);
+    roots
+}
+
+pub fn torus_ray_intersection_logic(input: TorusRayInput) -> Result<TorusRayResult, String> {
+    let torus = input.torus;
+    let ray = input.ray;
+
+    if !torus.center.is_finite() {
+        return Err("Torus center coordinates must be finite".to_string());
+    }
+    if !torus.axis.is_finite() || torus.axis.magnitude() == 0.0 {
+        return Err("Torus axis must be a finite, non-zero vector".to_string());
+    }
+    let (major, minor) = (torus.major_radius, torus.minor_radius);
+    if !(major.is_finite() && minor.is_finite() && minor > 0.0) {
+        return Err("Torus radii must be positive and finite".to_string());
+    }
+    if minor >= major {
+        return Err("Torus minor_radius must be less than major_radius".to_string());
+    }
+    if !ray.origin.is_finite() {
+        return Err("Ray origin coordinates must be finite".to_string());
+    }
+    if !ray.direction.is_finite() {
+        return Err("Ray direction coordinates must be finite".to_string());
+    }
+    let speed = ray.direction.magnitude();
+    if speed == 0.0 {
+        return Err("Ray direction cannot be zero vector".to_string());
+    }
+
+    let axis = torus.axis.scale(1.0 / torus.axis.magnitude());
+    let direction = ray.direction.scale(1.0 / speed);
+    // Work relative to the center, so the torus is
+    // (|p|^2 + R^2 - r^2)^2 = 4 R^2 (|p|^2 - (p.axis)^2)
+    let offset = ray.origin.subtract(&torus.center);
+    let miss = TorusRayResult {
+        intersects: false,
+        intersection_points: vec![],
+        closest_distance: None,
+    };
+
+    // Only the stretch of ray inside the bounding sphere can meet the torus
+    let b = offset.dot(&direction);
+    let bound = major + minor;
+    let discriminant = b * b - (offset.dot(&offset) - bound * bound);
+    if discriminant < 0.0 {
+        return Ok(miss);
+    }
+    let half_chord = discriminant.sqrt();
+    // Slack so a contact on the bounding sphere itself stays inside the interval
+    let slack = 1e-9 * (bound + offset.magnitude());
+    let (lo, hi) = ((-b - half_chord - slack).max(0.0), -b + half_chord + slack);
+    if hi < 0.0 {
+        return Ok(miss);
+    }
+
+    let (d_axis, o_axis) = (direction.dot(&axis), offset.dot(&axis));
+    let k = offset.dot(&offset) + major * major - minor * minor;
+    let four_r2 = 4.0 * major * major;
+    let coefficients = [
+        k * k - four_r2 * (offset.dot(&offset) - o_axis * o_axis),
+        4.0 * b * k - 2.0 * four_r2 * (b - o_axis * d_axis),
+        4.0 * b * b + 2.0 * k - four_r2 * (1.0 - d_axis * d_axis),
+        4.0 * b,
+        1.0,
+    ];
+
+    let intersection_points: Vec<IntersectionPoint> = real_roots(&coefficients, lo, hi)
+        .into_iter()
+        .map(|t| {
+            let local = offset.add(&direction.scale(t));
+            // Outward from the nearest point of the tube's center circle
+            let radial = local.subtract(&axis.scale(local.dot(&axis)));
+            let ring = radial.scale(major / radial.magnitude());
+            let outward = local.subtract(&ring);
+            IntersectionPoint {
+                point: torus.center.add(&local),
+                distance: t,
+                normal: outward.scale(1.0 / outward.magnitude()),
+            }
+        })
+        .collect();
+
+    Ok(TorusRayResult {
+        intersects: !intersection_points.is_empty(),
+        closest_distance: intersection_points.first().map(|p| p.distance),
+        intersection_points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-8;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    /// Torus about the z axis with major radius 3 and minor radius 1
+    fn input(origin: Vector3D, direction: Vector3D) -> TorusRayInput {
+        TorusRayInput {
+            torus: Torus {
+                center: v(0.0, 0.0, 0.0),
+                axis: v(0.0, 0.0, 1.0),
+                major_radius: 3.0,
+                minor_radius: 1.0,
+            },
+            ray: Ray { origin, direction },
+        }
+    }
+
+    fn distances(result: &TorusRayResult) -> Vec<f64> {
+        result
+            .intersection_points
+            .iter()
+            .map(|p| p.distance)
+            .collect()
+    }
+
+    fn assert_all_close(actual: Vec<f64>, expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < EPSILON, "{actual:?} != {expected:?}");
+        }
+    }
+
+    fn assert_close(a: Vector3D, b: Vector3D) {
+        assert!(a.subtract(&b).magnitude() < EPSILON, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_four_hits_through_ring() {
+        let result =
+            torus_ray_intersection_logic(input(v(-10.0, 0.0, 0.0), v(2.0, 0.0, 0.0))).unwrap();
+        assert!(result.intersects);
+        assert_all_close(distances(&result), &[6.0, 8.0, 12.0, 14.0]);
+        let points = &result.intersection_points;
+        assert_close(points[0].point, v(-4.0, 0.0, 0.0));
+        assert_close(points[0].normal, v(-1.0, 0.0, 0.0));
+        // Inner wall of the tube faces the hole
+        assert_close(points[1].normal, v(1.0, 0.0, 0.0));
+        assert_eq!(result.closest_distance, Some(points[0].distance));
+    }
+
+    #[test]
+    fn test_through_hole_misses() {
+        let result =
+            torus_ray_intersection_logic(input(v(0.0, 0.0, 10.0), v(0.0, 0.0, -1.0))).unwrap();
+        assert!(!result.intersects);
+        assert!(result.closest_distance.is_none());
+        let far = torus_ray_intersection_logic(input(v(0.0, 10.0, 0.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert!(!far.intersects);
+    }
+
+    #[test]
+    fn test_through_tube_along_axis() {
+        let result =
+            torus_ray_intersection_logic(input(v(3.0, 0.0, 10.0), v(0.0, 0.0, -1.0))).unwrap();
+        assert_all_close(distances(&result), &[9.0, 11.0]);
+        assert_close(result.intersection_points[0].normal, v(0.0, 0.0, 1.0));
+        assert_close(result.intersection_points[1].normal, v(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_tangent_to_top() {
+        // Grazes the top of the tube on both sides of the hole
+        let result =
+            torus_ray_intersection_logic(input(v(-10.0, 0.0, 1.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert_all_close(distances(&result), &[7.0, 13.0]);
+        assert_close(result.intersection_points[0].normal, v(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_origin_inside_and_tilted_axis() {
+        let inside =
+            torus_ray_intersection_logic(input(v(3.0, 0.0, 0.0), v(1.0, 0.0, 0.0))).unwrap();
+        assert_all_close(distances(&inside), &[1.0]);
+
+        // Torus standing upright around the y axis, centered at (1, 1, 1)
+        let mut request = input(v(1.0, -5.0, 4.0), v(0.0, 1.0, 0.0));
+        request.torus.center = v(1.0, 1.0, 1.0);
+        request.torus.axis = v(0.0, -2.0, 0.0);
+        let result = torus_ray_intersection_logic(request).unwrap();
+        assert_all_close(distances(&result), &[5.0, 7.0]);
+        assert_close(result.intersection_points[0].point, v(1.0, 0.0, 4.0));
+        assert_close(result.intersection_points[0].normal, v(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut request = input(v(0.0, 0.0, 0.0), v(0.0, 0.0, 0.0));
+        assert_eq!(
+            torus_ray_intersection_logic(request.clone()).unwrap_err(),
+            "Ray direction cannot be zero vector"
+        );
+        request.ray.direction = v(1.0, 0.0, 0.0);
+        request.torus.minor_radius = 3.0;
+        assert_eq!(
+            torus_ray_intersection_logic(request.clone()).unwrap_err(),
+            "Torus minor_radius must be less than major_radius"
+        );
+        request.torus.minor_radius = 1.0;
+        request.torus.axis = v(0.0, 0.0, 0.0);
+        assert!(torus_ray_intersection_logic(request).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+use ftl_units::AngleUnit;
+#[cfg(feature = "individual")]
+use ftl_units::AngleUnitResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+// Re-export types from logic module
+pub use logic::{
+    TransformPointsInput as LogicInput, TransformPointsResponse as LogicOutput, apply_transform,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    QuaternionSchema as Quaternion, RotationSchema as Rotation,
+    TransformPointsResponseSchema as TransformPointsResponse, Vector3DSchema as Vector3D,
+};
+
+// Shared matrix type, which derives JsonSchema itself
+pub use ftl_matrix4::Matrix4;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TransformPointsInput {
+    #[serde(flatten)]
+    pub params: logic::TransformPointsInputSchema,
+    /// Only check the points and transform and return a validation report, without
+    /// transforming
+    pub validate_only: Option<bool>,
+    /// Unit of the rotation angle: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Apply a transform, given as a 4x4 matrix or as translation, rotation and scale, to a
+/// batch of points, returning the transformed points and the matrix applied
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn transform_points(request: Request<TransformPointsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "transform_points",
+                TransformPointsInput,
+                AngleUnitResponse<TransformPointsResponse>
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("transform_points", &input);
+    log.finish(run_transform_points(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_transform_points(input: TransformPointsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("points", &input.params.points)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    let mut params: logic::TransformPointsInput = input.params.into();
+    if let Some(rotation) = params.rotation.as_mut() {
+        rotation.angle = rotation.angle.map(|angle| angle_unit.to_radians(angle));
+    }
+
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_transform_input(&params);
+        return ToolResponse::text(
+            ftl_json::to_string(&report).unwrap_or_else(|_| "Error serializing result".to_string()),
+        );
+    }
+
+    match logic::apply_transform(params) {
+        Ok(logic_result) => {
+            let response = angle_unit.response(TransformPointsResponse::from(logic_result));
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,343 @@
+use ftl_matrix4::{Matrix4, axis_angle_rotation, quaternion_rotation};
+use ftl_preflight::ValidationReport;
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most points transformed in one call
+pub const MAX_POINTS: usize = 100_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Quaternion x·i + y·j + z·k + w
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Quaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+/// Rotation as a quaternion, or as an axis and angle
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Rotation {
+    /// Rotation as a quaternion, normalized before use
+    #[tool_types(nested)]
+    pub quaternion: Option<Quaternion>,
+    /// Rotation axis, normalized before use; give it with angle instead of quaternion
+    #[tool_types(nested)]
+    pub axis: Option<Vector3D>,
+    /// Rotation angle in radians about axis, counterclockwise looking down the axis
+    pub angle: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TransformPointsInput {
+    /// Points to transform
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Transform as a 4x4 matrix acting on column vectors; give either this or the
+    /// translation, rotation and scale components
+    pub matrix: Option<Matrix4>,
+    /// Translation, applied last
+    #[tool_types(nested)]
+    pub translation: Option<Vector3D>,
+    /// Rotation, applied after the scale
+    #[tool_types(nested)]
+    pub rotation: Option<Rotation>,
+    /// Scale along each axis, applied first
+    #[tool_types(nested)]
+    pub scale: Option<Vector3D>,
+    /// Apply the inverse of the transform, to map points back into the source frame
+    /// (default: false)
+    pub inverse: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TransformPointsResponse {
+    /// Transformed points, in input order
+    #[tool_types(nested)]
+    pub points: Vec<Vector3D>,
+    /// Matrix that was applied, composed as translation · rotation · scale and inverted
+    /// if requested
+    pub matrix: Matrix4,
+    /// Number of points transformed
+    pub count: usize,
+}
+
+fn components(v: &Vector3D) -> [f64; 3] {
+    [v.x, v.y, v.z]
+}
+
+fn rotation_matrix(rotation: &Rotation) -> Result<[[f64; 3]; 3], String> {
+    match (&rotation.quaternion, &rotation.axis, rotation.angle) {
+        (Some(q), None, None) => quaternion_rotation([q.x, q.y, q.z, q.w]),
+        (None, Some(axis), Some(angle)) => axis_angle_rotation(components(axis), angle),
+        _ => Err("rotation needs either quaternion, or axis and angle".to_string()),
+    }
+}
+
+/// The matrix to apply, from `matrix` or from the components
+fn build_matrix(input: &TransformPointsInput) -> Result<Matrix4, String> {
+    let has_components =
+        input.translation.is_some() || input.rotation.is_some() || input.scale.is_some();
+    match (&input.matrix, has_components) {
+        (Some(_), true) => {
+            Err("Give either matrix or translation/rotation/scale, not both".to_string())
+        }
+        (None, false) => {
+            Err("Give a matrix or at least one of translation, rotation and scale".to_string())
+        }
+        (Some(matrix), false) => {
+            if !matrix.is_finite() {
+                return Err("Matrix contains NaN or infinite values".to_string());
+            }
+            Ok(*matrix)
+        }
+        (None, true) => {
+            let translation = input.translation.as_ref().map_or([0.0; 3], components);
+            let scale = input.scale.as_ref().map_or([1.0; 3], components);
+            if !translation.iter().chain(&scale).all(|v| v.is_finite()) {
+                return Err("Translation and scale must be finite".to_string());
+            }
+            let rotation = match &input.rotation {
+                Some(rotation) => rotation_matrix(rotation)?,
+                None => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            };
+            Ok(Matrix4::compose(translation, rotation, scale))
+        }
+    }
+}
+
+/// Check the points and transform without applying it
+pub fn validate_transform_input(input: &TransformPointsInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    if input.points.len() > MAX_POINTS {
+        report.error(
+            "points",
+            format!("At most {MAX_POINTS} points can be transformed at once"),
+        );
+    }
+    if let Some(index) = input
+        .points
+        .iter()
+        .position(|p| !components(p).iter().all(|v| v.is_finite()))
+    {
+        report.error(
+            format!("points[{index}]"),
+            format!("Point {index} contains NaN or infinite values"),
+        );
+    }
+
+    match build_matrix(input) {
+        Ok(matrix) => {
+            if input.inverse.unwrap_or(false)
+                && let Err(e) = matrix.inverse()
+            {
+                report.error("inverse", e);
+            }
+        }
+        Err(e) => report.error("matrix", e),
+    }
+
+    report
+}
+
+pub fn apply_transform(input: TransformPointsInput) -> Result<TransformPointsResponse, String> {
+    validate_transform_input(&input).into_result()?;
+
+    let mut matrix = build_matrix(&input)?;
+    if input.inverse.unwrap_or(false) {
+        matrix = matrix.inverse()?;
+    }
+
+    let points = input
+        .points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            let [x, y, z] = matrix
+                .transform_point(components(point))
+                .map_err(|e| format!("Point {index}: {e}"))?;
+            Ok(Vector3D { x, y, z })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(TransformPointsResponse {
+        count: points.len(),
+        points,
+        matrix,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn point(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn input(points: Vec<Vector3D>) -> TransformPointsInput {
+        TransformPointsInput {
+            points,
+            matrix: None,
+            translation: None,
+            rotation: None,
+            scale: None,
+            inverse: None,
+        }
+    }
+
+    fn assert_points(actual: &[Vector3D], expected: &[[f64; 3]]) {
+        assert_eq!(actual.len(), expected.len());
+        for (p, e) in actual.iter().zip(expected) {
+            for (value, expected) in components(p).iter().zip(e) {
+                assert!(
+                    (value - expected).abs() < 1e-12,
+                    "{actual:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    fn quarter_turn_z() -> Rotation {
+        Rotation {
+            quaternion: None,
+            axis: Some(point(0.0, 0.0, 1.0)),
+            angle: Some(FRAC_PI_2),
+        }
+    }
+
+    #[test]
+    fn test_components_apply_scale_rotation_translation() {
+        let mut request = input(vec![point(1.0, 0.0, 0.0), point(0.0, 1.0, 1.0)]);
+        request.scale = Some(point(2.0, 2.0, 2.0));
+        request.rotation = Some(quarter_turn_z());
+        request.translation = Some(point(10.0, 0.0, 0.0));
+        let result = apply_transform(request).unwrap();
+        assert_eq!(result.count, 2);
+        assert_points(&result.points, &[[10.0, 2.0, 0.0], [8.0, 0.0, 2.0]]);
+        // Translation sits in the last column
+        assert_eq!(
+            [
+                result.matrix.0[0][3],
+                result.matrix.0[1][3],
+                result.matrix.0[2][3]
+            ],
+            [10.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_quaternion_rotation() {
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let mut request = input(vec![point(1.0, 0.0, 0.0)]);
+        request.rotation = Some(Rotation {
+            quaternion: Some(Quaternion {
+                x: 0.0,
+                y: 0.0,
+                z: 2.0 * half,
+                w: 2.0 * half,
+            }),
+            axis: None,
+            angle: None,
+        });
+        let result = apply_transform(request).unwrap();
+        assert_points(&result.points, &[[0.0, 1.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_matrix_and_inverse() {
+        let matrix = Matrix4::compose(
+            [1.0, 2.0, 3.0],
+            [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+            [1.0, 1.0, 1.0],
+        );
+        let mut request = input(vec![point(1.0, 1.0, 1.0)]);
+        request.matrix = Some(matrix);
+        let forward = apply_transform(request.clone()).unwrap();
+        assert_points(&forward.points, &[[0.0, 3.0, 4.0]]);
+
+        request.points = forward.points;
+        request.inverse = Some(true);
+        let back = apply_transform(request).unwrap();
+        assert_points(&back.points, &[[1.0, 1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_projective_matrix() {
+        let mut matrix = Matrix4::IDENTITY;
+        matrix.0[3] = [0.0, 0.0, 1.0, 0.0];
+        let mut request = input(vec![point(2.0, 4.0, 2.0), point(1.0, 1.0, 0.0)]);
+        request.matrix = Some(matrix);
+        assert_eq!(
+            apply_transform(request).unwrap_err(),
+            "Point 1: Point maps to infinity (w = 0)"
+        );
+    }
+
+    #[test]
+    fn test_empty_points() {
+        let mut request = input(vec![]);
+        request.scale = Some(point(2.0, 2.0, 2.0));
+        let result = apply_transform(request).unwrap();
+        assert_eq!(result.count, 0);
+        assert_eq!(result.matrix, Matrix4::from_scale([2.0, 2.0, 2.0]));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = apply_transform(input(vec![point(1.0, 2.0, 3.0)])).unwrap_err();
+        assert!(err.starts_with("Give a matrix or at least one"));
+
+        let mut both = input(vec![point(1.0, 2.0, 3.0)]);
+        both.matrix = Some(Matrix4::IDENTITY);
+        both.scale = Some(point(1.0, 1.0, 1.0));
+        assert!(apply_transform(both).unwrap_err().contains("not both"));
+
+        let mut nan = input(vec![point(0.0, 0.0, 0.0), point(f64::NAN, 0.0, 0.0)]);
+        nan.translation = Some(point(1.0, 0.0, 0.0));
+        assert_eq!(
+            apply_transform(nan).unwrap_err(),
+            "Point 1 contains NaN or infinite values"
+        );
+
+        let mut partial = input(vec![point(1.0, 2.0, 3.0)]);
+        partial.rotation = Some(Rotation {
+            quaternion: None,
+            axis: Some(point(0.0, 0.0, 1.0)),
+            angle: None,
+        });
+        assert!(
+            apply_transform(partial)
+                .unwrap_err()
+                .contains("axis and angle")
+        );
+
+        let mut singular = input(vec![point(1.0, 2.0, 3.0)]);
+        singular.scale = Some(point(1.0, 0.0, 1.0));
+        singular.inverse = Some(true);
+        assert!(apply_transform(singular).unwrap_err().contains("singular"));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let mut request = input(vec![point(f64::NAN, 0.0, 0.0)]);
+        request.scale = Some(point(1.0, 0.0, 1.0));
+        request.inverse = Some(true);
+        let report = validate_transform_input(&request);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["points[0]", "inverse"]);
+
+        request.points[0].x = 1.0;
+        request.scale = Some(point(2.0, 2.0, 2.0));
+        assert!(validate_transform_input(&request).valid);
+    }
+}
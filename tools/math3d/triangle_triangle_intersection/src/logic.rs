@@ -0,0 +1,472 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Default tolerance, relative to the longest triangle edge
+const RELATIVE_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Vector3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TriangleIntersectionInput {
+    /// First triangle, as three vertices
+    #[tool_types(nested)]
+    pub triangle1: [Vector3D; 3],
+    /// Second triangle, as three vertices
+    #[tool_types(nested)]
+    pub triangle2: [Vector3D; 3],
+    /// Distance below which points count as touching (default: 1e-9 times the longest
+    /// triangle edge)
+    pub tolerance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct Segment {
+    #[tool_types(nested)]
+    pub start: Vector3D,
+    #[tool_types(nested)]
+    pub end: Vector3D,
+    pub length: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TriangleIntersectionResult {
+    /// Whether the triangles touch or overlap
+    pub intersects: bool,
+    /// Whether the triangles lie in the same plane
+    pub coplanar: bool,
+    /// Intersection when it is a segment, or a single point with start equal to end
+    #[tool_types(nested)]
+    pub segment: Option<Segment>,
+    /// Overlap region of coplanar triangles that share an area, as a convex polygon
+    #[tool_types(nested)]
+    pub overlap_polygon: Option<Vec<Vector3D>>,
+    /// Area of the overlap region
+    pub overlap_area: Option<f64>,
+}
+
+type V = [f64; 3];
+
+fn sub(a: V, b: V) -> V {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add(a: V, b: V) -> V {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: V, s: f64) -> V {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: V, b: V) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: V, b: V) -> V {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: V) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn to_array(v: &Vector3D) -> V {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector(v: V) -> Vector3D {
+    Vector3D {
+        x: v[0],
+        y: v[1],
+        z: v[2],
+    }
+}
+
+fn longest_edge(t: &[V; 3]) -> f64 {
+    (0..3)
+        .map(|i| norm(sub(t[(i + 1) % 3], t[i])))
+        .fold(0.0, f64::max)
+}
+
+/// Unit normal of a triangle, or an error if its vertices are collinear
+fn unit_normal(t: &[V; 3], tolerance: f64, name: &str) -> Result<V, String> {
+    let n = cross(sub(t[1], t[0]), sub(t[2], t[0]));
+    let length = norm(n);
+    // Twice the area over the longest edge is the smallest height of the triangle
+    if length <= tolerance * longest_edge(t) || length == 0.0 {
+        return Err(format!("{name} is degenerate (its vertices are collinear)"));
+    }
+    Ok(scale(n, 1.0 / length))
+}
+
+/// Signed distances of the vertices to a plane, snapped to zero within tolerance
+fn plane_distances(t: &[V; 3], normal: V, origin: V, tolerance: f64) -> [f64; 3] {
+    t.map(|p| {
+        let d = dot(normal, sub(p, origin));
+        if d.abs() <= tolerance { 0.0 } else { d }
+    })
+}
+
+fn same_side(d: &[f64; 3]) -> bool {
+    d.iter().all(|&v| v > 0.0) || d.iter().all(|&v| v < 0.0)
+}
+
+/// Points where a triangle meets a plane it straddles, given its vertex distances
+fn plane_crossing(t: &[V; 3], d: &[f64; 3]) -> Vec<V> {
+    let mut points = Vec::new();
+    for (i, (&p, &dp)) in t.iter().zip(d).enumerate() {
+        let (q, dq) = (t[(i + 1) % 3], d[(i + 1) % 3]);
+        if dp == 0.0 {
+            points.push(p);
+        }
+        if dp * dq < 0.0 {
+            points.push(add(p, scale(sub(q, p), dp / (dp - dq))));
+        }
+    }
+    points
+}
+
+/// Extent of points along a direction, as (min param, min point, max param, max point)
+fn interval(points: &[V], direction: V) -> (f64, V, f64, V) {
+    let mut lo = (f64::INFINITY, points[0]);
+    let mut hi = (f64::NEG_INFINITY, points[0]);
+    for &p in points {
+        let t = dot(p, direction);
+        if t < lo.0 {
+            lo = (t, p);
+        }
+        if t > hi.0 {
+            hi = (t, p);
+        }
+    }
+    (lo.0, lo.1, hi.0, hi.1)
+}
+
+fn segment(start: V, end: V) -> Segment {
+    Segment {
+        start: to_vector(start),
+        end: to_vector(end),
+        length: norm(sub(end, start)),
+    }
+}
+
+/// Clip `polygon` to the inside of triangle `t`, which lies in the same plane with unit
+/// normal `normal` (Sutherland-Hodgman)
+fn clip_to_triangle(mut polygon: Vec<V>, t: &[V; 3], normal: V, tolerance: f64) -> Vec<V> {
+    for (i, &a) in t.iter().enumerate() {
+        if polygon.is_empty() {
+            break;
+        }
+        let b = t[(i + 1) % 3];
+        // Points into the triangle, since the normal follows the vertex order
+        let inward = cross(normal, sub(b, a));
+        let inward = scale(inward, 1.0 / norm(inward));
+        let side = |p: V| dot(inward, sub(p, a));
+
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for (k, &current) in polygon.iter().enumerate() {
+            let next = polygon[(k + 1) % polygon.len()];
+            let (f_current, f_next) = (side(current), side(next));
+            let (current_in, next_in) = (f_current >= -tolerance, f_next >= -tolerance);
+            if current_in {
+                clipped.push(current);
+            }
+            if current_in != next_in {
+                let s = f_current / (f_current - f_next);
+                clipped.push(add(current, scale(sub(next, current), s)));
+            }
+        }
+        polygon = clipped;
+    }
+    dedup_points(polygon, tolerance)
+}
+
+/// Drop consecutive points, including the last and first, closer than the tolerance
+fn dedup_points(points: Vec<V>, tolerance: f64) -> Vec<V> {
+    let mut unique: Vec<V> = Vec::with_capacity(points.len());
+    for p in points {
+        if unique
+            .last()
+            .is_none_or(|&last| norm(sub(p, last)) > tolerance)
+        {
+            unique.push(p);
+        }
+    }
+    while unique.len() > 1 && norm(sub(unique[0], unique[unique.len() - 1])) <= tolerance {
+        unique.pop();
+    }
+    unique
+}
+
+fn polygon_area(points: &[V], normal: V) -> f64 {
+    let mut sum = [0.0; 3];
+    for (i, &p) in points.iter().enumerate() {
+        sum = add(sum, cross(p, points[(i + 1) % points.len()]));
+    }
+    (dot(sum, normal) / 2.0).abs()
+}
+
+fn coplanar_result(
+    t1: &[V; 3],
+    t2: &[V; 3],
+    normal: V,
+    tolerance: f64,
+) -> TriangleIntersectionResult {
+    let polygon = clip_to_triangle(t1.to_vec(), t2, normal, tolerance);
+    let mut result = TriangleIntersectionResult {
+        intersects: !polygon.is_empty(),
+        coplanar: true,
+        segment: None,
+        overlap_polygon: None,
+        overlap_area: None,
+    };
+    if polygon.is_empty() {
+        return result;
+    }
+
+    let area = polygon_area(&polygon, normal);
+    let diameter = polygon
+        .iter()
+        .flat_map(|&p| polygon.iter().map(move |&q| norm(sub(p, q))))
+        .fold(0.0, f64::max);
+    if polygon.len() >= 3 && area > tolerance * diameter {
+        result.overlap_area = Some(area);
+        result.overlap_polygon = Some(polygon.into_iter().map(to_vector).collect());
+    } else {
+        // Touching along an edge or at a vertex: report the two farthest points
+        let mut ends = (polygon[0], polygon[0]);
+        for &p in &polygon {
+            for &q in &polygon {
+                if norm(sub(p, q)) > norm(sub(ends.1, ends.0)) {
+                    ends = (p, q);
+                }
+            }
+        }
+        result.segment = Some(segment(ends.0, ends.1));
+    }
+    result
+}
+
+pub fn triangle_intersection_logic(
+    input: TriangleIntersectionInput,
+) -> Result<TriangleIntersectionResult, String> {
+    let t1 = input.triangle1.each_ref().map(to_array);
+    let t2 = input.triangle2.each_ref().map(to_array);
+    if !t1.iter().chain(&t2).flatten().all(|v| v.is_finite()) {
+        return Err("Triangle vertices must be finite".to_string());
+    }
+    let tolerance = match input.tolerance {
+        Some(t) if !t.is_finite() || t < 0.0 => {
+            return Err("tolerance must be a non-negative number".to_string());
+        }
+        Some(t) => t,
+        None => RELATIVE_TOLERANCE * longest_edge(&t1).max(longest_edge(&t2)),
+    };
+
+    let n1 = unit_normal(&t1, tolerance, "Triangle 1")?;
+    let n2 = unit_normal(&t2, tolerance, "Triangle 2")?;
+
+    let disjoint = TriangleIntersectionResult {
+        intersects: false,
+        coplanar: false,
+        segment: None,
+        overlap_polygon: None,
+        overlap_area: None,
+    };
+
+    // Triangle 1 against the plane of triangle 2
+    let d1 = plane_distances(&t1, n2, t2[0], tolerance);
+    if same_side(&d1) {
+        return Ok(disjoint);
+    }
+    if d1.iter().all(|&d| d == 0.0) {
+        return Ok(coplanar_result(&t1, &t2, n2, tolerance));
+    }
+
+    // Triangle 2 against the plane of triangle 1
+    let d2 = plane_distances(&t2, n1, t1[0], tolerance);
+    if same_side(&d2) {
+        return Ok(disjoint);
+    }
+    if d2.iter().all(|&d| d == 0.0) {
+        return Ok(coplanar_result(&t1, &t2, n2, tolerance));
+    }
+
+    // Both triangles cross the line where the planes meet; they intersect where the
+    // two crossing intervals on that line overlap
+    let direction = cross(n1, n2);
+    let direction = scale(direction, 1.0 / norm(direction));
+    let (lo1, lo1_point, hi1, hi1_point) = interval(&plane_crossing(&t1, &d1), direction);
+    let (lo2, lo2_point, hi2, hi2_point) = interval(&plane_crossing(&t2, &d2), direction);
+
+    let (start_param, start) = if lo1 >= lo2 {
+        (lo1, lo1_point)
+    } else {
+        (lo2, lo2_point)
+    };
+    let (end_param, end) = if hi1 <= hi2 {
+        (hi1, hi1_point)
+    } else {
+        (hi2, hi2_point)
+    };
+    if start_param > end_param + tolerance {
+        return Ok(disjoint);
+    }
+    let end = if end_param < start_param { start } else { end };
+
+    Ok(TriangleIntersectionResult {
+        intersects: true,
+        coplanar: false,
+        segment: Some(segment(start, end)),
+        overlap_polygon: None,
+        overlap_area: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f64, y: f64, z: f64) -> Vector3D {
+        Vector3D { x, y, z }
+    }
+
+    fn input(triangle1: [Vector3D; 3], triangle2: [Vector3D; 3]) -> TriangleIntersectionInput {
+        TriangleIntersectionInput {
+            triangle1,
+            triangle2,
+            tolerance: None,
+        }
+    }
+
+    fn ground() -> [Vector3D; 3] {
+        [v(0.0, 0.0, 0.0), v(4.0, 0.0, 0.0), v(0.0, 4.0, 0.0)]
+    }
+
+    fn assert_close(actual: &Vector3D, expected: [f64; 3]) {
+        let a = to_array(actual);
+        assert!(
+            (0..3).all(|i| (a[i] - expected[i]).abs() < 1e-9),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    fn assert_segment(segment: &Segment, a: [f64; 3], b: [f64; 3]) {
+        let matches = |s: [f64; 3], e: [f64; 3]| {
+            (0..3).all(|i| (to_array(&segment.start)[i] - s[i]).abs() < 1e-9)
+                && (0..3).all(|i| (to_array(&segment.end)[i] - e[i]).abs() < 1e-9)
+        };
+        assert!(matches(a, b) || matches(b, a), "{segment:?} != {a:?}-{b:?}");
+    }
+
+    #[test]
+    fn test_crossing_triangles() {
+        // A vertical triangle piercing the ground triangle along x = 1
+        let wall = [v(1.0, -1.0, -1.0), v(1.0, 3.0, -1.0), v(1.0, 1.0, 3.0)];
+        let result = triangle_intersection_logic(input(ground(), wall)).unwrap();
+        assert!(result.intersects);
+        assert!(!result.coplanar);
+        // The wall meets z = 0 for y in -0.5..2.5, the ground spans y in 0..3 at x = 1
+        let segment = result.segment.unwrap();
+        assert_segment(&segment, [1.0, 0.0, 0.0], [1.0, 2.5, 0.0]);
+        assert!((segment.length - 2.5).abs() < 1e-12);
+        assert!(result.overlap_polygon.is_none());
+    }
+
+    #[test]
+    fn test_separated_and_near_miss() {
+        let above = [v(0.0, 0.0, 1.0), v(4.0, 0.0, 1.0), v(0.0, 4.0, 2.0)];
+        let result = triangle_intersection_logic(input(ground(), above)).unwrap();
+        assert!(!result.intersects);
+        assert!(result.segment.is_none());
+
+        // Crosses the ground plane, but outside the ground triangle
+        let beside = [v(5.0, 5.0, -1.0), v(6.0, 5.0, 1.0), v(5.0, 6.0, 1.0)];
+        let result = triangle_intersection_logic(input(ground(), beside)).unwrap();
+        assert!(!result.intersects);
+        assert!(!result.coplanar);
+    }
+
+    #[test]
+    fn test_touching_at_vertex() {
+        // Apex resting on the ground triangle
+        let tent = [v(1.0, 1.0, 0.0), v(0.0, 2.0, 2.0), v(2.0, 2.0, 2.0)];
+        let result = triangle_intersection_logic(input(ground(), tent)).unwrap();
+        assert!(result.intersects);
+        let segment = result.segment.unwrap();
+        assert_close(&segment.start, [1.0, 1.0, 0.0]);
+        assert_close(&segment.end, [1.0, 1.0, 0.0]);
+        assert_eq!(segment.length, 0.0);
+    }
+
+    #[test]
+    fn test_coplanar_overlap() {
+        let shifted = [v(1.0, 1.0, 0.0), v(5.0, 1.0, 0.0), v(1.0, 5.0, 0.0)];
+        let result = triangle_intersection_logic(input(ground(), shifted)).unwrap();
+        assert!(result.intersects);
+        assert!(result.coplanar);
+        assert!(result.segment.is_none());
+        // The overlap is the triangle (1,1), (3,1), (1,3)
+        assert!((result.overlap_area.unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(result.overlap_polygon.unwrap().len(), 3);
+
+        // Vertex order of either triangle does not matter
+        let reversed = [v(1.0, 5.0, 0.0), v(5.0, 1.0, 0.0), v(1.0, 1.0, 0.0)];
+        let result = triangle_intersection_logic(input(ground(), reversed)).unwrap();
+        assert!((result.overlap_area.unwrap() - 2.0).abs() < 1e-9);
+
+        // One triangle inside the other
+        let inner = [v(0.5, 0.5, 0.0), v(1.5, 0.5, 0.0), v(0.5, 1.5, 0.0)];
+        let result = triangle_intersection_logic(input(ground(), inner)).unwrap();
+        assert!((result.overlap_area.unwrap() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_coplanar_touching_and_disjoint() {
+        // Shares part of the hypotenuse x + y = 4
+        let neighbour = [v(4.0, 0.0, 0.0), v(4.0, 4.0, 0.0), v(0.0, 4.0, 0.0)];
+        let result = triangle_intersection_logic(input(ground(), neighbour)).unwrap();
+        assert!(result.intersects);
+        assert!(result.coplanar);
+        assert!(result.overlap_area.is_none());
+        assert_segment(&result.segment.unwrap(), [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]);
+
+        let far = [v(5.0, 5.0, 0.0), v(6.0, 5.0, 0.0), v(5.0, 6.0, 0.0)];
+        let result = triangle_intersection_logic(input(ground(), far)).unwrap();
+        assert!(!result.intersects);
+        assert!(result.coplanar);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let line = [v(0.0, 0.0, 0.0), v(1.0, 1.0, 1.0), v(2.0, 2.0, 2.0)];
+        assert_eq!(
+            triangle_intersection_logic(input(ground(), line)).unwrap_err(),
+            "Triangle 2 is degenerate (its vertices are collinear)"
+        );
+
+        let nan = [v(f64::NAN, 0.0, 0.0), v(1.0, 0.0, 0.0), v(0.0, 1.0, 0.0)];
+        assert!(
+            triangle_intersection_logic(input(nan, ground()))
+                .unwrap_err()
+                .contains("finite")
+        );
+
+        let mut negative = input(ground(), ground());
+        negative.tolerance = Some(-1.0);
+        assert!(
+            triangle_intersection_logic(negative)
+                .unwrap_err()
+                .contains("tolerance")
+        );
+    }
+}
@@ -1,21 +1,42 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+use ftl_request_context::ResponseMetadata;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::{AngleUnit, Quantity};
+use ftl_vector3d::Vector3DInput;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
 
 // Re-export types from logic module
-pub use logic::{VectorAnalysisInput as LogicInput, VectorAnalysisOutput as LogicOutput};
+pub use logic::{
+    VectorAnalysisInput as LogicInput, VectorAnalysisOutput as LogicOutput, analyze_vectors,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VectorAnalysisInput {
-    /// First 3D vector [x, y, z]
-    pub vector_a: Vec<f64>,
-    /// Second 3D vector [x, y, z]
-    pub vector_b: Vec<f64>,
+    /// First 3D vector, as [x, y, z] or {"x", "y", "z"}
+    pub vector_a: Vector3DInput,
+    /// Second 3D vector, as [x, y, z] or {"x", "y", "z"}
+    pub vector_b: Vector3DInput,
+    /// Correlation id to propagate to the atomic tools; generated when omitted
+    pub correlation_id: Option<String>,
+    /// Unit for `angle_between`: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -24,6 +45,8 @@ pub struct VectorAnalysisOutput {
     pub magnitude_a: f64,
     /// Magnitude of vector B  
     pub magnitude_b: f64,
+    /// Angle between vectors in the requested unit
+    pub angle_between: Quantity,
     /// Angle between vectors in radians
     pub angle_between_radians: f64,
     /// Angle between vectors in degrees
@@ -38,6 +61,8 @@ pub struct VectorAnalysisOutput {
     pub is_parallel: bool,
     /// Vector similarity score (-1 to 1, cosine similarity)
     pub vector_similarity: f64,
+    /// Correlation id and per-tool timings for this call chain
+    pub metadata: ResponseMetadata,
 }
 
 /// Comprehensive vector analysis using composition of atomic math3d tools
@@ -45,12 +70,40 @@ pub struct VectorAnalysisOutput {
 /// This composite tool demonstrates the composition pattern by calling multiple
 /// atomic tools (vector_magnitude, vector_angle, dot_product, cross_product) and
 /// combining their results for comprehensive vector analysis.
-#[cfg_attr(not(test), tool)]
-pub async fn vector_analysis(input: VectorAnalysisInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn vector_analysis(request: Request<VectorAnalysisInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "vector_analysis",
+                VectorAnalysisInput,
+                VectorAnalysisOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("vector_analysis", &input);
+    log.finish(run_vector_analysis(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_vector_analysis(input: VectorAnalysisInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
     // Convert to logic types
     let logic_input = LogicInput {
-        vector_a: input.vector_a,
-        vector_b: input.vector_b,
+        vector_a: input.vector_a.to_array().to_vec(),
+        vector_b: input.vector_b.to_array().to_vec(),
+        correlation_id: input.correlation_id,
     };
 
     // Call async logic implementation
@@ -60,6 +113,7 @@ pub async fn vector_analysis(input: VectorAnalysisInput) -> ToolResponse {
             let response = VectorAnalysisOutput {
                 magnitude_a: result.magnitude_a,
                 magnitude_b: result.magnitude_b,
+                angle_between: Quantity::angle(result.angle_between_radians, angle_unit),
                 angle_between_radians: result.angle_between_radians,
                 angle_between_degrees: result.angle_between_degrees,
                 dot_product: result.dot_product,
@@ -67,10 +121,15 @@ pub async fn vector_analysis(input: VectorAnalysisInput) -> ToolResponse {
                 is_orthogonal: result.is_orthogonal,
                 is_parallel: result.is_parallel,
                 vector_similarity: result.vector_similarity,
+                metadata: result.metadata,
             };
-            ToolResponse::text(serde_json::to_string_pretty(&response).unwrap())
+            let mut value = serde_json::to_value(&response).unwrap();
+            if let Some(precision) = precision {
+                precision.apply(&mut value);
+            }
+            ToolResponse::text(ftl_json::to_string_pretty(&value).unwrap())
         }
-        Err(e) => ToolResponse::text(format!("Error: {e}")),
+        Err(e) => ToolResponse::text(ftl_outbound::error_body(&e)),
     }
 }
 
@@ -80,13 +139,16 @@ mod tests {
 
     #[test]
     fn test_vector_analysis_input_validation() {
-        let input = VectorAnalysisInput {
-            vector_a: vec![1.0, 0.0, 0.0],
-            vector_b: vec![0.0, 1.0, 0.0],
-        };
+        let input: VectorAnalysisInput = serde_json::from_str(
+            r#"{"vector_a": [1, 0, 0], "vector_b": {"x": 0, "y": 1, "z": 0}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(input.vector_a.to_array(), [1.0, 0.0, 0.0]);
+        assert_eq!(input.vector_b.to_array(), [0.0, 1.0, 0.0]);
 
-        assert_eq!(input.vector_a.len(), 3);
-        assert_eq!(input.vector_b.len(), 3);
+        let short = r#"{"vector_a": [1, 0], "vector_b": [0, 1, 0]}"#;
+        assert!(serde_json::from_str::<VectorAnalysisInput>(short).is_err());
     }
 
     #[test]
@@ -94,6 +156,7 @@ mod tests {
         let output = VectorAnalysisOutput {
             magnitude_a: 1.0,
             magnitude_b: 1.0,
+            angle_between: Quantity::angle(std::f64::consts::PI / 2.0, AngleUnit::Degrees),
             angle_between_radians: std::f64::consts::PI / 2.0,
             angle_between_degrees: 90.0,
             dot_product: 0.0,
@@ -101,10 +164,16 @@ mod tests {
             is_orthogonal: true,
             is_parallel: false,
             vector_similarity: 0.0,
+            metadata: ResponseMetadata {
+                correlation_id: "test".to_string(),
+                total_duration_ms: 0.0,
+                steps: Vec::new(),
+            },
         };
 
         assert!(output.is_orthogonal);
         assert!(!output.is_parallel);
+        assert_eq!(output.angle_between.unit, "degrees");
         assert_eq!(output.cross_product.len(), 3);
     }
 }
@@ -1,9 +1,12 @@
+use ftl_outbound::Outbound;
+use ftl_request_context::{RequestContext, ResponseMetadata};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct VectorAnalysisInput {
     pub vector_a: Vec<f64>,
     pub vector_b: Vec<f64>,
+    pub correlation_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -17,6 +20,7 @@ pub struct VectorAnalysisOutput {
     pub is_orthogonal: bool,
     pub is_parallel: bool,
     pub vector_similarity: f64,
+    pub metadata: ResponseMetadata,
 }
 
 // Helper structs for calling other tools
@@ -116,12 +120,40 @@ pub async fn analyze_vectors(input: VectorAnalysisInput) -> Result<VectorAnalysi
         return Err("Both vectors must be 3-dimensional".to_string());
     }
 
-    // Call atomic tools via Spin HTTP
-    let magnitude_a = call_vector_magnitude(&input.vector_a).await?;
-    let magnitude_b = call_vector_magnitude(&input.vector_b).await?;
-    let angle_result = call_vector_angle(&input.vector_a, &input.vector_b).await?;
-    let dot_product = call_dot_product(&input.vector_a, &input.vector_b).await?;
-    let cross_product = call_cross_product(&input.vector_a, &input.vector_b).await?;
+    // Call atomic tools via Spin HTTP, forwarding the correlation id to each
+    let mut ctx = RequestContext::new(input.correlation_id);
+    let id = ctx.correlation_id().to_string();
+    let outbound = Outbound::from_env();
+    let magnitude_a = ctx
+        .time(
+            "vector_magnitude",
+            call_vector_magnitude(&input.vector_a, &id, &outbound),
+        )
+        .await?;
+    let magnitude_b = ctx
+        .time(
+            "vector_magnitude",
+            call_vector_magnitude(&input.vector_b, &id, &outbound),
+        )
+        .await?;
+    let angle_result = ctx
+        .time(
+            "vector_angle",
+            call_vector_angle(&input.vector_a, &input.vector_b, &id, &outbound),
+        )
+        .await?;
+    let dot_product = ctx
+        .time(
+            "dot_product",
+            call_dot_product(&input.vector_a, &input.vector_b, &id, &outbound),
+        )
+        .await?;
+    let cross_product = ctx
+        .time(
+            "cross_product",
+            call_cross_product(&input.vector_a, &input.vector_b, &id, &outbound),
+        )
+        .await?;
 
     // Calculate derived properties
     let is_orthogonal = dot_product.abs() < 1e-10;
@@ -142,12 +174,15 @@ pub async fn analyze_vectors(input: VectorAnalysisInput) -> Result<VectorAnalysi
         is_orthogonal,
         is_parallel,
         vector_similarity,
+        metadata: ctx.finish(),
     })
 }
 
-async fn call_vector_magnitude(vector: &[f64]) -> Result<f64, String> {
-    use spin_sdk::http::{Method, Request};
-
+async fn call_vector_magnitude(
+    vector: &[f64],
+    correlation_id: &str,
+    outbound: &Outbound,
+) -> Result<f64, String> {
     if vector.len() != 3 {
         return Err("Vector must be 3-dimensional".to_string());
     }
@@ -162,20 +197,9 @@ async fn call_vector_magnitude(vector: &[f64]) -> Result<f64, String> {
     let request_body = serde_json::to_string(&input)
         .map_err(|e| format!("Failed to serialize vector input: {e}"))?;
 
-    let request = Request::builder()
-        .method(Method::Post)
-        .uri("http://vector-magnitude.spin.internal")
-        .header("Content-Type", "application/json")
-        .body(request_body.into_bytes())
-        .build();
-
-    let response: spin_sdk::http::Response = spin_sdk::http::send(request)
-        .await
-        .map_err(|e| format!("Failed to call vector_magnitude: {e:?}"))?;
-
-    let body_bytes = response.into_body();
-    let body =
-        String::from_utf8(body_bytes).map_err(|e| format!("Failed to parse response body: {e}"))?;
+    let body = outbound
+        .post_json("vector-magnitude", &request_body, Some(correlation_id))
+        .await?;
 
     // Parse direct ToolResponse format like pythagorean does
     let wrapper: ToolResponseWrapper<String> = serde_json::from_str(&body)
@@ -188,9 +212,12 @@ async fn call_vector_magnitude(vector: &[f64]) -> Result<f64, String> {
     Ok(result.magnitude)
 }
 
-async fn call_vector_angle(vector_a: &[f64], vector_b: &[f64]) -> Result<f64, String> {
-    use spin_sdk::http::{Method, Request};
-
+async fn call_vector_angle(
+    vector_a: &[f64],
+    vector_b: &[f64],
+    correlation_id: &str,
+    outbound: &Outbound,
+) -> Result<f64, String> {
     if vector_a.len() != 3 || vector_b.len() != 3 {
         return Err("Vectors must be 3-dimensional".to_string());
     }
@@ -210,20 +237,9 @@ async fn call_vector_angle(vector_a: &[f64], vector_b: &[f64]) -> Result<f64, St
     let request_body = serde_json::to_string(&input)
         .map_err(|e| format!("Failed to serialize vector angle input: {e}"))?;
 
-    let request = Request::builder()
-        .method(Method::Post)
-        .uri("http://vector-angle.spin.internal")
-        .header("Content-Type", "application/json")
-        .body(request_body.into_bytes())
-        .build();
-
-    let response: spin_sdk::http::Response = spin_sdk::http::send(request)
-        .await
-        .map_err(|e| format!("Failed to call vector_angle: {e:?}"))?;
-
-    let body_bytes = response.into_body();
-    let body =
-        String::from_utf8(body_bytes).map_err(|e| format!("Failed to parse response body: {e}"))?;
+    let body = outbound
+        .post_json("vector-angle", &request_body, Some(correlation_id))
+        .await?;
 
     let wrapper: ToolResponseWrapper<String> = serde_json::from_str(&body)
         .map_err(|e| format!("Failed to parse response wrapper: {e}"))?;
@@ -235,9 +251,12 @@ async fn call_vector_angle(vector_a: &[f64], vector_b: &[f64]) -> Result<f64, St
     Ok(result.angle_radians)
 }
 
-async fn call_dot_product(vector_a: &[f64], vector_b: &[f64]) -> Result<f64, String> {
-    use spin_sdk::http::{Method, Request};
-
+async fn call_dot_product(
+    vector_a: &[f64],
+    vector_b: &[f64],
+    correlation_id: &str,
+    outbound: &Outbound,
+) -> Result<f64, String> {
     let input = TwoVectorInput {
         vector1: Vector3D {
             x: vector_a[0],
@@ -253,20 +272,9 @@ async fn call_dot_product(vector_a: &[f64], vector_b: &[f64]) -> Result<f64, Str
     let request_body = serde_json::to_string(&input)
         .map_err(|e| format!("Failed to serialize dot product input: {e}"))?;
 
-    let request = Request::builder()
-        .method(Method::Post)
-        .uri("http://dot-product.spin.internal")
-        .header("Content-Type", "application/json")
-        .body(request_body.into_bytes())
-        .build();
-
-    let response: spin_sdk::http::Response = spin_sdk::http::send(request)
-        .await
-        .map_err(|e| format!("Failed to call dot_product: {e:?}"))?;
-
-    let body_bytes = response.into_body();
-    let body =
-        String::from_utf8(body_bytes).map_err(|e| format!("Failed to parse response body: {e}"))?;
+    let body = outbound
+        .post_json("dot-product", &request_body, Some(correlation_id))
+        .await?;
 
     let wrapper: ToolResponseWrapper<String> = serde_json::from_str(&body)
         .map_err(|e| format!("Failed to parse response wrapper: {e}"))?;
@@ -278,9 +286,12 @@ async fn call_dot_product(vector_a: &[f64], vector_b: &[f64]) -> Result<f64, Str
     Ok(result.dot_product)
 }
 
-async fn call_cross_product(vector_a: &[f64], vector_b: &[f64]) -> Result<Vec<f64>, String> {
-    use spin_sdk::http::{Method, Request};
-
+async fn call_cross_product(
+    vector_a: &[f64],
+    vector_b: &[f64],
+    correlation_id: &str,
+    outbound: &Outbound,
+) -> Result<Vec<f64>, String> {
     let input = TwoVectorInput {
         vector1: Vector3D {
             x: vector_a[0],
@@ -296,20 +307,9 @@ async fn call_cross_product(vector_a: &[f64], vector_b: &[f64]) -> Result<Vec<f6
     let request_body = serde_json::to_string(&input)
         .map_err(|e| format!("Failed to serialize cross product input: {e}"))?;
 
-    let request = Request::builder()
-        .method(Method::Post)
-        .uri("http://cross-product.spin.internal")
-        .header("Content-Type", "application/json")
-        .body(request_body.into_bytes())
-        .build();
-
-    let response: spin_sdk::http::Response = spin_sdk::http::send(request)
-        .await
-        .map_err(|e| format!("Failed to call cross_product: {e:?}"))?;
-
-    let body_bytes = response.into_body();
-    let body =
-        String::from_utf8(body_bytes).map_err(|e| format!("Failed to parse response body: {e}"))?;
+    let body = outbound
+        .post_json("cross-product", &request_body, Some(correlation_id))
+        .await?;
 
     let wrapper: ToolResponseWrapper<String> = serde_json::from_str(&body)
         .map_err(|e| format!("Failed to parse response wrapper: {e}"))?;
@@ -1,48 +1,91 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
+use ftl_units::{AngleUnit, Quantity};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{TwoVectorInput as LogicInput, Vector3D as LogicVector3D, vector_angle_logic};
 
-#[derive(Deserialize, Serialize, Clone, JsonSchema)]
-pub struct Vector3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
+// Re-export types from logic module
+pub use logic::{
+    TwoVectorInput as LogicInput, Vector3D as LogicVector3D, VectorAngleResult as LogicOutput,
+    vector_angle_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{Vector3DSchema as Vector3D, VectorAngleResultSchema as VectorAngleResult};
 
-#[derive(Deserialize, JsonSchema)]
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TwoVectorInput {
-    pub vector1: Vector3D,
-    pub vector2: Vector3D,
+    #[serde(flatten)]
+    pub params: logic::TwoVectorInputSchema,
+    /// Unit for `angle`: "radians" or "degrees" (default: radians)
+    pub angle_unit: Option<AngleUnit>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-impl From<Vector3D> for LogicVector3D {
-    fn from(v: Vector3D) -> Self {
-        LogicVector3D {
-            x: v.x,
-            y: v.y,
-            z: v.z,
-        }
-    }
+// The generated result plus the angle in the requested unit
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VectorAngleResponse {
+    /// Angle between the vectors in the requested unit
+    pub angle: Quantity,
+    #[serde(flatten)]
+    pub result: VectorAngleResult,
 }
 
-impl From<TwoVectorInput> for LogicInput {
-    fn from(input: TwoVectorInput) -> Self {
-        LogicInput {
-            vector1: input.vector1.into(),
-            vector2: input.vector2.into(),
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn vector_angle(request: Request<TwoVectorInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "vector_angle",
+                TwoVectorInput,
+                VectorAngleResponse
+            ));
         }
-    }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("vector_angle", &input);
+    log.finish(run_vector_angle(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn vector_angle(input: TwoVectorInput) -> ToolResponse {
-    match vector_angle_logic(input.into()) {
-        Ok(result) => ToolResponse::text(serde_json::to_string(&result).unwrap()),
+#[cfg(feature = "individual")]
+fn run_vector_angle(input: TwoVectorInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    let angle_unit = input.angle_unit.unwrap_or_default();
+
+    match vector_angle_logic(input.params.into()) {
+        Ok(result) => {
+            let response = VectorAngleResponse {
+                angle: Quantity::angle(result.angle_radians, angle_unit),
+                result: result.into(),
+            };
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
 }
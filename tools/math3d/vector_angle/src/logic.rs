@@ -1,19 +1,26 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Deserialize, Serialize, Clone, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
     pub x: f64,
     pub y: f64,
     pub z: f64,
 }
 
-#[derive(Deserialize, Clone)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Deserialize, Clone, ToolTypes)]
 pub struct TwoVectorInput {
+    #[tool_types(nested)]
     pub vector1: Vector3D,
+    #[tool_types(nested)]
     pub vector2: Vector3D,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug, ToolTypes)]
 pub struct VectorAngleResult {
     pub angle_radians: f64,
     pub angle_degrees: f64,
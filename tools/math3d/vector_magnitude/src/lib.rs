@@ -1,5 +1,11 @@
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,58 +15,60 @@ mod logic;
 // Re-export types from logic module
 pub use logic::{
     Vector3D as LogicVector3D, VectorMagnitudeInput as LogicInput,
-    VectorMagnitudeOutput as LogicOutput,
+    VectorMagnitudeOutput as LogicOutput, compute_vector_magnitude,
 };
 
-// Define wrapper types with JsonSchema for FTL-SDK
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct Vector3D {
-    /// X component of the vector
-    pub x: f64,
-    /// Y component of the vector
-    pub y: f64,
-    /// Z component of the vector
-    pub z: f64,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{Vector3DSchema as Vector3D, VectorMagnitudeOutputSchema as VectorMagnitudeResult};
 
+// The generated input plus options that only affect how the result is serialized
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VectorMagnitudeInput {
-    /// 3D vector to calculate magnitude for
-    pub vector: Vector3D,
+    #[serde(flatten)]
+    pub params: logic::VectorMagnitudeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct VectorMagnitudeResult {
-    pub magnitude: f64,
-    pub unit_vector: Vector3D,
-    pub is_zero_vector: bool,
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn vector_magnitude(request: Request<VectorMagnitudeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "vector_magnitude",
+                VectorMagnitudeInput,
+                VectorMagnitudeResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("vector_magnitude", &input);
+    log.finish(run_vector_magnitude(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn vector_magnitude(input: VectorMagnitudeInput) -> ToolResponse {
-    // Convert to logic types
-    let logic_input = LogicInput {
-        vector: LogicVector3D {
-            x: input.vector.x,
-            y: input.vector.y,
-            z: input.vector.z,
-        },
+#[cfg(feature = "individual")]
+fn run_vector_magnitude(input: VectorMagnitudeInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
     };
 
     // Call logic implementation
-    match logic::compute_vector_magnitude(logic_input) {
+    match logic::compute_vector_magnitude(input.params.into()) {
         Ok(result) => {
-            // Convert back to wrapper types
-            let response = VectorMagnitudeResult {
-                magnitude: result.magnitude,
-                unit_vector: Vector3D {
-                    x: result.unit_vector.x,
-                    y: result.unit_vector.y,
-                    z: result.unit_vector.z,
-                },
-                is_zero_vector: result.is_zero_vector,
-            };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            let response = VectorMagnitudeResult::from(result);
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
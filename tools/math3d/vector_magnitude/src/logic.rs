@@ -1,20 +1,31 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 3D vector, as {"x", "y", "z"} or as [x, y, z]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+#[serde(from = "ftl_vector3d::Vector3DInput")]
 pub struct Vector3D {
+    /// X component of the vector
     pub x: f64,
+    /// Y component of the vector
     pub y: f64,
+    /// Z component of the vector
     pub z: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+ftl_vector3d::from_vector3d_input!(Vector3D, Vector3DSchema);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct VectorMagnitudeInput {
+    /// 3D vector to calculate magnitude for
+    #[tool_types(nested)]
     pub vector: Vector3D,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct VectorMagnitudeOutput {
     pub magnitude: f64,
+    #[tool_types(nested)]
     pub unit_vector: Vector3D,
     pub is_zero_vector: bool,
 }
@@ -1,5 +1,14 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_messages::Locale;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -7,7 +16,10 @@ use serde::{Deserialize, Serialize};
 mod logic;
 
 // Re-export types from logic module
-pub use logic::{AnalyzeDistributionInput as LogicInput, AnalyzeDistributionOutput as LogicOutput};
+pub use logic::{
+    AnalyzeDistributionInput as LogicInput, AnalyzeDistributionOutput as LogicOutput,
+    calculate_analyze_distribution,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -16,6 +28,14 @@ pub struct AnalyzeDistributionInput {
     pub data: Vec<f64>,
     /// Number of histogram bins (optional, auto-calculated if not provided)
     pub num_bins: Option<usize>,
+    /// Language of the interpretation and suggested distribution: "en" (default), "es", "de" or "ja"
+    pub locale: Option<Locale>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -86,12 +106,43 @@ pub struct DistributionParameters {
 
 /// Analyze distribution characteristics including histogram, normality tests, and parameter estimation
 /// This tool combines histogram generation and normality testing to provide comprehensive distribution analysis
-#[cfg_attr(not(test), tool)]
-pub async fn analyze_distribution(input: AnalyzeDistributionInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn analyze_distribution(request: Request<AnalyzeDistributionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "analyze_distribution",
+                AnalyzeDistributionInput,
+                AnalyzeDistributionOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("analyze_distribution", &input);
+    log.finish(run_analyze_distribution(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_analyze_distribution(input: AnalyzeDistributionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().array("data", &input.data).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         data: input.data,
         num_bins: input.num_bins,
+        locale: input.locale,
     };
 
     // Call logic implementation
@@ -131,8 +182,11 @@ pub async fn analyze_distribution(input: AnalyzeDistributionInput) -> ToolRespon
                     suggested_distribution: result.distribution_parameters.suggested_distribution,
                 },
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
-        Err(e) => ToolResponse::text(format!("Error: {e}")),
+        Err(e) => ToolResponse::text(ftl_outbound::error_body(&e)),
     }
 }
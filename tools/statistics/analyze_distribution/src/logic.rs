@@ -1,9 +1,12 @@
+use ftl_messages::Locale;
+use ftl_outbound::Outbound;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct AnalyzeDistributionInput {
     pub data: Vec<f64>,
     pub num_bins: Option<usize>,
+    pub locale: Option<Locale>,
 }
 
 #[derive(Debug, Serialize)]
@@ -59,6 +62,7 @@ struct HistogramInput {
 #[derive(Serialize)]
 struct TestNormalityInput {
     data: Vec<f64>,
+    locale: Option<Locale>,
 }
 
 #[derive(Deserialize)]
@@ -83,15 +87,21 @@ pub async fn calculate_analyze_distribution(
         return Err("Input data contains invalid values (NaN or Infinite)".to_string());
     }
 
+    // Sibling calls share one retry and circuit breaker policy
+    let outbound = Outbound::from_env();
+
     // Step 1: Call histogram tool
-    let histogram = call_histogram_tool(&input.data, input.num_bins).await?;
+    let histogram = call_histogram_tool(&input.data, input.num_bins, &outbound).await?;
 
     // Step 2: Call test_normality tool
-    let normality_test = call_test_normality_tool(&input.data).await?;
+    let normality_test = call_test_normality_tool(&input.data, input.locale, &outbound).await?;
 
     // Step 3: Calculate distribution parameters locally
-    let distribution_parameters =
-        calculate_distribution_parameters(&input.data, normality_test.is_normal)?;
+    let distribution_parameters = calculate_distribution_parameters(
+        &input.data,
+        normality_test.is_normal,
+        input.locale.unwrap_or_default(),
+    )?;
 
     Ok(AnalyzeDistributionOutput {
         histogram,
@@ -103,9 +113,8 @@ pub async fn calculate_analyze_distribution(
 async fn call_histogram_tool(
     data: &[f64],
     num_bins: Option<usize>,
+    outbound: &Outbound,
 ) -> Result<HistogramOutput, String> {
-    use spin_sdk::http::{Method, Request};
-
     let histogram_input = HistogramInput {
         data: data.to_vec(),
         num_bins,
@@ -114,20 +123,7 @@ async fn call_histogram_tool(
     let request_body = serde_json::to_string(&histogram_input)
         .map_err(|e| format!("Failed to serialize histogram input: {e}"))?;
 
-    let request = Request::builder()
-        .method(Method::Post)
-        .uri("http://histogram.spin.internal")
-        .header("Content-Type", "application/json")
-        .body(request_body.into_bytes())
-        .build();
-
-    let response: spin_sdk::http::Response = spin_sdk::http::send(request)
-        .await
-        .map_err(|e| format!("Error calling histogram tool: {e:?}"))?;
-
-    let body_bytes = response.into_body();
-    let body =
-        String::from_utf8(body_bytes).map_err(|e| format!("Failed to parse response body: {e}"))?;
+    let body = outbound.post_json("histogram", &request_body, None).await?;
 
     let wrapper: ToolResponseWrapper<HistogramOutput> =
         serde_json::from_str(&body).map_err(|e| format!("Failed to parse tool response: {e}"))?;
@@ -137,30 +133,22 @@ async fn call_histogram_tool(
     Ok(histogram_result)
 }
 
-async fn call_test_normality_tool(data: &[f64]) -> Result<NormalityTestOutput, String> {
-    use spin_sdk::http::{Method, Request};
-
+async fn call_test_normality_tool(
+    data: &[f64],
+    locale: Option<Locale>,
+    outbound: &Outbound,
+) -> Result<NormalityTestOutput, String> {
     let test_normality_input = TestNormalityInput {
         data: data.to_vec(),
+        locale,
     };
 
     let request_body = serde_json::to_string(&test_normality_input)
         .map_err(|e| format!("Failed to serialize test_normality input: {e}"))?;
 
-    let request = Request::builder()
-        .method(Method::Post)
-        .uri("http://test-normality.spin.internal")
-        .header("Content-Type", "application/json")
-        .body(request_body.into_bytes())
-        .build();
-
-    let response: spin_sdk::http::Response = spin_sdk::http::send(request)
-        .await
-        .map_err(|e| format!("Error calling test_normality tool: {e:?}"))?;
-
-    let body_bytes = response.into_body();
-    let body =
-        String::from_utf8(body_bytes).map_err(|e| format!("Failed to parse response body: {e}"))?;
+    let body = outbound
+        .post_json("test-normality", &request_body, None)
+        .await?;
 
     let wrapper: ToolResponseWrapper<NormalityTestOutput> =
         serde_json::from_str(&body).map_err(|e| format!("Failed to parse tool response: {e}"))?;
@@ -173,6 +161,7 @@ async fn call_test_normality_tool(data: &[f64]) -> Result<NormalityTestOutput, S
 fn calculate_distribution_parameters(
     data: &[f64],
     is_normal: bool,
+    locale: Locale,
 ) -> Result<DistributionParameters, String> {
     let n = data.len() as f64;
 
@@ -202,7 +191,7 @@ fn calculate_distribution_parameters(
         - 3.0; // Excess kurtosis
 
     // Suggest distribution type based on characteristics
-    let suggested_distribution = suggest_distribution(skewness, kurtosis, is_normal);
+    let suggested_distribution = suggest_distribution(skewness, kurtosis, is_normal, locale);
 
     Ok(DistributionParameters {
         mean,
@@ -213,22 +202,23 @@ fn calculate_distribution_parameters(
     })
 }
 
-fn suggest_distribution(skewness: f64, kurtosis: f64, is_normal: bool) -> String {
-    if is_normal {
-        "Normal Distribution".to_string()
+fn suggest_distribution(skewness: f64, kurtosis: f64, is_normal: bool, locale: Locale) -> String {
+    let message = if is_normal {
+        ftl_messages::DISTRIBUTION_NORMAL
     } else if skewness.abs() < 0.5 && kurtosis.abs() < 0.5 {
-        "Approximately Normal Distribution".to_string()
+        ftl_messages::DISTRIBUTION_APPROXIMATELY_NORMAL
     } else if skewness > 1.0 {
-        "Right-skewed Distribution (consider Log-normal, Exponential, or Gamma)".to_string()
+        ftl_messages::DISTRIBUTION_RIGHT_SKEWED
     } else if skewness < -1.0 {
-        "Left-skewed Distribution (consider Beta or transformed distributions)".to_string()
+        ftl_messages::DISTRIBUTION_LEFT_SKEWED
     } else if kurtosis > 3.0 {
-        "Heavy-tailed Distribution (consider t-distribution or Laplace)".to_string()
+        ftl_messages::DISTRIBUTION_HEAVY_TAILED
     } else if kurtosis < -1.0 {
-        "Light-tailed Distribution (consider Uniform or truncated distributions)".to_string()
+        ftl_messages::DISTRIBUTION_LIGHT_TAILED
     } else {
-        "Non-normal Distribution (consider non-parametric approaches)".to_string()
-    }
+        ftl_messages::DISTRIBUTION_NON_NORMAL
+    };
+    message.text(locale).to_string()
 }
 
 #[cfg(test)]
@@ -238,49 +228,64 @@ mod tests {
     #[test]
     fn test_suggest_distribution() {
         // Test normal distribution suggestion
-        assert_eq!(suggest_distribution(0.0, 0.0, true), "Normal Distribution");
+        assert_eq!(
+            suggest_distribution(0.0, 0.0, true, Locale::En),
+            "Normal Distribution"
+        );
 
         // Test approximately normal
         assert_eq!(
-            suggest_distribution(0.3, 0.2, false),
+            suggest_distribution(0.3, 0.2, false, Locale::En),
             "Approximately Normal Distribution"
         );
 
         // Test right-skewed
         assert_eq!(
-            suggest_distribution(1.5, 0.0, false),
+            suggest_distribution(1.5, 0.0, false, Locale::En),
             "Right-skewed Distribution (consider Log-normal, Exponential, or Gamma)"
         );
 
         // Test left-skewed
         assert_eq!(
-            suggest_distribution(-1.5, 0.0, false),
+            suggest_distribution(-1.5, 0.0, false, Locale::En),
             "Left-skewed Distribution (consider Beta or transformed distributions)"
         );
 
         // Test heavy-tailed
         assert_eq!(
-            suggest_distribution(0.0, 4.0, false),
+            suggest_distribution(0.0, 4.0, false, Locale::En),
             "Heavy-tailed Distribution (consider t-distribution or Laplace)"
         );
 
         // Test light-tailed
         assert_eq!(
-            suggest_distribution(0.0, -1.5, false),
+            suggest_distribution(0.0, -1.5, false, Locale::En),
             "Light-tailed Distribution (consider Uniform or truncated distributions)"
         );
 
         // Test non-normal
         assert_eq!(
-            suggest_distribution(0.8, 1.0, false),
+            suggest_distribution(0.8, 1.0, false, Locale::En),
             "Non-normal Distribution (consider non-parametric approaches)"
         );
     }
 
+    #[test]
+    fn test_suggest_distribution_localized() {
+        assert_eq!(
+            suggest_distribution(0.0, 0.0, true, Locale::Es),
+            "Distribución normal"
+        );
+        assert_eq!(
+            suggest_distribution(1.5, 0.0, false, Locale::Ja),
+            ftl_messages::DISTRIBUTION_RIGHT_SKEWED.ja
+        );
+    }
+
     #[test]
     fn test_calculate_distribution_parameters() {
         let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let result = calculate_distribution_parameters(&data, false).unwrap();
+        let result = calculate_distribution_parameters(&data, false, Locale::En).unwrap();
 
         assert_eq!(result.mean, 3.0);
         assert!((result.std_dev - std::f64::consts::SQRT_2).abs() < 1e-10);
@@ -291,20 +296,20 @@ mod tests {
     #[test]
     fn test_input_validation() {
         // Test that single element data is caught (std_dev will be 0)
-        let result = calculate_distribution_parameters(&[1.0], false);
+        let result = calculate_distribution_parameters(&[1.0], false, Locale::En);
         assert!(result.is_err()); // Single element has std_dev = 0
 
         // Test that empty data would cause issues (though it's caught earlier)
         // Empty slice would have mean = 0/0 = NaN, but we catch it before this function
         // Let's just test a simple case with different values
-        let result = calculate_distribution_parameters(&[1.0, 2.0, 3.0], false);
+        let result = calculate_distribution_parameters(&[1.0, 2.0, 3.0], false, Locale::En);
         assert!(result.is_ok()); // This should work fine
     }
 
     #[test]
     fn test_zero_variance() {
         let data = vec![5.0, 5.0, 5.0, 5.0, 5.0]; // All identical
-        let result = calculate_distribution_parameters(&data, false);
+        let result = calculate_distribution_parameters(&data, false, Locale::En);
         assert!(result.is_err());
         assert!(result.err().unwrap().contains("Standard deviation is zero"));
     }
@@ -313,7 +318,7 @@ mod tests {
     fn test_skewed_data() {
         // Right-skewed data
         let data = vec![1.0, 1.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0];
-        let result = calculate_distribution_parameters(&data, false).unwrap();
+        let result = calculate_distribution_parameters(&data, false, Locale::En).unwrap();
 
         assert!(result.skewness > 0.0); // Should be positive for right-skewed
         assert!(
@@ -326,7 +331,7 @@ mod tests {
     fn test_heavy_tailed_data() {
         // Data with outliers (heavy tails)
         let data = vec![-10.0, -1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 10.0];
-        let result = calculate_distribution_parameters(&data, false).unwrap();
+        let result = calculate_distribution_parameters(&data, false, Locale::En).unwrap();
 
         // Should detect high kurtosis
         assert!(result.kurtosis > 0.0);
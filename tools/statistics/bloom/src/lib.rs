@@ -0,0 +1,156 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{BloomInput as LogicInput, BloomOutput as LogicOutput, process_bloom_filter};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BloomInput {
+    /// Keys to add to the filter
+    pub keys: Option<Vec<String>>,
+    /// Base64 filters returned by earlier calls, merged together with keys;
+    /// all must have the same size
+    pub filters: Option<Vec<String>>,
+    /// Keys to test for membership after adding keys and merging filters
+    pub queries: Option<Vec<String>>,
+    /// Number of keys a new filter is sized for (default: 10000); ignored when merging
+    pub expected_items: Option<u64>,
+    /// Target false-positive rate of a new filter at expected_items keys, between 0 and 1
+    /// (default: 0.01); ignored when merging
+    pub false_positive_rate: Option<f64>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Membership {
+    /// Queried key
+    pub key: String,
+    /// False means the key was never added; true means it probably was
+    pub possibly_present: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BloomOutput {
+    /// Base64 filter with its parameters; pass it back in filters to add, merge or query more
+    pub filter: String,
+    /// Size of the filter in bits
+    pub bits: u64,
+    /// Number of hash functions per key
+    pub hash_functions: u32,
+    /// Number of bits currently set
+    pub bits_set: u64,
+    /// Number of keys the filter was sized for
+    pub expected_items: u64,
+    /// False-positive rate the filter was sized for
+    pub target_false_positive_rate: f64,
+    /// Current false-positive rate given the bits set so far
+    pub false_positive_rate: f64,
+    /// Keys added over the filter's lifetime, counting repeats
+    pub items_added: u64,
+    /// Distinct keys estimated from the bits set
+    pub estimated_items: f64,
+    /// Number of filters merged in this call
+    pub filters_merged: usize,
+    /// Membership result for each query, in order
+    pub membership: Vec<Membership>,
+}
+
+/// Build, merge and query Bloom filters of string keys, so pipelines working in chunks
+/// can skip keys already seen in earlier chunks
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn bloom(request: Request<BloomInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("bloom", BloomInput, BloomOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("bloom", &input);
+    log.finish(run_bloom(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_bloom(input: BloomInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    let filters = input.filters.as_deref().unwrap_or_default();
+    let guard = Guard::from_env()
+        .array("keys", input.keys.as_deref().unwrap_or_default())
+        .array("filters", filters)
+        .array("queries", input.queries.as_deref().unwrap_or_default());
+    let guard = filters
+        .iter()
+        .fold(guard, |guard, filter| guard.string("filters", filter));
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        keys: input.keys,
+        filters: input.filters,
+        queries: input.queries,
+        expected_items: input.expected_items,
+        false_positive_rate: input.false_positive_rate,
+    };
+
+    // Call logic implementation
+    match logic::process_bloom_filter(logic_input) {
+        Ok(result) => {
+            // Convert back to wrapper types
+            let response = BloomOutput {
+                filter: result.filter,
+                bits: result.bits,
+                hash_functions: result.hash_functions,
+                bits_set: result.bits_set,
+                expected_items: result.expected_items,
+                target_false_positive_rate: result.target_false_positive_rate,
+                false_positive_rate: result.false_positive_rate,
+                items_added: result.items_added,
+                estimated_items: result.estimated_items,
+                filters_merged: result.filters_merged,
+                membership: result
+                    .membership
+                    .into_iter()
+                    .map(|m| Membership {
+                        key: m.key,
+                        possibly_present: m.possibly_present,
+                    })
+                    .collect(),
+            };
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
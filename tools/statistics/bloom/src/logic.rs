@@ -0,0 +1,391 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::LN_2;
+
+pub const DEFAULT_EXPECTED_ITEMS: u64 = 10_000;
+pub const DEFAULT_FALSE_POSITIVE_RATE: f64 = 0.01;
+/// Largest filter, in bits (8 MiB before base64)
+pub const MAX_BITS: u64 = 1 << 26;
+
+/// Leading bytes of a serialized filter: magic "BF" and the format version
+const HEADER: [u8; 3] = [b'B', b'F', 1];
+/// Header, bit count, hash count, expected items, target rate and items added
+const FIXED_LEN: usize = HEADER.len() + 8 + 4 + 8 + 8 + 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BloomInput {
+    pub keys: Option<Vec<String>>,
+    pub filters: Option<Vec<String>>,
+    pub queries: Option<Vec<String>>,
+    pub expected_items: Option<u64>,
+    pub false_positive_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Membership {
+    pub key: String,
+    pub possibly_present: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BloomOutput {
+    pub filter: String,
+    pub bits: u64,
+    pub hash_functions: u32,
+    pub bits_set: u64,
+    pub expected_items: u64,
+    pub target_false_positive_rate: f64,
+    pub false_positive_rate: f64,
+    pub items_added: u64,
+    pub estimated_items: f64,
+    pub filters_merged: usize,
+    pub membership: Vec<Membership>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Filter {
+    bits: u64,
+    hash_functions: u32,
+    expected_items: u64,
+    target_rate: f64,
+    /// Keys added over the filter's lifetime, including duplicates
+    items_added: u64,
+    words: Vec<u8>,
+}
+
+impl Filter {
+    /// Size a filter for `expected_items` keys at the target false-positive rate
+    fn with_capacity(expected_items: u64, target_rate: f64) -> Result<Self, String> {
+        let n = expected_items as f64;
+        let bits = (-n * target_rate.ln() / (LN_2 * LN_2)).ceil().max(8.0);
+        if bits > MAX_BITS as f64 {
+            return Err(format!(
+                "A filter for {expected_items} items at rate {target_rate} needs {bits} bits, more than the {MAX_BITS} allowed"
+            ));
+        }
+        let bits = bits as u64;
+        let hash_functions = ((bits as f64 / n) * LN_2).round().clamp(1.0, 32.0) as u32;
+        Ok(Filter {
+            bits,
+            hash_functions,
+            expected_items,
+            target_rate,
+            items_added: 0,
+            words: vec![0; bits.div_ceil(8) as usize],
+        })
+    }
+
+    /// Bit positions for a key, by double hashing (Kirsch & Mitzenmacher)
+    fn positions(&self, key: &str) -> impl Iterator<Item = u64> + use<> {
+        let h1 = hash64(key.as_bytes(), 0xcbf29ce484222325);
+        let h2 = hash64(key.as_bytes(), 0x84222325cbf29ce4) | 1;
+        let bits = self.bits;
+        (0..u64::from(self.hash_functions)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % bits)
+    }
+
+    fn insert(&mut self, key: &str) {
+        for bit in self.positions(key) {
+            self.words[(bit / 8) as usize] |= 1 << (bit % 8);
+        }
+        self.items_added += 1;
+    }
+
+    fn contains(&self, key: &str) -> bool {
+        self.positions(key)
+            .all(|bit| self.words[(bit / 8) as usize] & (1 << (bit % 8)) != 0)
+    }
+
+    fn merge(&mut self, other: &Filter) -> Result<(), String> {
+        if (other.bits, other.hash_functions) != (self.bits, self.hash_functions) {
+            return Err(format!(
+                "filters must have the same size to merge: {} bits with {} hashes vs {} bits with {} hashes",
+                self.bits, self.hash_functions, other.bits, other.hash_functions
+            ));
+        }
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+        self.items_added += other.items_added;
+        Ok(())
+    }
+
+    fn bits_set(&self) -> u64 {
+        self.words.iter().map(|w| u64::from(w.count_ones())).sum()
+    }
+
+    /// Chance that a key never added is reported present, given the bits set so far
+    fn false_positive_rate(&self) -> f64 {
+        (self.bits_set() as f64 / self.bits as f64).powi(self.hash_functions as i32)
+    }
+
+    /// Distinct keys estimated from the fill ratio (Swamidass & Baldi)
+    fn estimated_items(&self) -> f64 {
+        let m = self.bits as f64;
+        let fill = self.bits_set() as f64 / m;
+        if fill >= 1.0 {
+            return f64::INFINITY;
+        }
+        -m / f64::from(self.hash_functions) * (1.0 - fill).ln()
+    }
+
+    fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(FIXED_LEN + self.words.len());
+        bytes.extend_from_slice(&HEADER);
+        bytes.extend_from_slice(&self.bits.to_le_bytes());
+        bytes.extend_from_slice(&self.hash_functions.to_le_bytes());
+        bytes.extend_from_slice(&self.expected_items.to_le_bytes());
+        bytes.extend_from_slice(&self.target_rate.to_le_bytes());
+        bytes.extend_from_slice(&self.items_added.to_le_bytes());
+        bytes.extend_from_slice(&self.words);
+        general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn decode(encoded: &str) -> Result<Self, String> {
+        let bytes = general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("invalid base64: {e}"))?;
+        if bytes.len() < FIXED_LEN || bytes[..HEADER.len()] != HEADER {
+            return Err("not a serialized Bloom filter".to_string());
+        }
+        let u64_at =
+            |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let bits = u64_at(3);
+        let hash_functions = u32::from_le_bytes(bytes[11..15].try_into().unwrap());
+        let expected_items = u64_at(15);
+        let target_rate = f64::from_bits(u64_at(23));
+        let items_added = u64_at(31);
+        let words = bytes[FIXED_LEN..].to_vec();
+
+        if bits == 0 || bits > MAX_BITS || hash_functions == 0 {
+            return Err("filter parameters are out of range".to_string());
+        }
+        if words.len() as u64 != bits.div_ceil(8) {
+            return Err(format!(
+                "expected {} bytes of bits, found {}",
+                bits.div_ceil(8),
+                words.len()
+            ));
+        }
+        Ok(Filter {
+            bits,
+            hash_functions,
+            expected_items,
+            target_rate,
+            items_added,
+            words,
+        })
+    }
+}
+
+/// FNV-1a from the given offset basis followed by the MurmurHash3 finalizer;
+/// stable across platforms and releases
+fn hash64(bytes: &[u8], basis: u64) -> u64 {
+    let mut hash = basis;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^ (hash >> 33)
+}
+
+pub fn process_bloom_filter(input: BloomInput) -> Result<BloomOutput, String> {
+    let keys = input.keys.unwrap_or_default();
+    let encoded = input.filters.unwrap_or_default();
+    let queries = input.queries.unwrap_or_default();
+    if keys.is_empty() && encoded.is_empty() && input.expected_items.is_none() {
+        return Err(
+            "Provide keys, filters to merge, or expected_items for an empty filter".to_string(),
+        );
+    }
+
+    let mut filters = encoded
+        .iter()
+        .enumerate()
+        .map(|(i, f)| Filter::decode(f).map_err(|e| format!("filters[{i}]: {e}")));
+    let filters_merged = encoded.len();
+
+    // Sizing options only apply when no existing filter is given
+    let mut filter = match filters.next() {
+        Some(first) => {
+            let mut filter = first?;
+            for (i, other) in filters.enumerate() {
+                filter
+                    .merge(&other?)
+                    .map_err(|e| format!("filters[{}]: {e}", i + 1))?;
+            }
+            filter
+        }
+        None => {
+            let expected_items = input.expected_items.unwrap_or(DEFAULT_EXPECTED_ITEMS);
+            if expected_items == 0 {
+                return Err("expected_items must be at least 1".to_string());
+            }
+            let target_rate = input
+                .false_positive_rate
+                .unwrap_or(DEFAULT_FALSE_POSITIVE_RATE);
+            if !(target_rate > 0.0 && target_rate < 1.0) {
+                return Err(format!(
+                    "false_positive_rate must be between 0 and 1 (exclusive), got {target_rate}"
+                ));
+            }
+            Filter::with_capacity(expected_items, target_rate)?
+        }
+    };
+
+    for key in &keys {
+        filter.insert(key);
+    }
+    let membership = queries
+        .into_iter()
+        .map(|key| Membership {
+            possibly_present: filter.contains(&key),
+            key,
+        })
+        .collect();
+
+    Ok(BloomOutput {
+        filter: filter.encode(),
+        bits: filter.bits,
+        hash_functions: filter.hash_functions,
+        bits_set: filter.bits_set(),
+        expected_items: filter.expected_items,
+        target_false_positive_rate: filter.target_rate,
+        false_positive_rate: filter.false_positive_rate(),
+        items_added: filter.items_added,
+        estimated_items: filter.estimated_items(),
+        filters_merged,
+        membership,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(keys: Vec<String>) -> BloomInput {
+        BloomInput {
+            keys: Some(keys),
+            filters: None,
+            queries: None,
+            expected_items: None,
+            false_positive_rate: None,
+        }
+    }
+
+    fn ids(range: std::ops::Range<u32>) -> Vec<String> {
+        range.map(|i| format!("order-{i}")).collect()
+    }
+
+    #[test]
+    fn test_sizing() {
+        let mut request = input(vec![]);
+        request.expected_items = Some(1_000);
+        let result = process_bloom_filter(request).unwrap();
+        // m = -n ln(p) / ln(2)^2 and k = m / n ln(2) for n = 1000, p = 0.01
+        assert_eq!(result.bits, 9_586);
+        assert_eq!(result.hash_functions, 7);
+        assert_eq!(result.bits_set, 0);
+        assert_eq!(result.false_positive_rate, 0.0);
+    }
+
+    #[test]
+    fn test_no_false_negatives_and_bounded_false_positives() {
+        let mut request = input(ids(0..10_000));
+        request.queries = Some(ids(0..20_000));
+        let result = process_bloom_filter(request).unwrap();
+
+        assert!(
+            result.membership[..10_000]
+                .iter()
+                .all(|m| m.possibly_present)
+        );
+        let false_positives = result.membership[10_000..]
+            .iter()
+            .filter(|m| m.possibly_present)
+            .count();
+        // Target is 1%: 100 expected out of 10000 absent keys
+        assert!(false_positives < 200, "{false_positives}");
+        assert!((result.false_positive_rate - 0.01).abs() < 0.005);
+        assert!((result.estimated_items - 10_000.0).abs() < 300.0);
+        assert_eq!(result.membership[0].key, "order-0");
+    }
+
+    #[test]
+    fn test_chunked_and_merged_filters_match() {
+        let whole = process_bloom_filter(input(ids(0..3_000))).unwrap();
+
+        let mut filter: Option<String> = None;
+        for start in (0..3_000).step_by(1_000) {
+            let result = process_bloom_filter(BloomInput {
+                keys: Some(ids(start..start + 1_000)),
+                filters: filter.map(|f| vec![f]),
+                queries: None,
+                expected_items: None,
+                false_positive_rate: None,
+            })
+            .unwrap();
+            filter = Some(result.filter);
+        }
+        assert_eq!(filter.unwrap(), whole.filter);
+
+        let a = process_bloom_filter(input(ids(0..1_500))).unwrap().filter;
+        let b = process_bloom_filter(input(ids(1_500..3_000)))
+            .unwrap()
+            .filter;
+        let merged = process_bloom_filter(BloomInput {
+            keys: None,
+            filters: Some(vec![a, b]),
+            queries: Some(vec!["order-2999".to_string()]),
+            expected_items: None,
+            false_positive_rate: None,
+        })
+        .unwrap();
+        assert_eq!(merged.filter, whole.filter);
+        assert_eq!(merged.filters_merged, 2);
+        assert!(merged.membership[0].possibly_present);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(process_bloom_filter(input(vec![])).is_err());
+
+        let mut request = input(ids(0..1));
+        request.false_positive_rate = Some(1.0);
+        assert_eq!(
+            process_bloom_filter(request).unwrap_err(),
+            "false_positive_rate must be between 0 and 1 (exclusive), got 1"
+        );
+
+        let mut request = input(ids(0..1));
+        request.expected_items = Some(u64::MAX);
+        assert!(process_bloom_filter(request).is_err());
+
+        let small = process_bloom_filter(BloomInput {
+            expected_items: Some(10),
+            ..input(vec![])
+        })
+        .unwrap()
+        .filter;
+        let large = process_bloom_filter(input(ids(0..1))).unwrap().filter;
+        let error = process_bloom_filter(BloomInput {
+            filters: Some(vec![small, large]),
+            ..input(vec![])
+        })
+        .unwrap_err();
+        assert!(
+            error.starts_with("filters[1]: filters must have the same size"),
+            "{error}"
+        );
+
+        let error = process_bloom_filter(BloomInput {
+            filters: Some(vec!["bm90IGEgZmlsdGVy".to_string()]),
+            ..input(vec![])
+        })
+        .unwrap_err();
+        assert_eq!(error, "filters[0]: not a serialized Bloom filter");
+    }
+}
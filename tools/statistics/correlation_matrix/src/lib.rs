@@ -3,14 +3,22 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
     CorrelationMatrixOutput as LogicCorrelationMatrixOutput,
-    MultiSeriesInput as LogicMultiSeriesInput,
+    MultiSeriesInput as LogicMultiSeriesInput, calculate_correlation_matrix,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -20,6 +28,12 @@ pub struct MultiSeriesInput {
     pub data: Vec<Vec<f64>>,
     /// Optional names for each variable (if not provided, will use Variable_1, Variable_2, etc.)
     pub variable_names: Option<Vec<String>>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -32,8 +46,42 @@ pub struct CorrelationMatrixOutput {
     pub sample_size: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn correlation_matrix(input: MultiSeriesInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn correlation_matrix(request: Request<MultiSeriesInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "correlation_matrix",
+                MultiSeriesInput,
+                CorrelationMatrixOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("correlation_matrix", &input);
+    log.finish(run_correlation_matrix(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_correlation_matrix(input: MultiSeriesInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("data", &input.data)
+        .array_len("data", input.data.iter().map(Vec::len).sum())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicMultiSeriesInput {
         data: input.data,
@@ -41,7 +89,11 @@ pub fn correlation_matrix(input: MultiSeriesInput) -> ToolResponse {
     };
 
     // Call logic implementation
-    match logic::calculate_correlation_matrix(logic_input) {
+    match ftl_result_cache::cached_async("correlation_matrix", &logic_input, || {
+        logic::calculate_correlation_matrix(logic_input.clone())
+    })
+    .await
+    {
         Ok(result) => {
             // Convert back to wrapper types
             let response = CorrelationMatrixOutput {
@@ -49,7 +101,10 @@ pub fn correlation_matrix(input: MultiSeriesInput) -> ToolResponse {
                 correlation_matrix: result.correlation_matrix,
                 sample_size: result.sample_size,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,3 +1,4 @@
+use ftl_yield::{DEFAULT_YIELD_INTERVAL, Yielder};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,7 +28,7 @@ pub struct CorrelationOutput {
     pub interpretation: String,
 }
 
-pub fn calculate_correlation_matrix(
+pub async fn calculate_correlation_matrix(
     input: MultiSeriesInput,
 ) -> Result<CorrelationMatrixOutput, String> {
     if input.data.is_empty() {
@@ -63,9 +64,13 @@ pub fn calculate_correlation_matrix(
     // Create correlation matrix
     let mut correlation_matrix = vec![vec![0.0; num_variables]; num_variables];
 
+    // Each pair costs O(sample_size), so yield after a comparable amount of work
+    let mut yielder = Yielder::new(DEFAULT_YIELD_INTERVAL.div_ceil(sample_size));
+
     #[allow(clippy::needless_range_loop)]
     for i in 0..num_variables {
         for j in 0..num_variables {
+            yielder.tick().await;
             if i == j {
                 correlation_matrix[i][j] = 1.0;
             } else {
@@ -240,6 +245,13 @@ fn standard_normal_cdf(x: f64) -> f64 {
 mod tests {
     use super::*;
 
+    // Drive the async logic to completion from synchronous tests
+    fn calculate_correlation_matrix(
+        input: MultiSeriesInput,
+    ) -> Result<CorrelationMatrixOutput, String> {
+        ftl_yield::block_on(super::calculate_correlation_matrix(input))
+    }
+
     #[test]
     fn test_identity_matrix_single_variable() {
         let input = MultiSeriesInput {
@@ -0,0 +1,86 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    DecisionTreePredictInput as LogicInput, DecisionTreePredictOutput as LogicOutput,
+    decision_tree_predict_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    DecisionTreePredictOutputSchema as DecisionTreePredictOutput,
+    DecisionTreePredictionSchema as DecisionTreePrediction,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DecisionTreePredictInput {
+    #[serde(flatten)]
+    pub params: logic::DecisionTreePredictInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Apply a tree from decision_tree_train to feature rows, returning each row's predicted
+/// class or value and the conditions it met on the way to its leaf
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn decision_tree_predict(request: Request<DecisionTreePredictInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "decision_tree_predict",
+                DecisionTreePredictInput,
+                DecisionTreePredictOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("decision_tree_predict", &input);
+    log.finish(run_decision_tree_predict(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_decision_tree_predict(input: DecisionTreePredictInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("features", &input.params.features)
+        .array_len("features", input.params.features.iter().map(Vec::len).sum())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::decision_tree_predict_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &DecisionTreePredictOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,248 @@
+use decision_tree_train_tool::{
+    LogicTreeNode as TreeNode, MAX_DEPTH, MAX_VALUES, TreeNode as TreeNodeSchema, leaf_for,
+};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DecisionTreePredictInput {
+    /// Tree returned by decision_tree_train
+    #[tool_types(nested)]
+    pub tree: TreeNode,
+    /// Rows to predict, with the same features, in the same order, as the training rows
+    pub features: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DecisionTreePrediction {
+    /// Predicted class, for classification trees
+    pub label: Option<String>,
+    /// Share of the leaf's training rows in the predicted class
+    pub confidence: Option<f64>,
+    /// Predicted value, for regression trees
+    pub value: Option<f64>,
+    /// Conditions the row satisfied from the root to its leaf
+    pub path: Vec<String>,
+    /// Training rows in the leaf
+    pub leaf_samples: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DecisionTreePredictOutput {
+    /// One prediction per row, in input order
+    #[tool_types(nested)]
+    pub predictions: Vec<DecisionTreePrediction>,
+    /// "classification" or "regression"
+    pub task: String,
+}
+
+/// Checks the tree's shape, returning the highest feature index it splits on and whether
+/// its leaves carry labels
+fn check_tree(node: &TreeNode, depth: usize) -> Result<(Option<usize>, bool), String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("tree is deeper than {MAX_DEPTH} splits"));
+    }
+    match (node.children.as_slice(), node.feature, node.threshold) {
+        ([], _, _) => match (&node.label, node.value) {
+            (Some(_), None) => Ok((None, true)),
+            (None, Some(value)) if value.is_finite() => Ok((None, false)),
+            _ => Err("every leaf needs either a label or a finite value".to_string()),
+        },
+        ([yes, no], Some(feature), Some(threshold)) if threshold.is_finite() => {
+            let (yes_feature, yes_labeled) = check_tree(yes, depth + 1)?;
+            let (no_feature, no_labeled) = check_tree(no, depth + 1)?;
+            if yes_labeled != no_labeled {
+                return Err("tree mixes classification and regression leaves".to_string());
+            }
+            Ok((
+                [Some(feature), yes_feature, no_feature]
+                    .into_iter()
+                    .flatten()
+                    .max(),
+                yes_labeled,
+            ))
+        }
+        _ => Err(
+            "every split needs a feature, a finite threshold and exactly two children".to_string(),
+        ),
+    }
+}
+
+/// Conditions met on the way from `node` to the row's leaf
+fn path_for(node: &TreeNode, row: &[f64], path: &mut Vec<String>) {
+    if let ([yes, no], Some(feature), Some(threshold)) =
+        (node.children.as_slice(), node.feature, node.threshold)
+    {
+        let name = node
+            .feature_name
+            .clone()
+            .unwrap_or_else(|| format!("feature_{feature}"));
+        if row[feature] <= threshold {
+            path.push(format!("{name} <= {threshold}"));
+            path_for(yes, row, path);
+        } else {
+            path.push(format!("{name} > {threshold}"));
+            path_for(no, row, path);
+        }
+    }
+}
+
+pub fn decision_tree_predict_logic(
+    input: DecisionTreePredictInput,
+) -> Result<DecisionTreePredictOutput, String> {
+    let (highest_feature, classification) =
+        check_tree(&input.tree, 0).map_err(|e| format!("tree: {e}"))?;
+    let width = input.features.first().map_or(0, Vec::len);
+    if input.features.is_empty() {
+        return Err("features must contain at least one row".to_string());
+    }
+    if input.features.len().saturating_mul(width) > MAX_VALUES {
+        return Err(format!("At most {MAX_VALUES} feature values are allowed"));
+    }
+    if let Some(row) = input.features.iter().position(|r| r.len() != width) {
+        return Err(format!(
+            "features row {row} has {} values, expected {width}",
+            input.features[row].len()
+        ));
+    }
+    if input.features.iter().flatten().any(|v| !v.is_finite()) {
+        return Err("features must contain finite numbers".to_string());
+    }
+    if let Some(feature) = highest_feature.filter(|f| *f >= width) {
+        return Err(format!(
+            "The tree splits on feature {feature} but rows have {width} features"
+        ));
+    }
+
+    let predictions = input
+        .features
+        .iter()
+        .map(|row| {
+            let leaf = leaf_for(&input.tree, row);
+            let mut path = Vec::new();
+            path_for(&input.tree, row, &mut path);
+            DecisionTreePrediction {
+                label: leaf.label.clone(),
+                confidence: leaf.confidence,
+                value: leaf.value,
+                path,
+                leaf_samples: leaf.samples,
+            }
+        })
+        .collect();
+
+    Ok(DecisionTreePredictOutput {
+        predictions,
+        task: if classification {
+            "classification"
+        } else {
+            "regression"
+        }
+        .to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use decision_tree_train_tool::{LogicInput as TrainInput, decision_tree_train_logic};
+
+    fn train(features: Vec<Vec<f64>>, labels: &[&str]) -> TreeNode {
+        decision_tree_train_logic(TrainInput {
+            features,
+            labels: Some(labels.iter().map(|l| l.to_string()).collect()),
+            targets: None,
+            feature_names: Some(vec!["width".to_string(), "height".to_string()]),
+            max_depth: None,
+            min_samples_split: None,
+            min_samples_leaf: None,
+            criterion: None,
+        })
+        .unwrap()
+        .tree
+    }
+
+    fn predict(
+        tree: TreeNode,
+        features: Vec<Vec<f64>>,
+    ) -> Result<DecisionTreePredictOutput, String> {
+        decision_tree_predict_logic(DecisionTreePredictInput { tree, features })
+    }
+
+    #[test]
+    fn test_prediction_path() {
+        // Wide shapes are "wide"; narrow ones split again by height
+        let tree = train(
+            vec![
+                vec![8.0, 1.0],
+                vec![9.0, 5.0],
+                vec![1.0, 1.0],
+                vec![2.0, 2.0],
+                vec![1.0, 8.0],
+                vec![2.0, 9.0],
+            ],
+            &["wide", "wide", "small", "small", "tall", "tall"],
+        );
+        let result = predict(tree, vec![vec![10.0, 0.0], vec![1.5, 7.0]]).unwrap();
+        assert_eq!(result.task, "classification");
+        assert_eq!(result.predictions[0].label.as_deref(), Some("wide"));
+        assert_eq!(result.predictions[1].label.as_deref(), Some("tall"));
+        assert_eq!(result.predictions[1].path, vec!["width <= 5", "height > 5"]);
+        assert_eq!(result.predictions[1].leaf_samples, 2);
+    }
+
+    #[test]
+    fn test_tree_round_trips_through_json() {
+        let tree = train(
+            vec![
+                vec![1.0, 0.0],
+                vec![2.0, 0.0],
+                vec![3.0, 0.0],
+                vec![4.0, 0.0],
+            ],
+            &["a", "a", "b", "b"],
+        );
+        let json = serde_json::to_string(&TreeNodeSchema::from(tree.clone())).unwrap();
+        let parsed: TreeNodeSchema = serde_json::from_str(&json).unwrap();
+        assert_eq!(TreeNode::from(parsed), tree);
+    }
+
+    #[test]
+    fn test_regression_leaf() {
+        let leaf = TreeNode {
+            feature: None,
+            feature_name: None,
+            threshold: None,
+            rule: None,
+            samples: 4,
+            impurity: 0.0,
+            label: None,
+            confidence: None,
+            value: Some(2.5),
+            children: Vec::new(),
+        };
+        let result = predict(leaf, vec![vec![1.0, 2.0]]).unwrap();
+        assert_eq!(result.task, "regression");
+        assert_eq!(result.predictions[0].value, Some(2.5));
+        assert!(result.predictions[0].path.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let tree = train(
+            vec![vec![0.0, 1.0], vec![0.0, 2.0], vec![0.0, 3.0]],
+            &["a", "a", "b"],
+        );
+        assert_eq!(
+            predict(tree.clone(), vec![vec![0.0]]).unwrap_err(),
+            "The tree splits on feature 1 but rows have 1 features"
+        );
+        let mut broken = tree;
+        broken.children.pop();
+        assert!(
+            predict(broken, vec![vec![0.0, 1.0]])
+                .unwrap_err()
+                .starts_with("tree: every split")
+        );
+    }
+}
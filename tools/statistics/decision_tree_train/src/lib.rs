@@ -0,0 +1,97 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    DEFAULT_MAX_DEPTH, DecisionTreeTrainInput as LogicInput,
+    DecisionTreeTrainOutput as LogicOutput, MAX_CLASSES, MAX_DEPTH, MAX_VALUES,
+    TreeNode as LogicTreeNode, decision_tree_train_logic, leaf_for, leaf_rules, tree_depth,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    DecisionTreeTrainOutputSchema as DecisionTreeTrainOutput,
+    FeatureImportanceSchema as FeatureImportance, TreeNodeSchema as TreeNode,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DecisionTreeTrainInput {
+    #[serde(flatten)]
+    pub params: logic::DecisionTreeTrainInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Grow a depth-limited CART classification or regression tree from labeled feature
+/// rows, returning the tree as nested rules, one readable rule per leaf and feature
+/// importances; pass the tree to decision_tree_predict
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn decision_tree_train(request: Request<DecisionTreeTrainInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "decision_tree_train",
+                DecisionTreeTrainInput,
+                DecisionTreeTrainOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("decision_tree_train", &input);
+    log.finish(run_decision_tree_train(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_decision_tree_train(input: DecisionTreeTrainInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("features", &input.params.features)
+        .array_len("features", input.params.features.iter().map(Vec::len).sum())
+        .array("labels", input.params.labels.as_deref().unwrap_or_default())
+        .array(
+            "targets",
+            input.params.targets.as_deref().unwrap_or_default(),
+        )
+        .array(
+            "feature_names",
+            input.params.feature_names.as_deref().unwrap_or_default(),
+        )
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::decision_tree_train_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &DecisionTreeTrainOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
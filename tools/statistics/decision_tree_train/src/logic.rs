@@ -0,0 +1,649 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most feature values, rows times columns, accepted in one call
+pub const MAX_VALUES: usize = 200_000;
+/// Deepest tree that can be grown or applied
+pub const MAX_DEPTH: usize = 20;
+/// Default depth limit
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+/// Most distinct classes in one tree
+pub const MAX_CLASSES: usize = 1_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DecisionTreeTrainInput {
+    /// Training rows, one feature vector per observation
+    pub features: Vec<Vec<f64>>,
+    /// Class label of each row, for a classification tree
+    pub labels: Option<Vec<String>>,
+    /// Numeric target of each row, for a regression tree
+    pub targets: Option<Vec<f64>>,
+    /// Name of each feature, used in rules (default: feature_0, feature_1, ...)
+    pub feature_names: Option<Vec<String>>,
+    /// Most splits from the root to a leaf, 0-20 (default: 5)
+    pub max_depth: Option<usize>,
+    /// Fewest rows a node needs before it may be split (default: 2)
+    pub min_samples_split: Option<usize>,
+    /// Fewest rows allowed on either side of a split (default: 1)
+    pub min_samples_leaf: Option<usize>,
+    /// "gini" or "entropy" for classification; regression always uses squared error
+    /// (default: gini)
+    pub criterion: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToolTypes)]
+pub struct TreeNode {
+    /// Index of the split feature; absent on leaves
+    pub feature: Option<usize>,
+    /// Name of the split feature
+    pub feature_name: Option<String>,
+    /// Rows whose feature is at most this value follow the first child, the rest the second
+    pub threshold: Option<f64>,
+    /// The split as text, e.g. "age <= 42.5"
+    pub rule: Option<String>,
+    /// Training rows that reached this node
+    pub samples: usize,
+    /// Gini, entropy or variance of those rows
+    pub impurity: f64,
+    /// Classification: majority class of those rows, the first in sorted order on ties
+    pub label: Option<String>,
+    /// Classification: share of those rows in the majority class
+    pub confidence: Option<f64>,
+    /// Regression: mean target of those rows
+    pub value: Option<f64>,
+    /// Empty on leaves; otherwise the branch where the rule holds, then the one where
+    /// it does not
+    #[tool_types(nested)]
+    pub children: Vec<TreeNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct FeatureImportance {
+    pub feature: usize,
+    pub name: String,
+    /// Share of the tree's total impurity decrease gained from splits on this feature
+    pub importance: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct DecisionTreeTrainOutput {
+    /// The fitted tree; pass it unchanged to decision_tree_predict
+    #[tool_types(nested)]
+    pub tree: TreeNode,
+    /// One "IF ... THEN ..." rule per leaf, left to right
+    pub rules: Vec<String>,
+    /// Every feature, in input order
+    #[tool_types(nested)]
+    pub feature_importances: Vec<FeatureImportance>,
+    /// "classification" or "regression"
+    pub task: String,
+    /// "gini", "entropy" or "squared_error"
+    pub criterion: String,
+    pub feature_count: usize,
+    /// Splits on the longest root-to-leaf path
+    pub depth: usize,
+    pub leaf_count: usize,
+    /// Classification: fraction of training rows the tree classifies correctly
+    pub training_accuracy: Option<f64>,
+    /// Regression: root mean squared error on the training rows
+    pub training_rmse: Option<f64>,
+}
+
+/// Row targets: class indices into a sorted label list, or numbers
+enum Targets<'a> {
+    Classes(Vec<usize>, Vec<&'a String>),
+    Values(&'a [f64]),
+}
+
+struct Grower<'a> {
+    features: &'a [Vec<f64>],
+    targets: Targets<'a>,
+    names: Vec<String>,
+    entropy: bool,
+    max_depth: usize,
+    min_samples_split: usize,
+    min_samples_leaf: usize,
+    /// Weighted impurity decrease per feature
+    gains: Vec<f64>,
+}
+
+/// Running sums of the targets of a set of rows, enough to compute its impurity
+#[derive(Clone)]
+enum Tally {
+    Counts(Vec<usize>, usize),
+    Moments { n: usize, sum: f64, sum_sq: f64 },
+}
+
+impl Tally {
+    fn add(&mut self, targets: &Targets, row: usize, sign: isize) {
+        match (self, targets) {
+            (Tally::Counts(counts, n), Targets::Classes(classes, _)) => {
+                counts[classes[row]] = counts[classes[row]].wrapping_add_signed(sign);
+                *n = n.wrapping_add_signed(sign);
+            }
+            (Tally::Moments { n, sum, sum_sq }, Targets::Values(values)) => {
+                *n = n.wrapping_add_signed(sign);
+                *sum += sign as f64 * values[row];
+                *sum_sq += sign as f64 * values[row] * values[row];
+            }
+            _ => unreachable!("tally and targets share a task"),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Tally::Counts(_, n) | Tally::Moments { n, .. } => *n,
+        }
+    }
+
+    fn impurity(&self, entropy: bool) -> f64 {
+        match self {
+            Tally::Counts(_, 0) | Tally::Moments { n: 0, .. } => 0.0,
+            Tally::Counts(counts, n) => {
+                let shares = counts
+                    .iter()
+                    .filter(|c| **c > 0)
+                    .map(|c| *c as f64 / *n as f64);
+                if entropy {
+                    shares.map(|p| -p * p.log2()).sum()
+                } else {
+                    1.0 - shares.map(|p| p * p).sum::<f64>()
+                }
+            }
+            Tally::Moments { n, sum, sum_sq } => {
+                let mean = sum / *n as f64;
+                (sum_sq / *n as f64 - mean * mean).max(0.0)
+            }
+        }
+    }
+}
+
+impl Grower<'_> {
+    fn tally(&self, rows: &[usize]) -> Tally {
+        let mut tally = match &self.targets {
+            Targets::Classes(_, labels) => Tally::Counts(vec![0; labels.len()], 0),
+            Targets::Values(_) => Tally::Moments {
+                n: 0,
+                sum: 0.0,
+                sum_sq: 0.0,
+            },
+        };
+        for &row in rows {
+            tally.add(&self.targets, row, 1);
+        }
+        tally
+    }
+
+    /// Best (feature, threshold, weighted child impurity) over all features, if any
+    /// split satisfies min_samples_leaf
+    fn best_split(&self, rows: &[usize], whole: &Tally) -> Option<(usize, f64, f64)> {
+        let n = rows.len() as f64;
+        let mut best: Option<(usize, f64, f64)> = None;
+        for feature in 0..self.names.len() {
+            let mut sorted = rows.to_vec();
+            sorted
+                .sort_by(|a, b| self.features[*a][feature].total_cmp(&self.features[*b][feature]));
+            let mut left = self.tally(&[]);
+            let mut right = whole.clone();
+            for pair in sorted.windows(2) {
+                left.add(&self.targets, pair[0], 1);
+                right.add(&self.targets, pair[0], -1);
+                let (here, next) = (
+                    self.features[pair[0]][feature],
+                    self.features[pair[1]][feature],
+                );
+                if here == next
+                    || left.len() < self.min_samples_leaf
+                    || right.len() < self.min_samples_leaf
+                {
+                    continue;
+                }
+                let score = (left.len() as f64 * left.impurity(self.entropy)
+                    + right.len() as f64 * right.impurity(self.entropy))
+                    / n;
+                // Strictly better only, so earlier features and thresholds win ties
+                if best.is_none_or(|(_, _, s)| score < s - 1e-12) {
+                    let midpoint = here + (next - here) / 2.0;
+                    // Keep the threshold below the upper value if the midpoint rounds up
+                    let threshold = if midpoint < next { midpoint } else { here };
+                    best = Some((feature, threshold, score));
+                }
+            }
+        }
+        best
+    }
+
+    fn grow(&mut self, rows: &[usize], depth: usize) -> TreeNode {
+        let tally = self.tally(rows);
+        let impurity = match &self.targets {
+            // Two passes, so constant targets give exactly zero variance
+            Targets::Values(values) => {
+                let mean = rows.iter().map(|r| values[*r]).sum::<f64>() / rows.len() as f64;
+                rows.iter()
+                    .map(|r| (values[*r] - mean).powi(2))
+                    .sum::<f64>()
+                    / rows.len() as f64
+            }
+            Targets::Classes(..) => tally.impurity(self.entropy),
+        };
+        let (label, confidence, value) = match &tally {
+            Tally::Counts(counts, n) => {
+                let best =
+                    (0..counts.len()).fold(0, |b, c| if counts[c] > counts[b] { c } else { b });
+                let Targets::Classes(_, labels) = &self.targets else {
+                    unreachable!("counts come from classes")
+                };
+                (
+                    Some(labels[best].to_string()),
+                    Some(counts[best] as f64 / *n as f64),
+                    None,
+                )
+            }
+            Tally::Moments { n, sum, .. } => (None, None, Some(sum / *n as f64)),
+        };
+        let mut node = TreeNode {
+            feature: None,
+            feature_name: None,
+            threshold: None,
+            rule: None,
+            samples: rows.len(),
+            impurity,
+            label,
+            confidence,
+            value,
+            children: Vec::new(),
+        };
+        if depth >= self.max_depth || rows.len() < self.min_samples_split || impurity <= 1e-12 {
+            return node;
+        }
+        let Some((feature, threshold, score)) = self.best_split(rows, &tally) else {
+            return node;
+        };
+        if score >= impurity - 1e-12 {
+            return node;
+        }
+
+        let total = self.features.len() as f64;
+        self.gains[feature] += rows.len() as f64 / total * (impurity - score);
+        let (left, right): (Vec<usize>, Vec<usize>) = rows
+            .iter()
+            .partition(|row| self.features[**row][feature] <= threshold);
+        let name = self.names[feature].clone();
+        node.rule = Some(format!("{name} <= {threshold}"));
+        node.feature = Some(feature);
+        node.feature_name = Some(name);
+        node.threshold = Some(threshold);
+        node.children = vec![self.grow(&left, depth + 1), self.grow(&right, depth + 1)];
+        node
+    }
+}
+
+/// Splits on the longest root-to-leaf path
+pub fn tree_depth(node: &TreeNode) -> usize {
+    node.children
+        .iter()
+        .map(|child| tree_depth(child) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// One "IF ... THEN ..." rule per leaf below `node`, left to right
+pub fn leaf_rules(node: &TreeNode, conditions: &mut Vec<String>, rules: &mut Vec<String>) {
+    if let [yes, no] = node.children.as_slice() {
+        let name = node.feature_name.clone().unwrap_or_default();
+        let threshold = node.threshold.unwrap_or_default();
+        conditions.push(format!("{name} <= {threshold}"));
+        leaf_rules(yes, conditions, rules);
+        conditions.pop();
+        conditions.push(format!("{name} > {threshold}"));
+        leaf_rules(no, conditions, rules);
+        conditions.pop();
+        return;
+    }
+    let premise = if conditions.is_empty() {
+        "TRUE".to_string()
+    } else {
+        conditions.join(" AND ")
+    };
+    let outcome = match (&node.label, node.value) {
+        (Some(label), _) => label.clone(),
+        (None, Some(value)) => value.to_string(),
+        (None, None) => String::new(),
+    };
+    rules.push(format!("IF {premise} THEN {outcome}"));
+}
+
+pub fn decision_tree_train_logic(
+    input: DecisionTreeTrainInput,
+) -> Result<DecisionTreeTrainOutput, String> {
+    let width = input.features.first().map_or(0, Vec::len);
+    if width == 0 {
+        return Err("features must contain at least one non-empty row".to_string());
+    }
+    if input.features.len().saturating_mul(width) > MAX_VALUES {
+        return Err(format!("At most {MAX_VALUES} feature values are allowed"));
+    }
+    if let Some(row) = input.features.iter().position(|r| r.len() != width) {
+        return Err(format!(
+            "features row {row} has {} values, expected {width}",
+            input.features[row].len()
+        ));
+    }
+    if input.features.iter().flatten().any(|v| !v.is_finite()) {
+        return Err("features must contain finite numbers".to_string());
+    }
+
+    let n = input.features.len();
+    let targets = match (&input.labels, &input.targets) {
+        (Some(labels), None) if labels.len() == n => {
+            let mut classes: Vec<&String> = labels.iter().collect();
+            classes.sort();
+            classes.dedup();
+            if classes.len() > MAX_CLASSES {
+                return Err(format!(
+                    "At most {MAX_CLASSES} distinct classes are allowed"
+                ));
+            }
+            let indices = labels
+                .iter()
+                .map(|label| classes.binary_search(&label).unwrap())
+                .collect();
+            Targets::Classes(indices, classes)
+        }
+        (None, Some(targets)) if targets.len() == n => {
+            if targets.iter().any(|t| !t.is_finite()) {
+                return Err("targets must be finite numbers".to_string());
+            }
+            Targets::Values(targets)
+        }
+        (Some(values), None) => {
+            return Err(format!("Got {} labels for {n} rows", values.len()));
+        }
+        (None, Some(values)) => {
+            return Err(format!("Got {} targets for {n} rows", values.len()));
+        }
+        _ => {
+            return Err(
+                "Give labels for classification or targets for regression, not both".to_string(),
+            );
+        }
+    };
+
+    let names = match input.feature_names {
+        Some(names) if names.len() != width => {
+            return Err(format!(
+                "Got {} feature_names for {width} features",
+                names.len()
+            ));
+        }
+        Some(names) => names,
+        None => (0..width).map(|f| format!("feature_{f}")).collect(),
+    };
+    let max_depth = input.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+    if max_depth > MAX_DEPTH {
+        return Err(format!("max_depth must be at most {MAX_DEPTH}"));
+    }
+    let min_samples_split = input.min_samples_split.unwrap_or(2);
+    if min_samples_split < 2 {
+        return Err("min_samples_split must be at least 2".to_string());
+    }
+    let min_samples_leaf = input.min_samples_leaf.unwrap_or(1);
+    if min_samples_leaf == 0 {
+        return Err("min_samples_leaf must be at least 1".to_string());
+    }
+    let criterion = match (&targets, input.criterion.as_deref().map(str::trim)) {
+        (Targets::Classes(..), None) => "gini".to_string(),
+        (Targets::Classes(..), Some(c)) if c.eq_ignore_ascii_case("gini") => "gini".to_string(),
+        (Targets::Classes(..), Some(c)) if c.eq_ignore_ascii_case("entropy") => {
+            "entropy".to_string()
+        }
+        (Targets::Classes(..), Some(other)) => {
+            return Err(format!(
+                "Unknown criterion '{other}', use 'gini' or 'entropy'"
+            ));
+        }
+        (Targets::Values(_), None) => "squared_error".to_string(),
+        (Targets::Values(_), Some(c)) if c.eq_ignore_ascii_case("squared_error") => {
+            "squared_error".to_string()
+        }
+        (Targets::Values(_), Some(_)) => {
+            return Err("Regression trees only support the squared_error criterion".to_string());
+        }
+    };
+
+    let mut grower = Grower {
+        features: &input.features,
+        targets,
+        names,
+        entropy: criterion == "entropy",
+        max_depth,
+        min_samples_split,
+        min_samples_leaf,
+        gains: vec![0.0; width],
+    };
+    let rows: Vec<usize> = (0..n).collect();
+    let tree = grower.grow(&rows, 0);
+
+    let total_gain: f64 = grower.gains.iter().sum();
+    let feature_importances = grower
+        .names
+        .iter()
+        .zip(&grower.gains)
+        .enumerate()
+        .map(|(feature, (name, gain))| FeatureImportance {
+            feature,
+            name: name.clone(),
+            importance: if total_gain > 0.0 {
+                gain / total_gain
+            } else {
+                0.0
+            },
+        })
+        .collect();
+
+    let leaves: Vec<&TreeNode> = input
+        .features
+        .iter()
+        .map(|row| leaf_for(&tree, row))
+        .collect();
+    let (training_accuracy, training_rmse) = match (&input.labels, &input.targets) {
+        (Some(labels), _) => {
+            let correct = leaves
+                .iter()
+                .zip(labels)
+                .filter(|(leaf, label)| leaf.label.as_ref() == Some(*label))
+                .count();
+            (Some(correct as f64 / n as f64), None)
+        }
+        (None, Some(targets)) => {
+            let squared: f64 = leaves
+                .iter()
+                .zip(targets)
+                .map(|(leaf, t)| (leaf.value.unwrap_or_default() - t).powi(2))
+                .sum();
+            (None, Some((squared / n as f64).sqrt()))
+        }
+        (None, None) => unreachable!("task was checked above"),
+    };
+
+    let mut rules = Vec::new();
+    leaf_rules(&tree, &mut Vec::new(), &mut rules);
+    Ok(DecisionTreeTrainOutput {
+        task: if training_accuracy.is_some() {
+            "classification"
+        } else {
+            "regression"
+        }
+        .to_string(),
+        criterion,
+        feature_count: width,
+        depth: tree_depth(&tree),
+        leaf_count: rules.len(),
+        rules,
+        feature_importances,
+        training_accuracy,
+        training_rmse,
+        tree,
+    })
+}
+
+/// The leaf a row falls into; the row must have every feature the tree splits on
+pub fn leaf_for<'a>(node: &'a TreeNode, row: &[f64]) -> &'a TreeNode {
+    match (node.children.as_slice(), node.feature, node.threshold) {
+        ([yes, no], Some(feature), Some(threshold)) => {
+            leaf_for(if row[feature] <= threshold { yes } else { no }, row)
+        }
+        _ => node,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classification(features: Vec<Vec<f64>>, labels: &[&str]) -> DecisionTreeTrainInput {
+        DecisionTreeTrainInput {
+            features,
+            labels: Some(labels.iter().map(|l| l.to_string()).collect()),
+            targets: None,
+            feature_names: None,
+            max_depth: None,
+            min_samples_split: None,
+            min_samples_leaf: None,
+            criterion: None,
+        }
+    }
+
+    #[test]
+    fn test_single_split() {
+        let mut input = classification(
+            vec![
+                vec![1.0, 7.0],
+                vec![2.0, 3.0],
+                vec![3.0, 9.0],
+                vec![6.0, 2.0],
+                vec![7.0, 8.0],
+                vec![8.0, 4.0],
+            ],
+            &["small", "small", "small", "large", "large", "large"],
+        );
+        input.feature_names = Some(vec!["size".to_string(), "noise".to_string()]);
+        let result = decision_tree_train_logic(input).unwrap();
+        assert_eq!(result.tree.rule.as_deref(), Some("size <= 4.5"));
+        assert_eq!(result.tree.impurity, 0.5);
+        assert_eq!(result.tree.children[0].label.as_deref(), Some("small"));
+        assert_eq!(result.tree.children[1].label.as_deref(), Some("large"));
+        assert_eq!(
+            result.rules,
+            vec!["IF size <= 4.5 THEN small", "IF size > 4.5 THEN large"]
+        );
+        assert_eq!(result.feature_importances[0].importance, 1.0);
+        assert_eq!(result.feature_importances[1].importance, 0.0);
+        assert_eq!((result.depth, result.leaf_count), (1, 2));
+        assert_eq!(result.training_accuracy, Some(1.0));
+    }
+
+    #[test]
+    fn test_xor_root_stays_leaf() {
+        let features = vec![
+            vec![0.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+        ];
+        let labels = ["no", "yes", "yes", "no"];
+        // No single split reduces Gini impurity, so the root stays a leaf
+        let result = decision_tree_train_logic(classification(features, &labels)).unwrap();
+        assert_eq!(result.depth, 0);
+        assert_eq!(result.rules, vec!["IF TRUE THEN no"]);
+        assert_eq!(result.training_accuracy, Some(0.5));
+    }
+
+    #[test]
+    fn test_depth_and_leaf_limits() {
+        let features: Vec<Vec<f64>> = (0..8).map(|i| vec![i as f64]).collect();
+        let labels = ["a", "b", "a", "b", "a", "b", "a", "b"];
+        let mut input = classification(features, &labels);
+        input.max_depth = Some(2);
+        let result = decision_tree_train_logic(input.clone()).unwrap();
+        assert!(result.depth <= 2);
+        assert!(result.leaf_count <= 4);
+
+        input.max_depth = Some(10);
+        input.min_samples_leaf = Some(3);
+        let result = decision_tree_train_logic(input).unwrap();
+        fn check(node: &TreeNode) {
+            assert!(node.samples >= 3);
+            node.children.iter().for_each(check);
+        }
+        check(&result.tree);
+    }
+
+    #[test]
+    fn test_entropy_criterion() {
+        let mut input = classification(
+            vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0]],
+            &["a", "a", "b", "b"],
+        );
+        input.criterion = Some("Entropy".to_string());
+        let result = decision_tree_train_logic(input).unwrap();
+        assert_eq!(result.criterion, "entropy");
+        assert_eq!(result.tree.impurity, 1.0);
+        assert_eq!(result.tree.threshold, Some(2.5));
+    }
+
+    #[test]
+    fn test_regression_tree() {
+        let input = DecisionTreeTrainInput {
+            features: vec![vec![1.0], vec![2.0], vec![3.0], vec![10.0], vec![11.0]],
+            labels: None,
+            targets: Some(vec![5.0, 5.0, 5.0, 20.0, 22.0]),
+            feature_names: Some(vec!["x".to_string()]),
+            max_depth: Some(1),
+            min_samples_split: None,
+            min_samples_leaf: None,
+            criterion: None,
+        };
+        let result = decision_tree_train_logic(input).unwrap();
+        assert_eq!(result.task, "regression");
+        assert_eq!(result.criterion, "squared_error");
+        assert_eq!(result.tree.threshold, Some(6.5));
+        assert_eq!(result.tree.children[1].value, Some(21.0));
+        assert_eq!(result.rules[0], "IF x <= 6.5 THEN 5");
+        // Leaves predict 5 and 21, missing the last two targets by one each
+        let rmse = result.training_rmse.unwrap();
+        assert!((rmse - (2.0f64 / 5.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let base = classification(vec![vec![1.0], vec![2.0]], &["a", "b"]);
+        let deep = DecisionTreeTrainInput {
+            max_depth: Some(MAX_DEPTH + 1),
+            ..base.clone()
+        };
+        assert!(decision_tree_train_logic(deep).is_err());
+        let names = DecisionTreeTrainInput {
+            feature_names: Some(vec![]),
+            ..base.clone()
+        };
+        assert_eq!(
+            decision_tree_train_logic(names).unwrap_err(),
+            "Got 0 feature_names for 1 features"
+        );
+        let both = DecisionTreeTrainInput {
+            targets: Some(vec![1.0, 2.0]),
+            ..base.clone()
+        };
+        assert!(
+            decision_tree_train_logic(both)
+                .unwrap_err()
+                .contains("not both")
+        );
+        let criterion = DecisionTreeTrainInput {
+            criterion: Some("mse".to_string()),
+            ..base
+        };
+        assert!(decision_tree_train_logic(criterion).is_err());
+    }
+}
@@ -1,16 +1,35 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 mod logic;
-use logic::{StatisticsInput as LogicInput, descriptive_statistics_logic};
+pub use logic::{
+    DescriptiveStatisticsOutputSchema as DescriptiveStatisticsOutput,
+    StatisticsInput as LogicInput, descriptive_statistics_logic,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StatisticsInput {
     /// Array of numerical values to analyze
     pub data: Vec<f64>,
+    /// Only return these result fields, e.g. ["mean", "standard_deviation"] (default: all)
+    pub fields: Option<Vec<String>>,
+    /// Rounding applied to numeric results, e.g. {"significant_digits": 6} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 impl From<StatisticsInput> for LogicInput {
@@ -19,10 +38,51 @@ impl From<StatisticsInput> for LogicInput {
     }
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn descriptive_statistics(input: StatisticsInput) -> ToolResponse {
-    match descriptive_statistics_logic(input.into()) {
-        Ok(result) => ToolResponse::text(serde_json::to_string(&result).unwrap()),
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn descriptive_statistics(request: Request<StatisticsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "descriptive_statistics",
+                StatisticsInput,
+                DescriptiveStatisticsOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("descriptive_statistics", &input);
+    log.finish(run_descriptive_statistics(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_descriptive_statistics(input: StatisticsInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().array("data", &input.data).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let fields = input.fields.clone();
+    let logic_input: LogicInput = input.into();
+    match ftl_result_cache::cached("descriptive_statistics", &logic_input, || {
+        descriptive_statistics_logic(logic_input.clone())
+    }) {
+        Ok(result) => match ftl_response::to_json_with_precision(
+            &result,
+            fields.as_deref(),
+            precision.as_ref(),
+        ) {
+            Ok(json) => ToolResponse::text(json),
+            Err(e) => ToolResponse::text(format!("Error: {e}")),
+        },
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
 }
@@ -1,3 +1,4 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -7,7 +8,7 @@ pub struct StatisticsInput {
     pub data: Vec<f64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct DescriptiveStatisticsOutput {
     /// Number of data points
     pub count: usize,
@@ -30,6 +31,7 @@ pub struct DescriptiveStatisticsOutput {
     /// Sum of all values
     pub sum: f64,
     /// Quartile information
+    #[tool_types(nested)]
     pub quartiles: Quartiles,
     /// Measure of asymmetry
     pub skewness: f64,
@@ -37,7 +39,7 @@ pub struct DescriptiveStatisticsOutput {
     pub kurtosis: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct Quartiles {
     /// First quartile (25th percentile)
     pub q1: f64,
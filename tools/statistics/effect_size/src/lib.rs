@@ -0,0 +1,99 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    EffectSizeInput as LogicInput, EffectSizeOutput as LogicOutput,
+    GroupSummary as LogicGroupSummary, MEASURES, effect_size_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{EffectSizeOutputSchema as EffectSizeOutput, GroupSummarySchema as GroupSummary};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EffectSizeInput {
+    #[serde(flatten)]
+    pub params: logic::EffectSizeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Compute Cohen's d, Hedges' g, odds and risk ratios, Cramér's V, or eta and omega
+/// squared from raw data or summary statistics, with confidence intervals
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn effect_size(request: Request<EffectSizeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("effect_size", EffectSizeInput, EffectSizeOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("effect_size", &input);
+    log.finish(run_effect_size(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_effect_size(input: EffectSizeInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("groups", input.params.groups.as_deref().unwrap_or_default())
+        .array_len(
+            "groups",
+            input.params.groups.iter().flatten().map(Vec::len).sum(),
+        )
+        .array(
+            "summaries",
+            input.params.summaries.as_deref().unwrap_or_default(),
+        )
+        .array("table", input.params.table.as_deref().unwrap_or_default())
+        .array_len(
+            "table",
+            input.params.table.iter().flatten().map(Vec::len).sum(),
+        )
+        .array(
+            "row_values",
+            input.params.row_values.as_deref().unwrap_or_default(),
+        )
+        .array(
+            "column_values",
+            input.params.column_values.as_deref().unwrap_or_default(),
+        )
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::effect_size_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &EffectSizeOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
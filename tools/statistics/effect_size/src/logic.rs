@@ -0,0 +1,584 @@
+use ftl_distributions::{noncentral_chi_square_cdf, noncentral_f_cdf, normal_quantile};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Measures this tool computes
+pub const MEASURES: [&str; 7] = [
+    "cohens_d",
+    "hedges_g",
+    "odds_ratio",
+    "risk_ratio",
+    "cramers_v",
+    "eta_squared",
+    "omega_squared",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct GroupSummary {
+    pub mean: f64,
+    /// Sample standard deviation (n - 1 denominator)
+    pub sd: f64,
+    pub n: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EffectSizeInput {
+    /// One of "cohens_d", "hedges_g", "odds_ratio", "risk_ratio", "cramers_v",
+    /// "eta_squared" or "omega_squared"
+    pub measure: String,
+    /// Raw observations per group (two groups for d and g, two or more for eta and omega)
+    pub groups: Option<Vec<Vec<f64>>>,
+    /// Summary statistics per group, as an alternative to raw groups
+    #[tool_types(nested)]
+    pub summaries: Option<Vec<GroupSummary>>,
+    /// Contingency table of counts. For odds and risk ratios a 2x2 table with groups
+    /// (exposed, unexposed) as rows and outcomes (event, no event) as columns
+    pub table: Option<Vec<Vec<f64>>>,
+    /// Raw categorical data as an alternative to table: row category per observation,
+    /// paired by index with column_values. Categories are ordered by first appearance
+    pub row_values: Option<Vec<String>>,
+    /// Column category per observation, paired by index with row_values
+    pub column_values: Option<Vec<String>>,
+    /// Confidence level of the interval (default: 0.95)
+    pub confidence_level: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EffectSizeOutput {
+    pub measure: String,
+    pub estimate: f64,
+    pub confidence_level: f64,
+    pub ci_lower: f64,
+    pub ci_upper: f64,
+    /// How the interval was computed
+    pub ci_method: String,
+    /// Conventional label: "negligible", "small", "medium" or "large"; absent for
+    /// risk ratios, which have no accepted thresholds
+    pub magnitude: Option<String>,
+    /// Total number of observations
+    pub sample_size: usize,
+    /// Test statistic behind the estimate: chi-square for Cramér's V, F for eta and
+    /// omega squared
+    pub statistic: Option<f64>,
+    pub df1: Option<f64>,
+    pub df2: Option<f64>,
+    pub warnings: Vec<String>,
+}
+
+// Distributions
+
+/// Confidence limits for a noncentrality parameter by test inversion: the lower limit
+/// puts the observed statistic at the upper alpha/2 quantile, the upper limit at the
+/// lower one. `cdf` must decrease as the noncentrality grows
+fn noncentrality_interval(cdf: impl Fn(f64) -> f64, alpha: f64) -> (f64, f64) {
+    let solve = |target: f64| {
+        if cdf(0.0) <= target {
+            return 0.0;
+        }
+        let mut high = 1.0;
+        while cdf(high) > target && high < 1e7 {
+            high *= 2.0;
+        }
+        let mut low = 0.0;
+        for _ in 0..100 {
+            let mid = (low + high) / 2.0;
+            if cdf(mid) > target {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low + high) / 2.0
+    };
+    (solve(1.0 - alpha / 2.0), solve(alpha / 2.0))
+}
+
+// Measures
+
+struct Estimate {
+    estimate: f64,
+    ci: (f64, f64),
+    ci_method: &'static str,
+    magnitude: Option<&'static str>,
+    sample_size: usize,
+    statistic: Option<f64>,
+    df: (Option<f64>, Option<f64>),
+}
+
+fn label(value: f64, small: f64, medium: f64, large: f64) -> &'static str {
+    match value.abs() {
+        v if v >= large => "large",
+        v if v >= medium => "medium",
+        v if v >= small => "small",
+        _ => "negligible",
+    }
+}
+
+fn summarize(values: &[f64]) -> Result<GroupSummary, String> {
+    if values.iter().any(|v| !v.is_finite()) {
+        return Err("Group values must be finite numbers".to_string());
+    }
+    let n = values.len();
+    if n < 2 {
+        return Err("Each group needs at least 2 observations".to_string());
+    }
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+    Ok(GroupSummary {
+        mean,
+        sd: variance.sqrt(),
+        n,
+    })
+}
+
+fn group_summaries(input: &EffectSizeInput) -> Result<Vec<GroupSummary>, String> {
+    let summaries = match (&input.groups, &input.summaries) {
+        (Some(groups), None) => groups
+            .iter()
+            .map(|group| summarize(group))
+            .collect::<Result<Vec<_>, _>>()?,
+        (None, Some(summaries)) => summaries.clone(),
+        (Some(_), Some(_)) => return Err("Give groups or summaries, not both".to_string()),
+        (None, None) => {
+            return Err(format!(
+                "{} needs raw groups or group summaries",
+                input.measure
+            ));
+        }
+    };
+    for summary in &summaries {
+        if summary.n < 2 {
+            return Err("Each group needs n of at least 2".to_string());
+        }
+        if !summary.mean.is_finite() || !summary.sd.is_finite() || summary.sd < 0.0 {
+            return Err("Summary means must be finite and sds non-negative".to_string());
+        }
+    }
+    Ok(summaries)
+}
+
+/// Standardized mean difference of two groups with the pooled standard deviation,
+/// optionally with Hedges' small-sample correction
+fn mean_difference(groups: &[GroupSummary], hedges: bool, z: f64) -> Result<Estimate, String> {
+    let [first, second] = groups else {
+        return Err("Standardized mean differences need exactly 2 groups".to_string());
+    };
+    let (n1, n2) = (first.n as f64, second.n as f64);
+    let pooled =
+        (((n1 - 1.0) * first.sd.powi(2) + (n2 - 1.0) * second.sd.powi(2)) / (n1 + n2 - 2.0)).sqrt();
+    if pooled == 0.0 {
+        return Err("Pooled standard deviation is zero".to_string());
+    }
+    let d = (first.mean - second.mean) / pooled;
+    // Large-sample standard error (Hedges & Olkin)
+    let se = ((n1 + n2) / (n1 * n2) + d * d / (2.0 * (n1 + n2))).sqrt();
+    let correction = if hedges {
+        1.0 - 3.0 / (4.0 * (n1 + n2) - 9.0)
+    } else {
+        1.0
+    };
+    let estimate = d * correction;
+    Ok(Estimate {
+        estimate,
+        ci: (
+            estimate - z * se * correction,
+            estimate + z * se * correction,
+        ),
+        ci_method: "normal approximation",
+        magnitude: Some(label(estimate, 0.2, 0.5, 0.8)),
+        sample_size: first.n + second.n,
+        statistic: None,
+        df: (Some(n1 + n2 - 2.0), None),
+    })
+}
+
+/// Eta or omega squared from a one-way ANOVA decomposition
+fn variance_explained(
+    groups: &[GroupSummary],
+    omega: bool,
+    alpha: f64,
+    warnings: &mut Vec<String>,
+) -> Result<Estimate, String> {
+    if groups.len() < 2 {
+        return Err("Variance explained needs at least 2 groups".to_string());
+    }
+    let total_n: usize = groups.iter().map(|g| g.n).sum();
+    let n = total_n as f64;
+    let grand_mean = groups.iter().map(|g| g.mean * g.n as f64).sum::<f64>() / n;
+    let between: f64 = groups
+        .iter()
+        .map(|g| g.n as f64 * (g.mean - grand_mean).powi(2))
+        .sum();
+    let within: f64 = groups
+        .iter()
+        .map(|g| (g.n as f64 - 1.0) * g.sd.powi(2))
+        .sum();
+    let total = between + within;
+    if total == 0.0 {
+        return Err("All observations are identical".to_string());
+    }
+    let (df1, df2) = (groups.len() as f64 - 1.0, n - groups.len() as f64);
+    let mean_within = within / df2;
+    let f = if within > 0.0 {
+        (between / df1) / mean_within
+    } else {
+        f64::INFINITY
+    };
+
+    let estimate = if omega {
+        let omega_squared = (between - df1 * mean_within) / (total + mean_within);
+        if omega_squared < 0.0 {
+            warnings.push("Omega squared was negative and has been set to 0".to_string());
+        }
+        omega_squared.max(0.0)
+    } else {
+        between / total
+    };
+
+    // The interval is for the population proportion of variance explained, which
+    // both eta and omega squared estimate
+    let ci = if f.is_finite() {
+        let (low, high) =
+            noncentrality_interval(|lambda| noncentral_f_cdf(f, df1, df2, lambda), alpha);
+        (low / (low + n), high / (high + n))
+    } else {
+        (1.0, 1.0)
+    };
+    Ok(Estimate {
+        estimate,
+        ci,
+        ci_method: "noncentral F inversion",
+        magnitude: Some(label(estimate, 0.01, 0.06, 0.14)),
+        sample_size: total_n,
+        statistic: Some(f),
+        df: (Some(df1), Some(df2)),
+    })
+}
+
+/// Contingency table from `table` or from paired categorical observations
+fn contingency_table(input: &EffectSizeInput) -> Result<Vec<Vec<f64>>, String> {
+    let table = match (&input.table, &input.row_values, &input.column_values) {
+        (Some(table), None, None) => table.clone(),
+        (None, Some(rows), Some(columns)) => {
+            if rows.len() != columns.len() {
+                return Err("row_values and column_values must have the same length".to_string());
+            }
+            let index = |labels: &mut Vec<String>, value: &String| match labels
+                .iter()
+                .position(|l| l == value)
+            {
+                Some(i) => i,
+                None => {
+                    labels.push(value.clone());
+                    labels.len() - 1
+                }
+            };
+            let (mut row_labels, mut column_labels) = (Vec::new(), Vec::new());
+            let mut counts: HashMap<(usize, usize), f64> = HashMap::new();
+            for (row, column) in rows.iter().zip(columns) {
+                let cell = (
+                    index(&mut row_labels, row),
+                    index(&mut column_labels, column),
+                );
+                *counts.entry(cell).or_default() += 1.0;
+            }
+            (0..row_labels.len())
+                .map(|r| {
+                    (0..column_labels.len())
+                        .map(|c| counts.get(&(r, c)).copied().unwrap_or(0.0))
+                        .collect()
+                })
+                .collect()
+        }
+        (None, None, None) => {
+            return Err("Give a table or row_values and column_values".to_string());
+        }
+        _ => {
+            return Err(
+                "Give either a table or both row_values and column_values, not a mix".to_string(),
+            );
+        }
+    };
+    let columns = table.first().map_or(0, Vec::len);
+    if table.len() < 2 || columns < 2 || table.iter().any(|row| row.len() != columns) {
+        return Err("The table must be rectangular with at least 2 rows and 2 columns".to_string());
+    }
+    if table.iter().flatten().any(|c| !c.is_finite() || *c < 0.0) {
+        return Err("Table counts must be non-negative numbers".to_string());
+    }
+    Ok(table)
+}
+
+/// Odds or risk ratio from a 2x2 table on the log scale, with a Haldane-Anscombe
+/// correction when a cell is zero
+fn ratio(
+    table: &[Vec<f64>],
+    risk: bool,
+    z: f64,
+    warnings: &mut Vec<String>,
+) -> Result<Estimate, String> {
+    if table.len() != 2 || table[0].len() != 2 {
+        return Err("Odds and risk ratios need a 2x2 table".to_string());
+    }
+    let sample_size = table.iter().flatten().sum::<f64>().round() as usize;
+    let (mut a, mut b, mut c, mut d) = (table[0][0], table[0][1], table[1][0], table[1][1]);
+    if [a + b, c + d].contains(&0.0) {
+        return Err("Each row of the table needs at least one observation".to_string());
+    }
+    if [a, b, c, d].contains(&0.0) {
+        warnings.push("A cell is zero; 0.5 was added to every cell".to_string());
+        (a, b, c, d) = (a + 0.5, b + 0.5, c + 0.5, d + 0.5);
+    }
+    let (log_ratio, se) = if risk {
+        (
+            (a / (a + b)).ln() - (c / (c + d)).ln(),
+            (1.0 / a - 1.0 / (a + b) + 1.0 / c - 1.0 / (c + d)).sqrt(),
+        )
+    } else {
+        (
+            (a * d / (b * c)).ln(),
+            (1.0 / a + 1.0 / b + 1.0 / c + 1.0 / d).sqrt(),
+        )
+    };
+    Ok(Estimate {
+        estimate: log_ratio.exp(),
+        ci: ((log_ratio - z * se).exp(), (log_ratio + z * se).exp()),
+        ci_method: "normal approximation on the log scale",
+        // Chen, Cohen & Chen (2010) odds ratios equivalent to d = 0.2, 0.5, 0.8
+        magnitude: (!risk).then(|| label(log_ratio, 1.68f64.ln(), 3.47f64.ln(), 6.71f64.ln())),
+        sample_size,
+        statistic: None,
+        df: (None, None),
+    })
+}
+
+fn cramers_v(table: &[Vec<f64>], alpha: f64) -> Result<Estimate, String> {
+    let n: f64 = table.iter().flatten().sum();
+    let row_totals: Vec<f64> = table.iter().map(|row| row.iter().sum()).collect();
+    let column_totals: Vec<f64> = (0..table[0].len())
+        .map(|c| table.iter().map(|row| row[c]).sum())
+        .collect();
+    if row_totals.contains(&0.0) || column_totals.contains(&0.0) {
+        return Err("Every row and column of the table needs at least one observation".to_string());
+    }
+    let chi_square: f64 = table
+        .iter()
+        .enumerate()
+        .flat_map(|(r, row)| {
+            let (row_totals, column_totals) = (&row_totals, &column_totals);
+            row.iter().enumerate().map(move |(c, observed)| {
+                let expected = row_totals[r] * column_totals[c] / n;
+                (observed - expected).powi(2) / expected
+            })
+        })
+        .sum();
+    let k = table.len().min(table[0].len()) as f64;
+    let df = (table.len() as f64 - 1.0) * (table[0].len() as f64 - 1.0);
+    let scale = n * (k - 1.0);
+    let (low, high) = noncentrality_interval(
+        |lambda| noncentral_chi_square_cdf(chi_square, df, lambda),
+        alpha,
+    );
+    let estimate = (chi_square / scale).sqrt();
+    // Cohen's thresholds shrink with the smaller table dimension
+    let root = (k - 1.0).sqrt();
+    Ok(Estimate {
+        estimate,
+        ci: (
+            (low / scale).sqrt().min(1.0),
+            (high / scale).sqrt().min(1.0),
+        ),
+        ci_method: "noncentral chi-square inversion",
+        magnitude: Some(label(estimate, 0.1 / root, 0.3 / root, 0.5 / root)),
+        sample_size: n.round() as usize,
+        statistic: Some(chi_square),
+        df: (Some(df), None),
+    })
+}
+
+pub fn effect_size_logic(input: EffectSizeInput) -> Result<EffectSizeOutput, String> {
+    let measure = input.measure.trim().to_lowercase();
+    let confidence_level = input.confidence_level.unwrap_or(0.95);
+    if !(confidence_level > 0.0 && confidence_level < 1.0) {
+        return Err("confidence_level must be between 0 and 1".to_string());
+    }
+    let alpha = 1.0 - confidence_level;
+    let z = normal_quantile(1.0 - alpha / 2.0);
+    let mut warnings = Vec::new();
+
+    let result = match measure.as_str() {
+        "cohens_d" | "hedges_g" => {
+            mean_difference(&group_summaries(&input)?, measure == "hedges_g", z)?
+        }
+        "eta_squared" | "omega_squared" => variance_explained(
+            &group_summaries(&input)?,
+            measure == "omega_squared",
+            alpha,
+            &mut warnings,
+        )?,
+        "odds_ratio" | "risk_ratio" => ratio(
+            &contingency_table(&input)?,
+            measure == "risk_ratio",
+            z,
+            &mut warnings,
+        )?,
+        "cramers_v" => cramers_v(&contingency_table(&input)?, alpha)?,
+        other => {
+            return Err(format!(
+                "Unknown measure '{other}', use one of: {}",
+                MEASURES.join(", ")
+            ));
+        }
+    };
+
+    Ok(EffectSizeOutput {
+        measure,
+        estimate: result.estimate,
+        confidence_level,
+        ci_lower: result.ci.0,
+        ci_upper: result.ci.1,
+        ci_method: result.ci_method.to_string(),
+        magnitude: result.magnitude.map(str::to_string),
+        sample_size: result.sample_size,
+        statistic: result.statistic,
+        df1: result.df.0,
+        df2: result.df.1,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(measure: &str) -> EffectSizeInput {
+        EffectSizeInput {
+            measure: measure.to_string(),
+            groups: None,
+            summaries: None,
+            table: None,
+            row_values: None,
+            column_values: None,
+            confidence_level: None,
+        }
+    }
+
+    fn summary(mean: f64, sd: f64, n: usize) -> GroupSummary {
+        GroupSummary { mean, sd, n }
+    }
+
+    #[test]
+    fn test_cohens_d_and_hedges_g() {
+        let mut input = request("cohens_d");
+        input.summaries = Some(vec![summary(105.0, 10.0, 30), summary(100.0, 10.0, 30)]);
+        let d = effect_size_logic(input.clone()).unwrap();
+        assert!((d.estimate - 0.5).abs() < 1e-12);
+        // se = sqrt(60/900 + 0.25/120) = 0.26220
+        assert!((d.ci_lower - (0.5 - 1.959_964 * 0.262_202)).abs() < 1e-4);
+        assert_eq!(d.magnitude.as_deref(), Some("medium"));
+
+        input.measure = "hedges_g".to_string();
+        let g = effect_size_logic(input).unwrap();
+        assert!((g.estimate - 0.5 * (1.0 - 3.0 / 231.0)).abs() < 1e-12);
+
+        // Raw groups give the same answer as their summaries
+        let mut raw = request("cohens_d");
+        raw.groups = Some(vec![vec![1.0, 2.0, 3.0, 4.0], vec![3.0, 4.0, 5.0, 6.0]]);
+        let result = effect_size_logic(raw).unwrap();
+        assert!((result.estimate + 2.0 / (5.0f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(result.sample_size, 8);
+    }
+
+    #[test]
+    fn test_odds_and_risk_ratios() {
+        let mut input = request("odds_ratio");
+        input.table = Some(vec![vec![20.0, 80.0], vec![10.0, 90.0]]);
+        let odds = effect_size_logic(input.clone()).unwrap();
+        assert!((odds.estimate - 2.25).abs() < 1e-12);
+        let se: f64 = (1.0 / 20.0 + 1.0 / 80.0 + 1.0 / 10.0 + 1.0 / 90.0f64).sqrt();
+        assert!((odds.ci_upper - (2.25f64.ln() + 1.959_964 * se).exp()).abs() < 1e-4);
+        assert_eq!(odds.magnitude.as_deref(), Some("small"));
+
+        input.measure = "risk_ratio".to_string();
+        let risk = effect_size_logic(input.clone()).unwrap();
+        assert!((risk.estimate - 2.0).abs() < 1e-12);
+        assert!(risk.magnitude.is_none());
+
+        input.table = Some(vec![vec![0.0, 10.0], vec![5.0, 5.0]]);
+        let corrected = effect_size_logic(input).unwrap();
+        assert_eq!(corrected.warnings.len(), 1);
+        assert!(corrected.estimate > 0.0);
+    }
+
+    #[test]
+    fn test_cramers_v() {
+        let mut input = request("cramers_v");
+        input.table = Some(vec![vec![30.0, 10.0], vec![10.0, 30.0]]);
+        let result = effect_size_logic(input).unwrap();
+        // chi-square = 20 with n = 80, so V = 0.5
+        assert!((result.statistic.unwrap() - 20.0).abs() < 1e-9);
+        assert!((result.estimate - 0.5).abs() < 1e-12);
+        assert!(result.ci_lower < 0.5 && result.ci_upper > 0.5);
+        assert!(result.ci_lower > 0.25 && result.ci_upper < 0.75);
+        // At the lower limit the observed statistic sits at the 97.5th percentile
+        let lambda = result.ci_lower.powi(2) * 80.0;
+        assert!((noncentral_chi_square_cdf(20.0, 1.0, lambda) - 0.975).abs() < 1e-6);
+
+        // Raw categories tabulate to the same table
+        let mut raw = request("cramers_v");
+        let rows = ["a", "a", "a", "b", "b", "b"];
+        let columns = ["x", "x", "y", "y", "y", "x"];
+        raw.row_values = Some(rows.iter().map(|s| s.to_string()).collect());
+        raw.column_values = Some(columns.iter().map(|s| s.to_string()).collect());
+        let result = effect_size_logic(raw).unwrap();
+        assert_eq!(result.sample_size, 6);
+        assert!((result.statistic.unwrap() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_eta_and_omega_squared() {
+        let groups = vec![
+            vec![4.0, 5.0, 6.0, 5.0],
+            vec![6.0, 7.0, 8.0, 7.0],
+            vec![8.0, 9.0, 10.0, 9.0],
+        ];
+        let mut input = request("eta_squared");
+        input.groups = Some(groups);
+        let eta = effect_size_logic(input.clone()).unwrap();
+        // SS between = 32, SS within = 6, F = 16 / (6 / 9) = 24
+        assert!((eta.estimate - 32.0 / 38.0).abs() < 1e-12);
+        assert!((eta.statistic.unwrap() - 24.0).abs() < 1e-9);
+        assert_eq!((eta.df1, eta.df2), (Some(2.0), Some(9.0)));
+        assert!(eta.ci_lower > 0.3 && eta.ci_upper < 1.0);
+
+        input.measure = "omega_squared".to_string();
+        let omega = effect_size_logic(input).unwrap();
+        let mean_within = 6.0 / 9.0;
+        assert!((omega.estimate - (32.0 - 2.0 * mean_within) / (38.0 + mean_within)).abs() < 1e-12);
+        assert!(omega.estimate < eta.estimate);
+        assert_eq!(omega.magnitude.as_deref(), Some("large"));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(
+            effect_size_logic(request("glass_delta"))
+                .unwrap_err()
+                .contains("Unknown measure")
+        );
+        assert!(effect_size_logic(request("cohens_d")).is_err());
+        let mut input = request("odds_ratio");
+        input.table = Some(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        assert_eq!(
+            effect_size_logic(input).unwrap_err(),
+            "Odds and risk ratios need a 2x2 table"
+        );
+        let mut input = request("cohens_d");
+        input.groups = Some(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+        assert!(effect_size_logic(input).is_err());
+        let mut input = request("cramers_v");
+        input.table = Some(vec![vec![1.0, -2.0], vec![3.0, 4.0]]);
+        assert!(effect_size_logic(input).is_err());
+    }
+}
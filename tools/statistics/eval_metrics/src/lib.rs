@@ -0,0 +1,96 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    EvalMetricsInput as LogicInput, EvalMetricsOutput as LogicOutput, MAX_CLASSES,
+    MAX_OBSERVATIONS, eval_metrics_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    ClassMetricsSchema as ClassMetrics, ClassificationMetricsSchema as ClassificationMetrics,
+    EvalMetricsOutputSchema as EvalMetricsOutput, RegressionMetricsSchema as RegressionMetrics,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EvalMetricsInput {
+    #[serde(flatten)]
+    pub params: logic::EvalMetricsInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Evaluate model predictions: MAE, RMSE, MAPE and R² for regression, and accuracy,
+/// per-class precision, recall and F1, a confusion matrix and ROC-AUC from scores for
+/// classification
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn eval_metrics(request: Request<EvalMetricsInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "eval_metrics",
+                EvalMetricsInput,
+                EvalMetricsOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("eval_metrics", &input);
+    log.finish(run_eval_metrics(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_eval_metrics(input: EvalMetricsInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("actual", input.params.actual.as_deref().unwrap_or_default())
+        .array(
+            "predicted",
+            input.params.predicted.as_deref().unwrap_or_default(),
+        )
+        .array("labels", input.params.labels.as_deref().unwrap_or_default())
+        .array(
+            "predicted_labels",
+            input.params.predicted_labels.as_deref().unwrap_or_default(),
+        )
+        .array("scores", input.params.scores.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::eval_metrics_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &EvalMetricsOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,523 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most observations accepted in one call
+pub const MAX_OBSERVATIONS: usize = 1_000_000;
+/// Most distinct classes in a confusion matrix
+pub const MAX_CLASSES: usize = 1_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EvalMetricsInput {
+    /// Observed values, for regression metrics
+    pub actual: Option<Vec<f64>>,
+    /// Predicted values, paired with actual
+    pub predicted: Option<Vec<f64>>,
+    /// True class labels, for classification metrics
+    pub labels: Option<Vec<String>>,
+    /// Predicted class labels, paired with labels; may be omitted for a binary problem
+    /// when scores are given, and are then derived with threshold
+    pub predicted_labels: Option<Vec<String>>,
+    /// Scores or probabilities for the positive class, for ROC-AUC on binary labels
+    pub scores: Option<Vec<f64>>,
+    /// Class treated as positive for scores (default: the last class in sorted order)
+    pub positive_label: Option<String>,
+    /// Score at or above which the positive class is predicted, when predicted_labels
+    /// is omitted (default: 0.5)
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RegressionMetrics {
+    /// Mean absolute error
+    pub mae: f64,
+    /// Mean squared error
+    pub mse: f64,
+    /// Root mean squared error
+    pub rmse: f64,
+    /// Mean absolute percentage error, in percent, over observations with non-zero
+    /// actual values; absent when every actual value is zero
+    pub mape: Option<f64>,
+    /// Coefficient of determination, 1 - SS_res / SS_tot; absent when the actual
+    /// values are constant
+    pub r_squared: Option<f64>,
+    /// Number of observations
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ClassMetrics {
+    pub label: String,
+    /// Correct predictions of this class over all predictions of it
+    pub precision: f64,
+    /// Correct predictions of this class over all observations of it
+    pub recall: f64,
+    /// Harmonic mean of precision and recall
+    pub f1: f64,
+    /// Observations with this true label
+    pub support: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ClassificationMetrics {
+    /// Fraction of predictions equal to the true label
+    pub accuracy: f64,
+    /// Every label seen in labels or predictions, sorted; indexes the confusion matrix
+    pub classes: Vec<String>,
+    /// Counts with true labels as rows and predicted labels as columns
+    pub confusion_matrix: Vec<Vec<usize>>,
+    #[tool_types(nested)]
+    pub per_class: Vec<ClassMetrics>,
+    /// Unweighted means over classes
+    pub macro_precision: f64,
+    pub macro_recall: f64,
+    pub macro_f1: f64,
+    /// Mean of per-class F1 weighted by support
+    pub weighted_f1: f64,
+    /// Area under the ROC curve from scores; absent without scores or when only one
+    /// class is present
+    pub roc_auc: Option<f64>,
+    /// Class treated as positive for scores
+    pub positive_label: Option<String>,
+    /// Threshold used to derive predicted labels from scores
+    pub threshold: Option<f64>,
+    /// Number of observations
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct EvalMetricsOutput {
+    #[tool_types(nested)]
+    pub regression: Option<RegressionMetrics>,
+    #[tool_types(nested)]
+    pub classification: Option<ClassificationMetrics>,
+    pub warnings: Vec<String>,
+}
+
+fn regression_metrics(actual: &[f64], predicted: &[f64]) -> Result<RegressionMetrics, String> {
+    if actual.is_empty() {
+        return Err("actual must not be empty".to_string());
+    }
+    if actual.len() != predicted.len() {
+        return Err(format!(
+            "actual has {} values but predicted has {}",
+            actual.len(),
+            predicted.len()
+        ));
+    }
+    if actual.iter().chain(predicted).any(|v| !v.is_finite()) {
+        return Err("actual and predicted must be finite numbers".to_string());
+    }
+
+    let n = actual.len() as f64;
+    let errors: Vec<f64> = actual.iter().zip(predicted).map(|(a, p)| a - p).collect();
+    let mae = errors.iter().map(|e| e.abs()).sum::<f64>() / n;
+    let ss_res = errors.iter().map(|e| e * e).sum::<f64>();
+    let mse = ss_res / n;
+
+    let percentages: Vec<f64> = actual
+        .iter()
+        .zip(&errors)
+        .filter(|(a, _)| **a != 0.0)
+        .map(|(a, e)| (e / a).abs())
+        .collect();
+    let mape = (!percentages.is_empty())
+        .then(|| 100.0 * percentages.iter().sum::<f64>() / percentages.len() as f64);
+
+    let mean = actual.iter().sum::<f64>() / n;
+    let ss_tot = actual.iter().map(|a| (a - mean).powi(2)).sum::<f64>();
+
+    Ok(RegressionMetrics {
+        mae,
+        mse,
+        rmse: mse.sqrt(),
+        mape,
+        r_squared: (ss_tot > 0.0).then(|| 1.0 - ss_res / ss_tot),
+        count: actual.len(),
+    })
+}
+
+/// ROC-AUC as the Mann-Whitney probability that a positive outranks a negative, with
+/// tied scores counting one half
+fn roc_auc(scores: &[f64], positive: &[bool]) -> Option<f64> {
+    let n_pos = positive.iter().filter(|p| **p).count();
+    let n_neg = positive.len() - n_pos;
+    if n_pos == 0 || n_neg == 0 {
+        return None;
+    }
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+
+    // Sum of average ranks of the positives
+    let mut rank_sum = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && scores[order[end + 1]] == scores[order[start]] {
+            end += 1;
+        }
+        let average_rank = (start + end) as f64 / 2.0 + 1.0;
+        rank_sum +=
+            average_rank * order[start..=end].iter().filter(|&&i| positive[i]).count() as f64;
+        start = end + 1;
+    }
+    let (n_pos, n_neg) = (n_pos as f64, n_neg as f64);
+    Some((rank_sum - n_pos * (n_pos + 1.0) / 2.0) / (n_pos * n_neg))
+}
+
+fn ratio(numerator: usize, denominator: usize) -> Option<f64> {
+    (denominator > 0).then(|| numerator as f64 / denominator as f64)
+}
+
+fn classification_metrics(
+    input: &EvalMetricsInput,
+    labels: &[String],
+    warnings: &mut Vec<String>,
+) -> Result<ClassificationMetrics, String> {
+    if labels.is_empty() {
+        return Err("labels must not be empty".to_string());
+    }
+    if let Some(scores) = &input.scores {
+        if scores.len() != labels.len() {
+            return Err(format!(
+                "labels has {} values but scores has {}",
+                labels.len(),
+                scores.len()
+            ));
+        }
+        if scores.iter().any(|s| !s.is_finite()) {
+            return Err("scores must be finite numbers".to_string());
+        }
+    } else if input.positive_label.is_some() || input.threshold.is_some() {
+        return Err("positive_label and threshold only apply with scores".to_string());
+    }
+
+    let mut label_classes: Vec<String> = labels.to_vec();
+    label_classes.sort();
+    label_classes.dedup();
+    let positive_label = match (&input.scores, &input.positive_label) {
+        (None, _) => None,
+        (Some(_), Some(label)) => Some(label.clone()),
+        (Some(_), None) => label_classes.last().cloned(),
+    };
+
+    let (predicted, threshold) = match (&input.predicted_labels, &input.scores) {
+        (Some(predicted), _) => {
+            if input.threshold.is_some() {
+                return Err("threshold only applies when predicted_labels is omitted".to_string());
+            }
+            if predicted.len() != labels.len() {
+                return Err(format!(
+                    "labels has {} values but predicted_labels has {}",
+                    labels.len(),
+                    predicted.len()
+                ));
+            }
+            (predicted.clone(), None)
+        }
+        (None, Some(scores)) => {
+            let positive = positive_label.clone().unwrap_or_default();
+            let negatives: Vec<&String> =
+                label_classes.iter().filter(|c| **c != positive).collect();
+            if label_classes.len() != 2 || negatives.len() != 1 {
+                return Err(
+                    "Deriving predictions from scores needs exactly two classes in labels, \
+                     one of them positive_label"
+                        .to_string(),
+                );
+            }
+            let threshold = input.threshold.unwrap_or(0.5);
+            if !threshold.is_finite() {
+                return Err("threshold must be a finite number".to_string());
+            }
+            let predicted = scores
+                .iter()
+                .map(|&s| {
+                    if s >= threshold {
+                        positive.clone()
+                    } else {
+                        negatives[0].clone()
+                    }
+                })
+                .collect();
+            (predicted, Some(threshold))
+        }
+        (None, None) => return Err("labels need predicted_labels or scores".to_string()),
+    };
+
+    let mut classes: Vec<String> = labels.iter().chain(&predicted).cloned().collect();
+    classes.sort();
+    classes.dedup();
+    if classes.len() > MAX_CLASSES {
+        return Err(format!(
+            "At most {MAX_CLASSES} distinct classes are allowed"
+        ));
+    }
+    let index = |label: &String| classes.binary_search(label).unwrap_or_default();
+    let mut confusion_matrix = vec![vec![0usize; classes.len()]; classes.len()];
+    for (label, prediction) in labels.iter().zip(&predicted) {
+        confusion_matrix[index(label)][index(prediction)] += 1;
+    }
+
+    let n = labels.len();
+    let correct: usize = (0..classes.len()).map(|i| confusion_matrix[i][i]).sum();
+    let mut undefined_precision = Vec::new();
+    let per_class: Vec<ClassMetrics> = classes
+        .iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let true_positives = confusion_matrix[i][i];
+            let support: usize = confusion_matrix[i].iter().sum();
+            let predicted_count: usize = confusion_matrix.iter().map(|row| row[i]).sum();
+            // Classes never predicted (or never observed) score zero, as is conventional
+            let precision = ratio(true_positives, predicted_count).unwrap_or_else(|| {
+                undefined_precision.push(label.clone());
+                0.0
+            });
+            let recall = ratio(true_positives, support).unwrap_or(0.0);
+            let f1 = if precision + recall > 0.0 {
+                2.0 * precision * recall / (precision + recall)
+            } else {
+                0.0
+            };
+            ClassMetrics {
+                label: label.clone(),
+                precision,
+                recall,
+                f1,
+                support,
+            }
+        })
+        .collect();
+    if !undefined_precision.is_empty() {
+        warnings.push(format!(
+            "Never predicted, so precision was set to 0: {}",
+            undefined_precision.join(", ")
+        ));
+    }
+    let unobserved: Vec<&str> = per_class
+        .iter()
+        .filter(|c| c.support == 0)
+        .map(|c| c.label.as_str())
+        .collect();
+    if !unobserved.is_empty() {
+        warnings.push(format!(
+            "Absent from labels, so recall was set to 0: {}",
+            unobserved.join(", ")
+        ));
+    }
+
+    let k = classes.len() as f64;
+    let roc_auc = match (&input.scores, &positive_label) {
+        (Some(scores), Some(positive)) => {
+            if !label_classes.contains(positive) {
+                return Err(format!(
+                    "positive_label '{positive}' does not occur in labels"
+                ));
+            }
+            if label_classes.len() > 2 {
+                return Err("ROC-AUC from scores needs binary labels".to_string());
+            }
+            let is_positive: Vec<bool> = labels.iter().map(|l| l == positive).collect();
+            let auc = roc_auc(scores, &is_positive);
+            if auc.is_none() {
+                warnings.push("ROC-AUC is undefined when labels contain one class".to_string());
+            }
+            auc
+        }
+        _ => None,
+    };
+
+    Ok(ClassificationMetrics {
+        accuracy: correct as f64 / n as f64,
+        confusion_matrix,
+        macro_precision: per_class.iter().map(|c| c.precision).sum::<f64>() / k,
+        macro_recall: per_class.iter().map(|c| c.recall).sum::<f64>() / k,
+        macro_f1: per_class.iter().map(|c| c.f1).sum::<f64>() / k,
+        weighted_f1: per_class
+            .iter()
+            .map(|c| c.f1 * c.support as f64)
+            .sum::<f64>()
+            / n as f64,
+        per_class,
+        classes,
+        roc_auc,
+        positive_label,
+        threshold,
+        count: n,
+    })
+}
+
+pub fn eval_metrics_logic(input: EvalMetricsInput) -> Result<EvalMetricsOutput, String> {
+    let lengths = [
+        input.actual.as_ref().map_or(0, Vec::len),
+        input.predicted.as_ref().map_or(0, Vec::len),
+        input.labels.as_ref().map_or(0, Vec::len),
+        input.predicted_labels.as_ref().map_or(0, Vec::len),
+        input.scores.as_ref().map_or(0, Vec::len),
+    ];
+    if lengths.iter().any(|&len| len > MAX_OBSERVATIONS) {
+        return Err(format!(
+            "At most {MAX_OBSERVATIONS} observations are allowed"
+        ));
+    }
+    let mut warnings = Vec::new();
+
+    let regression = match (&input.actual, &input.predicted) {
+        (Some(actual), Some(predicted)) => Some(regression_metrics(actual, predicted)?),
+        (None, None) => None,
+        _ => return Err("actual and predicted must be given together".to_string()),
+    };
+    if regression.as_ref().is_some_and(|r| r.mape.is_none()) {
+        warnings.push("MAPE is undefined when every actual value is zero".to_string());
+    }
+    if regression.as_ref().is_some_and(|r| r.r_squared.is_none()) {
+        warnings.push("R² is undefined when the actual values are constant".to_string());
+    }
+
+    let classification = match &input.labels {
+        Some(labels) => Some(classification_metrics(&input, labels, &mut warnings)?),
+        None if input.predicted_labels.is_some() || input.scores.is_some() => {
+            return Err("predicted_labels and scores need labels".to_string());
+        }
+        None => None,
+    };
+
+    if regression.is_none() && classification.is_none() {
+        return Err(
+            "Give actual and predicted for regression, or labels with predicted_labels or \
+             scores for classification"
+                .to_string(),
+        );
+    }
+
+    Ok(EvalMetricsOutput {
+        regression,
+        classification,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn request() -> EvalMetricsInput {
+        EvalMetricsInput {
+            actual: None,
+            predicted: None,
+            labels: None,
+            predicted_labels: None,
+            scores: None,
+            positive_label: None,
+            threshold: None,
+        }
+    }
+
+    #[test]
+    fn test_regression_metrics() {
+        let mut input = request();
+        input.actual = Some(vec![3.0, -0.5, 2.0, 7.0]);
+        input.predicted = Some(vec![2.5, 0.0, 2.0, 8.0]);
+        let result = eval_metrics_logic(input).unwrap();
+        let metrics = result.regression.unwrap();
+        assert!((metrics.mae - 0.5).abs() < 1e-12);
+        assert!((metrics.mse - 0.375).abs() < 1e-12);
+        // Matches the scikit-learn documentation example
+        assert!((metrics.r_squared.unwrap() - 0.948_608_137_044_967_9).abs() < 1e-12);
+        let mape = (0.5 / 3.0 + 1.0 + 0.0 + 1.0 / 7.0) / 4.0 * 100.0;
+        assert!((metrics.mape.unwrap() - mape).abs() < 1e-9);
+        assert!(result.classification.is_none());
+    }
+
+    #[test]
+    fn test_multiclass_confusion_matrix() {
+        let mut input = request();
+        input.labels = Some(strings(&["cat", "cat", "dog", "dog", "bird", "bird"]));
+        input.predicted_labels = Some(strings(&["cat", "dog", "dog", "dog", "cat", "bird"]));
+        let metrics = eval_metrics_logic(input).unwrap().classification.unwrap();
+        assert_eq!(metrics.classes, strings(&["bird", "cat", "dog"]));
+        assert_eq!(
+            metrics.confusion_matrix,
+            vec![vec![1, 1, 0], vec![0, 1, 1], vec![0, 0, 2]]
+        );
+        assert!((metrics.accuracy - 4.0 / 6.0).abs() < 1e-12);
+        let dog = &metrics.per_class[2];
+        assert!((dog.precision - 2.0 / 3.0).abs() < 1e-12 && dog.recall == 1.0);
+        assert!((dog.f1 - 0.8).abs() < 1e-12);
+        assert!(metrics.roc_auc.is_none());
+    }
+
+    #[test]
+    fn test_roc_auc_with_ties() {
+        let mut input = request();
+        input.labels = Some(strings(&["0", "0", "1", "1"]));
+        input.scores = Some(vec![0.1, 0.4, 0.35, 0.8]);
+        let metrics = eval_metrics_logic(input.clone())
+            .unwrap()
+            .classification
+            .unwrap();
+        assert!((metrics.roc_auc.unwrap() - 0.75).abs() < 1e-12);
+        assert_eq!(metrics.positive_label.as_deref(), Some("1"));
+        // Predictions derived at the default 0.5 threshold
+        assert_eq!(metrics.confusion_matrix, vec![vec![2, 0], vec![1, 1]]);
+
+        input.scores = Some(vec![0.5, 0.5, 0.5, 0.9]);
+        input.threshold = Some(0.6);
+        let metrics = eval_metrics_logic(input).unwrap().classification.unwrap();
+        // The tied negatives and positive count half: (2 * 0.5 + 2) / 4
+        assert!((metrics.roc_auc.unwrap() - 0.75).abs() < 1e-12);
+        assert_eq!(metrics.threshold, Some(0.6));
+    }
+
+    #[test]
+    fn test_undefined_metrics_warn() {
+        let mut input = request();
+        input.labels = Some(strings(&["a", "a", "b"]));
+        input.actual = Some(vec![0.0, 0.0, 0.0]);
+        input.predicted = Some(vec![1.0, -1.0, 0.0]);
+        input.predicted_labels = Some(strings(&["a", "a", "a"]));
+        let result = eval_metrics_logic(input).unwrap();
+        let regression = result.regression.unwrap();
+        assert!(regression.mape.is_none() && regression.r_squared.is_none());
+        let classification = result.classification.unwrap();
+        assert_eq!(classification.per_class[1].precision, 0.0);
+        assert_eq!(result.warnings.len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(eval_metrics_logic(request()).is_err());
+        let mut input = request();
+        input.actual = Some(vec![1.0, 2.0]);
+        input.predicted = Some(vec![1.0]);
+        assert_eq!(
+            eval_metrics_logic(input).unwrap_err(),
+            "actual has 2 values but predicted has 1"
+        );
+        let mut input = request();
+        input.labels = Some(strings(&["a", "b", "c"]));
+        input.scores = Some(vec![0.1, 0.2, 0.3]);
+        assert!(
+            eval_metrics_logic(input)
+                .unwrap_err()
+                .contains("two classes")
+        );
+        let mut input = request();
+        input.labels = Some(strings(&["a", "b"]));
+        input.predicted_labels = Some(strings(&["a", "b"]));
+        input.scores = Some(vec![0.1, 0.9]);
+        input.positive_label = Some("c".to_string());
+        assert!(
+            eval_metrics_logic(input)
+                .unwrap_err()
+                .contains("positive_label")
+        );
+        let mut input = request();
+        input.predicted_labels = Some(strings(&["a"]));
+        assert!(eval_metrics_logic(input).is_err());
+    }
+}
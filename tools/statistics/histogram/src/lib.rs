@@ -3,22 +3,45 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_chunked_upload::{ChunkReader, SpinStore};
+use ftl_encoding::ResponseEncoding;
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
     HistogramBin as LogicBin, HistogramInput as LogicInput, HistogramOutput as LogicOutput,
+    generate_histogram,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HistogramInput {
-    /// Array of numerical values to analyze
+    /// Array of numerical values to analyze (omit when using upload_session_id)
+    #[serde(default)]
     pub data: Vec<f64>,
+    /// Finalized chunked upload session holding the values, separated by commas or whitespace
+    pub upload_session_id: Option<String>,
     /// Number of bins for the histogram (optional, uses Sturges' rule if not specified)
     pub num_bins: Option<usize>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Response encoding: "json" (default) or "msgpack" (base64-encoded MessagePack, smaller for large arrays)
+    pub response_encoding: Option<ResponseEncoding>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -47,16 +70,68 @@ pub struct HistogramBin {
     pub density: f64,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn histogram(input: HistogramInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+fn read_uploaded_data(session_id: &str) -> Result<Vec<f64>, String> {
+    let store = SpinStore::open_default()?;
+    let reader = ChunkReader::open(&store, session_id)?;
+    let data = logic::parse_number_stream(reader)?;
+    ftl_chunked_upload::discard(&store, session_id)?;
+    Ok(data)
+}
+
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn histogram(request: Request<HistogramInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("histogram", HistogramInput, HistogramOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("histogram", &input);
+    log.finish(run_histogram(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_histogram(input: HistogramInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let encoding = input.response_encoding.unwrap_or_default();
+
+    // Read the values from a chunked upload if one was given
+    let data = match input.upload_session_id {
+        Some(_) if !input.data.is_empty() => {
+            return ToolResponse::text("Error: Give data or upload_session_id, not both");
+        }
+        Some(session_id) => match read_uploaded_data(&session_id) {
+            Ok(data) => data,
+            Err(e) => return ToolResponse::text(format!("Error: {e}")),
+        },
+        None => input.data,
+    };
+
+    // Reject oversized inputs, inline or uploaded, before binning them
+    if let Err(e) = Guard::from_env().array("data", &data).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
-        data: input.data,
+        data,
         num_bins: input.num_bins,
     };
 
     // Call logic implementation
-    match logic::generate_histogram(logic_input) {
+    match ftl_result_cache::cached_async("histogram", &logic_input, || {
+        logic::generate_histogram(logic_input.clone())
+    })
+    .await
+    {
         Ok(result) => {
             // Convert back to wrapper types
             let response = HistogramOutput {
@@ -75,8 +150,32 @@ pub fn histogram(input: HistogramInput) -> ToolResponse {
                 bin_width: result.bin_width,
                 range: result.range,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_encoding::encode(&response, encoding, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_and_upload_session_are_exclusive() {
+        let input = HistogramInput {
+            data: vec![1.0, 2.0, 3.0],
+            upload_session_id: Some("session".to_string()),
+            num_bins: None,
+            precision: None,
+            output_precision: None,
+            response_encoding: None,
+            include_metrics: None,
+        };
+        let response = ftl_yield::block_on(run_histogram(input));
+        let text = serde_json::to_string(&response).unwrap();
+        assert!(text.contains("not both"), "{text}");
+    }
+}
@@ -1,4 +1,7 @@
+use ftl_yield::Yielder;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "individual")]
+use std::io::{BufRead, BufReader, Read};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistogramInput {
@@ -23,7 +26,7 @@ pub struct HistogramBin {
     pub density: f64,
 }
 
-pub fn generate_histogram(input: HistogramInput) -> Result<HistogramOutput, String> {
+pub async fn generate_histogram(input: HistogramInput) -> Result<HistogramOutput, String> {
     if input.data.is_empty() {
         return Err("Input data cannot be empty".to_string());
     }
@@ -55,7 +58,9 @@ pub fn generate_histogram(input: HistogramInput) -> Result<HistogramOutput, Stri
     let mut counts = vec![0; num_bins];
 
     // Count data points in each bin
+    let mut yielder = Yielder::default();
     for &value in data {
+        yielder.tick().await;
         let bin_index = if value == max_val {
             num_bins - 1 // Put max value in last bin
         } else {
@@ -106,10 +111,56 @@ pub fn generate_histogram(input: HistogramInput) -> Result<HistogramOutput, Stri
     })
 }
 
+/// Read numbers separated by commas, whitespace or JSON array brackets from `source`,
+/// one buffered block at a time. Used for chunked uploads.
+#[cfg(feature = "individual")]
+pub fn parse_number_stream<R: Read>(source: R) -> Result<Vec<f64>, String> {
+    let mut reader = BufReader::new(source);
+    let mut values = Vec::new();
+    let mut token = String::new();
+
+    let mut push_token = |token: &mut String| -> Result<(), String> {
+        if !token.is_empty() {
+            let value = token
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid number in uploaded data: '{token}'"))?;
+            values.push(value);
+            token.clear();
+        }
+        Ok(())
+    };
+
+    loop {
+        let block = reader
+            .fill_buf()
+            .map_err(|e| format!("Failed to read uploaded data: {e}"))?;
+        if block.is_empty() {
+            break;
+        }
+        for &byte in block {
+            match byte {
+                b',' | b'[' | b']' => push_token(&mut token)?,
+                b if b.is_ascii_whitespace() => push_token(&mut token)?,
+                b => token.push(b as char),
+            }
+        }
+        let consumed = block.len();
+        reader.consume(consumed);
+    }
+    push_token(&mut token)?;
+
+    Ok(values)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // Drive the async logic to completion from synchronous tests
+    fn generate_histogram(input: HistogramInput) -> Result<HistogramOutput, String> {
+        ftl_yield::block_on(super::generate_histogram(input))
+    }
+
     #[test]
     fn test_basic_histogram() {
         let input = HistogramInput {
@@ -189,4 +240,26 @@ mod tests {
         assert_eq!(result.bins[1].count, 4);
         assert_eq!(result.bins[1].frequency, 4.0 / 9.0);
     }
+
+    #[test]
+    fn test_parse_number_stream_across_chunks() {
+        use ftl_chunked_upload::{ChunkReader, MemoryStore};
+
+        let store = MemoryStore::new();
+        let session = ftl_chunked_upload::init(&store, None).unwrap();
+        for chunk in ["[1.5, 2", "5, -3e", "2,\n4]"] {
+            ftl_chunked_upload::append(&store, &session.session_id, None, chunk).unwrap();
+        }
+        ftl_chunked_upload::finalize(&store, &session.session_id).unwrap();
+
+        let reader = ChunkReader::open(&store, &session.session_id).unwrap();
+        let data = parse_number_stream(reader).unwrap();
+        assert_eq!(data, vec![1.5, 25.0, -300.0, 4.0]);
+    }
+
+    #[test]
+    fn test_parse_number_stream_invalid_token() {
+        let err = parse_number_stream("1, 2, abc".as_bytes()).unwrap_err();
+        assert!(err.contains("'abc'"));
+    }
 }
@@ -0,0 +1,121 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{HllInput as LogicInput, HllOutput as LogicOutput, estimate_cardinality};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HllInput {
+    /// IDs to add to the sketch; repeated IDs are counted once
+    pub items: Option<Vec<String>>,
+    /// Base64 sketches returned by earlier calls, merged together with items
+    pub sketches: Option<Vec<String>>,
+    /// Log2 of the register count, 4-16: higher is more accurate and larger
+    /// (default: 12, or the smallest among merged sketches)
+    pub register_bits: Option<u8>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HllOutput {
+    /// Base64 sketch of all items so far; pass it back in sketches to add or merge more
+    pub sketch: String,
+    /// Estimated number of distinct items, rounded
+    pub cardinality: u64,
+    /// Unrounded distinct-count estimate
+    pub estimate: f64,
+    /// Relative standard error of the estimate (1.04 / sqrt(registers))
+    pub standard_error: f64,
+    /// Log2 of the register count of the sketch
+    pub register_bits: u8,
+    /// Number of items added in this call
+    pub items_added: usize,
+    /// Number of sketches merged in this call
+    pub sketches_merged: usize,
+}
+
+/// Estimate distinct counts with HyperLogLog sketches that can be built chunk by chunk
+/// and merged, for ID streams too large to send in one request
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn hll(request: Request<HllInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("hll", HllInput, HllOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("hll", &input);
+    log.finish(run_hll(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_hll(input: HllInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    let sketches = input.sketches.as_deref().unwrap_or_default();
+    let guard = Guard::from_env()
+        .array("items", input.items.as_deref().unwrap_or_default())
+        .array("sketches", sketches);
+    let guard = sketches
+        .iter()
+        .fold(guard, |guard, sketch| guard.string("sketches", sketch));
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        items: input.items,
+        sketches: input.sketches,
+        register_bits: input.register_bits,
+    };
+
+    // Call logic implementation
+    match logic::estimate_cardinality(logic_input) {
+        Ok(result) => {
+            // Convert back to wrapper types
+            let response = HllOutput {
+                sketch: result.sketch,
+                cardinality: result.cardinality,
+                estimate: result.estimate,
+                standard_error: result.standard_error,
+                register_bits: result.register_bits,
+                items_added: result.items_added,
+                sketches_merged: result.sketches_merged,
+            };
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
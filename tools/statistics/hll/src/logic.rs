@@ -0,0 +1,325 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+
+/// Default register bits: 4096 registers, about 1.6% standard error
+pub const DEFAULT_REGISTER_BITS: u8 = 12;
+pub const MIN_REGISTER_BITS: u8 = 4;
+pub const MAX_REGISTER_BITS: u8 = 16;
+
+/// Leading bytes of a serialized sketch: magic "HL" and the format version
+const HEADER: [u8; 3] = [b'H', b'L', 1];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HllInput {
+    pub items: Option<Vec<String>>,
+    pub sketches: Option<Vec<String>>,
+    pub register_bits: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HllOutput {
+    pub sketch: String,
+    pub cardinality: u64,
+    pub estimate: f64,
+    pub standard_error: f64,
+    pub register_bits: u8,
+    pub items_added: usize,
+    pub sketches_merged: usize,
+}
+
+/// HyperLogLog (Flajolet et al.) over 64-bit hashes, with linear counting
+/// for small cardinalities
+#[derive(Debug, Clone, PartialEq)]
+struct Sketch {
+    bits: u8,
+    registers: Vec<u8>,
+}
+
+impl Sketch {
+    fn new(bits: u8) -> Self {
+        Sketch {
+            bits,
+            registers: vec![0; 1 << bits],
+        }
+    }
+
+    fn add(&mut self, item: &str) {
+        let hash = hash64(item.as_bytes());
+        let index = (hash >> (64 - self.bits)) as usize;
+        // Rank of the first set bit after the index bits, capped by the sentinel
+        let rest = (hash << self.bits) | (1 << (self.bits - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    /// Fold into fewer registers so sketches of different sizes can be merged
+    fn reduce(self, bits: u8) -> Self {
+        if bits >= self.bits {
+            return self;
+        }
+        let shift = self.bits - bits;
+        let mut reduced = Sketch::new(bits);
+        for (index, &rank) in self.registers.iter().enumerate() {
+            if rank == 0 {
+                continue;
+            }
+            // The dropped index bits become the leading bits of the rank
+            let dropped = index & ((1 << shift) - 1);
+            let rank = if dropped == 0 {
+                rank + shift
+            } else {
+                (dropped << (usize::BITS - u32::from(shift))).leading_zeros() as u8 + 1
+            };
+            let target = &mut reduced.registers[index >> shift];
+            *target = (*target).max(rank);
+        }
+        reduced
+    }
+
+    fn merge(&mut self, other: &Sketch) {
+        for (register, &rank) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(rank);
+        }
+    }
+
+    fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&rank| rank == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate while many registers are empty
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+
+    fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(HEADER.len() + 1 + self.registers.len());
+        bytes.extend_from_slice(&HEADER);
+        bytes.push(self.bits);
+        bytes.extend_from_slice(&self.registers);
+        general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn decode(encoded: &str) -> Result<Self, String> {
+        let bytes = general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("invalid base64: {e}"))?;
+        if bytes.len() <= HEADER.len() || bytes[..HEADER.len()] != HEADER {
+            return Err("not a serialized HyperLogLog sketch".to_string());
+        }
+        let bits = bytes[HEADER.len()];
+        if !(MIN_REGISTER_BITS..=MAX_REGISTER_BITS).contains(&bits) {
+            return Err(format!("unsupported register_bits {bits}"));
+        }
+        let registers = bytes[HEADER.len() + 1..].to_vec();
+        if registers.len() != 1 << bits {
+            return Err(format!(
+                "expected {} registers, found {}",
+                1 << bits,
+                registers.len()
+            ));
+        }
+        if registers.iter().any(|&rank| rank > 65 - bits) {
+            return Err("sketch contents are inconsistent".to_string());
+        }
+        Ok(Sketch { bits, registers })
+    }
+}
+
+/// FNV-1a followed by the MurmurHash3 finalizer, so similar IDs spread over
+/// all 64 bits; stable across platforms and releases
+fn hash64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^ (hash >> 33)
+}
+
+pub fn estimate_cardinality(input: HllInput) -> Result<HllOutput, String> {
+    let items = input.items.unwrap_or_default();
+    let encoded = input.sketches.unwrap_or_default();
+    if items.is_empty() && encoded.is_empty() {
+        return Err("Provide items, sketches to merge, or both".to_string());
+    }
+
+    let sketches = encoded
+        .iter()
+        .enumerate()
+        .map(|(i, s)| Sketch::decode(s).map_err(|e| format!("sketches[{i}]: {e}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Merged sketches use the coarsest register count among them unless one is given
+    let bits = match input.register_bits {
+        Some(bits) if (MIN_REGISTER_BITS..=MAX_REGISTER_BITS).contains(&bits) => bits,
+        Some(bits) => {
+            return Err(format!(
+                "register_bits must be between {MIN_REGISTER_BITS} and {MAX_REGISTER_BITS}, got {bits}"
+            ));
+        }
+        None => sketches
+            .iter()
+            .map(|s| s.bits)
+            .min()
+            .unwrap_or(DEFAULT_REGISTER_BITS),
+    };
+    if let Some(s) = sketches.iter().find(|s| s.bits < bits) {
+        return Err(format!(
+            "register_bits {bits} is larger than a merged sketch's {}; registers cannot be split",
+            s.bits
+        ));
+    }
+
+    let mut sketch = Sketch::new(bits);
+    let sketches_merged = sketches.len();
+    for other in sketches {
+        sketch.merge(&other.reduce(bits));
+    }
+    for item in &items {
+        sketch.add(item);
+    }
+
+    let estimate = sketch.estimate();
+    Ok(HllOutput {
+        sketch: sketch.encode(),
+        cardinality: estimate.round() as u64,
+        estimate,
+        standard_error: 1.04 / (sketch.registers.len() as f64).sqrt(),
+        register_bits: bits,
+        items_added: items.len(),
+        sketches_merged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(items: Vec<String>) -> HllInput {
+        HllInput {
+            items: Some(items),
+            sketches: None,
+            register_bits: None,
+        }
+    }
+
+    fn ids(range: std::ops::Range<u32>) -> Vec<String> {
+        range.map(|i| format!("user-{i}")).collect()
+    }
+
+    #[test]
+    fn test_small_counts_are_near_exact() {
+        let result = estimate_cardinality(input(ids(0..100))).unwrap();
+        assert!(
+            (result.estimate - 100.0).abs() <= 2.0,
+            "{}",
+            result.estimate
+        );
+        assert_eq!(result.register_bits, DEFAULT_REGISTER_BITS);
+        assert_eq!(result.items_added, 100);
+
+        // Duplicates do not count twice
+        let mut items = ids(0..100);
+        items.extend(ids(0..100));
+        let repeated = estimate_cardinality(input(items)).unwrap();
+        assert_eq!(repeated.sketch, result.sketch);
+    }
+
+    #[test]
+    fn test_large_count_within_error() {
+        let result = estimate_cardinality(input(ids(0..200_000))).unwrap();
+        let error = (result.estimate - 200_000.0).abs() / 200_000.0;
+        assert!(error < 3.0 * result.standard_error, "{}", result.estimate);
+    }
+
+    #[test]
+    fn test_chunked_merge_equals_single_pass() {
+        let whole = estimate_cardinality(input(ids(0..30_000))).unwrap();
+
+        // One chunk at a time, passing the sketch back in
+        let mut sketch: Option<String> = None;
+        for start in (0..30_000).step_by(10_000) {
+            let result = estimate_cardinality(HllInput {
+                items: Some(ids(start..start + 10_000)),
+                sketches: sketch.map(|s| vec![s]),
+                register_bits: None,
+            })
+            .unwrap();
+            sketch = Some(result.sketch);
+        }
+        assert_eq!(sketch.unwrap(), whole.sketch);
+
+        // Merging independent chunks with overlap
+        let a = estimate_cardinality(input(ids(0..20_000))).unwrap().sketch;
+        let b = estimate_cardinality(input(ids(10_000..30_000)))
+            .unwrap()
+            .sketch;
+        let merged = estimate_cardinality(HllInput {
+            items: None,
+            sketches: Some(vec![a, b]),
+            register_bits: None,
+        })
+        .unwrap();
+        assert_eq!(merged.sketch, whole.sketch);
+        assert_eq!(merged.sketches_merged, 2);
+    }
+
+    #[test]
+    fn test_reduce_matches_direct_sketch() {
+        let mut fine = input(ids(0..5_000));
+        fine.register_bits = Some(14);
+        let fine = estimate_cardinality(fine).unwrap();
+        let coarse = estimate_cardinality(input(ids(0..5_000))).unwrap();
+
+        let reduced = estimate_cardinality(HllInput {
+            items: None,
+            sketches: Some(vec![fine.sketch]),
+            register_bits: Some(12),
+        })
+        .unwrap();
+        assert_eq!(reduced.sketch, coarse.sketch);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(estimate_cardinality(input(vec![])).is_err());
+
+        let mut request = input(ids(0..1));
+        request.register_bits = Some(20);
+        assert_eq!(
+            estimate_cardinality(request).unwrap_err(),
+            "register_bits must be between 4 and 16, got 20"
+        );
+
+        let sketch = estimate_cardinality(input(ids(0..1))).unwrap().sketch;
+        let mut request = input(vec![]);
+        request.sketches = Some(vec![sketch]);
+        request.register_bits = Some(14);
+        assert!(estimate_cardinality(request).is_err());
+
+        let mut request = input(vec![]);
+        request.sketches = Some(vec!["bm90IGEgc2tldGNo".to_string()]);
+        assert_eq!(
+            estimate_cardinality(request).unwrap_err(),
+            "sketches[0]: not a serialized HyperLogLog sketch"
+        );
+    }
+}
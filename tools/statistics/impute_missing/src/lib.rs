@@ -0,0 +1,79 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    ImputeInput as LogicInput, ImputeOutput as LogicOutput, ImputedValue as LogicImputedValue,
+    MAX_KNN_ROWS, MAX_VALUES, impute_missing_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{ImputeOutputSchema as ImputeOutput, ImputedValueSchema as ImputedValue};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImputeInput {
+    #[serde(flatten)]
+    pub params: logic::ImputeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Fill missing values in numeric series by mean, median, mode, forward or backward
+/// fill, linear interpolation or k-nearest neighbours, reporting every value filled
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn impute_missing(request: Request<ImputeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("impute_missing", ImputeInput, ImputeOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("impute_missing", &input);
+    log.finish(run_impute_missing(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_impute_missing(input: ImputeInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("series", &input.params.series)
+        .array_len("series", input.params.series.iter().map(Vec::len).sum())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::impute_missing_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &ImputeOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,393 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most values, over all series, accepted in one call
+pub const MAX_VALUES: usize = 1_000_000;
+/// Most rows for k-NN imputation, which compares every missing row with every other
+pub const MAX_KNN_ROWS: usize = 10_000;
+/// Default number of neighbours for k-NN imputation
+const DEFAULT_NEIGHBORS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ImputeInput {
+    /// One or more aligned numeric series of equal length; null marks a missing value
+    pub series: Vec<Vec<Option<f64>>>,
+    /// "mean", "median", "mode", "forward_fill", "backward_fill", "linear" or "knn"
+    pub strategy: String,
+    /// Values that also mark a missing observation, such as -999
+    pub missing_markers: Option<Vec<f64>>,
+    /// Neighbours averaged by the knn strategy (default: 5). Rows are compared on the
+    /// other series; with a single series, neighbours are the nearest positions
+    pub k: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ImputedValue {
+    /// Series the value belongs to
+    pub series: usize,
+    /// Position within the series
+    pub index: usize,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ImputeOutput {
+    pub strategy: String,
+    /// Series with missing values filled; values that could not be filled stay null
+    pub series: Vec<Vec<Option<f64>>>,
+    /// Every filled value, by series then position
+    #[tool_types(nested)]
+    pub imputed: Vec<ImputedValue>,
+    pub imputed_count: usize,
+    /// Missing values per series before imputation
+    pub missing_counts: Vec<usize>,
+    /// Missing values left unfilled, such as leading gaps under forward fill
+    pub remaining_missing: usize,
+    /// Constant fill value per series for mean, median and mode
+    pub fill_values: Option<Vec<Option<f64>>>,
+    pub warnings: Vec<String>,
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len();
+    match n {
+        0 => None,
+        _ if n % 2 == 1 => Some(sorted[n / 2]),
+        _ => Some((sorted[n / 2 - 1] + sorted[n / 2]) / 2.0),
+    }
+}
+
+/// Most frequent value, the smallest on ties
+fn mode(values: &[f64]) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mut best: Option<(f64, usize)> = None;
+    for run in sorted.chunk_by(|a, b| a == b) {
+        if best.is_none_or(|(_, count)| run.len() > count) {
+            best = Some((run[0], run.len()));
+        }
+    }
+    best.map(|(value, _)| value)
+}
+
+/// Fills each gap with the nearest observed value before it, or after it when
+/// `backward` is set
+fn carry(series: &[Option<f64>], backward: bool) -> Vec<Option<f64>> {
+    let mut filled = series.to_vec();
+    let mut last = None;
+    let order: Box<dyn Iterator<Item = usize>> = if backward {
+        Box::new((0..series.len()).rev())
+    } else {
+        Box::new(0..series.len())
+    };
+    for i in order {
+        match series[i] {
+            Some(value) => last = Some(value),
+            None => filled[i] = last,
+        }
+    }
+    filled
+}
+
+/// Interpolates linearly by position between the observed values around each gap
+fn interpolate(series: &[Option<f64>]) -> Vec<Option<f64>> {
+    let mut filled = series.to_vec();
+    let mut previous: Option<(usize, f64)> = None;
+    for (i, value) in series.iter().enumerate() {
+        let Some(value) = *value else { continue };
+        if let Some((start, start_value)) = previous {
+            let span = (i - start) as f64;
+            for (j, slot) in filled.iter_mut().enumerate().take(i).skip(start + 1) {
+                *slot = Some(start_value + (value - start_value) * (j - start) as f64 / span);
+            }
+        }
+        previous = Some((i, value));
+    }
+    filled
+}
+
+/// Averages the `k` nearest rows observed in `target`. Distances use the other series
+/// observed in both rows, scaled up for the coordinates missing (as in nan-Euclidean
+/// distance); rows sharing no other series fall back to distance by position
+fn knn(columns: &[Vec<Option<f64>>], target: usize, k: usize) -> Vec<Option<f64>> {
+    let rows = columns[target].len();
+    let others: Vec<&Vec<Option<f64>>> = columns
+        .iter()
+        .enumerate()
+        .filter(|(c, _)| *c != target)
+        .map(|(_, column)| column)
+        .collect();
+    let distance = |a: usize, b: usize| -> (bool, f64) {
+        let (mut sum, mut shared) = (0.0, 0);
+        for column in &others {
+            if let (Some(x), Some(y)) = (column[a], column[b]) {
+                sum += (x - y).powi(2);
+                shared += 1;
+            }
+        }
+        if shared == 0 {
+            (false, a.abs_diff(b) as f64)
+        } else {
+            (true, (sum * others.len() as f64 / shared as f64).sqrt())
+        }
+    };
+    let donors: Vec<usize> = (0..rows)
+        .filter(|&r| columns[target][r].is_some())
+        .collect();
+    let mut filled = columns[target].clone();
+    for (row, slot) in filled.iter_mut().enumerate() {
+        if slot.is_some() || donors.is_empty() {
+            continue;
+        }
+        // Rows compared on feature values rank ahead of those compared by position
+        let mut ranked: Vec<((bool, f64), usize)> =
+            donors.iter().map(|&d| (distance(row, d), d)).collect();
+        ranked.sort_by(|(a, ai), (b, bi)| b.0.cmp(&a.0).then(a.1.total_cmp(&b.1)).then(ai.cmp(bi)));
+        let nearest: Vec<f64> = ranked
+            .iter()
+            .take(k)
+            .filter_map(|(_, d)| columns[target][*d])
+            .collect();
+        *slot = mean(&nearest);
+    }
+    filled
+}
+
+pub fn impute_missing_logic(input: ImputeInput) -> Result<ImputeOutput, String> {
+    if input.series.is_empty() {
+        return Err("At least one series is required".to_string());
+    }
+    let rows = input.series[0].len();
+    if input.series.iter().any(|s| s.len() != rows) {
+        return Err("All series must have the same length".to_string());
+    }
+    if rows == 0 {
+        return Err("Series must not be empty".to_string());
+    }
+    if rows * input.series.len() > MAX_VALUES {
+        return Err(format!("At most {MAX_VALUES} values are allowed"));
+    }
+    let strategy = input.strategy.trim().to_lowercase();
+    let k = input.k.unwrap_or(DEFAULT_NEIGHBORS);
+    if strategy == "knn" {
+        if k == 0 {
+            return Err("k must be at least 1".to_string());
+        }
+        if rows > MAX_KNN_ROWS {
+            return Err(format!(
+                "knn imputation accepts at most {MAX_KNN_ROWS} rows"
+            ));
+        }
+    } else if input.k.is_some() {
+        return Err("k only applies to the knn strategy".to_string());
+    }
+
+    // Markers and non-finite values count as missing, like null
+    let markers = input.missing_markers.unwrap_or_default();
+    let columns: Vec<Vec<Option<f64>>> = input
+        .series
+        .iter()
+        .map(|series| {
+            series
+                .iter()
+                .map(|v| v.filter(|v| v.is_finite() && !markers.contains(v)))
+                .collect()
+        })
+        .collect();
+    let missing_counts: Vec<usize> = columns
+        .iter()
+        .map(|c| c.iter().filter(|v| v.is_none()).count())
+        .collect();
+
+    let observed = |column: &Vec<Option<f64>>| column.iter().flatten().copied().collect::<Vec<_>>();
+    let mut fill_values = None;
+    let filled: Vec<Vec<Option<f64>>> = match strategy.as_str() {
+        "mean" | "median" | "mode" => {
+            let statistic = match strategy.as_str() {
+                "mean" => mean,
+                "median" => median,
+                _ => mode,
+            };
+            let values: Vec<Option<f64>> =
+                columns.iter().map(|c| statistic(&observed(c))).collect();
+            let filled = columns
+                .iter()
+                .zip(&values)
+                .map(|(column, fill)| column.iter().map(|v| v.or(*fill)).collect())
+                .collect();
+            fill_values = Some(values);
+            filled
+        }
+        "forward_fill" => columns.iter().map(|c| carry(c, false)).collect(),
+        "backward_fill" => columns.iter().map(|c| carry(c, true)).collect(),
+        "linear" => columns.iter().map(|c| interpolate(c)).collect(),
+        "knn" => (0..columns.len()).map(|c| knn(&columns, c, k)).collect(),
+        other => {
+            return Err(format!(
+                "Unknown strategy '{other}', use 'mean', 'median', 'mode', 'forward_fill', \
+                 'backward_fill', 'linear' or 'knn'"
+            ));
+        }
+    };
+
+    let mut imputed = Vec::new();
+    for (s, (before, after)) in columns.iter().zip(&filled).enumerate() {
+        for (index, (old, new)) in before.iter().zip(after).enumerate() {
+            if let (None, Some(value)) = (old, new) {
+                imputed.push(ImputedValue {
+                    series: s,
+                    index,
+                    value: *value,
+                });
+            }
+        }
+    }
+    let total_missing: usize = missing_counts.iter().sum();
+    let remaining_missing = total_missing - imputed.len();
+
+    let mut warnings = Vec::new();
+    for (s, column) in columns.iter().enumerate() {
+        if column.iter().all(Option::is_none) {
+            warnings.push(format!("Series {s} has no observed values to impute from"));
+        }
+    }
+    if remaining_missing > 0
+        && matches!(
+            strategy.as_str(),
+            "forward_fill" | "backward_fill" | "linear"
+        )
+    {
+        warnings.push(format!(
+            "{remaining_missing} values at the ends of a series have no observed neighbour on \
+             the side {strategy} needs and were left missing"
+        ));
+    }
+
+    Ok(ImputeOutput {
+        strategy,
+        series: filled,
+        imputed_count: imputed.len(),
+        imputed,
+        missing_counts,
+        remaining_missing,
+        fill_values,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(series: Vec<Vec<Option<f64>>>, strategy: &str) -> ImputeInput {
+        ImputeInput {
+            series,
+            strategy: strategy.to_string(),
+            missing_markers: None,
+            k: None,
+        }
+    }
+
+    #[test]
+    fn test_constant_fills() {
+        let series = vec![vec![
+            Some(1.0),
+            None,
+            Some(2.0),
+            Some(2.0),
+            Some(10.0),
+            None,
+        ]];
+        let mean = impute_missing_logic(request(series.clone(), "mean")).unwrap();
+        assert_eq!(mean.series[0][1], Some(3.75));
+        assert_eq!(mean.fill_values, Some(vec![Some(3.75)]));
+        assert_eq!((mean.imputed_count, mean.remaining_missing), (2, 0));
+
+        let median = impute_missing_logic(request(series.clone(), "median")).unwrap();
+        assert_eq!(median.series[0][5], Some(2.0));
+        let mode = impute_missing_logic(request(series, "mode")).unwrap();
+        assert_eq!(mode.series[0][1], Some(2.0));
+        assert_eq!(mode.imputed[1].index, 5);
+    }
+
+    #[test]
+    fn test_fills_and_interpolation() {
+        let series = vec![vec![None, Some(1.0), None, None, Some(4.0), None]];
+        let forward = impute_missing_logic(request(series.clone(), "forward_fill")).unwrap();
+        assert_eq!(
+            forward.series[0],
+            vec![None, Some(1.0), Some(1.0), Some(1.0), Some(4.0), Some(4.0)]
+        );
+        assert_eq!(forward.remaining_missing, 1);
+        assert_eq!(forward.warnings.len(), 1);
+
+        let backward = impute_missing_logic(request(series.clone(), "backward_fill")).unwrap();
+        assert_eq!(backward.series[0][0], Some(1.0));
+        assert_eq!(backward.series[0][5], None);
+
+        let linear = impute_missing_logic(request(series, "linear")).unwrap();
+        assert_eq!(
+            linear.series[0],
+            vec![None, Some(1.0), Some(2.0), Some(3.0), Some(4.0), None]
+        );
+        assert_eq!((linear.imputed_count, linear.remaining_missing), (2, 2));
+    }
+
+    #[test]
+    fn test_markers_count_as_missing() {
+        let mut input = request(vec![vec![Some(1.0), Some(-999.0), Some(3.0)]], "linear");
+        input.missing_markers = Some(vec![-999.0]);
+        let result = impute_missing_logic(input).unwrap();
+        assert_eq!(result.missing_counts, vec![1]);
+        assert_eq!(result.series[0][1], Some(2.0));
+    }
+
+    #[test]
+    fn test_knn() {
+        // Row 3 is closest to rows 0 and 1 on the second series
+        let series = vec![
+            vec![Some(10.0), Some(12.0), Some(50.0), None, Some(52.0)],
+            vec![Some(1.0), Some(1.1), Some(5.0), Some(1.05), Some(5.1)],
+        ];
+        let mut input = request(series, "knn");
+        input.k = Some(2);
+        let result = impute_missing_logic(input).unwrap();
+        assert_eq!(result.series[0][3], Some(11.0));
+
+        // A single series uses the nearest positions
+        let mut input = request(
+            vec![vec![Some(1.0), Some(2.0), None, Some(4.0), Some(9.0)]],
+            "knn",
+        );
+        input.k = Some(2);
+        let result = impute_missing_logic(input).unwrap();
+        assert_eq!(result.series[0][2], Some(3.0));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(impute_missing_logic(request(vec![], "mean")).is_err());
+        let ragged = vec![vec![Some(1.0)], vec![Some(1.0), None]];
+        assert_eq!(
+            impute_missing_logic(request(ragged, "mean")).unwrap_err(),
+            "All series must have the same length"
+        );
+        assert!(
+            impute_missing_logic(request(vec![vec![None]], "spline"))
+                .unwrap_err()
+                .contains("Unknown strategy")
+        );
+        let mut input = request(vec![vec![None, Some(1.0)]], "mean");
+        input.k = Some(3);
+        assert!(impute_missing_logic(input).is_err());
+        let empty = impute_missing_logic(request(vec![vec![None, None]], "mean")).unwrap();
+        assert_eq!(empty.remaining_missing, 2);
+        assert_eq!(empty.warnings.len(), 1);
+    }
+}
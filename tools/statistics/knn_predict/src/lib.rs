@@ -0,0 +1,89 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    DEFAULT_K, KnnPredictInput as LogicInput, KnnPredictOutput as LogicOutput, MAX_VALUES,
+    knn_predict_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    KnnPredictOutputSchema as KnnPredictOutput, KnnPredictionSchema as KnnPrediction,
+    NeighborSchema as Neighbor,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KnnPredictInput {
+    #[serde(flatten)]
+    pub params: logic::KnnPredictInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Predict a class by neighbor vote or a value by neighbor average for each query row,
+/// using the k nearest of the supplied training rows
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn knn_predict(request: Request<KnnPredictInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("knn_predict", KnnPredictInput, KnnPredictOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("knn_predict", &input);
+    log.finish(run_knn_predict(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_knn_predict(input: KnnPredictInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("features", &input.params.features)
+        .array_len("features", input.params.features.iter().map(Vec::len).sum())
+        .array("labels", input.params.labels.as_deref().unwrap_or_default())
+        .array(
+            "targets",
+            input.params.targets.as_deref().unwrap_or_default(),
+        )
+        .array("queries", &input.params.queries)
+        .array_len("queries", input.params.queries.iter().map(Vec::len).sum())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::knn_predict_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &KnnPredictOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
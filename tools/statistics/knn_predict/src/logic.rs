@@ -0,0 +1,394 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most feature values, training plus query rows times columns, accepted in one call
+pub const MAX_VALUES: usize = 1_000_000;
+/// Default number of neighbors
+pub const DEFAULT_K: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct KnnPredictInput {
+    /// Training rows, one feature vector per observation
+    pub features: Vec<Vec<f64>>,
+    /// Class label of each training row, for classification
+    pub labels: Option<Vec<String>>,
+    /// Numeric target of each training row, for regression
+    pub targets: Option<Vec<f64>>,
+    /// Rows to predict, with the same features as the training rows
+    pub queries: Vec<Vec<f64>>,
+    /// Number of neighbors consulted, at most the number of training rows (default: 5)
+    pub k: Option<usize>,
+    /// "euclidean", "manhattan" or "chebyshev" (default: euclidean)
+    pub metric: Option<String>,
+    /// "uniform", or "distance" to weight neighbors by inverse distance (default: uniform)
+    pub weights: Option<String>,
+    /// Z-score each feature with the training rows' mean and standard deviation before
+    /// measuring distances, so features on large scales do not dominate (default: false)
+    pub standardize: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Neighbor {
+    /// Index of the training row
+    pub index: usize,
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct KnnPrediction {
+    /// Predicted class, for classification
+    pub label: Option<String>,
+    /// Share of the neighbors' weight that voted for the predicted class
+    pub confidence: Option<f64>,
+    /// Predicted value, the (weighted) mean of the neighbors' targets, for regression
+    pub value: Option<f64>,
+    /// The k nearest training rows, nearest first
+    #[tool_types(nested)]
+    pub neighbors: Vec<Neighbor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct KnnPredictOutput {
+    /// One prediction per query, in input order
+    #[tool_types(nested)]
+    pub predictions: Vec<KnnPrediction>,
+    /// "classification" or "regression"
+    pub task: String,
+    pub k: usize,
+    pub metric: String,
+    pub weights: String,
+}
+
+fn check_rows(rows: &[Vec<f64>], name: &str, width: usize) -> Result<(), String> {
+    if let Some(row) = rows.iter().position(|r| r.len() != width) {
+        return Err(format!(
+            "{name} row {row} has {} values, expected {width}",
+            rows[row].len()
+        ));
+    }
+    if rows.iter().flatten().any(|v| !v.is_finite()) {
+        return Err(format!("{name} must contain finite numbers"));
+    }
+    Ok(())
+}
+
+fn distance(metric: &str, a: &[f64], b: &[f64]) -> f64 {
+    let differences = a.iter().zip(b).map(|(x, y)| (x - y).abs());
+    match metric {
+        "manhattan" => differences.sum(),
+        "chebyshev" => differences.fold(0.0, f64::max),
+        _ => differences.map(|d| d * d).sum::<f64>().sqrt(),
+    }
+}
+
+pub fn knn_predict_logic(input: KnnPredictInput) -> Result<KnnPredictOutput, String> {
+    let width = input.features.first().map_or(0, Vec::len);
+    if width == 0 {
+        return Err("features must contain at least one non-empty row".to_string());
+    }
+    if input.queries.is_empty() {
+        return Err("queries must contain at least one row".to_string());
+    }
+    let total_rows = input.features.len().saturating_add(input.queries.len());
+    if total_rows.saturating_mul(width) > MAX_VALUES {
+        return Err(format!("At most {MAX_VALUES} feature values are allowed"));
+    }
+    check_rows(&input.features, "features", width)?;
+    check_rows(&input.queries, "queries", width)?;
+
+    let n = input.features.len();
+    let task = match (&input.labels, &input.targets) {
+        (Some(labels), None) if labels.len() == n => "classification",
+        (None, Some(targets)) if targets.len() == n => {
+            if targets.iter().any(|t| !t.is_finite()) {
+                return Err("targets must be finite numbers".to_string());
+            }
+            "regression"
+        }
+        (Some(values), None) => {
+            return Err(format!("Got {} labels for {n} training rows", values.len()));
+        }
+        (None, Some(values)) => {
+            return Err(format!(
+                "Got {} targets for {n} training rows",
+                values.len()
+            ));
+        }
+        _ => {
+            return Err(
+                "Give labels for classification or targets for regression, not both".to_string(),
+            );
+        }
+    };
+    let k = input.k.unwrap_or(DEFAULT_K.min(n));
+    if k == 0 || k > n {
+        return Err(format!("k must be between 1 and the {n} training rows"));
+    }
+    let metric = input
+        .metric
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .unwrap_or_else(|| "euclidean".to_string());
+    if !matches!(metric.as_str(), "euclidean" | "manhattan" | "chebyshev") {
+        return Err(format!(
+            "Unknown metric '{metric}', use 'euclidean', 'manhattan' or 'chebyshev'"
+        ));
+    }
+    let weights = input
+        .weights
+        .as_deref()
+        .map(|w| w.trim().to_lowercase())
+        .unwrap_or_else(|| "uniform".to_string());
+    if weights != "uniform" && weights != "distance" {
+        return Err(format!(
+            "Unknown weights '{weights}', use 'uniform' or 'distance'"
+        ));
+    }
+
+    // Per-feature (center, scale); constant features are only centered
+    let scaling: Vec<(f64, f64)> = if input.standardize.unwrap_or(false) {
+        (0..width)
+            .map(|f| {
+                let mean = input.features.iter().map(|r| r[f]).sum::<f64>() / n as f64;
+                let variance = input
+                    .features
+                    .iter()
+                    .map(|r| (r[f] - mean).powi(2))
+                    .sum::<f64>()
+                    / n as f64;
+                let sd = variance.sqrt();
+                (mean, if sd > 0.0 { sd } else { 1.0 })
+            })
+            .collect()
+    } else {
+        vec![(0.0, 1.0); width]
+    };
+    let scale = |row: &Vec<f64>| -> Vec<f64> {
+        row.iter()
+            .zip(&scaling)
+            .map(|(v, (center, spread))| (v - center) / spread)
+            .collect()
+    };
+    let training: Vec<Vec<f64>> = input.features.iter().map(scale).collect();
+
+    let predictions = input
+        .queries
+        .iter()
+        .map(|query| {
+            let query = scale(query);
+            let mut ranked: Vec<Neighbor> = training
+                .iter()
+                .enumerate()
+                .map(|(index, row)| Neighbor {
+                    index,
+                    distance: distance(&metric, &query, row),
+                })
+                .collect();
+            // Stable sort keeps earlier training rows first among equal distances
+            ranked.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+            ranked.truncate(k);
+
+            // Exact matches take all the weight under inverse-distance weighting
+            let exact = ranked.iter().any(|nb| nb.distance == 0.0);
+            let weight = |nb: &Neighbor| match (weights.as_str(), exact) {
+                ("distance", true) => f64::from(u8::from(nb.distance == 0.0)),
+                ("distance", false) => 1.0 / nb.distance,
+                _ => 1.0,
+            };
+            let total: f64 = ranked.iter().map(weight).sum();
+
+            match (&input.labels, &input.targets) {
+                (Some(labels), _) => {
+                    // Votes per label, in order of first appearance among the neighbors
+                    let mut votes: Vec<(&String, f64)> = Vec::new();
+                    for nb in &ranked {
+                        let label = &labels[nb.index];
+                        match votes.iter_mut().find(|(l, _)| *l == label) {
+                            Some((_, w)) => *w += weight(nb),
+                            None => votes.push((label, weight(nb))),
+                        }
+                    }
+                    // Ties go to the label whose first neighbor is nearest
+                    let (label, best) = votes
+                        .iter()
+                        .fold(votes[0], |best, &v| if v.1 > best.1 { v } else { best });
+                    KnnPrediction {
+                        label: Some(label.clone()),
+                        confidence: Some(best / total),
+                        value: None,
+                        neighbors: ranked,
+                    }
+                }
+                (None, Some(targets)) => KnnPrediction {
+                    label: None,
+                    confidence: None,
+                    value: Some(
+                        ranked
+                            .iter()
+                            .map(|nb| weight(nb) * targets[nb.index])
+                            .sum::<f64>()
+                            / total,
+                    ),
+                    neighbors: ranked,
+                },
+                (None, None) => unreachable!("task was checked above"),
+            }
+        })
+        .collect();
+
+    Ok(KnnPredictOutput {
+        predictions,
+        task: task.to_string(),
+        k,
+        metric,
+        weights,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classification(
+        features: Vec<Vec<f64>>,
+        labels: &[&str],
+        queries: Vec<Vec<f64>>,
+    ) -> KnnPredictInput {
+        KnnPredictInput {
+            features,
+            labels: Some(labels.iter().map(|l| l.to_string()).collect()),
+            targets: None,
+            queries,
+            k: Some(3),
+            metric: None,
+            weights: None,
+            standardize: None,
+        }
+    }
+
+    #[test]
+    fn test_majority_vote() {
+        let input = classification(
+            vec![
+                vec![0.0, 0.0],
+                vec![0.0, 1.0],
+                vec![1.0, 0.0],
+                vec![5.0, 5.0],
+                vec![5.0, 6.0],
+            ],
+            &["a", "a", "b", "c", "c"],
+            vec![vec![0.2, 0.2], vec![5.0, 5.4]],
+        );
+        let result = knn_predict_logic(input).unwrap();
+        assert_eq!(result.task, "classification");
+        let first = &result.predictions[0];
+        assert_eq!(first.label.as_deref(), Some("a"));
+        assert!((first.confidence.unwrap() - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(first.neighbors[0].index, 0);
+        assert_eq!(result.predictions[1].label.as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_distance_weighting_and_exact_match() {
+        let mut input = classification(
+            vec![vec![0.0], vec![4.0], vec![5.0]],
+            &["near", "far", "far"],
+            vec![vec![1.0], vec![4.0]],
+        );
+        // Uniform voting lets the two far rows outvote the near one
+        let uniform = knn_predict_logic(input.clone()).unwrap();
+        assert_eq!(uniform.predictions[0].label.as_deref(), Some("far"));
+
+        input.weights = Some("distance".to_string());
+        let weighted = knn_predict_logic(input).unwrap();
+        // Weights 1, 1/3 and 1/4
+        assert_eq!(weighted.predictions[0].label.as_deref(), Some("near"));
+        let confidence = weighted.predictions[0].confidence.unwrap();
+        assert!((confidence - 1.0 / (1.0 + 1.0 / 3.0 + 0.25)).abs() < 1e-12);
+        assert_eq!(weighted.predictions[1].confidence, Some(1.0));
+    }
+
+    #[test]
+    fn test_regression_and_metrics() {
+        let input = KnnPredictInput {
+            features: vec![
+                vec![0.0, 0.0],
+                vec![3.0, 0.0],
+                vec![0.0, 2.0],
+                vec![10.0, 10.0],
+            ],
+            labels: None,
+            targets: Some(vec![1.0, 2.0, 3.0, 100.0]),
+            queries: vec![vec![1.0, 1.0]],
+            k: Some(2),
+            metric: Some("manhattan".to_string()),
+            weights: None,
+            standardize: None,
+        };
+        let result = knn_predict_logic(input.clone()).unwrap();
+        // Manhattan distances 2, 3, 2: rows 0 and 2 tie for nearest
+        let prediction = &result.predictions[0];
+        assert_eq!(prediction.value, Some(2.0));
+        assert_eq!(prediction.neighbors[0].index, 0);
+        assert_eq!(prediction.neighbors[1].index, 2);
+
+        let chebyshev = knn_predict_logic(KnnPredictInput {
+            metric: Some("chebyshev".to_string()),
+            ..input
+        })
+        .unwrap();
+        assert_eq!(chebyshev.predictions[0].neighbors[0].distance, 1.0);
+    }
+
+    #[test]
+    fn test_standardize_balances_scales() {
+        // The second feature is in much larger units and would dominate unscaled
+        let mut input = classification(
+            vec![
+                vec![0.0, 1000.0],
+                vec![0.1, 5000.0],
+                vec![1.0, 1100.0],
+                vec![0.9, 4900.0],
+            ],
+            &["x", "x", "y", "y"],
+            vec![vec![0.05, 1400.0]],
+        );
+        input.k = Some(1);
+        let unscaled = knn_predict_logic(input.clone()).unwrap();
+        assert_eq!(unscaled.predictions[0].label.as_deref(), Some("y"));
+        input.standardize = Some(true);
+        let scaled = knn_predict_logic(input).unwrap();
+        assert_eq!(scaled.predictions[0].label.as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let base = classification(vec![vec![0.0], vec![1.0]], &["a", "b"], vec![vec![0.5]]);
+        assert!(
+            knn_predict_logic(base.clone())
+                .unwrap_err()
+                .contains("k must be")
+        );
+        let both = KnnPredictInput {
+            targets: Some(vec![1.0, 2.0]),
+            k: Some(1),
+            ..base.clone()
+        };
+        assert!(knn_predict_logic(both).unwrap_err().contains("not both"));
+        let ragged = KnnPredictInput {
+            queries: vec![vec![0.5, 1.0]],
+            k: Some(1),
+            ..base.clone()
+        };
+        assert_eq!(
+            knn_predict_logic(ragged).unwrap_err(),
+            "queries row 0 has 2 values, expected 1"
+        );
+        let metric = KnnPredictInput {
+            metric: Some("cosine".to_string()),
+            k: Some(1),
+            ..base
+        };
+        assert!(knn_predict_logic(metric).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+//! How long a large regression holds the executor between yields.
+//!
+//! Polls `calculate_linear_regression` by hand and times every poll. Without
+//! cooperative yielding the whole computation would be one poll, so the
+//! longest poll is the worst delay any other future on the executor sees.
+//! Every figure is the median of several runs.
+//!
+//! ```text
+//! cargo run --release --example yield_latency
+//! ```
+
+use ftl_yield::Yielder;
+use linear_regression::{LogicInput, calculate_linear_regression};
+use std::hint::black_box;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 7;
+
+/// Poll timings of one run
+struct Timings {
+    total: Duration,
+    polls: usize,
+    median_poll: Duration,
+    longest_poll: Duration,
+}
+
+fn poll_timings<F: Future>(future: F) -> Timings {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    let started = Instant::now();
+    let mut polls = Vec::new();
+    loop {
+        let poll_started = Instant::now();
+        let poll = future.as_mut().poll(&mut cx);
+        polls.push(poll_started.elapsed());
+        if let Poll::Ready(output) = poll {
+            black_box(output);
+            let total = started.elapsed();
+            polls.sort();
+            return Timings {
+                total,
+                polls: polls.len(),
+                median_poll: polls[polls.len() / 2],
+                longest_poll: polls[polls.len() - 1],
+            };
+        }
+    }
+}
+
+fn median(mut values: Vec<Duration>) -> Duration {
+    values.sort();
+    values[values.len() / 2]
+}
+
+/// Median of each timing over `RUNS` runs of the future built by `run`, after
+/// one warm-up run
+fn measure<F: Future>(run: impl Fn() -> F) -> Timings {
+    poll_timings(run());
+    let runs: Vec<Timings> = (0..RUNS).map(|_| poll_timings(run())).collect();
+    Timings {
+        total: median(runs.iter().map(|t| t.total).collect()),
+        polls: runs[0].polls,
+        median_poll: median(runs.iter().map(|t| t.median_poll).collect()),
+        longest_poll: median(runs.iter().map(|t| t.longest_poll).collect()),
+    }
+}
+
+/// Sum of squares over `n` values, ticking `yielder` once per value
+async fn sum_of_squares(n: usize, mut yielder: Yielder) -> f64 {
+    let mut total = 0.0;
+    for i in 0..n {
+        yielder.tick().await;
+        total += black_box(i as f64).powi(2);
+    }
+    total
+}
+
+fn main() {
+    println!("linear_regression, executor hold time per poll");
+    for n in [100_000, 1_000_000] {
+        let x: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        let y: Vec<f64> = x.iter().map(|v| 2.0 * v + 1.0 + (v * 0.7).sin()).collect();
+        let t = measure(|| {
+            calculate_linear_regression(LogicInput {
+                x: x.clone(),
+                y: y.clone(),
+            })
+        });
+        println!(
+            "  {n:>9} points: total {:.2?}, {} polls, median poll {:.2?}, longest poll {:.2?}",
+            t.total, t.polls, t.median_poll, t.longest_poll
+        );
+    }
+
+    println!("Yielder overhead on a 10M-iteration loop");
+    let n = 10_000_000;
+    let never = measure(|| sum_of_squares(n, Yielder::new(usize::MAX)));
+    let every = measure(|| sum_of_squares(n, Yielder::default()));
+    println!(
+        "  never yielding: total {:.2?}; every 4096 iterations: total {:.2?}, {} polls, longest poll {:.2?}",
+        never.total, every.total, every.polls, every.longest_poll
+    );
+}
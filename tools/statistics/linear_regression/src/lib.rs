@@ -1,5 +1,14 @@
+use ftl_encoding::ResponseEncoding;
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -7,7 +16,10 @@ use serde::{Deserialize, Serialize};
 mod logic;
 
 // Re-export types from logic module
-pub use logic::{LinearRegressionOutput as LogicOutput, RegressionInput as LogicInput};
+pub use logic::{
+    LinearRegressionOutput as LogicOutput, RegressionInput as LogicInput,
+    calculate_linear_regression,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -16,6 +28,16 @@ pub struct RegressionInput {
     pub x: Vec<f64>,
     /// Y values (dependent variable)
     pub y: Vec<f64>,
+    /// Only check the input and return a validation report, without fitting
+    pub validate_only: Option<bool>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Response encoding: "json" (default) or "msgpack" (base64-encoded MessagePack, smaller for large arrays)
+    pub response_encoding: Option<ResponseEncoding>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -52,16 +74,60 @@ pub struct LinearRegressionOutput {
     pub sample_size: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn linear_regression(input: RegressionInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn linear_regression(request: Request<RegressionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "linear_regression",
+                RegressionInput,
+                LinearRegressionOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("linear_regression", &input);
+    log.finish(run_linear_regression(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_linear_regression(input: RegressionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let encoding = input.response_encoding.unwrap_or_default();
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("x", &input.x)
+        .array("y", &input.y)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         x: input.x,
         y: input.y,
     };
 
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_regression_input(&logic_input);
+        return ToolResponse::text(ftl_json::to_string(&report).unwrap());
+    }
+
     // Call logic implementation
-    match logic::calculate_linear_regression(logic_input) {
+    match ftl_result_cache::cached_async("linear_regression", &logic_input, || {
+        logic::calculate_linear_regression(logic_input.clone())
+    })
+    .await
+    {
         Ok(result) => {
             // Convert back to wrapper types
             let response = LinearRegressionOutput {
@@ -81,7 +147,10 @@ pub fn linear_regression(input: RegressionInput) -> ToolResponse {
                 predicted_values: result.predicted_values,
                 sample_size: result.sample_size,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_encoding::encode(&response, encoding, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -1,3 +1,5 @@
+use ftl_preflight::ValidationReport;
+use ftl_yield::Yielder;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,24 +27,43 @@ pub struct LinearRegressionOutput {
     pub sample_size: usize,
 }
 
-pub fn calculate_linear_regression(
-    input: RegressionInput,
-) -> Result<LinearRegressionOutput, String> {
+/// Check the preconditions of a regression without computing it
+pub fn validate_regression_input(input: &RegressionInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
     if input.x.len() != input.y.len() {
-        return Err("X and Y series must have the same length".to_string());
+        report.error("y", "X and Y series must have the same length");
     }
 
     if input.x.len() < 2 {
-        return Err("Need at least 2 data points for regression".to_string());
+        report.error("x", "Need at least 2 data points for regression");
     }
 
     // Check for invalid values
-    if input.x.iter().any(|&x| x.is_nan() || x.is_infinite())
-        || input.y.iter().any(|&y| y.is_nan() || y.is_infinite())
-    {
-        return Err("Input data contains invalid values (NaN or Infinite)".to_string());
+    for (field, values) in [("x", &input.x), ("y", &input.y)] {
+        if values.iter().any(|v| !v.is_finite()) {
+            report.error(
+                field,
+                "Input data contains invalid values (NaN or Infinite)",
+            );
+        }
+    }
+
+    if report.valid && input.x.iter().all(|&x| x == input.x[0]) {
+        report.error(
+            "x",
+            "X values have zero variance - cannot perform regression",
+        );
     }
 
+    report
+}
+
+pub async fn calculate_linear_regression(
+    input: RegressionInput,
+) -> Result<LinearRegressionOutput, String> {
+    validate_regression_input(&input).into_result()?;
+
     let n = input.x.len() as f64;
     let x_mean = input.x.iter().sum::<f64>() / n;
     let y_mean = input.y.iter().sum::<f64>() / n;
@@ -52,7 +73,9 @@ pub fn calculate_linear_regression(
     let mut sum_x_squared = 0.0;
     let mut sum_y_squared = 0.0;
 
+    let mut yielder = Yielder::default();
     for i in 0..input.x.len() {
+        yielder.tick().await;
         let x_dev = input.x[i] - x_mean;
         let y_dev = input.y[i] - y_mean;
 
@@ -76,6 +99,7 @@ pub fn calculate_linear_regression(
     let mut residual_sum_squares = 0.0;
 
     for i in 0..input.x.len() {
+        yielder.tick().await;
         let predicted = slope * input.x[i] + intercept;
         let residual = input.y[i] - predicted;
 
@@ -212,6 +236,13 @@ fn standard_normal_cdf(x: f64) -> f64 {
 mod tests {
     use super::*;
 
+    // Drive the async logic to completion from synchronous tests
+    fn calculate_linear_regression(
+        input: RegressionInput,
+    ) -> Result<LinearRegressionOutput, String> {
+        ftl_yield::block_on(super::calculate_linear_regression(input))
+    }
+
     #[test]
     fn test_perfect_linear_relationship() {
         let input = RegressionInput {
@@ -367,4 +398,22 @@ mod tests {
             assert!((result.predicted_values[i] - expected).abs() < 0.0001);
         }
     }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let input = RegressionInput {
+            x: vec![f64::NAN],
+            y: vec![1.0, 2.0],
+        };
+        let report = validate_regression_input(&input);
+        assert!(!report.valid);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["y", "x", "x"]);
+
+        let valid = RegressionInput {
+            x: vec![1.0, 2.0, 3.0],
+            y: vec![2.0, 4.0, 6.0],
+        };
+        assert!(validate_regression_input(&valid).valid);
+    }
 }
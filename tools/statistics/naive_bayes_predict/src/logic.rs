@@ -0,0 +1,176 @@
+use ftl_tool_types_derive::ToolTypes;
+use naive_bayes_train_tool::{NaiveBayesModel, check_features};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct NaiveBayesPredictInput {
+    /// Base64 model returned by naive_bayes_train
+    pub model: String,
+    /// Rows to classify, with the same features, in the same order, as the training rows
+    pub features: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct NaiveBayesPrediction {
+    /// Most probable class
+    pub label: String,
+    /// Posterior probability of that class
+    pub probability: f64,
+    /// Posterior probability of every class, in the order of classes
+    pub probabilities: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct NaiveBayesPredictOutput {
+    /// One prediction per row, in input order
+    #[tool_types(nested)]
+    pub predictions: Vec<NaiveBayesPrediction>,
+    /// Class labels known to the model, in sorted order
+    pub classes: Vec<String>,
+    pub variant: String,
+}
+
+pub fn naive_bayes_predict_logic(
+    input: NaiveBayesPredictInput,
+) -> Result<NaiveBayesPredictOutput, String> {
+    let model = NaiveBayesModel::decode(&input.model).map_err(|e| format!("model: {e}"))?;
+    let width = check_features(&input.features, "features")?;
+    if width != model.feature_count {
+        return Err(format!(
+            "The model was trained on {} features but rows have {width}",
+            model.feature_count
+        ));
+    }
+    if model.variant() == "multinomial" && input.features.iter().flatten().any(|v| *v < 0.0) {
+        return Err("multinomial features must be non-negative counts".to_string());
+    }
+
+    let predictions = input
+        .features
+        .iter()
+        .map(|row| {
+            let probabilities = model.predict_probabilities(row);
+            let best = model.predict_index(row);
+            NaiveBayesPrediction {
+                label: model.classes[best].label.clone(),
+                probability: probabilities[best],
+                probabilities,
+            }
+        })
+        .collect();
+
+    Ok(NaiveBayesPredictOutput {
+        predictions,
+        classes: model.classes.iter().map(|c| c.label.clone()).collect(),
+        variant: model.variant().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use naive_bayes_train_tool::{LogicInput as TrainInput, naive_bayes_train_logic};
+
+    fn train(features: Vec<Vec<f64>>, labels: &[&str], variant: &str) -> String {
+        naive_bayes_train_logic(TrainInput {
+            features,
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            variant: Some(variant.to_string()),
+            var_smoothing: None,
+            alpha: None,
+        })
+        .unwrap()
+        .model
+    }
+
+    fn predict(model: &str, features: Vec<Vec<f64>>) -> Result<NaiveBayesPredictOutput, String> {
+        naive_bayes_predict_logic(NaiveBayesPredictInput {
+            model: model.to_string(),
+            features,
+        })
+    }
+
+    #[test]
+    fn test_gaussian_prediction() {
+        let model = train(
+            vec![
+                vec![1.0],
+                vec![2.0],
+                vec![3.0],
+                vec![7.0],
+                vec![8.0],
+                vec![9.0],
+            ],
+            &["small", "small", "small", "large", "large", "large"],
+            "gaussian",
+        );
+        let result = predict(&model, vec![vec![1.5], vec![8.5], vec![5.0]]).unwrap();
+        assert_eq!(result.classes, vec!["large", "small"]);
+        assert_eq!(result.predictions[0].label, "small");
+        assert_eq!(result.predictions[1].label, "large");
+        // Equal spreads and priors leave the midpoint undecided
+        assert!((result.predictions[2].probability - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_gaussian_posterior_matches_hand_calculation() {
+        // Class means 0 and 2 with unit variance; at x = 1.5 the odds are e^(1.5 * 2 - 2)
+        let model = train(
+            vec![vec![-1.0], vec![1.0], vec![1.0], vec![3.0]],
+            &["a", "a", "b", "b"],
+            "gaussian",
+        );
+        let result = predict(&model, vec![vec![1.5]]).unwrap();
+        let odds = 1f64.exp();
+        let expected = odds / (1.0 + odds);
+        assert!((result.predictions[0].probabilities[1] - expected).abs() < 1e-6);
+        assert_eq!(result.predictions[0].label, "b");
+    }
+
+    #[test]
+    fn test_multinomial_prediction() {
+        let model = train(
+            vec![
+                vec![3.0, 0.0, 1.0],
+                vec![2.0, 1.0, 0.0],
+                vec![0.0, 2.0, 3.0],
+                vec![0.0, 3.0, 2.0],
+            ],
+            &["sports", "sports", "politics", "politics"],
+            "multinomial",
+        );
+        let result = predict(&model, vec![vec![4.0, 0.0, 0.0], vec![0.0, 1.0, 1.0]]).unwrap();
+        assert_eq!(result.variant, "multinomial");
+        assert_eq!(result.predictions[0].label, "sports");
+        assert_eq!(result.predictions[1].label, "politics");
+        let total: f64 = result.predictions[1].probabilities.iter().sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_feature_count_must_match() {
+        let model = train(
+            vec![vec![1.0, 2.0], vec![3.0, 4.0]],
+            &["a", "b"],
+            "gaussian",
+        );
+        assert_eq!(
+            predict(&model, vec![vec![1.0]]).unwrap_err(),
+            "The model was trained on 2 features but rows have 1"
+        );
+        let counts = train(vec![vec![1.0], vec![2.0]], &["a", "b"], "multinomial");
+        assert!(predict(&counts, vec![vec![-1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_model() {
+        assert!(
+            predict("not base64!", vec![vec![1.0]])
+                .unwrap_err()
+                .starts_with("model: invalid base64")
+        );
+        let model = train(vec![vec![1.0], vec![2.0]], &["a", "b"], "gaussian");
+        let truncated = &model[..model.len() - 8];
+        assert!(predict(truncated, vec![vec![1.0]]).is_err());
+    }
+}
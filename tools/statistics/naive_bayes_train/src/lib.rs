@@ -0,0 +1,87 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    ClassModel, DEFAULT_ALPHA, DEFAULT_VAR_SMOOTHING, Likelihood, MAX_CLASSES, MAX_VALUES,
+    NaiveBayesModel, NaiveBayesTrainInput as LogicInput, NaiveBayesTrainOutput as LogicOutput,
+    check_features, naive_bayes_train_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    ClassSummarySchema as ClassSummary, NaiveBayesTrainOutputSchema as NaiveBayesTrainOutput,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NaiveBayesTrainInput {
+    #[serde(flatten)]
+    pub params: logic::NaiveBayesTrainInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Train a Gaussian or multinomial naive Bayes classifier on labeled feature rows,
+/// returning per-class parameters and a base64 model for naive_bayes_predict
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn naive_bayes_train(request: Request<NaiveBayesTrainInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "naive_bayes_train",
+                NaiveBayesTrainInput,
+                NaiveBayesTrainOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("naive_bayes_train", &input);
+    log.finish(run_naive_bayes_train(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_naive_bayes_train(input: NaiveBayesTrainInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("features", &input.params.features)
+        .array_len("features", input.params.features.iter().map(Vec::len).sum())
+        .array("labels", &input.params.labels)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::naive_bayes_train_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &NaiveBayesTrainOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
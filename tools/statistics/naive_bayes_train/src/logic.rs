@@ -0,0 +1,537 @@
+use base64::{Engine as _, engine::general_purpose};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most feature values, rows times columns, accepted in one call
+pub const MAX_VALUES: usize = 1_000_000;
+/// Most distinct classes in one model
+pub const MAX_CLASSES: usize = 1_000;
+/// Default var_smoothing for Gaussian models, as a fraction of the largest feature variance
+pub const DEFAULT_VAR_SMOOTHING: f64 = 1e-9;
+/// Default additive (Laplace) smoothing for multinomial models
+pub const DEFAULT_ALPHA: f64 = 1.0;
+
+/// Leading bytes of a serialized model: magic "NB" and the format version
+const HEADER: [u8; 3] = [b'N', b'B', 1];
+const GAUSSIAN: u8 = 0;
+const MULTINOMIAL: u8 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct NaiveBayesTrainInput {
+    /// Training rows, one feature vector per observation
+    pub features: Vec<Vec<f64>>,
+    /// Class label of each row
+    pub labels: Vec<String>,
+    /// "gaussian" for continuous features or "multinomial" for non-negative counts
+    /// (default: gaussian)
+    pub variant: Option<String>,
+    /// Gaussian only: added to every variance, as a fraction of the largest feature
+    /// variance, so constant features stay usable (default: 1e-9)
+    pub var_smoothing: Option<f64>,
+    /// Multinomial only: pseudo-count added to every feature count (default: 1)
+    pub alpha: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ClassSummary {
+    pub label: String,
+    /// Training rows with this label
+    pub count: u64,
+    /// Fraction of training rows with this label
+    pub prior: f64,
+    /// Gaussian only: per-feature means
+    pub means: Option<Vec<f64>>,
+    /// Gaussian only: per-feature variances, smoothing included
+    pub variances: Option<Vec<f64>>,
+    /// Multinomial only: smoothed probability of each feature within the class
+    pub feature_probabilities: Option<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct NaiveBayesTrainOutput {
+    /// Base64 model; pass it to naive_bayes_predict
+    pub model: String,
+    pub variant: String,
+    pub feature_count: usize,
+    /// Classes in sorted label order
+    #[tool_types(nested)]
+    pub classes: Vec<ClassSummary>,
+    /// Fraction of training rows the model classifies correctly
+    pub training_accuracy: f64,
+}
+
+/// Per-class likelihood parameters
+#[derive(Debug, Clone, PartialEq)]
+pub enum Likelihood {
+    Gaussian {
+        means: Vec<f64>,
+        variances: Vec<f64>,
+    },
+    Multinomial {
+        log_probabilities: Vec<f64>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassModel {
+    pub label: String,
+    pub count: u64,
+    pub log_prior: f64,
+    pub likelihood: Likelihood,
+}
+
+/// A trained naive Bayes classifier, serialized as base64 between calls
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaiveBayesModel {
+    pub feature_count: usize,
+    pub classes: Vec<ClassModel>,
+}
+
+impl NaiveBayesModel {
+    pub fn variant(&self) -> &'static str {
+        match self.classes.first().map(|c| &c.likelihood) {
+            Some(Likelihood::Multinomial { .. }) => "multinomial",
+            _ => "gaussian",
+        }
+    }
+
+    /// Log prior plus log likelihood of the row for each class
+    pub fn joint_log_likelihood(&self, row: &[f64]) -> Vec<f64> {
+        self.classes
+            .iter()
+            .map(|class| {
+                let log_likelihood: f64 = match &class.likelihood {
+                    Likelihood::Gaussian { means, variances } => row
+                        .iter()
+                        .zip(means.iter().zip(variances))
+                        .map(|(x, (mean, variance))| {
+                            -0.5 * ((std::f64::consts::TAU * variance).ln()
+                                + (x - mean).powi(2) / variance)
+                        })
+                        .sum(),
+                    Likelihood::Multinomial { log_probabilities } => row
+                        .iter()
+                        .zip(log_probabilities)
+                        .map(|(x, lp)| x * lp)
+                        .sum(),
+                };
+                class.log_prior + log_likelihood
+            })
+            .collect()
+    }
+
+    /// Posterior probability of each class, normalized with log-sum-exp
+    pub fn predict_probabilities(&self, row: &[f64]) -> Vec<f64> {
+        let joint = self.joint_log_likelihood(row);
+        let max = joint.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = joint.iter().map(|j| (j - max).exp()).collect();
+        let total: f64 = weights.iter().sum();
+        weights.iter().map(|w| w / total).collect()
+    }
+
+    /// Index of the most probable class, the first in label order on ties
+    pub fn predict_index(&self, row: &[f64]) -> usize {
+        let joint = self.joint_log_likelihood(row);
+        (0..joint.len()).fold(0, |best, i| if joint[i] > joint[best] { i } else { best })
+    }
+
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&HEADER);
+        bytes.push(match self.variant() {
+            "multinomial" => MULTINOMIAL,
+            _ => GAUSSIAN,
+        });
+        bytes.extend_from_slice(&(self.feature_count as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.classes.len() as u32).to_le_bytes());
+        for class in &self.classes {
+            bytes.extend_from_slice(&(class.label.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(class.label.as_bytes());
+            bytes.extend_from_slice(&class.count.to_le_bytes());
+            bytes.extend_from_slice(&class.log_prior.to_le_bytes());
+            let values: Vec<f64> = match &class.likelihood {
+                Likelihood::Gaussian { means, variances } => {
+                    means.iter().chain(variances).copied().collect()
+                }
+                Likelihood::Multinomial { log_probabilities } => log_probabilities.clone(),
+            };
+            for value in values {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        general_purpose::STANDARD.encode(bytes)
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self, String> {
+        let bytes = general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("invalid base64: {e}"))?;
+        if bytes.len() < HEADER.len() + 9 || bytes[..HEADER.len()] != HEADER {
+            return Err("not a serialized naive Bayes model".to_string());
+        }
+        let truncated = || "model data is truncated".to_string();
+        let mut offset = HEADER.len();
+        let mut take = |len: usize| -> Result<&[u8], String> {
+            let end = offset.checked_add(len).ok_or_else(truncated)?;
+            let slice = bytes.get(offset..end).ok_or_else(truncated)?;
+            offset = end;
+            Ok(slice)
+        };
+        let variant = take(1)?[0];
+        let u32_at = |b: &[u8]| u32::from_le_bytes(b.try_into().unwrap()) as usize;
+        let feature_count = u32_at(take(4)?);
+        let class_count = u32_at(take(4)?);
+        if variant > MULTINOMIAL
+            || feature_count == 0
+            || class_count == 0
+            || class_count > MAX_CLASSES
+        {
+            return Err("model header is inconsistent".to_string());
+        }
+        let per_class = if variant == GAUSSIAN {
+            feature_count.checked_mul(2).ok_or_else(truncated)?
+        } else {
+            feature_count
+        };
+
+        let mut classes = Vec::with_capacity(class_count);
+        for _ in 0..class_count {
+            let label_len = u32_at(take(4)?);
+            let label = String::from_utf8(take(label_len)?.to_vec())
+                .map_err(|_| "model label is not valid UTF-8".to_string())?;
+            let count = u64::from_le_bytes(take(8)?.try_into().unwrap());
+            let log_prior = f64::from_le_bytes(take(8)?.try_into().unwrap());
+            let values = take(per_class.checked_mul(8).ok_or_else(truncated)?)?
+                .chunks_exact(8)
+                .map(|c| f64::from_le_bytes(c.try_into().unwrap()))
+                .collect::<Vec<f64>>();
+            let likelihood = if variant == GAUSSIAN {
+                let (means, variances) = values.split_at(feature_count);
+                if variances.iter().any(|v| !(v.is_finite() && *v > 0.0)) {
+                    return Err("model variances must be positive".to_string());
+                }
+                Likelihood::Gaussian {
+                    means: means.to_vec(),
+                    variances: variances.to_vec(),
+                }
+            } else {
+                Likelihood::Multinomial {
+                    log_probabilities: values,
+                }
+            };
+            classes.push(ClassModel {
+                label,
+                count,
+                log_prior,
+                likelihood,
+            });
+        }
+        if offset != bytes.len() {
+            return Err("model data has trailing bytes".to_string());
+        }
+        let finite = classes.iter().all(|c| {
+            c.log_prior.is_finite()
+                && match &c.likelihood {
+                    Likelihood::Gaussian { means, .. } => means.iter().all(|v| v.is_finite()),
+                    Likelihood::Multinomial { log_probabilities } => {
+                        log_probabilities.iter().all(|v| v.is_finite())
+                    }
+                }
+        });
+        if !finite {
+            return Err("model contents are inconsistent".to_string());
+        }
+        Ok(NaiveBayesModel {
+            feature_count,
+            classes,
+        })
+    }
+}
+
+/// Validates a feature matrix, returning its column count
+pub fn check_features(features: &[Vec<f64>], name: &str) -> Result<usize, String> {
+    let width = features.first().map_or(0, Vec::len);
+    if features.is_empty() || width == 0 {
+        return Err(format!("{name} must contain at least one non-empty row"));
+    }
+    if features.len().saturating_mul(width) > MAX_VALUES {
+        return Err(format!("At most {MAX_VALUES} feature values are allowed"));
+    }
+    if let Some(row) = features.iter().position(|r| r.len() != width) {
+        return Err(format!(
+            "{name} row {row} has {} values, expected {width}",
+            features[row].len()
+        ));
+    }
+    if features.iter().flatten().any(|v| !v.is_finite()) {
+        return Err(format!("{name} must contain finite numbers"));
+    }
+    Ok(width)
+}
+
+pub fn naive_bayes_train_logic(
+    input: NaiveBayesTrainInput,
+) -> Result<NaiveBayesTrainOutput, String> {
+    let width = check_features(&input.features, "features")?;
+    if input.labels.len() != input.features.len() {
+        return Err(format!(
+            "Got {} labels for {} rows",
+            input.labels.len(),
+            input.features.len()
+        ));
+    }
+    let variant = input
+        .variant
+        .as_deref()
+        .map(|v| v.trim().to_lowercase())
+        .unwrap_or_else(|| "gaussian".to_string());
+    match variant.as_str() {
+        "gaussian" if input.alpha.is_some() => {
+            return Err("alpha only applies to multinomial".to_string());
+        }
+        "multinomial" if input.var_smoothing.is_some() => {
+            return Err("var_smoothing only applies to gaussian".to_string());
+        }
+        "gaussian" | "multinomial" => {}
+        other => {
+            return Err(format!(
+                "Unknown variant '{other}', use 'gaussian' or 'multinomial'"
+            ));
+        }
+    }
+    if variant == "multinomial" && input.features.iter().flatten().any(|v| *v < 0.0) {
+        return Err("multinomial features must be non-negative counts".to_string());
+    }
+
+    let mut labels: Vec<&String> = input.labels.iter().collect();
+    labels.sort();
+    labels.dedup();
+    if labels.len() > MAX_CLASSES {
+        return Err(format!(
+            "At most {MAX_CLASSES} distinct classes are allowed"
+        ));
+    }
+    let n = input.features.len() as f64;
+    let rows_of = |label: &String| -> Vec<&Vec<f64>> {
+        input
+            .features
+            .iter()
+            .zip(&input.labels)
+            .filter(|(_, l)| *l == label)
+            .map(|(row, _)| row)
+            .collect()
+    };
+
+    let classes: Vec<ClassModel> = if variant == "gaussian" {
+        let smoothing = input.var_smoothing.unwrap_or(DEFAULT_VAR_SMOOTHING);
+        if !(smoothing.is_finite() && smoothing >= 0.0) {
+            return Err("var_smoothing must be a non-negative number".to_string());
+        }
+        // Scale the smoothing by the spread of the whole training set
+        let largest_variance = (0..width)
+            .map(|f| {
+                let mean = input.features.iter().map(|r| r[f]).sum::<f64>() / n;
+                input
+                    .features
+                    .iter()
+                    .map(|r| (r[f] - mean).powi(2))
+                    .sum::<f64>()
+                    / n
+            })
+            .fold(0.0, f64::max);
+        let epsilon = (smoothing * largest_variance).max(f64::MIN_POSITIVE.sqrt());
+        labels
+            .iter()
+            .map(|label| {
+                let rows = rows_of(label);
+                let count = rows.len() as f64;
+                let means: Vec<f64> = (0..width)
+                    .map(|f| rows.iter().map(|r| r[f]).sum::<f64>() / count)
+                    .collect();
+                let variances = (0..width)
+                    .map(|f| {
+                        rows.iter().map(|r| (r[f] - means[f]).powi(2)).sum::<f64>() / count
+                            + epsilon
+                    })
+                    .collect();
+                ClassModel {
+                    label: label.to_string(),
+                    count: rows.len() as u64,
+                    log_prior: (count / n).ln(),
+                    likelihood: Likelihood::Gaussian { means, variances },
+                }
+            })
+            .collect()
+    } else {
+        let alpha = input.alpha.unwrap_or(DEFAULT_ALPHA);
+        if !(alpha.is_finite() && alpha > 0.0) {
+            return Err("alpha must be a positive number".to_string());
+        }
+        labels
+            .iter()
+            .map(|label| {
+                let rows = rows_of(label);
+                let totals: Vec<f64> = (0..width)
+                    .map(|f| rows.iter().map(|r| r[f]).sum::<f64>() + alpha)
+                    .collect();
+                let grand_total: f64 = totals.iter().sum();
+                ClassModel {
+                    label: label.to_string(),
+                    count: rows.len() as u64,
+                    log_prior: (rows.len() as f64 / n).ln(),
+                    likelihood: Likelihood::Multinomial {
+                        log_probabilities: totals.iter().map(|t| (t / grand_total).ln()).collect(),
+                    },
+                }
+            })
+            .collect()
+    };
+
+    let model = NaiveBayesModel {
+        feature_count: width,
+        classes,
+    };
+    let correct = input
+        .features
+        .iter()
+        .zip(&input.labels)
+        .filter(|(row, label)| model.classes[model.predict_index(row)].label == **label)
+        .count();
+
+    Ok(NaiveBayesTrainOutput {
+        model: model.encode(),
+        variant,
+        feature_count: width,
+        classes: model
+            .classes
+            .iter()
+            .map(|class| {
+                let (means, variances, feature_probabilities) = match &class.likelihood {
+                    Likelihood::Gaussian { means, variances } => {
+                        (Some(means.clone()), Some(variances.clone()), None)
+                    }
+                    Likelihood::Multinomial { log_probabilities } => (
+                        None,
+                        None,
+                        Some(log_probabilities.iter().map(|lp| lp.exp()).collect()),
+                    ),
+                };
+                ClassSummary {
+                    label: class.label.clone(),
+                    count: class.count,
+                    prior: class.log_prior.exp(),
+                    means,
+                    variances,
+                    feature_probabilities,
+                }
+            })
+            .collect(),
+        training_accuracy: correct as f64 / n,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(features: Vec<Vec<f64>>, labels: &[&str], variant: &str) -> NaiveBayesTrainInput {
+        NaiveBayesTrainInput {
+            features,
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            variant: Some(variant.to_string()),
+            var_smoothing: None,
+            alpha: None,
+        }
+    }
+
+    #[test]
+    fn test_gaussian_parameters() {
+        let features = vec![
+            vec![1.0, 10.0],
+            vec![3.0, 12.0],
+            vec![8.0, 1.0],
+            vec![10.0, 3.0],
+        ];
+        let result =
+            naive_bayes_train_logic(request(features, &["b", "b", "a", "a"], "gaussian")).unwrap();
+        assert_eq!(result.classes[0].label, "a");
+        assert_eq!(result.classes[0].means, Some(vec![9.0, 2.0]));
+        let variances = result.classes[1].variances.clone().unwrap();
+        assert!((variances[0] - 1.0).abs() < 1e-6 && (variances[1] - 1.0).abs() < 1e-6);
+        assert_eq!(result.classes[1].prior, 0.5);
+        assert_eq!(result.training_accuracy, 1.0);
+    }
+
+    #[test]
+    fn test_multinomial_smoothing() {
+        // Word counts for two documents per class over a three-word vocabulary
+        let features = vec![
+            vec![2.0, 1.0, 0.0],
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 3.0],
+            vec![0.0, 0.0, 2.0],
+        ];
+        let result = naive_bayes_train_logic(request(
+            features,
+            &["spam", "spam", "ham", "ham"],
+            "multinomial",
+        ))
+        .unwrap();
+        // spam totals [3, 1, 0] plus alpha 1 over 7
+        let spam = result.classes[1].feature_probabilities.clone().unwrap();
+        for (p, expected) in spam.iter().zip([4.0 / 7.0, 2.0 / 7.0, 1.0 / 7.0]) {
+            assert!((p - expected).abs() < 1e-12);
+        }
+        assert!(result.classes[1].means.is_none());
+    }
+
+    #[test]
+    fn test_model_round_trip() {
+        let features = vec![vec![1.0], vec![2.0], vec![5.0], vec![6.0], vec![7.0]];
+        let result = naive_bayes_train_logic(request(
+            features,
+            &["low", "low", "high", "high", "hïgh"],
+            "gaussian",
+        ))
+        .unwrap();
+        let model = NaiveBayesModel::decode(&result.model).unwrap();
+        assert_eq!(model.classes.len(), 3);
+        assert_eq!(model.encode(), result.model);
+        let probabilities = model.predict_probabilities(&[1.5]);
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert_eq!(model.classes[model.predict_index(&[1.5])].label, "low");
+    }
+
+    #[test]
+    fn test_constant_feature_is_smoothed() {
+        let features = vec![
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![1.0, 9.0],
+            vec![1.0, 10.0],
+        ];
+        let result =
+            naive_bayes_train_logic(request(features, &["x", "x", "y", "y"], "gaussian")).unwrap();
+        let variances = result.classes[0].variances.clone().unwrap();
+        assert!(variances[0] > 0.0 && variances[0] < 1e-6);
+        assert_eq!(result.training_accuracy, 1.0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(naive_bayes_train_logic(request(vec![], &[], "gaussian")).is_err());
+        let ragged = request(vec![vec![1.0, 2.0], vec![1.0]], &["a", "b"], "gaussian");
+        assert_eq!(
+            naive_bayes_train_logic(ragged).unwrap_err(),
+            "features row 1 has 1 values, expected 2"
+        );
+        let negative = request(vec![vec![-1.0]], &["a"], "multinomial");
+        assert!(naive_bayes_train_logic(negative).is_err());
+        let mismatched = request(vec![vec![1.0]], &["a", "b"], "gaussian");
+        assert!(naive_bayes_train_logic(mismatched).is_err());
+        let mut wrong_option = request(vec![vec![1.0]], &["a"], "gaussian");
+        wrong_option.alpha = Some(1.0);
+        assert!(naive_bayes_train_logic(wrong_option).is_err());
+        assert!(NaiveBayesModel::decode("SEVMTE8=").is_err());
+    }
+}
@@ -1,5 +1,13 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -7,7 +15,9 @@ use serde::{Deserialize, Serialize};
 mod logic;
 
 // Re-export types from logic module
-pub use logic::{CorrelationOutput as LogicOutput, TwoSeriesInput as LogicInput};
+pub use logic::{
+    CorrelationOutput as LogicOutput, TwoSeriesInput as LogicInput, calculate_correlation,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -16,6 +26,12 @@ pub struct TwoSeriesInput {
     pub x: Vec<f64>,
     /// Y values (second data series)
     pub y: Vec<f64>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -30,8 +46,42 @@ pub struct CorrelationOutput {
     pub interpretation: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn pearson_correlation(input: TwoSeriesInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn pearson_correlation(request: Request<TwoSeriesInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "pearson_correlation",
+                TwoSeriesInput,
+                CorrelationOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("pearson_correlation", &input);
+    log.finish(run_pearson_correlation(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_pearson_correlation(input: TwoSeriesInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("x", &input.x)
+        .array("y", &input.y)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         x: input.x,
@@ -39,7 +89,9 @@ pub fn pearson_correlation(input: TwoSeriesInput) -> ToolResponse {
     };
 
     // Call logic implementation
-    match logic::calculate_correlation(logic_input) {
+    match ftl_result_cache::cached("pearson_correlation", &logic_input, || {
+        logic::calculate_correlation(logic_input.clone())
+    }) {
         Ok(result) => {
             // Convert back to wrapper types
             let response = CorrelationOutput {
@@ -48,7 +100,10 @@ pub fn pearson_correlation(input: TwoSeriesInput) -> ToolResponse {
                 sample_size: result.sample_size,
                 interpretation: result.interpretation,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
@@ -3,13 +3,23 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+use ftl_encoding::ResponseEncoding;
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
     PolynomialRegressionInput as LogicInput, PolynomialRegressionOutput as LogicOutput,
+    calculate_polynomial_regression,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -21,6 +31,16 @@ pub struct PolynomialRegressionInput {
     pub y: Vec<f64>,
     /// Degree of the polynomial (1-10)
     pub degree: usize,
+    /// Only check the input and return a validation report, without fitting
+    pub validate_only: Option<bool>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Response encoding: "json" (default) or "msgpack" (base64-encoded MessagePack, smaller for large arrays)
+    pub response_encoding: Option<ResponseEncoding>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -39,8 +59,43 @@ pub struct PolynomialRegressionOutput {
     pub degree: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn polynomial_regression(input: PolynomialRegressionInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub async fn polynomial_regression(request: Request<PolynomialRegressionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "polynomial_regression",
+                PolynomialRegressionInput,
+                PolynomialRegressionOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("polynomial_regression", &input);
+    log.finish(run_polynomial_regression(input).await)
+}
+
+#[cfg(feature = "individual")]
+async fn run_polynomial_regression(input: PolynomialRegressionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+    let encoding = input.response_encoding.unwrap_or_default();
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("x", &input.x)
+        .array("y", &input.y)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         x: input.x,
@@ -48,8 +103,17 @@ pub fn polynomial_regression(input: PolynomialRegressionInput) -> ToolResponse {
         degree: input.degree,
     };
 
+    if input.validate_only.unwrap_or(false) {
+        let report = logic::validate_polynomial_input(&logic_input);
+        return ToolResponse::text(ftl_json::to_string(&report).unwrap());
+    }
+
     // Call logic implementation
-    match logic::calculate_polynomial_regression(logic_input) {
+    match ftl_result_cache::cached_async("polynomial_regression", &logic_input, || {
+        logic::calculate_polynomial_regression(logic_input.clone())
+    })
+    .await
+    {
         Ok(result) => {
             // Convert back to wrapper types
             let response = PolynomialRegressionOutput {
@@ -60,7 +124,10 @@ pub fn polynomial_regression(input: PolynomialRegressionInput) -> ToolResponse {
                 residuals: result.residuals,
                 degree: result.degree,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_encoding::encode(&response, encoding, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
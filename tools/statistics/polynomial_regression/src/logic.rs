@@ -1,13 +1,15 @@
+use ftl_preflight::ValidationReport;
+use ftl_yield::Yielder;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolynomialRegressionInput {
     pub x: Vec<f64>,
     pub y: Vec<f64>,
     pub degree: usize,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PolynomialRegressionOutput {
     pub coefficients: Vec<f64>,
     pub r_squared: f64,
@@ -17,42 +19,80 @@ pub struct PolynomialRegressionOutput {
     pub degree: usize,
 }
 
-pub fn calculate_polynomial_regression(
-    input: PolynomialRegressionInput,
-) -> Result<PolynomialRegressionOutput, String> {
+/// Check the preconditions of a polynomial fit without computing it
+pub fn validate_polynomial_input(input: &PolynomialRegressionInput) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
     if input.x.len() != input.y.len() {
-        return Err("X and Y series must have the same length".to_string());
+        report.error("y", "X and Y series must have the same length");
     }
 
     if input.x.len() < input.degree + 1 {
-        return Err(format!(
-            "Need at least {} data points for degree {} polynomial",
-            input.degree + 1,
-            input.degree
-        ));
+        report.error(
+            "x",
+            format!(
+                "Need at least {} data points for degree {} polynomial",
+                input.degree + 1,
+                input.degree
+            ),
+        );
     }
 
     if input.degree == 0 {
-        return Err("Polynomial degree must be at least 1".to_string());
+        report.error("degree", "Polynomial degree must be at least 1");
     }
 
     if input.degree > 10 {
-        return Err("Polynomial degree cannot exceed 10 (numerical stability)".to_string());
+        report.error(
+            "degree",
+            "Polynomial degree cannot exceed 10 (numerical stability)",
+        );
     }
 
     // Check for invalid values
-    if input.x.iter().any(|&x| x.is_nan() || x.is_infinite())
-        || input.y.iter().any(|&y| y.is_nan() || y.is_infinite())
-    {
-        return Err("Input data contains invalid values (NaN or Infinite)".to_string());
+    for (field, values) in [("x", &input.x), ("y", &input.y)] {
+        if values.iter().any(|v| !v.is_finite()) {
+            report.error(
+                field,
+                "Input data contains invalid values (NaN or Infinite)",
+            );
+        }
     }
 
+    // The normal equations are singular unless there are enough distinct x values
+    if report.valid {
+        let mut distinct = input.x.clone();
+        distinct.sort_by(f64::total_cmp);
+        distinct.dedup();
+        if distinct.len() < input.degree + 1 {
+            report.warning(
+                "x",
+                format!(
+                    "Only {} distinct x values; a degree {} fit needs {} and will likely fail as singular",
+                    distinct.len(),
+                    input.degree,
+                    input.degree + 1
+                ),
+            );
+        }
+    }
+
+    report
+}
+
+pub async fn calculate_polynomial_regression(
+    input: PolynomialRegressionInput,
+) -> Result<PolynomialRegressionOutput, String> {
+    validate_polynomial_input(&input).into_result()?;
+
     let n = input.x.len();
     let degree = input.degree;
 
     // Create design matrix (Vandermonde matrix)
     let mut design_matrix = vec![vec![0.0; degree + 1]; n];
+    let mut yielder = Yielder::default();
     for (i, row) in design_matrix.iter_mut().enumerate().take(n) {
+        yielder.tick().await;
         for (j, item) in row.iter_mut().enumerate().take(degree + 1) {
             *item = input.x[i].powi(j as i32);
         }
@@ -66,6 +106,7 @@ pub fn calculate_polynomial_regression(
     for (i, row) in xtx.iter_mut().enumerate().take(degree + 1) {
         for j in 0..=degree {
             for design_row in design_matrix.iter().take(n) {
+                yielder.tick().await;
                 row[j] += design_row[i] * design_row[j];
             }
         }
@@ -74,6 +115,7 @@ pub fn calculate_polynomial_regression(
     // Calculate X^T y
     for (i, xty_item) in xty.iter_mut().enumerate().take(degree + 1) {
         for (design_row, &y_val) in design_matrix.iter().zip(input.y.iter()).take(n) {
+            yielder.tick().await;
             *xty_item += design_row[i] * y_val;
         }
     }
@@ -87,6 +129,7 @@ pub fn calculate_polynomial_regression(
     let mut residual_sum_squares = 0.0;
 
     for i in 0..n {
+        yielder.tick().await;
         let mut predicted = 0.0;
         for (j, &coeff) in coefficients.iter().enumerate().take(degree + 1) {
             predicted += coeff * input.x[i].powi(j as i32);
@@ -189,6 +232,13 @@ fn solve_linear_system(
 mod tests {
     use super::*;
 
+    // Drive the async logic to completion from synchronous tests
+    fn calculate_polynomial_regression(
+        input: PolynomialRegressionInput,
+    ) -> Result<PolynomialRegressionOutput, String> {
+        ftl_yield::block_on(super::calculate_polynomial_regression(input))
+    }
+
     #[test]
     fn test_linear_polynomial() {
         // Test degree 1 (should match linear regression)
@@ -377,4 +427,25 @@ mod tests {
         let result = calculate_polynomial_regression(input).unwrap();
         assert!((result.r_squared - 1.0).abs() < 1e-10); // Perfect fit
     }
+
+    #[test]
+    fn test_validation_report() {
+        let input = PolynomialRegressionInput {
+            x: vec![1.0, 2.0],
+            y: vec![1.0, 2.0, 3.0],
+            degree: 11,
+        };
+        let report = validate_polynomial_input(&input);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["y", "x", "degree"]);
+
+        let repeated_x = PolynomialRegressionInput {
+            x: vec![1.0, 1.0, 2.0, 2.0],
+            y: vec![1.0, 2.0, 3.0, 4.0],
+            degree: 2,
+        };
+        let report = validate_polynomial_input(&repeated_x);
+        assert!(report.valid);
+        assert_eq!(report.warnings.len(), 1);
+    }
 }
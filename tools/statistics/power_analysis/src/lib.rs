@@ -0,0 +1,72 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_SAMPLE_SIZE, PowerAnalysisInput as LogicInput, PowerAnalysisOutput as LogicOutput,
+    power_analysis_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::PowerAnalysisOutputSchema as PowerAnalysisOutput;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PowerAnalysisInput {
+    #[serde(flatten)]
+    pub params: logic::PowerAnalysisInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Compute achieved power or the required sample size for t-tests, proportion tests,
+/// one-way ANOVA and correlations from an effect size and alpha
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn power_analysis(request: Request<PowerAnalysisInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "power_analysis",
+                PowerAnalysisInput,
+                PowerAnalysisOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("power_analysis", &input);
+    log.finish(run_power_analysis(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_power_analysis(input: PowerAnalysisInput) -> ToolResponse {
+    let result = match logic::power_analysis_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &PowerAnalysisOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,465 @@
+use ftl_distributions::{
+    f_quantile, noncentral_f_cdf, noncentral_t_cdf, normal_cdf, normal_quantile, t_quantile,
+};
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Largest sample size searched when solving for n
+pub const MAX_SAMPLE_SIZE: usize = 10_000_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PowerAnalysisInput {
+    /// Test family: "t_test", "proportion", "anova" or "correlation"
+    pub test: String,
+    /// For t_test: "one_sample", "paired" or "two_sample"; for proportion: "one_sample"
+    /// or "two_sample" (default: two_sample)
+    pub design: Option<String>,
+    /// Standardized effect: Cohen's d (t_test), Cohen's h (proportion), Cohen's f
+    /// (anova) or Pearson r (correlation)
+    pub effect_size: Option<f64>,
+    /// Proportion in the (first) group; with p2 this sets Cohen's h for proportion tests
+    pub p1: Option<f64>,
+    /// Proportion in the second group, or the null proportion for one_sample
+    pub p2: Option<f64>,
+    /// Significance level (default: 0.05)
+    pub alpha: Option<f64>,
+    /// Target power when solving for sample size (default: 0.8)
+    pub power: Option<f64>,
+    /// Sample size per group; when given, the achieved power is computed instead of the
+    /// required sample size
+    pub sample_size: Option<usize>,
+    /// Number of groups for anova (default: 2)
+    pub groups: Option<usize>,
+    /// "two_sided", "greater" or "less"; anova is always an upper-tail F test
+    /// (default: two_sided)
+    pub alternative: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PowerAnalysisOutput {
+    pub test: String,
+    pub design: String,
+    pub alternative: String,
+    /// What was solved for: "power" or "sample_size"
+    pub solved_for: String,
+    /// Effect size used
+    pub effect_size: f64,
+    /// Cohen's label for the effect: "negligible", "small", "medium" or "large"
+    pub effect_magnitude: String,
+    pub alpha: f64,
+    /// Power at the reported sample size
+    pub power: f64,
+    /// Sample size per group
+    pub sample_size: usize,
+    /// Sample size over all groups
+    pub total_sample_size: usize,
+    /// Critical value of the test statistic (t, F or z)
+    pub critical_value: f64,
+    /// Noncentrality: delta for t, lambda for F, or the expected z statistic
+    pub noncentrality: f64,
+    /// Degrees of freedom (numerator for F)
+    pub df1: Option<f64>,
+    /// Denominator degrees of freedom for F
+    pub df2: Option<f64>,
+    /// Distribution used for the power calculation
+    pub method: String,
+}
+
+// Power calculations
+
+#[derive(Clone, Copy, PartialEq)]
+enum Alternative {
+    TwoSided,
+    Greater,
+    Less,
+}
+
+/// Power at one sample size, with the critical value, noncentrality and degrees of
+/// freedom behind it
+struct PowerPoint {
+    power: f64,
+    critical_value: f64,
+    noncentrality: f64,
+    df1: Option<f64>,
+    df2: Option<f64>,
+}
+
+/// Power of a test whose statistic is normal with mean `shift` under the alternative
+fn normal_power(shift: f64, alpha: f64, alternative: Alternative) -> PowerPoint {
+    let (power, critical_value) = match alternative {
+        Alternative::TwoSided => {
+            let z = normal_quantile(1.0 - alpha / 2.0);
+            (normal_cdf(shift - z) + normal_cdf(-shift - z), z)
+        }
+        Alternative::Greater => {
+            let z = normal_quantile(1.0 - alpha);
+            (normal_cdf(shift - z), z)
+        }
+        Alternative::Less => {
+            let z = normal_quantile(1.0 - alpha);
+            (normal_cdf(-shift - z), -z)
+        }
+    };
+    PowerPoint {
+        power,
+        critical_value,
+        noncentrality: shift,
+        df1: None,
+        df2: None,
+    }
+}
+
+fn t_power(delta: f64, df: f64, alpha: f64, alternative: Alternative) -> PowerPoint {
+    let (power, critical_value) = match alternative {
+        Alternative::TwoSided => {
+            let t = t_quantile(1.0 - alpha / 2.0, df);
+            (
+                1.0 - noncentral_t_cdf(t, df, delta) + noncentral_t_cdf(-t, df, delta),
+                t,
+            )
+        }
+        Alternative::Greater => {
+            let t = t_quantile(1.0 - alpha, df);
+            (1.0 - noncentral_t_cdf(t, df, delta), t)
+        }
+        Alternative::Less => {
+            let t = t_quantile(1.0 - alpha, df);
+            (noncentral_t_cdf(-t, df, delta), -t)
+        }
+    };
+    PowerPoint {
+        power,
+        critical_value,
+        noncentrality: delta,
+        df1: Some(df),
+        df2: None,
+    }
+}
+
+struct Setup {
+    test: &'static str,
+    design: &'static str,
+    effect_size: f64,
+    groups: usize,
+    min_n: usize,
+    method: &'static str,
+}
+
+impl Setup {
+    fn power_at(&self, n: usize, alpha: f64, alternative: Alternative) -> PowerPoint {
+        let (n, d) = (n as f64, self.effect_size);
+        match (self.test, self.design) {
+            ("t_test", "two_sample") => {
+                t_power(d * (n / 2.0).sqrt(), 2.0 * n - 2.0, alpha, alternative)
+            }
+            ("t_test", _) => t_power(d * n.sqrt(), n - 1.0, alpha, alternative),
+            ("proportion", "two_sample") => normal_power(d * (n / 2.0).sqrt(), alpha, alternative),
+            ("proportion", _) => normal_power(d * n.sqrt(), alpha, alternative),
+            ("correlation", _) => normal_power(d.atanh() * (n - 3.0).sqrt(), alpha, alternative),
+            _ => {
+                let k = self.groups as f64;
+                let (df1, df2) = (k - 1.0, k * (n - 1.0));
+                let lambda = d * d * k * n;
+                let critical_value = f_quantile(1.0 - alpha, df1, df2);
+                PowerPoint {
+                    power: 1.0 - noncentral_f_cdf(critical_value, df1, df2, lambda),
+                    critical_value,
+                    noncentrality: lambda,
+                    df1: Some(df1),
+                    df2: Some(df2),
+                }
+            }
+        }
+    }
+
+    /// Cohen's conventional small, medium and large effects
+    fn magnitude(&self) -> &'static str {
+        let (small, medium, large) = match self.test {
+            "anova" => (0.1, 0.25, 0.4),
+            "correlation" => (0.1, 0.3, 0.5),
+            _ => (0.2, 0.5, 0.8),
+        };
+        match self.effect_size.abs() {
+            e if e >= large => "large",
+            e if e >= medium => "medium",
+            e if e >= small => "small",
+            _ => "negligible",
+        }
+    }
+}
+
+fn setup(input: &PowerAnalysisInput) -> Result<Setup, String> {
+    let test = match input.test.trim().to_lowercase().as_str() {
+        "t_test" => "t_test",
+        "proportion" => "proportion",
+        "anova" => "anova",
+        "correlation" => "correlation",
+        other => {
+            return Err(format!(
+                "Unknown test '{other}', use 't_test', 'proportion', 'anova' or 'correlation'"
+            ));
+        }
+    };
+    let design = input.design.as_deref().map(|d| d.trim().to_lowercase());
+    let design = match (test, design.as_deref()) {
+        ("t_test" | "proportion", None | Some("two_sample")) => "two_sample",
+        ("t_test" | "proportion", Some("one_sample")) => "one_sample",
+        ("t_test", Some("paired")) => "paired",
+        ("anova", None | Some("one_way")) => "one_way",
+        ("correlation", None | Some("bivariate")) => "bivariate",
+        (_, other) => {
+            return Err(format!(
+                "Design '{}' is not available for {test}",
+                other.unwrap_or_default()
+            ));
+        }
+    };
+
+    let effect_size = match (test, input.effect_size, input.p1, input.p2) {
+        ("proportion", None, Some(p1), Some(p2)) => {
+            if !(0.0..=1.0).contains(&p1) || !(0.0..=1.0).contains(&p2) {
+                return Err("p1 and p2 must be between 0 and 1".to_string());
+            }
+            // Cohen's h, the difference of arcsine-transformed proportions
+            2.0 * p1.sqrt().asin() - 2.0 * p2.sqrt().asin()
+        }
+        (_, Some(effect), None, None) => effect,
+        (_, Some(_), _, _) => return Err("Give effect_size or p1 and p2, not both".to_string()),
+        ("proportion", None, _, _) => {
+            return Err("proportion tests need effect_size (Cohen's h) or p1 and p2".to_string());
+        }
+        _ => return Err(format!("effect_size is required for {test}")),
+    };
+    if !effect_size.is_finite() || effect_size == 0.0 {
+        return Err("effect_size must be a non-zero finite number".to_string());
+    }
+    match test {
+        "anova" if effect_size < 0.0 => {
+            return Err("Cohen's f for anova must be positive".to_string());
+        }
+        "correlation" if effect_size.abs() >= 1.0 => {
+            return Err("Correlation effect_size must be strictly between -1 and 1".to_string());
+        }
+        _ => {}
+    }
+
+    let groups = input.groups.unwrap_or(2);
+    if test == "anova" && groups < 2 {
+        return Err("anova needs at least 2 groups".to_string());
+    }
+    let (groups, min_n, method) = match (test, design) {
+        ("t_test", "two_sample") => (2, 2, "noncentral t"),
+        ("t_test", _) => (1, 2, "noncentral t"),
+        ("proportion", "two_sample") => (2, 1, "normal approximation (Cohen's h)"),
+        ("proportion", _) => (1, 1, "normal approximation (Cohen's h)"),
+        ("correlation", _) => (1, 4, "normal approximation (Fisher z)"),
+        _ => (groups, 2, "noncentral F"),
+    };
+    Ok(Setup {
+        test,
+        design,
+        effect_size,
+        groups,
+        min_n,
+        method,
+    })
+}
+
+pub fn power_analysis_logic(input: PowerAnalysisInput) -> Result<PowerAnalysisOutput, String> {
+    let setup = setup(&input)?;
+    let alpha = input.alpha.unwrap_or(0.05);
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return Err("alpha must be between 0 and 1".to_string());
+    }
+    let alternative_name = input
+        .alternative
+        .as_deref()
+        .map(|a| a.trim().to_lowercase())
+        .unwrap_or_else(|| "two_sided".to_string());
+    let alternative = match alternative_name.as_str() {
+        "two_sided" => Alternative::TwoSided,
+        "greater" => Alternative::Greater,
+        "less" => Alternative::Less,
+        other => {
+            return Err(format!(
+                "Unknown alternative '{other}', use 'two_sided', 'greater' or 'less'"
+            ));
+        }
+    };
+    if setup.test == "anova" && alternative != Alternative::TwoSided {
+        return Err("anova power uses the upper-tail F test; leave alternative unset".to_string());
+    }
+
+    let (solved_for, n, point) = match input.sample_size {
+        Some(n) => {
+            if n < setup.min_n {
+                return Err(format!(
+                    "sample_size must be at least {} for this design",
+                    setup.min_n
+                ));
+            }
+            ("power", n, setup.power_at(n, alpha, alternative))
+        }
+        None => {
+            let target = input.power.unwrap_or(0.8);
+            if !(target > alpha && target < 1.0) {
+                return Err("power must be between alpha and 1".to_string());
+            }
+            // Power grows with n: double until the target is met, then bisect
+            let reaches = |n: usize| setup.power_at(n, alpha, alternative).power >= target;
+            let mut high = setup.min_n;
+            while !reaches(high) {
+                if high >= MAX_SAMPLE_SIZE {
+                    return Err(format!(
+                        "More than {MAX_SAMPLE_SIZE} per group would be needed for this power"
+                    ));
+                }
+                high = (high * 2).min(MAX_SAMPLE_SIZE);
+            }
+            let mut low = (high / 2).max(setup.min_n);
+            if reaches(low) {
+                high = low;
+            }
+            while high - low > 1 {
+                let mid = (low + high) / 2;
+                if reaches(mid) {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+            (
+                "sample_size",
+                high,
+                setup.power_at(high, alpha, alternative),
+            )
+        }
+    };
+
+    Ok(PowerAnalysisOutput {
+        test: setup.test.to_string(),
+        design: setup.design.to_string(),
+        alternative: if setup.test == "anova" {
+            "greater".to_string()
+        } else {
+            alternative_name
+        },
+        solved_for: solved_for.to_string(),
+        effect_size: setup.effect_size,
+        effect_magnitude: setup.magnitude().to_string(),
+        alpha,
+        power: point.power,
+        sample_size: n,
+        total_sample_size: n * setup.groups,
+        critical_value: point.critical_value,
+        noncentrality: point.noncentrality,
+        df1: point.df1,
+        df2: point.df2,
+        method: setup.method.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(test: &str, effect_size: f64) -> PowerAnalysisInput {
+        PowerAnalysisInput {
+            test: test.to_string(),
+            design: None,
+            effect_size: Some(effect_size),
+            p1: None,
+            p2: None,
+            alpha: None,
+            power: None,
+            sample_size: None,
+            groups: None,
+            alternative: None,
+        }
+    }
+
+    #[test]
+    fn test_t_test_sample_size() {
+        // Standard references: d = 0.5, alpha = 0.05, power 0.8
+        let result = power_analysis_logic(request("t_test", 0.5)).unwrap();
+        assert_eq!(result.solved_for, "sample_size");
+        assert_eq!((result.sample_size, result.total_sample_size), (64, 128));
+        assert!((result.power - 0.8015).abs() < 1e-4, "{}", result.power);
+        assert_eq!(result.effect_magnitude, "medium");
+
+        let mut one_sample = request("t_test", 0.5);
+        one_sample.design = Some("one_sample".to_string());
+        assert_eq!(power_analysis_logic(one_sample).unwrap().sample_size, 34);
+    }
+
+    #[test]
+    fn test_achieved_power_and_direction() {
+        let mut input = request("t_test", 0.5);
+        input.sample_size = Some(64);
+        input.alternative = Some("greater".to_string());
+        let greater = power_analysis_logic(input.clone()).unwrap();
+        assert_eq!(greater.solved_for, "power");
+        assert!((greater.power - 0.8794).abs() < 1e-3, "{}", greater.power);
+        // Testing the wrong direction has almost no power
+        input.alternative = Some("less".to_string());
+        assert!(power_analysis_logic(input).unwrap().power < 0.001);
+    }
+
+    #[test]
+    fn test_anova_correlation_and_proportions() {
+        let mut anova = request("anova", 0.25);
+        anova.groups = Some(3);
+        let result = power_analysis_logic(anova).unwrap();
+        assert_eq!((result.sample_size, result.total_sample_size), (53, 159));
+        assert_eq!((result.df1, result.df2), (Some(2.0), Some(156.0)));
+
+        assert_eq!(
+            power_analysis_logic(request("correlation", 0.3))
+                .unwrap()
+                .sample_size,
+            85
+        );
+
+        let mut proportion = request("proportion", 0.0);
+        proportion.effect_size = None;
+        proportion.p1 = Some(0.65);
+        proportion.p2 = Some(0.45);
+        let result = power_analysis_logic(proportion).unwrap();
+        assert!(
+            (result.effect_size - 0.4049).abs() < 1e-3,
+            "{}",
+            result.effect_size
+        );
+        assert_eq!(result.sample_size, 96);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(
+            power_analysis_logic(request("chi_square", 0.3))
+                .unwrap_err()
+                .contains("Unknown test")
+        );
+        assert!(
+            power_analysis_logic(request("correlation", 1.2))
+                .unwrap_err()
+                .contains("between -1 and 1")
+        );
+        let mut input = request("t_test", 0.5);
+        input.design = Some("one_way".to_string());
+        assert_eq!(
+            power_analysis_logic(input).unwrap_err(),
+            "Design 'one_way' is not available for t_test"
+        );
+        let mut input = request("anova", 0.25);
+        input.alternative = Some("less".to_string());
+        assert!(power_analysis_logic(input).is_err());
+        let mut input = request("t_test", 0.5);
+        input.sample_size = Some(1);
+        assert!(
+            power_analysis_logic(input)
+                .unwrap_err()
+                .contains("at least 2")
+        );
+    }
+}
@@ -1,5 +1,13 @@
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,7 +17,7 @@ mod logic;
 // Re-export types from logic module
 pub use logic::{
     PredictionInput as LogicInput, PredictionOutput as LogicOutput,
-    RegressionPrediction as LogicPrediction,
+    RegressionPrediction as LogicPrediction, predict_values as predict_values_logic,
 };
 
 // Define wrapper types with JsonSchema for FTL-SDK
@@ -21,6 +29,12 @@ pub struct PredictionInput {
     pub intercept: f64,
     /// X values to predict Y values for
     pub x_values: Vec<f64>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -39,8 +53,41 @@ pub struct RegressionPrediction {
     pub confidence_interval: Option<(f64, f64)>,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn predict_values(input: PredictionInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn predict_values(request: Request<PredictionInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "predict_values",
+                PredictionInput,
+                PredictionOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("predict_values", &input);
+    log.finish(run_predict_values(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_predict_values(input: PredictionInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("x_values", &input.x_values)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         slope: input.slope,
@@ -49,7 +96,9 @@ pub fn predict_values(input: PredictionInput) -> ToolResponse {
     };
 
     // Call logic implementation
-    match logic::predict_values(logic_input) {
+    match ftl_result_cache::cached("predict_values", &logic_input, || {
+        logic::predict_values(logic_input.clone())
+    }) {
         Ok(result) => {
             // Convert back to wrapper types
             let response = PredictionOutput {
@@ -63,7 +112,10 @@ pub fn predict_values(input: PredictionInput) -> ToolResponse {
                     })
                     .collect(),
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
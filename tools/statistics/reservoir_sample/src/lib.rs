@@ -0,0 +1,132 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{ReservoirInput as LogicInput, ReservoirOutput as LogicOutput, update_reservoir};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReservoirSampleInput {
+    /// Next chunk of the stream
+    pub values: Vec<f64>,
+    /// Non-negative weight for each value, for weighted sampling: a value's chance of
+    /// being kept is proportional to its weight. Must be given on every call of a
+    /// weighted stream and never for an unweighted one.
+    pub weights: Option<Vec<f64>>,
+    /// Base64 state returned by the previous call; omit for the first chunk
+    pub state: Option<String>,
+    /// Number of values to keep, 1-100000 (default: 1000); fixed once sampling starts
+    pub sample_size: Option<usize>,
+    /// Random seed for a reproducible sample (default: random); only used on the first chunk
+    pub seed: Option<u64>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReservoirSampleOutput {
+    /// Base64 state to pass with the next chunk
+    pub state: String,
+    /// Uniform (or weighted) random sample of every value seen so far
+    pub sample: Vec<f64>,
+    /// Number of values in the sample
+    pub sample_size: usize,
+    /// Most values the sample can hold
+    pub capacity: usize,
+    /// Whether this is a weighted sample
+    pub weighted: bool,
+    /// Values seen across all chunks so far
+    pub items_seen: u64,
+    /// Values in this chunk
+    pub items_added: usize,
+    /// Sum of all weights seen so far (weighted samples only)
+    pub total_weight: Option<f64>,
+}
+
+/// Keep a fixed-size uniform or weighted random sample of a stream sent in chunks,
+/// so statistics tools can run on a fair sample of data too large to send at once
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn reservoir_sample(request: Request<ReservoirSampleInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "reservoir_sample",
+                ReservoirSampleInput,
+                ReservoirSampleOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("reservoir_sample", &input);
+    log.finish(run_reservoir_sample(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_reservoir_sample(input: ReservoirSampleInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    let guard = Guard::from_env()
+        .array("values", &input.values)
+        .array("weights", input.weights.as_deref().unwrap_or_default())
+        .string("state", input.state.as_deref().unwrap_or_default());
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        values: input.values,
+        weights: input.weights,
+        state: input.state,
+        sample_size: input.sample_size,
+        seed: input.seed,
+    };
+
+    // Call logic implementation
+    match logic::update_reservoir(logic_input) {
+        Ok(result) => {
+            // Convert back to wrapper types
+            let response = ReservoirSampleOutput {
+                state: result.state,
+                sample: result.sample,
+                sample_size: result.sample_size,
+                capacity: result.capacity,
+                weighted: result.weighted,
+                items_seen: result.items_seen,
+                items_added: result.items_added,
+                total_weight: result.total_weight,
+            };
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
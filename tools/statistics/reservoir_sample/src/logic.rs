@@ -0,0 +1,388 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SAMPLE_SIZE: usize = 1_000;
+pub const MAX_SAMPLE_SIZE: usize = 100_000;
+
+/// Leading bytes of a serialized reservoir: magic "RS" and the format version
+const HEADER: [u8; 3] = [b'R', b'S', 1];
+/// Header, mode, capacity, items seen, total weight and generator state
+const FIXED_LEN: usize = HEADER.len() + 1 + 4 + 8 + 8 + 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReservoirInput {
+    pub values: Vec<f64>,
+    pub weights: Option<Vec<f64>>,
+    pub state: Option<String>,
+    pub sample_size: Option<usize>,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReservoirOutput {
+    pub state: String,
+    pub sample: Vec<f64>,
+    pub sample_size: usize,
+    pub capacity: usize,
+    pub weighted: bool,
+    pub items_seen: u64,
+    pub items_added: usize,
+    pub total_weight: Option<f64>,
+}
+
+/// SplitMix64, small enough to carry its state between calls so a chunked
+/// stream samples exactly like a single pass with the same seed
+#[derive(Debug, Clone, PartialEq)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, bound)
+    fn below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// Uniform in (0, 1]
+    fn unit(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// One sampled value; `key` orders weighted entries and is unused otherwise
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    key: f64,
+    value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Reservoir {
+    weighted: bool,
+    capacity: usize,
+    seen: u64,
+    total_weight: f64,
+    rng: SplitMix64,
+    entries: Vec<Entry>,
+}
+
+impl Reservoir {
+    /// Algorithm R (Vitter): item i replaces a random slot with probability k / i
+    fn offer(&mut self, value: f64) {
+        self.seen += 1;
+        if self.entries.len() < self.capacity {
+            self.entries.push(Entry { key: 0.0, value });
+        } else {
+            let slot = self.rng.below(self.seen) as usize;
+            if slot < self.capacity {
+                self.entries[slot] = Entry { key: 0.0, value };
+            }
+        }
+    }
+
+    /// A-Res (Efraimidis & Spirakis): keep the k largest keys u^(1/w), compared
+    /// as ln(u) / w to avoid underflow for small weights
+    fn offer_weighted(&mut self, value: f64, weight: f64) {
+        self.seen += 1;
+        if weight == 0.0 {
+            return;
+        }
+        self.total_weight += weight;
+        let key = self.rng.unit().ln() / weight;
+        if self.entries.len() < self.capacity {
+            self.entries.push(Entry { key, value });
+            return;
+        }
+        let (min_index, min_key) = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (i, e.key))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("reservoir is full");
+        if key > min_key {
+            self.entries[min_index] = Entry { key, value };
+        }
+    }
+
+    fn encode(&self) -> String {
+        let entry_len = if self.weighted { 16 } else { 8 };
+        let mut bytes = Vec::with_capacity(FIXED_LEN + self.entries.len() * entry_len);
+        bytes.extend_from_slice(&HEADER);
+        bytes.push(u8::from(self.weighted));
+        bytes.extend_from_slice(&(self.capacity as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.seen.to_le_bytes());
+        bytes.extend_from_slice(&self.total_weight.to_le_bytes());
+        bytes.extend_from_slice(&self.rng.0.to_le_bytes());
+        for entry in &self.entries {
+            if self.weighted {
+                bytes.extend_from_slice(&entry.key.to_le_bytes());
+            }
+            bytes.extend_from_slice(&entry.value.to_le_bytes());
+        }
+        general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn decode(encoded: &str) -> Result<Self, String> {
+        let bytes = general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("invalid base64: {e}"))?;
+        if bytes.len() < FIXED_LEN || bytes[..HEADER.len()] != HEADER {
+            return Err("not a serialized reservoir sample".to_string());
+        }
+        let weighted = match bytes[3] {
+            0 => false,
+            1 => true,
+            mode => return Err(format!("unknown sampling mode {mode}")),
+        };
+        let u64_at =
+            |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let capacity = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        let seen = u64_at(8);
+        let total_weight = f64::from_bits(u64_at(16));
+        let rng = SplitMix64(u64_at(24));
+
+        let entry_len = if weighted { 16 } else { 8 };
+        let body = &bytes[FIXED_LEN..];
+        if capacity == 0 || capacity > MAX_SAMPLE_SIZE || body.len() % entry_len != 0 {
+            return Err("reservoir contents are inconsistent".to_string());
+        }
+        let entries: Vec<Entry> = body
+            .chunks_exact(entry_len)
+            .map(|chunk| {
+                let f64_at = |offset: usize| {
+                    f64::from_le_bytes(chunk[offset..offset + 8].try_into().unwrap())
+                };
+                if weighted {
+                    Entry {
+                        key: f64_at(0),
+                        value: f64_at(8),
+                    }
+                } else {
+                    Entry {
+                        key: 0.0,
+                        value: f64_at(0),
+                    }
+                }
+            })
+            .collect();
+        if entries.len() > capacity || entries.len() as u64 > seen {
+            return Err("reservoir contents are inconsistent".to_string());
+        }
+        Ok(Reservoir {
+            weighted,
+            capacity,
+            seen,
+            total_weight,
+            rng,
+            entries,
+        })
+    }
+}
+
+pub fn update_reservoir(input: ReservoirInput) -> Result<ReservoirOutput, String> {
+    if let Some((i, _)) = input
+        .values
+        .iter()
+        .enumerate()
+        .find(|(_, v)| !v.is_finite())
+    {
+        return Err(format!("values[{i}] is not a finite number"));
+    }
+    if let Some(weights) = &input.weights {
+        if weights.len() != input.values.len() {
+            return Err(format!(
+                "weights has {} entries but values has {}",
+                weights.len(),
+                input.values.len()
+            ));
+        }
+        if let Some((i, w)) = weights
+            .iter()
+            .enumerate()
+            .find(|(_, w)| !w.is_finite() || **w < 0.0)
+        {
+            return Err(format!(
+                "weights[{i}] must be a non-negative finite number, got {w}"
+            ));
+        }
+    }
+
+    let mut reservoir = match &input.state {
+        Some(state) => {
+            let reservoir = Reservoir::decode(state).map_err(|e| format!("state: {e}"))?;
+            if input
+                .sample_size
+                .is_some_and(|size| size != reservoir.capacity)
+            {
+                return Err(format!(
+                    "sample_size cannot change once sampling has started (state holds {})",
+                    reservoir.capacity
+                ));
+            }
+            if reservoir.weighted != input.weights.is_some() {
+                return Err(if reservoir.weighted {
+                    "state is a weighted sample; provide weights for every value".to_string()
+                } else {
+                    "state is an unweighted sample; weights cannot be added later".to_string()
+                });
+            }
+            reservoir
+        }
+        None => {
+            let capacity = input.sample_size.unwrap_or(DEFAULT_SAMPLE_SIZE);
+            if !(1..=MAX_SAMPLE_SIZE).contains(&capacity) {
+                return Err(format!(
+                    "sample_size must be between 1 and {MAX_SAMPLE_SIZE}, got {capacity}"
+                ));
+            }
+            Reservoir {
+                weighted: input.weights.is_some(),
+                capacity,
+                seen: 0,
+                total_weight: 0.0,
+                rng: SplitMix64(input.seed.unwrap_or_else(rand::random)),
+                entries: Vec::new(),
+            }
+        }
+    };
+
+    match &input.weights {
+        Some(weights) => {
+            for (&value, &weight) in input.values.iter().zip(weights) {
+                reservoir.offer_weighted(value, weight);
+            }
+        }
+        None => {
+            for &value in &input.values {
+                reservoir.offer(value);
+            }
+        }
+    }
+
+    Ok(ReservoirOutput {
+        state: reservoir.encode(),
+        sample: reservoir.entries.iter().map(|e| e.value).collect(),
+        sample_size: reservoir.entries.len(),
+        capacity: reservoir.capacity,
+        weighted: reservoir.weighted,
+        items_seen: reservoir.seen,
+        items_added: input.values.len(),
+        total_weight: reservoir.weighted.then_some(reservoir.total_weight),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(values: Vec<f64>, sample_size: usize) -> ReservoirInput {
+        ReservoirInput {
+            values,
+            weights: None,
+            state: None,
+            sample_size: Some(sample_size),
+            seed: Some(42),
+        }
+    }
+
+    fn range(start: u32, end: u32) -> Vec<f64> {
+        (start..end).map(f64::from).collect()
+    }
+
+    #[test]
+    fn test_fills_before_sampling() {
+        let result = update_reservoir(input(range(0, 5), 10)).unwrap();
+        assert_eq!(result.sample, range(0, 5));
+        assert_eq!(result.items_seen, 5);
+        assert_eq!(result.capacity, 10);
+        assert_eq!(result.total_weight, None);
+    }
+
+    #[test]
+    fn test_chunked_matches_single_pass() {
+        let whole = update_reservoir(input(range(0, 10_000), 100)).unwrap();
+
+        let mut state = None;
+        for start in (0..10_000).step_by(2_500) {
+            let mut chunk = input(range(start, start + 2_500), 100);
+            chunk.state = state;
+            state = Some(update_reservoir(chunk).unwrap().state);
+        }
+        assert_eq!(state.unwrap(), whole.state);
+        assert_eq!(whole.items_seen, 10_000);
+        assert_eq!(whole.sample_size, 100);
+    }
+
+    #[test]
+    fn test_uniform_sample_is_unbiased() {
+        // Mean of many sample means approaches the stream mean
+        let mut total = 0.0;
+        for seed in 0..200 {
+            let mut request = input(range(0, 1_000), 50);
+            request.seed = Some(seed);
+            let sample = update_reservoir(request).unwrap().sample;
+            total += sample.iter().sum::<f64>() / sample.len() as f64;
+        }
+        let mean = total / 200.0;
+        assert!((mean - 499.5).abs() < 10.0, "{mean}");
+    }
+
+    #[test]
+    fn test_weighted_sample_prefers_heavy_items() {
+        // Values 0..100 with weight 1, values 100..200 with weight 9
+        let mut request = input(range(0, 200), 20);
+        request.weights = Some((0..200).map(|i| if i < 100 { 1.0 } else { 9.0 }).collect());
+        let result = update_reservoir(request).unwrap();
+        let heavy = result.sample.iter().filter(|&&v| v >= 100.0).count();
+        assert!(heavy >= 14, "{heavy}");
+        assert!(result.weighted);
+        assert_eq!(result.total_weight, Some(1000.0));
+
+        // Zero weights are counted but never sampled
+        let mut request = input(range(0, 10), 5);
+        request.weights = Some(vec![0.0; 10]);
+        let result = update_reservoir(request).unwrap();
+        assert!(result.sample.is_empty());
+        assert_eq!(result.items_seen, 10);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(update_reservoir(input(vec![1.0, f64::NAN], 5)).is_err());
+        assert!(update_reservoir(input(vec![1.0], 0)).is_err());
+
+        let mut request = input(vec![1.0, 2.0], 5);
+        request.weights = Some(vec![1.0]);
+        assert_eq!(
+            update_reservoir(request).unwrap_err(),
+            "weights has 1 entries but values has 2"
+        );
+
+        let state = update_reservoir(input(vec![1.0], 5)).unwrap().state;
+        let mut request = input(vec![2.0], 6);
+        request.state = Some(state.clone());
+        assert!(update_reservoir(request).is_err());
+
+        let mut request = input(vec![2.0], 5);
+        request.state = Some(state);
+        request.weights = Some(vec![1.0]);
+        assert_eq!(
+            update_reservoir(request).unwrap_err(),
+            "state is an unweighted sample; weights cannot be added later"
+        );
+
+        let mut request = input(vec![], 5);
+        request.state = Some("bm90IGEgcmVzZXJ2b2ly".to_string());
+        assert_eq!(
+            update_reservoir(request).unwrap_err(),
+            "state: not a serialized reservoir sample"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_VALUES, ScaleDataInput as LogicInput, ScaleDataOutput as LogicOutput,
+    ScaleParameters as LogicScaleParameters, scale_data_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    ScaleDataOutputSchema as ScaleDataOutput, ScaleParametersSchema as ScaleParameters,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScaleDataInput {
+    #[serde(flatten)]
+    pub params: logic::ScaleDataInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Scale one or more series by min-max, z-score, robust (median/IQR) or unit-vector
+/// scaling, returning the parameters needed to scale new data or invert the transform
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn scale_data(request: Request<ScaleDataInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("scale_data", ScaleDataInput, ScaleDataOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("scale_data", &input);
+    log.finish(run_scale_data(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_scale_data(input: ScaleDataInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("series", &input.params.series)
+        .array_len("series", input.params.series.iter().map(Vec::len).sum())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::scale_data_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &ScaleDataOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,347 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most values, over all series, accepted in one call
+pub const MAX_VALUES: usize = 1_000_000;
+
+/// Affine map of one series: scaled = (value - center) / scale
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct ScaleParameters {
+    /// Subtracted before dividing: the mean for z_score, the median for robust, 0 for
+    /// unit_vector, and the minimum shifted for feature_range for min_max
+    pub center: f64,
+    /// Divisor: the standard deviation, interquartile range, Euclidean norm, or data
+    /// range over the feature_range width
+    pub scale: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ScaleDataInput {
+    /// One or more numeric series, each scaled independently
+    pub series: Vec<Vec<f64>>,
+    /// "min_max", "z_score", "robust" (median and interquartile range) or "unit_vector"
+    /// (default: z_score)
+    pub method: Option<String>,
+    /// Target [min, max] for min_max (default: [0, 1])
+    pub feature_range: Option<[f64; 2]>,
+    /// Parameters from an earlier call, one per series, to scale new data the same way
+    /// instead of fitting them to this data
+    #[tool_types(nested)]
+    pub parameters: Option<Vec<ScaleParameters>>,
+    /// Map scaled values back to the original units with the given parameters
+    /// (default: false)
+    pub inverse: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct ScaleDataOutput {
+    /// Method the parameters were fitted with; absent when parameters were supplied
+    pub method: Option<String>,
+    /// Scaled series, or original-unit series for inverse
+    pub series: Vec<Vec<f64>>,
+    /// Parameters per series; invert with value = scaled * scale + center
+    #[tool_types(nested)]
+    pub parameters: Vec<ScaleParameters>,
+    pub inverse: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Quantile by linear interpolation between order statistics of sorted values
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (low, high) = (position.floor() as usize, position.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (position - low as f64)
+}
+
+fn fit(values: &[f64], method: &str, range: [f64; 2]) -> (ScaleParameters, f64) {
+    let n = values.len() as f64;
+    match method {
+        "min_max" => {
+            let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let scale = (max - min) / (range[1] - range[0]);
+            (
+                ScaleParameters {
+                    center: min - range[0] * scale,
+                    scale,
+                },
+                max - min,
+            )
+        }
+        "robust" => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+            (
+                ScaleParameters {
+                    center: quantile(&sorted, 0.5),
+                    scale: iqr,
+                },
+                iqr,
+            )
+        }
+        "unit_vector" => {
+            let norm = values.iter().map(|v| v * v).sum::<f64>().sqrt();
+            (
+                ScaleParameters {
+                    center: 0.0,
+                    scale: norm,
+                },
+                norm,
+            )
+        }
+        _ => {
+            let mean = values.iter().sum::<f64>() / n;
+            // Population standard deviation, so the scaled series has unit variance
+            let sd = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+            (
+                ScaleParameters {
+                    center: mean,
+                    scale: sd,
+                },
+                sd,
+            )
+        }
+    }
+}
+
+pub fn scale_data_logic(input: ScaleDataInput) -> Result<ScaleDataOutput, String> {
+    if input.series.is_empty() || input.series.iter().any(Vec::is_empty) {
+        return Err("At least one non-empty series is required".to_string());
+    }
+    if input.series.iter().map(Vec::len).sum::<usize>() > MAX_VALUES {
+        return Err(format!("At most {MAX_VALUES} values are allowed"));
+    }
+    if input.series.iter().flatten().any(|v| !v.is_finite()) {
+        return Err("Series values must be finite numbers".to_string());
+    }
+    let inverse = input.inverse.unwrap_or(false);
+    let mut warnings = Vec::new();
+
+    let (method, parameters) = match input.parameters {
+        Some(parameters) => {
+            if input.method.is_some() || input.feature_range.is_some() {
+                return Err(
+                    "method and feature_range are fixed by the supplied parameters".to_string(),
+                );
+            }
+            if parameters.len() != input.series.len() {
+                return Err(format!(
+                    "Got {} parameter sets for {} series",
+                    parameters.len(),
+                    input.series.len()
+                ));
+            }
+            if parameters
+                .iter()
+                .any(|p| !p.center.is_finite() || !p.scale.is_finite() || p.scale == 0.0)
+            {
+                return Err(
+                    "Parameters need a finite center and a finite non-zero scale".to_string(),
+                );
+            }
+            (None, parameters)
+        }
+        None => {
+            if inverse {
+                return Err(
+                    "inverse needs the parameters returned when the data was scaled".to_string(),
+                );
+            }
+            let method = input
+                .method
+                .as_deref()
+                .map(|m| m.trim().to_lowercase())
+                .unwrap_or_else(|| "z_score".to_string());
+            if !matches!(
+                method.as_str(),
+                "min_max" | "z_score" | "robust" | "unit_vector"
+            ) {
+                return Err(format!(
+                    "Unknown method '{method}', use 'min_max', 'z_score', 'robust' or 'unit_vector'"
+                ));
+            }
+            let range = input.feature_range.unwrap_or([0.0, 1.0]);
+            if method != "min_max" && input.feature_range.is_some() {
+                return Err("feature_range only applies to min_max".to_string());
+            }
+            if !(range[0].is_finite() && range[1].is_finite() && range[0] < range[1]) {
+                return Err("feature_range must be finite with min below max".to_string());
+            }
+            let parameters = input
+                .series
+                .iter()
+                .enumerate()
+                .map(|(i, values)| {
+                    let (mut parameters, spread) = fit(values, &method, range);
+                    if spread == 0.0 {
+                        // A constant series has nothing to scale by; only shift it
+                        warnings.push(format!(
+                            "Series {i} has zero spread for {method}; its scale was set to 1"
+                        ));
+                        parameters.scale = 1.0;
+                        if method == "min_max" {
+                            parameters.center = values[0] - range[0];
+                        }
+                    }
+                    parameters
+                })
+                .collect();
+            (Some(method), parameters)
+        }
+    };
+
+    let series = input
+        .series
+        .iter()
+        .zip(&parameters)
+        .map(|(values, p)| {
+            values
+                .iter()
+                .map(|v| {
+                    if inverse {
+                        v * p.scale + p.center
+                    } else {
+                        (v - p.center) / p.scale
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(ScaleDataOutput {
+        method,
+        series,
+        parameters,
+        inverse,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(series: Vec<Vec<f64>>, method: &str) -> ScaleDataInput {
+        ScaleDataInput {
+            series,
+            method: Some(method.to_string()),
+            feature_range: None,
+            parameters: None,
+            inverse: None,
+        }
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_min_max_and_feature_range() {
+        let result = scale_data_logic(request(vec![vec![2.0, 4.0, 6.0, 10.0]], "min_max")).unwrap();
+        assert_close(&result.series[0], &[0.0, 0.25, 0.5, 1.0]);
+
+        let mut input = request(vec![vec![2.0, 4.0, 6.0, 10.0]], "min_max");
+        input.feature_range = Some([-1.0, 1.0]);
+        let result = scale_data_logic(input).unwrap();
+        assert_close(&result.series[0], &[-1.0, -0.5, 0.0, 1.0]);
+        assert_eq!(
+            result.parameters[0],
+            ScaleParameters {
+                center: 6.0,
+                scale: 4.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_z_score_robust_and_unit_vector() {
+        let data = vec![vec![1.0, 2.0, 3.0, 4.0, 5.0], vec![3.0, 4.0]];
+        let z = scale_data_logic(request(data.clone(), "z_score")).unwrap();
+        let sd = 2f64.sqrt();
+        assert_close(
+            &z.series[0],
+            &[-2.0 / sd, -1.0 / sd, 0.0, 1.0 / sd, 2.0 / sd],
+        );
+
+        let robust = scale_data_logic(request(data.clone(), "robust")).unwrap();
+        // Median 3, quartiles 2 and 4
+        assert_close(&robust.series[0], &[-1.0, -0.5, 0.0, 0.5, 1.0]);
+
+        let unit = scale_data_logic(request(data, "unit_vector")).unwrap();
+        assert_close(&unit.series[1], &[0.6, 0.8]);
+    }
+
+    #[test]
+    fn test_inverse_round_trip_and_reuse() {
+        let data = vec![vec![10.0, 20.0, 35.0], vec![-1.0, 0.0, 4.0]];
+        let scaled = scale_data_logic(request(data.clone(), "robust")).unwrap();
+        let inverse = scale_data_logic(ScaleDataInput {
+            series: scaled.series.clone(),
+            method: None,
+            feature_range: None,
+            parameters: Some(scaled.parameters.clone()),
+            inverse: Some(true),
+        })
+        .unwrap();
+        assert!(inverse.inverse && inverse.method.is_none());
+        assert_close(&inverse.series[0], &data[0]);
+        assert_close(&inverse.series[1], &data[1]);
+
+        // New data scaled with the fitted parameters
+        let reused = scale_data_logic(ScaleDataInput {
+            series: vec![vec![20.0], vec![0.0]],
+            method: None,
+            feature_range: None,
+            parameters: Some(scaled.parameters),
+            inverse: None,
+        })
+        .unwrap();
+        assert_close(&reused.series[0], &[0.0]);
+    }
+
+    #[test]
+    fn test_constant_series() {
+        let result = scale_data_logic(request(vec![vec![7.0, 7.0, 7.0]], "z_score")).unwrap();
+        assert_close(&result.series[0], &[0.0, 0.0, 0.0]);
+        assert_eq!(result.warnings.len(), 1);
+
+        let mut input = request(vec![vec![7.0, 7.0]], "min_max");
+        input.feature_range = Some([2.0, 5.0]);
+        let result = scale_data_logic(input).unwrap();
+        assert_close(&result.series[0], &[2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(scale_data_logic(request(vec![], "z_score")).is_err());
+        assert!(
+            scale_data_logic(request(vec![vec![1.0]], "log"))
+                .unwrap_err()
+                .contains("Unknown method")
+        );
+        let mut input = request(vec![vec![1.0, 2.0]], "z_score");
+        input.inverse = Some(true);
+        assert!(scale_data_logic(input).unwrap_err().contains("parameters"));
+        let mut input = request(vec![vec![1.0, 2.0]], "min_max");
+        input.feature_range = Some([1.0, 0.0]);
+        assert!(scale_data_logic(input).is_err());
+        let input = ScaleDataInput {
+            series: vec![vec![1.0], vec![2.0]],
+            method: None,
+            feature_range: None,
+            parameters: Some(vec![ScaleParameters {
+                center: 0.0,
+                scale: 1.0,
+            }]),
+            inverse: None,
+        };
+        assert_eq!(
+            scale_data_logic(input).unwrap_err(),
+            "Got 1 parameter sets for 2 series"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{TDigestInput as LogicInput, TDigestOutput as LogicOutput, process_tdigest};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TDigestInput {
+    /// Values to add to the digest
+    pub data: Option<Vec<f64>>,
+    /// Base64 digests returned by earlier calls, merged together with data
+    pub digests: Option<Vec<String>>,
+    /// Compression (10-10000): higher is more accurate and larger (default: 100, or the largest among merged digests)
+    pub compression: Option<f64>,
+    /// Quantiles to estimate, each between 0 and 1 (e.g. [0.5, 0.95, 0.99])
+    pub quantiles: Option<Vec<f64>>,
+    /// Values whose cumulative fraction (CDF) to estimate
+    pub values: Option<Vec<f64>>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct QuantileEstimate {
+    /// Requested quantile
+    pub quantile: f64,
+    /// Estimated value at that quantile
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CdfEstimate {
+    /// Requested value
+    pub value: f64,
+    /// Estimated fraction of values less than or equal to it
+    pub fraction: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TDigestOutput {
+    /// Base64 digest of all values so far; pass it back in digests to add or merge more
+    pub digest: String,
+    /// Number of values summarized
+    pub count: f64,
+    /// Smallest value (exact)
+    pub min: f64,
+    /// Largest value (exact)
+    pub max: f64,
+    /// Mean of all values
+    pub mean: f64,
+    /// Compression of the digest
+    pub compression: f64,
+    /// Number of centroids kept in the digest
+    pub centroid_count: usize,
+    /// Estimates for the requested quantiles
+    pub quantiles: Vec<QuantileEstimate>,
+    /// Estimates for the requested CDF values
+    pub cdf: Vec<CdfEstimate>,
+}
+
+/// Build, merge and query t-digest sketches: compact quantile summaries that can be
+/// built in parts and merged, for percentiles of datasets too large for one request
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn tdigest(request: Request<TDigestInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("tdigest", TDigestInput, TDigestOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("tdigest", &input);
+    log.finish(run_tdigest(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_tdigest(input: TDigestInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    let digests = input.digests.as_deref().unwrap_or_default();
+    let guard = Guard::from_env()
+        .array("data", input.data.as_deref().unwrap_or_default())
+        .array("digests", digests)
+        .array("quantiles", input.quantiles.as_deref().unwrap_or_default())
+        .array("values", input.values.as_deref().unwrap_or_default());
+    let guard = digests
+        .iter()
+        .fold(guard, |guard, digest| guard.string("digests", digest));
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        data: input.data,
+        digests: input.digests,
+        compression: input.compression,
+        quantiles: input.quantiles,
+        values: input.values,
+    };
+
+    // Call logic implementation
+    match logic::process_tdigest(logic_input) {
+        Ok(result) => {
+            // Convert back to wrapper types
+            let response = TDigestOutput {
+                digest: result.digest,
+                count: result.count,
+                min: result.min,
+                max: result.max,
+                mean: result.mean,
+                compression: result.compression,
+                centroid_count: result.centroid_count,
+                quantiles: result
+                    .quantiles
+                    .into_iter()
+                    .map(|q| QuantileEstimate {
+                        quantile: q.quantile,
+                        value: q.value,
+                    })
+                    .collect(),
+                cdf: result
+                    .cdf
+                    .into_iter()
+                    .map(|c| CdfEstimate {
+                        value: c.value,
+                        fraction: c.fraction,
+                    })
+                    .collect(),
+            };
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
+        }
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
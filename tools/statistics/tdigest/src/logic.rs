@@ -0,0 +1,463 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Default compression (delta); digests keep roughly this many centroids
+pub const DEFAULT_COMPRESSION: f64 = 100.0;
+pub const MIN_COMPRESSION: f64 = 10.0;
+pub const MAX_COMPRESSION: f64 = 10_000.0;
+
+/// Leading bytes of a serialized digest: magic "TD" and the format version
+const HEADER: [u8; 3] = [b'T', b'D', 1];
+/// Header, compression, min, max and the centroid count
+const FIXED_LEN: usize = HEADER.len() + 3 * 8 + 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TDigestInput {
+    pub data: Option<Vec<f64>>,
+    pub digests: Option<Vec<String>>,
+    pub compression: Option<f64>,
+    pub quantiles: Option<Vec<f64>>,
+    pub values: Option<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileEstimate {
+    pub quantile: f64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CdfEstimate {
+    pub value: f64,
+    pub fraction: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TDigestOutput {
+    pub digest: String,
+    pub count: f64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub compression: f64,
+    pub centroid_count: usize,
+    pub quantiles: Vec<QuantileEstimate>,
+    pub cdf: Vec<CdfEstimate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Merging t-digest (Dunning & Ertl) with the arcsine scale function, which
+/// keeps centroids small near the tails where quantile estimates matter most
+#[derive(Debug, Clone, PartialEq)]
+struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    /// Build a digest from unsorted centroids, merging neighbours as long as
+    /// each merged centroid spans at most one unit of the scale function
+    fn from_centroids(compression: f64, mut centroids: Vec<Centroid>) -> Self {
+        let min = centroids
+            .iter()
+            .map(|c| c.mean)
+            .fold(f64::INFINITY, f64::min);
+        let max = centroids
+            .iter()
+            .map(|c| c.mean)
+            .fold(f64::NEG_INFINITY, f64::max);
+        centroids.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+
+        let mut merged: Vec<Centroid> = Vec::new();
+        let mut weight_before = 0.0;
+        let mut q_limit = k_inverse(k_scale(0.0, compression) + 1.0, compression);
+        for centroid in centroids {
+            let Some(current) = merged.last_mut() else {
+                merged.push(centroid);
+                continue;
+            };
+            let q = (weight_before + current.weight + centroid.weight) / total;
+            if q <= q_limit {
+                let weight = current.weight + centroid.weight;
+                current.mean += (centroid.mean - current.mean) * centroid.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_before += current.weight;
+                q_limit = k_inverse(
+                    k_scale(weight_before / total, compression) + 1.0,
+                    compression,
+                );
+                merged.push(centroid);
+            }
+        }
+
+        TDigest {
+            compression,
+            centroids: merged,
+            min,
+            max,
+        }
+    }
+
+    fn count(&self) -> f64 {
+        self.centroids.iter().map(|c| c.weight).sum()
+    }
+
+    fn mean(&self) -> f64 {
+        self.centroids
+            .iter()
+            .map(|c| c.mean * c.weight)
+            .sum::<f64>()
+            / self.count()
+    }
+
+    /// Interpolation knots as (cumulative weight, value): the minimum at 0,
+    /// each centroid at the middle of its weight, and the maximum at the total
+    fn knots(&self) -> Vec<(f64, f64)> {
+        let mut knots = Vec::with_capacity(self.centroids.len() + 2);
+        knots.push((0.0, self.min));
+        let mut cumulative = 0.0;
+        for c in &self.centroids {
+            knots.push((cumulative + c.weight / 2.0, c.mean));
+            cumulative += c.weight;
+        }
+        knots.push((cumulative, self.max));
+        knots
+    }
+
+    fn quantile(&self, q: f64) -> f64 {
+        if q <= 0.0 {
+            return self.min;
+        }
+        if q >= 1.0 {
+            return self.max;
+        }
+        let knots = self.knots();
+        let index = q * self.count();
+        let upper = knots
+            .iter()
+            .position(|&(rank, _)| rank >= index)
+            .unwrap_or(knots.len() - 1);
+        if upper == 0 {
+            return self.min;
+        }
+        let ((r0, v0), (r1, v1)) = (knots[upper - 1], knots[upper]);
+        if r1 == r0 {
+            return v1;
+        }
+        v0 + (v1 - v0) * (index - r0) / (r1 - r0)
+    }
+
+    /// Estimated fraction of values less than or equal to `x`
+    fn cdf(&self, x: f64) -> f64 {
+        if x < self.min {
+            return 0.0;
+        }
+        if x >= self.max {
+            return 1.0;
+        }
+        let knots = self.knots();
+        // min <= x < max, so some knot lies above x and the one before it does not
+        let upper = knots.iter().position(|&(_, value)| value > x).unwrap();
+        let ((r0, v0), (r1, v1)) = (knots[upper - 1], knots[upper]);
+        (r0 + (r1 - r0) * (x - v0) / (v1 - v0)) / self.count()
+    }
+
+    fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(FIXED_LEN + self.centroids.len() * 16);
+        bytes.extend_from_slice(&HEADER);
+        bytes.extend_from_slice(&self.compression.to_le_bytes());
+        bytes.extend_from_slice(&self.min.to_le_bytes());
+        bytes.extend_from_slice(&self.max.to_le_bytes());
+        bytes.extend_from_slice(&(self.centroids.len() as u32).to_le_bytes());
+        for c in &self.centroids {
+            bytes.extend_from_slice(&c.mean.to_le_bytes());
+            bytes.extend_from_slice(&c.weight.to_le_bytes());
+        }
+        general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn decode(encoded: &str) -> Result<Self, String> {
+        let bytes = general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("invalid base64: {e}"))?;
+        if bytes.len() < FIXED_LEN || bytes[..HEADER.len()] != HEADER {
+            return Err("not a serialized t-digest".to_string());
+        }
+        let f64_at =
+            |offset: usize| f64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let compression = f64_at(3);
+        let min = f64_at(11);
+        let max = f64_at(19);
+        let len = u32::from_le_bytes(bytes[27..FIXED_LEN].try_into().unwrap()) as usize;
+        if len.checked_mul(16).and_then(|n| n.checked_add(FIXED_LEN)) != Some(bytes.len()) {
+            return Err(format!(
+                "expected {len} centroids, data is truncated or too long"
+            ));
+        }
+
+        let centroids: Vec<Centroid> = (0..len)
+            .map(|i| Centroid {
+                mean: f64_at(FIXED_LEN + i * 16),
+                weight: f64_at(FIXED_LEN + i * 16 + 8),
+            })
+            .collect();
+        let valid = (MIN_COMPRESSION..=MAX_COMPRESSION).contains(&compression)
+            && !centroids.is_empty()
+            && min <= max
+            && centroids
+                .iter()
+                .all(|c| c.weight.is_finite() && c.weight > 0.0 && c.mean >= min && c.mean <= max);
+        if !valid {
+            return Err("t-digest contents are inconsistent".to_string());
+        }
+
+        Ok(TDigest {
+            compression,
+            centroids,
+            min,
+            max,
+        })
+    }
+}
+
+/// Arcsine scale function k(q) = delta / (2 pi) * asin(2q - 1)
+fn k_scale(q: f64, compression: f64) -> f64 {
+    compression / (2.0 * PI) * (2.0 * q - 1.0).asin()
+}
+
+fn k_inverse(k: f64, compression: f64) -> f64 {
+    ((k * 2.0 * PI / compression).min(PI / 2.0).sin() + 1.0) / 2.0
+}
+
+pub fn process_tdigest(input: TDigestInput) -> Result<TDigestOutput, String> {
+    let data = input.data.unwrap_or_default();
+    let encoded = input.digests.unwrap_or_default();
+    if data.is_empty() && encoded.is_empty() {
+        return Err("Provide data values, digests to merge, or both".to_string());
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err("Input data contains invalid values (NaN or Infinite)".to_string());
+    }
+
+    let digests = encoded
+        .iter()
+        .enumerate()
+        .map(|(i, d)| TDigest::decode(d).map_err(|e| format!("digests[{i}]: {e}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Merged digests keep the finest compression among them unless one is given
+    let compression = match input.compression {
+        Some(c) if (MIN_COMPRESSION..=MAX_COMPRESSION).contains(&c) => c,
+        Some(c) => {
+            return Err(format!(
+                "compression must be between {MIN_COMPRESSION} and {MAX_COMPRESSION}, got {c}"
+            ));
+        }
+        None => digests
+            .iter()
+            .map(|d| d.compression)
+            .fold(DEFAULT_COMPRESSION, f64::max),
+    };
+
+    let quantiles = input.quantiles.unwrap_or_default();
+    if let Some(q) = quantiles.iter().find(|q| !(0.0..=1.0).contains(*q)) {
+        return Err(format!("Quantiles must be between 0 and 1, got {q}"));
+    }
+    let values = input.values.unwrap_or_default();
+    if values.iter().any(|x| !x.is_finite()) {
+        return Err("CDF values contain invalid values (NaN or Infinite)".to_string());
+    }
+
+    let mut centroids: Vec<Centroid> = data
+        .iter()
+        .map(|&mean| Centroid { mean, weight: 1.0 })
+        .collect();
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    for digest in &digests {
+        centroids.extend_from_slice(&digest.centroids);
+        min = min.min(digest.min);
+        max = max.max(digest.max);
+    }
+    let mut digest = TDigest::from_centroids(compression, centroids);
+    // Exact extremes survive merging even though centroid means do not
+    digest.min = digest.min.min(min);
+    digest.max = digest.max.max(max);
+
+    Ok(TDigestOutput {
+        digest: digest.encode(),
+        count: digest.count(),
+        min: digest.min,
+        max: digest.max,
+        mean: digest.mean(),
+        compression,
+        centroid_count: digest.centroids.len(),
+        quantiles: quantiles
+            .iter()
+            .map(|&quantile| QuantileEstimate {
+                quantile,
+                value: digest.quantile(quantile),
+            })
+            .collect(),
+        cdf: values
+            .iter()
+            .map(|&value| CdfEstimate {
+                value,
+                fraction: digest.cdf(value),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(data: Vec<f64>) -> TDigestInput {
+        TDigestInput {
+            data: Some(data),
+            digests: None,
+            compression: None,
+            quantiles: None,
+            values: None,
+        }
+    }
+
+    /// Deterministic pseudo-random values in [0, 1)
+    fn uniform(n: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_data_is_exact() {
+        let mut request = input((1..=100).map(f64::from).collect());
+        request.quantiles = Some(vec![0.0, 0.5, 1.0]);
+        request.values = Some(vec![0.0, 50.0, 100.0]);
+        let result = process_tdigest(request).unwrap();
+
+        assert_eq!(result.count, 100.0);
+        assert_eq!(result.mean, 50.5);
+        assert_eq!((result.min, result.max), (1.0, 100.0));
+        assert_eq!(result.quantiles[0].value, 1.0);
+        assert!((result.quantiles[1].value - 50.5).abs() < 1.0);
+        assert_eq!(result.quantiles[2].value, 100.0);
+        assert_eq!(result.cdf[0].fraction, 0.0);
+        assert!((result.cdf[1].fraction - 0.5).abs() < 0.01);
+        assert_eq!(result.cdf[2].fraction, 1.0);
+    }
+
+    #[test]
+    fn test_large_data_is_compressed_and_accurate() {
+        let mut request = input(uniform(100_000, 7));
+        request.quantiles = Some(vec![0.001, 0.01, 0.25, 0.5, 0.75, 0.99, 0.999]);
+        let result = process_tdigest(request).unwrap();
+
+        assert!(result.centroid_count <= 200, "{}", result.centroid_count);
+        for estimate in &result.quantiles {
+            // Uniform on [0, 1): the q-th quantile is q
+            let tolerance = 0.01 * (estimate.quantile * (1.0 - estimate.quantile)).sqrt() + 1e-3;
+            assert!(
+                (estimate.value - estimate.quantile).abs() < tolerance,
+                "q={} estimated {}",
+                estimate.quantile,
+                estimate.value
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_matches_single_digest() {
+        let data = uniform(20_000, 42);
+        let parts: Vec<String> = data
+            .chunks(5_000)
+            .map(|chunk| process_tdigest(input(chunk.to_vec())).unwrap().digest)
+            .collect();
+
+        let merged = process_tdigest(TDigestInput {
+            data: None,
+            digests: Some(parts),
+            compression: None,
+            quantiles: Some(vec![0.1, 0.5, 0.9]),
+            values: Some(vec![0.5]),
+        })
+        .unwrap();
+        let mut whole = input(data.clone());
+        whole.quantiles = Some(vec![0.1, 0.5, 0.9]);
+        let whole = process_tdigest(whole).unwrap();
+
+        assert_eq!(merged.count, 20_000.0);
+        assert_eq!(merged.min, whole.min);
+        assert_eq!(merged.max, whole.max);
+        assert!((merged.mean - whole.mean).abs() < 1e-9);
+        for (m, w) in merged.quantiles.iter().zip(&whole.quantiles) {
+            assert!((m.value - w.value).abs() < 0.01, "{m:?} vs {w:?}");
+        }
+        assert!((merged.cdf[0].fraction - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_digest_round_trip() {
+        let data: Vec<f64> = uniform(1_000, 3).iter().map(|x| x * 10.0 - 5.0).collect();
+        let result = process_tdigest(input(data.clone())).unwrap();
+        let digest = TDigest::decode(&result.digest).unwrap();
+        assert_eq!(digest.encode(), result.digest);
+        assert_eq!(digest.count(), 1_000.0);
+
+        // Adding values to an existing digest
+        let mut request = input(vec![100.0]);
+        request.digests = Some(vec![result.digest]);
+        let extended = process_tdigest(request).unwrap();
+        assert_eq!(extended.count, 1_001.0);
+        assert_eq!(extended.max, 100.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(process_tdigest(input(vec![])).is_err());
+        assert!(process_tdigest(input(vec![1.0, f64::NAN])).is_err());
+
+        let mut request = input(vec![1.0]);
+        request.quantiles = Some(vec![1.5]);
+        assert_eq!(
+            process_tdigest(request).unwrap_err(),
+            "Quantiles must be between 0 and 1, got 1.5"
+        );
+
+        let mut request = input(vec![1.0]);
+        request.compression = Some(1.0);
+        assert!(process_tdigest(request).is_err());
+
+        let mut request = input(vec![]);
+        request.digests = Some(vec!["bm90IGEgZGlnZXN0".to_string()]);
+        assert_eq!(
+            process_tdigest(request).unwrap_err(),
+            "digests[0]: not a serialized t-digest"
+        );
+
+        let mut request = input(vec![]);
+        request.digests = Some(vec!["%%%".to_string()]);
+        assert!(
+            process_tdigest(request)
+                .unwrap_err()
+                .starts_with("digests[0]: invalid base64")
+        );
+    }
+}
@@ -3,18 +3,37 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_messages::Locale;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{TestNormalityInput as LogicInput, TestNormalityOutput as LogicOutput};
+pub use logic::{
+    TestNormalityInput as LogicInput, TestNormalityOutput as LogicOutput, calculate_test_normality,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TestNormalityInput {
     /// Data values to test for normality
     pub data: Vec<f64>,
+    /// Language of the interpretation: "en" (default), "es", "de" or "ja"
+    pub locale: Option<Locale>,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Significant figures to round numeric results to, 1-17 (default: full precision)
+    pub output_precision: Option<u32>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -33,13 +52,48 @@ pub struct TestNormalityOutput {
     pub interpretation: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn test_normality(input: TestNormalityInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn test_normality(request: Request<TestNormalityInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "test_normality",
+                TestNormalityInput,
+                TestNormalityOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("test_normality", &input);
+    log.finish(run_test_normality(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_test_normality(input: TestNormalityInput) -> ToolResponse {
+    // Rounding applied when serializing the response
+    let precision = match ftl_response::rounding(input.precision.as_ref(), input.output_precision) {
+        Ok(precision) => precision,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().array("data", &input.data).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
-    let logic_input = LogicInput { data: input.data };
+    let logic_input = LogicInput {
+        data: input.data,
+        locale: input.locale,
+    };
 
     // Call logic implementation
-    match logic::calculate_test_normality(logic_input) {
+    match ftl_result_cache::cached("test_normality", &logic_input, || {
+        logic::calculate_test_normality(logic_input.clone())
+    }) {
         Ok(result) => {
             // Convert back to wrapper types
             let response = TestNormalityOutput {
@@ -50,7 +104,10 @@ pub fn test_normality(input: TestNormalityInput) -> ToolResponse {
                 confidence_level: result.confidence_level,
                 interpretation: result.interpretation,
             };
-            ToolResponse::text(serde_json::to_string(&response).unwrap())
+            match ftl_response::to_json_with_precision(&response, None, precision.as_ref()) {
+                Ok(body) => ToolResponse::text(body),
+                Err(e) => ToolResponse::text(format!("Error: {e}")),
+            }
         }
         Err(e) => ToolResponse::text(format!("Error: {e}")),
     }
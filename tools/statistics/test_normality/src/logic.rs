@@ -1,11 +1,13 @@
+use ftl_messages::{Locale, NORMALITY_NORMAL, NORMALITY_NOT_NORMAL};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestNormalityInput {
     pub data: Vec<f64>,
+    pub locale: Option<Locale>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TestNormalityOutput {
     pub is_normal: bool,
     pub shapiro_wilk_statistic: Option<f64>,
@@ -63,15 +65,21 @@ pub fn calculate_test_normality(input: TestNormalityInput) -> Result<TestNormali
     let confidence_level = 0.05;
     let is_normal = p_value > confidence_level;
 
-    let interpretation = if is_normal {
-        format!(
-            "Data appears to be normally distributed (p-value: {p_value:.4} > {confidence_level:.2})"
-        )
+    let message = if is_normal {
+        NORMALITY_NORMAL
     } else {
-        format!(
-            "Data does not appear to be normally distributed (p-value: {p_value:.4} <= {confidence_level:.2})"
-        )
+        NORMALITY_NOT_NORMAL
     };
+    let interpretation = message.format(
+        input.locale.unwrap_or_default(),
+        &[
+            ("p_value", format!("{p_value:.4}").as_str()),
+            (
+                "confidence_level",
+                format!("{confidence_level:.2}").as_str(),
+            ),
+        ],
+    );
 
     // Shapiro-Wilk test would be more accurate but is complex to implement
     // For now, we set it to None
@@ -139,6 +147,7 @@ mod tests {
         // Test with approximately normal data
         let input = TestNormalityInput {
             data: vec![1.0, 2.0, 3.0, 4.0, 5.0, 4.0, 3.0, 2.0, 1.0, 3.0], // Symmetric-ish
+            locale: None,
         };
 
         let result = calculate_test_normality(input).unwrap();
@@ -153,6 +162,7 @@ mod tests {
         // Test with clearly non-normal data (exponential-like)
         let input = TestNormalityInput {
             data: vec![1.0, 1.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0, 34.0], // Exponential pattern
+            locale: None,
         };
 
         let result = calculate_test_normality(input).unwrap();
@@ -166,6 +176,7 @@ mod tests {
     fn test_insufficient_data() {
         let input = TestNormalityInput {
             data: vec![1.0, 2.0], // Only 2 points
+            locale: None,
         };
 
         let result = calculate_test_normality(input);
@@ -180,7 +191,10 @@ mod tests {
 
     #[test]
     fn test_empty_data() {
-        let input = TestNormalityInput { data: vec![] };
+        let input = TestNormalityInput {
+            data: vec![],
+            locale: None,
+        };
 
         let result = calculate_test_normality(input);
         assert!(result.is_err());
@@ -191,6 +205,7 @@ mod tests {
     fn test_zero_variance() {
         let input = TestNormalityInput {
             data: vec![5.0, 5.0, 5.0, 5.0, 5.0], // All identical
+            locale: None,
         };
 
         let result = calculate_test_normality(input);
@@ -202,6 +217,7 @@ mod tests {
     fn test_nan_values() {
         let input = TestNormalityInput {
             data: vec![1.0, f64::NAN, 3.0],
+            locale: None,
         };
 
         let result = calculate_test_normality(input);
@@ -213,6 +229,7 @@ mod tests {
     fn test_infinite_values() {
         let input = TestNormalityInput {
             data: vec![1.0, f64::INFINITY, 3.0],
+            locale: None,
         };
 
         let result = calculate_test_normality(input);
@@ -225,6 +242,7 @@ mod tests {
         // Test with known data that has specific skewness and kurtosis
         let input = TestNormalityInput {
             data: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            locale: None,
         };
 
         let result = calculate_test_normality(input).unwrap();
@@ -237,6 +255,7 @@ mod tests {
     fn test_p_value_bounds() {
         let input = TestNormalityInput {
             data: vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0],
+            locale: None,
         };
 
         let result = calculate_test_normality(input).unwrap();
@@ -248,6 +267,7 @@ mod tests {
     fn test_output_fields() {
         let input = TestNormalityInput {
             data: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            locale: None,
         };
 
         let result = calculate_test_normality(input).unwrap();
@@ -270,7 +290,7 @@ mod tests {
             data.push(i as f64);
         }
 
-        let input = TestNormalityInput { data };
+        let input = TestNormalityInput { data, locale: None };
         let result = calculate_test_normality(input).unwrap();
 
         assert!(result.jarque_bera_statistic >= 0.0);
@@ -282,10 +302,24 @@ mod tests {
         // Test with negative values
         let input = TestNormalityInput {
             data: vec![-5.0, -2.0, 0.0, 2.0, 5.0],
+            locale: None,
         };
 
         let result = calculate_test_normality(input).unwrap();
         assert!(result.jarque_bera_statistic >= 0.0);
         assert!(result.p_value >= 0.0 && result.p_value <= 1.0);
     }
+
+    #[test]
+    fn test_localized_interpretation() {
+        let input = TestNormalityInput {
+            data: vec![1.0, 2.0, 3.0, 4.0, 5.0, 4.0, 3.0, 2.0, 1.0, 3.0],
+            locale: Some(Locale::De),
+        };
+
+        let result = calculate_test_normality(input).unwrap();
+        assert!(result.interpretation.contains("normalverteilt"));
+        assert!(result.interpretation.contains("p-Wert"));
+        assert!(!result.interpretation.contains('{'));
+    }
 }
@@ -0,0 +1,96 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_DENSE_VALUES, MAX_DOCUMENTS, MAX_NGRAM, MAX_TEXT_BYTES, TextVectorizeInput as LogicInput,
+    TextVectorizeOutput as LogicOutput, text_vectorize_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{SparseRowSchema as SparseRow, TextVectorizeOutputSchema as TextVectorizeOutput};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TextVectorizeInput {
+    #[serde(flatten)]
+    pub params: logic::TextVectorizeInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Turn documents into bag-of-words count, binary, term-frequency or TF-IDF vectors over
+/// a shared vocabulary, with n-gram, stop word and document-frequency options, as sparse
+/// rows or a dense feature matrix
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn text_vectorize(request: Request<TextVectorizeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "text_vectorize",
+                TextVectorizeInput,
+                TextVectorizeOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("text_vectorize", &input);
+    log.finish(run_text_vectorize(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_text_vectorize(input: TextVectorizeInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    let guard = Guard::from_env()
+        .array("documents", &input.params.documents)
+        .array(
+            "vocabulary",
+            input.params.vocabulary.as_deref().unwrap_or_default(),
+        )
+        .array("idf", input.params.idf.as_deref().unwrap_or_default())
+        .array(
+            "stop_words",
+            input.params.stop_words.as_deref().unwrap_or_default(),
+        );
+    let guard = input
+        .params
+        .documents
+        .iter()
+        .fold(guard, |guard, text| guard.string("documents", text));
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::text_vectorize_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &TextVectorizeOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
@@ -0,0 +1,456 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Most documents accepted in one call
+pub const MAX_DOCUMENTS: usize = 10_000;
+/// Most bytes of text, over all documents, accepted in one call
+pub const MAX_TEXT_BYTES: usize = 5_000_000;
+/// Largest dense matrix, documents times vocabulary, returned in one call
+pub const MAX_DENSE_VALUES: usize = 1_000_000;
+/// Longest n-gram, in tokens
+pub const MAX_NGRAM: usize = 5;
+
+/// Common English function words, removed with english_stop_words
+const ENGLISH_STOP_WORDS: &str = "\
+    about above after again against all am an and any are as at be because been before \
+    being below between both but by can could did do does doing down during each few for \
+    from further had has have having he her here hers herself him himself his how if in \
+    into is it its itself just me more most my myself no nor not now of off on once only \
+    or other our ours ourselves out over own same she should so some such than that the \
+    their theirs them themselves then there these they this those through to too under \
+    until up very was we were what when where which while who whom why will with would you \
+    your yours yourself yourselves";
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TextVectorizeInput {
+    /// Documents to vectorize; tokens are runs of two or more letters, digits or
+    /// underscores
+    pub documents: Vec<String>,
+    /// "count", "binary", "tf" (counts over the document's term total) or "tfidf"
+    /// (default: tfidf)
+    pub weighting: Option<String>,
+    /// Shortest n-gram, in tokens (default: 1)
+    pub ngram_min: Option<usize>,
+    /// Longest n-gram, in tokens, at most 5 (default: ngram_min)
+    pub ngram_max: Option<usize>,
+    /// Lowercase text before tokenizing (default: true)
+    pub lowercase: Option<bool>,
+    /// Drop common English function words such as "the" and "of" (default: false)
+    pub english_stop_words: Option<bool>,
+    /// Further tokens to drop, matched after lowercasing
+    pub stop_words: Option<Vec<String>>,
+    /// Keep terms found in at least this many documents (default: 1)
+    pub min_df: Option<usize>,
+    /// Keep terms found in at most this fraction of documents, 0-1 (default: 1)
+    pub max_df: Option<f64>,
+    /// Keep only the terms with the most occurrences over all documents
+    pub max_features: Option<usize>,
+    /// "l2", "l1" or "none" row normalization (default: l2 for tfidf, none otherwise)
+    pub norm: Option<String>,
+    /// Vocabulary from an earlier call, to vectorize new documents into the same columns;
+    /// terms outside it are ignored
+    pub vocabulary: Option<Vec<String>>,
+    /// Inverse document frequencies from the same earlier call; required with
+    /// vocabulary for tfidf
+    pub idf: Option<Vec<f64>>,
+    /// Also return the full document-term matrix, the features format the classifier
+    /// tools take (default: false)
+    pub dense: Option<bool>,
+}
+
+/// Non-zero entries of one document's row
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct SparseRow {
+    /// Vocabulary indices, ascending
+    pub indices: Vec<usize>,
+    /// Weight of each listed term
+    pub values: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TextVectorizeOutput {
+    /// Terms in column order, sorted
+    pub vocabulary: Vec<String>,
+    /// Documents containing each term
+    pub document_frequencies: Vec<usize>,
+    /// Smoothed inverse document frequency of each term, ln((1 + n) / (1 + df)) + 1;
+    /// tfidf only
+    pub idf: Option<Vec<f64>>,
+    /// One sparse row per document, in input order
+    #[tool_types(nested)]
+    pub rows: Vec<SparseRow>,
+    /// Dense rows, documents by vocabulary; only with dense
+    pub matrix: Option<Vec<Vec<f64>>>,
+    pub weighting: String,
+    pub norm: String,
+    pub document_count: usize,
+    /// Tokens dropped as stop words, over all documents
+    pub stop_words_removed: usize,
+}
+
+/// Tokens of a document, lowercased if asked, minus stop words; also returns how many
+/// tokens were dropped
+fn tokenize(text: &str, lowercase: bool, stop_words: &BTreeSet<String>) -> (Vec<String>, usize) {
+    let text = if lowercase {
+        text.to_lowercase()
+    } else {
+        text.to_string()
+    };
+    let mut dropped = 0;
+    let tokens = text
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|t| t.chars().count() >= 2)
+        .filter(|t| {
+            let stop = stop_words.contains(&t.to_lowercase());
+            dropped += usize::from(stop);
+            !stop
+        })
+        .map(str::to_string)
+        .collect();
+    (tokens, dropped)
+}
+
+/// Counts of every n-gram of the tokens with ngram_min to ngram_max tokens
+fn ngram_counts(tokens: &[String], ngram_min: usize, ngram_max: usize) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for n in ngram_min..=ngram_max {
+        for window in tokens.windows(n) {
+            *counts.entry(window.join(" ")).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+pub fn text_vectorize_logic(input: TextVectorizeInput) -> Result<TextVectorizeOutput, String> {
+    if input.documents.is_empty() {
+        return Err("documents must contain at least one document".to_string());
+    }
+    if input.documents.len() > MAX_DOCUMENTS {
+        return Err(format!("At most {MAX_DOCUMENTS} documents are allowed"));
+    }
+    if input.documents.iter().map(String::len).sum::<usize>() > MAX_TEXT_BYTES {
+        return Err(format!(
+            "At most {MAX_TEXT_BYTES} bytes of text are allowed"
+        ));
+    }
+    let weighting = input
+        .weighting
+        .as_deref()
+        .map(|w| w.trim().to_lowercase())
+        .unwrap_or_else(|| "tfidf".to_string());
+    if !matches!(weighting.as_str(), "count" | "binary" | "tf" | "tfidf") {
+        return Err(format!(
+            "Unknown weighting '{weighting}', use 'count', 'binary', 'tf' or 'tfidf'"
+        ));
+    }
+    let ngram_min = input.ngram_min.unwrap_or(1);
+    let ngram_max = input.ngram_max.unwrap_or(ngram_min);
+    if ngram_min == 0 || ngram_min > ngram_max || ngram_max > MAX_NGRAM {
+        return Err(format!(
+            "n-gram sizes must satisfy 1 <= ngram_min <= ngram_max <= {MAX_NGRAM}"
+        ));
+    }
+    let norm = input
+        .norm
+        .as_deref()
+        .map(|n| n.trim().to_lowercase())
+        .unwrap_or_else(|| if weighting == "tfidf" { "l2" } else { "none" }.to_string());
+    if !matches!(norm.as_str(), "l2" | "l1" | "none") {
+        return Err(format!("Unknown norm '{norm}', use 'l2', 'l1' or 'none'"));
+    }
+    let max_df = input.max_df.unwrap_or(1.0);
+    if !(0.0..=1.0).contains(&max_df) {
+        return Err("max_df must be between 0 and 1".to_string());
+    }
+
+    let mut stop_words: BTreeSet<String> = BTreeSet::new();
+    if input.english_stop_words.unwrap_or(false) {
+        stop_words.extend(ENGLISH_STOP_WORDS.split_whitespace().map(str::to_string));
+    }
+    stop_words.extend(input.stop_words.iter().flatten().map(|w| w.to_lowercase()));
+    let lowercase = input.lowercase.unwrap_or(true);
+    let mut stop_words_removed = 0;
+    let counts: Vec<HashMap<String, usize>> = input
+        .documents
+        .iter()
+        .map(|doc| {
+            let (tokens, dropped) = tokenize(doc, lowercase, &stop_words);
+            stop_words_removed += dropped;
+            ngram_counts(&tokens, ngram_min, ngram_max)
+        })
+        .collect();
+    let n = input.documents.len();
+
+    let (vocabulary, idf) = match input.vocabulary {
+        Some(vocabulary) => {
+            if input.min_df.is_some() || input.max_df.is_some() || input.max_features.is_some() {
+                return Err(
+                    "min_df, max_df and max_features only apply when building a vocabulary"
+                        .to_string(),
+                );
+            }
+            let unique: BTreeSet<&String> = vocabulary.iter().collect();
+            if unique.len() != vocabulary.len() {
+                return Err("vocabulary must not repeat terms".to_string());
+            }
+            let idf = match (weighting.as_str(), input.idf) {
+                ("tfidf", Some(idf)) if idf.len() == vocabulary.len() => {
+                    if idf.iter().any(|v| !v.is_finite()) {
+                        return Err("idf must contain finite numbers".to_string());
+                    }
+                    Some(idf)
+                }
+                ("tfidf", Some(idf)) => {
+                    return Err(format!(
+                        "Got {} idf values for {} vocabulary terms",
+                        idf.len(),
+                        vocabulary.len()
+                    ));
+                }
+                ("tfidf", None) => {
+                    return Err("tfidf with a supplied vocabulary also needs its idf".to_string());
+                }
+                (_, Some(_)) => return Err("idf only applies to tfidf".to_string()),
+                (_, None) => None,
+            };
+            (vocabulary, idf)
+        }
+        None => {
+            if input.idf.is_some() {
+                return Err("idf can only be supplied together with vocabulary".to_string());
+            }
+            // Documents containing each term, and its occurrences over all documents
+            let mut totals: BTreeMap<&String, (usize, usize)> = BTreeMap::new();
+            for doc in &counts {
+                for (term, count) in doc {
+                    let entry = totals.entry(term).or_insert((0, 0));
+                    entry.0 += 1;
+                    entry.1 += count;
+                }
+            }
+            let min_df = input.min_df.unwrap_or(1);
+            let max_documents = (max_df * n as f64).floor() as usize;
+            let mut kept: Vec<(&String, usize)> = totals
+                .into_iter()
+                .filter(|(_, (df, _))| *df >= min_df && *df <= max_documents)
+                .map(|(term, (_, total))| (term, total))
+                .collect();
+            if let Some(max_features) = input.max_features {
+                // Most frequent first; the stable sort keeps ties in term order
+                kept.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+                kept.truncate(max_features);
+                kept.sort_by(|a, b| a.0.cmp(b.0));
+            }
+            let vocabulary: Vec<String> = kept.into_iter().map(|(t, _)| t.clone()).collect();
+            (vocabulary, None)
+        }
+    };
+
+    let document_frequencies: Vec<usize> = vocabulary
+        .iter()
+        .map(|term| counts.iter().filter(|doc| doc.contains_key(term)).count())
+        .collect();
+    let idf = match idf {
+        Some(idf) => Some(idf),
+        None if weighting == "tfidf" => Some(
+            document_frequencies
+                .iter()
+                .map(|df| ((1.0 + n as f64) / (1.0 + *df as f64)).ln() + 1.0)
+                .collect(),
+        ),
+        None => None,
+    };
+
+    let rows: Vec<SparseRow> = counts
+        .iter()
+        .map(|doc| {
+            let total: usize = doc.values().sum();
+            let (indices, mut values): (Vec<usize>, Vec<f64>) = vocabulary
+                .iter()
+                .enumerate()
+                .filter_map(|(index, term)| {
+                    let count = *doc.get(term)? as f64;
+                    let value = match weighting.as_str() {
+                        "binary" => 1.0,
+                        "tf" => count / total as f64,
+                        "tfidf" => count * idf.as_ref().map_or(1.0, |idf| idf[index]),
+                        _ => count,
+                    };
+                    Some((index, value))
+                })
+                .unzip();
+            let length = match norm.as_str() {
+                "l2" => values.iter().map(|v| v * v).sum::<f64>().sqrt(),
+                "l1" => values.iter().map(|v| v.abs()).sum(),
+                _ => 1.0,
+            };
+            if length > 0.0 {
+                values.iter_mut().for_each(|v| *v /= length);
+            }
+            SparseRow { indices, values }
+        })
+        .collect();
+
+    let matrix = if input.dense.unwrap_or(false) {
+        if n.saturating_mul(vocabulary.len()) > MAX_DENSE_VALUES {
+            return Err(format!(
+                "The dense matrix would have {} values, more than {MAX_DENSE_VALUES}; use the \
+                 sparse rows, max_features or min_df",
+                n.saturating_mul(vocabulary.len())
+            ));
+        }
+        Some(
+            rows.iter()
+                .map(|row| {
+                    let mut dense = vec![0.0; vocabulary.len()];
+                    for (index, value) in row.indices.iter().zip(&row.values) {
+                        dense[*index] = *value;
+                    }
+                    dense
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    Ok(TextVectorizeOutput {
+        vocabulary,
+        document_frequencies,
+        idf,
+        rows,
+        matrix,
+        weighting,
+        norm,
+        document_count: n,
+        stop_words_removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(documents: &[&str], weighting: &str) -> TextVectorizeInput {
+        TextVectorizeInput {
+            documents: documents.iter().map(|d| d.to_string()).collect(),
+            weighting: Some(weighting.to_string()),
+            ngram_min: None,
+            ngram_max: None,
+            lowercase: None,
+            english_stop_words: None,
+            stop_words: None,
+            min_df: None,
+            max_df: None,
+            max_features: None,
+            norm: None,
+            vocabulary: None,
+            idf: None,
+            dense: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_and_vocabulary() {
+        let mut input = request(&["The cat sat. The cat!", "A dog sat"], "count");
+        input.dense = Some(true);
+        let result = text_vectorize_logic(input).unwrap();
+        // "A" is a single character and so not a token
+        assert_eq!(result.vocabulary, vec!["cat", "dog", "sat", "the"]);
+        assert_eq!(result.document_frequencies, vec![1, 1, 2, 1]);
+        assert_eq!(
+            result.rows[0],
+            SparseRow {
+                indices: vec![0, 2, 3],
+                values: vec![2.0, 1.0, 2.0],
+            }
+        );
+        assert_eq!(
+            result.matrix,
+            Some(vec![vec![2.0, 0.0, 1.0, 2.0], vec![0.0, 1.0, 1.0, 0.0]])
+        );
+        assert_eq!(result.norm, "none");
+        assert!(result.idf.is_none());
+    }
+
+    #[test]
+    fn test_tfidf_weights() {
+        let result =
+            text_vectorize_logic(request(&["apple banana", "apple cherry"], "tfidf")).unwrap();
+        // apple is in both documents: ln(3/3) + 1; the others in one: ln(3/2) + 1
+        let idf = result.idf.unwrap();
+        assert_eq!(idf[0], 1.0);
+        assert!((idf[1] - (1.5f64.ln() + 1.0)).abs() < 1e-12);
+        let row = &result.rows[0];
+        assert_eq!(row.indices, vec![0, 1]);
+        let length = (1.0 + idf[1] * idf[1]).sqrt();
+        assert!((row.values[0] - 1.0 / length).abs() < 1e-12);
+        assert!((row.values[1] - idf[1] / length).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ngrams_and_stop_words() {
+        let mut input = request(&["the quick brown fox", "the lazy dog"], "count");
+        input.english_stop_words = Some(true);
+        input.stop_words = Some(vec!["Lazy".to_string()]);
+        input.ngram_min = Some(1);
+        input.ngram_max = Some(2);
+        let result = text_vectorize_logic(input).unwrap();
+        assert_eq!(
+            result.vocabulary,
+            vec!["brown", "brown fox", "dog", "fox", "quick", "quick brown"]
+        );
+        assert_eq!(result.stop_words_removed, 3);
+    }
+
+    #[test]
+    fn test_document_frequency_filters() {
+        let documents = ["red blue", "red green", "red blue green", "red yellow"];
+        let mut input = request(&documents, "binary");
+        input.min_df = Some(2);
+        input.max_df = Some(0.9);
+        let result = text_vectorize_logic(input).unwrap();
+        assert_eq!(result.vocabulary, vec!["blue", "green"]);
+
+        let mut input = request(&documents, "count");
+        input.max_features = Some(2);
+        let result = text_vectorize_logic(input).unwrap();
+        // red (4) then blue and green tie at 2, broken in term order
+        assert_eq!(result.vocabulary, vec!["blue", "red"]);
+    }
+
+    #[test]
+    fn test_reuse_vocabulary() {
+        let fitted =
+            text_vectorize_logic(request(&["spam offer", "meeting notes"], "tfidf")).unwrap();
+        let mut input = request(&["new offer for the meeting"], "tfidf");
+        input.vocabulary = Some(fitted.vocabulary.clone());
+        input.idf = fitted.idf.clone();
+        let result = text_vectorize_logic(input).unwrap();
+        assert_eq!(result.vocabulary, fitted.vocabulary);
+        // Only "meeting" and "offer" are known; equal idf gives equal weights
+        assert_eq!(result.rows[0].indices, vec![0, 2]);
+        assert!((result.rows[0].values[0] - 0.5f64.sqrt()).abs() < 1e-12);
+
+        let mut missing_idf = request(&["offer"], "tfidf");
+        missing_idf.vocabulary = Some(fitted.vocabulary);
+        assert!(text_vectorize_logic(missing_idf).is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(text_vectorize_logic(request(&[], "count")).is_err());
+        assert!(text_vectorize_logic(request(&["text"], "bm25")).is_err());
+        let mut ngrams = request(&["text"], "count");
+        ngrams.ngram_min = Some(3);
+        ngrams.ngram_max = Some(2);
+        assert!(text_vectorize_logic(ngrams).is_err());
+        let mut idf_alone = request(&["text"], "tfidf");
+        idf_alone.idf = Some(vec![1.0]);
+        assert_eq!(
+            text_vectorize_logic(idf_alone).unwrap_err(),
+            "idf can only be supplied together with vocabulary"
+        );
+    }
+}
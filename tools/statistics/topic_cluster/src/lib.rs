@@ -0,0 +1,94 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    MAX_CENTROID_VALUES, MAX_CLUSTERS, MAX_ITERATIONS, MAX_RESTARTS,
+    TopicClusterInput as LogicInput, TopicClusterOutput as LogicOutput, topic_cluster_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    RepresentativeDocumentSchema as RepresentativeDocument,
+    TopicClusterOutputSchema as TopicClusterOutput,
+    TopicClusterSummarySchema as TopicClusterSummary, TopicTermSchema as TopicTerm,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TopicClusterInput {
+    #[serde(flatten)]
+    pub params: logic::TopicClusterInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Group documents by topic with k-means over their TF-IDF vectors, returning each
+/// cluster's size, top terms and most representative documents
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn topic_cluster(request: Request<TopicClusterInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "topic_cluster",
+                TopicClusterInput,
+                TopicClusterOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("topic_cluster", &input);
+    log.finish(run_topic_cluster(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_topic_cluster(input: TopicClusterInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    let guard = Guard::from_env()
+        .array("documents", &input.params.documents)
+        .array(
+            "stop_words",
+            input.params.stop_words.as_deref().unwrap_or_default(),
+        );
+    let guard = input
+        .params
+        .documents
+        .iter()
+        .fold(guard, |guard, text| guard.string("documents", text));
+    if let Err(e) = guard.finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::topic_cluster_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &TopicClusterOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
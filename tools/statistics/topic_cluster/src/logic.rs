@@ -0,0 +1,519 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use text_vectorize_tool::{LogicInput as VectorizeInput, text_vectorize_logic};
+
+/// Most clusters in one call
+pub const MAX_CLUSTERS: usize = 100;
+/// Most centroid values, clusters times vocabulary
+pub const MAX_CENTROID_VALUES: usize = 2_000_000;
+/// Most k-means iterations
+pub const MAX_ITERATIONS: usize = 1_000;
+/// Most k-means restarts
+pub const MAX_RESTARTS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TopicClusterInput {
+    /// Documents to group by topic
+    pub documents: Vec<String>,
+    /// Number of clusters, at most the number of documents
+    pub clusters: usize,
+    /// Highest-weighted terms listed per cluster (default: 10)
+    pub top_terms: Option<usize>,
+    /// Documents closest to each cluster's centre listed per cluster (default: 3)
+    pub representatives: Option<usize>,
+    /// Shortest n-gram, in tokens (default: 1)
+    pub ngram_min: Option<usize>,
+    /// Longest n-gram, in tokens, at most 5 (default: ngram_min)
+    pub ngram_max: Option<usize>,
+    /// Drop common English function words such as "the" and "of" (default: true)
+    pub english_stop_words: Option<bool>,
+    /// Further tokens to drop, matched after lowercasing
+    pub stop_words: Option<Vec<String>>,
+    /// Keep terms found in at least this many documents (default: 1)
+    pub min_df: Option<usize>,
+    /// Keep terms found in at most this fraction of documents, 0-1 (default: 1)
+    pub max_df: Option<f64>,
+    /// Keep only the terms with the most occurrences over all documents
+    pub max_features: Option<usize>,
+    /// Most k-means iterations, at most 1000 (default: 100)
+    pub max_iterations: Option<usize>,
+    /// Independent k-means runs, keeping the one with the lowest inertia, at most 100
+    /// (default: 10)
+    pub restarts: Option<usize>,
+    /// Seed for choosing the initial centres (default: random; the seed used is returned)
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TopicTerm {
+    pub term: String,
+    /// Mean TF-IDF weight of the term over the cluster's documents
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct RepresentativeDocument {
+    /// Index into documents
+    pub document: usize,
+    /// Cosine similarity to the cluster centre
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TopicClusterSummary {
+    /// Cluster number, as used in assignments
+    pub cluster: usize,
+    /// Documents in the cluster
+    pub size: usize,
+    /// Highest-weighted terms, heaviest first
+    #[tool_types(nested)]
+    pub top_terms: Vec<TopicTerm>,
+    /// Member documents most similar to the centre, closest first
+    #[tool_types(nested)]
+    pub representative_documents: Vec<RepresentativeDocument>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct TopicClusterOutput {
+    /// Clusters, largest first
+    #[tool_types(nested)]
+    pub clusters: Vec<TopicClusterSummary>,
+    /// Cluster of each document, in input order
+    pub assignments: Vec<usize>,
+    /// Sum of squared distances from documents to their cluster centres
+    pub inertia: f64,
+    /// Iterations of the winning run
+    pub iterations: usize,
+    /// Whether assignments stopped changing before max_iterations
+    pub converged: bool,
+    pub vocabulary_size: usize,
+    pub seed: u64,
+}
+
+/// SplitMix64, so a seed gives the same clusters on every platform
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [0, bound)
+    fn below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next_u64()) * bound as u128) >> 64) as usize
+    }
+}
+
+/// A document's TF-IDF row as (vocabulary index, weight) pairs
+type Row = Vec<(usize, f64)>;
+
+/// Dense centre with its squared length, so distances to sparse rows stay cheap
+struct Centroid {
+    values: Vec<f64>,
+    length_squared: f64,
+}
+
+impl Centroid {
+    fn from_rows<'a>(rows: impl Iterator<Item = &'a Row>, width: usize) -> Centroid {
+        let mut values = vec![0.0; width];
+        let mut count = 0;
+        for row in rows {
+            for (index, value) in row {
+                values[*index] += value;
+            }
+            count += 1;
+        }
+        if count > 0 {
+            values.iter_mut().for_each(|v| *v /= count as f64);
+        }
+        let length_squared = values.iter().map(|v| v * v).sum();
+        Centroid {
+            values,
+            length_squared,
+        }
+    }
+
+    fn dot(&self, row: &Row) -> f64 {
+        row.iter()
+            .map(|(index, value)| self.values[*index] * value)
+            .sum()
+    }
+
+    fn distance_squared(&self, row: &Row) -> f64 {
+        let row_squared: f64 = row.iter().map(|(_, v)| v * v).sum();
+        (row_squared - 2.0 * self.dot(row) + self.length_squared).max(0.0)
+    }
+}
+
+/// Nearest centre to the row, lowest index on ties, with its squared distance
+fn nearest(centroids: &[Centroid], row: &Row) -> (usize, f64) {
+    centroids
+        .iter()
+        .map(|c| c.distance_squared(row))
+        .enumerate()
+        .fold(
+            (0, f64::INFINITY),
+            |best, (i, d)| if d < best.1 { (i, d) } else { best },
+        )
+}
+
+/// k-means++ seeding: each further centre is a document drawn with probability
+/// proportional to its squared distance from the centres so far
+fn seed_centroids(rows: &[Row], k: usize, width: usize, rng: &mut SplitMix64) -> Vec<Centroid> {
+    let mut centroids = vec![Centroid::from_rows(
+        std::iter::once(&rows[rng.below(rows.len())]),
+        width,
+    )];
+    while centroids.len() < k {
+        let distances: Vec<f64> = rows.iter().map(|row| nearest(&centroids, row).1).collect();
+        let total: f64 = distances.iter().sum();
+        let pick = if total > 0.0 {
+            let mut target = rng.unit() * total;
+            distances
+                .iter()
+                .position(|d| {
+                    target -= d;
+                    target < 0.0 && *d > 0.0
+                })
+                .unwrap_or_else(|| distances.iter().rposition(|d| *d > 0.0).unwrap_or(0))
+        } else {
+            // Every document already sits on a centre
+            rng.below(rows.len())
+        };
+        centroids.push(Centroid::from_rows(std::iter::once(&rows[pick]), width));
+    }
+    centroids
+}
+
+struct KMeans {
+    assignments: Vec<usize>,
+    centroids: Vec<Centroid>,
+    inertia: f64,
+    iterations: usize,
+    converged: bool,
+}
+
+/// Lloyd's k-means from k-means++ centres, until assignments stop changing
+fn kmeans(
+    rows: &[Row],
+    k: usize,
+    width: usize,
+    max_iterations: usize,
+    rng: &mut SplitMix64,
+) -> KMeans {
+    let n = rows.len();
+    let mut centroids = seed_centroids(rows, k, width, rng);
+    let mut assignments: Vec<usize> = vec![usize::MAX; n];
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < max_iterations {
+        iterations += 1;
+        let nearest: Vec<(usize, f64)> = rows.iter().map(|row| nearest(&centroids, row)).collect();
+        let mut next: Vec<usize> = nearest.iter().map(|(cluster, _)| *cluster).collect();
+        // An emptied cluster takes over the document furthest from its own centre
+        let mut sizes = vec![0usize; k];
+        next.iter().for_each(|c| sizes[*c] += 1);
+        for cluster in 0..k {
+            if sizes[cluster] == 0 {
+                let furthest = (0..n)
+                    .filter(|i| sizes[next[*i]] > 1)
+                    .max_by(|a, b| nearest[*a].1.total_cmp(&nearest[*b].1));
+                if let Some(document) = furthest {
+                    sizes[next[document]] -= 1;
+                    sizes[cluster] = 1;
+                    next[document] = cluster;
+                }
+            }
+        }
+        if next == assignments {
+            converged = true;
+            break;
+        }
+        assignments = next;
+        centroids = (0..k)
+            .map(|cluster| {
+                Centroid::from_rows(
+                    rows.iter()
+                        .zip(&assignments)
+                        .filter(|(_, c)| **c == cluster)
+                        .map(|(row, _)| row),
+                    width,
+                )
+            })
+            .collect();
+    }
+
+    let inertia = rows
+        .iter()
+        .zip(&assignments)
+        .map(|(row, c)| centroids[*c].distance_squared(row))
+        .sum();
+    KMeans {
+        assignments,
+        centroids,
+        inertia,
+        iterations,
+        converged,
+    }
+}
+
+pub fn topic_cluster_logic(input: TopicClusterInput) -> Result<TopicClusterOutput, String> {
+    let n = input.documents.len();
+    let k = input.clusters;
+    if k == 0 || k > MAX_CLUSTERS {
+        return Err(format!("clusters must be between 1 and {MAX_CLUSTERS}"));
+    }
+    if k > n {
+        return Err(format!(
+            "clusters ({k}) cannot exceed the number of documents ({n})"
+        ));
+    }
+    let max_iterations = input.max_iterations.unwrap_or(100);
+    if max_iterations == 0 || max_iterations > MAX_ITERATIONS {
+        return Err(format!(
+            "max_iterations must be between 1 and {MAX_ITERATIONS}"
+        ));
+    }
+    let top_terms = input.top_terms.unwrap_or(10);
+    let representatives = input.representatives.unwrap_or(3);
+    let restarts = input.restarts.unwrap_or(10);
+    if restarts == 0 || restarts > MAX_RESTARTS {
+        return Err(format!("restarts must be between 1 and {MAX_RESTARTS}"));
+    }
+
+    let vectors = text_vectorize_logic(VectorizeInput {
+        documents: input.documents,
+        weighting: Some("tfidf".to_string()),
+        ngram_min: input.ngram_min,
+        ngram_max: input.ngram_max,
+        lowercase: None,
+        english_stop_words: Some(input.english_stop_words.unwrap_or(true)),
+        stop_words: input.stop_words,
+        min_df: input.min_df,
+        max_df: input.max_df,
+        max_features: input.max_features,
+        norm: Some("l2".to_string()),
+        vocabulary: None,
+        idf: None,
+        dense: None,
+    })?;
+    let width = vectors.vocabulary.len();
+    if width == 0 {
+        return Err(
+            "No terms are left to cluster on; loosen min_df, max_df or the stop words".to_string(),
+        );
+    }
+    if k.saturating_mul(width) > MAX_CENTROID_VALUES {
+        return Err(format!(
+            "{k} clusters over {width} terms is more than {MAX_CENTROID_VALUES} centroid \
+             values; use max_features or min_df"
+        ));
+    }
+    let rows: Vec<Row> = vectors
+        .rows
+        .iter()
+        .map(|row| {
+            row.indices
+                .iter()
+                .copied()
+                .zip(row.values.iter().copied())
+                .collect()
+        })
+        .collect();
+
+    let seed = input.seed.unwrap_or_else(rand::random);
+    let mut rng = SplitMix64(seed);
+    // Each restart starts from fresh centres; the tightest clustering wins
+    let mut best = kmeans(&rows, k, width, max_iterations, &mut rng);
+    for _ in 1..restarts {
+        let run = kmeans(&rows, k, width, max_iterations, &mut rng);
+        if run.inertia < best.inertia {
+            best = run;
+        }
+    }
+    let KMeans {
+        assignments,
+        centroids,
+        inertia,
+        iterations,
+        converged,
+    } = best;
+
+    // Number clusters largest first, then by their first document
+    let mut order: Vec<usize> = (0..k).collect();
+    let size = |cluster: usize| assignments.iter().filter(|c| **c == cluster).count();
+    let first = |cluster: usize| assignments.iter().position(|c| *c == cluster);
+    order.sort_by_key(|c| (std::cmp::Reverse(size(*c)), first(*c)));
+    let mut renumber = vec![0; k];
+    for (new, old) in order.iter().enumerate() {
+        renumber[*old] = new;
+    }
+
+    let clusters = order
+        .iter()
+        .enumerate()
+        .map(|(number, old)| {
+            let centroid = &centroids[*old];
+            let mut terms: Vec<(usize, f64)> = centroid
+                .values
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|(_, w)| *w > 0.0)
+                .collect();
+            // Heaviest first; the stable sort keeps ties in vocabulary order
+            terms.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let length = centroid.length_squared.sqrt();
+            let mut members: Vec<RepresentativeDocument> = (0..n)
+                .filter(|i| assignments[*i] == *old)
+                .map(|document| RepresentativeDocument {
+                    document,
+                    similarity: if length > 0.0 {
+                        centroid.dot(&rows[document]) / length
+                    } else {
+                        0.0
+                    },
+                })
+                .collect();
+            let size = members.len();
+            members.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+            members.truncate(representatives);
+            TopicClusterSummary {
+                cluster: number,
+                size,
+                top_terms: terms
+                    .into_iter()
+                    .take(top_terms)
+                    .map(|(index, weight)| TopicTerm {
+                        term: vectors.vocabulary[index].clone(),
+                        weight,
+                    })
+                    .collect(),
+                representative_documents: members,
+            }
+        })
+        .collect();
+
+    Ok(TopicClusterOutput {
+        clusters,
+        assignments: assignments.iter().map(|c| renumber[*c]).collect(),
+        inertia,
+        iterations,
+        converged,
+        vocabulary_size: width,
+        seed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(documents: &[&str], clusters: usize) -> TopicClusterInput {
+        TopicClusterInput {
+            documents: documents.iter().map(|d| d.to_string()).collect(),
+            clusters,
+            top_terms: Some(3),
+            representatives: None,
+            ngram_min: None,
+            ngram_max: None,
+            english_stop_words: None,
+            stop_words: None,
+            min_df: None,
+            max_df: None,
+            max_features: None,
+            max_iterations: None,
+            restarts: None,
+            seed: Some(7),
+        }
+    }
+
+    const DOCUMENTS: [&str; 6] = [
+        "the cat chased the mouse",
+        "a cat and a kitten sleep",
+        "the kitten is a young cat",
+        "stocks fell as the market dropped",
+        "the market rallied and stocks rose",
+        "investors sold stocks in the market",
+    ];
+
+    #[test]
+    fn test_separates_topics() {
+        let result = topic_cluster_logic(input(&DOCUMENTS, 2)).unwrap();
+        assert!(result.converged);
+        let a = result.assignments[0];
+        assert_eq!(&result.assignments[..3], &[a, a, a]);
+        assert!(result.assignments[3..].iter().all(|c| *c != a));
+
+        let cats = &result.clusters[a];
+        assert_eq!(cats.size, 3);
+        assert_eq!(cats.top_terms[0].term, "cat");
+        let markets = &result.clusters[1 - a];
+        let terms: Vec<&str> = markets.top_terms.iter().map(|t| t.term.as_str()).collect();
+        assert!(terms.contains(&"stocks") && terms.contains(&"market"));
+        // Stop words are dropped by default
+        assert!(cats.top_terms.iter().all(|t| t.term != "the"));
+    }
+
+    #[test]
+    fn test_representatives_and_ordering() {
+        let mut documents = DOCUMENTS.to_vec();
+        documents.push("my cat naps while the kitten plays");
+        let mut request = input(&documents, 2);
+        request.representatives = Some(2);
+        let result = topic_cluster_logic(request).unwrap();
+        assert_eq!(result.clusters[0].size, 4);
+        assert_eq!(result.clusters[0].cluster, 0);
+        let reps = &result.clusters[0].representative_documents;
+        assert_eq!(reps.len(), 2);
+        assert!(reps[0].similarity >= reps[1].similarity);
+        assert!(reps.iter().all(|r| result.assignments[r.document] == 0));
+    }
+
+    #[test]
+    fn test_seed_reproducible() {
+        let first = topic_cluster_logic(input(&DOCUMENTS, 3)).unwrap();
+        let second = topic_cluster_logic(input(&DOCUMENTS, 3)).unwrap();
+        assert_eq!(first.assignments, second.assignments);
+        assert_eq!(first.seed, 7);
+        assert!(first.clusters.iter().all(|c| c.size > 0));
+    }
+
+    #[test]
+    fn test_one_cluster_per_document() {
+        let result = topic_cluster_logic(input(&DOCUMENTS, 6)).unwrap();
+        let mut assignments = result.assignments.clone();
+        assignments.sort();
+        assert_eq!(assignments, vec![0, 1, 2, 3, 4, 5]);
+        assert!(result.inertia.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(
+            topic_cluster_logic(input(&DOCUMENTS[..2], 3)).unwrap_err(),
+            "clusters (3) cannot exceed the number of documents (2)"
+        );
+        assert!(topic_cluster_logic(input(&DOCUMENTS, 0)).is_err());
+        assert!(
+            topic_cluster_logic(input(&["the and of", "it is"], 1))
+                .unwrap_err()
+                .starts_with("No terms")
+        );
+        let mut request = input(&DOCUMENTS, 2);
+        request.max_df = Some(2.0);
+        assert_eq!(
+            topic_cluster_logic(request).unwrap_err(),
+            "max_df must be between 0 and 1"
+        );
+    }
+}
@@ -3,13 +3,22 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{StringCaseConverterInput as LogicInput, StringCaseConverterOutput as LogicOutput};
+pub use logic::{
+    StringCaseConverterInput as LogicInput, StringCaseConverterOutput as LogicOutput, convert_case,
+};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -20,6 +29,8 @@ pub struct StringCaseConverterInput {
     /// Options: "lower", "upper", "title", "sentence", "camelCase", "PascalCase",
     /// "snake_case", "SCREAMING_SNAKE_CASE", "kebab-case", "SCREAMING-KEBAB-CASE"
     pub target_case: String,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -34,8 +45,32 @@ pub struct StringCaseConverterOutput {
     pub changed: bool,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn string_case_converter(input: StringCaseConverterInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn string_case_converter(request: Request<StringCaseConverterInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "string_case_converter",
+                StringCaseConverterInput,
+                StringCaseConverterOutput
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("string_case_converter", &input);
+    log.finish(run_string_case_converter(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_string_case_converter(input: StringCaseConverterInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("text", &input.text).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         text: input.text,
@@ -57,7 +92,7 @@ pub fn string_case_converter(input: StringCaseConverterInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
@@ -3,13 +3,20 @@ use serde::{Deserialize, Serialize};
 
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{StringSplitInput as LogicInput, StringSplitResult as LogicResult};
+pub use logic::{StringSplitInput as LogicInput, StringSplitResult as LogicResult, split_string};
 
 // Define wrapper types with JsonSchema for FTL-SDK
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -40,6 +47,8 @@ pub struct StringSplitInput {
     /// Case sensitivity (for string split_type)
     #[serde(default)]
     pub case_sensitive: Option<bool>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 fn default_delimiter() -> String {
@@ -64,8 +73,32 @@ pub struct StringSplitResult {
     pub split_type: String,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn string_splitter(input: StringSplitInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn string_splitter(request: Request<StringSplitInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "string_splitter",
+                StringSplitInput,
+                StringSplitResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("string_splitter", &input);
+    log.finish(run_string_splitter(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_string_splitter(input: StringSplitInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("text", &input.text).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         text: input.text,
@@ -93,7 +126,7 @@ pub fn string_splitter(input: StringSplitInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
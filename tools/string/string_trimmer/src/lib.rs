@@ -1,12 +1,19 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 mod logic;
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
-pub use logic::{StringTrimInput as LogicInput, StringTrimResult as LogicResult};
+pub use logic::{StringTrimInput as LogicInput, StringTrimResult as LogicResult, process_string};
 
 // Define wrapper types with JsonSchema for FTL-SDK (duplicating logic types but with JsonSchema)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -34,6 +41,8 @@ pub struct StringTrimInput {
     /// Side to pad (for pad operation): left, right (default)
     #[serde(default = "default_pad_side")]
     pub pad_side: String,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
 fn default_operation() -> String {
@@ -62,8 +71,32 @@ pub struct StringTrimResult {
     pub length_after: usize,
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn string_trimmer(input: StringTrimInput) -> ToolResponse {
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn string_trimmer(request: Request<StringTrimInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "string_trimmer",
+                StringTrimInput,
+                StringTrimResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("string_trimmer", &input);
+    log.finish(run_string_trimmer(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_string_trimmer(input: StringTrimInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("text", &input.text).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
     // Convert to logic types
     let logic_input = LogicInput {
         text: input.text,
@@ -90,7 +123,7 @@ pub fn string_trimmer(input: StringTrimInput) -> ToolResponse {
     };
 
     ToolResponse::text(
-        serde_json::to_string_pretty(&output)
+        ftl_json::to_string_pretty(&output)
             .unwrap_or_else(|_| "Error serializing output".to_string()),
     )
 }
@@ -0,0 +1,78 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    AddressComponents as LogicComponents, AddressParseInput as LogicInput,
+    AddressParseResult as LogicOutput, COUNTRIES, CountryRules, find_country, format_postal_code,
+    parse_address,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    AddressComponentsSchema as AddressComponents, AddressParseResultSchema as AddressParseResult,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AddressParseInput {
+    #[serde(flatten)]
+    pub params: logic::AddressParseInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Split a free-form postal address into house number, street, unit, city, region,
+/// postal code and country, and normalize it to a single line
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn address_parse(request: Request<AddressParseInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "address_parse",
+                AddressParseInput,
+                AddressParseResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("address_parse", &input);
+    log.finish(run_address_parse(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_address_parse(input: AddressParseInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("address", &input.params.address)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::parse_address(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&AddressParseResult::from(result))
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
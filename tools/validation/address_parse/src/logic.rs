@@ -0,0 +1,926 @@
+use ftl_tool_types_derive::ToolTypes;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Longest address accepted, in bytes
+pub const MAX_ADDRESS_BYTES: usize = 1024;
+
+/// Address conventions of one country
+pub struct CountryRules {
+    /// ISO 3166-1 alpha-2 code
+    pub code: &'static str,
+    pub name: &'static str,
+    /// Names the country goes by in addresses, lowercase
+    aliases: &'static [&'static str],
+    /// Postal code pattern, matched case-insensitively
+    pub postal_pattern: &'static str,
+    /// House number before the street ("221B Baker Street") rather than after
+    /// ("Hauptstraße 5")
+    number_first: bool,
+    /// Postal code before the city ("10115 Berlin") rather than after it
+    /// ("London SW1A 1AA")
+    postal_first: bool,
+    /// Two-letter province code after the city ("00184 Roma RM")
+    province_after_city: bool,
+    /// State or province abbreviations with their names
+    pub regions: &'static [(&'static str, &'static str)],
+}
+
+const US_STATES: &[(&str, &str)] = &[
+    ("AL", "Alabama"),
+    ("AK", "Alaska"),
+    ("AZ", "Arizona"),
+    ("AR", "Arkansas"),
+    ("CA", "California"),
+    ("CO", "Colorado"),
+    ("CT", "Connecticut"),
+    ("DE", "Delaware"),
+    ("DC", "District of Columbia"),
+    ("FL", "Florida"),
+    ("GA", "Georgia"),
+    ("HI", "Hawaii"),
+    ("ID", "Idaho"),
+    ("IL", "Illinois"),
+    ("IN", "Indiana"),
+    ("IA", "Iowa"),
+    ("KS", "Kansas"),
+    ("KY", "Kentucky"),
+    ("LA", "Louisiana"),
+    ("ME", "Maine"),
+    ("MD", "Maryland"),
+    ("MA", "Massachusetts"),
+    ("MI", "Michigan"),
+    ("MN", "Minnesota"),
+    ("MS", "Mississippi"),
+    ("MO", "Missouri"),
+    ("MT", "Montana"),
+    ("NE", "Nebraska"),
+    ("NV", "Nevada"),
+    ("NH", "New Hampshire"),
+    ("NJ", "New Jersey"),
+    ("NM", "New Mexico"),
+    ("NY", "New York"),
+    ("NC", "North Carolina"),
+    ("ND", "North Dakota"),
+    ("OH", "Ohio"),
+    ("OK", "Oklahoma"),
+    ("OR", "Oregon"),
+    ("PA", "Pennsylvania"),
+    ("PR", "Puerto Rico"),
+    ("RI", "Rhode Island"),
+    ("SC", "South Carolina"),
+    ("SD", "South Dakota"),
+    ("TN", "Tennessee"),
+    ("TX", "Texas"),
+    ("UT", "Utah"),
+    ("VT", "Vermont"),
+    ("VA", "Virginia"),
+    ("WA", "Washington"),
+    ("WV", "West Virginia"),
+    ("WI", "Wisconsin"),
+    ("WY", "Wyoming"),
+];
+
+const CA_PROVINCES: &[(&str, &str)] = &[
+    ("AB", "Alberta"),
+    ("BC", "British Columbia"),
+    ("MB", "Manitoba"),
+    ("NB", "New Brunswick"),
+    ("NL", "Newfoundland and Labrador"),
+    ("NS", "Nova Scotia"),
+    ("NT", "Northwest Territories"),
+    ("NU", "Nunavut"),
+    ("ON", "Ontario"),
+    ("PE", "Prince Edward Island"),
+    ("QC", "Quebec"),
+    ("SK", "Saskatchewan"),
+    ("YT", "Yukon"),
+];
+
+const AU_STATES: &[(&str, &str)] = &[
+    ("ACT", "Australian Capital Territory"),
+    ("NSW", "New South Wales"),
+    ("NT", "Northern Territory"),
+    ("QLD", "Queensland"),
+    ("SA", "South Australia"),
+    ("TAS", "Tasmania"),
+    ("VIC", "Victoria"),
+    ("WA", "Western Australia"),
+];
+
+/// Countries with address rules
+pub const COUNTRIES: &[CountryRules] = &[
+    CountryRules {
+        code: "US",
+        name: "United States",
+        aliases: &[
+            "usa",
+            "united states",
+            "united states of america",
+            "america",
+        ],
+        postal_pattern: r"(\d{5})(?:-(\d{4}))?",
+        number_first: true,
+        postal_first: false,
+        province_after_city: false,
+        regions: US_STATES,
+    },
+    CountryRules {
+        code: "CA",
+        name: "Canada",
+        aliases: &["canada"],
+        postal_pattern: r"([ABCEGHJ-NPRSTVXY]\d[ABCEGHJ-NPRSTV-Z]) ?(\d[ABCEGHJ-NPRSTV-Z]\d)",
+        number_first: true,
+        postal_first: false,
+        province_after_city: false,
+        regions: CA_PROVINCES,
+    },
+    CountryRules {
+        code: "GB",
+        name: "United Kingdom",
+        aliases: &[
+            "uk",
+            "united kingdom",
+            "great britain",
+            "england",
+            "scotland",
+            "wales",
+            "northern ireland",
+        ],
+        postal_pattern: r"([A-Z]{1,2}\d[A-Z\d]?) ?(\d[A-Z]{2})",
+        number_first: true,
+        postal_first: false,
+        province_after_city: false,
+        regions: &[],
+    },
+    CountryRules {
+        code: "AU",
+        name: "Australia",
+        aliases: &["australia"],
+        postal_pattern: r"(\d{4})",
+        number_first: true,
+        postal_first: false,
+        province_after_city: false,
+        regions: AU_STATES,
+    },
+    CountryRules {
+        code: "DE",
+        name: "Germany",
+        aliases: &["germany", "deutschland"],
+        postal_pattern: r"(\d{5})",
+        number_first: false,
+        postal_first: true,
+        province_after_city: false,
+        regions: &[],
+    },
+    CountryRules {
+        code: "FR",
+        name: "France",
+        aliases: &["france"],
+        postal_pattern: r"(\d{5})",
+        number_first: true,
+        postal_first: true,
+        province_after_city: false,
+        regions: &[],
+    },
+    CountryRules {
+        code: "NL",
+        name: "Netherlands",
+        aliases: &["netherlands", "the netherlands", "nederland", "holland"],
+        postal_pattern: r"(\d{4}) ?([A-Z]{2})",
+        number_first: false,
+        postal_first: true,
+        province_after_city: false,
+        regions: &[],
+    },
+    CountryRules {
+        code: "ES",
+        name: "Spain",
+        aliases: &["spain", "españa", "espana"],
+        postal_pattern: r"(\d{5})",
+        number_first: false,
+        postal_first: true,
+        province_after_city: false,
+        regions: &[],
+    },
+    CountryRules {
+        code: "IT",
+        name: "Italy",
+        aliases: &["italy", "italia"],
+        postal_pattern: r"(\d{5})",
+        number_first: false,
+        postal_first: true,
+        province_after_city: true,
+        regions: &[],
+    },
+];
+
+/// Street types that end a street name, for telling the street from the city when an
+/// address has no commas
+const STREET_TYPES: &[&str] = &[
+    "street",
+    "st",
+    "avenue",
+    "ave",
+    "av",
+    "road",
+    "rd",
+    "boulevard",
+    "blvd",
+    "drive",
+    "dr",
+    "lane",
+    "ln",
+    "court",
+    "ct",
+    "place",
+    "pl",
+    "terrace",
+    "ter",
+    "parkway",
+    "pkwy",
+    "highway",
+    "hwy",
+    "circle",
+    "cir",
+    "square",
+    "sq",
+    "trail",
+    "trl",
+    "way",
+    "crescent",
+    "cres",
+    "close",
+    "grove",
+    "gardens",
+    "row",
+    "mews",
+    "hill",
+];
+
+/// USPS abbreviations used in normalized United States addresses
+const US_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("street", "St"),
+    ("avenue", "Ave"),
+    ("road", "Rd"),
+    ("boulevard", "Blvd"),
+    ("drive", "Dr"),
+    ("lane", "Ln"),
+    ("court", "Ct"),
+    ("place", "Pl"),
+    ("terrace", "Ter"),
+    ("parkway", "Pkwy"),
+    ("highway", "Hwy"),
+    ("circle", "Cir"),
+    ("square", "Sq"),
+    ("trail", "Trl"),
+    ("north", "N"),
+    ("south", "S"),
+    ("east", "E"),
+    ("west", "W"),
+    ("northeast", "NE"),
+    ("northwest", "NW"),
+    ("southeast", "SE"),
+    ("southwest", "SW"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct AddressParseInput {
+    /// Free-form postal address, with parts separated by commas or newlines
+    pub address: String,
+    /// Country as an ISO code or name, used when the address doesn't name one
+    /// (default: detected from the country name or postal code format)
+    pub country: Option<String>,
+    /// Whether to end the normalized line with the country name (default: true)
+    pub include_country: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct AddressComponents {
+    pub house_number: Option<String>,
+    pub street: Option<String>,
+    /// Apartment, suite, flat or similar, normalized like "Apt 4B"
+    pub unit: Option<String>,
+    pub city: Option<String>,
+    /// State or province abbreviation
+    pub region: Option<String>,
+    /// Postal code in the country's standard format
+    pub postal_code: Option<String>,
+    /// ISO 3166-1 alpha-2 code
+    pub country: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct AddressParseResult {
+    #[tool_types(nested)]
+    pub components: AddressComponents,
+    pub country_name: Option<String>,
+    /// How the country was found: "hint", "name", "postal_code" or "none"
+    pub country_source: String,
+    /// Single-line address in the country's usual order
+    pub normalized: String,
+    /// Expected components that were not found
+    pub missing: Vec<String>,
+    /// Address parts that fit no component
+    pub unparsed: Vec<String>,
+}
+
+/// Looks up a supported country by ISO code or name, ignoring case and dots
+pub fn find_country(name: &str) -> Option<&'static CountryRules> {
+    let key = name.trim().replace('.', "").to_lowercase();
+    COUNTRIES
+        .iter()
+        .find(|c| c.code.eq_ignore_ascii_case(&key) || c.aliases.contains(&key.as_str()))
+}
+
+/// The postal code in the country's standard spacing and case, or None when it doesn't
+/// match the country's format
+pub fn format_postal_code(rules: &CountryRules, code: &str) -> Option<String> {
+    let full = Regex::new(&format!(r"(?i)^\s*(?:{})\s*$", rules.postal_pattern)).unwrap();
+    let captures = full.captures(code)?;
+    let first = captures[1].to_uppercase();
+    Some(match (rules.code, captures.get(2)) {
+        ("US", Some(plus4)) => format!("{first}-{}", plus4.as_str()),
+        (_, Some(second)) => format!("{first} {}", second.as_str().to_uppercase()),
+        (_, None) => first,
+    })
+}
+
+fn supported_codes() -> String {
+    COUNTRIES
+        .iter()
+        .map(|c| c.code)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Splits a trailing state or province off the text, preferring the longest name
+fn split_region(text: &str, rules: Option<&CountryRules>) -> (String, Option<String>) {
+    let text = text.trim().trim_end_matches([',', '.']).trim();
+    let lower = text.to_lowercase();
+    let regions = rules.map_or(&[][..], |r| r.regions);
+    let found = regions
+        .iter()
+        .flat_map(|(code, name)| [(code, code.to_lowercase()), (code, name.to_lowercase())])
+        .filter(|(_, key)| {
+            lower.ends_with(key.as_str())
+                && lower[..lower.len() - key.len()]
+                    .chars()
+                    .next_back()
+                    .is_none_or(|c| c == ' ' || c == ',')
+        })
+        .max_by_key(|(_, key)| key.len());
+    match found {
+        Some((code, key)) => {
+            let rest = text[..text.len() - key.len()].trim().trim_end_matches(',');
+            (rest.trim().to_string(), Some(code.to_string()))
+        }
+        None => (text.to_string(), None),
+    }
+}
+
+/// Splits a country name off the end of "Berlin 10117 Germany", only when the postal
+/// code in front of it has that country's format
+fn split_country(segment: &str) -> Option<(String, &'static CountryRules)> {
+    let lower = segment.to_lowercase();
+    COUNTRIES.iter().find_map(|rules| {
+        let alias = rules.aliases.iter().find(|alias| {
+            lower.len() > alias.len()
+                && lower.ends_with(*alias)
+                && lower[..lower.len() - alias.len()].ends_with(' ')
+        })?;
+        let rest = segment[..segment.len() - alias.len()].trim_end();
+        let postal = Regex::new(&format!(r"(?i)\b(?:{})$", rules.postal_pattern)).unwrap();
+        postal.is_match(rest).then(|| (rest.to_string(), rules))
+    })
+}
+
+/// Guesses the country from postal code formats that are unique to it, or from a state
+/// or province in front of the postal code
+fn infer_country(segments: &[String]) -> Option<&'static CountryRules> {
+    let searchable = &segments[usize::from(segments.len() > 1)..];
+    for code in ["CA", "NL", "GB"] {
+        let rules = find_country(code)?;
+        let re = Regex::new(&format!(r"(?i)\b(?:{})\b", rules.postal_pattern)).unwrap();
+        if searchable.iter().any(|s| re.is_match(s)) {
+            return Some(rules);
+        }
+    }
+    for code in ["US", "AU"] {
+        let rules = find_country(code)?;
+        let re = Regex::new(&format!(r"(?i)\b(?:{})\b", rules.postal_pattern)).unwrap();
+        for segment in searchable.iter().rev() {
+            if let Some(m) = re.find_iter(segment).last() {
+                if split_region(&segment[..m.start()], Some(rules)).1.is_some() {
+                    return Some(rules);
+                }
+                break;
+            }
+        }
+    }
+    None
+}
+
+/// Pulls an apartment, suite or similar designator out of a street line
+fn take_unit(line: &str) -> (String, Option<String>) {
+    let re = Regex::new(
+        r"(?i)(?:^|\s)(?:(apartment|apt|suite|ste|unit|flat|floor|fl|room|rm|building|bldg)\b\.?\s*#?\s*|(#)\s*)(\d[A-Z0-9-]*|[A-Z]\d[A-Z0-9-]*|[A-Z])\b",
+    )
+    .unwrap();
+    let Some(captures) = re.captures(line) else {
+        return (line.to_string(), None);
+    };
+    let designator = captures
+        .get(1)
+        .or(captures.get(2))
+        .map_or("", |m| m.as_str());
+    let label = match designator.to_lowercase().as_str() {
+        "apartment" | "apt" => "Apt",
+        "suite" | "ste" => "Suite",
+        "unit" => "Unit",
+        "flat" => "Flat",
+        "floor" | "fl" => "Floor",
+        "room" | "rm" => "Room",
+        "building" | "bldg" => "Bldg",
+        _ => "#",
+    };
+    let value = captures[3].to_uppercase();
+    let unit = if label == "#" {
+        format!("#{value}")
+    } else {
+        format!("{label} {value}")
+    };
+    let whole = captures.get(0).unwrap();
+    let rest = format!("{} {}", &line[..whole.start()], &line[whole.end()..]);
+    (
+        collapse_whitespace(&rest)
+            .trim_matches(',')
+            .trim()
+            .to_string(),
+        Some(unit),
+    )
+}
+
+/// Splits a street line into house number and street name
+fn split_house_number(line: &str, number_first: bool) -> (Option<String>, String) {
+    let number = r"\d+[A-Za-z]?(?:[-/]\d+[A-Za-z]?)?";
+    let leading = Regex::new(&format!(r"^({number}),?\s+(.+)$")).unwrap();
+    let trailing = Regex::new(&format!(r"^(.+?),?\s+({number})$")).unwrap();
+    let from_leading = || {
+        leading
+            .captures(line)
+            .map(|c| (c[1].to_string(), c[2].to_string()))
+    };
+    let from_trailing = || {
+        trailing
+            .captures(line)
+            .map(|c| (c[2].to_string(), c[1].to_string()))
+    };
+    let split = if number_first {
+        from_leading().or_else(from_trailing)
+    } else {
+        from_trailing().or_else(from_leading)
+    };
+    match split {
+        Some((house, street)) => (Some(house), street),
+        None => (None, line.to_string()),
+    }
+}
+
+/// For an address without commas, splits "123 Main St Springfield" after the last
+/// street type into the street line and the city
+fn split_street_and_city(text: &str) -> (String, Option<String>) {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let last_type = words.iter().rposition(|w| {
+        STREET_TYPES.contains(&w.trim_end_matches(['.', ',']).to_lowercase().as_str())
+    });
+    match last_type {
+        Some(i) if i + 1 < words.len() && i > 0 => (
+            words[..=i].join(" ").trim_end_matches(',').to_string(),
+            Some(words[i + 1..].join(" ")),
+        ),
+        _ => (text.to_string(), None),
+    }
+}
+
+fn abbreviate_us_street(street: &str) -> String {
+    street
+        .split_whitespace()
+        .map(|word| {
+            let key = word.trim_end_matches('.').to_lowercase();
+            US_ABBREVIATIONS
+                .iter()
+                .find(|(full, _)| *full == key)
+                .map_or(word.to_string(), |(_, short)| short.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn parse_address(input: AddressParseInput) -> Result<AddressParseResult, String> {
+    let text = input.address.trim();
+    if text.is_empty() {
+        return Err("Address cannot be empty".to_string());
+    }
+    if text.len() > MAX_ADDRESS_BYTES {
+        return Err(format!("Address is longer than {MAX_ADDRESS_BYTES} bytes"));
+    }
+    let mut segments: Vec<String> = text
+        .split([',', '\n', ';'])
+        .map(collapse_whitespace)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let hinted = match input.country.as_deref() {
+        Some(name) => Some(find_country(name).ok_or_else(|| {
+            format!(
+                "Unsupported country '{name}', use one of {}",
+                supported_codes()
+            )
+        })?),
+        None => None,
+    };
+    let mut named = if segments.len() > 1 {
+        segments.last().and_then(|s| find_country(s))
+    } else {
+        None
+    };
+    if named.is_some() {
+        segments.pop();
+    } else if let Some((rest, rules)) = segments.last().and_then(|s| split_country(s)) {
+        named = Some(rules);
+        *segments.last_mut().unwrap() = rest;
+    }
+    let (rules, country_source) = match (hinted, named) {
+        (Some(rules), _) => (Some(rules), "hint"),
+        (None, Some(rules)) => (Some(rules), "name"),
+        (None, None) => match infer_country(&segments) {
+            Some(rules) => (Some(rules), "postal_code"),
+            None => (None, "none"),
+        },
+    };
+
+    let n = segments.len();
+    let mut used = vec![false; n];
+    let mut unparsed: Vec<String> = Vec::new();
+    let (mut city, mut region, mut postal_code) = (None, None, None);
+    // Street text when the street shares a part with the locality
+    let mut street_text: Option<String> = None;
+    // The text after the street type when that text is the city
+    let mut split_city = false;
+
+    let pattern = rules.map_or(r"\d{4,6}", |r| r.postal_pattern);
+    let postal_re = Regex::new(&format!(r"(?i)\b(?:{pattern})\b")).unwrap();
+    // Skip the first part when there are several: numbers there are house numbers
+    let postal = (usize::from(n > 1)..n).rev().find_map(|i| {
+        postal_re
+            .find_iter(&segments[i])
+            .last()
+            .map(|m| (i, m.start(), m.end()))
+    });
+
+    if let Some((i, start, end)) = postal {
+        used[i] = true;
+        let raw = &segments[i][start..end];
+        postal_code = Some(rules.map_or_else(
+            || raw.to_uppercase(),
+            |r| format_postal_code(r, raw).unwrap_or_else(|| raw.to_uppercase()),
+        ));
+        let before = segments[i][..start].trim().to_string();
+        let after = segments[i][end..].trim().to_string();
+        let postal_first = rules.map_or(!after.is_empty(), |r| r.postal_first);
+        if postal_first {
+            // "10115 Berlin": the city follows the postal code
+            let mut locality = after;
+            if locality.is_empty() && i + 1 < n {
+                locality = segments[i + 1].clone();
+                used[i + 1] = true;
+            }
+            if rules.is_some_and(|r| r.province_after_city) {
+                let province = Regex::new(r"^(.+?)\s+\(?([A-Z]{2})\)?$").unwrap();
+                if let Some(c) = province.captures(&locality.clone()) {
+                    locality = c[1].to_string();
+                    region = Some(c[2].to_string());
+                }
+            }
+            city = Some(locality).filter(|c| !c.is_empty());
+            if !before.is_empty() {
+                if i == 0 {
+                    street_text = Some(before);
+                } else {
+                    unparsed.push(before);
+                }
+            }
+        } else {
+            // "Springfield, IL 62704" or "London SW1A 1AA": the city, then any region,
+            // come before the postal code
+            let (rest, found) = split_region(&before, rules);
+            region = found;
+            if !after.is_empty() {
+                unparsed.push(after);
+            }
+            if i == 0 {
+                street_text = Some(rest);
+                split_city = true;
+            } else if !rest.is_empty() {
+                city = Some(rest);
+            } else if i >= 2 {
+                city = Some(segments[i - 1].clone());
+                used[i - 1] = true;
+            }
+        }
+    } else if n > 1 {
+        // Without a postal code the last part is the locality, with any region
+        let (rest, found) = split_region(&segments[n - 1], rules);
+        region = found;
+        used[n - 1] = true;
+        if !rest.is_empty() {
+            city = Some(rest);
+        } else if n >= 3 {
+            city = Some(segments[n - 2].clone());
+            used[n - 2] = true;
+        }
+    }
+
+    let mut unit = None;
+    let mut street_line = street_text;
+    for (i, segment) in segments.iter().enumerate() {
+        if used[i] {
+            continue;
+        }
+        let (rest, found) = take_unit(segment);
+        if found.is_some() && unit.is_none() && (rest.is_empty() || street_line.is_some()) {
+            unit = found;
+            if !rest.is_empty() {
+                unparsed.push(rest);
+            }
+        } else if street_line.is_none() {
+            street_line = Some(segment.clone());
+        } else {
+            unparsed.push(segment.clone());
+        }
+    }
+
+    let (mut house_number, mut street) = (None, None);
+    if let Some(line) = street_line.filter(|l| !l.is_empty()) {
+        let (mut line, found) = take_unit(&line);
+        if unit.is_none() {
+            unit = found;
+        }
+        if split_city && city.is_none() {
+            let (street_part, city_part) = split_street_and_city(&line);
+            line = street_part;
+            city = city_part;
+        }
+        // Australian "2/45 Smith Street": unit 2 of number 45
+        let slash = Regex::new(r"^(\w+)/(\d+\w?)\s+(.+)$").unwrap();
+        let captures = if rules.is_some_and(|r| r.code == "AU") && unit.is_none() {
+            slash.captures(&line)
+        } else {
+            None
+        };
+        if let Some(c) = captures {
+            unit = Some(format!("Unit {}", &c[1]));
+            house_number = Some(c[2].to_string());
+            street = Some(c[3].to_string());
+        } else {
+            let (house, name) = split_house_number(&line, rules.is_none_or(|r| r.number_first));
+            house_number = house;
+            street = Some(name).filter(|s| !s.is_empty());
+        }
+    }
+
+    // Single line in the country's order
+    let number_first = rules.is_none_or(|r| r.number_first);
+    let street_name = match (rules.map(|r| r.code), &street) {
+        (Some("US"), Some(s)) => Some(abbreviate_us_street(s)),
+        (_, s) => s.clone(),
+    };
+    let street_parts = if number_first {
+        [house_number.clone(), street_name]
+    } else {
+        [street_name, house_number.clone()]
+    };
+    let street_part = street_parts
+        .into_iter()
+        .chain([unit.clone()])
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let locality = match rules.map(|r| r.code) {
+        Some("US") | Some("CA") => {
+            let region_postal = [region.clone(), postal_code.clone()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            [city.clone(), Some(region_postal)]
+                .into_iter()
+                .flatten()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        Some(_) if rules.is_some_and(|r| r.postal_first) => {
+            [postal_code.clone(), city.clone(), region.clone()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+        _ => [city.clone(), region.clone(), postal_code.clone()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+    let country_name = rules.map(|r| r.name.to_string());
+    let country_part = country_name
+        .clone()
+        .filter(|_| input.include_country.unwrap_or(true));
+    let normalized = [Some(street_part), Some(locality), country_part]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let components = AddressComponents {
+        house_number,
+        street,
+        unit,
+        city,
+        region,
+        postal_code,
+        country: rules.map(|r| r.code.to_string()),
+    };
+    let mut missing = Vec::new();
+    let expected = [
+        ("house_number", components.house_number.is_none()),
+        ("street", components.street.is_none()),
+        ("city", components.city.is_none()),
+        (
+            "region",
+            components.region.is_none() && rules.is_some_and(|r| !r.regions.is_empty()),
+        ),
+        ("postal_code", components.postal_code.is_none()),
+        ("country", components.country.is_none()),
+    ];
+    for (name, absent) in expected {
+        if absent {
+            missing.push(name.to_string());
+        }
+    }
+
+    Ok(AddressParseResult {
+        components,
+        country_name,
+        country_source: country_source.to_string(),
+        normalized,
+        missing,
+        unparsed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(address: &str, country: Option<&str>) -> AddressParseResult {
+        parse_address(AddressParseInput {
+            address: address.to_string(),
+            country: country.map(str::to_string),
+            include_country: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_us_address() {
+        let result = parse(
+            "123 North Main Street Apt 4b, Springfield, IL 62704-1234",
+            None,
+        );
+        let c = &result.components;
+        assert_eq!(c.house_number.as_deref(), Some("123"));
+        assert_eq!(c.street.as_deref(), Some("North Main Street"));
+        assert_eq!(c.unit.as_deref(), Some("Apt 4B"));
+        assert_eq!(c.city.as_deref(), Some("Springfield"));
+        assert_eq!(c.region.as_deref(), Some("IL"));
+        assert_eq!(c.postal_code.as_deref(), Some("62704-1234"));
+        assert_eq!(c.country.as_deref(), Some("US"));
+        assert_eq!(result.country_source, "postal_code");
+        assert_eq!(
+            result.normalized,
+            "123 N Main St Apt 4B, Springfield, IL 62704-1234, United States"
+        );
+        assert!(result.missing.is_empty());
+    }
+
+    #[test]
+    fn test_single_line_us_address() {
+        let result = parse("500 Oak Ave Kansas City Missouri 64106 USA", None);
+        let c = &result.components;
+        assert_eq!(c.street.as_deref(), Some("Oak Ave"));
+        assert_eq!(c.city.as_deref(), Some("Kansas City"));
+        assert_eq!(c.region.as_deref(), Some("MO"));
+        assert_eq!(c.postal_code.as_deref(), Some("64106"));
+        assert!(result.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_uk_and_canada() {
+        let result = parse(
+            "Flat 3, 221B Baker Street, London NW1 6xe, United Kingdom",
+            None,
+        );
+        let c = &result.components;
+        assert_eq!(c.unit.as_deref(), Some("Flat 3"));
+        assert_eq!(c.house_number.as_deref(), Some("221B"));
+        assert_eq!(c.city.as_deref(), Some("London"));
+        assert_eq!(c.postal_code.as_deref(), Some("NW1 6XE"));
+        assert_eq!(result.country_source, "name");
+        assert_eq!(
+            result.normalized,
+            "221B Baker Street Flat 3, London NW1 6XE, United Kingdom"
+        );
+
+        let result = parse("24 Sussex Drive\nOttawa ON k1m1m4", None);
+        assert_eq!(result.components.country.as_deref(), Some("CA"));
+        assert_eq!(result.components.postal_code.as_deref(), Some("K1M 1M4"));
+        assert_eq!(result.components.region.as_deref(), Some("ON"));
+    }
+
+    #[test]
+    fn test_postal_code_first_countries() {
+        let result = parse("Unter den Linden 77, 10117 Berlin, Germany", None);
+        let c = &result.components;
+        assert_eq!(c.street.as_deref(), Some("Unter den Linden"));
+        assert_eq!(c.house_number.as_deref(), Some("77"));
+        assert_eq!(c.city.as_deref(), Some("Berlin"));
+        assert_eq!(
+            result.normalized,
+            "Unter den Linden 77, 10117 Berlin, Germany"
+        );
+
+        let result = parse("Damrak 1, 1012lg Amsterdam", None);
+        assert_eq!(result.components.postal_code.as_deref(), Some("1012 LG"));
+        assert_eq!(result.components.country.as_deref(), Some("NL"));
+
+        let result = parse("Via del Corso 12, 00186 Roma RM", Some("Italy"));
+        assert_eq!(result.components.city.as_deref(), Some("Roma"));
+        assert_eq!(result.components.region.as_deref(), Some("RM"));
+
+        let result = parse("10 rue de Rivoli 75004 Paris", Some("FR"));
+        assert_eq!(result.components.house_number.as_deref(), Some("10"));
+        assert_eq!(result.components.street.as_deref(), Some("rue de Rivoli"));
+        assert_eq!(result.components.city.as_deref(), Some("Paris"));
+    }
+
+    #[test]
+    fn test_australian_unit_and_missing_parts() {
+        let result = parse("2/45 Smith St, Fitzroy VIC 3065", None);
+        let c = &result.components;
+        assert_eq!(c.unit.as_deref(), Some("Unit 2"));
+        assert_eq!(c.house_number.as_deref(), Some("45"));
+        assert_eq!(c.city.as_deref(), Some("Fitzroy"));
+        assert_eq!(c.region.as_deref(), Some("VIC"));
+
+        let result = parse("Main Street, Springfield", None);
+        assert_eq!(result.components.country, None);
+        assert_eq!(result.country_source, "none");
+        assert_eq!(
+            result.missing,
+            vec!["house_number", "postal_code", "country"]
+        );
+    }
+
+    #[test]
+    fn test_errors_and_postal_formatting() {
+        let error = parse_address(AddressParseInput {
+            address: "1 Main St".to_string(),
+            country: Some("Atlantis".to_string()),
+            include_country: None,
+        })
+        .unwrap_err();
+        assert!(error.starts_with("Unsupported country 'Atlantis', use one of US, CA"));
+        assert!(
+            parse_address(AddressParseInput {
+                address: "  ".to_string(),
+                country: None,
+                include_country: None,
+            })
+            .is_err()
+        );
+        let gb = find_country("U.K.").unwrap();
+        assert_eq!(
+            format_postal_code(gb, "sw1a1aa").as_deref(),
+            Some("SW1A 1AA")
+        );
+        assert_eq!(format_postal_code(gb, "12345"), None);
+    }
+}
@@ -0,0 +1,74 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    CheckDigitInput as LogicInput, CheckDigitOutput as LogicOutput,
+    CheckDigitResult as LogicResult, SCHEMES, check_digit_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    CheckDigitOutputSchema as CheckDigitOutput, CheckDigitResultSchema as CheckDigitResult,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckDigitInput {
+    #[serde(flatten)]
+    pub params: logic::CheckDigitInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Generate or verify Luhn, Verhoeff, Damm and ISO 7064 MOD 97-10 check digits for one
+/// value or a batch
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn check_digit(request: Request<CheckDigitInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("check_digit", CheckDigitInput, CheckDigitOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("check_digit", &input);
+    log.finish(run_check_digit(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_check_digit(input: CheckDigitInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("value", input.params.value.as_deref().unwrap_or_default())
+        .array("values", input.params.values.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::check_digit_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&CheckDigitOutput::from(result))
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
@@ -0,0 +1,452 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most values accepted in one request
+pub const MAX_VALUES: usize = 10_000;
+/// Longest accepted value, in characters after removing separators
+const MAX_VALUE_LEN: usize = 256;
+
+/// Supported schemes, as accepted in `scheme`
+pub const SCHEMES: &[&str] = &["luhn", "verhoeff", "damm", "mod97"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct CheckDigitInput {
+    /// Check digit scheme: "luhn", "verhoeff", "damm" or "mod97" (ISO 7064 MOD 97-10)
+    pub scheme: String,
+    /// "verify" to check values that end in their check digit, or "generate" to compute
+    /// the check digit for values without one (default: verify)
+    pub mode: Option<String>,
+    /// Value to process; spaces and hyphens are ignored
+    pub value: Option<String>,
+    /// Values to process in one batch; give either this or value
+    pub values: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct CheckDigitOutput {
+    pub scheme: String,
+    pub mode: String,
+    /// One result per value, in input order
+    #[tool_types(nested)]
+    pub results: Vec<CheckDigitResult>,
+    /// Values that passed verification, or got a check digit when generating
+    pub valid_count: usize,
+    /// Values that failed verification or could not be processed
+    pub invalid_count: usize,
+    /// Whether every value is valid
+    pub all_valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct CheckDigitResult {
+    /// Value as given
+    pub input: String,
+    /// Value with separators removed (and letters uppercased for mod97)
+    pub normalized: String,
+    /// Whether the value ends in a correct check digit (absent when generating)
+    pub valid: Option<bool>,
+    /// Check digit for the value when generating, or the one it should end in when
+    /// verifying (two digits for mod97)
+    pub check_digit: Option<String>,
+    /// Normalized value followed by its check digit (absent when verifying)
+    pub with_check_digit: Option<String>,
+    /// Why the value could not be processed
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scheme {
+    Luhn,
+    Verhoeff,
+    Damm,
+    Mod97,
+}
+
+const VERHOEFF_D: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+const VERHOEFF_P: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 6, 8, 7, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+const VERHOEFF_INV: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// Totally anti-symmetric quasigroup of order 10 from Damm's thesis
+const DAMM: [[u8; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+impl Scheme {
+    fn parse(name: &str) -> Result<Scheme, String> {
+        match name.trim().to_lowercase().as_str() {
+            "luhn" => Ok(Scheme::Luhn),
+            "verhoeff" => Ok(Scheme::Verhoeff),
+            "damm" => Ok(Scheme::Damm),
+            "mod97" | "mod-97" | "mod97-10" => Ok(Scheme::Mod97),
+            _ => Err(format!(
+                "Unknown scheme '{name}'. Valid schemes: {}",
+                SCHEMES.join(", ")
+            )),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Scheme::Luhn => "luhn",
+            Scheme::Verhoeff => "verhoeff",
+            Scheme::Damm => "damm",
+            Scheme::Mod97 => "mod97",
+        }
+    }
+
+    /// Characters the check digit takes at the end of a value
+    fn width(self) -> usize {
+        if self == Scheme::Mod97 { 2 } else { 1 }
+    }
+
+    /// Value without separators, as digit values; mod97 also takes letters, as 10-35
+    fn digits(self, value: &str) -> Result<(String, Vec<u8>), String> {
+        let normalized: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if normalized.is_empty() {
+            return Err("Value has no digits".to_string());
+        }
+        if normalized.chars().count() > MAX_VALUE_LEN {
+            return Err(format!("Value is longer than {MAX_VALUE_LEN} characters"));
+        }
+        let digits = normalized
+            .chars()
+            .map(|c| match c.to_digit(36) {
+                Some(d) if d < 10 || self == Scheme::Mod97 => Ok(d as u8),
+                _ if self == Scheme::Mod97 => Err(format!("'{c}' is not a digit or letter")),
+                _ => Err(format!("'{c}' is not a digit")),
+            })
+            .collect::<Result<Vec<u8>, String>>()?;
+        Ok((normalized, digits))
+    }
+
+    /// Check digit to append to the digits
+    fn generate(self, digits: &[u8]) -> String {
+        match self {
+            Scheme::Luhn => {
+                // Once the check digit is appended, the rightmost payload digit is
+                // second from the right and gets doubled
+                let sum: u32 = digits
+                    .iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(i, &d)| luhn_term(d, i % 2 == 0))
+                    .sum();
+                ((10 - sum % 10) % 10).to_string()
+            }
+            Scheme::Verhoeff => {
+                let c = digits.iter().rev().enumerate().fold(0, |c, (i, &d)| {
+                    VERHOEFF_D[c as usize][VERHOEFF_P[(i + 1) % 8][d as usize] as usize]
+                });
+                VERHOEFF_INV[c as usize].to_string()
+            }
+            Scheme::Damm => damm(digits).to_string(),
+            Scheme::Mod97 => format!("{:02}", 98 - mod97(digits) * 100 % 97),
+        }
+    }
+
+    /// Whether the digits end in a correct check digit
+    fn verify(self, digits: &[u8]) -> bool {
+        match self {
+            Scheme::Luhn => {
+                let sum: u32 = digits
+                    .iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(i, &d)| luhn_term(d, i % 2 == 1))
+                    .sum();
+                sum.is_multiple_of(10)
+            }
+            Scheme::Verhoeff => {
+                digits.iter().rev().enumerate().fold(0, |c, (i, &d)| {
+                    VERHOEFF_D[c as usize][VERHOEFF_P[i % 8][d as usize] as usize]
+                }) == 0
+            }
+            Scheme::Damm => damm(digits) == 0,
+            Scheme::Mod97 => mod97(digits) == 1,
+        }
+    }
+}
+
+fn luhn_term(digit: u8, doubled: bool) -> u32 {
+    let d = digit as u32;
+    if !doubled {
+        d
+    } else if d > 4 {
+        d * 2 - 9
+    } else {
+        d * 2
+    }
+}
+
+fn damm(digits: &[u8]) -> u8 {
+    digits
+        .iter()
+        .fold(0, |interim, &d| DAMM[interim as usize][d as usize])
+}
+
+/// Remainder modulo 97 of the number spelled by the digits, letters counting as the two
+/// decimal digits of their value
+fn mod97(digits: &[u8]) -> u32 {
+    digits.iter().fold(0, |rem, &d| {
+        let d = d as u32;
+        if d < 10 {
+            (rem * 10 + d) % 97
+        } else {
+            (rem * 100 + d) % 97
+        }
+    })
+}
+
+fn process(scheme: Scheme, generate: bool, value: &str) -> CheckDigitResult {
+    let mut result = CheckDigitResult {
+        input: value.to_string(),
+        normalized: String::new(),
+        valid: None,
+        check_digit: None,
+        with_check_digit: None,
+        error: None,
+    };
+    let (normalized, digits) = match scheme.digits(value) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            result.normalized = value.to_string();
+            result.error = Some(e);
+            if !generate {
+                result.valid = Some(false);
+            }
+            return result;
+        }
+    };
+    result.normalized = normalized;
+
+    if generate {
+        let check_digit = scheme.generate(&digits);
+        result.with_check_digit = Some(format!("{}{check_digit}", result.normalized));
+        result.check_digit = Some(check_digit);
+    } else if digits.len() <= scheme.width() {
+        result.valid = Some(false);
+        result.error = Some(format!(
+            "Value needs at least one digit before its {}-character check digit",
+            scheme.width()
+        ));
+    } else {
+        result.valid = Some(scheme.verify(&digits));
+        result.check_digit = Some(scheme.generate(&digits[..digits.len() - scheme.width()]));
+    }
+    result
+}
+
+pub fn check_digit_logic(input: CheckDigitInput) -> Result<CheckDigitOutput, String> {
+    let scheme = Scheme::parse(&input.scheme)?;
+    let generate = match input
+        .mode
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .as_deref()
+    {
+        None | Some("verify") => false,
+        Some("generate") => true,
+        Some(other) => {
+            return Err(format!(
+                "Unknown mode '{other}', use 'verify' or 'generate'"
+            ));
+        }
+    };
+    let values = match (input.value, input.values) {
+        (Some(value), None) => vec![value],
+        (None, Some(values)) if values.is_empty() => {
+            return Err("values must not be empty".to_string());
+        }
+        (None, Some(values)) => values,
+        _ => return Err("Give either value or values".to_string()),
+    };
+    if values.len() > MAX_VALUES {
+        return Err(format!("At most {MAX_VALUES} values are supported"));
+    }
+
+    let results: Vec<CheckDigitResult> = values
+        .iter()
+        .map(|value| process(scheme, generate, value))
+        .collect();
+    let valid_count = results
+        .iter()
+        .filter(|r| r.error.is_none() && r.valid != Some(false))
+        .count();
+    let invalid_count = results.len() - valid_count;
+
+    Ok(CheckDigitOutput {
+        scheme: scheme.name().to_string(),
+        mode: if generate { "generate" } else { "verify" }.to_string(),
+        results,
+        valid_count,
+        invalid_count,
+        all_valid: invalid_count == 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(scheme: &str, mode: &str, values: &[&str]) -> CheckDigitOutput {
+        check_digit_logic(CheckDigitInput {
+            scheme: scheme.to_string(),
+            mode: Some(mode.to_string()),
+            value: None,
+            values: Some(values.iter().map(|v| v.to_string()).collect()),
+        })
+        .unwrap()
+    }
+
+    fn generated(scheme: &str, value: &str) -> String {
+        run(scheme, "generate", &[value]).results[0]
+            .check_digit
+            .clone()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_known_check_digits() {
+        assert_eq!(generated("luhn", "7992739871"), "3");
+        assert_eq!(generated("verhoeff", "236"), "3");
+        assert_eq!(generated("damm", "572"), "4");
+        assert_eq!(generated("mod97", "5493001KJTIIGC8Y1R"), "12");
+        let result = run("luhn", "generate", &["4111 1111 1111 111"]);
+        assert_eq!(
+            result.results[0].with_check_digit.as_deref(),
+            Some("4111111111111111")
+        );
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let result = run(
+            "luhn",
+            "verify",
+            &["4111-1111-1111-1111", "4111111111111112", "79927398713"],
+        );
+        let valid: Vec<_> = result.results.iter().map(|r| r.valid.unwrap()).collect();
+        assert_eq!(valid, [true, false, true]);
+        // A failed value reports the check digit it should end in
+        assert_eq!(result.results[1].check_digit.as_deref(), Some("1"));
+        assert_eq!(result.valid_count, 2);
+        assert_eq!(result.invalid_count, 1);
+        assert!(!result.all_valid);
+
+        let result = run(
+            "mod97",
+            "verify",
+            &["529900t8bm49aursdo55", "529900T8BM49AURSDO56"],
+        );
+        assert_eq!(result.results[0].normalized, "529900T8BM49AURSDO55");
+        assert_eq!(result.results[0].valid, Some(true));
+        assert_eq!(result.results[1].valid, Some(false));
+        assert_eq!(result.results[1].check_digit.as_deref(), Some("55"));
+    }
+
+    #[test]
+    fn test_generated_values_verify() {
+        for scheme in SCHEMES {
+            for payload in ["0", "12345", "9876543210", "31415926535897932384"] {
+                let generated = run(scheme, "generate", &[payload]);
+                let full = generated.results[0].with_check_digit.clone().unwrap();
+                assert!(run(scheme, "verify", &[&full]).all_valid, "{scheme} {full}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_digit_errors_are_caught() {
+        // Verhoeff and Damm catch every single-digit error and adjacent transposition
+        for scheme in ["verhoeff", "damm"] {
+            let full = run(scheme, "generate", &["8473"]).results[0]
+                .with_check_digit
+                .clone()
+                .unwrap();
+            let mut bytes = full.into_bytes();
+            bytes.swap(1, 2);
+            let swapped = String::from_utf8(bytes).unwrap();
+            assert!(!run(scheme, "verify", &[&swapped]).all_valid, "{scheme}");
+        }
+        // Luhn misses the 09 ↔ 90 transposition
+        assert_eq!(generated("luhn", "09"), generated("luhn", "90"));
+    }
+
+    #[test]
+    fn test_invalid_values_and_input() {
+        let result = run("damm", "verify", &["12a4", "5", ""]);
+        assert_eq!(result.invalid_count, 3);
+        assert_eq!(
+            result.results[0].error.as_deref(),
+            Some("'A' is not a digit")
+        );
+        assert!(
+            result.results[1]
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("at least one digit")
+        );
+        assert_eq!(
+            result.results[2].error.as_deref(),
+            Some("Value has no digits")
+        );
+
+        let result = run("mod97", "generate", &["AB_1"]);
+        assert_eq!(result.results[0].valid, None);
+        assert!(result.results[0].error.as_ref().unwrap().contains("'_'"));
+
+        let err = check_digit_logic(CheckDigitInput {
+            scheme: "isbn".to_string(),
+            mode: None,
+            value: Some("1".to_string()),
+            values: None,
+        })
+        .unwrap_err();
+        assert!(err.contains("Unknown scheme 'isbn'"));
+
+        let err = check_digit_logic(CheckDigitInput {
+            scheme: "luhn".to_string(),
+            mode: Some("fix".to_string()),
+            value: Some("1".to_string()),
+            values: None,
+        })
+        .unwrap_err();
+        assert!(err.contains("Unknown mode 'fix'"));
+    }
+}
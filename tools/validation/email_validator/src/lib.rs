@@ -1,104 +1,80 @@
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
     EmailParts as LogicParts, EmailValidatorInput as LogicInput,
-    EmailValidatorResult as LogicOutput, ValidationChecks as LogicChecks,
+    EmailValidatorResult as LogicOutput, ValidationChecks as LogicChecks, validate_email,
 };
 
-// Define wrapper types with JsonSchema for FTL-SDK
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct EmailValidatorInput {
-    /// Email address to validate
-    pub email: String,
-    /// Whether to check DNS records (not implemented in basic version)
-    pub check_dns: Option<bool>,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    EmailPartsSchema as EmailParts, EmailValidatorResultSchema as EmailValidatorResult,
+    ValidationChecksSchema as ValidationChecks,
+};
 
+// The generated input plus options handled by this wrapper
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct EmailValidatorResult {
-    /// Whether the email is valid
-    pub is_valid: bool,
-    /// Reason for invalidity (if applicable)
-    pub error: Option<String>,
-    /// Email parts breakdown
-    pub parts: Option<EmailParts>,
-    /// Validation checks performed
-    pub checks: ValidationChecks,
+pub struct EmailValidatorInput {
+    #[serde(flatten)]
+    pub params: logic::EmailValidatorInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct EmailParts {
-    /// Local part (before @)
-    pub local: String,
-    /// Domain part (after @)
-    pub domain: String,
-    /// Top-level domain
-    pub tld: Option<String>,
-}
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn email_validator(request: Request<EmailValidatorInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "email_validator",
+                EmailValidatorInput,
+                EmailValidatorResult
+            ));
+        }
+    };
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct ValidationChecks {
-    /// Has exactly one @ symbol
-    pub has_single_at: bool,
-    /// Local part is valid
-    pub valid_local: bool,
-    /// Domain part is valid
-    pub valid_domain: bool,
-    /// No consecutive dots
-    pub no_consecutive_dots: bool,
-    /// No leading/trailing dots
-    pub no_edge_dots: bool,
-    /// Valid characters only
-    pub valid_characters: bool,
-    /// Reasonable length
-    pub reasonable_length: bool,
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("email_validator", &input);
+    log.finish(run_email_validator(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn email_validator(input: EmailValidatorInput) -> ToolResponse {
-    // Convert to logic types
-    let logic_input = LogicInput {
-        email: input.email,
-        check_dns: input.check_dns,
-    };
+#[cfg(feature = "individual")]
+fn run_email_validator(input: EmailValidatorInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("email", &input.params.email)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
 
     // Call logic implementation
-    let result = match logic::validate_email(logic_input) {
+    let logic_input: LogicInput = input.params.into();
+    let result = match ftl_result_cache::cached("email_validator", &logic_input, || {
+        logic::validate_email(logic_input.clone())
+    }) {
         Ok(result) => result,
         Err(e) => return ToolResponse::text(format!("Error validating email: {e}")),
     };
 
-    // Convert back to wrapper types
-    let email_result = EmailValidatorResult {
-        is_valid: result.is_valid,
-        error: result.error,
-        parts: result.parts.map(|p| EmailParts {
-            local: p.local,
-            domain: p.domain,
-            tld: p.tld,
-        }),
-        checks: ValidationChecks {
-            has_single_at: result.checks.has_single_at,
-            valid_local: result.checks.valid_local,
-            valid_domain: result.checks.valid_domain,
-            no_consecutive_dots: result.checks.no_consecutive_dots,
-            no_edge_dots: result.checks.no_edge_dots,
-            valid_characters: result.checks.valid_characters,
-            reasonable_length: result.checks.reasonable_length,
-        },
-    };
-
     ToolResponse::text(
-        serde_json::to_string(&email_result)
+        ftl_json::to_string(&EmailValidatorResult::from(result))
             .unwrap_or_else(|_| "Error serializing result".to_string()),
     )
 }
@@ -1,26 +1,32 @@
+use ftl_messages::Locale;
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct EmailValidatorInput {
     /// Email address to validate
     pub email: String,
     /// Whether to check DNS records (not implemented in basic version)
     pub check_dns: Option<bool>,
+    /// Language of the error reason: "en" (default), "es", "de" or "ja"
+    pub locale: Option<Locale>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct EmailValidatorResult {
     /// Whether the email is valid
     pub is_valid: bool,
     /// Reason for invalidity (if applicable)
     pub error: Option<String>,
     /// Email parts breakdown
+    #[tool_types(nested)]
     pub parts: Option<EmailParts>,
     /// Validation checks performed
+    #[tool_types(nested)]
     pub checks: ValidationChecks,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct EmailParts {
     /// Local part (before @)
     pub local: String,
@@ -30,7 +36,7 @@ pub struct EmailParts {
     pub tld: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct ValidationChecks {
     /// Has exactly one @ symbol
     pub has_single_at: bool,
@@ -49,6 +55,7 @@ pub struct ValidationChecks {
 }
 
 pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult, String> {
+    let locale = input.locale.unwrap_or_default();
     let email = input.email.trim();
 
     // Initialize checks
@@ -67,7 +74,7 @@ pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult
     if !checks.reasonable_length {
         return Ok(EmailValidatorResult {
             is_valid: false,
-            error: Some("Email length must be between 3 and 320 characters".to_string()),
+            error: Some(ftl_messages::EMAIL_LENGTH.text(locale).to_string()),
             parts: None,
             checks,
         });
@@ -80,9 +87,9 @@ pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult
         return Ok(EmailValidatorResult {
             is_valid: false,
             error: Some(if at_count == 0 {
-                "Email must contain @ symbol".to_string()
+                ftl_messages::EMAIL_MISSING_AT.text(locale).to_string()
             } else {
-                "Email must contain exactly one @ symbol".to_string()
+                ftl_messages::EMAIL_MULTIPLE_AT.text(locale).to_string()
             }),
             parts: None,
             checks,
@@ -99,7 +106,11 @@ pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult
     if !checks.no_consecutive_dots {
         return Ok(EmailValidatorResult {
             is_valid: false,
-            error: Some("Email cannot contain consecutive dots".to_string()),
+            error: Some(
+                ftl_messages::EMAIL_CONSECUTIVE_DOTS
+                    .text(locale)
+                    .to_string(),
+            ),
             parts: None,
             checks,
         });
@@ -113,7 +124,7 @@ pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult
     if !checks.no_edge_dots {
         return Ok(EmailValidatorResult {
             is_valid: false,
-            error: Some("Email parts cannot start or end with dots".to_string()),
+            error: Some(ftl_messages::EMAIL_EDGE_DOTS.text(locale).to_string()),
             parts: None,
             checks,
         });
@@ -124,7 +135,7 @@ pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult
     if !checks.valid_local {
         return Ok(EmailValidatorResult {
             is_valid: false,
-            error: Some("Invalid local part (before @)".to_string()),
+            error: Some(ftl_messages::EMAIL_INVALID_LOCAL.text(locale).to_string()),
             parts: None,
             checks,
         });
@@ -135,7 +146,7 @@ pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult
     if !checks.valid_domain {
         return Ok(EmailValidatorResult {
             is_valid: false,
-            error: Some("Invalid domain part (after @)".to_string()),
+            error: Some(ftl_messages::EMAIL_INVALID_DOMAIN.text(locale).to_string()),
             parts: None,
             checks,
         });
@@ -146,7 +157,11 @@ pub fn validate_email(input: EmailValidatorInput) -> Result<EmailValidatorResult
     if !checks.valid_characters {
         return Ok(EmailValidatorResult {
             is_valid: false,
-            error: Some("Email contains invalid characters".to_string()),
+            error: Some(
+                ftl_messages::EMAIL_INVALID_CHARACTERS
+                    .text(locale)
+                    .to_string(),
+            ),
             parts: None,
             checks,
         });
@@ -258,6 +273,7 @@ mod tests {
             let input = EmailValidatorInput {
                 email: email.to_string(),
                 check_dns: None,
+                locale: None,
             };
             let result = validate_email(input).unwrap();
             assert!(result.is_valid, "Email '{email}' should be valid");
@@ -300,6 +316,7 @@ mod tests {
             let input = EmailValidatorInput {
                 email: email.to_string(),
                 check_dns: None,
+                locale: None,
             };
             let result = validate_email(input).unwrap();
             assert!(!result.is_valid, "Email '{email}' should be invalid");
@@ -312,11 +329,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_localized_error() {
+        let input = EmailValidatorInput {
+            email: "test".to_string(),
+            check_dns: None,
+            locale: Some(Locale::Es),
+        };
+        let result = validate_email(input).unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("El correo electrónico debe contener el símbolo @")
+        );
+    }
+
     #[test]
     fn test_email_parts() {
         let input = EmailValidatorInput {
             email: "user@example.com".to_string(),
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(result.is_valid);
@@ -332,6 +364,7 @@ mod tests {
         let input = EmailValidatorInput {
             email: "valid@example.com".to_string(),
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
 
@@ -353,6 +386,7 @@ mod tests {
         let input = EmailValidatorInput {
             email,
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(result.is_valid);
@@ -367,6 +401,7 @@ mod tests {
         let input = EmailValidatorInput {
             email,
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(!result.is_valid);
@@ -377,6 +412,7 @@ mod tests {
         let input = EmailValidatorInput {
             email: "test@example.123".to_string(),
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(!result.is_valid);
@@ -387,6 +423,7 @@ mod tests {
         let input = EmailValidatorInput {
             email: "test@ex-ample.com".to_string(),
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(result.is_valid);
@@ -397,6 +434,7 @@ mod tests {
         let input = EmailValidatorInput {
             email: "test@-example.com".to_string(),
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(!result.is_valid);
@@ -407,6 +445,7 @@ mod tests {
         let input = EmailValidatorInput {
             email: "test@mail.subdomain.example.com".to_string(),
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(result.is_valid);
@@ -418,6 +457,7 @@ mod tests {
         let input = EmailValidatorInput {
             email: "  test@example.com  ".to_string(),
             check_dns: None,
+            locale: None,
         };
         let result = validate_email(input).unwrap();
         assert!(result.is_valid);
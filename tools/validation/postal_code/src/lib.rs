@@ -0,0 +1,75 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    FORMATS, PostalCodeInput as LogicInput, PostalCodeOutput as LogicOutput,
+    PostalCodeResult as LogicResult, PostalFormat, find_format, lookup_centroid,
+    normalize_postal_code, postal_code_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    CentroidSchema as Centroid, PostalCodeOutputSchema as PostalCodeOutput,
+    PostalCodeResultSchema as PostalCodeResult,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PostalCodeInput {
+    #[serde(flatten)]
+    pub params: logic::PostalCodeInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Validate postal codes against per-country formats and return approximate centroids,
+/// ready for proximity_search, for US, Canadian, UK, Australian and German codes
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn postal_code(request: Request<PostalCodeInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("postal_code", PostalCodeInput, PostalCodeOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("postal_code", &input);
+    log.finish(run_postal_code(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_postal_code(input: PostalCodeInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("postal_codes", &input.params.postal_codes)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::postal_code_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&PostalCodeOutput::from(result))
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
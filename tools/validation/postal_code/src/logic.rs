@@ -0,0 +1,953 @@
+use ftl_tool_types_derive::ToolTypes;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Most postal codes checked in one call
+pub const MAX_CODES: usize = 10_000;
+
+/// Postal code format of one country
+pub struct PostalFormat {
+    /// ISO 3166-1 alpha-2 code
+    pub code: &'static str,
+    pub name: &'static str,
+    /// Anchored pattern over the uppercased code; its groups make up the standard form
+    pub pattern: &'static str,
+    /// Joins the pattern's groups in the standard form
+    separator: &'static str,
+    /// A valid code, quoted in error messages
+    pub example: &'static str,
+}
+
+/// Postal code formats by country
+pub const FORMATS: &[PostalFormat] = &[
+    PostalFormat {
+        code: "US",
+        name: "United States",
+        pattern: r"^(\d{5})(?:[- ]?(\d{4}))?$",
+        separator: "-",
+        example: "62704 or 62704-1234",
+    },
+    PostalFormat {
+        code: "CA",
+        name: "Canada",
+        pattern: r"^([ABCEGHJ-NPRSTVXY]\d[ABCEGHJ-NPRSTV-Z]) ?(\d[ABCEGHJ-NPRSTV-Z]\d)$",
+        separator: " ",
+        example: "K1A 0B1",
+    },
+    PostalFormat {
+        code: "GB",
+        name: "United Kingdom",
+        pattern: r"^([A-Z]{1,2}\d[A-Z\d]?) ?(\d[A-Z]{2})$",
+        separator: " ",
+        example: "SW1A 1AA",
+    },
+    PostalFormat {
+        code: "IE",
+        name: "Ireland",
+        pattern: r"^([AC-FHKNPRTV-Y]\d{2}|D6W) ?([0-9AC-FHKNPRTV-Y]{4})$",
+        separator: " ",
+        example: "D02 X285",
+    },
+    PostalFormat {
+        code: "AU",
+        name: "Australia",
+        pattern: r"^(\d{4})$",
+        separator: "",
+        example: "2000",
+    },
+    PostalFormat {
+        code: "NZ",
+        name: "New Zealand",
+        pattern: r"^(\d{4})$",
+        separator: "",
+        example: "6011",
+    },
+    PostalFormat {
+        code: "DE",
+        name: "Germany",
+        pattern: r"^(\d{5})$",
+        separator: "",
+        example: "10117",
+    },
+    PostalFormat {
+        code: "AT",
+        name: "Austria",
+        pattern: r"^([1-9]\d{3})$",
+        separator: "",
+        example: "1010",
+    },
+    PostalFormat {
+        code: "CH",
+        name: "Switzerland",
+        pattern: r"^([1-9]\d{3})$",
+        separator: "",
+        example: "8001",
+    },
+    PostalFormat {
+        code: "FR",
+        name: "France",
+        pattern: r"^(\d{5})$",
+        separator: "",
+        example: "75008",
+    },
+    PostalFormat {
+        code: "BE",
+        name: "Belgium",
+        pattern: r"^([1-9]\d{3})$",
+        separator: "",
+        example: "1000",
+    },
+    PostalFormat {
+        code: "NL",
+        name: "Netherlands",
+        pattern: r"^([1-9]\d{3}) ?([A-Z]{2})$",
+        separator: " ",
+        example: "1012 LG",
+    },
+    PostalFormat {
+        code: "ES",
+        name: "Spain",
+        pattern: r"^((?:0[1-9]|[1-4]\d|5[0-2])\d{3})$",
+        separator: "",
+        example: "28013",
+    },
+    PostalFormat {
+        code: "PT",
+        name: "Portugal",
+        pattern: r"^([1-9]\d{3})[- ]?(\d{3})$",
+        separator: "-",
+        example: "1100-148",
+    },
+    PostalFormat {
+        code: "IT",
+        name: "Italy",
+        pattern: r"^(\d{5})$",
+        separator: "",
+        example: "00184",
+    },
+    PostalFormat {
+        code: "SE",
+        name: "Sweden",
+        pattern: r"^([1-9]\d{2}) ?(\d{2})$",
+        separator: " ",
+        example: "111 29",
+    },
+    PostalFormat {
+        code: "NO",
+        name: "Norway",
+        pattern: r"^(\d{4})$",
+        separator: "",
+        example: "0150",
+    },
+    PostalFormat {
+        code: "DK",
+        name: "Denmark",
+        pattern: r"^([1-9]\d{3})$",
+        separator: "",
+        example: "1050",
+    },
+    PostalFormat {
+        code: "FI",
+        name: "Finland",
+        pattern: r"^(\d{5})$",
+        separator: "",
+        example: "00100",
+    },
+    PostalFormat {
+        code: "PL",
+        name: "Poland",
+        pattern: r"^(\d{2})-?(\d{3})$",
+        separator: "-",
+        example: "00-950",
+    },
+    PostalFormat {
+        code: "CZ",
+        name: "Czechia",
+        pattern: r"^([1-7]\d{2}) ?(\d{2})$",
+        separator: " ",
+        example: "110 00",
+    },
+    PostalFormat {
+        code: "JP",
+        name: "Japan",
+        pattern: r"^(\d{3})-?(\d{4})$",
+        separator: "-",
+        example: "100-0001",
+    },
+    PostalFormat {
+        code: "KR",
+        name: "South Korea",
+        pattern: r"^(\d{5})$",
+        separator: "",
+        example: "03187",
+    },
+    PostalFormat {
+        code: "CN",
+        name: "China",
+        pattern: r"^([1-8]\d{5})$",
+        separator: "",
+        example: "100000",
+    },
+    PostalFormat {
+        code: "IN",
+        name: "India",
+        pattern: r"^([1-9]\d{2}) ?(\d{3})$",
+        separator: "",
+        example: "110001",
+    },
+    PostalFormat {
+        code: "SG",
+        name: "Singapore",
+        pattern: r"^(\d{6})$",
+        separator: "",
+        example: "018956",
+    },
+    PostalFormat {
+        code: "BR",
+        name: "Brazil",
+        pattern: r"^(\d{5})-?(\d{3})$",
+        separator: "-",
+        example: "01310-100",
+    },
+    PostalFormat {
+        code: "MX",
+        name: "Mexico",
+        pattern: r"^(\d{5})$",
+        separator: "",
+        example: "06000",
+    },
+    PostalFormat {
+        code: "AR",
+        name: "Argentina",
+        pattern: r"^([A-HJ-NP-Z]\d{4}[A-Z]{3}|\d{4})$",
+        separator: "",
+        example: "C1002AAP",
+    },
+    PostalFormat {
+        code: "ZA",
+        name: "South Africa",
+        pattern: r"^(\d{4})$",
+        separator: "",
+        example: "8001",
+    },
+];
+
+/// US states by abbreviation, with approximate geographic centers
+const US_STATES: &[(&str, &str, f64, f64)] = &[
+    ("AL", "Alabama", 32.8, -86.8),
+    ("AK", "Alaska", 64.0, -152.0),
+    ("AZ", "Arizona", 34.3, -111.7),
+    ("AR", "Arkansas", 34.9, -92.4),
+    ("CA", "California", 37.2, -119.5),
+    ("CO", "Colorado", 39.0, -105.5),
+    ("CT", "Connecticut", 41.6, -72.7),
+    ("DE", "Delaware", 39.0, -75.5),
+    ("DC", "District of Columbia", 38.9, -77.03),
+    ("FL", "Florida", 28.6, -82.4),
+    ("GA", "Georgia", 32.7, -83.4),
+    ("GU", "Guam", 13.4, 144.8),
+    ("HI", "Hawaii", 20.8, -156.3),
+    ("ID", "Idaho", 44.4, -114.6),
+    ("IL", "Illinois", 40.0, -89.2),
+    ("IN", "Indiana", 39.9, -86.3),
+    ("IA", "Iowa", 42.1, -93.5),
+    ("KS", "Kansas", 38.5, -98.4),
+    ("KY", "Kentucky", 37.5, -85.3),
+    ("LA", "Louisiana", 31.1, -92.0),
+    ("ME", "Maine", 45.4, -69.2),
+    ("MD", "Maryland", 39.0, -76.8),
+    ("MA", "Massachusetts", 42.3, -71.8),
+    ("MI", "Michigan", 44.3, -85.4),
+    ("MN", "Minnesota", 46.3, -94.3),
+    ("MS", "Mississippi", 32.7, -89.7),
+    ("MO", "Missouri", 38.4, -92.5),
+    ("MT", "Montana", 47.0, -109.6),
+    ("NE", "Nebraska", 41.5, -99.8),
+    ("NV", "Nevada", 39.3, -116.6),
+    ("NH", "New Hampshire", 43.7, -71.6),
+    ("NJ", "New Jersey", 40.2, -74.7),
+    ("NM", "New Mexico", 34.4, -106.1),
+    ("NY", "New York", 42.9, -75.5),
+    ("NC", "North Carolina", 35.6, -79.4),
+    ("ND", "North Dakota", 47.5, -100.5),
+    ("OH", "Ohio", 40.3, -82.8),
+    ("OK", "Oklahoma", 35.6, -97.5),
+    ("OR", "Oregon", 43.9, -120.6),
+    ("PA", "Pennsylvania", 40.9, -77.8),
+    ("PR", "Puerto Rico", 18.2, -66.5),
+    ("RI", "Rhode Island", 41.7, -71.5),
+    ("SC", "South Carolina", 33.9, -80.9),
+    ("SD", "South Dakota", 44.4, -100.2),
+    ("TN", "Tennessee", 35.9, -86.4),
+    ("TX", "Texas", 31.5, -99.3),
+    ("UT", "Utah", 39.3, -111.7),
+    ("VT", "Vermont", 44.1, -72.7),
+    ("VA", "Virginia", 37.5, -78.9),
+    ("VI", "U.S. Virgin Islands", 18.3, -64.9),
+    ("WA", "Washington", 47.4, -120.5),
+    ("WV", "West Virginia", 38.6, -80.6),
+    ("WI", "Wisconsin", 44.6, -89.9),
+    ("WY", "Wyoming", 43.0, -107.5),
+];
+
+/// Three-digit ZIP prefix ranges by state; military and unassigned prefixes are absent
+const US_PREFIXES: &[(u32, u32, &str)] = &[
+    (5, 5, "NY"),
+    (6, 7, "PR"),
+    (8, 8, "VI"),
+    (9, 9, "PR"),
+    (10, 27, "MA"),
+    (28, 29, "RI"),
+    (30, 38, "NH"),
+    (39, 49, "ME"),
+    (50, 54, "VT"),
+    (55, 55, "MA"),
+    (56, 59, "VT"),
+    (60, 69, "CT"),
+    (70, 89, "NJ"),
+    (100, 149, "NY"),
+    (150, 196, "PA"),
+    (197, 199, "DE"),
+    (200, 200, "DC"),
+    (201, 201, "VA"),
+    (202, 205, "DC"),
+    (206, 219, "MD"),
+    (220, 246, "VA"),
+    (247, 268, "WV"),
+    (270, 289, "NC"),
+    (290, 299, "SC"),
+    (300, 319, "GA"),
+    (320, 339, "FL"),
+    (341, 349, "FL"),
+    (350, 369, "AL"),
+    (370, 385, "TN"),
+    (386, 397, "MS"),
+    (398, 399, "GA"),
+    (400, 427, "KY"),
+    (430, 459, "OH"),
+    (460, 479, "IN"),
+    (480, 499, "MI"),
+    (500, 528, "IA"),
+    (530, 549, "WI"),
+    (550, 567, "MN"),
+    (569, 569, "DC"),
+    (570, 577, "SD"),
+    (580, 588, "ND"),
+    (590, 599, "MT"),
+    (600, 629, "IL"),
+    (630, 658, "MO"),
+    (660, 679, "KS"),
+    (680, 693, "NE"),
+    (700, 715, "LA"),
+    (716, 729, "AR"),
+    (730, 731, "OK"),
+    (733, 733, "TX"),
+    (734, 749, "OK"),
+    (750, 799, "TX"),
+    (800, 816, "CO"),
+    (820, 831, "WY"),
+    (832, 838, "ID"),
+    (840, 847, "UT"),
+    (850, 865, "AZ"),
+    (870, 884, "NM"),
+    (885, 885, "TX"),
+    (889, 898, "NV"),
+    (900, 961, "CA"),
+    (967, 968, "HI"),
+    (969, 969, "GU"),
+    (970, 979, "OR"),
+    (980, 994, "WA"),
+    (995, 999, "AK"),
+];
+
+/// Three-digit ZIP prefix ranges of large cities, checked before the state ranges
+const US_METROS: &[(u32, u32, &str, f64, f64)] = &[
+    (21, 22, "Boston", 42.36, -71.06),
+    (100, 104, "New York City", 40.71, -74.0),
+    (191, 191, "Philadelphia", 39.95, -75.17),
+    (200, 205, "Washington", 38.9, -77.03),
+    (303, 303, "Atlanta", 33.75, -84.39),
+    (331, 332, "Miami", 25.77, -80.19),
+    (606, 608, "Chicago", 41.88, -87.63),
+    (750, 753, "Dallas", 32.78, -96.8),
+    (770, 772, "Houston", 29.76, -95.37),
+    (802, 802, "Denver", 39.74, -104.99),
+    (850, 853, "Phoenix", 33.45, -112.07),
+    (900, 908, "Los Angeles", 34.05, -118.25),
+    (941, 941, "San Francisco", 37.77, -122.42),
+    (981, 981, "Seattle", 47.61, -122.33),
+];
+
+/// Canadian postal districts by first letter, centered on where people live
+const CA_DISTRICTS: &[(char, &str, f64, f64)] = &[
+    ('A', "Newfoundland and Labrador", 48.9, -55.7),
+    ('B', "Nova Scotia", 45.0, -63.0),
+    ('C', "Prince Edward Island", 46.3, -63.3),
+    ('E', "New Brunswick", 46.5, -66.2),
+    ('G', "Eastern Quebec", 47.8, -70.5),
+    ('H', "Montreal", 45.55, -73.65),
+    ('J', "Western Quebec", 46.5, -74.0),
+    ('K', "Eastern Ontario", 44.8, -76.2),
+    ('L', "Central Ontario", 43.6, -79.9),
+    ('M', "Toronto", 43.7, -79.4),
+    ('N', "Southwestern Ontario", 43.1, -81.0),
+    ('P', "Northern Ontario", 47.5, -82.0),
+    ('R', "Manitoba", 50.5, -97.5),
+    ('S', "Saskatchewan", 52.0, -106.0),
+    ('T', "Alberta", 52.5, -114.0),
+    ('V', "British Columbia", 50.5, -122.5),
+    ('X', "Northwest Territories and Nunavut", 63.5, -105.0),
+    ('Y', "Yukon", 63.0, -135.5),
+];
+
+/// UK postcode areas with the location of the town each is named after
+const GB_AREAS: &[(&str, &str, f64, f64)] = &[
+    ("AB", "Aberdeen", 57.15, -2.1),
+    ("AL", "St Albans", 51.75, -0.34),
+    ("B", "Birmingham", 52.48, -1.9),
+    ("BA", "Bath", 51.38, -2.36),
+    ("BB", "Blackburn", 53.75, -2.48),
+    ("BD", "Bradford", 53.79, -1.75),
+    ("BH", "Bournemouth", 50.72, -1.88),
+    ("BL", "Bolton", 53.58, -2.43),
+    ("BN", "Brighton", 50.82, -0.14),
+    ("BR", "Bromley", 51.4, 0.02),
+    ("BS", "Bristol", 51.45, -2.59),
+    ("BT", "Belfast", 54.6, -5.93),
+    ("CA", "Carlisle", 54.89, -2.93),
+    ("CB", "Cambridge", 52.21, 0.12),
+    ("CF", "Cardiff", 51.48, -3.18),
+    ("CH", "Chester", 53.19, -2.89),
+    ("CM", "Chelmsford", 51.74, 0.47),
+    ("CO", "Colchester", 51.89, 0.9),
+    ("CR", "Croydon", 51.37, -0.1),
+    ("CT", "Canterbury", 51.28, 1.08),
+    ("CV", "Coventry", 52.41, -1.51),
+    ("CW", "Crewe", 53.1, -2.44),
+    ("DA", "Dartford", 51.45, 0.22),
+    ("DD", "Dundee", 56.46, -2.97),
+    ("DE", "Derby", 52.92, -1.48),
+    ("DG", "Dumfries", 55.07, -3.61),
+    ("DH", "Durham", 54.78, -1.57),
+    ("DL", "Darlington", 54.52, -1.55),
+    ("DN", "Doncaster", 53.52, -1.13),
+    ("DT", "Dorchester", 50.71, -2.44),
+    ("DY", "Dudley", 52.51, -2.09),
+    ("E", "East London", 51.54, -0.03),
+    ("EC", "East Central London", 51.52, -0.09),
+    ("EH", "Edinburgh", 55.95, -3.19),
+    ("EN", "Enfield", 51.65, -0.08),
+    ("EX", "Exeter", 50.72, -3.53),
+    ("FK", "Falkirk", 56.0, -3.78),
+    ("FY", "Blackpool", 53.82, -3.05),
+    ("G", "Glasgow", 55.86, -4.25),
+    ("GL", "Gloucester", 51.86, -2.24),
+    ("GU", "Guildford", 51.24, -0.57),
+    ("HA", "Harrow", 51.58, -0.34),
+    ("HD", "Huddersfield", 53.65, -1.78),
+    ("HG", "Harrogate", 53.99, -1.54),
+    ("HP", "Hemel Hempstead", 51.75, -0.47),
+    ("HR", "Hereford", 52.06, -2.72),
+    ("HS", "Outer Hebrides", 58.21, -6.39),
+    ("HU", "Hull", 53.74, -0.33),
+    ("HX", "Halifax", 53.72, -1.86),
+    ("IG", "Ilford", 51.56, 0.07),
+    ("IP", "Ipswich", 52.06, 1.16),
+    ("IV", "Inverness", 57.48, -4.22),
+    ("KA", "Kilmarnock", 55.61, -4.5),
+    ("KT", "Kingston upon Thames", 51.41, -0.3),
+    ("KW", "Kirkwall", 58.98, -2.96),
+    ("KY", "Kirkcaldy", 56.11, -3.16),
+    ("L", "Liverpool", 53.41, -2.98),
+    ("LA", "Lancaster", 54.05, -2.8),
+    ("LD", "Llandrindod Wells", 52.24, -3.38),
+    ("LE", "Leicester", 52.64, -1.13),
+    ("LL", "Llandudno", 53.32, -3.83),
+    ("LN", "Lincoln", 53.23, -0.54),
+    ("LS", "Leeds", 53.8, -1.55),
+    ("LU", "Luton", 51.88, -0.42),
+    ("M", "Manchester", 53.48, -2.24),
+    ("ME", "Rochester", 51.39, 0.5),
+    ("MK", "Milton Keynes", 52.04, -0.76),
+    ("ML", "Motherwell", 55.79, -3.99),
+    ("N", "North London", 51.57, -0.1),
+    ("NE", "Newcastle upon Tyne", 54.97, -1.61),
+    ("NG", "Nottingham", 52.95, -1.15),
+    ("NN", "Northampton", 52.24, -0.9),
+    ("NP", "Newport", 51.59, -3.0),
+    ("NR", "Norwich", 52.63, 1.3),
+    ("NW", "North West London", 51.55, -0.19),
+    ("OL", "Oldham", 53.54, -2.12),
+    ("OX", "Oxford", 51.75, -1.26),
+    ("PA", "Paisley", 55.85, -4.42),
+    ("PE", "Peterborough", 52.57, -0.24),
+    ("PH", "Perth", 56.4, -3.43),
+    ("PL", "Plymouth", 50.38, -4.14),
+    ("PO", "Portsmouth", 50.8, -1.09),
+    ("PR", "Preston", 53.76, -2.7),
+    ("RG", "Reading", 51.45, -0.97),
+    ("RH", "Redhill", 51.24, -0.17),
+    ("RM", "Romford", 51.58, 0.18),
+    ("S", "Sheffield", 53.38, -1.47),
+    ("SA", "Swansea", 51.62, -3.94),
+    ("SE", "South East London", 51.47, -0.05),
+    ("SG", "Stevenage", 51.9, -0.2),
+    ("SK", "Stockport", 53.41, -2.16),
+    ("SL", "Slough", 51.51, -0.59),
+    ("SM", "Sutton", 51.36, -0.19),
+    ("SN", "Swindon", 51.56, -1.78),
+    ("SO", "Southampton", 50.91, -1.4),
+    ("SP", "Salisbury", 51.07, -1.79),
+    ("SR", "Sunderland", 54.91, -1.38),
+    ("SS", "Southend-on-Sea", 51.54, 0.71),
+    ("ST", "Stoke-on-Trent", 53.0, -2.18),
+    ("SW", "South West London", 51.46, -0.17),
+    ("SY", "Shrewsbury", 52.71, -2.75),
+    ("TA", "Taunton", 51.02, -3.1),
+    ("TD", "Galashiels", 55.62, -2.81),
+    ("TF", "Telford", 52.68, -2.45),
+    ("TN", "Tonbridge", 51.2, 0.27),
+    ("TQ", "Torquay", 50.46, -3.53),
+    ("TR", "Truro", 50.26, -5.05),
+    ("TS", "Middlesbrough", 54.57, -1.23),
+    ("TW", "Twickenham", 51.45, -0.33),
+    ("UB", "Southall", 51.51, -0.38),
+    ("W", "West London", 51.51, -0.2),
+    ("WA", "Warrington", 53.39, -2.6),
+    ("WC", "West Central London", 51.52, -0.12),
+    ("WD", "Watford", 51.66, -0.4),
+    ("WF", "Wakefield", 53.68, -1.5),
+    ("WN", "Wigan", 53.55, -2.63),
+    ("WR", "Worcester", 52.19, -2.22),
+    ("WS", "Walsall", 52.59, -1.98),
+    ("WV", "Wolverhampton", 52.59, -2.13),
+    ("YO", "York", 53.96, -1.08),
+    ("ZE", "Lerwick", 60.15, -1.15),
+];
+
+/// Australian postcode ranges; capital-city ranges come first so they win over the
+/// state ranges that contain them
+const AU_RANGES: &[(u32, u32, &str, &str, f64, f64)] = &[
+    (800, 832, "metro", "Darwin", -12.46, 130.84),
+    (2000, 2234, "metro", "Sydney", -33.87, 151.21),
+    (2600, 2618, "metro", "Canberra", -35.28, 149.13),
+    (3000, 3207, "metro", "Melbourne", -37.81, 144.96),
+    (4000, 4207, "metro", "Brisbane", -27.47, 153.03),
+    (5000, 5199, "metro", "Adelaide", -34.93, 138.6),
+    (6000, 6199, "metro", "Perth", -31.95, 115.86),
+    (7000, 7099, "metro", "Hobart", -42.88, 147.33),
+    (
+        200,
+        299,
+        "state",
+        "Australian Capital Territory",
+        -35.5,
+        149.0,
+    ),
+    (800, 999, "state", "Northern Territory", -19.4, 133.4),
+    (1000, 2599, "state", "New South Wales", -32.2, 147.0),
+    (2619, 2899, "state", "New South Wales", -32.2, 147.0),
+    (
+        2900,
+        2920,
+        "state",
+        "Australian Capital Territory",
+        -35.5,
+        149.0,
+    ),
+    (2921, 2999, "state", "New South Wales", -32.2, 147.0),
+    (3000, 3999, "state", "Victoria", -36.9, 144.3),
+    (4000, 4999, "state", "Queensland", -22.5, 144.4),
+    (5000, 5999, "state", "South Australia", -30.1, 135.8),
+    (6000, 6999, "state", "Western Australia", -25.3, 122.3),
+    (7000, 7999, "state", "Tasmania", -42.0, 146.6),
+    (8000, 8999, "state", "Victoria", -36.9, 144.3),
+    (9000, 9999, "state", "Queensland", -22.5, 144.4),
+];
+
+/// German two-digit postal regions of large cities
+const DE_CITIES: &[(u32, u32, &str, f64, f64)] = &[
+    (1, 1, "Dresden", 51.05, 13.74),
+    (4, 4, "Leipzig", 51.34, 12.37),
+    (10, 14, "Berlin", 52.52, 13.4),
+    (20, 22, "Hamburg", 53.55, 9.99),
+    (28, 28, "Bremen", 53.08, 8.8),
+    (30, 30, "Hannover", 52.37, 9.74),
+    (40, 40, "Düsseldorf", 51.23, 6.78),
+    (44, 44, "Dortmund", 51.51, 7.47),
+    (45, 45, "Essen", 51.46, 7.01),
+    (50, 51, "Köln", 50.94, 6.96),
+    (60, 60, "Frankfurt am Main", 50.11, 8.68),
+    (70, 70, "Stuttgart", 48.78, 9.18),
+    (80, 81, "München", 48.14, 11.58),
+    (90, 90, "Nürnberg", 49.45, 11.08),
+];
+
+/// German postal zones by first digit
+const DE_ZONES: &[(&str, f64, f64)] = &[
+    ("Saxony and eastern Thuringia", 51.0, 13.0),
+    ("Berlin, Brandenburg and Mecklenburg-Vorpommern", 52.9, 13.2),
+    ("Hamburg, Bremen and Schleswig-Holstein", 53.7, 9.7),
+    ("Lower Saxony, Saxony-Anhalt and northern Hesse", 52.0, 10.5),
+    ("Ruhr, Münsterland and Osnabrück", 51.8, 7.4),
+    ("Rhineland, Cologne and Koblenz", 50.6, 7.0),
+    ("Hesse, Saarland and the Palatinate", 49.8, 8.3),
+    ("Baden-Württemberg", 48.5, 8.9),
+    ("Southern Bavaria", 48.0, 11.5),
+    ("Franconia, Upper Palatinate and Thuringia", 49.9, 11.0),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PostalCodeInput {
+    /// Postal codes to check
+    pub postal_codes: Vec<String>,
+    /// Country of the codes as an ISO code or English name (default: every country
+    /// whose format matches, with a centroid only when exactly one does)
+    pub country: Option<String>,
+}
+
+/// Location in the shape proximity_search takes for its points
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Centroid {
+    /// Latitude in decimal degrees
+    pub lat: f64,
+    /// Longitude in decimal degrees
+    pub lon: f64,
+    /// Standard form of the postal code
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PostalCodeResult {
+    pub input: String,
+    pub valid: bool,
+    /// ISO code of the country the code was validated against
+    pub country: Option<String>,
+    /// Code in the country's standard spacing and case
+    pub normalized: Option<String>,
+    /// Countries whose format the code matches, when no country was given
+    pub matching_countries: Vec<String>,
+    /// Approximate location of the area the code belongs to
+    #[tool_types(nested)]
+    pub centroid: Option<Centroid>,
+    /// Area the centroid stands for, like "Illinois" or "Manchester"
+    pub centroid_area: Option<String>,
+    /// Size of that area: "metro", "state", "province", "postcode_area" or "zone"
+    pub centroid_precision: Option<String>,
+    /// Why the code is invalid
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PostalCodeOutput {
+    #[tool_types(nested)]
+    pub results: Vec<PostalCodeResult>,
+    pub valid_count: usize,
+    pub invalid_count: usize,
+    /// Centroids of the valid codes, ready to pass to proximity_search
+    #[tool_types(nested)]
+    pub centroids: Vec<Centroid>,
+}
+
+/// Looks up a country's postal format by ISO code or English name, ignoring case
+pub fn find_format(country: &str) -> Option<&'static PostalFormat> {
+    let key = country.trim();
+    FORMATS
+        .iter()
+        .find(|f| f.code.eq_ignore_ascii_case(key) || f.name.eq_ignore_ascii_case(key))
+}
+
+/// The code in the country's standard form, or None when it doesn't match the format
+pub fn normalize_postal_code(format: &PostalFormat, code: &str) -> Option<String> {
+    let cleaned = code
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase();
+    let captures = Regex::new(format.pattern).unwrap().captures(&cleaned)?;
+    Some(
+        captures
+            .iter()
+            .skip(1)
+            .flatten()
+            .map(|m| m.as_str())
+            .collect::<Vec<_>>()
+            .join(format.separator),
+    )
+}
+
+fn leading_number(code: &str, digits: usize) -> Option<u32> {
+    code.get(..digits)?.parse().ok()
+}
+
+/// Approximate location of a normalized code as (area, precision, lat, lon), for the
+/// countries with embedded data
+pub fn lookup_centroid(country: &str, code: &str) -> Option<(String, &'static str, f64, f64)> {
+    match country {
+        "US" => {
+            let prefix = leading_number(code, 3)?;
+            if let Some(&(_, _, name, lat, lon)) = US_METROS
+                .iter()
+                .find(|(lo, hi, ..)| (*lo..=*hi).contains(&prefix))
+            {
+                return Some((name.to_string(), "metro", lat, lon));
+            }
+            let &(_, _, state) = US_PREFIXES
+                .iter()
+                .find(|(lo, hi, _)| (*lo..=*hi).contains(&prefix))?;
+            let &(_, name, lat, lon) = US_STATES.iter().find(|s| s.0 == state)?;
+            Some((name.to_string(), "state", lat, lon))
+        }
+        "CA" => {
+            let letter = code.chars().next()?;
+            let &(_, name, lat, lon) = CA_DISTRICTS.iter().find(|d| d.0 == letter)?;
+            Some((name.to_string(), "province", lat, lon))
+        }
+        "GB" => {
+            let area: String = code
+                .chars()
+                .take_while(|c| c.is_ascii_alphabetic())
+                .collect();
+            let &(_, name, lat, lon) = GB_AREAS.iter().find(|a| a.0 == area)?;
+            Some((name.to_string(), "postcode_area", lat, lon))
+        }
+        "AU" => {
+            let number = leading_number(code, 4)?;
+            let &(_, _, precision, name, lat, lon) = AU_RANGES
+                .iter()
+                .find(|(lo, hi, ..)| (*lo..=*hi).contains(&number))?;
+            Some((name.to_string(), precision, lat, lon))
+        }
+        "DE" => {
+            let region = leading_number(code, 2)?;
+            if let Some(&(_, _, name, lat, lon)) = DE_CITIES
+                .iter()
+                .find(|(lo, hi, ..)| (*lo..=*hi).contains(&region))
+            {
+                return Some((name.to_string(), "metro", lat, lon));
+            }
+            let &(name, lat, lon) = DE_ZONES.get((region / 10) as usize)?;
+            Some((name.to_string(), "zone", lat, lon))
+        }
+        _ => None,
+    }
+}
+
+fn check_code(input: &str, format: Option<&'static PostalFormat>) -> PostalCodeResult {
+    let mut result = PostalCodeResult {
+        input: input.to_string(),
+        valid: false,
+        country: None,
+        normalized: None,
+        matching_countries: Vec::new(),
+        centroid: None,
+        centroid_area: None,
+        centroid_precision: None,
+        error: None,
+    };
+    let matched: Vec<(&PostalFormat, String)> = match format {
+        Some(format) => {
+            result.country = Some(format.code.to_string());
+            match normalize_postal_code(format, input) {
+                Some(normalized) => vec![(format, normalized)],
+                None => {
+                    result.error = Some(format!(
+                        "Not a valid {} postal code, expected a code like {}",
+                        format.name, format.example
+                    ));
+                    return result;
+                }
+            }
+        }
+        None => {
+            let matched: Vec<_> = FORMATS
+                .iter()
+                .filter_map(|f| normalize_postal_code(f, input).map(|n| (f, n)))
+                .collect();
+            result.matching_countries = matched.iter().map(|(f, _)| f.code.to_string()).collect();
+            matched
+        }
+    };
+    if matched.is_empty() {
+        result.error = Some("Does not match the postal code format of any country".to_string());
+        return result;
+    }
+    result.valid = true;
+    // Without a country the standard form and centroid only make sense for one match
+    if let [(format, normalized)] = matched.as_slice() {
+        result.country = Some(format.code.to_string());
+        if let Some((area, precision, lat, lon)) = lookup_centroid(format.code, normalized) {
+            result.centroid = Some(Centroid {
+                lat,
+                lon,
+                id: Some(normalized.clone()),
+            });
+            result.centroid_area = Some(area);
+            result.centroid_precision = Some(precision.to_string());
+        }
+        result.normalized = Some(normalized.clone());
+    }
+    result
+}
+
+pub fn postal_code_logic(input: PostalCodeInput) -> Result<PostalCodeOutput, String> {
+    if input.postal_codes.is_empty() {
+        return Err("postal_codes cannot be empty".to_string());
+    }
+    if input.postal_codes.len() > MAX_CODES {
+        return Err(format!(
+            "At most {MAX_CODES} postal codes can be checked at once"
+        ));
+    }
+    let format = match input.country.as_deref() {
+        Some(country) => Some(find_format(country).ok_or_else(|| {
+            let codes: Vec<&str> = FORMATS.iter().map(|f| f.code).collect();
+            format!(
+                "Unsupported country '{country}', use one of {}",
+                codes.join(", ")
+            )
+        })?),
+        None => None,
+    };
+
+    let results: Vec<PostalCodeResult> = input
+        .postal_codes
+        .iter()
+        .map(|code| check_code(code, format))
+        .collect();
+    let valid_count = results.iter().filter(|r| r.valid).count();
+    let centroids = results.iter().filter_map(|r| r.centroid.clone()).collect();
+    Ok(PostalCodeOutput {
+        invalid_count: results.len() - valid_count,
+        valid_count,
+        results,
+        centroids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(codes: &[&str], country: Option<&str>) -> PostalCodeOutput {
+        postal_code_logic(PostalCodeInput {
+            postal_codes: codes.iter().map(|c| c.to_string()).collect(),
+            country: country.map(str::to_string),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_us_codes_normalize_and_locate() {
+        let output = check(&["62704", "60601 1234", "1234", "99501"], Some("us"));
+        assert_eq!(output.valid_count, 3);
+        assert_eq!(output.invalid_count, 1);
+        let springfield = &output.results[0];
+        assert_eq!(springfield.centroid_area.as_deref(), Some("Illinois"));
+        assert_eq!(springfield.centroid_precision.as_deref(), Some("state"));
+        let chicago = &output.results[1];
+        assert_eq!(chicago.normalized.as_deref(), Some("60601-1234"));
+        assert_eq!(chicago.centroid_area.as_deref(), Some("Chicago"));
+        assert!(
+            output.results[2]
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("62704-1234")
+        );
+        assert_eq!(output.results[3].centroid_area.as_deref(), Some("Alaska"));
+        assert_eq!(output.centroids.len(), 3);
+        assert_eq!(output.centroids[1].id.as_deref(), Some("60601-1234"));
+    }
+
+    #[test]
+    fn test_unique_formats_detect_country() {
+        let output = check(&["sw1a1aa", "k1a 0b1", "1012lg"], None);
+        let countries: Vec<_> = output
+            .results
+            .iter()
+            .map(|r| r.country.as_deref().unwrap())
+            .collect();
+        assert_eq!(countries, vec!["GB", "CA", "NL"]);
+        assert_eq!(output.results[0].normalized.as_deref(), Some("SW1A 1AA"));
+        assert_eq!(
+            output.results[0].centroid_area.as_deref(),
+            Some("South West London")
+        );
+        assert_eq!(
+            output.results[1].centroid_area.as_deref(),
+            Some("Eastern Ontario")
+        );
+        // The Netherlands has no embedded centroids
+        assert!(output.results[2].centroid.is_none());
+    }
+
+    #[test]
+    fn test_ambiguous_code_lists_countries_without_centroid() {
+        let result = &check(&["75008"], None).results[0];
+        assert!(result.valid);
+        assert!(result.matching_countries.contains(&"FR".to_string()));
+        assert!(result.matching_countries.contains(&"US".to_string()));
+        assert_eq!(result.country, None);
+        assert!(result.centroid.is_none());
+        let result = &check(&["75008"], Some("France")).results[0];
+        assert_eq!(result.country.as_deref(), Some("FR"));
+        assert!(result.matching_countries.is_empty());
+    }
+
+    #[test]
+    fn test_australia_and_germany_prefer_cities() {
+        let output = check(&["2000", "2480", "0872"], Some("AU"));
+        let areas: Vec<_> = output
+            .results
+            .iter()
+            .map(|r| r.centroid_area.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            areas,
+            vec!["Sydney", "New South Wales", "Northern Territory"]
+        );
+
+        let output = check(&["10117", "79098"], Some("de"));
+        assert_eq!(output.results[0].centroid_area.as_deref(), Some("Berlin"));
+        assert_eq!(
+            output.results[1].centroid_precision.as_deref(),
+            Some("zone")
+        );
+        assert_eq!(
+            output.results[1].centroid_area.as_deref(),
+            Some("Baden-Württemberg")
+        );
+    }
+
+    #[test]
+    fn test_formats_with_separators() {
+        let output = check(&["1000001"], Some("JP"));
+        assert_eq!(output.results[0].normalized.as_deref(), Some("100-0001"));
+        let output = check(&["00950", "00-950"], Some("Poland"));
+        assert_eq!(output.results[0].normalized.as_deref(), Some("00-950"));
+        assert_eq!(output.results[1].normalized.as_deref(), Some("00-950"));
+        let output = check(&["d02x285"], Some("IE"));
+        assert_eq!(output.results[0].normalized.as_deref(), Some("D02 X285"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = postal_code_logic(PostalCodeInput {
+            postal_codes: vec!["12345".to_string()],
+            country: Some("Atlantis".to_string()),
+        })
+        .unwrap_err();
+        assert!(error.starts_with("Unsupported country 'Atlantis', use one of US, CA"));
+        assert!(
+            postal_code_logic(PostalCodeInput {
+                postal_codes: vec![],
+                country: None,
+            })
+            .is_err()
+        );
+        let result = &check(&["not a code!"], None).results[0];
+        assert!(!result.valid);
+        assert!(result.error.as_ref().unwrap().contains("any country"));
+    }
+}
@@ -1,153 +1,82 @@
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
     CaptureGroup as LogicGroup, Match as LogicMatch, PatternInfo as LogicInfo,
     RegexFlags as LogicFlags, RegexMatcherInput as LogicInput, RegexMatcherResult as LogicOutput,
+    match_regex,
 };
 
-// Define wrapper types with JsonSchema for FTL-SDK
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct RegexMatcherInput {
-    /// Text to match against
-    pub text: String,
-    /// Regular expression pattern
-    pub pattern: String,
-    /// Whether to find all matches (vs just first)
-    pub find_all: Option<bool>,
-    /// Whether to capture groups
-    pub capture_groups: Option<bool>,
-    /// Regex flags (case_insensitive, multiline, dot_all)
-    pub flags: Option<RegexFlags>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct RegexFlags {
-    /// Case insensitive matching
-    pub case_insensitive: Option<bool>,
-    /// Multiline mode (^ and $ match line boundaries)
-    pub multiline: Option<bool>,
-    /// Dot matches newline
-    pub dot_all: Option<bool>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct RegexMatcherResult {
-    /// Whether any match was found
-    pub has_match: bool,
-    /// Number of matches found
-    pub match_count: usize,
-    /// All matches found
-    pub matches: Vec<Match>,
-    /// Pattern validation info
-    pub pattern_info: PatternInfo,
-    /// Error if pattern is invalid
-    pub error: Option<String>,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    CaptureGroupSchema as CaptureGroup, MatchSchema as Match, PatternInfoSchema as PatternInfo,
+    RegexFlagsSchema as RegexFlags, RegexMatcherResultSchema as RegexMatcherResult,
+};
 
+// The generated input plus options handled by this wrapper
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct Match {
-    /// The matched text
-    pub text: String,
-    /// Start position in the input
-    pub start: usize,
-    /// End position in the input
-    pub end: usize,
-    /// Captured groups (if any)
-    pub groups: Option<Vec<CaptureGroup>>,
+pub struct RegexMatcherInput {
+    #[serde(flatten)]
+    pub params: logic::RegexMatcherInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct CaptureGroup {
-    /// Group index (0 is full match)
-    pub index: usize,
-    /// Group name (if named)
-    pub name: Option<String>,
-    /// Captured text
-    pub text: Option<String>,
-    /// Start position
-    pub start: Option<usize>,
-    /// End position
-    pub end: Option<usize>,
-}
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn regex_matcher(request: Request<RegexMatcherInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "regex_matcher",
+                RegexMatcherInput,
+                RegexMatcherResult
+            ));
+        }
+    };
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct PatternInfo {
-    /// The pattern used
-    pub pattern: String,
-    /// Whether pattern is valid
-    pub is_valid: bool,
-    /// Number of capture groups
-    pub capture_group_count: usize,
-    /// Flags applied
-    pub flags_applied: String,
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("regex_matcher", &input);
+    log.finish(run_regex_matcher(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn regex_matcher(input: RegexMatcherInput) -> ToolResponse {
-    // Convert to logic types
-    let logic_input = LogicInput {
-        text: input.text,
-        pattern: input.pattern,
-        find_all: input.find_all,
-        capture_groups: input.capture_groups,
-        flags: input.flags.map(|f| LogicFlags {
-            case_insensitive: f.case_insensitive,
-            multiline: f.multiline,
-            dot_all: f.dot_all,
-        }),
-    };
+#[cfg(feature = "individual")]
+fn run_regex_matcher(input: RegexMatcherInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("text", &input.params.text)
+        .pattern("pattern", &input.params.pattern)
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
 
     // Call logic implementation
-    let result = match logic::match_regex(logic_input) {
+    let logic_input: LogicInput = input.params.into();
+    let result = match ftl_result_cache::cached("regex_matcher", &logic_input, || {
+        logic::match_regex(logic_input.clone())
+    }) {
         Ok(result) => result,
         Err(e) => return ToolResponse::text(format!("Error matching regex: {e}")),
     };
 
-    // Convert back to wrapper types
-    let regex_result = RegexMatcherResult {
-        has_match: result.has_match,
-        match_count: result.match_count,
-        matches: result
-            .matches
-            .into_iter()
-            .map(|m| Match {
-                text: m.text,
-                start: m.start,
-                end: m.end,
-                groups: m.groups.map(|groups| {
-                    groups
-                        .into_iter()
-                        .map(|g| CaptureGroup {
-                            index: g.index,
-                            name: g.name,
-                            text: g.text,
-                            start: g.start,
-                            end: g.end,
-                        })
-                        .collect()
-                }),
-            })
-            .collect(),
-        pattern_info: PatternInfo {
-            pattern: result.pattern_info.pattern,
-            is_valid: result.pattern_info.is_valid,
-            capture_group_count: result.pattern_info.capture_group_count,
-            flags_applied: result.pattern_info.flags_applied,
-        },
-        error: result.error,
-    };
-
     ToolResponse::text(
-        serde_json::to_string(&regex_result)
+        ftl_json::to_string(&RegexMatcherResult::from(result))
             .unwrap_or_else(|_| "Error serializing result".to_string()),
     )
 }
@@ -1,7 +1,9 @@
+use ftl_pagination::{PageInfo, PageRequest};
+use ftl_tool_types_derive::ToolTypes;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct RegexMatcherInput {
     /// Text to match against
     pub text: String,
@@ -12,10 +14,22 @@ pub struct RegexMatcherInput {
     /// Whether to capture groups
     pub capture_groups: Option<bool>,
     /// Regex flags (case_insensitive, multiline, dot_all)
+    #[tool_types(nested)]
     pub flags: Option<RegexFlags>,
+    /// Number of matches to skip before the returned page (default: 0)
+    pub offset: Option<usize>,
+    /// Maximum number of matches to return (default: all)
+    pub limit: Option<usize>,
+    /// Cursor from a previous response's `page_info.next_cursor`, in place of `offset`
+    pub cursor: Option<String>,
+    // Read by the wrapper only; left out of the serialized form so it does not
+    // split result cache entries
+    /// Append execution metrics (duration and input/output size) to the response
+    #[serde(skip_serializing)]
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct RegexFlags {
     /// Case insensitive matching
     pub case_insensitive: Option<bool>,
@@ -25,21 +39,25 @@ pub struct RegexFlags {
     pub dot_all: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct RegexMatcherResult {
     /// Whether any match was found
     pub has_match: bool,
     /// Number of matches found
     pub match_count: usize,
     /// All matches found
+    #[tool_types(nested)]
     pub matches: Vec<Match>,
     /// Pattern validation info
+    #[tool_types(nested)]
     pub pattern_info: PatternInfo,
     /// Error if pattern is invalid
     pub error: Option<String>,
+    /// Position of the returned matches within all matches
+    pub page_info: PageInfo,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct Match {
     /// The matched text
     pub text: String,
@@ -48,10 +66,11 @@ pub struct Match {
     /// End position in the input
     pub end: usize,
     /// Captured groups (if any)
+    #[tool_types(nested)]
     pub groups: Option<Vec<CaptureGroup>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct CaptureGroup {
     /// Group index (0 is full match)
     pub index: usize,
@@ -65,7 +84,7 @@ pub struct CaptureGroup {
     pub end: Option<usize>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct PatternInfo {
     /// The pattern used
     pub pattern: String,
@@ -78,6 +97,8 @@ pub struct PatternInfo {
 }
 
 pub fn match_regex(input: RegexMatcherInput) -> Result<RegexMatcherResult, String> {
+    let page = PageRequest::from_input(input.offset, input.limit, input.cursor.as_deref())?;
+
     // Build regex with flags
     let mut pattern_with_flags = String::new();
     let mut flags_applied = Vec::new();
@@ -114,6 +135,7 @@ pub fn match_regex(input: RegexMatcherInput) -> Result<RegexMatcherResult, Strin
                     flags_applied: flags_applied.join(", "),
                 },
                 error: Some(format!("Invalid regex pattern: {e}")),
+                page_info: page.info(0),
             });
         }
     };
@@ -121,11 +143,16 @@ pub fn match_regex(input: RegexMatcherInput) -> Result<RegexMatcherResult, Strin
     let capture_groups = input.capture_groups.unwrap_or(false);
     let find_all = input.find_all.unwrap_or(true);
 
-    // Find matches
+    // Find matches, keeping only the requested page
     let mut matches = Vec::new();
+    let mut match_count = 0;
 
     if find_all {
-        for cap in regex.captures_iter(&input.text) {
+        for (index, cap) in regex.captures_iter(&input.text).enumerate() {
+            match_count += 1;
+            if !page.contains(index) {
+                continue;
+            }
             let full_match = cap.get(0).unwrap();
             let mut match_item = Match {
                 text: full_match.as_str().to_string(),
@@ -165,7 +192,9 @@ pub fn match_regex(input: RegexMatcherInput) -> Result<RegexMatcherResult, Strin
         }
     } else {
         // Find only first match
-        if let Some(cap) = regex.captures(&input.text) {
+        let first = regex.captures(&input.text);
+        match_count = usize::from(first.is_some());
+        if let Some(cap) = first.filter(|_| page.contains(0)) {
             let full_match = cap.get(0).unwrap();
             let mut match_item = Match {
                 text: full_match.as_str().to_string(),
@@ -204,8 +233,7 @@ pub fn match_regex(input: RegexMatcherInput) -> Result<RegexMatcherResult, Strin
         }
     }
 
-    let has_match = !matches.is_empty();
-    let match_count = matches.len();
+    let has_match = match_count > 0;
 
     Ok(RegexMatcherResult {
         has_match,
@@ -222,6 +250,7 @@ pub fn match_regex(input: RegexMatcherInput) -> Result<RegexMatcherResult, Strin
             },
         },
         error: None,
+        page_info: page.info(match_count),
     })
 }
 
@@ -237,6 +266,10 @@ mod tests {
             find_all: None,
             capture_groups: None,
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -254,6 +287,10 @@ mod tests {
             find_all: Some(true),
             capture_groups: None,
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -271,6 +308,10 @@ mod tests {
             find_all: None,
             capture_groups: None,
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(!result.has_match);
@@ -289,6 +330,10 @@ mod tests {
                 multiline: None,
                 dot_all: None,
             }),
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -303,6 +348,10 @@ mod tests {
             find_all: None,
             capture_groups: Some(true),
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -326,6 +375,10 @@ mod tests {
                 multiline: Some(true),
                 dot_all: None,
             }),
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -344,6 +397,10 @@ mod tests {
                 multiline: None,
                 dot_all: Some(true),
             }),
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -357,6 +414,10 @@ mod tests {
             find_all: None,
             capture_groups: None,
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(!result.has_match);
@@ -373,6 +434,10 @@ mod tests {
             find_all: Some(false),
             capture_groups: None,
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -388,6 +453,10 @@ mod tests {
             find_all: Some(true),
             capture_groups: None,
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -403,6 +472,10 @@ mod tests {
             find_all: Some(true),
             capture_groups: None,
             flags: None,
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.has_match);
@@ -423,6 +496,10 @@ mod tests {
                 multiline: Some(true),
                 dot_all: None,
             }),
+            offset: None,
+            limit: None,
+            cursor: None,
+            include_metrics: None,
         };
         let result = match_regex(input).unwrap();
         assert!(result.pattern_info.is_valid);
@@ -435,4 +512,79 @@ mod tests {
         );
         assert!(result.pattern_info.flags_applied.contains("multiline"));
     }
+
+    #[test]
+    fn test_find_all_pagination() {
+        let input = RegexMatcherInput {
+            text: "a1 b2 c3 d4 e5".to_string(),
+            pattern: r"\w\d".to_string(),
+            find_all: Some(true),
+            capture_groups: None,
+            flags: None,
+            offset: Some(2),
+            limit: Some(2),
+            cursor: None,
+            include_metrics: None,
+        };
+        let result = match_regex(input).unwrap();
+        assert_eq!(result.match_count, 5);
+        let texts: Vec<&str> = result.matches.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["c3", "d4"]);
+        assert_eq!(result.page_info.offset, 2);
+        assert_eq!(result.page_info.total, 5);
+        assert!(result.page_info.truncated);
+
+        // The cursor picks up where the previous page ended
+        let input = RegexMatcherInput {
+            text: "a1 b2 c3 d4 e5".to_string(),
+            pattern: r"\w\d".to_string(),
+            find_all: Some(true),
+            capture_groups: None,
+            flags: None,
+            offset: None,
+            limit: None,
+            cursor: result.page_info.next_cursor,
+            include_metrics: None,
+        };
+        let result = match_regex(input).unwrap();
+        let texts: Vec<&str> = result.matches.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["e5"]);
+        assert!(result.page_info.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_page_past_last_match() {
+        let input = RegexMatcherInput {
+            text: "cat bat".to_string(),
+            pattern: "at".to_string(),
+            find_all: Some(true),
+            capture_groups: None,
+            flags: None,
+            offset: Some(5),
+            limit: None,
+            cursor: None,
+            include_metrics: None,
+        };
+        let result = match_regex(input).unwrap();
+        assert!(result.has_match);
+        assert_eq!(result.match_count, 2);
+        assert!(result.matches.is_empty());
+        assert!(!result.page_info.truncated);
+    }
+
+    #[test]
+    fn test_zero_limit_rejected() {
+        let input = RegexMatcherInput {
+            text: "cat".to_string(),
+            pattern: "at".to_string(),
+            find_all: None,
+            capture_groups: None,
+            flags: None,
+            offset: None,
+            limit: Some(0),
+            cursor: None,
+            include_metrics: None,
+        };
+        assert!(match_regex(input).is_err());
+    }
 }
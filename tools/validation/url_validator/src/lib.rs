@@ -1,122 +1,77 @@
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
 mod logic;
 
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
 use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[cfg(not(test))]
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
 use ftl_sdk::tool;
 
 // Re-export types from logic module
 pub use logic::{
     UrlComponents as LogicComponents, UrlValidatorInput as LogicInput,
-    UrlValidatorResult as LogicOutput, ValidationChecks as LogicChecks,
+    UrlValidatorResult as LogicOutput, ValidationChecks as LogicChecks, validate_url,
 };
 
-// Define wrapper types with JsonSchema for FTL-SDK
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct UrlValidatorInput {
-    /// URL to validate
-    pub url: String,
-    /// Whether to require HTTPS
-    pub require_https: Option<bool>,
-    /// Allowed schemes (if specified, only these are valid)
-    pub allowed_schemes: Option<Vec<String>>,
-}
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    UrlComponentsSchema as UrlComponents, UrlValidatorResultSchema as UrlValidatorResult,
+    ValidationChecksSchema as ValidationChecks,
+};
 
+// The generated input plus options handled by this wrapper
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct UrlValidatorResult {
-    /// Whether the URL is valid
-    pub is_valid: bool,
-    /// Error message if invalid
-    pub error: Option<String>,
-    /// Parsed URL components
-    pub components: Option<UrlComponents>,
-    /// Validation checks performed
-    pub checks: ValidationChecks,
+pub struct UrlValidatorInput {
+    #[serde(flatten)]
+    pub params: logic::UrlValidatorInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct UrlComponents {
-    /// URL scheme (http, https, ftp, etc.)
-    pub scheme: String,
-    /// Host/domain
-    pub host: Option<String>,
-    /// Port number
-    pub port: Option<u16>,
-    /// Path component
-    pub path: String,
-    /// Query string
-    pub query: Option<String>,
-    /// Fragment/anchor
-    pub fragment: Option<String>,
-    /// Username (if present)
-    pub username: Option<String>,
-    /// Whether a password is present
-    pub has_password: bool,
-}
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn url_validator(request: Request<UrlValidatorInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "url_validator",
+                UrlValidatorInput,
+                UrlValidatorResult
+            ));
+        }
+    };
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct ValidationChecks {
-    /// Valid URL syntax
-    pub valid_syntax: bool,
-    /// Has a scheme
-    pub has_scheme: bool,
-    /// Has a host
-    pub has_host: bool,
-    /// Scheme is allowed
-    pub scheme_allowed: bool,
-    /// Uses HTTPS (if required)
-    pub https_required_met: bool,
-    /// No credentials in URL
-    pub no_credentials: bool,
-    /// Valid port number
-    pub valid_port: bool,
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("url_validator", &input);
+    log.finish(run_url_validator(input))
 }
 
-#[cfg_attr(not(test), tool)]
-pub fn url_validator(input: UrlValidatorInput) -> ToolResponse {
-    // Convert to logic types
-    let logic_input = LogicInput {
-        url: input.url,
-        require_https: input.require_https,
-        allowed_schemes: input.allowed_schemes,
-    };
+#[cfg(feature = "individual")]
+fn run_url_validator(input: UrlValidatorInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("url", &input.params.url).finish() {
+        return ToolResponse::text(e.to_json());
+    }
 
     // Call logic implementation
-    let result = match logic::validate_url(logic_input) {
+    let logic_input: LogicInput = input.params.into();
+    let result = match ftl_result_cache::cached("url_validator", &logic_input, || {
+        logic::validate_url(logic_input.clone())
+    }) {
         Ok(result) => result,
         Err(e) => return ToolResponse::text(format!("Error validating URL: {e}")),
     };
 
-    // Convert back to wrapper types
-    let url_result = UrlValidatorResult {
-        is_valid: result.is_valid,
-        error: result.error,
-        components: result.components.map(|c| UrlComponents {
-            scheme: c.scheme,
-            host: c.host,
-            port: c.port,
-            path: c.path,
-            query: c.query,
-            fragment: c.fragment,
-            username: c.username,
-            has_password: c.has_password,
-        }),
-        checks: ValidationChecks {
-            valid_syntax: result.checks.valid_syntax,
-            has_scheme: result.checks.has_scheme,
-            has_host: result.checks.has_host,
-            scheme_allowed: result.checks.scheme_allowed,
-            https_required_met: result.checks.https_required_met,
-            no_credentials: result.checks.no_credentials,
-            valid_port: result.checks.valid_port,
-        },
-    };
-
     ToolResponse::text(
-        serde_json::to_string(&url_result)
+        ftl_json::to_string(&UrlValidatorResult::from(result))
             .unwrap_or_else(|_| "Error serializing result".to_string()),
     )
 }
@@ -1,7 +1,8 @@
+use ftl_tool_types_derive::ToolTypes;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct UrlValidatorInput {
     /// URL to validate
     pub url: String,
@@ -11,19 +12,21 @@ pub struct UrlValidatorInput {
     pub allowed_schemes: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct UrlValidatorResult {
     /// Whether the URL is valid
     pub is_valid: bool,
     /// Error message if invalid
     pub error: Option<String>,
     /// Parsed URL components
+    #[tool_types(nested)]
     pub components: Option<UrlComponents>,
     /// Validation checks performed
+    #[tool_types(nested)]
     pub checks: ValidationChecks,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct UrlComponents {
     /// URL scheme (http, https, ftp, etc.)
     pub scheme: String,
@@ -43,7 +46,7 @@ pub struct UrlComponents {
     pub has_password: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
 pub struct ValidationChecks {
     /// Valid URL syntax
     pub valid_syntax: bool,
@@ -0,0 +1,97 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+mod logic;
+
+use ftl_guard::Guard;
+use ftl_introspect::{Request, describe};
+use ftl_log::ToolLog;
+use ftl_sdk::ToolResponse;
+#[cfg(not(test))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{ValidationCategoryInput as LogicInput, ValidationCategoryResult as LogicOutput};
+
+// Define wrapper types with JsonSchema for FTL-SDK
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ValidationCategoryInput {
+    /// Kind of validation: "email", "url", "regex", "check_digit", "address",
+    /// "postal_code"
+    pub kind: String,
+    /// Value to validate (the email address, URL, text to match, number ending in a
+    /// check digit, postal address, or postal code)
+    pub value: String,
+    /// Extra options for the selected validator, e.g. {"require_https": true} for "url",
+    /// {"pattern": "\\d+", "find_all": true} for "regex" (pattern is required),
+    /// {"scheme": "luhn"} for "check_digit" (scheme is required) or {"country": "GB"}
+    /// for "address" and "postal_code"
+    pub options: Option<serde_json::Value>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ValidationCategoryResult {
+    /// Kind of validation performed
+    pub kind: String,
+    /// Value that was validated
+    pub value: String,
+    /// Whether the value passed validation (for "regex", whether the pattern matched; for
+    /// "address", whether every expected component was found)
+    pub is_valid: bool,
+    /// Full result from the underlying validator
+    pub details: serde_json::Value,
+}
+
+/// Validate emails, URLs, check digits, postal addresses and postal codes, or match regular
+/// expressions through a single `{"kind": ..., "value": ...}` interface
+#[cfg_attr(not(test), tool)]
+pub fn validation_category(request: Request<ValidationCategoryInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!(
+                "validation_category",
+                ValidationCategoryInput,
+                ValidationCategoryResult
+            ));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("validation_category", &input);
+    log.finish(run_validation_category(input))
+}
+
+fn run_validation_category(input: ValidationCategoryInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env().string("value", &input.value).finish() {
+        return ToolResponse::text(e.to_json());
+    }
+
+    // Convert to logic types
+    let logic_input = LogicInput {
+        kind: input.kind,
+        value: input.value,
+        options: input.options,
+    };
+
+    // Call logic implementation
+    let result = match logic::validate(logic_input) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    // Convert back to wrapper types
+    let response = ValidationCategoryResult {
+        kind: result.kind,
+        value: result.value,
+        is_valid: result.is_valid,
+        details: result.details,
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&response).unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationCategoryInput {
+    /// Kind of validation: "email", "url", "regex", "check_digit", "address", "postal_code"
+    pub kind: String,
+    /// Value to validate
+    pub value: String,
+    /// Extra options for the selected validator
+    pub options: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationCategoryResult {
+    /// Kind of validation performed
+    pub kind: String,
+    /// Value that was validated
+    pub value: String,
+    /// Whether the value passed validation
+    pub is_valid: bool,
+    /// Full result from the underlying validator
+    pub details: Value,
+}
+
+/// Supported validation kinds
+pub const KINDS: &[&str] = &[
+    "email",
+    "url",
+    "regex",
+    "check_digit",
+    "address",
+    "postal_code",
+];
+
+pub fn validate(input: ValidationCategoryInput) -> Result<ValidationCategoryResult, String> {
+    let kind = input.kind.trim().to_lowercase();
+
+    let (is_valid, details) = match kind.as_str() {
+        "email" => {
+            let args: email_validator_tool::LogicInput =
+                build_args(&kind, "email", input.value.as_str(), input.options)?;
+            let result = email_validator_tool::validate_email(args)?;
+            (result.is_valid, to_value(result)?)
+        }
+        "url" => {
+            let args: url_validator_tool::LogicInput =
+                build_args(&kind, "url", input.value.as_str(), input.options)?;
+            let result = url_validator_tool::validate_url(args)?;
+            (result.is_valid, to_value(result)?)
+        }
+        "regex" => {
+            let args: regex_matcher_tool::LogicInput =
+                build_args(&kind, "text", input.value.as_str(), input.options)?;
+            let result = regex_matcher_tool::match_regex(args)?;
+            (result.has_match, to_value(result)?)
+        }
+        "check_digit" => {
+            let args: check_digit_tool::LogicInput =
+                build_args(&kind, "value", input.value.as_str(), input.options)?;
+            let result = check_digit_tool::check_digit_logic(args)?;
+            (result.all_valid, to_value(result)?)
+        }
+        "address" => {
+            let args: address_parse_tool::LogicInput =
+                build_args(&kind, "address", input.value.as_str(), input.options)?;
+            let result = address_parse_tool::parse_address(args)?;
+            (result.missing.is_empty(), to_value(result)?)
+        }
+        "postal_code" => {
+            // The tool checks a list of codes; the category validates one
+            let args: postal_code_tool::LogicInput = build_args(
+                &kind,
+                "postal_codes",
+                vec![input.value.as_str()],
+                input.options,
+            )?;
+            let result = postal_code_tool::postal_code_logic(args)?;
+            (result.invalid_count == 0, to_value(result)?)
+        }
+        _ => {
+            return Err(format!(
+                "Unknown validation kind '{}'. Valid kinds: {}",
+                input.kind,
+                KINDS.join(", ")
+            ));
+        }
+    };
+
+    Ok(ValidationCategoryResult {
+        kind,
+        value: input.value,
+        is_valid,
+        details,
+    })
+}
+
+/// Merge the value into the options object under the validator's own field name
+fn build_args<T: for<'de> Deserialize<'de>>(
+    kind: &str,
+    value_field: &str,
+    value: impl Into<Value>,
+    options: Option<Value>,
+) -> Result<T, String> {
+    let mut args = match options {
+        None | Some(Value::Null) => Map::new(),
+        Some(Value::Object(map)) => map,
+        Some(_) => return Err("options must be a JSON object".to_string()),
+    };
+    args.insert(value_field.to_string(), value.into());
+
+    serde_json::from_value(Value::Object(args))
+        .map_err(|e| format!("Invalid options for '{kind}': {e}"))
+}
+
+fn to_value<T: Serialize>(result: T) -> Result<Value, String> {
+    serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(
+        kind: &str,
+        value: &str,
+        options: Option<Value>,
+    ) -> Result<ValidationCategoryResult, String> {
+        validate(ValidationCategoryInput {
+            kind: kind.to_string(),
+            value: value.to_string(),
+            options,
+        })
+    }
+
+    #[test]
+    fn test_valid_email() {
+        let result = check("email", "user@example.com", None).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.kind, "email");
+        assert_eq!(result.details["parts"]["domain"], "example.com");
+    }
+
+    #[test]
+    fn test_invalid_email() {
+        let result = check("email", "not-an-email", None).unwrap();
+        assert!(!result.is_valid);
+        assert!(result.details["error"].is_string());
+    }
+
+    #[test]
+    fn test_url_with_options() {
+        let result = check("url", "https://example.com/path", None).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.details["components"]["scheme"], "https");
+
+        let result = check(
+            "url",
+            "http://example.com",
+            Some(json!({ "require_https": true })),
+        )
+        .unwrap();
+        assert!(!result.is_valid);
+    }
+
+    #[test]
+    fn test_regex_match() {
+        let result = check(
+            "regex",
+            "order 42 and 7",
+            Some(json!({ "pattern": "\\d+", "find_all": true })),
+        )
+        .unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.details["match_count"], 2);
+    }
+
+    #[test]
+    fn test_regex_requires_pattern() {
+        let err = check("regex", "text", None).unwrap_err();
+        assert!(err.contains("Invalid options for 'regex'"));
+    }
+
+    #[test]
+    fn test_check_digit() {
+        let result = check(
+            "check_digit",
+            "4111 1111 1111 1111",
+            Some(json!({ "scheme": "luhn" })),
+        )
+        .unwrap();
+        assert!(result.is_valid);
+        assert_eq!(
+            result.details["results"][0]["normalized"],
+            "4111111111111111"
+        );
+
+        let result = check("check_digit", "2364", Some(json!({ "scheme": "verhoeff" }))).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.details["results"][0]["check_digit"], "3");
+    }
+
+    #[test]
+    fn test_address() {
+        let result = check(
+            "address",
+            "221B Baker Street, London, NW1 6XE",
+            Some(json!({ "country": "GB" })),
+        )
+        .unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.details["components"]["postal_code"], "NW1 6XE");
+
+        let result = check("address", "Baker Street", Some(json!({ "country": "GB" }))).unwrap();
+        assert!(!result.is_valid);
+        assert!(!result.details["missing"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_postal_code() {
+        let result = check("postal_code", "sw1a1aa", Some(json!({ "country": "GB" }))).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.details["results"][0]["normalized"], "SW1A 1AA");
+
+        let result = check("postal_code", "1234", Some(json!({ "country": "US" }))).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.details["invalid_count"], 1);
+    }
+
+    #[test]
+    fn test_kind_is_case_insensitive() {
+        let result = check(" EMAIL ", "user@example.com", None).unwrap();
+        assert_eq!(result.kind, "email");
+        assert!(result.is_valid);
+    }
+
+    #[test]
+    fn test_unknown_kind() {
+        let err = check("phone", "555-1234", None).unwrap_err();
+        assert!(err.contains("Unknown validation kind 'phone'"));
+    }
+
+    #[test]
+    fn test_options_must_be_object() {
+        let err = check("url", "https://example.com", Some(json!([1, 2]))).unwrap_err();
+        assert!(err.contains("options must be a JSON object"));
+    }
+}
@@ -0,0 +1,90 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+use ftl_precision::Precision;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    ColorStop as LogicColorStop, HeatmapBinInput as LogicInput, HeatmapBinOutput as LogicOutput,
+    HeatmapCell as LogicCell, MAX_BINS, MAX_VALUES, Triple as LogicTriple, heatmap_bin_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    ColorStopSchema as ColorStop, HeatmapBinOutputSchema as HeatmapBinOutput,
+    HeatmapCellSchema as HeatmapCell, TripleSchema as Triple,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HeatmapBinInput {
+    #[serde(flatten)]
+    pub params: logic::HeatmapBinInputSchema,
+    /// Rounding applied to numeric results, e.g. {"decimal_places": 10} (default: full precision)
+    pub precision: Option<Precision>,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Bin a numeric matrix or (x, y, value) points into a normalized 2D grid with colors,
+/// color-scale stops and optional outlier clipping
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn heatmap_bin(request: Request<HeatmapBinInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("heatmap_bin", HeatmapBinInput, HeatmapBinOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("heatmap_bin", &input);
+    log.finish(run_heatmap_bin(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_heatmap_bin(input: HeatmapBinInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .array("matrix", input.params.matrix.as_deref().unwrap_or_default())
+        .array_len(
+            "matrix",
+            input.params.matrix.iter().flatten().map(Vec::len).sum(),
+        )
+        .array("points", input.params.points.as_deref().unwrap_or_default())
+        .array(
+            "palette",
+            input.params.palette.as_deref().unwrap_or_default(),
+        )
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::heatmap_bin_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    match ftl_response::to_json_with_precision(
+        &HeatmapBinOutput::from(result),
+        None,
+        input.precision.as_ref(),
+    ) {
+        Ok(json) => ToolResponse::text(json),
+        Err(e) => ToolResponse::text(format!("Error: {e}")),
+    }
+}
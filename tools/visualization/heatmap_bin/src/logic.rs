@@ -0,0 +1,601 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+
+/// Most bins along either axis
+pub const MAX_BINS: usize = 500;
+/// Most points or matrix cells accepted
+pub const MAX_VALUES: usize = 1_000_000;
+/// Default bins per axis for point input
+const DEFAULT_POINT_BINS: usize = 10;
+/// Default color scale, viridis sampled at five stops
+const DEFAULT_PALETTE: [&str; 5] = ["#440154", "#3b528b", "#21918c", "#5ec962", "#fde725"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct Triple {
+    pub x: f64,
+    pub y: f64,
+    /// Value at the point (default: 1, so bins count points)
+    pub value: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct HeatmapBinInput {
+    /// Numeric matrix, one inner array per row; columns map to x and rows to y
+    pub matrix: Option<Vec<Vec<f64>>>,
+    /// Scattered (x, y, value) points to bin on a regular grid
+    #[tool_types(nested)]
+    pub points: Option<Vec<Triple>>,
+    /// Bins along x (default: matrix columns, or 10 for points; max: 500)
+    pub x_bins: Option<usize>,
+    /// Bins along y (default: matrix rows, or 10 for points; max: 500)
+    pub y_bins: Option<usize>,
+    /// How values sharing a bin combine: "mean", "sum", "count", "min" or "max"
+    /// (default: mean, or count for points without values)
+    pub aggregate: Option<String>,
+    /// Outlier clipping of the color domain: "none", "percentile" or "iqr" (1.5 × IQR
+    /// fences) (default: none)
+    pub clip: Option<String>,
+    /// Lower and upper percentiles kept by percentile clipping (default: [2, 98])
+    pub clip_percentiles: Option<(f64, f64)>,
+    /// Fixed lower end of the color domain, overriding clip
+    pub clip_min: Option<f64>,
+    /// Fixed upper end of the color domain, overriding clip
+    pub clip_max: Option<f64>,
+    /// Scale from values to the 0-1 range: "linear", "sqrt" or "log" (default: linear)
+    pub scale: Option<String>,
+    /// Colors of the scale as hex, low to high (default: viridis)
+    pub palette: Option<Vec<String>>,
+    /// Number of color-scale stops to report (default: 5)
+    pub stops: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct HeatmapCell {
+    /// Column of the bin, from 0 at the lowest x
+    pub x_index: usize,
+    /// Row of the bin, from 0 at the lowest y (first matrix row)
+    pub y_index: usize,
+    /// Aggregated value
+    pub value: f64,
+    /// Values that fell in the bin
+    pub count: usize,
+    /// Position on the color scale, 0 to 1 after clipping
+    pub normalized: f64,
+    /// Whether the value lay outside the color domain and was clamped
+    pub clipped: bool,
+    /// Color for the bin as #rrggbb
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct ColorStop {
+    /// Position on the color scale, 0 to 1
+    pub position: f64,
+    /// Data value at that position
+    pub value: f64,
+    /// Color as #rrggbb
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct HeatmapBinOutput {
+    /// Bin boundaries along x, one more than the bin count
+    pub x_edges: Vec<f64>,
+    /// Bin boundaries along y, one more than the bin count
+    pub y_edges: Vec<f64>,
+    /// Non-empty bins, row by row
+    #[tool_types(nested)]
+    pub cells: Vec<HeatmapCell>,
+    /// Normalized values as rows (y) of columns (x), null for empty bins
+    pub grid: Vec<Vec<Option<f64>>>,
+    /// Color scale from low to high
+    #[tool_types(nested)]
+    pub stops: Vec<ColorStop>,
+    /// Aggregation used
+    pub aggregate: String,
+    /// Scale used
+    pub scale: String,
+    /// Smallest aggregated value
+    pub data_min: f64,
+    /// Largest aggregated value
+    pub data_max: f64,
+    /// Value mapped to the bottom of the color scale, after clipping
+    pub domain_min: f64,
+    /// Value mapped to the top of the color scale, after clipping
+    pub domain_max: f64,
+    /// Bins clamped to the domain
+    pub clipped_cells: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Aggregate {
+    Mean,
+    Sum,
+    Count,
+    Min,
+    Max,
+}
+
+#[derive(Clone, Copy)]
+enum Scale {
+    Linear,
+    Sqrt,
+    Log,
+}
+
+impl Scale {
+    fn forward(self, v: f64) -> f64 {
+        match self {
+            Scale::Linear => v,
+            Scale::Sqrt => v.sqrt(),
+            Scale::Log => v.ln(),
+        }
+    }
+
+    fn inverse(self, v: f64) -> f64 {
+        match self {
+            Scale::Linear => v,
+            Scale::Sqrt => v * v,
+            Scale::Log => v.exp(),
+        }
+    }
+}
+
+/// Running aggregate of one bin
+#[derive(Clone)]
+struct Accumulator {
+    count: usize,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Accumulator {
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    fn value(&self, aggregate: Aggregate) -> f64 {
+        match aggregate {
+            Aggregate::Mean => self.sum / self.count as f64,
+            Aggregate::Sum => self.sum,
+            Aggregate::Count => self.count as f64,
+            Aggregate::Min => self.min,
+            Aggregate::Max => self.max,
+        }
+    }
+}
+
+fn bin_count(requested: Option<usize>, default: usize, axis: &str) -> Result<usize, String> {
+    let bins = requested.unwrap_or(default);
+    if !(1..=MAX_BINS).contains(&bins) {
+        return Err(format!("{axis}_bins must be between 1 and {MAX_BINS}"));
+    }
+    Ok(bins)
+}
+
+/// Equal-width edges over the range, widened around a single value
+fn edges(min: f64, max: f64, bins: usize) -> Vec<f64> {
+    let (low, high) = if min == max {
+        (min - 0.5, max + 0.5)
+    } else {
+        (min, max)
+    };
+    (0..=bins)
+        .map(|i| low + (high - low) * i as f64 / bins as f64)
+        .collect()
+}
+
+/// Bin of `v` between equal-width edges; the top edge joins the last bin
+fn bin_index(v: f64, edges: &[f64]) -> usize {
+    let bins = edges.len() - 1;
+    let (low, high) = (edges[0], edges[bins]);
+    (((v - low) / (high - low) * bins as f64).floor() as usize).min(bins - 1)
+}
+
+/// Linear interpolation percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64)
+}
+
+fn parse_hex(hex: &str) -> Result<[f64; 3], String> {
+    let digits = hex.trim().trim_start_matches('#');
+    if digits.len() != 6 || !digits.is_ascii() {
+        return Err(format!("Invalid palette color '{hex}', use #rrggbb"));
+    }
+    let channel = |i: usize| {
+        u8::from_str_radix(&digits[i..i + 2], 16)
+            .map(f64::from)
+            .map_err(|_| format!("Invalid palette color '{hex}', use #rrggbb"))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Color at position t (0-1) along the palette, interpolating in sRGB
+fn color_at(palette: &[[f64; 3]], t: f64) -> String {
+    let scaled = t.clamp(0.0, 1.0) * (palette.len() - 1) as f64;
+    let index = (scaled.floor() as usize).min(palette.len().saturating_sub(2));
+    let (from, to) = (palette[index], palette[(index + 1).min(palette.len() - 1)]);
+    let f = scaled - index as f64;
+    let rgb = [0, 1, 2].map(|i| (from[i] + (to[i] - from[i]) * f).round() as u8);
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+pub fn heatmap_bin_logic(input: HeatmapBinInput) -> Result<HeatmapBinOutput, String> {
+    let scale = match input.scale.as_deref().unwrap_or("linear") {
+        "linear" => Scale::Linear,
+        "sqrt" => Scale::Sqrt,
+        "log" => Scale::Log,
+        other => {
+            return Err(format!(
+                "Unknown scale '{other}', use 'linear', 'sqrt' or 'log'"
+            ));
+        }
+    };
+    let palette = match &input.palette {
+        Some(colors) if colors.len() < 2 => {
+            return Err("palette needs at least 2 colors".to_string());
+        }
+        Some(colors) => colors
+            .iter()
+            .map(|c| parse_hex(c))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => DEFAULT_PALETTE
+            .iter()
+            .map(|c| parse_hex(c))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    let stop_count = input.stops.unwrap_or(5);
+    if !(2..=100).contains(&stop_count) {
+        return Err("stops must be between 2 and 100".to_string());
+    }
+
+    // Gather (x, y, value) samples and the grid they fall on
+    let (samples, x_edges, y_edges, has_values): (Vec<(f64, f64, f64)>, _, _, bool) =
+        match (&input.matrix, &input.points) {
+            (Some(_), Some(_)) => {
+                return Err("Provide either matrix or points, not both".to_string());
+            }
+            (Some(matrix), None) => {
+                let columns = matrix.first().map_or(0, Vec::len);
+                if columns == 0 {
+                    return Err("matrix must have at least one row and column".to_string());
+                }
+                if matrix.iter().any(|row| row.len() != columns) {
+                    return Err("matrix rows must all have the same length".to_string());
+                }
+                if matrix.len() * columns > MAX_VALUES {
+                    return Err(format!("matrix is limited to {MAX_VALUES} cells"));
+                }
+                let x_bins = bin_count(input.x_bins, columns, "x")?.min(columns);
+                let y_bins = bin_count(input.y_bins, matrix.len(), "y")?.min(matrix.len());
+                // Cell (row, column) covers [column, column + 1) × [row, row + 1)
+                let samples = matrix
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(r, row)| {
+                        row.iter()
+                            .enumerate()
+                            .map(move |(c, &v)| (c as f64 + 0.5, r as f64 + 0.5, v))
+                    })
+                    .collect();
+                (
+                    samples,
+                    edges(0.0, columns as f64, x_bins),
+                    edges(0.0, matrix.len() as f64, y_bins),
+                    true,
+                )
+            }
+            (None, Some(points)) => {
+                if points.is_empty() {
+                    return Err("points must not be empty".to_string());
+                }
+                if points.len() > MAX_VALUES {
+                    return Err(format!("points is limited to {MAX_VALUES} entries"));
+                }
+                if points.iter().any(|p| !p.x.is_finite() || !p.y.is_finite()) {
+                    return Err("Point coordinates must be finite numbers".to_string());
+                }
+                let range = |f: fn(&Triple) -> f64| {
+                    points
+                        .iter()
+                        .map(f)
+                        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                            (lo.min(v), hi.max(v))
+                        })
+                };
+                let ((x_min, x_max), (y_min, y_max)) = (range(|p| p.x), range(|p| p.y));
+                let x_bins = bin_count(input.x_bins, DEFAULT_POINT_BINS, "x")?;
+                let y_bins = bin_count(input.y_bins, DEFAULT_POINT_BINS, "y")?;
+                (
+                    points
+                        .iter()
+                        .map(|p| (p.x, p.y, p.value.unwrap_or(1.0)))
+                        .collect(),
+                    edges(x_min, x_max, x_bins),
+                    edges(y_min, y_max, y_bins),
+                    points.iter().any(|p| p.value.is_some()),
+                )
+            }
+            (None, None) => return Err("Provide a matrix or points to bin".to_string()),
+        };
+    if samples.iter().any(|s| !s.2.is_finite()) {
+        return Err("Values must be finite numbers".to_string());
+    }
+
+    let aggregate_name = input
+        .aggregate
+        .clone()
+        .unwrap_or_else(|| if has_values { "mean" } else { "count" }.to_string());
+    let aggregate = match aggregate_name.as_str() {
+        "mean" => Aggregate::Mean,
+        "sum" => Aggregate::Sum,
+        "count" => Aggregate::Count,
+        "min" => Aggregate::Min,
+        "max" => Aggregate::Max,
+        other => {
+            return Err(format!(
+                "Unknown aggregate '{other}', use 'mean', 'sum', 'count', 'min' or 'max'"
+            ));
+        }
+    };
+
+    let (x_bins, y_bins) = (x_edges.len() - 1, y_edges.len() - 1);
+    let empty = Accumulator {
+        count: 0,
+        sum: 0.0,
+        min: f64::INFINITY,
+        max: f64::NEG_INFINITY,
+    };
+    let mut bins = vec![empty; x_bins * y_bins];
+    for &(x, y, v) in &samples {
+        bins[bin_index(y, &y_edges) * x_bins + bin_index(x, &x_edges)].add(v);
+    }
+    let filled: Vec<(usize, f64, usize)> = bins
+        .iter()
+        .enumerate()
+        .filter(|(_, acc)| acc.count > 0)
+        .map(|(i, acc)| (i, acc.value(aggregate), acc.count))
+        .collect();
+
+    // Color domain, clipped to keep outliers from flattening the scale
+    let mut sorted: Vec<f64> = filled.iter().map(|f| f.1).collect();
+    sorted.sort_by(f64::total_cmp);
+    let (data_min, data_max) = (sorted[0], sorted[sorted.len() - 1]);
+    let (mut domain_min, mut domain_max) = match input.clip.as_deref().unwrap_or("none") {
+        "none" => (data_min, data_max),
+        "percentile" => {
+            let (low, high) = input.clip_percentiles.unwrap_or((2.0, 98.0));
+            if !(0.0..=100.0).contains(&low) || !(0.0..=100.0).contains(&high) || low >= high {
+                return Err("clip_percentiles must be increasing values within 0-100".to_string());
+            }
+            (percentile(&sorted, low), percentile(&sorted, high))
+        }
+        "iqr" => {
+            let (q1, q3) = (percentile(&sorted, 25.0), percentile(&sorted, 75.0));
+            let fence = 1.5 * (q3 - q1);
+            ((q1 - fence).max(data_min), (q3 + fence).min(data_max))
+        }
+        other => {
+            return Err(format!(
+                "Unknown clip '{other}', use 'none', 'percentile' or 'iqr'"
+            ));
+        }
+    };
+    if let Some(min) = input.clip_min {
+        domain_min = min;
+    }
+    if let Some(max) = input.clip_max {
+        domain_max = max;
+    }
+    if !domain_min.is_finite() || !domain_max.is_finite() || domain_min > domain_max {
+        return Err("clip_min must not be above clip_max".to_string());
+    }
+    match scale {
+        Scale::Log if domain_min <= 0.0 => {
+            return Err("log scale needs a color domain above 0; set clip_min".to_string());
+        }
+        Scale::Sqrt if domain_min < 0.0 => {
+            return Err("sqrt scale needs a color domain of at least 0; set clip_min".to_string());
+        }
+        _ => {}
+    }
+
+    let (low, high) = (scale.forward(domain_min), scale.forward(domain_max));
+    let normalize = |v: f64| {
+        if high == low {
+            0.5
+        } else {
+            (scale.forward(v.clamp(domain_min, domain_max)) - low) / (high - low)
+        }
+    };
+
+    let mut grid = vec![vec![None; x_bins]; y_bins];
+    let mut clipped_cells = 0;
+    let cells = filled
+        .into_iter()
+        .map(|(i, value, count)| {
+            let clipped = value < domain_min || value > domain_max;
+            clipped_cells += clipped as usize;
+            let normalized = normalize(value);
+            let (x_index, y_index) = (i % x_bins, i / x_bins);
+            grid[y_index][x_index] = Some(normalized);
+            HeatmapCell {
+                x_index,
+                y_index,
+                value,
+                count,
+                normalized,
+                clipped,
+                color: color_at(&palette, normalized),
+            }
+        })
+        .collect();
+
+    let stops = (0..stop_count)
+        .map(|i| {
+            let position = i as f64 / (stop_count - 1) as f64;
+            ColorStop {
+                position,
+                value: scale.inverse(low + (high - low) * position),
+                color: color_at(&palette, position),
+            }
+        })
+        .collect();
+
+    Ok(HeatmapBinOutput {
+        x_edges,
+        y_edges,
+        cells,
+        grid,
+        stops,
+        aggregate: aggregate_name,
+        scale: input.scale.unwrap_or_else(|| "linear".to_string()),
+        data_min,
+        data_max,
+        domain_min,
+        domain_max,
+        clipped_cells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> HeatmapBinInput {
+        HeatmapBinInput {
+            matrix: None,
+            points: None,
+            x_bins: None,
+            y_bins: None,
+            aggregate: None,
+            clip: None,
+            clip_percentiles: None,
+            clip_min: None,
+            clip_max: None,
+            scale: None,
+            palette: None,
+            stops: None,
+        }
+    }
+
+    #[test]
+    fn test_matrix_cells() {
+        let mut request = input();
+        request.matrix = Some(vec![vec![0.0, 1.0], vec![2.0, 4.0]]);
+        let output = heatmap_bin_logic(request.clone()).unwrap();
+        assert_eq!(output.x_edges, vec![0.0, 1.0, 2.0]);
+        assert_eq!(
+            output.grid,
+            vec![vec![Some(0.0), Some(0.25)], vec![Some(0.5), Some(1.0)]]
+        );
+        assert_eq!(output.cells[0].color, "#440154");
+        assert_eq!(output.cells[3].color, "#fde725");
+        assert_eq!(output.aggregate, "mean");
+
+        // Coarser bins average blocks of cells
+        request.x_bins = Some(1);
+        request.aggregate = Some("sum".to_string());
+        let output = heatmap_bin_logic(request).unwrap();
+        let sums: Vec<(f64, usize)> = output.cells.iter().map(|c| (c.value, c.count)).collect();
+        assert_eq!(sums, vec![(1.0, 2), (6.0, 2)]);
+    }
+
+    #[test]
+    fn test_points_count_by_default() {
+        let mut request = input();
+        let point = |x, y| Triple { x, y, value: None };
+        request.points = Some(vec![point(0.0, 0.0), point(0.1, 0.2), point(10.0, 10.0)]);
+        request.x_bins = Some(2);
+        request.y_bins = Some(2);
+        let output = heatmap_bin_logic(request).unwrap();
+        assert_eq!(output.aggregate, "count");
+        assert_eq!(output.x_edges, vec![0.0, 5.0, 10.0]);
+        // The maximum lands in the last bin
+        let cells: Vec<(usize, usize, f64)> = output
+            .cells
+            .iter()
+            .map(|c| (c.x_index, c.y_index, c.value))
+            .collect();
+        assert_eq!(cells, vec![(0, 0, 2.0), (1, 1, 1.0)]);
+        assert_eq!(output.grid[0][1], None);
+    }
+
+    #[test]
+    fn test_outlier_clipping() {
+        let mut request = input();
+        request.matrix = Some(vec![vec![1.0, 2.0, 3.0, 4.0, 5.0, 1000.0]]);
+        request.clip = Some("iqr".to_string());
+        let output = heatmap_bin_logic(request.clone()).unwrap();
+        assert_eq!(output.data_max, 1000.0);
+        // Q1 = 2.25, Q3 = 4.75, upper fence 8.5
+        assert!((output.domain_max - 8.5).abs() < 1e-12);
+        assert_eq!(output.clipped_cells, 1);
+        assert!(output.cells[5].clipped && output.cells[5].normalized == 1.0);
+        assert!((output.cells[4].normalized - 4.0 / 7.5).abs() < 1e-12);
+
+        request.clip = Some("percentile".to_string());
+        request.clip_percentiles = Some((0.0, 80.0));
+        let output = heatmap_bin_logic(request.clone()).unwrap();
+        assert_eq!((output.domain_min, output.domain_max), (1.0, 5.0));
+
+        request.clip_max = Some(10.0);
+        assert_eq!(heatmap_bin_logic(request).unwrap().domain_max, 10.0);
+    }
+
+    #[test]
+    fn test_log_scale_stops() {
+        let mut request = input();
+        request.matrix = Some(vec![vec![1.0, 10.0, 100.0]]);
+        request.scale = Some("log".to_string());
+        request.stops = Some(3);
+        request.palette = Some(vec!["#000000".to_string(), "#ffffff".to_string()]);
+        let output = heatmap_bin_logic(request.clone()).unwrap();
+        let values: Vec<f64> = output.stops.iter().map(|s| s.value).collect();
+        assert!((values[1] - 10.0).abs() < 1e-9 && (values[2] - 100.0).abs() < 1e-9);
+        assert_eq!(output.stops[1].color, "#808080");
+        assert!((output.cells[1].normalized - 0.5).abs() < 1e-12);
+
+        request.matrix = Some(vec![vec![0.0, 10.0]]);
+        assert!(
+            heatmap_bin_logic(request)
+                .unwrap_err()
+                .contains("log scale")
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut request = input();
+        assert_eq!(
+            heatmap_bin_logic(request.clone()).unwrap_err(),
+            "Provide a matrix or points to bin"
+        );
+        request.matrix = Some(vec![vec![1.0, 2.0], vec![3.0]]);
+        assert!(
+            heatmap_bin_logic(request.clone())
+                .unwrap_err()
+                .contains("same length")
+        );
+        request.matrix = Some(vec![vec![1.0, 2.0]]);
+        request.aggregate = Some("median".to_string());
+        assert!(
+            heatmap_bin_logic(request.clone())
+                .unwrap_err()
+                .contains("Unknown aggregate")
+        );
+        request.aggregate = None;
+        request.palette = Some(vec!["red".to_string(), "#00ff00".to_string()]);
+        assert!(
+            heatmap_bin_logic(request)
+                .unwrap_err()
+                .contains("Invalid palette color")
+        );
+    }
+}
@@ -0,0 +1,72 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    CHARTS, PlotSpecInput as LogicInput, PlotSpecOutput as LogicOutput, VEGA_LITE_SCHEMA,
+    plot_spec_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::PlotSpecOutputSchema as PlotSpecOutput;
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlotSpecInput {
+    #[serde(flatten)]
+    pub params: logic::PlotSpecInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Turn histogram, linear_regression, kde or control_chart output into a Vega-Lite
+/// specification with the data inlined, ready for a client to render
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn plot_spec(request: Request<PlotSpecInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("plot_spec", PlotSpecInput, PlotSpecOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("plot_spec", &input);
+    log.finish(run_plot_spec(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_plot_spec(input: PlotSpecInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .json("result", &input.params.result)
+        .array("x", input.params.x.as_deref().unwrap_or_default())
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::plot_spec_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&PlotSpecOutput::from(result))
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
@@ -0,0 +1,500 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+pub const VEGA_LITE_SCHEMA: &str = "https://vega.github.io/schema/vega-lite/v5.json";
+/// Charts with a builder below
+pub const CHARTS: [&str; 4] = ["histogram", "linear_regression", "kde", "control_chart"];
+/// z value of the two-sided 95% band drawn around a regression line
+const BAND_Z: f64 = 1.96;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PlotSpecInput {
+    /// Tool whose output is being plotted: "histogram", "linear_regression", "kde" or
+    /// "control_chart"
+    pub chart: String,
+    /// Output of that tool, as a JSON object or the JSON text it returned. kde results
+    /// need `points` ([{x, density}]) or `x` and `density` arrays; control_chart results
+    /// need `values`, `center_line`, `upper_control_limit` and `lower_control_limit`
+    pub result: Value,
+    /// x values the regression was fitted on; recovered from the predicted values when
+    /// omitted
+    pub x: Option<Vec<f64>>,
+    /// Histogram bar height: "count", "frequency" or "density" (default: count)
+    pub y_field: Option<String>,
+    /// Chart title
+    pub title: Option<String>,
+    /// x axis title
+    pub x_title: Option<String>,
+    /// y axis title
+    pub y_title: Option<String>,
+    /// Chart width in pixels (default: 400)
+    pub width: Option<u32>,
+    /// Chart height in pixels (default: 300)
+    pub height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PlotSpecOutput {
+    /// Chart type plotted
+    pub chart: String,
+    /// Vega-Lite v5 specification with the data inlined
+    pub spec: Value,
+    /// Rows in the inlined data
+    pub data_points: usize,
+}
+
+/// Field of a tool result, trying each accepted name in turn
+fn field<'a>(result: &'a Value, chart: &str, names: &[&str]) -> Result<&'a Value, String> {
+    names
+        .iter()
+        .find_map(|name| result.get(*name))
+        .ok_or_else(|| format!("{chart} result is missing '{}'", names[0]))
+}
+
+fn number(result: &Value, chart: &str, names: &[&str]) -> Result<f64, String> {
+    field(result, chart, names)?
+        .as_f64()
+        .ok_or_else(|| format!("{chart} result field '{}' must be a number", names[0]))
+}
+
+fn numbers(result: &Value, chart: &str, name: &str) -> Result<Vec<f64>, String> {
+    field(result, chart, &[name])?
+        .as_array()
+        .and_then(|values| values.iter().map(Value::as_f64).collect())
+        .ok_or_else(|| format!("{chart} result field '{name}' must be an array of numbers"))
+}
+
+fn objects<'a>(result: &'a Value, chart: &str, name: &str) -> Result<&'a Vec<Value>, String> {
+    field(result, chart, &[name])?
+        .as_array()
+        .filter(|items| items.iter().all(Value::is_object))
+        .ok_or_else(|| format!("{chart} result field '{name}' must be an array of objects"))
+}
+
+/// Rows for the spec plus the chart-specific marks and encodings
+struct Chart {
+    rows: Vec<Value>,
+    body: Map<String, Value>,
+}
+
+fn axis(field: &str, title: &str, custom: &Option<String>) -> Value {
+    json!({
+        "field": field,
+        "type": "quantitative",
+        "title": custom.as_deref().unwrap_or(title),
+    })
+}
+
+fn histogram(result: Value, input: &PlotSpecInput) -> Result<Chart, String> {
+    let bins = objects(&result, "histogram", "bins")?;
+    let y_field = input.y_field.as_deref().unwrap_or("count");
+    if !["count", "frequency", "density"].contains(&y_field) {
+        return Err(format!(
+            "Unknown y_field '{y_field}', use 'count', 'frequency' or 'density'"
+        ));
+    }
+    if bins.is_empty() {
+        return Err("histogram result has no bins".to_string());
+    }
+    let mut x = axis("lower_bound", "Value", &input.x_title);
+    x["bin"] = json!({ "binned": true });
+    let y_title = match y_field {
+        "count" => "Count",
+        "frequency" => "Frequency",
+        _ => "Density",
+    };
+    let body = json!({
+        "mark": { "type": "bar", "tooltip": true },
+        "encoding": {
+            "x": x,
+            "x2": { "field": "upper_bound" },
+            "y": axis(y_field, y_title, &input.y_title),
+        },
+    });
+    Ok(Chart {
+        rows: bins
+            .iter()
+            .map(|bin| {
+                let mut row = Map::new();
+                for name in [
+                    "lower_bound",
+                    "upper_bound",
+                    "count",
+                    "frequency",
+                    "density",
+                ] {
+                    row.insert(name.to_string(), json!(number(bin, "histogram", &[name])?));
+                }
+                Ok(Value::Object(row))
+            })
+            .collect::<Result<_, String>>()?,
+        body: into_map(body),
+    })
+}
+
+fn linear_regression(result: Value, input: &PlotSpecInput) -> Result<Chart, String> {
+    let chart = "linear_regression";
+    let slope = number(&result, chart, &["slope"])?;
+    let intercept = number(&result, chart, &["intercept"])?;
+    let standard_error = number(&result, chart, &["standard_error"])?;
+    let residuals = numbers(&result, chart, "residuals")?;
+    let predicted = numbers(&result, chart, "predicted_values")?;
+    let n = predicted.len();
+    if n == 0 || residuals.len() != n {
+        return Err(
+            "linear_regression result needs matching predicted_values and residuals".to_string(),
+        );
+    }
+    let xs = match &input.x {
+        Some(xs) if xs.len() != n => {
+            return Err(format!(
+                "x has {} values but the regression has {n}",
+                xs.len()
+            ));
+        }
+        Some(xs) => xs.clone(),
+        // predicted = intercept + slope * x
+        None if slope != 0.0 => predicted.iter().map(|p| (p - intercept) / slope).collect(),
+        None => return Err("x is required when the regression slope is 0".to_string()),
+    };
+
+    let mut points: Vec<(f64, f64, f64)> = xs
+        .into_iter()
+        .zip(&predicted)
+        .zip(&residuals)
+        .map(|((x, fitted), residual)| (x, fitted + residual, *fitted))
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let margin = BAND_Z * standard_error;
+    let rows = points
+        .iter()
+        .map(|&(x, y, fitted)| {
+            json!({
+                "x": x,
+                "y": y,
+                "fitted": fitted,
+                "lower": fitted - margin,
+                "upper": fitted + margin,
+            })
+        })
+        .collect();
+
+    let x = axis("x", "x", &input.x_title);
+    let y_title = input.y_title.as_deref().unwrap_or("y");
+    let fit_title = result
+        .get("equation")
+        .and_then(Value::as_str)
+        .unwrap_or("Fit");
+    let body = json!({
+        "encoding": { "x": x },
+        "layer": [
+            {
+                // Approximate 95% band from the residual standard error
+                "mark": { "type": "area", "opacity": 0.2 },
+                "encoding": {
+                    "y": { "field": "lower", "type": "quantitative", "title": y_title },
+                    "y2": { "field": "upper" },
+                },
+            },
+            {
+                "mark": { "type": "point", "filled": true, "tooltip": true },
+                "encoding": { "y": { "field": "y", "type": "quantitative", "title": y_title } },
+            },
+            {
+                "mark": { "type": "line", "color": "firebrick" },
+                "encoding": {
+                    "y": { "field": "fitted", "type": "quantitative", "title": fit_title },
+                },
+            },
+        ],
+    });
+    Ok(Chart {
+        rows,
+        body: into_map(body),
+    })
+}
+
+fn kde(result: Value, input: &PlotSpecInput) -> Result<Chart, String> {
+    let mut points: Vec<(f64, f64)> = if result.get("points").is_some() {
+        objects(&result, "kde", "points")?
+            .iter()
+            .map(|p| Ok((number(p, "kde", &["x"])?, number(p, "kde", &["density"])?)))
+            .collect::<Result<_, String>>()?
+    } else if result.get("x").is_some() && result.get("density").is_some() {
+        let (x, density) = (
+            numbers(&result, "kde", "x")?,
+            numbers(&result, "kde", "density")?,
+        );
+        if x.len() != density.len() {
+            return Err("kde result x and density arrays differ in length".to_string());
+        }
+        x.into_iter().zip(density).collect()
+    } else {
+        return Err("kde result needs points, or x and density arrays".to_string());
+    };
+    if points.len() < 2 {
+        return Err("kde result needs at least 2 points".to_string());
+    }
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let body = json!({
+        "mark": { "type": "area", "line": true, "opacity": 0.4, "tooltip": true },
+        "encoding": {
+            "x": axis("x", "Value", &input.x_title),
+            "y": axis("density", "Density", &input.y_title),
+        },
+    });
+    Ok(Chart {
+        rows: points
+            .iter()
+            .map(|&(x, density)| json!({ "x": x, "density": density }))
+            .collect(),
+        body: into_map(body),
+    })
+}
+
+fn control_chart(result: Value, input: &PlotSpecInput) -> Result<Chart, String> {
+    let chart = "control_chart";
+    let values = numbers(&result, chart, "values")?;
+    let center_line = number(&result, chart, &["center_line", "center"])?;
+    let upper = number(&result, chart, &["upper_control_limit", "ucl"])?;
+    let lower = number(&result, chart, &["lower_control_limit", "lcl"])?;
+    if values.is_empty() {
+        return Err("control_chart result has no values".to_string());
+    }
+    if lower > upper {
+        return Err("lower_control_limit is above upper_control_limit".to_string());
+    }
+    let rows = values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            json!({
+                "index": i + 1,
+                "value": value,
+                "center_line": center_line,
+                "lower_control_limit": lower,
+                "upper_control_limit": upper,
+                "out_of_control": value < lower || value > upper,
+            })
+        })
+        .collect();
+    let y_title = input.y_title.as_deref().unwrap_or("Value");
+    let body = json!({
+        "encoding": {
+            "x": {
+                "field": "index",
+                "type": "quantitative",
+                "title": input.x_title.as_deref().unwrap_or("Sample"),
+            },
+        },
+        "layer": [
+            {
+                "mark": { "type": "area", "opacity": 0.15, "color": "seagreen" },
+                "encoding": {
+                    "y": { "field": "lower_control_limit", "type": "quantitative", "title": y_title },
+                    "y2": { "field": "upper_control_limit" },
+                },
+            },
+            {
+                "mark": { "type": "rule", "strokeDash": [4, 4] },
+                "encoding": { "y": { "field": "center_line", "type": "quantitative" } },
+            },
+            { "mark": "line", "encoding": { "y": { "field": "value", "type": "quantitative" } } },
+            {
+                "mark": { "type": "point", "filled": true, "tooltip": true },
+                "encoding": {
+                    "y": { "field": "value", "type": "quantitative" },
+                    "color": {
+                        "field": "out_of_control",
+                        "type": "nominal",
+                        "scale": { "domain": [false, true], "range": ["steelblue", "firebrick"] },
+                        "title": "Out of control",
+                    },
+                },
+            },
+        ],
+    });
+    Ok(Chart {
+        rows,
+        body: into_map(body),
+    })
+}
+
+fn into_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+pub fn plot_spec_logic(input: PlotSpecInput) -> Result<PlotSpecOutput, String> {
+    let chart = input.chart.trim().to_lowercase();
+    // Tools return their output as JSON text, which can be passed along unparsed
+    let result = match &input.result {
+        Value::String(text) => {
+            serde_json::from_str(text).map_err(|e| format!("result is not valid JSON: {e}"))?
+        }
+        value => value.clone(),
+    };
+    let built = match chart.as_str() {
+        "histogram" => histogram(result, &input)?,
+        "linear_regression" => linear_regression(result, &input)?,
+        "kde" => kde(result, &input)?,
+        "control_chart" => control_chart(result, &input)?,
+        _ => {
+            return Err(format!(
+                "Unknown chart '{chart}', use one of: {}",
+                CHARTS.join(", ")
+            ));
+        }
+    };
+
+    let data_points = built.rows.len();
+    let mut spec = Map::new();
+    spec.insert("$schema".to_string(), json!(VEGA_LITE_SCHEMA));
+    if let Some(title) = &input.title {
+        spec.insert("title".to_string(), json!(title));
+    }
+    spec.insert("width".to_string(), json!(input.width.unwrap_or(400)));
+    spec.insert("height".to_string(), json!(input.height.unwrap_or(300)));
+    spec.insert("data".to_string(), json!({ "values": built.rows }));
+    spec.extend(built.body);
+
+    Ok(PlotSpecOutput {
+        chart,
+        spec: Value::Object(spec),
+        data_points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(chart: &str, result: Value) -> PlotSpecInput {
+        PlotSpecInput {
+            chart: chart.to_string(),
+            result,
+            x: None,
+            y_field: None,
+            title: None,
+            x_title: None,
+            y_title: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    #[test]
+    fn test_histogram_bars() {
+        let result = json!({
+            "bins": [
+                { "lower_bound": 0.0, "upper_bound": 1.0, "count": 2, "frequency": 0.5, "density": 0.5 },
+                { "lower_bound": 1.0, "upper_bound": 2.0, "count": 2, "frequency": 0.5, "density": 0.5 },
+            ],
+            "total_count": 4,
+            "bin_width": 1.0,
+            "range": [0.0, 2.0],
+        });
+        let mut request = input("histogram", result);
+        request.y_field = Some("density".to_string());
+        request.title = Some("Latency".to_string());
+        let output = plot_spec_logic(request).unwrap();
+        let spec = &output.spec;
+        assert_eq!(spec["$schema"], VEGA_LITE_SCHEMA);
+        assert_eq!(spec["title"], "Latency");
+        assert_eq!(spec["mark"]["type"], "bar");
+        assert_eq!(spec["encoding"]["x"]["bin"]["binned"], true);
+        assert_eq!(spec["encoding"]["x2"]["field"], "upper_bound");
+        assert_eq!(spec["encoding"]["y"]["field"], "density");
+        assert_eq!(spec["data"]["values"][1]["lower_bound"], 1.0);
+        assert_eq!(output.data_points, 2);
+    }
+
+    #[test]
+    fn test_regression_layers() {
+        // y = 1 + 2x with residuals; x recovered from the fitted values
+        let result = json!({
+            "slope": 2.0,
+            "intercept": 1.0,
+            "standard_error": 0.5,
+            "residuals": [0.5, -0.5, 0.25],
+            "predicted_values": [5.0, 1.0, 3.0],
+            "equation": "y = 2x + 1",
+        });
+        let output = plot_spec_logic(input("linear_regression", result.clone())).unwrap();
+        let rows = output.spec["data"]["values"].as_array().unwrap();
+        let xs: Vec<f64> = rows.iter().map(|r| r["x"].as_f64().unwrap()).collect();
+        assert_eq!(xs, vec![0.0, 1.0, 2.0]);
+        assert_eq!(rows[0]["y"], 0.5);
+        assert_eq!(rows[2]["y"], 5.5);
+        assert!((rows[1]["upper"].as_f64().unwrap() - (3.0 + 0.98)).abs() < 1e-12);
+        let marks: Vec<&str> = output.spec["layer"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|layer| layer["mark"]["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(marks, vec!["area", "point", "line"]);
+
+        // Explicit x must line up with the results
+        let mut request = input("linear_regression", result);
+        request.x = Some(vec![1.0]);
+        assert_eq!(
+            plot_spec_logic(request).unwrap_err(),
+            "x has 1 values but the regression has 3"
+        );
+    }
+
+    #[test]
+    fn test_kde_from_text_result() {
+        let text = r#"{"x": [1.0, 0.0, 2.0], "density": [0.4, 0.2, 0.1], "bandwidth": 0.3}"#;
+        let output = plot_spec_logic(input("KDE", json!(text))).unwrap();
+        assert_eq!(output.chart, "kde");
+        assert_eq!(output.spec["mark"]["type"], "area");
+        assert_eq!(output.spec["data"]["values"][0]["x"], 0.0);
+        assert_eq!(output.spec["data"]["values"][0]["density"], 0.2);
+
+        let points = json!({ "points": [{ "x": 0.0, "density": 0.1 }] });
+        assert!(
+            plot_spec_logic(input("kde", points))
+                .unwrap_err()
+                .contains("at least 2")
+        );
+    }
+
+    #[test]
+    fn test_control_chart_flags() {
+        let result =
+            json!({ "values": [10.0, 12.5, 7.0], "center": 10.0, "ucl": 12.0, "lcl": 8.0 });
+        let output = plot_spec_logic(input("control_chart", result)).unwrap();
+        let rows = output.spec["data"]["values"].as_array().unwrap();
+        let flags: Vec<bool> = rows
+            .iter()
+            .map(|r| r["out_of_control"].as_bool().unwrap())
+            .collect();
+        assert_eq!(flags, vec![false, true, true]);
+        assert_eq!(rows[2]["index"], 3);
+        assert_eq!(output.spec["layer"][1]["mark"]["type"], "rule");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(
+            plot_spec_logic(input("pie", json!({})))
+                .unwrap_err()
+                .starts_with("Unknown chart 'pie'")
+        );
+        assert!(
+            plot_spec_logic(input("histogram", json!({ "bins": "none" })))
+                .unwrap_err()
+                .contains("'bins' must be an array of objects")
+        );
+        assert!(
+            plot_spec_logic(input("histogram", json!("{not json")))
+                .unwrap_err()
+                .starts_with("result is not valid JSON")
+        );
+    }
+}
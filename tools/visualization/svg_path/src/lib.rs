@@ -0,0 +1,95 @@
+mod logic;
+
+#[cfg(feature = "individual")]
+use ftl_guard::Guard;
+#[cfg(feature = "individual")]
+use ftl_introspect::{Request, describe};
+#[cfg(feature = "individual")]
+use ftl_log::ToolLog;
+#[cfg(feature = "individual")]
+use ftl_sdk::ToolResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "individual", not(any(test, feature = "library"))))]
+use ftl_sdk::tool;
+
+// Re-export types from logic module
+pub use logic::{
+    BoundingBox as LogicBoundingBox, GeoPoint as LogicGeoPoint, MAX_POINTS, MAX_SEGMENTS,
+    PathPoint as LogicPathPoint, PathSummary as LogicPathSummary, SvgPathInput as LogicInput,
+    SvgPathOutput as LogicOutput, svg_path_logic,
+};
+
+// Wrapper types with JsonSchema for FTL-SDK, generated from the logic types
+pub use logic::{
+    BoundingBoxSchema as BoundingBox, GeoPointSchema as GeoPoint, PathPointSchema as PathPoint,
+    PathSummarySchema as PathSummary, SvgPathOutputSchema as SvgPathOutput,
+};
+
+// The generated input plus options handled by this wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SvgPathInput {
+    #[serde(flatten)]
+    pub params: logic::SvgPathInputSchema,
+    /// Append execution metrics (duration and input/output size) to the response
+    pub include_metrics: Option<bool>,
+}
+
+/// Parse SVG path data to measure its length and exact bounds and sample points along it,
+/// and turn geospatial polygons and polylines into SVG paths
+#[cfg(feature = "individual")]
+#[cfg_attr(not(any(test, feature = "library")), tool)]
+pub fn svg_path(request: Request<SvgPathInput>) -> ToolResponse {
+    let input = match request {
+        Request::Call(input) => input,
+        Request::Introspect => {
+            return ToolResponse::text(describe!("svg_path", SvgPathInput, SvgPathOutput));
+        }
+    };
+
+    // Structured log event, written when the call completes
+    let log = ToolLog::start("svg_path", &input);
+    log.finish(run_svg_path(input))
+}
+
+#[cfg(feature = "individual")]
+fn run_svg_path(input: SvgPathInput) -> ToolResponse {
+    // Reject oversized inputs before doing any work
+    if let Err(e) = Guard::from_env()
+        .string("path", input.params.path.as_deref().unwrap_or_default())
+        .array(
+            "at_lengths",
+            input.params.at_lengths.as_deref().unwrap_or_default(),
+        )
+        .array(
+            "polygons",
+            input.params.polygons.as_deref().unwrap_or_default(),
+        )
+        .array_len(
+            "polygons",
+            input.params.polygons.iter().flatten().map(Vec::len).sum(),
+        )
+        .array(
+            "polylines",
+            input.params.polylines.as_deref().unwrap_or_default(),
+        )
+        .array_len(
+            "polylines",
+            input.params.polylines.iter().flatten().map(Vec::len).sum(),
+        )
+        .finish()
+    {
+        return ToolResponse::text(e.to_json());
+    }
+
+    let result = match logic::svg_path_logic(input.params.into()) {
+        Ok(result) => result,
+        Err(e) => return ToolResponse::text(format!("Error: {e}")),
+    };
+
+    ToolResponse::text(
+        ftl_json::to_string(&SvgPathOutput::from(result))
+            .unwrap_or_else(|_| "Error serializing result".to_string()),
+    )
+}
@@ -0,0 +1,915 @@
+use ftl_tool_types_derive::ToolTypes;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{PI, TAU};
+
+/// Most segments in one path
+pub const MAX_SEGMENTS: usize = 100_000;
+/// Most sampled or requested points per call
+pub const MAX_POINTS: usize = 10_000;
+/// Default decimals in generated path data
+const DEFAULT_PRECISION: u32 = 6;
+
+/// Gauss-Legendre nodes and weights on [-1, 1]
+const GAUSS: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+/// Subintervals per curve when integrating arc length
+const GAUSS_INTERVALS: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct GeoPoint {
+    /// Latitude in degrees
+    pub lat: f64,
+    /// Longitude in degrees
+    pub lon: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct SvgPathInput {
+    /// SVG path data to measure, like "M 0 0 C 10 20 30 20 40 0 Z"
+    pub path: Option<String>,
+    /// Number of evenly spaced points to sample along the path, ends included
+    pub samples: Option<usize>,
+    /// Distances along the path to return points for
+    pub at_lengths: Option<Vec<f64>>,
+    /// Polygons (as returned by buffer_polygon or polygon_simplification) to convert to
+    /// closed SVG paths, x = longitude and y = -latitude so north is up
+    #[tool_types(nested)]
+    pub polygons: Option<Vec<Vec<GeoPoint>>>,
+    /// Polylines to convert to open SVG paths, same projection as polygons
+    #[tool_types(nested)]
+    pub polylines: Option<Vec<Vec<GeoPoint>>>,
+    /// Decimals in generated path data (default: 6, max: 12)
+    pub precision: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToolTypes)]
+pub struct PathPoint {
+    /// Distance along the path
+    pub length: f64,
+    pub x: f64,
+    pub y: f64,
+    /// Direction of travel in degrees, clockwise from +x in SVG's y-down coordinates
+    pub angle: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct PathSummary {
+    /// Path rewritten with absolute M, L, C, Q, A and Z commands only
+    pub normalized: String,
+    /// Number of subpaths (M commands)
+    pub subpath_count: usize,
+    /// Number of drawn segments, closing lines included
+    pub segment_count: usize,
+    /// Total length of all segments
+    pub length: f64,
+    /// Exact bounds of the drawn geometry, curve extremes included
+    #[tool_types(nested)]
+    pub bounding_box: Option<BoundingBox>,
+    /// Evenly spaced points along the path
+    #[tool_types(nested)]
+    pub samples: Vec<PathPoint>,
+    /// Points at the requested distances, clamped to the path's length
+    #[tool_types(nested)]
+    pub at_lengths: Vec<PathPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToolTypes)]
+pub struct SvgPathOutput {
+    /// Measurements of `path`
+    #[tool_types(nested)]
+    pub path: Option<PathSummary>,
+    /// SVG path data for each polygon, then each polyline
+    pub shapes: Vec<String>,
+    /// viewBox ("min_x min_y width height") covering all shapes
+    pub view_box: Option<String>,
+}
+
+type P = [f64; 2];
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Line(P, P),
+    Quad(P, P, P),
+    Cubic(P, P, P, P),
+    /// Center parametrization: angle theta runs from `start` by `sweep` radians
+    Arc {
+        center: P,
+        rx: f64,
+        ry: f64,
+        phi: f64,
+        start: f64,
+        sweep: f64,
+    },
+}
+
+fn lerp(a: P, b: P, t: f64) -> P {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+impl Segment {
+    fn point(&self, t: f64) -> P {
+        match *self {
+            Segment::Line(a, b) => lerp(a, b, t),
+            Segment::Quad(a, b, c) => lerp(lerp(a, b, t), lerp(b, c, t), t),
+            Segment::Cubic(a, b, c, d) => {
+                let (ab, bc, cd) = (lerp(a, b, t), lerp(b, c, t), lerp(c, d, t));
+                lerp(lerp(ab, bc, t), lerp(bc, cd, t), t)
+            }
+            Segment::Arc {
+                center,
+                rx,
+                ry,
+                phi,
+                start,
+                sweep,
+            } => {
+                let theta = start + sweep * t;
+                let (sin_phi, cos_phi) = phi.sin_cos();
+                let (sin, cos) = theta.sin_cos();
+                [
+                    center[0] + rx * cos_phi * cos - ry * sin_phi * sin,
+                    center[1] + rx * sin_phi * cos + ry * cos_phi * sin,
+                ]
+            }
+        }
+    }
+
+    fn derivative(&self, t: f64) -> P {
+        let diff = |a: P, b: P, k: f64| [(b[0] - a[0]) * k, (b[1] - a[1]) * k];
+        match *self {
+            Segment::Line(a, b) => diff(a, b, 1.0),
+            Segment::Quad(a, b, c) => diff(lerp(a, b, t), lerp(b, c, t), 2.0),
+            Segment::Cubic(a, b, c, d) => {
+                let (ab, bc, cd) = (lerp(a, b, t), lerp(b, c, t), lerp(c, d, t));
+                diff(lerp(ab, bc, t), lerp(bc, cd, t), 3.0)
+            }
+            Segment::Arc {
+                rx,
+                ry,
+                phi,
+                start,
+                sweep,
+                ..
+            } => {
+                let theta = start + sweep * t;
+                let (sin_phi, cos_phi) = phi.sin_cos();
+                let (sin, cos) = theta.sin_cos();
+                [
+                    sweep * (-rx * cos_phi * sin - ry * sin_phi * cos),
+                    sweep * (-rx * sin_phi * sin + ry * cos_phi * cos),
+                ]
+            }
+        }
+    }
+
+    /// Arc length from t0 to t1
+    fn length_between(&self, t0: f64, t1: f64) -> f64 {
+        if let Segment::Line(a, b) = *self {
+            return (b[0] - a[0]).hypot(b[1] - a[1]) * (t1 - t0);
+        }
+        let step = (t1 - t0) / GAUSS_INTERVALS as f64;
+        (0..GAUSS_INTERVALS)
+            .map(|i| {
+                let mid = t0 + step * (i as f64 + 0.5);
+                GAUSS
+                    .iter()
+                    .map(|&(x, w)| {
+                        let d = self.derivative(mid + x * step / 2.0);
+                        w * d[0].hypot(d[1])
+                    })
+                    .sum::<f64>()
+                    * step
+                    / 2.0
+            })
+            .sum()
+    }
+
+    /// Parameter where the arc length from the start reaches `target`
+    fn parameter_at(&self, target: f64, total: f64) -> f64 {
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let (mut low, mut high) = (0.0, 1.0);
+        let mut t = (target / total).clamp(0.0, 1.0);
+        for _ in 0..50 {
+            let error = self.length_between(0.0, t) - target;
+            if error.abs() < 1e-12 * total.max(1.0) {
+                break;
+            }
+            if error > 0.0 {
+                high = t;
+            } else {
+                low = t;
+            }
+            // Newton step, falling back to bisection when it leaves the bracket
+            let d = self.derivative(t);
+            let speed = d[0].hypot(d[1]);
+            let next = t - error / speed;
+            t = if speed > 0.0 && next > low && next < high {
+                next
+            } else {
+                (low + high) / 2.0
+            };
+        }
+        t
+    }
+
+    /// Parameters in (0, 1) where x or y reaches a local extreme
+    fn extremes(&self) -> Vec<f64> {
+        let mut ts = Vec::new();
+        match *self {
+            Segment::Line(..) => {}
+            Segment::Quad(a, b, c) => {
+                for axis in 0..2 {
+                    let denominator = a[axis] - 2.0 * b[axis] + c[axis];
+                    if denominator.abs() > 1e-12 {
+                        ts.push((a[axis] - b[axis]) / denominator);
+                    }
+                }
+            }
+            Segment::Cubic(a, b, c, d) => {
+                for axis in 0..2 {
+                    // Roots of the derivative, divided by 3
+                    let qa = -a[axis] + 3.0 * b[axis] - 3.0 * c[axis] + d[axis];
+                    let qb = 2.0 * (a[axis] - 2.0 * b[axis] + c[axis]);
+                    let qc = b[axis] - a[axis];
+                    if qa.abs() < 1e-12 {
+                        if qb.abs() > 1e-12 {
+                            ts.push(-qc / qb);
+                        }
+                        continue;
+                    }
+                    let discriminant = qb * qb - 4.0 * qa * qc;
+                    if discriminant >= 0.0 {
+                        let root = discriminant.sqrt();
+                        ts.push((-qb + root) / (2.0 * qa));
+                        ts.push((-qb - root) / (2.0 * qa));
+                    }
+                }
+            }
+            Segment::Arc {
+                rx,
+                ry,
+                phi,
+                start,
+                sweep,
+                ..
+            } => {
+                let (sin_phi, cos_phi) = phi.sin_cos();
+                let x_extreme = (-ry * sin_phi).atan2(rx * cos_phi);
+                let y_extreme = (ry * cos_phi).atan2(rx * sin_phi);
+                for base in [x_extreme, x_extreme + PI, y_extreme, y_extreme + PI] {
+                    for turns in -2..=2 {
+                        ts.push((base + turns as f64 * TAU - start) / sweep);
+                    }
+                }
+            }
+        }
+        ts.retain(|t| *t > 0.0 && *t < 1.0);
+        ts
+    }
+}
+
+/// Endpoint to center conversion (SVG 1.1, appendix F.6.5); `None` when the arc
+/// degenerates to a straight line
+fn arc_segment(
+    from: P,
+    radii: P,
+    rotation: f64,
+    large: bool,
+    sweep: bool,
+    to: P,
+) -> Option<Segment> {
+    let (mut rx, mut ry) = (radii[0].abs(), radii[1].abs());
+    if rx == 0.0 || ry == 0.0 {
+        return None;
+    }
+    let phi = rotation.to_radians();
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    let (hx, hy) = ((from[0] - to[0]) / 2.0, (from[1] - to[1]) / 2.0);
+    let x1 = cos_phi * hx + sin_phi * hy;
+    let y1 = -sin_phi * hx + cos_phi * hy;
+    // Scale radii up when they cannot span the endpoints
+    let lambda = (x1 / rx).powi(2) + (y1 / ry).powi(2);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = (rx * ry).powi(2) - (rx * y1).powi(2) - (ry * x1).powi(2);
+    let denominator = (rx * y1).powi(2) + (ry * x1).powi(2);
+    let sign = if large == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let (cx1, cy1) = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+    let center = [
+        cos_phi * cx1 - sin_phi * cy1 + (from[0] + to[0]) / 2.0,
+        sin_phi * cx1 + cos_phi * cy1 + (from[1] + to[1]) / 2.0,
+    ];
+    let start = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+    let end = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+    let mut delta = (end - start).rem_euclid(TAU);
+    if !sweep && delta > 0.0 {
+        delta -= TAU;
+    }
+    Some(Segment::Arc {
+        center,
+        rx,
+        ry,
+        phi,
+        start,
+        sweep: delta,
+    })
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_separators(&mut self) {
+        while self.pos < self.bytes.len()
+            && (self.bytes[self.pos].is_ascii_whitespace() || self.bytes[self.pos] == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |parser: &mut Self| {
+            let from = parser.pos;
+            while parser.bytes.get(parser.pos).is_some_and(u8::is_ascii_digit) {
+                parser.pos += 1;
+            }
+            parser.pos > from
+        };
+        if matches!(self.bytes.get(self.pos), Some(b'-' | b'+')) {
+            self.pos += 1;
+        }
+        let mut any = digits(self);
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            any |= digits(self);
+        }
+        if any && matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'-' | b'+')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mark;
+            }
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or("");
+        match text.parse::<f64>() {
+            Ok(value) if any && value.is_finite() => Ok(value),
+            _ => Err(format!("Expected a number at position {start}")),
+        }
+    }
+
+    /// Arc flags are single characters and may be written without separators
+    fn flag(&mut self) -> Result<bool, String> {
+        self.skip_separators();
+        match self.bytes.get(self.pos) {
+            Some(b'0') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => Err(format!(
+                "Expected an arc flag (0 or 1) at position {}",
+                self.pos
+            )),
+        }
+    }
+
+    fn point(&mut self, relative: bool, current: P) -> Result<P, String> {
+        let (x, y) = (self.number()?, self.number()?);
+        Ok(if relative {
+            [current[0] + x, current[1] + y]
+        } else {
+            [x, y]
+        })
+    }
+}
+
+fn format_number(value: f64, precision: u32) -> String {
+    let text = format!("{value:.*}", precision as usize);
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[derive(Debug)]
+struct ParsedPath {
+    normalized: String,
+    subpaths: usize,
+    segments: Vec<Segment>,
+}
+
+fn parse_path(data: &str, precision: u32) -> Result<ParsedPath, String> {
+    let mut parser = Parser {
+        bytes: data.as_bytes(),
+        pos: 0,
+    };
+    let f = |v: f64| format_number(v, precision);
+    let fp = |p: P| format!("{} {}", f(p[0]), f(p[1]));
+    let mut commands: Vec<String> = Vec::new();
+    let mut segments = Vec::new();
+    let mut subpaths = 0;
+    let (mut current, mut start): (P, P) = ([0.0; 2], [0.0; 2]);
+    // Control point reflected by S (cubic) and T (quadratic)
+    let mut last_cubic: Option<P> = None;
+    let mut last_quad: Option<P> = None;
+    let mut command: Option<u8> = None;
+
+    loop {
+        parser.skip_separators();
+        let Some(&byte) = parser.bytes.get(parser.pos) else {
+            break;
+        };
+        if byte.is_ascii_alphabetic() {
+            parser.pos += 1;
+            command = Some(byte);
+        } else if command.is_none() {
+            return Err("Path data must start with a moveto (M or m) command".to_string());
+        } else if matches!(command, Some(b'Z' | b'z')) {
+            return Err(format!(
+                "Unexpected number after Z at position {}",
+                parser.pos
+            ));
+        }
+        let letter = command.unwrap_or(b'M');
+        if subpaths == 0 && !matches!(letter, b'M' | b'm') {
+            return Err("Path data must start with a moveto (M or m) command".to_string());
+        }
+        let relative = letter.is_ascii_lowercase();
+        let (mut cubic, mut quad) = (None, None);
+
+        match letter.to_ascii_uppercase() {
+            b'M' => {
+                current = parser.point(relative, current)?;
+                start = current;
+                subpaths += 1;
+                commands.push(format!("M {}", fp(current)));
+                // Further coordinate pairs are implicit linetos
+                command = Some(if relative { b'l' } else { b'L' });
+            }
+            b'L' | b'H' | b'V' => {
+                let to = match letter.to_ascii_uppercase() {
+                    b'L' => parser.point(relative, current)?,
+                    b'H' => {
+                        let x = parser.number()?;
+                        [if relative { current[0] + x } else { x }, current[1]]
+                    }
+                    _ => {
+                        let y = parser.number()?;
+                        [current[0], if relative { current[1] + y } else { y }]
+                    }
+                };
+                segments.push(Segment::Line(current, to));
+                commands.push(format!("L {}", fp(to)));
+                current = to;
+            }
+            b'C' | b'S' => {
+                let first = if letter.eq_ignore_ascii_case(&b'C') {
+                    parser.point(relative, current)?
+                } else {
+                    last_cubic.map_or(current, |c| {
+                        [2.0 * current[0] - c[0], 2.0 * current[1] - c[1]]
+                    })
+                };
+                let second = parser.point(relative, current)?;
+                let to = parser.point(relative, current)?;
+                segments.push(Segment::Cubic(current, first, second, to));
+                commands.push(format!("C {} {} {}", fp(first), fp(second), fp(to)));
+                cubic = Some(second);
+                current = to;
+            }
+            b'Q' | b'T' => {
+                let control = if letter.eq_ignore_ascii_case(&b'Q') {
+                    parser.point(relative, current)?
+                } else {
+                    last_quad.map_or(current, |c| {
+                        [2.0 * current[0] - c[0], 2.0 * current[1] - c[1]]
+                    })
+                };
+                let to = parser.point(relative, current)?;
+                segments.push(Segment::Quad(current, control, to));
+                commands.push(format!("Q {} {}", fp(control), fp(to)));
+                quad = Some(control);
+                current = to;
+            }
+            b'A' => {
+                let radii = [parser.number()?, parser.number()?];
+                let rotation = parser.number()?;
+                let (large, sweep) = (parser.flag()?, parser.flag()?);
+                let to = parser.point(relative, current)?;
+                // Arcs between identical endpoints are omitted
+                if to != current {
+                    match arc_segment(current, radii, rotation, large, sweep, to) {
+                        Some(arc) => {
+                            segments.push(arc);
+                            commands.push(format!(
+                                "A {} {} {} {} {} {}",
+                                f(radii[0].abs()),
+                                f(radii[1].abs()),
+                                f(rotation),
+                                large as u8,
+                                sweep as u8,
+                                fp(to)
+                            ));
+                        }
+                        None => {
+                            segments.push(Segment::Line(current, to));
+                            commands.push(format!("L {}", fp(to)));
+                        }
+                    }
+                }
+                current = to;
+            }
+            b'Z' => {
+                if current != start {
+                    segments.push(Segment::Line(current, start));
+                }
+                commands.push("Z".to_string());
+                current = start;
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown path command '{}' at position {}",
+                    letter as char,
+                    parser.pos - 1
+                ));
+            }
+        }
+        last_cubic = cubic;
+        last_quad = quad;
+
+        if segments.len() > MAX_SEGMENTS {
+            return Err(format!("Paths are limited to {MAX_SEGMENTS} segments"));
+        }
+    }
+
+    Ok(ParsedPath {
+        normalized: commands.join(" "),
+        subpaths,
+        segments,
+    })
+}
+
+fn bounding_box(segments: &[Segment]) -> Option<BoundingBox> {
+    let mut points = segments.iter().flat_map(|segment| {
+        [0.0, 1.0]
+            .into_iter()
+            .chain(segment.extremes())
+            .map(|t| segment.point(t))
+    });
+    let first = points.next()?;
+    let (min, max) = points.fold((first, first), |(min, max), p| {
+        (
+            [min[0].min(p[0]), min[1].min(p[1])],
+            [max[0].max(p[0]), max[1].max(p[1])],
+        )
+    });
+    Some(BoundingBox {
+        min_x: min[0],
+        min_y: min[1],
+        max_x: max[0],
+        max_y: max[1],
+        width: max[0] - min[0],
+        height: max[1] - min[1],
+    })
+}
+
+/// Point and direction at `distance` along the path
+fn point_at(segments: &[Segment], lengths: &[f64], distance: f64) -> PathPoint {
+    let total: f64 = lengths.iter().sum();
+    let distance = distance.clamp(0.0, total);
+    let mut remaining = distance;
+    // Last segment with non-zero length, so the path's end maps to its final point
+    let last = lengths.iter().rposition(|&l| l > 0.0).unwrap_or(0);
+    for (index, (segment, &length)) in segments.iter().zip(lengths).enumerate() {
+        if length <= 0.0 {
+            continue;
+        }
+        if remaining <= length || index == last {
+            let t = segment.parameter_at(remaining.min(length), length);
+            let [x, y] = segment.point(t);
+            let d = segment.derivative(t);
+            return PathPoint {
+                length: distance,
+                x,
+                y,
+                angle: d[1].atan2(d[0]).to_degrees(),
+            };
+        }
+        remaining -= length;
+    }
+    // Paths without length stay at their first point
+    let [x, y] = segments.first().map_or([0.0; 2], |s| s.point(0.0));
+    PathPoint {
+        length: 0.0,
+        x,
+        y,
+        angle: 0.0,
+    }
+}
+
+fn summarize(
+    data: &str,
+    samples: Option<usize>,
+    at_lengths: Option<Vec<f64>>,
+    precision: u32,
+) -> Result<PathSummary, String> {
+    let parsed = parse_path(data, precision)?;
+    let lengths: Vec<f64> = parsed
+        .segments
+        .iter()
+        .map(|s| s.length_between(0.0, 1.0))
+        .collect();
+    let length: f64 = lengths.iter().sum();
+
+    let samples = match samples {
+        None => Vec::new(),
+        Some(count) if !(2..=MAX_POINTS).contains(&count) => {
+            return Err(format!("samples must be between 2 and {MAX_POINTS}"));
+        }
+        Some(count) => (0..count)
+            .map(|i| {
+                point_at(
+                    &parsed.segments,
+                    &lengths,
+                    length * i as f64 / (count - 1) as f64,
+                )
+            })
+            .collect(),
+    };
+    let at_lengths = at_lengths.unwrap_or_default();
+    if at_lengths.len() > MAX_POINTS {
+        return Err(format!("At most {MAX_POINTS} at_lengths are allowed"));
+    }
+    if at_lengths.iter().any(|d| !d.is_finite() || *d < 0.0) {
+        return Err("at_lengths must be non-negative numbers".to_string());
+    }
+
+    Ok(PathSummary {
+        normalized: parsed.normalized,
+        subpath_count: parsed.subpaths,
+        segment_count: parsed.segments.len(),
+        length,
+        bounding_box: bounding_box(&parsed.segments),
+        samples,
+        at_lengths: at_lengths
+            .iter()
+            .map(|&d| point_at(&parsed.segments, &lengths, d))
+            .collect(),
+    })
+}
+
+/// SVG path data for a geographic ring or line, with x = lon and y = -lat
+fn geo_path(points: &[GeoPoint], closed: bool, precision: u32) -> Result<String, String> {
+    let mut points = points;
+    // Rings repeating their first point close with Z instead
+    if closed && points.len() > 1 {
+        let (first, last) = (&points[0], &points[points.len() - 1]);
+        if first.lat == last.lat && first.lon == last.lon {
+            points = &points[..points.len() - 1];
+        }
+    }
+    let needed = if closed { 3 } else { 2 };
+    if points.len() < needed {
+        let shape = if closed { "Polygons" } else { "Polylines" };
+        return Err(format!("{shape} need at least {needed} distinct points"));
+    }
+    if points
+        .iter()
+        .any(|p| !(-90.0..=90.0).contains(&p.lat) || !(-180.0..=180.0).contains(&p.lon))
+    {
+        return Err("Latitudes must be within ±90 and longitudes within ±180".to_string());
+    }
+    let f = |v: f64| format_number(v, precision);
+    let mut data: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let command = if i == 0 { "M" } else { "L" };
+            format!("{command} {} {}", f(p.lon), f(-p.lat))
+        })
+        .collect();
+    if closed {
+        data.push("Z".to_string());
+    }
+    Ok(data.join(" "))
+}
+
+pub fn svg_path_logic(input: SvgPathInput) -> Result<SvgPathOutput, String> {
+    let precision = input.precision.unwrap_or(DEFAULT_PRECISION);
+    if precision > 12 {
+        return Err("precision must be at most 12".to_string());
+    }
+    let polygons = input.polygons.unwrap_or_default();
+    let polylines = input.polylines.unwrap_or_default();
+    if input.path.is_none() && polygons.is_empty() && polylines.is_empty() {
+        return Err("Provide path data, polygons or polylines".to_string());
+    }
+
+    let path = match input.path {
+        Some(data) => Some(summarize(
+            &data,
+            input.samples,
+            input.at_lengths,
+            precision,
+        )?),
+        None if input.samples.is_some() || input.at_lengths.is_some() => {
+            return Err("samples and at_lengths need path data".to_string());
+        }
+        None => None,
+    };
+
+    let mut shapes = Vec::new();
+    for polygon in &polygons {
+        shapes.push(geo_path(polygon, true, precision)?);
+    }
+    for polyline in &polylines {
+        shapes.push(geo_path(polyline, false, precision)?);
+    }
+    let view_box = polygons
+        .iter()
+        .chain(&polylines)
+        .flatten()
+        .fold(None, |bounds, p| {
+            let [min_x, min_y, max_x, max_y] = bounds.unwrap_or([p.lon, -p.lat, p.lon, -p.lat]);
+            Some([
+                min_x.min(p.lon),
+                min_y.min(-p.lat),
+                max_x.max(p.lon),
+                max_y.max(-p.lat),
+            ])
+        });
+    let f = |v: f64| format_number(v, precision);
+
+    Ok(SvgPathOutput {
+        path,
+        shapes,
+        view_box: view_box.map(|[min_x, min_y, max_x, max_y]| {
+            format!(
+                "{} {} {} {}",
+                f(min_x),
+                f(min_y),
+                f(max_x - min_x),
+                f(max_y - min_y)
+            )
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(data: &str) -> PathSummary {
+        summarize(data, None, None, DEFAULT_PRECISION).unwrap()
+    }
+
+    #[test]
+    fn test_square_path() {
+        let result = measure("M0 0 H10 V10 H0 Z");
+        assert_eq!(result.normalized, "M 0 0 L 10 0 L 10 10 L 0 10 Z");
+        assert_eq!(result.subpath_count, 1);
+        assert_eq!(result.segment_count, 4);
+        assert!((result.length - 40.0).abs() < 1e-12);
+        let bbox = result.bounding_box.unwrap();
+        assert_eq!((bbox.min_x, bbox.max_y, bbox.width), (0.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn test_relative_and_packed_numbers() {
+        let result = measure("m10,20 l5-5.5.5 10");
+        assert_eq!(result.normalized, "M 10 20 L 15 14.5 L 15.5 24.5");
+        let result = measure("M1e1 2E1l-1.5e0.5");
+        assert_eq!(result.normalized, "M 10 20 L 8.5 20.5");
+        assert!(
+            parse_path("M 0 0 L 1", 6)
+                .unwrap_err()
+                .contains("Expected a number")
+        );
+        assert!(
+            parse_path("M 0 0 X 1 1", 6)
+                .unwrap_err()
+                .contains("Unknown path command 'X'")
+        );
+        assert!(parse_path("L 1 1", 6).unwrap_err().contains("moveto"));
+    }
+
+    #[test]
+    fn test_circle_from_arcs() {
+        let spaced = measure("M 0 10 A 10 10 0 1 0 20 10 A 10 10 0 1 0 0 10");
+        let packed = measure("M0 10a10 10 0 1020 0a10 10 0 10-20 0");
+        assert_eq!(spaced.normalized, packed.normalized);
+        assert!((spaced.length - 20.0 * PI).abs() < 1e-9);
+        let bbox = spaced.bounding_box.unwrap();
+        for (actual, expected) in [
+            (bbox.min_x, 0.0),
+            (bbox.min_y, 0.0),
+            (bbox.max_x, 20.0),
+            (bbox.max_y, 20.0),
+        ] {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+        // Sweep flag 0 runs counterclockwise on screen, through the bottom on the way right
+        let half = summarize("M 0 10 A 10 10 0 1 0 20 10", None, Some(vec![5.0 * PI]), 6).unwrap();
+        let bottom = &half.at_lengths[0];
+        assert!((bottom.x - 10.0).abs() < 1e-9 && (bottom.y - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cubic_bounds_and_reflection() {
+        let result = measure("M0 0 C0 10 10 10 10 0 S 20 -10 20 0");
+        assert_eq!(
+            result.normalized,
+            "M 0 0 C 0 10 10 10 10 0 C 10 -10 20 -10 20 0"
+        );
+        let bbox = result.bounding_box.unwrap();
+        assert!((bbox.max_y - 7.5).abs() < 1e-12);
+        assert!((bbox.min_y + 7.5).abs() < 1e-12);
+        // Straight cubics measure like lines
+        let line = measure("M0 0 C 1 0 2 0 3 0");
+        assert!((line.length - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sampling() {
+        let result = summarize("M0 0 L10 0 L10 10", Some(3), Some(vec![25.0]), 6).unwrap();
+        let points: Vec<_> = result
+            .samples
+            .iter()
+            .map(|p| (p.length, p.x, p.y, p.angle))
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                (0.0, 0.0, 0.0, 0.0),
+                (10.0, 10.0, 0.0, 0.0),
+                (20.0, 10.0, 10.0, 90.0)
+            ]
+        );
+        // Distances past the end clamp to it
+        assert_eq!(result.at_lengths[0].length, 20.0);
+        assert_eq!(
+            (result.at_lengths[0].x, result.at_lengths[0].y),
+            (10.0, 10.0)
+        );
+        assert!(summarize("M0 0 L1 0", Some(1), None, 6).is_err());
+    }
+
+    #[test]
+    fn test_geo_shapes() {
+        let point = |lat, lon| GeoPoint { lat, lon };
+        let output = svg_path_logic(SvgPathInput {
+            path: None,
+            samples: None,
+            at_lengths: None,
+            polygons: Some(vec![vec![
+                point(40.0, -74.0),
+                point(40.5, -74.0),
+                point(40.5, -73.5),
+                point(40.0, -74.0),
+            ]]),
+            polylines: Some(vec![vec![point(41.0, -73.0), point(40.25, -73.25)]]),
+            precision: Some(2),
+        })
+        .unwrap();
+        assert_eq!(output.shapes[0], "M -74 -40 L -74 -40.5 L -73.5 -40.5 Z");
+        assert_eq!(output.shapes[1], "M -73 -41 L -73.25 -40.25");
+        assert_eq!(output.view_box.as_deref(), Some("-74 -41 1 1"));
+        assert!(output.path.is_none());
+    }
+}